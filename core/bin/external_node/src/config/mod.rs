@@ -1,6 +1,7 @@
 use std::{
     env,
     ffi::OsString,
+    net::IpAddr,
     num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     time::Duration,
 };
@@ -9,7 +10,10 @@ use anyhow::Context;
 use serde::Deserialize;
 use zksync_config::{
     configs::{
-        api::{MaxResponseSize, MaxResponseSizeOverrides},
+        api::{
+            ClientRateLimits, MaxResponseSize, MaxResponseSizeOverrides, MethodFilter,
+            RequestsPerMinuteOverrides,
+        },
         consensus::{ConsensusConfig, ConsensusSecrets},
     },
     ObjectStoreConfig,
@@ -225,6 +229,9 @@ pub(crate) struct OptionalENConfig {
     /// Limit for fee history block range.
     #[serde(default = "OptionalENConfig::default_fee_history_limit")]
     pub fee_history_limit: u64,
+    /// Maximum number of L2 blocks scanned by a single `trace_filter` call.
+    #[serde(default = "OptionalENConfig::default_trace_filter_block_range_limit")]
+    pub trace_filter_block_range_limit: u32,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     #[serde(default = "OptionalENConfig::default_max_batch_request_size")]
     pub max_batch_request_size: usize,
@@ -234,6 +241,33 @@ pub(crate) struct OptionalENConfig {
     /// Method-specific overrides in MiBs for the maximum response body size.
     #[serde(default = "MaxResponseSizeOverrides::empty")]
     max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
+    /// Maximum number of requests per minute for a single client of the HTTP server.
+    /// If not set, requests are not limited per client.
+    pub client_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Per-client limits on the number of requests per minute for specific method groups
+    /// (full method names, e.g. `eth_call`, or namespaces, e.g. `debug`).
+    #[serde(default = "RequestsPerMinuteOverrides::empty")]
+    client_requests_per_minute_overrides: RequestsPerMinuteOverrides,
+    /// Addresses of reverse proxies trusted to set the `x-forwarded-for` / `x-real-ip` HTTP headers
+    /// used to identify clients for per-client rate limiting.
+    #[serde(default)]
+    client_rate_limit_trusted_proxies: Vec<IpAddr>,
+    /// API keys accepted in the `x-api-key` HTTP header to identify clients for per-client rate limiting.
+    #[serde(default)]
+    client_rate_limit_api_keys: Vec<String>,
+    /// Whether to compress HTTP responses for clients supporting it (gzip and brotli are supported).
+    #[serde(default)]
+    pub http_response_compression: bool,
+    /// If set, only the specified methods and namespaces (e.g., `eth_call` or `debug`) are exposed on the HTTP server.
+    http_methods_allowlist: Option<Vec<String>>,
+    /// Methods and namespaces not exposed on the HTTP server.
+    #[serde(default)]
+    http_methods_denylist: Vec<String>,
+    /// If set, only the specified methods and namespaces are exposed on the WebSocket server.
+    ws_methods_allowlist: Option<Vec<String>>,
+    /// Methods and namespaces not exposed on the WebSocket server.
+    #[serde(default)]
+    ws_methods_denylist: Vec<String>,
 
     // Other API config settings
     /// Interval between polling DB for Web3 subscriptions.
@@ -268,9 +302,13 @@ pub(crate) struct OptionalENConfig {
     /// Filters are local to the specific node they were created at. Meaning if
     /// there are multiple nodes behind a load balancer the client cannot reliably
     /// query the previously created filter as the request might get routed to a
-    /// different node.
+    /// different node. Alternatively, filters can be made persistent (see `persistent_filters`).
     #[serde(default)]
     pub filters_disabled: bool,
+    /// Whether to persist installed filters in Postgres instead of keeping them in the node memory,
+    /// so that they survive node restarts and are shared among all API servers using the same database.
+    #[serde(default)]
+    pub persistent_filters: bool,
     /// Polling period for mempool cache update - how often the mempool cache is updated from the database.
     /// Default is 50 milliseconds.
     #[serde(
@@ -329,6 +367,13 @@ pub(crate) struct OptionalENConfig {
     /// but the effects vary wildly depending on the setup (e.g., the filesystem used).
     #[serde(default = "OptionalENConfig::default_merkle_tree_multi_get_chunk_size")]
     pub merkle_tree_multi_get_chunk_size: usize,
+    /// Number of threads in a dedicated thread pool used to hash independent subtrees when processing L1 batches.
+    /// If not specified, the global thread pool will be used. 0 means the default number of threads.
+    pub merkle_tree_hashing_thread_count: Option<usize>,
+    /// Number of latest L1 batches for which Merkle tree versions are retained. Older versions are pruned, except
+    /// for versions of L1 batches that are not proven yet. If not specified, the tree is only pruned following
+    /// Postgres pruning.
+    pub merkle_tree_pruning_retained_l1_batches: Option<NonZeroU32>,
     /// Capacity of the block cache for the Merkle tree RocksDB. Reasonable values range from ~100 MiB to several GiB.
     /// The default value is 128 MiB.
     #[serde(default = "OptionalENConfig::default_merkle_tree_block_cache_size_mb")]
@@ -345,6 +390,15 @@ pub(crate) struct OptionalENConfig {
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     #[serde(default = "OptionalENConfig::default_merkle_tree_stalled_writes_timeout_sec")]
     merkle_tree_stalled_writes_timeout_sec: u64,
+    /// Interval between manual compactions of the Merkle tree RocksDB. If not specified, the tree database
+    /// is only compacted automatically by RocksDB.
+    merkle_tree_compaction_interval_sec: Option<NonZeroU64>,
+    /// Interval between iterations of the background Merkle tree verification against Postgres. If not specified,
+    /// background verification is disabled.
+    merkle_tree_verification_interval_sec: Option<NonZeroU64>,
+    /// Interval between exports of Merkle tree checkpoints to the snapshot object store (configured using
+    /// `EN_SNAPSHOTS_OBJECT_STORE_` env variables). If not specified, checkpoints are not exported.
+    merkle_tree_checkpoint_interval_sec: Option<NonZeroU64>,
 
     // Postgres config (new parameters)
     /// Threshold in milliseconds for the DB connection lifetime to denote it as long-living and log its details.
@@ -496,6 +550,10 @@ impl OptionalENConfig {
         1_024
     }
 
+    const fn default_trace_filter_block_range_limit() -> u32 {
+        10_000
+    }
+
     const fn default_max_batch_request_size() -> usize {
         500 // The default limit is chosen to be reasonably permissive.
     }
@@ -588,6 +646,24 @@ impl OptionalENConfig {
         Duration::from_secs(self.merkle_tree_stalled_writes_timeout_sec)
     }
 
+    /// Returns the interval between manual compactions of the Merkle tree RocksDB, if configured.
+    pub fn merkle_tree_compaction_interval(&self) -> Option<Duration> {
+        self.merkle_tree_compaction_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
+
+    /// Returns the interval between background Merkle tree verification iterations, if verification is enabled.
+    pub fn merkle_tree_verification_interval(&self) -> Option<Duration> {
+        self.merkle_tree_verification_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
+
+    /// Returns the interval between Merkle tree checkpoint exports, if exports are enabled.
+    pub fn merkle_tree_checkpoint_interval(&self) -> Option<Duration> {
+        self.merkle_tree_checkpoint_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
+
     pub fn long_connection_threshold(&self) -> Option<Duration> {
         self.database_long_connection_threshold_ms
             .map(Duration::from_millis)
@@ -612,6 +688,33 @@ impl OptionalENConfig {
         }
     }
 
+    pub fn client_rate_limits(&self) -> ClientRateLimits {
+        ClientRateLimits {
+            global: self.client_requests_per_minute_limit,
+            overrides: self.client_requests_per_minute_overrides.clone(),
+            trusted_proxies: self
+                .client_rate_limit_trusted_proxies
+                .iter()
+                .copied()
+                .collect(),
+            api_keys: self.client_rate_limit_api_keys.iter().cloned().collect(),
+        }
+    }
+
+    pub fn http_method_filter(&self) -> MethodFilter {
+        MethodFilter::new(
+            self.http_methods_allowlist.as_deref(),
+            &self.http_methods_denylist,
+        )
+    }
+
+    pub fn ws_method_filter(&self) -> MethodFilter {
+        MethodFilter::new(
+            self.ws_methods_allowlist.as_deref(),
+            &self.ws_methods_denylist,
+        )
+    }
+
     pub fn healthcheck_slow_time_limit(&self) -> Option<Duration> {
         self.healthcheck_slow_time_limit_ms
             .map(Duration::from_millis)
@@ -744,6 +847,9 @@ pub(crate) struct ExperimentalENConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// Interval between manual compactions of the state keeper RocksDB cache. If not specified, the cache
+    /// is only compacted automatically by RocksDB.
+    state_keeper_db_compaction_interval_sec: Option<NonZeroU64>,
 
     // Snapshot recovery
     /// L1 batch number of the snapshot to use during recovery. Specifying this parameter is mostly useful for testing.
@@ -761,6 +867,11 @@ pub(crate) struct ExperimentalENConfig {
     /// If not set, parallel persistence will be disabled.
     #[serde(default)] // Temporarily use a conservative option (sequential recovery) as default
     pub snapshots_recovery_tree_parallel_persistence_buffer: Option<NonZeroUsize>,
+    /// If set, the Merkle tree is bootstrapped from the latest tree checkpoint in the snapshot object store
+    /// if the tree RocksDB doesn't exist. This is much faster than recovering the tree from a Postgres snapshot.
+    /// If the store contains no suitable checkpoint, the tree is recovered as usual.
+    #[serde(default)]
+    pub snapshots_recovery_tree_checkpoint_import: bool,
 
     // Commitment generator
     /// Maximum degree of parallelism during commitment generation, i.e., the maximum number of L1 batches being processed in parallel.
@@ -783,9 +894,11 @@ impl ExperimentalENConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_compaction_interval_sec: None,
             snapshots_recovery_l1_batch: None,
             snapshots_recovery_tree_chunk_size: Self::default_snapshots_recovery_tree_chunk_size(),
             snapshots_recovery_tree_parallel_persistence_buffer: None,
+            snapshots_recovery_tree_checkpoint_import: false,
             commitment_generator_max_parallelism: None,
         }
     }
@@ -794,6 +907,12 @@ impl ExperimentalENConfig {
     pub fn state_keeper_db_block_cache_capacity(&self) -> usize {
         self.state_keeper_db_block_cache_capacity_mb * BYTES_IN_MEGABYTE
     }
+
+    /// Returns the interval between manual compactions of the state keeper RocksDB cache, if configured.
+    pub fn state_keeper_db_compaction_interval(&self) -> Option<Duration> {
+        self.state_keeper_db_compaction_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
 }
 
 pub(crate) fn read_consensus_secrets() -> anyhow::Result<Option<ConsensusSecrets>> {
@@ -835,6 +954,12 @@ pub struct ApiComponentConfig {
 #[derive(Debug, Deserialize)]
 pub struct TreeComponentConfig {
     pub api_port: Option<u16>,
+    /// Directory used by a read-only tree replica to store its RocksDB metadata. Required if the tree API
+    /// is run without the tree component; in this case, the API is served by a replica tailing the tree RocksDB
+    /// written by another EN process.
+    pub replica_path: Option<String>,
+    /// Interval between catching up a read-only tree replica with the tree RocksDB.
+    pub replica_poll_interval_ms: Option<NonZeroU64>,
 }
 
 /// External Node Config contains all the configuration required for the EN operation.
@@ -910,7 +1035,11 @@ impl ExternalNodeConfig {
             api_component: ApiComponentConfig {
                 tree_api_remote_url: None,
             },
-            tree_component: TreeComponentConfig { api_port: None },
+            tree_component: TreeComponentConfig {
+                api_port: None,
+                replica_path: None,
+                replica_poll_interval_ms: None,
+            },
         }
     }
 }
@@ -940,8 +1069,10 @@ impl From<&ExternalNodeConfig> for InternalApiConfig {
             l2_testnet_paymaster_addr: config.remote.l2_testnet_paymaster_addr,
             req_entities_limit: config.optional.req_entities_limit,
             fee_history_limit: config.optional.fee_history_limit,
+            trace_filter_block_range_limit: config.optional.trace_filter_block_range_limit,
             base_token_address: Some(config.remote.base_token_addr),
             filters_disabled: config.optional.filters_disabled,
+            persistent_filters: config.optional.persistent_filters,
             dummy_verifier: config.remote.dummy_verifier,
             l1_batch_commit_data_generator_mode: config.remote.l1_batch_commit_data_generator_mode,
         }
@@ -966,6 +1097,9 @@ impl From<&ExternalNodeConfig> for TxSenderConfig {
             chain_id: config.required.l2_chain_id,
             // Does not matter for EN.
             whitelisted_tokens_for_aa: Default::default(),
+            // Replacements are checked by the main node.
+            replacement_fee_bump_percent: None,
+            max_pending_txs_per_account: None,
         }
     }
 }
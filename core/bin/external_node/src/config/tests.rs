@@ -1,6 +1,6 @@
 //! Tests for EN configuration.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use assert_matches::assert_matches;
 
@@ -106,6 +106,7 @@ fn parsing_optional_config_from_empty_env() {
 fn parsing_optional_config_from_env() {
     let env_vars = [
         ("EN_FILTERS_DISABLED", "true"),
+        ("EN_PERSISTENT_FILTERS", "true"),
         ("EN_FILTERS_LIMIT", "5000"),
         ("EN_SUBSCRIPTIONS_LIMIT", "20000"),
         ("EN_FEE_HISTORY_LIMIT", "1000"),
@@ -125,6 +126,16 @@ fn parsing_optional_config_from_env() {
             "zks_getProof=100,eth_call=2",
         ),
         ("EN_L1_BATCH_COMMIT_DATA_GENERATOR_MODE", "Validium"),
+        ("EN_CLIENT_REQUESTS_PER_MINUTE_LIMIT", "600"),
+        (
+            "EN_CLIENT_REQUESTS_PER_MINUTE_OVERRIDES",
+            "eth_call=100,debug=10",
+        ),
+        ("EN_CLIENT_RATE_LIMIT_TRUSTED_PROXIES", "10.0.0.1"),
+        ("EN_CLIENT_RATE_LIMIT_API_KEYS", "key1,key2"),
+        ("EN_HTTP_RESPONSE_COMPRESSION", "true"),
+        ("EN_HTTP_METHODS_DENYLIST", "debug,eth_call"),
+        ("EN_WS_METHODS_ALLOWLIST", "eth"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -132,6 +143,16 @@ fn parsing_optional_config_from_env() {
 
     let config: OptionalENConfig = envy::prefixed("EN_").from_iter(env_vars).unwrap();
     assert!(config.filters_disabled);
+    assert!(config.persistent_filters);
+    assert!(config.http_response_compression);
+    let http_method_filter = config.http_method_filter();
+    assert_eq!(http_method_filter.allowlist, None);
+    assert!(!http_method_filter.is_allowed("debug_traceCall"));
+    assert!(!http_method_filter.is_allowed("eth_call"));
+    assert!(http_method_filter.is_allowed("eth_blockNumber"));
+    let ws_method_filter = config.ws_method_filter();
+    assert!(ws_method_filter.is_allowed("eth_subscribe"));
+    assert!(!ws_method_filter.is_allowed("zks_L1ChainId"));
     assert_eq!(config.filters_limit, 5_000);
     assert_eq!(config.subscriptions_limit, 20_000);
     assert_eq!(config.fee_history_limit, 1_000);
@@ -166,6 +187,23 @@ fn parsing_optional_config_from_env() {
             )
         ])
     );
+    let rate_limits = config.client_rate_limits();
+    assert_eq!(rate_limits.global, NonZeroU32::new(600));
+    assert_eq!(
+        rate_limits.overrides,
+        RequestsPerMinuteOverrides::from_iter([
+            ("eth_call", NonZeroU32::new(100).unwrap()),
+            ("debug", NonZeroU32::new(10).unwrap())
+        ])
+    );
+    assert_eq!(
+        rate_limits.trusted_proxies,
+        HashSet::from(["10.0.0.1".parse().unwrap()])
+    );
+    assert_eq!(
+        rate_limits.api_keys,
+        HashSet::from(["key1".to_owned(), "key2".to_owned()])
+    );
     assert_eq!(
         config.l1_batch_commit_data_generator_mode,
         L1BatchCommitmentMode::Validium
@@ -22,7 +22,8 @@ use zksync_db_connection::{
 use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
 use zksync_metadata_calculator::{
     api_server::{TreeApiClient, TreeApiHttpClient},
-    MetadataCalculator, MetadataCalculatorConfig, MetadataCalculatorRecoveryConfig,
+    MerkleTreePruningTask, MerkleTreeReplica, MetadataCalculator, MetadataCalculatorConfig,
+    MetadataCalculatorRecoveryConfig,
 };
 use zksync_node_api_server::{
     execution_sandbox::VmConcurrencyLimiter,
@@ -38,6 +39,7 @@ use zksync_node_sync::{
     tree_data_fetcher::TreeDataFetcher, validate_chain_ids_task::ValidateChainIdsTask, ActionQueue,
     MainNodeHealthCheck, SyncState,
 };
+use zksync_object_store::ObjectStoreFactory;
 use zksync_reorg_detector::ReorgDetector;
 use zksync_shared_metrics::rustc::RUST_METRICS;
 use zksync_state::{PostgresStorageCaches, RocksdbStorageOptions};
@@ -55,7 +57,7 @@ use zksync_web3_decl::{
 };
 
 use crate::{
-    config::ExternalNodeConfig,
+    config::{snapshot_recovery_object_store_config, ExternalNodeConfig},
     init::{ensure_storage_initialized, SnapshotRecoveryConfig},
 };
 
@@ -79,12 +81,15 @@ async fn build_state_keeper(
     chain_id: L2ChainId,
     task_handles: &mut Vec<JoinHandle<anyhow::Result<()>>>,
 ) -> anyhow::Result<ZkSyncStateKeeper> {
-    // We only need call traces on the external node if the `debug_` namespace is enabled.
-    let save_call_traces = config.optional.api_namespaces().contains(&Namespace::Debug);
+    // We only need call traces on the external node if the `debug_` or `trace_` namespace is enabled.
+    let api_namespaces = config.optional.api_namespaces();
+    let save_call_traces =
+        api_namespaces.contains(&Namespace::Debug) || api_namespaces.contains(&Namespace::Trace);
 
     let cache_options = RocksdbStorageOptions {
         block_cache_capacity: config.experimental.state_keeper_db_block_cache_capacity(),
         max_open_files: config.experimental.state_keeper_db_max_open_files,
+        compaction_interval: config.experimental.state_keeper_db_compaction_interval(),
     };
     let (storage_factory, task) =
         AsyncRocksdbCache::new(connection_pool.clone(), state_keeper_db_path, cache_options);
@@ -116,35 +121,43 @@ async fn build_state_keeper(
     ))
 }
 
-async fn run_tree(
-    task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
-    config: &ExternalNodeConfig,
-    api_config: Option<&MerkleTreeApiConfig>,
-    app_health: &AppHealthCheck,
-    stop_receiver: watch::Receiver<bool>,
-    tree_pool: ConnectionPool<Core>,
-) -> anyhow::Result<Arc<dyn TreeApiClient>> {
-    let metadata_calculator_config = MetadataCalculatorConfig {
+fn metadata_calculator_config(config: &ExternalNodeConfig) -> MetadataCalculatorConfig {
+    MetadataCalculatorConfig {
         db_path: config.required.merkle_tree_path.clone(),
         max_open_files: config.optional.merkle_tree_max_open_files,
         mode: MerkleTreeMode::Lightweight,
         delay_interval: config.optional.merkle_tree_processing_delay(),
         max_l1_batches_per_iter: config.optional.merkle_tree_max_l1_batches_per_iter,
         multi_get_chunk_size: config.optional.merkle_tree_multi_get_chunk_size,
+        hashing_thread_count: config.optional.merkle_tree_hashing_thread_count,
         block_cache_capacity: config.optional.merkle_tree_block_cache_size(),
         include_indices_and_filters_in_block_cache: config
             .optional
             .merkle_tree_include_indices_and_filters_in_block_cache,
         memtable_capacity: config.optional.merkle_tree_memtable_capacity(),
         stalled_writes_timeout: config.optional.merkle_tree_stalled_writes_timeout(),
+        pruning_retained_l1_batches: config.optional.merkle_tree_pruning_retained_l1_batches,
+        compaction_interval: config.optional.merkle_tree_compaction_interval(),
+        verification_interval: config.optional.merkle_tree_verification_interval(),
+        checkpoint_interval: config.optional.merkle_tree_checkpoint_interval(),
         recovery: MetadataCalculatorRecoveryConfig {
             desired_chunk_size: config.experimental.snapshots_recovery_tree_chunk_size,
             parallel_persistence_buffer: config
                 .experimental
                 .snapshots_recovery_tree_parallel_persistence_buffer,
         },
-    };
+    }
+}
 
+async fn run_tree(
+    task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    config: &ExternalNodeConfig,
+    api_config: Option<&MerkleTreeApiConfig>,
+    app_health: &AppHealthCheck,
+    stop_receiver: watch::Receiver<bool>,
+    tree_pool: ConnectionPool<Core>,
+) -> anyhow::Result<Arc<dyn TreeApiClient>> {
+    let metadata_calculator_config = metadata_calculator_config(config);
     let max_concurrency = config
         .optional
         .snapshots_recovery_postgres_max_concurrency
@@ -160,25 +173,67 @@ async fn run_tree(
     .await
     .context("failed creating DB pool for Merkle tree recovery")?;
 
+    let checkpoint_interval = config.optional.merkle_tree_checkpoint_interval();
+    let import_checkpoint = config
+        .experimental
+        .snapshots_recovery_tree_checkpoint_import;
+    let checkpoint_object_store = if checkpoint_interval.is_some() || import_checkpoint {
+        let object_store_config = snapshot_recovery_object_store_config()?;
+        Some(
+            ObjectStoreFactory::new(object_store_config)
+                .create_store()
+                .await?,
+        )
+    } else {
+        None
+    };
+
     let mut metadata_calculator =
         MetadataCalculator::new(metadata_calculator_config, None, tree_pool)
             .await
             .context("failed initializing metadata calculator")?
             .with_recovery_pool(recovery_pool);
+    if import_checkpoint {
+        if let Some(object_store) = &checkpoint_object_store {
+            tracing::warn!("Proceeding with Merkle tree checkpoint import. This is an experimental feature; use at your own risk");
+            metadata_calculator = metadata_calculator.with_checkpoint_import(object_store.clone());
+        }
+    }
 
     let tree_reader = Arc::new(metadata_calculator.tree_reader());
     app_health.insert_custom_component(Arc::new(metadata_calculator.tree_health_check()))?;
 
-    if config.optional.pruning_enabled {
+    let tree_pruning_poll_interval = if config.optional.pruning_enabled {
+        Some(config.optional.pruning_removal_delay() / 2)
+    } else {
+        config
+            .optional
+            .merkle_tree_pruning_retained_l1_batches
+            .map(|_| MerkleTreePruningTask::DEFAULT_POLL_INTERVAL)
+    };
+    if let Some(poll_interval) = tree_pruning_poll_interval {
         tracing::warn!("Proceeding with node state pruning for the Merkle tree. This is an experimental feature; use at your own risk");
 
-        let pruning_task =
-            metadata_calculator.pruning_task(config.optional.pruning_removal_delay() / 2);
+        let pruning_task = metadata_calculator.pruning_task(poll_interval);
         app_health.insert_component(pruning_task.health_check())?;
         let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver.clone()));
         task_futures.push(pruning_task_handle);
     }
 
+    if let Some(poll_interval) = config.optional.merkle_tree_verification_interval() {
+        let verification_task = metadata_calculator.verification_task(poll_interval);
+        app_health.insert_component(verification_task.health_check())?;
+        let verification_task_handle = tokio::spawn(verification_task.run(stop_receiver.clone()));
+        task_futures.push(verification_task_handle);
+    }
+
+    if let (Some(interval), Some(object_store)) = (checkpoint_interval, checkpoint_object_store) {
+        let checkpoint_task = metadata_calculator.checkpoint_task(object_store, interval);
+        app_health.insert_component(checkpoint_task.health_check())?;
+        let checkpoint_task_handle = tokio::spawn(checkpoint_task.run(stop_receiver.clone()));
+        task_futures.push(checkpoint_task_handle);
+    }
+
     if let Some(api_config) = api_config {
         let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
         let tree_reader = metadata_calculator.tree_reader();
@@ -199,6 +254,37 @@ async fn run_tree(
     Ok(tree_reader)
 }
 
+/// Runs the tree API backed by a read-only replica of the tree maintained by another EN process.
+async fn run_tree_api_replica(
+    task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    config: &ExternalNodeConfig,
+    api_config: &MerkleTreeApiConfig,
+    app_health: &AppHealthCheck,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<Arc<dyn TreeApiClient>> {
+    let replica_path = api_config.replica_path.clone().context(
+        "Merkle tree API run without a tree component requires `EN_TREE_REPLICA_PATH` to be specified",
+    )?;
+    let replica = MerkleTreeReplica::new(
+        metadata_calculator_config(config),
+        replica_path.into(),
+        api_config.replica_poll_interval(),
+    )
+    .await
+    .context("failed initializing Merkle tree replica")?;
+    app_health.insert_component(replica.health_check())?;
+
+    let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
+    let tree_reader = replica.tree_reader();
+    task_futures.push(tokio::spawn(
+        tree_reader
+            .clone()
+            .run_api_server(address, stop_receiver.clone()),
+    ));
+    task_futures.push(tokio::spawn(replica.run(stop_receiver)));
+    Ok(Arc::new(tree_reader))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_core(
     config: &ExternalNodeConfig,
@@ -506,10 +592,14 @@ async fn run_api(
 
     if components.contains(&Component::HttpApi) {
         let mut builder = ApiBuilder::jsonrpsee_backend(config.into(), connection_pool.clone())
+            .with_filters_pool(connection_pool.clone())
             .http(config.required.http_port)
             .with_filter_limit(config.optional.filters_limit)
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
+            .with_client_rate_limits(config.optional.client_rate_limits())
+            .with_method_filter(config.optional.http_method_filter())
+            .with_http_response_compression(config.optional.http_response_compression)
             .with_pruning_info_refresh_interval(pruning_info_refresh_interval)
             .with_tx_sender(tx_sender.clone())
             .with_vm_barrier(vm_barrier.clone())
@@ -532,12 +622,20 @@ async fn run_api(
     }
 
     if components.contains(&Component::WsApi) {
+        let l2_block_listener_pool = singleton_pool_builder
+            .build()
+            .await
+            .context("failed to build l2_block_listener_pool")?;
         let mut builder = ApiBuilder::jsonrpsee_backend(config.into(), connection_pool.clone())
+            .with_filters_pool(connection_pool.clone())
+            .with_l2_block_listener_pool(l2_block_listener_pool)
             .ws(config.required.ws_port)
             .with_filter_limit(config.optional.filters_limit)
             .with_subscriptions_limit(config.optional.subscriptions_limit)
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
+            .with_client_rate_limits(config.optional.client_rate_limits())
+            .with_method_filter(config.optional.ws_method_filter())
             .with_polling_interval(config.optional.polling_interval())
             .with_pruning_info_refresh_interval(pruning_info_refresh_interval)
             .with_tx_sender(tx_sender)
@@ -585,27 +683,25 @@ async fn init_tasks(
         .await
         .context("failed to build a tree_pool")?;
 
-    if !components.contains(&Component::Tree) {
-        anyhow::ensure!(
-            !components.contains(&Component::TreeApi),
-            "Merkle tree API cannot be started without a tree component"
-        );
-    }
+    let tree_api_config = if components.contains(&Component::TreeApi) {
+        let port = config
+            .tree_component
+            .api_port
+            .context("should contain tree api port")?;
+        Some(MerkleTreeApiConfig {
+            replica_path: config.tree_component.replica_path.clone(),
+            replica_poll_interval_ms: config.tree_component.replica_poll_interval_ms,
+            ..MerkleTreeApiConfig::for_port(port)
+        })
+    } else {
+        None
+    };
     // Create a tree reader. If the list of requested components has the tree itself, then
-    // we can get this tree's reader and use it right away. Otherwise, if configuration has
+    // we can get this tree's reader and use it right away. If only the tree API is requested, the API is served
+    // by a read-only replica of the tree maintained by another process. Otherwise, if configuration has
     // specified address of another instance hosting tree API, create a tree reader to that
     // remote API. A tree reader is necessary for `zks_getProof` method to work.
     let tree_reader: Option<Arc<dyn TreeApiClient>> = if components.contains(&Component::Tree) {
-        let tree_api_config = if components.contains(&Component::TreeApi) {
-            Some(MerkleTreeApiConfig {
-                port: config
-                    .tree_component
-                    .api_port
-                    .context("should contain tree api port")?,
-            })
-        } else {
-            None
-        };
         Some(
             run_tree(
                 task_handles,
@@ -617,6 +713,17 @@ async fn init_tasks(
             )
             .await?,
         )
+    } else if let Some(tree_api_config) = &tree_api_config {
+        Some(
+            run_tree_api_replica(
+                task_handles,
+                config,
+                tree_api_config,
+                app_health,
+                stop_receiver.clone(),
+            )
+            .await?,
+        )
     } else {
         None
     };
@@ -11,16 +11,18 @@ use zksync_config::{
         },
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        ContractsConfig, DatabaseSecrets, FriProofCompressorConfig, FriProverConfig,
-        FriProverGatewayConfig, FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig,
-        L1Secrets, ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig,
-        ProtectiveReadsWriterConfig, Secrets,
+        ApiSecrets, BaseTokenAdjusterConfig, BlockNotificationsConfig, ChangeFeedConfig,
+        ContractsConfig, DAClientConfig, DADispatcherConfig, DatabaseSecrets,
+        FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
+        FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig, L1Secrets, ObservabilityConfig,
+        PrometheusConfig, ProofDataHandlerConfig, ProtectiveReadsWriterConfig, PruningConfig,
+        Secrets, ShadowExecutionConfig,
     },
     ApiConfig, ContractVerifierConfig, DBConfig, EthConfig, EthWatchConfig, GasAdjusterConfig,
     GenesisConfig, ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
 use zksync_core_leftovers::{
-    genesis_init, initialize_components, is_genesis_needed, setup_sigint_handler,
+    genesis_init, initialize_components, is_genesis_needed, run_migrations, setup_sigint_handler,
     temp_config_store::{decode_yaml_repr, TempConfigStore},
     Component, Components,
 };
@@ -70,6 +72,14 @@ struct Cli {
     /// Run the node using the node framework.
     #[arg(long)]
     use_node_framework: bool,
+    /// Run pending Postgres migrations against a shadow schema, report their duration and acquired locks, and exit.
+    /// The database is not modified.
+    #[arg(long, conflicts_with = "migrate")]
+    migrate_dry_run: bool,
+    /// Apply pending Postgres migrations before starting the server. If a migration fails, all migrations
+    /// applied during this run are reverted.
+    #[arg(long)]
+    migrate: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -156,6 +166,7 @@ async fn main() -> anyhow::Result<()> {
             consensus: config::read_consensus_secrets().context("read_consensus_secrets()")?,
             database: DatabaseSecrets::from_env().ok(),
             l1: L1Secrets::from_env().ok(),
+            api: ApiSecrets::from_env().ok(),
         },
     };
 
@@ -183,6 +194,15 @@ async fn main() -> anyhow::Result<()> {
 
     let database_secrets = secrets.database.clone().context("DatabaseSecrets")?;
 
+    if opt.migrate_dry_run || opt.migrate {
+        run_migrations(&database_secrets, opt.migrate_dry_run)
+            .await
+            .context("run_migrations")?;
+        if opt.migrate_dry_run {
+            return Ok(());
+        }
+    }
+
     if opt.genesis || is_genesis_needed(&database_secrets).await {
         genesis_init(genesis.clone(), &database_secrets)
             .await
@@ -308,5 +328,12 @@ fn load_env_config() -> anyhow::Result<TempConfigStore> {
         observability: ObservabilityConfig::from_env().ok(),
         snapshot_creator: SnapshotsCreatorConfig::from_env().ok(),
         protective_reads_writer_config: ProtectiveReadsWriterConfig::from_env().ok(),
+        shadow_execution_config: ShadowExecutionConfig::from_env().ok(),
+        pruning: PruningConfig::from_env().ok(),
+        block_notifications: BlockNotificationsConfig::from_env().ok(),
+        change_feed: ChangeFeedConfig::from_env().ok(),
+        da_dispatcher_config: DADispatcherConfig::from_env().ok(),
+        da_client_config: DAClientConfig::from_env().ok(),
+        base_token_adjuster: BaseTokenAdjusterConfig::from_env().ok(),
     })
 }
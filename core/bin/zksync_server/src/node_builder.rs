@@ -4,7 +4,9 @@
 use anyhow::Context;
 use prometheus_exporter::PrometheusExporterConfig;
 use zksync_config::{
-    configs::{consensus::ConsensusConfig, wallets::Wallets, GeneralConfig, Secrets},
+    configs::{
+        consensus::ConsensusConfig, da_client::DAClient, wallets::Wallets, GeneralConfig, Secrets,
+    },
     ContractsConfig, GenesisConfig,
 };
 use zksync_core_leftovers::Component;
@@ -15,21 +17,31 @@ use zksync_node_api_server::{
 };
 use zksync_node_framework::{
     implementations::layers::{
+        address_denylist::AddressDenylistLayer,
+        balance_history::BalanceHistoryLayer,
+        base_token_adjuster::BaseTokenRatioPersisterLayer,
+        block_notifications::BlockNotificationsLayer,
+        change_feed::ChangeFeedLayer,
         circuit_breaker_checker::CircuitBreakerCheckerLayer,
         commitment_generator::CommitmentGeneratorLayer,
         consensus::{ConsensusLayer, Mode as ConsensusMode},
         contract_verification_api::ContractVerificationApiLayer,
+        da_clients::{
+            avail::AvailWiringLayer, celestia::CelestiaWiringLayer, eigen_da::EigenDAWiringLayer,
+        },
+        da_dispatcher::DataAvailabilityDispatcherLayer,
         eth_sender::{EthTxAggregatorLayer, EthTxManagerLayer},
         eth_watch::EthWatchLayer,
         healtcheck_server::HealthCheckLayer,
         house_keeper::HouseKeeperLayer,
         l1_gas::SequencerL1GasLayer,
-        metadata_calculator::MetadataCalculatorLayer,
+        metadata_calculator::{MetadataCalculatorLayer, TreeApiReplicaLayer},
         object_store::ObjectStoreLayer,
         pk_signing_eth_client::PKSigningEthClientLayer,
         pools_layer::PoolsLayerBuilder,
         prometheus_exporter::PrometheusExporterLayer,
         proof_data_handler::ProofDataHandlerLayer,
+        pruning::PruningLayer,
         query_eth_client::QueryEthClientLayer,
         sigint::SigintHandlerLayer,
         state_keeper::{
@@ -37,7 +49,10 @@ use zksync_node_framework::{
             StateKeeperLayer,
         },
         tee_verifier_input_producer::TeeVerifierInputProducerLayer,
-        vm_runner::protective_reads::ProtectiveReadsWriterLayer,
+        token_transfers::TokenTransfersLayer,
+        vm_runner::{
+            protective_reads::ProtectiveReadsWriterLayer, shadow_execution::ShadowExecutorLayer,
+        },
         web3_api::{
             caches::MempoolCacheLayer,
             server::{Web3ServerLayer, Web3ServerOptionalConfig},
@@ -48,6 +63,7 @@ use zksync_node_framework::{
     },
     service::{ZkStackService, ZkStackServiceBuilder},
 };
+use zksync_types::pubdata_da::PubdataEncoding;
 
 /// Macro that looks into a path to fetch an optional config,
 /// and clones it into a variable.
@@ -126,8 +142,15 @@ impl MainNodeBuilder {
     fn add_query_eth_client_layer(mut self) -> anyhow::Result<Self> {
         let genesis = self.genesis_config.clone();
         let eth_config = try_load_config!(self.secrets.l1);
-        let query_eth_client_layer =
-            QueryEthClientLayer::new(genesis.l1_chain_id, eth_config.l1_rpc_url);
+        let mut query_eth_client_layer =
+            QueryEthClientLayer::new(genesis.l1_chain_id, eth_config.l1_rpc_url)
+                .with_fallback_urls(eth_config.fallback_l1_rpc_urls);
+        if let Some(url) = eth_config.private_relay_rpc_url {
+            query_eth_client_layer = query_eth_client_layer.with_private_relay_url(url);
+        }
+        if let Some(url) = eth_config.gateway_rpc_url {
+            query_eth_client_layer = query_eth_client_layer.with_gateway_url(url);
+        }
         self.node.add_layer(query_eth_client_layer);
         Ok(self)
     }
@@ -148,6 +171,19 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_address_denylist_layer(mut self) -> anyhow::Result<Self> {
+        // The denylist is optional, so it's not an error if the mempool config is missing.
+        let layer = self
+            .configs
+            .mempool_config
+            .as_ref()
+            .and_then(AddressDenylistLayer::new);
+        if let Some(layer) = layer {
+            self.node.add_layer(layer);
+        }
+        Ok(self)
+    }
+
     fn add_object_store_layer(mut self) -> anyhow::Result<Self> {
         let object_store_config = try_load_config!(self.configs.prover_config)
             .object_store
@@ -157,7 +193,11 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_metadata_calculator_layer(mut self, with_tree_api: bool) -> anyhow::Result<Self> {
+    fn add_metadata_calculator_layer(
+        mut self,
+        with_tree_api: bool,
+        with_pruning: bool,
+    ) -> anyhow::Result<Self> {
         let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
         let operations_manager_env_config =
             try_load_config!(self.configs.operations_manager_config);
@@ -170,23 +210,64 @@ impl MainNodeBuilder {
             let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
             layer = layer.with_tree_api_config(merkle_tree_api_config);
         }
+        if with_pruning {
+            let pruning_config = try_load_config!(self.configs.pruning);
+            layer = layer.with_pruning_config(pruning_config);
+        }
         self.node.add_layer(layer);
         Ok(self)
     }
 
-    fn add_state_keeper_layer(mut self) -> anyhow::Result<Self> {
+    fn add_tree_api_replica_layer(mut self) -> anyhow::Result<Self> {
+        let merkle_tree_env_config = try_load_config!(self.configs.db_config).merkle_tree;
+        let operations_manager_env_config =
+            try_load_config!(self.configs.operations_manager_config);
+        let metadata_calculator_config = MetadataCalculatorConfig::for_main_node(
+            &merkle_tree_env_config,
+            &operations_manager_env_config,
+        );
+        let merkle_tree_api_config = try_load_config!(self.configs.api_config).merkle_tree;
+        self.node.add_layer(TreeApiReplicaLayer::new(
+            metadata_calculator_config,
+            merkle_tree_api_config,
+        ));
+        Ok(self)
+    }
+
+    fn add_state_keeper_layer(mut self, with_block_notifications: bool) -> anyhow::Result<Self> {
         let wallets = self.wallets.clone();
         let sk_config = try_load_config!(self.configs.state_keeper_config);
-        let mempool_io_layer = MempoolIOLayer::new(
+        let eth_sender_config = try_load_config!(self.configs.eth);
+        let pubdata_encoding = PubdataEncoding::new(
+            self.genesis_config.l1_batch_commit_data_generator_mode,
+            try_load_config!(eth_sender_config.sender).pubdata_sending_mode,
+        )?;
+        let mut mempool_io_layer = MempoolIOLayer::new(
             self.genesis_config.l2_chain_id,
             self.contracts_config.clone(),
             sk_config.clone(),
             try_load_config!(self.configs.mempool_config),
             try_load_config!(wallets.state_keeper),
-        );
+        )
+        .with_pubdata_encoding(pubdata_encoding);
+        if with_block_notifications {
+            mempool_io_layer = mempool_io_layer.with_block_notifications();
+        }
+        if let Some(da_client_config) = &self.configs.da_client_config {
+            mempool_io_layer = mempool_io_layer.with_da_client_config(&da_client_config.client);
+        }
         let db_config = try_load_config!(self.configs.db_config);
         let main_node_batch_executor_builder_layer = MainBatchExecutorLayer::new(sk_config);
-        let state_keeper_layer = StateKeeperLayer::new(db_config);
+        let mut state_keeper_layer = StateKeeperLayer::new(db_config);
+        // L1 batches can only be aborted via the private admin API server.
+        let with_admin_api = self
+            .configs
+            .api_config
+            .as_ref()
+            .is_some_and(|config| config.web3_json_rpc.admin_http_port.is_some());
+        if with_admin_api {
+            state_keeper_layer = state_keeper_layer.with_l1_batch_aborts();
+        }
         self.node
             .add_layer(mempool_io_layer)
             .add_layer(main_node_batch_executor_builder_layer)
@@ -199,6 +280,7 @@ impl MainNodeBuilder {
         self.node.add_layer(EthWatchLayer::new(
             try_load_config!(eth_config.watcher),
             self.contracts_config.clone(),
+            self.genesis_config.l1_chain_id,
         ));
         Ok(self)
     }
@@ -226,8 +308,13 @@ impl MainNodeBuilder {
             latest_values_cache_size: rpc_config.latest_values_cache_size() as u64,
         };
 
+        let mempool_config = try_load_config!(self.configs.mempool_config);
+
         // On main node we always use master pool sink.
-        self.node.add_layer(TxSinkLayer::MasterPoolSink);
+        self.node.add_layer(TxSinkLayer::MasterPoolSink {
+            replacement_fee_bump_percent: mempool_config.replacement_fee_bump_percent,
+            max_pending_txs_per_account: mempool_config.max_pending_txs_per_account,
+        });
         self.node.add_layer(TxSenderLayer::new(
             TxSenderConfig::new(
                 &sk_config,
@@ -267,9 +354,12 @@ impl MainNodeBuilder {
 
         let mut namespaces = Namespace::DEFAULT.to_vec();
         if with_debug_namespace {
-            namespaces.push(Namespace::Debug)
+            namespaces.extend([Namespace::Debug, Namespace::Trace]);
         }
         namespaces.push(Namespace::Snapshots);
+        if rpc_config.txpool_namespace_enabled {
+            namespaces.push(Namespace::Txpool);
+        }
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -277,6 +367,9 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            client_rate_limits: Some(rpc_config.client_rate_limits(self.secrets.api.as_ref())),
+            method_filter: Some(rpc_config.http_method_filter()),
+            http_response_compression: rpc_config.http_response_compression,
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -288,6 +381,24 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_admin_web3_api_layer(mut self) -> anyhow::Result<Self> {
+        let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
+        let Some(admin_http_port) = rpc_config.admin_http_port else {
+            return Ok(self);
+        };
+        let api_secrets = try_load_config!(self.secrets.api);
+        let admin_token = api_secrets
+            .admin_token
+            .context("admin token must be set if the admin API server is enabled")?;
+        self.node.add_layer(Web3ServerLayer::admin_http(
+            admin_http_port,
+            InternalApiConfig::new(&rpc_config, &self.contracts_config, &self.genesis_config),
+            admin_token,
+        ));
+
+        Ok(self)
+    }
+
     fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
         let rpc_config = try_load_config!(self.configs.api_config).web3_json_rpc;
         let state_keeper_config = try_load_config!(self.configs.state_keeper_config);
@@ -296,9 +407,12 @@ impl MainNodeBuilder {
 
         let mut namespaces = Namespace::DEFAULT.to_vec();
         if with_debug_namespace {
-            namespaces.push(Namespace::Debug)
+            namespaces.extend([Namespace::Debug, Namespace::Trace]);
         }
         namespaces.push(Namespace::Snapshots);
+        if rpc_config.txpool_namespace_enabled {
+            namespaces.push(Namespace::Txpool);
+        }
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -306,6 +420,8 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            client_rate_limits: Some(rpc_config.client_rate_limits(self.secrets.api.as_ref())),
+            method_filter: Some(rpc_config.ws_method_filter()),
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit(),
             ),
@@ -320,11 +436,14 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
-    fn add_eth_tx_manager_layer(mut self) -> anyhow::Result<Self> {
+    fn add_eth_tx_manager_layer(mut self, with_block_notifications: bool) -> anyhow::Result<Self> {
         let eth_sender_config = try_load_config!(self.configs.eth);
 
-        self.node
-            .add_layer(EthTxManagerLayer::new(eth_sender_config));
+        let mut layer = EthTxManagerLayer::new(eth_sender_config);
+        if with_block_notifications {
+            layer = layer.with_block_notifications();
+        }
+        self.node.add_layer(layer);
 
         Ok(self)
     }
@@ -360,6 +479,66 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_block_notifications_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.block_notifications);
+        self.node.add_layer(BlockNotificationsLayer::new(config));
+        Ok(self)
+    }
+
+    fn add_balance_history_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(BalanceHistoryLayer);
+        Ok(self)
+    }
+
+    fn add_base_token_ratio_persister_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.base_token_adjuster);
+        self.node
+            .add_layer(BaseTokenRatioPersisterLayer::new(config));
+        Ok(self)
+    }
+
+    fn add_token_transfers_layer(mut self) -> anyhow::Result<Self> {
+        self.node.add_layer(TokenTransfersLayer);
+        Ok(self)
+    }
+
+    fn add_change_feed_layer(mut self) -> anyhow::Result<Self> {
+        let config = try_load_config!(self.configs.change_feed);
+        self.node.add_layer(ChangeFeedLayer::new(config));
+        Ok(self)
+    }
+
+    fn add_da_client_layer(mut self) -> anyhow::Result<Self> {
+        let da_client_config = try_load_config!(self.configs.da_client_config);
+        match da_client_config.client {
+            DAClient::Avail(_) => {
+                self.node.add_layer(AvailWiringLayer::new(da_client_config));
+            }
+            DAClient::Celestia(_) => {
+                self.node
+                    .add_layer(CelestiaWiringLayer::new(da_client_config));
+            }
+            DAClient::EigenDA(_) => {
+                self.node
+                    .add_layer(EigenDAWiringLayer::new(da_client_config));
+            }
+        }
+        Ok(self)
+    }
+
+    fn add_da_dispatcher_layer(mut self) -> anyhow::Result<Self> {
+        let da_dispatcher_config = try_load_config!(self.configs.da_dispatcher_config);
+        self.node
+            .add_layer(DataAvailabilityDispatcherLayer::new(da_dispatcher_config));
+        Ok(self)
+    }
+
+    fn add_db_pruner_layer(mut self) -> anyhow::Result<Self> {
+        let pruning_config = try_load_config!(self.configs.pruning);
+        self.node.add_layer(PruningLayer::new(pruning_config));
+        Ok(self)
+    }
+
     fn add_commitment_generator_layer(mut self) -> anyhow::Result<Self> {
         self.node.add_layer(CommitmentGeneratorLayer::new(
             self.genesis_config.l1_batch_commit_data_generator_mode,
@@ -411,6 +590,16 @@ impl MainNodeBuilder {
         Ok(self)
     }
 
+    fn add_vm_runner_shadow_execution_layer(mut self) -> anyhow::Result<Self> {
+        let shadow_execution_config = try_load_config!(self.configs.shadow_execution_config);
+        self.node.add_layer(ShadowExecutorLayer::new(
+            shadow_execution_config,
+            self.genesis_config.l2_chain_id,
+        ));
+
+        Ok(self)
+    }
+
     pub fn build(mut self, mut components: Vec<Component>) -> anyhow::Result<ZkStackService> {
         // Add "base" layers (resources and helper tasks).
         self = self
@@ -421,7 +610,8 @@ impl MainNodeBuilder {
             .add_healthcheck_layer()?
             .add_prometheus_exporter_layer()?
             .add_query_eth_client_layer()?
-            .add_sequencer_l1_gas_layer()?;
+            .add_sequencer_l1_gas_layer()?
+            .add_address_denylist_layer()?;
 
         // Sort the components, so that the components they may depend on each other are added in the correct order.
         components.sort_unstable_by_key(|component| match component {
@@ -440,7 +630,8 @@ impl MainNodeBuilder {
                         .add_tx_sender_layer()?
                         .add_tree_api_client_layer()?
                         .add_api_caches_layer()?
-                        .add_http_web3_api_layer()?;
+                        .add_http_web3_api_layer()?
+                        .add_admin_web3_api_layer()?;
                 }
                 Component::WsApi => {
                     self = self
@@ -454,14 +645,16 @@ impl MainNodeBuilder {
                 }
                 Component::Tree => {
                     let with_tree_api = components.contains(&Component::TreeApi);
-                    self = self.add_metadata_calculator_layer(with_tree_api)?;
+                    // Tree pruning must follow Postgres pruning, so it's enabled together with the DB pruner.
+                    let with_pruning = components.contains(&Component::DbPruner);
+                    self = self.add_metadata_calculator_layer(with_tree_api, with_pruning)?;
                 }
                 Component::TreeApi => {
-                    anyhow::ensure!(
-                        components.contains(&Component::Tree),
-                        "Merkle tree API cannot be started without a tree component"
-                    );
-                    // Do nothing, will be handled by the `Tree` component.
+                    if !components.contains(&Component::Tree) {
+                        // The tree is run by another process; serve the tree API from a read-only replica.
+                        self = self.add_tree_api_replica_layer()?;
+                    }
+                    // Otherwise, do nothing; will be handled by the `Tree` component.
                 }
                 Component::EthWatcher => {
                     self = self.add_eth_watch_layer()?;
@@ -472,10 +665,15 @@ impl MainNodeBuilder {
                         .add_eth_tx_aggregator_layer()?;
                 }
                 Component::EthTxManager => {
-                    self = self.add_eth_tx_manager_layer()?;
+                    let with_block_notifications =
+                        components.contains(&Component::BlockNotifications);
+                    self = self.add_eth_tx_manager_layer(with_block_notifications)?;
                 }
                 Component::StateKeeper => {
-                    self = self.add_state_keeper_layer()?;
+                    // Notifications are written to the outbox only if they will be dispatched.
+                    let with_block_notifications =
+                        components.contains(&Component::BlockNotifications);
+                    self = self.add_state_keeper_layer(with_block_notifications)?;
                 }
                 Component::TeeVerifierInputProducer => {
                     self = self.add_tee_verifier_input_producer_layer()?;
@@ -495,6 +693,30 @@ impl MainNodeBuilder {
                 Component::VmRunnerProtectiveReads => {
                     self = self.add_vm_runner_protective_reads_layer()?;
                 }
+                Component::VmRunnerShadowExecution => {
+                    self = self.add_vm_runner_shadow_execution_layer()?;
+                }
+                Component::DbPruner => {
+                    self = self.add_db_pruner_layer()?;
+                }
+                Component::BlockNotifications => {
+                    self = self.add_block_notifications_layer()?;
+                }
+                Component::BalanceHistory => {
+                    self = self.add_balance_history_layer()?;
+                }
+                Component::BaseTokenRatioPersister => {
+                    self = self.add_base_token_ratio_persister_layer()?;
+                }
+                Component::TokenTransfers => {
+                    self = self.add_token_transfers_layer()?;
+                }
+                Component::ChangeFeed => {
+                    self = self.add_change_feed_layer()?;
+                }
+                Component::DADispatcher => {
+                    self = self.add_da_client_layer()?.add_da_dispatcher_layer()?;
+                }
             }
         }
         Ok(self.node.build()?)
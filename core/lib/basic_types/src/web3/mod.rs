@@ -731,6 +731,20 @@ pub struct TransactionReceipt {
     /// Effective gas price
     #[serde(rename = "effectiveGasPrice")]
     pub effective_gas_price: Option<U256>,
+    /// Blob gas used by this transaction; only present for EIP-4844 transactions.
+    #[serde(
+        rename = "blobGasUsed",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_used: Option<U256>,
+    /// Blob gas price paid by this transaction; only present for EIP-4844 transactions.
+    #[serde(
+        rename = "blobGasPrice",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_gas_price: Option<U256>,
 }
 
 /// Data for offline signed transaction
@@ -812,6 +826,13 @@ pub struct CallRequest {
         skip_serializing_if = "Option::is_none"
     )]
     pub max_priority_fee_per_gas: Option<U256>,
+    /// Versioned hashes of blobs (EIP-4844); allows `eth_call` to return them from the `BLOBHASH` opcode
+    #[serde(
+        rename = "blobVersionedHashes",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
 /// Represents condition on minimum block number or block timestamp.
@@ -0,0 +1,311 @@
+//! Circuit breaker checking cross-table invariants in Postgres.
+
+use std::{fmt, ops};
+
+use anyhow::Context as _;
+use tokio::sync::Mutex;
+use zksync_dal::{
+    integrity_dal::{CountMismatch, PublishedWrites},
+    Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_types::{
+    storage::writes::{decompress_state_diffs, StateDiffKey},
+    L1BatchNumber, L2BlockNumber,
+};
+
+use crate::{CircuitBreaker, CircuitBreakerError};
+
+/// Positions of the next L2 block / L1 batch to check. `None` means that checks haven't started yet.
+#[derive(Debug, Default)]
+struct CheckCursor {
+    next_l2_block: Option<u32>,
+    next_l1_batch: Option<u32>,
+    next_l1_batch_with_tree_data: Option<u32>,
+    next_l1_batch_with_commitment: Option<u32>,
+}
+
+impl CheckCursor {
+    /// Returns the next range to check and advances the cursor past it. If checks haven't started yet,
+    /// the range ends with `last_number`, so that historical data isn't rechecked on each node restart.
+    fn advance(
+        next: &mut Option<u32>,
+        last_number: u32,
+        chunk_size: u32,
+    ) -> Option<ops::RangeInclusive<u32>> {
+        let start = next.unwrap_or_else(|| (last_number + 1).saturating_sub(chunk_size));
+        if start > last_number {
+            return None;
+        }
+        let end = last_number.min(start.saturating_add(chunk_size - 1));
+        *next = Some(end + 1);
+        Some(start..=end)
+    }
+}
+
+/// Incrementally checks invariants between Postgres tables for newly sealed L2 blocks and L1 batches:
+///
+/// - The transaction count in each L2 block header matches the number of transactions in the block.
+/// - The transaction count in each L1 batch header matches the total transaction count of its L2 blocks.
+/// - The number of initial writes in each L1 batch matches the number of new leaves in the Merkle tree.
+/// - The number of repeated writes published in each L1 batch commitment matches the number of repeated writes
+///   according to storage logs.
+///
+/// Any mismatch indicates Postgres state corruption and trips the circuit breaker.
+#[derive(Debug)]
+pub struct DbIntegrityChecker {
+    pool: ConnectionPool<Core>,
+    chunk_size: u32,
+    cursor: Mutex<CheckCursor>,
+}
+
+impl DbIntegrityChecker {
+    /// Creates a checker processing at most `chunk_size` L2 blocks and L1 batches per check.
+    pub fn new(pool: ConnectionPool<Core>, chunk_size: u32) -> Self {
+        assert!(
+            chunk_size > 0,
+            "Integrity check chunk size must be positive"
+        );
+        Self {
+            pool,
+            chunk_size,
+            cursor: Mutex::default(),
+        }
+    }
+
+    fn ensure_no_mismatches<N: fmt::Display + fmt::Debug>(
+        mismatches: &[CountMismatch<N>],
+        entity: &str,
+        counted: &str,
+    ) -> Result<(), CircuitBreakerError> {
+        let Some(mismatch) = mismatches.first() else {
+            return Ok(());
+        };
+        let description = format!(
+            "{entity} #{} has {} {counted}, while {} is expected",
+            mismatch.number, mismatch.actual, mismatch.expected
+        );
+        tracing::error!("Detected Postgres inconsistencies for {entity} {counted}: {mismatches:?}");
+        Err(CircuitBreakerError::DbInconsistency(description))
+    }
+
+    /// Returns the number of repeated writes published in the L1 batch commitment.
+    fn published_repeated_writes_count(writes: &PublishedWrites) -> anyhow::Result<u64> {
+        if let Some(compressed) = &writes.compressed_state_diffs {
+            let (_, state_diffs) = decompress_state_diffs(compressed)?;
+            let count = state_diffs
+                .iter()
+                .filter(|diff| matches!(diff.key, StateDiffKey::EnumerationIndex(_)))
+                .count();
+            Ok(count as u64)
+        } else if let Some(compressed) = &writes.compressed_repeated_writes {
+            // Pre-boojum repeated writes are prefixed with their count.
+            let count_bytes = compressed
+                .get(..4)
+                .context("compressed repeated writes are truncated")?;
+            Ok(u32::from_be_bytes(count_bytes.try_into().unwrap()).into())
+        } else {
+            anyhow::bail!("neither compressed state diffs nor repeated writes are persisted")
+        }
+    }
+
+    async fn check_repeated_writes(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        cursor: &mut CheckCursor,
+    ) -> Result<(), CircuitBreakerError> {
+        let Some(last_l1_batch) = conn
+            .integrity_dal()
+            .get_last_l1_batch_with_commitment()
+            .await?
+        else {
+            return Ok(());
+        };
+        let Some(range) = CheckCursor::advance(
+            &mut cursor.next_l1_batch_with_commitment,
+            last_l1_batch.0,
+            self.chunk_size,
+        ) else {
+            return Ok(());
+        };
+        let range = L1BatchNumber(*range.start())..=L1BatchNumber(*range.end());
+        tracing::debug!("Checking repeated writes for L1 batches {range:?}");
+
+        let published_writes = conn.integrity_dal().get_published_writes(range).await?;
+        let mut mismatches = vec![];
+        for writes in &published_writes {
+            let expected = Self::published_repeated_writes_count(writes).map_err(|err| {
+                let description = format!(
+                    "L1 batch #{} has malformed published writes: {err:#}",
+                    writes.number
+                );
+                CircuitBreakerError::DbInconsistency(description)
+            })?;
+            let actual = conn
+                .integrity_dal()
+                .get_repeated_writes_count(writes.number)
+                .await?;
+            if expected != actual {
+                mismatches.push(CountMismatch {
+                    number: writes.number,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Self::ensure_no_mismatches(&mismatches, "L1 batch", "repeated writes")
+    }
+
+    async fn check_l2_blocks(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        cursor: &mut CheckCursor,
+    ) -> Result<(), CircuitBreakerError> {
+        let Some(last_l2_block) = conn.blocks_dal().get_sealed_l2_block_number().await? else {
+            return Ok(());
+        };
+        let Some(range) =
+            CheckCursor::advance(&mut cursor.next_l2_block, last_l2_block.0, self.chunk_size)
+        else {
+            return Ok(());
+        };
+        let range = L2BlockNumber(*range.start())..=L2BlockNumber(*range.end());
+        tracing::debug!("Checking integrity of L2 blocks {range:?}");
+        let mismatches = conn
+            .integrity_dal()
+            .get_l2_block_tx_count_mismatches(range)
+            .await?;
+        Self::ensure_no_mismatches(&mismatches, "L2 block", "transactions")
+    }
+
+    async fn check_l1_batches(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        cursor: &mut CheckCursor,
+    ) -> Result<(), CircuitBreakerError> {
+        if let Some(last_l1_batch) = conn.blocks_dal().get_sealed_l1_batch_number().await? {
+            if let Some(range) =
+                CheckCursor::advance(&mut cursor.next_l1_batch, last_l1_batch.0, self.chunk_size)
+            {
+                let range = L1BatchNumber(*range.start())..=L1BatchNumber(*range.end());
+                tracing::debug!("Checking transaction counts for L1 batches {range:?}");
+                let mismatches = conn
+                    .integrity_dal()
+                    .get_l1_batch_tx_count_mismatches(range)
+                    .await?;
+                Self::ensure_no_mismatches(&mismatches, "L1 batch", "transactions")?;
+            }
+        }
+
+        let last_l1_batch_with_tree_data = conn
+            .blocks_dal()
+            .get_last_l1_batch_number_with_tree_data()
+            .await?;
+        if let Some(last_l1_batch) = last_l1_batch_with_tree_data {
+            if let Some(range) = CheckCursor::advance(
+                &mut cursor.next_l1_batch_with_tree_data,
+                last_l1_batch.0,
+                self.chunk_size,
+            ) {
+                let range = L1BatchNumber(*range.start())..=L1BatchNumber(*range.end());
+                tracing::debug!("Checking initial writes for L1 batches {range:?}");
+                let mismatches = conn
+                    .integrity_dal()
+                    .get_initial_writes_count_mismatches(range)
+                    .await?;
+                Self::ensure_no_mismatches(&mismatches, "L1 batch", "initial writes")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for DbIntegrityChecker {
+    fn name(&self) -> &'static str {
+        "db_integrity"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let mut conn = self.pool.connection_tagged("circuit_breaker").await?;
+        let mut cursor = self.cursor.lock().await;
+        self.check_l2_blocks(&mut conn, &mut cursor).await?;
+        self.check_l1_batches(&mut conn, &mut cursor).await?;
+        self.check_repeated_writes(&mut conn, &mut cursor).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{
+        commitment::pre_boojum_serialize_commitments,
+        storage::writes::{RepeatedStorageWrite, StateDiffCompression, StateDiffRecord},
+        Address, ProtocolVersionId, H256, U256,
+    };
+
+    use super::*;
+
+    #[test]
+    fn counting_published_repeated_writes() {
+        let repeated_writes: Vec<_> = (1..=3)
+            .map(|index| RepeatedStorageWrite {
+                index,
+                value: H256::repeat_byte(1),
+            })
+            .collect();
+        let writes = PublishedWrites {
+            number: L1BatchNumber(1),
+            compressed_repeated_writes: Some(pre_boojum_serialize_commitments(&repeated_writes)),
+            compressed_state_diffs: None,
+        };
+        assert_eq!(
+            DbIntegrityChecker::published_repeated_writes_count(&writes).unwrap(),
+            3
+        );
+
+        let state_diffs = (0..4)
+            .map(|i| StateDiffRecord {
+                address: Address::repeat_byte(1),
+                key: U256::from(i),
+                derived_key: [i as u8; 32],
+                enumeration_index: i,
+                initial_value: U256::zero(),
+                final_value: U256::from(i + 1),
+            })
+            .collect();
+        let writes = PublishedWrites {
+            number: L1BatchNumber(2),
+            compressed_repeated_writes: None,
+            compressed_state_diffs: Some(
+                StateDiffCompression::for_protocol_version(ProtocolVersionId::latest())
+                    .compress(state_diffs),
+            ),
+        };
+        // The diff with the zero enumeration index is an initial write.
+        assert_eq!(
+            DbIntegrityChecker::published_repeated_writes_count(&writes).unwrap(),
+            3
+        );
+
+        let writes = PublishedWrites {
+            number: L1BatchNumber(3),
+            compressed_repeated_writes: Some(vec![0; 2]),
+            compressed_state_diffs: None,
+        };
+        DbIntegrityChecker::published_repeated_writes_count(&writes).unwrap_err();
+    }
+
+    #[test]
+    fn advancing_check_cursor() {
+        let mut next = None;
+        assert_eq!(CheckCursor::advance(&mut next, 100, 10), Some(91..=100));
+        assert_eq!(next, Some(101));
+        assert_eq!(CheckCursor::advance(&mut next, 100, 10), None);
+        assert_eq!(CheckCursor::advance(&mut next, 125, 10), Some(101..=110));
+        assert_eq!(CheckCursor::advance(&mut next, 125, 10), Some(111..=120));
+        assert_eq!(CheckCursor::advance(&mut next, 125, 10), Some(121..=125));
+        assert_eq!(next, Some(126));
+
+        let mut next = None;
+        assert_eq!(CheckCursor::advance(&mut next, 3, 10), Some(0..=3));
+    }
+}
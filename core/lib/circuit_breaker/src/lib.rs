@@ -3,8 +3,10 @@ use std::{fmt, sync::Arc, time::Duration};
 use thiserror::Error;
 use tokio::sync::{watch, Mutex};
 
+pub mod db_integrity;
 pub mod l1_txs;
 mod metrics;
+pub mod pool_saturation;
 pub mod replication_lag;
 
 #[derive(Default, Debug)]
@@ -35,6 +37,17 @@ pub enum CircuitBreakerError {
     FailedL1Transaction,
     #[error("Replication lag ({lag:?}) is above the threshold ({threshold:?})")]
     ReplicationLag { lag: Duration, threshold: Duration },
+    #[error(
+        "API DB pool is saturated for {duration:?} (utilization: {utilization_percent}%, \
+         acquire latency: {acquire_latency:?})"
+    )]
+    PoolSaturation {
+        duration: Duration,
+        utilization_percent: u32,
+        acquire_latency: Duration,
+    },
+    #[error("Postgres state is inconsistent: {0}")]
+    DbInconsistency(String),
     #[error("Internal error running circuit breaker checks")]
     Internal(#[from] anyhow::Error),
 }
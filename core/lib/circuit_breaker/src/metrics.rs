@@ -9,6 +9,12 @@ use vise::{Gauge, Global, Metrics};
 pub(crate) struct CircuitBreakerMetrics {
     /// Replication lag for Postgres in seconds.
     pub replication_lag: Gauge<Duration>,
+    /// Share of active connections in the DB pool used by API servers, in percent.
+    pub pool_utilization_percent: Gauge<u64>,
+    /// Smoothed latency of acquiring a connection from the DB pool used by API servers.
+    pub pool_acquire_latency: Gauge<Duration>,
+    /// Set to 1 while API read requests are shed because of the API DB pool saturation.
+    pub pool_load_shedding: Gauge<u64>,
 }
 
 #[vise::register]
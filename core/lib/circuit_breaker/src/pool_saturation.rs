@@ -0,0 +1,177 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core};
+
+use crate::{metrics::METRICS, CircuitBreaker, CircuitBreakerError};
+
+/// Thresholds used by [`PoolSaturationChecker`].
+#[derive(Debug, Clone, Copy)]
+pub struct PoolSaturationThresholds {
+    /// Share of active connections in the pool (in percent) above which the pool is considered saturated.
+    pub utilization_percent: u32,
+    /// Smoothed connection acquisition latency above which the pool is considered saturated.
+    pub acquire_latency: Option<Duration>,
+    /// Time the pool may stay saturated before the circuit breaker trips. If not set, the checker only sheds load.
+    pub trip_after: Option<Duration>,
+}
+
+/// Monitors saturation of the DB pool used by API servers and the transaction sender. While the pool is saturated,
+/// the checker sets a load shedding signal that API servers use to reject read requests, so that the pool
+/// connections are available for transaction submission. If the pool stays saturated for too long,
+/// the circuit breaker trips.
+///
+/// The checker must observe the same pool instance that serves API requests; shedding requests served
+/// by another pool doesn't free up any connections.
+#[derive(Debug)]
+pub struct PoolSaturationChecker {
+    pool: ConnectionPool<Core>,
+    thresholds: PoolSaturationThresholds,
+    load_shedding_sender: watch::Sender<bool>,
+    saturated_since: Mutex<Option<Instant>>,
+}
+
+impl PoolSaturationChecker {
+    pub fn new(pool: ConnectionPool<Core>, thresholds: PoolSaturationThresholds) -> Self {
+        Self {
+            pool,
+            thresholds,
+            load_shedding_sender: watch::channel(false).0,
+            saturated_since: Mutex::new(None),
+        }
+    }
+
+    /// Returns a signal which is set to `true` while non-critical load should be shed. The signal
+    /// is refreshed on each check.
+    pub fn load_shedding_signal(&self) -> watch::Receiver<bool> {
+        self.load_shedding_sender.subscribe()
+    }
+
+    fn is_saturated(&self, utilization_percent: u32, acquire_latency: Duration) -> bool {
+        utilization_percent >= self.thresholds.utilization_percent
+            || self
+                .thresholds
+                .acquire_latency
+                .is_some_and(|threshold| acquire_latency >= threshold)
+    }
+
+    fn update(
+        &self,
+        utilization_percent: u32,
+        acquire_latency: Duration,
+        now: Instant,
+    ) -> Result<(), CircuitBreakerError> {
+        let is_saturated = self.is_saturated(utilization_percent, acquire_latency);
+        self.load_shedding_sender.send_if_modified(|is_shedding| {
+            if *is_shedding == is_saturated {
+                return false;
+            }
+            if is_saturated {
+                tracing::warn!(
+                    "API DB pool is saturated (utilization: {utilization_percent}%, acquire latency: {acquire_latency:?}); \
+                     shedding read requests"
+                );
+            } else {
+                tracing::info!("API DB pool is no longer saturated; stopped shedding load");
+            }
+            *is_shedding = is_saturated;
+            true
+        });
+        METRICS.pool_load_shedding.set(is_saturated.into());
+
+        let mut saturated_since = self.saturated_since.lock().unwrap();
+        if !is_saturated {
+            *saturated_since = None;
+            return Ok(());
+        }
+        let duration = now.duration_since(*saturated_since.get_or_insert(now));
+        match self.thresholds.trip_after {
+            Some(trip_after) if duration >= trip_after => {
+                Err(CircuitBreakerError::PoolSaturation {
+                    duration,
+                    utilization_percent,
+                    acquire_latency,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl CircuitBreaker for PoolSaturationChecker {
+    fn name(&self) -> &'static str {
+        "pool_saturation"
+    }
+
+    async fn check(&self) -> Result<(), CircuitBreakerError> {
+        let saturation = self.pool.saturation();
+        let utilization_percent = saturation.utilization_percent();
+        METRICS
+            .pool_utilization_percent
+            .set(utilization_percent.into());
+        METRICS.pool_acquire_latency.set(saturation.acquire_latency);
+        self.update(
+            utilization_percent,
+            saturation.acquire_latency,
+            Instant::now(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn shedding_load_and_tripping() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let checker = PoolSaturationChecker::new(
+            pool,
+            PoolSaturationThresholds {
+                utilization_percent: 80,
+                acquire_latency: Some(Duration::from_millis(100)),
+                trip_after: Some(Duration::from_secs(10)),
+            },
+        );
+        let signal = checker.load_shedding_signal();
+        let start = Instant::now();
+
+        checker.update(50, Duration::ZERO, start).unwrap();
+        assert!(!*signal.borrow());
+        checker.update(90, Duration::ZERO, start).unwrap();
+        assert!(*signal.borrow());
+        checker
+            .update(
+                50,
+                Duration::from_millis(200),
+                start + Duration::from_secs(5),
+            )
+            .unwrap();
+        assert!(*signal.borrow());
+
+        let err = checker
+            .update(90, Duration::ZERO, start + Duration::from_secs(10))
+            .unwrap_err();
+        assert_matches!(
+            err,
+            CircuitBreakerError::PoolSaturation { duration, utilization_percent: 90, .. }
+                if duration == Duration::from_secs(10)
+        );
+
+        // Saturation duration is reset once the pool recovers.
+        checker
+            .update(10, Duration::ZERO, start + Duration::from_secs(11))
+            .unwrap();
+        assert!(!*signal.borrow());
+        checker
+            .update(100, Duration::ZERO, start + Duration::from_secs(12))
+            .unwrap();
+        assert!(*signal.borrow());
+    }
+}
@@ -1,16 +1,18 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
-    net::SocketAddr,
-    num::{NonZeroU32, NonZeroUsize},
+    net::{IpAddr, SocketAddr},
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
     str::FromStr,
     time::Duration,
 };
 
 use anyhow::Context as _;
+use secrecy::ExposeSecret as _;
 use serde::{de, Deserialize, Deserializer};
 use zksync_basic_types::{Address, H256};
 
+use crate::configs::ApiSecrets;
 pub use crate::configs::PrometheusConfig;
 
 /// API configuration.
@@ -132,6 +134,164 @@ pub struct MaxResponseSize {
     pub overrides: MaxResponseSizeOverrides,
 }
 
+/// Per-client rate limits for specific RPC method groups, measured in requests per minute.
+///
+/// A method group is either a full method name (e.g., `eth_call`) or a namespace (e.g., `debug`);
+/// the former takes precedence if both are specified for a method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestsPerMinuteOverrides(HashMap<String, NonZeroU32>);
+
+impl<S: Into<String>> FromIterator<(S, NonZeroU32)> for RequestsPerMinuteOverrides {
+    fn from_iter<I: IntoIterator<Item = (S, NonZeroU32)>>(iter: I) -> Self {
+        Self(
+            iter.into_iter()
+                .map(|(group, limit)| (group.into(), limit))
+                .collect(),
+        )
+    }
+}
+
+impl FromStr for RequestsPerMinuteOverrides {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut overrides = HashMap::new();
+        for part in s.split(',') {
+            let (group, limit) = part
+                .split_once('=')
+                .with_context(|| format!("Part `{part}` doesn't have form <method_group>=<int>"))?;
+            let group = group.trim();
+            let limit = limit.trim();
+            let limit = limit.parse().with_context(|| {
+                format!("`{limit}` specified for method group `{group}` is not a valid limit")
+            })?;
+
+            if let Some(prev_limit) = overrides.insert(group.to_owned(), limit) {
+                anyhow::bail!("Rate limit for `{group}` is redefined from {prev_limit} to {limit}");
+            }
+        }
+        Ok(Self(overrides))
+    }
+}
+
+impl RequestsPerMinuteOverrides {
+    pub fn empty() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Gets the limit for the specified method group, or `None` if it's not set.
+    pub fn get(&self, group: &str) -> Option<NonZeroU32> {
+        self.0.get(group).copied()
+    }
+
+    /// Iterates over all overrides.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, NonZeroU32)> + '_ {
+        self.0.iter().map(|(group, &limit)| (group.as_str(), limit))
+    }
+}
+
+impl<'de> Deserialize<'de> for RequestsPerMinuteOverrides {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ParseVisitor;
+
+        impl<'v> de::Visitor<'v> for ParseVisitor {
+            type Value = RequestsPerMinuteOverrides;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("comma-separated list of <method_group>=<limit> tuples, such as: eth_call=100,debug=10")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(ParseVisitor)
+    }
+}
+
+/// Per-client rate limits for JSON-RPC servers. Clients are identified by an API key (the `x-api-key` HTTP header)
+/// if it is in the `api_keys` allowlist, or by their IP address otherwise. The IP address is the peer address
+/// of the TCP connection; `x-forwarded-for` / `x-real-ip` HTTP headers are only honored if the peer is
+/// one of `trusted_proxies`.
+#[derive(Clone)]
+pub struct ClientRateLimits {
+    /// Limit on the total number of requests per minute for a single client.
+    pub global: Option<NonZeroU32>,
+    /// Limits on the number of requests per minute for method groups; each group has a separate quota.
+    pub overrides: RequestsPerMinuteOverrides,
+    /// Addresses of reverse proxies allowed to specify the client IP address via HTTP headers.
+    pub trusted_proxies: HashSet<IpAddr>,
+    /// API keys accepted to identify clients. Unknown keys are ignored.
+    pub api_keys: HashSet<String>,
+}
+
+impl fmt::Debug for ClientRateLimits {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // API keys are secret, so only their number is output.
+        formatter
+            .debug_struct("ClientRateLimits")
+            .field("global", &self.global)
+            .field("overrides", &self.overrides)
+            .field("trusted_proxies", &self.trusted_proxies)
+            .field("api_keys", &self.api_keys.len())
+            .finish()
+    }
+}
+
+impl ClientRateLimits {
+    /// Checks whether any limits are set.
+    pub fn is_empty(&self) -> bool {
+        self.global.is_none() && self.overrides.0.is_empty()
+    }
+}
+
+/// Filter for methods exposed by a JSON-RPC server. Entries are either full method names (e.g., `eth_call`)
+/// or namespaces (e.g., `debug`).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MethodFilter {
+    /// If set, only methods matching one of the entries are exposed.
+    pub allowlist: Option<HashSet<String>>,
+    /// Methods matching one of the entries are not exposed. Takes precedence over `allowlist`.
+    pub denylist: HashSet<String>,
+}
+
+impl MethodFilter {
+    pub fn new(allowlist: Option<&[String]>, denylist: &[String]) -> Self {
+        Self {
+            allowlist: allowlist.map(|entries| entries.iter().cloned().collect()),
+            denylist: denylist.iter().cloned().collect(),
+        }
+    }
+
+    fn matches(entries: &HashSet<String>, method_name: &str) -> bool {
+        if entries.contains(method_name) {
+            return true;
+        }
+        method_name
+            .split_once('_')
+            .map_or(false, |(namespace, _)| entries.contains(namespace))
+    }
+
+    /// Checks whether the filter doesn't exclude any methods.
+    pub fn is_empty(&self) -> bool {
+        self.allowlist.is_none() && self.denylist.is_empty()
+    }
+
+    /// Checks whether the specified method should be exposed.
+    pub fn is_allowed(&self, method_name: &str) -> bool {
+        if Self::matches(&self.denylist, method_name) {
+            return false;
+        }
+        self.allowlist
+            .as_ref()
+            .map_or(true, |allowlist| Self::matches(allowlist, method_name))
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Web3JsonRpcConfig {
     /// Port to which the HTTP RPC server is listening.
@@ -151,14 +311,20 @@ pub struct Web3JsonRpcConfig {
     /// Filters are local to the specific node they were created at. Meaning if
     /// there are multiple nodes behind a load balancer the client cannot reliably
     /// query the previously created filter as the request might get routed to a
-    /// different node.
+    /// different node. Alternatively, filters can be made persistent (see `persistent_filters`).
     #[serde(default)]
     pub filters_disabled: bool,
+    /// Whether to persist installed filters in Postgres instead of keeping them in the server memory.
+    /// Persistent filters survive server restarts and are shared among all servers using the same database,
+    /// e.g. multiple API replicas behind a load balancer.
+    #[serde(default)]
+    pub persistent_filters: bool,
     /// Max possible limit of filters to be in the state at once.
     pub filters_limit: Option<u32>,
     /// Max possible limit of subscriptions to be in the state at once.
     pub subscriptions_limit: Option<u32>,
-    /// Interval between polling db for pubsub (in ms).
+    /// Interval between polling db for pubsub (in ms). New blocks and logs are pushed via Postgres `LISTEN` / `NOTIFY`
+    /// if possible, in which case polling for them is performed much less frequently.
     pub pubsub_polling_interval: Option<u64>,
     /// Tx nonce: how far ahead from the committed nonce can it be.
     pub max_nonce_ahead: u32,
@@ -191,17 +357,55 @@ pub struct Web3JsonRpcConfig {
     pub latest_values_cache_size_mb: Option<usize>,
     /// Limit for fee history block range.
     pub fee_history_limit: Option<u64>,
+    /// Maximum number of L2 blocks scanned by a single `trace_filter` call. Default is 10,000.
+    pub trace_filter_block_range_limit: Option<u32>,
     /// Maximum number of requests in a single batch JSON RPC request. Default is 500.
     pub max_batch_request_size: Option<usize>,
-    /// Maximum response body size in MiBs. Default is 10 MiB.
+    /// Maximum response body size in MiBs. Default is 10 MiB. Methods returning potentially large lists of items
+    /// (e.g., `eth_getLogs` or `debug_traceBlockByNumber`) check this limit while the response is assembled,
+    /// so that oversized responses are rejected before being fully loaded into memory.
     pub max_response_body_size_mb: Option<usize>,
     /// Method-specific overrides in MiBs for the maximum response body size.
     #[serde(default = "MaxResponseSizeOverrides::empty")]
     pub max_response_body_size_overrides_mb: MaxResponseSizeOverrides,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
-    /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Maximum number of requests per minute for a single client of the HTTP server. See [`ClientRateLimits`]
+    /// for how clients are identified. If not set, requests are not limited per client.
+    pub client_requests_per_minute_limit: Option<NonZeroU32>,
+    /// Per-client limits on the number of requests per minute for specific method groups (full method names
+    /// or namespaces).
+    #[serde(default = "RequestsPerMinuteOverrides::empty")]
+    pub client_requests_per_minute_overrides: RequestsPerMinuteOverrides,
+    /// Addresses of reverse proxies trusted to set the `x-forwarded-for` / `x-real-ip` HTTP headers
+    /// used to identify clients for per-client rate limiting.
+    #[serde(default)]
+    pub client_rate_limit_trusted_proxies: Vec<IpAddr>,
+    /// Whether to compress HTTP server responses (using gzip or brotli) for clients that support it,
+    /// as indicated by the `Accept-Encoding` request header. Disabled by default.
+    #[serde(default)]
+    pub http_response_compression: bool,
+    /// If set, only the specified methods and namespaces (e.g., `eth_call` or `debug`) are exposed
+    /// on the HTTP server. Namespaces must additionally be enabled for the server.
+    pub http_methods_allowlist: Option<Vec<String>>,
+    /// Methods and namespaces not exposed on the HTTP server. Takes precedence over `http_methods_allowlist`.
+    #[serde(default)]
+    pub http_methods_denylist: Vec<String>,
+    /// If set, only the specified methods and namespaces are exposed on the WebSocket server.
+    pub ws_methods_allowlist: Option<Vec<String>>,
+    /// Methods and namespaces not exposed on the WebSocket server. Takes precedence over `ws_methods_allowlist`.
+    #[serde(default)]
+    pub ws_methods_denylist: Vec<String>,
+    /// Port of the admin HTTP server exposing the `admin` namespace with operator-only methods (e.g., aborting
+    /// the currently open L1 batch). The server only listens on localhost and requires the admin token
+    /// from the API secrets. The `admin` namespace is never exposed on the public HTTP and WebSocket servers.
+    /// If not set (default), the admin server is not started.
+    pub admin_http_port: Option<u16>,
+    /// Whether to expose the `txpool` namespace. The namespace loads the entire mempool (up to `req_entities_limit`
+    /// transactions) on each call, so it is disabled by default.
+    #[serde(default)]
+    pub txpool_namespace_enabled: bool,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
     /// Polling period for mempool cache update - how often the mempool cache is updated from the database.
@@ -227,6 +431,7 @@ impl Web3JsonRpcConfig {
             ws_url: "ws://localhost:3051".into(),
             req_entities_limit: Some(10000),
             filters_disabled: false,
+            persistent_filters: false,
             filters_limit: Some(10000),
             subscriptions_limit: Some(10000),
             pubsub_polling_interval: Some(200),
@@ -243,10 +448,21 @@ impl Web3JsonRpcConfig {
             initial_writes_cache_size_mb: Default::default(),
             latest_values_cache_size_mb: Default::default(),
             fee_history_limit: Default::default(),
+            trace_filter_block_range_limit: Default::default(),
             max_batch_request_size: Default::default(),
             max_response_body_size_mb: Default::default(),
             max_response_body_size_overrides_mb: MaxResponseSizeOverrides::empty(),
             websocket_requests_per_minute_limit: Default::default(),
+            client_requests_per_minute_limit: Default::default(),
+            client_requests_per_minute_overrides: RequestsPerMinuteOverrides::empty(),
+            client_rate_limit_trusted_proxies: vec![],
+            http_response_compression: false,
+            http_methods_allowlist: None,
+            http_methods_denylist: vec![],
+            ws_methods_allowlist: None,
+            ws_methods_denylist: vec![],
+            admin_http_port: None,
+            txpool_namespace_enabled: false,
             mempool_cache_update_interval: Default::default(),
             mempool_cache_size: Default::default(),
             tree_api_url: None,
@@ -312,6 +528,10 @@ impl Web3JsonRpcConfig {
         self.fee_history_limit.unwrap_or(1024)
     }
 
+    pub fn trace_filter_block_range_limit(&self) -> u32 {
+        self.trace_filter_block_range_limit.unwrap_or(10_000)
+    }
+
     pub fn max_batch_request_size(&self) -> usize {
         // The default limit is chosen to be reasonably permissive.
         self.max_batch_request_size.unwrap_or(500)
@@ -331,6 +551,39 @@ impl Web3JsonRpcConfig {
             .unwrap_or(NonZeroU32::new(6000).unwrap())
     }
 
+    /// Returns per-client rate limits. API keys identifying clients are taken from `api_secrets`
+    /// (see [`ApiSecrets::client_rate_limit_api_keys`]); if secrets are not provided, clients are identified by IP only.
+    pub fn client_rate_limits(&self, api_secrets: Option<&ApiSecrets>) -> ClientRateLimits {
+        let api_keys = api_secrets.map_or(&[][..], |secrets| &secrets.client_rate_limit_api_keys);
+        ClientRateLimits {
+            global: self.client_requests_per_minute_limit,
+            overrides: self.client_requests_per_minute_overrides.clone(),
+            trusted_proxies: self
+                .client_rate_limit_trusted_proxies
+                .iter()
+                .copied()
+                .collect(),
+            api_keys: api_keys
+                .iter()
+                .map(|key| key.expose_secret().clone())
+                .collect(),
+        }
+    }
+
+    pub fn http_method_filter(&self) -> MethodFilter {
+        MethodFilter::new(
+            self.http_methods_allowlist.as_deref(),
+            &self.http_methods_denylist,
+        )
+    }
+
+    pub fn ws_method_filter(&self) -> MethodFilter {
+        MethodFilter::new(
+            self.ws_methods_allowlist.as_deref(),
+            &self.ws_methods_denylist,
+        )
+    }
+
     pub fn tree_api_url(&self) -> Option<&str> {
         self.tree_api_url.as_deref()
     }
@@ -390,12 +643,40 @@ pub struct MerkleTreeApiConfig {
     /// Port to bind the Merkle tree API server to.
     #[serde(default = "MerkleTreeApiConfig::default_port")]
     pub port: u16,
+    /// Directory used by a read-only tree replica to store its RocksDB metadata. Required if the tree API
+    /// is run without the tree component; in this case, the API is served by a replica tailing the tree RocksDB
+    /// written by the tree component in another process.
+    #[serde(default)]
+    pub replica_path: Option<String>,
+    /// Interval between catching up a read-only tree replica with the tree RocksDB. If not set, a default value
+    /// (1 second) is used.
+    #[serde(default)]
+    pub replica_poll_interval_ms: Option<NonZeroU64>,
 }
 
 impl MerkleTreeApiConfig {
+    const DEFAULT_REPLICA_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
     const fn default_port() -> u16 {
         3_072
     }
+
+    /// Creates a config with the specified port and default values for other params.
+    pub fn for_port(port: u16) -> Self {
+        Self {
+            port,
+            replica_path: None,
+            replica_poll_interval_ms: None,
+        }
+    }
+
+    /// Returns the interval between catching up a read-only tree replica with the tree RocksDB.
+    pub fn replica_poll_interval(&self) -> Duration {
+        self.replica_poll_interval_ms
+            .map_or(Self::DEFAULT_REPLICA_POLL_INTERVAL, |ms| {
+                Duration::from_millis(ms.get())
+            })
+    }
 }
 
 #[cfg(test)]
@@ -421,4 +702,44 @@ mod tests {
         assert_eq!(scaled.get("zks_getProof"), Some(32_000));
         assert_eq!(scaled.get("eth_blockNumber"), None);
     }
+
+    #[test]
+    fn working_with_requests_per_minute_overrides() {
+        let overrides: RequestsPerMinuteOverrides = "eth_call=100, debug = 10".parse().unwrap();
+        assert_eq!(overrides.iter().len(), 2);
+        assert_eq!(overrides.get("eth_call"), NonZeroU32::new(100));
+        assert_eq!(overrides.get("debug"), NonZeroU32::new(10));
+        assert_eq!(overrides.get("eth_blockNumber"), None);
+
+        "eth_call=0"
+            .parse::<RequestsPerMinuteOverrides>()
+            .unwrap_err();
+        "eth_call=1,eth_call=2"
+            .parse::<RequestsPerMinuteOverrides>()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn filtering_methods() {
+        let filter = MethodFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.is_allowed("debug_traceCall"));
+
+        let filter = MethodFilter::new(
+            Some(&["eth".to_owned(), "debug_traceCall".to_owned()]),
+            &["eth_call".to_owned()],
+        );
+        assert!(!filter.is_empty());
+        assert!(filter.is_allowed("eth_blockNumber"));
+        assert!(filter.is_allowed("debug_traceCall"));
+        assert!(!filter.is_allowed("eth_call"));
+        assert!(!filter.is_allowed("debug_traceBlockByNumber"));
+        assert!(!filter.is_allowed("zks_getProof"));
+        // Namespaces are only matched by the prefix before the first underscore.
+        assert!(!filter.is_allowed("ethereum"));
+
+        let filter = MethodFilter::new(None, &["debug".to_owned()]);
+        assert!(filter.is_allowed("eth_call"));
+        assert!(!filter.is_allowed("debug_traceCall"));
+    }
 }
@@ -0,0 +1,32 @@
+use std::{num::NonZeroU64, time::Duration};
+
+use serde::Deserialize;
+
+/// Configuration for persisting the conversion ratio between ETH and the base token of the chain.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BaseTokenAdjusterConfig {
+    /// Interval between fetching the conversion ratio from the price source, in milliseconds.
+    #[serde(default = "BaseTokenAdjusterConfig::default_price_polling_interval_ms")]
+    pub price_polling_interval_ms: u64,
+    /// Numerator of the fixed conversion ratio: `fixed_ratio_numerator` ETH wei correspond to
+    /// `fixed_ratio_denominator` base token units. The default 1:1 ratio corresponds to ETH as the base token.
+    #[serde(default = "BaseTokenAdjusterConfig::default_ratio_part")]
+    pub fixed_ratio_numerator: NonZeroU64,
+    /// Denominator of the fixed conversion ratio.
+    #[serde(default = "BaseTokenAdjusterConfig::default_ratio_part")]
+    pub fixed_ratio_denominator: NonZeroU64,
+}
+
+impl BaseTokenAdjusterConfig {
+    const fn default_price_polling_interval_ms() -> u64 {
+        30_000
+    }
+
+    const fn default_ratio_part() -> NonZeroU64 {
+        NonZeroU64::MIN
+    }
+
+    pub fn price_polling_interval(&self) -> Duration {
+        Duration::from_millis(self.price_polling_interval_ms)
+    }
+}
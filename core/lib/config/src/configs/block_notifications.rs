@@ -0,0 +1,32 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Configuration for dispatching block notifications from the transactional outbox.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BlockNotificationsConfig {
+    /// Interval between polling the outbox for new notifications, in milliseconds.
+    #[serde(default = "BlockNotificationsConfig::default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Maximum number of notifications dispatched at a time.
+    #[serde(default = "BlockNotificationsConfig::default_max_batch_size")]
+    pub max_batch_size: usize,
+    /// URL of the webhook that notifications are `POST`ed to as a JSON array. If not set,
+    /// notifications are only dispatched to in-process subscribers.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+impl BlockNotificationsConfig {
+    const fn default_poll_interval_ms() -> u64 {
+        100
+    }
+
+    const fn default_max_batch_size() -> usize {
+        100
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
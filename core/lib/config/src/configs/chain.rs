@@ -107,6 +107,30 @@ pub struct StateKeeperConfig {
     pub max_gas_per_batch: u64,
     /// The maximum amount of pubdata that can be used by the batch. Note that if the calldata is used as pubdata, this variable should not exceed 128kb.
     pub max_pubdata_per_batch: u64,
+    /// The maximum L1 cost (in wei) of publishing pubdata for a batch. The cost is estimated using the fair pubdata
+    /// price of the batch, which depends on the used DA solution (calldata, blobs or an external DA layer).
+    /// If not set, the batch is sealed only based on the pubdata size.
+    #[serde(default)]
+    pub max_pubdata_cost_per_batch: Option<u64>,
+    /// The maximum wall-clock time (in milliseconds) the VM may spend executing a single L2 transaction.
+    /// Execution of transactions exceeding this time is aborted, and they are rejected. If not set, execution time
+    /// is not limited.
+    #[serde(default)]
+    pub max_tx_execution_time_ms: Option<u64>,
+    /// The maximum number of L1 priority operations included into a single L1 batch. Once the limit is reached,
+    /// only L2 transactions are included into the batch. If not set, the number of priority operations is not limited.
+    #[serde(default)]
+    pub max_priority_ops_per_batch: Option<u32>,
+    /// The maximum total gas limit of L1 priority operations included into a single L1 batch.
+    /// A priority operation is always included into a batch with no other priority operations, even if it exceeds the limit.
+    #[serde(default)]
+    pub max_priority_ops_gas_per_batch: Option<u64>,
+    /// Same as `max_priority_ops_per_batch`, but for a single L2 block.
+    #[serde(default)]
+    pub max_priority_ops_per_l2_block: Option<u32>,
+    /// Same as `max_priority_ops_gas_per_batch`, but for a single L2 block.
+    #[serde(default)]
+    pub max_priority_ops_gas_per_l2_block: Option<u64>,
 
     /// The version of the fee model to use.
     pub fee_model_version: FeeModelVersion,
@@ -114,6 +138,27 @@ pub struct StateKeeperConfig {
     /// Max number of computational gas that validation step is allowed to take.
     pub validation_computational_gas_limit: u32,
     pub save_call_traces: bool,
+    /// Whether to collect per-opcode execution statistics for executed transactions and report them as metrics.
+    /// Has noticeable performance overhead, so should only be enabled for profiling.
+    #[serde(default)]
+    pub enable_opcode_profiling: bool,
+    /// Whether to attribute gas consumed by executed transactions to contracts and log top gas consumers
+    /// for each L1 batch. Like opcode profiling, has performance overhead.
+    #[serde(default)]
+    pub enable_gas_attribution: bool,
+    /// Whether to speculatively pre-execute mempool transactions against the state of the previous L1 batch
+    /// and use their storage read sets to warm up the batch executor storage. Uses an additional VM instance
+    /// running in parallel with the batch executor.
+    #[serde(default)]
+    pub enable_mempool_pre_execution: bool,
+    /// Whether to cache execution results of pre-executed transactions rejected by the VM, so that the batch executor
+    /// can reject them without re-execution. Requires mempool pre-execution to be enabled.
+    #[serde(default)]
+    pub enable_execution_result_cache: bool,
+    /// Whether to prefetch storage slots likely touched by mempool transactions (e.g., nonces and balances
+    /// of their initiators) in parallel with executing transactions.
+    #[serde(default)]
+    pub enable_storage_prefetching: bool,
 
     /// The maximal number of circuits that a batch can support.
     /// Note, that this number corresponds to the "base layer" circuits, i.e. it does not include
@@ -158,16 +203,31 @@ impl StateKeeperConfig {
             batch_overhead_l1_gas: 800_000,
             max_gas_per_batch: 200_000_000,
             max_pubdata_per_batch: 100_000,
+            max_pubdata_cost_per_batch: None,
+            max_tx_execution_time_ms: None,
+            max_priority_ops_per_batch: None,
+            max_priority_ops_gas_per_batch: None,
+            max_priority_ops_per_l2_block: None,
+            max_priority_ops_gas_per_l2_block: None,
             minimal_l2_gas_price: 100000000,
             fee_model_version: FeeModelVersion::V2,
             validation_computational_gas_limit: 300000,
             save_call_traces: true,
+            enable_opcode_profiling: false,
+            enable_gas_attribution: false,
+            enable_mempool_pre_execution: false,
+            enable_execution_result_cache: false,
+            enable_storage_prefetching: false,
             max_circuits_per_batch: 24100,
             bootloader_hash: None,
             default_aa_hash: None,
             l1_batch_commit_data_generator_mode: L1BatchCommitmentMode::Rollup,
         }
     }
+
+    pub fn max_tx_execution_time(&self) -> Option<Duration> {
+        self.max_tx_execution_time_ms.map(Duration::from_millis)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -188,6 +248,19 @@ pub struct CircuitBreakerConfig {
     pub http_req_max_retry_number: usize,
     pub http_req_retry_interval_sec: u8,
     pub replication_lag_limit_sec: Option<u32>,
+    /// Maximum number of L2 blocks / L1 batches checked at a time by the Postgres integrity checker.
+    /// If not set, cross-table integrity checks are disabled.
+    pub db_integrity_check_chunk_size: Option<u32>,
+    /// Share of active connections in the DB pool used by API servers (in percent) above which the pool is considered
+    /// saturated. While the pool is saturated, API servers reject read requests so that connections are available
+    /// for transaction submission. If not set, the pool saturation check is disabled.
+    pub pool_saturation_shedding_percent: Option<u32>,
+    /// Smoothed latency of acquiring a connection from the API DB pool (in milliseconds) above which the pool
+    /// is considered saturated. Only used if `pool_saturation_shedding_percent` is set.
+    pub pool_acquire_latency_shedding_ms: Option<u64>,
+    /// Time (in seconds) the API DB pool may stay saturated before the circuit breaker trips.
+    /// If not set, the pool saturation check only sheds load.
+    pub pool_saturation_trip_after_sec: Option<u32>,
 }
 
 impl CircuitBreakerConfig {
@@ -203,6 +276,16 @@ impl CircuitBreakerConfig {
         self.replication_lag_limit_sec
             .map(|limit| Duration::from_secs(limit.into()))
     }
+
+    pub fn pool_acquire_latency_shedding_threshold(&self) -> Option<Duration> {
+        self.pool_acquire_latency_shedding_ms
+            .map(Duration::from_millis)
+    }
+
+    pub fn pool_saturation_trip_after(&self) -> Option<Duration> {
+        self.pool_saturation_trip_after_sec
+            .map(|secs| Duration::from_secs(secs.into()))
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -213,9 +296,40 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// If set (default), executable L2 transactions are returned to the state keeper in the order they were received.
+    /// Otherwise, transactions with higher effective priority fee are returned first (nonces are still
+    /// respected for each account).
+    #[serde(default = "MempoolConfig::default_fifo_ordering")]
+    pub fifo_ordering: bool,
+    /// Minimum increase (in percent) of both the max fee and the max priority fee required for a transaction
+    /// to replace a pending transaction with the same initiator and nonce. If not set, replacements
+    /// are accepted regardless of fees.
+    pub replacement_fee_bump_percent: Option<u32>,
+    /// Time-to-live for pending L2 transactions in seconds. Transactions that were received earlier
+    /// are marked as expired and evicted from the mempool. If not set, transactions don't expire.
+    pub tx_ttl_sec: Option<u64>,
+    /// Maximum allowed difference between the nonce of a pending L2 transaction and the current nonce
+    /// of its initiator. Transactions with larger nonces are marked as expired and evicted from the mempool.
+    /// If not set, the nonce gap is not limited.
+    pub max_nonce_gap: Option<u32>,
+    /// Interval between checks for expired transactions in milliseconds. Default value is 1 minute.
+    pub eviction_interval_ms: Option<u64>,
+    /// Maximum number of pending L2 transactions per initiator account (i.e., the account itself for
+    /// account abstraction transactions). Transactions exceeding this limit are rejected by the API server
+    /// and are not added to the mempool. If not set, the number of transactions per account is not limited.
+    pub max_pending_txs_per_account: Option<u32>,
+    /// Path to a file with addresses (one per line) that cannot send or receive L2 transactions. Such transactions
+    /// are rejected both by the API server and by the state keeper. The file is periodically reloaded.
+    pub address_denylist_path: Option<String>,
+    /// Interval between reloads of the address denylist in milliseconds. Default value is 10 seconds.
+    pub address_denylist_reload_interval_ms: Option<u64>,
 }
 
 impl MempoolConfig {
+    const fn default_fifo_ordering() -> bool {
+        true
+    }
+
     pub fn sync_interval(&self) -> Duration {
         Duration::from_millis(self.sync_interval_ms)
     }
@@ -227,4 +341,16 @@ impl MempoolConfig {
     pub fn delay_interval(&self) -> Duration {
         Duration::from_millis(self.delay_interval)
     }
+
+    pub fn tx_ttl(&self) -> Option<Duration> {
+        self.tx_ttl_sec.map(Duration::from_secs)
+    }
+
+    pub fn eviction_interval(&self) -> Duration {
+        Duration::from_millis(self.eviction_interval_ms.unwrap_or(60_000))
+    }
+
+    pub fn address_denylist_reload_interval(&self) -> Duration {
+        Duration::from_millis(self.address_denylist_reload_interval_ms.unwrap_or(10_000))
+    }
 }
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Configuration for the change-data feed tailing Postgres logical replication.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ChangeFeedConfig {
+    /// Name of the logical replication slot used by the feed; also used as the name of the publication
+    /// for the tailed tables. The slot and the publication are created on the first start.
+    #[serde(default = "ChangeFeedConfig::default_slot_name")]
+    pub slot_name: String,
+    /// Interval between polling the replication slot for new changes, in milliseconds.
+    #[serde(default = "ChangeFeedConfig::default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Maximum number of changes read from the replication slot at a time. May be exceeded by large Postgres transactions.
+    #[serde(default = "ChangeFeedConfig::default_max_batch_size")]
+    pub max_batch_size: usize,
+    /// URL of the webhook that change events are `POST`ed to as a JSON array. If not set,
+    /// events are only dispatched to in-process subscribers.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+}
+
+impl ChangeFeedConfig {
+    fn default_slot_name() -> String {
+        "zksync_change_feed".to_owned()
+    }
+
+    const fn default_poll_interval_ms() -> u64 {
+        500
+    }
+
+    const fn default_max_batch_size() -> usize {
+        1_000
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms)
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+/// Configuration for the Avail DA layer. Blobs are submitted via the HTTP API of an Avail light client
+/// running in the app client mode; the application ID and the signing account are configured in the light client.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AvailConfig {
+    /// URL of the light client HTTP API, e.g. `http://localhost:7007`.
+    pub api_node_url: String,
+    /// URL of the Avail bridge API providing proofs of data inclusion in data roots bridged to Ethereum.
+    pub bridge_api_url: String,
+}
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+
+/// Configuration for the Celestia DA layer. Blobs are submitted via the JSON-RPC API of a celestia-node
+/// (a light or a bridge node) with a funded account.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CelestiaConfig {
+    /// URL of the celestia-node JSON-RPC API, e.g. `http://localhost:26658`.
+    pub api_node_url: String,
+    /// Hex-encoded ID of the version 0 namespace to submit blobs to (up to 10 bytes).
+    pub namespace: String,
+    /// Authentication token for the celestia-node API with the `write` permission.
+    pub auth_token: String,
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use serde::Deserialize;
+
+/// Configuration for the EigenDA DA layer. Blobs are dispersed via the gRPC API of an EigenDA disperser.
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct EigenDAConfig {
+    /// URL of the disperser gRPC API, e.g. `https://disperser-holesky.eigenda.xyz:443`.
+    pub disperser_rpc: String,
+    /// Quorums that must attest to the availability of dispersed blobs in addition to the quorums required
+    /// by the disperser. If empty, only the required quorums are used.
+    #[serde(default)]
+    pub custom_quorum_numbers: Vec<u32>,
+    /// Whether to wait until the batch containing a blob is finalized on Ethereum before considering
+    /// the blob included. If not set, the blob is considered included once its batch is confirmed.
+    #[serde(default)]
+    pub wait_for_finalization: bool,
+    /// Maximum size of a blob accepted by the disperser, in bytes.
+    #[serde(default = "EigenDAConfig::default_blob_size_limit")]
+    pub blob_size_limit: u32,
+    /// ID of the account dispersing blobs, i.e. the `0x`-prefixed hex-encoded uncompressed public key of
+    /// the signer. Optional; if specified, it's checked to correspond to `signer_private_key`.
+    #[serde(default)]
+    pub account_id: Option<String>,
+    /// Hex-encoded secp256k1 private key of the account dispersing blobs. If specified, blobs are dispersed
+    /// via the authenticated disperser API and paid for by the account; otherwise, blobs are dispersed
+    /// without authentication, which is only supported by some dispersers.
+    #[serde(default)]
+    pub signer_private_key: Option<String>,
+    /// Path to the file with G1 points of the EigenDA structured reference string (SRS), in the compressed format
+    /// used by the EigenDA operators (e.g., `g1.point` from the EigenDA repository). Used to check that blob
+    /// commitments returned by the disperser correspond to dispersed data. The file must contain at least as many points
+    /// as there are field elements in the largest dispersed blob.
+    pub g1_srs_path: String,
+}
+
+impl fmt::Debug for EigenDAConfig {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("EigenDAConfig")
+            .field("disperser_rpc", &self.disperser_rpc)
+            .field("custom_quorum_numbers", &self.custom_quorum_numbers)
+            .field("wait_for_finalization", &self.wait_for_finalization)
+            .field("blob_size_limit", &self.blob_size_limit)
+            .field("account_id", &self.account_id)
+            .field(
+                "signer_private_key",
+                &self.signer_private_key.as_ref().map(|_| "_"),
+            )
+            .field("g1_srs_path", &self.g1_srs_path)
+            .finish()
+    }
+}
+
+impl EigenDAConfig {
+    const fn default_blob_size_limit() -> u32 {
+        2 * 1_024 * 1_024
+    }
+}
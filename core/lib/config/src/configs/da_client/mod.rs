@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+pub use self::{avail::AvailConfig, celestia::CelestiaConfig, eigen_da::EigenDAConfig};
+
+pub mod avail;
+pub mod celestia;
+pub mod eigen_da;
+
+/// Configuration for the client of a data availability (DA) layer used by the DA dispatcher.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DAClientConfig {
+    #[serde(flatten)]
+    pub client: DAClient,
+    /// Timeout for requests to the DA layer, in milliseconds.
+    #[serde(default = "DAClientConfig::default_request_timeout_ms")]
+    pub request_timeout_ms: u64,
+}
+
+impl DAClientConfig {
+    const fn default_request_timeout_ms() -> u64 {
+        30_000
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.request_timeout_ms)
+    }
+}
+
+/// DA layer and its client-specific configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "client")]
+pub enum DAClient {
+    Avail(AvailConfig),
+    Celestia(CelestiaConfig),
+    EigenDA(EigenDAConfig),
+}
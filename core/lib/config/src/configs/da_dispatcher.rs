@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Configuration for the dispatcher sending L1 batch pubdata to a data availability (DA) layer.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DADispatcherConfig {
+    /// Interval between polling the DB for L1 batches to dispatch and the DA layer for inclusion data, in milliseconds.
+    #[serde(default = "DADispatcherConfig::default_polling_interval_ms")]
+    pub polling_interval_ms: u32,
+    /// Maximum number of L1 batches dispatched at a time.
+    #[serde(default = "DADispatcherConfig::default_max_rows_to_dispatch")]
+    pub max_rows_to_dispatch: u32,
+    /// Maximum number of attempts for a single request to the DA layer that has failed with a retriable error.
+    #[serde(default = "DADispatcherConfig::default_max_retries")]
+    pub max_retries: u16,
+}
+
+impl DADispatcherConfig {
+    const fn default_polling_interval_ms() -> u32 {
+        5_000
+    }
+
+    const fn default_max_rows_to_dispatch() -> u32 {
+        100
+    }
+
+    const fn default_max_retries() -> u16 {
+        5
+    }
+
+    pub fn polling_interval(&self) -> Duration {
+        Duration::from_millis(self.polling_interval_ms.into())
+    }
+}
@@ -1,4 +1,7 @@
-use std::time::Duration;
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
@@ -46,6 +49,28 @@ pub struct MerkleTreeConfig {
     /// Maximum number of L1 batches to be processed by the Merkle tree at a time.
     #[serde(default = "MerkleTreeConfig::default_max_l1_batches_per_iter")]
     pub max_l1_batches_per_iter: usize,
+    /// Number of threads in a dedicated thread pool used to hash independent subtrees when processing
+    /// L1 batches. If not specified, the global `rayon` thread pool will be used. 0 means the default
+    /// number of threads (i.e., the number of logical CPUs).
+    #[serde(default)]
+    pub hashing_thread_count: Option<usize>,
+    /// Number of latest L1 batches for which Merkle tree versions are retained. Older versions are pruned,
+    /// except for versions of L1 batches that are not proven yet. If not specified, the tree is only pruned
+    /// following Postgres pruning.
+    #[serde(default)]
+    pub pruning_retained_l1_batches: Option<NonZeroU32>,
+    /// Interval between manual compactions of the Merkle tree RocksDB. If not specified, the tree database
+    /// is only compacted automatically by RocksDB.
+    #[serde(default)]
+    pub compaction_interval_sec: Option<NonZeroU64>,
+    /// Interval between iterations of the background tree verification against Postgres. Each iteration verifies
+    /// the latest root hash and a random subtree. If not specified, background verification is disabled.
+    #[serde(default)]
+    pub verification_interval_sec: Option<NonZeroU64>,
+    /// Interval between exports of tree checkpoints to the object store. Exported checkpoints can be imported
+    /// to bootstrap the tree on new nodes. If not specified, checkpoints are not exported.
+    #[serde(default)]
+    pub checkpoint_interval_sec: Option<NonZeroU64>,
 }
 
 impl Default for MerkleTreeConfig {
@@ -58,6 +83,11 @@ impl Default for MerkleTreeConfig {
             memtable_capacity_mb: Self::default_memtable_capacity_mb(),
             stalled_writes_timeout_sec: Self::default_stalled_writes_timeout_sec(),
             max_l1_batches_per_iter: Self::default_max_l1_batches_per_iter(),
+            hashing_thread_count: None,
+            pruning_retained_l1_batches: None,
+            compaction_interval_sec: None,
+            verification_interval_sec: None,
+            checkpoint_interval_sec: None,
         }
     }
 }
@@ -101,6 +131,24 @@ impl MerkleTreeConfig {
     pub fn stalled_writes_timeout(&self) -> Duration {
         Duration::from_secs(self.stalled_writes_timeout_sec)
     }
+
+    /// Returns the interval between manual compactions of the Merkle tree database, if configured.
+    pub fn compaction_interval(&self) -> Option<Duration> {
+        self.compaction_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
+
+    /// Returns the interval between background tree verification iterations, if verification is enabled.
+    pub fn verification_interval(&self) -> Option<Duration> {
+        self.verification_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
+
+    /// Returns the interval between tree checkpoint exports, if exporting checkpoints is enabled.
+    pub fn checkpoint_interval(&self) -> Option<Duration> {
+        self.checkpoint_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
 }
 
 /// Database configuration.
@@ -145,6 +193,14 @@ pub struct PostgresConfig {
     pub long_connection_threshold_ms: Option<u64>,
     /// Threshold in milliseconds to denote a DB query as "slow" and log its details.
     pub slow_query_threshold_ms: Option<u64>,
+    /// Whether to report latency for all DB queries labeled by the query name. By default, latency is only reported
+    /// for a subset of queries.
+    pub report_all_query_latencies: Option<bool>,
+    /// Whether to redact query arguments when logging slow or failing DB queries. By default, arguments are redacted.
+    pub redact_query_args: Option<bool>,
+    /// Maximum replication lag in seconds for read replicas. Read-only connections are not acquired from replicas
+    /// lagging behind the main database by more than this value.
+    pub max_replica_lag_sec: Option<u64>,
     pub test_server_url: Option<String>,
     pub test_prover_url: Option<String>,
 }
@@ -176,4 +232,9 @@ impl PostgresConfig {
     pub fn slow_query_threshold(&self) -> Option<Duration> {
         self.slow_query_threshold_ms.map(Duration::from_millis)
     }
+
+    /// Returns the maximum replication lag for read replicas.
+    pub fn max_replica_lag(&self) -> Option<Duration> {
+        self.max_replica_lag_sec.map(Duration::from_secs)
+    }
 }
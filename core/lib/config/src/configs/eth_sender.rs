@@ -40,6 +40,23 @@ impl EthConfig {
                 l1_batch_min_age_before_execute_seconds: None,
                 max_acceptable_priority_fee_in_gwei: 100000000000,
                 pubdata_sending_mode: PubdataSendingMode::Calldata,
+                dynamic_pubdata_sending_hysteresis: None,
+                fee_bump_strategy: None,
+                fee_bump_percent: None,
+                blob_fee_bump_strategy: None,
+                blob_fee_bump_percent: None,
+                max_total_fee_bump_percent: None,
+                blocks_before_first_fee_bump: None,
+                private_relay_fallback_blocks: None,
+                heal_nonce_divergence: None,
+                heal_nonce_divergence_after_blocks: None,
+                dry_run: None,
+                pause_commit: None,
+                pause_prove: None,
+                pause_execute: None,
+                commit_aggregation_criteria: None,
+                proof_aggregation_criteria: None,
+                execute_aggregation_criteria: None,
             }),
             gas_adjuster: Some(GasAdjusterConfig {
                 default_priority_fee_per_gas: 1000000000,
@@ -81,6 +98,33 @@ pub enum PubdataSendingMode {
     #[default]
     Calldata,
     Blobs,
+    /// Pubdata is sent to a data availability (DA) layer by the DA dispatcher; commit transactions include
+    /// the DA inclusion data. Only supported in the validium mode.
+    Custom,
+}
+
+/// Strategy of increasing fees of L1 transactions that are resent because they are not mined.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeeBumpStrategy {
+    /// Each resend increases the fee by a fixed percentage of the fee used for the first sending attempt.
+    Linear,
+    /// Each resend increases the fee by a fixed percentage of the fee used for the previous sending attempt.
+    #[default]
+    Exponential,
+}
+
+/// Criterion limiting L1 batches published in a single L1 transaction.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum L1BatchAggregationCriterion {
+    /// Limits the number of L1 batches (`max_aggregated_blocks_to_commit` etc.).
+    Number,
+    /// Limits the estimated gas consumed by the transaction (`max_aggregated_tx_gas`).
+    Gas,
+    /// Limits the size of the transaction data (`max_eth_tx_data_size`). Only applicable to commit transactions.
+    DataSize,
+    /// Publishes L1 batches once the oldest of them is older than the deadline (`aggregated_block_commit_deadline`
+    /// etc.). Without this criterion, L1 batches are only published once another criterion is triggered.
+    Deadline,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -114,11 +158,186 @@ pub struct SenderConfig {
     // Max acceptable fee for sending tx it acts as a safeguard to prevent sending tx with very high fees.
     pub max_acceptable_priority_fee_in_gwei: u64,
 
-    /// The mode in which we send pubdata, either Calldata or Blobs
+    /// The mode in which we send pubdata: Calldata, Blobs or Custom (i.e., via a DA layer)
     pub pubdata_sending_mode: PubdataSendingMode,
+    /// If set, pubdata DA for each commit transaction is chosen dynamically based on the current blob and calldata
+    /// prices reported by the gas adjuster; `pubdata_sending_mode` is then used as the initial choice. Requires
+    /// a blob operator. The value is the relative advantage that the other DA must have over the currently used one
+    /// for the choice to be switched (e.g., 0.1 means that the other DA must be at least 10% cheaper), which
+    /// prevents flapping between DAs if prices are close.
+    pub dynamic_pubdata_sending_hysteresis: Option<f64>,
+
+    /// Strategy of bumping the priority fee of resent transactions without blobs. The base fee of such transactions
+    /// is determined by the gas adjuster based on the time spent in the mempool.
+    pub fee_bump_strategy: Option<FeeBumpStrategy>,
+    /// Fee bump (in percent) applied to resent transactions without blobs. Should be at least 10%;
+    /// otherwise, L1 nodes will reject replacement transactions.
+    pub fee_bump_percent: Option<u64>,
+    /// Strategy of bumping all fees (base, priority and blob base fees) of resent blob transactions.
+    pub blob_fee_bump_strategy: Option<FeeBumpStrategy>,
+    /// Fee bump (in percent) applied to resent blob transactions. Should be at least 100%;
+    /// otherwise, L1 nodes will reject replacement transactions.
+    pub blob_fee_bump_percent: Option<u64>,
+    /// Cap on the total increase (in percent) of each bumped fee relative to the fee used for the first sending
+    /// attempt. Once a fee cannot be bumped without exceeding the cap, the transaction is no longer resent,
+    /// and an alert is logged. If not set, fees can be increased indefinitely (subject to
+    /// `max_acceptable_priority_fee_in_gwei`).
+    pub max_total_fee_bump_percent: Option<u64>,
+    /// Number of L1 blocks a transaction should spend in the mempool before it's resent with increased fees
+    /// for the first time.
+    pub blocks_before_first_fee_bump: Option<u32>,
+    /// Number of L1 blocks since the transaction creation during which a transaction is submitted only via
+    /// the private relay RPC (if one is configured in L1 secrets). If the transaction isn't mined by then,
+    /// it's submitted to the public mempool. Transactions rejected by the relay and blob transactions are
+    /// always submitted to the public mempool.
+    pub private_relay_fallback_blocks: Option<u32>,
+    /// Whether to automatically renumber unconfirmed transactions if the on-chain nonce of an operator diverges
+    /// from the nonces tracked in Postgres (e.g., after manual transactions were sent from the operator account).
+    /// If disabled, divergences are only reported. Disabled by default.
+    pub heal_nonce_divergence: Option<bool>,
+    /// Number of L1 blocks a nonce divergence must persist for before it's healed. Transactions are never
+    /// renumbered while any of their sending attempts is known to the L1 node (i.e., may still be mined).
+    pub heal_nonce_divergence_after_blocks: Option<u32>,
+    /// If set to `true`, transactions are not broadcast to L1. Instead, each transaction is simulated once
+    /// using `eth_call`, and the simulation result is persisted in Postgres. Can be used to validate contract
+    /// deployments and calldata formats on staging chains. Simulations don't account for previously simulated
+    /// transactions, so only transactions not depending on other unsent transactions (e.g., the first commit
+    /// transaction) are simulated meaningfully.
+    pub dry_run: Option<bool>,
+    /// If set to `true`, no new commit transactions are created on start. Can be changed at runtime
+    /// via the admin API; transactions already created are still sent.
+    pub pause_commit: Option<bool>,
+    /// Same as `pause_commit`, but for proof transactions.
+    pub pause_prove: Option<bool>,
+    /// Same as `pause_commit`, but for execute transactions.
+    pub pause_execute: Option<bool>,
+    /// Criteria deciding which L1 batches are committed in a single L1 transaction. If not set, all criteria are used.
+    pub commit_aggregation_criteria: Option<Vec<L1BatchAggregationCriterion>>,
+    /// Same as `commit_aggregation_criteria`, but for proof transactions. If not set, all criteria
+    /// except for `DataSize` are used.
+    pub proof_aggregation_criteria: Option<Vec<L1BatchAggregationCriterion>>,
+    /// Same as `proof_aggregation_criteria`, but for execute transactions.
+    pub execute_aggregation_criteria: Option<Vec<L1BatchAggregationCriterion>>,
 }
 
 impl SenderConfig {
+    const DEFAULT_FEE_BUMP_PERCENT: u64 = 20;
+    const DEFAULT_BLOB_FEE_BUMP_PERCENT: u64 = 100;
+    /// Minimum fee bump accepted by L1 nodes for replacement transactions without blobs.
+    const MIN_FEE_BUMP_PERCENT: u64 = 10;
+    /// Minimum fee bump accepted by L1 nodes for replacement blob transactions.
+    const MIN_BLOB_FEE_BUMP_PERCENT: u64 = 100;
+    const ALL_AGGREGATION_CRITERIA: &'static [L1BatchAggregationCriterion] = &[
+        L1BatchAggregationCriterion::Number,
+        L1BatchAggregationCriterion::Gas,
+        L1BatchAggregationCriterion::DataSize,
+        L1BatchAggregationCriterion::Deadline,
+    ];
+    const DEFAULT_NON_COMMIT_AGGREGATION_CRITERIA: &'static [L1BatchAggregationCriterion] = &[
+        L1BatchAggregationCriterion::Number,
+        L1BatchAggregationCriterion::Gas,
+        L1BatchAggregationCriterion::Deadline,
+    ];
+    const DEFAULT_PRIVATE_RELAY_FALLBACK_BLOCKS: u32 = 10;
+    const DEFAULT_HEAL_NONCE_DIVERGENCE_AFTER_BLOCKS: u32 = 10;
+
+    pub fn fee_bump_percent(&self) -> u64 {
+        self.fee_bump_percent
+            .unwrap_or(Self::DEFAULT_FEE_BUMP_PERCENT)
+    }
+
+    pub fn blob_fee_bump_percent(&self) -> u64 {
+        self.blob_fee_bump_percent
+            .unwrap_or(Self::DEFAULT_BLOB_FEE_BUMP_PERCENT)
+    }
+
+    pub fn blocks_before_first_fee_bump(&self) -> u32 {
+        self.blocks_before_first_fee_bump.unwrap_or(1)
+    }
+
+    pub fn private_relay_fallback_blocks(&self) -> u32 {
+        self.private_relay_fallback_blocks
+            .unwrap_or(Self::DEFAULT_PRIVATE_RELAY_FALLBACK_BLOCKS)
+    }
+
+    pub fn heal_nonce_divergence(&self) -> bool {
+        self.heal_nonce_divergence.unwrap_or(false)
+    }
+
+    pub fn heal_nonce_divergence_after_blocks(&self) -> u32 {
+        self.heal_nonce_divergence_after_blocks
+            .unwrap_or(Self::DEFAULT_HEAL_NONCE_DIVERGENCE_AFTER_BLOCKS)
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(false)
+    }
+
+    pub fn commit_aggregation_criteria(&self) -> &[L1BatchAggregationCriterion] {
+        self.commit_aggregation_criteria
+            .as_deref()
+            .unwrap_or(Self::ALL_AGGREGATION_CRITERIA)
+    }
+
+    pub fn proof_aggregation_criteria(&self) -> &[L1BatchAggregationCriterion] {
+        self.proof_aggregation_criteria
+            .as_deref()
+            .unwrap_or(Self::DEFAULT_NON_COMMIT_AGGREGATION_CRITERIA)
+    }
+
+    pub fn execute_aggregation_criteria(&self) -> &[L1BatchAggregationCriterion] {
+        self.execute_aggregation_criteria
+            .as_deref()
+            .unwrap_or(Self::DEFAULT_NON_COMMIT_AGGREGATION_CRITERIA)
+    }
+
+    /// Checks invariants of the config that cannot be expressed in its type.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if let Some(hysteresis) = self.dynamic_pubdata_sending_hysteresis {
+            anyhow::ensure!(
+                hysteresis.is_finite() && hysteresis >= 0.0,
+                "dynamic_pubdata_sending_hysteresis must be a non-negative number, got {hysteresis}"
+            );
+        }
+        anyhow::ensure!(
+            self.fee_bump_percent() >= Self::MIN_FEE_BUMP_PERCENT,
+            "fee_bump_percent must be at least {}, got {}",
+            Self::MIN_FEE_BUMP_PERCENT,
+            self.fee_bump_percent()
+        );
+        anyhow::ensure!(
+            self.blob_fee_bump_percent() >= Self::MIN_BLOB_FEE_BUMP_PERCENT,
+            "blob_fee_bump_percent must be at least {}, got {}",
+            Self::MIN_BLOB_FEE_BUMP_PERCENT,
+            self.blob_fee_bump_percent()
+        );
+
+        let criteria = [
+            (
+                "commit_aggregation_criteria",
+                self.commit_aggregation_criteria(),
+            ),
+            (
+                "proof_aggregation_criteria",
+                self.proof_aggregation_criteria(),
+            ),
+            (
+                "execute_aggregation_criteria",
+                self.execute_aggregation_criteria(),
+            ),
+        ];
+        for (name, criteria) in criteria {
+            anyhow::ensure!(!criteria.is_empty(), "{name} must not be empty");
+        }
+        for (name, criteria) in &criteria[1..] {
+            anyhow::ensure!(
+                !criteria.contains(&L1BatchAggregationCriterion::DataSize),
+                "{name} must not contain `DataSize`, which is only applicable to commit transactions"
+            );
+        }
+        Ok(())
+    }
+
     /// Converts `self.tx_poll_period` into `Duration`.
     pub fn tx_poll_period(&self) -> Duration {
         Duration::from_secs(self.tx_poll_period)
@@ -1,6 +1,9 @@
 //! Experimental part of configuration.
 
-use std::num::NonZeroU32;
+use std::{
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
 
 use serde::Deserialize;
 
@@ -12,6 +15,10 @@ pub struct ExperimentalDBConfig {
     /// Maximum number of files concurrently opened by state keeper cache RocksDB. Useful to fit into OS limits; can be used
     /// as a rudimentary way to control RAM usage of the cache.
     pub state_keeper_db_max_open_files: Option<NonZeroU32>,
+    /// Interval between manual compactions of the state keeper RocksDB cache. If not specified, the cache
+    /// is only compacted automatically by RocksDB.
+    #[serde(default)]
+    pub state_keeper_db_compaction_interval_sec: Option<NonZeroU64>,
 }
 
 impl Default for ExperimentalDBConfig {
@@ -20,6 +27,7 @@ impl Default for ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb:
                 Self::default_state_keeper_db_block_cache_capacity_mb(),
             state_keeper_db_max_open_files: None,
+            state_keeper_db_compaction_interval_sec: None,
         }
     }
 }
@@ -32,4 +40,10 @@ impl ExperimentalDBConfig {
     pub fn state_keeper_db_block_cache_capacity(&self) -> usize {
         self.state_keeper_db_block_cache_capacity_mb * super::BYTES_IN_MEGABYTE
     }
+
+    /// Returns the interval between manual compactions of the state keeper RocksDB cache, if configured.
+    pub fn state_keeper_db_compaction_interval(&self) -> Option<Duration> {
+        self.state_keeper_db_compaction_interval_sec
+            .map(|interval| Duration::from_secs(interval.get()))
+    }
 }
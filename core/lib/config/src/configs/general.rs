@@ -3,10 +3,11 @@ use crate::{
         chain::{CircuitBreakerConfig, MempoolConfig, OperationsManagerConfig, StateKeeperConfig},
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
-        vm_runner::ProtectiveReadsWriterConfig,
-        FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
+        vm_runner::{ProtectiveReadsWriterConfig, ShadowExecutionConfig},
+        BaseTokenAdjusterConfig, BlockNotificationsConfig, ChangeFeedConfig, DAClientConfig,
+        DADispatcherConfig, FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
         FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig, ObservabilityConfig,
-        PrometheusConfig, ProofDataHandlerConfig,
+        PrometheusConfig, ProofDataHandlerConfig, PruningConfig,
     },
     ApiConfig, ContractVerifierConfig, DBConfig, EthConfig, PostgresConfig, SnapshotsCreatorConfig,
 };
@@ -34,4 +35,11 @@ pub struct GeneralConfig {
     pub snapshot_creator: Option<SnapshotsCreatorConfig>,
     pub observability: Option<ObservabilityConfig>,
     pub protective_reads_writer_config: Option<ProtectiveReadsWriterConfig>,
+    pub shadow_execution_config: Option<ShadowExecutionConfig>,
+    pub pruning: Option<PruningConfig>,
+    pub block_notifications: Option<BlockNotificationsConfig>,
+    pub change_feed: Option<ChangeFeedConfig>,
+    pub da_dispatcher_config: Option<DADispatcherConfig>,
+    pub da_client_config: Option<DAClientConfig>,
+    pub base_token_adjuster: Option<BaseTokenAdjusterConfig>,
 }
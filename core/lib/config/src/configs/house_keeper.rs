@@ -17,6 +17,11 @@ pub struct HouseKeeperConfig {
     pub prover_job_archiver_archive_after_secs: Option<u64>,
     pub fri_gpu_prover_archiver_archiving_interval_ms: Option<u64>,
     pub fri_gpu_prover_archiver_archive_after_secs: Option<u64>,
+    /// Interval between runs of the manager creating and dropping partitions of the `events` table.
+    /// If not set, partitions are not managed, and all new data is stored in the default partitions.
+    pub table_partitioning_interval_ms: Option<u64>,
+    /// Number of L2 blocks in a single table partition.
+    pub table_partition_size: Option<u32>,
 }
 
 impl HouseKeeperConfig {
@@ -29,4 +34,9 @@ impl HouseKeeperConfig {
         self.fri_gpu_prover_archiver_archiving_interval_ms
             .zip(self.fri_gpu_prover_archiver_archive_after_secs)
     }
+
+    pub fn table_partitioning_params(&self) -> Option<(u64, u32)> {
+        self.table_partitioning_interval_ms
+            .zip(self.table_partition_size)
+    }
 }
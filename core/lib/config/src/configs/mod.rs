@@ -1,8 +1,13 @@
 // Public re-exports
 pub use self::{
     api::ApiConfig,
+    base_token_adjuster::BaseTokenAdjusterConfig,
+    block_notifications::BlockNotificationsConfig,
+    change_feed::ChangeFeedConfig,
     contract_verifier::ContractVerifierConfig,
     contracts::{ContractsConfig, EcosystemContracts},
+    da_client::DAClientConfig,
+    da_dispatcher::DADispatcherConfig,
     database::{DBConfig, PostgresConfig},
     eth_sender::{EthConfig, GasAdjusterConfig},
     eth_watch::EthWatchConfig,
@@ -17,17 +22,23 @@ pub use self::{
     object_store::ObjectStoreConfig,
     observability::{ObservabilityConfig, OpentelemetryConfig},
     proof_data_handler::ProofDataHandlerConfig,
-    secrets::{DatabaseSecrets, L1Secrets, Secrets},
+    pruning::PruningConfig,
+    secrets::{ApiSecrets, DatabaseSecrets, L1Secrets, Secrets},
     snapshots_creator::SnapshotsCreatorConfig,
     utils::PrometheusConfig,
-    vm_runner::ProtectiveReadsWriterConfig,
+    vm_runner::{ProtectiveReadsWriterConfig, ShadowExecutionConfig},
 };
 
 pub mod api;
+pub mod base_token_adjuster;
+pub mod block_notifications;
 pub mod chain;
+pub mod change_feed;
 pub mod consensus;
 pub mod contract_verifier;
 pub mod contracts;
+pub mod da_client;
+pub mod da_dispatcher;
 pub mod database;
 pub mod eth_sender;
 pub mod eth_watch;
@@ -44,6 +55,7 @@ pub mod house_keeper;
 pub mod object_store;
 pub mod observability;
 pub mod proof_data_handler;
+pub mod pruning;
 pub mod secrets;
 pub mod snapshots_creator;
 pub mod utils;
@@ -0,0 +1,41 @@
+use std::{num::NonZeroU64, time::Duration};
+
+use serde::Deserialize;
+
+/// Configuration for pruning the historical node state (both Postgres and the Merkle tree).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PruningConfig {
+    /// Number of L1 batches pruned at a time.
+    #[serde(default = "PruningConfig::default_chunk_size")]
+    pub chunk_size: u32,
+    /// Delta between soft- and hard-removing data from Postgres. Should be reasonably large (order of 60 seconds).
+    #[serde(default = "PruningConfig::default_removal_delay_sec")]
+    pub removal_delay_sec: NonZeroU64,
+    /// L1 batches are pruned after the batch timestamp is this old (in seconds). An L1 batch may be retained
+    /// for longer for other reasons; e.g., a batch is never pruned until it is executed on L1.
+    /// If set to 0, L1 batches are not retained based on their timestamp.
+    #[serde(default = "PruningConfig::default_data_retention_sec")]
+    pub data_retention_sec: u64,
+}
+
+impl PruningConfig {
+    const fn default_chunk_size() -> u32 {
+        10
+    }
+
+    fn default_removal_delay_sec() -> NonZeroU64 {
+        NonZeroU64::new(60).unwrap()
+    }
+
+    const fn default_data_retention_sec() -> u64 {
+        3_600 // 1 hour
+    }
+
+    pub fn removal_delay(&self) -> Duration {
+        Duration::from_secs(self.removal_delay_sec.get())
+    }
+
+    pub fn data_retention(&self) -> Duration {
+        Duration::from_secs(self.data_retention_sec)
+    }
+}
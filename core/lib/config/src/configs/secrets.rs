@@ -1,4 +1,5 @@
 use anyhow::Context;
+use secrecy::{ExposeSecret as _, Secret};
 use zksync_basic_types::url::SensitiveUrl;
 
 use crate::configs::consensus::ConsensusSecrets;
@@ -8,11 +9,50 @@ pub struct DatabaseSecrets {
     pub server_url: Option<SensitiveUrl>,
     pub prover_url: Option<SensitiveUrl>,
     pub server_replica_url: Option<SensitiveUrl>,
+    /// Read replicas used by the replica connection pool (e.g., by the API server). If specified,
+    /// `server_replica_url` is only used as a fallback if none of the replicas are healthy.
+    pub server_read_replica_urls: Vec<SensitiveUrl>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct L1Secrets {
     pub l1_rpc_url: SensitiveUrl,
+    /// URL of a private relay RPC (e.g., Flashbots Protect) used by the Ethereum sender to submit transactions
+    /// without exposing them in the public mempool.
+    pub private_relay_rpc_url: Option<SensitiveUrl>,
+    /// URL of the gateway chain RPC. Required to settle on the gateway after the settlement layer of the chain
+    /// is switched to it.
+    pub gateway_rpc_url: Option<SensitiveUrl>,
+    /// URLs of fallback L1 RPC providers. If specified, L1 requests fail over to these providers (in order)
+    /// if the main provider at `l1_rpc_url` is unavailable or inconsistent with other providers.
+    pub fallback_l1_rpc_urls: Vec<SensitiveUrl>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiSecrets {
+    /// Bearer token required by the private admin API server (see `Web3JsonRpcConfig::admin_http_port`).
+    /// Must be set if the admin API server is enabled.
+    pub admin_token: Option<Secret<String>>,
+    /// API keys accepted in the `x-api-key` HTTP header to identify clients for per-client rate limiting
+    /// (see `Web3JsonRpcConfig::client_rate_limits()`).
+    pub client_rate_limit_api_keys: Vec<Secret<String>>,
+}
+
+impl PartialEq for ApiSecrets {
+    fn eq(&self, other: &Self) -> bool {
+        let admin_tokens_eq = match (&self.admin_token, &other.admin_token) {
+            (Some(this), Some(other)) => this.expose_secret() == other.expose_secret(),
+            (None, None) => true,
+            _ => false,
+        };
+        admin_tokens_eq
+            && self.client_rate_limit_api_keys.len() == other.client_rate_limit_api_keys.len()
+            && self
+                .client_rate_limit_api_keys
+                .iter()
+                .zip(&other.client_rate_limit_api_keys)
+                .all(|(this, other)| this.expose_secret() == other.expose_secret())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +60,7 @@ pub struct Secrets {
     pub consensus: Option<ConsensusSecrets>,
     pub database: Option<DatabaseSecrets>,
     pub l1: Option<L1Secrets>,
+    pub api: Option<ApiSecrets>,
 }
 
 impl DatabaseSecrets {
@@ -17,3 +17,24 @@ impl ProtectiveReadsWriterConfig {
         "./db/protective_reads_writer".to_owned()
     }
 }
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct ShadowExecutionConfig {
+    /// Path to the RocksDB data directory that serves state cache.
+    #[serde(default = "ShadowExecutionConfig::default_db_path")]
+    pub db_path: String,
+    /// How many max batches should be processed at the same time.
+    pub window_size: u32,
+    /// All batches before this one (inclusive) are always considered to be processed.
+    pub first_processed_batch: L1BatchNumber,
+    /// Candidate protocol version to re-execute batches with. If not set, the latest protocol version
+    /// supported by the server is used.
+    #[serde(default)]
+    pub protocol_version: Option<u16>,
+}
+
+impl ShadowExecutionConfig {
+    fn default_db_path() -> String {
+        "./db/shadow_execution".to_owned()
+    }
+}
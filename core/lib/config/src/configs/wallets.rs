@@ -1,4 +1,5 @@
-use zksync_basic_types::{Address, H160, H256};
+use secrecy::Secret;
+use zksync_basic_types::{url::SensitiveUrl, Address, H160, H256};
 use zksync_crypto_primitives::K256PrivateKey;
 
 #[derive(Debug, Clone)]
@@ -16,17 +17,47 @@ impl AddressWallet {
     }
 }
 
+/// Remote backend holding the private key of a wallet. Private keys of such wallets never leave the backend.
+#[derive(Debug, Clone)]
+pub enum RemoteSignerConfig {
+    /// Key version in Google Cloud KMS, e.g. `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`.
+    GcpKms { key_name: String },
+    /// Key in AWS KMS specified by its ID or ARN. Credentials are obtained using the default AWS credential provider chain.
+    AwsKms { key_id: String, region: String },
+    /// Generic remote signing API.
+    Api {
+        url: SensitiveUrl,
+        /// Value of the `Authorization` header sent with each request, e.g. `Bearer <token>`.
+        authorization: Option<Secret<String>>,
+    },
+}
+
+/// Source of signatures for a [`Wallet`].
+#[derive(Debug, Clone)]
+pub enum WalletSigner {
+    PrivateKey(K256PrivateKey),
+    Remote(RemoteSignerConfig),
+}
+
 #[derive(Debug, Clone)]
 pub struct Wallet {
     address: Address,
-    private_key: K256PrivateKey,
+    signer: WalletSigner,
 }
 
 impl Wallet {
     pub fn new(private_key: K256PrivateKey) -> Self {
         Self {
             address: private_key.address(),
-            private_key,
+            signer: WalletSigner::PrivateKey(private_key),
+        }
+    }
+
+    /// Creates a wallet with a remote signer. The address is checked against the signer when the signer is initialized.
+    pub fn remote(address: Address, signer: RemoteSignerConfig) -> Self {
+        Self {
+            address,
+            signer: WalletSigner::Remote(signer),
         }
     }
 
@@ -45,7 +76,7 @@ impl Wallet {
 
         Ok(Self {
             address: calculated_address,
-            private_key,
+            signer: WalletSigner::PrivateKey(private_key),
         })
     }
 
@@ -53,8 +84,16 @@ impl Wallet {
         self.address
     }
 
-    pub fn private_key(&self) -> &K256PrivateKey {
-        &self.private_key
+    /// Returns the private key of this wallet, or `None` if the wallet uses a remote signer.
+    pub fn private_key(&self) -> Option<&K256PrivateKey> {
+        match &self.signer {
+            WalletSigner::PrivateKey(private_key) => Some(private_key),
+            WalletSigner::Remote(_) => None,
+        }
+    }
+
+    pub fn signer(&self) -> &WalletSigner {
+        &self.signer
     }
 }
 
@@ -62,6 +101,13 @@ impl Wallet {
 pub struct EthSender {
     pub operator: Wallet,
     pub blob_operator: Option<Wallet>,
+    /// Successor of `operator` used for key rotation. If set, new transactions that would be sent by `operator`
+    /// are signed with this key instead, once all transactions sent by `operator` are mined. Transactions
+    /// already sent by `operator` are still tracked until they are finalized. After that, the successor
+    /// can be promoted to `operator`.
+    pub successor_operator: Option<Wallet>,
+    /// Successor of `blob_operator` used for key rotation; see `successor_operator` for details.
+    pub successor_blob_operator: Option<Wallet>,
 }
 
 #[derive(Debug, Clone)]
@@ -83,6 +129,8 @@ impl Wallets {
                 blob_operator: Some(
                     Wallet::from_private_key_bytes(H256::repeat_byte(0x2), None).unwrap(),
                 ),
+                successor_operator: None,
+                successor_blob_operator: None,
             }),
             state_keeper: Some(StateKeeper {
                 fee_account: AddressWallet::from_address(H160::repeat_byte(0x3)),
@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 
 use rand::{distributions::Distribution, Rng};
 use zksync_basic_types::{
@@ -62,6 +62,7 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             ws_url: self.sample(rng),
             req_entities_limit: self.sample(rng),
             filters_disabled: self.sample(rng),
+            persistent_filters: self.sample(rng),
             filters_limit: self.sample(rng),
             subscriptions_limit: self.sample(rng),
             pubsub_polling_interval: self.sample(rng),
@@ -78,6 +79,7 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             initial_writes_cache_size_mb: self.sample(rng),
             latest_values_cache_size_mb: self.sample(rng),
             fee_history_limit: self.sample(rng),
+            trace_filter_block_range_limit: self.sample(rng),
             max_batch_request_size: self.sample(rng),
             max_response_body_size_mb: self.sample(rng),
             max_response_body_size_overrides_mb: [
@@ -93,6 +95,27 @@ impl Distribution<configs::api::Web3JsonRpcConfig> for EncodeDist {
             .into_iter()
             .collect(),
             websocket_requests_per_minute_limit: self.sample(rng),
+            client_requests_per_minute_limit: self.sample(rng),
+            client_requests_per_minute_overrides: [
+                (
+                    "eth_call",
+                    NonZeroU32::new(self.sample(rng)).unwrap_or(NonZeroU32::MAX),
+                ),
+                (
+                    "debug",
+                    NonZeroU32::new(self.sample(rng)).unwrap_or(NonZeroU32::MAX),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+            client_rate_limit_trusted_proxies: vec![[10, 0, 0, 1].into()],
+            http_response_compression: self.sample(rng),
+            http_methods_allowlist: self.sample_opt(|| vec!["eth".to_owned(), "zks".to_owned()]),
+            http_methods_denylist: vec!["debug".to_owned()],
+            ws_methods_allowlist: self.sample_opt(|| vec!["eth_subscribe".to_owned()]),
+            ws_methods_denylist: vec![],
+            admin_http_port: self.sample(rng),
+            txpool_namespace_enabled: self.sample(rng),
             tree_api_url: self.sample(rng),
             mempool_cache_update_interval: self.sample(rng),
             mempool_cache_size: self.sample(rng),
@@ -124,6 +147,9 @@ impl Distribution<configs::api::MerkleTreeApiConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::api::MerkleTreeApiConfig {
         configs::api::MerkleTreeApiConfig {
             port: self.sample(rng),
+            replica_path: self.sample_opt(|| self.sample(rng)),
+            replica_poll_interval_ms: self
+                .sample_opt(|| NonZeroU64::new(rng.gen()).unwrap_or(NonZeroU64::MAX)),
         }
     }
 }
@@ -171,9 +197,20 @@ impl Distribution<configs::chain::StateKeeperConfig> for EncodeDist {
             batch_overhead_l1_gas: self.sample(rng),
             max_gas_per_batch: self.sample(rng),
             max_pubdata_per_batch: self.sample(rng),
+            max_pubdata_cost_per_batch: self.sample(rng),
+            max_tx_execution_time_ms: self.sample(rng),
+            max_priority_ops_per_batch: self.sample(rng),
+            max_priority_ops_gas_per_batch: self.sample(rng),
+            max_priority_ops_per_l2_block: self.sample(rng),
+            max_priority_ops_gas_per_l2_block: self.sample(rng),
             fee_model_version: self.sample(rng),
             validation_computational_gas_limit: self.sample(rng),
             save_call_traces: self.sample(rng),
+            enable_opcode_profiling: self.sample(rng),
+            enable_gas_attribution: self.sample(rng),
+            enable_mempool_pre_execution: self.sample(rng),
+            enable_execution_result_cache: self.sample(rng),
+            enable_storage_prefetching: self.sample(rng),
             max_circuits_per_batch: self.sample(rng),
             // These values are not involved into files serialization skip them
             fee_account_addr: None,
@@ -199,6 +236,10 @@ impl Distribution<configs::chain::CircuitBreakerConfig> for EncodeDist {
             http_req_max_retry_number: self.sample(rng),
             http_req_retry_interval_sec: self.sample(rng),
             replication_lag_limit_sec: self.sample(rng),
+            db_integrity_check_chunk_size: self.sample(rng),
+            pool_saturation_shedding_percent: self.sample(rng),
+            pool_acquire_latency_shedding_ms: self.sample(rng),
+            pool_saturation_trip_after_sec: self.sample(rng),
         }
     }
 }
@@ -212,6 +253,14 @@ impl Distribution<configs::chain::MempoolConfig> for EncodeDist {
             stuck_tx_timeout: self.sample(rng),
             remove_stuck_txs: self.sample(rng),
             delay_interval: self.sample(rng),
+            fifo_ordering: self.sample(rng),
+            replacement_fee_bump_percent: self.sample(rng),
+            tx_ttl_sec: self.sample(rng),
+            max_nonce_gap: self.sample(rng),
+            eviction_interval_ms: self.sample(rng),
+            max_pending_txs_per_account: self.sample(rng),
+            address_denylist_path: self.sample(rng),
+            address_denylist_reload_interval_ms: self.sample(rng),
         }
     }
 }
@@ -271,6 +320,15 @@ impl Distribution<configs::database::MerkleTreeConfig> for EncodeDist {
             memtable_capacity_mb: self.sample(rng),
             stalled_writes_timeout_sec: self.sample(rng),
             max_l1_batches_per_iter: self.sample(rng),
+            hashing_thread_count: self.sample(rng),
+            pruning_retained_l1_batches: self
+                .sample_opt(|| NonZeroU32::new(self.sample(rng)).unwrap_or(NonZeroU32::MAX)),
+            compaction_interval_sec: self
+                .sample_opt(|| NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX)),
+            verification_interval_sec: self
+                .sample_opt(|| NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX)),
+            checkpoint_interval_sec: self
+                .sample_opt(|| NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX)),
         }
     }
 }
@@ -280,6 +338,8 @@ impl Distribution<configs::ExperimentalDBConfig> for EncodeDist {
         configs::ExperimentalDBConfig {
             state_keeper_db_block_cache_capacity_mb: self.sample(rng),
             state_keeper_db_max_open_files: self.sample(rng),
+            state_keeper_db_compaction_interval_sec: self
+                .sample_opt(|| NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX)),
         }
     }
 }
@@ -303,6 +363,9 @@ impl Distribution<configs::database::PostgresConfig> for EncodeDist {
             statement_timeout_sec: self.sample(rng),
             long_connection_threshold_ms: self.sample(rng),
             slow_query_threshold_ms: self.sample(rng),
+            report_all_query_latencies: self.sample(rng),
+            redact_query_args: self.sample(rng),
+            max_replica_lag_sec: self.sample(rng),
             test_server_url: self.sample(rng),
             test_prover_url: self.sample(rng),
         }
@@ -330,6 +393,16 @@ impl Distribution<configs::eth_sender::ProofSendingMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::eth_sender::FeeBumpStrategy> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::FeeBumpStrategy {
+        type T = configs::eth_sender::FeeBumpStrategy;
+        match rng.gen_range(0..2) {
+            0 => T::Linear,
+            _ => T::Exponential,
+        }
+    }
+}
+
 impl Distribution<configs::eth_sender::ProofLoadingMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::ProofLoadingMode {
         type T = configs::eth_sender::ProofLoadingMode;
@@ -340,12 +413,28 @@ impl Distribution<configs::eth_sender::ProofLoadingMode> for EncodeDist {
     }
 }
 
+impl Distribution<configs::eth_sender::L1BatchAggregationCriterion> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> configs::eth_sender::L1BatchAggregationCriterion {
+        type T = configs::eth_sender::L1BatchAggregationCriterion;
+        match rng.gen_range(0..4) {
+            0 => T::Number,
+            1 => T::Gas,
+            2 => T::DataSize,
+            _ => T::Deadline,
+        }
+    }
+}
+
 impl Distribution<configs::eth_sender::PubdataSendingMode> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::eth_sender::PubdataSendingMode {
         type T = configs::eth_sender::PubdataSendingMode;
-        match rng.gen_range(0..2) {
+        match rng.gen_range(0..3) {
             0 => T::Calldata,
-            _ => T::Blobs,
+            1 => T::Blobs,
+            _ => T::Custom,
         }
     }
 }
@@ -370,6 +459,29 @@ impl Distribution<configs::eth_sender::SenderConfig> for EncodeDist {
             l1_batch_min_age_before_execute_seconds: self.sample(rng),
             max_acceptable_priority_fee_in_gwei: self.sample(rng),
             pubdata_sending_mode: PubdataSendingMode::Calldata,
+            dynamic_pubdata_sending_hysteresis: self.sample(rng),
+            fee_bump_strategy: self.sample(rng),
+            fee_bump_percent: self.sample_opt(|| rng.gen_range(10..1_000)),
+            blob_fee_bump_strategy: self.sample(rng),
+            blob_fee_bump_percent: self.sample_opt(|| rng.gen_range(100..1_000)),
+            max_total_fee_bump_percent: self.sample(rng),
+            blocks_before_first_fee_bump: self.sample(rng),
+            private_relay_fallback_blocks: self.sample(rng),
+            heal_nonce_divergence: self.sample(rng),
+            heal_nonce_divergence_after_blocks: self.sample(rng),
+            dry_run: self.sample(rng),
+            pause_commit: self.sample(rng),
+            pause_prove: self.sample(rng),
+            pause_execute: self.sample(rng),
+            commit_aggregation_criteria: self.sample_opt(|| vec![self.sample(rng)]),
+            proof_aggregation_criteria: self.sample_opt(|| {
+                vec![
+                    configs::eth_sender::L1BatchAggregationCriterion::Number,
+                    configs::eth_sender::L1BatchAggregationCriterion::Deadline,
+                ]
+            }),
+            execute_aggregation_criteria: self
+                .sample_opt(|| vec![configs::eth_sender::L1BatchAggregationCriterion::Gas]),
         }
     }
 }
@@ -585,6 +697,8 @@ impl Distribution<configs::house_keeper::HouseKeeperConfig> for EncodeDist {
             prover_job_archiver_archive_after_secs: self.sample(rng),
             fri_gpu_prover_archiver_archiving_interval_ms: self.sample(rng),
             fri_gpu_prover_archiver_archive_after_secs: self.sample(rng),
+            table_partitioning_interval_ms: self.sample(rng),
+            table_partition_size: self.sample(rng),
         }
     }
 }
@@ -639,6 +753,91 @@ impl Distribution<configs::SnapshotsCreatorConfig> for EncodeDist {
     }
 }
 
+impl Distribution<configs::PruningConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::PruningConfig {
+        configs::PruningConfig {
+            chunk_size: self.sample(rng),
+            removal_delay_sec: NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX),
+            data_retention_sec: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::BlockNotificationsConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::BlockNotificationsConfig {
+        configs::BlockNotificationsConfig {
+            poll_interval_ms: self.sample(rng),
+            max_batch_size: self.sample(rng),
+            webhook_url: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::BaseTokenAdjusterConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::BaseTokenAdjusterConfig {
+        configs::BaseTokenAdjusterConfig {
+            price_polling_interval_ms: self.sample(rng),
+            fixed_ratio_numerator: NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX),
+            fixed_ratio_denominator: NonZeroU64::new(self.sample(rng)).unwrap_or(NonZeroU64::MAX),
+        }
+    }
+}
+
+impl Distribution<configs::ChangeFeedConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ChangeFeedConfig {
+        configs::ChangeFeedConfig {
+            slot_name: self.sample(rng),
+            poll_interval_ms: self.sample(rng),
+            max_batch_size: self.sample(rng),
+            webhook_url: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::DADispatcherConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::DADispatcherConfig {
+        configs::DADispatcherConfig {
+            polling_interval_ms: self.sample(rng),
+            max_rows_to_dispatch: self.sample(rng),
+            max_retries: self.sample(rng),
+        }
+    }
+}
+
+impl Distribution<configs::da_client::DAClient> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::da_client::DAClient {
+        match rng.gen_range(0..3) {
+            0 => configs::da_client::DAClient::Avail(configs::da_client::AvailConfig {
+                api_node_url: self.sample(rng),
+                bridge_api_url: self.sample(rng),
+            }),
+            1 => configs::da_client::DAClient::Celestia(configs::da_client::CelestiaConfig {
+                api_node_url: self.sample(rng),
+                namespace: self.sample(rng),
+                auth_token: self.sample(rng),
+            }),
+            _ => configs::da_client::DAClient::EigenDA(configs::da_client::EigenDAConfig {
+                disperser_rpc: self.sample(rng),
+                custom_quorum_numbers: self.sample_collect(rng),
+                wait_for_finalization: self.sample(rng),
+                blob_size_limit: self.sample(rng),
+                account_id: self.sample_opt(|| self.sample(rng)),
+                signer_private_key: self.sample_opt(|| self.sample(rng)),
+                g1_srs_path: self.sample(rng),
+            }),
+        }
+    }
+}
+
+impl Distribution<configs::DAClientConfig> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::DAClientConfig {
+        configs::DAClientConfig {
+            client: self.sample(rng),
+            request_timeout_ms: self.sample(rng),
+        }
+    }
+}
+
 impl Distribution<configs::ObservabilityConfig> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::ObservabilityConfig {
         configs::ObservabilityConfig {
@@ -759,6 +958,13 @@ impl Distribution<configs::secrets::L1Secrets> for EncodeDist {
         use configs::secrets::L1Secrets;
         L1Secrets {
             l1_rpc_url: format!("localhost:{}", rng.gen::<u16>()).parse().unwrap(),
+            private_relay_rpc_url: self
+                .sample_opt(|| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            gateway_rpc_url: self
+                .sample_opt(|| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            fallback_l1_rpc_urls: (0..rng.gen_range(0..3))
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
         }
     }
 }
@@ -769,11 +975,25 @@ impl Distribution<configs::secrets::DatabaseSecrets> for EncodeDist {
         DatabaseSecrets {
             server_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
             server_replica_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
+            server_read_replica_urls: (0..rng.gen_range(0..3))
+                .map(|_| format!("localhost:{}", rng.gen::<u16>()).parse().unwrap())
+                .collect(),
             prover_url: Some(format!("localhost:{}", rng.gen::<u16>()).parse().unwrap()),
         }
     }
 }
 
+impl Distribution<configs::secrets::ApiSecrets> for EncodeDist {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::ApiSecrets {
+        configs::secrets::ApiSecrets {
+            admin_token: self.sample_opt(|| format!("{:x}", rng.gen::<u128>()).into()),
+            client_rate_limit_api_keys: (0..rng.gen_range(0..3))
+                .map(|_| format!("{:x}", rng.gen::<u128>()).into())
+                .collect(),
+        }
+    }
+}
+
 impl Distribution<configs::secrets::Secrets> for EncodeDist {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> configs::secrets::Secrets {
         use configs::secrets::Secrets;
@@ -781,6 +1001,7 @@ impl Distribution<configs::secrets::Secrets> for EncodeDist {
             consensus: self.sample_opt(|| self.sample(rng)),
             database: self.sample_opt(|| self.sample(rng)),
             l1: self.sample_opt(|| self.sample(rng)),
+            api: self.sample_opt(|| self.sample(rng)),
         }
     }
 }
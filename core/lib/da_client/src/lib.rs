@@ -0,0 +1,53 @@
+//! Interface of clients for data availability (DA) layers.
+
+use std::fmt;
+
+use async_trait::async_trait;
+
+pub use self::types::{DAError, DispatchResponse, InclusionData, InclusionStatus};
+
+pub mod types;
+
+/// Client for a data availability layer.
+#[async_trait]
+pub trait DataAvailabilityClient: Sync + Send + fmt::Debug {
+    /// Returns the name of the DA layer. The name is persisted together with blob IDs.
+    fn client_type(&self) -> &'static str;
+
+    /// Dispatches a blob with the pubdata of the specified L1 batch to the DA layer.
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError>;
+
+    /// Fetches inclusion data for a previously dispatched blob. Returns [`InclusionStatus::Failed`] if the blob
+    /// will never be included, so that the caller dispatches it again.
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<InclusionStatus, DAError>;
+
+    /// Verifies that the inclusion data returned by [`Self::get_inclusion_data()`] proves inclusion of a blob
+    /// with the specified data. Implementations should check everything that can be checked without L1;
+    /// parts of the proof anchored to L1 (e.g., attested DA layer roots) are checked by the L1 verifier contract
+    /// when the L1 batch is committed.
+    ///
+    /// Inclusion data that fails verification is never persisted, so that the L1 batch is not committed. If the returned
+    /// error is not retriable, the blob is considered invalid and is dispatched again.
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        data: &[u8],
+        inclusion_data: &InclusionData,
+    ) -> Result<(), DAError>;
+
+    /// Clones the client and wraps the clone in a `Box`.
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient>;
+
+    /// Returns the maximum size of a blob (in bytes) accepted by the DA layer, or `None` if there's no limit.
+    fn blob_size_limit(&self) -> Option<usize>;
+}
+
+impl Clone for Box<dyn DataAvailabilityClient> {
+    fn clone(&self) -> Self {
+        self.clone_boxed()
+    }
+}
@@ -0,0 +1,63 @@
+use std::{error, fmt};
+
+/// Error returned by a [`DataAvailabilityClient`](crate::DataAvailabilityClient).
+#[derive(Debug)]
+pub struct DAError {
+    pub error: anyhow::Error,
+    /// Whether the request may succeed if retried (e.g., if the DA layer was temporarily unavailable).
+    pub is_retriable: bool,
+}
+
+impl DAError {
+    pub fn is_retriable(&self) -> bool {
+        self.is_retriable
+    }
+}
+
+impl fmt::Display for DAError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_retriable {
+            "retriable"
+        } else {
+            "fatal"
+        };
+        write!(
+            formatter,
+            "{kind} data availability client error: {:#}",
+            self.error
+        )
+    }
+}
+
+impl error::Error for DAError {}
+
+/// Response to dispatching a blob to a DA layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DispatchResponse {
+    /// Identifier of the blob on the DA layer. The format of the ID depends on the DA layer.
+    pub blob_id: String,
+}
+
+impl From<String> for DispatchResponse {
+    fn from(blob_id: String) -> Self {
+        Self { blob_id }
+    }
+}
+
+/// Data proving that a blob is included into the DA layer (e.g., an inclusion proof or an attestation).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionData {
+    pub data: Vec<u8>,
+}
+
+/// Inclusion status of a dispatched blob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InclusionStatus {
+    /// Inclusion data is not available yet (e.g., the blob is not finalized on the DA layer).
+    Pending,
+    /// The blob is included into the DA layer.
+    Included(InclusionData),
+    /// The DA layer has dropped the blob (e.g., it wasn't attested by the required quorums), so it must be
+    /// dispatched again.
+    Failed { reason: String },
+}
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS tee_verifier_input_producer_jobs;
+
+DROP TYPE IF EXISTS tee_verifier_input_producer_job_status;
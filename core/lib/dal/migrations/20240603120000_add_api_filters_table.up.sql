@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS api_filters (
+    id BYTEA PRIMARY KEY,
+    filter JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS api_filters_updated_at_idx ON api_filters (updated_at);
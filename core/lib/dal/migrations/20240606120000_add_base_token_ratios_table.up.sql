@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS base_token_ratios (
+    id SERIAL PRIMARY KEY,
+    ratio_timestamp TIMESTAMP NOT NULL,
+    numerator NUMERIC(20, 0) NOT NULL,
+    denominator NUMERIC(20, 0) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS l1_gas_price_history_l1_block_timestamp_idx;
+DROP TABLE IF EXISTS l1_gas_price_history;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS l1_gas_price_history (
+    l1_block_number BIGINT PRIMARY KEY,
+    l1_block_timestamp BIGINT NOT NULL,
+    base_fee_per_gas NUMERIC(80, 0) NOT NULL,
+    blob_base_fee NUMERIC(80, 0),
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS l1_gas_price_history_l1_block_timestamp_idx ON l1_gas_price_history (l1_block_timestamp);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS replaced_transactions (
+    hash BYTEA PRIMARY KEY,
+    replaced_by BYTEA NOT NULL,
+    initiator_address BYTEA NOT NULL,
+    nonce BIGINT NOT NULL,
+    gas_per_pubdata_limit NUMERIC(80, 0) NOT NULL,
+    received_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
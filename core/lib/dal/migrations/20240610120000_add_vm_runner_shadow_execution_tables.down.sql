@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS vm_runner_shadow_divergences;
+DROP TABLE IF EXISTS vm_runner_shadow_execution;
@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS vm_runner_shadow_execution
+(
+    l1_batch_number       BIGINT    NOT NULL PRIMARY KEY,
+    created_at            TIMESTAMP NOT NULL,
+    updated_at            TIMESTAMP NOT NULL,
+    time_taken            TIME
+);
+
+CREATE TABLE IF NOT EXISTS vm_runner_shadow_divergences
+(
+    id                    BIGSERIAL PRIMARY KEY,
+    l1_batch_number       BIGINT    NOT NULL,
+    protocol_version      INT       NOT NULL,
+    tx_hash               BYTEA,
+    kind                  TEXT      NOT NULL,
+    details               TEXT      NOT NULL,
+    created_at            TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS vm_runner_shadow_divergences_l1_batch_number_idx
+    ON vm_runner_shadow_divergences (l1_batch_number);
@@ -0,0 +1,10 @@
+-- Copies data from all partitions back into plain tables.
+CREATE TABLE events_unpartitioned (LIKE events INCLUDING ALL);
+INSERT INTO events_unpartitioned SELECT * FROM events;
+DROP TABLE events;
+ALTER TABLE events_unpartitioned RENAME TO events;
+
+CREATE TABLE storage_logs_unpartitioned (LIKE storage_logs INCLUDING ALL);
+INSERT INTO storage_logs_unpartitioned SELECT * FROM storage_logs;
+DROP TABLE storage_logs;
+ALTER TABLE storage_logs_unpartitioned RENAME TO storage_logs;
@@ -0,0 +1,39 @@
+-- Converts `events` and `storage_logs` into tables range-partitioned by the L2 block number. Existing data is not copied;
+-- instead, the original tables are attached as partitions covering all L2 blocks persisted at the time of migration.
+-- Attaching a partition requires a single scan of the attached table to validate the partition bounds.
+-- Partitions for new L2 blocks are created by the house keeper; L2 blocks not covered by range partitions
+-- are stored in the default partition.
+DO $$
+DECLARE
+    next_l2_block BIGINT;
+BEGIN
+    SELECT COALESCE(MAX(miniblock_number), -1) + 1 INTO next_l2_block FROM events;
+    next_l2_block := GREATEST(next_l2_block, (SELECT COALESCE(MAX(number), -1) + 1 FROM miniblocks));
+
+    ALTER TABLE events RENAME TO events_legacy;
+    CREATE TABLE events (LIKE events_legacy INCLUDING ALL) PARTITION BY RANGE (miniblock_number);
+    IF next_l2_block > 0 THEN
+        EXECUTE format(
+            'ALTER TABLE events ATTACH PARTITION events_legacy FOR VALUES FROM (0) TO (%s)',
+            next_l2_block
+        );
+    ELSE
+        DROP TABLE events_legacy;
+    END IF;
+    CREATE TABLE events_default PARTITION OF events DEFAULT;
+
+    SELECT COALESCE(MAX(miniblock_number), -1) + 1 INTO next_l2_block FROM storage_logs;
+    next_l2_block := GREATEST(next_l2_block, (SELECT COALESCE(MAX(number), -1) + 1 FROM miniblocks));
+
+    ALTER TABLE storage_logs RENAME TO storage_logs_legacy;
+    CREATE TABLE storage_logs (LIKE storage_logs_legacy INCLUDING ALL) PARTITION BY RANGE (miniblock_number);
+    IF next_l2_block > 0 THEN
+        EXECUTE format(
+            'ALTER TABLE storage_logs ATTACH PARTITION storage_logs_legacy FOR VALUES FROM (0) TO (%s)',
+            next_l2_block
+        );
+    ELSE
+        DROP TABLE storage_logs_legacy;
+    END IF;
+    CREATE TABLE storage_logs_default PARTITION OF storage_logs DEFAULT;
+END $$;
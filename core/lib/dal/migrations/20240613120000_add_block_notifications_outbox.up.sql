@@ -0,0 +1,7 @@
+-- Notifications about sealed L2 blocks and L1 batch status changes. Rows are inserted in the same transaction
+-- as the data they notify about, and are removed once dispatched.
+CREATE TABLE IF NOT EXISTS block_notifications_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    notification JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS miniblocks_notify_insert ON miniblocks;
+DROP FUNCTION IF EXISTS notify_new_miniblock;
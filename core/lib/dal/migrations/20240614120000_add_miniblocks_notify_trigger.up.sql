@@ -0,0 +1,10 @@
+CREATE OR REPLACE FUNCTION notify_new_miniblock() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_notify('new_miniblocks', NEW.number::TEXT);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER miniblocks_notify_insert
+    AFTER INSERT ON miniblocks
+    FOR EACH ROW EXECUTE FUNCTION notify_new_miniblock();
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS node_storage_mode;
+DROP TYPE IF EXISTS storage_mode;
@@ -0,0 +1,10 @@
+CREATE TYPE storage_mode AS ENUM ('Archive', 'Pruned');
+
+CREATE TABLE IF NOT EXISTS node_storage_mode (
+    -- Ensures that the table contains at most one row.
+    singleton BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    mode storage_mode NOT NULL,
+    data_retention_sec BIGINT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
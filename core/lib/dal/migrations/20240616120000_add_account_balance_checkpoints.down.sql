@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS account_balance_index;
+DROP TABLE IF EXISTS account_balance_checkpoints;
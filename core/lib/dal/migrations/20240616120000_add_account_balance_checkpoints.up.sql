@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS account_balance_checkpoints (
+    address BYTEA NOT NULL,
+    miniblock_number BIGINT NOT NULL,
+    balance NUMERIC(80) NOT NULL,
+    PRIMARY KEY (address, miniblock_number)
+);
+-- Used when rolling back checkpoints.
+CREATE INDEX IF NOT EXISTS account_balance_checkpoints_miniblock_number_idx
+    ON account_balance_checkpoints (miniblock_number);
+
+CREATE TABLE IF NOT EXISTS account_balance_index (
+    -- Ensures that the table contains at most one row.
+    singleton BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    first_miniblock_number BIGINT NOT NULL,
+    last_miniblock_number BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS token_transfers_index;
+DROP TABLE IF EXISTS token_balances;
+DROP TABLE IF EXISTS token_transfers;
@@ -0,0 +1,34 @@
+CREATE TABLE IF NOT EXISTS token_transfers (
+    miniblock_number BIGINT NOT NULL,
+    event_index_in_block INT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    token_address BYTEA NOT NULL,
+    from_address BYTEA NOT NULL,
+    to_address BYTEA NOT NULL,
+    amount NUMERIC(80) NOT NULL,
+    PRIMARY KEY (miniblock_number, event_index_in_block)
+);
+CREATE INDEX IF NOT EXISTS token_transfers_from_address_idx
+    ON token_transfers (from_address, miniblock_number);
+CREATE INDEX IF NOT EXISTS token_transfers_to_address_idx
+    ON token_transfers (to_address, miniblock_number);
+
+-- Current token balances computed from indexed transfers. Balances may be negative for
+-- non-standard tokens (e.g., rebasing ones) that change balances without emitting `Transfer` events.
+-- Conversely, malicious tokens can emit transfers summing to balances out of the 256-bit range, so
+-- the balance precision is unconstrained; otherwise, such a token would stall the indexer.
+CREATE TABLE IF NOT EXISTS token_balances (
+    address BYTEA NOT NULL,
+    token_address BYTEA NOT NULL,
+    balance NUMERIC NOT NULL,
+    updated_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (address, token_address)
+);
+
+CREATE TABLE IF NOT EXISTS token_transfers_index (
+    -- Ensures that the table contains at most one row.
+    singleton BOOLEAN NOT NULL PRIMARY KEY DEFAULT TRUE CHECK (singleton),
+    last_miniblock_number BIGINT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS eth_txs_dry_runs (
+    eth_tx_id INT PRIMARY KEY REFERENCES eth_txs (id) ON DELETE CASCADE,
+    l1_block_number INT NOT NULL,
+    revert_reason TEXT,
+    created_at TIMESTAMP NOT NULL
+);
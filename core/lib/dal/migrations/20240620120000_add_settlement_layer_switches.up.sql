@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS settlement_layer_switches (
+    id SERIAL PRIMARY KEY,
+    chain_id BIGINT NOT NULL,
+    is_gateway BOOLEAN NOT NULL,
+    diamond_proxy_addr BYTEA NOT NULL,
+    validator_timelock_addr BYTEA NOT NULL,
+    l1_block_number INT NOT NULL,
+    event_index INT NOT NULL,
+    -- Set by eth_sender once all transactions settled on the previous layer are confirmed.
+    applied_after_eth_tx_id INT,
+    applied_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL,
+    UNIQUE (l1_block_number, event_index)
+);
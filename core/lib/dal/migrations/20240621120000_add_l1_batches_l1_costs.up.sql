@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS l1_batches_l1_costs (
+    l1_batch_number BIGINT NOT NULL REFERENCES l1_batches (number) ON DELETE CASCADE,
+    tx_type TEXT NOT NULL,
+    eth_tx_id INT NOT NULL REFERENCES eth_txs (id) ON DELETE CASCADE,
+    l1_batches_in_tx INT NOT NULL,
+    gas_used BIGINT NOT NULL,
+    gas_price NUMERIC(80) NOT NULL,
+    blob_gas_used BIGINT NOT NULL,
+    blob_gas_price NUMERIC(80) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    PRIMARY KEY (l1_batch_number, tx_type)
+);
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS eth_sender_paused_operations (
+    tx_type TEXT PRIMARY KEY,
+    paused_at TIMESTAMP NOT NULL
+);
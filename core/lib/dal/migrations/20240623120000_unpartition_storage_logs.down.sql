@@ -0,0 +1,19 @@
+DO $$
+DECLARE
+    next_l2_block BIGINT;
+BEGIN
+    SELECT COALESCE(MAX(miniblock_number), -1) + 1 INTO next_l2_block FROM storage_logs;
+    next_l2_block := GREATEST(next_l2_block, (SELECT COALESCE(MAX(number), -1) + 1 FROM miniblocks));
+
+    ALTER TABLE storage_logs RENAME TO storage_logs_legacy;
+    CREATE TABLE storage_logs (LIKE storage_logs_legacy INCLUDING ALL) PARTITION BY RANGE (miniblock_number);
+    IF next_l2_block > 0 THEN
+        EXECUTE format(
+            'ALTER TABLE storage_logs ATTACH PARTITION storage_logs_legacy FOR VALUES FROM (0) TO (%s)',
+            next_l2_block
+        );
+    ELSE
+        DROP TABLE storage_logs_legacy;
+    END IF;
+    CREATE TABLE storage_logs_default PARTITION OF storage_logs DEFAULT;
+END $$;
@@ -0,0 +1,18 @@
+-- Converts `storage_logs` back into a plain table. Unlike events, storage logs cannot be pruned by dropping partitions
+-- since hard pruning retains the latest log for each storage key, so partitioning the table has no benefits.
+-- To avoid copying all data, the legacy partition (if any) is detached and becomes the table again;
+-- only logs stored in other partitions are copied.
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM pg_class WHERE relname = 'storage_logs_legacy') THEN
+        ALTER TABLE storage_logs DETACH PARTITION storage_logs_legacy;
+        INSERT INTO storage_logs_legacy SELECT * FROM storage_logs;
+        DROP TABLE storage_logs;
+        ALTER TABLE storage_logs_legacy RENAME TO storage_logs;
+    ELSE
+        CREATE TABLE storage_logs_unpartitioned (LIKE storage_logs INCLUDING ALL);
+        INSERT INTO storage_logs_unpartitioned SELECT * FROM storage_logs;
+        DROP TABLE storage_logs;
+        ALTER TABLE storage_logs_unpartitioned RENAME TO storage_logs;
+    END IF;
+END $$;
@@ -0,0 +1,165 @@
+//! Storage for filters installed via the Web3 API (`eth_newFilter` etc.).
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::H256;
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct ApiFiltersDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ApiFiltersDal<'_, '_> {
+    /// Inserts a new filter. The filter contents are opaque for the DAL.
+    pub async fn insert_filter(&mut self, id: H256, filter: &serde_json::Value) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                api_filters (id, filter, created_at, updated_at)
+            VALUES
+                ($1, $2, NOW(), NOW())
+            "#,
+            id.as_bytes(),
+            filter
+        )
+        .instrument("insert_filter")
+        .with_arg("id", &id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns a filter with the specified ID and marks it as recently used.
+    pub async fn get_filter(&mut self, id: H256) -> DalResult<Option<serde_json::Value>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE api_filters
+            SET
+                updated_at = NOW()
+            WHERE
+                id = $1
+            RETURNING
+                filter
+            "#,
+            id.as_bytes()
+        )
+        .instrument("get_filter")
+        .with_arg("id", &id)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.filter))
+    }
+
+    /// Updates contents of a filter with the specified ID. Does nothing if the filter does not exist.
+    pub async fn update_filter(&mut self, id: H256, filter: &serde_json::Value) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE api_filters
+            SET
+                filter = $2,
+                updated_at = NOW()
+            WHERE
+                id = $1
+            "#,
+            id.as_bytes(),
+            filter
+        )
+        .instrument("update_filter")
+        .with_arg("id", &id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes a filter with the specified ID. Returns `false` if the filter does not exist.
+    pub async fn remove_filter(&mut self, id: H256) -> DalResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM api_filters
+            WHERE
+                id = $1
+            "#,
+            id.as_bytes()
+        )
+        .instrument("remove_filter")
+        .with_arg("id", &id)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Removes least recently used filters so that at most `max_count` filters remain.
+    /// Returns the number of removed filters.
+    pub async fn prune_filters(&mut self, max_count: usize) -> DalResult<u64> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM api_filters
+            WHERE
+                id IN (
+                    SELECT
+                        id
+                    FROM
+                        api_filters
+                    ORDER BY
+                        updated_at DESC
+                    OFFSET
+                        $1
+                )
+            "#,
+            max_count as i64
+        )
+        .instrument("prune_filters")
+        .with_arg("max_count", &max_count)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn filters_basics() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        let ids = [H256::repeat_byte(1), H256::repeat_byte(2)];
+        for (i, &id) in ids.iter().enumerate() {
+            let filter = serde_json::json!({ "blocks": i });
+            conn.api_filters_dal()
+                .insert_filter(id, &filter)
+                .await
+                .unwrap();
+        }
+
+        let filter = conn.api_filters_dal().get_filter(ids[0]).await.unwrap();
+        assert_eq!(filter, Some(serde_json::json!({ "blocks": 0 })));
+        let missing_filter = conn
+            .api_filters_dal()
+            .get_filter(H256::zero())
+            .await
+            .unwrap();
+        assert_eq!(missing_filter, None);
+
+        let new_filter = serde_json::json!({ "blocks": 10 });
+        conn.api_filters_dal()
+            .update_filter(ids[1], &new_filter)
+            .await
+            .unwrap();
+        let filter = conn.api_filters_dal().get_filter(ids[1]).await.unwrap();
+        assert_eq!(filter, Some(new_filter));
+
+        // The first filter is the least recently used one.
+        let pruned_count = conn.api_filters_dal().prune_filters(1).await.unwrap();
+        assert_eq!(pruned_count, 1);
+        let filter = conn.api_filters_dal().get_filter(ids[0]).await.unwrap();
+        assert_eq!(filter, None);
+
+        assert!(conn.api_filters_dal().remove_filter(ids[1]).await.unwrap());
+        assert!(!conn.api_filters_dal().remove_filter(ids[1]).await.unwrap());
+    }
+}
@@ -0,0 +1,466 @@
+//! Index of historical account balances in the base token.
+
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{api::BalanceCheckpoint, Address, L2BlockNumber, H256};
+use zksync_utils::{bigdecimal_to_u256, u256_to_big_decimal};
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct BalanceHistoryDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BalanceHistoryDal<'_, '_> {
+    /// Returns the range of L2 blocks covered by the balance index, or `None` if the index is empty.
+    pub async fn get_indexed_range(
+        &mut self,
+    ) -> DalResult<Option<ops::RangeInclusive<L2BlockNumber>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                first_miniblock_number,
+                last_miniblock_number
+            FROM
+                account_balance_index
+            "#
+        )
+        .instrument("get_indexed_range")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| {
+            L2BlockNumber(row.first_miniblock_number as u32)
+                ..=L2BlockNumber(row.last_miniblock_number as u32)
+        }))
+    }
+
+    /// Returns indexed topics of events emitted by the `token_address` contract in the specified L2 blocks.
+    /// For the base token contract, these topics contain all accounts that could have their balance changed.
+    pub async fn get_event_topics(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        token_address: Address,
+    ) -> DalResult<Vec<H256>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                topic AS "topic!"
+            FROM
+                events,
+                UNNEST(ARRAY[topic2, topic3]) AS topic
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND address = $3
+                AND LENGTH(topic) = 32
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            token_address.as_bytes()
+        )
+        .instrument("get_event_topics")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("token_address", &token_address)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| H256::from_slice(&row.topic))
+            .collect())
+    }
+
+    /// Returns final values written to the specified storage slots in each of the specified L2 blocks.
+    /// Slots not written to in a certain L2 block are not returned for this block. The returned tuples
+    /// are `(l2_block_number, hashed_key, value)`.
+    pub async fn get_final_writes(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        hashed_keys: &[H256],
+    ) -> DalResult<Vec<(L2BlockNumber, H256, H256)>> {
+        let hashed_keys: Vec<_> = hashed_keys.iter().map(H256::as_bytes).collect();
+        let rows = sqlx::query!(
+            r#"
+            SELECT DISTINCT
+                ON (hashed_key, miniblock_number) hashed_key,
+                miniblock_number,
+                value
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND hashed_key = ANY ($3)
+            ORDER BY
+                hashed_key,
+                miniblock_number,
+                operation_number DESC
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            &hashed_keys as &[&[u8]]
+        )
+        .instrument("get_final_writes")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("hashed_keys.len", &hashed_keys.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                (
+                    L2BlockNumber(row.miniblock_number as u32),
+                    H256::from_slice(&row.hashed_key),
+                    H256::from_slice(&row.value),
+                )
+            })
+            .collect())
+    }
+
+    /// Inserts balance checkpoints for the specified range of L2 blocks and extends the indexed range
+    /// to include these blocks. The caller is responsible for the range to directly follow
+    /// the previously indexed range (or to be the first indexed range).
+    pub async fn insert_checkpoints(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        checkpoints: &[(Address, BalanceCheckpoint)],
+    ) -> DalResult<()> {
+        let mut addresses = Vec::with_capacity(checkpoints.len());
+        let mut l2_block_numbers = Vec::with_capacity(checkpoints.len());
+        let mut balances = Vec::with_capacity(checkpoints.len());
+        for (address, checkpoint) in checkpoints {
+            addresses.push(address.as_bytes());
+            l2_block_numbers.push(i64::from(checkpoint.block_number.0));
+            balances.push(u256_to_big_decimal(checkpoint.balance));
+        }
+
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                account_balance_checkpoints (address, miniblock_number, balance)
+            SELECT
+                *
+            FROM
+                UNNEST($1::BYTEA[], $2::BIGINT[], $3::NUMERIC[])
+            ON CONFLICT (address, miniblock_number) DO
+            UPDATE
+            SET
+                balance = excluded.balance
+            "#,
+            &addresses as &[&[u8]],
+            &l2_block_numbers,
+            &balances
+        )
+        .instrument("insert_checkpoints#insert_checkpoints")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("checkpoints.len", &checkpoints.len())
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                account_balance_index (
+                    first_miniblock_number,
+                    last_miniblock_number,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, NOW(), NOW())
+            ON CONFLICT (singleton) DO
+            UPDATE
+            SET
+                last_miniblock_number = excluded.last_miniblock_number,
+                updated_at = NOW()
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("insert_checkpoints#update_index")
+        .with_arg("l2_blocks", &l2_blocks)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+
+    /// Returns balance checkpoints of the account in the specified range of L2 blocks ordered by the L2 block number.
+    /// At most `limit` checkpoints are returned.
+    pub async fn get_balance_history(
+        &mut self,
+        address: Address,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        limit: usize,
+    ) -> DalResult<Vec<BalanceCheckpoint>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                balance
+            FROM
+                account_balance_checkpoints
+            WHERE
+                address = $1
+                AND miniblock_number BETWEEN $2 AND $3
+            ORDER BY
+                miniblock_number
+            LIMIT
+                $4
+            "#,
+            address.as_bytes(),
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            limit as i64
+        )
+        .instrument("get_balance_history")
+        .with_arg("address", &address)
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| BalanceCheckpoint {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                balance: bigdecimal_to_u256(row.balance),
+            })
+            .collect())
+    }
+
+    /// Removes checkpoints for L2 blocks after `last_l2_block_to_keep` and shrinks the indexed range accordingly.
+    pub async fn roll_back_checkpoints(
+        &mut self,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            DELETE FROM account_balance_checkpoints
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("roll_back_checkpoints#delete_checkpoints")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM account_balance_index
+            WHERE
+                first_miniblock_number > $1
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("roll_back_checkpoints#delete_index")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE account_balance_index
+            SET
+                last_miniblock_number = $1,
+                updated_at = NOW()
+            WHERE
+                last_miniblock_number > $1
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("roll_back_checkpoints#update_index")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{AccountTreeId, ProtocolVersion, StorageKey, StorageLog, U256};
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, Core, CoreDal};
+
+    fn checkpoint(block_number: u32, balance: u64) -> BalanceCheckpoint {
+        BalanceCheckpoint {
+            block_number: L2BlockNumber(block_number),
+            balance: U256::from(balance),
+        }
+    }
+
+    #[tokio::test]
+    async fn inserting_and_querying_checkpoints() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.balance_history_dal();
+        assert_eq!(dal.get_indexed_range().await.unwrap(), None);
+
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+        let checkpoints = [
+            (address, checkpoint(1, 100)),
+            (other_address, checkpoint(2, 5)),
+            (address, checkpoint(3, 50)),
+        ];
+        dal.insert_checkpoints(L2BlockNumber(1)..=L2BlockNumber(3), &checkpoints)
+            .await
+            .unwrap();
+        dal.insert_checkpoints(
+            L2BlockNumber(4)..=L2BlockNumber(5),
+            &[(address, checkpoint(5, 0))],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            dal.get_indexed_range().await.unwrap(),
+            Some(L2BlockNumber(1)..=L2BlockNumber(5))
+        );
+
+        let history = dal
+            .get_balance_history(address, L2BlockNumber(0)..=L2BlockNumber(5), 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            history,
+            [checkpoint(1, 100), checkpoint(3, 50), checkpoint(5, 0)]
+        );
+        let history = dal
+            .get_balance_history(address, L2BlockNumber(2)..=L2BlockNumber(5), 10)
+            .await
+            .unwrap();
+        assert_eq!(history, [checkpoint(3, 50), checkpoint(5, 0)]);
+        let history = dal
+            .get_balance_history(address, L2BlockNumber(0)..=L2BlockNumber(5), 2)
+            .await
+            .unwrap();
+        assert_eq!(history, [checkpoint(1, 100), checkpoint(3, 50)]);
+        let history = dal
+            .get_balance_history(other_address, L2BlockNumber(3)..=L2BlockNumber(5), 10)
+            .await
+            .unwrap();
+        assert_eq!(history, []);
+    }
+
+    #[tokio::test]
+    async fn rolling_back_checkpoints() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let mut dal = conn.balance_history_dal();
+        let address = Address::repeat_byte(1);
+        dal.insert_checkpoints(
+            L2BlockNumber(3)..=L2BlockNumber(5),
+            &[(address, checkpoint(3, 1)), (address, checkpoint(5, 2))],
+        )
+        .await
+        .unwrap();
+
+        dal.roll_back_checkpoints(L2BlockNumber(4)).await.unwrap();
+        assert_eq!(
+            dal.get_indexed_range().await.unwrap(),
+            Some(L2BlockNumber(3)..=L2BlockNumber(4))
+        );
+        let history = dal
+            .get_balance_history(address, L2BlockNumber(0)..=L2BlockNumber(5), 10)
+            .await
+            .unwrap();
+        assert_eq!(history, [checkpoint(3, 1)]);
+
+        dal.roll_back_checkpoints(L2BlockNumber(2)).await.unwrap();
+        assert_eq!(dal.get_indexed_range().await.unwrap(), None);
+        let history = dal
+            .get_balance_history(address, L2BlockNumber(0)..=L2BlockNumber(5), 10)
+            .await
+            .unwrap();
+        assert_eq!(history, []);
+    }
+
+    #[tokio::test]
+    async fn getting_final_writes() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let key = StorageKey::new(account, H256::zero());
+        let other_key = StorageKey::new(account, H256::repeat_byte(1));
+        for number in [1, 2] {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+        let logs = [
+            (
+                H256::repeat_byte(1),
+                vec![
+                    StorageLog::new_write_log(key, H256::repeat_byte(1)),
+                    StorageLog::new_write_log(other_key, H256::repeat_byte(2)),
+                ],
+            ),
+            (
+                H256::repeat_byte(2),
+                vec![StorageLog::new_write_log(key, H256::repeat_byte(3))],
+            ),
+        ];
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await
+            .unwrap();
+        let logs = [(
+            H256::repeat_byte(3),
+            vec![StorageLog::new_write_log(other_key, H256::repeat_byte(4))],
+        )];
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(2), &logs)
+            .await
+            .unwrap();
+
+        let mut writes = conn
+            .balance_history_dal()
+            .get_final_writes(L2BlockNumber(1)..=L2BlockNumber(2), &[key.hashed_key()])
+            .await
+            .unwrap();
+        assert_eq!(
+            writes,
+            [(L2BlockNumber(1), key.hashed_key(), H256::repeat_byte(3))]
+        );
+
+        writes = conn
+            .balance_history_dal()
+            .get_final_writes(
+                L2BlockNumber(1)..=L2BlockNumber(2),
+                &[key.hashed_key(), other_key.hashed_key()],
+            )
+            .await
+            .unwrap();
+        writes.sort_unstable();
+        let mut expected = vec![
+            (L2BlockNumber(1), key.hashed_key(), H256::repeat_byte(3)),
+            (
+                L2BlockNumber(1),
+                other_key.hashed_key(),
+                H256::repeat_byte(2),
+            ),
+            (
+                L2BlockNumber(2),
+                other_key.hashed_key(),
+                H256::repeat_byte(4),
+            ),
+        ];
+        expected.sort_unstable();
+        assert_eq!(writes, expected);
+    }
+}
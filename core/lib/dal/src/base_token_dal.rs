@@ -0,0 +1,114 @@
+//! Storage for ETH <-> base token conversion ratios persisted by the base token adjuster.
+
+use std::num::NonZeroU64;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use sqlx::types::chrono::{DateTime, Utc};
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalResult, SqlxContext},
+    instrument::InstrumentExt,
+};
+use zksync_types::api::BaseTokenConversionRatio;
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct BaseTokenDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl BaseTokenDal<'_, '_> {
+    /// Persists a new conversion ratio. `ratio_timestamp` is the time the ratio was fetched at
+    /// from the price source.
+    pub async fn insert_token_ratio(&mut self, ratio: &BaseTokenConversionRatio) -> DalResult<u32> {
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO
+                base_token_ratios (numerator, denominator, ratio_timestamp, created_at, updated_at)
+            VALUES
+                ($1, $2, $3, NOW(), NOW())
+            RETURNING
+                id
+            "#,
+            BigDecimal::from(ratio.numerator.get()),
+            BigDecimal::from(ratio.denominator.get()),
+            ratio.ratio_timestamp.naive_utc()
+        )
+        .instrument("insert_token_ratio")
+        .with_arg("ratio", ratio)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(row.id as u32)
+    }
+
+    /// Returns the most recently persisted conversion ratio.
+    pub async fn get_latest_ratio(&mut self) -> DalResult<Option<BaseTokenConversionRatio>> {
+        sqlx::query!(
+            r#"
+            SELECT
+                numerator,
+                denominator,
+                ratio_timestamp
+            FROM
+                base_token_ratios
+            ORDER BY
+                id DESC
+            LIMIT
+                1
+            "#
+        )
+        .try_map(|row| {
+            Ok(BaseTokenConversionRatio {
+                numerator: Self::parse_ratio_part(&row.numerator).decode_column("numerator")?,
+                denominator: Self::parse_ratio_part(&row.denominator)
+                    .decode_column("denominator")?,
+                ratio_timestamp: DateTime::<Utc>::from_naive_utc_and_offset(
+                    row.ratio_timestamp,
+                    Utc,
+                ),
+            })
+        })
+        .instrument("get_latest_ratio")
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    fn parse_ratio_part(value: &BigDecimal) -> Result<NonZeroU64, &'static str> {
+        value
+            .to_u64()
+            .and_then(NonZeroU64::new)
+            .ok_or("value is not a positive u64")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn inserting_and_getting_ratios() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let latest_ratio = conn.base_token_dal().get_latest_ratio().await.unwrap();
+        assert_eq!(latest_ratio, None);
+
+        let timestamp = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let ratios =
+            [(1, 2), (u64::MAX, 3)].map(|(numerator, denominator)| BaseTokenConversionRatio {
+                numerator: NonZeroU64::new(numerator).unwrap(),
+                denominator: NonZeroU64::new(denominator).unwrap(),
+                ratio_timestamp: timestamp,
+            });
+        for ratio in &ratios {
+            conn.base_token_dal()
+                .insert_token_ratio(ratio)
+                .await
+                .unwrap();
+        }
+
+        let latest_ratio = conn.base_token_dal().get_latest_ratio().await.unwrap();
+        assert_eq!(latest_ratio, Some(ratios[1].clone()));
+    }
+}
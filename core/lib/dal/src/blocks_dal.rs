@@ -32,6 +32,10 @@ use crate::{
     Core, CoreDal,
 };
 
+/// Postgres channel notified with the L2 block number each time an L2 block header is inserted.
+/// Notifications are delivered once the inserting transaction is committed.
+pub const NEW_L2_BLOCKS_CHANNEL: &str = "new_miniblocks";
+
 #[derive(Debug)]
 pub struct BlocksDal<'a, 'c> {
     pub(crate) storage: &'a mut Connection<'c, Core>,
@@ -691,6 +695,7 @@ impl BlocksDal<'_, '_> {
                     virtual_blocks,
                     fair_pubdata_price,
                     gas_limit,
+                    logs_bloom,
                     created_at,
                     updated_at
                 )
@@ -712,6 +717,7 @@ impl BlocksDal<'_, '_> {
                     $14,
                     $15,
                     $16,
+                    $17,
                     NOW(),
                     NOW()
                 )
@@ -738,6 +744,7 @@ impl BlocksDal<'_, '_> {
             i64::from(l2_block_header.virtual_blocks),
             l2_block_header.batch_fee_input.fair_pubdata_price() as i64,
             l2_block_header.gas_limit as i64,
+            l2_block_header.logs_bloom.as_bytes(),
         );
 
         instrumentation.with(query).execute(self.storage).await?;
@@ -764,7 +771,8 @@ impl BlocksDal<'_, '_> {
                 protocol_version,
                 virtual_blocks,
                 fair_pubdata_price,
-                gas_limit
+                gas_limit,
+                logs_bloom
             FROM
                 miniblocks
             ORDER BY
@@ -803,7 +811,8 @@ impl BlocksDal<'_, '_> {
                 protocol_version,
                 virtual_blocks,
                 fair_pubdata_price,
-                gas_limit
+                gas_limit,
+                logs_bloom
             FROM
                 miniblocks
             WHERE
@@ -2302,6 +2311,56 @@ impl BlocksDal<'_, '_> {
         Ok(())
     }
 
+    /// Sets the name of the seal criterion that has triggered sealing the specified L1 batch.
+    pub async fn set_l1_batch_seal_criterion(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        criterion: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE l1_batches
+            SET
+                seal_criterion = $1
+            WHERE
+                number = $2
+            "#,
+            criterion,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("set_l1_batch_seal_criterion")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("criterion", &criterion)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the name of the seal criterion that has triggered sealing the specified L1 batch.
+    /// Returns `None` if the batch is not sealed, or if it was sealed because of a non-deterministic criterion
+    /// (e.g., a timeout).
+    pub async fn get_l1_batch_seal_criterion(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<String>> {
+        Ok(sqlx::query!(
+            r#"
+            SELECT
+                seal_criterion
+            FROM
+                l1_batches
+            WHERE
+                number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l1_batch_seal_criterion")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?
+        .and_then(|row| row.seal_criterion))
+    }
+
     pub async fn insert_mock_l1_batch(&mut self, header: &L1BatchHeader) -> DalResult<()> {
         self.insert_l1_batch(
             header,
@@ -2366,7 +2425,7 @@ mod tests {
     };
 
     use super::*;
-    use crate::{ConnectionPool, Core, CoreDal};
+    use crate::{tests::create_l2_block_header, ConnectionPool, Core, CoreDal};
 
     async fn save_mock_eth_tx(action_type: AggregatedActionType, conn: &mut Connection<'_, Core>) {
         conn.eth_sender_dal()
@@ -2401,6 +2460,38 @@ mod tests {
         header
     }
 
+    #[tokio::test]
+    async fn setting_l1_batch_seal_criterion() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&mock_l1_batch_header())
+            .await
+            .unwrap();
+
+        let criterion = conn
+            .blocks_dal()
+            .get_l1_batch_seal_criterion(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(criterion, None);
+
+        conn.blocks_dal()
+            .set_l1_batch_seal_criterion(L1BatchNumber(1), "slots")
+            .await
+            .unwrap();
+        let criterion = conn
+            .blocks_dal()
+            .get_l1_batch_seal_criterion(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(criterion.as_deref(), Some("slots"));
+    }
+
     #[tokio::test]
     async fn set_tx_id_works_correctly() {
         let pool = ConnectionPool::<Core>::test_pool().await;
@@ -2578,4 +2669,37 @@ mod tests {
             assert_eq!(gas, 3 * expected_gas);
         }
     }
+
+    #[tokio::test]
+    async fn inserting_l2_block_notifies_listeners() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut listener = pool.listener(&[NEW_L2_BLOCKS_CHANNEL]).await.unwrap();
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+        let notification = listener.recv().await.unwrap();
+        assert_eq!(notification.channel(), NEW_L2_BLOCKS_CHANNEL);
+        assert_eq!(notification.payload(), "1");
+
+        // Notifications must only be delivered after the transaction is committed.
+        let mut transaction = conn.start_transaction().await.unwrap();
+        transaction
+            .blocks_dal()
+            .insert_l2_block(&create_l2_block_header(2))
+            .await
+            .unwrap();
+        let recv_result =
+            tokio::time::timeout(std::time::Duration::from_millis(100), listener.recv()).await;
+        assert!(recv_result.is_err(), "{recv_result:?}");
+        transaction.commit().await.unwrap();
+        let notification = listener.recv().await.unwrap();
+        assert_eq!(notification.payload(), "2");
+    }
 }
@@ -1,3 +1,5 @@
+use std::ops;
+
 use zksync_db_connection::{
     connection::Connection, error::DalResult, instrument::InstrumentExt, interpolate_query,
     match_query_as,
@@ -513,10 +515,13 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(result)
     }
 
-    /// Returns call traces for all transactions in the specified L2 block in the order of their execution.
+    /// Returns call traces for transactions in the specified L2 block in the order of their execution.
+    /// Traces are paginated: up to `limit` traces are returned, skipping the first `offset` traces.
     pub async fn get_traces_for_l2_block(
         &mut self,
         block_number: L2BlockNumber,
+        offset: usize,
+        limit: usize,
     ) -> DalResult<Vec<Call>> {
         let protocol_version = sqlx::query!(
             r#"
@@ -552,11 +557,19 @@ impl BlocksWeb3Dal<'_, '_> {
                 transactions.miniblock_number = $1
             ORDER BY
                 transactions.index_in_block
+            OFFSET
+                $2
+            LIMIT
+                $3
             "#,
-            i64::from(block_number.0)
+            i64::from(block_number.0),
+            i64::try_from(offset).unwrap_or(i64::MAX),
+            i64::try_from(limit).unwrap_or(i64::MAX)
         )
         .instrument("get_traces_for_l2_block")
         .with_arg("block_number", &block_number)
+        .with_arg("offset", &offset)
+        .with_arg("limit", &limit)
         .fetch_all(self.storage)
         .await?
         .into_iter()
@@ -564,6 +577,58 @@ impl BlocksWeb3Dal<'_, '_> {
         .collect())
     }
 
+    /// Returns call traces for all transactions in the specified L2 block range together with transaction locations,
+    /// ordered by L2 block number and the transaction index in the block.
+    pub async fn get_traces_for_l2_blocks(
+        &mut self,
+        l2_block_range: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(api::trace::TransactionLocation, Call)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                call_traces.call_trace,
+                call_traces.tx_hash,
+                transactions.index_in_block AS "index_in_block!",
+                transactions.miniblock_number AS "miniblock_number!",
+                miniblocks.hash AS block_hash,
+                miniblocks.protocol_version
+            FROM
+                call_traces
+                INNER JOIN transactions ON tx_hash = transactions.hash
+                INNER JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            WHERE
+                transactions.miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                transactions.miniblock_number,
+                transactions.index_in_block
+            "#,
+            i64::from(l2_block_range.start().0),
+            i64::from(l2_block_range.end().0)
+        )
+        .try_map(|row| {
+            let protocol_version = row
+                .protocol_version
+                .map(parse_protocol_version)
+                .transpose()?
+                .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+            let location = api::trace::TransactionLocation {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                block_hash: H256::from_slice(&row.block_hash),
+                transaction_hash: H256::from_slice(&row.tx_hash),
+                transaction_position: row.index_in_block as usize,
+            };
+            let call_trace = CallTrace {
+                call_trace: row.call_trace,
+            };
+            Ok((location, call_trace.into_call(protocol_version)))
+        })
+        .instrument("get_traces_for_l2_blocks")
+        .with_arg("l2_block_range", &l2_block_range)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(rows)
+    }
+
     /// Returns `base_fee_per_gas` for L2 block range [min(newest_block - block_count + 1, 0), newest_block]
     /// in descending order of L2 block numbers.
     pub async fn get_fee_history(
@@ -599,6 +664,60 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(result)
     }
 
+    /// Returns effective priority fees per gas and gas used for L2 transactions in the specified L2 block range
+    /// as `(l2_block_number, priority_fee_per_gas, gas_used)` tuples, ordered by L2 block number and the transaction
+    /// index in the block. Priority (L1) transactions are not included.
+    pub async fn get_fee_history_priority_fees(
+        &mut self,
+        l2_block_range: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(L2BlockNumber, U256, u64)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                transactions.miniblock_number AS "miniblock_number!",
+                transactions.effective_gas_price,
+                transactions.gas_limit,
+                transactions.refunded_gas,
+                miniblocks.base_fee_per_gas
+            FROM
+                transactions
+                INNER JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+            WHERE
+                transactions.miniblock_number BETWEEN $1 AND $2
+                AND transactions.is_priority = FALSE
+            ORDER BY
+                transactions.miniblock_number,
+                transactions.index_in_block
+            "#,
+            i64::from(l2_block_range.start().0),
+            i64::from(l2_block_range.end().0)
+        )
+        .instrument("get_fee_history_priority_fees")
+        .with_arg("l2_block_range", &l2_block_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let base_fee_per_gas = bigdecimal_to_u256(row.base_fee_per_gas);
+                let effective_gas_price = row
+                    .effective_gas_price
+                    .map_or(base_fee_per_gas, bigdecimal_to_u256);
+                // Transaction gas limits are validated to fit into `u64` when transactions are accepted.
+                let gas_limit = row
+                    .gas_limit
+                    .map_or(0, |limit| bigdecimal_to_u256(limit).low_u64());
+                let gas_used = gas_limit.saturating_sub(row.refunded_gas as u64);
+                (
+                    L2BlockNumber(row.miniblock_number as u32),
+                    effective_gas_price.saturating_sub(base_fee_per_gas),
+                    gas_used,
+                )
+            })
+            .collect())
+    }
+
     pub async fn get_block_details(
         &mut self,
         block_number: L2BlockNumber,
@@ -971,7 +1090,7 @@ mod tests {
 
         let traces = conn
             .blocks_web3_dal()
-            .get_traces_for_l2_block(L2BlockNumber(1))
+            .get_traces_for_l2_block(L2BlockNumber(1), 0, usize::MAX)
             .await
             .unwrap();
         assert_eq!(traces.len(), 2);
@@ -979,5 +1098,100 @@ mod tests {
             let expected_trace = tx_result.call_trace().unwrap();
             assert_eq!(*trace, expected_trace);
         }
+
+        let second_page = conn
+            .blocks_web3_dal()
+            .get_traces_for_l2_block(L2BlockNumber(1), 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(second_page, [traces[1].clone()]);
+
+        let block_hash = conn
+            .blocks_web3_dal()
+            .get_l2_block_hash(L2BlockNumber(1))
+            .await
+            .unwrap()
+            .unwrap();
+        let located_traces = conn
+            .blocks_web3_dal()
+            .get_traces_for_l2_blocks(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(located_traces.len(), 2);
+        for (i, ((location, trace), tx_result)) in
+            located_traces.iter().zip(&tx_results).enumerate()
+        {
+            assert_eq!(location.block_number, L2BlockNumber(1));
+            assert_eq!(location.block_hash, block_hash);
+            assert_eq!(location.transaction_hash, tx_result.hash);
+            assert_eq!(location.transaction_position, i);
+            assert_eq!(*trace, tx_result.call_trace().unwrap());
+        }
+
+        let located_traces = conn
+            .blocks_web3_dal()
+            .get_traces_for_l2_blocks(L2BlockNumber(2)..=L2BlockNumber(3))
+            .await
+            .unwrap();
+        assert!(located_traces.is_empty());
+    }
+
+    #[tokio::test]
+    async fn getting_fee_history_priority_fees() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+
+        let transactions = [mock_l2_transaction(), mock_l2_transaction()];
+        let mut tx_results = vec![];
+        for (i, tx) in transactions.into_iter().enumerate() {
+            conn.transactions_dal()
+                .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+            let mut tx_result = mock_execution_result(tx);
+            tx_result.refunded_gas = 100_000 * i as u64;
+            tx_results.push(tx_result);
+        }
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_l2_block(
+                L2BlockNumber(1),
+                &tx_results,
+                100.into(),
+                ProtocolVersionId::latest(),
+                false,
+            )
+            .await
+            .unwrap();
+
+        let fees = conn
+            .blocks_web3_dal()
+            .get_fee_history_priority_fees(L2BlockNumber(1)..=L2BlockNumber(2))
+            .await
+            .unwrap();
+        // Transactions are charged only for the base fee, so effective priority fees are zero.
+        assert_eq!(
+            fees,
+            [
+                (L2BlockNumber(1), U256::zero(), 1_000_000),
+                (L2BlockNumber(1), U256::zero(), 900_000),
+            ]
+        );
+
+        let fees = conn
+            .blocks_web3_dal()
+            .get_fee_history_priority_fees(L2BlockNumber(2)..=L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert!(fees.is_empty());
     }
 }
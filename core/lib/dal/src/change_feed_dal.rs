@@ -0,0 +1,242 @@
+//! Access to Postgres logical decoding used by the change-data feed.
+
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+
+use crate::Core;
+
+/// Output plugin used to decode changes. `pgoutput` is shipped with Postgres and only outputs changes
+/// in the tables included into the specified publications.
+const OUTPUT_PLUGIN: &str = "pgoutput";
+/// Version of the `pgoutput` logical replication protocol.
+const PROTOCOL_VERSION: &str = "1";
+/// Tables and columns included into the publication. Columns must include the primary key, since it's
+/// the replica identity for published updates. Column lists require Postgres 15+.
+const PUBLISHED_TABLES: &str = "transactions (hash, initiator_address, nonce, miniblock_number, \
+    l1_batch_number, error), miniblocks (number, hash), l1_batches (number, timestamp)";
+
+/// Change read from a logical replication slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawChange {
+    /// Log sequence number (LSN) of the change. For transaction commits, this is the end of the commit record.
+    pub lsn: u64,
+    /// Binary message produced by the `pgoutput` output plugin (e.g., a relation description, an inserted row,
+    /// or a transaction commit).
+    pub data: Vec<u8>,
+}
+
+/// Formats an LSN in the textual Postgres representation (e.g., `16/B374D848`).
+fn format_lsn(lsn: u64) -> String {
+    format!("{:X}/{:X}", lsn >> 32, lsn & u64::from(u32::MAX))
+}
+
+#[derive(Debug)]
+pub struct ChangeFeedDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl ChangeFeedDal<'_, '_> {
+    /// Returns the `wal_level` Postgres setting. Logical decoding requires it to be `logical`.
+    pub async fn get_wal_level(&mut self) -> DalResult<String> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                CURRENT_SETTING('wal_level') AS "wal_level!"
+            "#
+        )
+        .instrument("get_wal_level")
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.wal_level)
+    }
+
+    /// Creates a publication with the specified name for the tables used by the change feed
+    /// unless it already exists. Returns `true` if the publication was created. Only inserts and updates
+    /// are published.
+    ///
+    /// The publication must be created before the replication slot; changes preceding the publication
+    /// cannot be decoded.
+    pub async fn create_publication_if_missing(
+        &mut self,
+        publication_name: &str,
+    ) -> DalResult<bool> {
+        let instrumentation = Instrumented::new("create_publication_if_missing#create")
+            .with_arg("publication_name", &publication_name);
+        // The name is used as an identifier, so it cannot be passed as a query parameter.
+        let is_valid_name = !publication_name.is_empty()
+            && publication_name
+                .bytes()
+                .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == b'_');
+        if !is_valid_name {
+            let err = anyhow::anyhow!("name may only contain lowercase letters, digits and `_`");
+            return Err(instrumentation.arg_error("publication_name", err));
+        }
+
+        let exists = sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        pg_publication
+                    WHERE
+                        pubname = $1::TEXT
+                ) AS "exists!"
+            "#,
+            publication_name
+        )
+        .instrument("create_publication_if_missing#exists")
+        .with_arg("publication_name", &publication_name)
+        .fetch_one(self.storage)
+        .await?
+        .exists;
+        if exists {
+            return Ok(false);
+        }
+
+        let query = format!(
+            "CREATE PUBLICATION {publication_name} FOR TABLE {PUBLISHED_TABLES} \
+             WITH (publish = 'insert, update')"
+        );
+        instrumentation
+            .with(sqlx::query(&query))
+            .execute(self.storage)
+            .await?;
+        Ok(true)
+    }
+
+    /// Creates a logical replication slot with the specified name unless it already exists.
+    /// Returns `true` if the slot was created. Errors if the existing slot uses an unexpected output plugin.
+    ///
+    /// Postgres retains WAL until it's consumed from the slot, so a slot that isn't consumed should be dropped.
+    pub async fn create_replication_slot_if_missing(&mut self, slot_name: &str) -> DalResult<bool> {
+        let instrumentation = Instrumented::new("create_replication_slot_if_missing#plugin")
+            .with_arg("slot_name", &slot_name);
+        let query = sqlx::query!(
+            r#"
+            SELECT
+                plugin::TEXT AS "plugin!"
+            FROM
+                pg_replication_slots
+            WHERE
+                slot_name = $1::TEXT
+            "#,
+            slot_name
+        );
+        let row = instrumentation
+            .clone()
+            .with(query)
+            .fetch_optional(self.storage)
+            .await?;
+        if let Some(row) = row {
+            if row.plugin != OUTPUT_PLUGIN {
+                let err = anyhow::anyhow!(
+                    "replication slot `{slot_name}` uses output plugin `{}` instead of `{OUTPUT_PLUGIN}`; \
+                     the slot should be dropped",
+                    row.plugin
+                );
+                return Err(instrumentation.constraint_error(err));
+            }
+            return Ok(false);
+        }
+
+        sqlx::query!(
+            r#"
+            SELECT
+                slot_name::TEXT AS "slot_name!"
+            FROM
+                PG_CREATE_LOGICAL_REPLICATION_SLOT($1::TEXT, $2::TEXT)
+            "#,
+            slot_name,
+            OUTPUT_PLUGIN
+        )
+        .instrument("create_replication_slot_if_missing#create")
+        .with_arg("slot_name", &slot_name)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(true)
+    }
+
+    /// Streams changes in the tables included into the specified publication from the specified slot without
+    /// consuming them. Changes are returned for whole transactions (i.e., Postgres stops decoding after
+    /// the transaction during which `limit` is reached), so more than `limit` changes may be returned;
+    /// hence, changes should be processed as they are streamed. Use [`Self::advance_replication_slot()`]
+    /// to consume changes once they are processed.
+    pub fn stream_changes<'s>(
+        &'s mut self,
+        slot_name: &'s str,
+        publication_name: &'s str,
+        limit: usize,
+    ) -> BoxStream<'s, DalResult<RawChange>> {
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        sqlx::query!(
+            r#"
+            SELECT
+                (lsn - '0/0'::PG_LSN)::BIGINT AS "lsn!",
+                data AS "data!"
+            FROM
+                PG_LOGICAL_SLOT_PEEK_BINARY_CHANGES(
+                    $1::TEXT,
+                    NULL,
+                    $2::INTEGER,
+                    'proto_version',
+                    $3::TEXT,
+                    'publication_names',
+                    $4::TEXT
+                )
+            "#,
+            slot_name,
+            limit,
+            PROTOCOL_VERSION,
+            publication_name
+        )
+        .instrument("stream_changes")
+        .with_arg("slot_name", &slot_name)
+        .with_arg("publication_name", &publication_name)
+        .with_arg("limit", &limit)
+        .fetch(self.storage)
+        .map_ok(|row| RawChange {
+            lsn: row.lsn as u64,
+            data: row.data,
+        })
+        .boxed()
+    }
+
+    /// Consumes changes from the specified slot up to and including the specified LSN.
+    pub async fn advance_replication_slot(&mut self, slot_name: &str, lsn: u64) -> DalResult<()> {
+        let lsn = format_lsn(lsn);
+        sqlx::query!(
+            r#"
+            SELECT
+                slot_name::TEXT AS "slot_name!"
+            FROM
+                PG_REPLICATION_SLOT_ADVANCE($1::TEXT, $2::TEXT::PG_LSN)
+            "#,
+            slot_name,
+            &lsn
+        )
+        .instrument("advance_replication_slot")
+        .with_arg("slot_name", &slot_name)
+        .with_arg("lsn", &lsn)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting_lsn() {
+        assert_eq!(format_lsn(0), "0/0");
+        assert_eq!(format_lsn(0x16_B374_D848), "16/B374D848");
+    }
+}
@@ -0,0 +1,386 @@
+//! Storage for references to L1 batch pubdata dispatched to data availability (DA) layers.
+
+use sqlx::types::chrono::{DateTime, Utc};
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    api,
+    pubdata_da::{DataAvailabilityBlob, L1BatchDA},
+    L1BatchNumber,
+};
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct DataAvailabilityDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl DataAvailabilityDal<'_, '_> {
+    /// Records that pubdata for the specified L1 batch was dispatched to a DA layer. `blob_id` is an opaque
+    /// identifier of the pubdata on the DA layer (e.g., a blob hash); its format depends on `client_type`.
+    pub async fn insert_l1_batch_da(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        client_type: &str,
+        blob_id: &str,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                data_availability (
+                    l1_batch_number,
+                    client_type,
+                    blob_id,
+                    sent_at,
+                    created_at,
+                    updated_at
+                )
+            VALUES
+                ($1, $2, $3, NOW(), NOW(), NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+            client_type,
+            blob_id
+        )
+        .instrument("insert_l1_batch_da")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .with_arg("client_type", &client_type)
+        .with_arg("blob_id", &blob_id)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Saves inclusion data (e.g., an inclusion proof) for the pubdata of the specified L1 batch received
+    /// from the DA layer. Does nothing if the batch pubdata wasn't dispatched.
+    pub async fn save_l1_batch_inclusion_data(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        inclusion_data: &[u8],
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE data_availability
+            SET
+                inclusion_data = $2,
+                updated_at = NOW()
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0),
+            inclusion_data
+        )
+        .instrument("save_l1_batch_inclusion_data")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the record of dispatching pubdata for the specified L1 batch, so that the pubdata is dispatched again.
+    /// Does nothing if inclusion data is already saved for the batch. Returns whether the record was removed.
+    pub async fn remove_l1_batch_da(&mut self, l1_batch_number: L1BatchNumber) -> DalResult<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM data_availability
+            WHERE
+                l1_batch_number = $1
+                AND inclusion_data IS NULL
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("remove_l1_batch_da")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Returns inclusion data for the pubdata of the specified L1 batch, or `None` if the batch pubdata wasn't dispatched
+    /// or no inclusion data was saved for it yet.
+    pub async fn get_l1_batch_inclusion_data(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<Vec<u8>>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                inclusion_data
+            FROM
+                data_availability
+            WHERE
+                l1_batch_number = $1
+                AND inclusion_data IS NOT NULL
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l1_batch_inclusion_data")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.and_then(|row| row.inclusion_data))
+    }
+
+    /// Returns DA information for the specified L1 batch, or `None` if the batch pubdata wasn't dispatched.
+    pub async fn get_l1_batch_da_details(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Option<api::L1BatchDaDetails>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                client_type,
+                blob_id,
+                inclusion_data IS NOT NULL AS "has_inclusion_data!",
+                sent_at
+            FROM
+                data_availability
+            WHERE
+                l1_batch_number = $1
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l1_batch_da_details")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| api::L1BatchDaDetails {
+            client_type: row.client_type,
+            blob_id: row.blob_id,
+            inclusion_data_available: row.has_inclusion_data,
+            sent_at: DateTime::<Utc>::from_naive_utc_and_offset(row.sent_at, Utc),
+        }))
+    }
+
+    /// Returns pubdata for up to `limit` L1 batches that weren't dispatched to a DA layer yet, ordered by batch number.
+    /// Only batches that aren't committed to L1 and have pubdata input persisted are returned.
+    pub async fn get_ready_for_da_dispatch_l1_batches(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<L1BatchDA>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                pubdata_input AS "pubdata_input!"
+            FROM
+                l1_batches
+                LEFT JOIN data_availability ON data_availability.l1_batch_number = l1_batches.number
+            WHERE
+                eth_commit_tx_id IS NULL
+                AND number != 0
+                AND data_availability.blob_id IS NULL
+                AND pubdata_input IS NOT NULL
+            ORDER BY
+                number
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_ready_for_da_dispatch_l1_batches")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchDA {
+                l1_batch_number: L1BatchNumber(row.number as u32),
+                pubdata: row.pubdata_input,
+            })
+            .collect())
+    }
+
+    /// Returns the earliest dispatched blob for which no inclusion data is saved yet.
+    pub async fn get_first_da_blob_awaiting_inclusion(
+        &mut self,
+    ) -> DalResult<Option<DataAvailabilityBlob>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_batch_number,
+                blob_id,
+                sent_at
+            FROM
+                data_availability
+            WHERE
+                inclusion_data IS NULL
+            ORDER BY
+                l1_batch_number
+            LIMIT
+                1
+            "#
+        )
+        .instrument("get_first_da_blob_awaiting_inclusion")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| DataAvailabilityBlob {
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            blob_id: row.blob_id,
+            sent_at: DateTime::<Utc>::from_naive_utc_and_offset(row.sent_at, Utc),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{block::L1BatchHeader, ProtocolVersion, ProtocolVersionId};
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn data_availability_basics() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            100,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.pubdata_input = Some(vec![1, 2, 3]);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+
+        let details = conn
+            .data_availability_dal()
+            .get_l1_batch_da_details(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(details, None);
+        let ready_batches = conn
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(10)
+            .await
+            .unwrap();
+        assert_eq!(
+            ready_batches,
+            [L1BatchDA {
+                l1_batch_number: L1BatchNumber(1),
+                pubdata: vec![1, 2, 3],
+            }]
+        );
+
+        conn.data_availability_dal()
+            .insert_l1_batch_da(L1BatchNumber(1), "Avail", "0x0102")
+            .await
+            .unwrap();
+        let ready_batches = conn
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(10)
+            .await
+            .unwrap();
+        assert!(ready_batches.is_empty());
+        let blob = conn
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap()
+            .expect("no blob awaiting inclusion");
+        assert_eq!(blob.l1_batch_number, L1BatchNumber(1));
+        assert_eq!(blob.blob_id, "0x0102");
+        let details = conn
+            .data_availability_dal()
+            .get_l1_batch_da_details(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no DA details");
+        assert_eq!(details.client_type, "Avail");
+        assert_eq!(details.blob_id, "0x0102");
+        assert!(!details.inclusion_data_available);
+        let inclusion_data = conn
+            .data_availability_dal()
+            .get_l1_batch_inclusion_data(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(inclusion_data, None);
+
+        conn.data_availability_dal()
+            .save_l1_batch_inclusion_data(L1BatchNumber(1), b"proof")
+            .await
+            .unwrap();
+        let details = conn
+            .data_availability_dal()
+            .get_l1_batch_da_details(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("no DA details");
+        assert!(details.inclusion_data_available);
+        let inclusion_data = conn
+            .data_availability_dal()
+            .get_l1_batch_inclusion_data(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(inclusion_data.as_deref(), Some(b"proof".as_slice()));
+        let blob = conn
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap();
+        assert_eq!(blob, None);
+        // Blobs with saved inclusion data cannot be removed.
+        let removed = conn
+            .data_availability_dal()
+            .remove_l1_batch_da(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(!removed);
+    }
+
+    #[tokio::test]
+    async fn removing_dispatched_blob() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            100,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.pubdata_input = Some(vec![1, 2, 3]);
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+        conn.data_availability_dal()
+            .insert_l1_batch_da(L1BatchNumber(1), "EigenDA", "0102")
+            .await
+            .unwrap();
+
+        let removed = conn
+            .data_availability_dal()
+            .remove_l1_batch_da(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert!(removed);
+        let blob = conn
+            .data_availability_dal()
+            .get_first_da_blob_awaiting_inclusion()
+            .await
+            .unwrap();
+        assert_eq!(blob, None);
+        let ready_batches = conn
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(10)
+            .await
+            .unwrap();
+        assert_eq!(ready_batches.len(), 1);
+        assert_eq!(ready_batches[0].l1_batch_number, L1BatchNumber(1));
+    }
+}
@@ -5,9 +5,13 @@ use sqlx::types::chrono::{DateTime, Utc};
 use zksync_db_connection::{connection::Connection, interpolate_query, match_query_as};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, EthTxBlobSidecar, TxHistory, TxHistoryToSend},
-    Address, L1BatchNumber, H256, U256,
+    eth_sender::{
+        EthTx, EthTxBlobSidecar, EthTxDryRun, EthTxL1Cost, L1BatchL1Cost, TxHistory,
+        TxHistoryToSend,
+    },
+    Address, L1BatchNumber, L1BlockNumber, Nonce, H256, U256,
 };
+use zksync_utils::{bigdecimal_to_u256, u256_to_big_decimal};
 
 use crate::{
     models::storage_eth_tx::{
@@ -229,6 +233,7 @@ impl EthSenderDal<'_, '_> {
         blob_base_fee_per_gas: Option<u64>,
         tx_hash: H256,
         raw_signed_tx: &[u8],
+        fee_bump_policy: Option<&serde_json::Value>,
     ) -> anyhow::Result<Option<u32>> {
         let priority_fee_per_gas =
             i64::try_from(priority_fee_per_gas).context("Can't convert u64 to i64")?;
@@ -247,10 +252,11 @@ impl EthSenderDal<'_, '_> {
                     signed_raw_tx,
                     created_at,
                     updated_at,
-                    blob_base_fee_per_gas
+                    blob_base_fee_per_gas,
+                    fee_bump_policy
                 )
             VALUES
-                ($1, $2, $3, $4, $5, NOW(), NOW(), $6)
+                ($1, $2, $3, $4, $5, NOW(), NOW(), $6, $7)
             ON CONFLICT (tx_hash) DO NOTHING
             RETURNING
                 id
@@ -261,6 +267,7 @@ impl EthSenderDal<'_, '_> {
             tx_hash,
             raw_signed_tx,
             blob_base_fee_per_gas.map(|v| v as i64),
+            fee_bump_policy,
         )
         .fetch_optional(self.storage.conn())
         .await?
@@ -533,6 +540,31 @@ impl EthSenderDal<'_, '_> {
         Ok(history_item.map(|tx| tx.into()))
     }
 
+    /// Returns all sending attempts for the specified transaction, from the earliest to the latest one.
+    /// Besides fees, each attempt records parameters of the fee bump policy used to calculate them.
+    pub async fn get_tx_history(&mut self, eth_tx_id: u32) -> sqlx::Result<Vec<TxHistory>> {
+        let history = sqlx::query_as!(
+            StorageTxHistory,
+            r#"
+            SELECT
+                eth_txs_history.*,
+                eth_txs.blob_sidecar
+            FROM
+                eth_txs_history
+                LEFT JOIN eth_txs ON eth_tx_id = eth_txs.id
+            WHERE
+                eth_tx_id = $1
+            ORDER BY
+                eth_txs_history.created_at,
+                eth_txs_history.id
+            "#,
+            eth_tx_id as i32
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(history.into_iter().map(|tx| tx.into()).collect())
+    }
+
     /// Returns the next nonce for the operator account
     ///
     /// # Params
@@ -568,6 +600,371 @@ impl EthSenderDal<'_, '_> {
         Ok(nonce.map(|n| n + 1))
     }
 
+    /// Same as [`Self::get_next_nonce()`], but only considers transactions with ID greater than `eth_tx_id`.
+    /// Used after a settlement layer switch, since nonces of transactions settled on the previous layer
+    /// are unrelated to nonces on the new one.
+    pub async fn get_next_nonce_after_eth_tx(
+        &mut self,
+        from_address: Option<Address>,
+        eth_tx_id: u32,
+    ) -> sqlx::Result<Option<u64>> {
+        struct NonceRow {
+            nonce: i64,
+        }
+
+        let query = match_query_as!(
+            NonceRow,
+            [
+                "SELECT nonce FROM eth_txs WHERE ",
+                _, // WHERE condition
+                " ORDER BY id DESC LIMIT 1"
+            ],
+            match (from_address) {
+                Some(address) => ("from_addr = $1::bytea AND id > $2"; address.as_bytes(), eth_tx_id as i32),
+                None => ("from_addr IS NULL AND id > $1"; eth_tx_id as i32),
+            }
+        );
+
+        let nonce = query
+            .fetch_optional(self.storage.conn())
+            .await?
+            .map(|row| row.nonce as u64);
+        Ok(nonce.map(|n| n + 1))
+    }
+
+    /// Checks whether there are transactions (sent by any operator) that are not confirmed yet.
+    pub async fn has_unconfirmed_txs(&mut self) -> sqlx::Result<bool> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        eth_txs
+                    WHERE
+                        confirmed_eth_tx_history_id IS NULL
+                ) AS "exists!"
+            "#
+        )
+        .fetch_one(self.storage.conn())
+        .await?;
+        Ok(row.exists)
+    }
+
+    /// Returns all transactions sent by the specified operator that are not confirmed yet (regardless of whether
+    /// they were sent to L1), ordered by nonce. `from_address` has the same meaning as in [`Self::get_next_nonce()`].
+    pub async fn get_unconfirmed_txs(
+        &mut self,
+        from_address: Option<Address>,
+    ) -> sqlx::Result<Vec<EthTx>> {
+        let query = match_query_as!(
+            StorageEthTx,
+            [
+                "SELECT * FROM eth_txs WHERE confirmed_eth_tx_history_id IS NULL AND ",
+                _, // WHERE condition
+                " ORDER BY nonce"
+            ],
+            match (from_address) {
+                Some(address) => ("from_addr = $1::bytea"; address.as_bytes()),
+                None => ("from_addr IS NULL";),
+            }
+        );
+
+        let txs = query.fetch_all(self.storage.conn()).await?;
+        Ok(txs.into_iter().map(|tx| tx.into()).collect())
+    }
+
+    /// Changes the nonce of the specified transaction. This should only be used to heal nonce divergence
+    /// for transactions with no sending attempts mined on L1.
+    pub async fn set_nonce(&mut self, eth_tx_id: u32, nonce: Nonce) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE eth_txs
+            SET
+                nonce = $1,
+                updated_at = NOW()
+            WHERE
+                id = $2
+            "#,
+            i64::from(nonce.0),
+            eth_tx_id as i32
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns transactions that were neither sent to L1 nor simulated in the dry-run mode, ordered by ID.
+    pub async fn get_eth_txs_to_dry_run(&mut self, limit: u64) -> sqlx::Result<Vec<EthTx>> {
+        let txs = sqlx::query_as!(
+            StorageEthTx,
+            r#"
+            SELECT
+                *
+            FROM
+                eth_txs
+            WHERE
+                id > (
+                    SELECT
+                        COALESCE(MAX(eth_tx_id), 0)
+                    FROM
+                        eth_txs_history
+                )
+                AND NOT EXISTS (
+                    SELECT
+                        1
+                    FROM
+                        eth_txs_dry_runs
+                    WHERE
+                        eth_tx_id = eth_txs.id
+                )
+            ORDER BY
+                id
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(txs.into_iter().map(|tx| tx.into()).collect())
+    }
+
+    pub async fn insert_dry_run(&mut self, dry_run: &EthTxDryRun) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_txs_dry_runs (eth_tx_id, l1_block_number, revert_reason, created_at)
+            VALUES
+                ($1, $2, $3, NOW())
+            ON CONFLICT (eth_tx_id) DO NOTHING
+            "#,
+            dry_run.eth_tx_id as i32,
+            dry_run.l1_block_number.0 as i32,
+            dry_run.revert_reason.as_deref()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_dry_run(&mut self, eth_tx_id: u32) -> sqlx::Result<Option<EthTxDryRun>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                l1_block_number,
+                revert_reason
+            FROM
+                eth_txs_dry_runs
+            WHERE
+                eth_tx_id = $1
+            "#,
+            eth_tx_id as i32
+        )
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(|row| EthTxDryRun {
+            eth_tx_id,
+            l1_block_number: L1BlockNumber(row.l1_block_number as u32),
+            revert_reason: row.revert_reason,
+        }))
+    }
+
+    /// Records L1 costs of a mined transaction for all L1 batches published by it.
+    pub async fn insert_l1_batch_l1_costs(
+        &mut self,
+        eth_tx_id: u32,
+        tx_type: AggregatedActionType,
+        cost: &EthTxL1Cost,
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            r#"
+            WITH
+                batches AS (
+                    SELECT
+                        number
+                    FROM
+                        l1_batches
+                    WHERE
+                        eth_commit_tx_id = $1
+                        OR eth_prove_tx_id = $1
+                        OR eth_execute_tx_id = $1
+                ),
+                batch_count AS (
+                    SELECT
+                        COUNT(*) AS count
+                    FROM
+                        batches
+                )
+            INSERT INTO
+                l1_batches_l1_costs (
+                    l1_batch_number,
+                    tx_type,
+                    eth_tx_id,
+                    l1_batches_in_tx,
+                    gas_used,
+                    gas_price,
+                    blob_gas_used,
+                    blob_gas_price,
+                    created_at
+                )
+            SELECT
+                batches.number,
+                $2,
+                $1,
+                batch_count.count,
+                $3::BIGINT / batch_count.count,
+                $4,
+                $5::BIGINT / batch_count.count,
+                $6,
+                NOW()
+            FROM
+                batches,
+                batch_count
+            ON CONFLICT (l1_batch_number, tx_type) DO
+            UPDATE
+            SET
+                eth_tx_id = excluded.eth_tx_id,
+                l1_batches_in_tx = excluded.l1_batches_in_tx,
+                gas_used = excluded.gas_used,
+                gas_price = excluded.gas_price,
+                blob_gas_used = excluded.blob_gas_used,
+                blob_gas_price = excluded.blob_gas_price,
+                created_at = excluded.created_at
+            "#,
+            eth_tx_id as i32,
+            tx_type.as_str(),
+            cost.gas_used as i64,
+            u256_to_big_decimal(cost.gas_price),
+            cost.blob_gas_used as i64,
+            u256_to_big_decimal(cost.blob_gas_price)
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns L1 costs recorded for the specified L1 batch, in the order of operations (commit, prove, execute).
+    pub async fn get_l1_batch_l1_costs(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Vec<L1BatchL1Cost>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_type,
+                eth_tx_id,
+                l1_batches_in_tx,
+                gas_used,
+                gas_price,
+                blob_gas_used,
+                blob_gas_price
+            FROM
+                l1_batches_l1_costs
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                eth_tx_id
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1BatchL1Cost {
+                l1_batch_number,
+                tx_type: row
+                    .tx_type
+                    .parse()
+                    .expect("invalid tx_type in the database"),
+                eth_tx_id: row.eth_tx_id as u32,
+                l1_batches_in_tx: row.l1_batches_in_tx as u32,
+                gas_used: row.gas_used as u64,
+                gas_price: bigdecimal_to_u256(row.gas_price),
+                blob_gas_used: row.blob_gas_used as u64,
+                blob_gas_price: bigdecimal_to_u256(row.blob_gas_price),
+            })
+            .collect())
+    }
+
+    /// Returns operations for which creating new transactions is paused.
+    pub async fn get_paused_operations(&mut self) -> sqlx::Result<Vec<AggregatedActionType>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_type
+            FROM
+                eth_sender_paused_operations
+            "#
+        )
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                row.tx_type
+                    .parse()
+                    .expect("invalid tx_type in the database")
+            })
+            .collect())
+    }
+
+    /// Pauses creating new transactions for the specified operation. Returns whether the operation was already paused.
+    pub async fn pause_operation(&mut self, tx_type: AggregatedActionType) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO
+                eth_sender_paused_operations (tx_type, paused_at)
+            VALUES
+                ($1, NOW())
+            ON CONFLICT (tx_type) DO NOTHING
+            "#,
+            tx_type.as_str()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(result.rows_affected() == 0)
+    }
+
+    /// Resumes creating new transactions for the specified operation. Returns whether the operation was paused.
+    pub async fn resume_operation(&mut self, tx_type: AggregatedActionType) -> sqlx::Result<bool> {
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM eth_sender_paused_operations
+            WHERE
+                tx_type = $1
+            "#,
+            tx_type.as_str()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Attributes all transactions sent from `address` to the main operator (i.e., resets their `from_addr` to `NULL`).
+    /// This is used when the successor operator is promoted to the main operator after key rotation.
+    /// Returns the number of affected transactions.
+    pub async fn reset_sender_to_main_operator(&mut self, address: Address) -> sqlx::Result<u64> {
+        let result = sqlx::query!(
+            r#"
+            UPDATE eth_txs
+            SET
+                from_addr = NULL
+            WHERE
+                from_addr = $1
+            "#,
+            address.as_bytes()
+        )
+        .execute(self.storage.conn())
+        .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn mark_failed_transaction(&mut self, eth_tx_id: u32) -> sqlx::Result<()> {
         sqlx::query!(
             r#"
@@ -3,6 +3,7 @@ use std::{collections::HashMap, fmt};
 use sqlx::types::chrono::Utc;
 use zksync_db_connection::{
     connection::Connection,
+    copy::BinaryCopyBuffer,
     error::DalResult,
     instrument::{CopyStatement, InstrumentExt},
     write_str, writeln_str,
@@ -101,6 +102,68 @@ impl EventsDal<'_, '_> {
         copy.send(buffer.as_bytes()).await
     }
 
+    /// Saves events for the specified L2 block using a binary `COPY`. This is equivalent to [`Self::save_events()`],
+    /// but is more efficient for large blocks since data is streamed to Postgres without conversions.
+    pub async fn copy_events(
+        &mut self,
+        block_number: L2BlockNumber,
+        all_block_events: &[(IncludedTxLocation, Vec<&VmEvent>)],
+    ) -> DalResult<()> {
+        let events_len = all_block_events.len();
+        let mut copy = CopyStatement::new(
+            "COPY events(
+                    miniblock_number, tx_hash, tx_index_in_block, address,
+                    event_index_in_block, event_index_in_tx,
+                    topic1, topic2, topic3, topic4, value,
+                    tx_initiator_address,
+                    created_at, updated_at
+                )
+                FROM STDIN WITH (FORMAT BINARY)",
+        )
+        .instrument("copy_events")
+        .with_arg("block_number", &block_number)
+        .with_arg("events.len", &events_len)
+        .start(self.storage)
+        .await?;
+
+        let mut buffer = BinaryCopyBuffer::new();
+        let now = Utc::now();
+        let mut event_index_in_block = 0_i32;
+        for (tx_location, events) in all_block_events {
+            let IncludedTxLocation {
+                tx_hash,
+                tx_index_in_l2_block,
+                tx_initiator_address,
+            } = tx_location;
+
+            for (event_index_in_tx, event) in events.iter().enumerate() {
+                buffer
+                    .start_row(14)
+                    .push_int8(block_number.0.into())
+                    .push_bytea(tx_hash.as_bytes())
+                    .push_int4(*tx_index_in_l2_block as i32)
+                    .push_bytea(event.address.as_bytes())
+                    .push_int4(event_index_in_block)
+                    .push_int4(event_index_in_tx as i32);
+                for topic_index in 0..4 {
+                    let topic = event.indexed_topics.get(topic_index);
+                    buffer.push_bytea(topic.map_or(&[][..], H256::as_bytes));
+                }
+                buffer
+                    .push_bytea(&event.value)
+                    .push_bytea(tx_initiator_address.as_bytes())
+                    .push_timestamp(now)
+                    .push_timestamp(now);
+
+                if buffer.len() >= BinaryCopyBuffer::CHUNK_SIZE {
+                    copy.send_chunk(&buffer.take_chunk()).await?;
+                }
+                event_index_in_block += 1;
+            }
+        }
+        copy.send(&buffer.finish()).await
+    }
+
     /// Removes events with a block number strictly greater than the specified `block_number`.
     pub async fn roll_back_events(&mut self, block_number: L2BlockNumber) -> DalResult<()> {
         sqlx::query!(
@@ -428,6 +491,62 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn copying_events() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+
+        let first_location = IncludedTxLocation {
+            tx_hash: H256([1; 32]),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::repeat_byte(0x11),
+        };
+        let first_events = [create_vm_event(0, 0), create_vm_event(1, 4)];
+        let second_location = IncludedTxLocation {
+            tx_hash: H256([2; 32]),
+            tx_index_in_l2_block: 1,
+            tx_initiator_address: Address::repeat_byte(0x22),
+        };
+        let second_events = [create_vm_event(2, 1), create_vm_event(3, 3)];
+        let all_events = vec![
+            (first_location, first_events.iter().collect()),
+            (second_location, second_events.iter().collect()),
+        ];
+        // Events inserted using the text and binary `COPY` must be identical.
+        conn.events_dal()
+            .save_events(L2BlockNumber(1), &all_events)
+            .await
+            .unwrap();
+        conn.events_dal()
+            .copy_events(L2BlockNumber(2), &all_events)
+            .await
+            .unwrap();
+
+        let logs = conn
+            .events_web3_dal()
+            .get_all_logs(L2BlockNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 8);
+        let mut logs_by_block = HashMap::<_, Vec<_>>::new();
+        for mut log in logs {
+            let block_number = log.block_number.take().unwrap();
+            log.block_hash = None;
+            logs_by_block.entry(block_number).or_default().push(log);
+        }
+        assert_eq!(logs_by_block[&1.into()], logs_by_block[&2.into()]);
+    }
+
     #[tokio::test]
     async fn storing_events() {
         let pool = ConnectionPool::<Core>::test_pool().await;
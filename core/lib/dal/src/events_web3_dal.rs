@@ -1,3 +1,5 @@
+use std::iter;
+
 use sqlx::{
     postgres::PgArguments,
     query::{Query, QueryAs},
@@ -5,11 +7,15 @@ use sqlx::{
 };
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
 use zksync_types::{
-    api::{GetLogsFilter, Log},
-    Address, L2BlockNumber, H256,
+    api::{GetLogsFilter, L2ToL1Log, Log},
+    ethabi::ethereum_types::BloomInput,
+    Address, L1BatchNumber, L2BlockNumber, H2048, H256,
 };
 
-use crate::{models::storage_event::StorageWeb3Log, Core};
+use crate::{
+    models::storage_event::{StorageL2ToL1Log, StorageWeb3Log},
+    Core,
+};
 
 #[derive(Debug)]
 pub struct EventsWeb3Dal<'a, 'c> {
@@ -24,7 +30,10 @@ impl EventsWeb3Dal<'_, '_> {
         filter: &GetLogsFilter,
         offset: usize,
     ) -> DalResult<Option<L2BlockNumber>> {
-        let (where_sql, arg_index) = self.build_get_logs_where_clause(filter);
+        let (mut where_sql, arg_index) = self.build_get_logs_where_clause(filter);
+        if let Some(bloom_sql) = Self::build_logs_bloom_filter(filter) {
+            where_sql += &bloom_sql;
+        }
 
         let query = format!(
             r#"
@@ -66,7 +75,10 @@ impl EventsWeb3Dal<'_, '_> {
     /// Returns logs for given filter.
     #[allow(clippy::type_complexity)]
     pub async fn get_logs(&mut self, filter: GetLogsFilter, limit: usize) -> DalResult<Vec<Log>> {
-        let (where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
+        let (mut where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
+        if let Some(bloom_sql) = Self::build_logs_bloom_filter(&filter) {
+            where_sql += &bloom_sql;
+        }
         let query = format!(
             r#"
             WITH events_select AS (
@@ -119,6 +131,13 @@ impl EventsWeb3Dal<'_, '_> {
 
         let mut where_sql = format!("(miniblock_number >= {})", filter.from_block.0);
         where_sql += &format!(" AND (miniblock_number <= {})", filter.to_block.0);
+        // Keyset pagination; the row comparison matches the `events` primary key, so it can use the index.
+        if let Some(cursor) = &filter.after {
+            where_sql += &format!(
+                " AND ((miniblock_number, event_index_in_block) > ({}, {}))",
+                cursor.block_number.0, cursor.log_index
+            );
+        }
 
         // Add filters for address (like `address = ANY($1)` or `address = $1`)
         if let Some(filter_sql) =
@@ -143,6 +162,46 @@ impl EventsWeb3Dal<'_, '_> {
         (where_sql, arg_index)
     }
 
+    /// Builds a condition restricting events to L2 blocks whose logs bloom may contain logs matching the filter.
+    /// Blooms are checked bit by bit in SQL, so that block ranges of any size can be filtered without loading blooms.
+    /// L2 blocks without a stored bloom are never skipped. Returns `None` if the filter doesn't constrain addresses
+    /// or topics, since all blocks would match in this case.
+    fn build_logs_bloom_filter(filter: &GetLogsFilter) -> Option<String> {
+        let address_group: Vec<&[u8]> = filter.addresses.iter().map(Address::as_bytes).collect();
+        let topic_groups = filter
+            .topics
+            .iter()
+            .map(|(_, topics)| topics.iter().map(H256::as_bytes).collect());
+        // Values in a group are alternatives; a block must match all groups.
+        let group_conditions: Vec<_> = iter::once(address_group)
+            .chain(topic_groups)
+            .filter(|values| !values.is_empty())
+            .map(|values| {
+                let alternatives: Vec<_> = values
+                    .into_iter()
+                    .map(|value| {
+                        let bit_checks: Vec<_> = bloom_bit_indices(value)
+                            .map(|idx| format!("GET_BIT(logs_bloom, {idx}) = 1"))
+                            .collect();
+                        format!("({})", bit_checks.join(" AND "))
+                    })
+                    .collect();
+                format!("({})", alternatives.join(" OR "))
+            })
+            .collect();
+        if group_conditions.is_empty() {
+            return None;
+        }
+
+        Some(format!(
+            " AND (miniblock_number IN (SELECT number FROM miniblocks \
+             WHERE number BETWEEN {} AND {} AND (logs_bloom IS NULL OR ({}))))",
+            filter.from_block.0,
+            filter.to_block.0,
+            group_conditions.join(" AND ")
+        ))
+    }
+
     // Builds SQL filter for optional filter (like address or topics).
     fn build_sql_filter(
         number_of_entities: u32,
@@ -239,14 +298,106 @@ impl EventsWeb3Dal<'_, '_> {
         let logs = db_logs.into_iter().map(Into::into).collect();
         Ok(logs)
     }
+
+    /// Returns all L2-to-L1 logs emitted in L2 blocks after `from_block`, ordered by the L2 block number
+    /// and the log index in the block.
+    pub async fn get_l2_to_l1_logs_after(
+        &mut self,
+        from_block: L2BlockNumber,
+    ) -> DalResult<Vec<L2ToL1Log>> {
+        let db_logs: Vec<StorageL2ToL1Log> = sqlx::query_as!(
+            StorageL2ToL1Log,
+            r#"
+            SELECT
+                l2_to_l1_logs.miniblock_number,
+                l2_to_l1_logs.log_index_in_miniblock,
+                l2_to_l1_logs.log_index_in_tx,
+                l2_to_l1_logs.tx_hash,
+                miniblocks.hash AS "block_hash?",
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                l2_to_l1_logs.shard_id,
+                l2_to_l1_logs.is_service,
+                l2_to_l1_logs.tx_index_in_miniblock,
+                l2_to_l1_logs.tx_index_in_l1_batch,
+                l2_to_l1_logs.sender,
+                l2_to_l1_logs.key,
+                l2_to_l1_logs.value
+            FROM
+                l2_to_l1_logs
+                INNER JOIN miniblocks ON l2_to_l1_logs.miniblock_number = miniblocks.number
+            WHERE
+                l2_to_l1_logs.miniblock_number > $1
+            ORDER BY
+                l2_to_l1_logs.miniblock_number ASC,
+                l2_to_l1_logs.log_index_in_miniblock ASC
+            "#,
+            i64::from(from_block.0)
+        )
+        .instrument("get_l2_to_l1_logs_after")
+        .with_arg("from_block", &from_block)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(db_logs.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns all L2-to-L1 logs in the specified L1 batch. Logs are ordered in the same way as leaves
+    /// in the Merkle tree of L2-to-L1 logs for the batch.
+    pub async fn get_l2_to_l1_logs_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<L2ToL1Log>> {
+        let db_logs: Vec<StorageL2ToL1Log> = sqlx::query_as!(
+            StorageL2ToL1Log,
+            r#"
+            SELECT
+                l2_to_l1_logs.miniblock_number,
+                l2_to_l1_logs.log_index_in_miniblock,
+                l2_to_l1_logs.log_index_in_tx,
+                l2_to_l1_logs.tx_hash,
+                miniblocks.hash AS "block_hash?",
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                l2_to_l1_logs.shard_id,
+                l2_to_l1_logs.is_service,
+                l2_to_l1_logs.tx_index_in_miniblock,
+                l2_to_l1_logs.tx_index_in_l1_batch,
+                l2_to_l1_logs.sender,
+                l2_to_l1_logs.key,
+                l2_to_l1_logs.value
+            FROM
+                l2_to_l1_logs
+                INNER JOIN miniblocks ON l2_to_l1_logs.miniblock_number = miniblocks.number
+            WHERE
+                miniblocks.l1_batch_number = $1
+            ORDER BY
+                l2_to_l1_logs.miniblock_number ASC,
+                l2_to_l1_logs.log_index_in_miniblock ASC
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_l2_to_l1_logs_for_l1_batch")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+        Ok(db_logs.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Returns indices of the bits set by `value` in a logs bloom. Indices are compatible with the Postgres `GET_BIT`
+/// function, i.e., bits are numbered from the least significant one within each byte.
+fn bloom_bit_indices(value: &[u8]) -> impl Iterator<Item = usize> {
+    let bloom_bytes = H2048::from(BloomInput::Raw(value)).0;
+    (0..bloom_bytes.len() * 8).filter(move |&idx| bloom_bytes[idx / 8] & (1 << (idx % 8)) != 0)
 }
 
 #[cfg(test)]
 mod tests {
-    use zksync_types::{Address, H256};
+    use zksync_types::{
+        api::LogCursor, event::extract_logs_bloom, tx::IncludedTxLocation, Address, L1BatchNumber,
+        ProtocolVersion, VmEvent, H256, U64,
+    };
 
     use super::*;
-    use crate::{ConnectionPool, Core};
+    use crate::{tests::create_l2_block_header, ConnectionPool, Core, CoreDal};
 
     #[tokio::test]
     async fn test_build_get_logs_where_clause() {
@@ -258,6 +409,7 @@ mod tests {
             to_block: L2BlockNumber(200),
             addresses: vec![Address::from_low_u64_be(123)],
             topics: vec![(0, vec![H256::from_low_u64_be(456)])],
+            after: None,
         };
 
         let expected_sql = "(miniblock_number >= 100) AND (miniblock_number <= 200) AND (address = $1) AND (topic0 = $2)";
@@ -292,6 +444,7 @@ mod tests {
                 ),
                 (2, vec![H256::from_low_u64_be(789)]),
             ],
+            after: None,
         };
 
         let expected_sql = "(miniblock_number >= 10) AND (miniblock_number <= 400) AND (address = ANY($1)) AND (topic0 = ANY($2)) AND (topic2 = $3)";
@@ -313,6 +466,7 @@ mod tests {
             to_block: L2BlockNumber(400),
             addresses: vec![],
             topics: vec![(2, vec![H256::from_low_u64_be(789)])],
+            after: None,
         };
 
         let expected_sql =
@@ -324,4 +478,137 @@ mod tests {
         assert_eq!(actual_sql, expected_sql);
         assert_eq!(actual_arg_index, expected_arg_index);
     }
+
+    #[tokio::test]
+    async fn test_build_get_logs_with_cursor_where_clause() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let storage = &mut connection_pool.connection().await.unwrap();
+        let events_web3_dal = EventsWeb3Dal { storage };
+        let filter = GetLogsFilter {
+            from_block: L2BlockNumber(10),
+            to_block: L2BlockNumber(400),
+            addresses: vec![Address::from_low_u64_be(123)],
+            topics: vec![],
+            after: Some(LogCursor {
+                block_number: L2BlockNumber(20),
+                log_index: 5,
+            }),
+        };
+
+        let expected_sql = "(miniblock_number >= 10) AND (miniblock_number <= 400) \
+            AND ((miniblock_number, event_index_in_block) > (20, 5)) AND (address = $1)";
+        let expected_arg_index = 2;
+
+        let (actual_sql, actual_arg_index) = events_web3_dal.build_get_logs_where_clause(&filter);
+
+        assert_eq!(actual_sql, expected_sql);
+        assert_eq!(actual_arg_index, expected_arg_index);
+    }
+
+    #[test]
+    fn bloom_bit_indices_are_consistent_with_bloom() {
+        let value = Address::repeat_byte(0x42);
+        let mut bloom = H2048::zero();
+        bloom.accrue(BloomInput::Raw(value.as_bytes()));
+
+        let indices: Vec<_> = bloom_bit_indices(value.as_bytes()).collect();
+        assert!(!indices.is_empty() && indices.len() <= 3, "{indices:?}");
+        let set_bits_count: u32 = bloom.0.iter().map(|byte| byte.count_ones()).sum();
+        assert_eq!(set_bits_count as usize, indices.len());
+        for idx in indices {
+            assert_ne!(bloom.0[idx / 8] & (1 << (idx % 8)), 0);
+        }
+    }
+
+    async fn get_log_blocks(conn: &mut Connection<'_, Core>, filter: GetLogsFilter) -> Vec<U64> {
+        let logs = conn.events_web3_dal().get_logs(filter, 100).await.unwrap();
+        logs.into_iter()
+            .map(|log| log.block_number.unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn skipping_l2_blocks_using_logs_bloom() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let event = VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::repeat_byte(1),
+            indexed_topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+            value: vec![4],
+        };
+        let location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(1),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::default(),
+        };
+        // L2 block #1 has a correct bloom, #2 has a (deliberately inconsistent) empty bloom,
+        // and #3 has no bloom, like L2 blocks sealed before blooms were introduced.
+        for number in 1..=3 {
+            let mut header = create_l2_block_header(number);
+            if number == 1 {
+                header.logs_bloom = extract_logs_bloom([&event]);
+            }
+            conn.blocks_dal().insert_l2_block(&header).await.unwrap();
+            conn.events_dal()
+                .save_events(L2BlockNumber(number), &[(location, vec![&event])])
+                .await
+                .unwrap();
+        }
+        sqlx::query("UPDATE miniblocks SET logs_bloom = NULL WHERE number = 3")
+            .execute(conn.conn())
+            .await
+            .unwrap();
+
+        let base_filter = GetLogsFilter {
+            from_block: L2BlockNumber(1),
+            to_block: L2BlockNumber(3),
+            addresses: vec![],
+            topics: vec![],
+            after: None,
+        };
+
+        let all_blocks = get_log_blocks(&mut conn, base_filter.clone()).await;
+        assert_eq!(all_blocks, [U64::from(1), U64::from(2), U64::from(3)]);
+
+        let matching_filters = [
+            GetLogsFilter {
+                addresses: vec![event.address],
+                ..base_filter.clone()
+            },
+            GetLogsFilter {
+                addresses: vec![Address::repeat_byte(0xff), event.address],
+                topics: vec![(2, vec![H256::repeat_byte(3)])],
+                ..base_filter.clone()
+            },
+        ];
+        for filter in matching_filters {
+            let blocks = get_log_blocks(&mut conn, filter).await;
+            assert_eq!(blocks, [U64::from(1), U64::from(3)]);
+        }
+
+        let non_matching_filter = GetLogsFilter {
+            topics: vec![(1, vec![H256::repeat_byte(0xff)])],
+            ..base_filter.clone()
+        };
+        assert_eq!(get_log_blocks(&mut conn, non_matching_filter).await, []);
+
+        let address_filter = GetLogsFilter {
+            addresses: vec![event.address],
+            ..base_filter
+        };
+        for (offset, expected_block) in [(0, Some(1)), (1, Some(3)), (2, None)] {
+            let block = conn
+                .events_web3_dal()
+                .get_log_block_number(&address_filter, offset)
+                .await
+                .unwrap();
+            assert_eq!(block, expected_block.map(L2BlockNumber));
+        }
+    }
 }
@@ -1,8 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
 use anyhow::Context as _;
+use sqlx::types::chrono::Utc;
 use zksync_contracts::{BaseSystemContracts, SystemContractCode};
-use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_db_connection::{
+    connection::Connection,
+    copy::BinaryCopyBuffer,
+    error::DalResult,
+    instrument::{CopyStatement, InstrumentExt},
+};
 use zksync_types::{L2BlockNumber, H256, U256};
 use zksync_utils::{bytes_to_be_words, bytes_to_chunks};
 
@@ -55,6 +61,71 @@ impl FactoryDepsDal<'_, '_> {
         Ok(())
     }
 
+    /// Inserts factory dependencies for an L2 block using a binary `COPY`. Unlike [`Self::insert_factory_deps()`],
+    /// `COPY` cannot skip conflicting rows, so already stored dependencies are filtered out beforehand. Hence, this method
+    /// must not be called concurrently with other inserts of factory deps (which is the case for the state keeper,
+    /// the only writer of factory deps).
+    pub async fn copy_factory_deps(
+        &mut self,
+        block_number: L2BlockNumber,
+        factory_deps: &HashMap<H256, Vec<u8>>,
+    ) -> DalResult<()> {
+        let bytecode_hashes: Vec<_> = factory_deps.keys().map(H256::as_bytes).collect();
+        let existing_hashes: HashSet<_> = sqlx::query!(
+            r#"
+            SELECT
+                bytecode_hash
+            FROM
+                factory_deps
+            WHERE
+                bytecode_hash = ANY ($1)
+            "#,
+            &bytecode_hashes as &[&[u8]]
+        )
+        .instrument("copy_factory_deps#get_existing")
+        .with_arg("block_number", &block_number)
+        .with_arg("factory_deps.len", &factory_deps.len())
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(|row| H256::from_slice(&row.bytecode_hash))
+        .collect();
+
+        let new_deps_len = factory_deps.len() - existing_hashes.len();
+        if new_deps_len == 0 {
+            return Ok(());
+        }
+        let mut copy = CopyStatement::new(
+            "COPY factory_deps(bytecode_hash, bytecode, miniblock_number, created_at, updated_at)
+            FROM STDIN WITH (FORMAT BINARY)",
+        )
+        .instrument("copy_factory_deps")
+        .with_arg("block_number", &block_number)
+        .with_arg("new_deps.len", &new_deps_len)
+        .start(self.storage)
+        .await?;
+
+        let mut buffer = BinaryCopyBuffer::new();
+        let now = Utc::now();
+        for (hash, bytecode) in factory_deps {
+            if existing_hashes.contains(hash) {
+                continue;
+            }
+            buffer
+                .start_row(5)
+                .push_bytea(hash.as_bytes())
+                .push_bytea(bytecode)
+                .push_int8(block_number.0.into())
+                .push_timestamp(now)
+                .push_timestamp(now);
+
+            if buffer.len() >= BinaryCopyBuffer::CHUNK_SIZE {
+                copy.send_chunk(&buffer.take_chunk()).await?;
+            }
+        }
+        copy.send(&buffer.finish()).await
+    }
+
     /// Returns bytecode for a factory dependency with the specified bytecode `hash`.
     /// Returns bytecodes only from sealed miniblocks.
     pub async fn get_sealed_factory_dep(&mut self, hash: H256) -> DalResult<Option<Vec<u8>>> {
@@ -215,3 +286,54 @@ impl FactoryDepsDal<'_, '_> {
         .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::ProtocolVersion;
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn copying_factory_deps() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+
+        let stored_deps = HashMap::from([(H256::repeat_byte(1), vec![1; 32])]);
+        conn.factory_deps_dal()
+            .insert_factory_deps(L2BlockNumber(1), &stored_deps)
+            .await
+            .unwrap();
+        // The already stored dependency must be skipped rather than fail the `COPY`.
+        let new_deps = HashMap::from([
+            (H256::repeat_byte(1), vec![1; 32]),
+            (H256::repeat_byte(2), vec![2; 64]),
+        ]);
+        conn.factory_deps_dal()
+            .copy_factory_deps(L2BlockNumber(2), &new_deps)
+            .await
+            .unwrap();
+
+        let all_deps = conn
+            .factory_deps_dal()
+            .dump_all_factory_deps_for_tests()
+            .await;
+        assert_eq!(all_deps, new_deps);
+        let reverted_hashes = conn
+            .factory_deps_dal()
+            .get_factory_deps_for_revert(L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(reverted_hashes, [H256::repeat_byte(2)]);
+    }
+}
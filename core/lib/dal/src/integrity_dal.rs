@@ -0,0 +1,438 @@
+//! Cross-table invariant checks for detecting Postgres state corruption.
+
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{L1BatchNumber, L2BlockNumber};
+
+use crate::Core;
+
+/// Mismatch between an entity count recorded in a block header and the actual number of rows
+/// in a dependent table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CountMismatch<N> {
+    /// Number of the L2 block or L1 batch.
+    pub number: N,
+    /// Count recorded in the block header.
+    pub expected: u64,
+    /// Count of rows in the dependent table.
+    pub actual: u64,
+}
+
+impl<N> CountMismatch<N> {
+    fn new(number: N, expected: i64, actual: i64) -> Self {
+        Self {
+            number,
+            expected: expected as u64,
+            actual: actual as u64,
+        }
+    }
+}
+
+/// Storage writes published in the commitment of an L1 batch, in the compressed form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedWrites {
+    pub number: L1BatchNumber,
+    /// Compressed repeated writes; only set for pre-boojum L1 batches.
+    pub compressed_repeated_writes: Option<Vec<u8>>,
+    /// Compressed state diffs (including both initial and repeated writes); only set for post-boojum L1 batches.
+    pub compressed_state_diffs: Option<Vec<u8>>,
+}
+
+#[derive(Debug)]
+pub struct IntegrityDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl IntegrityDal<'_, '_> {
+    /// Returns L2 blocks in the specified range for which the number of stored transactions differs
+    /// from the transaction count in the L2 block header.
+    pub async fn get_l2_block_tx_count_mismatches(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<CountMismatch<L2BlockNumber>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblocks.number,
+                (miniblocks.l1_tx_count + miniblocks.l2_tx_count)::BIGINT AS "expected!",
+                COUNT(transactions.hash) AS "actual!"
+            FROM
+                miniblocks
+                LEFT JOIN transactions ON transactions.miniblock_number = miniblocks.number
+            WHERE
+                miniblocks.number BETWEEN $1 AND $2
+            GROUP BY
+                miniblocks.number
+            HAVING
+                COUNT(transactions.hash) <> miniblocks.l1_tx_count + miniblocks.l2_tx_count
+            ORDER BY
+                miniblocks.number
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("get_l2_block_tx_count_mismatches")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                CountMismatch::new(L2BlockNumber(row.number as u32), row.expected, row.actual)
+            })
+            .collect())
+    }
+
+    /// Returns L1 batches in the specified range for which the transaction count in the batch header
+    /// differs from the total transaction count of its L2 blocks.
+    pub async fn get_l1_batch_tx_count_mismatches(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    ) -> DalResult<Vec<CountMismatch<L1BatchNumber>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_batches.number,
+                (l1_batches.l1_tx_count + l1_batches.l2_tx_count)::BIGINT AS "expected!",
+                COALESCE(SUM(miniblocks.l1_tx_count + miniblocks.l2_tx_count), 0)::BIGINT AS "actual!"
+            FROM
+                l1_batches
+                LEFT JOIN miniblocks ON miniblocks.l1_batch_number = l1_batches.number
+            WHERE
+                l1_batches.number BETWEEN $1 AND $2
+            GROUP BY
+                l1_batches.number
+            HAVING
+                COALESCE(SUM(miniblocks.l1_tx_count + miniblocks.l2_tx_count), 0) <> l1_batches.l1_tx_count + l1_batches.l2_tx_count
+            ORDER BY
+                l1_batches.number
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0)
+        )
+        .instrument("get_l1_batch_tx_count_mismatches")
+        .with_arg("l1_batches", &l1_batches)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                CountMismatch::new(L1BatchNumber(row.number as u32), row.expected, row.actual)
+            })
+            .collect())
+    }
+
+    /// Returns L1 batches in the specified range for which the number of initial writes differs from
+    /// the number of new leaves in the Merkle tree (i.e., the difference between `rollup_last_leaf_index`
+    /// of the batch and of the previous batch). Only L1 batches with tree data for both the batch and the previous batch
+    /// are checked.
+    pub async fn get_initial_writes_count_mismatches(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    ) -> DalResult<Vec<CountMismatch<L1BatchNumber>>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                counts.number AS "number!",
+                counts.expected AS "expected!",
+                counts.actual AS "actual!"
+            FROM
+                (
+                    SELECT
+                        l1_batches.number,
+                        l1_batches.rollup_last_leaf_index - prev_batches.rollup_last_leaf_index AS expected,
+                        (
+                            SELECT
+                                COUNT(*)
+                            FROM
+                                initial_writes
+                            WHERE
+                                initial_writes.l1_batch_number = l1_batches.number
+                        ) AS actual
+                    FROM
+                        l1_batches
+                        JOIN l1_batches prev_batches ON prev_batches.number = l1_batches.number - 1
+                    WHERE
+                        l1_batches.number BETWEEN $1 AND $2
+                        AND l1_batches.rollup_last_leaf_index IS NOT NULL
+                        AND prev_batches.rollup_last_leaf_index IS NOT NULL
+                ) AS counts
+            WHERE
+                counts.expected <> counts.actual
+            ORDER BY
+                counts.number
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0)
+        )
+        .instrument("get_initial_writes_count_mismatches")
+        .with_arg("l1_batches", &l1_batches)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                CountMismatch::new(L1BatchNumber(row.number as u32), row.expected, row.actual)
+            })
+            .collect())
+    }
+
+    /// Returns the number of the latest L1 batch with a computed commitment.
+    pub async fn get_last_l1_batch_with_commitment(&mut self) -> DalResult<Option<L1BatchNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                MAX(number) AS "number"
+            FROM
+                l1_batches
+            WHERE
+                commitment IS NOT NULL
+            "#
+        )
+        .instrument("get_last_l1_batch_with_commitment")
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.number.map(|number| L1BatchNumber(number as u32)))
+    }
+
+    /// Returns storage writes published for L1 batches with a computed commitment in the specified range.
+    pub async fn get_published_writes(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    ) -> DalResult<Vec<PublishedWrites>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                number,
+                compressed_repeated_writes,
+                compressed_state_diffs
+            FROM
+                l1_batches
+            WHERE
+                number BETWEEN $1 AND $2
+                AND commitment IS NOT NULL
+            ORDER BY
+                number
+            "#,
+            i64::from(l1_batches.start().0),
+            i64::from(l1_batches.end().0)
+        )
+        .instrument("get_published_writes")
+        .with_arg("l1_batches", &l1_batches)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PublishedWrites {
+                number: L1BatchNumber(row.number as u32),
+                compressed_repeated_writes: row.compressed_repeated_writes,
+                compressed_state_diffs: row.compressed_state_diffs,
+            })
+            .collect())
+    }
+
+    /// Returns the number of repeated writes in the specified L1 batch according to storage logs, i.e. the number of slots
+    /// that were initially written to in a previous L1 batch, and which final value in this batch differs
+    /// from their value before the batch. This matches the repeated writes included into the L1 batch commitment.
+    pub async fn get_repeated_writes_count(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<u64> {
+        let row = sqlx::query!(
+            r#"
+            WITH
+                batch_l2_blocks AS (
+                    SELECT
+                        MIN(number) AS first_l2_block,
+                        MAX(number) AS last_l2_block
+                    FROM
+                        miniblocks
+                    WHERE
+                        l1_batch_number = $1
+                ),
+                touched_slots AS (
+                    SELECT DISTINCT
+                        ON (storage_logs.hashed_key) storage_logs.hashed_key,
+                        storage_logs.value
+                    FROM
+                        storage_logs,
+                        batch_l2_blocks
+                    WHERE
+                        storage_logs.miniblock_number BETWEEN batch_l2_blocks.first_l2_block AND batch_l2_blocks.last_l2_block
+                    ORDER BY
+                        storage_logs.hashed_key,
+                        storage_logs.miniblock_number DESC,
+                        storage_logs.operation_number DESC
+                )
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                touched_slots
+                JOIN initial_writes ON initial_writes.hashed_key = touched_slots.hashed_key
+            WHERE
+                initial_writes.l1_batch_number < $1
+                AND touched_slots.value <> COALESCE(
+                    (
+                        SELECT
+                            prev_logs.value
+                        FROM
+                            storage_logs prev_logs,
+                            batch_l2_blocks
+                        WHERE
+                            prev_logs.hashed_key = touched_slots.hashed_key
+                            AND prev_logs.miniblock_number < batch_l2_blocks.first_l2_block
+                        ORDER BY
+                            prev_logs.miniblock_number DESC,
+                            prev_logs.operation_number DESC
+                        LIMIT
+                            1
+                    ),
+                    DECODE(REPEAT('00', 32), 'hex')
+                )
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_repeated_writes_count")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_one(self.storage)
+        .await?;
+
+        Ok(row.count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_contracts::BaseSystemContractsHashes;
+    use zksync_types::{
+        block::L1BatchHeader, AccountTreeId, Address, ProtocolVersion, ProtocolVersionId,
+        StorageKey, StorageLog, H256,
+    };
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, CoreDal};
+
+    async fn insert_l1_batch(
+        conn: &mut Connection<'_, Core>,
+        number: u32,
+        logs: Vec<StorageLog>,
+        initial_writes: &[StorageKey],
+    ) {
+        let header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            0,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::default(),
+        );
+        conn.blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(number))
+            .await
+            .unwrap();
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(number), &[(H256::zero(), logs)])
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(number))
+            .await
+            .unwrap();
+        conn.storage_logs_dedup_dal()
+            .insert_initial_writes(L1BatchNumber(number), initial_writes)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn counting_repeated_writes() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let keys: Vec<_> = (0..3)
+            .map(|i| StorageKey::new(account, H256::from_low_u64_be(i)))
+            .collect();
+        let logs = vec![
+            StorageLog::new_write_log(keys[0], H256::repeat_byte(1)),
+            StorageLog::new_write_log(keys[1], H256::repeat_byte(2)),
+        ];
+        insert_l1_batch(&mut conn, 1, logs, &keys[..2]).await;
+
+        // The write to `keys[1]` doesn't change its value, so it isn't a repeated write.
+        let logs = vec![
+            StorageLog::new_write_log(keys[0], H256::repeat_byte(3)),
+            StorageLog::new_write_log(keys[1], H256::repeat_byte(4)),
+            StorageLog::new_write_log(keys[1], H256::repeat_byte(2)),
+            StorageLog::new_write_log(keys[2], H256::repeat_byte(5)),
+        ];
+        insert_l1_batch(&mut conn, 2, logs, &keys[2..]).await;
+
+        let count = conn
+            .integrity_dal()
+            .get_repeated_writes_count(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+        let count = conn
+            .integrity_dal()
+            .get_repeated_writes_count(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn detecting_l2_block_tx_count_mismatch() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+        let mut corrupted_header = create_l2_block_header(2);
+        corrupted_header.l2_tx_count = 3;
+        conn.blocks_dal()
+            .insert_l2_block(&corrupted_header)
+            .await
+            .unwrap();
+
+        let all_l2_blocks = L2BlockNumber(0)..=L2BlockNumber(10);
+        let mismatches = conn
+            .integrity_dal()
+            .get_l2_block_tx_count_mismatches(all_l2_blocks)
+            .await
+            .unwrap();
+        assert_eq!(
+            mismatches,
+            [CountMismatch {
+                number: L2BlockNumber(2),
+                expected: 3,
+                actual: 0,
+            }]
+        );
+
+        let mismatches = conn
+            .integrity_dal()
+            .get_l2_block_tx_count_mismatches(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(mismatches, []);
+    }
+}
@@ -0,0 +1,146 @@
+//! Storage for L1 gas prices observed by the gas adjuster.
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::api::L1GasPriceSample;
+use zksync_utils::{bigdecimal_to_u256, u256_to_big_decimal};
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct L1GasPriceHistoryDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl L1GasPriceHistoryDal<'_, '_> {
+    /// Persists gas price samples. Samples for L1 blocks that are already present in the storage are ignored.
+    pub async fn insert_samples(&mut self, samples: &[L1GasPriceSample]) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        for sample in samples {
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                    l1_gas_price_history (
+                        l1_block_number,
+                        l1_block_timestamp,
+                        base_fee_per_gas,
+                        blob_base_fee,
+                        created_at
+                    )
+                VALUES
+                    ($1, $2, $3, $4, NOW())
+                ON CONFLICT (l1_block_number) DO NOTHING
+                "#,
+                sample.l1_block_number as i64,
+                sample.l1_block_timestamp as i64,
+                u256_to_big_decimal(sample.base_fee_per_gas),
+                sample.blob_base_fee.map(u256_to_big_decimal)
+            )
+            .instrument("insert_samples#insert_sample")
+            .with_arg("sample", sample)
+            .execute(&mut transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+
+    /// Returns up to `limit` samples for L1 blocks with timestamps in the specified inclusive range (in seconds),
+    /// ordered by the L1 block number.
+    pub async fn get_samples(
+        &mut self,
+        from_timestamp: u64,
+        to_timestamp: u64,
+        limit: usize,
+    ) -> DalResult<Vec<L1GasPriceSample>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                l1_block_number,
+                l1_block_timestamp,
+                base_fee_per_gas,
+                blob_base_fee
+            FROM
+                l1_gas_price_history
+            WHERE
+                l1_block_timestamp >= $1
+                AND l1_block_timestamp <= $2
+            ORDER BY
+                l1_block_number
+            LIMIT
+                $3
+            "#,
+            from_timestamp.min(i64::MAX as u64) as i64,
+            to_timestamp.min(i64::MAX as u64) as i64,
+            limit as i64
+        )
+        .instrument("get_samples")
+        .with_arg("from_timestamp", &from_timestamp)
+        .with_arg("to_timestamp", &to_timestamp)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| L1GasPriceSample {
+                l1_block_number: row.l1_block_number as u64,
+                l1_block_timestamp: row.l1_block_timestamp as u64,
+                base_fee_per_gas: bigdecimal_to_u256(row.base_fee_per_gas),
+                blob_base_fee: row.blob_base_fee.map(bigdecimal_to_u256),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::U256;
+
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn sample(l1_block_number: u64, blob_base_fee: Option<u64>) -> L1GasPriceSample {
+        L1GasPriceSample {
+            l1_block_number,
+            l1_block_timestamp: l1_block_number * 12,
+            base_fee_per_gas: U256::from(l1_block_number * 1_000),
+            blob_base_fee: blob_base_fee.map(U256::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn inserting_and_getting_samples() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let samples = [sample(1, None), sample(2, Some(1)), sample(3, Some(10))];
+        conn.l1_gas_price_history_dal()
+            .insert_samples(&samples)
+            .await
+            .unwrap();
+        // Repeated samples must be ignored.
+        conn.l1_gas_price_history_dal()
+            .insert_samples(&[sample(3, Some(100))])
+            .await
+            .unwrap();
+
+        let all_samples = conn
+            .l1_gas_price_history_dal()
+            .get_samples(0, u64::MAX, 10)
+            .await
+            .unwrap();
+        assert_eq!(all_samples, samples);
+
+        let filtered_samples = conn
+            .l1_gas_price_history_dal()
+            .get_samples(24, 36, 10)
+            .await
+            .unwrap();
+        assert_eq!(filtered_samples, samples[1..]);
+
+        let limited_samples = conn
+            .l1_gas_price_history_dal()
+            .get_samples(0, u64::MAX, 1)
+            .await
+            .unwrap();
+        assert_eq!(limited_samples, samples[..1]);
+    }
+}
@@ -12,36 +12,53 @@ pub use zksync_db_connection::{
 };
 
 use crate::{
-    blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal, consensus_dal::ConsensusDal,
-    contract_verification_dal::ContractVerificationDal, eth_sender_dal::EthSenderDal,
-    events_dal::EventsDal, events_web3_dal::EventsWeb3Dal, factory_deps_dal::FactoryDepsDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    api_filters_dal::ApiFiltersDal, balance_history_dal::BalanceHistoryDal,
+    base_token_dal::BaseTokenDal, blocks_dal::BlocksDal, blocks_web3_dal::BlocksWeb3Dal,
+    change_feed_dal::ChangeFeedDal, consensus_dal::ConsensusDal,
+    contract_verification_dal::ContractVerificationDal, data_availability_dal::DataAvailabilityDal,
+    eth_sender_dal::EthSenderDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
+    factory_deps_dal::FactoryDepsDal, integrity_dal::IntegrityDal,
+    l1_gas_price_history_dal::L1GasPriceHistoryDal, migrations_dal::MigrationsDal,
+    outbox_dal::OutboxDal, partitions_dal::PartitionsDal, proof_generation_dal::ProofGenerationDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, pruning_dal::PruningDal,
-    snapshot_recovery_dal::SnapshotRecoveryDal, snapshots_creator_dal::SnapshotsCreatorDal,
-    snapshots_dal::SnapshotsDal, storage_logs_dal::StorageLogsDal,
-    storage_logs_dedup_dal::StorageLogsDedupDal, storage_web3_dal::StorageWeb3Dal,
-    sync_dal::SyncDal, system_dal::SystemDal,
-    tee_verifier_input_producer_dal::TeeVerifierInputProducerDal, tokens_dal::TokensDal,
-    tokens_web3_dal::TokensWeb3Dal, transactions_dal::TransactionsDal,
-    transactions_web3_dal::TransactionsWeb3Dal, vm_runner_dal::VmRunnerDal,
+    settlement_layer_dal::SettlementLayerDal, snapshot_recovery_dal::SnapshotRecoveryDal,
+    snapshots_creator_dal::SnapshotsCreatorDal, snapshots_dal::SnapshotsDal,
+    storage_logs_dal::StorageLogsDal, storage_logs_dedup_dal::StorageLogsDedupDal,
+    storage_web3_dal::StorageWeb3Dal, sync_dal::SyncDal, system_dal::SystemDal,
+    tee_verifier_input_producer_dal::TeeVerifierInputProducerDal,
+    token_transfers_dal::TokenTransfersDal, tokens_dal::TokensDal, tokens_web3_dal::TokensWeb3Dal,
+    transactions_dal::TransactionsDal, transactions_web3_dal::TransactionsWeb3Dal,
+    vm_runner_dal::VmRunnerDal,
 };
 
+pub mod api_filters_dal;
+pub mod balance_history_dal;
+pub mod base_token_dal;
 pub mod blocks_dal;
 pub mod blocks_web3_dal;
+pub mod change_feed_dal;
 pub mod consensus;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
+pub mod data_availability_dal;
 pub mod eth_sender_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
 pub mod factory_deps_dal;
 pub mod helpers;
+pub mod integrity_dal;
+pub mod l1_gas_price_history_dal;
 pub mod metrics;
+pub mod migrations_dal;
 mod models;
+pub mod outbox_dal;
+pub mod partitions_dal;
 pub mod proof_generation_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
 pub mod pruning_dal;
+pub mod settlement_layer_dal;
 pub mod snapshot_recovery_dal;
 pub mod snapshots_creator_dal;
 pub mod snapshots_dal;
@@ -51,6 +68,7 @@ pub mod storage_web3_dal;
 pub mod sync_dal;
 pub mod system_dal;
 pub mod tee_verifier_input_producer_dal;
+pub mod token_transfers_dal;
 pub mod tokens_dal;
 pub mod tokens_web3_dal;
 pub mod transactions_dal;
@@ -81,6 +99,8 @@ where
 
     fn blocks_web3_dal(&mut self) -> BlocksWeb3Dal<'_, 'a>;
 
+    fn change_feed_dal(&mut self) -> ChangeFeedDal<'_, 'a>;
+
     fn consensus_dal(&mut self) -> ConsensusDal<'_, 'a>;
 
     fn eth_sender_dal(&mut self) -> EthSenderDal<'_, 'a>;
@@ -122,6 +142,28 @@ where
     fn pruning_dal(&mut self) -> PruningDal<'_, 'a>;
 
     fn vm_runner_dal(&mut self) -> VmRunnerDal<'_, 'a>;
+
+    fn api_filters_dal(&mut self) -> ApiFiltersDal<'_, 'a>;
+
+    fn data_availability_dal(&mut self) -> DataAvailabilityDal<'_, 'a>;
+
+    fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a>;
+
+    fn l1_gas_price_history_dal(&mut self) -> L1GasPriceHistoryDal<'_, 'a>;
+
+    fn partitions_dal(&mut self) -> PartitionsDal<'_, 'a>;
+
+    fn integrity_dal(&mut self) -> IntegrityDal<'_, 'a>;
+
+    fn migrations_dal(&mut self) -> MigrationsDal<'_, 'a>;
+
+    fn outbox_dal(&mut self) -> OutboxDal<'_, 'a>;
+
+    fn balance_history_dal(&mut self) -> BalanceHistoryDal<'_, 'a>;
+
+    fn token_transfers_dal(&mut self) -> TokenTransfersDal<'_, 'a>;
+
+    fn settlement_layer_dal(&mut self) -> SettlementLayerDal<'_, 'a>;
 }
 
 #[derive(Clone, Debug)]
@@ -153,6 +195,10 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
         BlocksWeb3Dal { storage: self }
     }
 
+    fn change_feed_dal(&mut self) -> ChangeFeedDal<'_, 'a> {
+        ChangeFeedDal { storage: self }
+    }
+
     fn consensus_dal(&mut self) -> ConsensusDal<'_, 'a> {
         ConsensusDal { storage: self }
     }
@@ -236,4 +282,48 @@ impl<'a> CoreDal<'a> for Connection<'a, Core> {
     fn vm_runner_dal(&mut self) -> VmRunnerDal<'_, 'a> {
         VmRunnerDal { storage: self }
     }
+
+    fn api_filters_dal(&mut self) -> ApiFiltersDal<'_, 'a> {
+        ApiFiltersDal { storage: self }
+    }
+
+    fn data_availability_dal(&mut self) -> DataAvailabilityDal<'_, 'a> {
+        DataAvailabilityDal { storage: self }
+    }
+
+    fn base_token_dal(&mut self) -> BaseTokenDal<'_, 'a> {
+        BaseTokenDal { storage: self }
+    }
+
+    fn l1_gas_price_history_dal(&mut self) -> L1GasPriceHistoryDal<'_, 'a> {
+        L1GasPriceHistoryDal { storage: self }
+    }
+
+    fn partitions_dal(&mut self) -> PartitionsDal<'_, 'a> {
+        PartitionsDal { storage: self }
+    }
+
+    fn integrity_dal(&mut self) -> IntegrityDal<'_, 'a> {
+        IntegrityDal { storage: self }
+    }
+
+    fn migrations_dal(&mut self) -> MigrationsDal<'_, 'a> {
+        MigrationsDal { storage: self }
+    }
+
+    fn outbox_dal(&mut self) -> OutboxDal<'_, 'a> {
+        OutboxDal { storage: self }
+    }
+
+    fn balance_history_dal(&mut self) -> BalanceHistoryDal<'_, 'a> {
+        BalanceHistoryDal { storage: self }
+    }
+
+    fn token_transfers_dal(&mut self) -> TokenTransfersDal<'_, 'a> {
+        TokenTransfersDal { storage: self }
+    }
+
+    fn settlement_layer_dal(&mut self) -> SettlementLayerDal<'_, 'a> {
+        SettlementLayerDal { storage: self }
+    }
 }
@@ -0,0 +1,327 @@
+//! Programmatic management of Postgres migrations.
+
+use std::{
+    collections::BTreeSet,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use sqlx::{
+    migrate::{Migrate, MigrateError, Migration, Migrator},
+    Executor, PgConnection,
+};
+use zksync_db_connection::{connection::Connection, instrument::InstrumentExt};
+
+use crate::Core;
+
+/// All migrations in the `migrations` directory of this crate.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Name of the temporary schema used for migration dry runs.
+const SHADOW_SCHEMA: &str = "migrations_dry_run";
+
+/// Migration that is not yet applied to the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    pub version: i64,
+    pub description: String,
+}
+
+impl From<&Migration> for PendingMigration {
+    fn from(migration: &Migration) -> Self {
+        Self {
+            version: migration.version,
+            description: migration.description.to_string(),
+        }
+    }
+}
+
+/// Table-level lock acquired by a migration.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TableLock {
+    pub table: String,
+    /// Postgres lock mode, e.g. `AccessExclusiveLock`.
+    pub mode: String,
+}
+
+/// Outcome of running a pending migration in the dry-run mode.
+#[derive(Debug, Clone)]
+pub struct MigrationDryRun {
+    pub migration: PendingMigration,
+    /// Duration of the migration against the shadow schema. Since the shadow schema has no data, this is a lower bound
+    /// of the actual duration; data-heavy migrations will take significantly longer.
+    pub duration: Duration,
+    /// Table locks acquired by the migration in addition to locks held before it. Unlike duration,
+    /// lock modes do not depend on the amount of data, so they closely match the locks taken on the actual migration.
+    pub locks: Vec<TableLock>,
+}
+
+/// Migration applied by [`MigrationsDal::apply_pending_migrations()`].
+#[derive(Debug, Clone)]
+pub struct AppliedMigration {
+    pub migration: PendingMigration,
+    pub duration: Duration,
+}
+
+async fn pending_migrations(conn: &mut PgConnection) -> anyhow::Result<Vec<&'static Migration>> {
+    conn.ensure_migrations_table().await?;
+    if let Some(version) = conn.dirty_version().await? {
+        return Err(MigrateError::Dirty(version).into());
+    }
+    let applied_migrations = conn.list_applied_migrations().await?;
+
+    let mut pending = vec![];
+    for migration in MIGRATOR.iter() {
+        if migration.migration_type.is_down_migration() {
+            continue;
+        }
+        let applied = applied_migrations
+            .iter()
+            .find(|applied| applied.version == migration.version);
+        match applied {
+            Some(applied) if applied.checksum != migration.checksum => {
+                return Err(MigrateError::VersionMismatch(migration.version).into());
+            }
+            Some(_) => { /* The migration is already applied */ }
+            None => pending.push(migration),
+        }
+    }
+    Ok(pending)
+}
+
+async fn revert_migrations(
+    conn: &mut PgConnection,
+    applied: &[AppliedMigration],
+) -> anyhow::Result<()> {
+    for applied in applied.iter().rev() {
+        let version = applied.migration.version;
+        let down_migration = MIGRATOR
+            .iter()
+            .find(|migration| {
+                migration.version == version && migration.migration_type.is_down_migration()
+            })
+            .with_context(|| format!("migration {version} has no down migration"))?;
+        let elapsed = conn
+            .revert(down_migration)
+            .await
+            .with_context(|| format!("failed reverting migration {version}"))?;
+        tracing::info!(
+            "Reverted migration {version} ({}) in {elapsed:?}",
+            applied.migration.description
+        );
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct MigrationsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl MigrationsDal<'_, '_> {
+    /// Returns migrations that are not yet applied to the database, in the order they should be applied.
+    /// Errors if the database contains a partially applied migration, or the applied migrations differ
+    /// from ones in the migrations directory.
+    pub async fn get_pending_migrations(&mut self) -> anyhow::Result<Vec<PendingMigration>> {
+        let pending = pending_migrations(self.storage.conn()).await?;
+        Ok(pending.into_iter().map(PendingMigration::from).collect())
+    }
+
+    async fn get_table_locks(
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<BTreeSet<TableLock>> {
+        let schemas = [SHADOW_SCHEMA.to_owned(), "public".to_owned()];
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                pg_class.relname::TEXT AS "table!",
+                pg_locks.mode AS "mode!"
+            FROM
+                pg_locks
+                JOIN pg_class ON pg_locks.relation = pg_class.oid
+                JOIN pg_namespace ON pg_class.relnamespace = pg_namespace.oid
+            WHERE
+                pg_locks.pid = PG_BACKEND_PID()
+                AND pg_locks.granted
+                AND pg_class.relkind IN ('r', 'p')
+                AND pg_namespace.nspname::TEXT = ANY ($1)
+            "#,
+            &schemas
+        )
+        .instrument("get_table_locks")
+        .fetch_all(conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TableLock {
+                table: row.table,
+                mode: row.mode,
+            })
+            .collect())
+    }
+
+    /// Runs pending migrations against a shadow schema without changing the database. The shadow schema contains
+    /// empty copies of all tables in the `public` schema; it is created in a transaction that is rolled back
+    /// after all migrations are run.
+    ///
+    /// # Errors
+    ///
+    /// Propagates an error from the first failed migration.
+    pub async fn dry_run_pending_migrations(&mut self) -> anyhow::Result<Vec<MigrationDryRun>> {
+        let pending = pending_migrations(self.storage.conn()).await?;
+        if pending.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut transaction = self.storage.start_transaction().await?;
+        let sql = format!("CREATE SCHEMA {SHADOW_SCHEMA}");
+        sqlx::query(&sql)
+            .instrument("dry_run_pending_migrations#create_schema")
+            .execute(&mut transaction)
+            .await?;
+        let tables: Vec<String> = sqlx::query_scalar(
+            "SELECT tablename::TEXT FROM pg_tables WHERE schemaname = 'public' ORDER BY tablename",
+        )
+        .instrument("dry_run_pending_migrations#list_tables")
+        .fetch_all(&mut transaction)
+        .await?;
+        for table in &tables {
+            let sql = format!(
+                "CREATE TABLE {SHADOW_SCHEMA}.\"{table}\" (LIKE public.\"{table}\" INCLUDING ALL)"
+            );
+            sqlx::query(&sql)
+                .instrument("dry_run_pending_migrations#copy_table")
+                .with_arg("table", table)
+                .execute(&mut transaction)
+                .await?;
+        }
+        // Unqualified names in migrations will resolve to shadow tables, while types and functions will still
+        // be resolved from the `public` schema.
+        let sql = format!("SET LOCAL search_path TO {SHADOW_SCHEMA}, public");
+        sqlx::query(&sql)
+            .instrument("dry_run_pending_migrations#set_search_path")
+            .execute(&mut transaction)
+            .await?;
+
+        let mut reports = Vec::with_capacity(pending.len());
+        for migration in pending {
+            let locks_before = Self::get_table_locks(&mut transaction).await?;
+            let started_at = Instant::now();
+            transaction
+                .conn()
+                .execute(&*migration.sql)
+                .await
+                .with_context(|| {
+                    format!(
+                        "migration {} ({}) failed in dry run",
+                        migration.version, migration.description
+                    )
+                })?;
+            let duration = started_at.elapsed();
+            let locks = Self::get_table_locks(&mut transaction).await?;
+
+            let report = MigrationDryRun {
+                migration: migration.into(),
+                duration,
+                locks: locks.difference(&locks_before).cloned().collect(),
+            };
+            tracing::info!("Dry-run migration: {report:?}");
+            reports.push(report);
+        }
+
+        transaction.rollback().await?;
+        Ok(reports)
+    }
+
+    /// Applies pending migrations one by one. If a migration fails, all migrations applied by this call are reverted
+    /// using their down migrations, and the error is returned. Migrations are applied under an advisory lock,
+    /// so concurrent calls (including from `sqlx` CLI) are safe.
+    pub async fn apply_pending_migrations(&mut self) -> anyhow::Result<Vec<AppliedMigration>> {
+        let conn = self.storage.conn();
+        conn.lock()
+            .await
+            .context("failed acquiring migrations lock")?;
+        let result = Self::apply_migrations_with_rollback(conn).await;
+        conn.unlock()
+            .await
+            .context("failed releasing migrations lock")?;
+        result
+    }
+
+    async fn apply_migrations_with_rollback(
+        conn: &mut PgConnection,
+    ) -> anyhow::Result<Vec<AppliedMigration>> {
+        let pending = pending_migrations(conn).await?;
+        let mut applied = Vec::with_capacity(pending.len());
+        for migration in pending {
+            match conn.apply(migration).await {
+                Ok(duration) => {
+                    tracing::info!(
+                        "Applied migration {} ({}) in {duration:?}",
+                        migration.version,
+                        migration.description
+                    );
+                    applied.push(AppliedMigration {
+                        migration: migration.into(),
+                        duration,
+                    });
+                }
+                Err(err) => {
+                    tracing::error!(
+                        "Migration {} ({}) failed: {err}; reverting {} migration(s) applied before it",
+                        migration.version,
+                        migration.description,
+                        applied.len()
+                    );
+                    revert_migrations(conn, &applied).await?;
+                    return Err(anyhow::Error::new(err).context(format!(
+                        "failed applying migration {} ({})",
+                        migration.version, migration.description
+                    )));
+                }
+            }
+        }
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[test]
+    fn migrations_are_reversible() {
+        for migration in MIGRATOR.iter() {
+            if migration.migration_type.is_up_migration() {
+                let has_down_migration = MIGRATOR.iter().any(|down| {
+                    down.version == migration.version && down.migration_type.is_down_migration()
+                });
+                assert!(
+                    has_down_migration,
+                    "Migration {} has no down migration",
+                    migration.version
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn no_pending_migrations_in_test_database() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let pending = conn
+            .migrations_dal()
+            .get_pending_migrations()
+            .await
+            .unwrap();
+        assert_eq!(pending, []);
+        let reports = conn
+            .migrations_dal()
+            .dry_run_pending_migrations()
+            .await
+            .unwrap();
+        assert!(reports.is_empty());
+    }
+}
@@ -247,6 +247,7 @@ impl TryFrom<StorageL1Batch> for L1BatchMetadata {
             bootloader_initial_content_commitment: batch
                 .bootloader_initial_content_commitment
                 .map(|v| H256::from_slice(&v)),
+            da_inclusion_data: None,
         })
     }
 }
@@ -393,6 +394,7 @@ impl From<StorageL1BatchDetails> for api::L1BatchDetails {
         api::L1BatchDetails {
             base,
             number: L1BatchNumber(details.number as u32),
+            data_availability: None,
         }
     }
 }
@@ -426,6 +428,8 @@ pub(crate) struct StorageL2BlockHeader {
     /// The formal value of the gas limit for the miniblock.
     /// This value should bound the maximal amount of gas that can be spent by transactions in the miniblock.
     pub gas_limit: Option<i64>,
+    /// Bloom filter for the event logs. `None` for L2 blocks sealed before the filter was introduced.
+    pub logs_bloom: Option<Vec<u8>>,
 }
 
 impl From<StorageL2BlockHeader> for L2BlockHeader {
@@ -468,6 +472,10 @@ impl From<StorageL2BlockHeader> for L2BlockHeader {
             protocol_version,
             virtual_blocks: row.virtual_blocks as u32,
             gas_limit: row.gas_limit.unwrap_or(i64::from(LEGACY_BLOCK_GAS_LIMIT)) as u64,
+            logs_bloom: row
+                .logs_bloom
+                .map(|bloom| H2048::from_slice(&bloom))
+                .unwrap_or_default(),
         }
     }
 }
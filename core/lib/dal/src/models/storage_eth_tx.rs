@@ -66,6 +66,7 @@ pub struct StorageTxHistory {
     // Format a `bincode`-encoded `EthTxBlobSidecar` enum.
     pub blob_sidecar: Option<Vec<u8>>,
     pub blob_base_fee_per_gas: Option<i64>,
+    pub fee_bump_policy: Option<serde_json::Value>,
 }
 
 impl From<StorageEthTx> for EthTx {
@@ -101,6 +102,7 @@ impl From<StorageTxHistory> for TxHistory {
                 .expect("Should rely only on the new txs"),
 
             sent_at_block: history.sent_at_block.map(|block| block as u32),
+            fee_bump_policy: history.fee_bump_policy,
         }
     }
 }
@@ -18,7 +18,7 @@ use zksync_types::{
 };
 use zksync_utils::{bigdecimal_to_u256, h256_to_account_address};
 
-use crate::BigDecimal;
+use crate::{transactions_dal::EXPIRED_TX_ERROR, BigDecimal};
 
 #[derive(Debug, Clone, sqlx::FromRow)]
 #[cfg_attr(test, derive(Default))]
@@ -414,7 +414,9 @@ pub struct StorageTransactionDetails {
 
 impl StorageTransactionDetails {
     fn get_transaction_status(&self) -> TransactionStatus {
-        if self.error.is_some() {
+        if self.miniblock_number.is_none() && self.error.as_deref() == Some(EXPIRED_TX_ERROR) {
+            TransactionStatus::Expired
+        } else if self.error.is_some() {
             TransactionStatus::Failed
         } else if self.eth_execute_tx_hash.is_some() {
             TransactionStatus::Verified
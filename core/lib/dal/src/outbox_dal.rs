@@ -0,0 +1,236 @@
+//! Transactional outbox for block notifications.
+
+use serde::{Deserialize, Serialize};
+use sqlx::types::chrono::{DateTime, Utc};
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+
+use crate::Core;
+
+/// Status of an L1 batch reported in [`BlockNotification`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum L1BatchStatus {
+    /// The batch is sealed by the state keeper.
+    Sealed,
+    /// The batch commitment is confirmed on L1.
+    Committed,
+    /// The batch proof is confirmed on L1.
+    Proven,
+    /// The batch execution is confirmed on L1.
+    Executed,
+}
+
+/// Notification about a block written to the outbox.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BlockNotification {
+    /// New L2 block is sealed, with all its data persisted.
+    NewL2Block {
+        number: L2BlockNumber,
+        hash: H256,
+        timestamp: u64,
+    },
+    /// L1 batch has reached a new status.
+    L1BatchStatus {
+        number: L1BatchNumber,
+        status: L1BatchStatus,
+    },
+}
+
+/// Notification read from the outbox.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OutboxNotification {
+    /// Sequential ID of the notification. IDs are increasing, but may have gaps, and a notification may become visible
+    /// after notifications with greater IDs (if it was inserted by a transaction committed later).
+    pub id: u64,
+    #[serde(flatten)]
+    pub notification: BlockNotification,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+pub struct OutboxDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl OutboxDal<'_, '_> {
+    /// Inserts notifications into the outbox. To guarantee that dispatched notifications only refer to committed data,
+    /// this should be called in the same transaction that persists the data.
+    pub async fn insert_notifications(
+        &mut self,
+        notifications: &[BlockNotification],
+    ) -> DalResult<()> {
+        let notifications_json: Vec<_> = notifications
+            .iter()
+            .map(|notification| {
+                serde_json::to_value(notification).expect("failed serializing block notification")
+            })
+            .collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                block_notifications_outbox (notification, created_at)
+            SELECT
+                u.notification,
+                NOW()
+            FROM
+                UNNEST($1::JSONB[]) WITH ORDINALITY AS u (notification, ord)
+            ORDER BY
+                u.ord
+            "#,
+            &notifications_json
+        )
+        .instrument("insert_notifications")
+        .with_arg("notifications.len", &notifications.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` oldest notifications in the outbox.
+    pub async fn get_pending_notifications(
+        &mut self,
+        limit: usize,
+    ) -> DalResult<Vec<OutboxNotification>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                id,
+                notification,
+                created_at
+            FROM
+                block_notifications_outbox
+            ORDER BY
+                id
+            LIMIT
+                $1
+            "#,
+            limit as i64
+        )
+        .instrument("get_pending_notifications")
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| OutboxNotification {
+                id: row.id as u64,
+                notification: serde_json::from_value(row.notification)
+                    .expect("invalid block notification in outbox"),
+                created_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+            })
+            .collect())
+    }
+
+    /// Removes notifications with the specified IDs. Returns the number of removed notifications.
+    ///
+    /// Notifications must be removed by their exact IDs rather than by an ID range, since a notification
+    /// with a lesser ID may be committed after the removed notifications were read.
+    pub async fn remove_notifications(&mut self, ids: &[u64]) -> DalResult<u64> {
+        let ids_i64: Vec<_> = ids.iter().map(|&id| id as i64).collect();
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM block_notifications_outbox
+            WHERE
+                id = ANY ($1)
+            "#,
+            &ids_i64
+        )
+        .instrument("remove_notifications")
+        .with_arg("ids.len", &ids.len())
+        .execute(self.storage)
+        .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[test]
+    fn serializing_notifications() {
+        let notification = BlockNotification::NewL2Block {
+            number: L2BlockNumber(1),
+            hash: H256::repeat_byte(1),
+            timestamp: 100,
+        };
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "type": "new_l2_block",
+                "number": 1,
+                "hash": format!("{:?}", H256::repeat_byte(1)),
+                "timestamp": 100,
+            })
+        );
+
+        let notification = BlockNotification::L1BatchStatus {
+            number: L1BatchNumber(3),
+            status: L1BatchStatus::Proven,
+        };
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "type": "l1_batch_status", "number": 3, "status": "proven" })
+        );
+    }
+
+    #[tokio::test]
+    async fn outbox_basics() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let notifications: Vec<_> = (1..=3)
+            .map(|number| BlockNotification::NewL2Block {
+                number: L2BlockNumber(number),
+                hash: H256::from_low_u64_be(number.into()),
+                timestamp: number.into(),
+            })
+            .chain([BlockNotification::L1BatchStatus {
+                number: L1BatchNumber(1),
+                status: L1BatchStatus::Sealed,
+            }])
+            .collect();
+        conn.outbox_dal()
+            .insert_notifications(&notifications)
+            .await
+            .unwrap();
+
+        let pending = conn
+            .outbox_dal()
+            .get_pending_notifications(10)
+            .await
+            .unwrap();
+        let pending_notifications: Vec<_> = pending
+            .iter()
+            .map(|notification| notification.notification.clone())
+            .collect();
+        assert_eq!(pending_notifications, notifications);
+        assert!(pending.windows(2).all(|pair| pair[0].id < pair[1].id));
+
+        let removed_count = conn
+            .outbox_dal()
+            .remove_notifications(&[pending[0].id, pending[2].id])
+            .await
+            .unwrap();
+        assert_eq!(removed_count, 2);
+        let pending = conn
+            .outbox_dal()
+            .get_pending_notifications(10)
+            .await
+            .unwrap();
+        let pending_notifications: Vec<_> = pending
+            .iter()
+            .map(|notification| notification.notification.clone())
+            .collect();
+        assert_eq!(
+            pending_notifications,
+            [notifications[1].clone(), notifications[3].clone()]
+        );
+    }
+}
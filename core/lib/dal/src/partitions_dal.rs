@@ -0,0 +1,332 @@
+//! Management of range partitions for the largest tables.
+
+use std::ops;
+
+use zksync_db_connection::{
+    connection::Connection,
+    error::DalResult,
+    instrument::{InstrumentExt, Instrumented},
+};
+use zksync_types::L2BlockNumber;
+
+use crate::{Core, CoreDal};
+
+/// Table range-partitioned by the L2 block number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartitionedTable {
+    Events,
+}
+
+impl PartitionedTable {
+    pub const ALL: [Self; 1] = [Self::Events];
+
+    /// Returns the name of the partitioned (parent) table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Events => "events",
+        }
+    }
+
+    fn default_partition_name(self) -> String {
+        format!("{}_default", self.name())
+    }
+}
+
+/// Partition of a [`PartitionedTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TablePartition {
+    pub name: String,
+    /// L2 blocks covered by the partition. `None` for the default partition.
+    pub l2_blocks: Option<ops::Range<L2BlockNumber>>,
+}
+
+/// Parses partition bounds as output by Postgres, e.g. `FOR VALUES FROM ('0') TO ('100')`.
+fn parse_partition_bounds(bounds: &str) -> Option<ops::Range<L2BlockNumber>> {
+    fn parse_value(value: &str) -> Option<L2BlockNumber> {
+        let value = value.trim().strip_prefix('(')?.strip_suffix(')')?;
+        let value = value.trim_matches('\'');
+        let value = if value == "MINVALUE" {
+            0
+        } else {
+            value.parse().ok()?
+        };
+        Some(L2BlockNumber(value))
+    }
+
+    let bounds = bounds.strip_prefix("FOR VALUES FROM ")?;
+    let (start, end) = bounds.split_once(" TO ")?;
+    Some(parse_value(start)?..parse_value(end)?)
+}
+
+fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+#[derive(Debug)]
+pub struct PartitionsDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl PartitionsDal<'_, '_> {
+    /// Returns all partitions of the specified table ordered by name. Partitions with unparsable bounds
+    /// (e.g., created manually with a non-range bound) are skipped.
+    pub async fn get_partitions(
+        &mut self,
+        table: PartitionedTable,
+    ) -> DalResult<Vec<TablePartition>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                child.relname::TEXT AS "name!",
+                PG_GET_EXPR(child.relpartbound, child.oid) AS "bounds!"
+            FROM
+                pg_inherits
+                JOIN pg_class parent ON pg_inherits.inhparent = parent.oid
+                JOIN pg_class child ON pg_inherits.inhrelid = child.oid
+            WHERE
+                parent.relname::TEXT = $1
+            ORDER BY
+                child.relname
+            "#,
+            table.name()
+        )
+        .instrument("get_partitions")
+        .with_arg("table", &table)
+        .fetch_all(self.storage)
+        .await?;
+
+        let partitions = rows.into_iter().filter_map(|row| {
+            let l2_blocks = if row.bounds == "DEFAULT" {
+                None
+            } else {
+                Some(parse_partition_bounds(&row.bounds)?)
+            };
+            Some(TablePartition {
+                name: row.name,
+                l2_blocks,
+            })
+        });
+        Ok(partitions.collect())
+    }
+
+    /// Creates a partition for the specified L2 blocks. The table is locked for writes while the partition is created,
+    /// and the default partition is checked under the lock; if it already contains any of the blocks (e.g., because
+    /// they were inserted concurrently), the partition is not created and `None` is returned.
+    /// Otherwise, returns the name of the created partition.
+    pub async fn create_partition(
+        &mut self,
+        table: PartitionedTable,
+        l2_blocks: ops::Range<L2BlockNumber>,
+    ) -> DalResult<Option<String>> {
+        let instrumentation = Instrumented::new("create_partition")
+            .with_arg("table", &table)
+            .with_arg("l2_blocks", &l2_blocks);
+        let mut transaction = self.storage.start_transaction().await?;
+
+        // Blocks inserts into all partitions of the table until the transaction is finished; reads are not blocked.
+        let sql = format!("LOCK TABLE {} IN SHARE ROW EXCLUSIVE MODE", table.name());
+        let query = sqlx::query(&sql);
+        instrumentation
+            .clone()
+            .with(query)
+            .execute(&mut transaction)
+            .await?;
+
+        let max_default_l2_block = transaction
+            .partitions_dal()
+            .get_max_l2_block_in_default_partition(table)
+            .await?;
+        if max_default_l2_block.is_some_and(|number| number >= l2_blocks.start) {
+            return Ok(None);
+        }
+
+        let name = format!("{}_{}_{}", table.name(), l2_blocks.start, l2_blocks.end);
+        let sql = format!(
+            "CREATE TABLE {} PARTITION OF {} FOR VALUES FROM ({}) TO ({})",
+            quote_ident(&name),
+            table.name(),
+            l2_blocks.start,
+            l2_blocks.end
+        );
+        let query = sqlx::query(&sql);
+        instrumentation
+            .with(query)
+            .execute(&mut transaction)
+            .await?;
+        transaction.commit().await?;
+        Ok(Some(name))
+    }
+
+    /// Returns the greatest L2 block stored in the default partition of the specified table.
+    pub async fn get_max_l2_block_in_default_partition(
+        &mut self,
+        table: PartitionedTable,
+    ) -> DalResult<Option<L2BlockNumber>> {
+        let sql = format!(
+            "SELECT MAX(miniblock_number) FROM {}",
+            quote_ident(&table.default_partition_name())
+        );
+        let max_l2_block: Option<i64> = sqlx::query_scalar(&sql)
+            .instrument("get_max_l2_block_in_default_partition")
+            .with_arg("table", &table)
+            .fetch_one(self.storage)
+            .await?;
+        Ok(max_l2_block.map(|number| L2BlockNumber(number as u32)))
+    }
+
+    /// Detaches the specified partition from the table and drops it, together with all data in it.
+    pub async fn drop_partition(
+        &mut self,
+        table: PartitionedTable,
+        partition_name: &str,
+    ) -> DalResult<()> {
+        let instrumentation = Instrumented::new("drop_partition")
+            .with_arg("table", &table)
+            .with_arg("partition_name", &partition_name);
+        let mut transaction = self.storage.start_transaction().await?;
+
+        let sql = format!(
+            "ALTER TABLE {} DETACH PARTITION {}",
+            table.name(),
+            quote_ident(partition_name)
+        );
+        let query = sqlx::query(&sql);
+        instrumentation
+            .clone()
+            .with(query)
+            .execute(&mut transaction)
+            .await?;
+
+        let sql = format!("DROP TABLE {}", quote_ident(partition_name));
+        let query = sqlx::query(&sql);
+        instrumentation
+            .with(query)
+            .execute(&mut transaction)
+            .await?;
+        transaction.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_db_connection::connection_pool::ConnectionPool;
+    use zksync_types::{
+        tx::IncludedTxLocation, Address, L1BatchNumber, ProtocolVersion, VmEvent, H256,
+    };
+
+    use super::*;
+    use crate::tests::create_l2_block_header;
+
+    #[test]
+    fn parsing_partition_bounds() {
+        assert_eq!(
+            parse_partition_bounds("FOR VALUES FROM ('0') TO ('100')"),
+            Some(L2BlockNumber(0)..L2BlockNumber(100))
+        );
+        assert_eq!(
+            parse_partition_bounds("FOR VALUES FROM (100) TO (200)"),
+            Some(L2BlockNumber(100)..L2BlockNumber(200))
+        );
+        assert_eq!(
+            parse_partition_bounds("FOR VALUES FROM (MINVALUE) TO ('5')"),
+            Some(L2BlockNumber(0)..L2BlockNumber(5))
+        );
+        assert_eq!(parse_partition_bounds("DEFAULT"), None);
+        assert_eq!(parse_partition_bounds("FOR VALUES IN (1, 2)"), None);
+    }
+
+    #[tokio::test]
+    async fn managing_partitions() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+
+        for table in PartitionedTable::ALL {
+            let default_partition = TablePartition {
+                name: table.default_partition_name(),
+                l2_blocks: None,
+            };
+            let partitions = conn.partitions_dal().get_partitions(table).await.unwrap();
+            assert_eq!(partitions, [default_partition.clone()]);
+            let max_l2_block = conn
+                .partitions_dal()
+                .get_max_l2_block_in_default_partition(table)
+                .await
+                .unwrap();
+            assert_eq!(max_l2_block, None);
+
+            let name = conn
+                .partitions_dal()
+                .create_partition(table, L2BlockNumber(0)..L2BlockNumber(100))
+                .await
+                .unwrap()
+                .expect("partition not created");
+            assert_eq!(name, format!("{}_0_100", table.name()));
+            let partitions = conn.partitions_dal().get_partitions(table).await.unwrap();
+            assert_eq!(
+                partitions,
+                [
+                    TablePartition {
+                        name: name.clone(),
+                        l2_blocks: Some(L2BlockNumber(0)..L2BlockNumber(100)),
+                    },
+                    default_partition.clone(),
+                ]
+            );
+            // Overlapping partitions are not allowed.
+            conn.partitions_dal()
+                .create_partition(table, L2BlockNumber(50)..L2BlockNumber(150))
+                .await
+                .unwrap_err();
+
+            conn.partitions_dal()
+                .drop_partition(table, &name)
+                .await
+                .unwrap();
+            let partitions = conn.partitions_dal().get_partitions(table).await.unwrap();
+            assert_eq!(partitions, [default_partition]);
+        }
+    }
+
+    #[tokio::test]
+    async fn partition_is_not_created_for_l2_blocks_in_default_partition() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(5))
+            .await
+            .unwrap();
+        let location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(1),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::repeat_byte(1),
+        };
+        let event = VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: Address::repeat_byte(2),
+            indexed_topics: vec![],
+            value: vec![],
+        };
+        conn.events_dal()
+            .save_events(L2BlockNumber(5), &[(location, vec![&event])])
+            .await
+            .unwrap();
+
+        let table = PartitionedTable::Events;
+        let name = conn
+            .partitions_dal()
+            .create_partition(table, L2BlockNumber(0)..L2BlockNumber(10))
+            .await
+            .unwrap();
+        assert_eq!(name, None);
+        let name = conn
+            .partitions_dal()
+            .create_partition(table, L2BlockNumber(10)..L2BlockNumber(20))
+            .await
+            .unwrap();
+        assert_eq!(name.as_deref(), Some("events_10_20"));
+    }
+}
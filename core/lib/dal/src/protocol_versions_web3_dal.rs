@@ -1,5 +1,11 @@
+use std::collections::HashMap;
+
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::api::ProtocolVersion;
+use zksync_types::{
+    api::{ProtocolVersion, ProtocolVersionInfo, ProtocolVersionPatchInfo},
+    protocol_version::{L1VerifierConfig, VerifierParams},
+    L1BatchNumber, H256,
+};
 
 use crate::{models::storage_protocol_version::StorageApiProtocolVersion, Core, CoreDal};
 
@@ -48,4 +54,104 @@ impl ProtocolVersionsWeb3Dal<'_, '_> {
             .await
             .map(|v| v.unwrap())
     }
+
+    /// Returns all known protocol versions together with their patches and the first L1 batches
+    /// executed with each version, in the ascending version order.
+    pub async fn get_protocol_version_history(&mut self) -> DalResult<Vec<ProtocolVersionInfo>> {
+        let patch_rows = sqlx::query!(
+            r#"
+            SELECT
+                minor,
+                patch,
+                recursion_scheduler_level_vk_hash,
+                recursion_node_level_vk_hash,
+                recursion_leaf_level_vk_hash,
+                recursion_circuits_set_vks_hash
+            FROM
+                protocol_patches
+            ORDER BY
+                minor,
+                patch
+            "#
+        )
+        .instrument("get_protocol_version_history#patches")
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut patches_by_minor = HashMap::<i32, Vec<_>>::new();
+        for row in patch_rows {
+            let verifier_config = L1VerifierConfig {
+                params: VerifierParams {
+                    recursion_node_level_vk_hash: H256::from_slice(
+                        &row.recursion_node_level_vk_hash,
+                    ),
+                    recursion_leaf_level_vk_hash: H256::from_slice(
+                        &row.recursion_leaf_level_vk_hash,
+                    ),
+                    recursion_circuits_set_vks_hash: H256::from_slice(
+                        &row.recursion_circuits_set_vks_hash,
+                    ),
+                },
+                recursion_scheduler_level_vk_hash: H256::from_slice(
+                    &row.recursion_scheduler_level_vk_hash,
+                ),
+            };
+            patches_by_minor
+                .entry(row.minor)
+                .or_default()
+                .push(ProtocolVersionPatchInfo {
+                    patch: row.patch as u32,
+                    verifier_config,
+                });
+        }
+
+        let version_rows = sqlx::query!(
+            r#"
+            SELECT
+                protocol_versions.id AS "minor!",
+                protocol_versions.timestamp,
+                protocol_versions.bootloader_code_hash,
+                protocol_versions.default_account_code_hash,
+                protocol_versions.upgrade_tx_hash,
+                first_l1_batch.number AS "activation_l1_batch?",
+                first_l1_batch.timestamp AS "activation_timestamp?"
+            FROM
+                protocol_versions
+                LEFT JOIN LATERAL (
+                    SELECT
+                        number,
+                        timestamp
+                    FROM
+                        l1_batches
+                    WHERE
+                        l1_batches.protocol_version = protocol_versions.id
+                    ORDER BY
+                        number
+                    LIMIT
+                        1
+                ) first_l1_batch ON TRUE
+            ORDER BY
+                protocol_versions.id
+            "#
+        )
+        .instrument("get_protocol_version_history")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(version_rows
+            .into_iter()
+            .map(|row| ProtocolVersionInfo {
+                minor_version: row.minor as u16,
+                timestamp: row.timestamp as u64,
+                bootloader_code_hash: H256::from_slice(&row.bootloader_code_hash),
+                default_account_code_hash: H256::from_slice(&row.default_account_code_hash),
+                l2_system_upgrade_tx_hash: row.upgrade_tx_hash.as_deref().map(H256::from_slice),
+                activation_l1_batch: row
+                    .activation_l1_batch
+                    .map(|number| L1BatchNumber(number as u32)),
+                activation_timestamp: row.activation_timestamp.map(|timestamp| timestamp as u64),
+                patches: patches_by_minor.remove(&row.minor).unwrap_or_default(),
+            })
+            .collect())
+    }
 }
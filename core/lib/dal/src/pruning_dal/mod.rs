@@ -1,4 +1,4 @@
-use std::ops;
+use std::{ops, time::Duration};
 
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
 use zksync_types::{L1BatchNumber, L2BlockNumber};
@@ -22,6 +22,85 @@ pub struct PruningInfo {
     pub last_hard_pruned_l2_block: Option<L2BlockNumber>,
 }
 
+impl PruningInfo {
+    /// Returns `true` if any data was pruned from the storage, including as a result of snapshot recovery.
+    pub fn is_pruned(&self) -> bool {
+        self.last_soft_pruned_l1_batch.is_some()
+    }
+
+    /// Returns the first L2 block retained in the storage, i.e., the retention horizon for L2 blocks.
+    pub fn first_retained_l2_block(&self) -> L2BlockNumber {
+        self.last_soft_pruned_l2_block
+            .map_or(L2BlockNumber(0), |number| number + 1)
+    }
+
+    /// Returns the first L1 batch retained in the storage, i.e., the retention horizon for L1 batches.
+    pub fn first_retained_l1_batch(&self) -> L1BatchNumber {
+        self.last_soft_pruned_l1_batch
+            .map_or(L1BatchNumber(0), |number| number + 1)
+    }
+
+    /// Checks that the specified L2 block is not pruned.
+    pub fn ensure_l2_block_retained(&self, number: L2BlockNumber) -> Result<(), PrunedDataError> {
+        let first_retained = self.first_retained_l2_block();
+        if number < first_retained {
+            return Err(PrunedDataError::L2Block {
+                requested: number,
+                first_retained,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the specified L1 batch is not pruned.
+    pub fn ensure_l1_batch_retained(&self, number: L1BatchNumber) -> Result<(), PrunedDataError> {
+        let first_retained = self.first_retained_l1_batch();
+        if number < first_retained {
+            return Err(PrunedDataError::L1Batch {
+                requested: number,
+                first_retained,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when the requested data is beyond the retention horizon of the node, as opposed to
+/// the data not existing yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum PrunedDataError {
+    #[error("L2 block #{requested} is pruned; first retained L2 block is #{first_retained}")]
+    L2Block {
+        requested: L2BlockNumber,
+        first_retained: L2BlockNumber,
+    },
+    #[error("L1 batch #{requested} is pruned; first retained L1 batch is #{first_retained}")]
+    L1Batch {
+        requested: L1BatchNumber,
+        first_retained: L1BatchNumber,
+    },
+}
+
+/// Storage mode of the node determining which historical data it retains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageMode {
+    /// All data since genesis is retained.
+    Archive,
+    /// Data older than the retention horizon is pruned.
+    Pruned {
+        /// Minimum age of data eligible for pruning. `None` if data is not retained based on its age,
+        /// or if the retention period is unknown.
+        data_retention: Option<Duration>,
+    },
+}
+
+#[derive(Debug, sqlx::Type)]
+#[sqlx(type_name = "storage_mode")]
+enum StorageModeType {
+    Archive,
+    Pruned,
+}
+
 /// Statistics about a single hard pruning iteration.
 #[derive(Debug, Default)]
 pub struct HardPruningStats {
@@ -103,6 +182,85 @@ impl PruningDal<'_, '_> {
         Ok(pruning_info.unwrap_or_default())
     }
 
+    /// Returns the storage mode of the node. If the mode was never set explicitly, it is inferred
+    /// from the pruning log: the node is considered an archive one iff no data was ever pruned from it.
+    pub async fn get_storage_mode(&mut self) -> DalResult<StorageMode> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                mode AS "mode: StorageModeType",
+                data_retention_sec
+            FROM
+                node_storage_mode
+            "#
+        )
+        .instrument("get_storage_mode")
+        .fetch_optional(self.storage)
+        .await?;
+
+        if let Some(row) = row {
+            return Ok(match row.mode {
+                StorageModeType::Archive => StorageMode::Archive,
+                StorageModeType::Pruned => StorageMode::Pruned {
+                    data_retention: row
+                        .data_retention_sec
+                        .map(|secs| Duration::from_secs(secs as u64)),
+                },
+            });
+        }
+        let pruning_info = self.get_pruning_info().await?;
+        Ok(if pruning_info.is_pruned() {
+            StorageMode::Pruned {
+                data_retention: None,
+            }
+        } else {
+            StorageMode::Archive
+        })
+    }
+
+    /// Sets the storage mode of the node.
+    ///
+    /// # Errors
+    ///
+    /// Errors if switching to [`StorageMode::Archive`] after any data was pruned, or on a DB error.
+    pub async fn set_storage_mode(&mut self, mode: StorageMode) -> anyhow::Result<()> {
+        let (mode_type, data_retention) = match mode {
+            StorageMode::Archive => {
+                let pruning_info = self.get_pruning_info().await?;
+                anyhow::ensure!(
+                    !pruning_info.is_pruned(),
+                    "cannot switch node to archive mode: L1 batches before #{} are pruned",
+                    pruning_info.first_retained_l1_batch()
+                );
+                (StorageModeType::Archive, None)
+            }
+            StorageMode::Pruned { data_retention } => (StorageModeType::Pruned, data_retention),
+        };
+        let data_retention_sec = data_retention.map(|retention| retention.as_secs() as i64);
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                node_storage_mode (mode, data_retention_sec, created_at, updated_at)
+            VALUES
+                ($1, $2, NOW(), NOW())
+            ON CONFLICT (singleton) DO
+            UPDATE
+            SET
+                mode = excluded.mode,
+                data_retention_sec = excluded.data_retention_sec,
+                updated_at = NOW()
+            "#,
+            mode_type as StorageModeType,
+            data_retention_sec
+        )
+        .instrument("set_storage_mode")
+        .with_arg("mode", &mode)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn soft_prune_batches_range(
         &mut self,
         last_l1_batch_to_prune: L1BatchNumber,
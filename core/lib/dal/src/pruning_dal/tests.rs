@@ -520,3 +520,94 @@ async fn transactions_are_handled_correctly_after_pruning() {
         .unwrap();
     assert!(transaction_details.is_none(), "{transaction_details:?}");
 }
+
+#[tokio::test]
+async fn switching_storage_mode() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+
+    let mode = conn.pruning_dal().get_storage_mode().await.unwrap();
+    assert_eq!(mode, StorageMode::Archive);
+    let pruned_mode = StorageMode::Pruned {
+        data_retention: Some(Duration::from_secs(3_600)),
+    };
+    conn.pruning_dal()
+        .set_storage_mode(pruned_mode)
+        .await
+        .unwrap();
+    let mode = conn.pruning_dal().get_storage_mode().await.unwrap();
+    assert_eq!(mode, pruned_mode);
+
+    // Switching back to the archive mode is allowed as long as no data is pruned.
+    conn.pruning_dal()
+        .set_storage_mode(StorageMode::Archive)
+        .await
+        .unwrap();
+    let mode = conn.pruning_dal().get_storage_mode().await.unwrap();
+    assert_eq!(mode, StorageMode::Archive);
+
+    conn.pruning_dal()
+        .soft_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11))
+        .await
+        .unwrap();
+    let err = conn
+        .pruning_dal()
+        .set_storage_mode(StorageMode::Archive)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("pruned"), "{err}");
+    conn.pruning_dal()
+        .set_storage_mode(pruned_mode)
+        .await
+        .unwrap();
+    let err = conn
+        .pruning_dal()
+        .set_storage_mode(StorageMode::Archive)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("pruned"), "{err}");
+    let mode = conn.pruning_dal().get_storage_mode().await.unwrap();
+    assert_eq!(mode, pruned_mode);
+}
+
+#[tokio::test]
+async fn inferring_storage_mode_from_pruning_log() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut conn = pool.connection().await.unwrap();
+    conn.pruning_dal()
+        .soft_prune_batches_range(L1BatchNumber(5), L2BlockNumber(11))
+        .await
+        .unwrap();
+
+    let mode = conn.pruning_dal().get_storage_mode().await.unwrap();
+    assert_eq!(
+        mode,
+        StorageMode::Pruned {
+            data_retention: None
+        }
+    );
+
+    let pruning_info = conn.pruning_dal().get_pruning_info().await.unwrap();
+    assert_eq!(pruning_info.first_retained_l1_batch(), L1BatchNumber(6));
+    assert_eq!(pruning_info.first_retained_l2_block(), L2BlockNumber(12));
+    pruning_info
+        .ensure_l2_block_retained(L2BlockNumber(12))
+        .unwrap();
+    assert_eq!(
+        pruning_info.ensure_l2_block_retained(L2BlockNumber(11)),
+        Err(PrunedDataError::L2Block {
+            requested: L2BlockNumber(11),
+            first_retained: L2BlockNumber(12),
+        })
+    );
+    pruning_info
+        .ensure_l1_batch_retained(L1BatchNumber(6))
+        .unwrap();
+    assert_eq!(
+        pruning_info.ensure_l1_batch_retained(L1BatchNumber(0)),
+        Err(PrunedDataError::L1Batch {
+            requested: L1BatchNumber(0),
+            first_retained: L1BatchNumber(6),
+        })
+    );
+}
@@ -0,0 +1,236 @@
+//! Settlement layer switches of the chain announced on L1.
+
+use zksync_db_connection::{
+    connection::Connection,
+    error::{DalResult, SqlxContext},
+    instrument::InstrumentExt,
+};
+use zksync_types::{
+    settlement::{SettlementLayer, SettlementLayerSwitch, StoredSettlementLayerSwitch},
+    Address, L1BlockNumber, L1ChainId, L2ChainId,
+};
+
+use crate::Core;
+
+#[derive(Debug)]
+struct StorageSettlementLayerSwitch {
+    id: i32,
+    chain_id: i64,
+    is_gateway: bool,
+    diamond_proxy_addr: Vec<u8>,
+    validator_timelock_addr: Vec<u8>,
+    l1_block_number: i32,
+    event_index: i32,
+    applied_after_eth_tx_id: Option<i32>,
+}
+
+impl TryFrom<StorageSettlementLayerSwitch> for StoredSettlementLayerSwitch {
+    type Error = sqlx::Error;
+
+    fn try_from(row: StorageSettlementLayerSwitch) -> Result<Self, Self::Error> {
+        let chain_id = row.chain_id as u64;
+        let settlement_layer = if row.is_gateway {
+            SettlementLayer::Gateway(L2ChainId::try_from(chain_id).decode_column("chain_id")?)
+        } else {
+            SettlementLayer::L1(L1ChainId(chain_id))
+        };
+        Ok(Self {
+            id: row.id as u32,
+            switch: SettlementLayerSwitch {
+                settlement_layer,
+                diamond_proxy_addr: Address::from_slice(&row.diamond_proxy_addr),
+                validator_timelock_addr: Address::from_slice(&row.validator_timelock_addr),
+                l1_block_number: L1BlockNumber(row.l1_block_number as u32),
+                event_index: row.event_index as u32,
+            },
+            applied_after_eth_tx_id: row.applied_after_eth_tx_id.map(|id| id as u32),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct SettlementLayerDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl SettlementLayerDal<'_, '_> {
+    /// Inserts a settlement layer switch. Switches already present in the database (e.g., ones
+    /// reprocessed by eth_watch after a restart) are ignored.
+    pub async fn insert_switch(&mut self, switch: &SettlementLayerSwitch) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                settlement_layer_switches (
+                    chain_id,
+                    is_gateway,
+                    diamond_proxy_addr,
+                    validator_timelock_addr,
+                    l1_block_number,
+                    event_index,
+                    created_at
+                )
+            VALUES
+                ($1, $2, $3, $4, $5, $6, NOW())
+            ON CONFLICT (l1_block_number, event_index) DO NOTHING
+            "#,
+            switch.settlement_layer.chain_id() as i64,
+            switch.settlement_layer.is_gateway(),
+            switch.diamond_proxy_addr.as_bytes(),
+            switch.validator_timelock_addr.as_bytes(),
+            switch.l1_block_number.0 as i32,
+            switch.event_index as i32
+        )
+        .instrument("insert_switch")
+        .with_arg("switch", switch)
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the latest announced settlement layer switch, regardless of whether it's applied.
+    pub async fn get_latest_switch(&mut self) -> DalResult<Option<StoredSettlementLayerSwitch>> {
+        sqlx::query_as!(
+            StorageSettlementLayerSwitch,
+            r#"
+            SELECT
+                id,
+                chain_id,
+                is_gateway,
+                diamond_proxy_addr,
+                validator_timelock_addr,
+                l1_block_number,
+                event_index,
+                applied_after_eth_tx_id
+            FROM
+                settlement_layer_switches
+            ORDER BY
+                l1_block_number DESC,
+                event_index DESC
+            LIMIT
+                1
+            "#
+        )
+        .try_map(StoredSettlementLayerSwitch::try_from)
+        .instrument("get_latest_switch")
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    /// Returns the latest settlement layer switch applied by eth_sender. If there are no applied switches,
+    /// the chain settles on L1.
+    pub async fn get_latest_applied_switch(
+        &mut self,
+    ) -> DalResult<Option<StoredSettlementLayerSwitch>> {
+        sqlx::query_as!(
+            StorageSettlementLayerSwitch,
+            r#"
+            SELECT
+                id,
+                chain_id,
+                is_gateway,
+                diamond_proxy_addr,
+                validator_timelock_addr,
+                l1_block_number,
+                event_index,
+                applied_after_eth_tx_id
+            FROM
+                settlement_layer_switches
+            WHERE
+                applied_after_eth_tx_id IS NOT NULL
+            ORDER BY
+                l1_block_number DESC,
+                event_index DESC
+            LIMIT
+                1
+            "#
+        )
+        .try_map(StoredSettlementLayerSwitch::try_from)
+        .instrument("get_latest_applied_switch")
+        .fetch_optional(self.storage)
+        .await
+    }
+
+    /// Marks the specified switch as applied. All `eth_txs` created after this call are settled on the new layer.
+    /// Returns the ID of the last transaction settled on the previous layer (0 if there are no transactions),
+    /// or `None` if the switch doesn't exist or is already applied.
+    pub async fn mark_switch_applied(&mut self, id: u32) -> DalResult<Option<u32>> {
+        let row = sqlx::query!(
+            r#"
+            UPDATE settlement_layer_switches
+            SET
+                applied_after_eth_tx_id = (
+                    SELECT
+                        COALESCE(MAX(id), 0)
+                    FROM
+                        eth_txs
+                ),
+                applied_at = NOW()
+            WHERE
+                id = $1
+                AND applied_after_eth_tx_id IS NULL
+            RETURNING
+                applied_after_eth_tx_id AS "applied_after_eth_tx_id!"
+            "#,
+            id as i32
+        )
+        .instrument("mark_switch_applied")
+        .with_arg("id", &id)
+        .fetch_optional(self.storage)
+        .await?;
+        Ok(row.map(|row| row.applied_after_eth_tx_id as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    fn mock_switch(
+        l1_block_number: u32,
+        settlement_layer: SettlementLayer,
+    ) -> SettlementLayerSwitch {
+        SettlementLayerSwitch {
+            settlement_layer,
+            diamond_proxy_addr: Address::repeat_byte(1),
+            validator_timelock_addr: Address::repeat_byte(2),
+            l1_block_number: L1BlockNumber(l1_block_number),
+            event_index: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn settlement_layer_switches_basics() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let dal = &mut conn.settlement_layer_dal();
+        assert_eq!(dal.get_latest_switch().await.unwrap(), None);
+
+        let gateway_switch = mock_switch(10, SettlementLayer::Gateway(L2ChainId::from(505)));
+        dal.insert_switch(&gateway_switch).await.unwrap();
+        // Repeated insertions must be no-ops.
+        dal.insert_switch(&gateway_switch).await.unwrap();
+
+        let latest = dal.get_latest_switch().await.unwrap().unwrap();
+        assert_eq!(latest.switch, gateway_switch);
+        assert!(!latest.is_applied());
+        assert_eq!(dal.get_latest_applied_switch().await.unwrap(), None);
+
+        let applied_after = dal.mark_switch_applied(latest.id).await.unwrap();
+        assert_eq!(applied_after, Some(0));
+        // Repeated applications must be no-ops.
+        let applied_after = dal.mark_switch_applied(latest.id).await.unwrap();
+        assert_eq!(applied_after, None);
+        let applied = dal.get_latest_applied_switch().await.unwrap().unwrap();
+        assert_eq!(applied.id, latest.id);
+        assert_eq!(applied.applied_after_eth_tx_id, Some(0));
+
+        let l1_switch = mock_switch(20, SettlementLayer::L1(L1ChainId(9)));
+        dal.insert_switch(&l1_switch).await.unwrap();
+        let latest = dal.get_latest_switch().await.unwrap().unwrap();
+        assert_eq!(latest.switch, l1_switch);
+        assert!(!latest.is_applied());
+        let applied = dal.get_latest_applied_switch().await.unwrap().unwrap();
+        assert_eq!(applied.switch, gateway_switch);
+    }
+}
@@ -3,6 +3,7 @@ use std::{collections::HashMap, ops, time::Instant};
 use sqlx::types::chrono::Utc;
 use zksync_db_connection::{
     connection::Connection,
+    copy::BinaryCopyBuffer,
     error::DalResult,
     instrument::{CopyStatement, InstrumentExt},
     write_str, writeln_str,
@@ -74,6 +75,53 @@ impl StorageLogsDal<'_, '_> {
         copy.send(buffer.as_bytes()).await
     }
 
+    /// Inserts storage logs for an L2 block using a binary `COPY`. This is equivalent to [`Self::insert_storage_logs()`],
+    /// but is more efficient for large blocks since data is streamed to Postgres without conversions.
+    pub async fn copy_storage_logs(
+        &mut self,
+        block_number: L2BlockNumber,
+        logs: &[(H256, Vec<StorageLog>)],
+    ) -> DalResult<()> {
+        let logs_len = logs.len();
+        let mut copy = CopyStatement::new(
+            "COPY storage_logs(
+                hashed_key, address, key, value, operation_number, tx_hash, miniblock_number,
+                created_at, updated_at
+            )
+            FROM STDIN WITH (FORMAT BINARY)",
+        )
+        .instrument("copy_storage_logs")
+        .with_arg("block_number", &block_number)
+        .with_arg("logs.len", &logs_len)
+        .start(self.storage)
+        .await?;
+
+        let mut buffer = BinaryCopyBuffer::new();
+        let now = Utc::now();
+        let mut operation_number = 0_i32;
+        for (tx_hash, logs) in logs {
+            for log in logs {
+                buffer
+                    .start_row(9)
+                    .push_bytea(log.key.hashed_key().as_bytes())
+                    .push_bytea(log.key.address().as_bytes())
+                    .push_bytea(log.key.key().as_bytes())
+                    .push_bytea(log.value.as_bytes())
+                    .push_int4(operation_number)
+                    .push_bytea(tx_hash.as_bytes())
+                    .push_int8(block_number.0.into())
+                    .push_timestamp(now)
+                    .push_timestamp(now);
+
+                if buffer.len() >= BinaryCopyBuffer::CHUNK_SIZE {
+                    copy.send_chunk(&buffer.take_chunk()).await?;
+                }
+                operation_number += 1;
+            }
+        }
+        copy.send(&buffer.finish()).await
+    }
+
     pub async fn insert_storage_logs_from_snapshot(
         &mut self,
         l2_block_number: L2BlockNumber,
@@ -555,6 +603,50 @@ impl StorageLogsDal<'_, '_> {
             .collect())
     }
 
+    /// Returns storage logs for the specified L2 block ordered by the operation number, i.e., in the order
+    /// they were applied.
+    pub async fn get_storage_logs_for_l2_block(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<DbStorageLog>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hashed_key,
+                address,
+                key,
+                value,
+                operation_number,
+                tx_hash,
+                miniblock_number
+            FROM
+                storage_logs
+            WHERE
+                miniblock_number = $1
+            ORDER BY
+                operation_number
+            "#,
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_storage_logs_for_l2_block")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DbStorageLog {
+                hashed_key: H256::from_slice(&row.hashed_key),
+                address: H160::from_slice(&row.address),
+                key: H256::from_slice(&row.key),
+                value: H256::from_slice(&row.value),
+                operation_number: row.operation_number as u64,
+                tx_hash: H256::from_slice(&row.tx_hash),
+                l2_block_number: L2BlockNumber(row.miniblock_number as u32),
+            })
+            .collect())
+    }
+
     /// Retrieves all storage log entries for testing purposes.
     pub async fn dump_all_storage_logs_for_tests(&mut self) -> Vec<DbStorageLog> {
         let rows = sqlx::query!(
@@ -798,9 +890,87 @@ mod tests {
         assert_eq!(touched_slots[&first_key], H256::repeat_byte(3));
         assert_eq!(touched_slots[&second_key], H256::repeat_byte(2));
 
+        let block_logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let block_logs: Vec<_> = block_logs
+            .iter()
+            .map(|log| (log.tx_hash, log.hashed_key, log.value))
+            .collect();
+        assert_eq!(
+            block_logs,
+            [
+                (H256::zero(), first_key.hashed_key(), H256::repeat_byte(1)),
+                (H256::zero(), second_key.hashed_key(), H256::repeat_byte(2)),
+                (
+                    H256::repeat_byte(1),
+                    first_key.hashed_key(),
+                    H256::repeat_byte(3)
+                ),
+            ]
+        );
+
         test_revert(&mut conn, first_key, second_key).await;
     }
 
+    #[tokio::test]
+    async fn copying_storage_logs() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        for number in [1, 2] {
+            conn.blocks_dal()
+                .insert_l2_block(&create_l2_block_header(number))
+                .await
+                .unwrap();
+        }
+
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let logs: Vec<_> = (0..3)
+            .map(|i| {
+                let key = StorageKey::new(account, H256::from_low_u64_be(i));
+                (
+                    H256::from_low_u64_be(i + 1),
+                    vec![StorageLog::new_write_log(key, H256::repeat_byte(i as u8))],
+                )
+            })
+            .collect();
+        conn.storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &logs)
+            .await
+            .unwrap();
+        conn.storage_logs_dal()
+            .copy_storage_logs(L2BlockNumber(2), &logs)
+            .await
+            .unwrap();
+
+        let inserted_logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_block(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let copied_logs = conn
+            .storage_logs_dal()
+            .get_storage_logs_for_l2_block(L2BlockNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(copied_logs.len(), 3);
+        for (inserted, copied) in inserted_logs.into_iter().zip(copied_logs) {
+            assert_eq!(
+                DbStorageLog {
+                    l2_block_number: L2BlockNumber(2),
+                    ..inserted
+                },
+                copied
+            );
+        }
+    }
+
     async fn test_revert(conn: &mut Connection<'_, Core>, key: StorageKey, second_key: StorageKey) {
         let new_account = AccountTreeId::new(Address::repeat_byte(2));
         let new_key = StorageKey::new(new_account, H256::zero());
@@ -1,8 +1,9 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_db_connection::connection_pool::ConnectionPool;
 use zksync_types::{
+    api,
     block::{L2BlockHasher, L2BlockHeader},
     fee::{Fee, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
@@ -22,7 +23,7 @@ use crate::{
     protocol_versions_dal::ProtocolVersionsDal,
     transactions_dal::{L2TxSubmissionResult, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
-    Core,
+    Core, CoreDal,
 };
 
 const DEFAULT_GAS_PER_PUBDATA: u32 = 100;
@@ -48,6 +49,7 @@ pub(crate) fn create_l2_block_header(number: u32) -> L2BlockHeader {
         protocol_version: Some(protocol_version),
         virtual_blocks: 1,
         gas_limit: 0,
+        logs_bloom: Default::default(),
     }
 }
 
@@ -234,6 +236,161 @@ async fn workflow_with_submit_tx_diff_hashes() {
     assert_eq!(result, L2TxSubmissionResult::Replaced);
 }
 
+#[tokio::test]
+async fn replacing_tx_with_fee_bump() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let storage = &mut connection_pool.connection().await.unwrap();
+
+    let tx = mock_l2_transaction();
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2_with_fee_bump(&tx, mock_tx_execution_metrics(), Some(10))
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::Added);
+
+    let mut underpriced_tx = mock_l2_transaction();
+    underpriced_tx.common_data.nonce = tx.common_data.nonce;
+    underpriced_tx.common_data.initiator_address = tx.common_data.initiator_address;
+    underpriced_tx.common_data.fee.max_fee_per_gas = tx.common_data.fee.max_fee_per_gas * 105 / 100;
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2_with_fee_bump(&underpriced_tx, mock_tx_execution_metrics(), Some(10))
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::ReplacementUnderpriced);
+
+    let mut replacement_tx = underpriced_tx.clone();
+    replacement_tx.set_input(H256::random().0.to_vec(), H256::random());
+    replacement_tx.common_data.fee.max_fee_per_gas = tx.common_data.fee.max_fee_per_gas * 110 / 100;
+    let result = storage
+        .transactions_dal()
+        .insert_transaction_l2_with_fee_bump(&replacement_tx, mock_tx_execution_metrics(), Some(10))
+        .await
+        .unwrap();
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+
+    let mut web3_dal = storage.transactions_web3_dal();
+    let details = web3_dal
+        .get_transaction_details(tx.hash())
+        .await
+        .unwrap()
+        .expect("no details for replaced tx");
+    assert!(matches!(details.status, api::TransactionStatus::Replaced));
+    assert_eq!(details.initiator_address, tx.initiator_account());
+    let details = web3_dal
+        .get_transaction_details(replacement_tx.hash())
+        .await
+        .unwrap()
+        .expect("no details for replacement tx");
+    assert!(matches!(details.status, api::TransactionStatus::Pending));
+    let details = web3_dal
+        .get_transaction_details(underpriced_tx.hash())
+        .await
+        .unwrap();
+    assert!(details.is_none());
+}
+
+#[tokio::test]
+async fn counting_pending_txs() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let storage = &mut connection_pool.connection().await.unwrap();
+
+    let tx = mock_l2_transaction();
+    let initiator = tx.initiator_account();
+    let mut next_tx = mock_l2_transaction();
+    next_tx.common_data.initiator_address = initiator;
+    next_tx.common_data.nonce = zksync_types::Nonce(1);
+    for tx in [&tx, &next_tx, &mock_l2_transaction()] {
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(tx, mock_tx_execution_metrics())
+            .await
+            .unwrap();
+    }
+
+    let mut dal = storage.transactions_dal();
+    let count = dal
+        .get_pending_l2_txs_count(initiator, zksync_types::Nonce(2))
+        .await
+        .unwrap();
+    assert_eq!(count, 2);
+    let count = dal
+        .get_pending_l2_txs_count(initiator, zksync_types::Nonce(1))
+        .await
+        .unwrap();
+    assert_eq!(count, 1);
+    let count = dal
+        .get_pending_l2_txs_count(Address::random(), zksync_types::Nonce(0))
+        .await
+        .unwrap();
+    assert_eq!(count, 0);
+}
+
+#[tokio::test]
+async fn expiring_txs() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let storage = &mut connection_pool.connection().await.unwrap();
+
+    let mut stale_tx = mock_l2_transaction();
+    stale_tx.received_timestamp_ms =
+        unix_timestamp_ms() - Duration::new(1000, 0).as_millis() as u64;
+    let fresh_tx = mock_l2_transaction();
+    let mut tx_with_gap = mock_l2_transaction();
+    tx_with_gap.common_data.initiator_address = fresh_tx.initiator_account();
+    tx_with_gap.common_data.nonce = zksync_types::Nonce(5);
+    for tx in [&stale_tx, &fresh_tx, &tx_with_gap] {
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(tx, mock_tx_execution_metrics())
+            .await
+            .unwrap();
+    }
+
+    let expired = storage
+        .transactions_dal()
+        .mark_stale_txs_as_expired(Duration::from_secs(500))
+        .await
+        .unwrap();
+    assert_eq!(expired, [stale_tx.initiator_account()]);
+
+    let max_nonces = storage
+        .transactions_dal()
+        .get_max_pending_l2_nonces()
+        .await
+        .unwrap();
+    assert_eq!(
+        max_nonces,
+        HashMap::from([(fresh_tx.initiator_account(), zksync_types::Nonce(5))])
+    );
+    let max_allowed_nonces =
+        HashMap::from([(fresh_tx.initiator_account(), zksync_types::Nonce(2))]);
+    let expired = storage
+        .transactions_dal()
+        .mark_txs_as_expired_after_nonces(&max_allowed_nonces)
+        .await
+        .unwrap();
+    assert_eq!(expired, [fresh_tx.initiator_account()]);
+
+    let txs = storage
+        .transactions_dal()
+        .sync_mempool(&[], &[], 0, 0, 1000)
+        .await
+        .unwrap();
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].hash(), fresh_tx.hash());
+
+    for tx in [&stale_tx, &tx_with_gap] {
+        let details = storage
+            .transactions_web3_dal()
+            .get_transaction_details(tx.hash())
+            .await
+            .unwrap()
+            .expect("no details for expired tx");
+        assert!(matches!(details.status, api::TransactionStatus::Expired));
+    }
+}
+
 #[tokio::test]
 async fn remove_stuck_txs() {
     let connection_pool = ConnectionPool::<Core>::test_pool().await;
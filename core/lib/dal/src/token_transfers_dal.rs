@@ -0,0 +1,671 @@
+//! Index of ERC-20 token transfers and current token balances computed from them.
+
+use std::ops;
+
+use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
+use zksync_types::{
+    api::TokenTransfer, event::TRANSFER_EVENT_SIGNATURE, Address, L2BlockNumber, H256,
+    L2_BASE_TOKEN_ADDRESS, U256,
+};
+use zksync_utils::{
+    h256_to_account_address, h256_to_u256, try_bigdecimal_to_u256, u256_to_big_decimal,
+};
+
+use crate::Core;
+
+#[derive(Debug)]
+pub struct TokenTransfersDal<'a, 'c> {
+    pub(crate) storage: &'a mut Connection<'c, Core>,
+}
+
+impl TokenTransfersDal<'_, '_> {
+    /// Returns the last L2 block covered by the transfer index, or `None` if the index is empty.
+    pub async fn get_last_indexed_l2_block(&mut self) -> DalResult<Option<L2BlockNumber>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                last_miniblock_number
+            FROM
+                token_transfers_index
+            "#
+        )
+        .instrument("get_last_indexed_l2_block")
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| L2BlockNumber(row.last_miniblock_number as u32)))
+    }
+
+    /// Decodes token transfers from `Transfer` events emitted in the specified L2 blocks. Events with
+    /// the same signature but a different layout (e.g., ERC-721 transfers with an indexed token ID) are skipped,
+    /// as are events emitted by the base token contract (its balances change without `Transfer` events, e.g. on deposits).
+    pub async fn get_transfers_from_events(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<TokenTransfer>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                address,
+                topic2,
+                topic3,
+                value
+            FROM
+                events
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+                AND topic1 = $3
+                AND LENGTH(topic2) = 32
+                AND LENGTH(topic3) = 32
+                AND LENGTH(topic4) = 0
+                AND LENGTH(value) = 32
+                AND address != $4
+            ORDER BY
+                miniblock_number,
+                event_index_in_block
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            TRANSFER_EVENT_SIGNATURE.as_bytes(),
+            L2_BASE_TOKEN_ADDRESS.as_bytes()
+        )
+        .instrument("get_transfers_from_events")
+        .with_arg("l2_blocks", &l2_blocks)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TokenTransfer {
+                block_number: L2BlockNumber(row.miniblock_number as u32),
+                log_index: row.event_index_in_block as u32,
+                transaction_hash: H256::from_slice(&row.tx_hash),
+                token_address: Address::from_slice(&row.address),
+                from: h256_to_account_address(&H256::from_slice(&row.topic2)),
+                to: h256_to_account_address(&H256::from_slice(&row.topic3)),
+                amount: h256_to_u256(H256::from_slice(&row.value)),
+            })
+            .collect())
+    }
+
+    /// Inserts transfers for the specified range of L2 blocks, updates token balances accordingly
+    /// and marks the range as indexed. The caller is responsible for the range to directly follow
+    /// the previously indexed range.
+    pub async fn insert_transfers(
+        &mut self,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        transfers: &[TokenTransfer],
+    ) -> DalResult<()> {
+        let mut l2_block_numbers = Vec::with_capacity(transfers.len());
+        let mut log_indices = Vec::with_capacity(transfers.len());
+        let mut tx_hashes = Vec::with_capacity(transfers.len());
+        let mut token_addresses = Vec::with_capacity(transfers.len());
+        let mut from_addresses = Vec::with_capacity(transfers.len());
+        let mut to_addresses = Vec::with_capacity(transfers.len());
+        let mut amounts = Vec::with_capacity(transfers.len());
+        for transfer in transfers {
+            l2_block_numbers.push(i64::from(transfer.block_number.0));
+            log_indices.push(transfer.log_index as i32);
+            tx_hashes.push(transfer.transaction_hash.as_bytes());
+            token_addresses.push(transfer.token_address.as_bytes());
+            from_addresses.push(transfer.from.as_bytes());
+            to_addresses.push(transfer.to.as_bytes());
+            amounts.push(u256_to_big_decimal(transfer.amount));
+        }
+
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                token_transfers (
+                    miniblock_number,
+                    event_index_in_block,
+                    tx_hash,
+                    token_address,
+                    from_address,
+                    to_address,
+                    amount
+                )
+            SELECT
+                *
+            FROM
+                UNNEST(
+                    $1::BIGINT[],
+                    $2::INT[],
+                    $3::BYTEA[],
+                    $4::BYTEA[],
+                    $5::BYTEA[],
+                    $6::BYTEA[],
+                    $7::NUMERIC[]
+                )
+            "#,
+            &l2_block_numbers,
+            &log_indices,
+            &tx_hashes as &[&[u8]],
+            &token_addresses as &[&[u8]],
+            &from_addresses as &[&[u8]],
+            &to_addresses as &[&[u8]],
+            &amounts
+        )
+        .instrument("insert_transfers#insert_transfers")
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("transfers.len", &transfers.len())
+        .execute(&mut transaction)
+        .await?;
+
+        // Mints and burns are transfers from / to the zero address; we don't track the balance of the zero address.
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                token_balances (address, token_address, balance, updated_at)
+            SELECT
+                address,
+                token_address,
+                SUM(delta),
+                NOW()
+            FROM
+                (
+                    SELECT
+                        to_address AS address,
+                        token_address,
+                        amount AS delta
+                    FROM
+                        token_transfers
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                    UNION ALL
+                    SELECT
+                        from_address AS address,
+                        token_address,
+                        -amount AS delta
+                    FROM
+                        token_transfers
+                    WHERE
+                        miniblock_number BETWEEN $1 AND $2
+                ) AS deltas
+            WHERE
+                address != $3
+            GROUP BY
+                address,
+                token_address
+            ON CONFLICT (address, token_address) DO
+            UPDATE
+            SET
+                balance = token_balances.balance + excluded.balance,
+                updated_at = NOW()
+            "#,
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            Address::zero().as_bytes()
+        )
+        .instrument("insert_transfers#update_balances")
+        .with_arg("l2_blocks", &l2_blocks)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                token_transfers_index (last_miniblock_number, created_at, updated_at)
+            VALUES
+                ($1, NOW(), NOW())
+            ON CONFLICT (singleton) DO
+            UPDATE
+            SET
+                last_miniblock_number = excluded.last_miniblock_number,
+                updated_at = NOW()
+            "#,
+            i64::from(l2_blocks.end().0)
+        )
+        .instrument("insert_transfers#update_index")
+        .with_arg("l2_blocks", &l2_blocks)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+
+    /// Returns positive token balances of the specified account as `(token_address, balance)` tuples
+    /// ordered by the token address. Only tokens with the address greater than `after_token` (if specified)
+    /// are returned, and at most `limit` balances are returned.
+    ///
+    /// Balances out of the `U256` range (which may be produced by malicious tokens) are skipped.
+    pub async fn get_token_balances(
+        &mut self,
+        address: Address,
+        after_token: Option<Address>,
+        limit: usize,
+    ) -> DalResult<Vec<(Address, U256)>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                token_address,
+                balance
+            FROM
+                token_balances
+            WHERE
+                address = $1
+                AND (
+                    $2::BYTEA IS NULL
+                    OR token_address > $2
+                )
+                AND balance > 0
+                AND balance <= $3
+            ORDER BY
+                token_address
+            LIMIT
+                $4
+            "#,
+            address.as_bytes(),
+            after_token.as_ref().map(Address::as_bytes),
+            u256_to_big_decimal(U256::MAX),
+            limit as i64
+        )
+        .instrument("get_token_balances")
+        .with_arg("address", &address)
+        .with_arg("after_token", &after_token)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let balance = try_bigdecimal_to_u256(row.balance)?;
+                Some((Address::from_slice(&row.token_address), balance))
+            })
+            .collect())
+    }
+
+    /// Returns transfers from or to the specified account in the specified range of L2 blocks, ordered
+    /// by the L2 block number and the event index. At most `limit` transfers are returned.
+    pub async fn get_transfers(
+        &mut self,
+        address: Address,
+        l2_blocks: ops::RangeInclusive<L2BlockNumber>,
+        limit: usize,
+    ) -> DalResult<Vec<TokenTransfer>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                miniblock_number,
+                event_index_in_block,
+                tx_hash,
+                token_address,
+                from_address,
+                to_address,
+                amount
+            FROM
+                token_transfers
+            WHERE
+                (
+                    from_address = $1
+                    OR to_address = $1
+                )
+                AND miniblock_number BETWEEN $2 AND $3
+            ORDER BY
+                miniblock_number,
+                event_index_in_block
+            LIMIT
+                $4
+            "#,
+            address.as_bytes(),
+            i64::from(l2_blocks.start().0),
+            i64::from(l2_blocks.end().0),
+            limit as i64
+        )
+        .instrument("get_transfers")
+        .with_arg("address", &address)
+        .with_arg("l2_blocks", &l2_blocks)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage)
+        .await?;
+
+        // Amounts are decoded from 32-byte event values, so they always fit into `U256`; we don't want to panic
+        // on a corrupted row regardless.
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(TokenTransfer {
+                    block_number: L2BlockNumber(row.miniblock_number as u32),
+                    log_index: row.event_index_in_block as u32,
+                    transaction_hash: H256::from_slice(&row.tx_hash),
+                    token_address: Address::from_slice(&row.token_address),
+                    from: Address::from_slice(&row.from_address),
+                    to: Address::from_slice(&row.to_address),
+                    amount: try_bigdecimal_to_u256(row.amount)?,
+                })
+            })
+            .collect())
+    }
+
+    /// Removes transfers for L2 blocks after `last_l2_block_to_keep`, reverts their effect on token balances
+    /// and shrinks the indexed range accordingly.
+    pub async fn roll_back_transfers(
+        &mut self,
+        last_l2_block_to_keep: L2BlockNumber,
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            UPDATE token_balances
+            SET
+                balance = token_balances.balance - deltas.delta,
+                updated_at = NOW()
+            FROM
+                (
+                    SELECT
+                        address,
+                        token_address,
+                        SUM(delta) AS delta
+                    FROM
+                        (
+                            SELECT
+                                to_address AS address,
+                                token_address,
+                                amount AS delta
+                            FROM
+                                token_transfers
+                            WHERE
+                                miniblock_number > $1
+                            UNION ALL
+                            SELECT
+                                from_address AS address,
+                                token_address,
+                                -amount AS delta
+                            FROM
+                                token_transfers
+                            WHERE
+                                miniblock_number > $1
+                        ) AS all_deltas
+                    GROUP BY
+                        address,
+                        token_address
+                ) AS deltas
+            WHERE
+                token_balances.address = deltas.address
+                AND token_balances.token_address = deltas.token_address
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("roll_back_transfers#revert_balances")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM token_transfers
+            WHERE
+                miniblock_number > $1
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("roll_back_transfers#delete_transfers")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE token_transfers_index
+            SET
+                last_miniblock_number = $1,
+                updated_at = NOW()
+            WHERE
+                last_miniblock_number > $1
+            "#,
+            i64::from(last_l2_block_to_keep.0)
+        )
+        .instrument("roll_back_transfers#update_index")
+        .with_arg("last_l2_block_to_keep", &last_l2_block_to_keep)
+        .execute(&mut transaction)
+        .await?;
+
+        transaction.commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use zksync_types::{tx::IncludedTxLocation, L1BatchNumber, ProtocolVersion, VmEvent};
+    use zksync_utils::{address_to_h256, u256_to_h256};
+
+    use super::*;
+    use crate::{tests::create_l2_block_header, ConnectionPool, Core, CoreDal};
+
+    fn transfer(block_number: u32, log_index: u32, from: u8, to: u8, amount: u64) -> TokenTransfer {
+        TokenTransfer {
+            block_number: L2BlockNumber(block_number),
+            log_index,
+            transaction_hash: H256::from_low_u64_be(block_number.into()),
+            token_address: Address::repeat_byte(0xff),
+            from: Address::repeat_byte(from),
+            to: Address::repeat_byte(to),
+            amount: amount.into(),
+        }
+    }
+
+    async fn get_balances(conn: &mut Connection<'_, Core>, address: u8) -> HashMap<Address, U256> {
+        conn.token_transfers_dal()
+            .get_token_balances(Address::repeat_byte(address), None, 100)
+            .await
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn indexing_and_rolling_back_transfers() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let token = Address::repeat_byte(0xff);
+
+        let first_transfers = [transfer(1, 0, 0, 1, 100), transfer(1, 1, 1, 2, 30)];
+        conn.token_transfers_dal()
+            .insert_transfers(L2BlockNumber(0)..=L2BlockNumber(1), &first_transfers)
+            .await
+            .unwrap();
+        let second_transfers = [transfer(2, 0, 2, 1, 10), transfer(2, 1, 1, 0, 50)];
+        conn.token_transfers_dal()
+            .insert_transfers(L2BlockNumber(2)..=L2BlockNumber(2), &second_transfers)
+            .await
+            .unwrap();
+        let last_indexed_l2_block = conn
+            .token_transfers_dal()
+            .get_last_indexed_l2_block()
+            .await
+            .unwrap();
+        assert_eq!(last_indexed_l2_block, Some(L2BlockNumber(2)));
+
+        assert_eq!(
+            get_balances(&mut conn, 1).await,
+            HashMap::from([(token, 30.into())])
+        );
+        assert_eq!(
+            get_balances(&mut conn, 2).await,
+            HashMap::from([(token, 20.into())])
+        );
+        assert_eq!(get_balances(&mut conn, 0).await, HashMap::new());
+
+        let transfers = conn
+            .token_transfers_dal()
+            .get_transfers(
+                Address::repeat_byte(2),
+                L2BlockNumber(0)..=L2BlockNumber(2),
+                10,
+            )
+            .await
+            .unwrap();
+        assert_eq!(transfers, [first_transfers[1], second_transfers[0]]);
+        let transfers = conn
+            .token_transfers_dal()
+            .get_transfers(
+                Address::repeat_byte(1),
+                L2BlockNumber(1)..=L2BlockNumber(2),
+                3,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            transfers,
+            [first_transfers[0], first_transfers[1], second_transfers[0]]
+        );
+
+        conn.token_transfers_dal()
+            .roll_back_transfers(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let last_indexed_l2_block = conn
+            .token_transfers_dal()
+            .get_last_indexed_l2_block()
+            .await
+            .unwrap();
+        assert_eq!(last_indexed_l2_block, Some(L2BlockNumber(1)));
+        assert_eq!(
+            get_balances(&mut conn, 1).await,
+            HashMap::from([(token, 70.into())])
+        );
+        assert_eq!(
+            get_balances(&mut conn, 2).await,
+            HashMap::from([(token, 30.into())])
+        );
+    }
+
+    #[tokio::test]
+    async fn paginating_token_balances() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let address = Address::repeat_byte(1);
+        let transfers: Vec<_> = (0..5)
+            .map(|i| TokenTransfer {
+                token_address: Address::repeat_byte(0xf0 + i),
+                ..transfer(1, i.into(), 0, 1, 100)
+            })
+            .collect();
+        conn.token_transfers_dal()
+            .insert_transfers(L2BlockNumber(0)..=L2BlockNumber(1), &transfers)
+            .await
+            .unwrap();
+
+        let first_page = conn
+            .token_transfers_dal()
+            .get_token_balances(address, None, 3)
+            .await
+            .unwrap();
+        let first_page_tokens: Vec<_> = first_page.iter().map(|(token, _)| *token).collect();
+        let expected_tokens: Vec<_> = transfers.iter().map(|tx| tx.token_address).collect();
+        assert_eq!(first_page_tokens, expected_tokens[..3]);
+        let second_page = conn
+            .token_transfers_dal()
+            .get_token_balances(address, Some(first_page_tokens[2]), 3)
+            .await
+            .unwrap();
+        let second_page_tokens: Vec<_> = second_page.iter().map(|(token, _)| *token).collect();
+        assert_eq!(second_page_tokens, expected_tokens[3..]);
+    }
+
+    #[tokio::test]
+    async fn skipping_out_of_range_token_balances() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let malicious_token = Address::repeat_byte(0xee);
+        // Minting `U256::MAX` twice pushes the balance out of the `U256` range.
+        let transfers = [0, 1].map(|log_index| TokenTransfer {
+            token_address: malicious_token,
+            amount: U256::MAX,
+            ..transfer(1, log_index, 0, 1, 0)
+        });
+        conn.token_transfers_dal()
+            .insert_transfers(L2BlockNumber(0)..=L2BlockNumber(1), &transfers)
+            .await
+            .unwrap();
+        conn.token_transfers_dal()
+            .insert_transfers(
+                L2BlockNumber(2)..=L2BlockNumber(2),
+                &[transfer(2, 0, 0, 1, 100)],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_balances(&mut conn, 1).await,
+            HashMap::from([(Address::repeat_byte(0xff), 100.into())])
+        );
+    }
+
+    #[tokio::test]
+    async fn decoding_transfers_from_events() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        conn.blocks_dal()
+            .insert_l2_block(&create_l2_block_header(1))
+            .await
+            .unwrap();
+
+        let token = Address::repeat_byte(0xff);
+        let from = Address::repeat_byte(1);
+        let to = Address::repeat_byte(2);
+        let erc20_transfer = VmEvent {
+            location: (L1BatchNumber(1), 0),
+            address: token,
+            indexed_topics: vec![
+                *TRANSFER_EVENT_SIGNATURE,
+                address_to_h256(&from),
+                address_to_h256(&to),
+            ],
+            value: u256_to_h256(42.into()).as_bytes().to_vec(),
+        };
+        let erc721_transfer = VmEvent {
+            indexed_topics: vec![
+                *TRANSFER_EVENT_SIGNATURE,
+                address_to_h256(&from),
+                address_to_h256(&to),
+                H256::from_low_u64_be(1),
+            ],
+            value: vec![],
+            ..erc20_transfer.clone()
+        };
+        let base_token_transfer = VmEvent {
+            address: L2_BASE_TOKEN_ADDRESS,
+            ..erc20_transfer.clone()
+        };
+        let location = IncludedTxLocation {
+            tx_hash: H256::repeat_byte(1),
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: from,
+        };
+        let events = vec![&base_token_transfer, &erc721_transfer, &erc20_transfer];
+        conn.events_dal()
+            .save_events(L2BlockNumber(1), &[(location, events)])
+            .await
+            .unwrap();
+
+        let transfers = conn
+            .token_transfers_dal()
+            .get_transfers_from_events(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(
+            transfers,
+            [TokenTransfer {
+                block_number: L2BlockNumber(1),
+                log_index: 2,
+                transaction_hash: location.tx_hash,
+                token_address: token,
+                from,
+                to,
+                amount: 42.into(),
+            }]
+        );
+    }
+}
@@ -11,22 +11,25 @@ use zksync_db_connection::{
 };
 use zksync_types::{
     block::L2BlockExecutionData,
-    fee::TransactionExecutionMetrics,
+    fee::{Fee, TransactionExecutionMetrics},
     l1::L1Tx,
     l2::L2Tx,
     protocol_upgrade::ProtocolUpgradeTx,
     tx::{tx_execution_info::TxExecutionStatus, TransactionExecutionResult},
     vm_trace::Call,
-    Address, ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, L2BlockNumber, PriorityOpId,
-    ProtocolVersionId, Transaction, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
+    Address, ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, L2BlockNumber, Nonce,
+    PriorityOpId, ProtocolVersionId, Transaction, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
-use zksync_utils::u256_to_big_decimal;
+use zksync_utils::{bigdecimal_to_u256, u256_to_big_decimal};
 
 use crate::{
     models::storage_transaction::{CallTrace, StorageTransaction},
     Core, CoreDal,
 };
 
+/// Error set for pending L2 transactions evicted from the mempool because of their age or nonce gap.
+pub const EXPIRED_TX_ERROR: &str = "expired";
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum L2TxSubmissionResult {
     Added,
@@ -35,6 +38,8 @@ pub enum L2TxSubmissionResult {
     Duplicate,
     Proxied,
     InsertionInProgress,
+    /// The transaction would replace a pending transaction, but doesn't bump its fees sufficiently.
+    ReplacementUnderpriced,
 }
 
 impl fmt::Display for L2TxSubmissionResult {
@@ -46,6 +51,7 @@ impl fmt::Display for L2TxSubmissionResult {
             Self::Duplicate => "duplicate",
             Self::Proxied => "proxied",
             Self::InsertionInProgress => "insertion_in_progress",
+            Self::ReplacementUnderpriced => "replacement_underpriced",
         })
     }
 }
@@ -262,6 +268,21 @@ impl TransactionsDal<'_, '_> {
         &mut self,
         tx: &L2Tx,
         exec_info: TransactionExecutionMetrics,
+    ) -> DalResult<L2TxSubmissionResult> {
+        self.insert_transaction_l2_with_fee_bump(tx, exec_info, None)
+            .await
+    }
+
+    /// Inserts an L2 transaction, possibly replacing a pending transaction with the same initiator and nonce.
+    /// If `min_fee_bump_percent` is specified, the replacement is only performed if both the max fee
+    /// and the max priority fee are increased by at least the specified percentage; otherwise,
+    /// [`L2TxSubmissionResult::ReplacementUnderpriced`] is returned. Replaced transactions are recorded,
+    /// so that their status can be reported by the API.
+    pub async fn insert_transaction_l2_with_fee_bump(
+        &mut self,
+        tx: &L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        min_fee_bump_percent: Option<u32>,
     ) -> DalResult<L2TxSubmissionResult> {
         let tx_hash = tx.hash();
         let is_duplicate = sqlx::query!(
@@ -311,6 +332,61 @@ impl TransactionsDal<'_, '_> {
         let nanosecs = ((tx.received_timestamp_ms % 1000) * 1_000_000) as u32;
         #[allow(deprecated)]
         let received_at = NaiveDateTime::from_timestamp_opt(secs, nanosecs).unwrap();
+
+        let mut transaction = self.storage.start_transaction().await?;
+        let replaced_tx = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                gas_per_pubdata_limit,
+                received_at
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce = $2
+                AND is_priority = FALSE
+                AND miniblock_number IS NULL
+            FOR UPDATE
+            "#,
+            initiator_address.as_bytes(),
+            nonce
+        )
+        .instrument("insert_transaction_l2#get_replaced_tx")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(&mut transaction)
+        .await?;
+
+        if let (Some(replaced_tx), Some(min_bump_percent)) = (&replaced_tx, min_fee_bump_percent) {
+            let replaced_fee = Fee {
+                gas_limit: U256::zero(), // not used for the check
+                max_fee_per_gas: bigdecimal_to_u256(
+                    replaced_tx.max_fee_per_gas.clone().unwrap_or_default(),
+                ),
+                max_priority_fee_per_gas: bigdecimal_to_u256(
+                    replaced_tx
+                        .max_priority_fee_per_gas
+                        .clone()
+                        .unwrap_or_default(),
+                ),
+                gas_per_pubdata_limit: U256::zero(), // not used for the check
+            };
+            if !tx
+                .common_data
+                .fee
+                .is_sufficient_replacement_for(&replaced_fee, min_bump_percent)
+            {
+                tracing::debug!(
+                    "Prevented replacing L2 transaction {:?} with {tx_hash:?}: insufficient fee bump",
+                    H256::from_slice(&replaced_tx.hash)
+                );
+                return Ok(L2TxSubmissionResult::ReplacementUnderpriced);
+            }
+        }
+
         // Besides just adding or updating(on conflict) the record, we want to extract some info
         // from the query below, to indicate what actually happened:
         // 1) transaction is added
@@ -427,7 +503,7 @@ impl TransactionsDal<'_, '_> {
         )
         .instrument("insert_transaction_l2")
         .with_arg("tx_hash", &tx_hash)
-        .fetch_optional(self.storage)
+        .fetch_optional(&mut transaction)
         .await
         .map(|option_record| option_record.map(|record| record.is_replaced));
 
@@ -458,6 +534,47 @@ impl TransactionsDal<'_, '_> {
                 return Err(err);
             }
         };
+
+        if let (L2TxSubmissionResult::Replaced, Some(replaced_tx)) =
+            (l2_tx_insertion_result, &replaced_tx)
+        {
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                    replaced_transactions (
+                        hash,
+                        replaced_by,
+                        initiator_address,
+                        nonce,
+                        gas_per_pubdata_limit,
+                        received_at,
+                        created_at
+                    )
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, NOW())
+                ON CONFLICT (hash) DO
+                UPDATE
+                SET
+                    replaced_by = $2,
+                    created_at = NOW()
+                "#,
+                &replaced_tx.hash,
+                tx_hash.as_bytes(),
+                initiator_address.as_bytes(),
+                nonce,
+                replaced_tx
+                    .gas_per_pubdata_limit
+                    .clone()
+                    .unwrap_or_default(),
+                replaced_tx.received_at
+            )
+            .instrument("insert_transaction_l2#record_replaced_tx")
+            .with_arg("tx_hash", &tx_hash)
+            .execute(&mut transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+
         tracing::debug!(
             "{:?} l2 transaction {:?} to DB. init_acc {:?} nonce {:?} returned option {:?}",
             l2_tx_insertion_result,
@@ -1691,6 +1808,164 @@ impl TransactionsDal<'_, '_> {
         Ok(rows.len())
     }
 
+    /// Marks pending L2 transactions received more than `ttl` ago as expired. Returns initiators
+    /// of the expired transactions (one entry per transaction).
+    pub async fn mark_stale_txs_as_expired(&mut self, ttl: Duration) -> DalResult<Vec<Address>> {
+        let ttl = pg_interval_from_duration(ttl);
+        let rows = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = $2,
+                updated_at = NOW()
+            WHERE
+                miniblock_number IS NULL
+                AND received_at < NOW() - $1::INTERVAL
+                AND is_priority = FALSE
+                AND error IS NULL
+            RETURNING
+                initiator_address
+            "#,
+            ttl,
+            EXPIRED_TX_ERROR
+        )
+        .instrument("mark_stale_txs_as_expired")
+        .with_arg("ttl", &ttl)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Address::from_slice(&row.initiator_address))
+            .collect())
+    }
+
+    /// Returns the maximum nonce among pending L2 transactions for each initiator having such transactions.
+    pub async fn get_max_pending_l2_nonces(&mut self) -> DalResult<HashMap<Address, Nonce>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                initiator_address,
+                MAX(nonce) AS "max_nonce!"
+            FROM
+                transactions
+            WHERE
+                miniblock_number IS NULL
+                AND is_priority = FALSE
+                AND error IS NULL
+            GROUP BY
+                initiator_address
+            "#
+        )
+        .instrument("get_max_pending_l2_nonces")
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let address = Address::from_slice(&row.initiator_address);
+                (address, Nonce(row.max_nonce as u32))
+            })
+            .collect())
+    }
+
+    /// Returns the number of pending L2 transactions for the specified initiator, not counting a transaction
+    /// with `excluded_nonce` (which would be replaced by a new transaction with this nonce).
+    pub async fn get_pending_l2_txs_count(
+        &mut self,
+        initiator_address: Address,
+        excluded_nonce: Nonce,
+    ) -> DalResult<usize> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT
+                COUNT(*) AS "count!"
+            FROM
+                transactions
+            WHERE
+                initiator_address = $1
+                AND nonce <> $2
+                AND miniblock_number IS NULL
+                AND is_priority = FALSE
+                AND error IS NULL
+            "#,
+            initiator_address.as_bytes(),
+            i64::from(excluded_nonce.0)
+        )
+        .instrument("get_pending_l2_txs_count")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("excluded_nonce", &excluded_nonce)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(count as usize)
+    }
+
+    /// Acquires a lock on submitting transactions for the specified initiator, so that checks on the pending
+    /// transactions of the initiator (e.g., [`Self::get_pending_l2_txs_count()`]) and the following insertion
+    /// are atomic. Must be called within a DB transaction; the lock is released when the transaction ends.
+    pub async fn lock_initiator_for_submission(
+        &mut self,
+        initiator_address: Address,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            SELECT
+                TRUE
+            FROM
+                PG_ADVISORY_XACT_LOCK(HASHTEXTEXTENDED(ENCODE($1::bytea, 'hex'), 0))
+            "#,
+            initiator_address.as_bytes()
+        )
+        .instrument("lock_initiator_for_submission")
+        .with_arg("initiator_address", &initiator_address)
+        .fetch_one(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Marks pending L2 transactions with nonces greater than the specified nonce for their initiator as expired.
+    /// Returns initiators of the expired transactions (one entry per transaction).
+    pub async fn mark_txs_as_expired_after_nonces(
+        &mut self,
+        max_allowed_nonces: &HashMap<Address, Nonce>,
+    ) -> DalResult<Vec<Address>> {
+        let (addresses, nonces): (Vec<_>, Vec<_>) = max_allowed_nonces
+            .iter()
+            .map(|(address, nonce)| (address.as_bytes(), i64::from(nonce.0)))
+            .unzip();
+        let rows = sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                error = $3,
+                updated_at = NOW()
+            FROM
+                UNNEST($1::bytea[], $2::BIGINT[]) AS allowed (address, nonce)
+            WHERE
+                transactions.initiator_address = allowed.address
+                AND transactions.nonce > allowed.nonce
+                AND transactions.miniblock_number IS NULL
+                AND transactions.is_priority = FALSE
+                AND transactions.error IS NULL
+            RETURNING
+                transactions.initiator_address
+            "#,
+            &addresses as &[&[u8]],
+            &nonces,
+            EXPIRED_TX_ERROR
+        )
+        .instrument("mark_txs_as_expired_after_nonces")
+        .with_arg("max_allowed_nonces.len", &max_allowed_nonces.len())
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Address::from_slice(&row.initiator_address))
+            .collect())
+    }
+
     /// Fetches new updates for mempool. Returns new transactions and current nonces for related accounts;
     /// the latter are only used to bootstrap mempool for given account.
     pub async fn sync_mempool(
@@ -1795,6 +2070,28 @@ impl TransactionsDal<'_, '_> {
         Ok(transactions)
     }
 
+    /// Returns the specified transactions from the mempool to the pool of pending transactions, so that they
+    /// are fetched again by [`Self::sync_mempool()`].
+    pub async fn reset_mempool_transactions(&mut self, tx_hashes: &[H256]) -> DalResult<()> {
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(H256::as_bytes).collect();
+        sqlx::query!(
+            r#"
+            UPDATE transactions
+            SET
+                in_mempool = FALSE
+            WHERE
+                in_mempool = TRUE
+                AND hash = ANY ($1)
+            "#,
+            &tx_hashes as &[&[u8]]
+        )
+        .instrument("reset_mempool_transactions")
+        .with_arg("tx_hashes.len", &tx_hashes.len())
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
     pub async fn reset_mempool(&mut self) -> DalResult<()> {
         sqlx::query!(
             r#"
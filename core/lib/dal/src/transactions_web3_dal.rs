@@ -1,4 +1,6 @@
-use sqlx::types::chrono::NaiveDateTime;
+use std::ops;
+
+use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
 use zksync_db_connection::{
     connection::Connection, error::DalResult, instrument::InstrumentExt, interpolate_query,
     match_query_as,
@@ -7,6 +9,7 @@ use zksync_types::{
     api, api::TransactionReceipt, Address, L2BlockNumber, L2ChainId, Transaction,
     ACCOUNT_CODE_STORAGE_ADDRESS, FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
 };
+use zksync_utils::bigdecimal_to_u256;
 
 use crate::{
     models::storage_transaction::{
@@ -20,6 +23,7 @@ use crate::{
 enum TransactionSelector<'a> {
     Hashes(&'a [H256]),
     Position(L2BlockNumber, u32),
+    Mempool(u32),
 }
 
 #[derive(Debug)]
@@ -91,6 +95,85 @@ impl TransactionsWeb3Dal<'_, '_> {
         .map(Into::into)
         .collect();
 
+        self.fill_receipt_logs(hashes, &mut receipts).await?;
+        Ok(receipts)
+    }
+
+    /// Returns receipts for all transactions in the specified L2 block, ordered by the transaction index
+    /// in the block. Unlike [`Self::get_transaction_receipts()`], this doesn't require knowing transaction hashes
+    /// beforehand. If the block doesn't exist, returns an empty list.
+    pub async fn get_l2_block_receipts(
+        &mut self,
+        l2_block_number: L2BlockNumber,
+    ) -> DalResult<Vec<TransactionReceipt>> {
+        let mut receipts: Vec<TransactionReceipt> = sqlx::query_as!(
+            StorageTransactionReceipt,
+            r#"
+            WITH
+                sl AS (
+                    SELECT DISTINCT
+                        ON (storage_logs.tx_hash) *
+                    FROM
+                        storage_logs
+                    WHERE
+                        storage_logs.address = $1
+                        AND storage_logs.miniblock_number = $3
+                    ORDER BY
+                        storage_logs.tx_hash,
+                        storage_logs.operation_number DESC
+                )
+            SELECT
+                transactions.hash AS tx_hash,
+                transactions.index_in_block AS index_in_block,
+                transactions.l1_batch_tx_index AS l1_batch_tx_index,
+                transactions.miniblock_number AS "block_number!",
+                transactions.error AS error,
+                transactions.effective_gas_price AS effective_gas_price,
+                transactions.initiator_address AS initiator_address,
+                transactions.data -> 'to' AS "transfer_to?",
+                transactions.data -> 'contractAddress' AS "execute_contract_address?",
+                transactions.tx_format AS "tx_format?",
+                transactions.refunded_gas AS refunded_gas,
+                transactions.gas_limit AS gas_limit,
+                miniblocks.hash AS "block_hash",
+                miniblocks.l1_batch_number AS "l1_batch_number?",
+                sl.key AS "contract_address?"
+            FROM
+                transactions
+                JOIN miniblocks ON miniblocks.number = transactions.miniblock_number
+                LEFT JOIN sl ON sl.value != $2
+                AND sl.tx_hash = transactions.hash
+            WHERE
+                transactions.miniblock_number = $3
+                AND transactions.data != '{}'::jsonb
+            ORDER BY
+                transactions.index_in_block
+            "#,
+            ACCOUNT_CODE_STORAGE_ADDRESS.as_bytes(),
+            FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH.as_bytes(),
+            i64::from(l2_block_number.0)
+        )
+        .instrument("get_l2_block_receipts")
+        .with_arg("l2_block_number", &l2_block_number)
+        .fetch_all(self.storage)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect();
+
+        let hashes: Vec<_> = receipts
+            .iter()
+            .map(|receipt| receipt.transaction_hash)
+            .collect();
+        self.fill_receipt_logs(&hashes, &mut receipts).await?;
+        Ok(receipts)
+    }
+
+    async fn fill_receipt_logs(
+        &mut self,
+        hashes: &[H256],
+        receipts: &mut [TransactionReceipt],
+    ) -> DalResult<()> {
         let mut logs = self
             .storage
             .events_dal()
@@ -103,7 +186,7 @@ impl TransactionsWeb3Dal<'_, '_> {
             .get_l2_to_l1_logs_by_hashes(hashes)
             .await?;
 
-        for receipt in &mut receipts {
+        for receipt in receipts {
             let logs_for_tx = logs.remove(&receipt.transaction_hash);
 
             if let Some(logs) = logs_for_tx {
@@ -129,8 +212,7 @@ impl TransactionsWeb3Dal<'_, '_> {
                     .collect();
             }
         }
-
-        Ok(receipts)
+        Ok(())
     }
 
     /// Obtains transactions with the specified hashes. Transactions are returned in no particular order; if some hashes
@@ -197,6 +279,10 @@ impl TransactionsWeb3Dal<'_, '_> {
                     i64::from(block_number.0),
                     idx as i32
                 ),
+                TransactionSelector::Mempool(limit) => (
+                    "transactions.hash = ANY(ARRAY(SELECT hash FROM transactions WHERE miniblock_number IS NULL AND error IS NULL AND is_priority = FALSE ORDER BY received_at LIMIT $1))";
+                    i64::from(limit)
+                ),
             }
         );
 
@@ -236,6 +322,17 @@ impl TransactionsWeb3Dal<'_, '_> {
             .next())
     }
 
+    /// Returns up to `limit` L2 transactions from the mempool (i.e., not included into a block and not rejected),
+    /// starting from the oldest ones. The returned transactions are not ordered.
+    pub async fn get_mempool_transactions(
+        &mut self,
+        limit: u32,
+        chain_id: L2ChainId,
+    ) -> DalResult<Vec<api::Transaction>> {
+        self.get_transactions_inner(TransactionSelector::Mempool(limit), chain_id)
+            .await
+    }
+
     pub async fn get_transaction_details(
         &mut self,
         hash: H256,
@@ -285,7 +382,47 @@ impl TransactionsWeb3Dal<'_, '_> {
         .fetch_optional(self.storage)
         .await?;
 
-        Ok(row.map(Into::into))
+        if let Some(row) = row {
+            return Ok(Some(row.into()));
+        }
+        self.get_replaced_transaction_details(hash).await
+    }
+
+    /// Returns details for a transaction that was replaced by another transaction with the same initiator
+    /// and nonce before it was included into an L2 block.
+    async fn get_replaced_transaction_details(
+        &mut self,
+        hash: H256,
+    ) -> DalResult<Option<api::TransactionDetails>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                initiator_address,
+                gas_per_pubdata_limit,
+                received_at
+            FROM
+                replaced_transactions
+            WHERE
+                hash = $1
+            "#,
+            hash.as_bytes()
+        )
+        .instrument("get_replaced_transaction_details")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage)
+        .await?;
+
+        Ok(row.map(|row| api::TransactionDetails {
+            is_l1_originated: false,
+            status: api::TransactionStatus::Replaced,
+            fee: U256::zero(),
+            gas_per_pubdata: bigdecimal_to_u256(row.gas_per_pubdata_limit),
+            initiator_address: Address::from_slice(&row.initiator_address),
+            received_at: DateTime::<Utc>::from_naive_utc_and_offset(row.received_at, Utc),
+            eth_commit_tx_hash: None,
+            eth_prove_tx_hash: None,
+            eth_execute_tx_hash: None,
+        }))
     }
 
     /// Returns hashes of txs which were received after `from_timestamp` and the time of receiving the last tx.
@@ -405,6 +542,46 @@ impl TransactionsWeb3Dal<'_, '_> {
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
+
+    /// Returns raw transactions for all L2 blocks in the specified range, grouped by L2 block and ordered
+    /// by the block number. Blocks without transactions are not included into the output.
+    pub async fn get_raw_l2_blocks_transactions(
+        &mut self,
+        l2_block_range: ops::RangeInclusive<L2BlockNumber>,
+    ) -> DalResult<Vec<(L2BlockNumber, Vec<Transaction>)>> {
+        let rows = sqlx::query_as!(
+            StorageTransaction,
+            r#"
+            SELECT
+                *
+            FROM
+                transactions
+            WHERE
+                miniblock_number BETWEEN $1 AND $2
+            ORDER BY
+                miniblock_number,
+                index_in_block
+            "#,
+            i64::from(l2_block_range.start().0),
+            i64::from(l2_block_range.end().0)
+        )
+        .instrument("get_raw_l2_blocks_transactions")
+        .with_arg("l2_block_range", &l2_block_range)
+        .fetch_all(self.storage)
+        .await?;
+
+        let mut blocks: Vec<(L2BlockNumber, Vec<Transaction>)> = vec![];
+        for row in rows {
+            // `unwrap()` is safe due to the query filter.
+            let block_number = L2BlockNumber(row.miniblock_number.unwrap() as u32);
+            let tx = Transaction::from(row);
+            match blocks.last_mut() {
+                Some((last_number, txs)) if *last_number == block_number => txs.push(tx),
+                _ => blocks.push((block_number, vec![tx])),
+            }
+        }
+        Ok(blocks)
+    }
 }
 
 #[cfg(test)]
@@ -512,6 +689,52 @@ mod tests {
         assert!(web3_tx.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn getting_mempool_transactions() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let executed_tx = mock_l2_transaction();
+        prepare_transactions(&mut conn, vec![executed_tx]).await;
+
+        let txs: Vec<_> = (0..3).map(|_| mock_l2_transaction()).collect();
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+        conn.transactions_dal()
+            .mark_tx_as_rejected(txs[2].hash(), "oops")
+            .await
+            .unwrap();
+
+        let mempool_txs = conn
+            .transactions_web3_dal()
+            .get_mempool_transactions(10, L2ChainId::from(270))
+            .await
+            .unwrap();
+        let mut mempool_hashes: Vec<_> = mempool_txs.iter().map(|tx| tx.hash).collect();
+        mempool_hashes.sort_unstable();
+        let mut expected_hashes = vec![txs[0].hash(), txs[1].hash()];
+        expected_hashes.sort_unstable();
+        assert_eq!(mempool_hashes, expected_hashes);
+        for tx in &mempool_txs {
+            assert_eq!(tx.block_number, None);
+            assert_eq!(tx.transaction_index, None);
+        }
+
+        let mempool_txs = conn
+            .transactions_web3_dal()
+            .get_mempool_transactions(1, L2ChainId::from(270))
+            .await
+            .unwrap();
+        assert_eq!(mempool_txs.len(), 1);
+    }
+
     #[tokio::test]
     async fn getting_receipts() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -541,6 +764,43 @@ mod tests {
         assert_eq!(receipts[1].transaction_hash, tx2_hash);
     }
 
+    #[tokio::test]
+    async fn getting_l2_block_receipts() {
+        let connection_pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = connection_pool.connection().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(&ProtocolVersion::default())
+            .await
+            .unwrap();
+        let txs = vec![mock_l2_transaction(), mock_l2_transaction()];
+        let tx_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        prepare_transactions(&mut conn, txs).await;
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_l2_block_receipts(L2BlockNumber(1))
+            .await
+            .unwrap();
+        let receipt_hashes: Vec<_> = receipts
+            .iter()
+            .map(|receipt| receipt.transaction_hash)
+            .collect();
+        assert_eq!(receipt_hashes, tx_hashes);
+        for (i, receipt) in receipts.iter().enumerate() {
+            assert_eq!(receipt.block_number, 1.into());
+            assert_eq!(receipt.transaction_index, i.into());
+        }
+
+        for block_number in [0, 2] {
+            let receipts = conn
+                .transactions_web3_dal()
+                .get_l2_block_receipts(L2BlockNumber(block_number))
+                .await
+                .unwrap();
+            assert!(receipts.is_empty());
+        }
+    }
+
     #[tokio::test]
     async fn getting_l2_block_transactions() {
         let connection_pool = ConnectionPool::<Core>::test_pool().await;
@@ -567,6 +827,23 @@ mod tests {
             .unwrap();
         assert_eq!(raw_txs.len(), 1);
         assert_eq!(raw_txs[0].hash(), tx_hash);
+
+        let raw_blocks = conn
+            .transactions_web3_dal()
+            .get_raw_l2_blocks_transactions(L2BlockNumber(0)..=L2BlockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(raw_blocks.len(), 1);
+        assert_eq!(raw_blocks[0].0, L2BlockNumber(1));
+        assert_eq!(raw_blocks[0].1.len(), 1);
+        assert_eq!(raw_blocks[0].1[0].hash(), tx_hash);
+
+        let raw_blocks = conn
+            .transactions_web3_dal()
+            .get_raw_l2_blocks_transactions(L2BlockNumber(2)..=L2BlockNumber(10))
+            .await
+            .unwrap();
+        assert!(raw_blocks.is_empty());
     }
 
     #[tokio::test]
@@ -1,8 +1,29 @@
 use zksync_db_connection::{connection::Connection, error::DalResult, instrument::InstrumentExt};
-use zksync_types::L1BatchNumber;
+use zksync_types::{
+    tx::tx_execution_info::TxExecutionStatus, L1BatchNumber, ProtocolVersionId, H256,
+};
 
 use crate::Core;
 
+/// Divergence between the canonical execution of an L1 batch and its shadow re-execution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VmDivergence {
+    /// Hash of the diverged transaction, or `None` if the divergence concerns the entire L1 batch.
+    pub tx_hash: Option<H256>,
+    /// Short machine-readable divergence kind, e.g. `status`.
+    pub kind: String,
+    /// Human-readable description of the divergence.
+    pub details: String,
+}
+
+/// Outcome of executing a transaction as persisted by the state keeper.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TxExecutionOutcome {
+    pub hash: H256,
+    pub status: TxExecutionStatus,
+    pub refunded_gas: u64,
+}
+
 #[derive(Debug)]
 pub struct VmRunnerDal<'c, 'a> {
     pub(crate) storage: &'c mut Connection<'a, Core>,
@@ -82,4 +103,244 @@ impl VmRunnerDal<'_, '_> {
         .await?;
         Ok(())
     }
+
+    pub async fn get_shadow_execution_latest_processed_batch(
+        &mut self,
+        default_batch: L1BatchNumber,
+    ) -> DalResult<L1BatchNumber> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(MAX(l1_batch_number), $1) AS "last_processed_l1_batch!"
+            FROM
+                vm_runner_shadow_execution
+            "#,
+            default_batch.0 as i32
+        )
+        .instrument("get_shadow_execution_latest_processed_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(L1BatchNumber(row.last_processed_l1_batch as u32))
+    }
+
+    pub async fn get_shadow_execution_last_ready_batch(
+        &mut self,
+        window_size: u32,
+    ) -> DalResult<L1BatchNumber> {
+        let row = sqlx::query!(
+            r#"
+            WITH
+                available_batches AS (
+                    SELECT
+                        MAX(number) AS "last_batch"
+                    FROM
+                        l1_batches
+                ),
+                processed_batches AS (
+                    SELECT
+                        COALESCE(MAX(l1_batch_number), 0) + $1 AS "last_ready_batch"
+                    FROM
+                        vm_runner_shadow_execution
+                )
+            SELECT
+                LEAST(last_batch, last_ready_batch) AS "last_ready_batch!"
+            FROM
+                available_batches
+                FULL JOIN processed_batches ON TRUE
+            "#,
+            window_size as i32
+        )
+        .instrument("get_shadow_execution_last_ready_batch")
+        .report_latency()
+        .fetch_one(self.storage)
+        .await?;
+        Ok(L1BatchNumber(row.last_ready_batch as u32))
+    }
+
+    pub async fn mark_shadow_execution_batch_as_completed(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO
+                vm_runner_shadow_execution (l1_batch_number, created_at, updated_at)
+            VALUES
+                ($1, NOW(), NOW())
+            "#,
+            i64::from(l1_batch_number.0),
+        )
+        .instrument("mark_shadow_execution_batch_as_completed")
+        .report_latency()
+        .execute(self.storage)
+        .await?;
+        Ok(())
+    }
+
+    /// Returns execution outcomes of all transactions in the specified L1 batch in their execution order.
+    pub async fn get_tx_execution_outcomes(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<TxExecutionOutcome>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                hash,
+                error IS NULL AS "is_successful!",
+                refunded_gas
+            FROM
+                transactions
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                miniblock_number,
+                index_in_block
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_tx_execution_outcomes")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| TxExecutionOutcome {
+                hash: H256::from_slice(&row.hash),
+                status: TxExecutionStatus::from_has_failed(!row.is_successful),
+                refunded_gas: row.refunded_gas as u64,
+            })
+            .collect())
+    }
+
+    /// Records divergences found during shadow re-execution of an L1 batch using the specified protocol version.
+    pub async fn insert_shadow_divergences(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        protocol_version: ProtocolVersionId,
+        divergences: &[VmDivergence],
+    ) -> DalResult<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        for divergence in divergences {
+            sqlx::query!(
+                r#"
+                INSERT INTO
+                    vm_runner_shadow_divergences (
+                        l1_batch_number,
+                        protocol_version,
+                        tx_hash,
+                        kind,
+                        details,
+                        created_at
+                    )
+                VALUES
+                    ($1, $2, $3, $4, $5, NOW())
+                "#,
+                i64::from(l1_batch_number.0),
+                protocol_version as i32,
+                divergence.tx_hash.as_ref().map(H256::as_bytes),
+                &divergence.kind,
+                &divergence.details
+            )
+            .instrument("insert_shadow_divergences#insert_divergence")
+            .with_arg("l1_batch_number", &l1_batch_number)
+            .execute(&mut transaction)
+            .await?;
+        }
+        transaction.commit().await
+    }
+
+    /// Returns divergences recorded for the specified L1 batch in the order they were inserted.
+    pub async fn get_shadow_divergences(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> DalResult<Vec<VmDivergence>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                tx_hash,
+                kind,
+                details
+            FROM
+                vm_runner_shadow_divergences
+            WHERE
+                l1_batch_number = $1
+            ORDER BY
+                id
+            "#,
+            i64::from(l1_batch_number.0)
+        )
+        .instrument("get_shadow_divergences")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .fetch_all(self.storage)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| VmDivergence {
+                tx_hash: row.tx_hash.as_deref().map(H256::from_slice),
+                kind: row.kind,
+                details: row.details,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConnectionPool, CoreDal};
+
+    #[tokio::test]
+    async fn recording_shadow_execution() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut conn = pool.connection().await.unwrap();
+        let latest_batch = conn
+            .vm_runner_dal()
+            .get_shadow_execution_latest_processed_batch(L1BatchNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(latest_batch, L1BatchNumber(0));
+
+        let divergences = [
+            VmDivergence {
+                tx_hash: Some(H256::repeat_byte(1)),
+                kind: "status".into(),
+                details: "success vs failure".into(),
+            },
+            VmDivergence {
+                tx_hash: None,
+                kind: "storage_write".into(),
+                details: "different value".into(),
+            },
+        ];
+        conn.vm_runner_dal()
+            .insert_shadow_divergences(L1BatchNumber(1), ProtocolVersionId::latest(), &divergences)
+            .await
+            .unwrap();
+        conn.vm_runner_dal()
+            .mark_shadow_execution_batch_as_completed(L1BatchNumber(1))
+            .await
+            .unwrap();
+
+        let latest_batch = conn
+            .vm_runner_dal()
+            .get_shadow_execution_latest_processed_batch(L1BatchNumber(0))
+            .await
+            .unwrap();
+        assert_eq!(latest_batch, L1BatchNumber(1));
+        let loaded_divergences = conn
+            .vm_runner_dal()
+            .get_shadow_divergences(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(loaded_divergences, divergences);
+        let loaded_divergences = conn
+            .vm_runner_dal()
+            .get_shadow_divergences(L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert!(loaded_divergences.is_empty());
+    }
 }
@@ -4,17 +4,17 @@ use std::{
     marker::PhantomData,
     panic::Location,
     sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
 use rand::Rng;
 use sqlx::{
     pool::PoolConnection,
-    postgres::{PgConnectOptions, PgPool, PgPoolOptions, Postgres},
+    postgres::{PgConnectOptions, PgListener, PgPool, PgPoolOptions, Postgres},
 };
 use zksync_basic_types::url::SensitiveUrl;
 
@@ -31,6 +31,8 @@ pub struct ConnectionPoolBuilder<DB: DbMarker> {
     max_size: u32,
     acquire_timeout: Duration,
     statement_timeout: Option<Duration>,
+    replica_urls: Vec<SensitiveUrl>,
+    max_replica_lag: Duration,
     _db: PhantomData<DB>,
 }
 
@@ -42,6 +44,8 @@ impl<DB: DbMarker> fmt::Debug for ConnectionPoolBuilder<DB> {
             .field("max_size", &self.max_size)
             .field("acquire_timeout", &self.acquire_timeout)
             .field("statement_timeout", &self.statement_timeout)
+            .field("replica_urls", &self.replica_urls)
+            .field("max_replica_lag", &self.max_replica_lag)
             .field("db", &any::type_name::<DB>())
             .finish()
     }
@@ -72,18 +76,38 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
         self
     }
 
+    /// Sets read replicas for the pool. Replicas are only used by [`ConnectionPool::read_only_connection()`]
+    /// and [`ConnectionPool::read_only_connection_tagged()`], which acquire a connection from a healthy replica
+    /// (in the round-robin order); the main database is used as a fallback if no replicas are available.
+    /// Other methods always use the main database.
+    ///
+    /// A replica is considered unhealthy for a short period after a failed connection attempt, or if its replication lag
+    /// exceeds [the configured threshold](Self::set_max_replica_lag()). Connections to replicas are established lazily,
+    /// so an unavailable replica doesn't prevent building the pool.
+    pub fn set_replica_urls(&mut self, urls: Vec<SensitiveUrl>) -> &mut Self {
+        self.replica_urls = urls
+            .into_iter()
+            .map(|url| url.with_sensitive_query_params(&["user", "password"]))
+            .collect();
+        self
+    }
+
+    /// Sets the maximum replication lag for read replicas. Replicas lagging behind the main database by more
+    /// than this value are not used until they catch up. If not specified, the default value (10 seconds) is used.
+    pub fn set_max_replica_lag(&mut self, max_lag: Option<Duration>) -> &mut Self {
+        if let Some(max_lag) = max_lag {
+            self.max_replica_lag = max_lag;
+        }
+        self
+    }
+
     /// Returns the maximum number of connections that can be allocated by the pool.
     pub fn max_size(&self) -> u32 {
         self.max_size
     }
 
-    /// Builds a connection pool from this builder.
-    pub async fn build(&self) -> anyhow::Result<ConnectionPool<DB>> {
-        let options = PgPoolOptions::new()
-            .max_connections(self.max_size)
-            .acquire_timeout(self.acquire_timeout);
-        let mut connect_options: PgConnectOptions = self
-            .database_url
+    fn connect_options(&self, url: &SensitiveUrl) -> anyhow::Result<PgConnectOptions> {
+        let mut connect_options: PgConnectOptions = url
             .expose_str()
             .parse()
             .context("Failed parsing database URL")?;
@@ -91,15 +115,52 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
             let timeout_string = format!("{}s", timeout.as_secs());
             connect_options = connect_options.options([("statement_timeout", timeout_string)]);
         }
+        Ok(connect_options)
+    }
+
+    /// Builds a connection pool from this builder.
+    pub async fn build(&self) -> anyhow::Result<ConnectionPool<DB>> {
+        let options = PgPoolOptions::new()
+            .max_connections(self.max_size)
+            .acquire_timeout(self.acquire_timeout);
+        let connect_options = self.connect_options(&self.database_url)?;
         let pool = options
+            .clone()
             .connect_with(connect_options)
             .await
             .context("Failed connecting to database")?;
+
+        let replicas = if self.replica_urls.is_empty() {
+            None
+        } else {
+            // Use a short acquire timeout so that an unavailable replica doesn't stall acquiring a connection for too long.
+            let replica_options =
+                options.acquire_timeout(self.acquire_timeout.min(ReplicaSet::ACQUIRE_TIMEOUT));
+            let replicas = self.replica_urls.iter().map(|url| {
+                // Guards against accidental writes in case the URL points to the main database.
+                let connect_options = self
+                    .connect_options(url)?
+                    .options([("default_transaction_read_only", "on")]);
+                Ok(Replica {
+                    url: url.clone(),
+                    pool: replica_options.clone().connect_lazy_with(connect_options),
+                    health: Mutex::default(),
+                })
+            });
+            Some(Arc::new(ReplicaSet {
+                replicas: replicas.collect::<anyhow::Result<_>>()?,
+                next_index: AtomicUsize::new(0),
+                max_lag: self.max_replica_lag,
+            }))
+        };
+
         tracing::info!("Created DB pool with parameters {self:?}");
         Ok(ConnectionPool {
             database_url: self.database_url.clone(),
             inner: pool,
             max_size: self.max_size,
+            replicas,
+            acquire_latency: Arc::default(),
             traced_connections: None,
             _db: PhantomData,
         })
@@ -112,6 +173,8 @@ impl<DB: DbMarker> ConnectionPoolBuilder<DB> {
             max_size: 1,
             acquire_timeout: self.acquire_timeout,
             statement_timeout: self.statement_timeout,
+            replica_urls: self.replica_urls.clone(),
+            max_replica_lag: self.max_replica_lag,
             _db: PhantomData,
         };
         singleton_builder.build().await
@@ -209,6 +272,8 @@ pub struct GlobalConnectionPoolConfig {
     // We consider millisecond precision to be enough for config purposes.
     long_connection_threshold_ms: AtomicU64,
     slow_query_threshold_ms: AtomicU64,
+    report_all_latencies: AtomicBool,
+    redact_query_args: AtomicBool,
 }
 
 impl GlobalConnectionPoolConfig {
@@ -216,6 +281,8 @@ impl GlobalConnectionPoolConfig {
         Self {
             long_connection_threshold_ms: AtomicU64::new(5_000), // 5 seconds
             slow_query_threshold_ms: AtomicU64::new(100),        // 0.1 seconds
+            report_all_latencies: AtomicBool::new(false),
+            redact_query_args: AtomicBool::new(true),
         }
     }
 
@@ -227,6 +294,14 @@ impl GlobalConnectionPoolConfig {
         Duration::from_millis(self.slow_query_threshold_ms.load(Ordering::Relaxed))
     }
 
+    pub(crate) fn report_all_latencies(&self) -> bool {
+        self.report_all_latencies.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn redact_query_args(&self) -> bool {
+        self.redact_query_args.load(Ordering::Relaxed)
+    }
+
     /// Sets the threshold for the DB connection lifetime to denote a connection as long-living and log its details.
     pub fn set_long_connection_threshold(&self, threshold: Duration) -> anyhow::Result<&Self> {
         let millis = u64::try_from(threshold.as_millis())
@@ -246,6 +321,204 @@ impl GlobalConnectionPoolConfig {
         tracing::info!("Set slow query threshold to {threshold:?}");
         Ok(self)
     }
+
+    /// Sets whether latency should be reported for all instrumented DB queries, rather than only for queries
+    /// [explicitly opted in](crate::instrument::Instrumented::report_latency()).
+    pub fn set_report_all_latencies(&self, report: bool) -> &Self {
+        self.report_all_latencies.store(report, Ordering::Relaxed);
+        tracing::info!("Set reporting latencies for all queries to {report}");
+        self
+    }
+
+    /// Sets whether query arguments should be redacted when logging slow or failing queries and in returned errors.
+    /// Arguments may contain sensitive data, so they are redacted by default.
+    pub fn set_redact_query_args(&self, redact: bool) -> &Self {
+        self.redact_query_args.store(redact, Ordering::Relaxed);
+        tracing::info!("Set redacting query args to {redact}");
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReplicaHealth {
+    /// If set, the replica is considered unhealthy until this moment.
+    unhealthy_until: Option<Instant>,
+    /// Last time the replication lag was checked.
+    lag_checked_at: Option<Instant>,
+}
+
+/// Read replica of the database used by a [`ConnectionPool`].
+#[derive(Debug)]
+struct Replica {
+    url: SensitiveUrl,
+    pool: PgPool,
+    health: Mutex<ReplicaHealth>,
+}
+
+impl Replica {
+    /// Returns replication lag in seconds. If the database is not a replica, or it has replayed all received WAL
+    /// (which is the case if the main database doesn't receive writes), the lag is considered to be zero.
+    const LAG_QUERY: &'static str = "SELECT COALESCE(CASE \
+        WHEN NOT pg_is_in_recovery() OR pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0 \
+        ELSE EXTRACT(EPOCH FROM NOW() - pg_last_xact_replay_timestamp()) \
+        END, 0)::FLOAT8";
+
+    fn health(&self) -> std::sync::MutexGuard<'_, ReplicaHealth> {
+        self.health.lock().expect("replica health is poisoned")
+    }
+
+    fn is_healthy(&self) -> bool {
+        let unhealthy_until = self.health().unhealthy_until;
+        unhealthy_until.map_or(true, |until| Instant::now() >= until)
+    }
+
+    fn mark_unhealthy(&self) {
+        self.health().unhealthy_until = Some(Instant::now() + ReplicaSet::UNHEALTHY_COOLDOWN);
+    }
+
+    fn mark_healthy(&self) {
+        self.health().unhealthy_until = None;
+    }
+
+    /// Checks whether the replication lag should be checked, and if so, assumes that it will be checked.
+    fn should_check_lag(&self) -> bool {
+        let now = Instant::now();
+        let mut health = self.health();
+        let should_check = health.lag_checked_at.map_or(true, |checked_at| {
+            now >= checked_at + ReplicaSet::LAG_CHECK_INTERVAL
+        });
+        if should_check {
+            health.lag_checked_at = Some(now);
+        }
+        should_check
+    }
+
+    async fn lag(connection: &mut PoolConnection<Postgres>) -> sqlx::Result<Duration> {
+        let lag_secs: f64 = sqlx::query_scalar(Self::LAG_QUERY)
+            .fetch_one(&mut **connection)
+            .await?;
+        Ok(Duration::try_from_secs_f64(lag_secs).unwrap_or_default())
+    }
+}
+
+#[derive(Debug)]
+struct ReplicaSet {
+    replicas: Vec<Replica>,
+    next_index: AtomicUsize,
+    max_lag: Duration,
+}
+
+impl ReplicaSet {
+    const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(3);
+    const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(10);
+    const DEFAULT_MAX_LAG: Duration = Duration::from_secs(10);
+    const LAG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+    fn healthy_count(&self) -> usize {
+        self.replicas
+            .iter()
+            .filter(|replica| replica.is_healthy())
+            .count()
+    }
+
+    /// Acquires a connection from a healthy replica. Returns `None` if all replicas are unhealthy.
+    async fn acquire(&self) -> Option<PoolConnection<Postgres>> {
+        let start_index = self.next_index.fetch_add(1, Ordering::Relaxed);
+        for i in 0..self.replicas.len() {
+            let replica = &self.replicas[(start_index + i) % self.replicas.len()];
+            if !replica.is_healthy() {
+                continue;
+            }
+            match replica.pool.acquire().await {
+                Ok(mut connection) => {
+                    if replica.should_check_lag() {
+                        match Replica::lag(&mut connection).await {
+                            Ok(lag) if lag > self.max_lag => {
+                                CONNECTION_METRICS.replica_lagging.inc();
+                                tracing::warn!(
+                                    "DB replica {:?} lags by {lag:?} (max allowed: {:?}), marking it as unhealthy for {:?}",
+                                    replica.url,
+                                    self.max_lag,
+                                    Self::UNHEALTHY_COOLDOWN
+                                );
+                                replica.mark_unhealthy();
+                                continue;
+                            }
+                            Ok(_) => { /* the replica is up to date */ }
+                            Err(err) => {
+                                tracing::warn!(
+                                    "Failed checking replication lag for DB replica {:?}, marking it as unhealthy for {:?}: {err}",
+                                    replica.url,
+                                    Self::UNHEALTHY_COOLDOWN
+                                );
+                                replica.mark_unhealthy();
+                                continue;
+                            }
+                        }
+                    }
+                    replica.mark_healthy();
+                    return Some(connection);
+                }
+                Err(err) => {
+                    CONNECTION_METRICS.pool_acquire_error[&(&err).into()].inc();
+                    tracing::warn!(
+                        "Failed to get connection to DB replica {:?}, marking it as unhealthy for {:?}: {err}",
+                        replica.url,
+                        Self::UNHEALTHY_COOLDOWN
+                    );
+                    replica.mark_unhealthy();
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Snapshot of the main database pool utilization returned by [`ConnectionPool::saturation()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolSaturation {
+    /// Number of connections currently checked out from the pool.
+    pub active_connections: u32,
+    /// Maximum number of connections in the pool.
+    pub max_connections: u32,
+    /// Smoothed latency of acquiring a connection from the main pool.
+    pub acquire_latency: Duration,
+}
+
+impl PoolSaturation {
+    /// Returns the share of active connections in the pool, in percent.
+    pub fn utilization_percent(&self) -> u32 {
+        if self.max_connections == 0 {
+            return 0;
+        }
+        let percent = u64::from(self.active_connections) * 100 / u64::from(self.max_connections);
+        percent as u32
+    }
+}
+
+/// Exponentially weighted moving average of connection acquisition latency from the main pool.
+#[derive(Debug, Default)]
+struct AcquireLatency {
+    average_micros: AtomicU64,
+}
+
+impl AcquireLatency {
+    /// Weight of the previous average; new samples have weight `1 / SMOOTHING_FACTOR`.
+    const SMOOTHING_FACTOR: u64 = 8;
+
+    fn observe(&self, latency: Duration) {
+        let sample = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        self.average_micros
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+                let weighted = average.saturating_mul(Self::SMOOTHING_FACTOR - 1);
+                Some(weighted.saturating_add(sample) / Self::SMOOTHING_FACTOR)
+            })
+            .ok();
+    }
+
+    fn get(&self) -> Duration {
+        Duration::from_micros(self.average_micros.load(Ordering::Relaxed))
+    }
 }
 
 /// Pool of reusable database connections.
@@ -254,6 +527,8 @@ pub struct ConnectionPool<DB: DbMarker> {
     pub(crate) inner: PgPool,
     database_url: SensitiveUrl,
     max_size: u32,
+    replicas: Option<Arc<ReplicaSet>>,
+    acquire_latency: Arc<AcquireLatency>,
     pub(crate) traced_connections: Option<Arc<TracedConnections>>,
     _db: PhantomData<DB>,
 }
@@ -266,6 +541,8 @@ impl<DB: DbMarker> fmt::Debug for ConnectionPool<DB> {
             .field("options", &self.inner.options())
             .field("size", &self.inner.size())
             .field("num_idle", &self.inner.num_idle())
+            .field("replicas", &self.replicas)
+            .field("acquire_latency", &self.acquire_latency.get())
             .field("db", &any::type_name::<DB>())
             .field("traced_connections", &self.traced_connections)
             .finish()
@@ -316,6 +593,8 @@ impl<DB: DbMarker> ConnectionPool<DB> {
             max_size: max_pool_size,
             acquire_timeout: Duration::from_secs(30), // Default value used by `sqlx`
             statement_timeout: None,
+            replica_urls: Vec::new(),
+            max_replica_lag: ReplicaSet::DEFAULT_MAX_LAG,
             _db: PhantomData,
         }
     }
@@ -339,6 +618,41 @@ impl<DB: DbMarker> ConnectionPool<DB> {
         self.max_size
     }
 
+    /// Returns the number of read replicas and the number of currently healthy replicas,
+    /// or `None` if the pool has no replicas.
+    pub fn replicas_health(&self) -> Option<(usize, usize)> {
+        let replicas = self.replicas.as_ref()?;
+        Some((replicas.replicas.len(), replicas.healthy_count()))
+    }
+
+    /// Returns the current utilization of the main database pool. Connections acquired from read replicas
+    /// are not taken into account.
+    pub fn saturation(&self) -> PoolSaturation {
+        let idle_connections = u32::try_from(self.inner.num_idle()).unwrap_or(u32::MAX);
+        PoolSaturation {
+            active_connections: self.inner.size().saturating_sub(idle_connections),
+            max_connections: self.max_size,
+            acquire_latency: self.acquire_latency.get(),
+        }
+    }
+
+    /// Creates a listener for Postgres notifications sent to the specified channels (i.e., via `NOTIFY` or `pg_notify()`).
+    /// It holds a connection from this pool for its entire lifetime.
+    ///
+    /// The pool must be connected to the main database rather than a read replica: notifications are not propagated
+    /// to replicas, and hot standby replicas don't support `LISTEN`. Read replicas configured for the pool
+    /// are never used by the listener.
+    pub async fn listener(&self, channels: &[&str]) -> anyhow::Result<PgListener> {
+        let mut listener = PgListener::connect_with(&self.inner)
+            .await
+            .context("failed creating Postgres listener")?;
+        listener
+            .listen_all(channels.iter().copied())
+            .await
+            .with_context(|| format!("failed listening to channels {channels:?}"))?;
+        Ok(listener)
+    }
+
     /// Creates a `Connection` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
     /// it will be able to recover the connection (or, if connection cannot
@@ -348,7 +662,7 @@ impl<DB: DbMarker> ConnectionPool<DB> {
     /// This method is intended to be used in crucial contexts, where the
     /// database access is must-have (e.g. block committer).
     pub async fn connection(&self) -> DalResult<Connection<'_, DB>> {
-        self.connection_inner(None).await
+        self.connection_inner(None, false).await
     }
 
     /// A version of `connection` that would also expose the duration of the connection
@@ -368,16 +682,58 @@ impl<DB: DbMarker> ConnectionPool<DB> {
                 requester,
                 location,
             };
-            self.connection_inner(Some(tags)).await
+            self.connection_inner(Some(tags), false).await
+        }
+    }
+
+    /// Creates a `Connection` entity for read-only queries. If the pool has read replicas, the connection is acquired
+    /// from a healthy replica, falling back to the main database if there are no such replicas. Queries executed
+    /// on the connection may thus observe slightly outdated data (bounded by the max replication lag of the pool).
+    ///
+    /// Transactions on replica connections are read-only, so any writes will fail.
+    pub async fn read_only_connection(&self) -> DalResult<Connection<'_, DB>> {
+        self.connection_inner(None, true).await
+    }
+
+    /// Tagged version of [`Self::read_only_connection()`]; see [`Self::connection_tagged()`] for details.
+    #[track_caller] // In order to use it, we have to de-sugar `async fn`
+    pub fn read_only_connection_tagged(
+        &self,
+        requester: &'static str,
+    ) -> impl Future<Output = DalResult<Connection<'_, DB>>> + '_ {
+        let location = Location::caller();
+        async move {
+            let tags = ConnectionTags {
+                requester,
+                location,
+            };
+            self.connection_inner(Some(tags), true).await
         }
     }
 
     async fn connection_inner(
         &self,
         tags: Option<ConnectionTags>,
+        read_only: bool,
     ) -> DalResult<Connection<'_, DB>> {
         let acquire_latency = CONNECTION_METRICS.acquire.start();
-        let conn = self.acquire_connection_retried(tags.as_ref()).await?;
+        let replicas = self.replicas.as_ref().filter(|_| read_only);
+        let replica_conn = match replicas {
+            Some(replicas) => {
+                let conn = replicas.acquire().await;
+                if conn.is_none() {
+                    CONNECTION_METRICS.replica_fallback.inc();
+                    let tags_display = ConnectionTags::display(tags.as_ref());
+                    tracing::debug!("No healthy DB replicas ({tags_display}), falling back to the main database");
+                }
+                conn
+            }
+            None => None,
+        };
+        let conn = match replica_conn {
+            Some(conn) => conn,
+            None => self.acquire_connection_retried(tags.as_ref()).await?,
+        };
         let elapsed = acquire_latency.observe();
         if let Some(tags) = &tags {
             CONNECTION_METRICS.acquire_tagged[&tags.requester].observe(elapsed);
@@ -403,7 +759,9 @@ impl<DB: DbMarker> ConnectionPool<DB> {
                 .observe(self.inner.size() as usize);
             CONNECTION_METRICS.pool_idle.observe(self.inner.num_idle());
 
+            let started_at = Instant::now();
             let connection = self.inner.acquire().await;
+            self.acquire_latency.observe(started_at.elapsed());
             let connection_err = match connection {
                 Ok(connection) => return Ok(connection),
                 Err(err) => err,
@@ -465,4 +823,116 @@ mod tests {
             sqlx::Error::Database(db_err) if db_err.message().contains("statement timeout")
         );
     }
+
+    #[tokio::test]
+    async fn using_read_replicas() {
+        let db_url = TestTemplate::empty()
+            .unwrap()
+            .create_db::<InternalMarker>(1)
+            .await
+            .unwrap()
+            .database_url;
+        let mut unavailable_replica_url = db_url.expose_url().clone();
+        unavailable_replica_url.set_port(Some(1)).unwrap();
+
+        let pool = ConnectionPool::<InternalMarker>::builder(db_url.clone(), 2)
+            .set_replica_urls(vec![unavailable_replica_url.into(), db_url])
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(pool.replicas_health(), Some((2, 2)));
+
+        // Non-read-only connections must not use replicas.
+        for _ in 0..2 {
+            let mut storage = pool.connection().await.unwrap();
+            sqlx::query("CREATE TEMPORARY TABLE test (x INT)")
+                .execute(storage.conn())
+                .await
+                .unwrap();
+        }
+        assert_eq!(pool.replicas_health(), Some((2, 2)));
+
+        // Acquire connections until the unavailable replica is tried.
+        for _ in 0..2 {
+            let mut storage = pool.read_only_connection().await.unwrap();
+            sqlx::query("SELECT 1")
+                .execute(storage.conn())
+                .await
+                .unwrap();
+        }
+        assert_eq!(pool.replicas_health(), Some((2, 1)));
+
+        // The healthy "replica" is the main database, so it has zero lag, but its connections are read-only.
+        let replica = &pool.replicas.as_ref().unwrap().replicas[1];
+        let mut conn = replica.pool.acquire().await.unwrap();
+        assert_eq!(Replica::lag(&mut conn).await.unwrap(), Duration::ZERO);
+        drop(conn);
+
+        let mut storage = pool.read_only_connection().await.unwrap();
+        let err = sqlx::query("CREATE TABLE test (x INT)")
+            .execute(storage.conn())
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            sqlx::Error::Database(db_err) if db_err.message().contains("read-only transaction")
+        );
+    }
+
+    #[tokio::test]
+    async fn listening_to_notifications() {
+        let pool = ConnectionPool::<InternalMarker>::test_pool().await;
+        let mut listener = pool.listener(&["test_channel"]).await.unwrap();
+
+        let mut storage = pool.connection().await.unwrap();
+        sqlx::query(
+            "SELECT pg_notify('other_channel', 'ignored'), pg_notify('test_channel', '42')",
+        )
+        .execute(storage.conn())
+        .await
+        .unwrap();
+
+        let notification = listener.recv().await.unwrap();
+        assert_eq!(notification.channel(), "test_channel");
+        assert_eq!(notification.payload(), "42");
+    }
+
+    #[tokio::test]
+    async fn measuring_pool_saturation() {
+        let db_url = TestTemplate::empty()
+            .unwrap()
+            .create_db::<InternalMarker>(2)
+            .await
+            .unwrap()
+            .database_url;
+        let pool = ConnectionPool::<InternalMarker>::builder(db_url, 2)
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(pool.saturation().active_connections, 0);
+        assert_eq!(pool.saturation().utilization_percent(), 0);
+
+        let first_conn = pool.connection().await.unwrap();
+        let saturation = pool.saturation();
+        assert_eq!(saturation.active_connections, 1);
+        assert_eq!(saturation.max_connections, 2);
+        assert_eq!(saturation.utilization_percent(), 50);
+
+        let _second_conn = pool.connection().await.unwrap();
+        assert_eq!(pool.saturation().utilization_percent(), 100);
+        drop(first_conn);
+    }
+
+    #[test]
+    fn smoothing_acquire_latency() {
+        let latency = AcquireLatency::default();
+        latency.observe(Duration::from_millis(80));
+        assert_eq!(latency.get(), Duration::from_millis(10));
+        for _ in 0..100 {
+            latency.observe(Duration::from_millis(80));
+        }
+        let average = latency.get();
+        assert!(average > Duration::from_millis(79), "{average:?}");
+        assert!(average <= Duration::from_millis(80), "{average:?}");
+    }
 }
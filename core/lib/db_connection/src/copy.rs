@@ -0,0 +1,166 @@
+//! Encoding of data sent with binary `COPY` statements.
+
+use sqlx::types::chrono::{DateTime, Utc};
+
+/// Signature, flags field and header extension length of the binary `COPY` format.
+const HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+/// Field count marking the end of data.
+const TRAILER: i16 = -1;
+/// Postgres epoch (2000-01-01 00:00:00 UTC) as a UNIX timestamp in microseconds.
+const POSTGRES_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+/// Buffer for rows encoded in the [binary `COPY` format]. Unlike the text format, values are not parsed
+/// or converted by Postgres, so pushed values must exactly match the types of the corresponding columns
+/// (e.g., [`Self::push_int4()`] for `INT` columns and [`Self::push_int8()`] for `BIGINT` ones).
+///
+/// The buffer can be used with a [`CopyStatement`](crate::instrument::CopyStatement) with
+/// the `FORMAT BINARY` option. Encoded data can either be sent at once after [finishing](Self::finish()) the buffer,
+/// or streamed in chunks using [`Self::take_chunk()`].
+///
+/// [binary `COPY` format]: https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4
+#[derive(Debug)]
+pub struct BinaryCopyBuffer {
+    bytes: Vec<u8>,
+    remaining_fields: i16,
+}
+
+impl Default for BinaryCopyBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinaryCopyBuffer {
+    /// Recommended size of chunks when streaming data.
+    pub const CHUNK_SIZE: usize = 1 << 20;
+
+    /// Creates a buffer containing the format header.
+    pub fn new() -> Self {
+        Self {
+            bytes: HEADER.to_vec(),
+            remaining_fields: 0,
+        }
+    }
+
+    /// Returns the number of buffered bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Checks whether the buffer is empty (e.g., after [`Self::take_chunk()`]).
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Starts a new row with the specified number of fields. All fields of the previous row must be pushed.
+    pub fn start_row(&mut self, field_count: i16) -> &mut Self {
+        assert_eq!(self.remaining_fields, 0, "previous row has unfilled fields");
+        assert!(field_count > 0, "row must have at least one field");
+        self.bytes.extend_from_slice(&field_count.to_be_bytes());
+        self.remaining_fields = field_count;
+        self
+    }
+
+    fn push_field(&mut self, value: &[u8]) -> &mut Self {
+        assert!(self.remaining_fields > 0, "too many fields pushed to row");
+        let len = i32::try_from(value.len()).expect("field value is too large");
+        self.bytes.extend_from_slice(&len.to_be_bytes());
+        self.bytes.extend_from_slice(value);
+        self.remaining_fields -= 1;
+        self
+    }
+
+    /// Pushes a `BYTEA` value.
+    pub fn push_bytea(&mut self, value: &[u8]) -> &mut Self {
+        self.push_field(value)
+    }
+
+    /// Pushes an `INT` value.
+    pub fn push_int4(&mut self, value: i32) -> &mut Self {
+        self.push_field(&value.to_be_bytes())
+    }
+
+    /// Pushes a `BIGINT` value.
+    pub fn push_int8(&mut self, value: i64) -> &mut Self {
+        self.push_field(&value.to_be_bytes())
+    }
+
+    /// Pushes a `BOOLEAN` value.
+    pub fn push_bool(&mut self, value: bool) -> &mut Self {
+        self.push_field(&[u8::from(value)])
+    }
+
+    /// Pushes a `TIMESTAMP` (i.e., without time zone) value. As with text `COPY`s, the timestamp is stored in UTC.
+    pub fn push_timestamp(&mut self, value: DateTime<Utc>) -> &mut Self {
+        self.push_int8(value.timestamp_micros() - POSTGRES_EPOCH_MICROS)
+    }
+
+    /// Pushes a `NULL` value.
+    pub fn push_null(&mut self) -> &mut Self {
+        assert!(self.remaining_fields > 0, "too many fields pushed to row");
+        self.bytes.extend_from_slice(&(-1_i32).to_be_bytes());
+        self.remaining_fields -= 1;
+        self
+    }
+
+    /// Takes all data buffered so far, leaving the buffer empty. The row being pushed doesn't need to be complete.
+    pub fn take_chunk(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.bytes)
+    }
+
+    /// Finishes encoding and returns the remaining data.
+    pub fn finish(mut self) -> Vec<u8> {
+        assert_eq!(self.remaining_fields, 0, "last row has unfilled fields");
+        self.bytes.extend_from_slice(&TRAILER.to_be_bytes());
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn encoding_rows() {
+        let mut buffer = BinaryCopyBuffer::new();
+        buffer
+            .start_row(3)
+            .push_int4(1)
+            .push_bytea(b"\x01\x02")
+            .push_null();
+        buffer
+            .start_row(3)
+            .push_int8(-1)
+            .push_bool(true)
+            .push_timestamp(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 1).unwrap());
+        let header_len = HEADER.len();
+        let chunk = buffer.take_chunk();
+        assert_eq!(&chunk[..header_len], HEADER);
+        assert!(buffer.is_empty());
+        let data = buffer.finish();
+
+        let expected_rows: &[u8] = &[
+            0, 3, // field count
+            0, 0, 0, 4, 0, 0, 0, 1, // `INT` 1
+            0, 0, 0, 2, 1, 2, // `BYTEA`
+            255, 255, 255, 255, // `NULL`
+            0, 3, // field count
+            0, 0, 0, 8, 255, 255, 255, 255, 255, 255, 255, 255, // `BIGINT` -1
+            0, 0, 0, 1, 1, // `BOOLEAN` true
+            0, 0, 0, 8, 0, 0, 0, 0, 0, 0x0f, 0x42,
+            0x40, // `TIMESTAMP` 1s after Postgres epoch
+        ];
+        assert_eq!(&chunk[header_len..], expected_rows);
+        assert_eq!(data, [255, 255]);
+    }
+
+    #[test]
+    #[should_panic(expected = "previous row has unfilled fields")]
+    fn incomplete_row() {
+        let mut buffer = BinaryCopyBuffer::new();
+        buffer.start_row(2).push_int4(1);
+        buffer.start_row(1);
+    }
+}
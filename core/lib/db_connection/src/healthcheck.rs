@@ -7,13 +7,20 @@ use crate::{connection::DbMarker, connection_pool::ConnectionPool};
 struct ConnectionPoolHealthDetails {
     pool_size: u32,
     max_size: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replica_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    healthy_replica_count: Option<usize>,
 }
 
 impl ConnectionPoolHealthDetails {
     fn new<DB: DbMarker>(pool: &ConnectionPool<DB>) -> Self {
+        let replicas_health = pool.replicas_health();
         Self {
             pool_size: pool.inner.size(),
             max_size: pool.max_size(),
+            replica_count: replicas_health.map(|(count, _)| count),
+            healthy_replica_count: replicas_health.map(|(_, healthy_count)| healthy_count),
         }
     }
 }
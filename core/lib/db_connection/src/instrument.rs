@@ -13,6 +13,7 @@
 
 use std::{fmt, future::Future, panic::Location};
 
+use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use sqlx::{
     postgres::{PgCopyIn, PgQueryResult, PgRow},
     query::{Map, Query, QueryAs, QueryScalar},
@@ -34,13 +35,31 @@ type ThreadSafeDebug<'a> = dyn fmt::Debug + Send + Sync + 'a;
 #[derive(Debug, Clone, Default)]
 struct QueryArgs<'a> {
     inner: Vec<(&'static str, &'a ThreadSafeDebug<'a>)>,
+    /// If set, argument values are replaced with a placeholder both in logs and in errors.
+    redacted: bool,
 }
 
 impl QueryArgs<'_> {
+    const REDACTED_VALUE: &'static str = "***";
+
+    fn new() -> Self {
+        Self {
+            inner: vec![],
+            redacted: ConnectionPool::<InternalMarker>::global_config().redact_query_args(),
+        }
+    }
+
     fn to_owned(&self) -> Vec<(&'static str, String)> {
         self.inner
             .iter()
-            .map(|(name, value)| (*name, format!("{value:?}")))
+            .map(|(name, value)| {
+                let value = if self.redacted {
+                    Self::REDACTED_VALUE.to_owned()
+                } else {
+                    format!("{value:?}")
+                };
+                (*name, value)
+            })
             .collect()
     }
 }
@@ -52,7 +71,11 @@ impl fmt::Display for QueryArgs<'_> {
         } else {
             formatter.write_str("(")?;
             for (i, (name, value)) in self.inner.iter().enumerate() {
-                write!(formatter, "{name}={value:?}")?;
+                if self.redacted {
+                    write!(formatter, "{name}={}", Self::REDACTED_VALUE)?;
+                } else {
+                    write!(formatter, "{name}={value:?}")?;
+                }
                 if i + 1 < self.inner.len() {
                     formatter.write_str(", ")?;
                 }
@@ -164,19 +187,36 @@ impl fmt::Debug for ActiveCopy<'_> {
 }
 
 impl ActiveCopy<'_> {
+    fn convert_error(
+        data: &InstrumentedData<'_>,
+        tags: Option<&ConnectionTags>,
+        err: sqlx::Error,
+    ) -> DalError {
+        DalRequestError::new(err, data.name, data.location)
+            .with_args(data.args.to_owned())
+            .with_connection_tags(tags.cloned())
+            .into()
+    }
+
     /// Sends the specified bytes to the database and finishes the copy statement.
     // FIXME: measure latency?
     pub async fn send(mut self, data: &[u8]) -> DalResult<()> {
-        let inner_send = async {
-            self.raw.send(data).await?;
-            self.raw.finish().await.map(drop)
-        };
-        inner_send.await.map_err(|err| {
-            DalRequestError::new(err, self.data.name, self.data.location)
-                .with_args(self.data.args.to_owned())
-                .with_connection_tags(self.tags.cloned())
-                .into()
-        })
+        self.send_chunk(data).await?;
+        self.finish().await
+    }
+
+    /// Sends a chunk of data to the database without finishing the copy statement. This allows streaming data
+    /// without buffering all of it in memory.
+    pub async fn send_chunk(&mut self, data: &[u8]) -> DalResult<()> {
+        let result = self.raw.send(data).await.map(drop);
+        result.map_err(|err| Self::convert_error(&self.data, self.tags, err))
+    }
+
+    /// Finishes the copy statement after all data is sent using [`Self::send_chunk()`].
+    pub async fn finish(self) -> DalResult<()> {
+        let Self { raw, data, tags } = self;
+        let result = raw.finish().await.map(drop);
+        result.map_err(|err| Self::convert_error(&data, tags, err))
     }
 }
 
@@ -194,7 +234,7 @@ impl<'a> InstrumentedData<'a> {
         Self {
             name,
             location,
-            args: QueryArgs::default(),
+            args: QueryArgs::new(),
             report_latency: false,
             slow_query_reporting_enabled: true,
         }
@@ -238,7 +278,9 @@ impl<'a> InstrumentedData<'a> {
         };
 
         let elapsed = started_at.elapsed();
-        if report_latency {
+        if report_latency
+            || ConnectionPool::<InternalMarker>::global_config().report_all_latencies()
+        {
             REQUEST_METRICS.request[&name].observe(elapsed);
         }
 
@@ -278,6 +320,9 @@ impl<'a> InstrumentedData<'a> {
 ///   included in the case of a slow query, plus the error info.
 /// - Slow and erroneous queries are also reported using metrics (`dal.request.slow` and `dal.request.error`,
 ///   respectively). The query name is included as a metric label; args are not included for obvious reasons.
+/// - Query latency is reported as a metric (`dal.request`) labeled by the query name if [`Self::report_latency()`]
+///   is called, or if reporting latencies for all queries is enabled in the global pool config.
+/// - Logged args can be redacted via the global pool config; this affects both logs and returned errors.
 #[derive(Debug, Clone)]
 pub struct Instrumented<'a, Q> {
     query: Q,
@@ -332,7 +377,7 @@ impl<'a> Instrumented<'a, ()> {
 }
 
 impl<'a, Q> Instrumented<'a, Q> {
-    /// Indicates that latency should be reported for all calls.
+    /// Indicates that latency should be reported for all calls, even if this is not enabled globally.
     pub fn report_latency(mut self) -> Self {
         self.data.report_latency = true;
         self
@@ -440,6 +485,45 @@ where
         let (conn, tags) = storage.conn_and_tags();
         self.data.fetch(tags, self.query.fetch_all(conn)).await
     }
+
+    /// Streams rows returned by this query. Since rows are consumed lazily, query latency is not reported
+    /// and slow queries are not logged; errors are logged and reported as for other queries.
+    pub fn fetch<'c, DB: DbMarker>(
+        self,
+        storage: &'c mut Connection<'_, DB>,
+    ) -> BoxStream<'c, DalResult<O>>
+    where
+        'q: 'c,
+        F: 'c,
+        O: 'c,
+    {
+        let (conn, tags) = storage.conn_and_tags();
+        let InstrumentedData {
+            name,
+            location,
+            args,
+            ..
+        } = self.data;
+        let logged_args = args.to_string();
+        let args = args.to_owned();
+        let tags = tags.cloned();
+        self.query
+            .fetch(conn)
+            .map_err(move |err| {
+                tracing::warn!(
+                    "Query {name}{logged_args} called at {file}:{line} [{tags}] has resulted in error: {err}",
+                    file = location.file(),
+                    line = location.line(),
+                    tags = ConnectionTags::display(tags.as_ref())
+                );
+                REQUEST_METRICS.request_error[&name].inc();
+                DalRequestError::new(err, name, location)
+                    .with_args(args.clone())
+                    .with_connection_tags(tags.clone())
+                    .into()
+            })
+            .boxed()
+    }
 }
 
 impl<'a> Instrumented<'a, CopyStatement> {
@@ -472,6 +556,26 @@ mod tests {
     use super::*;
     use crate::{connection_pool::ConnectionPool, utils::InternalMarker};
 
+    #[test]
+    fn displaying_query_args() {
+        let l2_block = L2BlockNumber(1);
+        let hash = H256::zero();
+        let mut args = QueryArgs::default();
+        args.inner.push(("l2_block", &l2_block));
+        args.inner.push(("hash", &hash));
+        assert_eq!(
+            args.to_string(),
+            format!("(l2_block=L2BlockNumber(1), hash={hash:?})")
+        );
+
+        args.redacted = true;
+        assert_eq!(args.to_string(), "(l2_block=***, hash=***)");
+        assert_eq!(
+            args.to_owned(),
+            [("l2_block", "***".to_owned()), ("hash", "***".to_owned())]
+        );
+    }
+
     #[tokio::test]
     async fn instrumenting_erroneous_query() {
         let pool = ConnectionPool::<InternalMarker>::test_pool().await;
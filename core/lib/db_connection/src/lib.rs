@@ -2,6 +2,7 @@
 
 pub mod connection;
 pub mod connection_pool;
+pub mod copy;
 pub mod error;
 pub mod healthcheck;
 pub mod instrument;
@@ -87,6 +87,10 @@ pub(crate) struct ConnectionMetrics {
     pub pool_idle: Histogram<usize>,
     /// Number of errors occurred when acquiring a DB connection.
     pub pool_acquire_error: Family<ConnectionErrorKind, Counter>,
+    /// Number of times a connection was acquired from the main database because no read replicas were healthy.
+    pub replica_fallback: Counter,
+    /// Number of times a read replica was marked as unhealthy because of the replication lag.
+    pub replica_lagging: Counter,
     /// Lifetime of a DB connection, tagged with the requester label.
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds, labels = ["requester"])]
     pub lifetime: LabeledFamily<&'static str, Histogram<Duration>>,
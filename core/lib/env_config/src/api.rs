@@ -3,7 +3,7 @@ use zksync_config::configs::{
     api::{
         ContractVerificationApiConfig, HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig,
     },
-    ApiConfig, PrometheusConfig,
+    ApiConfig, ApiSecrets, PrometheusConfig,
 };
 
 use crate::{envy_load, FromEnv};
@@ -44,9 +44,27 @@ impl FromEnv for MerkleTreeApiConfig {
     }
 }
 
+impl FromEnv for ApiSecrets {
+    fn from_env() -> anyhow::Result<Self> {
+        let client_rate_limit_api_keys = match std::env::var("API_CLIENT_RATE_LIMIT_API_KEYS") {
+            Ok(keys) => keys
+                .split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(|key| key.to_owned().into())
+                .collect(),
+            Err(_) => vec![],
+        };
+        Ok(Self {
+            admin_token: std::env::var("API_ADMIN_TOKEN").ok().map(Into::into),
+            client_rate_limit_api_keys,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::num::{NonZeroU32, NonZeroUsize};
+    use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 
     use super::*;
     use crate::test_utils::{addr, hash, EnvMutex};
@@ -62,6 +80,7 @@ mod tests {
                 ws_url: "ws://127.0.0.1:3051".into(),
                 req_entities_limit: Some(10000),
                 filters_disabled: false,
+                persistent_filters: true,
                 filters_limit: Some(10000),
                 subscriptions_limit: Some(10000),
                 pubsub_polling_interval: Some(200),
@@ -81,6 +100,7 @@ mod tests {
                 initial_writes_cache_size_mb: Some(32),
                 latest_values_cache_size_mb: Some(256),
                 fee_history_limit: Some(100),
+                trace_filter_block_range_limit: Some(1000),
                 max_batch_request_size: Some(200),
                 max_response_body_size_mb: Some(10),
                 max_response_body_size_overrides_mb: [
@@ -91,6 +111,24 @@ mod tests {
                 .into_iter()
                 .collect(),
                 websocket_requests_per_minute_limit: Some(NonZeroU32::new(10).unwrap()),
+                client_requests_per_minute_limit: Some(NonZeroU32::new(600).unwrap()),
+                client_requests_per_minute_overrides: [
+                    ("eth_call", NonZeroU32::new(100).unwrap()),
+                    ("debug", NonZeroU32::new(10).unwrap()),
+                ]
+                .into_iter()
+                .collect(),
+                client_rate_limit_trusted_proxies: vec![
+                    "10.0.0.1".parse().unwrap(),
+                    "10.0.0.2".parse().unwrap(),
+                ],
+                http_response_compression: true,
+                http_methods_allowlist: Some(vec!["eth".to_owned(), "zks".to_owned()]),
+                http_methods_denylist: vec!["eth_call".to_owned()],
+                ws_methods_allowlist: None,
+                ws_methods_denylist: vec!["debug".to_owned()],
+                admin_http_port: Some(3055),
+                txpool_namespace_enabled: true,
                 tree_api_url: None,
                 mempool_cache_update_interval: Some(50),
                 mempool_cache_size: Some(10000),
@@ -109,7 +147,11 @@ mod tests {
                 slow_time_limit_ms: Some(250),
                 hard_time_limit_ms: Some(2_000),
             },
-            merkle_tree: MerkleTreeApiConfig { port: 8082 },
+            merkle_tree: MerkleTreeApiConfig {
+                port: 8082,
+                replica_path: Some("/db/tree_replica".to_owned()),
+                replica_poll_interval_ms: NonZeroU64::new(500),
+            },
         }
     }
 
@@ -123,6 +165,7 @@ mod tests {
             API_WEB3_JSON_RPC_WS_URL="ws://127.0.0.1:3051"
             API_WEB3_JSON_RPC_REQ_ENTITIES_LIMIT=10000
             API_WEB3_JSON_RPC_FILTERS_DISABLED=false
+            API_WEB3_JSON_RPC_PERSISTENT_FILTERS=true
             API_WEB3_JSON_RPC_FILTERS_LIMIT=10000
             API_WEB3_JSON_RPC_SUBSCRIPTIONS_LIMIT=10000
             API_WEB3_JSON_RPC_PUBSUB_POLLING_INTERVAL=200
@@ -139,8 +182,18 @@ mod tests {
             API_WEB3_JSON_RPC_INITIAL_WRITES_CACHE_SIZE_MB=32
             API_WEB3_JSON_RPC_LATEST_VALUES_CACHE_SIZE_MB=256
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
+            API_WEB3_JSON_RPC_TRACE_FILTER_BLOCK_RANGE_LIMIT=1000
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_CLIENT_REQUESTS_PER_MINUTE_LIMIT=600
+            API_WEB3_JSON_RPC_CLIENT_REQUESTS_PER_MINUTE_OVERRIDES="eth_call=100, debug=10"
+            API_WEB3_JSON_RPC_CLIENT_RATE_LIMIT_TRUSTED_PROXIES="10.0.0.1,10.0.0.2"
+            API_WEB3_JSON_RPC_HTTP_RESPONSE_COMPRESSION=true
+            API_WEB3_JSON_RPC_ADMIN_HTTP_PORT=3055
+            API_WEB3_JSON_RPC_TXPOOL_NAMESPACE_ENABLED=true
+            API_WEB3_JSON_RPC_HTTP_METHODS_ALLOWLIST="eth,zks"
+            API_WEB3_JSON_RPC_HTTP_METHODS_DENYLIST="eth_call"
+            API_WEB3_JSON_RPC_WS_METHODS_DENYLIST="debug"
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_SIZE=10000
             API_WEB3_JSON_RPC_MEMPOOL_CACHE_UPDATE_INTERVAL=50
             API_CONTRACT_VERIFICATION_PORT="3070"
@@ -154,10 +207,30 @@ mod tests {
             API_HEALTHCHECK_SLOW_TIME_LIMIT_MS=250
             API_HEALTHCHECK_HARD_TIME_LIMIT_MS=2000
             API_MERKLE_TREE_PORT=8082
+            API_MERKLE_TREE_REPLICA_PATH="/db/tree_replica"
+            API_MERKLE_TREE_REPLICA_POLL_INTERVAL_MS=500
         "#;
         lock.set_env(config);
 
         let actual = ApiConfig::from_env().unwrap();
         assert_eq!(actual, expected_config());
     }
+
+    #[test]
+    fn secrets_from_env() {
+        let mut lock = MUTEX.lock();
+        lock.set_env(
+            r#"
+            API_ADMIN_TOKEN="admin-token"
+            API_CLIENT_RATE_LIMIT_API_KEYS="key1,key2"
+            "#,
+        );
+
+        let actual = ApiSecrets::from_env().unwrap();
+        let expected = ApiSecrets {
+            admin_token: Some("admin-token".to_owned().into()),
+            client_rate_limit_api_keys: vec!["key1".to_owned().into(), "key2".to_owned().into()],
+        };
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,40 @@
+use zksync_config::configs::BaseTokenAdjusterConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for BaseTokenAdjusterConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("base_token_adjuster", "BASE_TOKEN_ADJUSTER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            BASE_TOKEN_ADJUSTER_PRICE_POLLING_INTERVAL_MS=10000
+            BASE_TOKEN_ADJUSTER_FIXED_RATIO_NUMERATOR=3
+            BASE_TOKEN_ADJUSTER_FIXED_RATIO_DENOMINATOR=2
+        "#;
+        lock.set_env(config);
+
+        let actual = BaseTokenAdjusterConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            BaseTokenAdjusterConfig {
+                price_polling_interval_ms: 10_000,
+                fixed_ratio_numerator: NonZeroU64::new(3).unwrap(),
+                fixed_ratio_denominator: NonZeroU64::new(2).unwrap(),
+            }
+        );
+    }
+}
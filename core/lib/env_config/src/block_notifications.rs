@@ -0,0 +1,38 @@
+use zksync_config::configs::BlockNotificationsConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for BlockNotificationsConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("block_notifications", "BLOCK_NOTIFICATIONS_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            BLOCK_NOTIFICATIONS_POLL_INTERVAL_MS=200
+            BLOCK_NOTIFICATIONS_MAX_BATCH_SIZE=50
+            BLOCK_NOTIFICATIONS_WEBHOOK_URL=http://127.0.0.1:8080/notifications
+        "#;
+        lock.set_env(config);
+
+        let actual = BlockNotificationsConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            BlockNotificationsConfig {
+                poll_interval_ms: 200,
+                max_batch_size: 50,
+                webhook_url: Some("http://127.0.0.1:8080/notifications".to_owned()),
+            }
+        );
+    }
+}
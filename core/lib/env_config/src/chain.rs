@@ -93,9 +93,20 @@ mod tests {
             batch_overhead_l1_gas: 800_000,
             max_gas_per_batch: 200_000_000,
             max_pubdata_per_batch: 100_000,
+            max_pubdata_cost_per_batch: Some(1_000_000_000_000_000),
+            max_tx_execution_time_ms: Some(500),
+            max_priority_ops_per_batch: Some(100),
+            max_priority_ops_gas_per_batch: Some(100_000_000),
+            max_priority_ops_per_l2_block: Some(10),
+            max_priority_ops_gas_per_l2_block: None,
             fee_model_version: FeeModelVersion::V2,
             validation_computational_gas_limit: 10_000_000,
             save_call_traces: false,
+            enable_opcode_profiling: true,
+            enable_gas_attribution: true,
+            enable_mempool_pre_execution: true,
+            enable_execution_result_cache: true,
+            enable_storage_prefetching: true,
             bootloader_hash: Some(hash(
                 "0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e",
             )),
@@ -130,9 +141,19 @@ mod tests {
             CHAIN_STATE_KEEPER_BATCH_OVERHEAD_L1_GAS="800000"
             CHAIN_STATE_KEEPER_MAX_GAS_PER_BATCH="200000000"
             CHAIN_STATE_KEEPER_MAX_PUBDATA_PER_BATCH="100000"
+            CHAIN_STATE_KEEPER_MAX_PUBDATA_COST_PER_BATCH="1000000000000000"
+            CHAIN_STATE_KEEPER_MAX_TX_EXECUTION_TIME_MS="500"
+            CHAIN_STATE_KEEPER_MAX_PRIORITY_OPS_PER_BATCH="100"
+            CHAIN_STATE_KEEPER_MAX_PRIORITY_OPS_GAS_PER_BATCH="100000000"
+            CHAIN_STATE_KEEPER_MAX_PRIORITY_OPS_PER_L2_BLOCK="10"
             CHAIN_STATE_KEEPER_FEE_MODEL_VERSION="V2"
             CHAIN_STATE_KEEPER_VALIDATION_COMPUTATIONAL_GAS_LIMIT="10000000"
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
+            CHAIN_STATE_KEEPER_ENABLE_OPCODE_PROFILING="true"
+            CHAIN_STATE_KEEPER_ENABLE_GAS_ATTRIBUTION="true"
+            CHAIN_STATE_KEEPER_ENABLE_MEMPOOL_PRE_EXECUTION="true"
+            CHAIN_STATE_KEEPER_ENABLE_EXECUTION_RESULT_CACHE="true"
+            CHAIN_STATE_KEEPER_ENABLE_STORAGE_PREFETCHING="true"
             CHAIN_STATE_KEEPER_BOOTLOADER_HASH=0x010007ede999d096c84553fb514d3d6ca76fbf39789dda76bfeda9f3ae06236e
             CHAIN_STATE_KEEPER_DEFAULT_AA_HASH=0x0100055b041eb28aff6e3a6e0f37c31fd053fc9ef142683b05e5f0aee6934066
             CHAIN_STATE_KEEPER_L1_BATCH_COMMIT_DATA_GENERATOR_MODE="{l1_batch_commit_data_generator_mode}"
@@ -168,6 +189,14 @@ mod tests {
             stuck_tx_timeout: 10,
             remove_stuck_txs: true,
             delay_interval: 100,
+            fifo_ordering: true,
+            replacement_fee_bump_percent: Some(10),
+            tx_ttl_sec: Some(3600),
+            max_nonce_gap: Some(100),
+            eviction_interval_ms: None,
+            max_pending_txs_per_account: Some(64),
+            address_denylist_path: Some("/etc/zksync/denylist.txt".to_owned()),
+            address_denylist_reload_interval_ms: None,
         }
     }
 
@@ -181,6 +210,12 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_FIFO_ORDERING="true"
+            CHAIN_MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT="10"
+            CHAIN_MEMPOOL_TX_TTL_SEC="3600"
+            CHAIN_MEMPOOL_MAX_NONCE_GAP="100"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_ADDRESS_DENYLIST_PATH="/etc/zksync/denylist.txt"
         "#;
         lock.set_env(config);
 
@@ -194,6 +229,10 @@ mod tests {
             http_req_max_retry_number: 5,
             http_req_retry_interval_sec: 2,
             replication_lag_limit_sec: Some(10),
+            db_integrity_check_chunk_size: Some(100),
+            pool_saturation_shedding_percent: Some(90),
+            pool_acquire_latency_shedding_ms: Some(500),
+            pool_saturation_trip_after_sec: Some(60),
         }
     }
 
@@ -205,6 +244,10 @@ mod tests {
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
             CHAIN_CIRCUIT_BREAKER_REPLICATION_LAG_LIMIT_SEC="10"
+            CHAIN_CIRCUIT_BREAKER_DB_INTEGRITY_CHECK_CHUNK_SIZE="100"
+            CHAIN_CIRCUIT_BREAKER_POOL_SATURATION_SHEDDING_PERCENT="90"
+            CHAIN_CIRCUIT_BREAKER_POOL_ACQUIRE_LATENCY_SHEDDING_MS="500"
+            CHAIN_CIRCUIT_BREAKER_POOL_SATURATION_TRIP_AFTER_SEC="60"
         "#;
         lock.set_env(config);
 
@@ -0,0 +1,40 @@
+use zksync_config::configs::ChangeFeedConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for ChangeFeedConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("change_feed", "CHANGE_FEED_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            CHANGE_FEED_SLOT_NAME=test_slot
+            CHANGE_FEED_POLL_INTERVAL_MS=200
+            CHANGE_FEED_MAX_BATCH_SIZE=50
+            CHANGE_FEED_WEBHOOK_URL=http://127.0.0.1:8080/changes
+        "#;
+        lock.set_env(config);
+
+        let actual = ChangeFeedConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            ChangeFeedConfig {
+                slot_name: "test_slot".to_owned(),
+                poll_interval_ms: 200,
+                max_batch_size: 50,
+                webhook_url: Some("http://127.0.0.1:8080/changes".to_owned()),
+            }
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use zksync_config::configs::DAClientConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for DAClientConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("da_client", "DA_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::da_client::{AvailConfig, CelestiaConfig, DAClient, EigenDAConfig};
+
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env_avail() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_CLIENT="Avail"
+            DA_API_NODE_URL="http://localhost:7007"
+            DA_BRIDGE_API_URL="https://turing-bridge-api.avail.so"
+            DA_REQUEST_TIMEOUT_MS=10000
+        "#;
+        lock.set_env(config);
+
+        let actual = DAClientConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            DAClientConfig {
+                client: DAClient::Avail(AvailConfig {
+                    api_node_url: "http://localhost:7007".to_owned(),
+                    bridge_api_url: "https://turing-bridge-api.avail.so".to_owned(),
+                }),
+                request_timeout_ms: 10_000,
+            }
+        );
+    }
+
+    #[test]
+    fn from_env_celestia() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_CLIENT="Celestia"
+            DA_API_NODE_URL="http://localhost:26658"
+            DA_NAMESPACE="7a6b73796e63"
+            DA_AUTH_TOKEN="token"
+        "#;
+        lock.set_env(config);
+        lock.remove_env(&["DA_REQUEST_TIMEOUT_MS"]);
+
+        let actual = DAClientConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            DAClientConfig {
+                client: DAClient::Celestia(CelestiaConfig {
+                    api_node_url: "http://localhost:26658".to_owned(),
+                    namespace: "7a6b73796e63".to_owned(),
+                    auth_token: "token".to_owned(),
+                }),
+                request_timeout_ms: 30_000,
+            }
+        );
+    }
+
+    #[test]
+    fn from_env_eigen_da() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_CLIENT="EigenDA"
+            DA_DISPERSER_RPC="https://disperser-holesky.eigenda.xyz:443"
+            DA_CUSTOM_QUORUM_NUMBERS="0,1"
+            DA_WAIT_FOR_FINALIZATION=true
+            DA_REQUEST_TIMEOUT_MS=60000
+            DA_SIGNER_PRIVATE_KEY="0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+            DA_G1_SRS_PATH="./resources/g1.point"
+        "#;
+        lock.set_env(config);
+
+        let actual = DAClientConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            DAClientConfig {
+                client: DAClient::EigenDA(EigenDAConfig {
+                    disperser_rpc: "https://disperser-holesky.eigenda.xyz:443".to_owned(),
+                    custom_quorum_numbers: vec![0, 1],
+                    wait_for_finalization: true,
+                    blob_size_limit: 2 * 1_024 * 1_024,
+                    account_id: None,
+                    signer_private_key: Some(
+                        "0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be"
+                            .to_owned()
+                    ),
+                    g1_srs_path: "./resources/g1.point".to_owned(),
+                }),
+                request_timeout_ms: 60_000,
+            }
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use zksync_config::configs::DADispatcherConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for DADispatcherConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("da_dispatcher", "DA_DISPATCHER_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            DA_DISPATCHER_POLLING_INTERVAL_MS=5000
+            DA_DISPATCHER_MAX_ROWS_TO_DISPATCH=60
+            DA_DISPATCHER_MAX_RETRIES=7
+        "#;
+        lock.set_env(config);
+
+        let actual = DADispatcherConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            DADispatcherConfig {
+                polling_interval_ms: 5_000,
+                max_rows_to_dispatch: 60,
+                max_retries: 7,
+            }
+        );
+    }
+}
@@ -8,8 +8,12 @@ use crate::{envy_load, FromEnv};
 
 impl FromEnv for EthConfig {
     fn from_env() -> anyhow::Result<Self> {
+        let sender = SenderConfig::from_env().ok();
+        if let Some(sender) = &sender {
+            sender.validate().context("invalid eth_sender config")?;
+        }
         Ok(Self {
-            sender: SenderConfig::from_env().ok(),
+            sender,
             gas_adjuster: GasAdjusterConfig::from_env().ok(),
             watcher: EthWatchConfig::from_env().ok(),
         })
@@ -23,6 +27,26 @@ impl FromEnv for L1Secrets {
                 .context("ETH_CLIENT_WEB3_URL")?
                 .parse()
                 .context("ETH_CLIENT_WEB3_URL")?,
+            private_relay_rpc_url: std::env::var("ETH_CLIENT_PRIVATE_RELAY_WEB3_URL")
+                .ok()
+                .map(|url| url.parse())
+                .transpose()
+                .context("ETH_CLIENT_PRIVATE_RELAY_WEB3_URL")?,
+            gateway_rpc_url: std::env::var("ETH_CLIENT_GATEWAY_WEB3_URL")
+                .ok()
+                .map(|url| url.parse())
+                .transpose()
+                .context("ETH_CLIENT_GATEWAY_WEB3_URL")?,
+            fallback_l1_rpc_urls: std::env::var("ETH_CLIENT_FALLBACK_WEB3_URLS")
+                .ok()
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|url| url.trim().parse())
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()
+                .context("ETH_CLIENT_FALLBACK_WEB3_URLS")?
+                .unwrap_or_default(),
         })
     }
 }
@@ -41,7 +65,9 @@ impl FromEnv for GasAdjusterConfig {
 
 #[cfg(test)]
 mod tests {
-    use zksync_config::configs::eth_sender::{ProofSendingMode, PubdataSendingMode};
+    use zksync_config::configs::eth_sender::{
+        FeeBumpStrategy, L1BatchAggregationCriterion, ProofSendingMode, PubdataSendingMode,
+    };
 
     use super::*;
     use crate::test_utils::{hash, EnvMutex};
@@ -70,6 +96,26 @@ mod tests {
                     l1_batch_min_age_before_execute_seconds: Some(1000),
                     max_acceptable_priority_fee_in_gwei: 100_000_000_000,
                     pubdata_sending_mode: PubdataSendingMode::Calldata,
+                    dynamic_pubdata_sending_hysteresis: Some(0.1),
+                    fee_bump_strategy: Some(FeeBumpStrategy::Linear),
+                    fee_bump_percent: Some(15),
+                    blob_fee_bump_strategy: None,
+                    blob_fee_bump_percent: Some(100),
+                    max_total_fee_bump_percent: Some(500),
+                    blocks_before_first_fee_bump: Some(2),
+                    private_relay_fallback_blocks: Some(5),
+                    heal_nonce_divergence: Some(false),
+                    heal_nonce_divergence_after_blocks: Some(20),
+                    dry_run: Some(true),
+                    pause_commit: None,
+                    pause_prove: Some(true),
+                    pause_execute: Some(false),
+                    commit_aggregation_criteria: None,
+                    proof_aggregation_criteria: Some(vec![
+                        L1BatchAggregationCriterion::Number,
+                        L1BatchAggregationCriterion::Deadline,
+                    ]),
+                    execute_aggregation_criteria: None,
                 }),
                 gas_adjuster: Some(GasAdjusterConfig {
                     default_priority_fee_per_gas: 20000000000,
@@ -92,6 +138,12 @@ mod tests {
             },
             L1Secrets {
                 l1_rpc_url: "http://127.0.0.1:8545".to_string().parse().unwrap(),
+                private_relay_rpc_url: Some("http://127.0.0.1:8546".to_string().parse().unwrap()),
+                gateway_rpc_url: Some("http://127.0.0.1:3050".to_string().parse().unwrap()),
+                fallback_l1_rpc_urls: vec![
+                    "http://127.0.0.1:8547".to_string().parse().unwrap(),
+                    "http://127.0.0.1:8548".to_string().parse().unwrap(),
+                ],
             },
         )
     }
@@ -131,7 +183,23 @@ mod tests {
             ETH_SENDER_SENDER_L1_BATCH_MIN_AGE_BEFORE_EXECUTE_SECONDS="1000"
             ETH_SENDER_SENDER_MAX_ACCEPTABLE_PRIORITY_FEE_IN_GWEI="100000000000"
             ETH_SENDER_SENDER_PUBDATA_SENDING_MODE="Calldata"
+            ETH_SENDER_SENDER_DYNAMIC_PUBDATA_SENDING_HYSTERESIS="0.1"
+            ETH_SENDER_SENDER_FEE_BUMP_STRATEGY="Linear"
+            ETH_SENDER_SENDER_FEE_BUMP_PERCENT="15"
+            ETH_SENDER_SENDER_BLOB_FEE_BUMP_PERCENT="100"
+            ETH_SENDER_SENDER_MAX_TOTAL_FEE_BUMP_PERCENT="500"
+            ETH_SENDER_SENDER_BLOCKS_BEFORE_FIRST_FEE_BUMP="2"
+            ETH_SENDER_SENDER_PRIVATE_RELAY_FALLBACK_BLOCKS="5"
+            ETH_SENDER_SENDER_HEAL_NONCE_DIVERGENCE="false"
+            ETH_SENDER_SENDER_HEAL_NONCE_DIVERGENCE_AFTER_BLOCKS="20"
+            ETH_SENDER_SENDER_DRY_RUN="true"
+            ETH_SENDER_SENDER_PAUSE_PROVE="true"
+            ETH_SENDER_SENDER_PAUSE_EXECUTE="false"
+            ETH_SENDER_SENDER_PROOF_AGGREGATION_CRITERIA="Number,Deadline"
             ETH_CLIENT_WEB3_URL="http://127.0.0.1:8545"
+            ETH_CLIENT_PRIVATE_RELAY_WEB3_URL="http://127.0.0.1:8546"
+            ETH_CLIENT_GATEWAY_WEB3_URL="http://127.0.0.1:3050"
+            ETH_CLIENT_FALLBACK_WEB3_URLS="http://127.0.0.1:8547,http://127.0.0.1:8548"
 
         "#;
         lock.set_env(config);
@@ -33,6 +33,8 @@ mod tests {
             fri_gpu_prover_archiver_archiving_interval_ms: Some(86_400_000),
             // 48 hours
             fri_gpu_prover_archiver_archive_after_secs: Some(172_800),
+            table_partitioning_interval_ms: Some(60_000),
+            table_partition_size: Some(100_000),
         }
     }
 
@@ -56,6 +58,8 @@ mod tests {
             HOUSE_KEEPER_PROVER_JOB_ARCHIVER_ARCHIVE_AFTER_SECS="172800"
             HOUSE_KEEPER_FRI_GPU_PROVER_ARCHIVER_ARCHIVING_INTERVAL_MS="86400000"
             HOUSE_KEEPER_FRI_GPU_PROVER_ARCHIVER_ARCHIVE_AFTER_SECS="172800"
+            HOUSE_KEEPER_TABLE_PARTITIONING_INTERVAL_MS="60000"
+            HOUSE_KEEPER_TABLE_PARTITION_SIZE="100000"
         "#;
         lock.set_env(config);
 
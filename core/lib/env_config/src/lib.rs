@@ -2,9 +2,14 @@ use anyhow::Context as _;
 use serde::de::DeserializeOwned;
 
 mod api;
+mod base_token_adjuster;
+mod block_notifications;
 mod chain;
+mod change_feed;
 mod contract_verifier;
 mod contracts;
+mod da_client;
+mod da_dispatcher;
 mod database;
 mod eth_sender;
 mod eth_watch;
@@ -18,6 +23,7 @@ mod house_keeper;
 pub mod object_store;
 mod observability;
 mod proof_data_handler;
+mod pruning;
 mod snapshots_creator;
 mod utils;
 
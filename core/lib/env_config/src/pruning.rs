@@ -0,0 +1,40 @@
+use zksync_config::configs::PruningConfig;
+
+use crate::{envy_load, FromEnv};
+
+impl FromEnv for PruningConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("pruning", "PRUNING_")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+    use crate::test_utils::EnvMutex;
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            PRUNING_CHUNK_SIZE=5
+            PRUNING_REMOVAL_DELAY_SEC=120
+            PRUNING_DATA_RETENTION_SEC=604800
+        "#;
+        lock.set_env(config);
+
+        let actual = PruningConfig::from_env().unwrap();
+        assert_eq!(
+            actual,
+            PruningConfig {
+                chunk_size: 5,
+                removal_delay_sec: NonZeroU64::new(120).unwrap(),
+                data_retention_sec: 604_800,
+            }
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use zksync_config::configs::ProtectiveReadsWriterConfig;
+use zksync_config::configs::{ProtectiveReadsWriterConfig, ShadowExecutionConfig};
 
 use crate::{envy_load, FromEnv};
 
@@ -7,3 +7,9 @@ impl FromEnv for ProtectiveReadsWriterConfig {
         envy_load("vm_runner.protective_reads", "VM_RUNNER_PROTECTIVE_READS_")
     }
 }
+
+impl FromEnv for ShadowExecutionConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        envy_load("vm_runner.shadow_execution", "VM_RUNNER_SHADOW_EXECUTION_")
+    }
+}
@@ -2,35 +2,59 @@ use std::str::FromStr;
 
 use anyhow::Context;
 use zksync_basic_types::{Address, H256};
-use zksync_config::configs::wallets::{AddressWallet, EthSender, StateKeeper, Wallet, Wallets};
+use zksync_config::configs::wallets::{
+    AddressWallet, EthSender, RemoteSignerConfig, StateKeeper, Wallet, Wallets,
+};
 
 use crate::FromEnv;
 
+/// Reads a wallet from env variables with the specified prefix. A wallet is specified either by
+/// `{prefix}_PRIVATE_KEY`, or by `{prefix}_ADDRESS` together with variables for one of the remote signers.
+fn read_wallet(prefix: &str) -> anyhow::Result<Option<Wallet>> {
+    let var = |name: &str| std::env::var(format!("{prefix}_{name}")).ok();
+
+    if let Some(private_key) = var("PRIVATE_KEY") {
+        let private_key = private_key.parse::<H256>().context("Malformed pk")?;
+        return Ok(Some(Wallet::from_private_key_bytes(private_key, None)?));
+    }
+
+    let remote_signer = if let Some(key_name) = var("GCP_KMS_KEY_NAME") {
+        RemoteSignerConfig::GcpKms { key_name }
+    } else if let Some(key_id) = var("AWS_KMS_KEY_ID") {
+        let region =
+            var("AWS_KMS_REGION").with_context(|| format!("{prefix}_AWS_KMS_REGION is not set"))?;
+        RemoteSignerConfig::AwsKms { key_id, region }
+    } else if let Some(url) = var("REMOTE_SIGNER_URL") {
+        RemoteSignerConfig::Api {
+            url: url.parse().context("Malformed remote signer URL")?,
+            authorization: var("REMOTE_SIGNER_AUTHORIZATION").map(Into::into),
+        }
+    } else {
+        return Ok(None);
+    };
+    let address = var("ADDRESS").with_context(|| {
+        format!("{prefix}_ADDRESS must be set for wallets with a remote signer")
+    })?;
+    let address = Address::from_str(&address).context("Malformed address")?;
+    Ok(Some(Wallet::remote(address, remote_signer)))
+}
+
 impl FromEnv for Wallets {
     fn from_env() -> anyhow::Result<Self> {
-        let operator = std::env::var("ETH_SENDER_SENDER_OPERATOR_PRIVATE_KEY")
-            .ok()
-            .map(|pk| pk.parse::<H256>().context("Malformed pk"))
-            .transpose()?;
-        let blob_operator = std::env::var("ETH_SENDER_SENDER_OPERATOR_BLOBS_PRIVATE_KEY")
-            .ok()
-            .map(|pk| pk.parse::<H256>().context("Malformed pk"))
-            .transpose()?;
-
-        let eth_sender = if let Some(operator) = operator {
-            let operator = Wallet::from_private_key_bytes(operator, None)?;
-            let blob_operator = if let Some(blob_operator) = blob_operator {
-                Some(Wallet::from_private_key_bytes(blob_operator, None)?)
-            } else {
-                None
-            };
-            Some(EthSender {
-                operator,
-                blob_operator,
-            })
-        } else {
-            None
-        };
+        let operator = read_wallet("ETH_SENDER_SENDER_OPERATOR").context("operator")?;
+        let blob_operator =
+            read_wallet("ETH_SENDER_SENDER_OPERATOR_BLOBS").context("blob operator")?;
+        let successor_operator =
+            read_wallet("ETH_SENDER_SENDER_SUCCESSOR_OPERATOR").context("successor operator")?;
+        let successor_blob_operator = read_wallet("ETH_SENDER_SENDER_SUCCESSOR_OPERATOR_BLOBS")
+            .context("successor blob operator")?;
+
+        let eth_sender = operator.map(|operator| EthSender {
+            operator,
+            blob_operator,
+            successor_operator,
+            successor_blob_operator,
+        });
 
         let fee_account = std::env::var("CHAIN_STATE_KEEPER_FEE_ACCOUNT_ADDR").ok();
         let state_keeper = if let Some(fee_account) = fee_account {
@@ -46,3 +70,59 @@ impl FromEnv for Wallets {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use secrecy::ExposeSecret as _;
+    use zksync_config::configs::wallets::WalletSigner;
+
+    use super::*;
+    use crate::test_utils::{addr, EnvMutex};
+
+    static MUTEX: EnvMutex = EnvMutex::new();
+
+    #[test]
+    fn wallets_with_remote_signers_from_env() {
+        let mut lock = MUTEX.lock();
+        // Use a custom prefix to not interfere with other tests reading wallet keys.
+        let config = r#"
+            TEST_WALLET_ADDRESS="0x0000000000000000000000000000000000000001"
+            TEST_WALLET_AWS_KMS_KEY_ID="alias/operator"
+            TEST_WALLET_AWS_KMS_REGION="us-east-1"
+        "#;
+        lock.set_env(config);
+
+        let wallet = read_wallet("TEST_WALLET").unwrap().expect("no wallet");
+        assert_eq!(
+            wallet.address(),
+            addr("0x0000000000000000000000000000000000000001")
+        );
+        assert!(wallet.private_key().is_none());
+        let WalletSigner::Remote(RemoteSignerConfig::AwsKms { key_id, region }) = wallet.signer()
+        else {
+            panic!("unexpected signer: {:?}", wallet.signer());
+        };
+        assert_eq!(key_id, "alias/operator");
+        assert_eq!(region, "us-east-1");
+
+        lock.remove_env(&["TEST_WALLET_ADDRESS"]);
+        read_wallet("TEST_WALLET").unwrap_err();
+        lock.remove_env(&["TEST_WALLET_AWS_KMS_KEY_ID"]);
+        assert!(read_wallet("TEST_WALLET").unwrap().is_none());
+
+        let config = r#"
+            TEST_WALLET_ADDRESS="0x0000000000000000000000000000000000000001"
+            TEST_WALLET_REMOTE_SIGNER_URL="http://127.0.0.1:8080/sign"
+            TEST_WALLET_REMOTE_SIGNER_AUTHORIZATION="Bearer token"
+        "#;
+        lock.set_env(config);
+        let wallet = read_wallet("TEST_WALLET").unwrap().expect("no wallet");
+        let WalletSigner::Remote(RemoteSignerConfig::Api { url, authorization }) = wallet.signer()
+        else {
+            panic!("unexpected signer: {:?}", wallet.signer());
+        };
+        assert_eq!(url.expose_str(), "http://127.0.0.1:8080/sign");
+        let authorization = authorization.as_ref().expect("no authorization");
+        assert_eq!(authorization.expose_secret(), "Bearer token");
+    }
+}
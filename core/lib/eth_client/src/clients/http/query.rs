@@ -190,6 +190,7 @@ where
                     data: Some(transaction.input),
                     transaction_type: None,
                     access_list: None,
+                    blob_versioned_hashes: None,
                 };
 
                 let block_number = receipt
@@ -1,8 +1,12 @@
 use std::{fmt, sync::Arc};
 
 use async_trait::async_trait;
+use zksync_config::configs::wallets::{RemoteSignerConfig, Wallet, WalletSigner};
 use zksync_contracts::hyperchain_contract;
-use zksync_eth_signer::{EthereumSigner, PrivateKeySigner, TransactionParameters};
+use zksync_eth_signer::{
+    AwsKmsSigner, DigestSigner, EthereumSigner, GcpKmsSigner, PrivateKeySigner, RemoteApiSigner,
+    RemoteSigner, SignerError, TransactionParameters,
+};
 use zksync_types::{
     ethabi, web3, Address, K256PrivateKey, L1ChainId, EIP_4844_TX_TYPE, H160, U256,
 };
@@ -40,6 +44,76 @@ impl PKSigningClient {
     }
 }
 
+/// HTTP-based Ethereum client, backed by a remote signer (e.g., a cloud KMS).
+pub type RemoteSigningClient = SigningClient<RemoteSigner>;
+
+impl RemoteSigningClient {
+    pub fn new_raw(
+        signer: RemoteSigner,
+        diamond_proxy_addr: Address,
+        default_priority_fee_per_gas: u64,
+        l1_chain_id: L1ChainId,
+        query_client: Box<DynClient<L1>>,
+    ) -> Self {
+        let operator_address = signer.address();
+        tracing::info!("Operator address: {operator_address:?} (remote signer)");
+        SigningClient::new(
+            query_client,
+            hyperchain_contract(),
+            operator_address,
+            signer,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas.into(),
+            l1_chain_id,
+        )
+    }
+}
+
+/// Creates a signing client for the specified wallet, which may be backed either by a private key
+/// or by a remote signer. For remote signers, checks that the signer key corresponds to the wallet address.
+pub async fn signing_client_for_wallet(
+    wallet: &Wallet,
+    diamond_proxy_addr: Address,
+    default_priority_fee_per_gas: u64,
+    l1_chain_id: L1ChainId,
+    query_client: Box<DynClient<L1>>,
+) -> Result<Box<dyn BoundEthInterface>, SignerError> {
+    Ok(match wallet.signer() {
+        WalletSigner::PrivateKey(private_key) => Box::new(PKSigningClient::new_raw(
+            private_key.clone(),
+            diamond_proxy_addr,
+            default_priority_fee_per_gas,
+            l1_chain_id,
+            query_client,
+        )),
+        WalletSigner::Remote(config) => {
+            let backend: Arc<dyn DigestSigner> = match config {
+                RemoteSignerConfig::GcpKms { key_name } => {
+                    Arc::new(GcpKmsSigner::new(key_name.clone()).await?)
+                }
+                RemoteSignerConfig::AwsKms { key_id, region } => {
+                    Arc::new(AwsKmsSigner::new(key_id.clone(), region.clone()).await?)
+                }
+                RemoteSignerConfig::Api { url, authorization } => {
+                    let mut signer = RemoteApiSigner::new(url.clone(), wallet.address());
+                    if let Some(authorization) = authorization {
+                        signer = signer.with_authorization(authorization.clone());
+                    }
+                    Arc::new(signer)
+                }
+            };
+            let signer = RemoteSigner::new(backend, Some(wallet.address())).await?;
+            Box::new(RemoteSigningClient::new_raw(
+                signer,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
+                l1_chain_id,
+                query_client,
+            ))
+        }
+    })
+}
+
 /// Gas limit value to be used in transaction if for some reason
 /// gas limit was not set for it.
 ///
@@ -94,7 +94,9 @@ impl MockEthereumInner {
         self.block_number += confirmations;
         let nonce = self.current_nonce;
         self.current_nonce += 1;
-        let tx_nonce = self.sent_txs[&tx_hash].nonce;
+        let sent_tx = &self.sent_txs[&tx_hash];
+        let tx_nonce = sent_tx.nonce;
+        let effective_gas_price = sent_tx.max_fee_per_gas;
 
         if non_ordering_confirmations {
             if tx_nonce >= nonce {
@@ -112,6 +114,7 @@ impl MockEthereumInner {
                 block_number: Some(block_number.into()),
                 transaction_hash: tx_hash,
                 status: Some(U64::from(if success { 1 } else { 0 })),
+                effective_gas_price: Some(effective_gas_price),
                 ..web3::TransactionReceipt::default()
             },
         };
@@ -519,6 +522,16 @@ impl MockEthereum {
         MockExecutedTxHandle { inner, tx_hash }
     }
 
+    /// Removes a sent, but not executed transaction, emulating its eviction from the mempool.
+    pub fn drop_tx(&self, tx_hash: H256) {
+        let mut inner = self.inner.write().unwrap();
+        assert!(
+            !inner.executed_txs.contains_key(&tx_hash),
+            "transaction {tx_hash:?} is already executed"
+        );
+        inner.sent_txs.remove(&tx_hash);
+    }
+
     /// Increases the block number in the network by the specified value.
     pub fn advance_block_number(&self, val: u64) -> u64 {
         let mut inner = self.inner.write().unwrap();
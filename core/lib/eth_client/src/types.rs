@@ -107,6 +107,7 @@ impl ContractCall<'_> {
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None,
+            blob_versioned_hashes: None,
         };
 
         let encoded_output = client
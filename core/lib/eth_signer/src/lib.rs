@@ -1,16 +1,25 @@
 use async_trait::async_trait;
 use zksync_types::{Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature};
 
-pub use crate::{pk_signer::PrivateKeySigner, raw_ethereum_tx::TransactionParameters};
+pub use crate::{
+    pk_signer::PrivateKeySigner,
+    raw_ethereum_tx::TransactionParameters,
+    remote::{
+        AwsKmsSigner, DigestSignature, DigestSigner, GcpKmsSigner, RemoteApiSigner, RemoteSigner,
+    },
+};
 
 mod pk_signer;
 mod raw_ethereum_tx;
+mod remote;
 
 #[derive(Debug, thiserror::Error)]
 #[non_exhaustive]
 pub enum SignerError {
     #[error("Signing failed: {0}")]
     SigningFailed(String),
+    #[error("Remote signer error: {0}")]
+    Remote(String),
 }
 
 #[async_trait]
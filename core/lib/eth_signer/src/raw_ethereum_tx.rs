@@ -70,6 +70,26 @@ pub struct Transaction {
     pub blob_versioned_hashes: Option<Vec<H256>>,
 }
 
+impl From<TransactionParameters> for Transaction {
+    fn from(params: TransactionParameters) -> Self {
+        // According to the code in web3 <https://docs.rs/web3/latest/src/web3/api/accounts.rs.html#86>
+        // We should use `max_fee_per_gas` as `gas_price` if we use EIP1559
+        Self {
+            to: params.to,
+            nonce: params.nonce,
+            gas: params.gas,
+            gas_price: params.max_fee_per_gas,
+            value: params.value,
+            data: params.data,
+            transaction_type: params.transaction_type,
+            access_list: params.access_list.unwrap_or_default(),
+            max_priority_fee_per_gas: params.max_priority_fee_per_gas,
+            max_fee_per_blob_gas: params.max_fee_per_blob_gas,
+            blob_versioned_hashes: params.blob_versioned_hashes,
+        }
+    }
+}
+
 impl Transaction {
     fn rlp_append_legacy(&self, stream: &mut RlpStream) {
         stream.append(&self.nonce);
@@ -240,23 +260,51 @@ impl Transaction {
         }
     }
 
-    /// Sign and return a raw signed transaction.
-    pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
-        let adjust_v_value = matches!(
+    /// Returns `true` if the `v` value of the transaction signature must include the chain ID (per EIP-155).
+    fn is_legacy(&self) -> bool {
+        matches!(
             self.transaction_type.map(|t| t.as_u64()),
             Some(LEGACY_TX_ID) | None
-        );
+        )
+    }
 
-        let encoded = self.encode(chain_id, None);
-        let message_hash = H256(keccak256(encoded.as_ref()));
+    /// Returns the hash that should be signed to produce the transaction signature.
+    pub fn signing_hash(&self, chain_id: u64) -> H256 {
+        H256(keccak256(self.encode(chain_id, None).as_ref()))
+    }
 
-        let signature = if adjust_v_value {
+    /// Sign and return a raw signed transaction.
+    pub fn sign(self, private_key: &K256PrivateKey, chain_id: u64) -> SignedTransaction {
+        let message_hash = self.signing_hash(chain_id);
+        let signature = if self.is_legacy() {
             private_key.sign_web3(&message_hash, Some(chain_id))
         } else {
             private_key.sign_web3_message(&message_hash)
         };
+        self.into_signed(chain_id, message_hash, &signature)
+    }
+
+    /// Returns a raw signed transaction given the signature of its [signing hash](Self::signing_hash())
+    /// with the standard recovery ID (0 or 1) in place of `v`.
+    pub fn with_signature(
+        self,
+        chain_id: u64,
+        message_hash: H256,
+        mut signature: Signature,
+    ) -> SignedTransaction {
+        if self.is_legacy() {
+            signature.v += 35 + chain_id * 2;
+        }
+        self.into_signed(chain_id, message_hash, &signature)
+    }
 
-        let signed = self.encode(chain_id, Some(&signature));
+    fn into_signed(
+        self,
+        chain_id: u64,
+        message_hash: H256,
+        signature: &Signature,
+    ) -> SignedTransaction {
+        let signed = self.encode(chain_id, Some(signature));
         let transaction_hash = keccak256(signed.as_ref()).into();
 
         SignedTransaction {
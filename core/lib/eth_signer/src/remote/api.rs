@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use secrecy::{ExposeSecret, Secret};
+use serde::{Deserialize, Serialize};
+use zksync_types::{url::SensitiveUrl, web3::Bytes, Address, H256};
+
+use super::{http_client, DigestSignature, DigestSigner};
+use crate::SignerError;
+
+#[derive(Debug, Serialize)]
+struct SignRequest {
+    address: Address,
+    digest: H256,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignResponse {
+    signature: Bytes,
+}
+
+/// Signer using a generic remote signing API. The API is expected to handle `POST` requests
+/// to the configured URL with a JSON body like `{ "address": "0x...", "digest": "0x..." }` and respond
+/// with `{ "signature": "0x..." }`, where the signature is 65 bytes `r || s || v` over the digest
+/// (the digest must be signed as is, without hashing).
+#[derive(Debug)]
+pub struct RemoteApiSigner {
+    url: SensitiveUrl,
+    address: Address,
+    authorization: Option<Secret<String>>,
+    client: reqwest::Client,
+}
+
+impl RemoteApiSigner {
+    /// Creates a signer for the specified account. The API doesn't provide a way to query the address,
+    /// so it must be known in advance.
+    pub fn new(url: SensitiveUrl, address: Address) -> Self {
+        Self {
+            url,
+            address,
+            authorization: None,
+            client: http_client(),
+        }
+    }
+
+    /// Sets the value of the `Authorization` header sent with each request (e.g., `Bearer <token>`).
+    pub fn with_authorization(mut self, authorization: Secret<String>) -> Self {
+        self.authorization = Some(authorization);
+        self
+    }
+}
+
+#[async_trait]
+impl DigestSigner for RemoteApiSigner {
+    async fn address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<DigestSignature, SignerError> {
+        let request = SignRequest {
+            address: self.address,
+            digest,
+        };
+        let mut request_builder = self.client.post(self.url.expose_str()).json(&request);
+        if let Some(authorization) = &self.authorization {
+            request_builder = request_builder.header(
+                reqwest::header::AUTHORIZATION,
+                authorization.expose_secret(),
+            );
+        }
+        let response = request_builder
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            // Errors may contain the URL, which may contain sensitive info (e.g., an access token).
+            .map_err(|err| {
+                SignerError::Remote(format!("failed signing digest: {}", err.without_url()))
+            })?;
+        let response: SignResponse = response
+            .json()
+            .await
+            .map_err(|err| SignerError::Remote(format!("malformed signing response: {err}")))?;
+        DigestSignature::from_packed(&response.signature.0)
+    }
+}
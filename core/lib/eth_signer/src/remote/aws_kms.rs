@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use aws_config::{timeout::TimeoutConfig, BehaviorVersion};
+use aws_sdk_kms::{
+    config::Region,
+    error::DisplayErrorContext,
+    primitives::Blob,
+    types::{MessageType, SigningAlgorithmSpec},
+};
+use zksync_types::{Address, H256};
+
+use super::{
+    address_from_public_key_der, DigestSignature, DigestSigner, CONNECT_TIMEOUT, REQUEST_TIMEOUT,
+};
+use crate::SignerError;
+
+/// Signer backed by an `ECC_SECG_P256K1` key in AWS KMS. Authenticates using the default AWS credential
+/// provider chain (env variables, shared config / credentials files, web identity tokens, ECS / EC2 instance roles etc.).
+#[derive(Debug)]
+pub struct AwsKmsSigner {
+    key_id: String,
+    client: aws_sdk_kms::Client,
+    address: Address,
+}
+
+impl AwsKmsSigner {
+    /// Creates a signer for the key with the specified ID or ARN in the specified AWS region.
+    pub async fn new(key_id: String, region: String) -> Result<Self, SignerError> {
+        let timeout_config = TimeoutConfig::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .operation_attempt_timeout(REQUEST_TIMEOUT)
+            .build();
+        let config = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(region))
+            .timeout_config(timeout_config)
+            .load()
+            .await;
+        let client = aws_sdk_kms::Client::new(&config);
+
+        let response = client
+            .get_public_key()
+            .key_id(&key_id)
+            .send()
+            .await
+            .map_err(|err| {
+                SignerError::Remote(format!(
+                    "KMS GetPublicKey request failed: {}",
+                    DisplayErrorContext(err)
+                ))
+            })?;
+        let der = response
+            .public_key()
+            .ok_or_else(|| SignerError::Remote("KMS returned no public key".to_owned()))?;
+        let address = address_from_public_key_der(der.as_ref())?;
+        tracing::info!("Initialized AWS KMS signer for key {key_id} with address {address:?}");
+        Ok(Self {
+            key_id,
+            client,
+            address,
+        })
+    }
+}
+
+#[async_trait]
+impl DigestSigner for AwsKmsSigner {
+    async fn address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<DigestSignature, SignerError> {
+        // KMS signs the provided digest as is, so it doesn't matter that it's not actually a SHA-256 digest.
+        let response = self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(digest.as_bytes()))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(SigningAlgorithmSpec::EcdsaSha256)
+            .send()
+            .await
+            .map_err(|err| {
+                SignerError::Remote(format!(
+                    "KMS Sign request failed: {}",
+                    DisplayErrorContext(err)
+                ))
+            })?;
+        let der_signature = response
+            .signature()
+            .ok_or_else(|| SignerError::Remote("KMS returned no signature".to_owned()))?;
+        DigestSignature::from_der(der_signature.as_ref(), &digest, self.address)
+    }
+}
@@ -0,0 +1,148 @@
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use google_cloud_auth::{project::Config, token::DefaultTokenSourceProvider};
+use google_cloud_token::{TokenSource, TokenSourceProvider};
+use serde::{Deserialize, Serialize};
+use zksync_types::{Address, H256};
+
+use super::{address_from_public_key_der, http_client, DigestSignature, DigestSigner};
+use crate::SignerError;
+
+const KMS_API_URL: &str = "https://cloudkms.googleapis.com/v1";
+const KMS_SCOPES: [&str; 1] = ["https://www.googleapis.com/auth/cloudkms"];
+
+#[derive(Debug, Serialize)]
+struct AsymmetricSignRequest {
+    digest: Digest,
+}
+
+#[derive(Debug, Serialize)]
+struct Digest {
+    sha256: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsymmetricSignResponse {
+    signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+}
+
+/// Signer backed by an `EC_SIGN_SECP256K1_SHA256` key version in Google Cloud KMS. Authenticates
+/// using the application default credentials.
+pub struct GcpKmsSigner {
+    key_name: String,
+    client: reqwest::Client,
+    token_source: Arc<dyn TokenSource>,
+    address: Address,
+}
+
+impl fmt::Debug for GcpKmsSigner {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("GcpKmsSigner")
+            .field("key_name", &self.key_name)
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GcpKmsSigner {
+    /// Creates a signer for the key version with the specified resource name, i.e.
+    /// `projects/*/locations/*/keyRings/*/cryptoKeys/*/cryptoKeyVersions/*`.
+    pub async fn new(key_name: String) -> Result<Self, SignerError> {
+        let token_provider =
+            DefaultTokenSourceProvider::new(Config::default().with_scopes(&KMS_SCOPES))
+                .await
+                .map_err(|err| {
+                    SignerError::Remote(format!("failed initializing GCP credentials: {err}"))
+                })?;
+        let mut this = Self {
+            key_name,
+            client: http_client(),
+            token_source: token_provider.token_source(),
+            address: Address::zero(),
+        };
+        this.address = this.fetch_address().await?;
+        tracing::info!(
+            "Initialized GCP KMS signer for key {} with address {:?}",
+            this.key_name,
+            this.address
+        );
+        Ok(this)
+    }
+
+    async fn authorization(&self) -> Result<String, SignerError> {
+        // The token source caches tokens and returns them with the `Bearer` prefix.
+        self.token_source
+            .token()
+            .await
+            .map_err(|err| SignerError::Remote(format!("failed getting GCP access token: {err}")))
+    }
+
+    async fn fetch_address(&self) -> Result<Address, SignerError> {
+        let url = format!("{KMS_API_URL}/{}/publicKey", self.key_name);
+        let response = self
+            .client
+            .get(url)
+            .header(reqwest::header::AUTHORIZATION, self.authorization().await?)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| SignerError::Remote(format!("failed getting public key: {err}")))?;
+        let response: PublicKeyResponse = response
+            .json()
+            .await
+            .map_err(|err| SignerError::Remote(format!("malformed public key response: {err}")))?;
+
+        let pem_body: String = response
+            .pem
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        let der = BASE64
+            .decode(pem_body)
+            .map_err(|err| SignerError::Remote(format!("malformed public key PEM: {err}")))?;
+        address_from_public_key_der(&der)
+    }
+}
+
+#[async_trait]
+impl DigestSigner for GcpKmsSigner {
+    async fn address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+
+    async fn sign_digest(&self, digest: H256) -> Result<DigestSignature, SignerError> {
+        // KMS signs the provided digest as is, so it doesn't matter that it's not actually a SHA-256 digest.
+        let request = AsymmetricSignRequest {
+            digest: Digest {
+                sha256: BASE64.encode(digest.as_bytes()),
+            },
+        };
+        let url = format!("{KMS_API_URL}/{}:asymmetricSign", self.key_name);
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::AUTHORIZATION, self.authorization().await?)
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| SignerError::Remote(format!("failed signing digest: {err}")))?;
+        let response: AsymmetricSignResponse = response
+            .json()
+            .await
+            .map_err(|err| SignerError::Remote(format!("malformed signing response: {err}")))?;
+
+        let der_signature = BASE64
+            .decode(response.signature)
+            .map_err(|err| SignerError::Remote(format!("malformed signature encoding: {err}")))?;
+        DigestSignature::from_der(&der_signature, &digest, self.address)
+    }
+}
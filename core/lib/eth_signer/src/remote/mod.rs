@@ -0,0 +1,385 @@
+//! Signers keeping private keys outside of the node, e.g. in a cloud KMS.
+
+use std::{fmt, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, PublicKey,
+};
+use zksync_types::{
+    web3::{self, keccak256},
+    Address, EIP712TypedStructure, Eip712Domain, PackedEthSignature, H256,
+};
+
+pub use self::{api::RemoteApiSigner, aws_kms::AwsKmsSigner, gcp_kms::GcpKmsSigner};
+use crate::{raw_ethereum_tx::Transaction, EthereumSigner, SignerError, TransactionParameters};
+
+mod api;
+mod aws_kms;
+mod gcp_kms;
+
+/// Timeout for establishing connections to remote signer backends.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Timeout for a single request to a remote signer backend.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Creates an HTTP client for a remote signer backend.
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("failed building HTTP client")
+}
+
+/// ECDSA signature over secp256k1 with a standard recovery ID (0 or 1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestSignature {
+    pub r: H256,
+    pub s: H256,
+    pub recovery_id: u8,
+}
+
+impl DigestSignature {
+    /// Converts a DER-encoded signature (as returned by cloud KMSs) to a signature usable in Ethereum.
+    /// `s` is normalized to be in the lower half of the curve order as required by Ethereum, and the recovery ID
+    /// is determined by matching the recovered address with the `signer` address.
+    pub(crate) fn from_der(
+        der: &[u8],
+        digest: &H256,
+        signer: Address,
+    ) -> Result<Self, SignerError> {
+        let mut signature = secp256k1::ecdsa::Signature::from_der(der)
+            .map_err(|err| SignerError::SigningFailed(format!("malformed DER signature: {err}")))?;
+        signature.normalize_s();
+        let compact = signature.serialize_compact();
+
+        let message = Message::from_slice(digest.as_bytes()).expect("digest has correct length");
+        for recovery_id in 0..2 {
+            let id = RecoveryId::from_i32(recovery_id).expect("recovery ID is valid");
+            let recoverable = RecoverableSignature::from_compact(&compact, id)
+                .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+            let Ok(public_key) = recoverable.recover(&message) else {
+                continue;
+            };
+            if public_key_to_address(&public_key) == signer {
+                return Ok(Self {
+                    r: H256::from_slice(&compact[..32]),
+                    s: H256::from_slice(&compact[32..]),
+                    recovery_id: recovery_id as u8,
+                });
+            }
+        }
+        Err(SignerError::SigningFailed(format!(
+            "signature doesn't correspond to the signer address {signer:?}"
+        )))
+    }
+
+    /// Parses a signature serialized as 65 bytes `r || s || v`, where `v` is either a recovery ID
+    /// or is in the Electrum notation (27 or 28). Like in [`Self::from_der()`], `s` is normalized
+    /// to be in the lower half of the curve order; the recovery ID is flipped accordingly.
+    pub(crate) fn from_packed(bytes: &[u8]) -> Result<Self, SignerError> {
+        if bytes.len() != 65 {
+            return Err(SignerError::SigningFailed(format!(
+                "unexpected signature length: {}",
+                bytes.len()
+            )));
+        }
+        let recovery_id = match bytes[64] {
+            v @ (0 | 1) => v,
+            v @ (27 | 28) => v - 27,
+            v => {
+                return Err(SignerError::SigningFailed(format!(
+                    "unexpected signature `v` value: {v}"
+                )))
+            }
+        };
+
+        let mut signature = secp256k1::ecdsa::Signature::from_compact(&bytes[..64])
+            .map_err(|err| SignerError::SigningFailed(format!("malformed signature: {err}")))?;
+        let original_s = H256::from_slice(&bytes[32..64]);
+        signature.normalize_s();
+        let compact = signature.serialize_compact();
+        let s = H256::from_slice(&compact[32..]);
+        // Negating `s` corresponds to negating the `R` point, i.e., flipping the parity of its `y` coordinate.
+        let recovery_id = if s == original_s {
+            recovery_id
+        } else {
+            recovery_id ^ 1
+        };
+        Ok(Self {
+            r: H256::from_slice(&compact[..32]),
+            s,
+            recovery_id,
+        })
+    }
+
+    fn recover_signer(&self, digest: &H256) -> Result<Address, SignerError> {
+        PackedEthSignature::from(*self)
+            .signature_recover_signer(digest)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))
+    }
+}
+
+impl From<DigestSignature> for PackedEthSignature {
+    fn from(signature: DigestSignature) -> Self {
+        PackedEthSignature::from_rsv(&signature.r, &signature.s, signature.recovery_id)
+    }
+}
+
+impl From<DigestSignature> for web3::Signature {
+    fn from(signature: DigestSignature) -> Self {
+        web3::Signature {
+            v: signature.recovery_id.into(),
+            r: signature.r,
+            s: signature.s,
+        }
+    }
+}
+
+/// Backend signing 32-byte digests with a secp256k1 key not accessible to the node.
+#[async_trait]
+pub trait DigestSigner: 'static + fmt::Debug + Send + Sync {
+    /// Returns the address corresponding to the signing key.
+    async fn address(&self) -> Result<Address, SignerError>;
+
+    /// Signs the provided digest as is (i.e., without hashing it).
+    async fn sign_digest(&self, digest: H256) -> Result<DigestSignature, SignerError>;
+}
+
+/// [`EthereumSigner`] delegating signing to a [`DigestSigner`] backend.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    backend: Arc<dyn DigestSigner>,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for the specified backend. If `expected_address` is specified, checks that the backend key
+    /// corresponds to it.
+    pub async fn new(
+        backend: Arc<dyn DigestSigner>,
+        expected_address: Option<Address>,
+    ) -> Result<Self, SignerError> {
+        let address = backend.address().await?;
+        if let Some(expected_address) = expected_address {
+            if address != expected_address {
+                return Err(SignerError::Remote(format!(
+                    "remote signer {backend:?} has address {address:?}, while {expected_address:?} is expected"
+                )));
+            }
+        }
+        Ok(Self { backend, address })
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign(&self, digest: H256) -> Result<DigestSignature, SignerError> {
+        let signature = self.backend.sign_digest(digest).await?;
+        // Backends are external services, so we don't trust them to return a correct signature.
+        let signer = signature.recover_signer(&digest)?;
+        if signer != self.address {
+            return Err(SignerError::SigningFailed(format!(
+                "remote signer {:?} returned a signature for {signer:?} instead of {:?}",
+                self.backend, self.address
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+#[async_trait]
+impl EthereumSigner for RemoteSigner {
+    async fn sign_typed_data<S: EIP712TypedStructure + Sync>(
+        &self,
+        domain: &Eip712Domain,
+        typed_struct: &S,
+    ) -> Result<PackedEthSignature, SignerError> {
+        let digest = PackedEthSignature::typed_data_to_signed_bytes(domain, typed_struct);
+        Ok(self.sign(digest).await?.into())
+    }
+
+    async fn sign_transaction(
+        &self,
+        raw_tx: TransactionParameters,
+    ) -> Result<Vec<u8>, SignerError> {
+        let chain_id = raw_tx.chain_id;
+        let tx = Transaction::from(raw_tx);
+        let message_hash = tx.signing_hash(chain_id);
+        let signature = self.sign(message_hash).await?;
+        let signed = tx.with_signature(chain_id, message_hash, signature.into());
+        Ok(signed.raw_transaction.0)
+    }
+
+    async fn get_address(&self) -> Result<Address, SignerError> {
+        Ok(self.address)
+    }
+}
+
+fn public_key_to_address(public_key: &PublicKey) -> Address {
+    let hash = keccak256(&public_key.serialize_uncompressed()[1..]);
+    Address::from_slice(&hash[12..])
+}
+
+/// Extracts the address from a DER-encoded `SubjectPublicKeyInfo` of a secp256k1 key (as returned by cloud KMSs).
+/// Such an encoding always ends with the uncompressed public key.
+pub(crate) fn address_from_public_key_der(der: &[u8]) -> Result<Address, SignerError> {
+    const UNCOMPRESSED_KEY_LEN: usize = 65;
+
+    if der.len() < UNCOMPRESSED_KEY_LEN {
+        return Err(SignerError::Remote(format!(
+            "public key is too short: {} bytes",
+            der.len()
+        )));
+    }
+    let public_key = PublicKey::from_slice(&der[der.len() - UNCOMPRESSED_KEY_LEN..])
+        .map_err(|err| SignerError::Remote(format!("malformed public key: {err}")))?;
+    Ok(public_key_to_address(&public_key))
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::SECP256K1;
+    use zksync_types::{K256PrivateKey, H160, U256, U64};
+
+    use super::*;
+    use crate::PrivateKeySigner;
+
+    /// Signs digests locally, returning DER-encoded signatures with high `s` values like some KMSs do.
+    #[derive(Debug)]
+    struct MockKms(K256PrivateKey);
+
+    #[async_trait]
+    impl DigestSigner for MockKms {
+        async fn address(&self) -> Result<Address, SignerError> {
+            Ok(self.0.address())
+        }
+
+        async fn sign_digest(&self, digest: H256) -> Result<DigestSignature, SignerError> {
+            let message = Message::from_slice(digest.as_bytes()).unwrap();
+            let signature = SECP256K1.sign_ecdsa(&message, self.0.expose_secret());
+            let der = denormalize_s(signature).serialize_der();
+            DigestSignature::from_der(&der, &digest, self.0.address())
+        }
+    }
+
+    fn denormalize_s(signature: secp256k1::ecdsa::Signature) -> secp256k1::ecdsa::Signature {
+        // Order of the secp256k1 curve.
+        let order = U256::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        let mut compact = signature.serialize_compact();
+        let s = U256::from_big_endian(&compact[32..]);
+        (order - s).to_big_endian(&mut compact[32..]);
+        secp256k1::ecdsa::Signature::from_compact(&compact).unwrap()
+    }
+
+    fn transaction(transaction_type: Option<u64>) -> TransactionParameters {
+        TransactionParameters {
+            nonce: U256::from(1u32),
+            to: Some(H160::repeat_byte(1)),
+            gas: 21_000.into(),
+            gas_price: Some(U256::from(2u32)),
+            max_fee_per_gas: U256::from(2u32),
+            max_priority_fee_per_gas: U256::from(1u32),
+            value: Default::default(),
+            data: vec![1, 2, 3],
+            chain_id: 270,
+            transaction_type: transaction_type.map(U64::from),
+            access_list: None,
+            blob_versioned_hashes: None,
+            max_fee_per_blob_gas: None,
+        }
+    }
+
+    #[test]
+    fn extracting_address_from_public_key_der() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let public_key = PublicKey::from_secret_key(SECP256K1, private_key.expose_secret());
+        // `SubjectPublicKeyInfo` prefix for secp256k1 keys.
+        let mut der = hex::decode("3056301006072a8648ce3d020106052b8104000a034200").unwrap();
+        der.extend_from_slice(&public_key.serialize_uncompressed());
+        assert_eq!(
+            address_from_public_key_der(&der).unwrap(),
+            private_key.address()
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_signer_produces_same_transactions_as_private_key_signer() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let local_signer = PrivateKeySigner::new(private_key.clone());
+        let remote_signer = RemoteSigner::new(
+            Arc::new(MockKms(private_key.clone())),
+            Some(private_key.address()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(remote_signer.address(), private_key.address());
+
+        // Signatures are deterministic (RFC 6979), so the transactions must coincide.
+        for transaction_type in [None, Some(2)] {
+            let tx = transaction(transaction_type);
+            let expected = local_signer.sign_transaction(tx.clone()).await.unwrap();
+            let actual = remote_signer.sign_transaction(tx).await.unwrap();
+            assert_eq!(actual, expected, "transaction_type = {transaction_type:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn remote_signer_checks_address() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let err = RemoteSigner::new(Arc::new(MockKms(private_key)), Some(Address::zero()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, SignerError::Remote(_)), "{err}");
+    }
+
+    #[test]
+    fn parsing_packed_signature() {
+        let mut bytes = [1_u8; 65];
+        bytes[64] = 28;
+        let signature = DigestSignature::from_packed(&bytes).unwrap();
+        assert_eq!(signature.recovery_id, 1);
+        assert_eq!(signature.r, H256::repeat_byte(1));
+
+        bytes[64] = 2;
+        DigestSignature::from_packed(&bytes).unwrap_err();
+        DigestSignature::from_packed(&bytes[..64]).unwrap_err();
+    }
+
+    #[test]
+    fn normalizing_packed_signature_with_high_s() {
+        let private_key = K256PrivateKey::from_bytes(H256::repeat_byte(5)).unwrap();
+        let digest = H256::repeat_byte(0x42);
+        let message = Message::from_slice(digest.as_bytes()).unwrap();
+        let signature = SECP256K1.sign_ecdsa_recoverable(&message, private_key.expose_secret());
+        let (recovery_id, compact) = signature.serialize_compact();
+        let recovery_id = recovery_id.to_i32() as u8;
+
+        let mut bytes = [0_u8; 65];
+        bytes[..64].copy_from_slice(&denormalize_s(signature.to_standard()).serialize_compact());
+        // The recovery ID must be flipped for the signature with the high `s` to be valid.
+        bytes[64] = (recovery_id ^ 1) + 27;
+        let parsed = DigestSignature::from_packed(&bytes).unwrap();
+        assert_eq!(parsed.r, H256::from_slice(&compact[..32]));
+        assert_eq!(parsed.s, H256::from_slice(&compact[32..]));
+        assert_eq!(parsed.recovery_id, recovery_id);
+        assert_eq!(
+            parsed.recover_signer(&digest).unwrap(),
+            private_key.address()
+        );
+
+        // Signatures with a low `s` are returned as is.
+        bytes[..64].copy_from_slice(&compact);
+        bytes[64] = recovery_id;
+        let parsed = DigestSignature::from_packed(&bytes).unwrap();
+        assert_eq!(parsed.s, H256::from_slice(&compact[32..]));
+        assert_eq!(parsed.recovery_id, recovery_id);
+    }
+}
@@ -1,15 +1,19 @@
 use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
 
 use zksync_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
+    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction, H256,
+    U256,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{AccountTransactions, L2TxFilter, MempoolOrdering, MempoolScore};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// Transactions that were not inserted because their accounts have too many pending transactions.
+    /// These transactions should be returned to storage, so that they are fetched again on a later sync.
+    pub rejected_transactions: Vec<H256>,
 }
 
 #[derive(Debug)]
@@ -30,9 +34,15 @@ pub struct MempoolStore {
     /// Next priority operation
     next_priority_id: PriorityOpId,
     stashed_accounts: Vec<Address>,
+    rejected_transactions: Vec<H256>,
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    ordering: MempoolOrdering,
+    /// Base fee per gas that the scores in `l2_priority_queue` are computed for.
+    base_fee: U256,
+    replacement_fee_bump_percent: Option<u32>,
+    max_transactions_per_account: Option<usize>,
 }
 
 impl MempoolStore {
@@ -43,11 +53,38 @@ impl MempoolStore {
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
+            rejected_transactions: vec![],
             size: 0,
             capacity,
+            ordering: MempoolOrdering::default(),
+            base_fee: U256::zero(),
+            replacement_fee_bump_percent: None,
+            max_transactions_per_account: None,
         }
     }
 
+    /// Sets the order in which executable L2 transactions are returned. Should be called before any
+    /// transactions are inserted.
+    pub fn with_ordering(mut self, ordering: MempoolOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Requires a transaction replacing a pending transaction with the same initiator and nonce to increase
+    /// both its max fee and max priority fee by at least the specified percentage. Replacements not satisfying
+    /// this requirement are ignored. Should be called before any transactions are inserted.
+    pub fn with_replacement_fee_bump(mut self, min_bump_percent: Option<u32>) -> Self {
+        self.replacement_fee_bump_percent = min_bump_percent;
+        self
+    }
+
+    /// Limits the number of pending L2 transactions per initiator account. Transactions with new nonces
+    /// for accounts that have reached the limit are ignored. Should be called before any transactions are inserted.
+    pub fn with_max_transactions_per_account(mut self, max_transactions: Option<usize>) -> Self {
+        self.max_transactions_per_account = max_transactions;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -101,13 +138,20 @@ impl MempoolStore {
         initial_nonces: &HashMap<Address, Nonce>,
     ) {
         let account = transaction.initiator_account();
+        let tx_hash = transaction.hash();
 
         let metadata = match self.l2_transactions_per_account.entry(account) {
             hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
             hash_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
-                    .insert(AccountTransactions::new(account_nonce))
+                    .insert(AccountTransactions::new(
+                        account_nonce,
+                        self.ordering,
+                        self.base_fee,
+                        self.replacement_fee_bump_percent,
+                        self.max_transactions_per_account,
+                    ))
                     .insert(transaction)
             }
         };
@@ -120,6 +164,9 @@ impl MempoolStore {
         if metadata.is_new {
             self.size += 1;
         }
+        if metadata.is_rejected {
+            self.rejected_transactions.push(tx_hash);
+        }
     }
 
     /// Returns `true` if there is a transaction in the mempool satisfying the filter.
@@ -138,7 +185,18 @@ impl MempoolStore {
             self.next_priority_id += 1;
             return Some(transaction.into());
         }
+        self.next_l2_transaction(filter)
+    }
 
+    /// Returns the next L1 priority operation that would be returned by [`Self::next_transaction()`],
+    /// without removing it from the mempool.
+    pub fn next_priority_op(&self) -> Option<&L1Tx> {
+        self.l1_transactions.get(&self.next_priority_id)
+    }
+
+    /// Same as [`Self::next_transaction()`], but skips pending L1 priority operations.
+    pub fn next_l2_transaction(&mut self, filter: &L2TxFilter) -> Option<Transaction> {
+        self.update_base_fee(filter.fee_per_gas.into());
         let mut removed = 0;
         // We want to fetch the next transaction that would match the fee requirements.
         let tx_pointer = self
@@ -179,6 +237,42 @@ impl MempoolStore {
         Some(transaction.into())
     }
 
+    /// Returns up to `limit` L2 transactions satisfying the filter in the order they would be returned
+    /// by [`Self::next_transaction()`], without removing them from the mempool. Only the next transaction
+    /// of each account is returned, since the following ones depend on it.
+    pub fn peek_l2_transactions(&mut self, filter: &L2TxFilter, limit: usize) -> Vec<Transaction> {
+        self.update_base_fee(filter.fee_per_gas.into());
+        self.l2_priority_queue
+            .iter()
+            .rev()
+            .filter(|pointer| pointer.matches_filter(filter))
+            .filter_map(|pointer| {
+                self.l2_transactions_per_account
+                    .get(&pointer.account)?
+                    .peek()
+            })
+            .take(limit)
+            .map(|transaction| transaction.clone().into())
+            .collect()
+    }
+
+    /// Recomputes scores in the priority queue if the base fee has changed. Effective priority fees depend
+    /// on the base fee, so the queue needs to be rebuilt; this only happens if the ordering uses priority fees.
+    fn update_base_fee(&mut self, base_fee: U256) {
+        if self.ordering == MempoolOrdering::Fifo || self.base_fee == base_fee {
+            return;
+        }
+        self.base_fee = base_fee;
+        self.l2_priority_queue = self
+            .l2_transactions_per_account
+            .values_mut()
+            .filter_map(|transactions| {
+                transactions.set_base_fee(base_fee);
+                transactions.next_score()
+            })
+            .collect();
+    }
+
     /// When a state_keeper starts the block over after a rejected transaction,
     /// we have to rollback the nonces/ids in the mempool and
     /// reinsert the transactions from the block back into mempool.
@@ -205,10 +299,29 @@ impl MempoolStore {
         }
     }
 
+    /// Removes all L2 transactions of the specified accounts from the mempool and stashes the accounts,
+    /// so that their remaining transactions are reloaded from the storage on the next sync.
+    pub fn stash_accounts(&mut self, accounts: &[Address]) {
+        for account in accounts {
+            let Some(transactions) = self.l2_transactions_per_account.remove(account) else {
+                continue;
+            };
+            if let Some(score) = transactions.next_score() {
+                self.l2_priority_queue.remove(&score);
+            }
+            self.size = self
+                .size
+                .checked_sub(transactions.len() as u64)
+                .expect("mempool size can't be negative");
+            self.stashed_accounts.push(*account);
+        }
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
             purged_accounts: self.gc(),
+            rejected_transactions: std::mem::take(&mut self.rejected_transactions),
         }
     }
 
@@ -12,7 +12,10 @@ use zksync_types::{
     H256, U256,
 };
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    types::{L2TxFilter, MempoolOrdering},
+};
 
 #[test]
 fn basic_flow() {
@@ -117,6 +120,32 @@ fn prioritize_l1_txns() {
         .is_l1())
 }
 
+#[test]
+fn skipping_l1_txns() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account = Address::random();
+    let transactions = vec![gen_l2_tx(account, Nonce(0)), gen_l1_tx(PriorityOpId(0))];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(
+        mempool.next_priority_op().unwrap().serial_id(),
+        PriorityOpId(0)
+    );
+
+    assert_eq!(
+        view(mempool.next_l2_transaction(&L2TxFilter::default())),
+        (account, 0)
+    );
+    assert!(mempool
+        .next_l2_transaction(&L2TxFilter::default())
+        .is_none());
+    // The skipped L1 transaction must still be available.
+    assert!(mempool
+        .next_transaction(&L2TxFilter::default())
+        .unwrap()
+        .is_l1());
+    assert!(mempool.next_priority_op().is_none());
+}
+
 #[test]
 fn l1_txns_priority_id() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -200,6 +229,83 @@ fn replace_tx() {
     assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
 }
 
+#[test]
+fn replace_tx_with_fee_bump() {
+    fn gen_replacement(
+        account: Address,
+        max_fee_per_gas: u64,
+        max_priority_fee_per_gas: u64,
+    ) -> Transaction {
+        let mut tx = gen_l2_tx_with_fees(
+            account,
+            Nonce(0),
+            0,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+        );
+        let hash = H256::from_low_u64_be((max_fee_per_gas << 32) | max_priority_fee_per_gas);
+        match &mut tx.common_data {
+            ExecuteTransactionCommon::L2(data) => data.set_input(vec![], hash),
+            _ => unreachable!(),
+        }
+        tx
+    }
+
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100).with_replacement_fee_bump(Some(10));
+    let account = Address::random();
+    mempool.insert(vec![gen_replacement(account, 100, 10)], HashMap::new());
+    // Neither of the fees is bumped sufficiently.
+    mempool.insert(vec![gen_replacement(account, 105, 20)], HashMap::new());
+    mempool.insert(vec![gen_replacement(account, 200, 10)], HashMap::new());
+    // Reinserting the same transaction is fine.
+    mempool.insert(vec![gen_replacement(account, 100, 10)], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(tx.hash(), H256::from_low_u64_be((100 << 32) | 10));
+
+    let account = Address::random();
+    mempool.insert(vec![gen_replacement(account, 100, 10)], HashMap::new());
+    mempool.insert(vec![gen_replacement(account, 110, 11)], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 1);
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(tx.hash(), H256::from_low_u64_be((110 << 32) | 11));
+    assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
+}
+
+#[test]
+fn limiting_transactions_per_account() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_max_transactions_per_account(Some(2));
+    let account = Address::random();
+    let transactions: Vec<_> = (0..4)
+        .map(|nonce| gen_l2_tx(account, Nonce(nonce)))
+        .collect();
+    let rejected_hashes: Vec<_> = transactions[2..].iter().map(Transaction::hash).collect();
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+    // Rejected transactions must be returned to storage.
+    assert_eq!(
+        mempool.get_mempool_info().rejected_transactions,
+        rejected_hashes
+    );
+    assert!(mempool.get_mempool_info().rejected_transactions.is_empty());
+    // Replacing a pending transaction is still allowed.
+    mempool.insert(vec![gen_l2_tx(account, Nonce(1))], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+
+    let tx = mempool.next_transaction(&L2TxFilter::default()).unwrap();
+    assert_eq!(tx.nonce(), Some(Nonce(0)));
+    // After a transaction was sent to the state keeper, the account can submit another transaction.
+    mempool.insert(vec![gen_l2_tx(account, Nonce(2))], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+    mempool.insert(vec![gen_l2_tx(account, Nonce(3))], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+
+    // Other accounts are not affected.
+    mempool.insert(vec![gen_l2_tx(Address::random(), Nonce(0))], HashMap::new());
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+}
+
 #[test]
 fn two_ready_txs() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -216,6 +322,41 @@ fn two_ready_txs() {
     );
 }
 
+#[test]
+fn peeking_transactions() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_timestamp(account0, Nonce(0), 100),
+        gen_l2_tx_with_timestamp(account0, Nonce(1), 300),
+        gen_l2_tx_with_timestamp(account1, Nonce(0), 200),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let peeked: Vec<_> = mempool
+        .peek_l2_transactions(&L2TxFilter::default(), 10)
+        .into_iter()
+        .map(|tx| view(Some(tx)))
+        .collect();
+    assert_eq!(peeked, [(account0, 0), (account1, 0)]);
+    let peeked = mempool.peek_l2_transactions(&L2TxFilter::default(), 1);
+    assert_eq!(view(peeked.into_iter().next()), (account0, 0));
+
+    // Peeking must not modify the mempool.
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    let peeked: Vec<_> = mempool
+        .peek_l2_transactions(&L2TxFilter::default(), 10)
+        .into_iter()
+        .map(|tx| view(Some(tx)))
+        .collect();
+    assert_eq!(peeked, [(account1, 0), (account0, 1)]);
+}
+
 #[test]
 fn mempool_size() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
@@ -319,6 +460,30 @@ fn stashed_accounts() {
     assert!(mempool.next_transaction(&filter_zero).is_none());
 }
 
+#[test]
+fn stashing_accounts() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx(account0, Nonce(0)),
+        gen_l2_tx(account0, Nonce(1)),
+        gen_l2_tx(account1, Nonce(0)),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    mempool.stash_accounts(&[account0, Address::random()]);
+
+    let stats = mempool.stats();
+    assert_eq!(stats.l2_transaction_count, 1);
+    assert_eq!(stats.l2_priority_queue_size, 1);
+    assert_eq!(mempool.get_mempool_info().stashed_accounts, [account0]);
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 0)
+    );
+    assert!(mempool.next_transaction(&L2TxFilter::default()).is_none());
+}
+
 #[test]
 fn mempool_capacity() {
     let mut mempool = MempoolStore::new(PriorityOpId(0), 5);
@@ -365,6 +530,101 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn priority_fee_ordering() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_fees(account0, Nonce(0), 0, 10, 1),
+        gen_l2_tx_with_fees(account0, Nonce(1), 1, 10, 100),
+        gen_l2_tx_with_fees(account1, Nonce(0), 2, 10, 5),
+        // Priority fee is capped by the max fee per gas, so the effective priority fee is 3.
+        gen_l2_tx_with_fees(account2, Nonce(0), 3, 3, 50),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    // `account0` transaction with nonce 1 has the highest priority fee, but it cannot be executed
+    // until the transaction with nonce 0 is.
+    let order: Vec<_> = (0..4)
+        .map(|_| view(mempool.next_transaction(&L2TxFilter::default())))
+        .collect();
+    assert_eq!(
+        order,
+        [(account1, 0), (account2, 0), (account0, 0), (account0, 1)]
+    );
+    assert_eq!(mempool.next_transaction(&L2TxFilter::default()), None);
+
+    // Transactions with equal priority fees are ordered by the received at timestamp.
+    let transactions = vec![
+        gen_l2_tx_with_fees(account1, Nonce(1), 5, 10, 2),
+        gen_l2_tx_with_fees(account2, Nonce(1), 4, 10, 2),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account2, 1)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 1)
+    );
+}
+
+#[test]
+fn priority_fee_ordering_accounts_for_base_fee() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_fees(account0, Nonce(0), 0, 10, 8),
+        gen_l2_tx_with_fees(account0, Nonce(1), 1, 10, 8),
+        gen_l2_tx_with_fees(account1, Nonce(0), 2, 20, 5),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let peeked: Vec<_> = mempool
+        .peek_l2_transactions(&L2TxFilter::default(), 10)
+        .into_iter()
+        .map(|tx| view(Some(tx)))
+        .collect();
+    assert_eq!(peeked, [(account0, 0), (account1, 0)]);
+
+    // With the base fee of 6, the effective priority fee of `account0` transactions is `min(8, 10 - 6) = 4`,
+    // which is lower than `min(5, 20 - 6) = 5` for the `account1` transaction.
+    let filter = L2TxFilter {
+        fee_per_gas: 6,
+        ..L2TxFilter::default()
+    };
+    let order: Vec<_> = (0..3)
+        .map(|_| view(mempool.next_transaction(&filter)))
+        .collect();
+    assert_eq!(order, [(account1, 0), (account0, 0), (account0, 1)]);
+}
+
+#[test]
+fn fifo_ordering_ignores_priority_fee() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let transactions = vec![
+        gen_l2_tx_with_fees(account0, Nonce(0), 0, 10, 1),
+        gen_l2_tx_with_fees(account1, Nonce(0), 1, 10, 10),
+    ];
+    mempool.insert(transactions, HashMap::new());
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account1, 0)
+    );
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -384,6 +644,24 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_fees(
+    address: Address,
+    nonce: Nonce,
+    received_at_ms: u64,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+) -> Transaction {
+    let mut tx = gen_l2_tx_with_timestamp(address, nonce, received_at_ms);
+    match &mut tx.common_data {
+        ExecuteTransactionCommon::L2(data) => {
+            data.fee.max_fee_per_gas = max_fee_per_gas.into();
+            data.fee.max_priority_fee_per_gas = max_priority_fee_per_gas.into();
+        }
+        _ => unreachable!(),
+    }
+    tx
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
@@ -12,13 +12,29 @@ pub(crate) struct AccountTransactions {
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    ordering: MempoolOrdering,
+    /// base fee per gas used to compute effective priority fees of transactions
+    base_fee: U256,
+    replacement_fee_bump_percent: Option<u32>,
+    /// maximum number of pending transactions for the account
+    max_transactions: Option<usize>,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(
+        nonce: Nonce,
+        ordering: MempoolOrdering,
+        base_fee: U256,
+        replacement_fee_bump_percent: Option<u32>,
+        max_transactions: Option<usize>,
+    ) -> Self {
         Self {
             transactions: HashMap::new(),
             nonce,
+            ordering,
+            base_fee,
+            replacement_fee_bump_percent,
+            max_transactions,
         }
     }
 
@@ -30,11 +46,44 @@ impl AccountTransactions {
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
+        // skip insertion if the account has too many pending transactions
+        if let Some(max_transactions) = self.max_transactions {
+            if !self.transactions.contains_key(&nonce)
+                && self.transactions.len() >= max_transactions
+            {
+                tracing::debug!(
+                    "rejected transaction with nonce {nonce} for account {:?}: account has {max_transactions} \
+                     pending transactions",
+                    transaction.initiator_account()
+                );
+                metadata.is_rejected = true;
+                return metadata;
+            }
+        }
+        // skip insertion if transaction replaces another one without a sufficient fee bump
+        if let (Some(existing), Some(min_bump_percent)) = (
+            self.transactions.get(&nonce),
+            self.replacement_fee_bump_percent,
+        ) {
+            let is_sufficient_replacement = existing.hash() == transaction.hash()
+                || transaction
+                    .common_data
+                    .fee
+                    .is_sufficient_replacement_for(&existing.common_data.fee, min_bump_percent);
+            if !is_sufficient_replacement {
+                tracing::debug!(
+                    "rejected replacement {:?} for transaction {:?}: insufficient fee bump",
+                    transaction.hash(),
+                    existing.hash()
+                );
+                return metadata;
+            }
+        }
+        let new_score = self.score_for_transaction(&transaction);
         let previous_score = self
             .transactions
             .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
+            .map(|tx| self.score_for_transaction(&tx));
         metadata.is_new = previous_score.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
@@ -54,10 +103,20 @@ impl AccountTransactions {
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
+            .map(|tx| self.score_for_transaction(tx));
         (transaction, score)
     }
 
+    /// Returns the next transaction to be included in block without removing it.
+    pub fn peek(&self) -> Option<&L2Tx> {
+        self.transactions.get(&self.nonce)
+    }
+
+    /// Returns the score of the next transaction to be included in block, if any.
+    pub fn next_score(&self) -> Option<MempoolScore> {
+        self.peek().map(|tx| self.score_for_transaction(tx))
+    }
+
     /// Handles transaction rejection. Returns optional score of its successor
     pub fn reset(&mut self, transaction: &Transaction) -> Option<MempoolScore> {
         // current nonce for the group needs to be reset
@@ -67,28 +126,57 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+            .map(|tx| self.score_for_transaction(tx))
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    /// Updates the base fee used to compute effective priority fees. Scores returned before the update
+    /// become stale.
+    pub fn set_base_fee(&mut self, base_fee: U256) {
+        self.base_fee = base_fee;
+    }
+
+    fn score_for_transaction(&self, transaction: &L2Tx) -> MempoolScore {
+        let fee_data = transaction.common_data.fee.clone();
+        let priority_fee = match self.ordering {
+            MempoolOrdering::Fifo => U256::zero(),
+            MempoolOrdering::PriorityFee => fee_data
+                .max_priority_fee_per_gas
+                .min(fee_data.max_fee_per_gas.saturating_sub(self.base_fee)),
+        };
         MempoolScore {
             account: transaction.initiator_account(),
             received_at_ms: transaction.received_timestamp_ms,
-            fee_data: transaction.common_data.fee.clone(),
+            priority_fee,
+            fee_data,
         }
     }
 }
 
-/// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Order in which executable L2 transactions are returned from the mempool. Transactions of the same account
+/// are always returned in the nonce order; the ordering only applies to the next transactions of different accounts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the received at timestamp.
+    #[default]
+    Fifo,
+    /// Transactions are ordered by the effective priority fee, i.e. `min(max_priority_fee_per_gas,
+    /// max_fee_per_gas - base_fee)`, with ties broken by the received at timestamp. The base fee is taken
+    /// from the filter provided by the state keeper.
+    PriorityFee,
+}
+
+/// Mempool score of transaction. Used to prioritize L2 transactions in mempool.
+/// Transactions are ordered by `priority_fee` and then by the received at timestamp.
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
     pub received_at_ms: u64,
+    /// Effective priority fee of the transaction; always zero if the mempool uses FIFO ordering.
+    pub priority_fee: U256,
     // Not used for actual scoring, but state keeper would request
     // transactions that have acceptable fee values (so transactions
     // with fee too low would be ignored until prices go down).
@@ -105,6 +193,10 @@ impl MempoolScore {
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority_fee.cmp(&other.priority_fee) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -124,6 +216,8 @@ pub(crate) struct InsertionMetadata {
     pub new_score: Option<MempoolScore>,
     pub previous_score: Option<MempoolScore>,
     pub is_new: bool,
+    /// Set if the transaction was rejected because the account has too many pending transactions.
+    pub is_rejected: bool,
 }
 
 /// Structure that can be used by state keeper to describe
@@ -160,6 +254,7 @@ mod tests {
         let score = MempoolScore {
             account: Address::random(),
             received_at_ms: Default::default(), // Not important
+            priority_fee: Default::default(),   // Not important
             fee_data: Fee {
                 gas_limit: Default::default(), // Not important
                 max_fee_per_gas: U256::from(MAX_FEE_PER_GAS),
@@ -7,12 +7,13 @@ use zksync_types::{L1BatchNumber, StorageKey};
 
 use crate::{
     consistency::ConsistencyError,
-    storage::{PatchSet, Patched, RocksDBWrapper},
+    storage::{InMemoryDatabase, PatchSet, Patched, RocksDBWrapper},
     types::{
-        Key, Root, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry, ValueHash,
-        TREE_DEPTH,
+        Key, Root, TreeDiff, TreeEntry, TreeEntryWithProof, TreeInstruction, TreeLogEntry,
+        TreeMultiProof, TreeRangeProof, ValueHash, TREE_DEPTH,
     },
-    BlockOutput, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle, NoVersionError,
+    BlockOutput, Database, HashTree, MerkleTree, MerkleTreePruner, MerkleTreePrunerHandle,
+    NoVersionError, PruneDatabase, TreeHasher,
 };
 
 /// Metadata for the current tree state.
@@ -38,32 +39,49 @@ enum TreeMode {
 /// [`Self::process_l1_batches()`] and [`Self::revert_logs()`] in RAM without saving them
 /// to RocksDB. The accumulated changes can be saved to RocksDB via [`Self::save()`]
 /// or discarded via [`Self::reset()`].
+///
+/// The tree is backed by RocksDB by default. For testing, it can be backed by an [`InMemoryDatabase`] instead;
+/// see [`Self::in_memory()`].
 #[derive(Debug)]
-pub struct ZkSyncTree {
-    tree: MerkleTree<Patched<RocksDBWrapper>>,
+pub struct ZkSyncTree<DB = RocksDBWrapper> {
+    tree: MerkleTree<Patched<DB>, TreeHasher>,
     thread_pool: Option<ThreadPool>,
     mode: TreeMode,
     pruning_enabled: bool,
 }
 
 impl ZkSyncTree {
-    /// Returns a hash of an empty tree. This is a constant value.
+    /// Returns a hash of an empty tree with the default Blake2s hasher. This is a constant value.
     pub fn empty_tree_hash() -> ValueHash {
         Blake2Hasher.empty_tree_hash()
     }
 
-    fn create_thread_pool(thread_count: usize) -> ThreadPool {
-        ThreadPoolBuilder::new()
-            .thread_name(|idx| format!("new-merkle-tree-{idx}"))
-            .num_threads(thread_count)
-            .build()
-            .expect("failed initializing `rayon` thread pool")
+    /// Creates a tree with the full processing mode.
+    ///
+    /// # Errors
+    ///
+    /// Errors if sanity checks fail.
+    pub fn new(db: RocksDBWrapper) -> anyhow::Result<Self> {
+        Self::new_with_mode(db, TreeMode::Full)
+    }
+
+    /// Creates a tree with the lightweight processing mode.
+    ///
+    /// # Errors
+    ///
+    /// Errors if sanity checks fail.
+    pub fn new_lightweight(db: RocksDBWrapper) -> anyhow::Result<Self> {
+        Self::new_with_mode(db, TreeMode::Lightweight)
     }
 
     /// Returns metadata based on `storage_logs` generated by the genesis L1 batch. This does not
-    /// create a persistent tree.
+    /// create a persistent tree. The tree is hashed using the provided `hasher`, which should be chosen
+    /// based on the genesis protocol version (see [`TreeHasher::for_protocol_version()`]).
     #[allow(clippy::missing_panics_doc)] // false positive
-    pub fn process_genesis_batch(storage_logs: &[TreeInstruction<StorageKey>]) -> BlockOutput {
+    pub fn process_genesis_batch(
+        storage_logs: &[TreeInstruction<StorageKey>],
+        hasher: TreeHasher,
+    ) -> BlockOutput {
         let kvs = Self::filter_write_instructions(storage_logs);
         tracing::info!(
             "Creating Merkle tree for genesis batch with {instr_count} writes",
@@ -76,7 +94,7 @@ impl ZkSyncTree {
             .collect();
 
         // `unwrap()`s are safe: in-memory trees never raise I/O errors
-        let mut in_memory_tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let mut in_memory_tree = MerkleTree::with_hasher(PatchSet::default(), hasher).unwrap();
         let output = in_memory_tree.extend(kvs).unwrap();
 
         tracing::info!(
@@ -87,40 +105,104 @@ impl ZkSyncTree {
         output
     }
 
-    /// Creates a tree with the full processing mode.
+    /// Sets the chunk size for multi-get operations. The requested keys will be split
+    /// into chunks of this size and requested in parallel using `rayon`. Setting chunk size
+    /// to a large value (e.g., `usize::MAX`) will effectively disable parallelism.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn set_multi_get_chunk_size(&mut self, chunk_size: usize) {
+        assert!(chunk_size > 0, "Multi-get chunk size must be positive");
+        self.tree
+            .db
+            .inner_mut()
+            .set_multi_get_chunk_size(chunk_size);
+    }
+}
+
+impl ZkSyncTree<InMemoryDatabase> {
+    /// Creates an in-memory tree with the full processing mode. Useful for testing.
     ///
     /// # Errors
     ///
     /// Errors if sanity checks fail.
-    pub fn new(db: RocksDBWrapper) -> anyhow::Result<Self> {
+    pub fn in_memory(db: InMemoryDatabase) -> anyhow::Result<Self> {
         Self::new_with_mode(db, TreeMode::Full)
     }
 
-    /// Creates a tree with the lightweight processing mode.
+    /// Creates an in-memory tree with the lightweight processing mode. Useful for testing.
     ///
     /// # Errors
     ///
     /// Errors if sanity checks fail.
-    pub fn new_lightweight(db: RocksDBWrapper) -> anyhow::Result<Self> {
+    pub fn in_memory_lightweight(db: InMemoryDatabase) -> anyhow::Result<Self> {
         Self::new_with_mode(db, TreeMode::Lightweight)
     }
+}
+
+impl<DB: PruneDatabase + Clone> ZkSyncTree<DB> {
+    fn create_thread_pool(thread_count: usize) -> ThreadPool {
+        ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("new-merkle-tree-{idx}"))
+            .num_threads(thread_count)
+            .build()
+            .expect("failed initializing `rayon` thread pool")
+    }
 
-    fn new_with_mode(db: RocksDBWrapper, mode: TreeMode) -> anyhow::Result<Self> {
+    /// Creates a tree with the full processing mode. If the tree is new (i.e., `db` is empty), it will be hashed
+    /// using `new_tree_hasher`; otherwise, the hasher is determined based on the tree tags, as in [`ZkSyncTree::new()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if sanity checks fail.
+    pub fn new_with_hasher(db: DB, new_tree_hasher: TreeHasher) -> anyhow::Result<Self> {
+        Self::new_with_mode_and_hasher(db, TreeMode::Full, new_tree_hasher)
+    }
+
+    /// Creates a tree with the lightweight processing mode. The hasher is selected in the same way
+    /// as in [`Self::new_with_hasher()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if sanity checks fail.
+    pub fn new_lightweight_with_hasher(
+        db: DB,
+        new_tree_hasher: TreeHasher,
+    ) -> anyhow::Result<Self> {
+        Self::new_with_mode_and_hasher(db, TreeMode::Lightweight, new_tree_hasher)
+    }
+
+    fn new_with_mode(db: DB, mode: TreeMode) -> anyhow::Result<Self> {
+        Self::new_with_mode_and_hasher(db, mode, TreeHasher::default())
+    }
+
+    fn new_with_mode_and_hasher(
+        db: DB,
+        mode: TreeMode,
+        new_tree_hasher: TreeHasher,
+    ) -> anyhow::Result<Self> {
+        let hasher = TreeHasher::from_db(&db)?.unwrap_or(new_tree_hasher);
         Ok(Self {
-            tree: MerkleTree::new(Patched::new(db))?,
+            tree: MerkleTree::with_hasher(Patched::new(db), hasher)?,
             thread_pool: None,
             mode,
             pruning_enabled: false,
         })
     }
 
+    /// Returns the hasher used by this tree.
+    pub fn hasher(&self) -> TreeHasher {
+        self.tree.hasher
+    }
+
     /// Returns tree pruner and a handle to stop it.
     ///
     /// # Panics
     ///
     /// Panics if this method was already called for the tree instance; it's logically unsound to run
     /// multiple pruners for the same tree concurrently.
-    pub fn pruner(&mut self) -> (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle) {
+    pub fn pruner(&mut self) -> (MerkleTreePruner<DB>, MerkleTreePrunerHandle) {
         assert!(
             !self.pruning_enabled,
             "pruner was already obtained for the tree"
@@ -132,28 +214,13 @@ impl ZkSyncTree {
 
     /// Returns a readonly handle to the tree. The handle **does not** see uncommitted changes to the tree,
     /// only ones flushed to RocksDB.
-    pub fn reader(&self) -> ZkSyncTreeReader {
+    pub fn reader(&self) -> ZkSyncTreeReader<DB> {
         let db = self.tree.db.inner().clone();
-        ZkSyncTreeReader(MerkleTree::new_unchecked(db))
-    }
-
-    /// Sets the chunk size for multi-get operations. The requested keys will be split
-    /// into chunks of this size and requested in parallel using `rayon`. Setting chunk size
-    /// to a large value (e.g., `usize::MAX`) will effectively disable parallelism.
-    ///
-    /// # Panics
-    ///
-    /// Panics if `chunk_size` is zero.
-    pub fn set_multi_get_chunk_size(&mut self, chunk_size: usize) {
-        assert!(chunk_size > 0, "Multi-get chunk size must be positive");
-        self.tree
-            .db
-            .inner_mut()
-            .set_multi_get_chunk_size(chunk_size);
+        ZkSyncTreeReader(MerkleTree::with_hasher_unchecked(db, self.tree.hasher))
     }
 
     /// Signals that the tree should use a dedicated `rayon` thread pool for parallel operations
-    /// (for now, hash computations).
+    /// (for now, hashing independent subtrees and multi-get operations).
     ///
     /// If `thread_count` is 0, the default number of threads will be used; see `rayon` docs
     /// for details.
@@ -166,6 +233,28 @@ impl ZkSyncTree {
         self.tree.latest_root_hash()
     }
 
+    /// Computes the root hash at the specified L1 batch as if the tree was hashed with the provided `hasher`.
+    /// Unlike [`ZkSyncTreeReader::root_hash_with_hasher()`], takes into account changes not yet flushed to the database.
+    /// See the reader method for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree version is missing, or tree nodes cannot be loaded.
+    pub fn root_hash_with_hasher(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hasher: TreeHasher,
+    ) -> Result<ValueHash, ConsistencyError> {
+        let version = l1_batch_number.0.into();
+        if hasher == self.tree.hasher {
+            self.tree
+                .root_hash(version)
+                .ok_or(ConsistencyError::MissingVersion(version))
+        } else {
+            self.tree.root_hash_with_hasher(version, &hasher)
+        }
+    }
+
     /// Checks whether this tree is empty.
     pub fn is_empty(&self) -> bool {
         let Some(version) = self.tree.latest_version() else {
@@ -243,7 +332,7 @@ impl ZkSyncTree {
         for (log, instruction) in output.logs.iter().zip(instructions) {
             let empty_levels_end = TREE_DEPTH - log.merkle_path.len();
             let empty_subtree_hashes =
-                (0..empty_levels_end).map(|i| Blake2Hasher.empty_subtree_hash(i));
+                (0..empty_levels_end).map(|i| self.tree.hasher.empty_subtree_hash(i));
             let merkle_paths = log.merkle_path.iter().copied();
             let merkle_paths = empty_subtree_hashes
                 .chain(merkle_paths)
@@ -379,34 +468,65 @@ impl ZkSyncTree {
 
 /// Readonly handle to a [`ZkSyncTree`].
 #[derive(Debug)]
-pub struct ZkSyncTreeReader(MerkleTree<RocksDBWrapper>);
+pub struct ZkSyncTreeReader<DB = RocksDBWrapper>(MerkleTree<DB, TreeHasher>);
 
 // While cloning `MerkleTree` is logically unsound, cloning a reader is reasonable since it is readonly.
-impl Clone for ZkSyncTreeReader {
+impl<DB: Database + Clone> Clone for ZkSyncTreeReader<DB> {
     fn clone(&self) -> Self {
-        Self(MerkleTree::new_unchecked(self.0.db.clone()))
+        Self(MerkleTree::with_hasher_unchecked(
+            self.0.db.clone(),
+            self.0.hasher,
+        ))
     }
 }
 
-impl ZkSyncTreeReader {
+impl<DB: PruneDatabase> ZkSyncTreeReader<DB> {
     /// Creates a tree reader based on the provided database.
     ///
     /// # Errors
     ///
     /// Errors if sanity checks fail.
-    pub fn new(db: RocksDBWrapper) -> anyhow::Result<Self> {
-        MerkleTree::new(db).map(Self)
+    pub fn new(db: DB) -> anyhow::Result<Self> {
+        let hasher = TreeHasher::from_db(&db)?.unwrap_or_default();
+        MerkleTree::with_hasher(db, hasher).map(Self)
+    }
+
+    /// Returns the hasher used by the tree.
+    pub fn hasher(&self) -> TreeHasher {
+        self.0.hasher
     }
 
     /// Returns a reference to the database this.
-    pub fn db(&self) -> &RocksDBWrapper {
+    pub fn db(&self) -> &DB {
         &self.0.db
     }
 
     /// Returns the root hash and leaf count at the specified L1 batch.
     pub fn root_info(&self, l1_batch_number: L1BatchNumber) -> Option<(ValueHash, u64)> {
         let root = self.0.root(l1_batch_number.0.into())?;
-        Some((root.hash(&Blake2Hasher), root.leaf_count()))
+        Some((root.hash(&self.0.hasher), root.leaf_count()))
+    }
+
+    /// Computes the root hash at the specified L1 batch as if the tree was hashed with the provided `hasher`.
+    /// This allows to commit to the tree state with both hashers during a transition between them.
+    /// This operation is expensive since it traverses the entire tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree version is missing, or tree nodes cannot be loaded.
+    pub fn root_hash_with_hasher(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hasher: TreeHasher,
+    ) -> Result<ValueHash, ConsistencyError> {
+        let version = l1_batch_number.0.into();
+        if hasher == self.0.hasher {
+            self.0
+                .root_hash(version)
+                .ok_or(ConsistencyError::MissingVersion(version))
+        } else {
+            self.0.root_hash_with_hasher(version, &hasher)
+        }
     }
 
     /// Returns the next L1 batch number that should be processed by the tree.
@@ -441,6 +561,66 @@ impl ZkSyncTreeReader {
         self.0.entries_with_proofs(version, keys)
     }
 
+    /// Reads entries with the specified keys together with a combined Merkle proof for all of them.
+    /// Entries in the proof are ordered by increasing key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multi_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        keys: &[Key],
+    ) -> Result<TreeMultiProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.entries_with_multi_proof(version, keys)
+    }
+
+    /// Proves that the specified key range (bounds included) contains certain entries and no other entries.
+    /// At most `max_entries` entries are proven; see [`MerkleTree::range_proof()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`.
+    pub fn range_proof(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeProof, NoVersionError> {
+        let version = u64::from(l1_batch_number.0);
+        self.0.range_proof(version, start_key, end_key, max_entries)
+    }
+
+    /// Returns entries that differ between the tree states after `old_l1_batch_number` and `new_l1_batch_number`.
+    /// Entries are returned in chunks of at most `max_entries` starting from `start_key`;
+    /// see [`MerkleTree::diff()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the tree versions is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_entries` is zero.
+    pub fn diff(
+        &self,
+        old_l1_batch_number: L1BatchNumber,
+        new_l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeDiff, NoVersionError> {
+        let old_version = u64::from(old_l1_batch_number.0);
+        let new_version = u64::from(new_l1_batch_number.0);
+        self.0
+            .diff(old_version, new_version, start_key, max_entries)
+    }
+
     /// Verifies consistency of the tree at the specified L1 batch number.
     ///
     /// # Errors
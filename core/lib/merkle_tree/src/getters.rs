@@ -4,7 +4,10 @@ use crate::{
     hasher::HasherWithStats,
     recovery::MerkleTreeRecovery,
     storage::{LoadAncestorsResult, SortedKeys, WorkingPatchSet},
-    types::{Nibbles, Node, ProfiledTreeOperation, TreeEntry, TreeEntryWithProof},
+    types::{
+        ChildRef, InternalNode, Nibbles, Node, ProfiledTreeOperation, Root, TreeDiff, TreeEntry,
+        TreeEntryDiff, TreeEntryWithProof, TreeMultiProof, TreeRangeProof, TREE_DEPTH,
+    },
     Database, HashTree, Key, MerkleTree, NoVersionError, PruneDatabase, ValueHash,
 };
 
@@ -59,21 +62,347 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
             },
         )
     }
+
+    /// Reads entries with the specified keys together with a combined Merkle proof for all of them.
+    /// Keys are sorted and deduplicated; i.e., entries in the returned proof are ordered by increasing key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    pub fn entries_with_multi_proof(
+        &self,
+        version: u64,
+        leaf_keys: &[Key],
+    ) -> Result<TreeMultiProof, NoVersionError> {
+        let mut sorted_keys = leaf_keys.to_vec();
+        sorted_keys.sort_unstable();
+        sorted_keys.dedup();
+        let entries = self.entries_with_proofs(version, &sorted_keys)?;
+        Ok(TreeMultiProof::new(&self.hasher, entries))
+    }
+
+    /// Proves that the specified key range (bounds included) contains certain entries and no other entries.
+    ///
+    /// At most `max_entries` entries strictly inside the range are proven. If the range contains more entries,
+    /// the range is truncated so that its end key is the first entry that didn't fit; i.e., [`TreeRangeProof::end`]
+    /// will have a key less than `end_key`. The caller can request the remaining part of the range starting
+    /// from this key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree `version` is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start_key > end_key`.
+    pub fn range_proof(
+        &self,
+        version: u64,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeProof, NoVersionError> {
+        assert!(
+            start_key <= end_key,
+            "Invalid key range: start key {start_key:0>64x} is greater than end key {end_key:0>64x}"
+        );
+
+        let mut entries = vec![];
+        if let Some(Root::Filled { node, .. }) = self.db.root(version) {
+            let _profiling_guard = self.db.start_profiling(ProfiledTreeOperation::GetEntries);
+            load_range_entries(
+                &self.db,
+                &node,
+                Nibbles::EMPTY,
+                (start_key, end_key),
+                max_entries.saturating_add(1),
+                &mut entries,
+            );
+        }
+        let end_key = if entries.len() > max_entries {
+            // `entries` contain exactly `max_entries + 1` items.
+            entries.pop().unwrap().key
+        } else {
+            end_key
+        };
+
+        // Missing `version` is checked here.
+        let mut boundaries = self.entries_with_proofs(version, &[start_key, end_key])?;
+        let end = boundaries.pop().unwrap();
+        let start = boundaries.pop().unwrap();
+        // ^ `unwrap()`s are safe: `entries_with_proofs()` returns an entry for each requested key
+
+        Ok(TreeRangeProof {
+            start,
+            entries,
+            end,
+        })
+    }
+
+    /// Returns entries that differ between `old_version` and `new_version` of the tree. Entries are ordered
+    /// by increasing key and start from `start_key` (inclusive); at most `max_entries` are returned. If there are more
+    /// changed entries, [`TreeDiff::next_key`] specifies `start_key` for the next chunk.
+    ///
+    /// Subtrees that are the same in both versions are skipped, so the cost of this method is roughly proportional
+    /// to the number of changed entries rather than to the tree size.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the tree versions is missing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_entries` is zero.
+    pub fn diff(
+        &self,
+        old_version: u64,
+        new_version: u64,
+        start_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeDiff, NoVersionError> {
+        assert!(max_entries > 0, "`max_entries` must be positive");
+
+        let old_root = load_root(&self.db, old_version)?;
+        let new_root = load_root(&self.db, new_version)?;
+        let mut collector = DiffCollector {
+            start_key,
+            max_entries,
+            entries: vec![],
+            next_key: None,
+        };
+        if old_version != new_version {
+            let _profiling_guard = self.db.start_profiling(ProfiledTreeOperation::GetEntries);
+            let old_node = match old_root {
+                Root::Filled { node, .. } => Some(node),
+                Root::Empty => None,
+            };
+            let new_node = match new_root {
+                Root::Filled { node, .. } => Some(node),
+                Root::Empty => None,
+            };
+            load_diff(
+                &self.db,
+                old_node.as_ref(),
+                new_node.as_ref(),
+                Nibbles::EMPTY,
+                &mut collector,
+            );
+        }
+        Ok(TreeDiff {
+            entries: collector.entries,
+            next_key: collector.next_key,
+        })
+    }
 }
 
-fn load_and_transform_entries<T>(
+/// Returns the range of keys (bounds included) covered by a subtree with the specified nibbles.
+fn subtree_key_range(nibbles: &Nibbles) -> (Key, Key) {
+    let min_key = Key::from_big_endian(nibbles.bytes());
+    let free_bits = TREE_DEPTH - nibbles.nibble_count() * 4;
+    let mask = if free_bits == TREE_DEPTH {
+        Key::MAX
+    } else {
+        (Key::one() << free_bits) - 1
+    };
+    (min_key, min_key | mask)
+}
+
+/// Loads non-empty entries with keys strictly inside the specified range in the order of increasing key.
+fn load_range_entries(
     db: &impl Database,
-    version: u64,
-    leaf_keys: &[Key],
-    mut transform: impl FnMut(&mut WorkingPatchSet, &Key, &Nibbles) -> T,
-) -> Result<Vec<T>, NoVersionError> {
-    let root = db.root(version).ok_or_else(|| {
+    node: &Node,
+    nibbles: Nibbles,
+    (start_key, end_key): (Key, Key),
+    limit: usize,
+    output: &mut Vec<TreeEntry>,
+) {
+    if output.len() >= limit {
+        return;
+    }
+    match node {
+        Node::Leaf(leaf) => {
+            if leaf.full_key > start_key && leaf.full_key < end_key {
+                output.push((*leaf).into());
+            }
+        }
+        Node::Internal(node) => {
+            // Children are iterated in the order of increasing nibble, i.e., increasing keys.
+            for (nibble, child_ref) in node.children() {
+                let child_nibbles = nibbles
+                    .push(nibble)
+                    .expect("internal node at terminal level");
+                let (min_key, max_key) = subtree_key_range(&child_nibbles);
+                if max_key <= start_key || min_key >= end_key {
+                    continue;
+                }
+
+                let child_key = child_nibbles.with_version(child_ref.version);
+                let child = db
+                    .tree_node(&child_key, child_ref.is_leaf)
+                    .unwrap_or_else(|| panic!("Node at {child_key} is missing from the tree"));
+                // Recursion is OK here; the tree isn't that deep.
+                load_range_entries(
+                    db,
+                    &child,
+                    child_nibbles,
+                    (start_key, end_key),
+                    limit,
+                    output,
+                );
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct DiffCollector {
+    start_key: Key,
+    max_entries: usize,
+    entries: Vec<TreeEntryDiff>,
+    next_key: Option<Key>,
+}
+
+impl DiffCollector {
+    fn is_full(&self) -> bool {
+        self.next_key.is_some()
+    }
+
+    fn push(&mut self, old: TreeEntry, new: TreeEntry) {
+        let key = old.key;
+        if key < self.start_key || self.is_full() {
+            return;
+        }
+        if self.entries.len() == self.max_entries {
+            self.next_key = Some(key);
+        } else {
+            self.entries.push(TreeEntryDiff { old, new });
+        }
+    }
+}
+
+/// Returns the child of `node` (which is located at `nibbles`) at the specified `nibble`. A leaf is considered
+/// to be its own child if its key matches `nibble`; this allows comparing a leaf with an internal node
+/// that has replaced it in another tree version.
+fn load_child(
+    db: &impl Database,
+    node: Option<&Node>,
+    nibbles: Nibbles,
+    nibble: u8,
+) -> Option<Node> {
+    match node? {
+        Node::Leaf(leaf) => {
+            let leaf_nibble = Nibbles::nibble(&leaf.full_key, nibbles.nibble_count());
+            (leaf_nibble == nibble).then_some(Node::Leaf(*leaf))
+        }
+        Node::Internal(node) => {
+            let child_ref = node.child_ref(nibble)?;
+            let child_key = nibbles
+                .push(nibble)
+                .expect("internal node at terminal level")
+                .with_version(child_ref.version);
+            let child = db
+                .tree_node(&child_key, child_ref.is_leaf)
+                .unwrap_or_else(|| panic!("Node at {child_key} is missing from the tree"));
+            Some(child)
+        }
+    }
+}
+
+/// Loads differences between subtrees located at the specified `nibbles` in the order of increasing key.
+fn load_diff(
+    db: &impl Database,
+    old: Option<&Node>,
+    new: Option<&Node>,
+    nibbles: Nibbles,
+    collector: &mut DiffCollector,
+) {
+    let (_, max_key) = subtree_key_range(&nibbles);
+    if max_key < collector.start_key || collector.is_full() {
+        return;
+    }
+
+    match (old, new) {
+        (None, None) => { /* Nothing to compare */ }
+        (Some(Node::Leaf(old)), None) => {
+            collector.push((*old).into(), TreeEntry::empty(old.full_key));
+        }
+        (None, Some(Node::Leaf(new))) => {
+            collector.push(TreeEntry::empty(new.full_key), (*new).into());
+        }
+        (Some(Node::Leaf(old)), Some(Node::Leaf(new))) => {
+            if old.full_key == new.full_key {
+                if old.value_hash != new.value_hash || old.leaf_index != new.leaf_index {
+                    collector.push((*old).into(), (*new).into());
+                }
+            } else {
+                let removed = ((*old).into(), TreeEntry::empty(old.full_key));
+                let inserted = (TreeEntry::empty(new.full_key), (*new).into());
+                let (first, second) = if old.full_key < new.full_key {
+                    (removed, inserted)
+                } else {
+                    (inserted, removed)
+                };
+                collector.push(first.0, first.1);
+                collector.push(second.0, second.1);
+            }
+        }
+        _ => {
+            // At least one of the nodes is internal. Children are iterated in the order of increasing nibble,
+            // i.e., increasing keys.
+            for nibble in 0..InternalNode::CHILD_COUNT {
+                let old_ref = child_ref(old, nibble);
+                let new_ref = child_ref(new, nibble);
+                if let (Some(old_ref), Some(new_ref)) = (old_ref, new_ref) {
+                    if old_ref.hash == new_ref.hash {
+                        continue; // The subtree hasn't changed
+                    }
+                }
+
+                let old_child = load_child(db, old, nibbles, nibble);
+                let new_child = load_child(db, new, nibbles, nibble);
+                let child_nibbles = nibbles
+                    .push(nibble)
+                    .expect("internal node at terminal level");
+                // Recursion is OK here; the tree isn't that deep.
+                load_diff(
+                    db,
+                    old_child.as_ref(),
+                    new_child.as_ref(),
+                    child_nibbles,
+                    collector,
+                );
+                if collector.is_full() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn child_ref(node: Option<&Node>, nibble: u8) -> Option<&ChildRef> {
+    match node? {
+        Node::Internal(node) => node.child_ref(nibble),
+        Node::Leaf(_) => None,
+    }
+}
+
+fn load_root(db: &impl Database, version: u64) -> Result<Root, NoVersionError> {
+    db.root(version).ok_or_else(|| {
         let manifest = db.manifest().unwrap_or_default();
         NoVersionError {
             missing_version: version,
             version_count: manifest.version_count,
         }
-    })?;
+    })
+}
+
+fn load_and_transform_entries<T>(
+    db: &impl Database,
+    version: u64,
+    leaf_keys: &[Key],
+    mut transform: impl FnMut(&mut WorkingPatchSet, &Key, &Nibbles) -> T,
+) -> Result<Vec<T>, NoVersionError> {
+    let root = load_root(db, version)?;
     let sorted_keys = SortedKeys::new(leaf_keys.iter().copied());
     let mut patch_set = WorkingPatchSet::new(version, root);
     let LoadAncestorsResult {
@@ -155,4 +484,90 @@ mod tests {
         assert!(entries[1].base.is_empty());
         entries[1].verify(&tree.hasher, output.root_hash);
     }
+
+    fn collect_diff(
+        tree: &MerkleTree<PatchSet>,
+        old_version: u64,
+        new_version: u64,
+        chunk_size: usize,
+    ) -> Vec<TreeEntryDiff> {
+        let mut start_key = Key::zero();
+        let mut entries = vec![];
+        loop {
+            let diff = tree
+                .diff(old_version, new_version, start_key, chunk_size)
+                .unwrap();
+            assert!(diff.entries.len() <= chunk_size);
+            entries.extend(diff.entries);
+            match diff.next_key {
+                Some(key) => start_key = key,
+                None => break entries,
+            }
+        }
+    }
+
+    #[test]
+    fn diff_between_versions() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let keys: Vec<_> = (0_u64..200)
+            .map(|i| (Key::from(i) * Key::from(0x_dead_beef_u64)) << 128)
+            .collect();
+        let initial_entries = keys[..100]
+            .iter()
+            .zip(1..)
+            .map(|(&key, i)| TreeEntry::new(key, i, ValueHash::from_low_u64_be(i)));
+        tree.extend(initial_entries.collect()).unwrap();
+        // Update every 3rd entry and insert new entries.
+        let updated_entries = keys[..100]
+            .iter()
+            .zip(1..)
+            .step_by(3)
+            .map(|(&key, i)| TreeEntry::new(key, i, ValueHash::repeat_byte(0xff)));
+        let inserted_entries = keys[100..]
+            .iter()
+            .zip(101..)
+            .map(|(&key, i)| TreeEntry::new(key, i, ValueHash::from_low_u64_be(i)));
+        tree.extend(updated_entries.chain(inserted_entries).collect())
+            .unwrap();
+
+        let diff = tree.diff(0, 0, Key::zero(), 10).unwrap();
+        assert!(diff.entries.is_empty());
+        assert_eq!(diff.next_key, None);
+
+        let mut changed_keys: Vec<_> = keys[..100]
+            .iter()
+            .step_by(3)
+            .chain(&keys[100..])
+            .copied()
+            .collect();
+        changed_keys.sort_unstable();
+        let new_entries = tree.entries(1, &changed_keys).unwrap();
+        let old_entries = tree.entries(0, &changed_keys).unwrap();
+
+        for chunk_size in [1, 7, 50, 1_000] {
+            let diff = collect_diff(&tree, 0, 1, chunk_size);
+            let diff_keys: Vec<_> = diff.iter().map(TreeEntryDiff::key).collect();
+            assert_eq!(diff_keys, changed_keys);
+            for ((entry_diff, old), new) in diff.iter().zip(&old_entries).zip(&new_entries) {
+                assert_eq!(entry_diff.old, *old);
+                assert_eq!(entry_diff.new, *new);
+            }
+
+            let reverse_diff = collect_diff(&tree, 1, 0, chunk_size);
+            assert_eq!(reverse_diff.len(), diff.len());
+            for (reverse, direct) in reverse_diff.iter().zip(&diff) {
+                assert_eq!(reverse.old, direct.new);
+                assert_eq!(reverse.new, direct.old);
+            }
+        }
+
+        let start_key = changed_keys[50];
+        let diff = tree.diff(0, 1, start_key, 10).unwrap();
+        let diff_keys: Vec<_> = diff.entries.iter().map(TreeEntryDiff::key).collect();
+        assert_eq!(diff_keys, changed_keys[50..60]);
+        assert_eq!(diff.next_key, Some(changed_keys[60]));
+
+        let err = tree.diff(0, 2, Key::zero(), 10).unwrap_err();
+        assert_eq!(err.missing_version, 2);
+    }
 }
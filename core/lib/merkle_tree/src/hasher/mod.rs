@@ -4,17 +4,24 @@ use std::{fmt, iter};
 
 use once_cell::sync::Lazy;
 use zksync_crypto::hasher::{blake2::Blake2Hasher, Hasher};
+use zksync_types::ProtocolVersionId;
 
 pub(crate) use self::nodes::{InternalNodeCache, MerklePath};
-pub use self::proofs::TreeRangeDigest;
+pub use self::{poseidon2::Poseidon2Hasher, proofs::TreeRangeDigest};
 use crate::{
     metrics::HashingStats,
     types::{TreeEntry, ValueHash, TREE_DEPTH},
+    Database,
 };
 
 mod nodes;
+mod poseidon2;
 mod proofs;
 
+/// First protocol version for which the tree is hashed using [`Poseidon2Hasher`]. `None` means
+/// that Poseidon2 hashing isn't activated for any protocol version yet.
+const POSEIDON2_PROTOCOL_VERSION: Option<ProtocolVersionId> = None;
+
 /// Tree hashing functionality.
 pub trait HashTree: Send + Sync {
     /// Returns the unique name of the hasher. This is used in Merkle tree tags to ensure
@@ -144,6 +151,74 @@ fn compute_empty_tree_hashes() -> Vec<ValueHash> {
     .collect()
 }
 
+/// Hasher used by the domain-specific tree. Allows selecting the hashing algorithm at runtime, e.g.
+/// depending on the protocol version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TreeHasher {
+    /// Blake2s-256 hashing; see [`Blake2Hasher`].
+    #[default]
+    Blake2s,
+    /// Poseidon2 hashing over the Goldilocks field; see [`Poseidon2Hasher`].
+    Poseidon2,
+}
+
+impl TreeHasher {
+    /// Returns the hasher that should be used for the tree at the specified protocol version.
+    pub fn for_protocol_version(protocol_version: ProtocolVersionId) -> Self {
+        match POSEIDON2_PROTOCOL_VERSION {
+            Some(activation_version) if protocol_version >= activation_version => Self::Poseidon2,
+            _ => Self::Blake2s,
+        }
+    }
+
+    /// Returns the hasher with the specified [name](HashTree::name()), or `None` if the name is unknown.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::Blake2s, Self::Poseidon2]
+            .into_iter()
+            .find(|hasher| hasher.name() == name)
+    }
+
+    /// Determines the hasher of the tree stored in `db` based on the tree tags. Returns `None` if the tree
+    /// doesn't have tags (e.g., if it is new).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the hasher specified in the tags is unknown.
+    pub fn from_db(db: &impl Database) -> anyhow::Result<Option<Self>> {
+        let Some(tags) = db.manifest().and_then(|manifest| manifest.tags) else {
+            return Ok(None);
+        };
+        let hasher = Self::from_name(&tags.hasher)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported tree hasher `{}`", tags.hasher))?;
+        Ok(Some(hasher))
+    }
+
+    fn as_dyn(self) -> &'static dyn HashTree {
+        match self {
+            Self::Blake2s => &Blake2Hasher,
+            Self::Poseidon2 => &Poseidon2Hasher,
+        }
+    }
+}
+
+impl HashTree for TreeHasher {
+    fn name(&self) -> &'static str {
+        self.as_dyn().name()
+    }
+
+    fn hash_leaf(&self, value_hash: &ValueHash, leaf_index: u64) -> ValueHash {
+        self.as_dyn().hash_leaf(value_hash, leaf_index)
+    }
+
+    fn hash_branch(&self, lhs: &ValueHash, rhs: &ValueHash) -> ValueHash {
+        self.as_dyn().hash_branch(lhs, rhs)
+    }
+
+    fn empty_subtree_hash(&self, depth: usize) -> ValueHash {
+        self.as_dyn().empty_subtree_hash(depth)
+    }
+}
+
 /// Hasher that keeps track of hashing metrics.
 ///
 /// On drop, the metrics are merged into `shared_stats` (if present). Such roundabout handling
@@ -281,4 +356,30 @@ mod tests {
         let folded_hash = hasher.inner.fold_merkle_path(&merkle_path, leaf.into());
         assert_eq!(folded_hash, expected_hash);
     }
+
+    #[test]
+    fn selecting_tree_hasher() {
+        for hasher in [TreeHasher::Blake2s, TreeHasher::Poseidon2] {
+            assert_eq!(TreeHasher::from_name(hasher.name()), Some(hasher));
+        }
+        assert_eq!(TreeHasher::from_name("no_op256"), None);
+        assert_eq!(
+            TreeHasher::Blake2s.empty_tree_hash(),
+            Blake2Hasher.empty_tree_hash()
+        );
+
+        let expected_hasher = if POSEIDON2_PROTOCOL_VERSION.is_some() {
+            TreeHasher::Poseidon2
+        } else {
+            TreeHasher::Blake2s
+        };
+        assert_eq!(
+            TreeHasher::for_protocol_version(ProtocolVersionId::latest()),
+            expected_hasher
+        );
+        assert_eq!(
+            TreeHasher::for_protocol_version(ProtocolVersionId::Version0),
+            TreeHasher::Blake2s
+        );
+    }
 }
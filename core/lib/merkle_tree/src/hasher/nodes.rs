@@ -174,6 +174,15 @@ impl InternalNode {
         Self::hash_inner(self.child_hashes(), hasher, level, None)
     }
 
+    /// Hashes this node using the provided child hashes instead of the ones stored in child refs.
+    pub(crate) fn hash_with_child_hashes(
+        child_hashes: [Option<ValueHash>; Self::CHILD_COUNT as usize],
+        hasher: &mut HasherWithStats<'_>,
+        level: usize,
+    ) -> ValueHash {
+        Self::hash_inner(child_hashes, hasher, level, None)
+    }
+
     pub(crate) fn updater<'s, 'h>(
         &'s mut self,
         hasher: &'s mut HasherWithStats<'h>,
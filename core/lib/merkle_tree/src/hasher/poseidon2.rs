@@ -0,0 +1,147 @@
+//! Poseidon2 tree hasher over the Goldilocks field.
+
+use std::iter;
+
+use boojum::{
+    algebraic_props::{round_function::AbsorptionModeOverwrite, sponge::GoldilocksPoseidon2Sponge},
+    cs::oracle::TreeHasher as _,
+    field::{goldilocks::GoldilocksField, U64Representable},
+};
+use once_cell::sync::Lazy;
+
+use crate::{
+    hasher::HashTree,
+    types::{ValueHash, TREE_DEPTH},
+};
+
+type Sponge = GoldilocksPoseidon2Sponge<AbsorptionModeOverwrite>;
+
+/// Number of field elements in a hash digest.
+const DIGEST_ELEMENTS: usize = 4;
+
+/// Tree hasher based on the Poseidon2 permutation over the Goldilocks field. Compared to [`Blake2Hasher`],
+/// it is significantly cheaper to verify in circuits.
+///
+/// Hashes are represented as 4 canonical field elements, each serialized as 8 big-endian bytes.
+/// Leaves are hashed as `hash(leaf_index_limbs ++ value_hash_limbs)`, where `leaf_index` is split
+/// into 2 and `value_hash` into 8 big-endian 32-bit limbs, so that any input is a valid field element.
+///
+/// [`Blake2Hasher`]: zksync_crypto::hasher::blake2::Blake2Hasher
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Poseidon2Hasher;
+
+impl Poseidon2Hasher {
+    fn limbs(bytes: &[u8]) -> impl Iterator<Item = GoldilocksField> + '_ {
+        bytes.chunks_exact(4).map(|chunk| {
+            let limb = u32::from_be_bytes(chunk.try_into().unwrap());
+            GoldilocksField::from_u64_unchecked(limb.into())
+        })
+    }
+
+    fn hash_to_elements(hash: &ValueHash) -> [GoldilocksField; DIGEST_ELEMENTS] {
+        let mut elements = [GoldilocksField::from_u64_unchecked(0); DIGEST_ELEMENTS];
+        for (element, chunk) in elements.iter_mut().zip(hash.as_bytes().chunks_exact(8)) {
+            let value = u64::from_be_bytes(chunk.try_into().unwrap());
+            *element = GoldilocksField::from_u64_with_reduction(value);
+        }
+        elements
+    }
+
+    fn elements_to_hash(elements: &[GoldilocksField; DIGEST_ELEMENTS]) -> ValueHash {
+        let mut hash = ValueHash::zero();
+        for (chunk, element) in hash.as_bytes_mut().chunks_exact_mut(8).zip(elements) {
+            chunk.copy_from_slice(&element.as_u64_reduced().to_be_bytes());
+        }
+        hash
+    }
+}
+
+impl HashTree for Poseidon2Hasher {
+    fn name(&self) -> &'static str {
+        "poseidon2_goldilocks"
+    }
+
+    fn hash_leaf(&self, value_hash: &ValueHash, leaf_index: u64) -> ValueHash {
+        let leaf_index_bytes = leaf_index.to_be_bytes();
+        let input: Vec<_> = Self::limbs(&leaf_index_bytes)
+            .chain(Self::limbs(value_hash.as_bytes()))
+            .collect();
+        let digest = Sponge::hash_into_leaf(&input);
+        Self::elements_to_hash(&digest)
+    }
+
+    fn hash_branch(&self, lhs: &ValueHash, rhs: &ValueHash) -> ValueHash {
+        let lhs = Self::hash_to_elements(lhs);
+        let rhs = Self::hash_to_elements(rhs);
+        // The depth is not used by the Poseidon2 sponge, so we don't need to track it.
+        let digest = Sponge::hash_into_node(&lhs, &rhs, 0);
+        Self::elements_to_hash(&digest)
+    }
+
+    fn empty_subtree_hash(&self, depth: usize) -> ValueHash {
+        static EMPTY_TREE_HASHES: Lazy<Vec<ValueHash>> = Lazy::new(|| {
+            let empty_leaf_hash = Poseidon2Hasher.hash_leaf(&ValueHash::zero(), 0);
+            iter::successors(Some(empty_leaf_hash), |hash| {
+                Some(Poseidon2Hasher.hash_branch(hash, hash))
+            })
+            .take(TREE_DEPTH + 1)
+            .collect()
+        });
+        EMPTY_TREE_HASHES[depth]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use zksync_crypto::hasher::blake2::Blake2Hasher;
+
+    use super::*;
+
+    #[test]
+    fn hash_serialization_roundtrip() {
+        let hash = Poseidon2Hasher.hash_leaf(&ValueHash::repeat_byte(0xff), u64::MAX);
+        let elements = Poseidon2Hasher::hash_to_elements(&hash);
+        assert_eq!(Poseidon2Hasher::elements_to_hash(&elements), hash);
+    }
+
+    #[test]
+    fn leaf_hashing_depends_on_all_inputs() {
+        let value_hash = ValueHash::repeat_byte(1);
+        let hashes: HashSet<_> = [
+            Poseidon2Hasher.hash_leaf(&value_hash, 1),
+            Poseidon2Hasher.hash_leaf(&value_hash, 2),
+            Poseidon2Hasher.hash_leaf(&value_hash, 1 << 32),
+            Poseidon2Hasher.hash_leaf(&ValueHash::repeat_byte(2), 1),
+            Poseidon2Hasher.hash_leaf(&ValueHash::zero(), 0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(hashes.len(), 5);
+    }
+
+    #[test]
+    fn branch_hashing_is_not_commutative() {
+        let lhs = Poseidon2Hasher.hash_leaf(&ValueHash::repeat_byte(1), 1);
+        let rhs = Poseidon2Hasher.hash_leaf(&ValueHash::repeat_byte(2), 2);
+        assert_ne!(
+            Poseidon2Hasher.hash_branch(&lhs, &rhs),
+            Poseidon2Hasher.hash_branch(&rhs, &lhs)
+        );
+    }
+
+    #[test]
+    fn empty_tree_hash_differs_from_blake2() {
+        let hasher: &dyn HashTree = &Poseidon2Hasher;
+        let empty_tree_hash = hasher.empty_tree_hash();
+        assert_ne!(empty_tree_hash, Blake2Hasher.empty_tree_hash());
+        assert_eq!(
+            empty_tree_hash,
+            Poseidon2Hasher.hash_branch(
+                &hasher.empty_subtree_hash(TREE_DEPTH - 1),
+                &hasher.empty_subtree_hash(TREE_DEPTH - 1)
+            )
+        );
+    }
+}
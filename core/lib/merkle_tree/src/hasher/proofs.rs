@@ -1,14 +1,14 @@
 //! Merkle proof-related hashing logic.
 
-use std::mem;
+use std::{mem, ops, slice};
 
-use anyhow::ensure;
+use anyhow::{ensure, Context as _};
 
 use crate::{
     hasher::{HashTree, HasherWithStats},
     types::{
         BlockOutputWithProofs, Key, LeafNode, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, ValueHash, TREE_DEPTH,
+        TreeLogEntry, TreeMultiProof, TreeRangeProof, ValueHash, TREE_DEPTH,
     },
     utils,
 };
@@ -96,6 +96,236 @@ impl TreeEntryWithProof {
     }
 }
 
+impl TreeMultiProof {
+    /// Combines proofs for separate entries. Entries must be ordered by increasing key.
+    pub(crate) fn new(hasher: &dyn HashTree, entries: Vec<TreeEntryWithProof>) -> Self {
+        let full_paths: Vec<Vec<_>> = entries
+            .iter()
+            .map(|entry| hasher.extend_merkle_path(&entry.merkle_path).collect())
+            .collect();
+        let mut this = Self {
+            entries: entries.into_iter().map(|entry| entry.base).collect(),
+            merkle_paths: Vec::with_capacity(full_paths.len()),
+            shared_hashes: vec![],
+        };
+        if !this.entries.is_empty() {
+            this.collect_hashes(hasher, &full_paths, 0..full_paths.len(), TREE_DEPTH);
+        }
+        this
+    }
+
+    /// Returns the index of the first entry in the right child of a subtree (i.e., one having
+    /// the `depth` bit set), or `entries.len()` if there are no such entries.
+    fn split_point(entries: &[TreeEntry], depth: usize) -> usize {
+        entries.partition_point(|entry| !entry.key.bit(depth))
+    }
+
+    fn collect_hashes(
+        &mut self,
+        hasher: &dyn HashTree,
+        full_paths: &[Vec<ValueHash>],
+        range: ops::Range<usize>,
+        height: usize,
+    ) {
+        if range.len() == 1 {
+            let path = &full_paths[range.start][..height];
+            let empty_hash_count = path
+                .iter()
+                .enumerate()
+                .take_while(|&(depth, hash)| *hash == hasher.empty_subtree_hash(depth))
+                .count();
+            self.merkle_paths.push(path[empty_hash_count..].to_vec());
+            return;
+        }
+
+        // `height > 0` since entry keys are distinct.
+        let depth = height - 1;
+        let split = range.start + Self::split_point(&self.entries[range.clone()], depth);
+        if split == range.start || split == range.end {
+            // All entries are in the same child subtree; the adjacent hash is the same for all of them.
+            self.shared_hashes.push(full_paths[range.start][depth]);
+            self.collect_hashes(hasher, full_paths, range, depth);
+        } else {
+            self.collect_hashes(hasher, full_paths, range.start..split, depth);
+            self.collect_hashes(hasher, full_paths, split..range.end, depth);
+        }
+    }
+
+    /// Verifies this proof.
+    ///
+    /// # Errors
+    ///
+    /// As the errors are not actionable, a string error with the failing condition is returned.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.entries.len() == self.merkle_paths.len(),
+            "Condition failed: `entries.len() == merkle_paths.len()` ({} vs {})",
+            self.entries.len(),
+            self.merkle_paths.len()
+        );
+        for window in self.entries.windows(2) {
+            ensure!(
+                window[0].key < window[1].key,
+                "Entries are not ordered by increasing key: {:0>64x} >= {:0>64x}",
+                window[0].key,
+                window[1].key
+            );
+        }
+        for entry in &self.entries {
+            ensure!(
+                entry.leaf_index != 0 || entry.value.is_zero(),
+                "Invalid missing value specification for key {:0>64x}: leaf index is zero, but value is non-default",
+                entry.key
+            );
+        }
+        if self.entries.is_empty() {
+            ensure!(self.shared_hashes.is_empty());
+            return Ok(());
+        }
+
+        let mut merkle_paths = self.merkle_paths.iter();
+        let mut shared_hashes = self.shared_hashes.iter();
+        let root_hash = Self::fold(
+            hasher,
+            &self.entries,
+            TREE_DEPTH,
+            &mut merkle_paths,
+            &mut shared_hashes,
+        )?;
+        ensure!(
+            shared_hashes.next().is_none(),
+            "Proof contains superfluous shared hashes"
+        );
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: {root_hash:?} vs {trusted_root_hash:?}"
+        );
+        Ok(())
+    }
+
+    /// Computes the hash of a subtree with the specified `height` containing `entries`.
+    fn fold(
+        hasher: &dyn HashTree,
+        entries: &[TreeEntry],
+        height: usize,
+        merkle_paths: &mut slice::Iter<'_, Vec<ValueHash>>,
+        shared_hashes: &mut slice::Iter<'_, ValueHash>,
+    ) -> anyhow::Result<ValueHash> {
+        if let [entry] = entries {
+            let path = merkle_paths.next().context("not enough Merkle paths")?;
+            ensure!(
+                path.len() <= height,
+                "Merkle path for key {:0>64x} is too long: {} hashes, while at most {height} are expected",
+                entry.key,
+                path.len()
+            );
+            let empty_hashes =
+                (0..height - path.len()).map(|depth| hasher.empty_subtree_hash(depth));
+            let full_path = empty_hashes.chain(path.iter().copied());
+            let mut hash = hasher.hash_leaf(&entry.value, entry.leaf_index);
+            for (depth, adjacent_hash) in full_path.enumerate() {
+                hash = if entry.key.bit(depth) {
+                    hasher.hash_branch(&adjacent_hash, &hash)
+                } else {
+                    hasher.hash_branch(&hash, &adjacent_hash)
+                };
+            }
+            return Ok(hash);
+        }
+
+        // `height > 0` since entry keys are distinct (checked before folding).
+        let depth = height - 1;
+        let split = Self::split_point(entries, depth);
+        Ok(if split == 0 || split == entries.len() {
+            let adjacent_hash = *shared_hashes.next().context("not enough shared hashes")?;
+            let hash = Self::fold(hasher, entries, depth, merkle_paths, shared_hashes)?;
+            if split == 0 {
+                hasher.hash_branch(&adjacent_hash, &hash)
+            } else {
+                hasher.hash_branch(&hash, &adjacent_hash)
+            }
+        } else {
+            let left_hash = Self::fold(
+                hasher,
+                &entries[..split],
+                depth,
+                merkle_paths,
+                shared_hashes,
+            )?;
+            let right_hash = Self::fold(
+                hasher,
+                &entries[split..],
+                depth,
+                merkle_paths,
+                shared_hashes,
+            )?;
+            hasher.hash_branch(&left_hash, &right_hash)
+        })
+    }
+}
+
+impl TreeRangeProof {
+    /// Verifies this proof.
+    ///
+    /// # Errors
+    ///
+    /// As the errors are not actionable, a string error with the failing condition is returned.
+    pub fn verify(
+        &self,
+        hasher: &dyn HashTree,
+        trusted_root_hash: ValueHash,
+    ) -> anyhow::Result<()> {
+        let (start_key, end_key) = (self.start.base.key, self.end.base.key);
+        ensure!(
+            start_key <= end_key,
+            "Condition failed: `start_key <= end_key` ({start_key:0>64x} vs {end_key:0>64x})"
+        );
+        for boundary in [&self.start, &self.end] {
+            ensure!(boundary.merkle_path.len() <= TREE_DEPTH);
+            ensure!(
+                boundary.base.leaf_index != 0 || boundary.base.value.is_zero(),
+                "Invalid missing value specification for key {:0>64x}: leaf index is zero, but value is non-default",
+                boundary.base.key
+            );
+        }
+
+        let mut prev_key = start_key;
+        for entry in &self.entries {
+            ensure!(
+                !entry.is_empty(),
+                "Empty entry for key {:0>64x} inside the range",
+                entry.key
+            );
+            ensure!(
+                entry.key > prev_key && entry.key < end_key,
+                "Entries are not ordered by increasing key or are not inside the range: {:0>64x}",
+                entry.key
+            );
+            prev_key = entry.key;
+        }
+
+        let root_hash = if start_key == end_key {
+            ensure!(self.entries.is_empty() && self.start.base == self.end.base);
+            hasher.fold_merkle_path(&self.start.merkle_path, self.start.base)
+        } else {
+            let mut digest = TreeRangeDigest::new(hasher, start_key, &self.start);
+            for &entry in &self.entries {
+                digest.update(entry);
+            }
+            digest.finalize(&self.end)
+        };
+        ensure!(
+            root_hash == trusted_root_hash,
+            "Root hash mismatch: {root_hash:?} vs {trusted_root_hash:?}"
+        );
+        Ok(())
+    }
+}
+
 /// Range digest in a Merkle tree allowing to compute its root hash based on the provided entries.
 ///
 /// - The entries must be ordered by key. I.e., the first entry must have the numerically smallest key,
@@ -10,6 +10,8 @@
 //!
 //! - [`RocksDBWrapper`] is a wrapper around RocksDB
 //! - [`PatchSet`] is an in-memory implementation useful for testing / benchmarking
+//! - [`InMemoryDatabase`] is a shareable in-memory implementation mirroring RocksDB semantics; it is useful
+//!   for testing components depending on the tree
 //! - [`Patched`] is a wrapper combining the persistent backend and a [`PatchSet`]. It's used
 //!   in `ZkSyncTree` to accumulate changes before flushing them to RocksDB.
 //!
@@ -17,6 +19,8 @@
 //! implementations:
 //!
 //! - [`Blake2Hasher`] is the main implementation based on Blake2s-256
+//! - [`Poseidon2Hasher`] is based on Poseidon2 over the Goldilocks field; it is cheaper to verify in circuits
+//! - [`TreeHasher`] allows selecting one of the above hashers at runtime (e.g., based on the protocol version)
 //! - `()` provides a no-op implementation useful for benchmarking.
 //!
 //! # Tree hashing specification
@@ -50,15 +54,16 @@ use zksync_crypto::hasher::blake2::Blake2Hasher;
 
 pub use crate::{
     errors::NoVersionError,
-    hasher::{HashTree, TreeRangeDigest},
+    hasher::{HashTree, Poseidon2Hasher, TreeHasher, TreeRangeDigest},
     pruning::{MerkleTreePruner, MerkleTreePrunerHandle},
     storage::{
-        Database, MerkleTreeColumnFamily, PatchSet, Patched, PruneDatabase, PrunePatchSet,
-        RocksDBWrapper,
+        Database, InMemoryDatabase, MerkleTreeColumnFamily, PatchSet, Patched, PruneDatabase,
+        PrunePatchSet, RocksDBWrapper,
     },
     types::{
-        BlockOutput, BlockOutputWithProofs, Key, TreeEntry, TreeEntryWithProof, TreeInstruction,
-        TreeLogEntry, TreeLogEntryWithProof, ValueHash,
+        BlockOutput, BlockOutputWithProofs, Key, TreeDiff, TreeEntry, TreeEntryDiff,
+        TreeEntryWithProof, TreeInstruction, TreeLogEntry, TreeLogEntryWithProof, TreeMultiProof,
+        TreeRangeProof, ValueHash,
     },
 };
 use crate::{storage::Storage, types::Root};
@@ -71,6 +76,7 @@ mod hasher;
 mod metrics;
 mod pruning;
 pub mod recovery;
+mod rehashing;
 mod storage;
 mod types;
 mod utils;
@@ -142,13 +148,6 @@ impl<DB: Database> MerkleTree<DB> {
     pub fn new(db: DB) -> anyhow::Result<Self> {
         Self::with_hasher(db, Blake2Hasher)
     }
-
-    pub(crate) fn new_unchecked(db: DB) -> Self {
-        Self {
-            db,
-            hasher: Blake2Hasher,
-        }
-    }
 }
 
 impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
@@ -169,6 +168,10 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
         Ok(Self { db, hasher })
     }
 
+    pub(crate) fn with_hasher_unchecked(db: DB, hasher: H) -> Self {
+        Self { db, hasher }
+    }
+
     /// Returns the root hash of a tree at the specified `version`, or `None` if the version
     /// was not written yet.
     pub fn root_hash(&self, version: u64) -> Option<ValueHash> {
@@ -258,7 +261,7 @@ impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
     }
 }
 
-impl<DB: PruneDatabase> MerkleTree<DB> {
+impl<DB: PruneDatabase, H: HashTree> MerkleTree<DB, H> {
     /// Returns the first retained version of the tree.
     pub fn first_retained_version(&self) -> Option<u64> {
         match self.db.min_stale_key_version() {
@@ -10,7 +10,7 @@ use vise::{
     Buckets, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Global, Histogram, Metrics, Unit,
 };
 
-use crate::types::Nibbles;
+use crate::types::{InternalNode, Nibbles};
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "merkle_tree")]
@@ -62,6 +62,7 @@ impl HashingStats {
 
 const NODE_COUNT_BUCKETS: Buckets = Buckets::linear(1_000.0..=10_000.0, 1_000.0);
 const LEAF_LEVEL_BUCKETS: Buckets = Buckets::linear(20.0..=40.0, 4.0);
+const CHILD_COUNT_BUCKETS: Buckets = Buckets::linear(1.0..=16.0, 1.0);
 
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "merkle_tree_extend_patch")]
@@ -85,6 +86,10 @@ struct TreeUpdateMetrics {
     /// Maximum level of leaves moved or created while processing a single batch.
     #[metrics(buckets = LEAF_LEVEL_BUCKETS)]
     max_leaf_level: Histogram<u64>,
+    /// Number of leaves moved or created while processing a single batch, grouped by the leaf nibble count.
+    /// Only non-zero counts are reported.
+    #[metrics(buckets = NODE_COUNT_BUCKETS)]
+    leaves_by_nibble_count: Family<LeafNibbleCount, Histogram<u64>>,
 
     // Metrics related to input instructions
     /// Number of keys read while processing a single batch (only applicable to the full operation mode).
@@ -97,6 +102,11 @@ struct TreeUpdateMetrics {
     /// Number of nodes of previous versions read from the DB while processing a single batch.
     #[metrics(buckets = NODE_COUNT_BUCKETS)]
     db_reads: Histogram<u64>,
+    /// Number of nodes of previous versions read from the DB while processing a single batch, grouped
+    /// by the key nibble count. Upper levels are read for almost every batch, so they are a good fit
+    /// for the RocksDB block cache.
+    #[metrics(buckets = NODE_COUNT_BUCKETS)]
+    db_reads_by_nibble_count: Family<NibbleCount, Histogram<u64>>,
     /// Number of nodes of the current version re-read from the patch set while processing a single batch.
     #[metrics(buckets = NODE_COUNT_BUCKETS)]
     patch_reads: Histogram<u64>,
@@ -114,9 +124,11 @@ pub(crate) struct TreeUpdaterStats {
     pub updated_leaves: u64,
     pub leaf_level_sum: u64,
     pub max_leaf_level: u64,
+    pub leaves_by_nibble_count: [u64; MAX_TRACKED_LEAF_NIBBLE_COUNT + 1],
     pub key_reads: u64,
     pub missing_key_reads: u64,
     pub db_reads: u64,
+    pub db_reads_by_nibble_count: DbReadsByNibbleCount,
     pub patch_reads: u64,
 }
 
@@ -130,9 +142,11 @@ impl fmt::Debug for TreeUpdaterStats {
             .field("updated_leaves", &self.updated_leaves)
             .field("avg_leaf_level", &self.avg_leaf_level())
             .field("max_leaf_level", &self.max_leaf_level)
+            .field("leaves_by_nibble_count", &self.leaves_by_nibble_count)
             .field("key_reads", &self.key_reads)
             .field("missing_key_reads", &self.missing_key_reads)
             .field("db_reads", &self.db_reads)
+            .field("db_reads_by_nibble_count", &self.db_reads_by_nibble_count)
             .field("patch_reads", &self.patch_reads)
             .finish_non_exhaustive()
     }
@@ -143,6 +157,12 @@ impl TreeUpdaterStats {
         let leaf_level = nibble_count as u64 * 4;
         self.leaf_level_sum += leaf_level;
         self.max_leaf_level = self.max_leaf_level.max(leaf_level);
+        self.leaves_by_nibble_count[nibble_count.min(MAX_TRACKED_LEAF_NIBBLE_COUNT)] += 1;
+    }
+
+    pub(crate) fn add_db_reads(&mut self, db_reads: DbReadsByNibbleCount) {
+        self.db_reads += db_reads.total();
+        self.db_reads_by_nibble_count += db_reads;
     }
 
     #[allow(clippy::cast_precision_loss)] // Acceptable for metrics
@@ -163,6 +183,13 @@ impl TreeUpdaterStats {
         metrics.updated_leaves.observe(self.updated_leaves);
         metrics.avg_leaf_level.observe(self.avg_leaf_level());
         metrics.max_leaf_level.observe(self.max_leaf_level);
+        let leaf_counts = self.leaves_by_nibble_count.iter().enumerate();
+        for (nibble_count, &leaf_count) in leaf_counts {
+            if leaf_count > 0 {
+                let label = LeafNibbleCount::new(nibble_count);
+                metrics.leaves_by_nibble_count[&label].observe(leaf_count);
+            }
+        }
 
         if self.key_reads > 0 {
             metrics.key_reads.observe(self.key_reads);
@@ -171,6 +198,10 @@ impl TreeUpdaterStats {
             metrics.key_reads.observe(self.missing_key_reads);
         }
         metrics.db_reads.observe(self.db_reads);
+        for (nibble_count, db_reads) in self.db_reads_by_nibble_count.iter() {
+            let label = NibbleCount::new(nibble_count);
+            metrics.db_reads_by_nibble_count[&label].observe(db_reads);
+        }
         metrics.patch_reads.observe(self.patch_reads);
     }
 }
@@ -183,10 +214,18 @@ impl ops::AddAssign for TreeUpdaterStats {
         self.updated_leaves += rhs.updated_leaves;
         self.leaf_level_sum += rhs.leaf_level_sum;
         self.max_leaf_level = self.max_leaf_level.max(rhs.max_leaf_level);
+        for (count, rhs_count) in self
+            .leaves_by_nibble_count
+            .iter_mut()
+            .zip(rhs.leaves_by_nibble_count)
+        {
+            *count += rhs_count;
+        }
 
         self.key_reads += rhs.key_reads;
         self.missing_key_reads += rhs.missing_key_reads;
         self.db_reads += rhs.db_reads;
+        self.db_reads_by_nibble_count += rhs.db_reads_by_nibble_count;
         self.patch_reads += rhs.patch_reads;
     }
 }
@@ -230,6 +269,55 @@ impl fmt::Display for NibbleCount {
     }
 }
 
+/// Leaves are located deeper in the tree than most internal nodes, so they are tracked with a separate cap.
+const MAX_TRACKED_LEAF_NIBBLE_COUNT: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "nibbles")]
+struct LeafNibbleCount(usize);
+
+impl LeafNibbleCount {
+    fn new(raw_count: usize) -> Self {
+        Self(raw_count.min(MAX_TRACKED_LEAF_NIBBLE_COUNT))
+    }
+}
+
+impl fmt::Display for LeafNibbleCount {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 == MAX_TRACKED_LEAF_NIBBLE_COUNT {
+            write!(formatter, "{MAX_TRACKED_LEAF_NIBBLE_COUNT}..")
+        } else {
+            write!(formatter, "{}", self.0)
+        }
+    }
+}
+
+/// Number of nodes read from the DB, grouped by the key nibble count.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct DbReadsByNibbleCount([u64; MAX_TRACKED_NIBBLE_COUNT + 1]);
+
+impl DbReadsByNibbleCount {
+    pub fn add(&mut self, nibble_count: usize, reads: u64) {
+        self.0[nibble_count.min(MAX_TRACKED_NIBBLE_COUNT)] += reads;
+    }
+
+    pub fn total(&self) -> u64 {
+        self.0.iter().sum()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, u64)> + '_ {
+        self.0.iter().copied().enumerate()
+    }
+}
+
+impl ops::AddAssign for DbReadsByNibbleCount {
+    fn add_assign(&mut self, rhs: Self) {
+        for (reads, rhs_reads) in self.0.iter_mut().zip(rhs.0) {
+            *reads += rhs_reads;
+        }
+    }
+}
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "merkle_tree_apply_patch")]
 struct ApplyPatchMetrics {
@@ -242,6 +330,10 @@ struct ApplyPatchMetrics {
     /// Total byte size of nodes included into a RocksDB patch per batch, grouped by the key nibble count.
     #[metrics(buckets = BYTE_SIZE_BUCKETS)]
     node_bytes: Family<NibbleCount, Histogram<u64>>,
+    /// Average number of children of internal nodes included into a RocksDB patch per batch, grouped
+    /// by the key nibble count. Only reported for levels with internal nodes in the patch.
+    #[metrics(buckets = CHILD_COUNT_BUCKETS)]
+    avg_child_count: Family<NibbleCount, Histogram<f64>>,
     /// Number of hashes in child references copied from previous tree versions. Allows to estimate
     /// the level of redundancy of the tree.
     #[metrics(buckets = NODE_COUNT_BUCKETS)]
@@ -257,6 +349,17 @@ struct PatchNodeStats {
     count: u64,
     /// Total serialized size of nodes (excluding key sizes).
     bytes: u64,
+    /// Number of internal nodes.
+    internal_count: u64,
+    /// Total number of children of internal nodes.
+    child_count: u64,
+}
+
+impl PatchNodeStats {
+    #[allow(clippy::cast_precision_loss)] // Acceptable for metrics
+    fn avg_child_count(&self) -> Option<f64> {
+        (self.internal_count > 0).then(|| self.child_count as f64 / self.internal_count as f64)
+    }
 }
 
 #[must_use = "patch metrics should be `report()`ed"]
@@ -282,6 +385,14 @@ impl ApplyPatchStats {
         stats.bytes += node_bytes.len() as u64;
     }
 
+    pub fn update_internal_node(&mut self, key_nibbles: &Nibbles, node: &InternalNode) {
+        let nibble_count = key_nibbles.nibble_count();
+        let idx = nibble_count.min(MAX_TRACKED_NIBBLE_COUNT);
+        let stats = &mut self.node_stats_by_nibble_count[idx];
+        stats.internal_count += 1;
+        stats.child_count += node.child_count() as u64;
+    }
+
     pub fn report(self) {
         let metrics = &APPLY_PATCH_METRICS;
         let total_node_count = self
@@ -296,6 +407,9 @@ impl ApplyPatchStats {
             let label = NibbleCount::new(nibble_count);
             metrics.nodes_by_nibble_count[&label].observe(stats.count);
             metrics.node_bytes[&label].observe(stats.bytes);
+            if let Some(avg_child_count) = stats.avg_child_count() {
+                metrics.avg_child_count[&label].observe(avg_child_count);
+            }
         }
 
         metrics.copied_hashes.observe(self.copied_hashes);
@@ -355,6 +469,9 @@ pub(crate) struct PruningTimings {
     /// Time spent removing stale keys from RocksDB per pruning iteration.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub apply_patch: Histogram<Duration>,
+    /// Time spent compacting pruned data in RocksDB.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub compact: Histogram<Duration>,
 }
 
 #[vise::register]
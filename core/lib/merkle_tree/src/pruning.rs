@@ -64,9 +64,16 @@ impl MerkleTreePrunerHandle {
 /// by a certain range of tree versions, and removes the corresponding nodes from the tree
 /// (in RocksDB, this uses simple pointwise `delete_cf()` operations). The range of versions
 /// depends on pruning policies; for now, it's passed via the pruner handle.
+///
+/// Pointwise deletions leave tombstones in RocksDB, which are only removed by compaction. Since pruned
+/// nodes occupy a contiguous key range that is never read, background compaction may not reach it
+/// for a long time. Hence, the pruner explicitly compacts pruned data once enough keys are pruned;
+/// see [`Self::set_compaction_threshold()`].
 pub struct MerkleTreePruner<DB> {
     db: DB,
     target_pruned_key_count: usize,
+    compaction_threshold: Option<usize>,
+    keys_pruned_since_compaction: usize,
     poll_interval: Duration,
     aborted_receiver: mpsc::Receiver<()>,
     target_retained_version: Arc<AtomicU64>,
@@ -77,6 +84,7 @@ impl<DB> fmt::Debug for MerkleTreePruner<DB> {
         formatter
             .debug_struct("MerkleTreePruner")
             .field("target_pruned_key_count", &self.target_pruned_key_count)
+            .field("compaction_threshold", &self.compaction_threshold)
             .field("poll_interval", &self.poll_interval)
             .field("target_retained_version", &self.target_retained_version)
             .finish_non_exhaustive()
@@ -99,6 +107,8 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         let this = Self {
             db,
             target_pruned_key_count: 500_000,
+            compaction_threshold: Some(5_000_000),
+            keys_pruned_since_compaction: 0,
             poll_interval: Duration::from_secs(60),
             aborted_receiver,
             target_retained_version,
@@ -115,6 +125,15 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
         self.target_pruned_key_count = count;
     }
 
+    /// Sets the number of pruned keys after which pruned data is compacted. Compaction is a blocking
+    /// and I/O-heavy operation, so it shouldn't be performed too often. `None` disables explicit compaction,
+    /// leaving it to RocksDB background jobs.
+    ///
+    /// The default value is 5M keys.
+    pub fn set_compaction_threshold(&mut self, threshold: Option<usize>) {
+        self.compaction_threshold = threshold;
+    }
+
     /// Sets the sleep duration when the pruner cannot progress. This time should be enough
     /// for the tree to produce enough stale keys.
     ///
@@ -183,13 +202,36 @@ impl<DB: PruneDatabase> MerkleTreePruner<DB> {
             pruned_key_count: pruned_keys.len(),
             deleted_stale_key_versions: deleted_stale_key_versions.clone(),
         };
-        let patch = PrunePatchSet::new(pruned_keys, deleted_stale_key_versions);
+        let patch = PrunePatchSet::new(pruned_keys, deleted_stale_key_versions.clone());
         let apply_patch_latency = PRUNING_TIMINGS.apply_patch.start();
         self.db.prune(patch)?;
         apply_patch_latency.observe();
+
+        self.keys_pruned_since_compaction += stats.pruned_key_count;
+        // All pruned nodes have versions less than the max new version of deleted stale keys.
+        self.compact_pruned_data_if_necessary(deleted_stale_key_versions.end);
         Ok(Some(stats))
     }
 
+    fn compact_pruned_data_if_necessary(&mut self, end_version: u64) {
+        let Some(threshold) = self.compaction_threshold else {
+            return;
+        };
+        if self.keys_pruned_since_compaction < threshold {
+            return;
+        }
+
+        tracing::info!(
+            "Pruned {} keys since last compaction; compacting pruned data with versions < {end_version}",
+            self.keys_pruned_since_compaction
+        );
+        let compact_latency = PRUNING_TIMINGS.compact.start();
+        self.db.compact_pruned_data(end_version);
+        let compact_latency = compact_latency.observe();
+        tracing::info!("Compacted pruned data in {compact_latency:?}");
+        self.keys_pruned_since_compaction = 0;
+    }
+
     fn wait_for_abort(&mut self, timeout: Duration) -> bool {
         match self.aborted_receiver.recv_timeout(timeout) {
             Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => true,
@@ -248,7 +290,7 @@ mod tests {
     use super::*;
     use crate::{
         types::{Node, NodeKey},
-        Database, Key, MerkleTree, PatchSet, TreeEntry, ValueHash,
+        Database, Key, MerkleTree, PatchSet, RocksDBWrapper, TreeEntry, ValueHash,
     };
 
     fn create_db() -> PatchSet {
@@ -504,4 +546,37 @@ mod tests {
         println!("Keys are pruned after each update");
         test_keys_are_removed_by_pruning_when_overwritten_in_multiple_batches(true);
     }
+
+    #[test]
+    fn pruned_data_is_compacted() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+        let mut tree = MerkleTree::new(&mut db).unwrap();
+        for chunk in generate_key_value_pairs(0..100).chunks(20) {
+            tree.extend(chunk.to_vec()).unwrap();
+        }
+        let latest_version = tree.latest_version().unwrap();
+
+        let (mut pruner, _handle) = MerkleTreePruner::new(&mut db);
+        pruner.set_target_pruned_key_count(1);
+        pruner.set_compaction_threshold(Some(1));
+        let stats = pruner
+            .prune_up_to(latest_version)
+            .unwrap()
+            .expect("tree was not pruned");
+        assert!(stats.has_more_work());
+        assert_eq!(pruner.keys_pruned_since_compaction, 0);
+
+        pruner.set_compaction_threshold(None);
+        while let Some(stats) = pruner.prune_up_to(latest_version).unwrap() {
+            assert!(pruner.keys_pruned_since_compaction >= stats.pruned_key_count);
+        }
+        pruner.set_compaction_threshold(Some(1));
+        pruner.compact_pruned_data_if_necessary(latest_version + 1);
+        assert_eq!(pruner.keys_pruned_since_compaction, 0);
+
+        let tree = MerkleTree::new(&mut db).unwrap();
+        assert_eq!(tree.first_retained_version(), Some(latest_version));
+        tree.verify_consistency(latest_version, true).unwrap();
+    }
 }
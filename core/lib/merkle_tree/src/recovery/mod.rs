@@ -136,6 +136,15 @@ impl<DB: PruneDatabase, H: HashTree> MerkleTreeRecovery<DB, H> {
         Ok(output)
     }
 
+    /// Returns custom tags for the tree.
+    pub fn custom_tags(&self) -> HashMap<String, String> {
+        let manifest = self.db.manifest();
+        manifest
+            .and_then(|manifest| manifest.tags)
+            .map(|tags| tags.custom)
+            .unwrap_or_default()
+    }
+
     /// Returns the version of the tree being recovered.
     pub fn recovered_version(&self) -> u64 {
         self.recovered_version
@@ -192,6 +201,17 @@ impl<DB: PruneDatabase, H: HashTree> MerkleTreeRecovery<DB, H> {
 
     /// Extends a tree with a chunk of entries. Unlike [`Self::extend_linear()`], entries may be
     /// ordered in any way you like.
+    pub fn extend_random(&mut self, entries: Vec<TreeEntry>) -> anyhow::Result<()> {
+        self.extend_random_with_custom_tags(entries, |_| {})
+    }
+
+    /// Same as [`Self::extend_random()`], but additionally updates custom tags for the tree using the provided closure.
+    /// Tags are persisted atomically with the chunk, which allows tracking recovery progress reliably
+    /// (e.g., if recovery is interrupted, either both the chunk and the tag update are persisted, or neither is).
+    ///
+    /// # Errors
+    ///
+    /// Propagates database I/O errors.
     #[tracing::instrument(
         level = "debug",
         skip_all,
@@ -200,13 +220,22 @@ impl<DB: PruneDatabase, H: HashTree> MerkleTreeRecovery<DB, H> {
             entries.len = entries.len(),
         ),
     )]
-    pub fn extend_random(&mut self, entries: Vec<TreeEntry>) -> anyhow::Result<()> {
+    pub fn extend_random_with_custom_tags(
+        &mut self,
+        entries: Vec<TreeEntry>,
+        update_tags: impl FnOnce(&mut HashMap<String, String>),
+    ) -> anyhow::Result<()> {
         tracing::debug!("Started extending tree");
         RECOVERY_METRICS.chunk_size.observe(entries.len());
 
         let stage_latency = RECOVERY_METRICS.stage_latency[&RecoveryStage::Extend].start();
         let storage = Storage::new(&self.db, &self.hasher, self.recovered_version, false);
-        let patch = storage.extend_during_random_recovery(entries);
+        let mut patch = storage.extend_during_random_recovery(entries);
+        let tags = patch
+            .manifest_mut()
+            .tags
+            .get_or_insert_with(|| TreeTags::new(&self.hasher));
+        update_tags(&mut tags.custom);
         let stage_latency = stage_latency.observe();
         tracing::debug!("Finished processing keys; took {stage_latency:?}");
 
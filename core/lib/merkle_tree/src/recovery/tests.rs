@@ -54,3 +54,25 @@ fn recovering_tree_with_single_node() {
     );
     tree.verify_consistency(42, true).unwrap();
 }
+
+#[test]
+fn custom_tags_are_updated_with_recovered_chunk() {
+    let mut recovery = MerkleTreeRecovery::new(PatchSet::default(), 42).unwrap();
+    assert!(recovery.custom_tags().is_empty());
+
+    let recovery_entry = TreeEntry::new(Key::from(123), 1, ValueHash::repeat_byte(1));
+    recovery
+        .extend_random_with_custom_tags(vec![recovery_entry], |tags| {
+            tags.insert("chunk.0".to_owned(), "done".to_owned());
+        })
+        .unwrap();
+    let tags = recovery.custom_tags();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags["chunk.0"], "done");
+    assert_eq!(recovery.last_processed_key(), Some(Key::from(123)));
+
+    // Tags must persist after recovery is restarted.
+    let db = recovery.db.join().unwrap();
+    let recovery = MerkleTreeRecovery::new(db, 42).unwrap();
+    assert_eq!(recovery.custom_tags()["chunk.0"], "done");
+}
@@ -0,0 +1,141 @@
+//! Recomputing tree hashes with an alternative hasher. Used to support transitions between hashers.
+
+use rayon::prelude::*;
+
+use crate::{
+    consistency::ConsistencyError,
+    hasher::{HashTree, HasherWithStats},
+    types::{InternalNode, Nibbles, Node, NodeKey, Root},
+    Database, MerkleTree, ValueHash,
+};
+
+impl<DB: Database, H: HashTree> MerkleTree<DB, H> {
+    /// Computes the root hash of the tree at the specified `version` as if the tree was hashed
+    /// with the provided `hasher`. This allows committing to the tree state with both the old
+    /// and the new hasher while migrating the tree to another hasher.
+    ///
+    /// Unlike [`Self::root_hash()`], this method cannot use child hashes stored in the tree,
+    /// so it traverses all tree nodes. Traversal is parallelized using `rayon`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the version doesn't exist, or tree nodes cannot be loaded.
+    pub fn root_hash_with_hasher(
+        &self,
+        version: u64,
+        hasher: &dyn HashTree,
+    ) -> Result<ValueHash, ConsistencyError> {
+        let manifest = self.db.try_manifest()?;
+        let manifest = manifest.ok_or(ConsistencyError::MissingVersion(version))?;
+        if version >= manifest.version_count {
+            return Err(ConsistencyError::MissingVersion(version));
+        }
+
+        let root = self
+            .db
+            .try_root(version)?
+            .ok_or(ConsistencyError::MissingRoot(version))?;
+        let Root::Filled { node, .. } = root else {
+            return Ok(hasher.empty_tree_hash());
+        };
+        let root_key = Nibbles::EMPTY.with_version(version);
+        self.rehash_node(&node, root_key, hasher)
+    }
+
+    fn rehash_node(
+        &self,
+        node: &Node,
+        key: NodeKey,
+        hasher: &dyn HashTree,
+    ) -> Result<ValueHash, ConsistencyError> {
+        let level = key.nibbles.nibble_count() * 4;
+        let node = match node {
+            Node::Leaf(leaf) => return Ok(leaf.hash(&mut HasherWithStats::new(hasher), level)),
+            Node::Internal(node) => node,
+        };
+
+        let children: Vec<_> = node.children().collect();
+        let child_hashes: Vec<_> = children
+            .into_par_iter()
+            .map(|(nibble, child_ref)| {
+                let child_key = key
+                    .nibbles
+                    .push(nibble)
+                    .ok_or(ConsistencyError::TerminalInternalNode { key })?;
+                let child_key = child_key.with_version(child_ref.version);
+                let child = self
+                    .db
+                    .try_tree_node(&child_key, child_ref.is_leaf)?
+                    .ok_or(ConsistencyError::MissingNode {
+                        key: child_key,
+                        is_leaf: child_ref.is_leaf,
+                    })?;
+                let child_hash = self.rehash_node(&child, child_key, hasher)?;
+                Ok((nibble, child_hash))
+            })
+            .collect::<Result<_, ConsistencyError>>()?;
+
+        let mut hashes = [None; InternalNode::CHILD_COUNT as usize];
+        for (nibble, child_hash) in child_hashes {
+            hashes[usize::from(nibble)] = Some(child_hash);
+        }
+        let mut hasher = HasherWithStats::new(hasher);
+        Ok(InternalNode::hash_with_child_hashes(
+            hashes,
+            &mut hasher,
+            level,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use zksync_crypto::hasher::blake2::Blake2Hasher;
+
+    use super::*;
+    use crate::{hasher::Poseidon2Hasher, Key, PatchSet, TreeEntry};
+
+    fn generate_entries(count: u64, start: u64) -> Vec<TreeEntry> {
+        (start..start + count)
+            .map(|i| {
+                let key = Key::from(i) * Key::from(0x_dead_beef_u64) + Key::from(i);
+                TreeEntry::new(key, i + 1, ValueHash::from_low_u64_be(i))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rehashing_empty_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        tree.extend(vec![]).unwrap();
+
+        let hash = tree.root_hash_with_hasher(0, &Poseidon2Hasher).unwrap();
+        assert_eq!(hash, Poseidon2Hasher.empty_tree_hash());
+        let err = tree.root_hash_with_hasher(1, &Poseidon2Hasher).unwrap_err();
+        assert_matches!(err, ConsistencyError::MissingVersion(1));
+    }
+
+    #[test]
+    fn rehashing_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+        let mut poseidon2_tree =
+            MerkleTree::with_hasher(PatchSet::default(), Poseidon2Hasher).unwrap();
+        for version in 0..3 {
+            let entries = generate_entries(100, version * 50);
+            tree.extend(entries.clone()).unwrap();
+            poseidon2_tree.extend(entries).unwrap();
+        }
+
+        for version in 0..3 {
+            let blake2_hash = tree.root_hash_with_hasher(version, &Blake2Hasher).unwrap();
+            assert_eq!(Some(blake2_hash), tree.root_hash(version));
+
+            let poseidon2_hash = tree
+                .root_hash_with_hasher(version, &Poseidon2Hasher)
+                .unwrap();
+            assert_eq!(Some(poseidon2_hash), poseidon2_tree.root_hash(version));
+            assert_ne!(poseidon2_hash, blake2_hash);
+        }
+    }
+}
@@ -400,6 +400,10 @@ pub trait PruneDatabase: Database {
     ///
     /// Propagates database I/O errors.
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()>;
+
+    /// Compacts tree nodes and stale keys with versions less than `end_version` so that the space
+    /// occupied by pruned data is reclaimed.
+    fn compact_pruned_data(&mut self, end_version: u64);
 }
 
 impl<T: PruneDatabase + ?Sized> PruneDatabase for &mut T {
@@ -414,6 +418,10 @@ impl<T: PruneDatabase + ?Sized> PruneDatabase for &mut T {
     fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
         (**self).prune(patch)
     }
+
+    fn compact_pruned_data(&mut self, end_version: u64) {
+        (**self).compact_pruned_data(end_version);
+    }
 }
 
 impl PruneDatabase for PatchSet {
@@ -447,6 +455,10 @@ impl PruneDatabase for PatchSet {
             .retain(|version, _| !patch.deleted_stale_key_versions.contains(version));
         Ok(())
     }
+
+    fn compact_pruned_data(&mut self, _end_version: u64) {
+        // Pruned data is removed from in-memory patches right away
+    }
 }
 
 #[cfg(test)]
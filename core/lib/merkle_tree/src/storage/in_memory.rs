@@ -0,0 +1,183 @@
+//! In-memory tree database.
+
+use std::{
+    any::Any,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use crate::{
+    errors::DeserializeError,
+    storage::{Database, PatchSet, PruneDatabase, PrunePatchSet},
+    types::{Manifest, Node, NodeKey, ProfiledTreeOperation, Root},
+};
+
+/// In-memory [`Database`] implementation with semantics mirroring [`RocksDBWrapper`]. Useful for testing
+/// components depending on the tree (e.g., a [`ZkSyncTree`]) without creating RocksDB instances.
+///
+/// Unlike [`PatchSet`], this database is cheaply cloneable; all clones share the same underlying data, similar
+/// to clones of [`RocksDBWrapper`]. Thus, it can be used to create a tree, its readers and a pruner simultaneously.
+///
+/// [`RocksDBWrapper`]: crate::RocksDBWrapper
+/// [`ZkSyncTree`]: crate::domain::ZkSyncTree
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryDatabase {
+    inner: Arc<RwLock<PatchSet>>,
+}
+
+impl InMemoryDatabase {
+    /// Creates an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, PatchSet> {
+        self.inner
+            .read()
+            .expect("in-memory tree database is poisoned")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, PatchSet> {
+        self.inner
+            .write()
+            .expect("in-memory tree database is poisoned")
+    }
+}
+
+impl Database for InMemoryDatabase {
+    fn try_manifest(&self) -> Result<Option<Manifest>, DeserializeError> {
+        let inner = self.read();
+        // Mimic RocksDB, which doesn't have a manifest until the first patch is applied.
+        let is_empty = inner.patches_by_version.is_empty() && inner.manifest.version_count == 0;
+        Ok((!is_empty).then(|| inner.manifest.clone()))
+    }
+
+    fn try_root(&self, version: u64) -> Result<Option<Root>, DeserializeError> {
+        self.read().try_root(version)
+    }
+
+    fn try_tree_node(
+        &self,
+        key: &NodeKey,
+        is_leaf: bool,
+    ) -> Result<Option<Node>, DeserializeError> {
+        self.read().try_tree_node(key, is_leaf)
+    }
+
+    fn start_profiling(&self, _operation: ProfiledTreeOperation) -> Box<dyn Any> {
+        Box::new(()) // no stats are collected
+    }
+
+    fn apply_patch(&mut self, patch: PatchSet) -> anyhow::Result<()> {
+        let mut inner = self.write();
+        let new_version_count = patch.manifest.version_count;
+        inner.manifest = patch.manifest;
+        // Versions truncated from the tree are inaccessible via the tree API, so we can remove them right away.
+        inner
+            .patches_by_version
+            .retain(|&version, _| version < new_version_count);
+
+        for (version, sub_patch) in patch.patches_by_version {
+            let is_update = patch.updated_version == Some(version);
+            if is_update {
+                if let Some(existing_patch) = inner.patches_by_version.get_mut(&version) {
+                    existing_patch.merge(sub_patch);
+                    continue;
+                }
+            }
+            // Like with RocksDB, a new version completely overwrites the previously stored version data.
+            inner.patches_by_version.insert(version, sub_patch);
+        }
+
+        for (version, stale_keys) in patch.stale_keys_by_version {
+            inner
+                .stale_keys_by_version
+                .entry(version)
+                .or_default()
+                .extend(stale_keys);
+        }
+        Ok(())
+    }
+}
+
+impl PruneDatabase for InMemoryDatabase {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        self.read().min_stale_key_version()
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        self.read().stale_keys(version)
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        self.write().prune(patch)
+    }
+
+    fn compact_pruned_data(&mut self, _end_version: u64) {
+        // Pruned data is removed from memory right away
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Key, MerkleTree, MerkleTreePruner, TreeEntry, ValueHash};
+
+    fn generate_entries(version: u64) -> Vec<TreeEntry> {
+        (0_u64..10)
+            .map(|i| {
+                let key = Key::from(i) << 200;
+                TreeEntry::new(key, i + 1, ValueHash::from_low_u64_be(version))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn in_memory_database_mirrors_rocksdb() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let rocksdb = crate::RocksDBWrapper::new(temp_dir.path()).unwrap();
+        let in_memory_db = InMemoryDatabase::new();
+        assert!(rocksdb.manifest().is_none());
+        assert!(in_memory_db.manifest().is_none());
+
+        let mut rocksdb_tree = MerkleTree::new(rocksdb).unwrap();
+        let mut in_memory_tree = MerkleTree::new(in_memory_db.clone()).unwrap();
+        for version in 0..5 {
+            let rocksdb_output = rocksdb_tree.extend(generate_entries(version)).unwrap();
+            let in_memory_output = in_memory_tree.extend(generate_entries(version)).unwrap();
+            assert_eq!(rocksdb_output, in_memory_output);
+        }
+
+        rocksdb_tree.truncate_recent_versions(3).unwrap();
+        in_memory_tree.truncate_recent_versions(3).unwrap();
+        assert_eq!(in_memory_tree.latest_version(), Some(2));
+        assert!(in_memory_db.root(3).is_none());
+        let rocksdb_output = rocksdb_tree.extend(generate_entries(10)).unwrap();
+        let in_memory_output = in_memory_tree.extend(generate_entries(10)).unwrap();
+        assert_eq!(rocksdb_output, in_memory_output);
+
+        // Clones share data with the original database.
+        let reader = MerkleTree::new(in_memory_db.clone()).unwrap();
+        assert_eq!(reader.latest_version(), Some(3));
+        assert_eq!(reader.latest_root_hash(), rocksdb_tree.latest_root_hash());
+        reader.verify_consistency(3, true).unwrap();
+    }
+
+    #[test]
+    fn pruning_in_memory_database() {
+        let db = InMemoryDatabase::new();
+        let mut tree = MerkleTree::new(db.clone()).unwrap();
+        for version in 0..5 {
+            tree.extend(generate_entries(version)).unwrap();
+        }
+        assert_eq!(tree.first_retained_version(), Some(0));
+
+        let (mut pruner, _handle) = MerkleTreePruner::new(db.clone());
+        while pruner.prune_up_to(4).unwrap().is_some() {
+            // Continue pruning
+        }
+
+        assert_eq!(tree.first_retained_version(), Some(4));
+        assert!(db.root(3).is_none());
+        tree.verify_consistency(4, true).unwrap();
+    }
+}
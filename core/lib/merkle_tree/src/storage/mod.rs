@@ -2,6 +2,7 @@
 
 pub use self::{
     database::{Database, NodeKeys, Patched, PruneDatabase, PrunePatchSet},
+    in_memory::InMemoryDatabase,
     parallel::PersistenceThreadHandle,
     patch::PatchSet,
     rocksdb::{MerkleTreeColumnFamily, RocksDBWrapper},
@@ -20,6 +21,7 @@ use crate::{
 };
 
 mod database;
+mod in_memory;
 mod parallel;
 mod patch;
 mod proofs;
@@ -100,14 +102,14 @@ impl TreeUpdater {
             db_reads,
         } = self.patch_set.load_ancestors(sorted_keys, db);
 
-        self.metrics.db_reads += db_reads;
+        self.metrics.add_db_reads(db_reads);
         longest_prefixes
     }
 
     /// Loads the greatest key from the database.
     fn load_greatest_key<DB: Database + ?Sized>(&mut self, db: &DB) -> Option<(LeafNode, Nibbles)> {
         let (leaf, load_result) = self.patch_set.load_greatest_key(db)?;
-        self.metrics.db_reads += load_result.db_reads;
+        self.metrics.add_db_reads(load_result.db_reads);
         assert_eq!(load_result.longest_prefixes.len(), 1);
         Some((leaf, load_result.longest_prefixes[0]))
     }
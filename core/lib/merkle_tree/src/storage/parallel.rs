@@ -375,6 +375,10 @@ impl<DB: PruneDatabase> PruneDatabase for ParallelDatabase<DB> {
             .context("failed synchronizing database before pruning")?;
         self.inner.prune(patch)
     }
+
+    fn compact_pruned_data(&mut self, end_version: u64) {
+        self.inner.compact_pruned_data(end_version);
+    }
 }
 
 /// Database with either sequential or parallel persistence.
@@ -479,6 +483,13 @@ impl<DB: PruneDatabase> PruneDatabase for MaybeParallel<DB> {
             Self::Parallel(db) => db.prune(patch),
         }
     }
+
+    fn compact_pruned_data(&mut self, end_version: u64) {
+        match self {
+            Self::Sequential(db) => db.compact_pruned_data(end_version),
+            Self::Parallel(db) => db.compact_pruned_data(end_version),
+        }
+    }
 }
 
 #[cfg(test)]
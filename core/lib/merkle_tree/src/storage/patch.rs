@@ -4,14 +4,14 @@ use std::{
     collections::{hash_map::Entry, HashMap},
     iter,
     sync::Arc,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use rayon::prelude::*;
 
 use crate::{
     hasher::{HashTree, HasherWithStats, MerklePath},
-    metrics::HashingStats,
+    metrics::{DbReadsByNibbleCount, HashingStats},
     storage::{proofs::SUBTREE_COUNT, Operation, SortedKeys, TraverseOutcome},
     types::{
         ChildRef, InternalNode, Key, LeafNode, Manifest, Nibbles, NibblesBytes, Node, NodeKey,
@@ -191,8 +191,8 @@ impl WorkingNode {
 pub(crate) struct LoadAncestorsResult {
     /// The longest prefixes present in the tree currently for each requested key.
     pub longest_prefixes: Vec<Nibbles>,
-    /// Number of db reads used.
-    pub db_reads: u64,
+    /// Number of db reads used, grouped by the key nibble count.
+    pub db_reads: DbReadsByNibbleCount,
 }
 
 /// Mutable version of [`PatchSet`] where we insert all changed nodes when updating
@@ -360,6 +360,10 @@ impl WorkingPatchSet {
     }
 
     /// Computes hashes and serializes this change set.
+    ///
+    /// Subtrees rooted at the children of the root node are independent, so hashes in them are computed
+    /// in parallel; within a subtree, nodes on the same level are hashed in parallel as well. This avoids
+    /// synchronizing all threads after each tree level, which becomes a bottleneck for large blocks.
     pub(super) fn finalize(
         self,
         manifest: Manifest,
@@ -368,32 +372,84 @@ impl WorkingPatchSet {
         hasher: &dyn HashTree,
     ) -> (ValueHash, PatchSet, HashingStats) {
         let mut stats = HashingStats::default();
-        let (root_hash, patch) = self.finalize_inner(
+        let started_at = Instant::now();
+        // `into_par_iter()` below uses `rayon` to parallelize hash computations across subtrees.
+        let parts = self.split().into_par_iter().map(|mut part| {
+            part.hash_subtree(hasher, &stats);
+            part
+        });
+        let merged = parts
+            .reduce_with(|mut this, other| {
+                this.merge(other);
+                this
+            })
+            .unwrap();
+        // ^ `unwrap()` is safe since there is a non-zero number of parts
+        stats.hashing_duration += started_at.elapsed();
+
+        let mut upper_levels_hasher = hasher.with_stats(&stats);
+        let mut upper_levels_hashing_duration = Duration::ZERO;
+        let (root_hash, patch) = merged.finalize_inner(
             manifest,
             leaf_count,
             operation,
             |nibble_count, level_changes| {
                 let started_at = Instant::now();
                 let tree_level = nibble_count * 4;
-                // `into_par_iter()` below uses `rayon` to parallelize hash computations.
                 let output = level_changes
-                    .into_par_iter()
-                    .map_init(
-                        || hasher.with_stats(&stats),
-                        |hasher, (nibbles, node)| {
-                            let nibbles = Nibbles::from_parts(nibbles, nibble_count);
-                            (nibbles, Some(node.inner.hash(hasher, tree_level)), node)
-                        },
-                    )
+                    .into_iter()
+                    .map(|(nibbles, node)| {
+                        let nibbles = Nibbles::from_parts(nibbles, nibble_count);
+                        // Hashes for nodes in subtrees are already propagated to their parents.
+                        let hash = (nibble_count < 2)
+                            .then(|| node.inner.hash(&mut upper_levels_hasher, tree_level));
+                        (nibbles, hash, node)
+                    })
                     .collect::<Vec<_>>();
-                stats.hashing_duration += started_at.elapsed();
+                upper_levels_hashing_duration += started_at.elapsed();
                 output
             },
         );
+        drop(upper_levels_hasher);
+        stats.hashing_duration += upper_levels_hashing_duration;
+
         let root_hash = root_hash.unwrap_or_else(|| hasher.empty_tree_hash());
         (root_hash, patch, stats)
     }
 
+    /// Computes hashes for changed nodes with at least 2 nibbles (i.e., ones that belong to a subtree
+    /// of a root node child) in the order of decreasing nibble count, and stores the computed hashes
+    /// in the parent nodes.
+    fn hash_subtree(&mut self, hasher: &dyn HashTree, stats: &HashingStats) {
+        for nibble_count in (2..self.changes_by_nibble_count.len()).rev() {
+            let tree_level = nibble_count * 4;
+            let (upper_levels, lower_levels) =
+                self.changes_by_nibble_count.split_at_mut(nibble_count);
+            // `par_iter()` below uses `rayon` to parallelize hash computations within a level.
+            let hashes = lower_levels[0]
+                .par_iter()
+                .map_init(
+                    || hasher.with_stats(stats),
+                    |hasher, (nibbles, node)| (*nibbles, node.inner.hash(hasher, tree_level)),
+                )
+                .collect::<Vec<_>>();
+
+            let parent_level = upper_levels.last_mut().unwrap();
+            // ^ `unwrap()` is safe: `nibble_count` is positive
+            for (nibbles, hash) in hashes {
+                let nibbles = Nibbles::from_parts(nibbles, nibble_count);
+                let (parent_nibbles, last_nibble) = nibbles.split_last().unwrap();
+                let parent = parent_level.get_mut(parent_nibbles.bytes()).unwrap();
+                let Node::Internal(parent) = &mut parent.inner else {
+                    unreachable!("Node parent must be an internal node");
+                };
+                // ^ `unwrap()`s are safe by construction: the parent of any changed node
+                // is an `InternalNode` that must be in the change set as well, and it must reference the child.
+                parent.child_ref_mut(last_nibble).unwrap().hash = hash;
+            }
+        }
+    }
+
     fn finalize_inner<I>(
         self,
         manifest: Manifest,
@@ -509,7 +565,7 @@ impl WorkingPatchSet {
         let Some(Node::Internal(_)) = self.get(&Nibbles::EMPTY) else {
             return LoadAncestorsResult {
                 longest_prefixes: vec![Nibbles::EMPTY; sorted_keys.0.len()],
-                db_reads: 0,
+                db_reads: DbReadsByNibbleCount::default(),
             };
         };
 
@@ -519,7 +575,7 @@ impl WorkingPatchSet {
         // Previous encountered when iterating by `sorted_keys` below.
         let mut prev_nibbles = None;
         // Cumulative number of db reads.
-        let mut db_reads = 0;
+        let mut db_reads = DbReadsByNibbleCount::default();
         for nibble_count in 1.. {
             // Extract `nibble_count` nibbles from each key for which we haven't found the parent
             // yet. Note that nibbles in `requested_keys` are sorted.
@@ -559,7 +615,7 @@ impl WorkingPatchSet {
                 break;
             }
             let new_nodes = db.tree_nodes(&requested_keys);
-            db_reads += new_nodes.len() as u64;
+            db_reads.add(nibble_count, new_nodes.len() as u64);
 
             // Since we load nodes level by level, we can update `patch_set` more efficiently
             // by pushing entire `HashMap`s into `changes_by_nibble_count`.
@@ -604,7 +660,7 @@ impl WorkingPatchSet {
         db: &DB,
     ) -> Option<(LeafNode, LoadAncestorsResult)> {
         let mut nibbles = Nibbles::EMPTY;
-        let mut db_reads = 0;
+        let mut db_reads = DbReadsByNibbleCount::default();
         let greatest_leaf = loop {
             match self.get(&nibbles) {
                 None => return None,
@@ -617,7 +673,7 @@ impl WorkingPatchSet {
                     let child_node = db.tree_node(&child_key, child_ref.is_leaf).unwrap();
                     // ^ `unwrap()` is safe by construction
                     self.push_level_from_db(iter::once((&child_key, child_node)));
-                    db_reads += 1;
+                    db_reads.add(nibbles.nibble_count(), 1);
                 }
             }
         };
@@ -766,7 +822,7 @@ mod tests {
         assert_eq!(greatest_leaf.full_key, key);
         assert_eq!(load_result.longest_prefixes.len(), 1);
         assert_eq!(load_result.longest_prefixes[0].nibble_count(), 0);
-        assert_eq!(load_result.db_reads, 0);
+        assert_eq!(load_result.db_reads.total(), 0);
 
         // Test DB with multiple entries.
         let other_key = Key::from_little_endian(&[0xa0; 32]);
@@ -782,7 +838,7 @@ mod tests {
         assert_eq!(greatest_leaf.full_key, other_key);
         assert_eq!(load_result.longest_prefixes.len(), 1);
         assert_eq!(load_result.longest_prefixes[0].nibble_count(), 1);
-        assert_eq!(load_result.db_reads, 1);
+        assert_eq!(load_result.db_reads.total(), 1);
 
         let greater_key = Key::from_little_endian(&[0xaf; 32]);
         let (_, patch) = Storage::new(&db, &(), 2, true).extend(vec![TreeEntry::new(
@@ -797,6 +853,10 @@ mod tests {
         assert_eq!(greatest_leaf.full_key, greater_key);
         assert_eq!(load_result.longest_prefixes.len(), 1);
         assert_eq!(load_result.longest_prefixes[0].nibble_count(), 2);
-        assert_eq!(load_result.db_reads, 2);
+        assert_eq!(load_result.db_reads.total(), 2);
+        let mut expected_reads = DbReadsByNibbleCount::default();
+        expected_reads.add(1, 1);
+        expected_reads.add(2, 1);
+        assert_eq!(load_result.db_reads, expected_reads);
     }
 }
@@ -157,6 +157,36 @@ impl RocksDBWrapper {
         })
     }
 
+    /// Performs manual compaction of the tree database. See [`RocksDB::compact()`] for details.
+    pub fn compact(&self) -> bool {
+        self.db.compact()
+    }
+
+    /// Checks whether the wrapped RocksDB is a read-only secondary instance.
+    pub fn is_secondary(&self) -> bool {
+        self.db.is_secondary()
+    }
+
+    /// Makes the wrapped secondary RocksDB instance catch up with the primary instance.
+    /// This is a no-op if the wrapped instance is primary.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), rocksdb::Error> {
+        self.db.try_catch_up_with_primary()
+    }
+
+    /// Creates a consistent checkpoint of the tree database in the specified directory.
+    /// See [`RocksDB::create_checkpoint()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        self.db.create_checkpoint(path)
+    }
+
     /// Returns the wrapped RocksDB instance.
     pub fn into_inner(self) -> RocksDB<MerkleTreeColumnFamily> {
         self.db
@@ -265,12 +295,22 @@ impl Database for RocksDBWrapper {
                 node_bytes.clear();
                 root.serialize(&mut node_bytes);
                 metrics.update_node_bytes(&Nibbles::EMPTY, &node_bytes);
+                if let Root::Filled {
+                    node: Node::Internal(node),
+                    ..
+                } = &root
+                {
+                    metrics.update_internal_node(&Nibbles::EMPTY, node);
+                }
                 write_batch.put_cf(tree_cf, &root_key.to_db_key(), &node_bytes);
             }
             for (node_key, node) in sub_patch.nodes {
                 node_bytes.clear();
                 node.serialize(&mut node_bytes);
                 metrics.update_node_bytes(&node_key.nibbles, &node_bytes);
+                if let Node::Internal(node) = &node {
+                    metrics.update_internal_node(&node_key.nibbles, node);
+                }
                 write_batch.put_cf(tree_cf, &node_key.to_db_key(), &node_bytes);
             }
         }
@@ -334,6 +374,17 @@ impl PruneDatabase for RocksDBWrapper {
             .write(write_batch)
             .context("Failed writing a batch to RocksDB")
     }
+
+    fn compact_pruned_data(&mut self, end_version: u64) {
+        // Both node keys and stale keys start with a big-endian version, so pruned data occupies
+        // a contiguous key range at the start of the corresponding column families.
+        let start_key: &[u8] = &[];
+        let end_key = &end_version.to_be_bytes() as &[_];
+        self.db
+            .compact_range_cf(MerkleTreeColumnFamily::Tree, start_key..end_key);
+        self.db
+            .compact_range_cf(MerkleTreeColumnFamily::StaleKeys, start_key..end_key);
+    }
 }
 
 #[cfg(test)]
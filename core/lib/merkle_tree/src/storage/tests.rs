@@ -193,6 +193,7 @@ fn inserting_node_in_non_empty_database() {
     let sorted_keys = SortedKeys::new([THIRD_KEY, E_KEY, SECOND_KEY].into_iter());
     let parent_nibbles = updater.load_ancestors(&sorted_keys, &db);
     assert_eq!(updater.metrics.db_reads, 10);
+    assert_eq!(updater.metrics.db_reads_by_nibble_count.total(), 10);
     assert_eq!(
         parent_nibbles,
         [
@@ -442,6 +443,37 @@ fn replaced_keys_are_correctly_tracked(writes_per_block: usize, with_proofs: boo
     }
 }
 
+#[test_casing(3, [10, 100, 1_000])]
+fn subtree_hashing_is_consistent_with_proofs(writes_per_block: usize) {
+    const RNG_SEED: u64 = 42;
+
+    // Trees with proofs compute hashes separately from `WorkingPatchSet::finalize()`,
+    // so they serve as a reference implementation.
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let mut database = PatchSet::default();
+    for version in 0..5 {
+        let entries: Vec<_> = (0..writes_per_block)
+            .map(|i| {
+                let key = Key::from_little_endian(&rng.gen::<[u8; 32]>());
+                TreeEntry::new(
+                    key,
+                    (version * writes_per_block + i + 1) as u64,
+                    H256(rng.gen()),
+                )
+            })
+            .collect();
+
+        let storage = Storage::new(&database, &Blake2Hasher, version as u64, true);
+        let (output, patch) = storage.extend(entries.clone());
+        let storage = Storage::new(&database, &Blake2Hasher, version as u64, true);
+        let instructions = entries.into_iter().map(TreeInstruction::Write).collect();
+        let (output_with_proofs, _) = storage.extend_with_proofs(instructions);
+
+        assert_eq!(Some(output.root_hash), output_with_proofs.root_hash());
+        database.apply_patch(patch).unwrap();
+    }
+}
+
 fn assert_replaced_keys(db: &PatchSet, patch: &PatchSet) {
     assert_eq!(patch.patches_by_version.len(), 1);
     let (&version, sub_patch) = patch.patches_by_version.iter().next().unwrap();
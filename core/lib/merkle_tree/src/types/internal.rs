@@ -100,6 +100,11 @@ impl Manifest {
         }
     }
 
+    /// Returns custom / user-defined tags stored in this manifest.
+    pub fn custom_tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref().map(|tags| &tags.custom)
+    }
+
     #[cfg(test)]
     pub(crate) fn new(version_count: u64, hasher: &dyn HashTree) -> Self {
         Self {
@@ -127,6 +127,58 @@ pub struct TreeEntryWithProof {
     pub merkle_path: Vec<ValueHash>,
 }
 
+/// Entries in a Merkle tree together with a combined proof of their authenticity. Unlike with separate
+/// [`TreeEntryWithProof`]s, hashes shared by Merkle paths of several entries are only included once.
+#[derive(Debug, Clone)]
+pub struct TreeMultiProof {
+    /// Proven entries ordered by increasing key. Entries for missing keys are [empty](TreeEntry::is_empty()).
+    pub entries: Vec<TreeEntry>,
+    /// Merkle path for each of `entries`, truncated to the root of the greatest subtree that contains
+    /// no other proven entries. Paths have the same format as [`TreeEntryWithProof::merkle_path`];
+    /// i.e., hashes corresponding to empty subtrees at the beginning of a path are skipped.
+    pub merkle_paths: Vec<Vec<ValueHash>>,
+    /// Hashes of subtrees adjacent to the shared parts of Merkle paths (i.e., ones above truncated `merkle_paths`)
+    /// in the depth-first traversal order. Hashes that can be computed from the proven entries are not included.
+    pub shared_hashes: Vec<ValueHash>,
+}
+
+/// Proof that a contiguous key range in a Merkle tree contains the specified entries and no other entries.
+#[derive(Debug, Clone)]
+pub struct TreeRangeProof {
+    /// Entry for the start key of the range together with its Merkle proof. The entry may be empty.
+    pub start: TreeEntryWithProof,
+    /// All non-empty entries with keys strictly between the start and end keys ordered by increasing key.
+    pub entries: Vec<TreeEntry>,
+    /// Entry for the end key of the range together with its Merkle proof. The entry may be empty.
+    pub end: TreeEntryWithProof,
+}
+
+/// Change of a single tree entry between two tree versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TreeEntryDiff {
+    /// Entry in the old tree version. The entry is empty if the key was inserted.
+    pub old: TreeEntry,
+    /// Entry in the new tree version. The entry is empty if the key was removed (e.g., when
+    /// diffing the tree with one of its older versions).
+    pub new: TreeEntry,
+}
+
+impl TreeEntryDiff {
+    /// Returns the key of the changed entry.
+    pub fn key(&self) -> Key {
+        self.old.key
+    }
+}
+
+/// Chunk of changes between two tree versions.
+#[derive(Debug, Clone)]
+pub struct TreeDiff {
+    /// Changed entries ordered by increasing key.
+    pub entries: Vec<TreeEntryDiff>,
+    /// Key to start the next chunk from, or `None` if this chunk is the last one.
+    pub next_key: Option<Key>,
+}
+
 /// Output of inserting a block of entries into a Merkle tree.
 #[derive(Debug, PartialEq, Eq)]
 pub struct BlockOutput {
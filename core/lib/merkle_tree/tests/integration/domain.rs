@@ -6,7 +6,10 @@ use serde::{Deserialize, Serialize};
 use serde_with::{hex::Hex, serde_as};
 use tempfile::TempDir;
 use zksync_crypto::hasher::blake2::Blake2Hasher;
-use zksync_merkle_tree::{domain::ZkSyncTree, HashTree, TreeEntry, TreeInstruction};
+use zksync_merkle_tree::{
+    domain::ZkSyncTree, HashTree, InMemoryDatabase, MerkleTree, PatchSet, RocksDBWrapper,
+    TreeEntry, TreeHasher, TreeInstruction,
+};
 use zksync_prover_interface::inputs::StorageLogMetadata;
 use zksync_storage::RocksDB;
 use zksync_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
@@ -101,6 +104,47 @@ fn basic_workflow_multiblock() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(12));
 }
 
+#[test]
+fn in_memory_tree_workflow() {
+    let logs = gen_storage_logs();
+    let db = InMemoryDatabase::new();
+    let mut tree = ZkSyncTree::in_memory(db.clone()).unwrap();
+    let reader = tree.reader();
+    for block in logs.chunks(9) {
+        tree.process_l1_batch(block).unwrap();
+    }
+    // The reader doesn't observe unsaved changes.
+    assert_eq!(reader.next_l1_batch_number(), L1BatchNumber(0));
+    tree.save().unwrap();
+
+    let expected_root_hash = H256([
+        125, 25, 107, 171, 182, 155, 32, 70, 138, 108, 238, 150, 140, 205, 193, 39, 90, 92, 122,
+        233, 118, 238, 248, 201, 160, 55, 58, 206, 244, 216, 188, 10,
+    ]);
+    assert_eq!(tree.root_hash(), expected_root_hash);
+    assert_eq!(reader.next_l1_batch_number(), L1BatchNumber(12));
+    let (root_hash, leaf_count) = reader.root_info(L1BatchNumber(11)).unwrap();
+    assert_eq!(root_hash, expected_root_hash);
+    assert_eq!(leaf_count, 100);
+    tree.verify_consistency(L1BatchNumber(11)).unwrap();
+
+    // Reopen the tree from the same database.
+    drop(tree);
+    let mut tree = ZkSyncTree::in_memory(db).unwrap();
+    assert_eq!(tree.root_hash(), expected_root_hash);
+    tree.roll_back_logs(L1BatchNumber(5)).unwrap();
+    tree.save().unwrap();
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(6));
+    assert_eq!(reader.next_l1_batch_number(), L1BatchNumber(6));
+
+    let (mut pruner, _handle) = tree.pruner();
+    while pruner.prune_up_to(5).unwrap().is_some() {
+        // Continue pruning
+    }
+    assert_eq!(reader.min_l1_batch_number(), Some(L1BatchNumber(5)));
+    tree.verify_consistency(L1BatchNumber(5)).unwrap();
+}
+
 #[test]
 fn tree_with_single_leaf_works_correctly() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
@@ -128,6 +172,64 @@ fn tree_with_single_leaf_works_correctly() {
     );
 }
 
+#[test]
+fn computing_root_hash_with_alternative_hasher() {
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let logs = gen_storage_logs();
+    let entries: Vec<_> = logs
+        .iter()
+        .map(|instr| match instr {
+            TreeInstruction::Write(entry) => {
+                TreeEntry::new(entry.key.hashed_key_u256(), entry.leaf_index, entry.value)
+            }
+            TreeInstruction::Read(_) => unreachable!(),
+        })
+        .collect();
+    let mut poseidon2_tree =
+        MerkleTree::with_hasher(PatchSet::default(), TreeHasher::Poseidon2).unwrap();
+    let expected_root_hash = poseidon2_tree.extend(entries).unwrap().root_hash;
+    let genesis_output = ZkSyncTree::process_genesis_batch(&logs, TreeHasher::Poseidon2);
+    assert_eq!(genesis_output.root_hash, expected_root_hash);
+
+    let db = RocksDB::new(temp_dir.as_ref()).unwrap();
+    let mut tree = ZkSyncTree::new(db.into()).unwrap();
+    assert_eq!(tree.hasher(), TreeHasher::Blake2s);
+    tree.process_l1_batch(&logs).unwrap();
+    // Unsaved changes must be taken into account.
+    let root_hash = tree
+        .root_hash_with_hasher(L1BatchNumber(0), TreeHasher::Poseidon2)
+        .unwrap();
+    assert_eq!(root_hash, expected_root_hash);
+    tree.save().unwrap();
+
+    let reader = tree.reader();
+    let root_hash = reader
+        .root_hash_with_hasher(L1BatchNumber(0), TreeHasher::Poseidon2)
+        .unwrap();
+    assert_eq!(root_hash, expected_root_hash);
+    let root_hash = reader
+        .root_hash_with_hasher(L1BatchNumber(0), TreeHasher::Blake2s)
+        .unwrap();
+    assert_eq!(root_hash, tree.root_hash());
+
+    // Check that a new tree can be created with Poseidon2 hashing, and that the hasher is then read from the tree tags.
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let db = RocksDBWrapper::new(temp_dir.path()).unwrap();
+    let mut tree = ZkSyncTree::new_with_hasher(db.clone(), TreeHasher::Poseidon2).unwrap();
+    assert_eq!(tree.hasher(), TreeHasher::Poseidon2);
+    tree.process_l1_batch(&logs).unwrap();
+    tree.save().unwrap();
+    assert_eq!(tree.root_hash(), expected_root_hash);
+    drop(tree);
+
+    let tree = ZkSyncTree::new(db.clone()).unwrap();
+    assert_eq!(tree.hasher(), TreeHasher::Poseidon2);
+    assert_eq!(tree.root_hash(), expected_root_hash);
+    drop(tree);
+    let tree = ZkSyncTree::new_lightweight_with_hasher(db, TreeHasher::Blake2s).unwrap();
+    assert_eq!(tree.hasher(), TreeHasher::Poseidon2);
+}
+
 #[test]
 fn filtering_out_no_op_writes() {
     let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
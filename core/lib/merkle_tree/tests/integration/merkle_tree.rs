@@ -543,6 +543,148 @@ fn range_proofs_with_random_ranges() {
     }
 }
 
+#[test_casing(5, [1, 2, 10, 50, 100])]
+fn multi_proofs_for_random_keys(key_count: usize) {
+    const RNG_SEED: u64 = 654;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    // Mix existing and missing keys.
+    let mut keys: Vec<_> = kvs
+        .choose_multiple(&mut rng, key_count)
+        .map(|entry| entry.key)
+        .collect();
+    keys.extend((0..key_count).map(|_| U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()])));
+    keys.shuffle(&mut rng);
+
+    let proof = tree.entries_with_multi_proof(0, &keys).unwrap();
+    proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+
+    let mut sorted_keys = keys.clone();
+    sorted_keys.sort_unstable();
+    assert_eq!(proof.entries, tree.entries(0, &sorted_keys).unwrap());
+
+    // The combined proof must be more compact than separate proofs.
+    let separate_proofs = tree.entries_with_proofs(0, &sorted_keys).unwrap();
+    let separate_hash_count: usize = separate_proofs
+        .iter()
+        .map(|entry| entry.merkle_path.len())
+        .sum();
+    let combined_hash_count = proof.shared_hashes.len()
+        + proof
+            .merkle_paths
+            .iter()
+            .map(|path| path.len())
+            .sum::<usize>();
+    assert!(
+        combined_hash_count <= separate_hash_count,
+        "{combined_hash_count} > {separate_hash_count}"
+    );
+
+    let mut tampered_proof = proof.clone();
+    let tampered_entry = &mut tampered_proof.entries[0];
+    tampered_entry.value = H256::repeat_byte(0xfe);
+    tampered_entry.leaf_index = 1;
+    let err = tampered_proof
+        .verify(&Blake2Hasher, *expected_hash)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Root hash mismatch"), "{err}");
+
+    let mut tampered_proof = proof;
+    tampered_proof.entries.reverse();
+    let err = tampered_proof
+        .verify(&Blake2Hasher, *expected_hash)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not ordered"), "{err}");
+}
+
+#[test]
+fn range_proofs_from_tree() {
+    const ITER_COUNT: usize = 100;
+    const RNG_SEED: u64 = 456;
+
+    let mut rng = StdRng::seed_from_u64(RNG_SEED);
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+
+    for _ in 0..ITER_COUNT {
+        let mut start_key = U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()]);
+        let mut end_key = U256([rng.gen(), rng.gen(), rng.gen(), rng.gen()]);
+        if start_key > end_key {
+            mem::swap(&mut start_key, &mut end_key);
+        }
+
+        let mut keys_in_range: Vec<_> = kvs
+            .iter()
+            .filter_map(|entry| (entry.key > start_key && entry.key < end_key).then_some(entry.key))
+            .collect();
+        keys_in_range.sort_unstable();
+
+        let proof = tree.range_proof(0, start_key, end_key, usize::MAX).unwrap();
+        let proven_keys: Vec<_> = proof.entries.iter().map(|entry| entry.key).collect();
+        assert_eq!(proven_keys, keys_in_range);
+        proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+
+        if !proof.entries.is_empty() {
+            // Omitting an entry must be detected.
+            let mut tampered_proof = proof.clone();
+            let idx = rng.gen_range(0..tampered_proof.entries.len());
+            tampered_proof.entries.remove(idx);
+            tampered_proof
+                .verify(&Blake2Hasher, *expected_hash)
+                .unwrap_err();
+        }
+    }
+
+    // Ranges with boundaries coinciding with existing keys.
+    let mut sorted_keys: Vec<_> = kvs.iter().map(|entry| entry.key).collect();
+    sorted_keys.sort_unstable();
+    for (start_idx, end_idx) in [(0, 0), (0, 1), (3, 10), (0, sorted_keys.len() - 1)] {
+        let (start_key, end_key) = (sorted_keys[start_idx], sorted_keys[end_idx]);
+        let proof = tree.range_proof(0, start_key, end_key, usize::MAX).unwrap();
+        assert_eq!(proof.entries.len(), end_idx.saturating_sub(start_idx + 1));
+        assert!(!proof.start.base.is_empty());
+        proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+    }
+}
+
+#[test]
+fn truncated_range_proofs_from_tree() {
+    let (kvs, expected_hash) = &*ENTRIES_AND_HASH;
+    let mut tree = MerkleTree::new(PatchSet::default()).unwrap();
+    tree.extend(kvs.clone()).unwrap();
+    let mut sorted_keys: Vec<_> = kvs.iter().map(|entry| entry.key).collect();
+    sorted_keys.sort_unstable();
+
+    let mut start_key = U256::zero();
+    let mut proven_keys = vec![];
+    for max_entries in 0.. {
+        let proof = tree
+            .range_proof(0, start_key, U256::MAX, max_entries)
+            .unwrap();
+        assert!(proof.entries.len() <= max_entries);
+        proof.verify(&Blake2Hasher, *expected_hash).unwrap();
+
+        proven_keys.extend(proof.entries.iter().map(|entry| entry.key));
+        let end_key = proof.end.base.key;
+        if end_key == U256::MAX {
+            break;
+        }
+        // The range must be truncated at an existing entry.
+        assert!(!proof.end.base.is_empty());
+        assert_eq!(proof.entries.len(), max_entries);
+        proven_keys.push(end_key);
+        start_key = end_key + 1;
+    }
+    assert_eq!(proven_keys, sorted_keys);
+}
+
 /// RocksDB-specific tests.
 mod rocksdb {
     use std::collections::BTreeMap;
@@ -46,8 +46,12 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmBlockResult> for crate::interface::Fi
                     gas_remaining: value.full_result.gas_remaining,
                     pubdata_published: 0,
                     circuit_statistic: Default::default(),
+                    circuit_cycles: Default::default(),
+                    opcode_histogram: None,
                 },
                 refunds: Refunds::default(),
+                call_traces: None,
+                gas_by_contract: None,
             },
             final_execution_state: CurrentExecutionState {
                 events: value.full_result.events,
@@ -106,8 +110,12 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmBlockResult> for crate::interface::Fi
                     gas_remaining: value.full_result.gas_remaining,
                     pubdata_published: 0,
                     circuit_statistic: Default::default(),
+                    circuit_cycles: Default::default(),
+                    opcode_histogram: None,
                 },
                 refunds: Refunds::default(),
+                call_traces: None,
+                gas_by_contract: None,
             },
             final_execution_state: CurrentExecutionState {
                 events: value.full_result.events,
@@ -153,6 +161,7 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult> for crate::interface:
                     user_l2_to_l1_logs: value.block_tip_result.logs.user_l2_to_l1_logs.clone(),
                     system_l2_to_l1_logs: value.block_tip_result.logs.system_l2_to_l1_logs.clone(),
                     storage_logs: value.block_tip_result.logs.storage_logs,
+                    storage_reads: None,
                     total_log_queries_count: value.block_tip_result.logs.total_log_queries_count,
                 },
                 statistics: VmExecutionStatistics {
@@ -164,8 +173,12 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult> for crate::interface:
                     gas_remaining: value.full_result.gas_remaining,
                     pubdata_published: 0,
                     circuit_statistic: Default::default(),
+                    circuit_cycles: Default::default(),
+                    opcode_histogram: None,
                 },
                 refunds: Refunds::default(),
+                call_traces: None,
+                gas_by_contract: None,
             },
             final_execution_state: CurrentExecutionState {
                 events: value.full_result.events,
@@ -225,6 +238,7 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult>
                     .into_iter()
                     .map(GlueInto::glue_into)
                     .collect(),
+                storage_reads: None,
                 total_log_queries_count: value.full_result.total_log_queries,
             },
             statistics: VmExecutionStatistics {
@@ -236,8 +250,12 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmBlockResult>
                 gas_remaining: value.full_result.gas_remaining,
                 pubdata_published: 0,
                 circuit_statistic: Default::default(),
+                circuit_cycles: Default::default(),
+                opcode_histogram: None,
             },
             refunds: Refunds::default(),
+            call_traces: None,
+            gas_by_contract: None,
         }
     }
 }
@@ -268,8 +286,12 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmBlockResult>
                 gas_remaining: value.full_result.gas_remaining,
                 pubdata_published: 0,
                 circuit_statistic: Default::default(),
+                circuit_cycles: Default::default(),
+                opcode_histogram: None,
             },
             refunds: Refunds::default(),
+            call_traces: None,
+            gas_by_contract: None,
         }
     }
 }
@@ -305,6 +327,7 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmBlockResult>
                     .into_iter()
                     .map(GlueInto::glue_into)
                     .collect(),
+                storage_reads: None,
                 total_log_queries_count: value.full_result.total_log_queries,
             },
             statistics: VmExecutionStatistics {
@@ -316,8 +339,12 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmBlockResult>
                 gas_remaining: value.full_result.gas_remaining,
                 pubdata_published: 0,
                 circuit_statistic: Default::default(),
+                circuit_cycles: Default::default(),
+                opcode_histogram: None,
             },
             refunds: Refunds::default(),
+            call_traces: None,
+            gas_by_contract: None,
         }
     }
 }
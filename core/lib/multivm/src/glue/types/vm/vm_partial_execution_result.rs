@@ -17,6 +17,8 @@ impl GlueFrom<crate::vm_m5::vm_instance::VmPartialExecutionResult>
                 computational_gas_used: 0,
                 pubdata_published: 0,
                 circuit_statistic: Default::default(),
+                circuit_cycles: Default::default(),
+                opcode_histogram: None,
             },
             refunds: crate::interface::Refunds {
                 gas_refunded: 0,
@@ -43,6 +45,8 @@ impl GlueFrom<crate::vm_m6::vm_instance::VmPartialExecutionResult>
                 gas_remaining: 0,
                 pubdata_published: 0,
                 circuit_statistic: Default::default(),
+                circuit_cycles: Default::default(),
+                opcode_histogram: None,
             },
             refunds: crate::interface::Refunds {
                 gas_refunded: 0,
@@ -69,6 +73,8 @@ impl GlueFrom<crate::vm_1_3_2::vm_instance::VmPartialExecutionResult>
                 gas_remaining: 0,
                 pubdata_published: 0,
                 circuit_statistic: Default::default(),
+                circuit_cycles: Default::default(),
+                opcode_histogram: None,
             },
             refunds: crate::interface::Refunds {
                 gas_refunded: 0,
@@ -66,12 +66,16 @@ impl GlueFrom<Result<crate::vm_m6::vm_instance::VmTxExecutionResult, crate::vm_m
                         logs: Default::default(),
                         statistics: Default::default(),
                         refunds: Default::default(),
+                        call_traces: None,
+                        gas_by_contract: None,
                     },
                     TxRevertReason::Halt(halt) => VmExecutionResultAndLogs {
                         result: ExecutionResult::Halt { reason: halt },
                         logs: Default::default(),
                         statistics: Default::default(),
                         refunds: Default::default(),
+                        call_traces: None,
+                        gas_by_contract: None,
                     },
                 }
             }
@@ -100,12 +104,16 @@ impl
                         logs: Default::default(),
                         statistics: Default::default(),
                         refunds: Default::default(),
+                        call_traces: None,
+                        gas_by_contract: None,
                     },
                     TxRevertReason::Halt(halt) => VmExecutionResultAndLogs {
                         result: ExecutionResult::Halt { reason: halt },
                         logs: Default::default(),
                         statistics: Default::default(),
                         refunds: Default::default(),
+                        call_traces: None,
+                        gas_by_contract: None,
                     },
                 }
             }
@@ -129,6 +137,8 @@ impl GlueFrom<Result<crate::vm_m5::vm_instance::VmTxExecutionResult, crate::vm_m
                         logs: Default::default(),
                         statistics: Default::default(),
                         refunds: Default::default(),
+                        call_traces: None,
+                        gas_by_contract: None,
                     },
                     _ => {
                         unreachable!("Halt is the only revert reason for VM 5")
@@ -1,7 +1,7 @@
 pub(crate) mod traits;
 
 pub use traits::{
-    tracers::dyn_tracers,
+    tracers::{dyn_tracers, versioned::VersionedTracer},
     vm::{VmInterface, VmInterfaceHistoryEnabled},
 };
 pub mod types;
@@ -12,8 +12,10 @@ pub use types::{
     },
     inputs::{L1BatchEnv, L2BlockEnv, SystemEnv, TxExecutionMode, VmExecutionMode},
     outputs::{
-        BootloaderMemory, CurrentExecutionState, ExecutionResult, FinishedL1Batch, L2Block,
-        Refunds, VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
+        bootloader_memory_diff, BalanceChange, BootloaderMemory, ContractGasUsage,
+        CurrentExecutionState, DeployedContract, DryRunResult, ExecutionResult, FinishedL1Batch,
+        L2Block, OpcodeHistogram, OpcodeStatistic, PubdataInput, Refunds, StorageDiff,
+        VmExecutionResultAndLogs, VmExecutionStatistics, VmMemoryMetrics,
     },
     tracer,
 };
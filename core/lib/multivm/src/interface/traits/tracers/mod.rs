@@ -1 +1,2 @@
 pub mod dyn_tracers;
+pub mod versioned;
@@ -0,0 +1,24 @@
+use crate::interface::tracer::{
+    FarCallEvent, OpcodeEvent, StorageAccessEvent, VmExecutionStopReason,
+};
+
+/// Tracer that can be attached to any VM version supporting custom tracers.
+///
+/// Unlike version-specific `VmTracer`s, this trait only operates on version-agnostic
+/// event types, so it can be implemented without depending on VM internals.
+/// To pass a tracer into the VM, wrap it into [`VersionedTracerAdapter`](crate::tracers::VersionedTracerAdapter).
+///
+/// Hooks are invoked *before* the corresponding opcode is executed.
+pub trait VersionedTracer {
+    /// Called for every executed opcode.
+    fn on_opcode(&mut self, _event: &OpcodeEvent) {}
+
+    /// Called for every storage read or write (including transient storage for VMs supporting it).
+    fn on_storage_access(&mut self, _event: &StorageAccessEvent) {}
+
+    /// Called for every far call.
+    fn on_far_call(&mut self, _event: &FarCallEvent) {}
+
+    /// Called once the VM execution is finished.
+    fn after_vm_execution(&mut self, _stop_reason: &VmExecutionStopReason) {}
+}
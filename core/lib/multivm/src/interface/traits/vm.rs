@@ -58,7 +58,7 @@ use crate::{
             inputs::{L1BatchEnv, L2BlockEnv, SystemEnv, VmExecutionMode},
             outputs::{BootloaderMemory, CurrentExecutionState, VmExecutionResultAndLogs},
         },
-        FinishedL1Batch, VmMemoryMetrics,
+        DryRunResult, FinishedL1Batch, VmMemoryMetrics,
     },
     tracers::TracerDispatcher,
     vm_latest::HistoryEnabled,
@@ -158,4 +158,15 @@ pub trait VmInterfaceHistoryEnabled<S>: VmInterface<S, HistoryEnabled> {
 
     /// Pop the latest snapshot from memory and destroy it.
     fn pop_snapshot_no_rollback(&mut self);
+
+    /// Execute a transaction without persisting its effects in the VM state, and return the produced
+    /// storage diffs, base token balance changes and deployed contracts.
+    fn dry_run_transaction(&mut self, tx: Transaction) -> DryRunResult {
+        let initiator = tx.initiator_account();
+        self.make_snapshot();
+        self.push_transaction(tx);
+        let execution_result = self.execute(VmExecutionMode::OneTx);
+        self.rollback_to_the_latest_snapshot();
+        DryRunResult::new(execution_result, &[initiator])
+    }
 }
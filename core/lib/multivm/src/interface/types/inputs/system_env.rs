@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use serde::{Deserialize, Serialize};
 use zksync_contracts::BaseSystemContracts;
-use zksync_types::{L2ChainId, ProtocolVersionId};
+use zksync_types::{L2ChainId, ProtocolVersionId, VmVersion};
 
 /// Params related to the execution process, not batch it self
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -15,6 +15,36 @@ pub struct SystemEnv {
     pub execution_mode: TxExecutionMode,
     pub default_validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
+    /// Whether to collect the per-opcode execution histogram in `VmExecutionStatistics`.
+    /// Has noticeable performance overhead, so should only be enabled for profiling.
+    /// Like other `enable_*` flags, it is ignored by VM versions not supporting execution instrumentation;
+    /// see [`Self::supports_instrumentation()`].
+    /// Not serialized in order to keep compatibility with stored (e.g., bincode-encoded) environments.
+    #[serde(skip)]
+    pub enable_opcode_profiling: bool,
+    /// Whether to collect the full call tree (with calldata and returndata) in `VmExecutionResultAndLogs`.
+    /// Not serialized for the same reason as `enable_opcode_profiling`.
+    #[serde(skip)]
+    pub enable_call_tracing: bool,
+    /// Whether to collect per-contract gas usage in `VmExecutionResultAndLogs`.
+    /// Not serialized for the same reason as `enable_opcode_profiling`.
+    #[serde(skip)]
+    pub enable_gas_attribution: bool,
+    /// Whether to collect the ordered list of storage reads in `VmExecutionLogs`.
+    /// Not serialized for the same reason as `enable_opcode_profiling`.
+    #[serde(skip)]
+    pub enable_storage_reads_log: bool,
+}
+
+impl SystemEnv {
+    /// Checks whether the VM used for this environment honors the `enable_*` instrumentation flags.
+    /// Only the latest VM version supports instrumentation; older versions ignore the flags.
+    pub fn supports_instrumentation(&self) -> bool {
+        matches!(
+            VmVersion::from(self.version),
+            VmVersion::Vm1_5_0SmallBootloaderMemory | VmVersion::Vm1_5_0IncreasedBootloaderMemory
+        )
+    }
 }
 
 impl Debug for SystemEnv {
@@ -33,6 +63,10 @@ impl Debug for SystemEnv {
             )
             .field("execution_mode", &self.execution_mode)
             .field("chain_id", &self.chain_id)
+            .field("enable_opcode_profiling", &self.enable_opcode_profiling)
+            .field("enable_call_tracing", &self.enable_call_tracing)
+            .field("enable_gas_attribution", &self.enable_gas_attribution)
+            .field("enable_storage_reads_log", &self.enable_storage_reads_log)
             .finish()
     }
 }
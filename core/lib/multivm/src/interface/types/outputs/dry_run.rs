@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use zksync_types::{
+    event::{DEPLOY_EVENT_SIGNATURE, TRANSFER_EVENT_SIGNATURE},
+    storage_key_for_eth_balance, AccountTreeId, Address, StorageKey, CONTRACT_DEPLOYER_ADDRESS,
+    H256, L2_BASE_TOKEN_ADDRESS, U256,
+};
+use zksync_utils::{h256_to_account_address, h256_to_u256, u256_to_h256};
+
+use crate::interface::VmExecutionResultAndLogs;
+
+/// Change of a single storage slot caused by the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageDiff {
+    pub key: StorageKey,
+    pub initial_value: H256,
+    pub final_value: H256,
+}
+
+/// Change of the base token balance of an account caused by the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BalanceChange {
+    pub address: Address,
+    pub initial_balance: U256,
+    pub final_balance: U256,
+}
+
+/// Contract deployed by the transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeployedContract {
+    pub address: Address,
+    pub bytecode_hash: H256,
+}
+
+/// Result of executing a transaction in the dry-run mode, i.e. without persisting its effects
+/// in the VM state.
+#[derive(Debug, Clone)]
+pub struct DryRunResult {
+    pub execution_result: VmExecutionResultAndLogs,
+    /// Net storage changes, ordered by the storage key. Slots that were written to, but ended up with
+    /// the initial value are not included.
+    pub storage_diffs: Vec<StorageDiff>,
+    /// Base token balance changes, ordered by the account address.
+    pub balance_changes: Vec<BalanceChange>,
+    /// Contracts deployed by the transaction in the deployment order.
+    pub deployed_contracts: Vec<DeployedContract>,
+}
+
+impl DryRunResult {
+    /// Computes transaction effects from its execution result. `accounts` are the accounts
+    /// for which the balance changes should be checked in addition to the participants
+    /// of base token transfers (e.g., the transaction initiator).
+    pub fn new(execution_result: VmExecutionResultAndLogs, accounts: &[Address]) -> Self {
+        let storage_diffs = Self::storage_diffs(&execution_result);
+        let deployed_contracts = Self::deployed_contracts(&execution_result);
+
+        let mut balance_accounts: BTreeSet<_> = accounts.iter().copied().collect();
+        for event in &execution_result.logs.events {
+            if event.address == L2_BASE_TOKEN_ADDRESS
+                && event.indexed_topics.len() == 3
+                && event.indexed_topics[0] == *TRANSFER_EVENT_SIGNATURE
+            {
+                balance_accounts.insert(h256_to_account_address(&event.indexed_topics[1]));
+                balance_accounts.insert(h256_to_account_address(&event.indexed_topics[2]));
+            }
+        }
+        let diffs_by_key: BTreeMap<_, _> =
+            storage_diffs.iter().map(|diff| (diff.key, diff)).collect();
+        let balance_changes = balance_accounts
+            .into_iter()
+            .filter_map(|address| {
+                let diff = diffs_by_key.get(&storage_key_for_eth_balance(&address))?;
+                Some(BalanceChange {
+                    address,
+                    initial_balance: h256_to_u256(diff.initial_value),
+                    final_balance: h256_to_u256(diff.final_value),
+                })
+            })
+            .collect();
+
+        Self {
+            execution_result,
+            storage_diffs,
+            balance_changes,
+            deployed_contracts,
+        }
+    }
+
+    fn storage_diffs(execution_result: &VmExecutionResultAndLogs) -> Vec<StorageDiff> {
+        // Maps keys to (initial value, current value).
+        let mut values = BTreeMap::<StorageKey, (U256, U256)>::new();
+        for log in &execution_result.logs.storage_logs {
+            let query = &log.log_query;
+            if !query.rw_flag {
+                continue;
+            }
+            let key = StorageKey::new(AccountTreeId::new(query.address), u256_to_h256(query.key));
+            // Rolled back writes restore the value preceding the write.
+            let new_value = if query.rollback {
+                query.read_value
+            } else {
+                query.written_value
+            };
+            values
+                .entry(key)
+                .and_modify(|(_, current)| *current = new_value)
+                .or_insert((query.read_value, new_value));
+        }
+
+        values
+            .into_iter()
+            .filter(|(_, (initial, current))| initial != current)
+            .map(|(key, (initial, current))| StorageDiff {
+                key,
+                initial_value: u256_to_h256(initial),
+                final_value: u256_to_h256(current),
+            })
+            .collect()
+    }
+
+    fn deployed_contracts(execution_result: &VmExecutionResultAndLogs) -> Vec<DeployedContract> {
+        execution_result
+            .logs
+            .events
+            .iter()
+            .filter(|event| {
+                event.address == CONTRACT_DEPLOYER_ADDRESS
+                    && event.indexed_topics.len() == 4
+                    && event.indexed_topics[0] == *DEPLOY_EVENT_SIGNATURE
+            })
+            .map(|event| DeployedContract {
+                address: h256_to_account_address(&event.indexed_topics[3]),
+                bytecode_hash: event.indexed_topics[2],
+            })
+            .collect()
+    }
+}
@@ -3,11 +3,12 @@ use zksync_types::{
     event::{extract_long_l2_to_l1_messages, extract_published_bytecodes},
     l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
     tx::ExecutionMetrics,
+    vm_trace::Call,
     StorageLogQuery, Transaction, VmEvent,
 };
 use zksync_utils::bytecode::bytecode_len_in_bytes;
 
-use crate::interface::{Halt, VmExecutionStatistics, VmRevertReason};
+use crate::interface::{ContractGasUsage, Halt, VmExecutionStatistics, VmRevertReason};
 
 /// Refunds produced for the user.
 #[derive(Debug, Clone, Default)]
@@ -25,6 +26,9 @@ pub struct VmExecutionLogs {
     // logs and so all the outputted logs were treated as user_l2_to_l1_logs.
     pub user_l2_to_l1_logs: Vec<UserL2ToL1Log>,
     pub system_l2_to_l1_logs: Vec<SystemL2ToL1Log>,
+    /// All reads from the persistent storage in the order they were performed, including the reads
+    /// in reverted call frames. Only collected if `SystemEnv::enable_storage_reads_log` is set.
+    pub storage_reads: Option<Vec<StorageLogQuery>>,
     // This field moved to statistics, but we need to keep it for backward compatibility
     pub total_log_queries_count: usize,
 }
@@ -42,6 +46,12 @@ pub struct VmExecutionResultAndLogs {
     pub logs: VmExecutionLogs,
     pub statistics: VmExecutionStatistics,
     pub refunds: Refunds,
+    /// Call tree of the execution. Only collected if call tracing is enabled in `SystemEnv`
+    /// and the VM version supports it.
+    pub call_traces: Option<Vec<Call>>,
+    /// Gas consumed by each contract. Only collected if gas attribution is enabled in `SystemEnv`
+    /// and the VM version supports it.
+    pub gas_by_contract: Option<ContractGasUsage>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
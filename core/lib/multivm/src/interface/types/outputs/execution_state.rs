@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use zksync_types::{
     l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
     zk_evm_types::LogQuery,
@@ -37,3 +39,51 @@ pub struct CurrentExecutionState {
 
 /// Bootloader Memory of the VM.
 pub type BootloaderMemory = Vec<(usize, U256)>;
+
+/// Returns the words of `memory` changed compared to the `previous` memory snapshot, sorted by the word index.
+/// Words missing from a snapshot are considered to be zero, so the words cleared since `previous`
+/// are returned with the zero value.
+pub fn bootloader_memory_diff(
+    previous: &[(usize, U256)],
+    memory: &[(usize, U256)],
+) -> BootloaderMemory {
+    let previous_words: HashMap<_, _> = previous.iter().copied().collect();
+    let current_indices: HashSet<_> = memory.iter().map(|(index, _)| *index).collect();
+
+    let changed_words = memory
+        .iter()
+        .copied()
+        .filter(|(index, value)| previous_words.get(index).copied().unwrap_or_default() != *value);
+    let cleared_words = previous_words
+        .iter()
+        .filter(|(index, value)| !value.is_zero() && !current_indices.contains(index))
+        .map(|(index, _)| (*index, U256::zero()));
+
+    let mut diff: BootloaderMemory = changed_words.chain(cleared_words).collect();
+    diff.sort_unstable_by_key(|(index, _)| *index);
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computing_bootloader_memory_diff() {
+        let previous = vec![(0, U256::from(1)), (1, U256::from(2)), (5, U256::from(3))];
+        let memory = vec![
+            (7, U256::from(4)),
+            (0, U256::from(1)),
+            (1, U256::from(20)),
+            (2, U256::zero()),
+        ];
+        let diff = bootloader_memory_diff(&previous, &memory);
+        assert_eq!(
+            diff,
+            [(1, U256::from(20)), (5, U256::zero()), (7, U256::from(4))]
+        );
+
+        assert!(bootloader_memory_diff(&memory, &memory).is_empty());
+        assert_eq!(bootloader_memory_diff(&[], &previous), previous);
+    }
+}
@@ -1,6 +1,9 @@
-use zksync_types::writes::StateDiffRecord;
+use zksync_types::{pubdata_da::PubdataEncoding, writes::StateDiffRecord, U256};
 
-use super::{BootloaderMemory, CurrentExecutionState, VmExecutionResultAndLogs};
+use super::{
+    execution_state::bootloader_memory_diff, BootloaderMemory, CurrentExecutionState,
+    VmExecutionResultAndLogs,
+};
 
 /// State of the VM after the batch execution.
 #[derive(Debug, Clone)]
@@ -11,8 +14,25 @@ pub struct FinishedL1Batch {
     pub final_execution_state: CurrentExecutionState,
     /// Memory of the bootloader with all executed transactions. Could be none for old versions of the VM.
     pub final_bootloader_memory: Option<BootloaderMemory>,
-    /// Pubdata to be published on L1. Could be none for old versions of the VM.
+    /// Pubdata to be published on L1 in the rollup encoding. Could be none for old versions of the VM.
     pub pubdata_input: Option<Vec<u8>>,
     /// List of state diffs. Could be none for old versions of the VM.
     pub state_diffs: Option<Vec<StateDiffRecord>>,
 }
+
+impl FinishedL1Batch {
+    /// Returns the words of the final bootloader memory changed compared to the `previous` memory snapshot
+    /// (e.g., obtained via `VmInterface::get_bootloader_memory()`). Returns `None` if the final bootloader memory
+    /// is not available.
+    pub fn bootloader_memory_diff(&self, previous: &[(usize, U256)]) -> Option<BootloaderMemory> {
+        let memory = self.final_bootloader_memory.as_ref()?;
+        Some(bootloader_memory_diff(previous, memory))
+    }
+
+    /// Returns pubdata of the batch in the specified encoding. Returns `None` if pubdata is not available.
+    pub fn encoded_pubdata(&self, encoding: PubdataEncoding) -> Option<Vec<u8>> {
+        self.pubdata_input
+            .clone()
+            .map(|pubdata| encoding.encode(pubdata))
+    }
+}
@@ -1,13 +1,19 @@
 pub use self::{
+    dry_run::{BalanceChange, DeployedContract, DryRunResult, StorageDiff},
     execution_result::{ExecutionResult, Refunds, VmExecutionLogs, VmExecutionResultAndLogs},
-    execution_state::{BootloaderMemory, CurrentExecutionState},
+    execution_state::{bootloader_memory_diff, BootloaderMemory, CurrentExecutionState},
     finished_l1batch::FinishedL1Batch,
     l2_block::L2Block,
-    statistic::{VmExecutionStatistics, VmMemoryMetrics},
+    pubdata::PubdataInput,
+    statistic::{
+        ContractGasUsage, OpcodeHistogram, OpcodeStatistic, VmExecutionStatistics, VmMemoryMetrics,
+    },
 };
 
+mod dry_run;
 mod execution_result;
 mod execution_state;
 mod finished_l1batch;
 mod l2_block;
+mod pubdata;
 mod statistic;
@@ -1,19 +1,29 @@
 use zksync_types::{
     event::L1MessengerL2ToL1Log,
-    writes::{compress_state_diffs, StateDiffRecord},
+    pubdata_da::PubdataEncoding,
+    writes::{StateDiffCompression, StateDiffRecord},
 };
 
 /// Struct based on which the pubdata blob is formed
 #[derive(Debug, Clone, Default)]
-pub(crate) struct PubdataInput {
-    pub(crate) user_logs: Vec<L1MessengerL2ToL1Log>,
-    pub(crate) l2_to_l1_messages: Vec<Vec<u8>>,
-    pub(crate) published_bytecodes: Vec<Vec<u8>>,
-    pub(crate) state_diffs: Vec<StateDiffRecord>,
+pub struct PubdataInput {
+    pub user_logs: Vec<L1MessengerL2ToL1Log>,
+    pub l2_to_l1_messages: Vec<Vec<u8>>,
+    pub published_bytecodes: Vec<Vec<u8>>,
+    pub state_diffs: Vec<StateDiffRecord>,
+    /// Algorithm used to compress state diffs; depends on the protocol version of the L1 batch.
+    pub state_diff_compression: StateDiffCompression,
 }
 
 impl PubdataInput {
-    pub(crate) fn build_pubdata(self, with_uncompressed_state_diffs: bool) -> Vec<u8> {
+    /// Builds pubdata in the specified encoding.
+    pub fn encode(&self, encoding: PubdataEncoding) -> Vec<u8> {
+        encoding.encode(self.build_pubdata(false))
+    }
+
+    /// Builds the full pubdata in the format expected by the `L1Messenger` system contract. Uncompressed state diffs
+    /// are only required by the `L1Messenger` and are not published.
+    pub(crate) fn build_pubdata(&self, with_uncompressed_state_diffs: bool) -> Vec<u8> {
         let mut l1_messenger_pubdata = vec![];
 
         let PubdataInput {
@@ -21,6 +31,7 @@ impl PubdataInput {
             l2_to_l1_messages,
             published_bytecodes,
             state_diffs,
+            state_diff_compression,
         } = self;
 
         // Encoding user L2->L1 logs.
@@ -48,7 +59,7 @@ impl PubdataInput {
 
         // Encoding state diffs
         // Format: `[size of compressed state diffs u32 || compressed state diffs || (# state diffs: intial + repeated) as u32 || sorted state diffs by <index, address, key>]`
-        let state_diffs_compressed = compress_state_diffs(state_diffs.clone());
+        let state_diffs_compressed = state_diff_compression.compress(state_diffs.clone());
         l1_messenger_pubdata.extend(state_diffs_compressed);
 
         if with_uncompressed_state_diffs {
@@ -114,6 +125,7 @@ mod tests {
             l2_to_l1_messages,
             published_bytecodes,
             state_diffs,
+            state_diff_compression: StateDiffCompression::BestValueStrategy,
         };
 
         let pubdata =
@@ -121,4 +133,21 @@ mod tests {
 
         assert_eq!(hex::encode(pubdata), "00000000000000000000000000000000000000000000000000000000000002c700000001000000000000000000000000000000000000000000008001000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000800000000100000004deadbeef0000000100000004aaaabbbb0100002a040001000000000000000000000000000000000000000000000000000000000000007e090e0000000c0901000000020000000000000000000000000000000000008002000000000000000000000000000000000000000000000000000000000000009b000000000000000000000000000000000000000000000000000000000000007d000000000000000c000000000000000000000000000000000000000000000000000000000000000b000000000000000000000000000000000000000000000000000000000000000c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008002000000000000000000000000000000000000000000000000000000000000009c000000000000000000000000000000000000000000000000000000000000007e00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000e000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
     }
+
+    #[test]
+    fn pubdata_encodings() {
+        let input = PubdataInput {
+            l2_to_l1_messages: vec![hex::decode("deadbeef").unwrap()],
+            published_bytecodes: vec![hex::decode("aaaabbbb").unwrap()],
+            ..PubdataInput::default()
+        };
+        let full_pubdata = input.build_pubdata(false);
+
+        assert_eq!(input.encode(PubdataEncoding::Rollup), full_pubdata);
+        assert_eq!(input.encode(PubdataEncoding::Custom), full_pubdata);
+        assert_eq!(
+            input.encode(PubdataEncoding::Validium),
+            zksync_types::web3::keccak256(&full_pubdata)
+        );
+    }
 }
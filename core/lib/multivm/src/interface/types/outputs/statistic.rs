@@ -1,4 +1,11 @@
-use zksync_types::circuit::CircuitStatistic;
+use std::collections::BTreeMap;
+
+use zksync_types::{
+    circuit::{CircuitCycleStatistic, CircuitStatistic},
+    Address,
+};
+
+use crate::interface::tracer::OpcodeKind;
 
 /// Statistics of the tx execution.
 #[derive(Debug, Default, Clone)]
@@ -16,7 +23,95 @@ pub struct VmExecutionStatistics {
     /// Number of log queries produced by the VM during the tx execution.
     pub total_log_queries: usize,
     pub pubdata_published: u32,
+    /// Estimated number of circuits used by the tx, per circuit type.
     pub circuit_statistic: CircuitStatistic,
+    /// Number of cycles used by the tx, per circuit type. Unlike `circuit_statistic`, these are raw counts
+    /// not divided by the circuit capacities. Only collected by the VM versions supporting circuit tracing;
+    /// zeroed for other versions.
+    pub circuit_cycles: CircuitCycleStatistic,
+    /// Per-opcode execution histogram. Only collected if opcode profiling is enabled in `SystemEnv`
+    /// and the VM version supports it.
+    pub opcode_histogram: Option<OpcodeHistogram>,
+}
+
+/// Execution statistics for a single [`OpcodeKind`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct OpcodeStatistic {
+    /// Number of times the opcode was executed.
+    pub count: u64,
+    /// Total computational gas charged for the opcode executions. Doesn't include gas passed
+    /// to the far calls, decommitment and memory growth costs.
+    pub gas_used: u64,
+}
+
+/// Opcode-frequency and gas-per-opcode histogram.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OpcodeHistogram(BTreeMap<OpcodeKind, OpcodeStatistic>);
+
+impl OpcodeHistogram {
+    pub fn record(&mut self, kind: OpcodeKind, gas_used: u32) {
+        let entry = self.0.entry(kind).or_default();
+        entry.count += 1;
+        entry.gas_used += u64::from(gas_used);
+    }
+
+    pub fn get(&self, kind: OpcodeKind) -> OpcodeStatistic {
+        self.0.get(&kind).copied().unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (OpcodeKind, OpcodeStatistic)> + '_ {
+        self.0.iter().map(|(&kind, &stat)| (kind, stat))
+    }
+
+    /// Total number of executed opcodes.
+    pub fn total_count(&self) -> u64 {
+        self.0.values().map(|stat| stat.count).sum()
+    }
+}
+
+/// Gas consumed by each contract during execution, keyed by the address of the contract in whose context
+/// the gas was spent (i.e., delegate calls are attributed to the caller). Gas passed to the callee in a far call
+/// is not attributed to the caller unless the callee burns it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ContractGasUsage(BTreeMap<Address, u64>);
+
+impl ContractGasUsage {
+    pub fn record(&mut self, address: Address, gas_used: u32) {
+        if gas_used > 0 {
+            *self.0.entry(address).or_default() += u64::from(gas_used);
+        }
+    }
+
+    /// Adds gas usage from `other` to this usage, e.g. to aggregate usage for the entire batch.
+    pub fn merge(&mut self, other: &Self) {
+        for (&address, &gas_used) in &other.0 {
+            *self.0.entry(address).or_default() += gas_used;
+        }
+    }
+
+    pub fn get(&self, address: &Address) -> u64 {
+        self.0.get(address).copied().unwrap_or_default()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Address, u64)> + '_ {
+        self.0
+            .iter()
+            .map(|(&address, &gas_used)| (address, gas_used))
+    }
+
+    /// Returns up to `count` contracts that consumed the most gas, ordered by the consumed gas descending.
+    pub fn top(&self, count: usize) -> Vec<(Address, u64)> {
+        let mut usage: Vec<_> = self.iter().collect();
+        usage.sort_unstable_by(|(address, gas), (other_address, other_gas)| {
+            other_gas.cmp(gas).then_with(|| address.cmp(other_address))
+        });
+        usage.truncate(count);
+        usage
+    }
+
+    pub fn total_gas_used(&self) -> u64 {
+        self.0.values().sum()
+    }
 }
 
 /// Oracle metrics of the VM.
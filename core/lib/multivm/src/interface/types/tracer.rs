@@ -1,3 +1,5 @@
+use zksync_types::{zk_evm_types::FarCallOpcode, Address, U256};
+
 use crate::interface::Halt;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -37,3 +39,71 @@ pub enum VmExecutionStopReason {
     VmFinished,
     TracerRequestedStop(TracerExecutionStopReason),
 }
+
+/// Version-agnostic kind of an executed opcode.
+///
+/// Mirrors the top-level opcode variants, which are stable across all supported VM versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OpcodeKind {
+    Invalid,
+    Nop,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Jump,
+    Context,
+    Shift,
+    Binop,
+    Ptr,
+    NearCall,
+    Log,
+    FarCall,
+    Ret,
+    Uma,
+}
+
+/// Information about an opcode that is about to be executed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpcodeEvent {
+    pub kind: OpcodeKind,
+    /// Program counter of the opcode.
+    pub pc: u16,
+    /// Address of the contract that executes the opcode.
+    pub contract_address: Address,
+    /// Ergs remaining in the current frame before the opcode is executed.
+    pub ergs_remaining: u32,
+    /// Depth of the call stack, including near call frames.
+    pub call_depth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StorageAccessKind {
+    Read,
+    Write,
+    /// Transient storage is only supported starting from VM 1.5.0.
+    TransientRead,
+    TransientWrite,
+}
+
+/// Information about a storage slot access that is about to be performed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StorageAccessEvent {
+    pub kind: StorageAccessKind,
+    pub address: Address,
+    pub key: U256,
+    /// Value being written; `None` for reads.
+    pub written_value: Option<U256>,
+}
+
+/// Information about a far call that is about to be performed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FarCallEvent {
+    pub far_call: FarCallOpcode,
+    pub caller: Address,
+    pub callee: Address,
+    /// Ergs requested to be passed to the callee.
+    pub ergs_passed: u32,
+    /// Depth of the call stack of the caller, including near call frames.
+    pub call_depth: usize,
+}
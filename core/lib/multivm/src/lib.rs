@@ -15,11 +15,13 @@ pub use crate::{
         history_mode::HistoryMode,
         tracers::{MultiVMTracer, MultiVmTracerPointer},
     },
+    shadow::{DivergenceHandling, ShadowVm},
     vm_instance::VmInstance,
 };
 
 mod glue;
 pub mod interface;
+mod shadow;
 pub mod tracers;
 pub mod utils;
 pub mod versions;
@@ -0,0 +1,291 @@
+//! VM wrapper executing every batch by two VM implementations and comparing their outputs.
+
+use std::{fmt, marker::PhantomData};
+
+use zksync_state::StoragePtr;
+use zksync_types::{L1BatchNumber, Transaction, H256};
+
+use crate::{
+    interface::{
+        BytecodeCompressionError, CurrentExecutionState, FinishedL1Batch, L1BatchEnv, L2BlockEnv,
+        SystemEnv, VmExecutionMode, VmExecutionResultAndLogs, VmInterface,
+        VmInterfaceHistoryEnabled,
+    },
+    vm_latest::HistoryEnabled,
+    HistoryMode,
+};
+
+/// Action taken by [`ShadowVm`] once the main and the shadow VM outputs diverge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DivergenceHandling {
+    /// Log the divergence and continue execution with the main VM only. The shadow VM is dropped,
+    /// since its state cannot be compared with the main VM state afterwards.
+    #[default]
+    Continue,
+    /// Panic on the first divergence.
+    Halt,
+}
+
+#[derive(Debug, Default)]
+struct DivergenceErrors(Vec<String>);
+
+impl DivergenceErrors {
+    fn check_match<T: fmt::Debug + PartialEq>(&mut self, context: &str, main: &T, shadow: &T) {
+        if main != shadow {
+            self.0.push(format!(
+                "{context} mismatch: main = {main:?}, shadow = {shadow:?}"
+            ));
+        }
+    }
+
+    fn check_results(
+        &mut self,
+        main: &VmExecutionResultAndLogs,
+        shadow: &VmExecutionResultAndLogs,
+    ) {
+        self.check_match("result", &main.result, &shadow.result);
+        self.check_match("logs", &main.logs, &shadow.logs);
+        self.check_match(
+            "gas_refunded",
+            &main.refunds.gas_refunded,
+            &shadow.refunds.gas_refunded,
+        );
+        self.check_match(
+            "operator_suggested_refund",
+            &main.refunds.operator_suggested_refund,
+            &shadow.refunds.operator_suggested_refund,
+        );
+        self.check_match(
+            "gas_used",
+            &main.statistics.gas_used,
+            &shadow.statistics.gas_used,
+        );
+    }
+
+    fn check_execution_states(
+        &mut self,
+        main: &CurrentExecutionState,
+        shadow: &CurrentExecutionState,
+    ) {
+        self.check_match("final_execution_state", main, shadow);
+    }
+}
+
+/// VM executing all operations by the `Main` VM and mirroring them to the `Shadow` VM (e.g., a new VM backend
+/// being rolled out). Execution results and the final [`CurrentExecutionState`] of both VMs are compared;
+/// the outputs of the main VM are always returned.
+///
+/// The VMs must use separate storages based on the same state, since VMs write to the storage during execution.
+/// Custom tracers are only applied to the main VM.
+#[derive(Debug)]
+pub struct ShadowVm<S, H, Main, Shadow> {
+    main: Main,
+    shadow: Option<Shadow>,
+    divergence_handling: DivergenceHandling,
+    l1_batch_number: L1BatchNumber,
+    /// Hash of the last pushed transaction; used to report divergences.
+    last_tx_hash: Option<H256>,
+    _types: PhantomData<(S, H)>,
+}
+
+impl<S, H, Main, Shadow> ShadowVm<S, H, Main, Shadow>
+where
+    H: HistoryMode,
+    Main: VmInterface<S, H>,
+    Shadow: VmInterface<S, H>,
+{
+    pub fn new(
+        batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+        main_storage: StoragePtr<S>,
+        shadow_storage: StoragePtr<S>,
+        divergence_handling: DivergenceHandling,
+    ) -> Self {
+        let l1_batch_number = batch_env.number;
+        let shadow = Shadow::new(batch_env.clone(), system_env.clone(), shadow_storage);
+        let main = Main::new(batch_env, system_env, main_storage);
+        Self {
+            main,
+            shadow: Some(shadow),
+            divergence_handling,
+            l1_batch_number,
+            last_tx_hash: None,
+            _types: PhantomData,
+        }
+    }
+
+    /// Returns the main VM.
+    pub fn main(&self) -> &Main {
+        &self.main
+    }
+
+    /// Checks whether the shadow VM is still running, i.e., no divergences were detected so far.
+    pub fn is_shadow_active(&self) -> bool {
+        self.shadow.is_some()
+    }
+
+    fn report(&mut self, errors: DivergenceErrors, tx_hash: Option<H256>) {
+        if errors.0.is_empty() {
+            return;
+        }
+
+        let location = match tx_hash {
+            Some(tx_hash) => format!("transaction {tx_hash:?}"),
+            None => "batch tip".to_owned(),
+        };
+        let message = format!(
+            "VM divergence in L1 batch #{} at {location}:\n{}",
+            self.l1_batch_number,
+            errors.0.join("\n")
+        );
+        match self.divergence_handling {
+            DivergenceHandling::Halt => panic!("{message}"),
+            DivergenceHandling::Continue => {
+                tracing::error!("{message}");
+                self.shadow = None;
+            }
+        }
+    }
+
+    pub fn push_transaction(&mut self, tx: Transaction) {
+        self.last_tx_hash = Some(tx.hash());
+        if let Some(shadow) = &mut self.shadow {
+            shadow.push_transaction(tx.clone());
+        }
+        self.main.push_transaction(tx);
+    }
+
+    pub fn execute(&mut self, execution_mode: VmExecutionMode) -> VmExecutionResultAndLogs {
+        self.inspect(Main::TracerDispatcher::default(), execution_mode)
+    }
+
+    /// Executes the next VM step with custom tracers applied to the main VM.
+    pub fn inspect(
+        &mut self,
+        dispatcher: Main::TracerDispatcher,
+        execution_mode: VmExecutionMode,
+    ) -> VmExecutionResultAndLogs {
+        let main_result = self.main.inspect(dispatcher, execution_mode);
+        if let Some(shadow) = &mut self.shadow {
+            let shadow_result = shadow.execute(execution_mode);
+            let mut errors = DivergenceErrors::default();
+            errors.check_results(&main_result, &shadow_result);
+            let tx_hash = match execution_mode {
+                VmExecutionMode::Bootloader | VmExecutionMode::Batch => None,
+                VmExecutionMode::OneTx => self.last_tx_hash,
+            };
+            self.report(errors, tx_hash);
+        }
+        main_result
+    }
+
+    pub fn start_new_l2_block(&mut self, l2_block_env: L2BlockEnv) {
+        if let Some(shadow) = &mut self.shadow {
+            shadow.start_new_l2_block(l2_block_env);
+        }
+        self.main.start_new_l2_block(l2_block_env);
+    }
+
+    pub fn execute_transaction_with_bytecode_compression(
+        &mut self,
+        tx: Transaction,
+        with_compression: bool,
+    ) -> (
+        Result<(), BytecodeCompressionError>,
+        VmExecutionResultAndLogs,
+    ) {
+        self.inspect_transaction_with_bytecode_compression(
+            Main::TracerDispatcher::default(),
+            tx,
+            with_compression,
+        )
+    }
+
+    /// Executes a transaction with custom tracers applied to the main VM.
+    pub fn inspect_transaction_with_bytecode_compression(
+        &mut self,
+        dispatcher: Main::TracerDispatcher,
+        tx: Transaction,
+        with_compression: bool,
+    ) -> (
+        Result<(), BytecodeCompressionError>,
+        VmExecutionResultAndLogs,
+    ) {
+        let tx_hash = tx.hash();
+        self.last_tx_hash = Some(tx_hash);
+        let shadow_output = self.shadow.as_mut().map(|shadow| {
+            shadow.execute_transaction_with_bytecode_compression(tx.clone(), with_compression)
+        });
+        let main_output = self.main.inspect_transaction_with_bytecode_compression(
+            dispatcher,
+            tx,
+            with_compression,
+        );
+
+        if let Some((shadow_compression_result, shadow_result)) = shadow_output {
+            let mut errors = DivergenceErrors::default();
+            errors.check_match(
+                "bytecode_compression_result",
+                &main_output.0.is_ok(),
+                &shadow_compression_result.is_ok(),
+            );
+            errors.check_results(&main_output.1, &shadow_result);
+            self.report(errors, Some(tx_hash));
+        }
+        main_output
+    }
+
+    pub fn get_current_execution_state(&self) -> CurrentExecutionState {
+        self.main.get_current_execution_state()
+    }
+
+    pub fn gas_remaining(&self) -> u32 {
+        self.main.gas_remaining()
+    }
+
+    /// Finishes the batch in both VMs and compares the batch tip results and final execution states.
+    pub fn finish_batch(&mut self) -> FinishedL1Batch {
+        let main_batch = self.main.finish_batch();
+        if let Some(shadow) = &mut self.shadow {
+            let shadow_batch = shadow.finish_batch();
+            let mut errors = DivergenceErrors::default();
+            errors.check_results(
+                &main_batch.block_tip_execution_result,
+                &shadow_batch.block_tip_execution_result,
+            );
+            errors.check_execution_states(
+                &main_batch.final_execution_state,
+                &shadow_batch.final_execution_state,
+            );
+            self.report(errors, None);
+        }
+        main_batch
+    }
+}
+
+impl<S, Main, Shadow> ShadowVm<S, HistoryEnabled, Main, Shadow>
+where
+    Main: VmInterfaceHistoryEnabled<S>,
+    Shadow: VmInterfaceHistoryEnabled<S>,
+{
+    pub fn make_snapshot(&mut self) {
+        if let Some(shadow) = &mut self.shadow {
+            shadow.make_snapshot();
+        }
+        self.main.make_snapshot();
+    }
+
+    pub fn rollback_to_the_latest_snapshot(&mut self) {
+        if let Some(shadow) = &mut self.shadow {
+            shadow.rollback_to_the_latest_snapshot();
+        }
+        self.main.rollback_to_the_latest_snapshot();
+    }
+
+    pub fn pop_snapshot_no_rollback(&mut self) {
+        if let Some(shadow) = &mut self.shadow {
+            shadow.pop_snapshot_no_rollback();
+        }
+        self.main.pop_snapshot_no_rollback();
+    }
+}
@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
-use zksync_types::vm_trace::Call;
+use zksync_types::{
+    vm_trace::{Call, CallType},
+    Address,
+};
 
 use crate::{glue::tracers::IntoOldVmTracer, tracers::call_tracer::metrics::CALL_METRICS};
 
@@ -17,6 +20,8 @@ pub mod vm_virtual_blocks;
 pub struct CallTracer {
     stack: Vec<FarcallAndNearCallCount>,
     result: Arc<OnceCell<Vec<Call>>>,
+    trace_near_calls: bool,
+    collapse_system_frames: bool,
 
     max_stack_depth: usize,
     max_near_calls: usize,
@@ -41,16 +46,37 @@ impl CallTracer {
         Self {
             stack: vec![],
             result,
+            trace_near_calls: false,
+            collapse_system_frames: false,
             max_stack_depth: 0,
             max_near_calls: 0,
         }
     }
 
+    /// Includes near call frames (with the [`CallType::NearCall`] type) into the output.
+    /// Not supported by VMs predating custom tracers.
+    pub fn with_near_calls(mut self) -> Self {
+        self.trace_near_calls = true;
+        self
+    }
+
+    /// Removes frames executed by system contracts from the output; their subcalls are attached
+    /// to the closest non-system frame instead. Not supported by VMs predating custom tracers.
+    pub fn with_collapsed_system_frames(mut self) -> Self {
+        self.collapse_system_frames = true;
+        self
+    }
+
     fn extract_result(&mut self) -> Vec<Call> {
-        std::mem::take(&mut self.stack)
+        let calls = std::mem::take(&mut self.stack)
             .into_iter()
             .map(|x| x.farcall)
-            .collect()
+            .collect();
+        if self.collapse_system_frames {
+            collapse_system_frames(calls)
+        } else {
+            calls
+        }
     }
 
     fn store_result(&mut self) {
@@ -80,6 +106,50 @@ impl CallTracer {
         self.max_near_calls = self.max_near_calls.max(near_calls_after);
     }
 
+    fn handle_near_call(&mut self, address: Address, gas: u64, parent_gas: u64) {
+        // Near calls are only traced inside the frame of the same contract; this filters out near calls
+        // performed by the bootloader after the top-level far call has finished.
+        let is_in_traced_frame = self
+            .stack
+            .last()
+            .is_some_and(|frame| frame.farcall.to == address);
+        if !self.trace_near_calls || !is_in_traced_frame {
+            self.increase_near_call_count();
+            return;
+        }
+
+        let near_call = Call {
+            r#type: CallType::NearCall,
+            from: address,
+            to: address,
+            gas,
+            parent_gas,
+            ..Call::default()
+        };
+        self.push_call_and_update_stats(near_call, 0);
+    }
+
+    /// Finishes the topmost frame if it is a traced near call. Returns `false` if the topmost frame is not
+    /// a near call, i.e. the return should be handled as a return from a far call.
+    fn finish_near_call(&mut self, ergs_remaining: u64, error: Option<&str>) -> bool {
+        let is_near_call = self.stack.last().is_some_and(|frame| {
+            frame.farcall.r#type == CallType::NearCall && frame.near_calls_after == 0
+        });
+        if !is_near_call {
+            return false;
+        }
+
+        let mut near_call = self.stack.pop().unwrap().farcall;
+        near_call.gas_used = near_call.parent_gas.saturating_sub(ergs_remaining);
+        near_call.error = error.map(str::to_owned);
+        if let Some(parent_call) = self.stack.last_mut() {
+            parent_call.farcall.calls.push(near_call);
+        } else {
+            self.push_call_and_update_stats(near_call, 0);
+        }
+        true
+    }
+
     fn increase_near_call_count(&mut self) {
         if let Some(last) = self.stack.last_mut() {
             last.near_calls_after += 1;
@@ -91,6 +161,25 @@ impl CallTracer {
     }
 }
 
+/// System contracts are deployed in the kernel space, i.e. at addresses below 2^16.
+fn is_system_contract(address: &Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
+
+fn collapse_system_frames(calls: Vec<Call>) -> Vec<Call> {
+    calls
+        .into_iter()
+        .flat_map(|mut call| {
+            call.calls = collapse_system_frames(std::mem::take(&mut call.calls));
+            if is_system_contract(&call.to) {
+                call.calls
+            } else {
+                vec![call]
+            }
+        })
+        .collect()
+}
+
 impl IntoOldVmTracer for CallTracer {
     fn old_tracer(&self) -> crate::tracers::old_tracers::OldTracers {
         crate::tracers::old_tracers::OldTracers::CallTracer(self.result.clone())
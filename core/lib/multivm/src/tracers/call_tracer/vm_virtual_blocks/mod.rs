@@ -32,7 +32,19 @@ impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for CallTracer {
     ) {
         match data.opcode.variant.opcode {
             Opcode::NearCall(_) => {
-                self.increase_near_call_count();
+                let current = &state.vm_local_state.callstack.current;
+                let parent_gas = state
+                    .vm_local_state
+                    .callstack
+                    .inner
+                    .last()
+                    .map(|call| call.ergs_remaining + current.ergs_remaining)
+                    .unwrap_or(current.ergs_remaining) as u64;
+                self.handle_near_call(
+                    current.this_address,
+                    current.ergs_remaining as u64,
+                    parent_gas,
+                );
             }
             Opcode::FarCall(far_call) => {
                 // We use parent gas for properly calculating gas used in the trace.
@@ -181,6 +193,18 @@ impl CallTracer {
         memory: &SimpleMemory<H>,
         ret_opcode: RetOpcode,
     ) {
+        let near_call_error = match ret_opcode {
+            RetOpcode::Ok => None,
+            RetOpcode::Revert => Some("Revert"),
+            RetOpcode::Panic => Some("Panic"),
+        };
+        if self.finish_near_call(
+            state.vm_local_state.callstack.current.ergs_remaining as u64,
+            near_call_error,
+        ) {
+            return;
+        }
+
         let Some(mut current_call) = self.stack.pop() else {
             return;
         };
@@ -0,0 +1,53 @@
+use std::time::{Duration, Instant};
+
+use crate::{glue::tracers::IntoOldVmTracer, interface::Halt};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer aborting the VM execution once the wall-clock deadline has passed.
+///
+/// The deadline is only enforced by VM versions starting from `vm_refunds_enhancement`, since older versions
+/// cannot abort execution with a custom halt reason.
+#[derive(Debug, Clone)]
+pub struct ExecutionDeadline {
+    deadline: Instant,
+    cycles_since_check: u32,
+}
+
+impl ExecutionDeadline {
+    /// Number of VM cycles between checking the current time, so that querying the clock doesn't slow down execution.
+    const CYCLES_PER_CHECK: u32 = 1_024;
+    const HALT_REASON: &'static str = "Execution deadline exceeded";
+
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + timeout,
+            cycles_since_check: 0,
+        }
+    }
+
+    /// Checks whether the execution was aborted by this tracer.
+    pub fn is_exceeded(halt: &Halt) -> bool {
+        matches!(halt, Halt::TracerCustom(reason) if reason == Self::HALT_REASON)
+    }
+
+    fn should_abort(&mut self) -> bool {
+        self.cycles_since_check += 1;
+        if self.cycles_since_check < Self::CYCLES_PER_CHECK {
+            return false;
+        }
+        self.cycles_since_check = 0;
+        Instant::now() >= self.deadline
+    }
+
+    fn halt() -> Halt {
+        Halt::TracerCustom(Self::HALT_REASON.to_owned())
+    }
+}
+
+impl IntoOldVmTracer for ExecutionDeadline {}
@@ -1,11 +1,19 @@
 pub mod call_tracer;
+pub mod execution_deadline;
 mod multivm_dispatcher;
 pub mod old_tracers;
 pub mod prestate_tracer;
+pub mod storage_access;
 pub mod storage_invocation;
+pub mod storage_watchpoint;
 pub mod validator;
+pub mod versioned;
 
 pub use call_tracer::CallTracer;
+pub use execution_deadline::ExecutionDeadline;
 pub use multivm_dispatcher::TracerDispatcher;
 pub use prestate_tracer::PrestateTracer;
+pub use storage_access::StorageAccessTracer;
 pub use storage_invocation::StorageInvocations;
+pub use storage_watchpoint::{StorageWatchpointTracer, WatchedStorageAccess};
+pub use versioned::VersionedTracerAdapter;
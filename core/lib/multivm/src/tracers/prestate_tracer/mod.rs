@@ -8,8 +8,11 @@ use zksync_types::{
 };
 use zksync_utils::{address_to_h256, h256_to_u256};
 
+use crate::glue::tracers::IntoOldVmTracer;
+
 pub mod vm_1_4_1;
 pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
 pub mod vm_latest;
 pub mod vm_refunds_enhancement;
 pub mod vm_virtual_blocks;
@@ -45,8 +48,13 @@ impl fmt::Display for Account {
     }
 }
 
-type State = HashMap<Address, Account>;
+pub type State = HashMap<Address, Account>;
 
+/// Tracer collecting the state of accounts touched during execution, similar to the geth `prestateTracer`.
+///
+/// In the default mode, the tracer returns pre-images of all touched accounts (i.e., their state
+/// in the underlying VM storage) as the first element of the result; the second element is empty. In the diff mode,
+/// the tracer returns the state of modified accounts before and after execution.
 #[derive(Debug, Clone)]
 pub struct PrestateTracer {
     pub pre: State,
@@ -66,6 +74,9 @@ impl PrestateTracer {
     }
 }
 
+/// Old VMs (pre-virtual blocks) are not supported by the tracer.
+impl IntoOldVmTracer for PrestateTracer {}
+
 #[derive(Debug, Clone)]
 pub struct PrestateTracerConfig {
     diff_mode: bool,
@@ -128,7 +139,47 @@ fn get_storage_if_present(
         .collect()
 }
 
-fn process_result(result: &Arc<OnceCell<(State, State)>>, mut pre: State, post: State) {
+/// Collects pre-images of all accounts touched during execution. Relies on the storage caching the initial values
+/// of all read or written slots, which holds for [`StorageView`](zksync_state::StorageView).
+fn collect_prestate<S: WriteStorage>(storage: &StoragePtr<S>) -> State {
+    let mut storage = storage.borrow_mut();
+    let initial_values = storage.read_storage_keys().clone();
+    let mut prestate = State::new();
+    for key in initial_values.keys() {
+        let address = *key.account().address();
+        if prestate.contains_key(&address) {
+            continue;
+        }
+        // Slots not touched during execution are unchanged, so their current value is the initial one.
+        let mut read_initial = |key: StorageKey| {
+            let value = initial_values
+                .get(&key)
+                .copied()
+                .unwrap_or_else(|| storage.read_value(&key));
+            h256_to_u256(value)
+        };
+        let account = Account {
+            balance: Some(read_initial(get_balance_key(key.account()))),
+            code: Some(read_initial(get_code_key(&address))),
+            nonce: Some(read_initial(get_nonce_key(&address))),
+            storage: Some(get_storage_if_present(key.account(), &initial_values)),
+        };
+        prestate.insert(address, account);
+    }
+    prestate
+}
+
+fn process_result(
+    result: &Arc<OnceCell<(State, State)>>,
+    diff_mode: bool,
+    mut pre: State,
+    post: State,
+) {
+    if !diff_mode {
+        result.set((pre, post)).unwrap();
+        return;
+    }
+
     pre.retain(|k, v| {
         if let Some(post_v) = post.get(k) {
             if v != post_v {
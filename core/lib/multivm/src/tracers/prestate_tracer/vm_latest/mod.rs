@@ -3,8 +3,8 @@ use zksync_state::{StoragePtr, WriteStorage};
 use zksync_types::StorageKey;
 
 use super::{
-    get_account_data, process_modified_storage_keys, process_result, PrestateTracer, State,
-    StorageAccess,
+    collect_prestate, get_account_data, process_modified_storage_keys, process_result,
+    PrestateTracer, State, StorageAccess,
 };
 use crate::{
     interface::dyn_tracers::vm_1_5_0::DynTracer,
@@ -41,15 +41,14 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
                 .map(|k| get_account_data(k.0, state, &modified_storage_keys))
                 .collect::<State>();
         } else {
-            let read_storage_keys = &state.storage.read_storage_keys;
-            let map = read_storage_keys.inner().clone();
-            let res = map
-                .iter()
-                .map(|k| get_account_data(k.0, state, &modified_storage_keys))
-                .collect::<State>();
-            self.post = res;
+            self.pre = collect_prestate(&state.storage.storage.inner().get_ptr());
         }
-        process_result(&self.result, self.pre.clone(), self.post.clone());
+        process_result(
+            &self.result,
+            self.config.diff_mode,
+            self.pre.clone(),
+            self.post.clone(),
+        );
     }
 }
 
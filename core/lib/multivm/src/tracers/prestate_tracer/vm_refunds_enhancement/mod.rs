@@ -1,12 +1,10 @@
-use std::collections::HashMap;
-
 use zk_evm_1_3_3::tracing::{BeforeExecutionData, VmLocalStateData};
 use zksync_state::{StoragePtr, WriteStorage};
-use zksync_types::{StorageKey, H256};
+use zksync_types::StorageKey;
 
 use super::{
-    get_account_data, process_modified_storage_keys, process_result, PrestateTracer, State,
-    StorageAccess,
+    collect_prestate, get_account_data, process_modified_storage_keys, process_result,
+    PrestateTracer, State, StorageAccess,
 };
 use crate::{
     interface::dyn_tracers::vm_1_3_3::DynTracer,
@@ -43,23 +41,14 @@ impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for PrestateTracer {
                 .map(|k| get_account_data(k.0, state, &modified_storage_keys))
                 .collect::<State>();
         } else {
-            let read_keys: &HashMap<StorageKey, H256> = &state
-                .storage
-                .storage
-                .inner()
-                .get_ptr()
-                .borrow()
-                .read_storage_keys()
-                .iter()
-                .map(|(k, v)| (*k, *v))
-                .collect();
-            let res = read_keys
-                .iter()
-                .map(|k| get_account_data(k.0, state, &modified_storage_keys))
-                .collect::<State>();
-            self.post = res;
+            self.pre = collect_prestate(&state.storage.storage.inner().get_ptr());
         }
-        process_result(&self.result, self.pre.clone(), self.post.clone());
+        process_result(
+            &self.result,
+            self.config.diff_mode,
+            self.pre.clone(),
+            self.post.clone(),
+        );
     }
 }
 
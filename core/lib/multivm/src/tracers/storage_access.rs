@@ -0,0 +1,102 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use zksync_types::{
+    web3::{AccessList, AccessListItem},
+    Address, H256,
+};
+use zksync_utils::u256_to_h256;
+
+use crate::interface::{
+    tracer::{StorageAccessEvent, StorageAccessKind},
+    VersionedTracer,
+};
+
+/// Tracer collecting all storage slots read or written during execution, e.g. to build an access list
+/// for `eth_createAccessList`. Transient storage accesses are ignored; accesses in the frames that were
+/// reverted later are recorded.
+///
+/// The tracer works with all VM versions supporting custom tracers; wrap it into
+/// [`VersionedTracerAdapter`](crate::tracers::VersionedTracerAdapter) to pass it into the VM.
+#[derive(Debug, Clone, Default)]
+pub struct StorageAccessTracer {
+    accessed_slots: BTreeMap<Address, BTreeSet<H256>>,
+}
+
+impl StorageAccessTracer {
+    /// Returns accessed slots grouped by the contract address.
+    pub fn accessed_slots(&self) -> &BTreeMap<Address, BTreeSet<H256>> {
+        &self.accessed_slots
+    }
+
+    /// Converts accessed slots into an access list. Addresses and slots are sorted in the ascending order.
+    pub fn into_access_list(self) -> AccessList {
+        self.accessed_slots
+            .into_iter()
+            .map(|(address, slots)| AccessListItem {
+                address,
+                storage_keys: slots.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+impl VersionedTracer for StorageAccessTracer {
+    fn on_storage_access(&mut self, event: &StorageAccessEvent) {
+        if matches!(
+            event.kind,
+            StorageAccessKind::Read | StorageAccessKind::Write
+        ) {
+            self.accessed_slots
+                .entry(event.address)
+                .or_default()
+                .insert(u256_to_h256(event.key));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::U256;
+
+    use super::*;
+
+    #[test]
+    fn building_access_list() {
+        let mut tracer = StorageAccessTracer::default();
+        let events = [
+            (StorageAccessKind::Read, Address::repeat_byte(2), 3_u64),
+            (StorageAccessKind::Write, Address::repeat_byte(1), 5),
+            (StorageAccessKind::Read, Address::repeat_byte(1), 1),
+            (StorageAccessKind::Write, Address::repeat_byte(2), 3),
+            (StorageAccessKind::TransientRead, Address::repeat_byte(3), 1),
+            (
+                StorageAccessKind::TransientWrite,
+                Address::repeat_byte(1),
+                2,
+            ),
+        ];
+        for (kind, address, key) in events {
+            tracer.on_storage_access(&StorageAccessEvent {
+                kind,
+                address,
+                key: key.into(),
+                written_value: matches!(kind, StorageAccessKind::Write).then(U256::one),
+            });
+        }
+
+        let access_list = tracer.into_access_list();
+        assert_eq!(
+            access_list,
+            [
+                AccessListItem {
+                    address: Address::repeat_byte(1),
+                    storage_keys: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(5)],
+                },
+                AccessListItem {
+                    address: Address::repeat_byte(2),
+                    storage_keys: vec![H256::from_low_u64_be(3)],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+
+use zksync_types::{Address, StorageKey, U256};
+use zksync_utils::h256_to_u256;
+
+use crate::interface::{
+    tracer::{OpcodeEvent, StorageAccessEvent, StorageAccessKind},
+    VersionedTracer,
+};
+
+/// Access to a watched storage slot recorded by [`StorageWatchpointTracer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WatchedStorageAccess {
+    /// Either [`StorageAccessKind::Read`] or [`StorageAccessKind::Write`].
+    pub kind: StorageAccessKind,
+    pub address: Address,
+    pub key: U256,
+    /// Value being written; `None` for reads.
+    pub written_value: Option<U256>,
+    /// Program counter of the storage access opcode in the executing frame.
+    pub pc: u16,
+    /// Depth of the executing frame, including near call frames.
+    pub call_depth: usize,
+}
+
+/// Tracer recording all reads and writes touching the watched storage slots, together with the frame
+/// performing them. Accesses in the frames that were reverted later are recorded as well.
+///
+/// The tracer works with all VM versions supporting custom tracers; wrap it into
+/// [`VersionedTracerAdapter`](crate::tracers::VersionedTracerAdapter) to pass it into the VM.
+#[derive(Debug, Clone, Default)]
+pub struct StorageWatchpointTracer {
+    watched_slots: HashSet<(Address, U256)>,
+    current_opcode: Option<OpcodeEvent>,
+    accesses: Vec<WatchedStorageAccess>,
+}
+
+impl StorageWatchpointTracer {
+    pub fn new(watched_slots: impl IntoIterator<Item = StorageKey>) -> Self {
+        let watched_slots = watched_slots
+            .into_iter()
+            .map(|key| (*key.address(), h256_to_u256(*key.key())))
+            .collect();
+        Self {
+            watched_slots,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the recorded accesses in the order they were performed.
+    pub fn accesses(&self) -> &[WatchedStorageAccess] {
+        &self.accesses
+    }
+
+    /// Takes the recorded accesses, e.g. to reuse the tracer for the next transaction.
+    pub fn take_accesses(&mut self) -> Vec<WatchedStorageAccess> {
+        std::mem::take(&mut self.accesses)
+    }
+}
+
+impl VersionedTracer for StorageWatchpointTracer {
+    fn on_opcode(&mut self, event: &OpcodeEvent) {
+        self.current_opcode = Some(*event);
+    }
+
+    fn on_storage_access(&mut self, event: &StorageAccessEvent) {
+        if !matches!(
+            event.kind,
+            StorageAccessKind::Read | StorageAccessKind::Write
+        ) || !self.watched_slots.contains(&(event.address, event.key))
+        {
+            return;
+        }
+
+        // `on_opcode()` is always called before `on_storage_access()` for the same opcode.
+        let (pc, call_depth) = self
+            .current_opcode
+            .map_or((0, 0), |opcode| (opcode.pc, opcode.call_depth));
+        self.accesses.push(WatchedStorageAccess {
+            kind: event.kind,
+            address: event.address,
+            key: event.key,
+            written_value: event.written_value,
+            pc,
+            call_depth,
+        });
+    }
+}
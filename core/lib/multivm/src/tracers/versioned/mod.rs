@@ -0,0 +1,52 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{glue::tracers::IntoOldVmTracer, interface::VersionedTracer};
+
+pub mod vm_1_4_1;
+pub mod vm_1_4_2;
+pub mod vm_boojum_integration;
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Adapter converting a [`VersionedTracer`] into tracers for all VM versions supporting custom tracers,
+/// so that it can be used as a [`MultiVMTracer`](crate::MultiVMTracer).
+///
+/// The wrapped tracer is shared between clones of the adapter; to access its state after the execution,
+/// keep a clone of the adapter or of the [shared handle](Self::shared()).
+#[derive(Debug)]
+pub struct VersionedTracerAdapter<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+impl<T> Clone for VersionedTracerAdapter<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T: VersionedTracer> VersionedTracerAdapter<T> {
+    pub fn new(tracer: T) -> Self {
+        Self::from_shared(Arc::new(Mutex::new(tracer)))
+    }
+
+    pub fn from_shared(inner: Arc<Mutex<T>>) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a shared handle to the wrapped tracer.
+    pub fn shared(&self) -> Arc<Mutex<T>> {
+        self.inner.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, T> {
+        // A poisoned mutex means that the tracer has panicked during VM execution, which would
+        // have propagated to the caller anyway.
+        self.inner.lock().expect("versioned tracer is poisoned")
+    }
+}
+
+/// Old VMs don't support custom tracers.
+impl<T> IntoOldVmTracer for VersionedTracerAdapter<T> {}
@@ -0,0 +1,112 @@
+use zk_evm_1_5_0::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{FarCallABI, LogOpcode, Opcode},
+};
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_utils::u256_to_account_address;
+
+use crate::{
+    glue::GlueInto,
+    interface::{
+        dyn_tracers::vm_1_5_0::DynTracer,
+        tracer::{
+            FarCallEvent, OpcodeEvent, OpcodeKind, StorageAccessEvent, StorageAccessKind,
+            VmExecutionStopReason,
+        },
+        VersionedTracer,
+    },
+    tracers::versioned::VersionedTracerAdapter,
+    vm_latest::{BootloaderState, HistoryMode, SimpleMemory, VmTracer, ZkSyncVmState},
+};
+
+pub(crate) fn opcode_kind(opcode: Opcode) -> OpcodeKind {
+    match opcode {
+        Opcode::Invalid(_) => OpcodeKind::Invalid,
+        Opcode::Nop(_) => OpcodeKind::Nop,
+        Opcode::Add(_) => OpcodeKind::Add,
+        Opcode::Sub(_) => OpcodeKind::Sub,
+        Opcode::Mul(_) => OpcodeKind::Mul,
+        Opcode::Div(_) => OpcodeKind::Div,
+        Opcode::Jump(_) => OpcodeKind::Jump,
+        Opcode::Context(_) => OpcodeKind::Context,
+        Opcode::Shift(_) => OpcodeKind::Shift,
+        Opcode::Binop(_) => OpcodeKind::Binop,
+        Opcode::Ptr(_) => OpcodeKind::Ptr,
+        Opcode::NearCall(_) => OpcodeKind::NearCall,
+        Opcode::Log(_) => OpcodeKind::Log,
+        Opcode::FarCall(_) => OpcodeKind::FarCall,
+        Opcode::Ret(_) => OpcodeKind::Ret,
+        Opcode::UMA(_) => OpcodeKind::Uma,
+    }
+}
+
+impl<S, H: HistoryMode, T: VersionedTracer> DynTracer<S, SimpleMemory<H>>
+    for VersionedTracerAdapter<T>
+{
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let current = &state.vm_local_state.callstack.current;
+        let call_depth = state.vm_local_state.callstack.inner.len();
+        let mut tracer = self.lock();
+
+        let opcode = data.opcode.variant.opcode;
+        tracer.on_opcode(&OpcodeEvent {
+            kind: opcode_kind(opcode),
+            pc: current.pc,
+            contract_address: current.this_address,
+            ergs_remaining: current.ergs_remaining,
+            call_depth,
+        });
+
+        let storage_access_kind = match opcode {
+            Opcode::Log(LogOpcode::StorageRead) => Some(StorageAccessKind::Read),
+            Opcode::Log(LogOpcode::StorageWrite) => Some(StorageAccessKind::Write),
+            Opcode::Log(LogOpcode::TransientStorageRead) => Some(StorageAccessKind::TransientRead),
+            Opcode::Log(LogOpcode::TransientStorageWrite) => {
+                Some(StorageAccessKind::TransientWrite)
+            }
+            _ => None,
+        };
+        if let Some(kind) = storage_access_kind {
+            let is_write = matches!(
+                kind,
+                StorageAccessKind::Write | StorageAccessKind::TransientWrite
+            );
+            tracer.on_storage_access(&StorageAccessEvent {
+                kind,
+                address: current.this_address,
+                key: data.src0_value.value,
+                written_value: is_write.then_some(data.src1_value.value),
+            });
+        }
+
+        if let Opcode::FarCall(far_call) = opcode {
+            let far_call_abi = FarCallABI::from_u256(data.src0_value.value);
+            tracer.on_far_call(&FarCallEvent {
+                far_call: far_call.glue_into(),
+                caller: current.this_address,
+                callee: u256_to_account_address(&data.src1_value.value),
+                ergs_passed: far_call_abi.ergs_passed,
+                call_depth,
+            });
+        }
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode, T: VersionedTracer> VmTracer<S, H>
+    for VersionedTracerAdapter<T>
+{
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        stop_reason: VmExecutionStopReason,
+    ) {
+        self.lock().after_vm_execution(&stop_reason);
+    }
+}
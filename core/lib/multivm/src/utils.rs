@@ -264,6 +264,29 @@ pub fn get_bootloader_max_txs_in_batch(version: VmVersion) -> usize {
     }
 }
 
+/// Checks whether a VM of the specified version can speculatively execute transactions, i.e., roll back
+/// to a snapshot without leaving any trace in the VM state (see [`VmInstance::execute_speculatively()`]).
+/// Pre-virtual-blocks VMs keep a part of their state (e.g., compressed bytecodes of the last transaction
+/// and storage invocation limits) outside snapshots, so they can only be rolled back to reject a transaction.
+///
+/// [`VmInstance::execute_speculatively()`]: crate::VmInstance::execute_speculatively()
+pub fn supports_speculative_execution(version: VmVersion) -> bool {
+    match version {
+        VmVersion::M5WithRefunds
+        | VmVersion::M5WithoutRefunds
+        | VmVersion::M6Initial
+        | VmVersion::M6BugWithCompressionFixed
+        | VmVersion::Vm1_3_2 => false,
+        VmVersion::VmVirtualBlocks
+        | VmVersion::VmVirtualBlocksRefundsEnhancement
+        | VmVersion::VmBoojumIntegration
+        | VmVersion::Vm1_4_1
+        | VmVersion::Vm1_4_2
+        | VmVersion::Vm1_5_0SmallBootloaderMemory
+        | VmVersion::Vm1_5_0IncreasedBootloaderMemory => true,
+    }
+}
+
 pub fn gas_bootloader_batch_tip_overhead(version: VmVersion) -> u32 {
     match version {
         VmVersion::M5WithRefunds
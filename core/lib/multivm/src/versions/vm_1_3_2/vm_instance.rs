@@ -451,6 +451,7 @@ impl<H: HistoryMode, S: WriteStorage> VmInstance<S, H> {
             storage_logs: storage_logs.into_iter().map(GlueInto::glue_into).collect(),
             events,
             user_l2_to_l1_logs: l2_to_l1_logs.into_iter().map(UserL2ToL1Log).collect(),
+            storage_reads: None,
             total_log_queries_count: storage_logs_count
                 + log_queries.len()
                 + precompile_calls_count,
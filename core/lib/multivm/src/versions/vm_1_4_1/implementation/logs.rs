@@ -59,6 +59,7 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
                 .into_iter()
                 .map(SystemL2ToL1Log)
                 .collect(),
+            storage_reads: None,
             total_log_queries_count,
         }
     }
@@ -9,8 +9,7 @@ use crate::{
     vm_1_4_2::{
         old_vm::utils::{vm_may_have_ended_inner, VmExecutionResult},
         tracers::{
-            circuits_capacity::circuit_statistic_from_cycles, dispatcher::TracerDispatcher,
-            DefaultExecutionTracer, PubdataTracer, RefundsTracer,
+            dispatcher::TracerDispatcher, DefaultExecutionTracer, PubdataTracer, RefundsTracer,
         },
         vm::Vm,
     },
@@ -87,7 +86,7 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             spent_pubdata_counter_before,
             pubdata_published,
             logs.total_log_queries_count,
-            circuit_statistic_from_cycles(tx_tracer.circuits_tracer.statistics),
+            tx_tracer.circuits_tracer.statistics,
         );
         let result = tx_tracer.result_tracer.into_result();
 
@@ -96,6 +95,8 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             logs,
             statistics,
             refunds,
+            call_traces: None,
+            gas_by_contract: None,
         };
 
         (stop_reason, result)
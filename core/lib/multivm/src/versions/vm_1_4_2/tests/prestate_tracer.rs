@@ -47,7 +47,9 @@ fn test_prestate_tracer() {
         .take()
         .unwrap_or_default();
 
-    assert!(prestate_result.1.contains_key(&contract_address));
+    // In the default mode, only pre-images of touched accounts are returned.
+    assert!(prestate_result.0.contains_key(&contract_address));
+    assert!(prestate_result.1.is_empty());
 }
 
 #[test]
@@ -1,6 +1,6 @@
 use zksync_types::{
     event::L1MessengerL2ToL1Log,
-    writes::{compress_state_diffs, StateDiffRecord},
+    writes::{StateDiffCompression, StateDiffRecord},
 };
 
 /// Struct based on which the pubdata blob is formed
@@ -48,7 +48,9 @@ impl PubdataInput {
 
         // Encoding state diffs
         // Format: `[size of compressed state diffs u32 || compressed state diffs || (# state diffs: intial + repeated) as u32 || sorted state diffs by <index, address, key>]`
-        let state_diffs_compressed = compress_state_diffs(state_diffs.clone());
+        // This VM is only used by protocol versions compressing state diffs with the original algorithm.
+        let state_diffs_compressed =
+            StateDiffCompression::BestValueStrategy.compress(state_diffs.clone());
         l1_messenger_pubdata.extend(state_diffs_compressed);
 
         if with_uncompressed_state_diffs {
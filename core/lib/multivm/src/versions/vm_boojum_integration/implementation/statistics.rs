@@ -1,10 +1,13 @@
 use zk_evm_1_4_0::aux_structures::Timestamp;
 use zksync_state::WriteStorage;
-use zksync_types::{circuit::CircuitStatistic, U256};
+use zksync_types::{circuit::CircuitCycleStatistic, U256};
 
 use crate::{
     interface::{VmExecutionStatistics, VmMemoryMetrics},
-    vm_boojum_integration::{tracers::DefaultExecutionTracer, vm::Vm},
+    vm_boojum_integration::{
+        tracers::{circuits_capacity::circuit_statistic_from_cycles, DefaultExecutionTracer},
+        vm::Vm,
+    },
     HistoryMode,
 };
 
@@ -24,7 +27,7 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
         spent_pubdata_counter_before: u32,
         pubdata_published: u32,
         total_log_queries_count: usize,
-        circuit_statistic: CircuitStatistic,
+        circuit_cycles: CircuitCycleStatistic,
     ) -> VmExecutionStatistics {
         let computational_gas_used = self.calculate_computational_gas_used(
             tracer,
@@ -42,7 +45,9 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             computational_gas_used,
             total_log_queries: total_log_queries_count,
             pubdata_published,
-            circuit_statistic,
+            circuit_statistic: circuit_statistic_from_cycles(circuit_cycles),
+            circuit_cycles,
+            opcode_histogram: None,
         }
     }
 
@@ -6,7 +6,7 @@ use zksync_utils::bytecode::CompressedBytecodeInfo;
 
 use super::{tx::BootloaderTx, utils::apply_pubdata_to_memory};
 use crate::{
-    interface::{BootloaderMemory, L2BlockEnv, TxExecutionMode},
+    interface::{BootloaderMemory, L2BlockEnv, PubdataInput, TxExecutionMode},
     vm_latest::{
         bootloader_state::{
             l2_block::BootloaderL2Block,
@@ -14,7 +14,7 @@ use crate::{
             utils::{apply_l2_block, apply_tx_to_memory},
         },
         constants::TX_DESCRIPTION_OFFSET,
-        types::internals::{PubdataInput, TransactionData},
+        types::internals::TransactionData,
         utils::l2_blocks::assert_next_block,
     },
 };
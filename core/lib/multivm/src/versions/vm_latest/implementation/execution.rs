@@ -1,16 +1,20 @@
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
 use zk_evm_1_5_0::aux_structures::Timestamp;
 use zksync_state::WriteStorage;
+use zksync_types::writes::StateDiffCompression;
 
 use crate::{
     interface::{
         types::tracer::{TracerExecutionStatus, VmExecutionStopReason},
         VmExecutionMode, VmExecutionResultAndLogs, VmInterface,
     },
+    tracers::CallTracer,
     vm_latest::{
         old_vm::utils::{vm_may_have_ended_inner, VmExecutionResult},
         tracers::{
-            circuits_capacity::circuit_statistic_from_cycles, dispatcher::TracerDispatcher,
-            DefaultExecutionTracer, PubdataTracer, RefundsTracer,
+            dispatcher::TracerDispatcher, DefaultExecutionTracer, PubdataTracer, RefundsTracer,
         },
         vm::Vm,
     },
@@ -51,6 +55,10 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
     ) -> (VmExecutionStopReason, VmExecutionResultAndLogs) {
         let refund_tracers = with_refund_tracer
             .then_some(RefundsTracer::new(self.batch_env.clone(), self.subversion));
+        let call_traces = self
+            .system_env
+            .enable_call_tracing
+            .then(|| Arc::new(OnceCell::new()));
         let mut tx_tracer: DefaultExecutionTracer<S, H::Vm1_5_0> = DefaultExecutionTracer::new(
             self.system_env.default_validation_computational_gas_limit,
             execution_mode,
@@ -62,9 +70,13 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
                     self.batch_env.clone(),
                     execution_mode,
                     self.subversion,
+                    StateDiffCompression::for_protocol_version(self.system_env.version),
                 ))
             }),
             self.subversion,
+            self.system_env.enable_opcode_profiling,
+            call_traces.clone().map(CallTracer::new),
+            self.system_env.enable_gas_attribution,
         );
 
         let timestamp_initial = Timestamp(self.state.local_state.timestamp);
@@ -90,15 +102,30 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             gas_remaining_after,
             pubdata_published,
             logs.total_log_queries_count,
-            circuit_statistic_from_cycles(tx_tracer.circuits_tracer.statistics),
+            tx_tracer.circuits_tracer.statistics,
+            tx_tracer.opcode_histogram.take(),
         );
+        let gas_by_contract = tx_tracer
+            .gas_attribution_tracer
+            .take()
+            .map(|tracer| tracer.usage);
         let result = tx_tracer.result_tracer.into_result();
+        // The call tracer must be dropped before taking ownership of the traces it has stored.
+        drop(tx_tracer.call_tracer.take());
+        let call_traces = call_traces.map(|cell| {
+            Arc::into_inner(cell)
+                .expect("call tracer is dropped")
+                .into_inner()
+                .unwrap_or_default()
+        });
 
         let result = VmExecutionResultAndLogs {
             result,
             logs,
             statistics,
             refunds,
+            call_traces,
+            gas_by_contract,
         };
 
         (stop_reason, result)
@@ -26,6 +26,11 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             .map(|log| **log)
             .collect();
         let storage_logs_count = storage_logs.len();
+        let storage_reads = self
+            .state
+            .storage
+            .storage_reads_after_timestamp(from_timestamp)
+            .map(|reads| reads.iter().copied().map(GlueInto::glue_into).collect());
 
         let (events, system_l2_to_l1_logs) =
             self.collect_events_and_l1_system_logs_after_timestamp(from_timestamp);
@@ -59,6 +64,7 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
                 .into_iter()
                 .map(SystemL2ToL1Log)
                 .collect(),
+            storage_reads,
             total_log_queries_count,
         }
     }
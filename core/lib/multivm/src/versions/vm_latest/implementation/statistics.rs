@@ -1,10 +1,10 @@
 use zk_evm_1_5_0::aux_structures::Timestamp;
 use zksync_state::WriteStorage;
-use zksync_types::{circuit::CircuitStatistic, U256};
+use zksync_types::{circuit::CircuitCycleStatistic, U256};
 
 use crate::{
-    interface::{VmExecutionStatistics, VmMemoryMetrics},
-    vm_latest::vm::Vm,
+    interface::{OpcodeHistogram, VmExecutionStatistics, VmMemoryMetrics},
+    vm_latest::{tracers::circuits_capacity::circuit_statistic_from_cycles, vm::Vm},
     HistoryMode,
 };
 
@@ -22,7 +22,8 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
         gas_remaining_after: u32,
         pubdata_published: u32,
         total_log_queries_count: usize,
-        circuit_statistic: CircuitStatistic,
+        circuit_cycles: CircuitCycleStatistic,
+        opcode_histogram: Option<OpcodeHistogram>,
     ) -> VmExecutionStatistics {
         let computational_gas_used = self.calculate_computational_gas_used(gas_remaining_before);
         VmExecutionStatistics {
@@ -36,7 +37,9 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             computational_gas_used,
             total_log_queries: total_log_queries_count,
             pubdata_published,
-            circuit_statistic,
+            circuit_statistic: circuit_statistic_from_cycles(circuit_cycles),
+            circuit_cycles,
+            opcode_histogram,
         }
     }
 
@@ -124,6 +124,11 @@ pub struct StorageOracle<S: WriteStorage, H: HistoryMode> {
     // we don't roll this value back in case of a panicked frame.
     // Note, that it is a superset of `written_storage_keys`, since every written key was also read at some point.
     pub(crate) read_storage_keys: HistoryRecorder<HashMap<StorageKey, ()>, HistoryEnabled>,
+
+    // Ordered log of all reads from the persistent storage. Unlike the reads in `storage_frames_stack`,
+    // it is not affected by panicked frames. Only populated if `record_storage_reads` is set.
+    pub(crate) storage_reads: HistoryRecorder<Vec<StorageLogQuery>, H>,
+    pub(crate) record_storage_reads: bool,
 }
 
 impl<S: WriteStorage> OracleWithHistory for StorageOracle<S, HistoryEnabled> {
@@ -139,6 +144,7 @@ impl<S: WriteStorage> OracleWithHistory for StorageOracle<S, HistoryEnabled> {
         self.returned_pubdata_costs.rollback_to_timestamp(timestamp);
         self.written_storage_keys.rollback_to_timestamp(timestamp);
         self.read_storage_keys.rollback_to_timestamp(timestamp);
+        self.storage_reads.rollback_to_timestamp(timestamp);
     }
 }
 
@@ -155,9 +161,16 @@ impl<S: WriteStorage, H: HistoryMode> StorageOracle<S, H> {
             returned_pubdata_costs: Default::default(),
             written_storage_keys: Default::default(),
             read_storage_keys: Default::default(),
+            storage_reads: Default::default(),
+            record_storage_reads: false,
         }
     }
 
+    /// Enables collecting the ordered log of storage reads.
+    pub(crate) fn enable_storage_reads_log(&mut self) {
+        self.record_storage_reads = true;
+    }
+
     pub fn delete_history(&mut self) {
         self.storage.delete_history();
         self.transient_storage.delete_history();
@@ -168,6 +181,7 @@ impl<S: WriteStorage, H: HistoryMode> StorageOracle<S, H> {
         self.returned_pubdata_costs.delete_history();
         self.written_storage_keys.delete_history();
         self.read_storage_keys.delete_history();
+        self.storage_reads.delete_history();
     }
 
     fn is_storage_key_free(&self, key: &StorageKey) -> bool {
@@ -193,6 +207,9 @@ impl<S: WriteStorage, H: HistoryMode> StorageOracle<S, H> {
 
         self.storage_frames_stack
             .push_forward(Box::new(storage_log_query), query.timestamp);
+        if self.record_storage_reads {
+            self.storage_reads.push(storage_log_query, query.timestamp);
+        }
     }
 
     fn write_storage_value(&mut self, query: LogQuery) {
@@ -332,6 +349,21 @@ impl<S: WriteStorage, H: HistoryMode> StorageOracle<S, H> {
             .unwrap_or(&[])
     }
 
+    /// Returns storage reads where `log.log_query.timestamp >= from_timestamp`, or `None` if storage reads
+    /// are not recorded.
+    pub(crate) fn storage_reads_after_timestamp(
+        &self,
+        from_timestamp: Timestamp,
+    ) -> Option<&[StorageLogQuery]> {
+        if !self.record_storage_reads {
+            return None;
+        }
+        // Reads are pushed with monotonically increasing timestamps, so binary search is fine here.
+        let reads = self.storage_reads.inner();
+        let start = reads.partition_point(|read| read.log_query.timestamp < from_timestamp);
+        Some(&reads[start..])
+    }
+
     pub(crate) fn get_final_log_queries(&self) -> Vec<StorageLogQuery> {
         assert_eq!(
             self.storage_frames_stack.len(),
@@ -8,8 +8,12 @@ use zksync_system_constants::{
     CONTRACT_FORCE_DEPLOYER_ADDRESS, KNOWN_CODES_STORAGE_ADDRESS, L1_MESSENGER_ADDRESS,
 };
 use zksync_types::{
-    commitment::SerializeCommitment, fee_model::BatchFeeInput, get_code_key,
-    l2_to_l1_log::L2ToL1Log, writes::StateDiffRecord, Address, Execute, H256, U256,
+    commitment::SerializeCommitment,
+    fee_model::BatchFeeInput,
+    get_code_key,
+    l2_to_l1_log::L2ToL1Log,
+    writes::{StateDiffCompression, StateDiffRecord},
+    Address, Execute, H256, U256,
 };
 use zksync_utils::{bytecode::hash_bytecode, bytes_to_be_words, h256_to_u256, u256_to_h256};
 
@@ -193,6 +197,7 @@ fn execute_test(test_data: L1MessengerTestData) -> TestStatistics {
         VmExecutionMode::Batch,
         test_data.state_diffs.clone(),
         crate::vm_latest::MultiVMSubversion::latest(),
+        StateDiffCompression::for_protocol_version(vm.vm.system_env.version),
     );
 
     let result = vm.vm.inspect_inner(
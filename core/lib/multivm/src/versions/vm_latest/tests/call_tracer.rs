@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
 use once_cell::sync::OnceCell;
-use zksync_types::{Address, Execute};
+use zksync_types::{
+    vm_trace::{Call, CallType},
+    Address, Execute,
+};
 
 use crate::{
     interface::{TxExecutionMode, VmExecutionMode, VmInterface},
@@ -9,7 +12,7 @@ use crate::{
     vm_latest::{
         constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
         tests::{
-            tester::VmTesterBuilder,
+            tester::{TxType, VmTesterBuilder},
             utils::{read_max_depth_contract, read_test_contract},
         },
         HistoryEnabled, ToTracerPointer,
@@ -90,3 +93,142 @@ fn test_basic_behavior() {
     assert!(subcall.len() > 10);
     assert!(!res.result.is_failed());
 }
+
+#[test]
+fn call_traces_in_execution_result() {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .with_call_tracing()
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let call_traces = res.call_traces.expect("call tracing is enabled");
+    assert_eq!(call_traces.len(), 1);
+    assert!(call_traces[0].calls.len() > 10);
+}
+
+#[test]
+fn no_call_traces_by_default() {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .build();
+
+    vm.deploy_test_contract();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm.test_contract.unwrap(),
+        false,
+        Default::default(),
+        false,
+        TxType::L2,
+    );
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(res.call_traces.is_none());
+}
+
+fn trace_increment_tx(call_tracer: CallTracer, result: &Arc<OnceCell<Vec<Call>>>) -> Vec<Call> {
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(read_test_contract(), address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(
+        call_tracer.into_tracer_pointer().into(),
+        VmExecutionMode::OneTx,
+    );
+    assert!(!res.result.is_failed());
+    result.get().unwrap().clone()
+}
+
+fn flatten_calls(calls: &[Call]) -> Vec<&Call> {
+    calls
+        .iter()
+        .flat_map(|call| std::iter::once(call).chain(flatten_calls(&call.calls)))
+        .collect()
+}
+
+#[test]
+fn near_calls_in_call_traces() {
+    let result = Arc::new(OnceCell::new());
+    let calls = trace_increment_tx(CallTracer::new(result.clone()), &result);
+    assert!(flatten_calls(&calls)
+        .iter()
+        .all(|call| call.r#type != CallType::NearCall));
+
+    let result = Arc::new(OnceCell::new());
+    let calls = trace_increment_tx(CallTracer::new(result.clone()).with_near_calls(), &result);
+    let near_calls: Vec<_> = flatten_calls(&calls)
+        .into_iter()
+        .filter(|call| call.r#type == CallType::NearCall)
+        .collect();
+    assert!(!near_calls.is_empty());
+    assert!(near_calls.iter().all(|call| call.from == call.to));
+}
+
+#[test]
+fn collapsing_system_frames_in_call_traces() {
+    let result = Arc::new(OnceCell::new());
+    let full_calls = trace_increment_tx(CallTracer::new(result.clone()), &result);
+    let result = Arc::new(OnceCell::new());
+    let calls = trace_increment_tx(
+        CallTracer::new(result.clone()).with_collapsed_system_frames(),
+        &result,
+    );
+
+    let is_system_contract = |address: &Address| address.as_bytes()[..18] == [0; 18];
+    let full_calls = flatten_calls(&full_calls);
+    let calls = flatten_calls(&calls);
+    assert!(full_calls.iter().any(|call| is_system_contract(&call.to)));
+    assert!(calls.iter().all(|call| !is_system_contract(&call.to)));
+    let non_system_calls = full_calls
+        .iter()
+        .filter(|call| !is_system_contract(&call.to))
+        .count();
+    assert_eq!(calls.len(), non_system_calls);
+}
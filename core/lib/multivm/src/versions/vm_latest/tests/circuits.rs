@@ -3,7 +3,8 @@ use zksync_types::{Address, Execute, U256};
 use crate::{
     interface::{TxExecutionMode, VmExecutionMode, VmInterface},
     vm_latest::{
-        constants::BATCH_COMPUTATIONAL_GAS_LIMIT, tests::tester::VmTesterBuilder, HistoryEnabled,
+        constants::BATCH_COMPUTATIONAL_GAS_LIMIT, tests::tester::VmTesterBuilder,
+        tracers::circuits_capacity::circuit_statistic_from_cycles, HistoryEnabled,
     },
 };
 
@@ -71,4 +72,11 @@ fn test_circuits() {
             );
         }
     }
+
+    // Check that per-circuit cycle counts are consistent with the estimated number of circuits.
+    let cycles = res.statistics.circuit_cycles;
+    assert_eq!(circuit_statistic_from_cycles(cycles), s);
+    assert!(cycles.main_vm_cycles > 0);
+    assert!(cycles.keccak256_cycles > 0);
+    assert_eq!(cycles.sha256_cycles, 0);
 }
@@ -0,0 +1,61 @@
+use zksync_types::get_nonce_key;
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface, VmInterfaceHistoryEnabled},
+    vm_latest::{
+        tests::{
+            tester::{DeployContractsTx, TxType, VmTesterBuilder},
+            utils::read_test_contract,
+        },
+        HistoryEnabled,
+    },
+};
+
+#[test]
+fn dry_run_returns_state_diffs() {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_random_rich_accounts(1)
+        .build();
+
+    let mut account = vm.rich_accounts[0].clone();
+    let DeployContractsTx {
+        tx,
+        bytecode_hash,
+        address,
+    } = account.get_deploy_tx(&read_test_contract(), None, TxType::L2);
+
+    let dry_run = vm.vm.dry_run_transaction(tx.clone());
+    assert!(!dry_run.execution_result.result.is_failed());
+
+    assert_eq!(dry_run.deployed_contracts.len(), 1);
+    assert_eq!(dry_run.deployed_contracts[0].address, address);
+    assert_eq!(dry_run.deployed_contracts[0].bytecode_hash, bytecode_hash);
+
+    let nonce_key = get_nonce_key(&account.address);
+    assert!(dry_run
+        .storage_diffs
+        .iter()
+        .any(|diff| diff.key == nonce_key));
+    assert!(dry_run
+        .storage_diffs
+        .iter()
+        .all(|diff| diff.initial_value != diff.final_value));
+
+    let initiator_balance = dry_run
+        .balance_changes
+        .iter()
+        .find(|change| change.address == account.address)
+        .expect("no balance change for the initiator");
+    assert!(initiator_balance.final_balance < initiator_balance.initial_balance);
+
+    // The dry run must not affect the VM state, so the same transaction can be executed afterwards.
+    vm.vm.push_transaction(tx);
+    let result = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(!result.result.is_failed(), "{:?}", result.result);
+    assert_eq!(
+        result.logs.storage_logs.len(),
+        dry_run.execution_result.logs.storage_logs.len()
+    );
+}
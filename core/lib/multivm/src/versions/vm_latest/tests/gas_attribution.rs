@@ -0,0 +1,77 @@
+use zksync_types::{Address, Execute};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    vm_latest::{
+        constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+        tests::{
+            tester::{TxType, VmTesterBuilder},
+            utils::read_test_contract,
+        },
+        HistoryEnabled,
+    },
+};
+
+#[test]
+fn gas_is_attributed_to_contracts() {
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(read_test_contract(), address, true)])
+        .with_gas_attribution()
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let usage = res.gas_by_contract.expect("gas attribution is enabled");
+    assert!(usage.get(&address) > 0);
+    assert!(usage.get(&account.address) > 0);
+    // Gas passed to the callees must not be counted twice.
+    assert!(usage.get(&address) < usage.total_gas_used());
+
+    let top = usage.top(3);
+    assert_eq!(top.len(), 3);
+    assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+}
+
+#[test]
+fn no_gas_attribution_by_default() {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .build();
+
+    vm.deploy_test_contract();
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm.test_contract.unwrap(),
+        false,
+        Default::default(),
+        false,
+        TxType::L2,
+    );
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(res.gas_by_contract.is_none());
+}
@@ -1,5 +1,6 @@
 mod bootloader;
 mod default_aa;
+mod dry_run;
 // TODO - fix this test
 // `mod invalid_bytecode;`
 mod block_tip;
@@ -8,6 +9,7 @@ mod call_tracer;
 mod circuits;
 mod code_oracle;
 mod constants;
+mod gas_attribution;
 mod gas_limit;
 mod get_used_contracts;
 mod is_write_initial;
@@ -15,16 +17,21 @@ mod l1_tx_execution;
 mod l2_blocks;
 mod migration;
 mod nonce_holder;
+mod opcode_profiling;
 mod precompiles;
 mod prestate_tracer;
 mod refunds;
 mod require_eip712;
 mod rollbacks;
 mod sekp256r1;
+mod shadow;
 mod simple_execution;
 mod storage;
+mod storage_reads;
+mod storage_watchpoint;
 mod tester;
 mod tracing_execution_error;
 mod transfer;
 mod upgrade;
 mod utils;
+mod versioned_tracer;
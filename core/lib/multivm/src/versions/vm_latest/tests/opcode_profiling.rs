@@ -0,0 +1,61 @@
+use crate::{
+    interface::{tracer::OpcodeKind, ExecutionResult, VmExecutionMode, VmInterface},
+    vm_latest::{
+        tests::tester::{TxType, VmTesterBuilder},
+        HistoryDisabled,
+    },
+};
+
+#[test]
+fn opcode_histogram_is_collected_when_enabled() {
+    let mut vm_tester = VmTesterBuilder::new(HistoryDisabled)
+        .with_empty_in_memory_storage()
+        .with_deployer()
+        .with_random_rich_accounts(1)
+        .with_opcode_profiling()
+        .build();
+
+    vm_tester.deploy_test_contract();
+    let account = &mut vm_tester.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm_tester.test_contract.unwrap(),
+        false,
+        Default::default(),
+        false,
+        TxType::L2,
+    );
+    vm_tester.vm.push_transaction(tx);
+    let result = vm_tester.vm.execute(VmExecutionMode::OneTx);
+    assert!(matches!(result.result, ExecutionResult::Success { .. }));
+
+    let histogram = result
+        .statistics
+        .opcode_histogram
+        .expect("opcode profiling is enabled");
+    assert!(histogram.total_count() > 0);
+    assert!(histogram.get(OpcodeKind::FarCall).count > 0);
+    assert!(histogram.get(OpcodeKind::Log).gas_used > 0);
+    assert_eq!(histogram.get(OpcodeKind::Invalid).count, 0);
+}
+
+#[test]
+fn opcode_histogram_is_not_collected_by_default() {
+    let mut vm_tester = VmTesterBuilder::new(HistoryDisabled)
+        .with_empty_in_memory_storage()
+        .with_deployer()
+        .with_random_rich_accounts(1)
+        .build();
+
+    vm_tester.deploy_test_contract();
+    let account = &mut vm_tester.rich_accounts[0];
+    let tx = account.get_test_contract_transaction(
+        vm_tester.test_contract.unwrap(),
+        false,
+        Default::default(),
+        false,
+        TxType::L2,
+    );
+    vm_tester.vm.push_transaction(tx);
+    let result = vm_tester.vm.execute(VmExecutionMode::OneTx);
+    assert!(result.statistics.opcode_histogram.is_none());
+}
@@ -17,6 +17,7 @@ use crate::{
         types::internals::ZkSyncVmState,
         BootloaderState, HistoryEnabled, HistoryMode, SimpleMemory, ToTracerPointer, VmTracer,
     },
+    VmInstance,
 };
 
 #[test]
@@ -261,3 +262,45 @@ fn test_layered_rollback() {
     let result = vm.vm.execute(VmExecutionMode::OneTx);
     assert!(!result.result.is_failed(), "transaction must not fail");
 }
+
+#[test]
+fn test_speculative_execution_rollback() {
+    let mut tester = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_random_rich_accounts(1)
+        .build();
+    let mut account = tester.rich_accounts[0].clone();
+    let counter = read_test_contract();
+    let tx = account.get_deploy_tx(&counter, None, TxType::L2).tx;
+
+    let mut vm = VmInstance::Vm1_5_0(tester.vm);
+    let VmInstance::Vm1_5_0(inner_vm) = &vm else {
+        unreachable!();
+    };
+    let state_before = inner_vm.dump_inner_state();
+
+    let speculative_result = vm.execute_speculatively(|vm| {
+        vm.push_transaction(tx.clone());
+        vm.execute(VmExecutionMode::OneTx)
+    });
+    assert!(
+        !speculative_result.result.is_failed(),
+        "{speculative_result:#?}"
+    );
+    assert!(!speculative_result.logs.storage_logs.is_empty());
+
+    let VmInstance::Vm1_5_0(inner_vm) = &vm else {
+        unreachable!();
+    };
+    assert_eq!(inner_vm.dump_inner_state(), state_before);
+
+    // The transaction must execute in the same way after the rollback, i.e. its nonce must not be consumed.
+    vm.push_transaction(tx);
+    let result = vm.execute(VmExecutionMode::OneTx);
+    assert_eq!(result.result, speculative_result.result);
+    assert_eq!(
+        result.logs.storage_logs,
+        speculative_result.logs.storage_logs
+    );
+}
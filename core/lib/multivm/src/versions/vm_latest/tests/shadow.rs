@@ -0,0 +1,111 @@
+use zksync_types::{Address, Execute, L1BatchNumber, Transaction};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode},
+    vm_latest::{
+        tests::{
+            tester::{default_l1_batch, Account, InMemoryStorageView, VmTester, VmTesterBuilder},
+            utils::read_test_contract,
+        },
+        HistoryEnabled, Vm,
+    },
+    DivergenceHandling, ShadowVm, VmInstance,
+};
+
+type TestShadowVm = ShadowVm<
+    InMemoryStorageView,
+    HistoryEnabled,
+    Vm<InMemoryStorageView, HistoryEnabled>,
+    VmInstance<InMemoryStorageView, HistoryEnabled>,
+>;
+
+const INCREMENT_BY_6_CALLDATA: &str =
+    "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+
+fn build_tester(account: &Account, contract: Option<Address>) -> VmTester<HistoryEnabled> {
+    let custom_contracts = contract
+        .map(|address| vec![(read_test_contract(), address, true)])
+        .unwrap_or_default();
+    VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_l1_batch_env(default_l1_batch(L1BatchNumber(1)))
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_rich_accounts(vec![account.clone()])
+        .with_custom_contracts(custom_contracts)
+        .build()
+}
+
+fn increment_tx(account: &mut Account, contract: Address) -> Transaction {
+    account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: contract,
+            calldata: hex::decode(INCREMENT_BY_6_CALLDATA).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    )
+}
+
+fn shadow_vm(
+    main: &VmTester<HistoryEnabled>,
+    shadow: &VmTester<HistoryEnabled>,
+    divergence_handling: DivergenceHandling,
+) -> TestShadowVm {
+    ShadowVm::new(
+        main.vm.batch_env.clone(),
+        main.vm.system_env.clone(),
+        main.storage.clone(),
+        shadow.storage.clone(),
+        divergence_handling,
+    )
+}
+
+#[test]
+fn shadow_vm_without_divergences() {
+    let mut account = Account::random();
+    let contract = Address::random();
+    let main = build_tester(&account, Some(contract));
+    let shadow = build_tester(&account, Some(contract));
+    let mut vm = shadow_vm(&main, &shadow, DivergenceHandling::Halt);
+
+    vm.make_snapshot();
+    vm.push_transaction(increment_tx(&mut account, contract));
+    let result = vm.execute(VmExecutionMode::OneTx);
+    assert!(!result.result.is_failed());
+    vm.pop_snapshot_no_rollback();
+
+    vm.finish_batch();
+    assert!(vm.is_shadow_active());
+}
+
+#[test]
+fn shadow_vm_is_dropped_on_divergence() {
+    let mut account = Account::random();
+    let contract = Address::random();
+    let main = build_tester(&account, Some(contract));
+    // The shadow VM doesn't have the contract deployed, so the transaction will have different storage logs.
+    let shadow = build_tester(&account, None);
+    let mut vm = shadow_vm(&main, &shadow, DivergenceHandling::Continue);
+
+    vm.push_transaction(increment_tx(&mut account, contract));
+    let result = vm.execute(VmExecutionMode::OneTx);
+    assert!(!result.result.is_failed());
+    assert!(!vm.is_shadow_active());
+
+    // The main VM is still functional.
+    vm.finish_batch();
+}
+
+#[test]
+#[should_panic(expected = "VM divergence in L1 batch #1")]
+fn shadow_vm_halts_on_divergence() {
+    let mut account = Account::random();
+    let contract = Address::random();
+    let main = build_tester(&account, Some(contract));
+    let shadow = build_tester(&account, None);
+    let mut vm = shadow_vm(&main, &shadow, DivergenceHandling::Halt);
+
+    vm.push_transaction(increment_tx(&mut account, contract));
+    vm.execute(VmExecutionMode::OneTx);
+}
@@ -0,0 +1,86 @@
+use zksync_types::{Address, Execute, StorageLogQueryType, U256};
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    vm_latest::{
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled,
+    },
+};
+
+#[test]
+fn storage_reads_are_logged() {
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(read_test_contract(), address, true)])
+        .with_storage_reads_log()
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let reads = res
+        .logs
+        .storage_reads
+        .expect("storage reads log is enabled");
+    assert!(reads
+        .iter()
+        .all(|read| read.log_type == StorageLogQueryType::Read));
+    assert!(reads
+        .windows(2)
+        .all(|pair| pair[0].log_query.timestamp <= pair[1].log_query.timestamp));
+    // The counter value is read before being incremented.
+    assert!(reads
+        .iter()
+        .any(|read| read.log_query.address == address && read.log_query.key == U256::zero()));
+
+    let logged_reads = res
+        .logs
+        .storage_logs
+        .iter()
+        .filter(|log| log.log_type == StorageLogQueryType::Read);
+    for log in logged_reads {
+        assert!(reads.contains(log), "{log:?}");
+    }
+}
+
+#[test]
+fn storage_reads_are_not_logged_by_default() {
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .build();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: Address::random(),
+            calldata: vec![],
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.execute(VmExecutionMode::OneTx);
+    assert!(res.logs.storage_reads.is_none());
+}
@@ -0,0 +1,63 @@
+use zksync_types::{AccountTreeId, Address, Execute, StorageKey, H256, U256};
+
+use crate::{
+    interface::{tracer::StorageAccessKind, TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::{StorageWatchpointTracer, VersionedTracerAdapter},
+    vm_latest::{
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+#[test]
+fn watched_slot_accesses_are_recorded() {
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(read_test_contract(), address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    // The counter value is stored in the first slot of the test contract.
+    let counter_slot = StorageKey::new(AccountTreeId::new(address), H256::zero());
+    let unrelated_slot = StorageKey::new(AccountTreeId::new(Address::random()), H256::zero());
+    let adapter =
+        VersionedTracerAdapter::new(StorageWatchpointTracer::new([counter_slot, unrelated_slot]));
+    let tracer = adapter.shared();
+    vm.vm.push_transaction(tx);
+    let res = vm
+        .vm
+        .inspect(adapter.into_tracer_pointer().into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let tracer = tracer.lock().unwrap();
+    let accesses = tracer.accesses();
+    assert!(!accesses.is_empty());
+    assert!(accesses
+        .iter()
+        .all(|access| access.address == address && access.key == U256::zero()));
+    assert!(accesses.iter().all(|access| access.call_depth > 0));
+    assert!(accesses
+        .iter()
+        .any(|access| access.kind == StorageAccessKind::Read));
+    let write = accesses
+        .iter()
+        .find(|access| access.kind == StorageAccessKind::Write)
+        .expect("no write to the counter slot");
+    assert_eq!(write.written_value, Some(U256::from(6)));
+}
@@ -145,6 +145,10 @@ impl<H: HistoryMode> VmTesterBuilder<H> {
                 execution_mode: TxExecutionMode::VerifyExecute,
                 default_validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
                 chain_id: L2ChainId::from(270),
+                enable_opcode_profiling: false,
+                enable_call_tracing: false,
+                enable_gas_attribution: false,
+                enable_storage_reads_log: false,
             },
             deployer: None,
             rich_accounts: vec![],
@@ -186,6 +190,26 @@ impl<H: HistoryMode> VmTesterBuilder<H> {
         self
     }
 
+    pub(crate) fn with_opcode_profiling(mut self) -> Self {
+        self.system_env.enable_opcode_profiling = true;
+        self
+    }
+
+    pub(crate) fn with_call_tracing(mut self) -> Self {
+        self.system_env.enable_call_tracing = true;
+        self
+    }
+
+    pub(crate) fn with_gas_attribution(mut self) -> Self {
+        self.system_env.enable_gas_attribution = true;
+        self
+    }
+
+    pub(crate) fn with_storage_reads_log(mut self) -> Self {
+        self.system_env.enable_storage_reads_log = true;
+        self
+    }
+
     pub(crate) fn with_empty_in_memory_storage(mut self) -> Self {
         self.storage = Some(get_empty_storage());
         self
@@ -0,0 +1,89 @@
+use zksync_types::{Address, Execute};
+
+use crate::{
+    interface::{
+        tracer::{
+            FarCallEvent, OpcodeEvent, StorageAccessEvent, StorageAccessKind, VmExecutionStopReason,
+        },
+        TxExecutionMode, VersionedTracer, VmExecutionMode, VmInterface,
+    },
+    tracers::VersionedTracerAdapter,
+    vm_latest::{
+        constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+        tests::{tester::VmTesterBuilder, utils::read_test_contract},
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+#[derive(Debug, Default)]
+struct CountingTracer {
+    opcodes: usize,
+    storage_writes: Vec<StorageAccessEvent>,
+    far_calls: Vec<FarCallEvent>,
+    finished: bool,
+}
+
+impl VersionedTracer for CountingTracer {
+    fn on_opcode(&mut self, _event: &OpcodeEvent) {
+        self.opcodes += 1;
+    }
+
+    fn on_storage_access(&mut self, event: &StorageAccessEvent) {
+        if event.kind == StorageAccessKind::Write {
+            self.storage_writes.push(*event);
+        }
+    }
+
+    fn on_far_call(&mut self, event: &FarCallEvent) {
+        self.far_calls.push(*event);
+    }
+
+    fn after_vm_execution(&mut self, _stop_reason: &VmExecutionStopReason) {
+        self.finished = true;
+    }
+}
+
+#[test]
+fn versioned_tracer_receives_events() {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_bootloader_gas_limit(BATCH_COMPUTATIONAL_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let increment_by_6_calldata =
+        "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(increment_by_6_calldata).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+
+    let adapter = VersionedTracerAdapter::new(CountingTracer::default());
+    let tracer = adapter.shared();
+    vm.vm.push_transaction(tx);
+    let res = vm
+        .vm
+        .inspect(adapter.into_tracer_pointer().into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let tracer = tracer.lock().unwrap();
+    assert!(tracer.finished);
+    assert!(tracer.opcodes > 0);
+    assert!(tracer.far_calls.iter().any(|call| call.callee == address));
+    assert!(tracer
+        .storage_writes
+        .iter()
+        .any(|write| write.address == address));
+}
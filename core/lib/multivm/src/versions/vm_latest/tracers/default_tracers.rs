@@ -21,8 +21,9 @@ use crate::{
         dyn_tracers::vm_1_5_0::DynTracer,
         tracer::{TracerExecutionStopReason, VmExecutionStopReason},
         types::tracer::TracerExecutionStatus,
-        Halt, VmExecutionMode,
+        Halt, OpcodeHistogram, VmExecutionMode,
     },
+    tracers::{versioned::vm_latest::opcode_kind, CallTracer},
     vm_latest::{
         bootloader_state::{utils::apply_l2_block, BootloaderState},
         constants::BOOTLOADER_HEAP_PAGE,
@@ -30,7 +31,7 @@ use crate::{
         tracers::{
             dispatcher::TracerDispatcher,
             utils::{computational_gas_price, print_debug_if_needed, VmHook},
-            CircuitsTracer, RefundsTracer, ResultTracer,
+            CircuitsTracer, GasAttributionTracer, RefundsTracer, ResultTracer,
         },
         types::internals::ZkSyncVmState,
         vm::MultiVMSubversion,
@@ -64,12 +65,20 @@ pub(crate) struct DefaultExecutionTracer<S: WriteStorage, H: HistoryMode> {
     // It only takes into account circuits that are generated for actual execution. It doesn't
     // take into account e.g circuits produced by the initial bootloader memory commitment.
     pub(crate) circuits_tracer: CircuitsTracer<S, H>,
+    // Per-opcode execution histogram; only collected if opcode profiling is enabled.
+    pub(crate) opcode_histogram: Option<OpcodeHistogram>,
+    // Builds the call tree returned in `VmExecutionResultAndLogs`; only present if call tracing is enabled.
+    // Unlike custom call tracers, it's statically dispatched.
+    pub(crate) call_tracer: Option<CallTracer>,
+    // Attributes consumed gas to contracts; only present if gas attribution is enabled.
+    pub(crate) gas_attribution_tracer: Option<GasAttributionTracer>,
     subversion: MultiVMSubversion,
     storage: StoragePtr<S>,
     _phantom: PhantomData<H>,
 }
 
 impl<S: WriteStorage, H: HistoryMode> DefaultExecutionTracer<S, H> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         computational_gas_limit: u32,
         execution_mode: VmExecutionMode,
@@ -78,6 +87,9 @@ impl<S: WriteStorage, H: HistoryMode> DefaultExecutionTracer<S, H> {
         refund_tracer: Option<RefundsTracer<S>>,
         pubdata_tracer: Option<PubdataTracer<S>>,
         subversion: MultiVMSubversion,
+        enable_opcode_profiling: bool,
+        call_tracer: Option<CallTracer>,
+        enable_gas_attribution: bool,
     ) -> Self {
         Self {
             tx_has_been_processed: false,
@@ -93,6 +105,9 @@ impl<S: WriteStorage, H: HistoryMode> DefaultExecutionTracer<S, H> {
             pubdata_tracer,
             ret_from_the_bootloader: None,
             circuits_tracer: CircuitsTracer::new(),
+            opcode_histogram: enable_opcode_profiling.then(OpcodeHistogram::default),
+            call_tracer,
+            gas_attribution_tracer: enable_gas_attribution.then(GasAttributionTracer::new),
             storage,
             _phantom: PhantomData,
         }
@@ -173,6 +188,12 @@ macro_rules! dispatch_tracers {
             tracer.$function($( $params ),*);
         }
         $self.circuits_tracer.$function($( $params ),*);
+        if let Some(tracer) = &mut $self.call_tracer {
+            tracer.$function($( $params ),*);
+        }
+        if let Some(tracer) = &mut $self.gas_attribution_tracer {
+            tracer.$function($( $params ),*);
+        }
     };
 }
 
@@ -210,6 +231,12 @@ impl<S: WriteStorage, H: HistoryMode> Tracer for DefaultExecutionTracer<S, H> {
                 .computational_gas_used
                 .saturating_add(computational_gas_price(state, &data));
         }
+        if let Some(histogram) = &mut self.opcode_histogram {
+            histogram.record(
+                opcode_kind(data.opcode.variant.opcode),
+                computational_gas_price(state, &data),
+            );
+        }
 
         let hook = VmHook::from_opcode_memory(&state, &data, self.subversion);
         print_debug_if_needed(
@@ -0,0 +1,79 @@
+use zk_evm_1_5_0::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    vm_state::VmLocalState,
+};
+use zksync_state::{StoragePtr, WriteStorage};
+use zksync_types::Address;
+
+use crate::{
+    interface::{
+        dyn_tracers::vm_1_5_0::DynTracer, tracer::VmExecutionStopReason, ContractGasUsage,
+    },
+    vm_latest::{
+        bootloader_state::BootloaderState,
+        old_vm::{history_recorder::HistoryMode, memory::SimpleMemory},
+        tracers::traits::VmTracer,
+        types::internals::ZkSyncVmState,
+    },
+};
+
+/// Tracer attributing the consumed gas to the contracts in whose context it was spent.
+///
+/// Gas consumed by an opcode is computed as the change of the total gas available in all call frames,
+/// so that gas passed to / returned from the call frames isn't treated as consumed.
+#[derive(Debug, Default)]
+pub(crate) struct GasAttributionTracer {
+    pub(crate) usage: ContractGasUsage,
+    /// Address of the context of the previously executed opcode and the total gas available before its execution.
+    last_opcode: Option<(Address, u32)>,
+}
+
+impl GasAttributionTracer {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn record(&mut self, local_state: &VmLocalState) {
+        let gas_available = total_gas_available(local_state);
+        if let Some((address, last_gas_available)) = self.last_opcode {
+            self.usage
+                .record(address, last_gas_available.saturating_sub(gas_available));
+        }
+        self.last_opcode = Some((local_state.callstack.current.this_address, gas_available));
+    }
+}
+
+fn total_gas_available(local_state: &VmLocalState) -> u32 {
+    let callstack = &local_state.callstack;
+    callstack
+        .inner
+        .iter()
+        .fold(callstack.current.ergs_remaining, |acc, frame| {
+            acc.saturating_add(frame.ergs_remaining)
+        })
+}
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for GasAttributionTracer {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        _data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        self.record(state.vm_local_state);
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for GasAttributionTracer {
+    fn after_vm_execution(
+        &mut self,
+        state: &mut ZkSyncVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        // Account for the last executed opcode.
+        self.record(&state.local_state);
+        self.last_opcode = None;
+    }
+}
@@ -1,11 +1,13 @@
 pub(crate) use circuits_tracer::CircuitsTracer;
 pub(crate) use default_tracers::DefaultExecutionTracer;
+pub(crate) use gas_attribution::GasAttributionTracer;
 pub(crate) use pubdata_tracer::PubdataTracer;
 pub(crate) use refunds::RefundsTracer;
 pub(crate) use result_tracer::ResultTracer;
 
 pub(crate) mod circuits_tracer;
 pub(crate) mod default_tracers;
+pub(crate) mod gas_attribution;
 pub(crate) mod pubdata_tracer;
 pub(crate) mod refunds;
 pub(crate) mod result_tracer;
@@ -11,7 +11,7 @@ use zksync_types::{
         extract_bytecode_publication_requests_from_l1_messenger,
         extract_l2tol1logs_from_l1_messenger, extract_long_l2_to_l1_messages, L1MessengerL2ToL1Log,
     },
-    writes::StateDiffRecord,
+    writes::{StateDiffCompression, StateDiffRecord},
     AccountTreeId, StorageKey, L1_MESSENGER_ADDRESS,
 };
 use zksync_utils::{h256_to_u256, u256_to_bytes_be, u256_to_h256};
@@ -21,14 +21,14 @@ use crate::{
         dyn_tracers::vm_1_5_0::DynTracer,
         tracer::{TracerExecutionStatus, TracerExecutionStopReason},
         types::inputs::L1BatchEnv,
-        VmExecutionMode,
+        PubdataInput, VmExecutionMode,
     },
     vm_latest::{
         bootloader_state::{utils::apply_pubdata_to_memory, BootloaderState},
         constants::BOOTLOADER_HEAP_PAGE,
         old_vm::{history_recorder::HistoryMode, memory::SimpleMemory},
         tracers::{traits::VmTracer, utils::VmHook},
-        types::internals::{PubdataInput, ZkSyncVmState},
+        types::internals::ZkSyncVmState,
         utils::logs::collect_events_and_l1_system_logs_after_timestamp,
         vm::MultiVMSubversion,
         StorageOracle,
@@ -45,6 +45,7 @@ pub(crate) struct PubdataTracer<S> {
     // to the L1Messenger.
     enforced_state_diffs: Option<Vec<StateDiffRecord>>,
     subversion: MultiVMSubversion,
+    state_diff_compression: StateDiffCompression,
     _phantom_data: PhantomData<S>,
 }
 
@@ -53,6 +54,7 @@ impl<S: WriteStorage> PubdataTracer<S> {
         l1_batch_env: L1BatchEnv,
         execution_mode: VmExecutionMode,
         subversion: MultiVMSubversion,
+        state_diff_compression: StateDiffCompression,
     ) -> Self {
         Self {
             l1_batch_env,
@@ -60,6 +62,7 @@ impl<S: WriteStorage> PubdataTracer<S> {
             execution_mode,
             enforced_state_diffs: None,
             subversion,
+            state_diff_compression,
             _phantom_data: Default::default(),
         }
     }
@@ -72,6 +75,7 @@ impl<S: WriteStorage> PubdataTracer<S> {
         execution_mode: VmExecutionMode,
         forced_state_diffs: Vec<StateDiffRecord>,
         subversion: MultiVMSubversion,
+        state_diff_compression: StateDiffCompression,
     ) -> Self {
         Self {
             l1_batch_env,
@@ -79,6 +83,7 @@ impl<S: WriteStorage> PubdataTracer<S> {
             execution_mode,
             enforced_state_diffs: Some(forced_state_diffs),
             subversion,
+            state_diff_compression,
             _phantom_data: Default::default(),
         }
     }
@@ -189,6 +194,7 @@ impl<S: WriteStorage> PubdataTracer<S> {
             l2_to_l1_messages: self.get_total_l1_messenger_messages(state),
             published_bytecodes: self.get_total_published_bytecodes(state),
             state_diffs: self.get_state_diffs(&state.storage),
+            state_diff_compression: self.state_diff_compression,
         }
     }
 }
@@ -77,7 +77,10 @@ pub(crate) fn new_vm_state<S: WriteStorage, H: HistoryMode>(
 
     assert_next_block(&last_l2_block, &l1_batch_env.first_l2_block);
     let first_l2_block = l1_batch_env.first_l2_block;
-    let storage_oracle: StorageOracle<S, H> = StorageOracle::new(storage.clone());
+    let mut storage_oracle: StorageOracle<S, H> = StorageOracle::new(storage.clone());
+    if system_env.enable_storage_reads_log {
+        storage_oracle.enable_storage_reads_log();
+    }
     let mut memory = SimpleMemory::default();
     let event_sink = InMemoryEventSink::default();
     let precompiles_processor = PrecompilesProcessorWithHistory::<H>::default();
@@ -3,6 +3,7 @@ use zksync_state::{StoragePtr, WriteStorage};
 use zksync_types::{
     event::extract_l2tol1logs_from_l1_messenger,
     l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
+    pubdata_da::PubdataEncoding,
     Transaction, VmVersion,
 };
 use zksync_utils::bytecode::CompressedBytecodeInfo;
@@ -206,8 +207,7 @@ impl<S: WriteStorage, H: HistoryMode> VmInterface<S, H> for Vm<S, H> {
             pubdata_input: Some(
                 self.bootloader_state
                     .get_pubdata_information()
-                    .clone()
-                    .build_pubdata(false),
+                    .encode(PubdataEncoding::Rollup),
             ),
             state_diffs: Some(
                 self.bootloader_state
@@ -467,6 +467,7 @@ impl<H: HistoryMode, S: Storage> VmInstance<S, H> {
             events,
             user_l2_to_l1_logs: l2_to_l1_logs.into_iter().map(UserL2ToL1Log).collect(),
             system_l2_to_l1_logs: vec![],
+            storage_reads: None,
             total_log_queries_count: storage_logs_count
                 + log_queries.len()
                 + precompile_calls_count,
@@ -87,6 +87,8 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             logs,
             statistics,
             refunds,
+            call_traces: None,
+            gas_by_contract: None,
         };
 
         (stop_reason, result)
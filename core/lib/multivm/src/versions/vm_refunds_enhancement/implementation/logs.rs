@@ -49,6 +49,7 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             events,
             user_l2_to_l1_logs: l2_to_l1_logs.into_iter().map(UserL2ToL1Log).collect(),
             system_l2_to_l1_logs: vec![],
+            storage_reads: None,
             total_log_queries_count,
         }
     }
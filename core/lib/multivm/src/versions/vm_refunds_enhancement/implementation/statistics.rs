@@ -42,6 +42,8 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             total_log_queries: total_log_queries_count,
             pubdata_published,
             circuit_statistic: Default::default(),
+            circuit_cycles: Default::default(),
+            opcode_histogram: None,
         }
     }
 
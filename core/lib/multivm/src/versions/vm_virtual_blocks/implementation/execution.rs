@@ -86,6 +86,8 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
                 .refund_tracer
                 .map(|r| r.get_refunds())
                 .unwrap_or_default(),
+            call_traces: None,
+            gas_by_contract: None,
         };
 
         tx_tracer.dispatcher.save_results(&mut result);
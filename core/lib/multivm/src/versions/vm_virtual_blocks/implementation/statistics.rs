@@ -42,6 +42,8 @@ impl<S: WriteStorage, H: HistoryMode> Vm<S, H> {
             // This field will be populated by the `RefundTracer`
             pubdata_published: 0,
             circuit_statistic: Default::default(),
+            circuit_cycles: Default::default(),
+            opcode_histogram: None,
         }
     }
 
@@ -141,6 +141,10 @@ impl<H: HistoryMode> VmTesterBuilder<H> {
                 execution_mode: TxExecutionMode::VerifyExecute,
                 default_validation_computational_gas_limit: BLOCK_GAS_LIMIT,
                 chain_id: 270.into(),
+                enable_opcode_profiling: false,
+                enable_call_tracing: false,
+                enable_gas_attribution: false,
+                enable_storage_reads_log: false,
             },
             deployer: None,
             rich_accounts: vec![],
@@ -146,6 +146,26 @@ impl<S: WriteStorage> VmInterfaceHistoryEnabled<S>
     }
 }
 
+impl<S: WriteStorage> VmInstance<S, crate::vm_latest::HistoryEnabled> {
+    /// Runs `action` on this VM and rolls back all its effects afterwards, e.g. to speculatively execute
+    /// a transaction. Afterwards, the VM can be used as if `action` was never run.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the VM version doesn't support speculative execution; this can be checked beforehand
+    /// with [`supports_speculative_execution()`](crate::utils::supports_speculative_execution).
+    pub fn execute_speculatively<T>(&mut self, action: impl FnOnce(&mut Self) -> T) -> T {
+        assert!(
+            !matches!(self, Self::VmM5(_) | Self::VmM6(_) | Self::Vm1_3_2(_)),
+            "Speculative execution is not supported by pre-virtual-blocks VMs"
+        );
+        self.make_snapshot();
+        let output = action(self);
+        self.rollback_to_the_latest_snapshot();
+        output
+    }
+}
+
 impl<S: WriteStorage, H: HistoryMode> VmInstance<S, H> {
     pub fn new_with_specific_version(
         l1_batch_env: L1BatchEnv,
@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, path::Path};
 
 use async_trait::async_trait;
 use tokio::{fs, io};
@@ -43,6 +43,7 @@ impl FileBackedObjectStore {
             Bucket::ProofsFri,
             Bucket::StorageSnapshot,
             Bucket::TeeVerifierInput,
+            Bucket::MerkleTreeCheckpoints,
         ] {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path).await?;
@@ -77,6 +78,26 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn put_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        fs::copy(path, filename).await.map_err(From::from)
+    }
+
+    async fn get_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let filename = self.filename(bucket, key);
+        fs::copy(filename, path).await.map_err(From::from)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_dir, bucket)
     }
@@ -131,4 +152,27 @@ mod test {
             .await
             .unwrap();
     }
+
+    #[tokio::test]
+    async fn test_put_and_get_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().to_str().unwrap().to_owned();
+        let object_store = FileBackedObjectStore::new(path).await.unwrap();
+        let expected = vec![9, 0, 8, 9, 0, 7];
+        let input_path = dir.path().join("input.bin");
+        fs::write(&input_path, &expected).await.unwrap();
+        let size = object_store
+            .put_file_raw(Bucket::ProverJobs, "test-key.bin", &input_path)
+            .await
+            .unwrap();
+        assert_eq!(size, expected.len() as u64);
+
+        let output_path = dir.path().join("output.bin");
+        let size = object_store
+            .get_file_raw(Bucket::ProverJobs, "test-key.bin", &output_path)
+            .await
+            .unwrap();
+        assert_eq!(size, expected.len() as u64);
+        assert_eq!(fs::read(&output_path).await.unwrap(), expected);
+    }
 }
@@ -1,8 +1,9 @@
 //! GCS-based [`ObjectStore`] implementation.
 
-use std::{error::Error as StdError, fmt, io};
+use std::{error::Error as StdError, fmt, io, path::Path, pin::pin};
 
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
 use google_cloud_auth::{credentials::CredentialsFile, error::Error as AuthError};
 use google_cloud_storage::{
     client::{Client, ClientConfig},
@@ -17,6 +18,10 @@ use google_cloud_storage::{
     },
 };
 use http::StatusCode;
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
 
 use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
 
@@ -49,6 +54,9 @@ pub enum GoogleCloudStoreAuthMode {
 }
 
 impl GoogleCloudStore {
+    /// Size of chunks in which files are streamed to GCS.
+    const FILE_CHUNK_SIZE: usize = 1 << 20; // 1 MiB
+
     /// Creates a new cloud store.
     ///
     /// # Errors
@@ -195,6 +203,78 @@ impl ObjectStore for GoogleCloudStore {
         Ok(())
     }
 
+    async fn put_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming file `{}` to GCS for key {filename} from bucket {}",
+            path.display(),
+            self.bucket_prefix
+        );
+
+        let file = fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+        let chunks = stream::try_unfold(file, |mut file| async move {
+            let mut chunk = vec![0_u8; Self::FILE_CHUNK_SIZE];
+            let chunk_len = file.read(&mut chunk).await?;
+            if chunk_len == 0 {
+                return Ok(None);
+            }
+            chunk.truncate(chunk_len);
+            Ok::<_, io::Error>(Some((chunk, file)))
+        });
+
+        let mut media = Media::new(filename);
+        media.content_length = Some(size);
+        let upload_type = UploadType::Simple(media);
+        let request = UploadObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            ..Default::default()
+        };
+        self.client
+            .upload_streamed_object(&request, chunks, &upload_type)
+            .await?;
+        Ok(size)
+    }
+
+    async fn get_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let filename = Self::filename(bucket.as_str(), key);
+        tracing::trace!(
+            "Streaming data from GCS for key {filename} from bucket {} to file `{}`",
+            self.bucket_prefix,
+            path.display()
+        );
+
+        let request = GetObjectRequest {
+            bucket: self.bucket_prefix.clone(),
+            object: filename,
+            ..GetObjectRequest::default()
+        };
+        let mut chunks = pin!(
+            self.client
+                .download_streamed_object(&request, &Range::default())
+                .await?
+        );
+        let mut file = fs::File::create(path).await?;
+        let mut size = 0;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(size)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
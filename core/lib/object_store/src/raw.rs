@@ -1,6 +1,7 @@
-use std::{error, fmt};
+use std::{error, fmt, path::Path};
 
 use async_trait::async_trait;
+use tokio::fs;
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -18,6 +19,7 @@ pub enum Bucket {
     ProofsFri,
     StorageSnapshot,
     TeeVerifierInput,
+    MerkleTreeCheckpoints,
 }
 
 impl Bucket {
@@ -35,6 +37,7 @@ impl Bucket {
             Self::ProofsFri => "proofs_fri",
             Self::StorageSnapshot => "storage_logs_snapshots",
             Self::TeeVerifierInput => "tee_verifier_inputs",
+            Self::MerkleTreeCheckpoints => "merkle_tree_checkpoints",
         }
     }
 }
@@ -153,5 +156,43 @@ pub trait ObjectStore: 'static + fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Stores the contents of a local file at `path` associating it with the key into the given bucket.
+    /// Returns the size of the stored value in bytes. The default implementation reads the entire file into memory;
+    /// implementations should override it if they can stream data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if the insertion / replacement operation fails.
+    async fn put_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let value = fs::read(path).await?;
+        let size = value.len() as u64;
+        self.put_raw(bucket, key, value).await?;
+        Ok(size)
+    }
+
+    /// Fetches the value for the given key from the given bucket and writes it to a local file at `path`,
+    /// replacing the file if it exists. Returns the size of the value in bytes. As with [`Self::put_file_raw()`],
+    /// the default implementation buffers the entire value in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an object with the `key` does not exist or cannot be accessed, or if the file cannot be written.
+    async fn get_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let value = self.get_raw(bucket, key).await?;
+        let size = value.len() as u64;
+        fs::write(path, value).await?;
+        Ok(size)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
@@ -1,4 +1,4 @@
-use std::{any, fmt, future::Future, time::Duration};
+use std::{any, fmt, future::Future, path::Path, time::Duration};
 
 use async_trait::async_trait;
 use rand::Rng;
@@ -121,6 +121,38 @@ impl<S: ObjectStore> ObjectStore for StoreWithRetries<S> {
             .await
     }
 
+    async fn put_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let latency = OBJECT_STORE_METRICS.start_store(bucket);
+        let result = Request::Put(bucket, key)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.put_file_raw(bucket, key, path)
+            })
+            .await;
+        latency.observe();
+        result
+    }
+
+    async fn get_file_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        path: &Path,
+    ) -> Result<u64, ObjectStoreError> {
+        let latency = OBJECT_STORE_METRICS.start_fetch(bucket);
+        let result = Request::Get(bucket, key)
+            .retry(&self.inner, self.max_retries, || {
+                self.inner.get_file_raw(bucket, key, path)
+            })
+            .await;
+        latency.observe();
+        result
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         self.inner.storage_prefix_raw(bucket)
     }
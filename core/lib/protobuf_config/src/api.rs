@@ -1,4 +1,4 @@
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroU64, NonZeroUsize};
 
 use anyhow::Context as _;
 use zksync_config::configs::{api, ApiConfig};
@@ -70,6 +70,24 @@ impl ProtoRepr for proto::Web3JsonRpc {
             .collect::<anyhow::Result<_>>()
             .context("max_response_body_size_overrides")?;
 
+        let client_requests_per_minute_overrides = self
+            .client_requests_per_minute_overrides
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let limit = required(&entry.limit).with_context(|| format!("[{i}].limit"))?;
+                let limit =
+                    NonZeroU32::new(*limit).with_context(|| format!("[{i}].limit is zero"))?;
+                Ok((
+                    required(&entry.method_group)
+                        .with_context(|| format!("[{i}].method_group"))?
+                        .clone(),
+                    limit,
+                ))
+            })
+            .collect::<anyhow::Result<_>>()
+            .context("client_requests_per_minute_overrides")?;
+
         Ok(Self::Type {
             http_port: required(&self.http_port)
                 .and_then(|p| Ok((*p).try_into()?))
@@ -81,6 +99,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
             ws_url: required(&self.ws_url).context("ws_url")?.clone(),
             req_entities_limit: self.req_entities_limit,
             filters_disabled: self.filters_disabled.unwrap_or(false),
+            persistent_filters: self.persistent_filters.unwrap_or(false),
             filters_limit: self.filters_limit,
             subscriptions_limit: self.subscriptions_limit,
             pubsub_polling_interval: self.pubsub_polling_interval,
@@ -124,6 +143,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .transpose()
                 .context("latest_values_cache_size_mb")?,
             fee_history_limit: self.fee_history_limit,
+            trace_filter_block_range_limit: self.trace_filter_block_range_limit,
             max_batch_request_size: self
                 .max_batch_request_size
                 .map(|x| x.try_into())
@@ -140,6 +160,32 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .map(|x| x.try_into())
                 .transpose()
                 .context("websocket_requests_per_minute_limit")?,
+            client_requests_per_minute_limit: self
+                .client_requests_per_minute_limit
+                .map(|x| x.try_into())
+                .transpose()
+                .context("client_requests_per_minute_limit")?,
+            client_requests_per_minute_overrides,
+            client_rate_limit_trusted_proxies: self
+                .client_rate_limit_trusted_proxies
+                .iter()
+                .enumerate()
+                .map(|(i, addr)| addr.parse().with_context(|| format!("[{i}]")))
+                .collect::<anyhow::Result<_>>()
+                .context("client_rate_limit_trusted_proxies")?,
+            http_response_compression: self.http_response_compression.unwrap_or(false),
+            http_methods_allowlist: (!self.http_methods_allowlist.is_empty())
+                .then(|| self.http_methods_allowlist.clone()),
+            http_methods_denylist: self.http_methods_denylist.clone(),
+            ws_methods_allowlist: (!self.ws_methods_allowlist.is_empty())
+                .then(|| self.ws_methods_allowlist.clone()),
+            ws_methods_denylist: self.ws_methods_denylist.clone(),
+            admin_http_port: self
+                .admin_http_port
+                .map(u16::try_from)
+                .transpose()
+                .context("admin_http_port")?,
+            txpool_namespace_enabled: self.txpool_namespace_enabled.unwrap_or(false),
             tree_api_url: self.tree_api_url.clone(),
             mempool_cache_update_interval: self.mempool_cache_update_interval,
             mempool_cache_size: self
@@ -165,6 +211,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
             ws_url: Some(this.ws_url.clone()),
             req_entities_limit: this.req_entities_limit,
             filters_disabled: Some(this.filters_disabled),
+            persistent_filters: Some(this.persistent_filters),
             mempool_cache_update_interval: this.mempool_cache_update_interval,
             mempool_cache_size: this.mempool_cache_size.map(|x| x.try_into().unwrap()),
             filters_limit: this.filters_limit,
@@ -197,6 +244,7 @@ impl ProtoRepr for proto::Web3JsonRpc {
                 .latest_values_cache_size_mb
                 .map(|x| x.try_into().unwrap()),
             fee_history_limit: this.fee_history_limit,
+            trace_filter_block_range_limit: this.trace_filter_block_range_limit,
             max_batch_request_size: this.max_batch_request_size.map(|x| x.try_into().unwrap()),
             max_response_body_size_mb: this
                 .max_response_body_size_mb
@@ -216,6 +264,29 @@ impl ProtoRepr for proto::Web3JsonRpc {
             websocket_requests_per_minute_limit: this
                 .websocket_requests_per_minute_limit
                 .map(|x| x.into()),
+            client_requests_per_minute_limit: this
+                .client_requests_per_minute_limit
+                .map(|x| x.into()),
+            client_requests_per_minute_overrides: this
+                .client_requests_per_minute_overrides
+                .iter()
+                .map(|(method_group, limit)| proto::RequestsPerMinuteOverride {
+                    method_group: Some(method_group.to_owned()),
+                    limit: Some(limit.get()),
+                })
+                .collect(),
+            client_rate_limit_trusted_proxies: this
+                .client_rate_limit_trusted_proxies
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            http_response_compression: Some(this.http_response_compression),
+            http_methods_allowlist: this.http_methods_allowlist.clone().unwrap_or_default(),
+            http_methods_denylist: this.http_methods_denylist.clone(),
+            ws_methods_allowlist: this.ws_methods_allowlist.clone().unwrap_or_default(),
+            ws_methods_denylist: this.ws_methods_denylist.clone(),
+            admin_http_port: this.admin_http_port.map(Into::into),
+            txpool_namespace_enabled: Some(this.txpool_namespace_enabled),
             tree_api_url: this.tree_api_url.clone(),
             whitelisted_tokens_for_aa: this
                 .whitelisted_tokens_for_aa
@@ -255,11 +326,19 @@ impl ProtoRepr for proto::MerkleTreeApi {
             port: required(&self.port)
                 .and_then(|p| Ok((*p).try_into()?))
                 .context("port")?,
+            replica_path: self.replica_path.clone(),
+            replica_poll_interval_ms: self
+                .replica_poll_interval_ms
+                .map(|ms| NonZeroU64::new(ms).context("cannot be 0"))
+                .transpose()
+                .context("replica_poll_interval_ms")?,
         })
     }
     fn build(this: &Self::Type) -> Self {
         Self {
             port: Some(this.port.into()),
+            replica_path: this.replica_path.clone(),
+            replica_poll_interval_ms: this.replica_poll_interval_ms.map(NonZeroU64::get),
         }
     }
 }
@@ -0,0 +1,32 @@
+use std::num::NonZeroU64;
+
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::proto::base_token_adjuster as proto;
+
+impl ProtoRepr for proto::BaseTokenAdjuster {
+    type Type = configs::BaseTokenAdjusterConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            price_polling_interval_ms: *required(&self.price_polling_interval_ms)
+                .context("price_polling_interval_ms")?,
+            fixed_ratio_numerator: required(&self.fixed_ratio_numerator)
+                .and_then(|&value| NonZeroU64::new(value).context("cannot be zero"))
+                .context("fixed_ratio_numerator")?,
+            fixed_ratio_denominator: required(&self.fixed_ratio_denominator)
+                .and_then(|&value| NonZeroU64::new(value).context("cannot be zero"))
+                .context("fixed_ratio_denominator")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            price_polling_interval_ms: Some(this.price_polling_interval_ms),
+            fixed_ratio_numerator: Some(this.fixed_ratio_numerator.get()),
+            fixed_ratio_denominator: Some(this.fixed_ratio_denominator.get()),
+        }
+    }
+}
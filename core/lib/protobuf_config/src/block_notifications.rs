@@ -0,0 +1,27 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::proto::block_notifications as proto;
+
+impl ProtoRepr for proto::BlockNotifications {
+    type Type = configs::BlockNotificationsConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            poll_interval_ms: *required(&self.poll_interval_ms).context("poll_interval_ms")?,
+            max_batch_size: required(&self.max_batch_size)
+                .and_then(|size| Ok((*size).try_into()?))
+                .context("max_batch_size")?,
+            webhook_url: self.webhook_url.clone(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            poll_interval_ms: Some(this.poll_interval_ms),
+            max_batch_size: Some(this.max_batch_size as u64),
+            webhook_url: this.webhook_url.clone(),
+        }
+    }
+}
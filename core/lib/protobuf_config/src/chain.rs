@@ -68,6 +68,12 @@ impl ProtoRepr for proto::StateKeeper {
             max_gas_per_batch: *required(&self.max_gas_per_batch).context("max_gas_per_batch")?,
             max_pubdata_per_batch: *required(&self.max_pubdata_per_batch)
                 .context("max_pubdata_per_batch")?,
+            max_pubdata_cost_per_batch: self.max_pubdata_cost_per_batch,
+            max_tx_execution_time_ms: self.max_tx_execution_time_ms,
+            max_priority_ops_per_batch: self.max_priority_ops_per_batch,
+            max_priority_ops_gas_per_batch: self.max_priority_ops_gas_per_batch,
+            max_priority_ops_per_l2_block: self.max_priority_ops_per_l2_block,
+            max_priority_ops_gas_per_l2_block: self.max_priority_ops_gas_per_l2_block,
             fee_model_version: required(&self.fee_model_version)
                 .and_then(|x| Ok(proto::FeeModelVersion::try_from(*x)?))
                 .context("fee_model_version")?
@@ -75,6 +81,11 @@ impl ProtoRepr for proto::StateKeeper {
             validation_computational_gas_limit: *required(&self.validation_computational_gas_limit)
                 .context("validation_computational_gas_limit")?,
             save_call_traces: *required(&self.save_call_traces).context("save_call_traces")?,
+            enable_opcode_profiling: self.enable_opcode_profiling.unwrap_or(false),
+            enable_gas_attribution: self.enable_gas_attribution.unwrap_or(false),
+            enable_mempool_pre_execution: self.enable_mempool_pre_execution.unwrap_or(false),
+            enable_execution_result_cache: self.enable_execution_result_cache.unwrap_or(false),
+            enable_storage_prefetching: self.enable_storage_prefetching.unwrap_or(false),
             max_circuits_per_batch: required(&self.max_circuits_per_batch)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_circuits_per_batch")?,
@@ -111,9 +122,20 @@ impl ProtoRepr for proto::StateKeeper {
             batch_overhead_l1_gas: Some(this.batch_overhead_l1_gas),
             max_gas_per_batch: Some(this.max_gas_per_batch),
             max_pubdata_per_batch: Some(this.max_pubdata_per_batch),
+            max_pubdata_cost_per_batch: this.max_pubdata_cost_per_batch,
+            max_tx_execution_time_ms: this.max_tx_execution_time_ms,
+            max_priority_ops_per_batch: this.max_priority_ops_per_batch,
+            max_priority_ops_gas_per_batch: this.max_priority_ops_gas_per_batch,
+            max_priority_ops_per_l2_block: this.max_priority_ops_per_l2_block,
+            max_priority_ops_gas_per_l2_block: this.max_priority_ops_gas_per_l2_block,
             fee_model_version: Some(proto::FeeModelVersion::new(&this.fee_model_version).into()),
             validation_computational_gas_limit: Some(this.validation_computational_gas_limit),
             save_call_traces: Some(this.save_call_traces),
+            enable_opcode_profiling: Some(this.enable_opcode_profiling),
+            enable_gas_attribution: Some(this.enable_gas_attribution),
+            enable_mempool_pre_execution: Some(this.enable_mempool_pre_execution),
+            enable_execution_result_cache: Some(this.enable_execution_result_cache),
+            enable_storage_prefetching: Some(this.enable_storage_prefetching),
             max_circuits_per_batch: Some(this.max_circuits_per_batch.try_into().unwrap()),
         }
     }
@@ -146,6 +168,14 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: *required(&self.stuck_tx_timeout).context("stuck_tx_timeout")?,
             remove_stuck_txs: *required(&self.remove_stuck_txs).context("remove_stuck_txs")?,
             delay_interval: *required(&self.delay_interval).context("delay_interval")?,
+            fifo_ordering: self.fifo_ordering.unwrap_or(true),
+            replacement_fee_bump_percent: self.replacement_fee_bump_percent,
+            tx_ttl_sec: self.tx_ttl_sec,
+            max_nonce_gap: self.max_nonce_gap,
+            eviction_interval_ms: self.eviction_interval_ms,
+            max_pending_txs_per_account: self.max_pending_txs_per_account,
+            address_denylist_path: self.address_denylist_path.clone(),
+            address_denylist_reload_interval_ms: self.address_denylist_reload_interval_ms,
         })
     }
 
@@ -157,6 +187,14 @@ impl ProtoRepr for proto::Mempool {
             stuck_tx_timeout: Some(this.stuck_tx_timeout),
             remove_stuck_txs: Some(this.remove_stuck_txs),
             delay_interval: Some(this.delay_interval),
+            fifo_ordering: Some(this.fifo_ordering),
+            replacement_fee_bump_percent: this.replacement_fee_bump_percent,
+            tx_ttl_sec: this.tx_ttl_sec,
+            max_nonce_gap: this.max_nonce_gap,
+            eviction_interval_ms: this.eviction_interval_ms,
+            max_pending_txs_per_account: this.max_pending_txs_per_account,
+            address_denylist_path: this.address_denylist_path.clone(),
+            address_denylist_reload_interval_ms: this.address_denylist_reload_interval_ms,
         }
     }
 }
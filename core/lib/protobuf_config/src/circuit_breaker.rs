@@ -16,6 +16,10 @@ impl ProtoRepr for proto::CircuitBreaker {
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("http_req_retry_interval_sec")?,
             replication_lag_limit_sec: self.replication_lag_limit_sec,
+            db_integrity_check_chunk_size: self.db_integrity_check_chunk_size,
+            pool_saturation_shedding_percent: self.pool_saturation_shedding_percent,
+            pool_acquire_latency_shedding_ms: self.pool_acquire_latency_shedding_ms,
+            pool_saturation_trip_after_sec: self.pool_saturation_trip_after_sec,
         })
     }
 
@@ -25,6 +29,10 @@ impl ProtoRepr for proto::CircuitBreaker {
             http_req_max_retry_number: Some(this.http_req_max_retry_number.try_into().unwrap()),
             http_req_retry_interval_sec: Some(this.http_req_retry_interval_sec.into()),
             replication_lag_limit_sec: this.replication_lag_limit_sec,
+            db_integrity_check_chunk_size: this.db_integrity_check_chunk_size,
+            pool_saturation_shedding_percent: this.pool_saturation_shedding_percent,
+            pool_acquire_latency_shedding_ms: this.pool_acquire_latency_shedding_ms,
+            pool_saturation_trip_after_sec: this.pool_saturation_trip_after_sec,
         }
     }
 }
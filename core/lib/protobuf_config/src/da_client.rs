@@ -0,0 +1,90 @@
+use anyhow::Context as _;
+use zksync_config::configs::da_client::{
+    AvailConfig, CelestiaConfig, DAClient, DAClientConfig, EigenDAConfig,
+};
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::proto::da_client as proto;
+
+impl ProtoRepr for proto::DataAvailabilityClient {
+    type Type = DAClientConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        let client = match required(&self.client).context("client")? {
+            proto::data_availability_client::Client::Avail(config) => {
+                DAClient::Avail(AvailConfig {
+                    api_node_url: required(&config.api_node_url)
+                        .context("api_node_url")?
+                        .clone(),
+                    bridge_api_url: required(&config.bridge_api_url)
+                        .context("bridge_api_url")?
+                        .clone(),
+                })
+            }
+            proto::data_availability_client::Client::Celestia(config) => {
+                DAClient::Celestia(CelestiaConfig {
+                    api_node_url: required(&config.api_node_url)
+                        .context("api_node_url")?
+                        .clone(),
+                    namespace: required(&config.namespace).context("namespace")?.clone(),
+                    auth_token: required(&config.auth_token).context("auth_token")?.clone(),
+                })
+            }
+            proto::data_availability_client::Client::EigenDa(config) => {
+                DAClient::EigenDA(EigenDAConfig {
+                    disperser_rpc: required(&config.disperser_rpc)
+                        .context("disperser_rpc")?
+                        .clone(),
+                    custom_quorum_numbers: config.custom_quorum_numbers.clone(),
+                    wait_for_finalization: *required(&config.wait_for_finalization)
+                        .context("wait_for_finalization")?,
+                    blob_size_limit: *required(&config.blob_size_limit)
+                        .context("blob_size_limit")?,
+                    account_id: config.account_id.clone(),
+                    signer_private_key: config.signer_private_key.clone(),
+                    g1_srs_path: required(&config.g1_srs_path)
+                        .context("g1_srs_path")?
+                        .clone(),
+                })
+            }
+        };
+        Ok(Self::Type {
+            client,
+            request_timeout_ms: *required(&self.request_timeout_ms)
+                .context("request_timeout_ms")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        let client = match &this.client {
+            DAClient::Avail(config) => proto::data_availability_client::Client::Avail(
+                proto::data_availability_client::Avail {
+                    api_node_url: Some(config.api_node_url.clone()),
+                    bridge_api_url: Some(config.bridge_api_url.clone()),
+                },
+            ),
+            DAClient::Celestia(config) => proto::data_availability_client::Client::Celestia(
+                proto::data_availability_client::Celestia {
+                    api_node_url: Some(config.api_node_url.clone()),
+                    namespace: Some(config.namespace.clone()),
+                    auth_token: Some(config.auth_token.clone()),
+                },
+            ),
+            DAClient::EigenDA(config) => proto::data_availability_client::Client::EigenDa(
+                proto::data_availability_client::EigenDa {
+                    disperser_rpc: Some(config.disperser_rpc.clone()),
+                    custom_quorum_numbers: config.custom_quorum_numbers.clone(),
+                    wait_for_finalization: Some(config.wait_for_finalization),
+                    blob_size_limit: Some(config.blob_size_limit),
+                    account_id: config.account_id.clone(),
+                    signer_private_key: config.signer_private_key.clone(),
+                    g1_srs_path: Some(config.g1_srs_path.clone()),
+                },
+            ),
+        };
+        Self {
+            client: Some(client),
+            request_timeout_ms: Some(this.request_timeout_ms),
+        }
+    }
+}
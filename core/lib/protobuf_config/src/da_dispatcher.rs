@@ -0,0 +1,29 @@
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::proto::da_dispatcher as proto;
+
+impl ProtoRepr for proto::DataAvailabilityDispatcher {
+    type Type = configs::DADispatcherConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            polling_interval_ms: *required(&self.polling_interval_ms)
+                .context("polling_interval_ms")?,
+            max_rows_to_dispatch: *required(&self.max_rows_to_dispatch)
+                .context("max_rows_to_dispatch")?,
+            max_retries: required(&self.max_retries)
+                .and_then(|x| Ok((*x).try_into()?))
+                .context("max_retries")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            polling_interval_ms: Some(this.polling_interval_ms),
+            max_rows_to_dispatch: Some(this.max_rows_to_dispatch),
+            max_retries: Some(this.max_retries.into()),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::num::{NonZeroU32, NonZeroU64};
+
 use anyhow::Context as _;
 use zksync_config::configs;
 use zksync_protobuf::{
@@ -48,6 +50,31 @@ impl ProtoRepr for proto::MerkleTree {
             max_l1_batches_per_iter: required(&self.max_l1_batches_per_iter)
                 .and_then(|x| Ok((*x).try_into()?))
                 .context("max_l1_batches_per_iter")?,
+            hashing_thread_count: self
+                .hashing_thread_count
+                .map(|x| x.try_into())
+                .transpose()
+                .context("hashing_thread_count")?,
+            pruning_retained_l1_batches: self
+                .pruning_retained_l1_batches
+                .map(|x| NonZeroU32::new(x).context("cannot be 0"))
+                .transpose()
+                .context("pruning_retained_l1_batches")?,
+            compaction_interval_sec: self
+                .compaction_interval_sec
+                .map(|x| NonZeroU64::new(x).context("cannot be 0"))
+                .transpose()
+                .context("compaction_interval_sec")?,
+            verification_interval_sec: self
+                .verification_interval_sec
+                .map(|x| NonZeroU64::new(x).context("cannot be 0"))
+                .transpose()
+                .context("verification_interval_sec")?,
+            checkpoint_interval_sec: self
+                .checkpoint_interval_sec
+                .map(|x| NonZeroU64::new(x).context("cannot be 0"))
+                .transpose()
+                .context("checkpoint_interval_sec")?,
         })
     }
 
@@ -60,6 +87,11 @@ impl ProtoRepr for proto::MerkleTree {
             memtable_capacity_mb: Some(this.memtable_capacity_mb.try_into().unwrap()),
             stalled_writes_timeout_sec: Some(this.stalled_writes_timeout_sec),
             max_l1_batches_per_iter: Some(this.max_l1_batches_per_iter.try_into().unwrap()),
+            hashing_thread_count: this.hashing_thread_count.map(|x| x.try_into().unwrap()),
+            pruning_retained_l1_batches: this.pruning_retained_l1_batches.map(NonZeroU32::get),
+            compaction_interval_sec: this.compaction_interval_sec.map(NonZeroU64::get),
+            verification_interval_sec: this.verification_interval_sec.map(NonZeroU64::get),
+            checkpoint_interval_sec: this.checkpoint_interval_sec.map(NonZeroU64::get),
         }
     }
 }
@@ -109,6 +141,9 @@ impl ProtoRepr for proto::Postgres {
             statement_timeout_sec: self.statement_timeout_sec,
             long_connection_threshold_ms: self.long_connection_threshold_ms,
             slow_query_threshold_ms: self.slow_query_threshold_ms,
+            report_all_query_latencies: self.report_all_query_latencies,
+            redact_query_args: self.redact_query_args,
+            max_replica_lag_sec: self.max_replica_lag_sec,
             test_server_url,
             test_prover_url,
         })
@@ -122,6 +157,9 @@ impl ProtoRepr for proto::Postgres {
             statement_timeout_sec: this.statement_timeout_sec,
             long_connection_threshold_ms: this.long_connection_threshold_ms,
             slow_query_threshold_ms: this.slow_query_threshold_ms,
+            report_all_query_latencies: this.report_all_query_latencies,
+            redact_query_args: this.redact_query_args,
+            max_replica_lag_sec: this.max_replica_lag_sec,
             test: Some(proto::TestDatabase {
                 server_url: this.test_server_url.clone(),
                 prover_url: this.test_prover_url.clone(),
@@ -30,6 +30,7 @@ impl proto::PubdataSendingMode {
         match x {
             From::Calldata => Self::Calldata,
             From::Blobs => Self::Blobs,
+            From::Custom => Self::Custom,
         }
     }
 
@@ -38,10 +39,76 @@ impl proto::PubdataSendingMode {
         match self {
             Self::Calldata => To::Calldata,
             Self::Blobs => To::Blobs,
+            Self::Custom => To::Custom,
         }
     }
 }
 
+impl proto::FeeBumpStrategy {
+    fn new(x: &configs::eth_sender::FeeBumpStrategy) -> Self {
+        use configs::eth_sender::FeeBumpStrategy as From;
+        match x {
+            From::Linear => Self::Linear,
+            From::Exponential => Self::Exponential,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::FeeBumpStrategy {
+        use configs::eth_sender::FeeBumpStrategy as To;
+        match self {
+            Self::Linear => To::Linear,
+            Self::Exponential => To::Exponential,
+        }
+    }
+}
+
+impl proto::L1BatchAggregationCriterion {
+    fn new(x: &configs::eth_sender::L1BatchAggregationCriterion) -> Self {
+        use configs::eth_sender::L1BatchAggregationCriterion as From;
+        match x {
+            From::Number => Self::Number,
+            From::Gas => Self::Gas,
+            From::DataSize => Self::DataSize,
+            From::Deadline => Self::Deadline,
+        }
+    }
+
+    fn parse(&self) -> configs::eth_sender::L1BatchAggregationCriterion {
+        use configs::eth_sender::L1BatchAggregationCriterion as To;
+        match self {
+            Self::Number => To::Number,
+            Self::Gas => To::Gas,
+            Self::DataSize => To::DataSize,
+            Self::Deadline => To::Deadline,
+        }
+    }
+
+    /// Reads a list of criteria; an empty list is treated as the default criteria.
+    fn read_list(
+        raw: &[i32],
+    ) -> anyhow::Result<Option<Vec<configs::eth_sender::L1BatchAggregationCriterion>>> {
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        let criteria = raw
+            .iter()
+            .enumerate()
+            .map(|(i, x)| Ok(Self::try_from(*x).context(i)?.parse()))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Some(criteria))
+    }
+
+    fn build_list(
+        criteria: Option<&[configs::eth_sender::L1BatchAggregationCriterion]>,
+    ) -> Vec<i32> {
+        criteria
+            .unwrap_or_default()
+            .iter()
+            .map(|x| Self::new(x).into())
+            .collect()
+    }
+}
+
 impl ProtoRepr for proto::Eth {
     type Type = configs::eth_sender::EthConfig;
 
@@ -65,7 +132,7 @@ impl ProtoRepr for proto::Eth {
 impl ProtoRepr for proto::Sender {
     type Type = configs::eth_sender::SenderConfig;
     fn read(&self) -> anyhow::Result<Self::Type> {
-        Ok(Self::Type {
+        let config = Self::Type {
             aggregated_proof_sizes: self
                 .aggregated_proof_sizes
                 .iter()
@@ -109,7 +176,45 @@ impl ProtoRepr for proto::Sender {
                 .and_then(|x| Ok(proto::PubdataSendingMode::try_from(*x)?))
                 .context("pubdata_sending_mode")?
                 .parse(),
-        })
+            dynamic_pubdata_sending_hysteresis: self.dynamic_pubdata_sending_hysteresis,
+            fee_bump_strategy: self
+                .fee_bump_strategy
+                .map(proto::FeeBumpStrategy::try_from)
+                .transpose()
+                .context("fee_bump_strategy")?
+                .map(|x| x.parse()),
+            fee_bump_percent: self.fee_bump_percent,
+            blob_fee_bump_strategy: self
+                .blob_fee_bump_strategy
+                .map(proto::FeeBumpStrategy::try_from)
+                .transpose()
+                .context("blob_fee_bump_strategy")?
+                .map(|x| x.parse()),
+            blob_fee_bump_percent: self.blob_fee_bump_percent,
+            max_total_fee_bump_percent: self.max_total_fee_bump_percent,
+            blocks_before_first_fee_bump: self.blocks_before_first_fee_bump,
+            private_relay_fallback_blocks: self.private_relay_fallback_blocks,
+            heal_nonce_divergence: self.heal_nonce_divergence,
+            heal_nonce_divergence_after_blocks: self.heal_nonce_divergence_after_blocks,
+            dry_run: self.dry_run,
+            pause_commit: self.pause_commit,
+            pause_prove: self.pause_prove,
+            pause_execute: self.pause_execute,
+            commit_aggregation_criteria: proto::L1BatchAggregationCriterion::read_list(
+                &self.commit_aggregation_criteria,
+            )
+            .context("commit_aggregation_criteria")?,
+            proof_aggregation_criteria: proto::L1BatchAggregationCriterion::read_list(
+                &self.proof_aggregation_criteria,
+            )
+            .context("proof_aggregation_criteria")?,
+            execute_aggregation_criteria: proto::L1BatchAggregationCriterion::read_list(
+                &self.execute_aggregation_criteria,
+            )
+            .context("execute_aggregation_criteria")?,
+        };
+        config.validate()?;
+        Ok(config)
     }
 
     fn build(this: &Self::Type) -> Self {
@@ -139,6 +244,35 @@ impl ProtoRepr for proto::Sender {
             pubdata_sending_mode: Some(
                 proto::PubdataSendingMode::new(&this.pubdata_sending_mode).into(),
             ),
+            dynamic_pubdata_sending_hysteresis: this.dynamic_pubdata_sending_hysteresis,
+            fee_bump_strategy: this
+                .fee_bump_strategy
+                .as_ref()
+                .map(|x| proto::FeeBumpStrategy::new(x).into()),
+            fee_bump_percent: this.fee_bump_percent,
+            blob_fee_bump_strategy: this
+                .blob_fee_bump_strategy
+                .as_ref()
+                .map(|x| proto::FeeBumpStrategy::new(x).into()),
+            blob_fee_bump_percent: this.blob_fee_bump_percent,
+            max_total_fee_bump_percent: this.max_total_fee_bump_percent,
+            blocks_before_first_fee_bump: this.blocks_before_first_fee_bump,
+            private_relay_fallback_blocks: this.private_relay_fallback_blocks,
+            heal_nonce_divergence: this.heal_nonce_divergence,
+            heal_nonce_divergence_after_blocks: this.heal_nonce_divergence_after_blocks,
+            dry_run: this.dry_run,
+            pause_commit: this.pause_commit,
+            pause_prove: this.pause_prove,
+            pause_execute: this.pause_execute,
+            commit_aggregation_criteria: proto::L1BatchAggregationCriterion::build_list(
+                this.commit_aggregation_criteria.as_deref(),
+            ),
+            proof_aggregation_criteria: proto::L1BatchAggregationCriterion::build_list(
+                this.proof_aggregation_criteria.as_deref(),
+            ),
+            execute_aggregation_criteria: proto::L1BatchAggregationCriterion::build_list(
+                this.execute_aggregation_criteria.as_deref(),
+            ),
         }
     }
 }
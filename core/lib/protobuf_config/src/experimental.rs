@@ -1,4 +1,4 @@
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroU64};
 
 use anyhow::Context as _;
 use zksync_config::configs;
@@ -21,6 +21,11 @@ impl ProtoRepr for proto::Db {
                 .map(|count| NonZeroU32::new(count).context("cannot be 0"))
                 .transpose()
                 .context("state_keeper_db_max_open_files")?,
+            state_keeper_db_compaction_interval_sec: self
+                .state_keeper_db_compaction_interval_sec
+                .map(|interval| NonZeroU64::new(interval).context("cannot be 0"))
+                .transpose()
+                .context("state_keeper_db_compaction_interval_sec")?,
         })
     }
 
@@ -34,6 +39,9 @@ impl ProtoRepr for proto::Db {
             state_keeper_db_max_open_files: this
                 .state_keeper_db_max_open_files
                 .map(NonZeroU32::get),
+            state_keeper_db_compaction_interval_sec: this
+                .state_keeper_db_compaction_interval_sec
+                .map(NonZeroU64::get),
         }
     }
 }
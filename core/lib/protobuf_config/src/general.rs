@@ -39,6 +39,17 @@ impl ProtoRepr for proto::GeneralConfig {
             observability: read_optional_repr(&self.observability).context("observability")?,
             protective_reads_writer_config: read_optional_repr(&self.protective_reads_writer)
                 .context("vm_runner")?,
+            shadow_execution_config: read_optional_repr(&self.shadow_execution)
+                .context("shadow_execution")?,
+            pruning: read_optional_repr(&self.pruning).context("pruning")?,
+            block_notifications: read_optional_repr(&self.block_notifications)
+                .context("block_notifications")?,
+            change_feed: read_optional_repr(&self.change_feed).context("change_feed")?,
+            da_dispatcher_config: read_optional_repr(&self.da_dispatcher)
+                .context("da_dispatcher")?,
+            da_client_config: read_optional_repr(&self.da_client).context("da_client")?,
+            base_token_adjuster: read_optional_repr(&self.base_token_adjuster)
+                .context("base_token_adjuster")?,
         })
     }
 
@@ -74,6 +85,13 @@ impl ProtoRepr for proto::GeneralConfig {
                 .protective_reads_writer_config
                 .as_ref()
                 .map(ProtoRepr::build),
+            shadow_execution: this.shadow_execution_config.as_ref().map(ProtoRepr::build),
+            pruning: this.pruning.as_ref().map(ProtoRepr::build),
+            block_notifications: this.block_notifications.as_ref().map(ProtoRepr::build),
+            change_feed: this.change_feed.as_ref().map(ProtoRepr::build),
+            da_dispatcher: this.da_dispatcher_config.as_ref().map(ProtoRepr::build),
+            da_client: this.da_client_config.as_ref().map(ProtoRepr::build),
+            base_token_adjuster: this.base_token_adjuster.as_ref().map(ProtoRepr::build),
         }
     }
 }
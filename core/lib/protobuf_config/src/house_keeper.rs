@@ -49,6 +49,8 @@ impl ProtoRepr for proto::HouseKeeper {
                 .fri_gpu_prover_archiver_archiving_interval_ms,
             fri_gpu_prover_archiver_archive_after_secs: self
                 .fri_gpu_prover_archiver_archive_after_secs,
+            table_partitioning_interval_ms: self.table_partitioning_interval_ms,
+            table_partition_size: self.table_partition_size,
         })
     }
 
@@ -83,6 +85,8 @@ impl ProtoRepr for proto::HouseKeeper {
                 .fri_gpu_prover_archiver_archiving_interval_ms,
             fri_gpu_prover_archiver_archive_after_secs: this
                 .fri_gpu_prover_archiver_archive_after_secs,
+            table_partitioning_interval_ms: this.table_partitioning_interval_ms,
+            table_partition_size: this.table_partition_size,
         }
     }
 }
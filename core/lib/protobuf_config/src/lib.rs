@@ -5,11 +5,16 @@
 //! * protobuf json format
 
 mod api;
+mod base_token_adjuster;
+mod block_notifications;
 mod chain;
+mod change_feed;
 mod circuit_breaker;
 mod consensus;
 mod contract_verifier;
 mod contracts;
+mod da_client;
+mod da_dispatcher;
 mod database;
 mod eth;
 mod experimental;
@@ -21,6 +26,7 @@ mod observability;
 mod proof_data_handler;
 pub mod proto;
 mod prover;
+mod pruning;
 mod secrets;
 mod snapshots_creator;
 pub mod testonly;
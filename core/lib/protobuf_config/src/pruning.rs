@@ -0,0 +1,31 @@
+use std::num::NonZeroU64;
+
+use anyhow::Context as _;
+use zksync_config::configs;
+use zksync_protobuf::{repr::ProtoRepr, required};
+
+use crate::proto::pruning as proto;
+
+impl ProtoRepr for proto::Pruning {
+    type Type = configs::PruningConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            chunk_size: *required(&self.chunk_size).context("chunk_size")?,
+            removal_delay_sec: NonZeroU64::new(
+                *required(&self.removal_delay_sec).context("removal_delay_sec")?,
+            )
+            .context("removal_delay_sec must be positive")?,
+            data_retention_sec: *required(&self.data_retention_sec)
+                .context("data_retention_sec")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            chunk_size: Some(this.chunk_size),
+            removal_delay_sec: Some(this.removal_delay_sec.get()),
+            data_retention_sec: Some(this.data_retention_sec),
+        }
+    }
+}
@@ -5,7 +5,7 @@ use secrecy::ExposeSecret;
 use zksync_basic_types::url::SensitiveUrl;
 use zksync_config::configs::{
     consensus::{ConsensusSecrets, NodeSecretKey, ValidatorSecretKey},
-    secrets::Secrets,
+    secrets::{ApiSecrets, Secrets},
     DatabaseSecrets, L1Secrets,
 };
 use zksync_protobuf::{required, ProtoRepr};
@@ -20,6 +20,7 @@ impl ProtoRepr for proto::Secrets {
             consensus: read_optional_repr(&self.consensus).context("consensus")?,
             database: read_optional_repr(&self.database).context("database")?,
             l1: read_optional_repr(&self.l1).context("l1")?,
+            api: read_optional_repr(&self.api).context("api")?,
         })
     }
 
@@ -28,6 +29,7 @@ impl ProtoRepr for proto::Secrets {
             database: this.database.as_ref().map(ProtoRepr::build),
             l1: this.l1.as_ref().map(ProtoRepr::build),
             consensus: this.consensus.as_ref().map(ProtoRepr::build),
+            api: this.api.as_ref().map(ProtoRepr::build),
         }
     }
 }
@@ -50,6 +52,12 @@ impl ProtoRepr for proto::DatabaseSecrets {
         if server_replica_url.is_none() {
             server_replica_url.clone_from(&server_url)
         }
+        let server_read_replica_urls = self
+            .server_read_replica_urls
+            .iter()
+            .map(|url| url.parse::<SensitiveUrl>())
+            .collect::<Result<_, _>>()
+            .context("server_read_replica_urls")?;
         let prover_url = self
             .prover_url
             .as_deref()
@@ -60,6 +68,7 @@ impl ProtoRepr for proto::DatabaseSecrets {
             server_url,
             prover_url,
             server_replica_url,
+            server_read_replica_urls,
         })
     }
 
@@ -70,6 +79,11 @@ impl ProtoRepr for proto::DatabaseSecrets {
                 .server_replica_url
                 .as_ref()
                 .map(|a| a.expose_str().to_string()),
+            server_read_replica_urls: this
+                .server_read_replica_urls
+                .iter()
+                .map(|a| a.expose_str().to_string())
+                .collect(),
             prover_url: this.prover_url.as_ref().map(|a| a.expose_str().to_string()),
         }
     }
@@ -80,12 +94,43 @@ impl ProtoRepr for proto::L1Secrets {
     fn read(&self) -> anyhow::Result<Self::Type> {
         Ok(Self::Type {
             l1_rpc_url: SensitiveUrl::from_str(required(&self.l1_rpc_url).context("l1_rpc_url")?)?,
+            private_relay_rpc_url: self
+                .private_relay_rpc_url
+                .as_deref()
+                .map(SensitiveUrl::from_str)
+                .transpose()
+                .context("private_relay_rpc_url")?,
+            gateway_rpc_url: self
+                .gateway_rpc_url
+                .as_deref()
+                .map(SensitiveUrl::from_str)
+                .transpose()
+                .context("gateway_rpc_url")?,
+            fallback_l1_rpc_urls: self
+                .fallback_l1_rpc_urls
+                .iter()
+                .map(|url| url.parse::<SensitiveUrl>())
+                .collect::<Result<_, _>>()
+                .context("fallback_l1_rpc_urls")?,
         })
     }
 
     fn build(this: &Self::Type) -> Self {
         Self {
             l1_rpc_url: Some(this.l1_rpc_url.expose_str().to_string()),
+            private_relay_rpc_url: this
+                .private_relay_rpc_url
+                .as_ref()
+                .map(|url| url.expose_str().to_string()),
+            gateway_rpc_url: this
+                .gateway_rpc_url
+                .as_ref()
+                .map(|url| url.expose_str().to_string()),
+            fallback_l1_rpc_urls: this
+                .fallback_l1_rpc_urls
+                .iter()
+                .map(|url| url.expose_str().to_string())
+                .collect(),
         }
     }
 }
@@ -115,3 +160,31 @@ impl ProtoRepr for proto::ConsensusSecrets {
         }
     }
 }
+
+impl ProtoRepr for proto::ApiSecrets {
+    type Type = ApiSecrets;
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            admin_token: self.admin_token.clone().map(Into::into),
+            client_rate_limit_api_keys: self
+                .client_rate_limit_api_keys
+                .iter()
+                .map(|key| key.clone().into())
+                .collect(),
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            admin_token: this
+                .admin_token
+                .as_ref()
+                .map(|token| token.expose_secret().clone()),
+            client_rate_limit_api_keys: this
+                .client_rate_limit_api_keys
+                .iter()
+                .map(|key| key.expose_secret().clone())
+                .collect(),
+        }
+    }
+}
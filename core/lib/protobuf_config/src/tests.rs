@@ -24,6 +24,7 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::consensus::GenesisSpec>>(rng);
     test_encode_all_formats::<ReprConv<proto::consensus::Config>>(rng);
     test_encode_all_formats::<ReprConv<proto::secrets::ConsensusSecrets>>(rng);
+    test_encode_all_formats::<ReprConv<proto::secrets::ApiSecrets>>(rng);
     test_encode_all_formats::<ReprConv<proto::secrets::Secrets>>(rng);
     test_encode_all_formats::<ReprConv<proto::contract_verifier::ContractVerifier>>(rng);
     test_encode_all_formats::<ReprConv<proto::contracts::Contracts>>(rng);
@@ -42,6 +43,12 @@ fn test_encoding() {
     test_encode_all_formats::<ReprConv<proto::prover::ProofDataHandler>>(rng);
     test_encode_all_formats::<ReprConv<proto::snapshot_creator::SnapshotsCreator>>(rng);
     test_encode_all_formats::<ReprConv<proto::observability::Observability>>(rng);
+    test_encode_all_formats::<ReprConv<proto::pruning::Pruning>>(rng);
+    test_encode_all_formats::<ReprConv<proto::block_notifications::BlockNotifications>>(rng);
+    test_encode_all_formats::<ReprConv<proto::change_feed::ChangeFeed>>(rng);
+    test_encode_all_formats::<ReprConv<proto::da_dispatcher::DataAvailabilityDispatcher>>(rng);
+    test_encode_all_formats::<ReprConv<proto::da_client::DataAvailabilityClient>>(rng);
+    test_encode_all_formats::<ReprConv<proto::base_token_adjuster::BaseTokenAdjuster>>(rng);
 }
 
 pub fn decode_yaml_repr<T: ProtoRepr>(
@@ -26,3 +26,31 @@ impl ProtoRepr for proto::ProtectiveReadsWriter {
         }
     }
 }
+
+impl ProtoRepr for proto::ShadowExecution {
+    type Type = configs::ShadowExecutionConfig;
+
+    fn read(&self) -> anyhow::Result<Self::Type> {
+        Ok(Self::Type {
+            db_path: required(&self.db_path).context("db_path")?.clone(),
+            window_size: *required(&self.window_size).context("window_size")? as u32,
+            first_processed_batch: L1BatchNumber(
+                *required(&self.first_processed_batch).context("first_batch")? as u32,
+            ),
+            protocol_version: self
+                .protocol_version
+                .map(u16::try_from)
+                .transpose()
+                .context("protocol_version")?,
+        })
+    }
+
+    fn build(this: &Self::Type) -> Self {
+        Self {
+            db_path: Some(this.db_path.clone()),
+            window_size: Some(this.window_size as u64),
+            first_processed_batch: Some(this.first_processed_batch.0 as u64),
+            protocol_version: this.protocol_version.map(u32::from),
+        }
+    }
+}
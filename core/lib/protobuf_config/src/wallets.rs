@@ -1,7 +1,8 @@
 use anyhow::Context;
+use secrecy::ExposeSecret;
 use zksync_config::configs::{
     self,
-    wallets::{AddressWallet, EthSender, StateKeeper, Wallet},
+    wallets::{AddressWallet, EthSender, RemoteSignerConfig, StateKeeper, Wallet, WalletSigner},
 };
 use zksync_protobuf::{required, ProtoRepr};
 
@@ -11,31 +12,30 @@ impl ProtoRepr for proto::Wallets {
     type Type = configs::wallets::Wallets;
     fn read(&self) -> anyhow::Result<Self::Type> {
         let eth_sender = if self.operator.is_some() && self.blob_operator.is_some() {
-            let blob_operator = if let Some(blob_operator) = &self.blob_operator {
-                Some(Wallet::from_private_key_bytes(
-                    parse_h256(required(&blob_operator.private_key).context("blob operator")?)?,
-                    blob_operator
-                        .address
-                        .as_ref()
-                        .and_then(|a| parse_h160(a).ok()),
-                )?)
-            } else {
-                None
-            };
-
-            let operator_wallet = &self.operator.clone().context("Operator private key")?;
+            let blob_operator = self
+                .blob_operator
+                .as_ref()
+                .map(|wallet| read_private_key_wallet(wallet).context("blob operator"))
+                .transpose()?;
+            let operator_wallet = self.operator.as_ref().context("Operator private key")?;
+            let operator = read_private_key_wallet(operator_wallet).context("operator")?;
 
-            let operator = Wallet::from_private_key_bytes(
-                parse_h256(required(&operator_wallet.private_key).context("operator")?)?,
-                operator_wallet
-                    .address
-                    .as_ref()
-                    .and_then(|a| parse_h160(a).ok()),
-            )?;
+            let successor_operator = self
+                .successor_operator
+                .as_ref()
+                .map(|wallet| read_private_key_wallet(wallet).context("successor_operator"))
+                .transpose()?;
+            let successor_blob_operator = self
+                .successor_blob_operator
+                .as_ref()
+                .map(|wallet| read_private_key_wallet(wallet).context("successor_blob_operator"))
+                .transpose()?;
 
             Some(EthSender {
                 operator,
                 blob_operator,
+                successor_operator,
+                successor_blob_operator,
             })
         } else {
             None
@@ -60,20 +60,28 @@ impl ProtoRepr for proto::Wallets {
     }
 
     fn build(this: &Self::Type) -> Self {
+        let (successor_operator, successor_blob_operator) =
+            if let Some(eth_sender) = &this.eth_sender {
+                (
+                    eth_sender
+                        .successor_operator
+                        .as_ref()
+                        .map(build_private_key_wallet),
+                    eth_sender
+                        .successor_blob_operator
+                        .as_ref()
+                        .map(build_private_key_wallet),
+                )
+            } else {
+                (None, None)
+            };
         let (operator, blob_operator) = if let Some(eth_sender) = &this.eth_sender {
-            let blob = eth_sender
-                .blob_operator
-                .as_ref()
-                .map(|blob| proto::PrivateKeyWallet {
-                    address: Some(format!("{:?}", blob.address())),
-                    private_key: Some(format!("{:?}", blob.private_key())),
-                });
             (
-                Some(proto::PrivateKeyWallet {
-                    address: Some(format!("{:?}", eth_sender.operator.address())),
-                    private_key: Some(format!("{:?}", eth_sender.operator.private_key())),
-                }),
-                blob,
+                Some(build_private_key_wallet(&eth_sender.operator)),
+                eth_sender
+                    .blob_operator
+                    .as_ref()
+                    .map(build_private_key_wallet),
             )
         } else {
             (None, None)
@@ -89,6 +97,75 @@ impl ProtoRepr for proto::Wallets {
             blob_operator,
             operator,
             fee_account,
+            successor_operator,
+            successor_blob_operator,
+        }
+    }
+}
+
+fn read_private_key_wallet(wallet: &proto::PrivateKeyWallet) -> anyhow::Result<Wallet> {
+    let Some(remote_signer) = &wallet.remote_signer else {
+        return Wallet::from_private_key_bytes(
+            parse_h256(required(&wallet.private_key).context("private_key")?)?,
+            wallet.address.as_ref().and_then(|a| parse_h160(a).ok()),
+        );
+    };
+
+    anyhow::ensure!(
+        wallet.private_key.is_none(),
+        "private_key must not be set for wallets with a remote signer"
+    );
+    let address = parse_h160(required(&wallet.address).context("address")?).context("address")?;
+    let remote_signer = match remote_signer {
+        proto::private_key_wallet::RemoteSigner::GcpKms(signer) => RemoteSignerConfig::GcpKms {
+            key_name: required(&signer.key_name).context("key_name")?.clone(),
+        },
+        proto::private_key_wallet::RemoteSigner::AwsKms(signer) => RemoteSignerConfig::AwsKms {
+            key_id: required(&signer.key_id).context("key_id")?.clone(),
+            region: required(&signer.region).context("region")?.clone(),
+        },
+        proto::private_key_wallet::RemoteSigner::RemoteApi(signer) => RemoteSignerConfig::Api {
+            url: required(&signer.url)
+                .context("url")?
+                .parse()
+                .context("url")?,
+            authorization: signer.authorization.clone().map(Into::into),
+        },
+    };
+    Ok(Wallet::remote(address, remote_signer))
+}
+
+fn build_private_key_wallet(wallet: &Wallet) -> proto::PrivateKeyWallet {
+    let (private_key, remote_signer) = match wallet.signer() {
+        WalletSigner::PrivateKey(private_key) => (Some(format!("{:?}", private_key)), None),
+        WalletSigner::Remote(signer) => {
+            let signer = match signer {
+                RemoteSignerConfig::GcpKms { key_name } => {
+                    proto::private_key_wallet::RemoteSigner::GcpKms(proto::GcpKmsSigner {
+                        key_name: Some(key_name.clone()),
+                    })
+                }
+                RemoteSignerConfig::AwsKms { key_id, region } => {
+                    proto::private_key_wallet::RemoteSigner::AwsKms(proto::AwsKmsSigner {
+                        key_id: Some(key_id.clone()),
+                        region: Some(region.clone()),
+                    })
+                }
+                RemoteSignerConfig::Api { url, authorization } => {
+                    proto::private_key_wallet::RemoteSigner::RemoteApi(proto::RemoteApiSigner {
+                        url: Some(url.expose_str().to_owned()),
+                        authorization: authorization
+                            .as_ref()
+                            .map(|value| value.expose_secret().clone()),
+                    })
+                }
+            };
+            (None, Some(signer))
         }
+    };
+    proto::PrivateKeyWallet {
+        address: Some(format!("{:?}", wallet.address())),
+        private_key,
+        remote_signer,
     }
 }
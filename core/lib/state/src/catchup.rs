@@ -1,4 +1,7 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context;
 use once_cell::sync::OnceCell;
@@ -42,7 +45,8 @@ impl AsyncCatchupTask {
         }
     }
 
-    /// Block until RocksDB cache instance is caught up with Postgres.
+    /// Block until RocksDB cache instance is caught up with Postgres. If [`RocksdbStorageOptions::compaction_interval`]
+    /// is set, continues running after the catch-up, periodically compacting RocksDB until a stop signal is received.
     ///
     /// # Errors
     ///
@@ -50,6 +54,7 @@ impl AsyncCatchupTask {
     pub async fn run(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let started_at = Instant::now();
         tracing::debug!("Catching up RocksDB asynchronously");
+        let compaction_interval = self.state_keeper_db_options.compaction_interval;
 
         let mut rocksdb_builder = RocksdbStorage::builder_with_options(
             self.state_keeper_db_path.as_ref(),
@@ -75,13 +80,47 @@ impl AsyncCatchupTask {
             .await
             .context("Failed to catch up RocksDB to Postgres")?;
         drop(connection);
-        if let Some(rocksdb) = rocksdb {
-            self.rocksdb_cell
-                .set(rocksdb.into_rocksdb())
-                .map_err(|_| anyhow::anyhow!("Async RocksDB cache was initialized twice"))?;
-        } else {
+        let Some(rocksdb) = rocksdb else {
             tracing::info!("Synchronizing RocksDB interrupted");
+            return Ok(());
+        };
+        let rocksdb = rocksdb.into_rocksdb();
+        self.rocksdb_cell
+            .set(rocksdb.clone())
+            .map_err(|_| anyhow::anyhow!("Async RocksDB cache was initialized twice"))?;
+
+        if let Some(interval) = compaction_interval {
+            Self::compact_periodically(rocksdb, interval, stop_receiver).await?;
+        }
+        Ok(())
+    }
+
+    async fn compact_periodically(
+        rocksdb: RocksDB<StateKeeperColumnFamily>,
+        interval: Duration,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting periodic compaction of state keeper RocksDB with interval {interval:?}"
+        );
+        while tokio::time::timeout(interval, stop_receiver.changed())
+            .await
+            .is_err()
+        {
+            tracing::info!("Starting scheduled compaction of state keeper RocksDB");
+            let rocksdb = rocksdb.clone();
+            let compacted = tokio::task::spawn_blocking(move || rocksdb.compact())
+                .await
+                .context("state keeper RocksDB compaction panicked")?;
+            if !compacted {
+                tracing::info!(
+                    "Skipped scheduled state keeper RocksDB compaction since another compaction is in progress"
+                );
+            }
         }
+        tracing::info!(
+            "Stop signal received, stopping periodic compaction of state keeper RocksDB"
+        );
         Ok(())
     }
 }
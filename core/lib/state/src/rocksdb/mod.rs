@@ -24,7 +24,7 @@ use std::{
     mem,
     num::NonZeroU32,
     path::{Path, PathBuf},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
@@ -133,6 +133,9 @@ pub struct RocksdbStorageOptions {
     /// Number of open files that can be simultaneously opened by RocksDB. Default is `None`, for no limit.
     /// Can be used to restrict memory usage of RocksDB.
     pub max_open_files: Option<NonZeroU32>,
+    /// Interval between manual compactions of RocksDB performed by [`AsyncCatchupTask`](crate::AsyncCatchupTask)
+    /// after the catch-up. Default is `None`, meaning that RocksDB is only compacted automatically.
+    pub compaction_interval: Option<Duration>,
 }
 
 impl Default for RocksdbStorageOptions {
@@ -140,6 +143,7 @@ impl Default for RocksdbStorageOptions {
         Self {
             block_cache_capacity: 128 << 20,
             max_open_files: None,
+            compaction_interval: None,
         }
     }
 }
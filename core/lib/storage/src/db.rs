@@ -8,7 +8,7 @@ use std::{
     ops,
     path::Path,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc, Condvar, Mutex, Weak,
     },
     thread,
@@ -104,6 +104,8 @@ pub(crate) struct RocksDBInner {
     db: DB,
     db_name: &'static str,
     cf_names: HashSet<&'static str>,
+    manual_compaction_in_progress: AtomicBool,
+    is_secondary: bool,
     _registry_entry: RegistryEntry,
     // Importantly, `Cache`s must be dropped after `DB`, so we place them as the last field
     // (fields in a struct are dropped in the declaration order).
@@ -168,13 +170,30 @@ impl RocksDBInner {
                 metrics.index_and_filters_size[&labels].set(size);
             }
 
+            let compaction_pending = self.int_property(cf, properties::COMPACTION_PENDING);
+            if let Some(compaction_pending) = compaction_pending {
+                metrics.compaction_pending[&labels].set(compaction_pending);
+            }
+
+            // Each level-0 file and each non-empty level >0 is a sorted run that may need to be checked on a read.
+            let mut sorted_runs = 0;
             for level in 0..=MAX_LEVEL {
                 let files_at_level = self.int_property(cf, &properties::num_files_at_level(level));
                 if let Some(files_at_level) = files_at_level {
                     metrics.files_at_level[&labels.for_level(level)].set(files_at_level);
+                    sorted_runs += if level == 0 {
+                        files_at_level
+                    } else {
+                        u64::from(files_at_level > 0)
+                    };
                 }
             }
+            metrics.sorted_runs[&labels].set(sorted_runs);
         }
+        let manual_compaction_in_progress =
+            self.manual_compaction_in_progress.load(Ordering::Relaxed);
+        metrics.manual_compaction_in_progress[&self.db_name.into()]
+            .set(manual_compaction_in_progress.into());
     }
 
     fn int_property(&self, cf: &ColumnFamily, name: &CStr) -> Option<u64> {
@@ -340,6 +359,27 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
     }
 
     pub fn with_options(path: &Path, options: RocksDBOptions) -> Result<Self, rocksdb::Error> {
+        Self::open(path, None, options)
+    }
+
+    /// Opens a read-only secondary instance for the database at `primary_path`. `secondary_path` is used
+    /// by the secondary instance to store its info logs; it must not coincide with `primary_path`.
+    ///
+    /// A secondary instance sees changes made by the primary instance (which may be running in another process)
+    /// only after calling [`Self::try_catch_up_with_primary()`]. All writes to a secondary instance fail.
+    pub fn secondary(
+        primary_path: &Path,
+        secondary_path: &Path,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
+        Self::open(primary_path, Some(secondary_path), options)
+    }
+
+    fn open(
+        path: &Path,
+        secondary_path: Option<&Path>,
+        options: RocksDBOptions,
+    ) -> Result<Self, rocksdb::Error> {
         let caches = RocksDBCaches::new(options.block_cache_capacity);
         let mut db_options = Self::rocksdb_options(None, None);
         let max_open_files = if let Some(non_zero) = options.max_open_files {
@@ -402,23 +442,40 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             ColumnFamilyDescriptor::new(cf_name, cf_options)
         });
 
-        let db = DB::open_cf_descriptors(&db_options, path, cfs)?;
+        let db = if let Some(secondary_path) = secondary_path {
+            // RocksDB docs recommend not limiting the number of open files for secondary instances, since otherwise
+            // files deleted by the primary may still be referenced by the secondary.
+            db_options.set_max_open_files(-1);
+            DB::open_cf_descriptors_as_secondary(&db_options, path, secondary_path, cfs)?
+        } else {
+            DB::open_cf_descriptors(&db_options, path, cfs)?
+        };
         let inner = Arc::new(RocksDBInner {
             db,
             db_name: CF::DB_NAME,
             cf_names,
+            manual_compaction_in_progress: AtomicBool::new(false),
+            is_secondary: secondary_path.is_some(),
             _registry_entry: RegistryEntry::new(),
             _caches: caches,
         });
         RocksdbSizeMetrics::register(CF::DB_NAME, Arc::downgrade(&inner));
 
-        tracing::info!(
-            "Initialized RocksDB `{}` at `{}` with {options:?}",
-            CF::DB_NAME,
-            path.display()
-        );
-
-        inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        if let Some(secondary_path) = secondary_path {
+            tracing::info!(
+                "Initialized secondary RocksDB `{}` at `{}` (primary at `{}`) with {options:?}",
+                CF::DB_NAME,
+                secondary_path.display(),
+                path.display()
+            );
+        } else {
+            tracing::info!(
+                "Initialized RocksDB `{}` at `{}` with {options:?}",
+                CF::DB_NAME,
+                path.display()
+            );
+            inner.wait_for_writes_to_resume(&options.stalled_writes_retries);
+        }
         Ok(Self {
             inner,
             sync_writes: false,
@@ -468,6 +525,27 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
         }
     }
 
+    /// Checks whether this is a read-only secondary instance of the database.
+    pub fn is_secondary(&self) -> bool {
+        self.inner.is_secondary
+    }
+
+    /// Makes a secondary instance catch up with changes made by the primary instance. This is a no-op
+    /// for primary instances.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB I/O errors.
+    pub fn try_catch_up_with_primary(&self) -> Result<(), rocksdb::Error> {
+        if !self.inner.is_secondary {
+            return Ok(());
+        }
+        let latency = METRICS.catch_up_latency[&CF::DB_NAME.into()].start();
+        self.inner.db.try_catch_up_with_primary()?;
+        latency.observe();
+        Ok(())
+    }
+
     pub fn estimated_number_of_entries(&self, cf: CF) -> u64 {
         const ERROR_MSG: &str = "failed to get estimated number of entries";
 
@@ -479,6 +557,83 @@ impl<CF: NamedColumnFamily> RocksDB<CF> {
             .unwrap_or(0)
     }
 
+    /// Compacts data in the specified key range of a column family. This is a blocking operation that can be used
+    /// to get rid of tombstones left after deleting many keys, which otherwise slow down reads.
+    pub fn compact_range_cf(&self, cf: CF, keys: ops::Range<&[u8]>) {
+        let cf = self.column_family(cf);
+        self.inner
+            .db
+            .compact_range_cf(cf, Some(keys.start), Some(keys.end));
+    }
+
+    /// Performs manual compaction of all column families in the database. This is a blocking operation
+    /// that may take a long time for large databases. Compaction reduces the number of sorted runs
+    /// that need to be checked on reads (i.e., read amplification), and removes tombstones left after deleting keys.
+    ///
+    /// Returns `false` if manual compaction of this database is already in progress (in which case, this method
+    /// returns immediately).
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a [secondary](Self::secondary()) instance.
+    pub fn compact(&self) -> bool {
+        assert!(
+            !self.inner.is_secondary,
+            "cannot compact secondary RocksDB instance"
+        );
+        let was_in_progress = self
+            .inner
+            .manual_compaction_in_progress
+            .swap(true, Ordering::SeqCst);
+        if was_in_progress {
+            tracing::info!(
+                "Manual compaction of RocksDB `{}` is already in progress",
+                CF::DB_NAME
+            );
+            return false;
+        }
+        // Resets the flag even if compaction panics, so that compaction can be retried afterwards.
+        let _in_progress_guard = InProgressGuard(&self.inner.manual_compaction_in_progress);
+
+        tracing::info!("Started manual compaction of RocksDB `{}`", CF::DB_NAME);
+        let latency = METRICS.manual_compaction_latency[&CF::DB_NAME.into()].start();
+        for &cf in CF::ALL {
+            let cf_handle = self.column_family(cf);
+            self.inner
+                .db
+                .compact_range_cf(cf_handle, None::<&[u8]>, None::<&[u8]>);
+        }
+        let latency = latency.observe();
+        tracing::info!(
+            "Finished manual compaction of RocksDB `{}` in {latency:?}",
+            CF::DB_NAME
+        );
+        true
+    }
+
+    /// Creates a consistent checkpoint of the database in the specified directory, which must not exist.
+    /// If the directory is located on the same filesystem as the database, SST files are hard-linked
+    /// rather than copied, so creating a checkpoint is cheap. The checkpoint can be opened as a separate RocksDB instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called on a [secondary](Self::secondary()) instance.
+    pub fn create_checkpoint(&self, path: &Path) -> Result<(), rocksdb::Error> {
+        assert!(
+            !self.inner.is_secondary,
+            "cannot create checkpoint of secondary RocksDB instance"
+        );
+        let latency = METRICS.checkpoint_latency[&CF::DB_NAME.into()].start();
+        rocksdb::checkpoint::Checkpoint::new(&self.inner.db)?.create_checkpoint(path)?;
+        let latency = latency.observe();
+        tracing::info!(
+            "Created checkpoint of RocksDB `{}` at `{}` in {latency:?}",
+            CF::DB_NAME,
+            path.display()
+        );
+        Ok(())
+    }
+
     pub fn multi_get<K, I>(&self, keys: I) -> Vec<Result<Option<Vec<u8>>, rocksdb::Error>>
     where
         K: AsRef<[u8]>,
@@ -774,6 +929,16 @@ impl Drop for ProfiledOperation {
     }
 }
 
+/// Resets the wrapped flag on drop.
+#[derive(Debug)]
+struct InProgressGuard<'a>(&'a AtomicBool);
+
+impl Drop for InProgressGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
 #[must_use = "Guard will report metrics on drop"]
 pub struct ProfileGuard {
     context: perf::PerfContext,
@@ -1007,6 +1172,116 @@ mod tests {
         assert_eq!(value.unwrap(), b"value");
     }
 
+    #[test]
+    fn manual_compaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        for i in 0_u32..100 {
+            let mut batch = db.new_write_batch();
+            batch.put_cf(NewColumnFamilies::Default, &i.to_be_bytes(), b"value");
+            batch.put_cf(NewColumnFamilies::Other, &i.to_be_bytes(), b"other");
+            db.write(batch).unwrap();
+        }
+        let mut batch = db.new_write_batch();
+        let (start, end) = (0_u32.to_be_bytes(), 50_u32.to_be_bytes());
+        batch.delete_range_cf(NewColumnFamilies::Default, &start[..]..&end[..]);
+        db.write(batch).unwrap();
+
+        assert!(db.compact());
+        assert!(!db
+            .inner
+            .manual_compaction_in_progress
+            .load(Ordering::SeqCst));
+        let value = db
+            .get_cf(NewColumnFamilies::Default, &10_u32.to_be_bytes())
+            .unwrap();
+        assert!(value.is_none());
+        let value = db
+            .get_cf(NewColumnFamilies::Default, &60_u32.to_be_bytes())
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+
+        // Emulate a concurrent compaction.
+        db.inner
+            .manual_compaction_in_progress
+            .store(true, Ordering::SeqCst);
+        assert!(!db.compact());
+    }
+
+    #[test]
+    fn in_progress_flag_is_reset_on_panic() {
+        let flag = AtomicBool::new(true);
+        let result = std::panic::catch_unwind(|| {
+            let _guard = InProgressGuard(&flag);
+            panic!("compaction failed");
+        });
+        assert!(result.is_err());
+        assert!(!flag.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn secondary_instance_catching_up_with_primary() {
+        let temp_dir = TempDir::new().unwrap();
+        let secondary_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let secondary = RocksDB::<NewColumnFamilies>::secondary(
+            temp_dir.path(),
+            secondary_dir.path(),
+            RocksDBOptions::default(),
+        )
+        .unwrap();
+        assert!(secondary.is_secondary());
+        let value = secondary
+            .get_cf(NewColumnFamilies::Default, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Other, b"test", b"other");
+        db.write(batch).unwrap();
+        secondary.try_catch_up_with_primary().unwrap();
+        let value = secondary.get_cf(NewColumnFamilies::Other, b"test").unwrap();
+        assert_eq!(value.unwrap(), b"other");
+
+        let mut batch = secondary.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"new_value");
+        secondary.write(batch).unwrap_err();
+    }
+
+    #[test]
+    fn creating_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = RocksDB::<NewColumnFamilies>::new(temp_dir.path())
+            .unwrap()
+            .with_sync_writes();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"value");
+        db.write(batch).unwrap();
+
+        let checkpoint_dir = TempDir::new().unwrap();
+        let checkpoint_path = checkpoint_dir.path().join("checkpoint");
+        db.create_checkpoint(&checkpoint_path).unwrap();
+        let mut batch = db.new_write_batch();
+        batch.put_cf(NewColumnFamilies::Default, b"test", b"new_value");
+        db.write(batch).unwrap();
+
+        let checkpoint = RocksDB::<NewColumnFamilies>::new(&checkpoint_path).unwrap();
+        let value = checkpoint
+            .get_cf(NewColumnFamilies::Default, b"test")
+            .unwrap();
+        assert_eq!(value.unwrap(), b"value");
+        // The checkpoint directory must not exist.
+        db.create_checkpoint(&checkpoint_path).unwrap_err();
+    }
+
     #[test]
     fn write_batch_can_be_restored_from_bytes() {
         let temp_dir = TempDir::new().unwrap();
@@ -69,6 +69,15 @@ pub(crate) struct RocksdbMetrics {
     /// leads to a panic).
     #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
     stalled_write_duration: Family<DbLabel, Histogram<Duration>>,
+    /// Latency of manual compactions of a RocksDB instance.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub manual_compaction_latency: Family<DbLabel, Histogram<Duration>>,
+    /// Latency of catching up a secondary RocksDB instance with the primary instance.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub catch_up_latency: Family<DbLabel, Histogram<Duration>>,
+    /// Latency of creating a checkpoint of a RocksDB instance.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub checkpoint_latency: Family<DbLabel, Histogram<Duration>>,
 }
 
 impl RocksdbMetrics {
@@ -122,6 +131,13 @@ pub(crate) struct RocksdbSizeMetrics {
     pub index_and_filters_size: Family<RocksdbLabels, Gauge<u64>>,
     /// Number of files at a certain level.
     pub files_at_level: Family<RocksdbLevelLabels, Gauge<u64>>,
+    /// Boolean gauge indicating whether at least one compaction is pending for the column family.
+    pub compaction_pending: Family<RocksdbLabels, Gauge<u64>>,
+    /// Number of sorted runs (level-0 files and non-empty levels) in the column family. This is an estimate
+    /// of read amplification; it grows if compaction doesn't keep up with writes.
+    pub sorted_runs: Family<RocksdbLabels, Gauge<u64>>,
+    /// Boolean gauge indicating whether a manual compaction is in progress for a RocksDB instance.
+    pub manual_compaction_in_progress: Family<DbLabel, Gauge<u64>>,
 }
 
 /// Weak refs to DB instances registered using [`RocksdbSizeMetrics::register()`].
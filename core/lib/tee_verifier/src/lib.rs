@@ -331,6 +331,10 @@ mod tests {
                 execution_mode: TxExecutionMode::VerifyExecute,
                 default_validation_computational_gas_limit: 0,
                 chain_id: Default::default(),
+                enable_opcode_profiling: false,
+                enable_call_tracing: false,
+                enable_gas_attribution: false,
+                enable_storage_reads_log: false,
             },
             vec![(H256([1; 32]), vec![0, 1, 2, 3, 4])],
         );
@@ -1,3 +1,8 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroU64,
+};
+
 use chrono::{DateTime, Utc};
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use strum::Display;
@@ -11,12 +16,15 @@ pub use crate::transaction_request::{
     Eip712Meta, SerializationTransactionError, TransactionRequest,
 };
 use crate::{
+    aggregated_operations::AggregatedActionType,
     protocol_version::L1VerifierConfig,
+    transaction_request::CallRequest,
     vm_trace::{Call, CallType},
-    Address, L2BlockNumber, ProtocolVersionId,
+    Address, L2BlockNumber, ProtocolVersionId, Transaction,
 };
 
 pub mod en;
+pub mod trace;
 
 /// Block Number
 #[derive(Copy, Clone, Debug, PartialEq, Display)]
@@ -562,6 +570,12 @@ pub enum TransactionStatus {
     Included,
     Verified,
     Failed,
+    /// Transaction was replaced by another transaction with the same initiator and nonce before being included
+    /// into a block.
+    Replaced,
+    /// Transaction was evicted from the mempool because it stayed there for too long or its nonce was too far
+    /// ahead of the account nonce.
+    Expired,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -584,6 +598,119 @@ pub struct GetLogsFilter {
     pub to_block: L2BlockNumber,
     pub addresses: Vec<Address>,
     pub topics: Vec<(u32, Vec<H256>)>,
+    /// If specified, only logs strictly after this position are returned.
+    pub after: Option<LogCursor>,
+}
+
+/// Position of a log in the chain, used as a pagination cursor by `zks_getLogsPaginated`.
+/// Cursors are stable: a cursor remains valid regardless of blocks sealed after it was issued.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCursor {
+    pub block_number: L2BlockNumber,
+    /// Index of the log in the block.
+    pub log_index: u32,
+}
+
+impl LogCursor {
+    /// Creates a cursor pointing to the specified log. The log must be loaded from the storage,
+    /// i.e., have its block number and index in the block set.
+    pub fn for_log(log: &Log) -> Self {
+        Self {
+            block_number: L2BlockNumber(log.block_number.unwrap_or_default().as_u32()),
+            log_index: log.log_index.unwrap_or_default().as_u32(),
+        }
+    }
+}
+
+/// Page of logs returned by `zks_getLogsPaginated`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    pub logs: Vec<Log>,
+    /// Cursor to request the next page with; `None` if there are no more logs matching the filter.
+    pub next_cursor: Option<LogCursor>,
+}
+
+/// Storage mode of a node returned by `zks_getStorageInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageMode {
+    /// The node retains all data since genesis.
+    Archive,
+    /// The node prunes historical data.
+    Pruned,
+}
+
+/// Information about historical data retained by a node returned by `zks_getStorageInfo`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageInfo {
+    pub mode: StorageMode,
+    /// Minimum age of data eligible for pruning. Only set for pruned nodes that retain data based on its age.
+    pub data_retention_sec: Option<u64>,
+    /// First L1 batch retained by the node. Requesting data for earlier L1 batches results in a "pruned" error.
+    pub first_retained_l1_batch: L1BatchNumber,
+    /// First L2 block retained by the node. Requesting data for earlier L2 blocks results in a "pruned" error.
+    pub first_retained_l2_block: L2BlockNumber,
+}
+
+/// Balance of an account after a certain L2 block. The balance stays the same until the next checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceCheckpoint {
+    pub block_number: L2BlockNumber,
+    pub balance: U256,
+}
+
+/// Base token balance history of an account returned by `zks_getBalanceHistory`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistory {
+    /// Checkpoints ordered by the L2 block number. The first checkpoint always corresponds to the start
+    /// of the requested range; subsequent checkpoints correspond to L2 blocks in which the balance was changed.
+    pub checkpoints: Vec<BalanceCheckpoint>,
+    /// First L2 block covered by the balance index. Balance changes before this block are not indexed.
+    pub first_indexed_block: L2BlockNumber,
+    /// Last L2 block covered by the balance index. Balance changes after this block are not indexed yet.
+    pub last_indexed_block: L2BlockNumber,
+}
+
+/// ERC-20 token transfer decoded from a `Transfer` event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    pub block_number: L2BlockNumber,
+    /// Index of the `Transfer` event in the L2 block.
+    pub log_index: u32,
+    pub transaction_hash: H256,
+    pub token_address: Address,
+    pub from: Address,
+    pub to: Address,
+    pub amount: U256,
+}
+
+/// Token balances of an account returned by `zks_getTokenBalances`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalances {
+    /// Non-zero balances keyed by the token address. Balances are computed from indexed `Transfer` events.
+    pub balances: HashMap<Address, U256>,
+    /// Last L2 block covered by the transfer index.
+    pub last_indexed_block: L2BlockNumber,
+    /// Set if the number of balances has reached the server-side limit on returned entities. In this case,
+    /// the next page of balances can be requested by passing this token address as `afterToken`.
+    pub next_token: Option<Address>,
 }
 
 /// Result of debugging block
@@ -640,6 +767,54 @@ impl From<Call> for DebugCall {
     }
 }
 
+/// Pre-image of an account touched during execution, returned by the `prestateTracer`.
+///
+/// Unlike geth, the contract bytecode is represented by its hash.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrestateAccount {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<H256>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+/// Accounts touched during execution together with their state.
+pub type Prestate = BTreeMap<Address, PrestateAccount>;
+
+/// State of the accounts modified during execution, returned by the `prestateTracer` in the diff mode.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PrestateDiff {
+    pub pre: Prestate,
+    pub post: Prestate,
+}
+
+/// Trace returned by the `debug` namespace methods; its shape depends on the requested tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DebugTrace {
+    Call(DebugCall),
+    PrestateDiff(PrestateDiff),
+    Prestate(Prestate),
+}
+
+impl From<DebugCall> for DebugTrace {
+    fn from(call: DebugCall) -> Self {
+        Self::Call(call)
+    }
+}
+
+/// Trace of a transaction in a block. Similar to [`ResultDebugCall`], but supports all tracers.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugTrace {
+    pub result: DebugTrace,
+}
+
 // TODO (PLA-965): remove deprecated fields from the struct. It is currently in a "migration" phase
 // to keep compatibility between old and new versions.
 #[derive(Default, Serialize, Deserialize, Clone, Debug)]
@@ -717,19 +892,82 @@ impl ProtocolVersion {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Information about a protocol version as returned by `zks_getProtocolVersions`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolVersionInfo {
+    pub minor_version: u16,
+    /// Timestamp at which the upgrade to this version was scheduled.
+    pub timestamp: u64,
+    pub bootloader_code_hash: H256,
+    pub default_account_code_hash: H256,
+    pub l2_system_upgrade_tx_hash: Option<H256>,
+    /// First L1 batch executed with this version. `None` if the version is not activated yet,
+    /// or if it was activated before the earliest L1 batch available on the node (e.g., after snapshot recovery).
+    pub activation_l1_batch: Option<L1BatchNumber>,
+    /// Timestamp of `activation_l1_batch`.
+    pub activation_timestamp: Option<u64>,
+    /// Patches of this version in the ascending order.
+    pub patches: Vec<ProtocolVersionPatchInfo>,
+}
+
+/// Patch of a protocol version. Patches only change the verifier configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolVersionPatchInfo {
+    pub patch: u32,
+    pub verifier_config: L1VerifierConfig,
+}
+
+/// Content of a protocol upgrade as returned by `zks_getProtocolUpgrade`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradeInfo {
+    pub minor_version: u16,
+    /// Timestamp at which the upgrade was scheduled to activate; may be in the future.
+    pub timestamp: u64,
+    pub bootloader_code_hash: H256,
+    pub default_account_code_hash: H256,
+    /// L2 system upgrade transaction; `None` if the upgrade doesn't have one.
+    pub upgrade_tx: Option<ProtocolUpgradeTxInfo>,
+}
+
+/// L2 system upgrade transaction included into [`ProtocolUpgradeInfo`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradeTxInfo {
+    pub hash: H256,
+    pub sender: Address,
+    pub contract_address: Address,
+    pub calldata: Bytes,
+    pub value: U256,
+    pub gas_limit: U256,
+    /// Hashes of the bytecodes published by the transaction.
+    pub factory_dep_hashes: Vec<H256>,
+    /// L1 block in which the upgrade transaction was observed.
+    pub l1_block_number: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
+    #[default]
     CallTracer,
+    PrestateTracer,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CallTracerConfig {
+    /// Only applies to `callTracer`.
+    #[serde(default)]
     pub only_top_call: bool,
+    /// Only applies to `prestateTracer`. If set, returns the state of modified accounts before and after execution.
+    #[serde(default)]
+    pub diff_mode: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
@@ -781,6 +1019,46 @@ pub struct L1BatchDetails {
     pub number: L1BatchNumber,
     #[serde(flatten)]
     pub base: BlockDetailsBase,
+    /// Information about the batch pubdata published to a data availability layer. `None` if the pubdata
+    /// wasn't dispatched to a DA layer (e.g., for rollup chains).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_availability: Option<L1BatchDaDetails>,
+}
+
+/// Reference to the L1 batch pubdata published to a data availability (DA) layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchDaDetails {
+    /// DA client used to dispatch the pubdata.
+    pub client_type: String,
+    /// Identifier of the pubdata on the DA layer (e.g., a blob hash). The format depends on the DA client.
+    pub blob_id: String,
+    /// Whether an inclusion proof for the pubdata was received from the DA layer.
+    pub inclusion_data_available: bool,
+    pub sent_at: DateTime<Utc>,
+}
+
+/// Pubdata committed for an L1 batch, as returned by `zks_getBatchPubdata`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchPubdata {
+    pub number: L1BatchNumber,
+    /// User L2-to-L1 logs, each serialized into 88 bytes in the same way as in pubdata.
+    pub l2_to_l1_logs: Vec<Bytes>,
+    pub l2_to_l1_messages: Vec<Bytes>,
+    /// Bytecodes published in the batch, in the order of their publication.
+    pub published_bytecodes: Vec<Bytes>,
+    pub state_diffs_compressed: Bytes,
+    /// All of the above packed in the same way as in the batch commitment.
+    pub pubdata: Bytes,
+}
+
+/// Raw transactions of an L2 block, as returned by `zks_getRawBlocksTransactions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L2BlockRawTransactions {
+    pub number: L2BlockNumber,
+    pub transactions: Vec<Transaction>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -799,6 +1077,63 @@ pub struct Proof {
     pub storage_proof: Vec<StorageProof>,
 }
 
+/// Conversion ratio between ETH and the base token of the chain: `numerator` ETH wei correspond to
+/// `denominator` base token units.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseTokenConversionRatio {
+    pub numerator: NonZeroU64,
+    pub denominator: NonZeroU64,
+    /// Time at which the ratio was fetched from the price source.
+    pub ratio_timestamp: DateTime<Utc>,
+}
+
+/// L1 gas prices observed by the gas adjuster for a single L1 block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1GasPriceSample {
+    pub l1_block_number: u64,
+    /// Timestamp of the L1 block in seconds since the Unix epoch.
+    pub l1_block_timestamp: u64,
+    pub base_fee_per_gas: U256,
+    /// Blob base fee; `None` for pre-Dencun blocks.
+    pub blob_base_fee: Option<U256>,
+}
+
+/// Storage keys of an account to get proofs for in `zks_getProofs`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofQuery {
+    pub address: Address,
+    pub keys: Vec<H256>,
+}
+
+/// Account proof returned by `eth_getProof`. Follows [EIP-1186] as closely as the zkSync storage layout allows:
+///
+/// - All accounts share a single Merkle tree, so `storage_hash` is the root hash of the tree
+///   after `l1_batch_number`.
+/// - Account fields are stored in slots of system contracts; `account_proof` contains proofs
+///   for the balance, nonce and code hash slots of the account (in this order).
+///
+/// [EIP-1186]: https://eips.ethereum.org/EIPS/eip-1186
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthProof {
+    pub address: Address,
+    /// L1 batch, after which the state is proven. The Merkle tree is only versioned by L1 batches,
+    /// so this is the latest L1 batch fully included in the requested block range.
+    pub l1_batch_number: L1BatchNumber,
+    /// Base token balance.
+    pub balance: U256,
+    /// Transaction nonce of the account.
+    pub nonce: U256,
+    /// Versioned bytecode hash of the account; zero if no contract is deployed at the address.
+    pub code_hash: H256,
+    pub storage_hash: H256,
+    pub account_proof: Vec<StorageProof>,
+    pub storage_proof: Vec<StorageProof>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionDetailedResult {
@@ -815,6 +1150,182 @@ pub struct ApiStorageLog {
     pub written_value: U256,
 }
 
+/// Overrides for a single account used in [`StateOverride`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverrideAccount {
+    pub balance: Option<U256>,
+    /// Transaction nonce of the account. The deployment nonce is left intact.
+    pub nonce: Option<U256>,
+    /// Bytecode of the account. Must be a valid zkEVM bytecode.
+    pub code: Option<Bytes>,
+    /// Full replacement for the account storage; slots not mentioned in the map are zeroed.
+    /// Mutually exclusive with `state_diff`.
+    pub state: Option<HashMap<H256, H256>>,
+    /// Storage slots to override; other slots retain their values.
+    pub state_diff: Option<HashMap<H256, H256>>,
+}
+
+/// State overrides applied before executing calls, keyed by the account address.
+pub type StateOverride = HashMap<Address, OverrideAccount>;
+
+/// Block-level overrides for `eth_simulateV1`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+    /// Block number. If specified, must be equal to the next block number in the simulated sequence.
+    pub number: Option<U64>,
+    /// Block timestamp. Defaults to the timestamp of the previous block + 1.
+    pub time: Option<U64>,
+    /// Base fee per gas. Since the base fee is the same for all blocks in an L1 batch,
+    /// it can only be overridden for the first simulated block.
+    pub base_fee_per_gas: Option<U256>,
+}
+
+/// Block of calls simulated by `eth_simulateV1`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+    pub block_overrides: Option<BlockOverrides>,
+    pub state_overrides: Option<StateOverride>,
+    #[serde(default)]
+    pub calls: Vec<CallRequest>,
+}
+
+/// Payload of `eth_simulateV1` requests.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    pub block_state_calls: Vec<SimulateBlock>,
+    /// Whether to trace base token transfers as logs. Not supported.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to validate transactions (nonces, balances etc.) as if they were submitted
+    /// to the mempool. Not supported; calls are always executed with `eth_call` semantics.
+    #[serde(default)]
+    pub validation: bool,
+}
+
+/// Error of a simulated call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimulatedCallError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+/// Result of a single call simulated by `eth_simulateV1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// 1 for successful calls, 0 for reverted ones.
+    pub status: U64,
+    pub return_data: Bytes,
+    pub gas_used: U256,
+    pub logs: Vec<Log>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulatedCallError>,
+}
+
+/// Block produced by `eth_simulateV1`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    pub number: U64,
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub timestamp: U64,
+    pub base_fee_per_gas: U256,
+    pub gas_used: U256,
+    pub calls: Vec<SimulatedCall>,
+}
+
+/// Result of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResult {
+    /// Storage slots accessed during the call, grouped by the contract address.
+    pub access_list: AccessList,
+    pub gas_used: U256,
+    /// Revert reason if the call was reverted. Slots accessed before the revert are still included
+    /// into the access list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Mempool transactions grouped by initiator address and nonce, as returned by `txpool_content`.
+///
+/// Pending transactions have nonces contiguous with the committed account nonce and can be included
+/// in the next blocks; queued transactions are blocked by a nonce gap.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContent {
+    pub pending: HashMap<Address, BTreeMap<u32, Transaction>>,
+    pub queued: HashMap<Address, BTreeMap<u32, Transaction>>,
+}
+
+/// Number of mempool transactions, as returned by `txpool_status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    pub pending: U64,
+    pub queued: U64,
+}
+
+/// Information about an L1 batch aborted with `admin_abortL1Batch`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AbortedL1Batch {
+    pub l1_batch_number: L1BatchNumber,
+    /// Number of transactions executed in the batch that were returned to the mempool.
+    pub transaction_count: usize,
+}
+
+/// L1 operation submitted by the eth_sender that can be paused with `admin_pauseEthSenderOperation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EthSenderOperation {
+    Commit,
+    Prove,
+    Execute,
+}
+
+impl From<EthSenderOperation> for AggregatedActionType {
+    fn from(operation: EthSenderOperation) -> Self {
+        match operation {
+            EthSenderOperation::Commit => Self::Commit,
+            EthSenderOperation::Prove => Self::PublishProofOnchain,
+            EthSenderOperation::Execute => Self::Execute,
+        }
+    }
+}
+
+impl From<AggregatedActionType> for EthSenderOperation {
+    fn from(action_type: AggregatedActionType) -> Self {
+        match action_type {
+            AggregatedActionType::Commit => Self::Commit,
+            AggregatedActionType::PublishProofOnchain => Self::Prove,
+            AggregatedActionType::Execute => Self::Execute,
+        }
+    }
+}
+
+/// L1 costs of an operation for an L1 batch returned by `admin_getL1BatchL1Costs`. Costs of an L1 transaction
+/// publishing multiple L1 batches are split evenly among them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchL1Cost {
+    pub operation: EthSenderOperation,
+    pub eth_tx_hash: Option<H256>,
+    /// Number of L1 batches published by the L1 transaction.
+    pub l1_batches_in_tx: u32,
+    pub gas_used: U64,
+    pub gas_price: U256,
+    pub blob_gas_used: U64,
+    pub blob_gas_price: U256,
+    /// Total cost in wei.
+    pub total_cost: U256,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
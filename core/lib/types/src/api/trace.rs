@@ -0,0 +1,378 @@
+//! API types for the OpenEthereum-style `trace` namespace.
+
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::{web3::Bytes, Address, L2BlockNumber, H256, U256};
+
+use super::BlockNumber;
+use crate::{
+    vm_trace::{Call, CallType},
+    zk_evm_types::FarCallOpcode,
+};
+
+/// Type of call in [`CallAction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallKind {
+    Call,
+    DelegateCall,
+}
+
+/// Action performed by a call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+    pub call_type: CallKind,
+    pub from: Address,
+    pub to: Address,
+    pub gas: U256,
+    pub input: Bytes,
+    pub value: U256,
+}
+
+/// Action performed by a contract deployment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+    pub from: Address,
+    pub gas: U256,
+    pub init: Bytes,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TraceAction {
+    Call(CallAction),
+    Create(CreateAction),
+}
+
+/// Output of a successful call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallOutput {
+    pub gas_used: U256,
+    pub output: Bytes,
+}
+
+/// Output of a successful contract deployment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOutput {
+    pub gas_used: U256,
+    pub code: Bytes,
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TraceOutput {
+    // Must go first so that deserialization doesn't confuse it with `CallOutput`.
+    Create(CreateOutput),
+    Call(CallOutput),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceType {
+    Call,
+    Create,
+}
+
+/// Location of a transaction in the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionLocation {
+    pub block_number: L2BlockNumber,
+    pub block_hash: H256,
+    pub transaction_hash: H256,
+    pub transaction_position: usize,
+}
+
+/// Single call in a transaction in the flat trace format, as returned by `trace_*` methods.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedTrace {
+    pub action: TraceAction,
+    /// Output of the call; `None` if the call has failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<TraceOutput>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of direct child calls.
+    pub subtraces: usize,
+    /// Indices of the call and its ancestors among their siblings, starting from the top-level call
+    /// (which has an empty trace address).
+    pub trace_address: Vec<usize>,
+    pub transaction_position: usize,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    pub block_hash: H256,
+    #[serde(rename = "type")]
+    pub trace_type: TraceType,
+}
+
+impl LocalizedTrace {
+    /// Flattens the call tree of a transaction into traces in the depth-first order.
+    pub fn flatten(call: &Call, location: &TransactionLocation) -> Vec<Self> {
+        let mut traces = vec![];
+        Self::flatten_recursive(call, location, &mut vec![], &mut traces);
+        traces
+    }
+
+    fn flatten_recursive(
+        call: &Call,
+        location: &TransactionLocation,
+        trace_address: &mut Vec<usize>,
+        traces: &mut Vec<Self>,
+    ) {
+        let child_calls = far_calls(&call.calls);
+        traces.push(Self::new(
+            call,
+            location,
+            trace_address.clone(),
+            child_calls.len(),
+        ));
+        for (i, &child_call) in child_calls.iter().enumerate() {
+            trace_address.push(i);
+            Self::flatten_recursive(child_call, location, trace_address, traces);
+            trace_address.pop();
+        }
+    }
+
+    fn new(
+        call: &Call,
+        location: &TransactionLocation,
+        trace_address: Vec<usize>,
+        subtraces: usize,
+    ) -> Self {
+        let (action, output, trace_type) = if matches!(call.r#type, CallType::Create) {
+            let action = TraceAction::Create(CreateAction {
+                from: call.from,
+                gas: call.gas.into(),
+                init: call.input.clone().into(),
+                value: call.value,
+            });
+            let output = TraceOutput::Create(CreateOutput {
+                gas_used: call.gas_used.into(),
+                code: call.output.clone().into(),
+                address: call.to,
+            });
+            (action, output, TraceType::Create)
+        } else {
+            let call_type = match call.r#type {
+                CallType::Call(FarCallOpcode::Delegate) => CallKind::DelegateCall,
+                _ => CallKind::Call,
+            };
+            let action = TraceAction::Call(CallAction {
+                call_type,
+                from: call.from,
+                to: call.to,
+                gas: call.gas.into(),
+                input: call.input.clone().into(),
+                value: call.value,
+            });
+            let output = TraceOutput::Call(CallOutput {
+                gas_used: call.gas_used.into(),
+                output: call.output.clone().into(),
+            });
+            (action, output, TraceType::Call)
+        };
+
+        let error = call
+            .error
+            .clone()
+            .or_else(|| call.revert_reason.as_ref().map(|_| "Reverted".to_owned()));
+        Self {
+            action,
+            result: error.is_none().then_some(output),
+            error,
+            subtraces,
+            trace_address,
+            transaction_position: location.transaction_position,
+            transaction_hash: location.transaction_hash,
+            block_number: location.block_number.0.into(),
+            block_hash: location.block_hash,
+            trace_type,
+        }
+    }
+
+    /// Returns the address of the called or deployed contract.
+    pub fn callee(&self) -> Address {
+        match (&self.action, &self.result) {
+            (TraceAction::Call(action), _) => action.to,
+            (TraceAction::Create(_), Some(TraceOutput::Create(output))) => output.address,
+            (TraceAction::Create(_), _) => Address::zero(),
+        }
+    }
+
+    /// Returns the address of the caller.
+    pub fn caller(&self) -> Address {
+        match &self.action {
+            TraceAction::Call(action) => action.from,
+            TraceAction::Create(action) => action.from,
+        }
+    }
+}
+
+/// Collects far calls among `calls`, replacing near calls with their child calls.
+fn far_calls(calls: &[Call]) -> Vec<&Call> {
+    let mut output = vec![];
+    for call in calls {
+        if matches!(call.r#type, CallType::NearCall) {
+            output.extend(far_calls(&call.calls));
+        } else {
+            output.push(call);
+        }
+    }
+    output
+}
+
+/// Filter for `trace_filter`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    /// Start of the block range (inclusive). If not specified, the latest block is used.
+    pub from_block: Option<BlockNumber>,
+    /// End of the block range (inclusive). If not specified, the latest block is used.
+    pub to_block: Option<BlockNumber>,
+    /// If specified, only calls made by one of these addresses are returned.
+    pub from_address: Option<Vec<Address>>,
+    /// If specified, only calls to one of these addresses (or deploying contracts at these addresses)
+    /// are returned.
+    pub to_address: Option<Vec<Address>>,
+    /// Number of matching traces to skip.
+    pub after: Option<usize>,
+    /// Maximum number of traces to return.
+    pub count: Option<usize>,
+}
+
+impl TraceFilter {
+    /// Checks whether the trace matches address conditions of this filter.
+    pub fn matches(&self, trace: &LocalizedTrace) -> bool {
+        let from_matches = self
+            .from_address
+            .as_ref()
+            .map_or(true, |addresses| addresses.contains(&trace.caller()));
+        let to_matches = self
+            .to_address
+            .as_ref()
+            .map_or(true, |addresses| addresses.contains(&trace.callee()));
+        from_matches && to_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_call() -> Call {
+        let reverted_call = Call {
+            r#type: CallType::Call(FarCallOpcode::Delegate),
+            from: Address::repeat_byte(2),
+            to: Address::repeat_byte(3),
+            revert_reason: Some("oops".to_owned()),
+            ..Call::default()
+        };
+        let near_call = Call {
+            r#type: CallType::NearCall,
+            calls: vec![reverted_call],
+            ..Call::default()
+        };
+        let deployment = Call {
+            r#type: CallType::Create,
+            from: Address::repeat_byte(2),
+            to: Address::repeat_byte(4),
+            gas: 100,
+            gas_used: 50,
+            output: b"code".to_vec(),
+            ..Call::default()
+        };
+        Call {
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            gas: 1_000,
+            gas_used: 500,
+            calls: vec![near_call, deployment],
+            ..Call::default()
+        }
+    }
+
+    #[test]
+    fn flattening_call_trace() {
+        let location = TransactionLocation {
+            block_number: L2BlockNumber(1),
+            block_hash: H256::repeat_byte(1),
+            transaction_hash: H256::repeat_byte(2),
+            transaction_position: 3,
+        };
+        let traces = LocalizedTrace::flatten(&test_call(), &location);
+
+        assert_eq!(traces.len(), 3);
+        assert_eq!(traces[0].subtraces, 2);
+        assert_eq!(traces[0].trace_address, [] as [usize; 0]);
+        assert_eq!(traces[0].trace_type, TraceType::Call);
+        assert_eq!(traces[0].caller(), Address::repeat_byte(1));
+        assert_eq!(traces[0].callee(), Address::repeat_byte(2));
+        assert_eq!(traces[0].block_number, 1);
+        assert_eq!(traces[0].transaction_position, 3);
+
+        assert_eq!(traces[1].trace_address, [0]);
+        assert_eq!(traces[1].error.as_deref(), Some("Reverted"));
+        assert_eq!(traces[1].result, None);
+        assert!(matches!(
+            &traces[1].action,
+            TraceAction::Call(CallAction {
+                call_type: CallKind::DelegateCall,
+                ..
+            })
+        ));
+
+        assert_eq!(traces[2].trace_address, [1]);
+        assert_eq!(traces[2].trace_type, TraceType::Create);
+        assert_eq!(traces[2].callee(), Address::repeat_byte(4));
+        assert_eq!(
+            traces[2].result,
+            Some(TraceOutput::Create(CreateOutput {
+                gas_used: 50.into(),
+                code: b"code".to_vec().into(),
+                address: Address::repeat_byte(4),
+            }))
+        );
+
+        let filter = TraceFilter {
+            from_address: Some(vec![Address::repeat_byte(2)]),
+            ..TraceFilter::default()
+        };
+        let filtered: Vec<_> = traces
+            .iter()
+            .filter(|trace| filter.matches(trace))
+            .collect();
+        assert_eq!(filtered.len(), 2);
+        let filter = TraceFilter {
+            from_address: Some(vec![Address::repeat_byte(2)]),
+            to_address: Some(vec![Address::repeat_byte(4)]),
+            ..TraceFilter::default()
+        };
+        assert!(!filter.matches(&traces[1]));
+        assert!(filter.matches(&traces[2]));
+    }
+
+    #[test]
+    fn trace_serialization() {
+        let location = TransactionLocation {
+            block_number: L2BlockNumber(1),
+            block_hash: H256::zero(),
+            transaction_hash: H256::zero(),
+            transaction_position: 0,
+        };
+        let traces = LocalizedTrace::flatten(&test_call(), &location);
+        let json = serde_json::to_value(&traces[0]).unwrap();
+        assert_eq!(json["type"], "call");
+        assert_eq!(json["action"]["callType"], "call");
+        assert_eq!(json["traceAddress"], serde_json::json!([]));
+        assert_eq!(json["blockNumber"], 1);
+
+        let restored: Vec<LocalizedTrace> =
+            serde_json::from_value(serde_json::to_value(&traces).unwrap()).unwrap();
+        assert_eq!(restored, traces);
+    }
+}
@@ -82,6 +82,9 @@ pub struct L2BlockHeader {
     /// Note, that it is an `u64`, i.e. while the computational limit for the bootloader is an `u32` a much larger
     /// amount of gas can be spent on pubdata.
     pub gas_limit: u64,
+    /// Bloom filter for the event logs emitted in the L2 block. Contains emitter addresses and indexed topics
+    /// of all events.
+    pub logs_bloom: H2048,
 }
 
 /// Structure that represents the data is returned by the storage oracle during batch execution.
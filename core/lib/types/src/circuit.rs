@@ -26,6 +26,31 @@ impl CircuitCycleStatistic {
     }
 }
 
+impl Add for CircuitCycleStatistic {
+    type Output = CircuitCycleStatistic;
+
+    fn add(self, other: CircuitCycleStatistic) -> CircuitCycleStatistic {
+        CircuitCycleStatistic {
+            main_vm_cycles: self.main_vm_cycles + other.main_vm_cycles,
+            ram_permutation_cycles: self.ram_permutation_cycles + other.ram_permutation_cycles,
+            storage_application_cycles: self.storage_application_cycles
+                + other.storage_application_cycles,
+            storage_sorter_cycles: self.storage_sorter_cycles + other.storage_sorter_cycles,
+            code_decommitter_cycles: self.code_decommitter_cycles + other.code_decommitter_cycles,
+            code_decommitter_sorter_cycles: self.code_decommitter_sorter_cycles
+                + other.code_decommitter_sorter_cycles,
+            log_demuxer_cycles: self.log_demuxer_cycles + other.log_demuxer_cycles,
+            events_sorter_cycles: self.events_sorter_cycles + other.events_sorter_cycles,
+            keccak256_cycles: self.keccak256_cycles + other.keccak256_cycles,
+            ecrecover_cycles: self.ecrecover_cycles + other.ecrecover_cycles,
+            sha256_cycles: self.sha256_cycles + other.sha256_cycles,
+            secp256k1_verify_cycles: self.secp256k1_verify_cycles + other.secp256k1_verify_cycles,
+            transient_storage_checker_cycles: self.transient_storage_checker_cycles
+                + other.transient_storage_checker_cycles,
+        }
+    }
+}
+
 /// Holds information about number of circuits used per circuit type.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct CircuitStatistic {
@@ -27,7 +27,7 @@ use crate::{
     },
     web3::keccak256,
     writes::{
-        compress_state_diffs, InitialStorageWrite, RepeatedStorageWrite, StateDiffRecord,
+        InitialStorageWrite, RepeatedStorageWrite, StateDiffCompression, StateDiffRecord,
         PADDED_ENCODED_STORAGE_DIFF_LEN_BYTES,
     },
     ProtocolVersionId, H256,
@@ -91,7 +91,12 @@ pub struct L1BatchMetadata {
     /// The commitment to the initial heap content of the bootloader. Practically it serves as a
     /// commitment to the transactions in the batch.
     pub bootloader_initial_content_commitment: Option<H256>,
+    /// Compressed state diffs; the first byte identifies the compression algorithm (see [`Self::state_diff_compression()`]).
     pub state_diffs_compressed: Vec<u8>,
+    /// Inclusion data for the batch pubdata received from a data availability (DA) layer. Not persisted together
+    /// with other metadata; it is only loaded by the Ethereum sender for batches committed with the custom pubdata DA.
+    #[serde(default)]
+    pub da_inclusion_data: Option<Vec<u8>>,
 }
 
 impl L1BatchMetadata {
@@ -101,6 +106,13 @@ impl L1BatchMetadata {
             rollup_last_leaf_index: self.rollup_last_leaf_index,
         }
     }
+
+    /// Returns the algorithm used to compress state diffs of the batch, or `None` if there are no compressed state diffs
+    /// (e.g., for pre-boojum batches) or the algorithm is unknown.
+    pub fn state_diff_compression(&self) -> Option<StateDiffCompression> {
+        let &id = self.state_diffs_compressed.first()?;
+        StateDiffCompression::from_id(id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -355,7 +367,9 @@ impl L1BatchAuxiliaryOutput {
 
                 let state_diffs_packed = serialize_commitments(&state_diffs);
                 let state_diffs_hash = H256::from(keccak256(&(state_diffs_packed)));
-                let state_diffs_compressed = compress_state_diffs(state_diffs);
+                let state_diffs_compressed =
+                    StateDiffCompression::for_protocol_version(common_input.protocol_version)
+                        .compress(state_diffs);
 
                 let blob_linear_hashes =
                     parse_system_logs_for_blob_hashes(&common_input.protocol_version, &system_logs);
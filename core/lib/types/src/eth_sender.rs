@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{aggregated_operations::AggregatedActionType, Address, Nonce, H256};
+use crate::{
+    aggregated_operations::AggregatedActionType, Address, L1BatchNumber, L1BlockNumber, Nonce,
+    H256, U256,
+};
 
 /// A forward-compatible `enum` describing a EIP4844 sidecar
 ///
@@ -77,6 +80,9 @@ pub struct TxHistory {
     pub tx_hash: H256,
     pub signed_raw_tx: Vec<u8>,
     pub sent_at_block: Option<u32>,
+    /// Parameters of the fee bump policy used to calculate fees for this sending attempt.
+    /// `None` for the first sending attempt of a transaction.
+    pub fee_bump_policy: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug)]
@@ -89,3 +95,54 @@ pub struct TxHistoryToSend {
     pub signed_raw_tx: Vec<u8>,
     pub nonce: Nonce,
 }
+
+/// Result of simulating an [`EthTx`] using `eth_call` in the dry-run mode of `eth_sender`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthTxDryRun {
+    pub eth_tx_id: u32,
+    /// Number of the L1 block the transaction was simulated on.
+    pub l1_block_number: L1BlockNumber,
+    /// Revert reason; `None` if the simulation succeeded.
+    pub revert_reason: Option<String>,
+}
+
+/// L1 costs of a mined [`EthTx`] as reported in its receipt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EthTxL1Cost {
+    pub gas_used: u64,
+    /// Effective price of gas (i.e., base fee + priority fee) in wei.
+    pub gas_price: U256,
+    /// Gas used by blobs; 0 for transactions without blobs.
+    pub blob_gas_used: u64,
+    /// Price of blob gas in wei; 0 for transactions without blobs.
+    pub blob_gas_price: U256,
+}
+
+impl EthTxL1Cost {
+    /// Returns the total cost in wei.
+    pub fn total_cost(&self) -> U256 {
+        self.gas_price * self.gas_used + self.blob_gas_price * self.blob_gas_used
+    }
+}
+
+/// Share of the L1 costs of an [`EthTx`] attributed to a single L1 batch. Costs of a transaction publishing
+/// multiple L1 batches are split evenly among them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct L1BatchL1Cost {
+    pub l1_batch_number: L1BatchNumber,
+    pub tx_type: AggregatedActionType,
+    pub eth_tx_id: u32,
+    /// Number of L1 batches published by the transaction.
+    pub l1_batches_in_tx: u32,
+    pub gas_used: u64,
+    pub gas_price: U256,
+    pub blob_gas_used: u64,
+    pub blob_gas_price: U256,
+}
+
+impl L1BatchL1Cost {
+    /// Returns the total cost in wei.
+    pub fn total_cost(&self) -> U256 {
+        self.gas_price * self.gas_used + self.blob_gas_price * self.blob_gas_used
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use zksync_basic_types::ethabi::Token;
+use zksync_basic_types::ethabi::{ethereum_types::BloomInput, Token};
 use zksync_system_constants::EVENT_WRITER_ADDRESS;
 use zksync_utils::{
     address_to_u256, h256_to_account_address, h256_to_u256, u256_to_bytes_be, u256_to_h256,
@@ -16,7 +16,7 @@ use crate::{
     tokens::{TokenInfo, TokenMetadata},
     web3::{Bytes, Index},
     zk_evm_types::{LogQuery, Timestamp},
-    Address, L1BatchNumber, CONTRACT_DEPLOYER_ADDRESS, H256, KNOWN_CODES_STORAGE_ADDRESS,
+    Address, L1BatchNumber, CONTRACT_DEPLOYER_ADDRESS, H2048, H256, KNOWN_CODES_STORAGE_ADDRESS,
     L1_MESSENGER_ADDRESS, U256, U64,
 };
 
@@ -73,6 +73,18 @@ pub static DEPLOY_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
     )
 });
 
+/// Signature of the `Transfer(address,address,uint256)` event emitted by the base token and ERC-20 contracts.
+pub static TRANSFER_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
+    ethabi::long_signature(
+        "Transfer",
+        &[
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Uint(256),
+        ],
+    )
+});
+
 static L1_MESSAGE_EVENT_SIGNATURE: Lazy<H256> = Lazy::new(|| {
     ethabi::long_signature(
         "L1MessageSent",
@@ -362,6 +374,19 @@ pub fn extract_published_bytecodes(all_generated_events: &[VmEvent]) -> Vec<H256
         .collect()
 }
 
+/// Builds a logs bloom filter for the provided events. As in Ethereum, the filter contains emitter addresses
+/// and all indexed topics of events.
+pub fn extract_logs_bloom<'a>(events: impl IntoIterator<Item = &'a VmEvent>) -> H2048 {
+    let mut bloom = H2048::zero();
+    for event in events {
+        bloom.accrue(BloomInput::Raw(event.address.as_bytes()));
+        for topic in &event.indexed_topics {
+            bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+        }
+    }
+    bloom
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct VmEventGroupKey {
     pub address: Address,
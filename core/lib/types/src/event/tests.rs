@@ -169,3 +169,22 @@ fn test_convert_vm_events_to_log_queries() {
         assert_eq!(actual_list, expected_list);
     }
 }
+
+#[test]
+fn test_extract_logs_bloom() {
+    let event = VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: Address::repeat_byte(1),
+        indexed_topics: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+        value: vec![4; 32],
+    };
+    let bloom = extract_logs_bloom([&event]);
+
+    assert!(bloom.contains_input(BloomInput::Raw(event.address.as_bytes())));
+    for topic in &event.indexed_topics {
+        assert!(bloom.contains_input(BloomInput::Raw(topic.as_bytes())));
+    }
+    // The value is not included into the filter.
+    assert!(!bloom.contains_input(BloomInput::Raw(&event.value)));
+    assert_eq!(extract_logs_bloom(&[] as &[VmEvent]), H2048::zero());
+}
@@ -65,6 +65,29 @@ pub struct Fee {
     pub gas_per_pubdata_limit: U256,
 }
 
+/// Components of the gas limit returned by fee estimation.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeeBreakdown {
+    /// Gas spent on the transaction execution, excluding pubdata.
+    pub computational_gas: U256,
+    /// Gas needed to pay for the pubdata published by the transaction.
+    pub gas_for_pubdata: U256,
+    /// Overhead charged for the transaction taking up batch resources (bootloader memory, transaction slots etc.).
+    pub overhead: U256,
+    /// Gas per pubdata byte used to compute `gas_for_pubdata`.
+    pub gas_per_pubdata: U256,
+}
+
+/// Fee estimate with an optional breakdown of the gas limit. The breakdown satisfies
+/// `fee.gas_limit == computational_gas + gas_for_pubdata + overhead`.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    #[serde(flatten)]
+    pub fee: Fee,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<FeeBreakdown>,
+}
+
 impl Fee {
     pub fn max_total_fee(&self) -> U256 {
         self.max_fee_per_gas * self.gas_limit
@@ -77,6 +100,21 @@ impl Fee {
         // For now, we charge only for base fee.
         block_base_fee_per_gas
     }
+
+    /// Checks whether a transaction with these fees can replace a pending transaction (i.e., one with the same
+    /// initiator and nonce) with `previous` fees. Both the max fee and the max priority fee must be increased
+    /// by at least `min_bump_percent` percent.
+    pub fn is_sufficient_replacement_for(&self, previous: &Self, min_bump_percent: u32) -> bool {
+        let bump_multiplier = U256::from(100 + u64::from(min_bump_percent));
+        let is_bumped = |new: U256, old: U256| {
+            new.saturating_mul(100.into()) >= old.saturating_mul(bump_multiplier)
+        };
+        is_bumped(self.max_fee_per_gas, previous.max_fee_per_gas)
+            && is_bumped(
+                self.max_priority_fee_per_gas,
+                previous.max_priority_fee_per_gas,
+            )
+    }
 }
 
 /// Returns how many slots would ABI-encoding of the transaction with such parameters take
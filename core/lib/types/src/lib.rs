@@ -47,6 +47,7 @@ pub mod l2_to_l1_log;
 pub mod priority_op_onchain_data;
 pub mod protocol_upgrade;
 pub mod pubdata_da;
+pub mod settlement;
 pub mod snapshots;
 pub mod storage;
 pub mod storage_writes_deduplicator;
@@ -1,14 +1,18 @@
+use chrono::{DateTime, Utc};
 use num_enum::TryFromPrimitive;
 use serde::{Deserialize, Serialize};
+use zksync_basic_types::{commitment::L1BatchCommitmentMode, web3::keccak256, L1BatchNumber};
 use zksync_config::configs::eth_sender::PubdataSendingMode;
 
 /// Enum holding the current values used for DA Layers.
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Hash, Serialize)]
 #[derive(TryFromPrimitive)]
 pub enum PubdataDA {
     Calldata = 0,
     Blobs,
+    /// Pubdata is published to a DA layer; L1 only receives the DA inclusion data.
+    Custom,
 }
 
 impl From<PubdataSendingMode> for PubdataDA {
@@ -16,6 +20,107 @@ impl From<PubdataSendingMode> for PubdataDA {
         match value {
             PubdataSendingMode::Calldata => PubdataDA::Calldata,
             PubdataSendingMode::Blobs => PubdataDA::Blobs,
+            PubdataSendingMode::Custom => PubdataDA::Custom,
         }
     }
 }
+
+/// Encoding of the L1 batch pubdata, i.e., what is published for each L1 batch and where.
+/// Determined by the commitment mode of the chain and the pubdata sending mode of its operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PubdataEncoding {
+    /// Full pubdata is published on L1, either in calldata or in blobs.
+    #[default]
+    Rollup,
+    /// Pubdata is not published; only its hash is retained.
+    Validium,
+    /// Full pubdata is published to a custom data availability (DA) layer by the DA dispatcher;
+    /// L1 only receives the DA inclusion data.
+    Custom,
+}
+
+impl PubdataEncoding {
+    pub fn new(
+        commitment_mode: L1BatchCommitmentMode,
+        pubdata_sending_mode: PubdataSendingMode,
+    ) -> anyhow::Result<Self> {
+        Ok(match (commitment_mode, pubdata_sending_mode) {
+            (L1BatchCommitmentMode::Rollup, PubdataSendingMode::Custom) => {
+                anyhow::bail!("Custom pubdata DA is only supported in the validium mode");
+            }
+            (L1BatchCommitmentMode::Rollup, _) => Self::Rollup,
+            (L1BatchCommitmentMode::Validium, PubdataSendingMode::Custom) => Self::Custom,
+            (L1BatchCommitmentMode::Validium, _) => Self::Validium,
+        })
+    }
+
+    /// Returns the commitment mode of chains using this encoding. Chains publishing pubdata to custom DA layers
+    /// are validiums from the L1 point of view.
+    pub fn commitment_mode(self) -> L1BatchCommitmentMode {
+        match self {
+            Self::Rollup => L1BatchCommitmentMode::Rollup,
+            Self::Validium | Self::Custom => L1BatchCommitmentMode::Validium,
+        }
+    }
+
+    /// Checks whether pubdata in this encoding is included into commit transactions.
+    pub fn is_published_on_l1(self) -> bool {
+        matches!(self, Self::Rollup)
+    }
+
+    /// Encodes the full pubdata (i.e., the pubdata in the format expected by the `L1Messenger` system contract).
+    pub fn encode(self, full_pubdata: Vec<u8>) -> Vec<u8> {
+        match self {
+            Self::Rollup | Self::Custom => full_pubdata,
+            Self::Validium => keccak256(&full_pubdata).to_vec(),
+        }
+    }
+}
+
+/// Pubdata of an L1 batch ready to be dispatched to a data availability (DA) layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct L1BatchDA {
+    pub l1_batch_number: L1BatchNumber,
+    pub pubdata: Vec<u8>,
+}
+
+/// Reference to the L1 batch pubdata dispatched to a DA layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataAvailabilityBlob {
+    pub l1_batch_number: L1BatchNumber,
+    pub blob_id: String,
+    pub sent_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pubdata_encoding_basics() {
+        let encoding =
+            PubdataEncoding::new(L1BatchCommitmentMode::Rollup, PubdataSendingMode::Blobs).unwrap();
+        assert_eq!(encoding, PubdataEncoding::Rollup);
+        assert!(encoding.is_published_on_l1());
+        assert_eq!(encoding.encode(vec![1, 2, 3]), [1, 2, 3]);
+
+        let encoding = PubdataEncoding::new(
+            L1BatchCommitmentMode::Validium,
+            PubdataSendingMode::Calldata,
+        )
+        .unwrap();
+        assert_eq!(encoding, PubdataEncoding::Validium);
+        assert_eq!(encoding.commitment_mode(), L1BatchCommitmentMode::Validium);
+        assert_eq!(encoding.encode(vec![1, 2, 3]), keccak256(&[1, 2, 3]));
+
+        let encoding =
+            PubdataEncoding::new(L1BatchCommitmentMode::Validium, PubdataSendingMode::Custom)
+                .unwrap();
+        assert_eq!(encoding, PubdataEncoding::Custom);
+        assert!(!encoding.is_published_on_l1());
+        assert_eq!(encoding.encode(vec![1, 2, 3]), [1, 2, 3]);
+
+        PubdataEncoding::new(L1BatchCommitmentMode::Rollup, PubdataSendingMode::Custom)
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,62 @@
+//! Types describing the settlement layer of the chain, i.e. the chain on which its L1 batches are committed,
+//! proven and executed.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, L1BlockNumber, L1ChainId, L2ChainId};
+
+/// Settlement layer of the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SettlementLayer {
+    /// Ethereum L1 (or its testnet).
+    L1(L1ChainId),
+    /// Gateway chain which itself settles on L1.
+    Gateway(L2ChainId),
+}
+
+impl SettlementLayer {
+    /// Returns the chain ID of the settlement layer.
+    pub fn chain_id(self) -> u64 {
+        match self {
+            Self::L1(chain_id) => chain_id.0,
+            Self::Gateway(chain_id) => chain_id.as_u64(),
+        }
+    }
+
+    pub fn is_gateway(self) -> bool {
+        matches!(self, Self::Gateway(_))
+    }
+}
+
+/// Switch of the settlement layer announced on L1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettlementLayerSwitch {
+    /// New settlement layer of the chain.
+    pub settlement_layer: SettlementLayer,
+    /// Address of the chain diamond proxy on the new settlement layer. Priority operations are emitted by it.
+    pub diamond_proxy_addr: Address,
+    /// Address of the validator timelock on the new settlement layer. Commit, prove and execute transactions
+    /// are sent to it.
+    pub validator_timelock_addr: Address,
+    /// L1 block in which the switch was announced.
+    pub l1_block_number: L1BlockNumber,
+    /// Index of the announcing event within its L1 block.
+    pub event_index: u32,
+}
+
+/// Settlement layer switch persisted in Postgres together with its application status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredSettlementLayerSwitch {
+    /// Sequential ID of the switch.
+    pub id: u32,
+    pub switch: SettlementLayerSwitch,
+    /// ID of the last `eth_txs` entry settled on the previous settlement layer. All later transactions are settled
+    /// on the new layer. `None` if the switch is not applied by eth_sender yet.
+    pub applied_after_eth_tx_id: Option<u32>,
+}
+
+impl StoredSettlementLayerSwitch {
+    pub fn is_applied(&self) -> bool {
+        self.applied_after_eth_tx_id.is_some()
+    }
+}
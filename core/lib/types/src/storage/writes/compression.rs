@@ -1,3 +1,4 @@
+use anyhow::Context as _;
 use zksync_basic_types::U256;
 
 // Starting with version 1 for this compression strategy. Any modifications to our current strategy MUST
@@ -185,6 +186,53 @@ pub fn compress_with_best_strategy(prev_value: U256, new_value: U256) -> Vec<u8>
         })
 }
 
+/// Update of a storage slot value recovered from a value compressed with [`compress_with_best_strategy()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueUpdate {
+    /// The new value is the previous value plus the specified diff (with overflow).
+    Add(U256),
+    /// The new value is the previous value minus the specified diff (with overflow).
+    Sub(U256),
+    /// The new value is specified explicitly.
+    Transform(U256),
+}
+
+impl ValueUpdate {
+    /// Computes the new value of the slot given its value before the update.
+    pub fn apply(self, prev_value: U256) -> U256 {
+        match self {
+            Self::Add(diff) => prev_value.overflowing_add(diff).0,
+            Self::Sub(diff) => prev_value.overflowing_sub(diff).0,
+            Self::Transform(value) => value,
+        }
+    }
+}
+
+/// Decompresses a value produced by [`compress_with_best_strategy()`] from the start of `bytes`.
+/// Returns the value update and the number of consumed bytes.
+pub fn decompress_value(bytes: &[u8]) -> anyhow::Result<(ValueUpdate, usize)> {
+    let (&metadata, bytes) = bytes.split_first().context("missing metadata byte")?;
+    let operation_id = metadata & 7;
+    let size = if operation_id == 0 {
+        32
+    } else {
+        usize::from(metadata >> 3)
+    };
+    anyhow::ensure!(
+        bytes.len() >= size,
+        "compressed value has {} bytes, expected {size}",
+        bytes.len()
+    );
+    let value = U256::from_big_endian(&bytes[..size]);
+    let update = match operation_id {
+        1 => ValueUpdate::Add(value),
+        2 => ValueUpdate::Sub(value),
+        0 | 3 => ValueUpdate::Transform(value),
+        _ => anyhow::bail!("unknown compression operation ID {operation_id}"),
+    };
+    Ok((update, size + 1))
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, BitAnd, Shr, Sub};
@@ -369,4 +417,27 @@ mod tests {
         assert!(compression_sub_strategy.compress_value_only().is_none());
         assert!(compression_sub_strategy.compress_extended().is_none());
     }
+
+    #[test]
+    fn decompressing_values() {
+        let values = [
+            (U256::from(255438218), U256::from(255438638)),
+            (U256::from(255438638), U256::from(255438218)),
+            (U256::MAX, U256::from(255u8)),
+            (U256::MAX, U256::zero()),
+            (U256::MAX / 2, U256::MAX),
+            (U256::zero(), U256::MAX),
+        ];
+        for (prev_value, new_value) in values {
+            let compressed = compress_with_best_strategy(prev_value, new_value);
+            let (update, consumed) = decompress_value(&compressed).unwrap();
+            assert_eq!(consumed, compressed.len());
+            assert_eq!(update.apply(prev_value), new_value);
+        }
+
+        decompress_value(&[]).unwrap_err();
+        // Add operation with a 2-byte value, but only 1 byte is present.
+        decompress_value(&[(2 << 3) | 1, 0]).unwrap_err();
+        decompress_value(&[(1 << 3) | 5, 0]).unwrap_err();
+    }
 }
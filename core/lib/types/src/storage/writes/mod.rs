@@ -1,10 +1,13 @@
 use std::{convert::TryInto, fmt};
 
+use anyhow::Context as _;
 use serde::{de, ser::SerializeTuple, Deserialize, Deserializer, Serialize, Serializer};
 use zksync_basic_types::{Address, U256};
 
+use self::compression::decompress_value;
+pub use self::compression::ValueUpdate;
 pub(crate) use self::compression::{compress_with_best_strategy, COMPRESSION_VERSION_NUMBER};
-use crate::H256;
+use crate::{ProtocolVersionId, H256};
 
 pub mod compression;
 
@@ -148,9 +151,108 @@ impl StateDiffRecord {
     }
 }
 
+/// Algorithm used to compress state diffs published as a part of pubdata. The algorithm ID is the first byte
+/// of the compressed state diffs, so that L1 contracts and pubdata consumers can tell the algorithms apart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StateDiffCompression {
+    /// Initial writes are identified by derived keys and repeated writes by enumeration indices; each value
+    /// is compressed using the best of the add / subtract / transform strategies.
+    #[default]
+    BestValueStrategy,
+}
+
+/// Algorithms used to compress state diffs, together with the first protocol version using each algorithm.
+/// Must be ordered by protocol version.
+///
+/// New algorithms require support in L1 contracts and circuits, so they must be enabled starting from a new
+/// protocol version by appending an entry here. The VM and the commitment generator get the algorithm
+/// via [`StateDiffCompression::for_protocol_version()`], so they stay consistent. Algorithms used by older
+/// protocol versions must remain supported by [`decompress_state_diffs()`].
+const ALGORITHMS_BY_PROTOCOL_VERSION: &[(ProtocolVersionId, StateDiffCompression)] = &[
+    // State diffs are published starting from the boojum upgrade.
+    (
+        ProtocolVersionId::Version18,
+        StateDiffCompression::BestValueStrategy,
+    ),
+];
+
+impl StateDiffCompression {
+    /// Returns the algorithm used for L1 batches with the specified protocol version. Pre-boojum batches
+    /// don't publish compressed state diffs; the first algorithm is returned for them.
+    pub fn for_protocol_version(protocol_version: ProtocolVersionId) -> Self {
+        ALGORITHMS_BY_PROTOCOL_VERSION
+            .iter()
+            .rev()
+            .find(|(first_version, _)| *first_version <= protocol_version)
+            .map_or(ALGORITHMS_BY_PROTOCOL_VERSION[0].1, |&(_, algorithm)| {
+                algorithm
+            })
+    }
+
+    /// Returns the ID of the algorithm stored in the header of compressed state diffs.
+    pub fn id(self) -> u8 {
+        match self {
+            Self::BestValueStrategy => COMPRESSION_VERSION_NUMBER,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            COMPRESSION_VERSION_NUMBER => Some(Self::BestValueStrategy),
+            _ => None,
+        }
+    }
+
+    /// Compresses state diffs using this algorithm. The output includes the header with the algorithm ID.
+    pub fn compress(self, state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
+        match self {
+            Self::BestValueStrategy => compress_with_best_value_strategy(state_diffs),
+        }
+    }
+
+    /// Decompresses state diffs following the algorithm ID in the header.
+    fn decompress(self, compressed: &[u8]) -> anyhow::Result<Vec<CompressedStateDiff>> {
+        match self {
+            Self::BestValueStrategy => decompress_with_best_value_strategy(compressed),
+        }
+    }
+}
+
+/// Identifier of the storage slot in compressed state diffs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateDiffKey {
+    /// Derived key of the slot; used for initial writes.
+    DerivedKey([u8; 32]),
+    /// Enumeration index of the slot in the Merkle tree; used for repeated writes.
+    EnumerationIndex(u64),
+}
+
+/// State diff recovered from compressed state diffs published in pubdata. Depending on the compression strategy,
+/// the new slot value may be expressed relative to the previous value, so reconstructing the state requires
+/// applying diffs on top of the state before the L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedStateDiff {
+    pub key: StateDiffKey,
+    pub value: ValueUpdate,
+}
+
+/// Decompresses state diffs produced by [`StateDiffCompression::compress()`], using the algorithm specified
+/// in the header. Initial writes are returned before repeated writes, in the order they were published.
+pub fn decompress_state_diffs(
+    compressed: &[u8],
+) -> anyhow::Result<(StateDiffCompression, Vec<CompressedStateDiff>)> {
+    let &id = compressed
+        .first()
+        .context("compressed state diffs are empty")?;
+    let algorithm = StateDiffCompression::from_id(id)
+        .with_context(|| format!("unknown state diff compression algorithm ID {id}"))?;
+    let state_diffs = algorithm.decompress(compressed)?;
+    Ok((algorithm, state_diffs))
+}
+
 /// Compresses a vector of state diff records according to the following:
-/// num_initial writes (u32) || compressed initial writes || compressed repeated writes
-pub fn compress_state_diffs(mut state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
+/// num_initial writes (u16) || compressed initial writes || compressed repeated writes
+fn compress_with_best_value_strategy(mut state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
     let mut res = vec![];
 
     // IMPORTANT: Sorting here is determined by the order expected in the circuits.
@@ -169,15 +271,74 @@ pub fn compress_state_diffs(mut state_diffs: Vec<StateDiffRecord>) -> Vec<u8> {
         res.extend(state_diff.compress());
     }
 
-    prepend_header(res)
+    prepend_header(StateDiffCompression::BestValueStrategy, res)
+}
+
+fn decompress_with_best_value_strategy(
+    compressed: &[u8],
+) -> anyhow::Result<Vec<CompressedStateDiff>> {
+    anyhow::ensure!(
+        compressed.len() >= 5,
+        "header of compressed state diffs is truncated"
+    );
+    let (header, mut body) = compressed.split_at(5);
+    let mut len_bytes = [0_u8; 4];
+    len_bytes[1..].copy_from_slice(&header[1..4]);
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    anyhow::ensure!(
+        body.len() == len,
+        "compressed state diffs have {} bytes, header specifies {len}",
+        body.len()
+    );
+    anyhow::ensure!(
+        header[4] == BYTES_PER_ENUMERATION_INDEX,
+        "unsupported enumeration index size: {}",
+        header[4]
+    );
+
+    anyhow::ensure!(body.len() >= 2, "number of initial writes is missing");
+    let initial_writes_count = u16::from_be_bytes([body[0], body[1]]);
+    body = &body[2..];
+
+    let mut state_diffs = vec![];
+    while !body.is_empty() {
+        let is_initial = state_diffs.len() < usize::from(initial_writes_count);
+        let key_size = if is_initial {
+            BYTES_PER_DERIVED_KEY
+        } else {
+            BYTES_PER_ENUMERATION_INDEX
+        };
+        let key_size = usize::from(key_size);
+        anyhow::ensure!(
+            body.len() >= key_size,
+            "state diff #{} is truncated",
+            state_diffs.len()
+        );
+        let (key, rest) = body.split_at(key_size);
+        let key = if is_initial {
+            StateDiffKey::DerivedKey(key.try_into().unwrap())
+        } else {
+            StateDiffKey::EnumerationIndex(u32::from_be_bytes(key.try_into().unwrap()).into())
+        };
+        let (value, consumed) = decompress_value(rest)
+            .with_context(|| format!("malformed value in state diff #{}", state_diffs.len()))?;
+        body = &rest[consumed..];
+        state_diffs.push(CompressedStateDiff { key, value });
+    }
+    anyhow::ensure!(
+        state_diffs.len() >= usize::from(initial_writes_count),
+        "expected {initial_writes_count} initial writes, got {}",
+        state_diffs.len()
+    );
+    Ok(state_diffs)
 }
 
 /// Adds the header to the beginning of the compressed state diffs so it can be used as part of the overall
 /// pubdata. Need to prepend: compression version || number of compressed state diffs || number of bytes used for
 /// enumeration index.
-fn prepend_header(compressed_state_diffs: Vec<u8>) -> Vec<u8> {
+fn prepend_header(algorithm: StateDiffCompression, compressed_state_diffs: Vec<u8>) -> Vec<u8> {
     let mut res = vec![0u8; 5];
-    res[0] = COMPRESSION_VERSION_NUMBER;
+    res[0] = algorithm.id();
 
     res[1..4].copy_from_slice(&(compressed_state_diffs.len() as u32).to_be_bytes()[1..4]);
 
@@ -397,7 +558,8 @@ mod tests {
             repeated_none,
         ];
 
-        let compressed_state_diffs = compress_state_diffs(storage_diffs.clone());
+        let compressed_state_diffs =
+            StateDiffCompression::BestValueStrategy.compress(storage_diffs.clone());
 
         let mut storage_diffs = storage_diffs.clone();
         storage_diffs.sort_by_key(|rec| (rec.address, rec.key));
@@ -531,6 +693,95 @@ mod tests {
         assert!(compressed_state_diffs.is_empty());
     }
 
+    #[test]
+    fn decompressing_state_diffs() {
+        let initial_write = StateDiffRecord {
+            address: Address::repeat_byte(1),
+            key: U256::from(1u8),
+            derived_key: [1u8; 32],
+            enumeration_index: 0,
+            initial_value: U256::zero(),
+            final_value: U256::from(64u8),
+        };
+        let repeated_write = StateDiffRecord {
+            address: Address::repeat_byte(2),
+            key: U256::from(2u8),
+            derived_key: [2u8; 32],
+            enumeration_index: 5,
+            initial_value: U256::MAX / 2,
+            final_value: U256::MAX,
+        };
+        let repeated_sub_write = StateDiffRecord {
+            address: Address::zero(),
+            key: U256::from(3u8),
+            derived_key: [3u8; 32],
+            enumeration_index: 7,
+            initial_value: U256::from(64u8),
+            final_value: U256::from(20u8),
+        };
+        let state_diffs = vec![
+            repeated_sub_write.clone(),
+            initial_write.clone(),
+            repeated_write.clone(),
+        ];
+
+        let compression = StateDiffCompression::for_protocol_version(ProtocolVersionId::latest());
+        let compressed = compression.compress(state_diffs);
+        assert_eq!(compressed[0], compression.id());
+        let (decompressed_algorithm, decompressed) = decompress_state_diffs(&compressed).unwrap();
+        assert_eq!(decompressed_algorithm, compression);
+
+        // Repeated writes are sorted by address.
+        let expected_diffs = [initial_write, repeated_sub_write, repeated_write];
+        assert_eq!(decompressed.len(), expected_diffs.len());
+        for (diff, expected) in decompressed.iter().zip(&expected_diffs) {
+            let expected_key = if expected.is_write_initial() {
+                StateDiffKey::DerivedKey(expected.derived_key)
+            } else {
+                StateDiffKey::EnumerationIndex(expected.enumeration_index)
+            };
+            assert_eq!(diff.key, expected_key);
+            assert_eq!(
+                diff.value.apply(expected.initial_value),
+                expected.final_value
+            );
+        }
+
+        let mut unknown_algorithm = compressed.clone();
+        unknown_algorithm[0] = 0xff;
+        let err = decompress_state_diffs(&unknown_algorithm).unwrap_err();
+        assert!(err.to_string().contains("unknown"), "{err}");
+        decompress_state_diffs(&compressed[..compressed.len() - 1]).unwrap_err();
+        decompress_state_diffs(&[]).unwrap_err();
+    }
+
+    #[test]
+    fn selecting_compression_algorithm() {
+        assert!(ALGORITHMS_BY_PROTOCOL_VERSION
+            .windows(2)
+            .all(|window| window[0].0 < window[1].0));
+        for (first_version, algorithm) in ALGORITHMS_BY_PROTOCOL_VERSION {
+            assert_eq!(
+                StateDiffCompression::for_protocol_version(*first_version),
+                *algorithm
+            );
+            assert_eq!(
+                StateDiffCompression::from_id(algorithm.id()),
+                Some(*algorithm)
+            );
+        }
+
+        let (_, latest_algorithm) = ALGORITHMS_BY_PROTOCOL_VERSION.last().unwrap();
+        assert_eq!(
+            StateDiffCompression::for_protocol_version(ProtocolVersionId::next()),
+            *latest_algorithm
+        );
+        assert_eq!(
+            StateDiffCompression::for_protocol_version(ProtocolVersionId::Version17),
+            StateDiffCompression::BestValueStrategy
+        );
+    }
+
     #[test]
     fn test_encoding() {
         let state_diff = StateDiffRecord {
@@ -22,9 +22,21 @@ fn biguint_to_u256(value: BigUint) -> U256 {
 }
 
 /// Converts `BigDecimal` value into the corresponding `U256` value.
+///
+/// # Panics
+///
+/// Panics if the value is negative or doesn't fit into `U256`. Use [`try_bigdecimal_to_u256()`]
+/// for values not guaranteed to be in range.
 pub fn bigdecimal_to_u256(value: BigDecimal) -> U256 {
+    try_bigdecimal_to_u256(value).expect("value is out of the `U256` range")
+}
+
+/// Converts `BigDecimal` value into the corresponding `U256` value. The fractional part is truncated.
+/// Returns `None` if the value is negative or doesn't fit into `U256`.
+pub fn try_bigdecimal_to_u256(value: BigDecimal) -> Option<U256> {
     let bigint = value.with_scale(0).into_bigint_and_exponent().0;
-    biguint_to_u256(bigint.to_biguint().unwrap())
+    let biguint = bigint.to_biguint()?;
+    (biguint.bits() <= 256).then(|| biguint_to_u256(biguint))
 }
 
 fn ensure_chunkable(bytes: &[u8]) {
@@ -182,4 +194,20 @@ mod test {
         let expected = U256::from(10000u32);
         assert_eq!(bigdecimal_to_u256(value), expected);
     }
+
+    #[test]
+    fn test_try_bigdecimal_to_u256() {
+        let max_value = u256_to_big_decimal(U256::MAX);
+        assert_eq!(try_bigdecimal_to_u256(max_value.clone()), Some(U256::MAX));
+        assert_eq!(try_bigdecimal_to_u256(max_value + 1), None);
+        assert_eq!(try_bigdecimal_to_u256(BigDecimal::from(-1)), None);
+        assert_eq!(
+            try_bigdecimal_to_u256(BigDecimal::new(BigInt::from(1), -77)),
+            Some(U256::exp10(77))
+        );
+        assert_eq!(
+            try_bigdecimal_to_u256(BigDecimal::new(BigInt::from(1), -78)),
+            None
+        );
+    }
 }
@@ -63,6 +63,10 @@ pub fn l1_batch_params(
             execution_mode: TxExecutionMode::VerifyExecute,
             default_validation_computational_gas_limit: validation_computational_gas_limit,
             chain_id,
+            enable_opcode_profiling: false,
+            enable_call_tracing: false,
+            enable_gas_attribution: false,
+            enable_storage_reads_log: false,
         },
         L1BatchEnv {
             previous_batch_hash: Some(previous_batch_hash),
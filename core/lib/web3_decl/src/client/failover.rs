@@ -0,0 +1,504 @@
+//! Client failing over between multiple RPC providers.
+
+use std::{
+    fmt, mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::future;
+use jsonrpsee::{
+    core::{
+        client::{BatchResponse, ClientT, Error},
+        params::BatchRequestBuilder,
+        traits::ToRpcParams,
+    },
+    rpc_params,
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::watch;
+use zksync_types::U64;
+
+use super::{
+    boxed::RawParams,
+    metrics::{ProviderLabels, FAILOVER_METRICS},
+    DynClient, ForNetwork, Network, TaggedClient,
+};
+
+/// Checks whether an error is caused by the provider rather than by the request, i.e., whether the request
+/// may succeed if sent to another provider.
+fn is_provider_error(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::Transport(_) | Error::RequestTimeout | Error::RestartNeeded(_)
+    )
+}
+
+#[derive(Debug)]
+struct FailoverState {
+    active_provider: usize,
+    /// Unhealthy providers are only used if all healthy providers fail. Health is updated by [`FailoverProbe`];
+    /// additionally, a provider is marked as unhealthy if a request to it fails because of a provider error.
+    healthy: Vec<bool>,
+}
+
+impl FailoverState {
+    /// Returns provider indices in the order they should be tried: the active provider, other healthy providers,
+    /// and then unhealthy providers.
+    fn provider_order(&self) -> Vec<usize> {
+        let provider_count = self.healthy.len();
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) = (0..provider_count)
+            .map(|i| (self.active_provider + i) % provider_count)
+            .partition(|&i| self.healthy[i]);
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+}
+
+/// Client distributing requests among multiple RPC providers for the same network. Requests are sent to a single
+/// active provider; if a request fails because of a provider error (e.g., a transport error or a timeout),
+/// the provider is marked as unhealthy, and the request is retried with the next provider. RPC-level errors
+/// are returned as is, since they are (usually) caused by the request itself.
+///
+/// Providers are ordered by priority, with the first provider being the main one. [`FailoverProbe`] should be run
+/// in the background to check provider health and return to the higher-priority providers once they recover.
+///
+/// Clones of the client share the failover state.
+#[derive(Debug, Clone)]
+pub struct FailoverClient<Net: Network> {
+    providers: Vec<Box<DynClient<Net>>>,
+    state: Arc<Mutex<FailoverState>>,
+    network: Net,
+}
+
+impl<Net: Network> FailoverClient<Net> {
+    /// Creates a client for the specified providers ordered by priority. All providers are initially considered healthy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `providers` is empty.
+    pub fn new(providers: Vec<Box<DynClient<Net>>>) -> Self {
+        assert!(!providers.is_empty(), "no RPC providers specified");
+        let network = providers[0].network();
+        let state = FailoverState {
+            active_provider: 0,
+            healthy: vec![true; providers.len()],
+        };
+        FAILOVER_METRICS.active_provider[&network.metric_label()].set(0);
+        Self {
+            providers,
+            state: Arc::new(Mutex::new(state)),
+            network,
+        }
+    }
+
+    /// Returns the index of the currently active provider.
+    pub fn active_provider(&self) -> usize {
+        self.lock_state().active_provider
+    }
+
+    /// Creates a health probe for providers used by this client.
+    pub fn probe(&self) -> FailoverProbe<Net> {
+        FailoverProbe {
+            client: self.clone(),
+            expected_chain_id: None,
+            max_head_lag: FailoverProbe::<Net>::DEFAULT_MAX_HEAD_LAG,
+            interval: FailoverProbe::<Net>::DEFAULT_INTERVAL,
+        }
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, FailoverState> {
+        self.state.lock().expect("failover state is poisoned")
+    }
+
+    fn provider_order(&self) -> Vec<usize> {
+        self.lock_state().provider_order()
+    }
+
+    fn report_success(&self, provider: usize) {
+        let mut state = self.lock_state();
+        if state.active_provider != provider {
+            let network_label = self.network.metric_label();
+            tracing::warn!(
+                network = network_label,
+                "Failed over from RPC provider #{} to #{provider}",
+                state.active_provider
+            );
+            state.active_provider = provider;
+            FAILOVER_METRICS.failovers[&network_label].inc();
+            FAILOVER_METRICS.active_provider[&network_label].set(provider);
+        }
+    }
+
+    fn report_failure(&self, provider: usize, method: &str, err: &Error) {
+        let mut state = self.lock_state();
+        if mem::replace(&mut state.healthy[provider], false) {
+            let network_label = self.network.metric_label();
+            tracing::warn!(
+                network = network_label,
+                component = self.component(),
+                method,
+                "Request `{method}` to RPC provider #{provider} failed, marking provider as unhealthy: {err}"
+            );
+            let labels = ProviderLabels {
+                network: network_label,
+                provider,
+            };
+            FAILOVER_METRICS.provider_health[&labels].set(0);
+        }
+    }
+}
+
+impl<Net: Network> ForNetwork for FailoverClient<Net> {
+    type Net = Net;
+
+    fn network(&self) -> Self::Net {
+        self.network
+    }
+
+    fn component(&self) -> &'static str {
+        self.providers[0].component()
+    }
+}
+
+impl<Net: Network> TaggedClient for FailoverClient<Net> {
+    fn set_component(&mut self, component_name: &'static str) {
+        self.providers = mem::take(&mut self.providers)
+            .into_iter()
+            .map(|provider| provider.for_component(component_name))
+            .collect();
+    }
+}
+
+#[async_trait]
+impl<Net: Network> ClientT for FailoverClient<Net> {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), Error>
+    where
+        Params: ToRpcParams + Send,
+    {
+        let params = params.to_rpc_params()?;
+        let mut last_err = None;
+        for provider in self.provider_order() {
+            let params = RawParams(params.clone());
+            match ClientT::notification(&self.providers[provider], method, params).await {
+                Err(err) if is_provider_error(&err) => {
+                    self.report_failure(provider, method, &err);
+                    last_err = Some(err);
+                }
+                result => {
+                    self.report_success(provider);
+                    return result;
+                }
+            }
+        }
+        Err(last_err.expect("no RPC providers"))
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, Error>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        let params = params.to_rpc_params()?;
+        let mut last_err = None;
+        for provider in self.provider_order() {
+            let params = RawParams(params.clone());
+            match ClientT::request(&self.providers[provider], method, params).await {
+                Err(err) if is_provider_error(&err) => {
+                    self.report_failure(provider, method, &err);
+                    last_err = Some(err);
+                }
+                result => {
+                    self.report_success(provider);
+                    return result;
+                }
+            }
+        }
+        Err(last_err.expect("no RPC providers"))
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, Error>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        let mut last_err = None;
+        for provider in self.provider_order() {
+            match ClientT::batch_request(&self.providers[provider], batch.clone()).await {
+                Err(err) if is_provider_error(&err) => {
+                    self.report_failure(provider, "batch", &err);
+                    last_err = Some(err);
+                }
+                result => {
+                    self.report_success(provider);
+                    return result;
+                }
+            }
+        }
+        Err(last_err.expect("no RPC providers"))
+    }
+}
+
+/// Result of probing a single provider.
+#[derive(Debug)]
+struct ProviderStatus {
+    chain_id: u64,
+    head: u64,
+}
+
+/// Periodically checks health of providers used by a [`FailoverClient`]. A provider is considered healthy if it:
+///
+/// - responds to `eth_chainId` and `eth_blockNumber` requests
+/// - reports the expected chain ID (if not configured, the chain ID reported by the highest-priority responding provider)
+/// - reports a head block lagging behind the most recent head among providers by at most the configured number of blocks.
+///
+/// After each check, the highest-priority healthy provider becomes active.
+#[derive(Debug)]
+pub struct FailoverProbe<Net: Network> {
+    client: FailoverClient<Net>,
+    expected_chain_id: Option<u64>,
+    max_head_lag: u64,
+    interval: Duration,
+}
+
+impl<Net: Network> FailoverProbe<Net> {
+    const DEFAULT_MAX_HEAD_LAG: u64 = 5;
+    const DEFAULT_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Sets the chain ID that all providers must report.
+    pub fn with_expected_chain_id(mut self, chain_id: u64) -> Self {
+        self.expected_chain_id = Some(chain_id);
+        self
+    }
+
+    /// Sets the maximum allowed lag of the provider head behind the most recent head among providers, in blocks.
+    pub fn with_max_head_lag(mut self, max_head_lag: u64) -> Self {
+        self.max_head_lag = max_head_lag;
+        self
+    }
+
+    /// Sets the interval between checks.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    async fn probe_provider(provider: &DynClient<Net>) -> Result<ProviderStatus, Error> {
+        let chain_id: U64 = ClientT::request(&provider, "eth_chainId", rpc_params![]).await?;
+        let head: U64 = ClientT::request(&provider, "eth_blockNumber", rpc_params![]).await?;
+        Ok(ProviderStatus {
+            chain_id: chain_id.as_u64(),
+            head: head.as_u64(),
+        })
+    }
+
+    /// Checks all providers and updates the failover state accordingly.
+    pub async fn check_providers(&self) {
+        let network_label = self.client.network.metric_label();
+        let probes = self
+            .client
+            .providers
+            .iter()
+            .map(|provider| Self::probe_provider(provider.as_ref()));
+        let statuses = future::join_all(probes).await;
+
+        let expected_chain_id = self.expected_chain_id.or_else(|| {
+            statuses
+                .iter()
+                .find_map(|status| Some(status.as_ref().ok()?.chain_id))
+        });
+        let max_head = statuses
+            .iter()
+            .filter_map(|status| status.as_ref().ok())
+            .filter(|status| Some(status.chain_id) == expected_chain_id)
+            .map(|status| status.head)
+            .max();
+
+        let healthy: Vec<_> = statuses
+            .iter()
+            .enumerate()
+            .map(|(provider, status)| {
+                let labels = ProviderLabels {
+                    network: network_label.clone(),
+                    provider,
+                };
+                let status = match status {
+                    Ok(status) => status,
+                    Err(err) => {
+                        tracing::info!(
+                            network = network_label,
+                            "RPC provider #{provider} is unhealthy: {err}"
+                        );
+                        return false;
+                    }
+                };
+                FAILOVER_METRICS.provider_head[&labels].set(status.head);
+
+                if Some(status.chain_id) != expected_chain_id {
+                    tracing::warn!(
+                        network = network_label,
+                        "RPC provider #{provider} is unhealthy: it reports chain ID {}, while {expected_chain_id:?} is expected",
+                        status.chain_id
+                    );
+                    return false;
+                }
+                let head_lag = max_head.unwrap_or(status.head).saturating_sub(status.head);
+                if head_lag > self.max_head_lag {
+                    tracing::info!(
+                        network = network_label,
+                        "RPC provider #{provider} is unhealthy: its head block #{} lags {head_lag} blocks \
+                         behind the most recent head",
+                        status.head
+                    );
+                    return false;
+                }
+                true
+            })
+            .collect();
+
+        for (provider, &is_healthy) in healthy.iter().enumerate() {
+            let labels = ProviderLabels {
+                network: network_label.clone(),
+                provider,
+            };
+            FAILOVER_METRICS.provider_health[&labels].set(is_healthy.into());
+        }
+
+        let mut state = self.client.lock_state();
+        if let Some(best_provider) = healthy.iter().position(|&is_healthy| is_healthy) {
+            if state.active_provider != best_provider {
+                tracing::info!(
+                    network = network_label,
+                    "Switching active RPC provider from #{} to #{best_provider}",
+                    state.active_provider
+                );
+                state.active_provider = best_provider;
+                FAILOVER_METRICS.failovers[&network_label].inc();
+                FAILOVER_METRICS.active_provider[&network_label].set(best_provider);
+            }
+        } else {
+            tracing::warn!(
+                network = network_label,
+                "All RPC providers are unhealthy; will keep sending requests to all of them"
+            );
+        }
+        state.healthy = healthy;
+    }
+
+    /// Runs the probe until a stop signal is received.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow() {
+            self.check_providers().await;
+            // Errors mean that the stop signal sender is dropped, which we treat as a stop signal as well.
+            if tokio::time::timeout(self.interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, RPC failover probe is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    use assert_matches::assert_matches;
+    use zksync_types::L1ChainId;
+
+    use super::*;
+    use crate::client::{MockClient, L1};
+
+    fn mock_provider(
+        chain_id: u64,
+        head: Arc<AtomicU64>,
+        is_up: Arc<AtomicBool>,
+    ) -> Box<DynClient<L1>> {
+        let client = MockClient::builder(L1ChainId(9).into())
+            .method("eth_chainId", move || Ok(U64::from(chain_id)))
+            .method("eth_blockNumber", move || {
+                if is_up.load(Ordering::Relaxed) {
+                    Ok(U64::from(head.load(Ordering::Relaxed)))
+                } else {
+                    Err(Error::Transport(anyhow::anyhow!("provider is down")))
+                }
+            })
+            .build();
+        Box::new(client)
+    }
+
+    #[tokio::test]
+    async fn failing_over_on_provider_errors() {
+        let main_is_up = Arc::new(AtomicBool::new(false));
+        let providers = vec![
+            mock_provider(9, Arc::new(AtomicU64::new(100)), main_is_up.clone()),
+            mock_provider(
+                9,
+                Arc::new(AtomicU64::new(101)),
+                Arc::new(AtomicBool::new(true)),
+            ),
+        ];
+        let client = FailoverClient::new(providers);
+
+        let head: U64 = client
+            .request("eth_blockNumber", rpc_params![])
+            .await
+            .unwrap();
+        assert_eq!(head, 101.into());
+        assert_eq!(client.active_provider(), 1);
+        assert_eq!(client.provider_order(), [1, 0]);
+
+        // RPC errors must not lead to failover.
+        let err = client
+            .request::<U64, _>("unknown", rpc_params![])
+            .await
+            .unwrap_err();
+        assert_matches!(err, Error::Call(_));
+        assert_eq!(client.active_provider(), 1);
+
+        // Returning to the main provider once it's healthy again.
+        main_is_up.store(true, Ordering::Relaxed);
+        client.probe().check_providers().await;
+        assert_eq!(client.active_provider(), 0);
+        let head: U64 = client
+            .request("eth_blockNumber", rpc_params![])
+            .await
+            .unwrap();
+        assert_eq!(head, 100.into());
+    }
+
+    #[tokio::test]
+    async fn probing_provider_consistency() {
+        let main_head = Arc::new(AtomicU64::new(90));
+        let providers = vec![
+            mock_provider(9, main_head.clone(), Arc::new(AtomicBool::new(true))),
+            mock_provider(
+                1,
+                Arc::new(AtomicU64::new(200)),
+                Arc::new(AtomicBool::new(true)),
+            ),
+            mock_provider(
+                9,
+                Arc::new(AtomicU64::new(100)),
+                Arc::new(AtomicBool::new(true)),
+            ),
+        ];
+        let client = FailoverClient::new(providers);
+        let probe = client.probe().with_expected_chain_id(9);
+
+        // The main provider lags behind, and the 2nd provider is for another chain.
+        probe.check_providers().await;
+        assert_eq!(client.active_provider(), 2);
+        assert_eq!(client.provider_order(), [2, 0, 1]);
+
+        main_head.store(98, Ordering::Relaxed);
+        probe.check_providers().await;
+        assert_eq!(client.active_provider(), 0);
+        assert_eq!(client.provider_order(), [0, 2, 1]);
+    }
+}
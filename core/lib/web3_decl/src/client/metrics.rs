@@ -4,8 +4,8 @@ use std::time::Duration;
 
 use jsonrpsee::{core::client, http_client::transport};
 use vise::{
-    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Info,
-    LabeledFamily, Metrics, Unit,
+    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram,
+    Info, LabeledFamily, Metrics, Unit,
 };
 
 use super::{AcquireStats, CallOrigin, SharedRateLimit};
@@ -33,6 +33,12 @@ pub(super) struct HttpErrorLabels {
     pub status: Option<u16>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct ProviderLabels {
+    pub network: String,
+    pub provider: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
 #[metrics(rename_all = "snake_case")]
 pub(super) enum CallErrorKind {
@@ -211,3 +217,21 @@ impl L2ClientMetrics {
 
 #[vise::register]
 pub(super) static METRICS: vise::Global<L2ClientMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "rpc_failover")]
+pub(super) struct FailoverMetrics {
+    /// Index of the provider currently used for requests.
+    #[metrics(labels = ["network"])]
+    pub active_provider: LabeledFamily<String, Gauge<usize>>,
+    /// Number of times the active provider was switched.
+    #[metrics(labels = ["network"])]
+    pub failovers: LabeledFamily<String, Counter>,
+    /// Whether a provider is considered healthy (1) or not (0).
+    pub provider_health: Family<ProviderLabels, Gauge<u64>>,
+    /// Latest block number reported by a provider.
+    pub provider_head: Family<ProviderLabels, Gauge<u64>>,
+}
+
+#[vise::register]
+pub(super) static FAILOVER_METRICS: vise::Global<FailoverMetrics> = vise::Global::new();
@@ -33,12 +33,27 @@ pub enum Web3Error {
     SerializationError(#[from] SerializationTransactionError),
     #[error("More than four topics in filter")]
     TooManyTopics,
+    #[error("Too many storage keys requested; the limit is {0}")]
+    TooManyProofKeys(usize),
     #[error("Filter not found")]
     FilterNotFound,
     #[error("Query returned more than {0} results. Try with this block range [{1:#x}, {2:#x}].")]
     LogsLimitExceeded(usize, u32, u32),
+    #[error(
+        "Query block range is limited to {0} blocks. Try with this block range [{1:#x}, {2:#x}]."
+    )]
+    BlockRangeLimitExceeded(u32, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
+    #[error("invalid reward percentiles: must be monotonically increasing and lie in [0, 100]")]
+    InvalidRewardPercentiles,
+    #[error("invalid simulation request: {0}")]
+    InvalidSimulation(String),
+    #[error("L1 batch #{0} has sealed L2 blocks and cannot be aborted")]
+    L1BatchNotAbortable(L1BatchNumber),
+    /// Response exceeds the size limit for the method (measured in bytes).
+    #[error("Response is too big")]
+    OversizedResponse(usize),
     /// Weaker form of a "method not found" error; the method implementation is technically present,
     /// but the node configuration prevents the method from functioning.
     #[error("Method not implemented")]
@@ -46,6 +61,9 @@ pub enum Web3Error {
     /// Unavailability caused by node configuration is returned as [`Self::MethodNotImplemented`].
     #[error("Tree API is temporarily unavailable")]
     TreeApiUnavailable,
+    /// Read requests are temporarily rejected to free up database connections for transaction submission.
+    #[error("Server is overloaded; try again later")]
+    ServerOverloaded,
     #[error("Internal error")]
     InternalError(#[from] anyhow::Error),
 }
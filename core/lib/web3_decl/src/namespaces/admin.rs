@@ -0,0 +1,54 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::{
+    api::{AbortedL1Batch, EthSenderOperation, L1BatchL1Cost},
+    Address, L1BatchNumber,
+};
+
+use crate::client::{ForNetwork, L2};
+
+/// Operator-only methods of the main node.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "admin", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "admin", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+pub trait AdminNamespace {
+    /// Aborts the L1 batch currently open in the state keeper, returning its transactions to the mempool.
+    /// Fails if the batch already has sealed L2 blocks.
+    #[method(name = "abortL1Batch")]
+    async fn abort_l1_batch(&self) -> RpcResult<AbortedL1Batch>;
+
+    /// Returns the operator fee account used by the state keeper for new L1 batches.
+    #[method(name = "getFeeAccount")]
+    async fn get_fee_account(&self) -> RpcResult<Address>;
+
+    /// Switches the operator fee account for new L1 batches, returning the previously used account.
+    /// The currently open L1 batch (if any) is not affected.
+    #[method(name = "setFeeAccount")]
+    async fn set_fee_account(&self, address: Address) -> RpcResult<Address>;
+
+    /// Returns eth_sender operations for which creating new L1 transactions is currently paused.
+    #[method(name = "getPausedEthSenderOperations")]
+    async fn get_paused_eth_sender_operations(&self) -> RpcResult<Vec<EthSenderOperation>>;
+
+    /// Pauses creating new L1 transactions for the specified operation. Transactions that are already created
+    /// are still sent. Returns whether the operation was already paused.
+    #[method(name = "pauseEthSenderOperation")]
+    async fn pause_eth_sender_operation(&self, operation: EthSenderOperation) -> RpcResult<bool>;
+
+    /// Resumes creating new L1 transactions for the specified operation. Returns whether the operation was paused.
+    #[method(name = "resumeEthSenderOperation")]
+    async fn resume_eth_sender_operation(&self, operation: EthSenderOperation) -> RpcResult<bool>;
+
+    /// Returns actual L1 costs of mined commit, prove and execute transactions for the specified L1 batch.
+    #[method(name = "getL1BatchL1Costs")]
+    async fn get_l1_batch_l1_costs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Vec<L1BatchL1Cost>>;
+}
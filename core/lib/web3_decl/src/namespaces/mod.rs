@@ -1,19 +1,25 @@
 pub use self::{
-    debug::DebugNamespaceClient, en::EnNamespaceClient, eth::EthNamespaceClient,
-    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient, web3::Web3NamespaceClient,
+    admin::AdminNamespaceClient, debug::DebugNamespaceClient, en::EnNamespaceClient,
+    eth::EthNamespaceClient, net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient,
+    trace::TraceNamespaceClient, txpool::TxpoolNamespaceClient, web3::Web3NamespaceClient,
     zks::ZksNamespaceClient,
 };
 #[cfg(feature = "server")]
 pub use self::{
-    debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
-    eth::EthPubSubServer, net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer,
-    web3::Web3NamespaceServer, zks::ZksNamespaceServer,
+    admin::AdminNamespaceServer, debug::DebugNamespaceServer, en::EnNamespaceServer,
+    eth::EthNamespaceServer, eth::EthPubSubServer, net::NetNamespaceServer,
+    snapshots::SnapshotsNamespaceServer, trace::TraceNamespaceServer,
+    txpool::TxpoolNamespaceServer, web3::Web3NamespaceServer, zks::ZksNamespaceServer,
+    zks::ZksPubSubServer,
 };
 
+mod admin;
 mod debug;
 mod en;
 mod eth;
 mod net;
 mod snapshots;
+mod trace;
+mod txpool;
 mod web3;
 mod zks;
@@ -0,0 +1,33 @@
+#[cfg_attr(not(feature = "server"), allow(unused_imports))]
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::proc_macros::rpc;
+use zksync_types::api::{
+    trace::{LocalizedTrace, TraceFilter},
+    BlockNumber,
+};
+
+use crate::{
+    client::{ForNetwork, L2},
+    types::H256,
+};
+
+/// OpenEthereum-style trace namespace. All methods return traces in the flat format based on the call traces
+/// persisted for executed transactions.
+#[cfg_attr(
+    feature = "server",
+    rpc(server, client, namespace = "trace", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+#[cfg_attr(
+    not(feature = "server"),
+    rpc(client, namespace = "trace", client_bounds(Self: ForNetwork<Net = L2>))
+)]
+pub trait TraceNamespace {
+    #[method(name = "block")]
+    async fn trace_block(&self, block: BlockNumber) -> RpcResult<Vec<LocalizedTrace>>;
+
+    #[method(name = "transaction")]
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Option<Vec<LocalizedTrace>>>;
+
+    #[method(name = "filter")]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>>;
+}
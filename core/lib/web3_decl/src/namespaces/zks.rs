@@ -5,10 +5,12 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetailedResult, TransactionDetails,
+        BalanceHistory, BaseTokenConversionRatio, BlockDetails, BridgeAddresses, L1BatchDetails,
+        L1BatchPubdata, L1GasPriceSample, L2BlockRawTransactions, L2ToL1LogProof, LogCursor,
+        LogsPage, Proof, ProofQuery, ProtocolUpgradeInfo, ProtocolVersion, ProtocolVersionInfo,
+        StorageInfo, TokenBalances, TokenTransfer, TransactionDetailedResult, TransactionDetails,
     },
-    fee::Fee,
+    fee::FeeEstimate,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     transaction_request::CallRequest,
     Address, L1BatchNumber, L2BlockNumber, H256, U256, U64,
@@ -16,7 +18,7 @@ use zksync_types::{
 
 use crate::{
     client::{ForNetwork, L2},
-    types::{Bytes, Token},
+    types::{Bytes, Filter, Token},
 };
 
 #[cfg_attr(
@@ -28,8 +30,14 @@ use crate::{
     rpc(client, namespace = "zks", client_bounds(Self: ForNetwork<Net = L2>))
 )]
 pub trait ZksNamespace {
+    /// Estimates the fee for a transaction. If `include_breakdown` is set, the response additionally
+    /// contains components of the estimated gas limit.
     #[method(name = "estimateFee")]
-    async fn estimate_fee(&self, req: CallRequest) -> RpcResult<Fee>;
+    async fn estimate_fee(
+        &self,
+        req: CallRequest,
+        include_breakdown: Option<bool>,
+    ) -> RpcResult<FeeEstimate>;
 
     #[method(name = "estimateGasL1ToL2")]
     async fn estimate_gas_l1_to_l2(&self, req: CallRequest) -> RpcResult<U256>;
@@ -49,6 +57,22 @@ pub trait ZksNamespace {
     #[method(name = "getBaseTokenL1Address")]
     async fn get_base_token_l1_address(&self) -> RpcResult<Address>;
 
+    /// Returns the latest ETH <-> base token conversion ratio persisted by the node, or `None` if there is no ratio
+    /// (e.g., if the chain uses ETH as the base token).
+    #[method(name = "getBaseTokenConversionRatio")]
+    async fn get_base_token_conversion_ratio(&self) -> RpcResult<Option<BaseTokenConversionRatio>>;
+
+    /// Returns L1 gas prices observed by the main node for L1 blocks with timestamps (in seconds) in the specified
+    /// inclusive range, ordered by the L1 block number. At most `limit` samples are returned; the limit is capped
+    /// by the server-side limit on returned entities.
+    #[method(name = "getL1GasPriceHistory")]
+    async fn get_l1_gas_price_history(
+        &self,
+        from_timestamp: Option<u64>,
+        to_timestamp: Option<u64>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<L1GasPriceSample>>;
+
     #[method(name = "L1ChainId")]
     async fn l1_chain_id(&self) -> RpcResult<U64>;
 
@@ -96,16 +120,33 @@ pub trait ZksNamespace {
         block_number: L2BlockNumber,
     ) -> RpcResult<Vec<zksync_types::Transaction>>;
 
+    /// Returns raw transactions for L2 blocks in the specified range. If the range is too large, the returned
+    /// blocks form a prefix of the range; the remaining blocks should be requested separately.
+    #[method(name = "getRawBlocksTransactions")]
+    async fn get_raw_blocks_transactions(
+        &self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> RpcResult<Vec<L2BlockRawTransactions>>;
+
     #[method(name = "getL1BatchDetails")]
     async fn get_l1_batch_details(&self, batch: L1BatchNumber)
         -> RpcResult<Option<L1BatchDetails>>;
 
+    #[method(name = "getBatchPubdata")]
+    async fn get_batch_pubdata(&self, batch: L1BatchNumber) -> RpcResult<Option<L1BatchPubdata>>;
+
+    /// Returns the bytecode with the specified hash, provided that it was published in one of sealed L2 blocks.
     #[method(name = "getBytecodeByHash")]
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>>;
 
     #[method(name = "getL1GasPrice")]
     async fn get_l1_gas_price(&self) -> RpcResult<U64>;
 
+    /// Returns the currently active fee model parameters: the fee model config (batch overhead, max gas and pubdata
+    /// per batch etc.) together with the current L1 gas and pubdata prices. Prices are returned without scaling
+    /// applied by the API server when estimating fees. The conversion ratio for custom base tokens is returned by
+    /// `zks_getBaseTokenConversionRatio`.
     #[method(name = "getFeeParams")]
     async fn get_fee_params(&self) -> RpcResult<FeeParams>;
 
@@ -115,6 +156,16 @@ pub trait ZksNamespace {
         version_id: Option<u16>,
     ) -> RpcResult<Option<ProtocolVersion>>;
 
+    /// Returns the content of the upgrade to the specified protocol version, including the decoded L2 system
+    /// upgrade transaction. Works both for upgrades that are already active and for scheduled ones.
+    /// Returns `None` if the version is unknown to the node.
+    #[method(name = "getProtocolUpgrade")]
+    async fn get_protocol_upgrade(&self, version_id: u16)
+        -> RpcResult<Option<ProtocolUpgradeInfo>>;
+
+    #[method(name = "getProtocolVersions")]
+    async fn get_protocol_versions(&self) -> RpcResult<Vec<ProtocolVersionInfo>>;
+
     #[method(name = "getProof")]
     async fn get_proof(
         &self,
@@ -123,12 +174,92 @@ pub trait ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Option<Proof>>;
 
+    /// Batched version of `zks_getProof` returning proofs for multiple accounts at the same L1 batch.
+    /// Proofs are returned in the same order as queries.
+    #[method(name = "getProofs")]
+    async fn get_proofs(
+        &self,
+        queries: Vec<ProofQuery>,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<Vec<Proof>>>;
+
     #[method(name = "getBatchFeeInput")]
     async fn get_batch_fee_input(&self) -> RpcResult<PubdataIndependentBatchFeeModelInput>;
 
+    /// Submits a transaction in the same way as `eth_sendRawTransaction`, and returns storage writes and events
+    /// produced by the transaction when it was executed in the sandbox during submission. The results are optimistic;
+    /// the transaction may produce different results once it's included into an L2 block.
     #[method(name = "sendRawTransactionWithDetailedOutput")]
     async fn send_raw_transaction_with_detailed_output(
         &self,
         tx_bytes: Bytes,
     ) -> RpcResult<TransactionDetailedResult>;
+
+    /// Cursor-based alternative to `eth_getLogs`. Returns up to `limit` logs matching the filter (capped by
+    /// the server-side limit on returned entities) and a cursor to request the following page with.
+    #[method(name = "getLogsPaginated")]
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> RpcResult<LogsPage>;
+
+    /// Returns the storage mode of the node (archive or pruned) together with its retention horizon,
+    /// i.e. the earliest L1 batch and L2 block that can be queried.
+    #[method(name = "getStorageInfo")]
+    async fn get_storage_info(&self) -> RpcResult<StorageInfo>;
+
+    /// Returns base token balance checkpoints of the account in the specified range of L2 blocks. Requires
+    /// the balance history index to be maintained on the node. If `from_block` is not specified, the range starts
+    /// from the first indexed L2 block; if `to_block` is not specified, it ends at the last indexed L2 block.
+    /// The number of returned checkpoints is capped by the server-side limit on returned entities.
+    #[method(name = "getBalanceHistory")]
+    async fn get_balance_history(
+        &self,
+        address: Address,
+        from_block: Option<L2BlockNumber>,
+        to_block: Option<L2BlockNumber>,
+    ) -> RpcResult<BalanceHistory>;
+
+    /// Returns ERC-20 token balances of the account computed from indexed `Transfer` events. Requires the token
+    /// transfer index to be maintained on the node. Balances are ordered by the token address and are paginated;
+    /// only tokens with the address greater than `after_token` are returned.
+    #[method(name = "getTokenBalances")]
+    async fn get_token_balances(
+        &self,
+        address: Address,
+        after_token: Option<Address>,
+    ) -> RpcResult<TokenBalances>;
+
+    /// Returns ERC-20 token transfers from or to the account in the specified range of L2 blocks. Requires the token
+    /// transfer index to be maintained on the node. The number of returned transfers is capped by the server-side limit
+    /// on returned entities.
+    #[method(name = "getTokenTransfers")]
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        from_block: Option<L2BlockNumber>,
+        to_block: Option<L2BlockNumber>,
+    ) -> RpcResult<Vec<TokenTransfer>>;
 }
+
+#[cfg(feature = "server")]
+mod pub_sub {
+    use jsonrpsee::{core::SubscriptionResult, proc_macros::rpc};
+
+    use crate::types::PubSubFilter;
+
+    #[rpc(server, namespace = "zks")]
+    pub trait ZksPubSub {
+        #[subscription(name = "subscribe" => "subscription", unsubscribe = "unsubscribe", item = PubSubResult)]
+        async fn subscribe(
+            &self,
+            sub_type: String,
+            filter: Option<PubSubFilter>,
+        ) -> SubscriptionResult;
+    }
+}
+
+#[cfg(feature = "server")]
+pub use self::pub_sub::ZksPubSubServer;
@@ -14,7 +14,7 @@ use core::{
 use rlp::Rlp;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 pub use zksync_types::{
-    api::{Block, BlockNumber, Log, TransactionReceipt, TransactionRequest},
+    api::{Block, BlockNumber, L2ToL1Log, Log, TransactionReceipt, TransactionRequest},
     ethabi,
     vm_trace::{ContractSourceDebugInfo, VmDebugTrace, VmExecutionStep},
     web3::{BlockHeader, Bytes, CallRequest, FeeHistory, Index, SyncState, TraceFilter, Work},
@@ -275,6 +275,17 @@ impl PubSubFilter {
         }
         true
     }
+
+    /// Checks whether the filter matches an L2-to-L1 log. Only the `address` part of the filter is applicable to these logs;
+    /// it is matched against the log sender.
+    pub fn matches_l2_to_l1_log(&self, log: &L2ToL1Log) -> bool {
+        if let Some(addresses) = &self.address {
+            if !addresses.0.contains(&log.sender) {
+                return false;
+            }
+        }
+        true
+    }
 }
 
 #[derive(Default, Clone)]
@@ -334,12 +345,36 @@ fn topic_to_option<T>(topic: ethabi::Topic<T>) -> Option<Vec<T>> {
     }
 }
 
+/// Status of an L2-to-L1 log reported in [`L2ToL1LogNotification`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum L2ToL1LogStatus {
+    /// The L2 block containing the log is sealed.
+    Sealed,
+    /// The L1 batch containing the log is executed on L1, i.e., the log can be proven on L1.
+    Executed,
+}
+
+/// Notification sent to subscribers of the `l2ToL1Logs` topic of `zks_subscribe`. Each log is reported twice:
+/// once the L2 block containing it is sealed, and then once its L1 batch is executed on L1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L2ToL1LogNotification {
+    #[serde(flatten)]
+    pub log: L2ToL1Log,
+    pub status: L2ToL1LogStatus,
+    /// Index of the log in the Merkle tree of L2-to-L1 logs of its L1 batch, i.e. the `id` returned
+    /// by `zks_getL2ToL1LogProof`. Only set for executed logs.
+    pub proof_id: Option<u32>,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum PubSubResult {
     Header(BlockHeader),
     Log(Log),
+    L2ToL1Log(L2ToL1LogNotification),
     TxHash(H256),
     Syncing(bool),
 }
@@ -14,8 +14,12 @@ use tokio::{
     sync::{oneshot, watch},
     task::JoinHandle,
 };
+use zksync_base_token_adjuster::{BaseTokenRatioPersister, FixedRatioSource};
 use zksync_circuit_breaker::{
-    l1_txs::FailedL1TransactionChecker, replication_lag::ReplicationLagChecker,
+    db_integrity::DbIntegrityChecker,
+    l1_txs::FailedL1TransactionChecker,
+    pool_saturation::{PoolSaturationChecker, PoolSaturationThresholds},
+    replication_lag::ReplicationLagChecker,
     CircuitBreakerChecker, CircuitBreakers,
 };
 use zksync_commitment_generator::{
@@ -27,19 +31,24 @@ use zksync_config::{
         api::{MerkleTreeApiConfig, Web3JsonRpcConfig},
         chain::{CircuitBreakerConfig, MempoolConfig, OperationsManagerConfig, StateKeeperConfig},
         consensus::ConsensusConfig,
+        da_client::{DAClient, DAClientConfig},
         database::{MerkleTreeConfig, MerkleTreeMode},
+        eth_sender::PubdataSendingMode,
         wallets,
         wallets::Wallets,
-        ContractsConfig, DatabaseSecrets, GeneralConfig, Secrets,
+        ApiSecrets, ContractsConfig, DatabaseSecrets, GeneralConfig, PruningConfig, Secrets,
     },
     ApiConfig, DBConfig, EthWatchConfig, GenesisConfig,
 };
 use zksync_contracts::governance_contract;
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_clients::{avail::AvailClient, celestia::CelestiaClient, eigen_da::EigenDAClient};
+use zksync_da_dispatcher::DataAvailabilityDispatcher;
 use zksync_dal::{metrics::PostgresMetrics, ConnectionPool, Core, CoreDal};
 use zksync_db_connection::healthcheck::ConnectionPoolHealthCheck;
-use zksync_eth_client::{clients::PKSigningClient, BoundEthInterface};
-use zksync_eth_sender::{Aggregator, EthTxAggregator, EthTxManager};
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch};
+use zksync_eth_client::{clients::signing_client_for_wallet, BoundEthInterface, EthInterface};
+use zksync_eth_sender::{Aggregator, EthSenderPauseHandle, EthTxAggregator, EthTxManager};
+use zksync_eth_watch::{EthClient, EthHttpQueryClient, EthWatch};
 use zksync_health_check::{AppHealthCheck, HealthStatus, ReactiveHealthCheck};
 use zksync_house_keeper::{
     blocks_state_reporter::L1BatchMetricsReporter,
@@ -50,31 +59,47 @@ use zksync_house_keeper::{
         FriWitnessGeneratorJobRetryManager, FriWitnessGeneratorQueueReporter,
         WaitingToQueuedFriWitnessJobMover,
     },
+    table_partition_manager::TablePartitionManager,
 };
 use zksync_metadata_calculator::{
-    api_server::TreeApiHttpClient, MetadataCalculator, MetadataCalculatorConfig,
+    api_server::TreeApiHttpClient, MerkleTreePruningTask, MerkleTreeReplica, MetadataCalculator,
+    MetadataCalculatorConfig,
 };
 use zksync_node_api_server::{
     healthcheck::HealthCheckHandle,
-    tx_sender::{build_tx_sender, TxSenderConfig},
+    tx_sender::{build_tx_sender, TxSender, TxSenderConfig},
     web3::{self, mempool_cache::MempoolCache, state::InternalApiConfig, Namespace},
 };
+use zksync_node_balance_history::{BalanceHistoryIndexer, BalanceHistoryIndexerConfig};
+use zksync_node_block_notifications::{
+    BlockNotificationsDispatcher, BlockNotificationsDispatcherConfig, WebhookSink,
+};
+use zksync_node_change_feed::{ChangeFeed, ChangeFeedConfig};
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
 use zksync_node_fee_model::{
-    l1_gas_price::GasAdjusterSingleton, BatchFeeModelInputProvider, MainNodeFeeInputProvider,
+    l1_gas_price::{GasAdjuster, GasAdjusterSingleton},
+    BatchFeeModelInputProvider, MainNodeFeeInputProvider,
 };
 use zksync_node_genesis::{ensure_genesis_state, GenesisParams};
+use zksync_node_token_transfers::{TokenTransfersIndexer, TokenTransfersIndexerConfig};
 use zksync_object_store::{ObjectStore, ObjectStoreFactory};
 use zksync_queued_job_processor::JobProcessor;
 use zksync_shared_metrics::{InitStage, APP_METRICS};
 use zksync_state::{PostgresStorageCaches, RocksdbStorageOptions};
 use zksync_state_keeper::{
-    create_state_keeper, io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
-    AsyncRocksdbCache, MempoolFetcher, MempoolGuard, OutputHandler, StateKeeperPersistence,
-    TreeWritesPersistence,
+    create_state_keeper,
+    io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
+    seal_criteria::{DaBlobSizeCriterion, SealCriterion},
+    AddressDenylist, AddressDenylistReloader, AsyncRocksdbCache, FeeAccountHandle,
+    L1BatchAbortHandle, L1BatchAbortReceiver, MempoolFetcher, MempoolGuard, OutputHandler,
+    StateKeeperPersistence, TreeWritesPersistence,
 };
 use zksync_tee_verifier_input_producer::TeeVerifierInputProducer;
-use zksync_types::{ethabi::Contract, fee_model::FeeModelConfig, Address, L2ChainId};
-use zksync_web3_decl::client::{Client, DynClient, L1};
+use zksync_types::{
+    ethabi::Contract, fee_model::FeeModelConfig, pubdata_da::PubdataEncoding, Address, L1ChainId,
+    L2ChainId,
+};
+use zksync_web3_decl::client::{Client, DynClient, FailoverClient, L1};
 
 pub mod temp_config_store;
 
@@ -96,6 +121,48 @@ pub async fn genesis_init(
     Ok(())
 }
 
+/// Runs pending Postgres migrations. In the dry-run mode, migrations are run against a shadow schema
+/// and the database is not modified; otherwise, migrations applied by this call are reverted if any of them fails.
+pub async fn run_migrations(
+    database_secrets: &DatabaseSecrets,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let db_url = database_secrets.master_url()?;
+    let pool = ConnectionPool::<Core>::singleton(db_url)
+        .build()
+        .await
+        .context("failed to build connection_pool")?;
+    let mut storage = pool.connection_tagged("migrations").await?;
+
+    let pending = storage.migrations_dal().get_pending_migrations().await?;
+    if pending.is_empty() {
+        tracing::info!("No pending Postgres migrations");
+        return Ok(());
+    }
+    tracing::info!("Pending Postgres migrations: {pending:?}");
+
+    if dry_run {
+        let reports = storage
+            .migrations_dal()
+            .dry_run_pending_migrations()
+            .await?;
+        for report in reports {
+            let migration = &report.migration;
+            tracing::info!(
+                "Migration {} ({}) took {:?} against the shadow schema and acquired locks: {:?}",
+                migration.version,
+                migration.description,
+                report.duration,
+                report.locks
+            );
+        }
+    } else {
+        let applied = storage.migrations_dal().apply_pending_migrations().await?;
+        tracing::info!("Applied {} Postgres migration(s)", applied.len());
+    }
+    Ok(())
+}
+
 pub async fn is_genesis_needed(database_secrets: &DatabaseSecrets) -> bool {
     let db_url = database_secrets.master_url().unwrap();
     let pool = ConnectionPool::<Core>::singleton(db_url)
@@ -156,6 +223,28 @@ pub enum Component {
     CommitmentGenerator,
     /// VM runner-based component that saves protective reads to Postgres.
     VmRunnerProtectiveReads,
+    /// VM runner-based component that re-executes L1 batches with a candidate protocol version
+    /// and records divergences from the canonical execution.
+    VmRunnerShadowExecution,
+    /// Component pruning old data from Postgres. Also enables pruning of the Merkle tree if it runs on the same node.
+    DbPruner,
+    /// Component dispatching notifications about sealed L2 blocks and L1 batch status changes. Also enables
+    /// writing notifications to the outbox in the state keeper and Ethereum sender running on the same node.
+    BlockNotifications,
+    /// Component indexing historical base token balances of accounts for the `zks_getBalanceHistory` API method.
+    BalanceHistory,
+    /// Component persisting the ETH <-> base token conversion ratio for the `zks_getBaseTokenConversionRatio`
+    /// API method.
+    BaseTokenRatioPersister,
+    /// Component indexing ERC-20 token transfers and balances for the `zks_getTokenBalances`
+    /// and `zks_getTokenTransfers` API methods.
+    TokenTransfers,
+    /// Component tailing Postgres logical replication and publishing changes of transactions, L2 blocks
+    /// and L1 batches as a change-data feed.
+    ChangeFeed,
+    /// Component dispatching L1 batch pubdata to a data availability (DA) layer and fetching inclusion data
+    /// for the dispatched blobs.
+    DADispatcher,
 }
 
 #[derive(Debug)]
@@ -195,6 +284,18 @@ impl FromStr for Components {
             "vm_runner_protective_reads" => {
                 Ok(Components(vec![Component::VmRunnerProtectiveReads]))
             }
+            "vm_runner_shadow_execution" => {
+                Ok(Components(vec![Component::VmRunnerShadowExecution]))
+            }
+            "db_pruner" => Ok(Components(vec![Component::DbPruner])),
+            "block_notifications" => Ok(Components(vec![Component::BlockNotifications])),
+            "balance_history" => Ok(Components(vec![Component::BalanceHistory])),
+            "base_token_ratio_persister" => {
+                Ok(Components(vec![Component::BaseTokenRatioPersister]))
+            }
+            "token_transfers" => Ok(Components(vec![Component::TokenTransfers])),
+            "change_feed" => Ok(Components(vec![Component::ChangeFeed])),
+            "da_dispatcher" => Ok(Components(vec![Component::DADispatcher])),
             other => Err(format!("{} is not a valid component name", other)),
         }
     }
@@ -225,6 +326,12 @@ pub async fn initialize_components(
     if let Some(threshold) = postgres_config.long_connection_threshold() {
         ConnectionPool::<Core>::global_config().set_long_connection_threshold(threshold)?;
     }
+    if let Some(report) = postgres_config.report_all_query_latencies {
+        ConnectionPool::<Core>::global_config().set_report_all_latencies(report);
+    }
+    if let Some(redact) = postgres_config.redact_query_args {
+        ConnectionPool::<Core>::global_config().set_redact_query_args(redact);
+    }
 
     let pool_size = postgres_config.max_connections()?;
     let pool_size_master = postgres_config
@@ -240,6 +347,8 @@ pub async fn initialize_components(
     // on Postgres.
     let replica_connection_pool =
         ConnectionPool::<Core>::builder(database_secrets.replica_url()?, pool_size)
+            .set_replica_urls(database_secrets.server_read_replica_urls.clone())
+            .set_max_replica_lag(postgres_config.max_replica_lag())
             .set_acquire_timeout(postgres_config.acquire_timeout())
             .set_statement_timeout(postgres_config.statement_timeout())
             .build()
@@ -264,12 +373,16 @@ pub async fn initialize_components(
         .clone()
         .context("circuit_breaker_config")?;
 
+    let (circuit_breakers, load_shedding) = circuit_breakers_for_components(
+        components,
+        &database_secrets,
+        &circuit_breaker_config,
+        &replica_connection_pool,
+    )
+    .await
+    .context("circuit_breakers_for_components")?;
     let circuit_breaker_checker = CircuitBreakerChecker::new(
-        Arc::new(
-            circuit_breakers_for_components(components, &database_secrets, &circuit_breaker_config)
-                .await
-                .context("circuit_breakers_for_components")?,
-        ),
+        Arc::new(circuit_breakers),
         circuit_breaker_config.sync_interval(),
     );
     circuit_breaker_checker.check().await.unwrap_or_else(|err| {
@@ -280,7 +393,35 @@ pub async fn initialize_components(
         .context("Ethereum client")?
         .for_network(genesis_config.l1_chain_id.into())
         .build();
-    let query_client = Box::new(query_client);
+    let mut query_client: Box<DynClient<L1>> = Box::new(query_client);
+    let mut l1_failover_probe = None;
+    if !l1_secrets.fallback_l1_rpc_urls.is_empty() {
+        let mut providers = vec![query_client];
+        for url in &l1_secrets.fallback_l1_rpc_urls {
+            let client = Client::http(url.clone())
+                .context("fallback Ethereum client")?
+                .for_network(genesis_config.l1_chain_id.into())
+                .build();
+            providers.push(Box::new(client) as Box<DynClient<L1>>);
+        }
+        let failover_client = FailoverClient::new(providers);
+        l1_failover_probe = Some(
+            failover_client
+                .probe()
+                .with_expected_chain_id(genesis_config.l1_chain_id.0),
+        );
+        query_client = Box::new(failover_client);
+    }
+    let gateway_client = l1_secrets
+        .gateway_rpc_url
+        .clone()
+        .map(|url| {
+            anyhow::Ok(
+                Box::new(Client::http(url).context("gateway client")?.build())
+                    as Box<DynClient<L1>>,
+            )
+        })
+        .transpose()?;
     let gas_adjuster_config = eth.gas_adjuster.context("gas_adjuster")?;
     let sender = eth.sender.as_ref().context("sender")?;
 
@@ -290,7 +431,8 @@ pub async fn initialize_components(
         gas_adjuster_config,
         sender.pubdata_sending_mode,
         genesis_config.l1_batch_commit_data_generator_mode,
-    );
+    )
+    .with_price_history(connection_pool.clone());
 
     let (stop_sender, stop_receiver) = watch::channel(false);
 
@@ -316,6 +458,30 @@ pub async fn initialize_components(
         prometheus_task,
         tokio::spawn(circuit_breaker_checker.run(stop_receiver.clone())),
     ];
+    if let Some(probe) = l1_failover_probe {
+        task_futures.push(tokio::spawn(probe.run(stop_receiver.clone())));
+    }
+
+    // The denylist is shared between the API server and the state keeper, so it's loaded once.
+    let address_denylist = if components.contains(&Component::WsApi)
+        || components.contains(&Component::HttpApi)
+        || components.contains(&Component::StateKeeper)
+    {
+        let mempool_config = configs.mempool_config.as_ref().context("mempool_config")?;
+        load_address_denylist(mempool_config, &mut task_futures, stop_receiver.clone())?
+    } else {
+        None
+    };
+
+    // The private admin API server is started after all other components, so that it can use their handles.
+    let admin_http_port = configs
+        .api_config
+        .as_ref()
+        .and_then(|config| config.web3_json_rpc.admin_http_port);
+    let mut admin_api_tx_sender = None;
+    let mut l1_batch_abort_handle = None;
+    let mut fee_account_handle = None;
+    let mut eth_sender_pause_handle = None;
 
     if components.contains(&Component::WsApi)
         || components.contains(&Component::HttpApi)
@@ -336,6 +502,18 @@ pub async fn initialize_components(
                 .fee_account
                 .address(),
             l2_chain_id,
+        )
+        .with_replacement_fee_bump(
+            configs
+                .mempool_config
+                .as_ref()
+                .and_then(|config| config.replacement_fee_bump_percent),
+        )
+        .with_max_pending_txs_per_account(
+            configs
+                .mempool_config
+                .as_ref()
+                .and_then(|config| config.max_pending_txs_per_account),
         );
         let internal_api_config =
             InternalApiConfig::new(&api_config.web3_json_rpc, contracts_config, genesis_config);
@@ -376,10 +554,11 @@ pub async fn initialize_components(
                 bounded_gas_adjuster,
                 FeeModelConfig::from_state_keeper_config(&state_keeper_config),
             ));
-            run_http_api(
+            let tx_sender = run_http_api(
                 &mut task_futures,
                 &app_health,
                 &database_secrets,
+                secrets.api.as_ref(),
                 &tx_sender_config,
                 &state_keeper_config,
                 &internal_api_config,
@@ -391,9 +570,12 @@ pub async fn initialize_components(
                 state_keeper_config.save_call_traces,
                 storage_caches.clone().unwrap(),
                 mempool_cache.clone(),
+                address_denylist.clone(),
+                load_shedding.clone(),
             )
             .await
             .context("run_http_api")?;
+            admin_api_tx_sender = Some(tx_sender);
 
             let elapsed = started_at.elapsed();
             APP_METRICS.init_latency[&InitStage::HttpApi].set(elapsed);
@@ -429,6 +611,7 @@ pub async fn initialize_components(
                 &mut task_futures,
                 &app_health,
                 &database_secrets,
+                secrets.api.as_ref(),
                 &tx_sender_config,
                 &state_keeper_config,
                 &internal_api_config,
@@ -439,6 +622,8 @@ pub async fn initialize_components(
                 stop_receiver.clone(),
                 storage_caches,
                 mempool_cache,
+                address_denylist.clone(),
+                load_shedding.clone(),
             )
             .await
             .context("run_ws_api")?;
@@ -495,7 +680,16 @@ pub async fn initialize_components(
             bounded_gas_adjuster,
             FeeModelConfig::from_state_keeper_config(&state_keeper_config),
         ));
-        add_state_keeper_to_task_futures(
+        let l1_batch_abort_receiver = admin_http_port.map(|_| {
+            let (handle, receiver) = L1BatchAbortHandle::new();
+            l1_batch_abort_handle = Some(handle);
+            receiver
+        });
+        let pubdata_encoding = PubdataEncoding::new(
+            genesis_config.l1_batch_commit_data_generator_mode,
+            sender.pubdata_sending_mode,
+        )?;
+        let handle = add_state_keeper_to_task_futures(
             &mut task_futures,
             &database_secrets,
             contracts_config,
@@ -508,10 +702,16 @@ pub async fn initialize_components(
             &db_config,
             &configs.mempool_config.clone().context("mempool_config")?,
             batch_fee_input_provider,
+            address_denylist.clone(),
+            components.contains(&Component::BlockNotifications),
+            l1_batch_abort_receiver,
+            pubdata_encoding,
+            configs.da_client_config.as_ref(),
             stop_receiver.clone(),
         )
         .await
         .context("add_state_keeper_to_task_futures()")?;
+        fee_account_handle = Some(handle);
 
         let elapsed = started_at.elapsed();
         APP_METRICS.init_latency[&InitStage::StateKeeper].set(elapsed);
@@ -580,6 +780,8 @@ pub async fn initialize_components(
                 diamond_proxy_addr,
                 state_transition_manager_addr,
                 governance,
+                genesis_config.l1_chain_id,
+                gateway_client.clone(),
                 stop_receiver.clone(),
             )
             .await
@@ -599,7 +801,6 @@ pub async fn initialize_components(
             .context("failed to build eth_sender_pool")?;
 
         let eth_sender_wallets = wallets.eth_sender.clone().context("eth_sender")?;
-        let operator_private_key = eth_sender_wallets.operator.private_key();
         let diamond_proxy_addr = contracts_config.diamond_proxy_addr;
         let default_priority_fee_per_gas = eth
             .gas_adjuster
@@ -608,13 +809,15 @@ pub async fn initialize_components(
             .default_priority_fee_per_gas;
         let l1_chain_id = genesis_config.l1_chain_id;
 
-        let eth_client = PKSigningClient::new_raw(
-            operator_private_key.clone(),
+        let eth_client = signing_client_for_wallet(
+            &eth_sender_wallets.operator,
             diamond_proxy_addr,
             default_priority_fee_per_gas,
             l1_chain_id,
             query_client.clone(),
-        );
+        )
+        .await
+        .context("failed creating signing client for operator")?;
 
         let l1_batch_commit_data_generator_mode =
             genesis_config.l1_batch_commit_data_generator_mode;
@@ -632,23 +835,51 @@ pub async fn initialize_components(
         let operator_blobs_address = eth_sender_wallets.blob_operator.map(|x| x.address());
 
         let sender_config = eth.sender.clone().context("eth_sender")?;
-        let eth_tx_aggregator_actor = EthTxAggregator::new(
+        let mut aggregator = Aggregator::new(
+            sender_config.clone(),
+            store_factory.create_store().await?,
+            operator_blobs_address.is_some(),
+            l1_batch_commit_data_generator_mode,
+        );
+        if aggregator.has_dynamic_pubdata_da() {
+            let gas_adjuster = gas_adjuster
+                .get_or_init()
+                .await
+                .context("gas_adjuster.get_or_init()")?;
+            aggregator = aggregator.with_gas_adjuster(gas_adjuster);
+        }
+        let mut eth_tx_aggregator_actor = EthTxAggregator::new(
             eth_sender_pool,
             sender_config.clone(),
-            Aggregator::new(
-                sender_config.clone(),
-                store_factory.create_store().await?,
-                operator_blobs_address.is_some(),
-                l1_batch_commit_data_generator_mode,
-            ),
-            Box::new(eth_client),
+            aggregator,
+            eth_client,
             contracts_config.validator_timelock_addr,
             contracts_config.l1_multicall3_addr,
             diamond_proxy_addr,
             l2_chain_id,
             operator_blobs_address,
         )
+        .await
+        .with_successor_operators(
+            eth_sender_wallets
+                .successor_operator
+                .map(|wallet| wallet.address()),
+            eth_sender_wallets
+                .successor_blob_operator
+                .map(|wallet| wallet.address()),
+        )
         .await;
+        if let Some(gateway_client) = gateway_client.clone() {
+            let gateway_client = create_gateway_signing_client(
+                &eth_sender_wallets.operator,
+                default_priority_fee_per_gas,
+                gateway_client,
+            )
+            .await?;
+            eth_tx_aggregator_actor = eth_tx_aggregator_actor.with_gateway_client(gateway_client);
+        }
+        app_health.insert_custom_component(Arc::new(eth_tx_aggregator_actor.pause_handle()))?;
+        eth_sender_pause_handle = Some(eth_tx_aggregator_actor.pause_handle());
         task_futures.push(tokio::spawn(
             eth_tx_aggregator_actor.run(stop_receiver.clone()),
         ));
@@ -666,7 +897,6 @@ pub async fn initialize_components(
             .context("failed to build eth_manager_pool")?;
         let eth_sender = configs.eth.clone().context("eth_sender_config")?;
         let eth_sender_wallets = wallets.eth_sender.clone().context("eth_sender")?;
-        let operator_private_key = eth_sender_wallets.operator.private_key();
         let diamond_proxy_addr = contracts_config.diamond_proxy_addr;
         let default_priority_fee_per_gas = eth
             .gas_adjuster
@@ -675,38 +905,95 @@ pub async fn initialize_components(
             .default_priority_fee_per_gas;
         let l1_chain_id = genesis_config.l1_chain_id;
 
-        let eth_client = PKSigningClient::new_raw(
-            operator_private_key.clone(),
+        let eth_client = signing_client_for_wallet(
+            &eth_sender_wallets.operator,
             diamond_proxy_addr,
             default_priority_fee_per_gas,
             l1_chain_id,
             query_client.clone(),
-        );
+        )
+        .await
+        .context("failed creating signing client for operator")?;
 
-        let eth_client_blobs = if let Some(blob_operator) = eth_sender_wallets.blob_operator {
-            let operator_blob_private_key = blob_operator.private_key().clone();
-            let client = Box::new(PKSigningClient::new_raw(
-                operator_blob_private_key,
+        let eth_client_blobs = if let Some(blob_operator) = &eth_sender_wallets.blob_operator {
+            let client = signing_client_for_wallet(
+                blob_operator,
                 diamond_proxy_addr,
                 default_priority_fee_per_gas,
                 l1_chain_id,
-                query_client,
-            ));
-            Some(client as Box<dyn BoundEthInterface>)
+                query_client.clone(),
+            )
+            .await
+            .context("failed creating signing client for blob operator")?;
+            Some(client)
         } else {
             None
         };
+        let mut successor_eth_clients = vec![];
+        for wallet in [
+            &eth_sender_wallets.successor_operator,
+            &eth_sender_wallets.successor_blob_operator,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let client = signing_client_for_wallet(
+                wallet,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
+                l1_chain_id,
+                query_client.clone(),
+            )
+            .await
+            .context("failed creating signing client for successor operator")?;
+            successor_eth_clients.push(client);
+        }
 
-        let eth_tx_manager_actor = EthTxManager::new(
+        let mut eth_tx_manager_actor = EthTxManager::new(
             eth_manager_pool,
             eth_sender.sender.clone().context("eth_sender")?,
             gas_adjuster
                 .get_or_init()
                 .await
                 .context("gas_adjuster.get_or_init()")?,
-            Box::new(eth_client),
+            eth_client,
             eth_client_blobs,
-        );
+        )
+        .with_successor_gateways(successor_eth_clients);
+        if components.contains(&Component::BlockNotifications) {
+            eth_tx_manager_actor = eth_tx_manager_actor.with_block_notifications();
+        }
+        if let Some(private_relay_url) = l1_secrets.private_relay_rpc_url.clone() {
+            let private_relay_client = Client::http(private_relay_url)
+                .context("private relay Ethereum client")?
+                .for_network(l1_chain_id.into())
+                .build();
+            eth_tx_manager_actor =
+                eth_tx_manager_actor.with_private_relay_client(Box::new(private_relay_client));
+        }
+        if let Some(gateway_client) = gateway_client.clone() {
+            // Pubdata is never sent to the gateway chain in blobs.
+            let gateway_gas_adjuster = GasAdjuster::new(
+                gateway_client.clone(),
+                gas_adjuster_config,
+                PubdataSendingMode::Calldata,
+                genesis_config.l1_batch_commit_data_generator_mode,
+            )
+            .await
+            .context("GasAdjuster::new() for gateway")?;
+            let gateway_gas_adjuster = Arc::new(gateway_gas_adjuster);
+            task_futures.push(tokio::spawn(
+                gateway_gas_adjuster.clone().run(stop_receiver.clone()),
+            ));
+            let gateway_client = create_gateway_signing_client(
+                &eth_sender_wallets.operator,
+                default_priority_fee_per_gas,
+                gateway_client,
+            )
+            .await?;
+            eth_tx_manager_actor =
+                eth_tx_manager_actor.with_gateway_client(gateway_client, gateway_gas_adjuster);
+        }
         task_futures.extend([tokio::spawn(
             eth_tx_manager_actor.run(stop_receiver.clone()),
         )]);
@@ -785,6 +1072,136 @@ pub async fn initialize_components(
         ));
     }
 
+    if components.contains(&Component::DbPruner) {
+        let pruning_config = configs.pruning.clone().context("pruning")?;
+        tracing::warn!("Proceeding with node state pruning for Postgres. This is an experimental feature; use at your own risk");
+
+        let minimum_l1_batch_age = pruning_config.data_retention();
+        tracing::info!(
+            "Configured pruning of batches after they become {minimum_l1_batch_age:?} old"
+        );
+        let db_pruner = DbPruner::for_main_node(
+            DbPrunerConfig {
+                removal_delay: pruning_config.removal_delay(),
+                pruned_batch_chunk_size: pruning_config.chunk_size,
+                minimum_l1_batch_age,
+            },
+            connection_pool.clone(),
+        );
+        app_health.insert_component(db_pruner.health_check())?;
+        task_futures.push(tokio::spawn(db_pruner.run(stop_receiver.clone())));
+    }
+
+    if components.contains(&Component::BlockNotifications) {
+        let config = configs
+            .block_notifications
+            .clone()
+            .context("block_notifications")?;
+        let mut dispatcher = BlockNotificationsDispatcher::new(
+            connection_pool.clone(),
+            BlockNotificationsDispatcherConfig {
+                poll_interval: config.poll_interval(),
+                max_batch_size: config.max_batch_size,
+            },
+        );
+        if let Some(webhook_url) = config.webhook_url {
+            dispatcher.add_sink(Box::new(WebhookSink::new(webhook_url)));
+        }
+        task_futures.push(tokio::spawn(dispatcher.run(stop_receiver.clone())));
+    }
+
+    if components.contains(&Component::BalanceHistory) {
+        let indexer = BalanceHistoryIndexer::new(
+            connection_pool.clone(),
+            BalanceHistoryIndexerConfig::default(),
+        );
+        task_futures.push(tokio::spawn(indexer.run(stop_receiver.clone())));
+    }
+
+    if components.contains(&Component::BaseTokenRatioPersister) {
+        let config = configs
+            .base_token_adjuster
+            .clone()
+            .context("base_token_adjuster")?;
+        let persister = BaseTokenRatioPersister::new(
+            connection_pool.clone(),
+            &config,
+            Box::new(FixedRatioSource::new(&config)),
+        );
+        task_futures.push(tokio::spawn(persister.run(stop_receiver.clone())));
+    }
+
+    if components.contains(&Component::TokenTransfers) {
+        let indexer = TokenTransfersIndexer::new(
+            connection_pool.clone(),
+            TokenTransfersIndexerConfig::default(),
+        );
+        task_futures.push(tokio::spawn(indexer.run(stop_receiver.clone())));
+    }
+
+    if components.contains(&Component::ChangeFeed) {
+        let config = configs.change_feed.clone().context("change_feed")?;
+        let mut feed = ChangeFeed::new(
+            connection_pool.clone(),
+            ChangeFeedConfig {
+                slot_name: config.slot_name.clone(),
+                poll_interval: config.poll_interval(),
+                max_batch_size: config.max_batch_size,
+            },
+        );
+        if let Some(webhook_url) = config.webhook_url {
+            feed.add_sink(Box::new(zksync_node_change_feed::WebhookSink::new(
+                webhook_url,
+            )));
+        }
+        task_futures.push(tokio::spawn(feed.run(stop_receiver.clone())));
+    }
+
+    if components.contains(&Component::DADispatcher) {
+        let da_client_config = configs
+            .da_client_config
+            .clone()
+            .context("da_client_config")?;
+        let da_dispatcher_config = configs
+            .da_dispatcher_config
+            .clone()
+            .context("da_dispatcher_config")?;
+        let da_client = create_da_client(da_client_config)?;
+        let dispatcher = DataAvailabilityDispatcher::new(
+            connection_pool.clone(),
+            da_dispatcher_config,
+            da_client,
+        );
+        task_futures.push(tokio::spawn(dispatcher.run(stop_receiver.clone())));
+    }
+
+    // Same as in the node framework, the admin API server is run together with the HTTP API server.
+    if let (Some(admin_http_port), Some(tx_sender)) = (admin_http_port, admin_api_tx_sender) {
+        let started_at = Instant::now();
+        tracing::info!("Initializing admin API");
+        run_admin_api(
+            &mut task_futures,
+            &app_health,
+            admin_http_port,
+            secrets,
+            configs,
+            contracts_config,
+            genesis_config,
+            replica_connection_pool.clone(),
+            tx_sender,
+            l1_batch_abort_handle,
+            fee_account_handle,
+            eth_sender_pause_handle,
+            stop_receiver.clone(),
+        )
+        .await
+        .context("run_admin_api")?;
+        tracing::info!(
+            "Initialized admin API on port {admin_http_port} in {:?}",
+            started_at.elapsed()
+        );
+    }
+
     // Run healthcheck server for all components.
     let db_health_check = ConnectionPoolHealthCheck::new(replica_connection_pool);
     app_health.insert_custom_component(Arc::new(db_health_check))?;
@@ -798,6 +1215,44 @@ pub async fn initialize_components(
     Ok((task_futures, stop_sender, health_check_handle))
 }
 
+fn create_da_client(config: DAClientConfig) -> anyhow::Result<Box<dyn DataAvailabilityClient>> {
+    let request_timeout = config.request_timeout();
+    Ok(match config.client {
+        DAClient::Avail(avail_config) => {
+            Box::new(AvailClient::new(avail_config, request_timeout).context("Avail client")?)
+        }
+        DAClient::Celestia(celestia_config) => Box::new(
+            CelestiaClient::new(celestia_config, request_timeout).context("Celestia client")?,
+        ),
+        DAClient::EigenDA(eigen_da_config) => Box::new(
+            EigenDAClient::new(eigen_da_config, request_timeout).context("EigenDA client")?,
+        ),
+    })
+}
+
+/// Loads the address denylist if it's configured, and spawns a task periodically reloading it.
+fn load_address_denylist(
+    mempool_config: &MempoolConfig,
+    task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<Option<AddressDenylist>> {
+    let Some(path) = &mempool_config.address_denylist_path else {
+        return Ok(None);
+    };
+    let denylist = AddressDenylist::from_file(path)?;
+    tracing::info!(
+        "Loaded address denylist with {} addresses from `{path}`",
+        denylist.len()
+    );
+    let reloader = AddressDenylistReloader::new(
+        denylist.clone(),
+        path,
+        mempool_config.address_denylist_reload_interval(),
+    );
+    task_futures.push(tokio::spawn(reloader.run(stop_receiver)));
+    Ok(Some(denylist))
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn add_state_keeper_to_task_futures(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
@@ -809,8 +1264,13 @@ async fn add_state_keeper_to_task_futures(
     db_config: &DBConfig,
     mempool_config: &MempoolConfig,
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
+    address_denylist: Option<AddressDenylist>,
+    insert_block_notifications: bool,
+    l1_batch_abort_receiver: Option<L1BatchAbortReceiver>,
+    pubdata_encoding: PubdataEncoding,
+    da_client_config: Option<&DAClientConfig>,
     stop_receiver: watch::Receiver<bool>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<FeeAccountHandle> {
     let state_keeper_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
         .build()
         .await
@@ -820,7 +1280,7 @@ async fn add_state_keeper_to_task_futures(
             .connection()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, mempool_config).await;
         mempool.register_metrics();
         mempool
     };
@@ -833,13 +1293,17 @@ async fn add_state_keeper_to_task_futures(
     .build()
     .await
     .context("failed to build l2_block_sealer_pool")?;
-    let (persistence, l2_block_sealer) = StateKeeperPersistence::new(
+    let (mut persistence, l2_block_sealer) = StateKeeperPersistence::new(
         persistence_pool.clone(),
         contracts_config
             .l2_shared_bridge_addr
             .context("`l2_shared_bridge_addr` config is missing")?,
         state_keeper_config.l2_block_seal_queue_capacity,
     );
+    persistence = persistence.with_pubdata_encoding(pubdata_encoding);
+    if insert_block_notifications {
+        persistence = persistence.with_block_notifications();
+    }
     task_futures.push(tokio::spawn(l2_block_sealer.run()));
 
     // One (potentially held long-term) connection for `AsyncCatchupTask` and another connection
@@ -853,6 +1317,7 @@ async fn add_state_keeper_to_task_futures(
             .experimental
             .state_keeper_db_block_cache_capacity(),
         max_open_files: db_config.experimental.state_keeper_db_max_open_files,
+        compaction_interval: db_config.experimental.state_keeper_db_compaction_interval(),
     };
     let (async_cache, async_catchup_task) = AsyncRocksdbCache::new(
         async_cache_pool,
@@ -863,7 +1328,14 @@ async fn add_state_keeper_to_task_futures(
     let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
     let output_handler =
         OutputHandler::new(Box::new(persistence)).with_handler(Box::new(tree_writes_persistence));
-    let state_keeper = create_state_keeper(
+    // Batches must fit into a single blob if their pubdata is dispatched to a DA layer.
+    let mut custom_seal_criteria: Vec<Box<dyn SealCriterion>> = vec![];
+    if let Some(blob_size_limit) =
+        da_client_config.and_then(|config| zksync_da_clients::blob_size_limit(&config.client))
+    {
+        custom_seal_criteria.push(Box::new(DaBlobSizeCriterion::new(blob_size_limit)));
+    }
+    let (mut state_keeper, fee_account_handle) = create_state_keeper(
         state_keeper_config,
         state_keeper_wallets,
         async_cache,
@@ -873,9 +1345,14 @@ async fn add_state_keeper_to_task_futures(
         mempool.clone(),
         batch_fee_input_provider.clone(),
         output_handler,
+        address_denylist,
+        custom_seal_criteria,
         stop_receiver.clone(),
     )
     .await;
+    if let Some(receiver) = l1_batch_abort_receiver {
+        state_keeper = state_keeper.with_l1_batch_abort_receiver(receiver);
+    }
 
     let mut stop_receiver_clone = stop_receiver.clone();
     task_futures.push(tokio::task::spawn(async move {
@@ -897,7 +1374,7 @@ async fn add_state_keeper_to_task_futures(
     );
     let mempool_fetcher_handle = tokio::spawn(mempool_fetcher.run(stop_receiver));
     task_futures.push(mempool_fetcher_handle);
-    Ok(())
+    Ok(fee_account_handle)
 }
 
 pub async fn start_eth_watch(
@@ -907,6 +1384,8 @@ pub async fn start_eth_watch(
     diamond_proxy_addr: Address,
     state_transition_manager_addr: Option<Address>,
     governance: (Contract, Address),
+    l1_chain_id: L1ChainId,
+    gateway_client: Option<Box<DynClient<L1>>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<JoinHandle<anyhow::Result<()>>> {
     let eth_client = EthHttpQueryClient::new(
@@ -925,10 +1404,41 @@ pub async fn start_eth_watch(
         config.poll_interval(),
     )
     .await?;
+    let gateway_client = gateway_client.map(|client| {
+        Box::new(EthHttpQueryClient::for_settlement_layer(
+            client,
+            config.confirmations_for_eth_event,
+        )) as Box<dyn EthClient>
+    });
+    let eth_watch = eth_watch
+        .with_settlement_layer_switches(l1_chain_id, gateway_client)
+        .await?;
 
     Ok(tokio::spawn(eth_watch.run(stop_receiver)))
 }
 
+/// Creates a signing client for the gateway settlement layer. Contract addresses are specified
+/// by the eth_sender for each transaction, so the client isn't bound to a diamond proxy.
+async fn create_gateway_signing_client(
+    operator: &wallets::Wallet,
+    default_priority_fee_per_gas: u64,
+    gateway_client: Box<DynClient<L1>>,
+) -> anyhow::Result<Box<dyn BoundEthInterface>> {
+    let gateway_chain_id = gateway_client
+        .fetch_chain_id()
+        .await
+        .context("failed fetching gateway chain ID")?;
+    signing_client_for_wallet(
+        operator,
+        Address::zero(),
+        default_priority_fee_per_gas,
+        gateway_chain_id,
+        gateway_client,
+    )
+    .await
+    .context("failed creating signing client for operator on gateway")
+}
+
 async fn add_trees_to_task_futures(
     configs: &GeneralConfig,
     secrets: &Secrets,
@@ -939,10 +1449,12 @@ async fn add_trees_to_task_futures(
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     if !components.contains(&Component::Tree) {
-        anyhow::ensure!(
-            !components.contains(&Component::TreeApi),
-            "Merkle tree API cannot be started without a tree component"
-        );
+        if components.contains(&Component::TreeApi) {
+            // The tree is run by another process; serve the tree API from a read-only replica.
+            return run_tree_api_replica(task_futures, app_health, configs, stop_receiver)
+                .await
+                .context("run_tree_api_replica()");
+        }
         return Ok(());
     }
 
@@ -961,10 +1473,22 @@ async fn add_trees_to_task_futures(
         .contains(&Component::TreeApi)
         .then_some(&api_config);
 
+    // Tree pruning must follow Postgres pruning, so it's enabled together with the DB pruner.
+    let pruning_config = if components.contains(&Component::DbPruner) {
+        Some(configs.pruning.clone().context("pruning")?)
+    } else {
+        None
+    };
+
     let object_store = match db_config.merkle_tree.mode {
         MerkleTreeMode::Lightweight => None,
         MerkleTreeMode::Full => Some(store_factory.create_store().await?),
     };
+    let checkpoint_object_store = if db_config.merkle_tree.checkpoint_interval().is_some() {
+        Some(store_factory.create_store().await?)
+    } else {
+        None
+    };
 
     run_tree(
         task_futures,
@@ -973,7 +1497,9 @@ async fn add_trees_to_task_futures(
         &db_config.merkle_tree,
         api_config,
         &operation_config,
+        pruning_config.as_ref(),
         object_store,
+        checkpoint_object_store,
         stop_receiver,
     )
     .await
@@ -988,7 +1514,9 @@ async fn run_tree(
     merkle_tree_config: &MerkleTreeConfig,
     api_config: Option<&MerkleTreeApiConfig>,
     operation_manager: &OperationsManagerConfig,
+    pruning_config: Option<&PruningConfig>,
     object_store: Option<Arc<dyn ObjectStore>>,
+    checkpoint_object_store: Option<Arc<dyn ObjectStore>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let started_at = Instant::now();
@@ -1010,7 +1538,7 @@ async fn run_tree(
         .build()
         .await
         .context("failed to build connection pool for Merkle tree recovery")?;
-    let metadata_calculator = MetadataCalculator::new(config, object_store, pool)
+    let mut metadata_calculator = MetadataCalculator::new(config, object_store, pool)
         .await
         .context("failed initializing metadata_calculator")?
         .with_recovery_pool(recovery_pool);
@@ -1029,6 +1557,36 @@ async fn run_tree(
         }));
     }
 
+    let tree_pruning_poll_interval = if let Some(pruning_config) = pruning_config {
+        Some(pruning_config.removal_delay() / 2)
+    } else {
+        merkle_tree_config
+            .pruning_retained_l1_batches
+            .map(|_| MerkleTreePruningTask::DEFAULT_POLL_INTERVAL)
+    };
+    if let Some(poll_interval) = tree_pruning_poll_interval {
+        tracing::warn!("Proceeding with node state pruning for the Merkle tree. This is an experimental feature; use at your own risk");
+
+        let pruning_task = metadata_calculator.pruning_task(poll_interval);
+        app_health.insert_component(pruning_task.health_check())?;
+        task_futures.push(tokio::spawn(pruning_task.run(stop_receiver.clone())));
+    }
+
+    if let Some(poll_interval) = merkle_tree_config.verification_interval() {
+        let verification_task = metadata_calculator.verification_task(poll_interval);
+        app_health.insert_component(verification_task.health_check())?;
+        task_futures.push(tokio::spawn(verification_task.run(stop_receiver.clone())));
+    }
+
+    if let (Some(interval), Some(object_store)) = (
+        merkle_tree_config.checkpoint_interval(),
+        checkpoint_object_store,
+    ) {
+        let checkpoint_task = metadata_calculator.checkpoint_task(object_store, interval);
+        app_health.insert_component(checkpoint_task.health_check())?;
+        task_futures.push(tokio::spawn(checkpoint_task.run(stop_receiver.clone())));
+    }
+
     let tree_health_check = metadata_calculator.tree_health_check();
     app_health.insert_custom_component(Arc::new(tree_health_check))?;
     let tree_task = tokio::spawn(metadata_calculator.run(stop_receiver));
@@ -1040,6 +1598,46 @@ async fn run_tree(
     Ok(())
 }
 
+async fn run_tree_api_replica(
+    task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    app_health: &AppHealthCheck,
+    configs: &GeneralConfig,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let db_config = configs.db_config.clone().context("db_config")?;
+    let operation_config = configs
+        .operations_manager_config
+        .clone()
+        .context("operations_manager_config")?;
+    let api_config = configs
+        .api_config
+        .clone()
+        .context("api_config")?
+        .merkle_tree;
+    let replica_path = api_config.replica_path.clone().context(
+        "Merkle tree API run without a tree component requires `replica_path` to be specified",
+    )?;
+
+    tracing::info!("Initializing read-only Merkle tree replica for tree API");
+    let config = MetadataCalculatorConfig::for_main_node(&db_config.merkle_tree, &operation_config);
+    let replica = MerkleTreeReplica::new(
+        config,
+        replica_path.into(),
+        api_config.replica_poll_interval(),
+    )
+    .await
+    .context("failed initializing Merkle tree replica")?;
+    app_health.insert_component(replica.health_check())?;
+
+    let address = (Ipv4Addr::UNSPECIFIED, api_config.port).into();
+    let tree_reader = replica.tree_reader();
+    task_futures.push(tokio::spawn(
+        tree_reader.run_api_server(address, stop_receiver.clone()),
+    ));
+    task_futures.push(tokio::spawn(replica.run(stop_receiver)));
+    Ok(())
+}
+
 async fn add_tee_verifier_input_producer_to_task_futures(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
     connection_pool: &ConnectionPool<Core>,
@@ -1079,6 +1677,8 @@ async fn add_house_keeper_to_task_futures(
     let secrets = secrets.database.clone().context("database_secrets")?;
     let connection_pool =
         ConnectionPool::<Core>::builder(secrets.replica_url()?, postgres_config.max_connections()?)
+            .set_replica_urls(secrets.server_read_replica_urls.clone())
+            .set_max_replica_lag(postgres_config.max_replica_lag())
             .build()
             .await
             .context("failed to build a connection pool")?;
@@ -1112,6 +1712,20 @@ async fn add_house_keeper_to_task_futures(
     let task = l1_batch_metrics_reporter.run(stop_receiver.clone());
     task_futures.push(tokio::spawn(task));
 
+    if let Some((partitioning_interval, partition_size)) =
+        house_keeper_config.table_partitioning_params()
+    {
+        // Partitions are managed using DDL statements, which cannot be executed on a replica.
+        let master_pool = ConnectionPool::<Core>::singleton(secrets.master_url()?)
+            .build()
+            .await
+            .context("failed to build a master_pool")?;
+        let table_partition_manager =
+            TablePartitionManager::new(master_pool, partitioning_interval, partition_size);
+        let task = table_partition_manager.run(stop_receiver.clone());
+        task_futures.push(tokio::spawn(task));
+    }
+
     // All FRI Prover related components are configured below.
     let fri_prover_config = configs.prover_config.clone().context("fri_prover_config")?;
     let fri_prover_job_retry_manager = FriProverJobRetryManager::new(
@@ -1235,6 +1849,7 @@ async fn run_http_api(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
     app_health: &AppHealthCheck,
     database_secrets: &DatabaseSecrets,
+    api_secrets: Option<&ApiSecrets>,
     tx_sender_config: &TxSenderConfig,
     state_keeper_config: &StateKeeperConfig,
     internal_api: &InternalApiConfig,
@@ -1246,23 +1861,29 @@ async fn run_http_api(
     with_debug_namespace: bool,
     storage_caches: PostgresStorageCaches,
     mempool_cache: MempoolCache,
-) -> anyhow::Result<()> {
+    address_denylist: Option<AddressDenylist>,
+    load_shedding: Option<watch::Receiver<bool>>,
+) -> anyhow::Result<TxSender> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
         &api_config.web3_json_rpc,
         state_keeper_config,
         replica_connection_pool.clone(),
-        master_connection_pool,
+        master_connection_pool.clone(),
         batch_fee_model_input_provider,
         storage_caches,
+        address_denylist,
     )
     .await;
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
     if with_debug_namespace {
-        namespaces.push(Namespace::Debug)
+        namespaces.extend([Namespace::Debug, Namespace::Trace]);
     }
     namespaces.push(Namespace::Snapshots);
+    if api_config.web3_json_rpc.txpool_namespace_enabled {
+        namespaces.push(Namespace::Txpool);
+    }
 
     let updaters_pool = ConnectionPool::<Core>::builder(database_secrets.replica_url()?, 2)
         .build()
@@ -1273,10 +1894,14 @@ async fn run_http_api(
         web3::ApiBuilder::jsonrpsee_backend(internal_api.clone(), replica_connection_pool)
             .http(api_config.web3_json_rpc.http_port)
             .with_updaters_pool(updaters_pool)
+            .with_filters_pool(master_connection_pool)
             .with_filter_limit(api_config.web3_json_rpc.filters_limit())
             .with_batch_request_size_limit(api_config.web3_json_rpc.max_batch_request_size())
             .with_response_body_size_limit(api_config.web3_json_rpc.max_response_body_size())
-            .with_tx_sender(tx_sender)
+            .with_client_rate_limits(api_config.web3_json_rpc.client_rate_limits(api_secrets))
+            .with_method_filter(api_config.web3_json_rpc.http_method_filter())
+            .with_http_response_compression(api_config.web3_json_rpc.http_response_compression)
+            .with_tx_sender(tx_sender.clone())
             .with_vm_barrier(vm_barrier)
             .with_mempool_cache(mempool_cache)
             .enable_api_namespaces(namespaces);
@@ -1285,6 +1910,9 @@ async fn run_http_api(
         api_builder = api_builder.with_tree_api(tree_api.clone());
         app_health.insert_custom_component(tree_api)?;
     }
+    if let Some(signal) = load_shedding {
+        api_builder = api_builder.with_load_shedding(signal);
+    }
 
     let server_handles = api_builder
         .build()
@@ -1293,6 +1921,56 @@ async fn run_http_api(
         .await?;
     task_futures.extend(server_handles.tasks);
     app_health.insert_component(server_handles.health_check)?;
+    Ok(tx_sender)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_admin_api(
+    task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    app_health: &AppHealthCheck,
+    port: u16,
+    secrets: &Secrets,
+    configs: &GeneralConfig,
+    contracts_config: &ContractsConfig,
+    genesis_config: &GenesisConfig,
+    replica_connection_pool: ConnectionPool<Core>,
+    tx_sender: TxSender,
+    l1_batch_abort_handle: Option<L1BatchAbortHandle>,
+    fee_account_handle: Option<FeeAccountHandle>,
+    eth_sender_pause_handle: Option<EthSenderPauseHandle>,
+    stop_receiver: watch::Receiver<bool>,
+) -> anyhow::Result<()> {
+    let api_config = configs.api_config.as_ref().context("api_config")?;
+    let admin_token = secrets
+        .api
+        .clone()
+        .context("api_secrets")?
+        .admin_token
+        .context("admin token must be set if the admin API server is enabled")?;
+    let internal_api =
+        InternalApiConfig::new(&api_config.web3_json_rpc, contracts_config, genesis_config);
+
+    let mut api_builder =
+        web3::ApiBuilder::jsonrpsee_backend(internal_api, replica_connection_pool)
+            .admin_http(port, admin_token)
+            .with_tx_sender(tx_sender);
+    if let Some(handle) = l1_batch_abort_handle {
+        api_builder = api_builder.with_l1_batch_abort_handle(handle);
+    }
+    if let Some(handle) = fee_account_handle {
+        api_builder = api_builder.with_fee_account_handle(handle);
+    }
+    if let Some(handle) = eth_sender_pause_handle {
+        api_builder = api_builder.with_eth_sender_pause_handle(handle);
+    }
+
+    let server_handles = api_builder
+        .build()
+        .context("failed to build admin API server")?
+        .run(stop_receiver)
+        .await?;
+    task_futures.extend(server_handles.tasks);
+    app_health.insert_component(server_handles.health_check)?;
     Ok(())
 }
 
@@ -1301,6 +1979,7 @@ async fn run_ws_api(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
     app_health: &AppHealthCheck,
     database_secrets: &DatabaseSecrets,
+    api_secrets: Option<&ApiSecrets>,
     tx_sender_config: &TxSenderConfig,
     state_keeper_config: &StateKeeperConfig,
     internal_api: &InternalApiConfig,
@@ -1311,33 +1990,47 @@ async fn run_ws_api(
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
     mempool_cache: MempoolCache,
+    address_denylist: Option<AddressDenylist>,
+    load_shedding: Option<watch::Receiver<bool>>,
 ) -> anyhow::Result<()> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
         &api_config.web3_json_rpc,
         state_keeper_config,
         replica_connection_pool.clone(),
-        master_connection_pool,
+        master_connection_pool.clone(),
         batch_fee_model_input_provider,
         storage_caches,
+        address_denylist,
     )
     .await;
     let updaters_pool = ConnectionPool::<Core>::singleton(database_secrets.replica_url()?)
         .build()
         .await
         .context("failed to build updaters_pool")?;
+    let l2_block_listener_pool = ConnectionPool::<Core>::singleton(database_secrets.master_url()?)
+        .build()
+        .await
+        .context("failed to build l2_block_listener_pool")?;
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
     namespaces.push(Namespace::Snapshots);
+    if api_config.web3_json_rpc.txpool_namespace_enabled {
+        namespaces.push(Namespace::Txpool);
+    }
 
     let mut api_builder =
         web3::ApiBuilder::jsonrpsee_backend(internal_api.clone(), replica_connection_pool)
             .ws(api_config.web3_json_rpc.ws_port)
             .with_updaters_pool(updaters_pool)
+            .with_l2_block_listener_pool(l2_block_listener_pool)
+            .with_filters_pool(master_connection_pool)
             .with_filter_limit(api_config.web3_json_rpc.filters_limit())
             .with_subscriptions_limit(api_config.web3_json_rpc.subscriptions_limit())
             .with_batch_request_size_limit(api_config.web3_json_rpc.max_batch_request_size())
             .with_response_body_size_limit(api_config.web3_json_rpc.max_response_body_size())
+            .with_client_rate_limits(api_config.web3_json_rpc.client_rate_limits(api_secrets))
+            .with_method_filter(api_config.web3_json_rpc.ws_method_filter())
             .with_websocket_requests_per_minute_limit(
                 api_config
                     .web3_json_rpc
@@ -1353,6 +2046,9 @@ async fn run_ws_api(
         api_builder = api_builder.with_tree_api(tree_api.clone());
         app_health.insert_custom_component(tree_api)?;
     }
+    if let Some(signal) = load_shedding {
+        api_builder = api_builder.with_load_shedding(signal);
+    }
 
     let server_handles = api_builder
         .build()
@@ -1368,7 +2064,8 @@ async fn circuit_breakers_for_components(
     components: &[Component],
     database_secrets: &DatabaseSecrets,
     circuit_breaker_config: &CircuitBreakerConfig,
-) -> anyhow::Result<CircuitBreakers> {
+    api_pool: &ConnectionPool<Core>,
+) -> anyhow::Result<(CircuitBreakers, Option<watch::Receiver<bool>>)> {
     let circuit_breakers = CircuitBreakers::default();
 
     if components
@@ -1400,5 +2097,36 @@ async fn circuit_breakers_for_components(
             }))
             .await;
     }
-    Ok(circuit_breakers)
+
+    if let Some(chunk_size) = circuit_breaker_config.db_integrity_check_chunk_size {
+        let pool = ConnectionPool::<Core>::singleton(database_secrets.replica_url()?)
+            .build()
+            .await
+            .context("failed to build a connection pool")?;
+        circuit_breakers
+            .insert(Box::new(DbIntegrityChecker::new(pool, chunk_size)))
+            .await;
+    }
+
+    // The saturation check only makes sense if the pool is used by API servers.
+    let mut load_shedding = None;
+    if let Some(utilization_percent) = circuit_breaker_config.pool_saturation_shedding_percent {
+        if components
+            .iter()
+            .any(|c| matches!(c, Component::HttpApi | Component::WsApi))
+        {
+            let checker = PoolSaturationChecker::new(
+                api_pool.clone(),
+                PoolSaturationThresholds {
+                    utilization_percent,
+                    acquire_latency: circuit_breaker_config
+                        .pool_acquire_latency_shedding_threshold(),
+                    trip_after: circuit_breaker_config.pool_saturation_trip_after(),
+                },
+            );
+            load_shedding = Some(checker.load_shedding_signal());
+            circuit_breakers.insert(Box::new(checker)).await;
+        }
+    }
+    Ok((circuit_breakers, load_shedding))
 }
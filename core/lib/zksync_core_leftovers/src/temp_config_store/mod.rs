@@ -8,9 +8,11 @@ use zksync_config::{
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
         wallets::{AddressWallet, EthSender, StateKeeper, Wallet, Wallets},
-        FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
+        BaseTokenAdjusterConfig, BlockNotificationsConfig, ChangeFeedConfig, DAClientConfig,
+        DADispatcherConfig, FriProofCompressorConfig, FriProverConfig, FriProverGatewayConfig,
         FriWitnessGeneratorConfig, FriWitnessVectorGeneratorConfig, GeneralConfig,
         ObservabilityConfig, PrometheusConfig, ProofDataHandlerConfig, ProtectiveReadsWriterConfig,
+        PruningConfig, ShadowExecutionConfig,
     },
     ApiConfig, ContractVerifierConfig, DBConfig, EthConfig, EthWatchConfig, GasAdjusterConfig,
     ObjectStoreConfig, PostgresConfig, SnapshotsCreatorConfig,
@@ -62,6 +64,13 @@ pub struct TempConfigStore {
     pub observability: Option<ObservabilityConfig>,
     pub snapshot_creator: Option<SnapshotsCreatorConfig>,
     pub protective_reads_writer_config: Option<ProtectiveReadsWriterConfig>,
+    pub shadow_execution_config: Option<ShadowExecutionConfig>,
+    pub pruning: Option<PruningConfig>,
+    pub block_notifications: Option<BlockNotificationsConfig>,
+    pub change_feed: Option<ChangeFeedConfig>,
+    pub da_dispatcher_config: Option<DADispatcherConfig>,
+    pub da_client_config: Option<DAClientConfig>,
+    pub base_token_adjuster: Option<BaseTokenAdjusterConfig>,
 }
 
 impl TempConfigStore {
@@ -88,6 +97,13 @@ impl TempConfigStore {
             snapshot_creator: self.snapshot_creator.clone(),
             observability: self.observability.clone(),
             protective_reads_writer_config: self.protective_reads_writer_config.clone(),
+            shadow_execution_config: self.shadow_execution_config.clone(),
+            pruning: self.pruning.clone(),
+            block_notifications: self.block_notifications.clone(),
+            change_feed: self.change_feed.clone(),
+            da_dispatcher_config: self.da_dispatcher_config.clone(),
+            da_client_config: self.da_client_config.clone(),
+            base_token_adjuster: self.base_token_adjuster.clone(),
         }
     }
 
@@ -103,6 +119,8 @@ impl TempConfigStore {
             Some(EthSender {
                 operator,
                 blob_operator,
+                successor_operator: None,
+                successor_blob_operator: None,
             })
         });
         let state_keeper = self
@@ -34,11 +34,14 @@ use zksync_types::{
 use zksync_utils::{h256_to_u256, time::seconds_since_epoch, u256_to_h256};
 
 use super::{
+    storage::{StorageOverrides, StorageWithOverrides},
     vm_metrics::{self, SandboxStage, SANDBOX_METRICS},
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
 
-type BoxedVm<'a> = Box<VmInstance<StorageView<PostgresStorage<'a>>, HistoryDisabled>>;
+/// Storage used by the sandboxed VM.
+pub(super) type SandboxStorage<'a> = StorageView<StorageWithOverrides<PostgresStorage<'a>>>;
+type BoxedVm<'a> = Box<VmInstance<SandboxStorage<'a>, HistoryDisabled>>;
 
 #[derive(Debug)]
 struct Sandbox<'a> {
@@ -46,7 +49,7 @@ struct Sandbox<'a> {
     l1_batch_env: L1BatchEnv,
     execution_args: &'a TxExecutionArgs,
     l2_block_info_to_reset: Option<StoredL2BlockInfo>,
-    storage_view: StorageView<PostgresStorage<'a>>,
+    storage_view: SandboxStorage<'a>,
 }
 
 impl<'a> Sandbox<'a> {
@@ -55,6 +58,7 @@ impl<'a> Sandbox<'a> {
         shared_args: TxSharedArgs,
         execution_args: &'a TxExecutionArgs,
         block_args: BlockArgs,
+        storage_overrides: StorageOverrides,
     ) -> anyhow::Result<Sandbox<'a>> {
         let resolve_started_at = Instant::now();
         let resolved_block_info = block_args
@@ -90,6 +94,7 @@ impl<'a> Sandbox<'a> {
         .context("cannot create `PostgresStorage`")?
         .with_caches(shared_args.caches.clone());
 
+        let storage = StorageWithOverrides::new(storage, storage_overrides);
         let storage_view = StorageView::new(storage);
         let (system_env, l1_batch_env) = Self::prepare_env(
             shared_args,
@@ -217,7 +222,7 @@ impl<'a> Sandbox<'a> {
         shared_args: TxSharedArgs,
         execution_args: &TxExecutionArgs,
         resolved_block_info: &ResolvedBlockInfo,
-        next_l2_block_info: L2BlockEnv,
+        mut next_l2_block_info: L2BlockEnv,
     ) -> (SystemEnv, L1BatchEnv) {
         let TxSharedArgs {
             operator_account,
@@ -228,6 +233,12 @@ impl<'a> Sandbox<'a> {
             ..
         } = shared_args;
 
+        let mut l1_batch_timestamp = resolved_block_info.l1_batch_timestamp;
+        if let Some(timestamp) = execution_args.enforced_timestamp {
+            next_l2_block_info.timestamp = timestamp;
+            l1_batch_timestamp = timestamp;
+        }
+
         // In case we are executing in a past block, we'll use the historical fee data.
         let fee_input = resolved_block_info
             .historical_fee_input
@@ -241,11 +252,15 @@ impl<'a> Sandbox<'a> {
             execution_mode: execution_args.execution_mode,
             default_validation_computational_gas_limit: validation_computational_gas_limit,
             chain_id,
+            enable_opcode_profiling: false,
+            enable_call_tracing: false,
+            enable_gas_attribution: false,
+            enable_storage_reads_log: false,
         };
         let l1_batch_env = L1BatchEnv {
             previous_batch_hash: None,
             number: resolved_block_info.vm_l1_batch_number,
-            timestamp: resolved_block_info.l1_batch_timestamp,
+            timestamp: l1_batch_timestamp,
             fee_input,
             fee_account: *operator_account.address(),
             enforced_base_fee: execution_args.enforced_base_fee,
@@ -259,7 +274,7 @@ impl<'a> Sandbox<'a> {
         mut self,
         tx: &Transaction,
         adjust_pubdata_price: bool,
-    ) -> (BoxedVm<'a>, StoragePtr<StorageView<PostgresStorage<'a>>>) {
+    ) -> (BoxedVm<'a>, StoragePtr<SandboxStorage<'a>>, L1BatchEnv) {
         self.setup_storage_view(tx);
         let protocol_version = self.system_env.version;
         if adjust_pubdata_price {
@@ -273,13 +288,13 @@ impl<'a> Sandbox<'a> {
 
         let storage_view = self.storage_view.to_rc_ptr();
         let vm = Box::new(VmInstance::new_with_specific_version(
-            self.l1_batch_env,
+            self.l1_batch_env.clone(),
             self.system_env,
             storage_view.clone(),
             protocol_version.into_api_vm_version(),
         ));
 
-        (vm, storage_view)
+        (vm, storage_view, self.l1_batch_env)
     }
 }
 
@@ -296,7 +311,40 @@ pub(super) fn apply_vm_in_sandbox<T>(
     tx: Transaction,
     block_args: BlockArgs,
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<SandboxStorage<'_>, HistoryDisabled>,
+        Transaction,
+        ProtocolVersionId,
+    ) -> T,
+) -> anyhow::Result<T> {
+    apply_vm_with_storage_in_sandbox(
+        vm_permit,
+        shared_args,
+        adjust_pubdata_price,
+        execution_args,
+        connection_pool,
+        tx,
+        block_args,
+        StorageOverrides::default(),
+        |vm, _, _, tx, protocol_version| apply(vm, tx, protocol_version),
+    )
+}
+
+/// Same as [`apply_vm_in_sandbox()`], but additionally provides the VM storage and the L1 batch environment
+/// to the `apply` closure, and allows to specify storage overrides.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_vm_with_storage_in_sandbox<T>(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    adjust_pubdata_price: bool,
+    execution_args: &TxExecutionArgs,
+    connection_pool: &ConnectionPool<Core>,
+    tx: Transaction,
+    block_args: BlockArgs,
+    storage_overrides: StorageOverrides,
+    apply: impl FnOnce(
+        &mut VmInstance<SandboxStorage<'_>, HistoryDisabled>,
+        &StoragePtr<SandboxStorage<'_>>,
+        &L1BatchEnv,
         Transaction,
         ProtocolVersionId,
     ) -> T,
@@ -319,9 +367,10 @@ pub(super) fn apply_vm_in_sandbox<T>(
         shared_args,
         execution_args,
         block_args,
+        storage_overrides,
     ))?;
     let protocol_version = sandbox.system_env.version;
-    let (mut vm, storage_view) = sandbox.into_vm(&tx, adjust_pubdata_price);
+    let (mut vm, storage_view, l1_batch_env) = sandbox.into_vm(&tx, adjust_pubdata_price);
 
     SANDBOX_METRICS.sandbox[&SandboxStage::Initialization].observe(stage_started_at.elapsed());
     span.exit();
@@ -332,7 +381,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
         tx.nonce().unwrap_or(Nonce(0))
     );
     let execution_latency = SANDBOX_METRICS.sandbox[&SandboxStage::Execution].start();
-    let result = apply(&mut vm, tx, protocol_version);
+    let result = apply(&mut vm, &storage_view, &l1_batch_env, tx, protocol_version);
     let vm_execution_took = execution_latency.observe();
 
     let memory_metrics = vm.record_vm_memory_metrics();
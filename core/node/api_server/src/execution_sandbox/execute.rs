@@ -2,20 +2,29 @@
 
 use anyhow::Context as _;
 use multivm::{
-    interface::{TxExecutionMode, VmExecutionResultAndLogs, VmInterface},
+    interface::{
+        ExecutionResult, L2BlockEnv, TxExecutionMode, VmExecutionResultAndLogs, VmInterface,
+    },
     tracers::StorageInvocations,
+    utils::get_batch_base_fee,
     MultiVMTracer,
 };
 use tracing::{span, Level};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, transaction_request::CallOverrides,
-    ExecuteTransactionCommon, Nonce, PackedEthSignature, Transaction, U256,
+    api::StateOverride,
+    block::L2BlockHasher,
+    fee::TransactionExecutionMetrics,
+    l2::L2Tx,
+    transaction_request::{CallOverrides, TransactionRequest},
+    ExecuteTransactionCommon, L2BlockNumber, Nonce, PackedEthSignature, Transaction, H256, U256,
 };
 
 use super::{
-    apply, testonly::MockTransactionExecutor, vm_metrics, ApiTracer, BlockArgs, TxSharedArgs,
-    VmPermit,
+    apply,
+    storage::{apply_state_override, StorageOverrides},
+    testonly::MockTransactionExecutor,
+    vm_metrics, ApiTracer, BlockArgs, TxSharedArgs, VmPermit,
 };
 
 #[derive(Debug)]
@@ -24,6 +33,8 @@ pub(crate) struct TxExecutionArgs {
     pub enforced_nonce: Option<Nonce>,
     pub added_balance: U256,
     pub enforced_base_fee: Option<u64>,
+    /// Timestamp of the L1 batch and its first L2 block; overrides the timestamp derived from the executed block.
+    pub enforced_timestamp: Option<u64>,
     pub missed_storage_invocation_limit: usize,
 }
 
@@ -34,6 +45,7 @@ impl TxExecutionArgs {
             enforced_nonce: Some(tx.nonce()),
             added_balance: U256::zero(),
             enforced_base_fee: Some(tx.common_data.fee.max_fee_per_gas.as_u64()),
+            enforced_timestamp: None,
             missed_storage_invocation_limit: usize::MAX,
         }
     }
@@ -48,6 +60,7 @@ impl TxExecutionArgs {
             enforced_nonce: None,
             added_balance: U256::zero(),
             enforced_base_fee,
+            enforced_timestamp: None,
             missed_storage_invocation_limit,
         }
     }
@@ -72,6 +85,7 @@ impl TxExecutionArgs {
             enforced_nonce: tx.nonce(),
             added_balance,
             enforced_base_fee: Some(base_fee),
+            enforced_timestamp: None,
         }
     }
 }
@@ -86,6 +100,29 @@ pub(crate) struct TransactionExecutionOutput {
     pub are_published_bytecodes_ok: bool,
 }
 
+/// Block of calls executed by [`TransactionExecutor::simulate_blocks()`].
+#[derive(Debug)]
+pub(crate) struct SimulationBlock {
+    /// Block timestamp. If not specified, the timestamp of the previous block + 1 is used; for the first block,
+    /// the timestamp is chosen in the same way as for `eth_call`. Timestamps not exceeding the previous block
+    /// timestamp are replaced with the minimum allowed value.
+    pub timestamp: Option<u64>,
+    /// State override applied before executing the block.
+    pub state_override: Option<StateOverride>,
+    pub txs: Vec<L2Tx>,
+}
+
+/// Output of a block executed by [`TransactionExecutor::simulate_blocks()`].
+#[derive(Debug)]
+pub(crate) struct SimulatedBlockOutput {
+    pub env: L2BlockEnv,
+    pub hash: H256,
+    pub base_fee: u64,
+    /// Execution results for each call in the block. If a call is halted, simulation stops;
+    /// the halted call is the last one in the output.
+    pub results: Vec<VmExecutionResultAndLogs>,
+}
+
 /// Executor of transactions.
 #[derive(Debug)]
 pub(crate) enum TransactionExecutor {
@@ -198,4 +235,126 @@ impl TransactionExecutor {
             .await?;
         Ok(output.vm)
     }
+    /// Sequentially executes blocks of calls in a single VM instance, so that each call observes changes
+    /// made by the previous calls. Calls are executed with the `eth_call` semantics.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn simulate_blocks(
+        &self,
+        vm_permit: VmPermit,
+        shared_args: TxSharedArgs,
+        connection_pool: ConnectionPool<Core>,
+        enforced_base_fee: Option<u64>,
+        mut blocks: Vec<SimulationBlock>,
+        block_args: BlockArgs,
+        vm_execution_cache_misses_limit: Option<usize>,
+    ) -> anyhow::Result<Vec<SimulatedBlockOutput>> {
+        for tx in blocks.iter_mut().flat_map(|block| &mut block.txs) {
+            if tx.common_data.signature.is_empty() {
+                tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+            }
+        }
+        if let Self::Mock(mock_executor) = self {
+            return mock_executor.simulate_blocks(blocks, &block_args);
+        }
+
+        let first_tx = blocks
+            .iter()
+            .find_map(|block| block.txs.first())
+            .context("no calls to simulate")?
+            .clone();
+        let mut execution_args =
+            TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
+        execution_args.enforced_timestamp = blocks.first().and_then(|block| block.timestamp);
+        let storage_overrides = StorageOverrides::new(
+            blocks
+                .iter()
+                .filter_map(|block| block.state_override.as_ref()),
+        );
+        let chain_id = shared_args.chain_id;
+
+        tokio::task::spawn_blocking(move || {
+            let span = span!(Level::DEBUG, "simulate_in_sandbox").entered();
+            let result = apply::apply_vm_with_storage_in_sandbox(
+                vm_permit,
+                shared_args,
+                false,
+                &execution_args,
+                &connection_pool,
+                first_tx.into(),
+                block_args,
+                storage_overrides,
+                |vm, storage, l1_batch_env, _, protocol_version| {
+                    let base_fee = get_batch_base_fee(l1_batch_env, protocol_version.into());
+                    let mut block_env = l1_batch_env.first_l2_block;
+                    let mut outputs = Vec::<SimulatedBlockOutput>::with_capacity(blocks.len());
+                    for block in blocks {
+                        if let Some(prev_output) = outputs.last() {
+                            // The VM requires block timestamps to strictly increase; the caller is responsible
+                            // for checking that the requested timestamps were used.
+                            let min_timestamp = prev_output.env.timestamp + 1;
+                            block_env = L2BlockEnv {
+                                number: prev_output.env.number + 1,
+                                timestamp: block
+                                    .timestamp
+                                    .map_or(min_timestamp, |ts| ts.max(min_timestamp)),
+                                prev_block_hash: prev_output.hash,
+                                max_virtual_blocks_to_create: 1,
+                            };
+                            vm.start_new_l2_block(block_env);
+                        }
+                        if let Some(state_override) = &block.state_override {
+                            apply_state_override(&mut *storage.borrow_mut(), state_override);
+                        }
+
+                        let mut hasher = L2BlockHasher::new(
+                            L2BlockNumber(block_env.number),
+                            block_env.timestamp,
+                            block_env.prev_block_hash,
+                        );
+                        let mut results = Vec::with_capacity(block.txs.len());
+                        let mut is_halted = false;
+                        for tx in block.txs {
+                            // The hash must match the one computed by the bootloader, so that the next L2 block
+                            // passes the previous block hash check.
+                            let mut tx_request = TransactionRequest::from(tx.clone());
+                            tx_request.chain_id = Some(chain_id.as_u64());
+                            let tx_hash = tx_request
+                                .get_tx_hash()
+                                .context("cannot compute transaction hash")?;
+                            hasher.push_tx_hash(tx_hash);
+
+                            let storage_invocation_tracer = StorageInvocations::new(
+                                execution_args.missed_storage_invocation_limit,
+                            );
+                            let (_, result) = vm.inspect_transaction_with_bytecode_compression(
+                                vec![storage_invocation_tracer.into_tracer_pointer()].into(),
+                                tx.into(),
+                                true,
+                            );
+                            is_halted = matches!(result.result, ExecutionResult::Halt { .. });
+                            results.push(result);
+                            if is_halted {
+                                break;
+                            }
+                        }
+
+                        outputs.push(SimulatedBlockOutput {
+                            env: block_env,
+                            hash: hasher.finalize(protocol_version),
+                            base_fee,
+                            results,
+                        });
+                        if is_halted {
+                            break;
+                        }
+                    }
+                    anyhow::Ok(outputs)
+                },
+            );
+            span.exit();
+            result
+        })
+        .await
+        .context("simulation panicked")??
+    }
 }
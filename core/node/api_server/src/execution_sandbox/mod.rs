@@ -15,7 +15,7 @@ use zksync_types::{
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{TransactionExecutor, TxExecutionArgs},
+    execute::{SimulatedBlockOutput, SimulationBlock, TransactionExecutor, TxExecutionArgs},
     tracers::ApiTracer,
     validate::ValidationError,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
@@ -26,6 +26,7 @@ use super::tx_sender::MultiVMBaseSystemContracts;
 mod apply;
 mod error;
 mod execute;
+mod storage;
 pub mod testonly;
 #[cfg(test)]
 mod tests;
@@ -264,7 +265,8 @@ impl BlockStartInfo {
         })
     }
 
-    async fn get_pruning_info(
+    /// Returns (potentially cached) information about pruning of the node storage.
+    pub async fn get_pruning_info(
         &self,
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<PruningInfo> {
@@ -283,11 +285,7 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L2BlockNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        let last_block = cached_pruning_info.last_soft_pruned_l2_block;
-        if let Some(L2BlockNumber(last_block)) = last_block {
-            return Ok(L2BlockNumber(last_block + 1));
-        }
-        Ok(L2BlockNumber(0))
+        Ok(cached_pruning_info.first_retained_l2_block())
     }
 
     pub async fn first_l1_batch(
@@ -295,11 +293,7 @@ impl BlockStartInfo {
         storage: &mut Connection<'_, Core>,
     ) -> anyhow::Result<L1BatchNumber> {
         let cached_pruning_info = self.get_pruning_info(storage).await?;
-        let last_batch = cached_pruning_info.last_soft_pruned_l1_batch;
-        if let Some(L1BatchNumber(last_block)) = last_batch {
-            return Ok(L1BatchNumber(last_block + 1));
-        }
-        Ok(L1BatchNumber(0))
+        Ok(cached_pruning_info.first_retained_l1_batch())
     }
 
     /// Checks whether a block with the specified ID is pruned and returns an error if it is.
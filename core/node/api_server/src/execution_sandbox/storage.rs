@@ -0,0 +1,121 @@
+//! VM storage with state overrides used when simulating calls.
+
+use std::collections::{HashMap, HashSet};
+
+use zksync_state::{ReadStorage, WriteStorage};
+use zksync_types::{
+    api::{OverrideAccount, StateOverride},
+    get_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, Address, StorageKey, StorageValue, H256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
+
+/// Overrides that must be known to the storage before the VM is instantiated.
+#[derive(Debug, Default)]
+pub(crate) struct StorageOverrides {
+    factory_deps: HashMap<H256, Vec<u8>>,
+    empty_accounts: HashSet<AccountTreeId>,
+}
+
+impl StorageOverrides {
+    /// Collects overrides from a sequence of state overrides. Bytecodes from all overrides are made available
+    /// from the start; this is safe since a bytecode is only loaded once it's referenced by a code hash slot.
+    /// Full storage replacements are only taken from the first override, since afterwards the storage
+    /// may be cached by the VM.
+    pub fn new<'a>(state_overrides: impl IntoIterator<Item = &'a StateOverride>) -> Self {
+        let mut this = Self::default();
+        for (i, state_override) in state_overrides.into_iter().enumerate() {
+            for (address, account) in state_override {
+                if let Some(code) = &account.code {
+                    this.factory_deps
+                        .insert(hash_bytecode(&code.0), code.0.clone());
+                }
+                if i == 0 && account.state.is_some() {
+                    this.empty_accounts.insert(AccountTreeId::new(*address));
+                }
+            }
+        }
+        this
+    }
+}
+
+/// Storage wrapper providing overridden bytecodes and cleared storage for accounts with fully replaced state.
+///
+/// Individual slots are not overridden by this wrapper; instead, they are written to the storage view
+/// wrapping it using [`apply_state_override()`], so that the overrides can be applied in the middle of execution.
+#[derive(Debug)]
+pub(crate) struct StorageWithOverrides<S> {
+    storage_handle: S,
+    overrides: StorageOverrides,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    pub fn new(storage_handle: S, overrides: StorageOverrides) -> Self {
+        Self {
+            storage_handle,
+            overrides,
+        }
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if self.overrides.empty_accounts.contains(key.account()) {
+            return StorageValue::zero();
+        }
+        self.storage_handle.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.storage_handle.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        if let Some(bytecode) = self.overrides.factory_deps.get(&hash) {
+            return Some(bytecode.clone());
+        }
+        self.storage_handle.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.storage_handle.get_enumeration_index(key)
+    }
+}
+
+/// Applies a state override to the VM storage. Bytecodes and full storage replacements must be provided
+/// to [`StorageWithOverrides`] beforehand (see [`StorageOverrides::new()`]).
+pub(crate) fn apply_state_override(
+    storage: &mut impl WriteStorage,
+    state_override: &StateOverride,
+) {
+    for (address, account) in state_override {
+        apply_account_override(storage, address, account);
+    }
+}
+
+fn apply_account_override(
+    storage: &mut impl WriteStorage,
+    address: &Address,
+    account: &OverrideAccount,
+) {
+    if let Some(balance) = account.balance {
+        storage.set_value(storage_key_for_eth_balance(address), u256_to_h256(balance));
+    }
+    if let Some(nonce) = account.nonce {
+        let nonce_key = get_nonce_key(address);
+        let full_nonce = storage.read_value(&nonce_key);
+        let (_, deployment_nonce) = decompose_full_nonce(h256_to_u256(full_nonce));
+        let new_full_nonce = nonces_to_full_nonce(nonce, deployment_nonce);
+        storage.set_value(nonce_key, u256_to_h256(new_full_nonce));
+    }
+    if let Some(code) = &account.code {
+        storage.set_value(get_code_key(address), hash_bytecode(&code.0));
+    }
+
+    let account_id = AccountTreeId::new(*address);
+    let slots = account.state.as_ref().or(account.state_diff.as_ref());
+    for (slot, value) in slots.into_iter().flatten() {
+        storage.set_value(StorageKey::new(account_id, *slot), *value);
+    }
+}
@@ -1,12 +1,15 @@
 use std::fmt;
 
-use multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
+use multivm::interface::{ExecutionResult, L2BlockEnv, VmExecutionResultAndLogs};
 use zksync_types::{
-    fee::TransactionExecutionMetrics, l2::L2Tx, ExecuteTransactionCommon, Transaction,
+    block::L2BlockHasher, fee::TransactionExecutionMetrics, l2::L2Tx, ExecuteTransactionCommon,
+    L2BlockNumber, ProtocolVersionId, Transaction, H256,
 };
 
 use super::{
-    execute::{TransactionExecutionOutput, TransactionExecutor},
+    execute::{
+        SimulatedBlockOutput, SimulationBlock, TransactionExecutionOutput, TransactionExecutor,
+    },
     validate::ValidationError,
     BlockArgs,
 };
@@ -71,6 +74,8 @@ impl MockTransactionExecutor {
                     logs: Default::default(),
                     statistics: Default::default(),
                     refunds: Default::default(),
+                    call_traces: None,
+                    gas_by_contract: None,
                 }
             },
         )
@@ -113,6 +118,42 @@ impl MockTransactionExecutor {
         Ok(output)
     }
 
+    /// Simulates blocks by executing each call using [call responses](Self::set_call_responses()).
+    /// Block environments start from the block number of `block_args`; the base fee is always 0.
+    pub(crate) fn simulate_blocks(
+        &self,
+        blocks: Vec<SimulationBlock>,
+        block_args: &BlockArgs,
+    ) -> anyhow::Result<Vec<SimulatedBlockOutput>> {
+        let mut outputs = Vec::<SimulatedBlockOutput>::with_capacity(blocks.len());
+        for block in blocks {
+            let (number, prev_timestamp, prev_block_hash) = match outputs.last() {
+                Some(prev) => (prev.env.number + 1, prev.env.timestamp, prev.hash),
+                None => (block_args.resolved_block_number().0, 0, H256::zero()),
+            };
+            let env = L2BlockEnv {
+                number,
+                timestamp: block.timestamp.unwrap_or(prev_timestamp + 1),
+                prev_block_hash,
+                max_virtual_blocks_to_create: 1,
+            };
+            let hash = L2BlockHasher::new(L2BlockNumber(number), env.timestamp, prev_block_hash)
+                .finalize(ProtocolVersionId::latest());
+            let results = block
+                .txs
+                .into_iter()
+                .map(|tx| self.get_execution_result(&tx.into(), block_args))
+                .collect();
+            outputs.push(SimulatedBlockOutput {
+                env,
+                hash,
+                base_fee: 0,
+                results,
+            });
+        }
+        Ok(outputs)
+    }
+
     fn get_execution_result(
         &self,
         tx: &Transaction,
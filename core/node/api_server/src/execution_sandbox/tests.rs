@@ -1,12 +1,28 @@
 //! Tests for the VM execution sandbox.
 
+use std::collections::HashMap;
+
 use assert_matches::assert_matches;
 use zksync_dal::ConnectionPool;
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l2_block, create_l2_transaction, prepare_recovery_snapshot};
+use zksync_state::{InMemoryStorage, ReadStorage, StorageView};
+use zksync_types::{
+    api::{OverrideAccount, StateOverride},
+    get_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    StorageKey, H256,
+};
+use zksync_utils::{bytecode::hash_bytecode, h256_to_u256, u256_to_h256};
 
 use super::*;
-use crate::{execution_sandbox::apply::apply_vm_in_sandbox, tx_sender::ApiContracts};
+use crate::{
+    execution_sandbox::{
+        apply::apply_vm_in_sandbox,
+        storage::{apply_state_override, StorageOverrides, StorageWithOverrides},
+    },
+    tx_sender::ApiContracts,
+};
 
 #[tokio::test]
 async fn creating_block_args() {
@@ -204,3 +220,42 @@ async fn test_instantiating_vm(pool: ConnectionPool<Core>, block_args: BlockArgs
     .expect("VM instantiation panicked")
     .expect("VM instantiation errored");
 }
+
+#[test]
+fn applying_state_overrides() {
+    let address = Address::repeat_byte(1);
+    let slot_key =
+        |slot: u64| StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(slot));
+    let mut base_storage = InMemoryStorage::default();
+    base_storage.set_value(slot_key(0), H256::repeat_byte(1));
+    base_storage.set_value(slot_key(1), H256::repeat_byte(2));
+    let full_nonce = nonces_to_full_nonce(5.into(), 3.into());
+    base_storage.set_value(get_nonce_key(&address), u256_to_h256(full_nonce));
+
+    let code = vec![0_u8; 32];
+    let account_override = OverrideAccount {
+        balance: Some(100.into()),
+        nonce: Some(7.into()),
+        code: Some(code.clone().into()),
+        state: Some(HashMap::from([(
+            H256::from_low_u64_be(1),
+            H256::repeat_byte(0xff),
+        )])),
+        state_diff: None,
+    };
+    let state_override = StateOverride::from([(address, account_override)]);
+    let overrides = StorageOverrides::new([&state_override]);
+    let mut storage = StorageView::new(StorageWithOverrides::new(base_storage, overrides));
+    apply_state_override(&mut storage, &state_override);
+
+    // The slot is cleared since the account storage is fully replaced.
+    assert_eq!(storage.read_value(&slot_key(0)), H256::zero());
+    assert_eq!(storage.read_value(&slot_key(1)), H256::repeat_byte(0xff));
+    let balance = storage.read_value(&storage_key_for_eth_balance(&address));
+    assert_eq!(h256_to_u256(balance), 100.into());
+    let full_nonce = h256_to_u256(storage.read_value(&get_nonce_key(&address)));
+    assert_eq!(decompose_full_nonce(full_nonce), (7.into(), 3.into()));
+    let code_hash = storage.read_value(&get_code_key(&address));
+    assert_eq!(code_hash, hash_bytecode(&code));
+    assert_eq!(storage.load_factory_dep(code_hash), Some(code));
+}
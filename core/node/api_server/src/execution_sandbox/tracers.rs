@@ -1,6 +1,13 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use multivm::{tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer};
+use multivm::{
+    tracers::{
+        prestate_tracer::State, CallTracer, PrestateTracer, StorageAccessTracer,
+        VersionedTracerAdapter,
+    },
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
+};
 use once_cell::sync::OnceCell;
 use zksync_state::WriteStorage;
 use zksync_types::vm_trace::Call;
@@ -9,6 +16,11 @@ use zksync_types::vm_trace::Call;
 #[derive(Debug)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: Arc<OnceCell<(State, State)>>,
+    },
+    StorageAccessTracer(Arc<Mutex<StorageAccessTracer>>),
 }
 
 impl ApiTracer {
@@ -20,6 +32,12 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::StorageAccessTracer(tracer) => {
+                VersionedTracerAdapter::from_shared(tracer).into_tracer_pointer()
+            }
         }
     }
 }
@@ -1,7 +1,7 @@
 use std::collections::hash_map::{Entry, HashMap};
 
 use tokio::sync::Mutex;
-use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal};
+use zksync_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool, Core, CoreDal, DalError};
 use zksync_shared_metrics::{TxStage, APP_METRICS};
 use zksync_types::{fee::TransactionExecutionMetrics, l2::L2Tx, Address, Nonce, H256};
 
@@ -13,6 +13,8 @@ use crate::web3::metrics::API_METRICS;
 pub struct MasterPoolSink {
     master_pool: ConnectionPool<Core>,
     inflight_requests: Mutex<HashMap<(Address, Nonce), H256>>,
+    replacement_fee_bump_percent: Option<u32>,
+    max_pending_txs_per_account: Option<u32>,
 }
 
 impl MasterPoolSink {
@@ -20,8 +22,70 @@ impl MasterPoolSink {
         Self {
             master_pool,
             inflight_requests: Mutex::new(HashMap::new()),
+            replacement_fee_bump_percent: None,
+            max_pending_txs_per_account: None,
         }
     }
+
+    /// Requires transactions replacing pending transactions with the same initiator and nonce to bump
+    /// their fees by at least the specified percentage.
+    pub fn with_replacement_fee_bump(mut self, min_bump_percent: Option<u32>) -> Self {
+        self.replacement_fee_bump_percent = min_bump_percent;
+        self
+    }
+
+    /// Rejects transactions from initiators that already have the specified number of pending transactions
+    /// (not counting a transaction replaced by the submitted one). The check and the insertion are performed
+    /// in a single DB transaction holding a per-initiator lock, so concurrent submissions cannot exceed the limit.
+    pub fn with_max_pending_txs_per_account(mut self, max_pending_txs: Option<u32>) -> Self {
+        self.max_pending_txs_per_account = max_pending_txs;
+        self
+    }
+
+    async fn insert_tx(
+        &self,
+        tx: &L2Tx,
+        execution_metrics: TransactionExecutionMetrics,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
+        let mut connection = self
+            .master_pool
+            .connection_tagged("api")
+            .await
+            .map_err(DalError::generalize)?;
+
+        let mut transaction = connection
+            .start_transaction()
+            .await
+            .map_err(DalError::generalize)?;
+        if let Some(max_pending_txs) = self.max_pending_txs_per_account {
+            transaction
+                .transactions_dal()
+                .lock_initiator_for_submission(tx.initiator_account())
+                .await
+                .map_err(DalError::generalize)?;
+            let pending_txs_count = transaction
+                .transactions_dal()
+                .get_pending_l2_txs_count(tx.initiator_account(), tx.nonce())
+                .await
+                .map_err(DalError::generalize)?;
+            if pending_txs_count >= max_pending_txs as usize {
+                return Err(SubmitTxError::TooManyPendingTxs(max_pending_txs));
+            }
+        }
+
+        let submission_res_handle = transaction
+            .transactions_dal()
+            .insert_transaction_l2_with_fee_bump(
+                tx,
+                execution_metrics,
+                self.replacement_fee_bump_percent,
+            )
+            .await
+            .map_err(DalError::generalize)?;
+        transaction.commit().await.map_err(DalError::generalize)?;
+        APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
+        Ok(submission_res_handle)
+    }
 }
 
 #[async_trait::async_trait]
@@ -51,18 +115,7 @@ impl TxSink for MasterPoolSink {
         };
         drop(lock);
 
-        let result = match self.master_pool.connection_tagged("api").await {
-            Ok(mut connection) => connection
-                .transactions_dal()
-                .insert_transaction_l2(tx, execution_metrics)
-                .await
-                .map(|submission_res_handle| {
-                    APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
-                    submission_res_handle
-                })
-                .map_err(|err| err.generalize().into()),
-            Err(err) => Err(err.generalize().into()),
-        };
+        let result = self.insert_tx(tx, execution_metrics).await;
 
         self.inflight_requests
             .lock()
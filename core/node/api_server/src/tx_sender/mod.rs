@@ -1,10 +1,15 @@
 //! Helper module to submit transactions into the zkSync Network.
 
-use std::{sync::Arc, time::Instant};
+use std::{
+    mem,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use anyhow::Context as _;
 use multivm::{
-    interface::VmExecutionResultAndLogs,
+    interface::{ExecutionResult, VmExecutionResultAndLogs},
+    tracers::StorageAccessTracer,
     utils::{
         adjust_pubdata_price_for_tx, derive_base_fee_and_gas_per_pubdata, derive_overhead,
         get_eth_call_gas_limit, get_max_batch_gas_limit,
@@ -21,15 +26,16 @@ use zksync_node_fee_model::{ApiFeeInputProvider, BatchFeeModelInputProvider};
 use zksync_state::PostgresStorageCaches;
 use zksync_state_keeper::{
     seal_criteria::{ConditionalSealer, NoopSealer, SealData},
-    SequencerSealer,
+    AddressDenylist, SequencerSealer,
 };
 use zksync_types::{
-    fee::{Fee, TransactionExecutionMetrics},
+    fee::{Fee, FeeBreakdown, TransactionExecutionMetrics},
     fee_model::BatchFeeInput,
     get_code_key, get_intrinsic_constants,
     l2::{error::TxCheckError::TxDuplication, L2Tx},
     transaction_request::CallOverrides,
     utils::storage_key_for_eth_balance,
+    web3::AccessList,
     AccountTreeId, Address, ExecuteTransactionCommon, L2ChainId, Nonce, PackedEthSignature,
     ProtocolVersionId, Transaction, VmVersion, H160, H256, MAX_L2_TX_GAS_LIMIT,
     MAX_NEW_FACTORY_DEPS, U256,
@@ -40,8 +46,9 @@ pub(super) use self::result::SubmitTxError;
 use self::{master_pool_sink::MasterPoolSink, tx_sink::TxSink};
 use crate::{
     execution_sandbox::{
-        BlockArgs, SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs,
-        VmConcurrencyBarrier, VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+        ApiTracer, BlockArgs, SandboxExecutionError, SimulatedBlockOutput, SimulationBlock,
+        SubmitTxStage, TransactionExecutor, TxExecutionArgs, TxSharedArgs, VmConcurrencyBarrier,
+        VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
     },
     tx_sender::result::ApiCallResult,
 };
@@ -61,15 +68,21 @@ pub async fn build_tx_sender(
     master_pool: ConnectionPool<Core>,
     batch_fee_model_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     storage_caches: PostgresStorageCaches,
+    address_denylist: Option<AddressDenylist>,
 ) -> (TxSender, VmConcurrencyBarrier) {
     let sequencer_sealer = SequencerSealer::new(state_keeper_config.clone());
-    let master_pool_sink = MasterPoolSink::new(master_pool);
-    let tx_sender_builder = TxSenderBuilder::new(
+    let master_pool_sink = MasterPoolSink::new(master_pool)
+        .with_replacement_fee_bump(tx_sender_config.replacement_fee_bump_percent)
+        .with_max_pending_txs_per_account(tx_sender_config.max_pending_txs_per_account);
+    let mut tx_sender_builder = TxSenderBuilder::new(
         tx_sender_config.clone(),
         replica_pool.clone(),
         Arc::new(master_pool_sink),
     )
     .with_sealer(Arc::new(sequencer_sealer));
+    if let Some(denylist) = address_denylist {
+        tx_sender_builder = tx_sender_builder.with_address_denylist(denylist);
+    }
 
     let max_concurrency = web3_json_config.vm_concurrency_limit();
     let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
@@ -206,6 +219,8 @@ pub struct TxSenderBuilder {
     sealer: Option<Arc<dyn ConditionalSealer>>,
     /// Cache for tokens that are white-listed for AA.
     whitelisted_tokens_for_aa_cache: Option<Arc<RwLock<Vec<Address>>>>,
+    /// Addresses that cannot send or receive transactions.
+    address_denylist: Option<AddressDenylist>,
 }
 
 impl TxSenderBuilder {
@@ -220,6 +235,7 @@ impl TxSenderBuilder {
             tx_sink,
             sealer: None,
             whitelisted_tokens_for_aa_cache: None,
+            address_denylist: None,
         }
     }
 
@@ -233,6 +249,11 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_address_denylist(mut self, denylist: AddressDenylist) -> Self {
+        self.address_denylist = Some(denylist);
+        self
+    }
+
     pub async fn build(
         self,
         batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
@@ -257,6 +278,7 @@ impl TxSenderBuilder {
             storage_caches,
             whitelisted_tokens_for_aa_cache,
             sealer,
+            address_denylist: self.address_denylist,
             executor: TransactionExecutor::Real,
         }))
     }
@@ -276,6 +298,10 @@ pub struct TxSenderConfig {
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
     pub whitelisted_tokens_for_aa: Vec<Address>,
+    /// Minimum fee bump (in percent) for replacing pending transactions; see `MempoolConfig`.
+    pub replacement_fee_bump_percent: Option<u32>,
+    /// Maximum number of pending transactions per initiator account; see `MempoolConfig`.
+    pub max_pending_txs_per_account: Option<u32>,
 }
 
 impl TxSenderConfig {
@@ -295,8 +321,20 @@ impl TxSenderConfig {
                 .validation_computational_gas_limit,
             chain_id,
             whitelisted_tokens_for_aa: web3_json_config.whitelisted_tokens_for_aa.clone(),
+            replacement_fee_bump_percent: None,
+            max_pending_txs_per_account: None,
         }
     }
+
+    pub fn with_replacement_fee_bump(mut self, min_bump_percent: Option<u32>) -> Self {
+        self.replacement_fee_bump_percent = min_bump_percent;
+        self
+    }
+
+    pub fn with_max_pending_txs_per_account(mut self, max_pending_txs: Option<u32>) -> Self {
+        self.max_pending_txs_per_account = max_pending_txs;
+        self
+    }
 }
 
 pub struct TxSenderInner {
@@ -315,6 +353,8 @@ pub struct TxSenderInner {
     pub(super) whitelisted_tokens_for_aa_cache: Arc<RwLock<Vec<Address>>>,
     /// Batch sealer used to check whether transaction can be executed by the sequencer.
     sealer: Arc<dyn ConditionalSealer>,
+    /// Addresses that cannot send or receive transactions.
+    address_denylist: Option<AddressDenylist>,
     pub(super) executor: TransactionExecutor,
 }
 
@@ -355,6 +395,7 @@ impl TxSender {
     ) -> Result<(L2TxSubmissionResult, VmExecutionResultAndLogs), SubmitTxError> {
         let tx_hash = tx.hash();
         let stage_latency = SANDBOX_METRICS.start_tx_submit_stage(tx_hash, SubmitTxStage::Validate);
+        self.ensure_addresses_allowed(&tx)?;
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = connection.blocks_dal().pending_protocol_version().await?;
         drop(connection);
@@ -441,6 +482,9 @@ impl TxSender {
                 Err(SubmitTxError::IncorrectTx(TxDuplication(tx.hash())))
             }
             L2TxSubmissionResult::InsertionInProgress => Err(SubmitTxError::InsertionInProgress),
+            L2TxSubmissionResult::ReplacementUnderpriced => {
+                Err(SubmitTxError::ReplacementUnderpriced)
+            }
             L2TxSubmissionResult::Proxied => {
                 stage_latency.set_stage(SubmitTxStage::TxProxy);
                 stage_latency.observe();
@@ -476,6 +520,21 @@ impl TxSender {
         })
     }
 
+    fn ensure_addresses_allowed(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        let Some(denylist) = &self.0.address_denylist else {
+            return Ok(());
+        };
+        let addresses = [tx.initiator_account(), tx.execute.contract_address];
+        if let Some(address) = denylist.first_denied(addresses) {
+            tracing::warn!(
+                "Rejected transaction {:?} sent from or to denylisted address {address:?}",
+                tx.hash()
+            );
+            return Err(SubmitTxError::DeniedAddress(address));
+        }
+        Ok(())
+    }
+
     async fn validate_tx(
         &self,
         tx: &L2Tx,
@@ -725,10 +784,23 @@ impl TxSender {
     ))]
     pub async fn get_txs_fee_in_wei(
         &self,
-        mut tx: Transaction,
+        tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u64,
     ) -> Result<Fee, SubmitTxError> {
+        let (fee, _) = self
+            .get_txs_fee_with_breakdown(tx, estimated_fee_scale_factor, acceptable_overestimation)
+            .await?;
+        Ok(fee)
+    }
+
+    /// Same as [`Self::get_txs_fee_in_wei()`], but also returns components of the estimated gas limit.
+    pub async fn get_txs_fee_with_breakdown(
+        &self,
+        mut tx: Transaction,
+        estimated_fee_scale_factor: f64,
+        acceptable_overestimation: u64,
+    ) -> Result<(Fee, FeeBreakdown), SubmitTxError> {
         let estimation_started_at = Instant::now();
 
         let mut connection = self.acquire_replica_connection().await?;
@@ -934,8 +1006,9 @@ impl TxSender {
         };
 
         let gas_for_pubdata = (tx_metrics.pubdata_published as u64) * gas_per_pubdata_byte;
+        // Cap the pubdata gas so that the breakdown always adds up to the full gas limit.
         let estimated_gas_for_pubdata =
-            (gas_for_pubdata as f64 * estimated_fee_scale_factor) as u64;
+            ((gas_for_pubdata as f64 * estimated_fee_scale_factor) as u64).min(suggested_gas_limit);
 
         tracing::debug!(
             "gas for pubdata: {estimated_gas_for_pubdata}, computational gas: {}, overhead gas: {overhead} \
@@ -944,12 +1017,19 @@ impl TxSender {
             suggested_gas_limit - estimated_gas_for_pubdata,
         );
 
-        Ok(Fee {
+        let fee = Fee {
             max_fee_per_gas: base_fee.into(),
             max_priority_fee_per_gas: 0u32.into(),
             gas_limit: full_gas_limit.into(),
             gas_per_pubdata_limit: gas_per_pubdata_byte.into(),
-        })
+        };
+        let breakdown = FeeBreakdown {
+            computational_gas: (suggested_gas_limit - estimated_gas_for_pubdata).into(),
+            gas_for_pubdata: estimated_gas_for_pubdata.into(),
+            overhead: overhead.into(),
+            gas_per_pubdata: gas_per_pubdata_byte.into(),
+        };
+        Ok((fee, breakdown))
     }
 
     // For now, both L1 gas price and pubdata price are scaled with the same coefficient
@@ -989,6 +1069,73 @@ impl TxSender {
             .into_api_call_result()
     }
 
+    /// Executes a call with the `eth_call` semantics, additionally returning the storage slots accessed
+    /// during execution.
+    pub(super) async fn create_access_list(
+        &self,
+        block_args: BlockArgs,
+        call_overrides: CallOverrides,
+        tx: L2Tx,
+    ) -> Result<(VmExecutionResultAndLogs, AccessList), SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let tracer = Arc::new(Mutex::new(StorageAccessTracer::default()));
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let result = self
+            .0
+            .executor
+            .execute_tx_eth_call(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                call_overrides,
+                tx,
+                block_args,
+                vm_execution_cache_misses_limit,
+                vec![ApiTracer::StorageAccessTracer(tracer.clone())],
+            )
+            .await?;
+        let mut tracer = tracer.lock().expect("storage access tracer is poisoned");
+        let access_list = mem::take(&mut *tracer).into_access_list();
+        Ok((result, access_list))
+    }
+
+    /// Simulates blocks of calls for `eth_simulateV1`. Returns an error if any of the calls is halted.
+    pub(super) async fn simulate(
+        &self,
+        block_args: BlockArgs,
+        enforced_base_fee: Option<u64>,
+        blocks: Vec<SimulationBlock>,
+    ) -> Result<Vec<SimulatedBlockOutput>, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        let outputs = self
+            .0
+            .executor
+            .simulate_blocks(
+                vm_permit,
+                self.shared_args().await?,
+                self.0.replica_connection_pool.clone(),
+                enforced_base_fee,
+                blocks,
+                block_args,
+                vm_execution_cache_misses_limit,
+            )
+            .await?;
+
+        let results = outputs.iter().flat_map(|output| &output.results);
+        for result in results {
+            if let ExecutionResult::Halt { reason } = &result.result {
+                let err: SandboxExecutionError = reason.clone().into();
+                return Err(err.into());
+            }
+        }
+        Ok(outputs)
+    }
+
     pub async fn gas_price(&self) -> anyhow::Result<u64> {
         let mut connection = self.acquire_replica_connection().await?;
         let protocol_version = connection
@@ -158,3 +158,34 @@ async fn submitting_tx_requires_one_connection() {
         .unwrap()
         .expect("transaction is not persisted");
 }
+
+#[tokio::test]
+async fn submitting_tx_with_denylisted_address() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(storage);
+
+    let l2_chain_id = L2ChainId::default();
+    let tx = create_l2_transaction(10, 100);
+    let tx_executor = MockTransactionExecutor::default().into();
+    let (mut tx_sender, _) = create_test_tx_sender(pool.clone(), l2_chain_id, tx_executor).await;
+    let denylist = AddressDenylist::new([tx.execute.contract_address].into());
+    Arc::get_mut(&mut tx_sender.0).unwrap().address_denylist = Some(denylist);
+
+    let err = tx_sender.submit_tx(tx.clone()).await.unwrap_err();
+    assert_matches!(
+        err,
+        SubmitTxError::DeniedAddress(address) if address == tx.execute.contract_address
+    );
+
+    let mut storage = pool.connection().await.unwrap();
+    let persisted_tx = storage
+        .transactions_web3_dal()
+        .get_transaction_by_hash(tx.hash(), l2_chain_id)
+        .await
+        .unwrap();
+    assert!(persisted_tx.is_none());
+}
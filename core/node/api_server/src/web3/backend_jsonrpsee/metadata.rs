@@ -1,7 +1,8 @@
 //! Method metadata.
 
-use std::{cell::RefCell, mem, sync::Arc, time::Instant};
+use std::{cell::RefCell, io, mem, sync::Arc, time::Instant};
 
+use serde::Serialize;
 use thread_local::ThreadLocal;
 use zksync_types::api;
 use zksync_web3_decl::{
@@ -24,16 +25,19 @@ pub(crate) struct MethodMetadata {
     pub block_diff: Option<u32>,
     /// Did this call return an app-level error?
     pub has_app_error: bool,
+    /// Maximum response size for the call in bytes.
+    pub max_response_size: usize,
 }
 
 impl MethodMetadata {
-    fn new(name: &'static str) -> Self {
+    fn new(name: &'static str, max_response_size: usize) -> Self {
         Self {
             name,
             started_at: Instant::now(),
             block_id: None,
             block_diff: None,
             has_app_error: false,
+            max_response_size,
         }
     }
 }
@@ -88,15 +92,30 @@ impl MethodTracer {
         }
     }
 
+    /// Creates a tracker for the size of a response to the current JSON-RPC method call. The tracker should be used
+    /// by method handlers returning potentially large lists of items (e.g., logs or traces), so that the handler
+    /// can bail out as soon as the response is known to exceed the size limit for the method.
+    ///
+    /// If called outside JSON-RPC method handlers, returns a tracker with no limit.
+    pub fn response_size_tracker(&self) -> ResponseSizeTracker {
+        let cell = self.inner.get_or_default();
+        let limit = cell
+            .borrow()
+            .as_ref()
+            .map_or(usize::MAX, |metadata| metadata.max_response_size);
+        ResponseSizeTracker::new(limit)
+    }
+
     pub(super) fn new_call<'a>(
         self: &Arc<Self>,
         name: &'static str,
         raw_params: ObservedRpcParams<'a>,
+        max_response_size: usize,
     ) -> MethodCall<'a> {
         MethodCall {
             tracer: self.clone(),
             params: raw_params,
-            meta: MethodMetadata::new(name),
+            meta: MethodMetadata::new(name, max_response_size),
             is_completed: false,
         }
     }
@@ -122,6 +141,76 @@ impl MethodTracer {
     }
 }
 
+/// Incrementally estimates the serialized size of a JSON array response as its items are produced.
+///
+/// `jsonrpsee` only checks the response size after the handler has returned, i.e., once the entire response
+/// is held in memory. Tracking the size while converting items allows to reject oversized responses early.
+/// The estimate doesn't include the JSON-RPC envelope, so it's a lower bound of the actual response size.
+#[derive(Debug)]
+pub struct ResponseSizeTracker {
+    limit: usize,
+    size: usize,
+}
+
+impl ResponseSizeTracker {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            // Array brackets
+            size: 2,
+        }
+    }
+
+    /// Returns the estimated size of the response tracked so far.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Accounts for the specified response item.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Web3Error::OversizedResponse`] if the response size exceeds the limit. Serialization
+    /// of the item is aborted as soon as the limit is reached.
+    pub fn observe<T: Serialize>(&mut self, item: &T) -> Result<(), Web3Error> {
+        let separator_len = usize::from(self.size > 2);
+        let mut writer = CountingWriter {
+            count: self.size + separator_len,
+            limit: self.limit,
+        };
+        let serialization_result = serde_json::to_writer(&mut writer, item);
+        self.size = writer.count;
+        if self.size > self.limit {
+            return Err(Web3Error::OversizedResponse(self.limit));
+        }
+        serialization_result.map_err(|err| {
+            Web3Error::InternalError(anyhow::anyhow!("failed serializing item: {err}"))
+        })
+    }
+}
+
+/// Writer discarding the written data and only counting the number of written bytes.
+#[derive(Debug)]
+struct CountingWriter {
+    count: usize,
+    limit: usize,
+}
+
+impl io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.count = self.count.saturating_add(buf.len());
+        if self.count > self.limit {
+            Err(io::Error::new(io::ErrorKind::Other, "size limit exceeded"))
+        } else {
+            Ok(buf.len())
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct MethodCall<'a> {
     tracer: Arc<MethodTracer>,
@@ -1,7 +1,9 @@
 use std::{
     cell::RefCell,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    fmt,
     future::Future,
+    net::{IpAddr, SocketAddr},
     num::NonZeroU32,
     pin::Pin,
     sync::Arc,
@@ -13,16 +15,18 @@ use governor::{
     clock::DefaultClock,
     middleware::NoOpMiddleware,
     state::{InMemoryState, NotKeyed},
-    Quota, RateLimiter,
+    DefaultKeyedRateLimiter, Quota, RateLimiter,
 };
 use once_cell::sync::OnceCell;
 use pin_project_lite::pin_project;
 use rand::{rngs::SmallRng, RngCore, SeedableRng};
-use tokio::sync::watch;
+use tokio::{sync::watch, task::futures::TaskLocalFuture};
 use tracing::instrument::{Instrument, Instrumented};
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram, Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, GaugeGuard, Histogram,
+    LabeledFamily, Metrics,
 };
+use zksync_config::configs::api::{ClientRateLimits, MaxResponseSize};
 use zksync_web3_decl::jsonrpsee::{
     server::middleware::rpc::{layer::ResponseFuture, RpcServiceT},
     types::{error::ErrorCode, ErrorObject, Request},
@@ -48,6 +52,10 @@ struct LimitMiddlewareMetrics {
     size: Family<Transport, Histogram<usize>>,
     /// Number of requests rejected by the limiter.
     rejected: Family<Transport, Counter>,
+    /// Number of requests rejected by per-client rate limits, grouped by the method group.
+    /// The `all` group corresponds to the limit on the total number of requests.
+    #[metrics(labels = ["method_group"])]
+    client_rate_limited: LabeledFamily<String, Counter>,
 }
 
 #[vise::register]
@@ -88,17 +96,224 @@ where
             // Note: if required, we can extract data on rate limiting from the error.
             if rate_limiter.check_n(num_requests).is_err() {
                 METRICS.rate_limited[&self.transport].inc();
+                return ResponseFuture::ready(too_many_requests(request));
+            }
+        }
+        ResponseFuture::future(self.inner.call(request))
+    }
+}
+
+fn too_many_requests(request: Request<'_>) -> MethodResponse {
+    MethodResponse::error(
+        request.id,
+        ErrorObject::borrowed(
+            ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into()).code(),
+            "Too many requests",
+            None,
+        ),
+    )
+}
 
-                let rp = MethodResponse::error(
-                    request.id,
-                    ErrorObject::borrowed(
-                        ErrorCode::ServerError(http::StatusCode::TOO_MANY_REQUESTS.as_u16().into())
-                            .code(),
-                        "Too many requests",
-                        None,
-                    ),
-                );
-                return ResponseFuture::ready(rp);
+tokio::task_local! {
+    /// Client that has sent the HTTP request being currently processed.
+    static CLIENT_ID: Option<ClientId>;
+}
+
+/// Identifier of an API client used for per-client rate limiting.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientId {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+impl ClientId {
+    fn current() -> Option<Self> {
+        CLIENT_ID.try_with(Clone::clone).ok().flatten()
+    }
+}
+
+/// Peer address of the TCP connection an HTTP request was received from. Inserted into request extensions by the server.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PeerAddr(pub SocketAddr);
+
+/// Rules to identify API clients based on HTTP requests.
+#[derive(Debug, Default)]
+pub(crate) struct ClientIdRules {
+    trusted_proxies: HashSet<IpAddr>,
+    api_keys: HashSet<String>,
+}
+
+impl ClientIdRules {
+    const API_KEY_HEADER: &'static str = "x-api-key";
+    const FORWARDED_FOR_HEADER: &'static str = "x-forwarded-for";
+    const REAL_IP_HEADER: &'static str = "x-real-ip";
+
+    pub(crate) fn new(limits: &ClientRateLimits) -> Self {
+        Self {
+            trusted_proxies: limits.trusted_proxies.clone(),
+            api_keys: limits.api_keys.clone(),
+        }
+    }
+
+    /// Identifies the client that has sent the request. Clients are identified by an allowlisted API key, or by
+    /// the peer IP address. Headers set by reverse proxies are only honored if the peer is a trusted proxy.
+    fn client_id(&self, peer_ip: Option<IpAddr>, headers: &http::HeaderMap) -> Option<ClientId> {
+        let header_str = |name: &str| headers.get(name)?.to_str().ok();
+
+        if let Some(api_key) = header_str(Self::API_KEY_HEADER) {
+            if self.api_keys.contains(api_key) {
+                return Some(ClientId::ApiKey(api_key.to_owned()));
+            }
+        }
+
+        let peer_ip = peer_ip?;
+        if !self.trusted_proxies.contains(&peer_ip) {
+            return Some(ClientId::Ip(peer_ip));
+        }
+        // Each proxy appends the address of its peer to `x-forwarded-for`, so the client address is the rightmost
+        // address not belonging to a trusted proxy. Addresses to the left of it are controlled by the client.
+        let forwarded_ip = header_str(Self::FORWARDED_FOR_HEADER).and_then(|addresses| {
+            for address in addresses.rsplit(',') {
+                let ip: IpAddr = address.trim().parse().ok()?;
+                if !self.trusted_proxies.contains(&ip) {
+                    return Some(ip);
+                }
+            }
+            None
+        });
+        let ip = forwarded_ip
+            .or_else(|| header_str(Self::REAL_IP_HEADER)?.trim().parse().ok())
+            .unwrap_or(peer_ip);
+        Some(ClientId::Ip(ip))
+    }
+}
+
+/// HTTP-level middleware extracting [`ClientId`] from HTTP requests and making it available to [`ClientLimitMiddleware`].
+#[derive(Debug, Clone)]
+pub(crate) struct ClientIdService<S> {
+    inner: S,
+    rules: Arc<ClientIdRules>,
+}
+
+impl<S> ClientIdService<S> {
+    pub(crate) fn new(inner: S, rules: Arc<ClientIdRules>) -> Self {
+        Self { inner, rules }
+    }
+}
+
+impl<S, B> tower::Service<http::Request<B>> for ClientIdService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<Option<ClientId>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        let peer_ip = request
+            .extensions()
+            .get::<PeerAddr>()
+            .map(|PeerAddr(addr)| addr.ip());
+        let client_id = self.rules.client_id(peer_ip, request.headers());
+        CLIENT_ID.scope(client_id, self.inner.call(request))
+    }
+}
+
+/// Per-client rate limiter shared among all requests processed by an API server.
+pub(crate) struct ClientRateLimiter {
+    global: Option<DefaultKeyedRateLimiter<ClientId>>,
+    /// Limiters for method groups keyed by the group name, i.e. a full method name or a namespace.
+    groups: HashMap<String, DefaultKeyedRateLimiter<ClientId>>,
+}
+
+impl fmt::Debug for ClientRateLimiter {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ClientRateLimiter")
+            .field("global", &self.global.is_some())
+            .field("groups", &self.groups.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ClientRateLimiter {
+    pub(crate) fn new(limits: &ClientRateLimits) -> Self {
+        Self {
+            global: limits
+                .global
+                .map(|limit| RateLimiter::keyed(Quota::per_minute(limit))),
+            groups: limits
+                .overrides
+                .iter()
+                .map(|(group, limit)| {
+                    let limiter = RateLimiter::keyed(Quota::per_minute(limit));
+                    (group.to_owned(), limiter)
+                })
+                .collect(),
+        }
+    }
+
+    /// Checks whether the specified client can call the method. On failure, returns the name of the exhausted method group.
+    fn check(&self, client_id: &ClientId, method_name: &str) -> Result<(), &str> {
+        if let Some(global) = &self.global {
+            global.check_key(client_id).map_err(|_| "all")?;
+        }
+
+        let group = self.groups.get_key_value(method_name).or_else(|| {
+            let (namespace, _) = method_name.split_once('_')?;
+            self.groups.get_key_value(namespace)
+        });
+        if let Some((group, limiter)) = group {
+            limiter.check_key(client_id).map_err(|_| group.as_str())?;
+        }
+        Ok(())
+    }
+
+    /// Removes state for clients that have their quotas fully replenished. Should be called periodically
+    /// to bound memory consumption.
+    pub(crate) fn retain_recent(&self) {
+        for limiter in self.global.iter().chain(self.groups.values()) {
+            limiter.retain_recent();
+        }
+    }
+}
+
+/// RPC-level middleware enforcing per-client rate limits. Client IDs are provided by [`ClientIdService`];
+/// if a client ID is not available, the request is not limited.
+pub(crate) struct ClientLimitMiddleware<S> {
+    inner: S,
+    rate_limiter: Arc<ClientRateLimiter>,
+    /// Client ID at the moment the middleware was instantiated. Used if there's no client ID for a specific request
+    /// (e.g., for WebSocket sessions).
+    session_client_id: Option<ClientId>,
+}
+
+impl<S> ClientLimitMiddleware<S> {
+    pub(crate) fn new(inner: S, rate_limiter: Arc<ClientRateLimiter>) -> Self {
+        Self {
+            inner,
+            rate_limiter,
+            session_client_id: ClientId::current(),
+        }
+    }
+}
+
+impl<'a, S> RpcServiceT<'a> for ClientLimitMiddleware<S>
+where
+    S: Send + Sync + RpcServiceT<'a>,
+{
+    type Future = ResponseFuture<S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        let client_id = ClientId::current().or_else(|| self.session_client_id.clone());
+        if let Some(client_id) = &client_id {
+            if let Err(group) = self.rate_limiter.check(client_id, request.method_name()) {
+                METRICS.client_rate_limited[&group.to_owned()].inc();
+                return ResponseFuture::ready(too_many_requests(request));
             }
         }
         ResponseFuture::future(self.inner.call(request))
@@ -120,6 +335,7 @@ pub(crate) struct MetadataMiddleware<S, const TRACE_PARAMS: bool> {
     inner: S,
     registered_method_names: Arc<HashSet<&'static str>>,
     method_tracer: Arc<MethodTracer>,
+    max_response_size: Arc<MaxResponseSize>,
 }
 
 impl<'a, S, const TRACE_PARAMS: bool> RpcServiceT<'a> for MetadataMiddleware<S, TRACE_PARAMS>
@@ -142,7 +358,14 @@ where
         } else {
             ObservedRpcParams::Unknown
         };
-        let call = self.method_tracer.new_call(method_name, observed_params);
+        let max_response_size = self
+            .max_response_size
+            .overrides
+            .get(method_name)
+            .unwrap_or(self.max_response_size.global);
+        let call = self
+            .method_tracer
+            .new_call(method_name, observed_params, max_response_size);
         WithMethodCall::new(self.inner.call(request), call)
     }
 }
@@ -190,16 +413,19 @@ impl<F: Future<Output = MethodResponse>> Future for WithMethodCall<'_, F> {
 pub(crate) struct MetadataLayer<const TRACE_PARAMS: bool> {
     registered_method_names: Arc<HashSet<&'static str>>,
     method_tracer: Arc<MethodTracer>,
+    max_response_size: Arc<MaxResponseSize>,
 }
 
 impl MetadataLayer<false> {
     pub fn new(
         registered_method_names: Arc<HashSet<&'static str>>,
         method_tracer: Arc<MethodTracer>,
+        max_response_size: Arc<MaxResponseSize>,
     ) -> Self {
         Self {
             registered_method_names,
             method_tracer,
+            max_response_size,
         }
     }
 
@@ -207,6 +433,7 @@ impl MetadataLayer<false> {
         MetadataLayer {
             registered_method_names: self.registered_method_names,
             method_tracer: self.method_tracer,
+            max_response_size: self.max_response_size,
         }
     }
 }
@@ -219,6 +446,7 @@ impl<Svc, const TRACE_PARAMS: bool> tower::Layer<Svc> for MetadataLayer<TRACE_PA
             inner,
             registered_method_names: self.registered_method_names.clone(),
             method_tracer: self.method_tracer.clone(),
+            max_response_size: self.max_response_size.clone(),
         }
     }
 }
@@ -334,10 +562,11 @@ where
 mod tests {
     use std::time::Duration;
 
+    use assert_matches::assert_matches;
     use rand::{thread_rng, Rng};
     use test_casing::{test_casing, Product};
     use zksync_types::api;
-    use zksync_web3_decl::jsonrpsee::helpers::MethodResponseResult;
+    use zksync_web3_decl::{error::Web3Error, jsonrpsee::helpers::MethodResponseResult};
 
     use super::*;
 
@@ -375,7 +604,7 @@ mod tests {
 
             WithMethodCall::new(
                 inner,
-                method_tracer.new_call("test", ObservedRpcParams::None),
+                method_tracer.new_call("test", ObservedRpcParams::None, usize::MAX),
             )
         });
 
@@ -398,6 +627,45 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn tracking_response_size() {
+        let method_tracer = Arc::new(MethodTracer::default());
+        // Outside method handlers, the response size is unlimited.
+        let mut response_size = method_tracer.response_size_tracker();
+        response_size.observe(&"!".repeat(1_000)).unwrap();
+
+        let current_method = method_tracer.clone();
+        let inner = async move {
+            let mut response_size = current_method.response_size_tracker();
+            response_size.observe(&"!".repeat(10)).unwrap();
+            // `["!!!!!!!!!!"]`
+            assert_eq!(response_size.size(), 14);
+            response_size.observe(&"!".repeat(10)).unwrap();
+            // `["!!!!!!!!!!","!!!!!!!!!!"]`
+            assert_eq!(response_size.size(), 27);
+
+            let err = response_size.observe(&"!".repeat(100)).unwrap_err();
+            assert_matches!(err, Web3Error::OversizedResponse(64));
+            let err = current_method.map_err(err);
+            MethodResponse {
+                result: "{}".to_string(),
+                success_or_error: MethodResponseResult::Failed(err.code()),
+                is_subscription: false,
+            }
+        };
+        WithMethodCall::new(
+            inner,
+            method_tracer.new_call("test", ObservedRpcParams::None, 64),
+        )
+        .await;
+
+        let calls = method_tracer.recorded_calls().take();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].metadata.max_response_size, 64);
+        assert!(calls[0].metadata.has_app_error);
+        assert!(!calls[0].response.is_success());
+    }
+
     #[tokio::test]
     async fn traffic_tracker_basics() {
         let traffic_tracker = TrafficTracker::default();
@@ -412,4 +680,89 @@ mod tests {
         let elapsed = now.elapsed();
         assert!(elapsed >= Duration::from_millis(15), "{elapsed:?}");
     }
+
+    #[test]
+    fn extracting_client_id() {
+        let rules = ClientIdRules {
+            trusted_proxies: HashSet::from(["192.168.0.1".parse().unwrap()]),
+            api_keys: HashSet::from(["test".to_owned()]),
+        };
+        let client_ip: IpAddr = "10.0.0.3".parse().unwrap();
+        let proxy_ip: IpAddr = "192.168.0.1".parse().unwrap();
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(rules.client_id(None, &headers), None);
+        assert_eq!(
+            rules.client_id(Some(client_ip), &headers),
+            Some(ClientId::Ip(client_ip))
+        );
+        assert_eq!(
+            rules.client_id(Some(proxy_ip), &headers),
+            Some(ClientId::Ip(proxy_ip))
+        );
+
+        headers.insert("x-real-ip", "10.0.0.2".parse().unwrap());
+        // Headers are ignored if the peer is not a trusted proxy.
+        assert_eq!(
+            rules.client_id(Some(client_ip), &headers),
+            Some(ClientId::Ip(client_ip))
+        );
+        assert_eq!(
+            rules.client_id(Some(proxy_ip), &headers),
+            Some(ClientId::Ip("10.0.0.2".parse().unwrap()))
+        );
+        headers.insert(
+            "x-forwarded-for",
+            "1.2.3.4, 10.0.0.1, 192.168.0.1".parse().unwrap(),
+        );
+        assert_eq!(
+            rules.client_id(Some(proxy_ip), &headers),
+            Some(ClientId::Ip("10.0.0.1".parse().unwrap()))
+        );
+
+        headers.insert("x-api-key", "unknown".parse().unwrap());
+        assert_eq!(
+            rules.client_id(Some(client_ip), &headers),
+            Some(ClientId::Ip(client_ip))
+        );
+        headers.insert("x-api-key", "test".parse().unwrap());
+        assert_eq!(
+            rules.client_id(Some(client_ip), &headers),
+            Some(ClientId::ApiKey("test".to_owned()))
+        );
+    }
+
+    #[test]
+    fn client_rate_limiter_basics() {
+        let limits = ClientRateLimits {
+            global: NonZeroU32::new(4),
+            overrides: [
+                ("debug", NonZeroU32::new(1).unwrap()),
+                ("eth_call", NonZeroU32::new(2).unwrap()),
+            ]
+            .into_iter()
+            .collect(),
+            trusted_proxies: HashSet::new(),
+            api_keys: HashSet::new(),
+        };
+        let rate_limiter = ClientRateLimiter::new(&limits);
+        let client = ClientId::Ip("10.0.0.1".parse().unwrap());
+        let other_client = ClientId::ApiKey("test".to_owned());
+
+        rate_limiter.check(&client, "debug_traceCall").unwrap();
+        assert_eq!(
+            rate_limiter.check(&client, "debug_traceBlockByNumber"),
+            Err("debug")
+        );
+        rate_limiter
+            .check(&other_client, "debug_traceCall")
+            .unwrap();
+
+        rate_limiter.check(&client, "eth_call").unwrap();
+        assert_eq!(rate_limiter.check(&client, "eth_call"), Err("eth_call"));
+        // All requests (including rejected ones) count towards the global limit.
+        assert_eq!(rate_limiter.check(&client, "eth_blockNumber"), Err("all"));
+        rate_limiter
+            .check(&other_client, "eth_blockNumber")
+            .unwrap();
+    }
 }
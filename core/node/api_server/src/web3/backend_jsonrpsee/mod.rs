@@ -4,13 +4,18 @@
 
 use zksync_web3_decl::{
     error::Web3Error,
-    jsonrpsee::types::{error::ErrorCode, ErrorObjectOwned},
+    jsonrpsee::types::{
+        error::{ErrorCode, OVERSIZED_RESPONSE_CODE},
+        ErrorObjectOwned,
+    },
 };
 
 pub(crate) use self::{
-    metadata::{MethodMetadata, MethodTracer},
+    metadata::{MethodMetadata, MethodTracer, ResponseSizeTracker},
     middleware::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, ShutdownMiddleware, TrafficTracker,
+        ClientIdRules, ClientIdService, ClientLimitMiddleware, ClientRateLimiter,
+        CorrelationMiddleware, LimitMiddleware, MetadataLayer, PeerAddr, ShutdownMiddleware,
+        TrafficTracker,
     },
 };
 use crate::tx_sender::SubmitTxError;
@@ -28,6 +33,8 @@ impl MethodTracer {
         let data = match &err {
             Web3Error::SubmitTransactionError(_, data) => Some(format!("0x{}", hex::encode(data))),
             Web3Error::ProxyError(_) => Some("0x".to_owned()),
+            // Mirror the error data returned by `jsonrpsee` if the response exceeds the configured limit.
+            Web3Error::OversizedResponse(limit) => Some(format!("Exceeded max limit of {limit}")),
             _ => None,
         };
         let code = match err {
@@ -37,13 +44,19 @@ impl MethodTracer {
             | Web3Error::PrunedBlock(_)
             | Web3Error::PrunedL1Batch(_)
             | Web3Error::TooManyTopics
+            | Web3Error::TooManyProofKeys(_)
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFilterBlockHash
-            | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
+            | Web3Error::InvalidRewardPercentiles
+            | Web3Error::InvalidSimulation(_)
+            | Web3Error::L1BatchNotAbortable(_)
+            | Web3Error::LogsLimitExceeded(_, _, _)
+            | Web3Error::BlockRangeLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _)
             | Web3Error::SerializationError(_)
             | Web3Error::ProxyError(_) => 3,
-            Web3Error::TreeApiUnavailable => 6,
+            Web3Error::TreeApiUnavailable | Web3Error::ServerOverloaded => 6,
+            Web3Error::OversizedResponse(_) => OVERSIZED_RESPONSE_CODE,
         };
         let message = match err {
             // Do not expose internal error details to the client.
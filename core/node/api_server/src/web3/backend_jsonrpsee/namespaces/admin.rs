@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use zksync_types::{
+    api::{AbortedL1Batch, EthSenderOperation, L1BatchL1Cost},
+    Address, L1BatchNumber,
+};
+use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::AdminNamespaceServer};
+
+use crate::web3::namespaces::AdminNamespace;
+
+#[async_trait]
+impl AdminNamespaceServer for AdminNamespace {
+    async fn abort_l1_batch(&self) -> RpcResult<AbortedL1Batch> {
+        self.abort_l1_batch_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_fee_account(&self) -> RpcResult<Address> {
+        self.get_fee_account_impl()
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn set_fee_account(&self, address: Address) -> RpcResult<Address> {
+        self.set_fee_account_impl(address)
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_paused_eth_sender_operations(&self) -> RpcResult<Vec<EthSenderOperation>> {
+        self.get_paused_eth_sender_operations_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn pause_eth_sender_operation(&self, operation: EthSenderOperation) -> RpcResult<bool> {
+        self.pause_eth_sender_operation_impl(operation)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn resume_eth_sender_operation(&self, operation: EthSenderOperation) -> RpcResult<bool> {
+        self.resume_eth_sender_operation_impl(operation)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_l1_batch_l1_costs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Vec<L1BatchL1Cost>> {
+        self.get_l1_batch_l1_costs_impl(l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
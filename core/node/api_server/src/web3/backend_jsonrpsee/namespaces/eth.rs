@@ -1,7 +1,8 @@
 use zksync_types::{
     api::{
-        Block, BlockId, BlockIdVariant, BlockNumber, Log, Transaction, TransactionId,
-        TransactionReceipt, TransactionVariant,
+        AccessListResult, Block, BlockId, BlockIdVariant, BlockNumber, EthProof, Log,
+        SimulatePayload, SimulatedBlock, Transaction, TransactionId, TransactionReceipt,
+        TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::{Bytes, FeeHistory, Index, SyncState},
@@ -33,6 +34,26 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Vec<SimulatedBlock>> {
+        self.simulate_v1_impl(payload, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn create_access_list(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<AccessListResult> {
+        self.create_access_list_impl(req, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn estimate_gas(&self, req: CallRequest, block: Option<BlockNumber>) -> RpcResult<U256> {
         self.estimate_gas_impl(req, block)
             .await
@@ -161,6 +182,17 @@ impl EthNamespaceServer for EthNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block: Option<BlockIdVariant>,
+    ) -> RpcResult<Option<EthProof>> {
+        self.get_proof_impl(address, keys, block.map(Into::into))
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_transaction_count(
         &self,
         address: Address,
@@ -1,7 +1,10 @@
+pub mod admin;
 pub mod debug;
 pub mod en;
 pub mod eth;
 pub mod net;
 pub mod snapshots;
+pub mod trace;
+pub mod txpool;
 pub mod web3;
 pub mod zks;
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use zksync_types::{
+    api::{
+        trace::{LocalizedTrace, TraceFilter},
+        BlockNumber,
+    },
+    H256,
+};
+use zksync_web3_decl::{jsonrpsee::core::RpcResult, namespaces::TraceNamespaceServer};
+
+use crate::web3::namespaces::TraceNamespace;
+
+#[async_trait]
+impl TraceNamespaceServer for TraceNamespace {
+    async fn trace_block(&self, block: BlockNumber) -> RpcResult<Vec<LocalizedTrace>> {
+        self.trace_block_impl(block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Option<Vec<LocalizedTrace>>> {
+        self.trace_transaction_impl(tx_hash)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<LocalizedTrace>> {
+        self.trace_filter_impl(filter)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+}
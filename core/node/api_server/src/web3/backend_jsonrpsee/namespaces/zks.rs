@@ -3,10 +3,13 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use zksync_types::{
     api::{
-        ApiStorageLog, BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Log, Proof,
-        ProtocolVersion, TransactionDetailedResult, TransactionDetails,
+        ApiStorageLog, BalanceHistory, BaseTokenConversionRatio, BlockDetails, BridgeAddresses,
+        L1BatchDetails, L1BatchPubdata, L1GasPriceSample, L2BlockRawTransactions, L2ToL1LogProof,
+        Log, LogCursor, LogsPage, Proof, ProofQuery, ProtocolUpgradeInfo, ProtocolVersion,
+        ProtocolVersionInfo, StorageInfo, TokenBalances, TokenTransfer, TransactionDetailedResult,
+        TransactionDetails,
     },
-    fee::Fee,
+    fee::FeeEstimate,
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     transaction_request::CallRequest,
     web3::Bytes,
@@ -15,15 +18,19 @@ use zksync_types::{
 use zksync_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
     namespaces::ZksNamespaceServer,
-    types::Token,
+    types::{Filter, Token},
 };
 
 use crate::web3::ZksNamespace;
 
 #[async_trait]
 impl ZksNamespaceServer for ZksNamespace {
-    async fn estimate_fee(&self, req: CallRequest) -> RpcResult<Fee> {
-        self.estimate_fee_impl(req)
+    async fn estimate_fee(
+        &self,
+        req: CallRequest,
+        include_breakdown: Option<bool>,
+    ) -> RpcResult<FeeEstimate> {
+        self.estimate_fee_impl(req, include_breakdown.unwrap_or(false))
             .await
             .map_err(|err| self.current_method().map_err(err))
     }
@@ -127,6 +134,16 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_raw_blocks_transactions(
+        &self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> RpcResult<Vec<L2BlockRawTransactions>> {
+        self.get_raw_blocks_transactions_impl(from_block, to_block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_l1_batch_details(
         &self,
         batch_number: L1BatchNumber,
@@ -136,6 +153,12 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_batch_pubdata(&self, batch: L1BatchNumber) -> RpcResult<Option<L1BatchPubdata>> {
+        self.get_batch_pubdata_impl(batch)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_bytecode_by_hash(&self, hash: H256) -> RpcResult<Option<Vec<u8>>> {
         self.get_bytecode_by_hash_impl(hash)
             .await
@@ -169,6 +192,21 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_protocol_upgrade(
+        &self,
+        version_id: u16,
+    ) -> RpcResult<Option<ProtocolUpgradeInfo>> {
+        self.get_protocol_upgrade_impl(version_id)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_protocol_versions(&self) -> RpcResult<Vec<ProtocolVersionInfo>> {
+        self.get_protocol_versions_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_proof(
         &self,
         address: Address,
@@ -180,11 +218,38 @@ impl ZksNamespaceServer for ZksNamespace {
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_proofs(
+        &self,
+        queries: Vec<ProofQuery>,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<Option<Vec<Proof>>> {
+        self.get_multi_account_proofs_impl(queries, l1_batch_number)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn get_base_token_l1_address(&self) -> RpcResult<Address> {
         self.get_base_token_l1_address_impl()
             .map_err(|err| self.current_method().map_err(err))
     }
 
+    async fn get_base_token_conversion_ratio(&self) -> RpcResult<Option<BaseTokenConversionRatio>> {
+        self.get_base_token_conversion_ratio_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_l1_gas_price_history(
+        &self,
+        from_timestamp: Option<u64>,
+        to_timestamp: Option<u64>,
+        limit: Option<usize>,
+    ) -> RpcResult<Vec<L1GasPriceSample>> {
+        self.get_l1_gas_price_history_impl(from_timestamp, to_timestamp, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
     async fn send_raw_transaction_with_detailed_output(
         &self,
         tx_bytes: Bytes,
@@ -215,4 +280,53 @@ impl ZksNamespaceServer for ZksNamespace {
             })
             .map_err(|err| self.current_method().map_err(err))
     }
+
+    async fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> RpcResult<LogsPage> {
+        self.get_logs_paginated_impl(filter, cursor, limit)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_storage_info(&self) -> RpcResult<StorageInfo> {
+        self.get_storage_info_impl()
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_balance_history(
+        &self,
+        address: Address,
+        from_block: Option<L2BlockNumber>,
+        to_block: Option<L2BlockNumber>,
+    ) -> RpcResult<BalanceHistory> {
+        self.get_balance_history_impl(address, from_block, to_block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_token_balances(
+        &self,
+        address: Address,
+        after_token: Option<Address>,
+    ) -> RpcResult<TokenBalances> {
+        self.get_token_balances_impl(address, after_token)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
+
+    async fn get_token_transfers(
+        &self,
+        address: Address,
+        from_block: Option<L2BlockNumber>,
+        to_block: Option<L2BlockNumber>,
+    ) -> RpcResult<Vec<TokenTransfer>> {
+        self.get_token_transfers_impl(address, from_block, to_block)
+            .await
+            .map_err(|err| self.current_method().map_err(err))
+    }
 }
@@ -165,10 +165,17 @@ enum Web3ErrorKind {
     TransactionSerialization,
     Proxy,
     TooManyTopics,
+    TooManyProofKeys,
     FilterNotFound,
     LogsLimitExceeded,
+    BlockRangeLimitExceeded,
     InvalidFilterBlockHash,
+    InvalidRewardPercentiles,
+    InvalidSimulation,
+    L1BatchNotAbortable,
+    OversizedResponse,
     TreeApiUnavailable,
+    ServerOverloaded,
     Internal,
 }
 
@@ -181,10 +188,17 @@ impl Web3ErrorKind {
             Web3Error::ProxyError(_) => Self::Proxy,
             Web3Error::SerializationError(_) => Self::TransactionSerialization,
             Web3Error::TooManyTopics => Self::TooManyTopics,
+            Web3Error::TooManyProofKeys(_) => Self::TooManyProofKeys,
             Web3Error::FilterNotFound => Self::FilterNotFound,
             Web3Error::LogsLimitExceeded(..) => Self::LogsLimitExceeded,
+            Web3Error::BlockRangeLimitExceeded(..) => Self::BlockRangeLimitExceeded,
             Web3Error::InvalidFilterBlockHash => Self::InvalidFilterBlockHash,
+            Web3Error::InvalidRewardPercentiles => Self::InvalidRewardPercentiles,
+            Web3Error::InvalidSimulation(_) => Self::InvalidSimulation,
+            Web3Error::L1BatchNotAbortable(_) => Self::L1BatchNotAbortable,
+            Web3Error::OversizedResponse(_) => Self::OversizedResponse,
             Web3Error::TreeApiUnavailable => Self::TreeApiUnavailable,
+            Web3Error::ServerOverloaded => Self::ServerOverloaded,
             Web3Error::InternalError(_) | Web3Error::MethodNotImplemented => Self::Internal,
         }
     }
@@ -419,6 +433,7 @@ pub enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    L2ToL1Logs,
 }
 
 #[derive(Debug, Metrics)]
@@ -445,6 +460,11 @@ pub(super) struct PubSubMetrics {
     pub skipped_broadcast_messages: Family<SubscriptionType, Histogram<u64>>,
     /// Number of subscribers dropped because of a send timeout.
     pub subscriber_send_timeouts: Family<SubscriptionType, Counter>,
+    /// Whether the listener for Postgres notifications about new L2 blocks is active (1) or notifiers
+    /// have fallen back to polling (0).
+    pub l2_block_listener_active: Gauge<u64>,
+    /// Number of Postgres notifications about new L2 blocks received by the listener.
+    pub l2_block_notifications: Counter,
 }
 
 #[vise::register]
@@ -1,47 +1,65 @@
-use std::{collections::HashSet, net::SocketAddr, num::NonZeroU32, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet, convert::Infallible, net::SocketAddr, num::NonZeroU32, sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
 use futures::future;
-use serde::Deserialize;
+use hyper::{server::conn::AddrStream, service::make_service_fn};
+use secrecy::{ExposeSecret as _, Secret};
+use serde::{Deserialize, Serialize};
 use tokio::{
-    sync::{mpsc, oneshot, watch, Mutex},
+    sync::{mpsc, oneshot, watch},
     task::JoinHandle,
 };
-use tower_http::{cors::CorsLayer, metrics::InFlightRequestsLayer};
-use zksync_config::configs::api::{MaxResponseSize, MaxResponseSizeOverrides};
+use tower_http::{
+    auth::require_authorization::Bearer,
+    compression::{predicate::DefaultPredicate, CompressionLayer, Predicate},
+    cors::CorsLayer,
+    metrics::InFlightRequestsLayer,
+    validate_request::ValidateRequestHeaderLayer,
+};
+use zksync_config::configs::api::{
+    ClientRateLimits, MaxResponseSize, MaxResponseSizeOverrides, MethodFilter,
+};
 use zksync_dal::{helpers::wait_for_l1_batch, ConnectionPool, Core};
+use zksync_eth_sender::EthSenderPauseHandle;
 use zksync_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_metadata_calculator::api_server::TreeApiClient;
 use zksync_node_sync::SyncState;
+use zksync_state_keeper::{FeeAccountHandle, L1BatchAbortHandle};
 use zksync_types::L2BlockNumber;
 use zksync_web3_decl::{
     jsonrpsee::{
         server::{
-            middleware::rpc::either::Either, BatchRequestConfig, RpcServiceBuilder, ServerBuilder,
+            middleware::rpc::either::Either, stop_channel, BatchRequestConfig, RpcServiceBuilder,
+            ServerBuilder,
         },
         MethodCallback, Methods, RpcModule,
     },
     namespaces::{
-        DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, EthPubSubServer,
-        NetNamespaceServer, SnapshotsNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
+        AdminNamespaceServer, DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer,
+        EthPubSubServer, NetNamespaceServer, SnapshotsNamespaceServer, TraceNamespaceServer,
+        TxpoolNamespaceServer, Web3NamespaceServer, ZksNamespaceServer, ZksPubSubServer,
     },
     types::Filter,
 };
 
 use self::{
     backend_jsonrpsee::{
-        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, ShutdownMiddleware,
-        TrafficTracker,
+        ClientIdRules, ClientIdService, ClientLimitMiddleware, ClientRateLimiter,
+        CorrelationMiddleware, LimitMiddleware, MetadataLayer, MethodTracer, PeerAddr,
+        ShutdownMiddleware, TrafficTracker,
     },
     mempool_cache::MempoolCache,
     metrics::API_METRICS,
     namespaces::{
-        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace, Web3Namespace,
-        ZksNamespace,
+        AdminNamespace, DebugNamespace, EnNamespace, EthNamespace, NetNamespace,
+        SnapshotsNamespace, TraceNamespace, TxpoolNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, EthSubscriptionIdProvider, PubSubEvent},
-    state::{Filters, InternalApiConfig, RpcState, SealedL2BlockNumber},
+    state::{InstalledFilters, InternalApiConfig, RpcState, SealedL2BlockNumber},
 };
 use crate::{
     execution_sandbox::{BlockStartInfo, VmConcurrencyBarrier},
@@ -68,9 +86,11 @@ const NO_REQUESTS_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
 /// Time interval with no requests sent to the API server to declare that traffic to the server is ceased,
 /// and start gracefully shutting down the server.
 const SHUTDOWN_INTERVAL_WITHOUT_REQUESTS: Duration = Duration::from_millis(500);
+/// Interval to clean up the state of per-client rate limits for inactive clients.
+const CLIENT_RATE_LIMITS_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
 
 /// Represents all kinds of `Filter`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum TypedFilter {
     // Events from some block with additional filters
     Events(Filter, L2BlockNumber),
@@ -97,6 +117,9 @@ pub enum Namespace {
     En,
     Pubsub,
     Snapshots,
+    Txpool,
+    Trace,
+    Admin,
 }
 
 impl Namespace {
@@ -129,10 +152,20 @@ struct OptionalApiParams {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<MaxResponseSize>,
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    client_rate_limits: Option<ClientRateLimits>,
+    http_response_compression: bool,
+    method_filter: Option<MethodFilter>,
     tree_api: Option<Arc<dyn TreeApiClient>>,
     mempool_cache: Option<MempoolCache>,
+    filters_pool: Option<ConnectionPool<Core>>,
+    l2_block_listener_pool: Option<ConnectionPool<Core>>,
+    l1_batch_abort_handle: Option<L1BatchAbortHandle>,
+    fee_account_handle: Option<FeeAccountHandle>,
+    eth_sender_pause_handle: Option<EthSenderPauseHandle>,
+    admin_token: Option<Secret<String>>,
     extended_tracing: bool,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
+    load_shedding: Option<watch::Receiver<bool>>,
 }
 
 /// Structure capable of spawning a configured Web3 API server along with all the required
@@ -198,6 +231,15 @@ impl ApiBuilder {
         self
     }
 
+    /// Configures a private HTTP server exposing only the `admin` namespace (namespaces set via
+    /// [`Self::enable_api_namespaces()`] are ignored). The server listens on localhost only and rejects requests
+    /// without the `Authorization: Bearer <admin_token>` header. The `admin` namespace cannot be enabled for any other server.
+    pub fn admin_http(mut self, port: u16, admin_token: Secret<String>) -> Self {
+        self.transport = Some(ApiTransport::Http(([127, 0, 0, 1], port).into()));
+        self.optional.admin_token = Some(admin_token);
+        self
+    }
+
     /// Configures a dedicated DB pool to be used for updating different information,
     /// such as last mined block number or account nonces. This pool is used to execute
     /// in a background task. If not called, the main pool will be used. If the API server is under high load,
@@ -246,6 +288,34 @@ impl ApiBuilder {
         self
     }
 
+    /// Enables per-client rate limiting, with clients identified by HTTP request headers (see [`ClientRateLimits`]).
+    pub fn with_client_rate_limits(mut self, client_rate_limits: ClientRateLimits) -> Self {
+        if client_rate_limits.is_empty() {
+            self.optional.client_rate_limits = None;
+        } else {
+            self.optional.client_rate_limits = Some(client_rate_limits);
+        }
+        self
+    }
+
+    /// Enables compression of HTTP server responses negotiated via the `Accept-Encoding` request header.
+    /// Has no effect for the WebSocket server.
+    pub fn with_http_response_compression(mut self, enabled: bool) -> Self {
+        self.optional.http_response_compression = enabled;
+        self
+    }
+
+    /// Restricts the set of methods exposed by the server. Methods are filtered after enabled namespaces
+    /// are registered, so the filter cannot enable methods from the disabled namespaces.
+    pub fn with_method_filter(mut self, method_filter: MethodFilter) -> Self {
+        if method_filter.is_empty() {
+            self.optional.method_filter = None;
+        } else {
+            self.optional.method_filter = Some(method_filter);
+        }
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.optional.sync_state = Some(sync_state);
         self
@@ -277,11 +347,53 @@ impl ApiBuilder {
         self
     }
 
+    /// Configures a DB pool used to store persistent filters. The pool must be writable (i.e., it cannot
+    /// point to a read replica). Required if persistent filters are enabled in the API config.
+    pub fn with_filters_pool(mut self, pool: ConnectionPool<Core>) -> Self {
+        self.optional.filters_pool = Some(pool);
+        self
+    }
+
+    /// Configures a DB pool used to listen to notifications about new L2 blocks for the `eth_subscribe` notifiers.
+    /// The pool must be connected to the main database (i.e., it cannot point to a read replica). The listener
+    /// holds a connection from the pool for its entire lifetime, so a dedicated singleton pool should be used.
+    /// If not set, the notifiers poll the database for new L2 blocks.
+    pub fn with_l2_block_listener_pool(mut self, pool: ConnectionPool<Core>) -> Self {
+        self.optional.l2_block_listener_pool = Some(pool);
+        self
+    }
+
+    /// Allows the `admin` namespace to abort L1 batches in the state keeper paired with the `handle`.
+    pub fn with_l1_batch_abort_handle(mut self, handle: L1BatchAbortHandle) -> Self {
+        self.optional.l1_batch_abort_handle = Some(handle);
+        self
+    }
+
+    /// Allows the `admin` namespace to switch the operator fee account of the state keeper paired with the `handle`.
+    pub fn with_fee_account_handle(mut self, handle: FeeAccountHandle) -> Self {
+        self.optional.fee_account_handle = Some(handle);
+        self
+    }
+
+    /// Allows the `admin` namespace to pause and resume operations of the eth_sender paired with the `handle`.
+    pub fn with_eth_sender_pause_handle(mut self, handle: EthSenderPauseHandle) -> Self {
+        self.optional.eth_sender_pause_handle = Some(handle);
+        self
+    }
+
     pub fn with_extended_tracing(mut self, extended_tracing: bool) -> Self {
         self.optional.extended_tracing = extended_tracing;
         self
     }
 
+    /// Allows to reject read requests that require a DB connection while the provided signal is set.
+    /// This is used to keep connections of the API DB pool available for transaction submission
+    /// if the pool is saturated; requests necessary to submit transactions are not rejected.
+    pub fn with_load_shedding(mut self, signal: watch::Receiver<bool>) -> Self {
+        self.optional.load_shedding = Some(signal);
+        self
+    }
+
     // Intended for tests only.
     #[doc(hidden)]
     fn with_pub_sub_events(mut self, sender: mpsc::UnboundedSender<PubSubEvent>) -> Self {
@@ -300,7 +412,21 @@ impl ApiBuilder {
 impl ApiBuilder {
     pub fn build(self) -> anyhow::Result<ApiServer> {
         let transport = self.transport.context("API transport not set")?;
+        let is_admin = self.optional.admin_token.is_some();
+        let namespaces = if is_admin {
+            Some(vec![Namespace::Admin])
+        } else {
+            self.namespaces
+        };
+        if !is_admin
+            && namespaces
+                .as_ref()
+                .is_some_and(|namespaces| namespaces.contains(&Namespace::Admin))
+        {
+            anyhow::bail!("`admin` namespace can only be served by the private admin API server");
+        }
         let health_check_name = match &transport {
+            ApiTransport::Http(_) if is_admin => "admin_api",
             ApiTransport::Http(_) => "http_api",
             ApiTransport::WebSocket(_) => "ws_api",
         };
@@ -315,7 +441,7 @@ impl ApiBuilder {
             tx_sender: self.tx_sender.context("Transaction sender not set")?,
             polling_interval: self.polling_interval,
             pruning_info_refresh_interval: self.pruning_info_refresh_interval,
-            namespaces: self.namespaces.unwrap_or_else(|| {
+            namespaces: namespaces.unwrap_or_else(|| {
                 tracing::warn!(
                     "debug_ and snapshots_ API namespace will be disabled by default in ApiBuilder"
                 );
@@ -345,10 +471,20 @@ impl ApiServer {
         let installed_filters =
             if matches!(self.transport, ApiTransport::Http(_)) && self.config.filters_disabled {
                 None
+            } else if self.config.persistent_filters {
+                let filters_pool = self
+                    .optional
+                    .filters_pool
+                    .clone()
+                    .context("persistent filters are enabled, but filters pool is not set")?;
+                Some(Arc::new(InstalledFilters::persistent(
+                    filters_pool,
+                    self.optional.filters_limit,
+                )))
             } else {
-                Some(Arc::new(Mutex::new(Filters::new(
+                Some(Arc::new(InstalledFilters::in_memory(
                     self.optional.filters_limit,
-                ))))
+                )))
             };
 
         Ok(RpcState {
@@ -362,6 +498,7 @@ impl ApiServer {
             mempool_cache: self.optional.mempool_cache,
             last_sealed_l2_block,
             tree_api: self.optional.tree_api,
+            load_shedding: self.optional.load_shedding,
         })
     }
 
@@ -372,12 +509,19 @@ impl ApiServer {
     ) -> anyhow::Result<RpcModule<()>> {
         let namespaces = self.namespaces.clone();
         let zksync_network_id = self.config.l2_chain_id;
+        let l1_batch_abort_handle = self.optional.l1_batch_abort_handle.clone();
+        let fee_account_handle = self.optional.fee_account_handle.clone();
+        let eth_sender_pause_handle = self.optional.eth_sender_pause_handle.clone();
         let rpc_state = self.build_rpc_state(last_sealed_l2_block).await?;
 
         // Collect all the methods into a single RPC module.
         let mut rpc = RpcModule::new(());
         if let Some(pub_sub) = pub_sub {
-            rpc.merge(pub_sub.into_rpc())
+            if namespaces.contains(&Namespace::Zks) {
+                rpc.merge(ZksPubSubServer::into_rpc(pub_sub.clone()))
+                    .context("cannot merge zks pubsub namespace")?;
+            }
+            rpc.merge(EthPubSubServer::into_rpc(pub_sub))
                 .context("cannot merge eth pubsub namespace")?;
         }
 
@@ -406,9 +550,27 @@ impl ApiServer {
                 .context("cannot merge en namespace")?;
         }
         if namespaces.contains(&Namespace::Snapshots) {
-            rpc.merge(SnapshotsNamespace::new(rpc_state).into_rpc())
+            rpc.merge(SnapshotsNamespace::new(rpc_state.clone()).into_rpc())
                 .context("cannot merge snapshots namespace")?;
         }
+        if namespaces.contains(&Namespace::Txpool) {
+            rpc.merge(TxpoolNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge txpool namespace")?;
+        }
+        if namespaces.contains(&Namespace::Trace) {
+            rpc.merge(TraceNamespace::new(rpc_state.clone()).into_rpc())
+                .context("cannot merge trace namespace")?;
+        }
+        if namespaces.contains(&Namespace::Admin) {
+            let admin_namespace = AdminNamespace::new(
+                rpc_state,
+                l1_batch_abort_handle,
+                fee_account_handle,
+                eth_sender_pause_handle,
+            );
+            rpc.merge(admin_namespace.into_rpc())
+                .context("cannot merge admin namespace")?;
+        }
         Ok(rpc)
     }
 
@@ -491,6 +653,7 @@ impl ApiServer {
 
             tasks.extend(pub_sub.spawn_notifiers(
                 self.pool.clone(),
+                self.optional.l2_block_listener_pool.clone(),
                 self.polling_interval,
                 stop_receiver.clone(),
             ));
@@ -520,6 +683,20 @@ impl ApiServer {
 
     /// Overrides max response sizes for specific RPC methods by additionally wrapping their callbacks
     /// to which the max response size is passed as a param.
+    fn filter_methods(rpc: &mut RpcModule<()>, method_filter: &MethodFilter, transport_str: &str) {
+        let disabled_methods: Vec<_> = rpc
+            .method_names()
+            .filter(|&method_name| !method_filter.is_allowed(method_name))
+            .collect();
+        for &method_name in &disabled_methods {
+            rpc.remove_method(method_name);
+        }
+        tracing::info!(
+            "Disabled {} methods for {transport_str} server according to the method filter: {disabled_methods:?}",
+            disabled_methods.len()
+        );
+    }
+
     fn override_method_response_sizes(
         rpc: RpcModule<()>,
         response_size_overrides: &MaxResponseSizeOverrides,
@@ -623,6 +800,21 @@ impl ApiServer {
                 (u32::MAX, MaxResponseSizeOverrides::empty())
             };
         let websocket_requests_per_minute_limit = self.optional.websocket_requests_per_minute_limit;
+        let client_rate_limiter = self
+            .optional
+            .client_rate_limits
+            .as_ref()
+            .map(|limits| Arc::new(ClientRateLimiter::new(limits)));
+        let client_id_rules = self
+            .optional
+            .client_rate_limits
+            .as_ref()
+            .map(|limits| Arc::new(ClientIdRules::new(limits)));
+        let compress_responses = is_http && self.optional.http_response_compression;
+        let admin_auth = self.optional.admin_token.as_ref().map(|token| {
+            ValidateRequestHeaderLayer::<Bearer<hyper::Body>>::bearer(token.expose_secret())
+        });
+        let method_filter = self.optional.method_filter.clone();
         let subscriptions_limit = self.optional.subscriptions_limit;
         let vm_barrier = self.optional.vm_barrier.clone();
         let health_updater = self.health_updater.clone();
@@ -633,7 +825,10 @@ impl ApiServer {
             tracing::info!("Enabled extended call tracing for {transport_str} API server; this might negatively affect performance");
         }
 
-        let rpc = self.build_rpc_module(pub_sub, last_sealed_l2_block).await?;
+        let mut rpc = self.build_rpc_module(pub_sub, last_sealed_l2_block).await?;
+        if let Some(method_filter) = &method_filter {
+            Self::filter_methods(&mut rpc, method_filter, transport_str);
+        }
         let registered_method_names = Arc::new(rpc.method_names().collect::<HashSet<_>>());
         tracing::debug!(
             "Built RPC module for {transport_str} server with {} methods: {registered_method_names:?}",
@@ -650,6 +845,14 @@ impl ApiServer {
                 .allow_origin(tower_http::cors::Any)
                 .allow_headers([http::header::CONTENT_TYPE])
         });
+        // Setup response compression. The layer is always present since its presence changes the response body type;
+        // if compression is disabled, responses are passed through as is.
+        let compression = CompressionLayer::new().compress_when(DefaultPredicate::new().and(
+            move |_: http::StatusCode,
+                  _: http::Version,
+                  _: &http::HeaderMap,
+                  _: &http::Extensions| { compress_responses },
+        ));
         // Setup metrics for the number of in-flight requests.
         let (in_flight_requests, counter) = InFlightRequestsLayer::pair();
         tokio::spawn(
@@ -658,10 +861,28 @@ impl ApiServer {
                 future::ready(())
             }),
         );
+        if let Some(rate_limiter) = &client_rate_limiter {
+            let rate_limiter = Arc::downgrade(rate_limiter);
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(CLIENT_RATE_LIMITS_CLEANUP_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let Some(rate_limiter) = rate_limiter.upgrade() else {
+                        break; // The server has stopped
+                    };
+                    rate_limiter.retain_recent();
+                }
+            });
+        }
         // Assemble server middleware.
         let middleware = tower::ServiceBuilder::new()
             .layer(in_flight_requests)
-            .option_layer(cors);
+            .option_layer(cors)
+            .layer(compression)
+            .option_layer(client_id_rules.map(|rules| {
+                tower::layer::layer_fn(move |svc| ClientIdService::new(svc, rules.clone()))
+            }))
+            .option_layer(admin_auth);
 
         // Settings shared by HTTP and WS servers.
         let max_connections = !is_http
@@ -669,7 +890,14 @@ impl ApiServer {
             .flatten()
             .unwrap_or(5_000);
 
-        let metadata_layer = MetadataLayer::new(registered_method_names, method_tracer);
+        // Response size limits are additionally passed to method handlers via `MethodTracer`, so that handlers
+        // returning large lists of items can bail out early instead of assembling the entire response in memory.
+        let max_response_size = Arc::new(MaxResponseSize {
+            global: response_body_size_limit as usize,
+            overrides: max_response_size_overrides,
+        });
+        let metadata_layer =
+            MetadataLayer::new(registered_method_names, method_tracer, max_response_size);
         let metadata_layer = if extended_tracing {
             Either::Left(metadata_layer.with_param_tracing())
         } else {
@@ -694,6 +922,12 @@ impl ApiServer {
                 tower::layer::layer_fn(move |svc| {
                     LimitMiddleware::new(svc, websocket_requests_per_minute_limit)
                 })
+            }))
+            // Client-based limits are checked after session-based ones; both are reported by `metadata_layer`.
+            .option_layer(client_rate_limiter.map(|rate_limiter| {
+                tower::layer::layer_fn(move |svc| {
+                    ClientLimitMiddleware::new(svc, rate_limiter.clone())
+                })
             }));
 
         let server_builder = ServerBuilder::default()
@@ -703,26 +937,43 @@ impl ApiServer {
             .set_batch_request_config(batch_request_config)
             .set_rpc_middleware(rpc_middleware);
 
-        let (local_addr, server_handle) = if is_http {
+        let server_builder = if is_http {
             // HTTP-specific settings
-            let server = server_builder
-                .http_only()
-                .build(addr)
-                .await
-                .context("Failed building HTTP JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            server_builder.http_only()
         } else {
             // WS-specific settings
-            let server = server_builder
-                .set_id_provider(EthSubscriptionIdProvider)
-                .build(addr)
-                .await
-                .context("Failed building WS JSON-RPC server")?;
-            (server.local_addr(), server.start(rpc))
+            server_builder.set_id_provider(EthSubscriptionIdProvider)
         };
-        let local_addr = local_addr.with_context(|| {
-            format!("Failed getting local address for {transport_str} JSON-RPC server")
-        })?;
+
+        // We drive the server using `hyper` directly, so that the peer address of each connection
+        // is available to the middleware (e.g., to identify clients for rate limiting).
+        let service_builder = server_builder.to_service_builder();
+        let methods = Methods::from(rpc);
+        let (stop_handle, server_handle) = stop_channel();
+        let shutdown_stop_handle = stop_handle.clone();
+        let make_service = make_service_fn(move |conn: &AddrStream| {
+            let peer_addr = PeerAddr(conn.remote_addr());
+            let service = service_builder
+                .clone()
+                .build(methods.clone(), stop_handle.clone());
+            let service = tower::ServiceBuilder::new()
+                .map_request(move |mut request: hyper::Request<hyper::Body>| {
+                    request.extensions_mut().insert(peer_addr);
+                    request
+                })
+                .service(service);
+            future::ok::<_, Infallible>(service)
+        });
+        let server = hyper::Server::try_bind(&addr)
+            .with_context(|| format!("Failed binding {transport_str} JSON-RPC server"))?
+            .serve(make_service);
+        let local_addr = server.local_addr();
+        let server = server.with_graceful_shutdown(shutdown_stop_handle.shutdown());
+        tokio::spawn(async move {
+            if let Err(err) = server.await {
+                tracing::error!("{transport_str} JSON-RPC server failed: {err}");
+            }
+        });
         tracing::info!("Initialized {transport_str} API on {local_addr:?}");
         local_addr_sender.send(local_addr).ok();
         health_updater.update(HealthStatus::Ready.into());
@@ -0,0 +1,152 @@
+use anyhow::Context as _;
+use zksync_dal::CoreDal;
+use zksync_eth_sender::EthSenderPauseHandle;
+use zksync_state_keeper::{FeeAccountHandle, L1BatchAbortError, L1BatchAbortHandle};
+use zksync_types::{
+    api::{AbortedL1Batch, EthSenderOperation, L1BatchL1Cost},
+    Address, L1BatchNumber,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, state::RpcState};
+
+#[derive(Debug, Clone)]
+pub(crate) struct AdminNamespace {
+    state: RpcState,
+    l1_batch_abort_handle: Option<L1BatchAbortHandle>,
+    fee_account_handle: Option<FeeAccountHandle>,
+    eth_sender_pause_handle: Option<EthSenderPauseHandle>,
+}
+
+impl AdminNamespace {
+    pub fn new(
+        state: RpcState,
+        l1_batch_abort_handle: Option<L1BatchAbortHandle>,
+        fee_account_handle: Option<FeeAccountHandle>,
+        eth_sender_pause_handle: Option<EthSenderPauseHandle>,
+    ) -> Self {
+        Self {
+            state,
+            l1_batch_abort_handle,
+            fee_account_handle,
+            eth_sender_pause_handle,
+        }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn abort_l1_batch_impl(&self) -> Result<AbortedL1Batch, Web3Error> {
+        // The handle is only available if the state keeper runs in the same process as the server.
+        let handle = self
+            .l1_batch_abort_handle
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        let aborted_batch = handle.abort_l1_batch().await.map_err(|err| match err {
+            L1BatchAbortError::HasSealedL2Blocks(number) => Web3Error::L1BatchNotAbortable(number),
+            L1BatchAbortError::Internal(err) => Web3Error::InternalError(err),
+        })?;
+        tracing::info!(
+            "Aborted L1 batch #{} on operator request",
+            aborted_batch.l1_batch_number
+        );
+        Ok(aborted_batch)
+    }
+
+    fn fee_account_handle(&self) -> Result<&FeeAccountHandle, Web3Error> {
+        // Same as the abort handle, the fee account handle is only available for the colocated state keeper.
+        self.fee_account_handle
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)
+    }
+
+    pub fn get_fee_account_impl(&self) -> Result<Address, Web3Error> {
+        Ok(self.fee_account_handle()?.address())
+    }
+
+    pub fn set_fee_account_impl(&self, address: Address) -> Result<Address, Web3Error> {
+        let prev_address = self.fee_account_handle()?.set(address);
+        tracing::info!("Set operator fee account to {address:?} on operator request");
+        Ok(prev_address)
+    }
+
+    fn eth_sender_pause_handle(&self) -> Result<&EthSenderPauseHandle, Web3Error> {
+        // The handle is only available if the eth_sender aggregator runs in the same process as the server.
+        self.eth_sender_pause_handle
+            .as_ref()
+            .ok_or(Web3Error::MethodNotImplemented)
+    }
+
+    pub async fn get_paused_eth_sender_operations_impl(
+        &self,
+    ) -> Result<Vec<EthSenderOperation>, Web3Error> {
+        let handle = self.eth_sender_pause_handle()?;
+        let mut storage = self.state.acquire_connection().await?;
+        handle
+            .refresh(&mut storage)
+            .await
+            .context("refresh paused operations")?;
+        let paused_operations = handle.paused_operations();
+        Ok(paused_operations.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn pause_eth_sender_operation_impl(
+        &self,
+        operation: EthSenderOperation,
+    ) -> Result<bool, Web3Error> {
+        let handle = self.eth_sender_pause_handle()?;
+        let mut storage = self.state.acquire_connection().await?;
+        let was_paused = handle
+            .pause(&mut storage, operation.into())
+            .await
+            .context("pause operation")?;
+        tracing::info!("Paused eth_sender operation {operation:?} on operator request");
+        Ok(was_paused)
+    }
+
+    pub async fn resume_eth_sender_operation_impl(
+        &self,
+        operation: EthSenderOperation,
+    ) -> Result<bool, Web3Error> {
+        let handle = self.eth_sender_pause_handle()?;
+        let mut storage = self.state.acquire_connection().await?;
+        let was_paused = handle
+            .resume(&mut storage, operation.into())
+            .await
+            .context("resume operation")?;
+        tracing::info!("Resumed eth_sender operation {operation:?} on operator request");
+        Ok(was_paused)
+    }
+
+    pub async fn get_l1_batch_l1_costs_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<L1BatchL1Cost>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let costs = storage
+            .eth_sender_dal()
+            .get_l1_batch_l1_costs(l1_batch_number)
+            .await
+            .context("get_l1_batch_l1_costs")?;
+
+        let mut api_costs = Vec::with_capacity(costs.len());
+        for cost in costs {
+            let eth_tx_hash = storage
+                .eth_sender_dal()
+                .get_confirmed_tx_hash_by_eth_tx_id(cost.eth_tx_id)
+                .await?;
+            api_costs.push(L1BatchL1Cost {
+                operation: cost.tx_type.into(),
+                eth_tx_hash,
+                l1_batches_in_tx: cost.l1_batches_in_tx,
+                gas_used: cost.gas_used.into(),
+                gas_price: cost.gas_price,
+                blob_gas_used: cost.blob_gas_used.into(),
+                blob_gas_price: cost.blob_gas_price,
+                total_cost: cost.total_cost(),
+            });
+        }
+        Ok(api_costs)
+    }
+}
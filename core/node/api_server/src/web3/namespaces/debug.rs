@@ -1,27 +1,126 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Context as _;
-use multivm::{interface::ExecutionResult, vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT};
+use multivm::{
+    interface::ExecutionResult, tracers::prestate_tracer,
+    vm_latest::constants::BATCH_COMPUTATIONAL_GAS_LIMIT,
+};
 use once_cell::sync::OnceCell;
-use zksync_dal::{CoreDal, DalError};
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_system_constants::MAX_ENCODED_TX_SIZE;
 use zksync_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugTrace, Prestate, PrestateAccount, PrestateDiff,
+        ResultDebugCall, ResultDebugTrace, SupportedTracers, TracerConfig,
+    },
     debug_flat_call::{flatten_debug_calls, DebugCallFlat},
     fee_model::BatchFeeInput,
     l2::L2Tx,
     transaction_request::CallRequest,
     vm_trace::Call,
-    AccountTreeId, H256,
+    AccountTreeId, L2BlockNumber, H256,
 };
+use zksync_utils::u256_to_h256;
 use zksync_web3_decl::error::Web3Error;
 
 use crate::{
     execution_sandbox::{ApiTracer, TxSharedArgs},
     tx_sender::{ApiContracts, TxSenderConfig},
-    web3::{backend_jsonrpsee::MethodTracer, state::RpcState},
+    web3::{
+        backend_jsonrpsee::{MethodTracer, ResponseSizeTracker},
+        state::RpcState,
+    },
 };
 
+/// Number of call traces loaded from the storage at a time when tracing an L2 block.
+const TRACES_PAGE_SIZE: usize = 100;
+
+/// Converts the output of the VM prestate tracer to the API representation.
+fn prestate_from_tracer(state: prestate_tracer::State) -> Prestate {
+    state
+        .into_iter()
+        .map(|(address, account)| {
+            let account = PrestateAccount {
+                balance: account.balance,
+                nonce: account.nonce,
+                code_hash: account.code.map(u256_to_h256),
+                storage: account.storage.unwrap_or_default().into_iter().collect(),
+            };
+            (address, account)
+        })
+        .collect()
+}
+
+fn empty_prestate_trace(diff_mode: bool) -> DebugTrace {
+    if diff_mode {
+        DebugTrace::PrestateDiff(PrestateDiff::default())
+    } else {
+        DebugTrace::Prestate(Prestate::default())
+    }
+}
+
+/// Computes prestate traces for all transactions in the specified L2 block based on the persisted storage logs.
+/// Since only storage writes are persisted, traces only contain written slots; in particular, balances and nonces
+/// are represented by the corresponding slots of system contracts.
+async fn prestate_traces_for_l2_block(
+    connection: &mut Connection<'_, Core>,
+    block_number: L2BlockNumber,
+    diff_mode: bool,
+) -> Result<HashMap<H256, DebugTrace>, Web3Error> {
+    let logs = connection
+        .storage_logs_dal()
+        .get_storage_logs_for_l2_block(block_number)
+        .await
+        .map_err(DalError::generalize)?;
+    let mut current_values = if let Some(prev_block_number) = block_number.0.checked_sub(1) {
+        let hashed_keys: Vec<_> = logs.iter().map(|log| log.hashed_key).collect();
+        connection
+            .storage_logs_dal()
+            .get_storage_values(&hashed_keys, L2BlockNumber(prev_block_number))
+            .await
+            .map_err(DalError::generalize)?
+    } else {
+        HashMap::new()
+    };
+
+    let mut diffs = HashMap::<H256, PrestateDiff>::new();
+    for log in logs {
+        let diff = diffs.entry(log.tx_hash).or_default();
+        let prev_value = current_values
+            .get(&log.hashed_key)
+            .copied()
+            .flatten()
+            .unwrap_or_default();
+        let pre_storage = &mut diff.pre.entry(log.address).or_default().storage;
+        pre_storage.entry(log.key).or_insert(prev_value);
+        let post_storage = &mut diff.post.entry(log.address).or_default().storage;
+        post_storage.insert(log.key, log.value);
+        current_values.insert(log.hashed_key, Some(log.value));
+    }
+
+    let traces = diffs.into_iter().map(|(tx_hash, mut diff)| {
+        let trace = if diff_mode {
+            // Like geth, only report slots that were actually changed by the transaction.
+            for (address, post_account) in &mut diff.post {
+                let pre_account = diff.pre.get_mut(address).unwrap();
+                pre_account
+                    .storage
+                    .retain(|key, value| post_account.storage[key] != *value);
+                post_account
+                    .storage
+                    .retain(|key, _| pre_account.storage.contains_key(key));
+            }
+            diff.pre.retain(|_, account| !account.storage.is_empty());
+            diff.post.retain(|_, account| !account.storage.is_empty());
+            DebugTrace::PrestateDiff(diff)
+        } else {
+            DebugTrace::Prestate(diff.pre)
+        };
+        (tx_hash, trace)
+    });
+    Ok(traces.collect())
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct DebugNamespace {
     batch_fee_input: BatchFeeInput,
@@ -61,33 +160,87 @@ impl DebugNamespace {
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
-    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+    ) -> Result<Vec<ResultDebugTrace>, Web3Error> {
         self.current_method().set_block_id(block_id);
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let TracerConfig {
+            tracer,
+            tracer_config,
+        } = options.unwrap_or_default();
         let mut connection = self.state.acquire_connection().await?;
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.current_method()
             .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
 
-        let call_traces = connection
-            .blocks_web3_dal()
-            .get_traces_for_l2_block(block_number)
-            .await
-            .map_err(DalError::generalize)?;
-        let call_trace = call_traces
+        let mut response_size = self.current_method().response_size_tracker();
+        let traces: Vec<DebugTrace> = match tracer {
+            SupportedTracers::CallTracer => {
+                let call_traces = Self::call_traces_for_l2_block(
+                    &mut connection,
+                    block_number,
+                    tracer_config.only_top_call,
+                    &mut response_size,
+                )
+                .await?;
+                call_traces
+                    .into_iter()
+                    .map(|ResultDebugCall { result }| result.into())
+                    .collect()
+            }
+            SupportedTracers::PrestateTracer => {
+                let diff_mode = tracer_config.diff_mode;
+                let mut prestate_traces =
+                    prestate_traces_for_l2_block(&mut connection, block_number, diff_mode).await?;
+                let transactions = connection
+                    .transactions_web3_dal()
+                    .get_raw_l2_block_transactions(block_number)
+                    .await
+                    .map_err(DalError::generalize)?;
+                let traces = transactions.iter().map(|tx| {
+                    let trace = prestate_traces
+                        .remove(&tx.hash())
+                        .unwrap_or_else(|| empty_prestate_trace(diff_mode));
+                    response_size.observe(&trace)?;
+                    Ok::<_, Web3Error>(trace)
+                });
+                traces.collect::<Result<_, _>>()?
+            }
+        };
+        Ok(traces
             .into_iter()
-            .map(|call_trace| {
+            .map(|result| ResultDebugTrace { result })
+            .collect())
+    }
+
+    async fn call_traces_for_l2_block(
+        connection: &mut Connection<'_, Core>,
+        block_number: L2BlockNumber,
+        only_top_call: bool,
+        response_size: &mut ResponseSizeTracker,
+    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+        // Traces are loaded in pages, so that an oversized response is detected before all traces
+        // are loaded into memory.
+        let mut results = vec![];
+        loop {
+            let call_traces = connection
+                .blocks_web3_dal()
+                .get_traces_for_l2_block(block_number, results.len(), TRACES_PAGE_SIZE)
+                .await
+                .map_err(DalError::generalize)?;
+            let is_last_page = call_traces.len() < TRACES_PAGE_SIZE;
+            for call_trace in call_traces {
                 let mut result: DebugCall = call_trace.into();
                 if only_top_call {
                     result.calls = vec![];
                 }
-                ResultDebugCall { result }
-            })
-            .collect();
-        Ok(call_trace)
+                let result = ResultDebugCall { result };
+                response_size.observe(&result)?;
+                results.push(result);
+            }
+            if is_last_page {
+                return Ok(results);
+            }
+        }
     }
 
     pub async fn debug_trace_block_flat_impl(
@@ -95,7 +248,25 @@ impl DebugNamespace {
         block_id: BlockId,
         options: Option<TracerConfig>,
     ) -> Result<Vec<DebugCallFlat>, Web3Error> {
-        let call_trace = self.debug_trace_block_impl(block_id, options).await?;
+        self.current_method().set_block_id(block_id);
+        let only_top_call = options
+            .map(|options| options.tracer_config.only_top_call)
+            .unwrap_or(false);
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        // The size of flattened traces is estimated by the size of the original traces. This underestimates
+        // the response size, but allows to bail out early for blocks with extremely large traces.
+        let mut response_size = self.current_method().response_size_tracker();
+        let call_trace = Self::call_traces_for_l2_block(
+            &mut connection,
+            block_number,
+            only_top_call,
+            &mut response_size,
+        )
+        .await?;
         let call_trace_flat = flatten_debug_calls(call_trace);
         Ok(call_trace_flat)
     }
@@ -104,23 +275,46 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Result<Option<DebugCall>, Web3Error> {
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+    ) -> Result<Option<DebugTrace>, Web3Error> {
+        let TracerConfig {
+            tracer,
+            tracer_config,
+        } = options.unwrap_or_default();
         let mut connection = self.state.acquire_connection().await?;
-        let call_trace = connection
-            .transactions_dal()
-            .get_call_trace(tx_hash)
-            .await
-            .map_err(DalError::generalize)?;
-        Ok(call_trace.map(|call_trace| {
-            let mut result: DebugCall = call_trace.into();
-            if only_top_call {
-                result.calls = vec![];
+        match tracer {
+            SupportedTracers::CallTracer => {
+                let call_trace = connection
+                    .transactions_dal()
+                    .get_call_trace(tx_hash)
+                    .await
+                    .map_err(DalError::generalize)?;
+                Ok(call_trace.map(|call_trace| {
+                    let mut result: DebugCall = call_trace.into();
+                    if tracer_config.only_top_call {
+                        result.calls = vec![];
+                    }
+                    result.into()
+                }))
+            }
+            SupportedTracers::PrestateTracer => {
+                let receipts = connection
+                    .transactions_web3_dal()
+                    .get_transaction_receipts(&[tx_hash])
+                    .await
+                    .map_err(DalError::generalize)?;
+                let Some(receipt) = receipts.into_iter().next() else {
+                    return Ok(None);
+                };
+                let block_number = L2BlockNumber(receipt.block_number.as_u32());
+                let diff_mode = tracer_config.diff_mode;
+                let mut prestate_traces =
+                    prestate_traces_for_l2_block(&mut connection, block_number, diff_mode).await?;
+                let trace = prestate_traces
+                    .remove(&tx_hash)
+                    .unwrap_or_else(|| empty_prestate_trace(diff_mode));
+                Ok(Some(trace))
             }
-            result
-        }))
+        }
     }
 
     pub async fn debug_trace_call_impl(
@@ -128,13 +322,14 @@ impl DebugNamespace {
         mut request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTrace, Web3Error> {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let TracerConfig {
+            tracer,
+            tracer_config,
+        } = options.unwrap_or_default();
 
         let mut connection = self.state.acquire_connection().await?;
         let block_args = self
@@ -174,10 +369,14 @@ impl DebugNamespace {
 
         // We don't need properly trace if we only need top call
         let call_tracer_result = Arc::new(OnceCell::default());
-        let custom_tracers = if only_top_call {
-            vec![]
-        } else {
-            vec![ApiTracer::CallTracer(call_tracer_result.clone())]
+        let prestate_tracer_result = Arc::new(OnceCell::default());
+        let custom_tracers = match tracer {
+            SupportedTracers::CallTracer if tracer_config.only_top_call => vec![],
+            SupportedTracers::CallTracer => vec![ApiTracer::CallTracer(call_tracer_result.clone())],
+            SupportedTracers::PrestateTracer => vec![ApiTracer::PrestateTracer {
+                diff_mode: tracer_config.diff_mode,
+                result: prestate_tracer_result.clone(),
+            }],
         };
 
         let executor = &self.state.tx_sender.0.executor;
@@ -205,6 +404,19 @@ impl DebugNamespace {
             }
         };
 
+        if tracer == SupportedTracers::PrestateTracer {
+            let (pre, post) = Arc::try_unwrap(prestate_tracer_result)
+                .unwrap()
+                .take()
+                .unwrap_or_default();
+            let (pre, post) = (prestate_from_tracer(pre), prestate_from_tracer(post));
+            return Ok(if tracer_config.diff_mode {
+                DebugTrace::PrestateDiff(PrestateDiff { pre, post })
+            } else {
+                DebugTrace::Prestate(pre)
+            });
+        }
+
         // We had only one copy of Arc this arc is already dropped it's safe to unwrap
         let trace = Arc::try_unwrap(call_tracer_result)
             .unwrap()
@@ -219,7 +431,7 @@ impl DebugNamespace {
             revert_reason,
             trace,
         );
-        Ok(call.into())
+        Ok(DebugCall::from(call).into())
     }
 
     async fn shared_args(&self) -> TxSharedArgs {
@@ -1,29 +1,69 @@
 use anyhow::Context as _;
-use zksync_dal::{CoreDal, DalError};
+use multivm::interface::ExecutionResult;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        AccessListResult, BlockId, BlockNumber, EthProof, GetLogsFilter, LogCursor,
+        SimulatePayload, SimulatedBlock, SimulatedCall, SimulatedCallError, StorageProof,
+        Transaction, TransactionId, TransactionReceipt, TransactionVariant,
     },
+    get_code_key, get_nonce_key,
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
-    utils::decompose_full_nonce,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
     web3::{self, Bytes, FeeHistory, SyncInfo, SyncState},
-    AccountTreeId, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
+    AccountTreeId, L1BatchNumber, L2BlockNumber, StorageKey, H256, L2_BASE_TOKEN_ADDRESS, U256,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{bytecode::validate_bytecode, h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{
     error::Web3Error,
     types::{Address, Block, Filter, FilterChanges, Log, U64},
 };
 
-use crate::web3::{
-    backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter,
+use crate::{
+    execution_sandbox::{SimulatedBlockOutput, SimulationBlock},
+    tx_sender::SubmitTxError,
+    web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, state::RpcState, TypedFilter},
 };
 
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
+/// Number of logs loaded from the storage at a time when serving logs for a filter.
+const LOGS_PAGE_SIZE: usize = 1_000;
+
+/// Converts addresses and topics from the API filter into a DAL filter for the specified block range.
+pub(crate) fn get_logs_filter(
+    filter: &Filter,
+    from_block: L2BlockNumber,
+    to_block: L2BlockNumber,
+) -> Result<GetLogsFilter, Web3Error> {
+    let addresses = if let Some(addresses) = &filter.address {
+        addresses.0.clone()
+    } else {
+        vec![]
+    };
+    let topics = if let Some(topics) = &filter.topics {
+        if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
+            return Err(Web3Error::TooManyTopics);
+        }
+        let topics_by_idx = topics
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, topics)| Some((idx as u32 + 1, topics.as_ref()?.0.clone())));
+        topics_by_idx.collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    Ok(GetLogsFilter {
+        from_block,
+        to_block,
+        addresses,
+        topics,
+        after: None,
+    })
+}
 
 #[derive(Debug)]
 pub(crate) struct EthNamespace {
@@ -92,6 +132,291 @@ impl EthNamespace {
         Ok(call_result.into())
     }
 
+    pub async fn create_access_list_impl(
+        &self,
+        mut request: CallRequest,
+        block_id: Option<BlockId>,
+    ) -> Result<AccessListResult, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        if request.gas.is_none() {
+            request.gas = Some(
+                self.state
+                    .tx_sender
+                    .get_default_eth_call_gas(block_args)
+                    .await
+                    .map_err(Web3Error::InternalError)?
+                    .into(),
+            )
+        }
+        let call_overrides = request.get_call_overrides()?;
+        let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+
+        let (result, access_list) = self
+            .state
+            .tx_sender
+            .create_access_list(block_args, call_overrides, tx)
+            .await?;
+        let gas_used = result.statistics.gas_used.into();
+        // Reverted calls still produce an access list, but halted ones are treated as errors.
+        let error = match result.result {
+            ExecutionResult::Success { .. } => None,
+            ExecutionResult::Revert { output } => Some(format!(
+                "execution reverted: {}",
+                output.to_user_friendly_string()
+            )),
+            ExecutionResult::Halt { reason } => {
+                return Err(Web3Error::SubmitTransactionError(
+                    reason.to_string(),
+                    vec![],
+                ))
+            }
+        };
+        Ok(AccessListResult {
+            access_list,
+            gas_used,
+            error,
+        })
+    }
+
+    pub async fn simulate_v1_impl(
+        &self,
+        payload: SimulatePayload,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        self.current_method().set_block_id(block_id);
+        let enforced_base_fee = Self::validate_simulation(&payload)?;
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_args = self
+            .state
+            .resolve_block_args(&mut connection, block_id)
+            .await?;
+        self.current_method().set_block_diff(
+            self.state
+                .last_sealed_l2_block
+                .diff_with_block_args(&block_args),
+        );
+        drop(connection);
+
+        let default_gas = self
+            .state
+            .tx_sender
+            .get_default_eth_call_gas(block_args)
+            .await
+            .map_err(Web3Error::InternalError)?;
+        let mut blocks = Vec::with_capacity(payload.block_state_calls.len());
+        let mut gas_limits = Vec::<Vec<U256>>::with_capacity(payload.block_state_calls.len());
+        for block in &payload.block_state_calls {
+            let mut txs = Vec::with_capacity(block.calls.len());
+            for call in &block.calls {
+                let mut call = call.clone();
+                if call.gas.is_none() {
+                    call.gas = Some(default_gas.into());
+                }
+                txs.push(L2Tx::from_request(
+                    call.into(),
+                    self.state.api_config.max_tx_size,
+                )?);
+            }
+            gas_limits.push(txs.iter().map(|tx| tx.common_data.fee.gas_limit).collect());
+            blocks.push(SimulationBlock {
+                timestamp: block
+                    .block_overrides
+                    .as_ref()
+                    .and_then(|overrides| overrides.time)
+                    .map(|time| time.as_u64()),
+                state_override: block.state_overrides.clone(),
+                txs,
+            });
+        }
+
+        let outputs = self
+            .state
+            .tx_sender
+            .simulate(block_args, enforced_base_fee, blocks)
+            .await?;
+        Self::check_simulated_block_envs(&payload, &outputs)?;
+        Ok(outputs
+            .into_iter()
+            .zip(gas_limits)
+            .map(|(output, gas_limits)| Self::simulated_block(output, gas_limits))
+            .collect())
+    }
+
+    /// Validates an `eth_simulateV1` payload and returns the overridden base fee.
+    fn validate_simulation(payload: &SimulatePayload) -> Result<Option<u64>, Web3Error> {
+        const MAX_SIMULATED_BLOCKS: usize = 256;
+
+        let invalid = |message: &str| Err(Web3Error::InvalidSimulation(message.to_owned()));
+        if payload.validation || payload.trace_transfers {
+            return invalid("`validation` and `traceTransfers` options are not supported");
+        }
+        if payload.block_state_calls.is_empty() {
+            return invalid("no blocks to simulate");
+        }
+        if payload.block_state_calls.len() > MAX_SIMULATED_BLOCKS {
+            return invalid("too many blocks to simulate");
+        }
+
+        let mut enforced_base_fee = None;
+        for (i, block) in payload.block_state_calls.iter().enumerate() {
+            if block.calls.is_empty() {
+                return invalid("each simulated block must contain at least one call");
+            }
+            if let Some(base_fee) = block
+                .block_overrides
+                .as_ref()
+                .and_then(|overrides| overrides.base_fee_per_gas)
+            {
+                if i > 0 {
+                    return invalid(
+                        "base fee can only be overridden for the first simulated block",
+                    );
+                }
+                if base_fee > U256::from(u64::MAX) {
+                    return invalid("base fee override is too large");
+                }
+                enforced_base_fee = Some(base_fee.as_u64());
+            }
+
+            for account in block
+                .state_overrides
+                .iter()
+                .flat_map(|overrides| overrides.values())
+            {
+                if account.state.is_some() && account.state_diff.is_some() {
+                    return invalid("`state` and `stateDiff` overrides are mutually exclusive");
+                }
+                if account.state.is_some() && i > 0 {
+                    return invalid(
+                        "full storage override (`state`) is only supported for the first simulated block",
+                    );
+                }
+                if let Some(code) = &account.code {
+                    if let Err(err) = validate_bytecode(&code.0) {
+                        return Err(Web3Error::InvalidSimulation(format!(
+                            "invalid bytecode override: {err}"
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(enforced_base_fee)
+    }
+
+    /// Checks that the block numbers and timestamps requested via block overrides were used by the simulation.
+    fn check_simulated_block_envs(
+        payload: &SimulatePayload,
+        outputs: &[SimulatedBlockOutput],
+    ) -> Result<(), Web3Error> {
+        for (block, output) in payload.block_state_calls.iter().zip(outputs) {
+            let Some(overrides) = &block.block_overrides else {
+                continue;
+            };
+            if let Some(number) = overrides.number {
+                if number != U64::from(output.env.number) {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block number override {number} does not match the simulated block number {}",
+                        output.env.number
+                    )));
+                }
+            }
+            if let Some(time) = overrides.time {
+                if time != U64::from(output.env.timestamp) {
+                    return Err(Web3Error::InvalidSimulation(format!(
+                        "block timestamps must strictly increase; timestamp {time} is invalid"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn simulated_block(output: SimulatedBlockOutput, gas_limits: Vec<U256>) -> SimulatedBlock {
+        let mut log_index = 0_u64;
+        let mut block_gas_used = U256::zero();
+        let calls = output.results.into_iter().zip(gas_limits).enumerate();
+        let calls = calls.map(|(tx_index, (result, gas_limit))| {
+            let gas_used = gas_limit.saturating_sub(result.refunds.gas_refunded.into());
+            block_gas_used += gas_used;
+            let (status, return_data, error) = match result.result {
+                ExecutionResult::Success { output } => (1_u64, output, None),
+                ExecutionResult::Revert { output } => {
+                    let data = output.encoded_data();
+                    let err = SubmitTxError::ExecutionReverted(
+                        output.to_user_friendly_string(),
+                        data.clone(),
+                    );
+                    let error = SimulatedCallError {
+                        code: 3,
+                        message: err.to_string(),
+                        data: Some(data.clone().into()),
+                    };
+                    (0_u64, data, Some(error))
+                }
+                ExecutionResult::Halt { .. } => unreachable!("halted calls are returned as errors"),
+            };
+
+            let logs = if error.is_none() {
+                result.logs.events
+            } else {
+                vec![]
+            };
+            let logs = logs.into_iter().enumerate().map(|(i, event)| {
+                let log = Log {
+                    address: event.address,
+                    topics: event.indexed_topics,
+                    data: event.value.into(),
+                    block_hash: Some(output.hash),
+                    block_number: Some(output.env.number.into()),
+                    l1_batch_number: None,
+                    transaction_hash: None,
+                    transaction_index: Some(tx_index.into()),
+                    log_index: Some(log_index.into()),
+                    transaction_log_index: Some(i.into()),
+                    log_type: None,
+                    removed: Some(false),
+                };
+                log_index += 1;
+                log
+            });
+
+            SimulatedCall {
+                status: status.into(),
+                return_data: return_data.into(),
+                gas_used,
+                logs: logs.collect(),
+                error,
+            }
+        });
+        let calls = calls.collect();
+
+        SimulatedBlock {
+            number: output.env.number.into(),
+            hash: output.hash,
+            parent_hash: output.env.prev_block_hash,
+            timestamp: output.env.timestamp.into(),
+            base_fee_per_gas: output.base_fee.into(),
+            gas_used: block_gas_used,
+            calls,
+        }
+    }
+
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
@@ -198,7 +523,7 @@ impl EthNamespace {
             .as_ref()
             .ok_or(Web3Error::MethodNotImplemented)?;
         // We clone the filter to not hold the filter lock for an extended period of time.
-        let maybe_filter = installed_filters.lock().await.get_and_update_stats(idx);
+        let maybe_filter = installed_filters.get_and_update_stats(idx).await?;
 
         let Some(TypedFilter::Events(filter, _)) = maybe_filter else {
             return Err(Web3Error::FilterNotFound);
@@ -206,7 +531,7 @@ impl EthNamespace {
 
         let from_block = self
             .state
-            .resolve_filter_block_number(filter.from_block)
+            .resolve_filter_from_block(filter.from_block)
             .await?;
         let logs = self
             .filter_changes(&mut TypedFilter::Events(filter, from_block))
@@ -333,22 +658,23 @@ impl EthNamespace {
         else {
             return Ok(None);
         };
-        let Some(block) = storage
-            .blocks_web3_dal()
-            .get_api_block(block_number)
-            .await
-            .map_err(DalError::generalize)?
-        else {
-            return Ok(None);
-        };
-        self.set_block_diff(block_number); // only report block diff for existing L2 blocks
-
-        let mut receipts = storage
+        let receipts = storage
             .transactions_web3_dal()
-            .get_transaction_receipts(&block.transactions)
+            .get_l2_block_receipts(block_number)
             .await
-            .with_context(|| format!("get_transaction_receipts({block_number})"))?;
-        receipts.sort_unstable_by_key(|receipt| receipt.transaction_index);
+            .map_err(DalError::generalize)?;
+        // An empty list of receipts can correspond either to an empty block, or to a non-existing one.
+        if receipts.is_empty() {
+            let block_hash = storage
+                .blocks_web3_dal()
+                .get_l2_block_hash(block_number)
+                .await
+                .map_err(DalError::generalize)?;
+            if block_hash.is_none() {
+                return Ok(None);
+            }
+        }
+        self.set_block_diff(block_number); // only report block diff for existing L2 blocks
         Ok(Some(receipts))
     }
 
@@ -397,6 +723,113 @@ impl EthNamespace {
         Ok(value)
     }
 
+    /// Returns the latest L1 batch fully included in L2 blocks up to and including `block_number`.
+    async fn resolve_l1_batch_for_proof(
+        connection: &mut Connection<'_, Core>,
+        block_number: L2BlockNumber,
+    ) -> Result<Option<L1BatchNumber>, Web3Error> {
+        let l1_batch_number = connection
+            .blocks_web3_dal()
+            .get_l1_batch_number_of_l2_block(block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(l1_batch_number) = l1_batch_number else {
+            // The block is not included into an L1 batch yet, so all sealed batches precede it.
+            return Ok(connection
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .map_err(DalError::generalize)?);
+        };
+
+        let (_, last_l2_block) = connection
+            .blocks_web3_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no L2 blocks"))?;
+        Ok(if last_l2_block == block_number {
+            Some(l1_batch_number)
+        } else {
+            l1_batch_number.0.checked_sub(1).map(L1BatchNumber)
+        })
+    }
+
+    pub async fn get_proof_impl(
+        &self,
+        address: Address,
+        keys: Vec<H256>,
+        block_id: Option<BlockId>,
+    ) -> Result<Option<EthProof>, Web3Error> {
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Latest));
+        self.current_method().set_block_id(block_id);
+
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.set_block_diff(block_number);
+        let Some(l1_batch_number) =
+            Self::resolve_l1_batch_for_proof(&mut connection, block_number).await?
+        else {
+            return Ok(None);
+        };
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut connection)
+            .await?;
+        let Some(storage_hash) = connection
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            // The L1 batch is not processed by the Merkle tree yet.
+            return Ok(None);
+        };
+        drop(connection);
+
+        let account_keys = [
+            storage_key_for_eth_balance(&address),
+            get_nonce_key(&address),
+            get_code_key(&address),
+        ];
+        let storage_keys = keys
+            .iter()
+            .map(|key| StorageKey::new(AccountTreeId::new(address), *key));
+        let account_key_count = account_keys.len();
+        let all_keys: Vec<_> = account_keys.into_iter().chain(storage_keys).collect();
+        let hashed_keys = all_keys.iter().map(StorageKey::hashed_key_u256).collect();
+        let Some(proofs) = self
+            .state
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut proofs = all_keys
+            .iter()
+            .zip(proofs)
+            .map(|(key, proof)| StorageProof {
+                key: *key.key(),
+                proof: proof.merkle_path,
+                value: proof.value,
+                index: proof.index,
+            });
+        let account_proof: Vec<_> = proofs.by_ref().take(account_key_count).collect();
+        let storage_proof = proofs.collect();
+        let (nonce, _) = decompose_full_nonce(h256_to_u256(account_proof[1].value));
+        Ok(Some(EthProof {
+            address,
+            l1_batch_number,
+            balance: h256_to_u256(account_proof[0].value),
+            nonce,
+            code_hash: account_proof[2].value,
+            storage_hash,
+            account_proof,
+            storage_proof,
+        }))
+    }
+
     /// Account nonce.
     pub async fn get_transaction_count_impl(
         &self,
@@ -406,7 +839,8 @@ impl EthNamespace {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         self.current_method().set_block_id(block_id);
 
-        let mut connection = self.state.acquire_connection().await?;
+        // The nonce is necessary to submit transactions, so the request is not shed.
+        let mut connection = self.state.acquire_connection_for_tx_submission().await?;
 
         let block_number = self.state.resolve_block(&mut connection, block_id).await?;
         self.set_block_diff(block_number);
@@ -511,10 +945,9 @@ impl EthNamespace {
         let next_block_number = last_block_number + 1;
         drop(storage);
 
-        Ok(installed_filters
-            .lock()
+        installed_filters
+            .add(TypedFilter::Blocks(next_block_number))
             .await
-            .add(TypedFilter::Blocks(next_block_number)))
     }
 
     pub async fn new_filter_impl(&self, mut filter: Filter) -> Result<U256, Web3Error> {
@@ -531,10 +964,9 @@ impl EthNamespace {
 
         self.state.resolve_filter_block_hash(&mut filter).await?;
         let from_block = self.state.get_filter_from_block(&filter).await?;
-        Ok(installed_filters
-            .lock()
+        installed_filters
+            .add(TypedFilter::Events(filter, from_block))
             .await
-            .add(TypedFilter::Events(filter, from_block)))
     }
 
     pub async fn new_pending_transaction_filter_impl(&self) -> Result<U256, Web3Error> {
@@ -543,12 +975,11 @@ impl EthNamespace {
             .installed_filters
             .as_ref()
             .ok_or(Web3Error::MethodNotImplemented)?;
-        Ok(installed_filters
-            .lock()
-            .await
+        installed_filters
             .add(TypedFilter::PendingTransactions(
                 chrono::Utc::now().naive_utc(),
-            )))
+            ))
+            .await
     }
 
     pub async fn get_filter_changes_impl(&self, idx: U256) -> Result<FilterChanges, Web3Error> {
@@ -558,19 +989,18 @@ impl EthNamespace {
             .as_ref()
             .ok_or(Web3Error::MethodNotImplemented)?;
         let mut filter = installed_filters
-            .lock()
-            .await
             .get_and_update_stats(idx)
+            .await?
             .ok_or(Web3Error::FilterNotFound)?;
 
         match self.filter_changes(&mut filter).await {
             Ok(changes) => {
-                installed_filters.lock().await.update(idx, filter);
+                installed_filters.update(idx, filter).await?;
                 Ok(changes)
             }
             Err(Web3Error::LogsLimitExceeded(..)) => {
                 // The filter was not being polled for a long time, so we remove it.
-                installed_filters.lock().await.remove(idx);
+                installed_filters.remove(idx).await?;
                 Err(Web3Error::FilterNotFound)
             }
             Err(err) => Err(err),
@@ -583,7 +1013,7 @@ impl EthNamespace {
             .installed_filters
             .as_ref()
             .ok_or(Web3Error::MethodNotImplemented)?;
-        Ok(installed_filters.lock().await.remove(idx))
+        installed_filters.remove(idx).await
     }
 
     pub fn protocol_version(&self) -> String {
@@ -633,6 +1063,13 @@ impl EthNamespace {
     ) -> Result<FeeHistory, Web3Error> {
         self.current_method()
             .set_block_id(BlockId::Number(newest_block));
+        let are_percentiles_valid = reward_percentiles
+            .iter()
+            .all(|percentile| (0.0..=100.0).contains(percentile))
+            && reward_percentiles.windows(2).all(|pair| pair[0] <= pair[1]);
+        if !are_percentiles_valid {
+            return Err(Web3Error::InvalidRewardPercentiles);
+        }
 
         // Limit `block_count`.
         let block_count = block_count
@@ -658,11 +1095,21 @@ impl EthNamespace {
         let oldest_block = newest_l2_block.0 + 1 - base_fee_per_gas.len() as u32;
         // We do not store gas used ratio for blocks, returns array of zeroes as a placeholder.
         let gas_used_ratio = vec![0.0; base_fee_per_gas.len()];
-        // Effective priority gas price is currently 0.
-        let reward = Some(vec![
-            vec![U256::zero(); reward_percentiles.len()];
-            base_fee_per_gas.len()
-        ]);
+
+        let priority_fees = connection
+            .blocks_web3_dal()
+            .get_fee_history_priority_fees(L2BlockNumber(oldest_block)..=newest_l2_block)
+            .await
+            .map_err(DalError::generalize)?;
+        let mut priority_fees_by_block = vec![vec![]; base_fee_per_gas.len()];
+        for (block_number, priority_fee, gas_used) in priority_fees {
+            priority_fees_by_block[(block_number.0 - oldest_block) as usize]
+                .push((priority_fee, gas_used));
+        }
+        let reward = priority_fees_by_block
+            .into_iter()
+            .map(|block_fees| block_rewards(&reward_percentiles, block_fees))
+            .collect();
 
         // `base_fee_per_gas` for next L2 block cannot be calculated, appending last fee as a placeholder.
         base_fee_per_gas.push(*base_fee_per_gas.last().unwrap());
@@ -670,7 +1117,7 @@ impl EthNamespace {
             oldest_block: web3::BlockNumber::Number(oldest_block.into()),
             base_fee_per_gas,
             gas_used_ratio,
-            reward,
+            reward: Some(reward),
         })
     }
 
@@ -729,23 +1176,6 @@ impl EthNamespace {
             }
 
             TypedFilter::Events(filter, from_block) => {
-                let addresses = if let Some(addresses) = &filter.address {
-                    addresses.0.clone()
-                } else {
-                    vec![]
-                };
-                let topics = if let Some(topics) = &filter.topics {
-                    if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
-                        return Err(Web3Error::TooManyTopics);
-                    }
-                    let topics_by_idx = topics.iter().enumerate().filter_map(|(idx, topics)| {
-                        Some((idx as u32 + 1, topics.as_ref()?.0.clone()))
-                    });
-                    topics_by_idx.collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-
                 let mut to_block = self
                     .state
                     .resolve_filter_block_number(filter.to_block)
@@ -759,12 +1189,7 @@ impl EthNamespace {
                     );
                 }
 
-                let get_logs_filter = GetLogsFilter {
-                    from_block: *from_block,
-                    to_block,
-                    addresses,
-                    topics,
-                };
+                let get_logs_filter = get_logs_filter(filter, *from_block, to_block)?;
 
                 let mut storage = self.state.acquire_connection().await?;
 
@@ -788,11 +1213,29 @@ impl EthNamespace {
                     }
                 }
 
-                let logs = storage
-                    .events_web3_dal()
-                    .get_logs(get_logs_filter, i32::MAX as usize)
-                    .await
-                    .map_err(DalError::generalize)?;
+                // A single block can contain an unbounded number of logs (the entities limit above only applies
+                // to multi-block ranges), so logs are loaded in pages and the response size is checked
+                // after loading each page.
+                let mut response_size = self.current_method().response_size_tracker();
+                let mut page_filter = get_logs_filter;
+                let mut logs = vec![];
+                loop {
+                    let page = storage
+                        .events_web3_dal()
+                        .get_logs(page_filter.clone(), LOGS_PAGE_SIZE)
+                        .await
+                        .map_err(DalError::generalize)?;
+                    for log in &page {
+                        response_size.observe(log)?;
+                    }
+                    let is_last_page = page.len() < LOGS_PAGE_SIZE;
+                    page_filter.after = page.last().map(LogCursor::for_log);
+                    logs.extend(page);
+                    if is_last_page {
+                        break;
+                    }
+                }
+                drop(storage);
                 *from_block = to_block + 1;
                 FilterChanges::Logs(logs)
             }
@@ -839,3 +1282,28 @@ impl EthNamespace {
     // - `compile_solidity`.
     // - `compile_serpent`.
 }
+
+/// Computes rewards for an L2 block in the same way as Geth: transactions are sorted by their effective priority fee,
+/// and the reward for a percentile is the priority fee of the first transaction at which the cumulative gas used
+/// reaches the percentile of the total gas used in the block.
+fn block_rewards(reward_percentiles: &[f32], mut priority_fees: Vec<(U256, u64)>) -> Vec<U256> {
+    if priority_fees.is_empty() {
+        return vec![U256::zero(); reward_percentiles.len()];
+    }
+    priority_fees.sort_unstable_by_key(|(priority_fee, _)| *priority_fee);
+    let total_gas_used: u64 = priority_fees.iter().map(|(_, gas_used)| gas_used).sum();
+
+    let mut tx_index = 0;
+    let mut cumulative_gas_used = priority_fees[0].1;
+    reward_percentiles
+        .iter()
+        .map(|&percentile| {
+            let threshold = (total_gas_used as f64 * f64::from(percentile) / 100.0) as u64;
+            while cumulative_gas_used < threshold && tx_index < priority_fees.len() - 1 {
+                tx_index += 1;
+                cumulative_gas_used += priority_fees[tx_index].1;
+            }
+            priority_fees[tx_index].0
+        })
+        .collect()
+}
@@ -0,0 +1,168 @@
+use zksync_dal::{CoreDal, DalError};
+use zksync_types::{
+    api::{
+        trace::{LocalizedTrace, TraceFilter, TransactionLocation},
+        BlockId, BlockNumber,
+    },
+    L2BlockNumber, H256,
+};
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, state::RpcState};
+
+/// Number of L2 blocks for which call traces are loaded from the storage at once in `trace_filter`.
+const FILTER_BLOCKS_CHUNK_SIZE: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub(crate) struct TraceNamespace {
+    state: RpcState,
+}
+
+impl TraceNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn trace_block_impl(
+        &self,
+        block: BlockNumber,
+    ) -> Result<Vec<LocalizedTrace>, Web3Error> {
+        let block_id = BlockId::Number(block);
+        self.current_method().set_block_id(block_id);
+        let mut connection = self.state.acquire_connection().await?;
+        let block_number = self.state.resolve_block(&mut connection, block_id).await?;
+        self.current_method()
+            .set_block_diff(self.state.last_sealed_l2_block.diff(block_number));
+
+        let call_traces = connection
+            .blocks_web3_dal()
+            .get_traces_for_l2_blocks(block_number..=block_number)
+            .await
+            .map_err(DalError::generalize)?;
+        let mut response_size = self.current_method().response_size_tracker();
+        let mut traces = vec![];
+        for (location, call) in &call_traces {
+            for trace in LocalizedTrace::flatten(call, location) {
+                response_size.observe(&trace)?;
+                traces.push(trace);
+            }
+        }
+        Ok(traces)
+    }
+
+    pub async fn trace_transaction_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<Vec<LocalizedTrace>>, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let receipts = connection
+            .transactions_web3_dal()
+            .get_transaction_receipts(&[tx_hash])
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(receipt) = receipts.into_iter().next() else {
+            return Ok(None);
+        };
+        let Some(call) = connection
+            .transactions_dal()
+            .get_call_trace(tx_hash)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+
+        let location = TransactionLocation {
+            block_number: L2BlockNumber(receipt.block_number.as_u32()),
+            block_hash: receipt.block_hash,
+            transaction_hash: tx_hash,
+            transaction_position: receipt.transaction_index.as_usize(),
+        };
+        Ok(Some(LocalizedTrace::flatten(&call, &location)))
+    }
+
+    /// Returns traces matching the filter. The number of traces (before applying `after` and `count` pagination)
+    /// is limited by `req_entities_limit` from the API config in the same way as for `eth_getLogs`. Since traces
+    /// are filtered after being loaded from the storage, the scanned block range is limited as well.
+    pub async fn trace_filter_impl(
+        &self,
+        filter: TraceFilter,
+    ) -> Result<Vec<LocalizedTrace>, Web3Error> {
+        let from_block = self
+            .state
+            .resolve_filter_from_block(filter.from_block)
+            .await?;
+        let latest_block = self.state.resolve_filter_block_number(None).await?;
+        let to_block = self
+            .state
+            .resolve_filter_to_block(filter.to_block)
+            .await?
+            .min(latest_block);
+        let block_range_limit = self.state.api_config.trace_filter_block_range_limit;
+        if to_block.0.saturating_sub(from_block.0) >= block_range_limit {
+            return Err(Web3Error::BlockRangeLimitExceeded(
+                block_range_limit,
+                from_block.0,
+                from_block
+                    .0
+                    .saturating_add(block_range_limit.saturating_sub(1)),
+            ));
+        }
+
+        let limit = self.state.api_config.req_entities_limit;
+        let mut to_skip = filter.after.unwrap_or(0);
+        let count = filter.count.unwrap_or(usize::MAX);
+        let mut matched_count = 0;
+        let mut response_size = self.current_method().response_size_tracker();
+        let mut traces = vec![];
+
+        let mut connection = self.state.acquire_connection().await?;
+        let mut chunk_start = from_block;
+        while chunk_start <= to_block && traces.len() < count {
+            let chunk_end = L2BlockNumber(
+                chunk_start
+                    .0
+                    .saturating_add(FILTER_BLOCKS_CHUNK_SIZE - 1)
+                    .min(to_block.0),
+            );
+            let call_traces = connection
+                .blocks_web3_dal()
+                .get_traces_for_l2_blocks(chunk_start..=chunk_end)
+                .await
+                .map_err(DalError::generalize)?;
+
+            for (location, call) in &call_traces {
+                let tx_traces = LocalizedTrace::flatten(call, location);
+                for trace in tx_traces.into_iter().filter(|trace| filter.matches(trace)) {
+                    matched_count += 1;
+                    if matched_count > limit {
+                        return Err(Web3Error::LogsLimitExceeded(
+                            limit,
+                            from_block.0,
+                            from_block.0.max(location.block_number.0.saturating_sub(1)),
+                        ));
+                    }
+                    if to_skip > 0 {
+                        to_skip -= 1;
+                        continue;
+                    }
+                    if traces.len() == count {
+                        return Ok(traces);
+                    }
+                    response_size.observe(&trace)?;
+                    traces.push(trace);
+                }
+            }
+
+            let Some(next_chunk_start) = chunk_end.0.checked_add(1) else {
+                break;
+            };
+            chunk_start = L2BlockNumber(next_chunk_start);
+        }
+        Ok(traces)
+    }
+}
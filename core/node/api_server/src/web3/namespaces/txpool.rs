@@ -0,0 +1,106 @@
+use std::collections::{BTreeMap, HashMap};
+
+use zksync_dal::{CoreDal, DalError};
+use zksync_types::{
+    api::{BlockId, BlockNumber, Transaction, TxpoolContent, TxpoolStatus},
+    get_nonce_key,
+    utils::decompose_full_nonce,
+    Address, H256,
+};
+use zksync_utils::h256_to_u256;
+use zksync_web3_decl::error::Web3Error;
+
+use crate::web3::{backend_jsonrpsee::MethodTracer, state::RpcState};
+
+#[derive(Debug, Clone)]
+pub(crate) struct TxpoolNamespace {
+    state: RpcState,
+}
+
+impl TxpoolNamespace {
+    pub fn new(state: RpcState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn current_method(&self) -> &MethodTracer {
+        &self.state.current_method
+    }
+
+    pub async fn content_impl(&self) -> Result<TxpoolContent, Web3Error> {
+        let mut connection = self.state.acquire_connection().await?;
+        let limit = u32::try_from(self.state.api_config.req_entities_limit).unwrap_or(u32::MAX);
+        let transactions = connection
+            .transactions_web3_dal()
+            .get_mempool_transactions(limit, self.state.api_config.l2_chain_id)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let mut txs_by_initiator = HashMap::<Address, BTreeMap<u32, Transaction>>::new();
+        for tx in transactions {
+            let Some(initiator) = tx.from else {
+                continue;
+            };
+            txs_by_initiator
+                .entry(initiator)
+                .or_default()
+                .insert(tx.nonce.as_u32(), tx);
+        }
+
+        let block_number = self
+            .state
+            .resolve_block(&mut connection, BlockId::Number(BlockNumber::Latest))
+            .await?;
+        let nonce_keys: Vec<H256> = txs_by_initiator
+            .keys()
+            .map(|initiator| get_nonce_key(initiator).hashed_key())
+            .collect();
+        let nonce_values = connection
+            .storage_logs_dal()
+            .get_storage_values(&nonce_keys, block_number)
+            .await
+            .map_err(DalError::generalize)?;
+
+        let mut content = TxpoolContent::default();
+        for (initiator, txs) in txs_by_initiator {
+            let nonce_key = get_nonce_key(&initiator).hashed_key();
+            let full_nonce = nonce_values
+                .get(&nonce_key)
+                .copied()
+                .flatten()
+                .unwrap_or_default();
+            let (committed_nonce, _) = decompose_full_nonce(h256_to_u256(full_nonce));
+            let mut next_nonce = committed_nonce.as_u32();
+
+            // Transactions are pending while their nonces are contiguous with the committed nonce;
+            // everything after the first gap is queued.
+            let mut pending = BTreeMap::new();
+            let mut queued = BTreeMap::new();
+            for (nonce, tx) in txs {
+                if nonce == next_nonce && queued.is_empty() {
+                    next_nonce += 1;
+                    pending.insert(nonce, tx);
+                } else {
+                    queued.insert(nonce, tx);
+                }
+            }
+            if !pending.is_empty() {
+                content.pending.insert(initiator, pending);
+            }
+            if !queued.is_empty() {
+                content.queued.insert(initiator, queued);
+            }
+        }
+        Ok(content)
+    }
+
+    pub async fn status_impl(&self) -> Result<TxpoolStatus, Web3Error> {
+        let content = self.content_impl().await?;
+        let count = |txs: &HashMap<Address, BTreeMap<u32, Transaction>>| {
+            txs.values().map(BTreeMap::len).sum::<usize>()
+        };
+        Ok(TxpoolStatus {
+            pending: count(&content.pending).into(),
+            queued: count(&content.queued).into(),
+        })
+    }
+}
@@ -2,34 +2,44 @@ use std::{collections::HashMap, convert::TryInto};
 
 use anyhow::Context as _;
 use multivm::interface::VmExecutionResultAndLogs;
-use zksync_dal::{Connection, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiError;
+use zksync_dal::{pruning_dal, Connection, Core, CoreDal, DalError};
 use zksync_mini_merkle_tree::MiniMerkleTree;
 use zksync_system_constants::DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE;
 use zksync_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        BalanceCheckpoint, BalanceHistory, BaseTokenConversionRatio, BlockDetails, BlockId,
+        BlockNumber, BridgeAddresses, GetLogsFilter, L1BatchDetails, L1BatchPubdata,
+        L1GasPriceSample, L2BlockRawTransactions, L2ToL1LogProof, LogCursor, LogsPage, Proof,
+        ProofQuery, ProtocolUpgradeInfo, ProtocolUpgradeTxInfo, ProtocolVersion,
+        ProtocolVersionInfo, StorageInfo, StorageMode, StorageProof, TokenBalances, TokenTransfer,
+        TransactionDetails,
     },
-    fee::Fee,
+    fee::{Fee, FeeBreakdown, FeeEstimate},
     fee_model::{FeeParams, PubdataIndependentBatchFeeModelInput},
     l1::L1Tx,
     l2::L2Tx,
     l2_to_l1_log::{l2_to_l1_logs_tree_size, L2ToL1Log},
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
-    utils::storage_key_for_standard_token_balance,
+    utils::{storage_key_for_eth_balance, storage_key_for_standard_token_balance},
     web3::Bytes,
     AccountTreeId, L1BatchNumber, L2BlockNumber, ProtocolVersionId, StorageKey, Transaction,
     L1_MESSENGER_ADDRESS, L2_BASE_TOKEN_ADDRESS, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256, U64,
 };
-use zksync_utils::{address_to_h256, h256_to_u256};
+use zksync_utils::{address_to_h256, bytecode::hash_bytecode, h256_to_u256};
 use zksync_web3_decl::{
     error::Web3Error,
-    types::{Address, Token, H256},
+    types::{Address, Filter, Token, H256},
 };
 
-use crate::web3::{backend_jsonrpsee::MethodTracer, metrics::API_METRICS, RpcState};
+use crate::web3::{
+    backend_jsonrpsee::MethodTracer, metrics::API_METRICS, namespaces::eth::get_logs_filter,
+    RpcState,
+};
+
+/// Number of L2 blocks for which raw transactions are loaded from the storage at once
+/// in `zks_getRawBlocksTransactions`.
+const RAW_TXS_BLOCKS_CHUNK_SIZE: u32 = 100;
 
 #[derive(Debug)]
 pub(crate) struct ZksNamespace {
@@ -45,7 +55,11 @@ impl ZksNamespace {
         &self.state.current_method
     }
 
-    pub async fn estimate_fee_impl(&self, request: CallRequest) -> Result<Fee, Web3Error> {
+    pub async fn estimate_fee_impl(
+        &self,
+        request: CallRequest,
+        include_breakdown: bool,
+    ) -> Result<FeeEstimate, Web3Error> {
         let mut request_with_gas_per_pubdata_overridden = request;
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
@@ -64,7 +78,11 @@ impl ZksNamespace {
         // not consider provided ones.
         tx.common_data.fee.max_priority_fee_per_gas = 0u64.into();
         tx.common_data.fee.gas_per_pubdata_limit = U256::from(DEFAULT_L2_TX_GAS_PER_PUBDATA_BYTE);
-        self.estimate_fee(tx.into()).await
+        let (fee, breakdown) = self.estimate_fee(tx.into()).await?;
+        Ok(FeeEstimate {
+            fee,
+            breakdown: include_breakdown.then_some(breakdown),
+        })
     }
 
     pub async fn estimate_l1_to_l2_gas_impl(
@@ -84,11 +102,11 @@ impl ZksNamespace {
             .try_into()
             .map_err(Web3Error::SerializationError)?;
 
-        let fee = self.estimate_fee(tx.into()).await?;
+        let (fee, _) = self.estimate_fee(tx.into()).await?;
         Ok(fee.gas_limit)
     }
 
-    async fn estimate_fee(&self, tx: Transaction) -> Result<Fee, Web3Error> {
+    async fn estimate_fee(&self, tx: Transaction) -> Result<(Fee, FeeBreakdown), Web3Error> {
         let scale_factor = self.state.api_config.estimate_gas_scale_factor;
         let acceptable_overestimation =
             self.state.api_config.estimate_gas_acceptable_overestimation;
@@ -96,7 +114,7 @@ impl ZksNamespace {
         Ok(self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation as u64)
+            .get_txs_fee_with_breakdown(tx, scale_factor, acceptable_overestimation as u64)
             .await?)
     }
 
@@ -227,6 +245,7 @@ impl ZksNamespace {
                         to_block: block_number,
                         addresses: vec![L1_MESSENGER_ADDRESS],
                         topics: vec![(2, vec![address_to_h256(&sender)]), (3, vec![msg])],
+                        after: None,
                     },
                     self.state.api_config.req_entities_limit,
                 )
@@ -394,6 +413,74 @@ impl ZksNamespace {
             .map_err(DalError::generalize)?)
     }
 
+    /// Returns raw transactions for the L2 blocks in the range, including blocks without transactions.
+    /// Blocks after the last sealed one are ignored. The total number of returned transactions is limited
+    /// by `req_entities_limit` from the API config; once it's reached, the returned blocks are truncated
+    /// on the block boundary (but at least one block is always returned).
+    pub async fn get_raw_blocks_transactions_impl(
+        &self,
+        from_block: L2BlockNumber,
+        to_block: L2BlockNumber,
+    ) -> Result<Vec<L2BlockRawTransactions>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(from_block, &mut storage)
+            .await?;
+        let latest_block_id = BlockId::Number(BlockNumber::Latest);
+        let latest_block = self
+            .state
+            .resolve_block(&mut storage, latest_block_id)
+            .await?;
+        let to_block = to_block.min(latest_block);
+
+        let limit = self.state.api_config.req_entities_limit;
+        let mut tx_count = 0;
+        let mut response_size = self.current_method().response_size_tracker();
+        let mut blocks = vec![];
+        let mut chunk_start = from_block;
+        while chunk_start <= to_block {
+            let chunk_end = L2BlockNumber(
+                chunk_start
+                    .0
+                    .saturating_add(RAW_TXS_BLOCKS_CHUNK_SIZE - 1)
+                    .min(to_block.0),
+            );
+            let mut chunk_txs = storage
+                .transactions_web3_dal()
+                .get_raw_l2_blocks_transactions(chunk_start..=chunk_end)
+                .await
+                .map_err(DalError::generalize)?
+                .into_iter()
+                .peekable();
+
+            for number in chunk_start.0..=chunk_end.0 {
+                let number = L2BlockNumber(number);
+                let transactions = chunk_txs
+                    .next_if(|(block_number, _)| *block_number == number)
+                    .map(|(_, txs)| txs)
+                    .unwrap_or_default();
+                tx_count += transactions.len();
+                if tx_count > limit && !blocks.is_empty() {
+                    return Ok(blocks);
+                }
+
+                let block = L2BlockRawTransactions {
+                    number,
+                    transactions,
+                };
+                response_size.observe(&block)?;
+                blocks.push(block);
+            }
+
+            let Some(next_chunk_start) = chunk_end.0.checked_add(1) else {
+                break;
+            };
+            chunk_start = L2BlockNumber(next_chunk_start);
+        }
+        Ok(blocks)
+    }
+
     pub async fn get_transaction_details_impl(
         &self,
         hash: H256,
@@ -422,11 +509,74 @@ impl ZksNamespace {
             .ensure_not_pruned(batch_number, &mut storage)
             .await?;
 
-        Ok(storage
+        let Some(mut details) = storage
             .blocks_web3_dal()
             .get_l1_batch_details(batch_number)
             .await
-            .map_err(DalError::generalize)?)
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        details.data_availability = storage
+            .data_availability_dal()
+            .get_l1_batch_da_details(batch_number)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(Some(details))
+    }
+
+    pub async fn get_batch_pubdata_impl(
+        &self,
+        batch_number: L1BatchNumber,
+    ) -> Result<Option<L1BatchPubdata>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(batch_number, &mut storage)
+            .await?;
+
+        // Batch metadata (in particular, compressed state diffs) is only available once the batch
+        // is processed by the Merkle tree and the commitment generator.
+        let Some(l1_batch) = storage
+            .blocks_dal()
+            .get_l1_batch_metadata(batch_number)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        drop(storage);
+
+        let protocol_version = l1_batch
+            .header
+            .protocol_version
+            .unwrap_or_else(ProtocolVersionId::last_potentially_undefined);
+        if protocol_version.is_pre_boojum() {
+            // Pre-boojum batches have a different pubdata layout which isn't supported.
+            return Ok(None);
+        }
+
+        let header = &l1_batch.header;
+        Ok(Some(L1BatchPubdata {
+            number: batch_number,
+            l2_to_l1_logs: header
+                .l2_to_l1_logs
+                .iter()
+                .map(|log| log.0.to_bytes().to_vec().into())
+                .collect(),
+            l2_to_l1_messages: header
+                .l2_to_l1_messages
+                .iter()
+                .map(|msg| msg.clone().into())
+                .collect(),
+            published_bytecodes: l1_batch
+                .raw_published_factory_deps
+                .iter()
+                .map(|bytecode| bytecode.clone().into())
+                .collect(),
+            state_diffs_compressed: l1_batch.metadata.state_diffs_compressed.clone().into(),
+            pubdata: l1_batch.construct_pubdata().into(),
+        }))
     }
 
     pub async fn get_bytecode_by_hash_impl(
@@ -473,6 +623,70 @@ impl ZksNamespace {
         Ok(protocol_version)
     }
 
+    pub async fn get_protocol_upgrade_impl(
+        &self,
+        version_id: u16,
+    ) -> Result<Option<ProtocolUpgradeInfo>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let Some(version) = storage
+            .protocol_versions_web3_dal()
+            .get_protocol_version_by_id(version_id)
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+
+        let upgrade_tx = if version.l2_system_upgrade_tx_hash().is_some() {
+            let protocol_version_id = ProtocolVersionId::try_from(version_id).map_err(|err| {
+                anyhow::anyhow!("protocol version {version_id} is not supported: {err}")
+            })?;
+            storage
+                .protocol_versions_dal()
+                .get_protocol_upgrade_tx(protocol_version_id)
+                .await
+                .map_err(DalError::generalize)?
+        } else {
+            None
+        };
+        drop(storage);
+
+        let upgrade_tx = upgrade_tx.map(|tx| ProtocolUpgradeTxInfo {
+            hash: tx.common_data.hash(),
+            sender: tx.common_data.sender,
+            contract_address: tx.execute.contract_address,
+            calldata: tx.execute.calldata.into(),
+            value: tx.execute.value,
+            gas_limit: tx.common_data.gas_limit,
+            factory_dep_hashes: tx
+                .execute
+                .factory_deps
+                .unwrap_or_default()
+                .iter()
+                .map(|bytecode| hash_bytecode(bytecode))
+                .collect(),
+            l1_block_number: tx.common_data.eth_block,
+        });
+        // `unwrap()`s are safe: these fields are always set for versions loaded from the storage.
+        Ok(Some(ProtocolUpgradeInfo {
+            minor_version: version.minor_version().unwrap(),
+            timestamp: version.timestamp,
+            bootloader_code_hash: version.bootloader_code_hash().unwrap(),
+            default_account_code_hash: version.default_account_code_hash().unwrap(),
+            upgrade_tx,
+        }))
+    }
+
+    pub async fn get_protocol_versions_impl(&self) -> Result<Vec<ProtocolVersionInfo>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let versions = storage
+            .protocol_versions_web3_dal()
+            .get_protocol_version_history()
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(versions)
+    }
+
     pub async fn get_proofs_impl(
         &self,
         address: Address,
@@ -488,31 +702,12 @@ impl ZksNamespace {
             .iter()
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
-        let tree_api = self
+        let Some(proofs) = self
             .state
-            .tree_api
-            .as_deref()
-            .ok_or(Web3Error::MethodNotImplemented)?;
-        let proofs_result = tree_api.get_proofs(l1_batch_number, hashed_keys).await;
-        let proofs = match proofs_result {
-            Ok(proofs) => proofs,
-            Err(TreeApiError::NotReady(_)) => return Err(Web3Error::TreeApiUnavailable),
-            Err(TreeApiError::NoVersion(err)) => {
-                return if err.missing_version > err.version_count {
-                    Ok(None)
-                } else {
-                    Err(Web3Error::InternalError(anyhow::anyhow!(
-                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
-                    )))
-                };
-            }
-            Err(TreeApiError::Internal(err)) => return Err(Web3Error::InternalError(err)),
-            Err(_) => {
-                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
-                return Err(Web3Error::InternalError(anyhow::anyhow!(
-                    "Unspecified tree API error"
-                )));
-            }
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+        else {
+            return Ok(None);
         };
 
         let storage_proof = proofs
@@ -532,6 +727,68 @@ impl ZksNamespace {
         }))
     }
 
+    /// Gets proofs for all queries with a single Merkle tree request. The total number of requested keys
+    /// is limited by `req_entities_limit` from the API config.
+    pub async fn get_multi_account_proofs_impl(
+        &self,
+        queries: Vec<ProofQuery>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Option<Vec<Proof>>, Web3Error> {
+        let limit = self.state.api_config.req_entities_limit;
+        let key_count: usize = queries.iter().map(|query| query.keys.len()).sum();
+        if key_count > limit {
+            return Err(Web3Error::TooManyProofKeys(limit));
+        }
+
+        let mut storage = self.state.acquire_connection().await?;
+        self.state
+            .start_info
+            .ensure_not_pruned(l1_batch_number, &mut storage)
+            .await?;
+        drop(storage);
+
+        let hashed_keys = queries
+            .iter()
+            .flat_map(|query| {
+                let account = AccountTreeId::new(query.address);
+                query
+                    .keys
+                    .iter()
+                    .map(move |key| StorageKey::new(account, *key).hashed_key_u256())
+            })
+            .collect();
+        let Some(tree_proofs) = self
+            .state
+            .get_tree_proofs(l1_batch_number, hashed_keys)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let mut tree_proofs = tree_proofs.into_iter();
+        let proofs = queries
+            .into_iter()
+            .map(|query| {
+                let storage_proof = query
+                    .keys
+                    .into_iter()
+                    .zip(tree_proofs.by_ref())
+                    .map(|(key, proof)| StorageProof {
+                        key,
+                        proof: proof.merkle_path,
+                        value: proof.value,
+                        index: proof.index,
+                    })
+                    .collect();
+                Proof {
+                    address: query.address,
+                    storage_proof,
+                }
+            })
+            .collect();
+        Ok(Some(proofs))
+    }
+
     pub fn get_base_token_l1_address_impl(&self) -> Result<Address, Web3Error> {
         self.state
             .api_config
@@ -539,6 +796,44 @@ impl ZksNamespace {
             .ok_or(Web3Error::MethodNotImplemented)
     }
 
+    pub async fn get_base_token_conversion_ratio_impl(
+        &self,
+    ) -> Result<Option<BaseTokenConversionRatio>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        Ok(storage
+            .base_token_dal()
+            .get_latest_ratio()
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    pub async fn get_l1_gas_price_history_impl(
+        &self,
+        from_timestamp: Option<u64>,
+        to_timestamp: Option<u64>,
+        limit: Option<usize>,
+    ) -> Result<Vec<L1GasPriceSample>, Web3Error> {
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = limit.map_or(max_limit, |limit| limit.clamp(1, max_limit));
+        let mut storage = self.state.acquire_connection().await?;
+        let samples = storage
+            .l1_gas_price_history_dal()
+            .get_samples(
+                from_timestamp.unwrap_or(0),
+                to_timestamp.unwrap_or(u64::MAX),
+                limit,
+            )
+            .await
+            .map_err(DalError::generalize)?;
+        drop(storage);
+
+        let mut response_size = self.current_method().response_size_tracker();
+        for sample in &samples {
+            response_size.observe(sample)?;
+        }
+        Ok(samples)
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn get_batch_fee_input_impl(
         &self,
@@ -568,4 +863,190 @@ impl ZksNamespace {
             err.into()
         })
     }
+
+    /// Returns a page of logs matching the filter, starting after the `cursor` (or from the start of the filter
+    /// block range if the cursor is not specified). The page size is capped by `req_entities_limit`
+    /// from the API config; unlike `eth_getLogs`, exceeding it is not an error.
+    pub async fn get_logs_paginated_impl(
+        &self,
+        mut filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> Result<LogsPage, Web3Error> {
+        self.state.resolve_filter_block_hash(&mut filter).await?;
+        let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = limit.map_or(max_limit, |limit| limit.clamp(1, max_limit));
+        let mut logs_filter = get_logs_filter(&filter, from_block, to_block)?;
+        logs_filter.after = cursor;
+
+        let mut storage = self.state.acquire_connection().await?;
+        // Request an extra log to find out whether there are more logs after the returned page.
+        let mut logs = storage
+            .events_web3_dal()
+            .get_logs(logs_filter, limit + 1)
+            .await
+            .map_err(DalError::generalize)?;
+        drop(storage);
+
+        let has_more_logs = logs.len() > limit;
+        logs.truncate(limit);
+        let mut response_size = self.current_method().response_size_tracker();
+        for log in &logs {
+            response_size.observe(log)?;
+        }
+
+        let last_log = logs.last().filter(|_| has_more_logs);
+        let next_cursor = last_log.map(LogCursor::for_log);
+        Ok(LogsPage { logs, next_cursor })
+    }
+
+    pub async fn get_storage_info_impl(&self) -> Result<StorageInfo, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let storage_mode = storage
+            .pruning_dal()
+            .get_storage_mode()
+            .await
+            .map_err(DalError::generalize)?;
+        let first_retained_l1_batch = self.state.start_info.first_l1_batch(&mut storage).await?;
+        let first_retained_l2_block = self.state.start_info.first_l2_block(&mut storage).await?;
+
+        let (mode, data_retention) = match storage_mode {
+            pruning_dal::StorageMode::Archive => (StorageMode::Archive, None),
+            pruning_dal::StorageMode::Pruned { data_retention } => {
+                (StorageMode::Pruned, data_retention)
+            }
+        };
+        Ok(StorageInfo {
+            mode,
+            data_retention_sec: data_retention.map(|retention| retention.as_secs()),
+            first_retained_l1_batch,
+            first_retained_l2_block,
+        })
+    }
+
+    pub async fn get_balance_history_impl(
+        &self,
+        address: Address,
+        from_block: Option<L2BlockNumber>,
+        to_block: Option<L2BlockNumber>,
+    ) -> Result<BalanceHistory, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let indexed_range = storage
+            .balance_history_dal()
+            .get_indexed_range()
+            .await
+            .map_err(DalError::generalize)?;
+        // The index is only populated if the indexer component runs on the node.
+        let Some(indexed_range) = indexed_range else {
+            return Err(Web3Error::MethodNotImplemented);
+        };
+        let first_indexed_block = *indexed_range.start();
+        let last_indexed_block = *indexed_range.end();
+
+        let from_block = from_block.unwrap_or(first_indexed_block);
+        if from_block < first_indexed_block {
+            return Err(Web3Error::PrunedBlock(first_indexed_block));
+        }
+        if from_block > last_indexed_block {
+            return Err(Web3Error::NoBlock);
+        }
+        // The balance at the start of the range is taken from the storage, so it must not be pruned.
+        self.state
+            .start_info
+            .ensure_not_pruned(from_block, &mut storage)
+            .await?;
+        let to_block = to_block.map_or(last_indexed_block, |number| number.min(last_indexed_block));
+
+        let mut checkpoints = vec![];
+        if from_block <= to_block {
+            let initial_balance = storage
+                .storage_web3_dal()
+                .get_historical_value_unchecked(&storage_key_for_eth_balance(&address), from_block)
+                .await
+                .map_err(DalError::generalize)?;
+            checkpoints.push(BalanceCheckpoint {
+                block_number: from_block,
+                balance: h256_to_u256(initial_balance),
+            });
+            if from_block < to_block {
+                let limit = self.state.api_config.req_entities_limit.saturating_sub(1);
+                let changes = storage
+                    .balance_history_dal()
+                    .get_balance_history(address, (from_block + 1)..=to_block, limit)
+                    .await
+                    .map_err(DalError::generalize)?;
+                checkpoints.extend(changes);
+            }
+        }
+
+        Ok(BalanceHistory {
+            checkpoints,
+            first_indexed_block,
+            last_indexed_block,
+        })
+    }
+
+    pub async fn get_token_balances_impl(
+        &self,
+        address: Address,
+        after_token: Option<Address>,
+    ) -> Result<TokenBalances, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let last_indexed_block = storage
+            .token_transfers_dal()
+            .get_last_indexed_l2_block()
+            .await
+            .map_err(DalError::generalize)?;
+        // The index is only populated if the indexer component runs on the node.
+        let Some(last_indexed_block) = last_indexed_block else {
+            return Err(Web3Error::MethodNotImplemented);
+        };
+        let limit = self.state.api_config.req_entities_limit;
+        let balances = storage
+            .token_transfers_dal()
+            .get_token_balances(address, after_token, limit)
+            .await
+            .map_err(DalError::generalize)?;
+        let next_token = if balances.len() >= limit {
+            balances.last().map(|(token, _)| *token)
+        } else {
+            None
+        };
+        Ok(TokenBalances {
+            balances: balances.into_iter().collect(),
+            last_indexed_block,
+            next_token,
+        })
+    }
+
+    pub async fn get_token_transfers_impl(
+        &self,
+        address: Address,
+        from_block: Option<L2BlockNumber>,
+        to_block: Option<L2BlockNumber>,
+    ) -> Result<Vec<TokenTransfer>, Web3Error> {
+        let mut storage = self.state.acquire_connection().await?;
+        let last_indexed_block = storage
+            .token_transfers_dal()
+            .get_last_indexed_l2_block()
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(last_indexed_block) = last_indexed_block else {
+            return Err(Web3Error::MethodNotImplemented);
+        };
+        let from_block = from_block.unwrap_or(L2BlockNumber(0));
+        let to_block = to_block.map_or(last_indexed_block, |number| number.min(last_indexed_block));
+        if from_block > to_block {
+            return Ok(vec![]);
+        }
+
+        let limit = self.state.api_config.req_entities_limit;
+        let transfers = storage
+            .token_transfers_dal()
+            .get_transfers(address, from_block..=to_block, limit)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(transfers)
+    }
 }
@@ -1,15 +1,16 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
+use anyhow::Context as _;
 use chrono::NaiveDateTime;
-use futures::FutureExt;
+use futures::{future, FutureExt};
 use tokio::{
     sync::{broadcast, mpsc, watch},
     task::JoinHandle,
     time::{interval, Duration},
 };
 use tracing::Instrument as _;
-use zksync_dal::{ConnectionPool, Core, CoreDal};
-use zksync_types::{L2BlockNumber, H128, H256};
+use zksync_dal::{blocks_dal::NEW_L2_BLOCKS_CHANNEL, ConnectionPool, Core, CoreDal};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H128, H256};
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{server::SubscriptionMessage, SubscriptionResult},
@@ -17,8 +18,11 @@ use zksync_web3_decl::{
         types::{error::ErrorCode, ErrorObject, SubscriptionId},
         PendingSubscriptionSink, SendTimeoutError, SubscriptionSink,
     },
-    namespaces::EthPubSubServer,
-    types::{BlockHeader, Log, PubSubFilter, PubSubResult},
+    namespaces::{EthPubSubServer, ZksPubSubServer},
+    types::{
+        BlockHeader, L2ToL1Log, L2ToL1LogNotification, L2ToL1LogStatus, Log, PubSubFilter,
+        PubSubResult,
+    },
 };
 
 use super::{
@@ -28,6 +32,9 @@ use super::{
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 1024;
 const SUBSCRIPTION_SINK_SEND_TIMEOUT: Duration = Duration::from_secs(1);
+/// Multiplier for the polling interval of notifiers woken up by [`L2BlockListener`] while the listener is active.
+/// Polling is retained as a safeguard against lost notifications (e.g., during listener reconnection).
+const LISTENER_POLLING_INTERVAL_MULTIPLIER: u32 = 20;
 
 #[derive(Debug, Clone, Copy)]
 pub struct EthSubscriptionIdProvider;
@@ -53,6 +60,8 @@ struct PubSubNotifier {
     sender: broadcast::Sender<Vec<PubSubResult>>,
     connection_pool: ConnectionPool<Core>,
     polling_interval: Duration,
+    /// Wake-ups from [`L2BlockListener`]. The value is `true` if the listener is active.
+    l2_block_wake_ups: Option<watch::Receiver<bool>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -86,10 +95,54 @@ impl PubSubNotifier {
             sender.send(event).ok();
         }
     }
+
+    /// Waits until the next iteration of a notifier processing new L2 blocks. If [`L2BlockListener`] is active,
+    /// waits for a wake-up from it, falling back to polling with an increased interval; otherwise, polls
+    /// with the configured interval. Returns `true` if the notifier was woken up by the listener.
+    ///
+    /// `retry_soon` forces the configured polling interval to be used; it should be set if the previous wake-up
+    /// didn't result in new data, e.g. because the pool reads from a replica lagging behind the main database.
+    async fn wait_for_l2_blocks(
+        &mut self,
+        stop_receiver: &mut watch::Receiver<bool>,
+        retry_soon: bool,
+    ) -> bool {
+        let is_listener_active = self
+            .l2_block_wake_ups
+            .as_ref()
+            .map_or(false, |wake_ups| *wake_ups.borrow());
+        let polling_interval = if is_listener_active && !retry_soon {
+            self.polling_interval * LISTENER_POLLING_INTERVAL_MULTIPLIER
+        } else {
+            self.polling_interval
+        };
+
+        let wake_ups = self.l2_block_wake_ups.as_mut();
+        let wake_up = async move {
+            match wake_ups {
+                Some(wake_ups) => wake_ups.changed().await.is_ok(),
+                None => future::pending().await,
+            }
+        };
+        tokio::select! {
+            is_woken_up = wake_up => {
+                if !is_woken_up {
+                    tracing::info!("L2 block listener has stopped, falling back to polling");
+                    self.l2_block_wake_ups = None;
+                }
+                is_woken_up
+            }
+            () = tokio::time::sleep(polling_interval) => false,
+            _ = stop_receiver.changed() => false,
+        }
+    }
 }
 
 impl PubSubNotifier {
-    async fn notify_blocks(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_blocks(
+        mut self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
             .await?
@@ -98,18 +151,18 @@ impl PubSubNotifier {
             return Ok(());
         };
 
-        let mut timer = interval(self.polling_interval);
+        let mut is_woken_up = false;
         loop {
             if *stop_receiver.borrow() {
                 tracing::info!("Stop signal received, pubsub_block_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
             let new_blocks = self.new_blocks(last_block_number).await?;
             db_latency.observe();
 
+            let has_new_blocks = !new_blocks.is_empty();
             if let Some(last_block) = new_blocks.last() {
                 last_block_number = L2BlockNumber(last_block.number.unwrap().as_u32());
                 let new_blocks = new_blocks.into_iter().map(PubSubResult::Header).collect();
@@ -122,6 +175,9 @@ impl PubSubNotifier {
             self.emit_event(PubSubEvent::NotifyIterationFinished(
                 SubscriptionType::Blocks,
             ));
+            is_woken_up = self
+                .wait_for_l2_blocks(&mut stop_receiver, is_woken_up && !has_new_blocks)
+                .await;
         }
         Ok(())
     }
@@ -185,7 +241,7 @@ impl PubSubNotifier {
             .map_err(Into::into)
     }
 
-    async fn notify_logs(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_logs(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let Some(mut last_block_number) = self
             .get_starting_l2_block_number(&mut stop_receiver)
             .await?
@@ -194,18 +250,18 @@ impl PubSubNotifier {
             return Ok(());
         };
 
-        let mut timer = interval(self.polling_interval);
+        let mut is_woken_up = false;
         loop {
             if *stop_receiver.borrow() {
                 tracing::info!("Stop signal received, pubsub_logs_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
             let new_logs = self.new_logs(last_block_number).await?;
             db_latency.observe();
 
+            let has_new_logs = !new_logs.is_empty();
             if let Some(last_log) = new_logs.last() {
                 last_block_number = L2BlockNumber(last_log.block_number.unwrap().as_u32());
                 let new_logs = new_logs.into_iter().map(PubSubResult::Log).collect();
@@ -216,6 +272,9 @@ impl PubSubNotifier {
                 ));
             }
             self.emit_event(PubSubEvent::NotifyIterationFinished(SubscriptionType::Logs));
+            is_woken_up = self
+                .wait_for_l2_blocks(&mut stop_receiver, is_woken_up && !has_new_logs)
+                .await;
         }
         Ok(())
     }
@@ -229,13 +288,189 @@ impl PubSubNotifier {
             .await
             .map_err(Into::into)
     }
+
+    async fn notify_l2_to_l1_logs(
+        self,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        let Some(mut last_block_number) = self
+            .get_starting_l2_block_number(&mut stop_receiver)
+            .await?
+        else {
+            tracing::info!("Stop signal received, pubsub_l2_to_l1_logs_notifier is shutting down");
+            return Ok(());
+        };
+        // If no L1 batches are executed yet, we start from the genesis batch, which doesn't contain L2-to-L1 logs.
+        let mut last_executed_batch = self.last_executed_l1_batch().await?.unwrap_or_default();
+
+        let sub_type = SubscriptionType::L2ToL1Logs;
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!(
+                    "Stop signal received, pubsub_l2_to_l1_logs_notifier is shutting down"
+                );
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency = PUB_SUB_METRICS.db_poll_latency[&sub_type].start();
+            let new_logs = self.new_l2_to_l1_logs(last_block_number).await?;
+            let executed_batch = self.last_executed_l1_batch().await?;
+            db_latency.observe();
+
+            if let Some(last_log) = new_logs.last() {
+                last_block_number = L2BlockNumber(last_log.block_number.as_u32());
+                let new_logs = new_logs
+                    .into_iter()
+                    .map(|log| {
+                        PubSubResult::L2ToL1Log(L2ToL1LogNotification {
+                            log,
+                            status: L2ToL1LogStatus::Sealed,
+                            proof_id: None,
+                        })
+                    })
+                    .collect();
+                self.send_pub_sub_results(new_logs, sub_type);
+                self.emit_event(PubSubEvent::L2BlockAdvanced(sub_type, last_block_number));
+            }
+
+            if let Some(executed_batch) = executed_batch {
+                while last_executed_batch < executed_batch {
+                    last_executed_batch += 1;
+                    let batch_logs = self.l1_batch_l2_to_l1_logs(last_executed_batch).await?;
+                    // Logs are ordered in the same way as in the L1 batch Merkle tree, so their indices are proof IDs.
+                    let batch_logs: Vec<_> = batch_logs
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, log)| {
+                            PubSubResult::L2ToL1Log(L2ToL1LogNotification {
+                                log,
+                                status: L2ToL1LogStatus::Executed,
+                                proof_id: Some(i as u32),
+                            })
+                        })
+                        .collect();
+                    if !batch_logs.is_empty() {
+                        self.send_pub_sub_results(batch_logs, sub_type);
+                    }
+                }
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(sub_type));
+        }
+        Ok(())
+    }
+
+    async fn new_l2_to_l1_logs(
+        &self,
+        last_block_number: L2BlockNumber,
+    ) -> anyhow::Result<Vec<L2ToL1Log>> {
+        self.connection_pool
+            .connection_tagged("api")
+            .await?
+            .events_web3_dal()
+            .get_l2_to_l1_logs_after(last_block_number)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn last_executed_l1_batch(&self) -> anyhow::Result<Option<L1BatchNumber>> {
+        self.connection_pool
+            .connection_tagged("api")
+            .await?
+            .blocks_dal()
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn l1_batch_l2_to_l1_logs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Vec<L2ToL1Log>> {
+        self.connection_pool
+            .connection_tagged("api")
+            .await?
+            .events_web3_dal()
+            .get_l2_to_l1_logs_for_l1_batch(l1_batch_number)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+/// Listener for Postgres notifications about new L2 blocks (see [`NEW_L2_BLOCKS_CHANNEL`]) waking up
+/// the corresponding notifiers. The listener pool must be connected to the main database. If listening fails
+/// (e.g., because of a connection error), notifiers fall back to polling until the listener is successfully restarted.
+#[derive(Debug)]
+struct L2BlockListener {
+    connection_pool: ConnectionPool<Core>,
+    wake_ups_sender: watch::Sender<bool>,
 }
 
-/// Subscription support for Web3 APIs.
+impl L2BlockListener {
+    const RESTART_INTERVAL: Duration = Duration::from_secs(10);
+
+    async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.listen(&mut stop_receiver).await {
+                tracing::warn!(
+                    "Failed listening to new L2 block notifications, falling back to polling for {:?}: {err:#}",
+                    Self::RESTART_INTERVAL
+                );
+            }
+            self.set_active(false);
+
+            if tokio::time::timeout(Self::RESTART_INTERVAL, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, pubsub_l2_block_listener is shutting down");
+        Ok(())
+    }
+
+    fn set_active(&self, is_active: bool) {
+        // Sending a value wakes up notifiers, which is desirable both when the listener is started (notifications
+        // may have been missed before that) and stopped (notifiers should switch to polling).
+        self.wake_ups_sender.send_replace(is_active);
+        PUB_SUB_METRICS
+            .l2_block_listener_active
+            .set(is_active.into());
+    }
+
+    async fn listen(&self, stop_receiver: &mut watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut listener = self
+            .connection_pool
+            .listener(&[NEW_L2_BLOCKS_CHANNEL])
+            .await?;
+        tracing::info!(
+            "Listening to new L2 block notifications on channel `{NEW_L2_BLOCKS_CHANNEL}`"
+        );
+        self.set_active(true);
+
+        loop {
+            tokio::select! {
+                notification = listener.recv() => {
+                    let notification = notification.context("failed receiving notification")?;
+                    tracing::trace!("Received notification about new L2 block #{}", notification.payload());
+                    PUB_SUB_METRICS.l2_block_notifications.inc();
+                    self.wake_ups_sender.send_replace(true);
+                }
+                _ = stop_receiver.changed() => return Ok(()),
+            }
+        }
+    }
+}
+
+/// Subscription support for Web3 APIs. Serves both `eth_subscribe` and `zks_subscribe` subscriptions.
+#[derive(Clone)]
 pub(super) struct EthSubscribe {
     blocks: broadcast::Sender<Vec<PubSubResult>>,
     transactions: broadcast::Sender<Vec<PubSubResult>>,
     logs: broadcast::Sender<Vec<PubSubResult>>,
+    l2_to_l1_logs: broadcast::Sender<Vec<PubSubResult>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -244,11 +479,13 @@ impl EthSubscribe {
         let (blocks, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (transactions, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
         let (logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
+        let (l2_to_l1_logs, _) = broadcast::channel(BROADCAST_CHANNEL_CAPACITY);
 
         Self {
             blocks,
             transactions,
             logs,
+            l2_to_l1_logs,
             events_sender: None,
         }
     }
@@ -323,11 +560,16 @@ impl EthSubscribe {
     ) -> Result<(), SendTimeoutError> {
         let notify_latency = PUB_SUB_METRICS.notify_subscribers_latency[&subscription_type].start();
         for item in new_items {
-            if let PubSubResult::Log(log) = &item {
-                if let Some(filter) = &filter {
-                    if !filter.matches(log) {
-                        continue;
+            if let Some(filter) = &filter {
+                let matches = match &item {
+                    PubSubResult::Log(log) => filter.matches(log),
+                    PubSubResult::L2ToL1Log(notification) => {
+                        filter.matches_l2_to_l1_log(&notification.log)
                     }
+                    _ => true,
+                };
+                if !matches {
+                    continue;
                 }
             }
 
@@ -415,6 +657,49 @@ impl EthSubscribe {
             }
         };
 
+        self.emit_subscribed_event(sub_type);
+    }
+
+    #[tracing::instrument(level = "debug", skip(self, pending_sink))]
+    pub async fn zks_sub(
+        &self,
+        pending_sink: PendingSubscriptionSink,
+        sub_type: String,
+        params: Option<PubSubFilter>,
+    ) {
+        let sub_type = match sub_type.as_str() {
+            "l2ToL1Logs" => {
+                let filter = params.unwrap_or_default();
+                // L2-to-L1 logs don't have topics; only filtering by sender address is supported.
+                if filter.topics.is_some() {
+                    Self::reject(pending_sink).await;
+                    None
+                } else {
+                    let Ok(sink) = pending_sink.accept().await else {
+                        return;
+                    };
+                    let logs_rx = self.l2_to_l1_logs.subscribe();
+                    tokio::spawn(
+                        Self::run_subscriber(
+                            sink,
+                            SubscriptionType::L2ToL1Logs,
+                            logs_rx,
+                            Some(filter),
+                        )
+                        .in_current_span(),
+                    );
+                    Some(SubscriptionType::L2ToL1Logs)
+                }
+            }
+            _ => {
+                Self::reject(pending_sink).await;
+                None
+            }
+        };
+        self.emit_subscribed_event(sub_type);
+    }
+
+    fn emit_subscribed_event(&self, sub_type: Option<SubscriptionType>) {
         if let Some(sub_type) = sub_type {
             if let Some(sender) = &self.events_sender {
                 sender.send(PubSubEvent::Subscribed(sub_type)).ok();
@@ -423,18 +708,33 @@ impl EthSubscribe {
     }
 
     /// Spawns notifier tasks. This should be called once per instance.
+    ///
+    /// If `listener_pool` is provided, it is used to listen to notifications about new L2 blocks; it must be connected
+    /// to the main database. Otherwise, notifiers only poll `connection_pool` for new data.
     pub fn spawn_notifiers(
         &self,
         connection_pool: ConnectionPool<Core>,
+        listener_pool: Option<ConnectionPool<Core>>,
         polling_interval: Duration,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(5);
+
+        let wake_ups_receiver = listener_pool.map(|listener_pool| {
+            let (wake_ups_sender, wake_ups_receiver) = watch::channel(false);
+            let listener = L2BlockListener {
+                connection_pool: listener_pool,
+                wake_ups_sender,
+            };
+            notifier_tasks.push(tokio::spawn(listener.run(stop_receiver.clone())));
+            wake_ups_receiver
+        });
 
         let notifier = PubSubNotifier {
             sender: self.blocks.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            l2_block_wake_ups: wake_ups_receiver.clone(),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_blocks(stop_receiver.clone()));
@@ -444,6 +744,7 @@ impl EthSubscribe {
             sender: self.transactions.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            l2_block_wake_ups: None,
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_txs(stop_receiver.clone()));
@@ -451,11 +752,22 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             sender: self.logs.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            l2_block_wake_ups: wake_ups_receiver,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            sender: self.l2_to_l1_logs.clone(),
             connection_pool,
             polling_interval,
+            l2_block_wake_ups: None,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_l2_to_l1_logs(stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
@@ -474,3 +786,16 @@ impl EthPubSubServer for EthSubscribe {
         Ok(())
     }
 }
+
+#[async_trait::async_trait]
+impl ZksPubSubServer for EthSubscribe {
+    async fn subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        sub_type: String,
+        filter: Option<PubSubFilter>,
+    ) -> SubscriptionResult {
+        self.zks_sub(pending, sub_type, filter).await;
+        Ok(())
+    }
+}
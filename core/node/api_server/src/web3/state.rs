@@ -8,7 +8,6 @@ use std::{
 };
 
 use anyhow::Context as _;
-use futures::TryFutureExt;
 use lru::LruCache;
 use tokio::sync::{watch, Mutex};
 use vise::GaugeGuard;
@@ -16,13 +15,16 @@ use zksync_config::{
     configs::{api::Web3JsonRpcConfig, ContractsConfig},
     GenesisConfig,
 };
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
-use zksync_metadata_calculator::api_server::TreeApiClient;
+use zksync_dal::{
+    pruning_dal::PrunedDataError, Connection, ConnectionPool, Core, CoreDal, DalError,
+};
+use zksync_metadata_calculator::api_server::{TreeApiClient, TreeApiError, TreeEntryWithProof};
 use zksync_node_sync::SyncState;
 use zksync_types::{
     api, commitment::L1BatchCommitmentMode, l2::L2Tx, transaction_request::CallRequest, Address,
     L1BatchNumber, L1ChainId, L2BlockNumber, L2ChainId, H256, U256, U64,
 };
+use zksync_utils::{h256_to_u256, u256_to_h256};
 use zksync_web3_decl::{error::Web3Error, types::Filter};
 
 use super::{
@@ -70,6 +72,19 @@ impl From<BlockArgsError> for Web3Error {
     }
 }
 
+impl From<PrunedDataError> for Web3Error {
+    fn from(err: PrunedDataError) -> Self {
+        match err {
+            PrunedDataError::L2Block { first_retained, .. } => {
+                Web3Error::PrunedBlock(first_retained)
+            }
+            PrunedDataError::L1Batch { first_retained, .. } => {
+                Web3Error::PrunedL1Batch(first_retained)
+            }
+        }
+    }
+}
+
 impl BlockStartInfo {
     pub(super) async fn ensure_not_pruned(
         &self,
@@ -79,11 +94,8 @@ impl BlockStartInfo {
         match query.into() {
             PruneQuery::BlockId(id) => Ok(self.ensure_not_pruned_block(id, storage).await?),
             PruneQuery::L1Batch(number) => {
-                let first_l1_batch = self.first_l1_batch(storage).await?;
-                if number < first_l1_batch {
-                    return Err(Web3Error::PrunedL1Batch(first_l1_batch));
-                }
-                Ok(())
+                let pruning_info = self.get_pruning_info(storage).await?;
+                Ok(pruning_info.ensure_l1_batch_retained(number)?)
             }
         }
     }
@@ -108,8 +120,10 @@ pub struct InternalApiConfig {
     pub l2_testnet_paymaster_addr: Option<Address>,
     pub req_entities_limit: usize,
     pub fee_history_limit: u64,
+    pub trace_filter_block_range_limit: u32,
     pub base_token_address: Option<Address>,
     pub filters_disabled: bool,
+    pub persistent_filters: bool,
     pub dummy_verifier: bool,
     pub l1_batch_commit_data_generator_mode: L1BatchCommitmentMode,
 }
@@ -151,8 +165,10 @@ impl InternalApiConfig {
             l2_testnet_paymaster_addr: contracts_config.l2_testnet_paymaster_addr,
             req_entities_limit: web3_config.req_entities_limit(),
             fee_history_limit: web3_config.fee_history_limit(),
+            trace_filter_block_range_limit: web3_config.trace_filter_block_range_limit(),
             base_token_address: contracts_config.base_token_addr,
             filters_disabled: web3_config.filters_disabled,
+            persistent_filters: web3_config.persistent_filters,
             dummy_verifier: genesis_config.dummy_verifier,
             l1_batch_commit_data_generator_mode: genesis_config.l1_batch_commit_data_generator_mode,
         }
@@ -236,7 +252,7 @@ impl SealedL2BlockNumber {
 #[derive(Debug, Clone)]
 pub(crate) struct RpcState {
     pub(super) current_method: Arc<MethodTracer>,
-    pub(super) installed_filters: Option<Arc<Mutex<Filters>>>,
+    pub(super) installed_filters: Option<Arc<InstalledFilters>>,
     pub(super) connection_pool: ConnectionPool<Core>,
     pub(super) tree_api: Option<Arc<dyn TreeApiClient>>,
     pub(super) tx_sender: TxSender,
@@ -247,6 +263,8 @@ pub(crate) struct RpcState {
     pub(super) start_info: BlockStartInfo,
     pub(super) mempool_cache: Option<MempoolCache>,
     pub(super) last_sealed_l2_block: SealedL2BlockNumber,
+    /// Signal to reject read requests while the API DB pool is saturated. `None` if load shedding is disabled.
+    pub(super) load_shedding: Option<watch::Receiver<bool>>,
 }
 
 impl RpcState {
@@ -272,16 +290,34 @@ impl RpcState {
         self.tx_sender.0.tx_sink.as_ref()
     }
 
-    /// Acquires a DB connection mapping possible errors.
+    /// Acquires a DB connection mapping possible errors. Fails with [`Web3Error::ServerOverloaded`] while load is shed.
     // `track_caller` is necessary to correctly record call location. `async fn`s don't support it yet,
     // thus manual de-sugaring.
     #[track_caller]
     pub(crate) fn acquire_connection(
         &self,
     ) -> impl Future<Output = Result<Connection<'_, Core>, Web3Error>> + '_ {
-        self.connection_pool
-            .connection_tagged("api")
-            .map_err(|err| err.generalize().into())
+        let is_shedding_load = self
+            .load_shedding
+            .as_ref()
+            .is_some_and(|receiver| *receiver.borrow());
+        let connection = self.acquire_connection_for_tx_submission();
+        async move {
+            if is_shedding_load {
+                return Err(Web3Error::ServerOverloaded);
+            }
+            connection.await
+        }
+    }
+
+    /// Acquires a DB connection for a request necessary to submit transactions (e.g., getting the account nonce).
+    /// Unlike [`Self::acquire_connection()`], such requests are not rejected while load is shed.
+    #[track_caller]
+    pub(crate) fn acquire_connection_for_tx_submission(
+        &self,
+    ) -> impl Future<Output = Result<Connection<'_, Core>, Web3Error>> + '_ {
+        let connection = self.connection_pool.read_only_connection_tagged("api");
+        async move { connection.await.map_err(|err| err.generalize().into()) }
     }
 
     /// Resolves the specified block ID to a block number, which is guaranteed to be present in the node storage.
@@ -325,6 +361,39 @@ impl RpcState {
         }
     }
 
+    /// Fetches Merkle tree proofs for the specified `hashed_keys` after the specified L1 batch.
+    /// Returns `None` if the L1 batch is not processed by the tree yet.
+    pub(crate) async fn get_tree_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Option<Vec<TreeEntryWithProof>>, Web3Error> {
+        let tree_api = self
+            .tree_api
+            .as_deref()
+            .ok_or(Web3Error::MethodNotImplemented)?;
+        match tree_api.get_proofs(l1_batch_number, hashed_keys).await {
+            Ok(proofs) => Ok(Some(proofs)),
+            Err(TreeApiError::NotReady(_)) => Err(Web3Error::TreeApiUnavailable),
+            Err(TreeApiError::NoVersion(err)) => {
+                if err.missing_version > err.version_count {
+                    Ok(None)
+                } else {
+                    Err(Web3Error::InternalError(anyhow::anyhow!(
+                        "L1 batch #{l1_batch_number} is pruned in Merkle tree, but not in Postgres"
+                    )))
+                }
+            }
+            Err(TreeApiError::Internal(err)) => Err(Web3Error::InternalError(err)),
+            Err(_) => {
+                // This branch is not expected to be executed, but has to be provided since the error is non-exhaustive.
+                Err(Web3Error::InternalError(anyhow::anyhow!(
+                    "Unspecified tree API error"
+                )))
+            }
+        }
+    }
+
     pub(crate) async fn resolve_block_args(
         &self,
         connection: &mut Connection<'_, Core>,
@@ -355,12 +424,49 @@ impl RpcState {
         // if called with an explicit number, and we've handled this case earlier.
     }
 
+    /// Resolves the first block of a filter range. Pruned blocks (e.g., `fromBlock: 0` or `earliest`
+    /// on a node recovered from a snapshot) are clamped to the first retained L2 block.
+    pub async fn resolve_filter_from_block(
+        &self,
+        block_number: Option<api::BlockNumber>,
+    ) -> Result<L2BlockNumber, Web3Error> {
+        let first_retained = match block_number {
+            Some(api::BlockNumber::Number(_) | api::BlockNumber::Earliest) => {
+                let mut conn = self.acquire_connection().await?;
+                let pruning_info = self.start_info.get_pruning_info(&mut conn).await?;
+                pruning_info.first_retained_l2_block()
+            }
+            _ => return self.resolve_filter_block_number(block_number).await,
+        };
+        Ok(match block_number {
+            Some(api::BlockNumber::Number(number)) => {
+                Self::u64_to_block_number(number).max(first_retained)
+            }
+            _ => first_retained,
+        })
+    }
+
+    /// Resolves the last block of a filter range. Returns an error if the block is explicitly specified and is pruned,
+    /// since the entire range is pruned in this case.
+    pub async fn resolve_filter_to_block(
+        &self,
+        block_number: Option<api::BlockNumber>,
+    ) -> Result<L2BlockNumber, Web3Error> {
+        let number = self.resolve_filter_block_number(block_number).await?;
+        if matches!(block_number, Some(api::BlockNumber::Number(_))) {
+            let mut conn = self.acquire_connection().await?;
+            let pruning_info = self.start_info.get_pruning_info(&mut conn).await?;
+            pruning_info.ensure_l2_block_retained(number)?;
+        }
+        Ok(number)
+    }
+
     pub async fn resolve_filter_block_range(
         &self,
         filter: &Filter,
     ) -> Result<(L2BlockNumber, L2BlockNumber), Web3Error> {
-        let from_block = self.resolve_filter_block_number(filter.from_block).await?;
-        let to_block = self.resolve_filter_block_number(filter.to_block).await?;
+        let from_block = self.resolve_filter_from_block(filter.from_block).await?;
+        let to_block = self.resolve_filter_to_block(filter.to_block).await?;
         Ok((from_block, to_block))
     }
 
@@ -518,6 +624,117 @@ impl Filters {
     }
 }
 
+/// Storage of installed filters.
+#[derive(Debug)]
+pub(crate) enum InstalledFilters {
+    /// Filters are stored in the server memory. They are lost on server restart and are local to the server.
+    InMemory(Mutex<Filters>),
+    /// Filters are persisted in Postgres. This allows them to survive server restarts and to be shared
+    /// among all API servers using the same database (e.g., several replicas behind a load balancer).
+    Persistent {
+        pool: ConnectionPool<Core>,
+        max_count: Option<usize>,
+    },
+}
+
+impl InstalledFilters {
+    pub fn in_memory(max_count: Option<usize>) -> Self {
+        Self::InMemory(Mutex::new(Filters::new(max_count)))
+    }
+
+    pub fn persistent(pool: ConnectionPool<Core>, max_count: Option<usize>) -> Self {
+        Self::Persistent { pool, max_count }
+    }
+
+    async fn connection(pool: &ConnectionPool<Core>) -> Result<Connection<'_, Core>, Web3Error> {
+        Ok(pool
+            .connection_tagged("api")
+            .await
+            .map_err(DalError::generalize)?)
+    }
+
+    /// Adds a filter and returns its key. If the filter capacity is exceeded, least recently used filters are removed.
+    pub async fn add(&self, filter: TypedFilter) -> Result<U256, Web3Error> {
+        let (pool, max_count) = match self {
+            Self::InMemory(filters) => return Ok(filters.lock().await.add(filter)),
+            Self::Persistent { pool, max_count } => (pool, *max_count),
+        };
+
+        let idx = H256::random();
+        let filter = serde_json::to_value(&filter).context("failed serializing filter")?;
+        let mut connection = Self::connection(pool).await?;
+        let mut dal = connection.api_filters_dal();
+        dal.insert_filter(idx, &filter)
+            .await
+            .map_err(DalError::generalize)?;
+        if let Some(max_count) = max_count {
+            dal.prune_filters(max_count)
+                .await
+                .map_err(DalError::generalize)?;
+        }
+        Ok(h256_to_u256(idx))
+    }
+
+    /// Retrieves a filter.
+    pub async fn get_and_update_stats(
+        &self,
+        index: U256,
+    ) -> Result<Option<TypedFilter>, Web3Error> {
+        let pool = match self {
+            Self::InMemory(filters) => return Ok(filters.lock().await.get_and_update_stats(index)),
+            Self::Persistent { pool, .. } => pool,
+        };
+
+        let mut connection = Self::connection(pool).await?;
+        let Some(filter) = connection
+            .api_filters_dal()
+            .get_filter(u256_to_h256(index))
+            .await
+            .map_err(DalError::generalize)?
+        else {
+            return Ok(None);
+        };
+        let filter =
+            serde_json::from_value(filter).context("failed deserializing stored filter")?;
+        Ok(Some(filter))
+    }
+
+    /// Updates a filter.
+    pub async fn update(&self, index: U256, new_filter: TypedFilter) -> Result<(), Web3Error> {
+        let pool = match self {
+            Self::InMemory(filters) => {
+                filters.lock().await.update(index, new_filter);
+                return Ok(());
+            }
+            Self::Persistent { pool, .. } => pool,
+        };
+
+        let new_filter = serde_json::to_value(&new_filter).context("failed serializing filter")?;
+        let mut connection = Self::connection(pool).await?;
+        connection
+            .api_filters_dal()
+            .update_filter(u256_to_h256(index), &new_filter)
+            .await
+            .map_err(DalError::generalize)?;
+        Ok(())
+    }
+
+    /// Removes a filter. Returns `false` if the filter doesn't exist.
+    pub async fn remove(&self, index: U256) -> Result<bool, Web3Error> {
+        let pool = match self {
+            Self::InMemory(filters) => return Ok(filters.lock().await.remove(index)),
+            Self::Persistent { pool, .. } => pool,
+        };
+
+        let mut connection = Self::connection(pool).await?;
+        Ok(connection
+            .api_filters_dal()
+            .remove_filter(u256_to_h256(index))
+            .await
+            .map_err(DalError::generalize)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::NaiveDateTime;
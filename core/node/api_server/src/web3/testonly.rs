@@ -44,6 +44,7 @@ pub(crate) async fn create_test_tx_sender(
         pool,
         batch_fee_model_input_provider,
         storage_caches,
+        None,
     )
     .await;
 
@@ -100,6 +101,8 @@ pub async fn spawn_http_server(
     pool: ConnectionPool<Core>,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
+    tree_api: Option<Arc<dyn TreeApiClient>>,
+    load_shedding: Option<watch::Receiver<bool>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
     spawn_server(
@@ -109,6 +112,8 @@ pub async fn spawn_http_server(
         None,
         tx_executor,
         method_tracer,
+        tree_api,
+        load_shedding,
         stop_receiver,
     )
     .await
@@ -128,11 +133,14 @@ pub async fn spawn_ws_server(
         websocket_requests_per_minute_limit,
         MockTransactionExecutor::default(),
         Arc::default(),
+        None,
+        None,
         stop_receiver,
     )
     .await
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn spawn_server(
     transport: ApiTransportLabel,
     api_config: InternalApiConfig,
@@ -140,6 +148,8 @@ async fn spawn_server(
     websocket_requests_per_minute_limit: Option<NonZeroU32>,
     tx_executor: MockTransactionExecutor,
     method_tracer: Arc<MethodTracer>,
+    tree_api: Option<Arc<dyn TreeApiClient>>,
+    load_shedding: Option<watch::Receiver<bool>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let (tx_sender, vm_barrier) =
@@ -147,13 +157,19 @@ async fn spawn_server(
     let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
-    namespaces.extend([Namespace::Debug, Namespace::Snapshots]);
+    namespaces.extend([
+        Namespace::Debug,
+        Namespace::Snapshots,
+        Namespace::Txpool,
+        Namespace::Trace,
+    ]);
 
-    let server_builder = match transport {
-        ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
+    let mut server_builder = match transport {
+        ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool.clone()).http(0),
         ApiTransportLabel::Ws => {
-            let mut builder = ApiBuilder::jsonrpsee_backend(api_config, pool)
+            let mut builder = ApiBuilder::jsonrpsee_backend(api_config, pool.clone())
                 .ws(0)
+                .with_l2_block_listener_pool(pool.clone())
                 .with_subscriptions_limit(100);
             if let Some(websocket_requests_per_minute_limit) = websocket_requests_per_minute_limit {
                 builder = builder
@@ -162,7 +178,14 @@ async fn spawn_server(
             builder
         }
     };
+    if let Some(tree_api) = tree_api {
+        server_builder = server_builder.with_tree_api(tree_api);
+    }
+    if let Some(signal) = load_shedding {
+        server_builder = server_builder.with_load_shedding(signal);
+    }
     let server_handles = server_builder
+        .with_filters_pool(pool)
         .with_polling_interval(POLL_INTERVAL)
         .with_tx_sender(tx_sender)
         .with_vm_barrier(vm_barrier)
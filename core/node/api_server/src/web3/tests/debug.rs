@@ -8,7 +8,7 @@ use zksync_web3_decl::{
 
 use super::*;
 
-fn execute_l2_transaction_with_traces(index_in_block: u8) -> TransactionExecutionResult {
+pub(super) fn execute_l2_transaction_with_traces(index_in_block: u8) -> TransactionExecutionResult {
     let first_call_trace = Call {
         from: Address::repeat_byte(index_in_block),
         to: Address::repeat_byte(index_in_block + 1),
@@ -61,7 +61,9 @@ impl HttpTest for TraceBlockTest {
 
             assert_eq!(block_traces.len(), tx_results.len()); // equals to the number of transactions in the block
             for (trace, tx_result) in block_traces.iter().zip(&tx_results) {
-                let api::ResultDebugCall { result } = trace;
+                let api::DebugTrace::Call(result) = &trace.result else {
+                    panic!("Unexpected trace: {trace:?}");
+                };
                 assert_eq!(result.from, Address::zero());
                 assert_eq!(result.to, BOOTLOADER_ADDRESS);
                 assert_eq!(result.gas, tx_result.transaction.gas_limit());
@@ -204,6 +206,9 @@ impl HttpTest for TraceTransactionTest {
             .trace_transaction(tx_results[0].hash, None)
             .await?
             .context("no transaction traces")?;
+        let api::DebugTrace::Call(result) = result else {
+            panic!("Unexpected trace: {result:?}");
+        };
         assert_eq!(result.from, Address::zero());
         assert_eq!(result.to, BOOTLOADER_ADDRESS);
         assert_eq!(result.gas, tx_results[0].transaction.gas_limit());
@@ -218,6 +223,108 @@ async fn tracing_transaction() {
     test_http_server(TraceTransactionTest).await;
 }
 
+#[derive(Debug)]
+struct PrestateTraceTest;
+
+impl PrestateTraceTest {
+    fn prestate_options(diff_mode: bool) -> Option<api::TracerConfig> {
+        Some(api::TracerConfig {
+            tracer: api::SupportedTracers::PrestateTracer,
+            tracer_config: api::CallTracerConfig {
+                diff_mode,
+                ..api::CallTracerConfig::default()
+            },
+        })
+    }
+
+    fn account(slots: &[(H256, H256)]) -> api::PrestateAccount {
+        api::PrestateAccount {
+            storage: slots.iter().copied().collect(),
+            ..api::PrestateAccount::default()
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for PrestateTraceTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1].map(execute_l2_transaction_with_traces);
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+
+        let address = Address::repeat_byte(0x23);
+        let (first_slot, second_slot) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+        let first_key = StorageKey::new(AccountTreeId::new(address), first_slot);
+        let second_key = StorageKey::new(AccountTreeId::new(address), second_slot);
+        let (value1, value2, value3) = (
+            H256::repeat_byte(1),
+            H256::repeat_byte(2),
+            H256::repeat_byte(3),
+        );
+        let storage_logs = [
+            (
+                tx_results[0].hash,
+                vec![StorageLog::new_write_log(first_key, value1)],
+            ),
+            (
+                tx_results[1].hash,
+                vec![
+                    StorageLog::new_write_log(first_key, value2),
+                    StorageLog::new_write_log(second_key, value3),
+                    // Rewriting the slot with the same value should not be reported in the diff mode.
+                    StorageLog::new_write_log(second_key, H256::zero()),
+                ],
+            ),
+        ];
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &storage_logs)
+            .await?;
+        drop(storage);
+
+        let trace = client
+            .trace_transaction(tx_results[1].hash, Self::prestate_options(false))
+            .await?
+            .context("no transaction trace")?;
+        let expected_prestate = api::Prestate::from([(
+            address,
+            Self::account(&[(first_slot, value1), (second_slot, H256::zero())]),
+        )]);
+        assert_eq!(trace, api::DebugTrace::Prestate(expected_prestate));
+
+        let block_traces = client
+            .trace_block_by_number(api::BlockNumber::from(1), Self::prestate_options(true))
+            .await?;
+        let block_traces: Vec<_> = block_traces.into_iter().map(|trace| trace.result).collect();
+        let expected_diffs = [
+            api::PrestateDiff {
+                pre: api::Prestate::from([(address, Self::account(&[(first_slot, H256::zero())]))]),
+                post: api::Prestate::from([(address, Self::account(&[(first_slot, value1)]))]),
+            },
+            api::PrestateDiff {
+                pre: api::Prestate::from([(address, Self::account(&[(first_slot, value1)]))]),
+                post: api::Prestate::from([(address, Self::account(&[(first_slot, value2)]))]),
+            },
+        ];
+        let expected_diffs: Vec<_> = expected_diffs
+            .into_iter()
+            .map(api::DebugTrace::PrestateDiff)
+            .collect();
+        assert_eq!(block_traces, expected_diffs);
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_prestate() {
+    test_http_server(PrestateTraceTest).await;
+}
+
 #[derive(Debug)]
 struct TraceBlockTestWithSnapshotRecovery;
 
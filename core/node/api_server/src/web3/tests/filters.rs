@@ -2,6 +2,7 @@
 
 use std::fmt;
 
+use zksync_utils::h256_to_u256;
 use zksync_web3_decl::{
     jsonrpsee::{
         core::{client::Error, ClientError as RpcError},
@@ -15,6 +16,7 @@ use super::*;
 #[derive(Debug)]
 struct BasicFilterChangesTest {
     snapshot_recovery: bool,
+    persistent_filters: bool,
 }
 
 #[async_trait]
@@ -27,6 +29,10 @@ impl HttpTest for BasicFilterChangesTest {
         }
     }
 
+    fn persistent_filters(&self) -> bool {
+        self.persistent_filters
+    }
+
     async fn test(
         &self,
         client: &DynClient<L2>,
@@ -86,6 +92,7 @@ impl HttpTest for BasicFilterChangesTest {
 async fn basic_filter_changes() {
     test_http_server(BasicFilterChangesTest {
         snapshot_recovery: false,
+        persistent_filters: false,
     })
     .await;
 }
@@ -94,10 +101,77 @@ async fn basic_filter_changes() {
 async fn basic_filter_changes_after_snapshot_recovery() {
     test_http_server(BasicFilterChangesTest {
         snapshot_recovery: true,
+        persistent_filters: false,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn basic_filter_changes_with_persistent_filters() {
+    test_http_server(BasicFilterChangesTest {
+        snapshot_recovery: false,
+        persistent_filters: true,
     })
     .await;
 }
 
+#[derive(Debug)]
+struct SharedPersistentFiltersTest;
+
+#[async_trait]
+impl HttpTest for SharedPersistentFiltersTest {
+    fn persistent_filters(&self) -> bool {
+        true
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let block_filter_id = client.new_block_filter().await?;
+        let mut storage = pool.connection().await?;
+        let stored_filter = storage
+            .api_filters_dal()
+            .get_filter(u256_to_h256(block_filter_id))
+            .await?;
+        assert!(stored_filter.is_some());
+
+        // Emulate a filter installed by another API server sharing the same database.
+        let foreign_filter_id = H256::repeat_byte(0x23);
+        let foreign_filter = serde_json::to_value(TypedFilter::Blocks(L2BlockNumber(1)))?;
+        storage
+            .api_filters_dal()
+            .insert_filter(foreign_filter_id, &foreign_filter)
+            .await?;
+
+        let new_l2_block = store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        for filter_id in [block_filter_id, h256_to_u256(foreign_filter_id)] {
+            let changes = client.get_filter_changes(filter_id).await?;
+            assert_matches!(
+                changes,
+                FilterChanges::Hashes(hashes) if hashes == [new_l2_block.hash]
+            );
+        }
+
+        let removed = client
+            .uninstall_filter(h256_to_u256(foreign_filter_id))
+            .await?;
+        assert!(removed);
+        let stored_filter = storage
+            .api_filters_dal()
+            .get_filter(foreign_filter_id)
+            .await?;
+        assert!(stored_filter.is_none());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn sharing_persistent_filters() {
+    test_http_server(SharedPersistentFiltersTest).await;
+}
+
 #[derive(Debug)]
 struct LogFilterChangesTest {
     snapshot_recovery: bool,
@@ -13,11 +13,14 @@ use zksync_config::{
     configs::{
         api::Web3JsonRpcConfig,
         chain::{NetworkConfig, StateKeeperConfig},
+        database::MerkleTreeMode,
         ContractsConfig,
     },
     GenesisConfig,
 };
 use zksync_dal::{transactions_dal::L2TxSubmissionResult, Connection, ConnectionPool, CoreDal};
+use zksync_merkle_tree::{domain::ZkSyncTree, InMemoryDatabase, TreeInstruction};
+use zksync_metadata_calculator::{api_server::TreeApiClient, AsyncTreeReader};
 use zksync_node_genesis::{insert_genesis_batch, mock_genesis_config, GenesisParams};
 use zksync_node_test_utils::{
     create_l1_batch, create_l1_batch_metadata, create_l2_block, create_l2_transaction,
@@ -25,10 +28,15 @@ use zksync_node_test_utils::{
 };
 use zksync_types::{
     api,
-    block::L2BlockHeader,
+    block::{L1BatchHeader, L2BlockHeader},
+    commitment::{L1BatchMetadata, L1BatchWithMetadata},
     fee::TransactionExecutionMetrics,
+    fee_model::FeeParams,
     get_nonce_key,
     l2::L2Tx,
+    l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+    protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
+    protocol_version::{ProtocolSemanticVersion, VersionPatch},
     storage::get_code_key,
     tokens::{TokenInfo, TokenMetadata},
     tx::{
@@ -36,10 +44,10 @@ use zksync_types::{
         TransactionExecutionResult,
     },
     utils::{storage_key_for_eth_balance, storage_key_for_standard_token_balance},
-    AccountTreeId, Address, L1BatchNumber, Nonce, ProtocolVersionId, StorageKey, StorageLog,
-    VmEvent, H256, U64,
+    AccountTreeId, Address, Execute, L1BatchNumber, Nonce, ProtocolVersion, ProtocolVersionId,
+    StorageKey, StorageLog, VmEvent, H256, U64,
 };
-use zksync_utils::u256_to_h256;
+use zksync_utils::{bytecode::hash_bytecode, u256_to_h256};
 use zksync_web3_decl::{
     client::{Client, DynClient, L2},
     jsonrpsee::{
@@ -51,7 +59,9 @@ use zksync_web3_decl::{
             ErrorObjectOwned,
         },
     },
-    namespaces::{EnNamespaceClient, EthNamespaceClient, ZksNamespaceClient},
+    namespaces::{
+        EnNamespaceClient, EthNamespaceClient, TxpoolNamespaceClient, ZksNamespaceClient,
+    },
 };
 
 use super::*;
@@ -63,6 +73,7 @@ use crate::{
 mod debug;
 mod filters;
 mod snapshots;
+mod trace;
 mod vm;
 mod ws;
 
@@ -144,6 +155,14 @@ trait HttpTest: Send + Sync {
         Arc::default()
     }
 
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        None
+    }
+
+    fn load_shedding(&self) -> Option<watch::Receiver<bool>> {
+        None
+    }
+
     async fn test(&self, client: &DynClient<L2>, pool: &ConnectionPool<Core>)
         -> anyhow::Result<()>;
 
@@ -151,6 +170,11 @@ trait HttpTest: Send + Sync {
     fn filters_disabled(&self) -> bool {
         false
     }
+
+    /// Overrides the `persistent_filters` configuration parameter for HTTP server startup
+    fn persistent_filters(&self) -> bool {
+        false
+    }
 }
 
 /// Storage initialization strategy.
@@ -228,11 +252,14 @@ async fn test_http_server(test: impl HttpTest) {
     let genesis = GenesisConfig::for_tests();
     let mut api_config = InternalApiConfig::new(&web3_config, &contracts_config, &genesis);
     api_config.filters_disabled = test.filters_disabled();
+    api_config.persistent_filters = test.persistent_filters();
     let mut server_handles = spawn_http_server(
         api_config,
         pool.clone(),
         test.transaction_executor(),
         test.method_tracer(),
+        test.tree_api(),
+        test.load_shedding(),
         stop_receiver,
     )
     .await;
@@ -643,6 +670,163 @@ async fn storage_access_with_snapshot_recovery() {
     test_http_server(StorageAccessWithSnapshotRecovery).await;
 }
 
+/// Creates a tree API backed by an in-memory Merkle tree containing the specified entries in each of `l1_batch_count`
+/// L1 batches. Leaf indices are assigned to entries in order, starting from 1.
+fn in_memory_tree_api(
+    entries: impl IntoIterator<Item = (StorageKey, H256)>,
+    l1_batch_count: u32,
+) -> Arc<dyn TreeApiClient> {
+    let db = InMemoryDatabase::new();
+    let mut tree = ZkSyncTree::in_memory(db.clone()).unwrap();
+    let instructions: Vec<_> = entries
+        .into_iter()
+        .zip(1..)
+        .map(|((key, value), leaf_index)| TreeInstruction::write(key, leaf_index, value))
+        .collect();
+    tree.process_l1_batch(&instructions).unwrap();
+    for _ in 1..l1_batch_count {
+        tree.process_l1_batch(&[]).unwrap();
+    }
+    tree.save().unwrap();
+    Arc::new(AsyncTreeReader::in_memory(db, MerkleTreeMode::Full).unwrap())
+}
+
+#[derive(Debug)]
+struct GetProofTest;
+
+impl GetProofTest {
+    const ADDRESS: Address = Address::repeat_byte(1);
+
+    fn storage_key() -> StorageKey {
+        StorageKey::new(AccountTreeId::new(Self::ADDRESS), H256::zero())
+    }
+}
+
+#[async_trait]
+impl HttpTest for GetProofTest {
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        let entries = [
+            // The storage key goes first so that it has leaf index 1.
+            (Self::storage_key(), H256::repeat_byte(0xff)),
+            (
+                storage_key_for_eth_balance(&Self::ADDRESS),
+                H256::from_low_u64_be(123),
+            ),
+            (get_nonce_key(&Self::ADDRESS), H256::from_low_u64_be(5)),
+            (get_code_key(&Self::ADDRESS), H256::repeat_byte(2)),
+        ];
+        Some(in_memory_tree_api(entries, 2))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        store_l2_block(&mut storage, L2BlockNumber(2), &[]).await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        store_l2_block(&mut storage, L2BlockNumber(3), &[]).await?;
+
+        let keys = vec![*Self::storage_key().key(), H256::repeat_byte(1)];
+        // Blocks not finishing an L1 batch are mapped to the previous L1 batch.
+        let expected_l1_batches = [(0, 0), (1, 0), (2, 1), (3, 1)];
+        for (l2_block_number, expected_l1_batch) in expected_l1_batches {
+            let number =
+                api::BlockIdVariant::BlockNumber(api::BlockNumber::Number(l2_block_number.into()));
+            let proof = client
+                .get_proof(Self::ADDRESS, keys.clone(), Some(number))
+                .await?
+                .context("no proof")?;
+
+            let expected_l1_batch = L1BatchNumber(expected_l1_batch);
+            assert_eq!(proof.l1_batch_number, expected_l1_batch);
+            let state_root = storage
+                .blocks_dal()
+                .get_l1_batch_state_root(expected_l1_batch)
+                .await?
+                .context("no state root")?;
+            assert_eq!(proof.storage_hash, state_root);
+
+            assert_eq!(proof.address, Self::ADDRESS);
+            assert_eq!(proof.balance, 123.into());
+            assert_eq!(proof.nonce, 5.into());
+            assert_eq!(proof.code_hash, H256::repeat_byte(2));
+            assert_eq!(proof.account_proof.len(), 3);
+            assert_eq!(proof.storage_proof.len(), 2);
+            assert_eq!(proof.storage_proof[0].key, H256::zero());
+            assert_eq!(proof.storage_proof[0].value, H256::repeat_byte(0xff));
+            assert_eq!(proof.storage_proof[0].index, 1);
+            assert_eq!(proof.storage_proof[1].key, H256::repeat_byte(1));
+            assert_eq!(proof.storage_proof[1].value, H256::zero());
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_proof() {
+    test_http_server(GetProofTest).await;
+}
+
+#[derive(Debug)]
+struct GetMultiAccountProofsTest;
+
+impl GetMultiAccountProofsTest {
+    const ADDRESSES: [Address; 2] = [Address::repeat_byte(1), Address::repeat_byte(2)];
+}
+
+#[async_trait]
+impl HttpTest for GetMultiAccountProofsTest {
+    fn tree_api(&self) -> Option<Arc<dyn TreeApiClient>> {
+        let entries = Self::ADDRESSES.iter().enumerate().map(|(i, &address)| {
+            let key = StorageKey::new(AccountTreeId::new(address), H256::zero());
+            (key, H256::from_low_u64_be(i as u64 + 1))
+        });
+        Some(in_memory_tree_api(entries, 1))
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let queries = vec![
+            api::ProofQuery {
+                address: Self::ADDRESSES[0],
+                keys: vec![H256::zero()],
+            },
+            api::ProofQuery {
+                address: Self::ADDRESSES[1],
+                keys: vec![H256::repeat_byte(1), H256::zero()],
+            },
+        ];
+        let proofs = client
+            .get_proofs(queries, L1BatchNumber(0))
+            .await?
+            .context("no proofs")?;
+
+        assert_eq!(proofs.len(), 2);
+        assert_eq!(proofs[0].address, Self::ADDRESSES[0]);
+        assert_eq!(proofs[0].storage_proof.len(), 1);
+        assert_eq!(proofs[0].storage_proof[0].key, H256::zero());
+        assert_eq!(proofs[0].storage_proof[0].value, H256::from_low_u64_be(1));
+        assert_eq!(proofs[1].address, Self::ADDRESSES[1]);
+        assert_eq!(proofs[1].storage_proof.len(), 2);
+        assert_eq!(proofs[1].storage_proof[0].key, H256::repeat_byte(1));
+        assert_eq!(proofs[1].storage_proof[0].value, H256::zero());
+        assert_eq!(proofs[1].storage_proof[1].key, H256::zero());
+        assert_eq!(proofs[1].storage_proof[1].value, H256::from_low_u64_be(2));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_multi_account_proofs() {
+    test_http_server(GetMultiAccountProofsTest).await;
+}
+
 #[derive(Debug)]
 struct TransactionCountTest;
 
@@ -805,6 +989,313 @@ async fn getting_transaction_count_for_account_after_snapshot_recovery() {
 }
 
 #[derive(Debug)]
+struct StorageInfoTest {
+    snapshot_recovery: bool,
+}
+
+#[async_trait]
+impl HttpTest for StorageInfoTest {
+    fn storage_initialization(&self) -> StorageInitialization {
+        if self.snapshot_recovery {
+            StorageInitialization::empty_recovery()
+        } else {
+            StorageInitialization::Genesis
+        }
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let storage_info = client.get_storage_info().await?;
+        let first_local_l2_block = if self.snapshot_recovery {
+            StorageInitialization::SNAPSHOT_RECOVERY_BLOCK + 1
+        } else {
+            L2BlockNumber(0)
+        };
+        let expected_info = if self.snapshot_recovery {
+            api::StorageInfo {
+                mode: api::StorageMode::Pruned,
+                data_retention_sec: None,
+                first_retained_l1_batch: StorageInitialization::SNAPSHOT_RECOVERY_BATCH + 1,
+                first_retained_l2_block: first_local_l2_block,
+            }
+        } else {
+            api::StorageInfo {
+                mode: api::StorageMode::Archive,
+                data_retention_sec: None,
+                first_retained_l1_batch: L1BatchNumber(0),
+                first_retained_l2_block: first_local_l2_block,
+            }
+        };
+        assert_eq!(storage_info, expected_info);
+
+        // The start of the queried range should be clamped to the first retained block.
+        for from_block in [
+            api::BlockNumber::Number(0.into()),
+            api::BlockNumber::Earliest,
+        ] {
+            let filter = Filter {
+                from_block: Some(from_block),
+                ..Filter::default()
+            };
+            let logs = client.get_logs(filter).await?;
+            assert!(logs.is_empty(), "{logs:?}");
+        }
+
+        // Querying logs from an entirely pruned range should return an error rather than empty results.
+        let filter = Filter {
+            from_block: Some(api::BlockNumber::Number(0.into())),
+            to_block: Some(api::BlockNumber::Number(0.into())),
+            ..Filter::default()
+        };
+        let logs_result = client.get_logs(filter).await;
+        if self.snapshot_recovery {
+            assert_pruned_block_error(&logs_result.unwrap_err(), first_local_l2_block);
+        } else {
+            logs_result?;
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_storage_info() {
+    test_http_server(StorageInfoTest {
+        snapshot_recovery: false,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn getting_storage_info_after_snapshot_recovery() {
+    test_http_server(StorageInfoTest {
+        snapshot_recovery: true,
+    })
+    .await;
+}
+
+#[derive(Debug)]
+struct BalanceHistoryTest;
+
+#[async_trait]
+impl HttpTest for BalanceHistoryTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let address = Address::repeat_byte(0x23);
+        // The balance index is empty, so the method should be unavailable.
+        let err = client
+            .get_balance_history(address, None, None)
+            .await
+            .unwrap_err();
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::MethodNotFound.code());
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        let balance_log = StorageLog::new_write_log(
+            storage_key_for_eth_balance(&address),
+            u256_to_h256(100.into()),
+        );
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &[(H256::zero(), vec![balance_log])])
+            .await?;
+        let checkpoint = api::BalanceCheckpoint {
+            block_number: L2BlockNumber(1),
+            balance: 100.into(),
+        };
+        storage
+            .balance_history_dal()
+            .insert_checkpoints(
+                L2BlockNumber(0)..=L2BlockNumber(1),
+                &[(address, checkpoint)],
+            )
+            .await?;
+
+        let history = client.get_balance_history(address, None, None).await?;
+        let initial_checkpoint = api::BalanceCheckpoint {
+            block_number: L2BlockNumber(0),
+            balance: 0.into(),
+        };
+        assert_eq!(
+            history,
+            api::BalanceHistory {
+                checkpoints: vec![initial_checkpoint, checkpoint],
+                first_indexed_block: L2BlockNumber(0),
+                last_indexed_block: L2BlockNumber(1),
+            }
+        );
+
+        // The checkpoint at the start of the range is returned only once.
+        let history = client
+            .get_balance_history(address, Some(L2BlockNumber(1)), Some(L2BlockNumber(10)))
+            .await?;
+        assert_eq!(history.checkpoints, [checkpoint]);
+
+        let err = client
+            .get_balance_history(address, Some(L2BlockNumber(2)), None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, ClientError::Call(_));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_balance_history() {
+    test_http_server(BalanceHistoryTest).await;
+}
+
+#[derive(Debug)]
+struct TokenTransfersTest;
+
+#[async_trait]
+impl HttpTest for TokenTransfersTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let address = Address::repeat_byte(0x23);
+        let token = Address::repeat_byte(0xff);
+        // The transfer index is empty, so the methods should be unavailable.
+        let err = client.get_token_balances(address, None).await.unwrap_err();
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::MethodNotFound.code());
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+
+        let transfers = [
+            api::TokenTransfer {
+                block_number: L2BlockNumber(1),
+                log_index: 0,
+                transaction_hash: H256::repeat_byte(1),
+                token_address: token,
+                from: Address::zero(),
+                to: address,
+                amount: 100.into(),
+            },
+            api::TokenTransfer {
+                block_number: L2BlockNumber(2),
+                log_index: 0,
+                transaction_hash: H256::repeat_byte(2),
+                token_address: token,
+                from: address,
+                to: Address::repeat_byte(1),
+                amount: 30.into(),
+            },
+        ];
+        let mut storage = pool.connection().await?;
+        storage
+            .token_transfers_dal()
+            .insert_transfers(L2BlockNumber(0)..=L2BlockNumber(2), &transfers)
+            .await?;
+
+        let balances = client.get_token_balances(address, None).await?;
+        assert_eq!(
+            balances,
+            api::TokenBalances {
+                balances: HashMap::from([(token, 70.into())]),
+                last_indexed_block: L2BlockNumber(2),
+                next_token: None,
+            }
+        );
+        let balances = client.get_token_balances(address, Some(token)).await?;
+        assert_eq!(balances.balances, HashMap::new());
+
+        let all_transfers = client.get_token_transfers(address, None, None).await?;
+        assert_eq!(all_transfers, transfers);
+        let later_transfers = client
+            .get_token_transfers(address, Some(L2BlockNumber(2)), Some(L2BlockNumber(10)))
+            .await?;
+        assert_eq!(later_transfers, transfers[1..]);
+        let no_transfers = client
+            .get_token_transfers(address, Some(L2BlockNumber(3)), None)
+            .await?;
+        assert_eq!(no_transfers, []);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_token_transfers() {
+    test_http_server(TokenTransfersTest).await;
+}
+
+#[derive(Debug)]
+struct TxpoolTest;
+
+#[async_trait]
+impl HttpTest for TxpoolTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let content = client.content().await?;
+        assert_eq!(content, api::TxpoolContent::default());
+
+        let test_address = Address::repeat_byte(11);
+        let mut storage = pool.connection().await?;
+        let mut committed_tx = create_l2_transaction(10, 200);
+        committed_tx.common_data.initiator_address = test_address;
+        store_l2_block(
+            &mut storage,
+            L2BlockNumber(1),
+            &[execute_l2_transaction(committed_tx)],
+        )
+        .await?;
+        let nonce_log =
+            StorageLog::new_write_log(get_nonce_key(&test_address), H256::from_low_u64_be(1));
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(1), &[(H256::zero(), vec![nonce_log])])
+            .await?;
+
+        let mut tx_hashes = HashMap::new();
+        for nonce in [1, 2, 4] {
+            let mut mempool_tx = create_l2_transaction(10, 200);
+            mempool_tx.common_data.initiator_address = test_address;
+            mempool_tx.common_data.nonce = Nonce(nonce);
+            tx_hashes.insert(nonce, mempool_tx.hash());
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(&mempool_tx, TransactionExecutionMetrics::default())
+                .await?;
+        }
+
+        let content = client.content().await?;
+        let pending = &content.pending[&test_address];
+        assert_eq!(pending.keys().copied().collect::<Vec<_>>(), [1, 2]);
+        for (nonce, tx) in pending {
+            assert_eq!(tx.hash, tx_hashes[nonce]);
+            assert_eq!(tx.block_number, None);
+        }
+        let queued = &content.queued[&test_address];
+        assert_eq!(queued.keys().copied().collect::<Vec<_>>(), [4]);
+        assert_eq!(queued[&4].hash, tx_hashes[&4]);
+
+        let status = client.status().await?;
+        assert_eq!(status.pending, 2.into());
+        assert_eq!(status.queued, 1.into());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_txpool_content() {
+    test_http_server(TxpoolTest).await;
+}
+
 struct TransactionReceiptsTest;
 
 #[async_trait]
@@ -847,6 +1338,14 @@ impl HttpTest for TransactionReceiptsTest {
             assert_eq!(receipt, expected_receipt);
         }
 
+        // Check receipts for an empty block.
+        store_l2_block(&mut storage, l2_block_number + 1, &[]).await?;
+        let receipts = client
+            .get_block_receipts(api::BlockId::Number((l2_block_number.0 + 1).into()))
+            .await?
+            .context("no receipts for empty block")?;
+        assert!(receipts.is_empty());
+
         // Check receipts for a missing block.
         let receipts = client
             .get_block_receipts(api::BlockId::Number(100.into()))
@@ -862,6 +1361,57 @@ async fn transaction_receipts() {
     test_http_server(TransactionReceiptsTest).await;
 }
 
+#[derive(Debug)]
+struct FeeHistoryTest;
+
+#[async_trait]
+impl HttpTest for FeeHistoryTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let tx_results = [
+            execute_l2_transaction(create_l2_transaction(10, 200)),
+            execute_l2_transaction(create_l2_transaction(10, 200)),
+        ];
+        store_l2_block(&mut storage, L2BlockNumber(1), &tx_results).await?;
+        store_l2_block(&mut storage, L2BlockNumber(2), &[]).await?;
+
+        let history = client
+            .fee_history(3.into(), api::BlockNumber::Latest, vec![25.0, 75.0])
+            .await?;
+        assert_eq!(
+            history.oldest_block,
+            zksync_types::web3::BlockNumber::Number(0.into())
+        );
+        // Base fee for the next block is appended as a placeholder.
+        assert_eq!(history.base_fee_per_gas.len(), 4);
+        assert_eq!(history.gas_used_ratio.len(), 3);
+        // Transactions are charged only for the base fee, so all rewards are zero.
+        let rewards = history.reward.context("no rewards")?;
+        assert_eq!(rewards, vec![vec![U256::zero(); 2]; 3]);
+
+        for invalid_percentiles in [vec![75.0, 25.0], vec![-1.0], vec![101.0]] {
+            let err = client
+                .fee_history(3.into(), api::BlockNumber::Latest, invalid_percentiles)
+                .await
+                .unwrap_err();
+            assert_matches!(
+                err,
+                ClientError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_fee_history() {
+    test_http_server(FeeHistoryTest).await;
+}
+
 #[derive(Debug)]
 struct AllAccountBalancesTest;
 
@@ -1075,3 +1625,630 @@ impl HttpTest for GenesisConfigTest {
 async fn tracing_genesis_config() {
     test_http_server(GenesisConfigTest).await;
 }
+
+#[derive(Debug)]
+struct PaginatedLogsTest;
+
+impl PaginatedLogsTest {
+    async fn get_all_pages(
+        client: &DynClient<L2>,
+        filter: &Filter,
+        limit: usize,
+    ) -> anyhow::Result<Vec<Vec<api::Log>>> {
+        let mut pages = vec![];
+        let mut cursor = None;
+        loop {
+            let page = client
+                .get_logs_paginated(filter.clone(), cursor, Some(limit))
+                .await?;
+            assert!(page.logs.len() <= limit);
+            pages.push(page.logs);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                return Ok(pages);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for PaginatedLogsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let mut all_events = vec![];
+        for (l2_block_number, start_idx) in [(1, 0), (2, 4), (3, 8)] {
+            let (_, events) = store_events(&mut storage, l2_block_number, start_idx).await?;
+            all_events.extend(events);
+        }
+        drop(storage);
+        let all_events: Vec<_> = all_events.iter().collect();
+
+        let all_logs_filter = Filter {
+            from_block: Some(api::BlockNumber::Earliest),
+            ..Filter::default()
+        };
+        let pages = Self::get_all_pages(client, &all_logs_filter, 5).await?;
+        let page_sizes: Vec<_> = pages.iter().map(Vec::len).collect();
+        assert_eq!(page_sizes, [5, 5, 2]);
+        assert_logs_match(&pages.concat(), &all_events);
+
+        let first_page = client
+            .get_logs_paginated(all_logs_filter.clone(), None, Some(5))
+            .await?;
+        assert_eq!(
+            first_page.next_cursor,
+            Some(api::LogCursor {
+                block_number: L2BlockNumber(2),
+                log_index: 0,
+            })
+        );
+        // Without a limit, all logs fit into a single page.
+        let single_page = client
+            .get_logs_paginated(all_logs_filter, None, None)
+            .await?;
+        assert_eq!(single_page.next_cursor, None);
+        assert_logs_match(&single_page.logs, &all_events);
+
+        let address_filter = Filter {
+            from_block: Some(api::BlockNumber::Earliest),
+            to_block: Some(api::BlockNumber::Number(2.into())),
+            address: Some(Address::repeat_byte(23).into()),
+            ..Filter::default()
+        };
+        let pages = Self::get_all_pages(client, &address_filter, 1).await?;
+        let expected_events = [all_events[0], all_events[3], all_events[4], all_events[7]];
+        assert_logs_match(&pages.concat(), &expected_events);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_paginated_logs() {
+    test_http_server(PaginatedLogsTest).await;
+}
+
+#[derive(Debug)]
+struct BatchPubdataTest;
+
+#[async_trait]
+impl HttpTest for BatchPubdataTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        // Batch is not sealed yet.
+        let pubdata = client.get_batch_pubdata(L1BatchNumber(1)).await?;
+        assert_eq!(pubdata, None);
+
+        let l2_to_l1_log = UserL2ToL1Log(L2ToL1Log {
+            sender: Address::repeat_byte(1),
+            key: H256::repeat_byte(2),
+            value: H256::repeat_byte(3),
+            ..L2ToL1Log::default()
+        });
+        let header = L1BatchHeader {
+            l2_to_l1_logs: vec![l2_to_l1_log.clone()],
+            l2_to_l1_messages: vec![b"message".to_vec()],
+            ..create_l1_batch(1)
+        };
+        storage.blocks_dal().insert_mock_l1_batch(&header).await?;
+        storage
+            .blocks_dal()
+            .mark_l2_blocks_as_executed_in_l1_batch(L1BatchNumber(1))
+            .await?;
+        // Batch doesn't have metadata yet.
+        let pubdata = client.get_batch_pubdata(L1BatchNumber(1)).await?;
+        assert_eq!(pubdata, None);
+
+        let metadata = L1BatchMetadata {
+            state_diffs_compressed: vec![1, 2, 3],
+            ..create_l1_batch_metadata(1)
+        };
+        storage
+            .blocks_dal()
+            .save_l1_batch_tree_data(L1BatchNumber(1), &metadata.tree_data())
+            .await?;
+        storage
+            .blocks_dal()
+            .save_l1_batch_commitment_artifacts(
+                L1BatchNumber(1),
+                &l1_batch_metadata_to_commitment_artifacts(&metadata),
+            )
+            .await?;
+        drop(storage);
+
+        let pubdata = client
+            .get_batch_pubdata(L1BatchNumber(1))
+            .await?
+            .context("no pubdata for sealed batch")?;
+        assert_eq!(pubdata.number, L1BatchNumber(1));
+        assert_eq!(
+            pubdata.l2_to_l1_logs,
+            [l2_to_l1_log.0.to_bytes().to_vec().into()]
+        );
+        assert_eq!(pubdata.l2_to_l1_messages, [b"message".to_vec().into()]);
+        assert!(pubdata.published_bytecodes.is_empty());
+        assert_eq!(pubdata.state_diffs_compressed.0, [1, 2, 3]);
+
+        let expected_pubdata =
+            L1BatchWithMetadata::new(header, metadata, HashMap::new(), &[]).construct_pubdata();
+        assert_eq!(pubdata.pubdata.0, expected_pubdata);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_batch_pubdata() {
+    test_http_server(BatchPubdataTest).await;
+}
+
+#[derive(Debug)]
+struct ProtocolVersionsTest;
+
+#[async_trait]
+impl HttpTest for ProtocolVersionsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let next_version = ProtocolVersion {
+            version: ProtocolSemanticVersion::new(ProtocolVersionId::next(), VersionPatch(0)),
+            timestamp: 1_000,
+            ..ProtocolVersion::default()
+        };
+        let mut storage = pool.connection().await?;
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&next_version)
+            .await?;
+        drop(storage);
+
+        let versions = client.get_protocol_versions().await?;
+        assert_eq!(versions.len(), 2, "{versions:?}");
+        let genesis_version = &versions[0];
+        assert_eq!(
+            genesis_version.minor_version,
+            ProtocolVersionId::latest() as u16
+        );
+        assert_eq!(genesis_version.activation_l1_batch, Some(L1BatchNumber(0)));
+        assert!(genesis_version.activation_timestamp.is_some());
+        assert_eq!(genesis_version.patches.len(), 1);
+
+        let next_version_info = &versions[1];
+        assert_eq!(
+            next_version_info.minor_version,
+            ProtocolVersionId::next() as u16
+        );
+        assert_eq!(next_version_info.timestamp, 1_000);
+        assert_eq!(next_version_info.activation_l1_batch, None);
+        assert_eq!(next_version_info.activation_timestamp, None);
+        assert_eq!(next_version_info.l2_system_upgrade_tx_hash, None);
+        assert_eq!(
+            next_version_info.patches[0].verifier_config,
+            next_version.l1_verifier_config
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_protocol_versions() {
+    test_http_server(ProtocolVersionsTest).await;
+}
+
+#[derive(Debug)]
+struct ProtocolUpgradeTest;
+
+#[async_trait]
+impl HttpTest for ProtocolUpgradeTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let bytecode = vec![0; 32];
+        let upgrade_tx = ProtocolUpgradeTx {
+            execute: Execute {
+                contract_address: Address::repeat_byte(0x11),
+                calldata: vec![1, 2, 3],
+                value: 0.into(),
+                factory_deps: Some(vec![bytecode.clone()]),
+            },
+            common_data: ProtocolUpgradeTxCommonData {
+                sender: Address::repeat_byte(0x22),
+                upgrade_id: ProtocolVersionId::next(),
+                max_fee_per_gas: 0.into(),
+                gas_limit: 1_000_000.into(),
+                gas_per_pubdata_limit: 800.into(),
+                eth_block: 10,
+                canonical_tx_hash: H256::repeat_byte(0x33),
+                to_mint: 0.into(),
+                refund_recipient: Address::zero(),
+            },
+            received_timestamp_ms: 0,
+        };
+        let next_version = ProtocolVersion {
+            version: ProtocolSemanticVersion::new(ProtocolVersionId::next(), VersionPatch(0)),
+            timestamp: 1_000,
+            tx: Some(upgrade_tx),
+            ..ProtocolVersion::default()
+        };
+        let mut storage = pool.connection().await?;
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(&next_version)
+            .await?;
+        drop(storage);
+
+        let genesis_upgrade = client
+            .get_protocol_upgrade(ProtocolVersionId::latest() as u16)
+            .await?
+            .context("no genesis upgrade")?;
+        assert_eq!(
+            genesis_upgrade.minor_version,
+            ProtocolVersionId::latest() as u16
+        );
+
+        let upgrade = client
+            .get_protocol_upgrade(ProtocolVersionId::next() as u16)
+            .await?
+            .context("no scheduled upgrade")?;
+        assert_eq!(upgrade.minor_version, ProtocolVersionId::next() as u16);
+        assert_eq!(upgrade.timestamp, 1_000);
+        let upgrade_tx = upgrade.upgrade_tx.context("no upgrade tx")?;
+        assert_eq!(upgrade_tx.hash, H256::repeat_byte(0x33));
+        assert_eq!(upgrade_tx.sender, Address::repeat_byte(0x22));
+        assert_eq!(upgrade_tx.contract_address, Address::repeat_byte(0x11));
+        assert_eq!(upgrade_tx.calldata.0, [1, 2, 3]);
+        assert_eq!(upgrade_tx.gas_limit, 1_000_000.into());
+        assert_eq!(upgrade_tx.factory_dep_hashes, [hash_bytecode(&bytecode)]);
+        assert_eq!(upgrade_tx.l1_block_number, 10);
+
+        let missing_upgrade = client.get_protocol_upgrade(u16::MAX).await?;
+        assert_eq!(missing_upgrade, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_protocol_upgrade() {
+    test_http_server(ProtocolUpgradeTest).await;
+}
+
+#[derive(Debug)]
+struct RawBlocksTransactionsTest;
+
+#[async_trait]
+impl HttpTest for RawBlocksTransactionsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        let first_tx = create_l2_transaction(1, 2);
+        store_l2_block(
+            &mut storage,
+            L2BlockNumber(1),
+            &[execute_l2_transaction(first_tx.clone())],
+        )
+        .await?;
+        store_l2_block(&mut storage, L2BlockNumber(2), &[]).await?;
+        let other_txs = [create_l2_transaction(3, 4), create_l2_transaction(5, 6)];
+        let other_tx_results: Vec<_> = other_txs
+            .iter()
+            .cloned()
+            .map(execute_l2_transaction)
+            .collect();
+        store_l2_block(&mut storage, L2BlockNumber(3), &other_tx_results).await?;
+        drop(storage);
+
+        // Blocks after the last sealed one should be ignored.
+        let blocks = client
+            .get_raw_blocks_transactions(L2BlockNumber(0), L2BlockNumber(100))
+            .await?;
+        let block_numbers: Vec<_> = blocks.iter().map(|block| block.number.0).collect();
+        assert_eq!(block_numbers, [0, 1, 2, 3]);
+        assert!(blocks[0].transactions.is_empty());
+        assert!(blocks[2].transactions.is_empty());
+        let first_block_hashes: Vec<_> =
+            blocks[1].transactions.iter().map(|tx| tx.hash()).collect();
+        assert_eq!(first_block_hashes, [first_tx.hash()]);
+        let last_block_hashes: Vec<_> = blocks[3].transactions.iter().map(|tx| tx.hash()).collect();
+        assert_eq!(
+            last_block_hashes,
+            [other_txs[0].hash(), other_txs[1].hash()]
+        );
+
+        for number in 0..=3 {
+            let expected_txs = client
+                .get_raw_block_transactions(L2BlockNumber(number))
+                .await?;
+            let blocks = client
+                .get_raw_blocks_transactions(L2BlockNumber(number), L2BlockNumber(number))
+                .await?;
+            assert_eq!(blocks.len(), 1);
+            assert_eq!(
+                serde_json::to_value(&blocks[0].transactions)?,
+                serde_json::to_value(&expected_txs)?
+            );
+        }
+
+        let blocks = client
+            .get_raw_blocks_transactions(L2BlockNumber(3), L2BlockNumber(1))
+            .await?;
+        assert!(blocks.is_empty());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_raw_blocks_transactions() {
+    test_http_server(RawBlocksTransactionsTest).await;
+}
+
+#[derive(Debug)]
+struct L1BatchDaDetailsTest;
+
+#[async_trait]
+impl HttpTest for L1BatchDaDetailsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+
+        let details = client
+            .get_l1_batch_details(L1BatchNumber(1))
+            .await?
+            .context("no details for sealed L1 batch")?;
+        assert_eq!(details.data_availability, None);
+
+        storage
+            .data_availability_dal()
+            .insert_l1_batch_da(L1BatchNumber(1), "Avail", "0x0102")
+            .await?;
+        let details = client
+            .get_l1_batch_details(L1BatchNumber(1))
+            .await?
+            .context("no details for sealed L1 batch")?;
+        let da_details = details.data_availability.context("no DA details")?;
+        assert_eq!(da_details.client_type, "Avail");
+        assert_eq!(da_details.blob_id, "0x0102");
+        assert!(!da_details.inclusion_data_available);
+
+        storage
+            .data_availability_dal()
+            .save_l1_batch_inclusion_data(L1BatchNumber(1), b"proof")
+            .await?;
+        let details = client
+            .get_l1_batch_details(L1BatchNumber(1))
+            .await?
+            .context("no details for sealed L1 batch")?;
+        let da_details = details.data_availability.context("no DA details")?;
+        assert!(da_details.inclusion_data_available);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_l1_batch_da_details() {
+    test_http_server(L1BatchDaDetailsTest).await;
+}
+
+#[derive(Debug)]
+struct FeeParamsTest;
+
+#[async_trait]
+impl HttpTest for FeeParamsTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let fee_params = client.get_fee_params().await?;
+        // The test server uses the mock provider returning the sensible V1 default.
+        let FeeParams::V1(expected_params) = FeeParams::sensible_v1_default() else {
+            unreachable!();
+        };
+        assert_matches!(
+            fee_params,
+            FeeParams::V1(params)
+                if params.l1_gas_price == expected_params.l1_gas_price
+                    && params.config.minimal_l2_gas_price == expected_params.config.minimal_l2_gas_price
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_fee_params() {
+    test_http_server(FeeParamsTest).await;
+}
+
+#[derive(Debug)]
+struct BaseTokenConversionRatioTest;
+
+#[async_trait]
+impl HttpTest for BaseTokenConversionRatioTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let ratio = client.get_base_token_conversion_ratio().await?;
+        assert_eq!(ratio, None);
+
+        let new_ratio = api::BaseTokenConversionRatio {
+            numerator: 3.try_into().unwrap(),
+            denominator: 2.try_into().unwrap(),
+            ratio_timestamp: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        };
+        let mut storage = pool.connection().await?;
+        storage
+            .base_token_dal()
+            .insert_token_ratio(&new_ratio)
+            .await?;
+        drop(storage);
+
+        let ratio = client.get_base_token_conversion_ratio().await?;
+        assert_eq!(ratio, Some(new_ratio));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_base_token_conversion_ratio() {
+    test_http_server(BaseTokenConversionRatioTest).await;
+}
+
+#[derive(Debug)]
+struct BytecodeByHashTest;
+
+#[async_trait]
+impl HttpTest for BytecodeByHashTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let sealed_hash = H256::repeat_byte(1);
+        let pending_hash = H256::repeat_byte(2);
+        let bytecode = vec![0xab; 64];
+
+        let mut storage = pool.connection().await?;
+        store_l2_block(&mut storage, L2BlockNumber(1), &[]).await?;
+        storage
+            .factory_deps_dal()
+            .insert_factory_deps(
+                L2BlockNumber(1),
+                &HashMap::from([(sealed_hash, bytecode.clone())]),
+            )
+            .await?;
+        // Factory deps from the pending L2 block must not be returned.
+        storage
+            .factory_deps_dal()
+            .insert_factory_deps(
+                L2BlockNumber(2),
+                &HashMap::from([(pending_hash, bytecode.clone())]),
+            )
+            .await?;
+        drop(storage);
+
+        let sealed_bytecode = client.get_bytecode_by_hash(sealed_hash).await?;
+        assert_eq!(sealed_bytecode, Some(bytecode));
+        let pending_bytecode = client.get_bytecode_by_hash(pending_hash).await?;
+        assert_eq!(pending_bytecode, None);
+        let missing_bytecode = client.get_bytecode_by_hash(H256::zero()).await?;
+        assert_eq!(missing_bytecode, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_bytecode_by_hash() {
+    test_http_server(BytecodeByHashTest).await;
+}
+
+#[derive(Debug)]
+struct L1GasPriceHistoryTest;
+
+#[async_trait]
+impl HttpTest for L1GasPriceHistoryTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let samples = client.get_l1_gas_price_history(None, None, None).await?;
+        assert_eq!(samples, []);
+
+        let samples: Vec<_> = (1_u64..=5)
+            .map(|number| api::L1GasPriceSample {
+                l1_block_number: number,
+                l1_block_timestamp: number * 12,
+                base_fee_per_gas: (number * 1_000_000_000).into(),
+                blob_base_fee: (number > 2).then(|| number.into()),
+            })
+            .collect();
+        let mut storage = pool.connection().await?;
+        storage
+            .l1_gas_price_history_dal()
+            .insert_samples(&samples)
+            .await?;
+        drop(storage);
+
+        let all_samples = client.get_l1_gas_price_history(None, None, None).await?;
+        assert_eq!(all_samples, samples);
+        let filtered_samples = client
+            .get_l1_gas_price_history(Some(24), Some(48), None)
+            .await?;
+        assert_eq!(filtered_samples, samples[1..4]);
+        let limited_samples = client
+            .get_l1_gas_price_history(Some(24), None, Some(2))
+            .await?;
+        assert_eq!(limited_samples, samples[1..3]);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn getting_l1_gas_price_history() {
+    test_http_server(L1GasPriceHistoryTest).await;
+}
+
+#[derive(Debug)]
+struct LoadSheddingTest {
+    signal_sender: watch::Sender<bool>,
+}
+
+impl LoadSheddingTest {
+    fn new() -> Self {
+        Self {
+            signal_sender: watch::channel(false).0,
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for LoadSheddingTest {
+    fn load_shedding(&self) -> Option<watch::Receiver<bool>> {
+        Some(self.signal_sender.subscribe())
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        client.get_block_number().await?;
+
+        self.signal_sender.send_replace(true);
+        let err = client.get_block_number().await.unwrap_err();
+        assert_matches!(err, ClientError::Call(err) if err.code() == 6);
+        // Methods not requiring DB access are unaffected.
+        client.chain_id().await?;
+        // Neither are methods necessary for transaction submission.
+        client.get_transaction_count(Address::zero(), None).await?;
+
+        self.signal_sender.send_replace(false);
+        client.get_block_number().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn shedding_load_for_saturated_pool() {
+    test_http_server(LoadSheddingTest::new()).await;
+}
@@ -0,0 +1,93 @@
+//! Tests for the `trace` Web3 namespace.
+
+use zksync_types::{
+    api::trace::{TraceFilter, TraceType},
+    BOOTLOADER_ADDRESS,
+};
+use zksync_web3_decl::{
+    client::{DynClient, L2},
+    namespaces::TraceNamespaceClient,
+};
+
+use super::{debug::execute_l2_transaction_with_traces, *};
+
+#[derive(Debug)]
+struct TraceNamespaceTest(L2BlockNumber);
+
+#[async_trait]
+impl HttpTest for TraceNamespaceTest {
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let tx_results = [0, 1, 2].map(execute_l2_transaction_with_traces);
+        let mut storage = pool.connection().await?;
+        let new_l2_block = store_l2_block(&mut storage, self.0, &tx_results).await?;
+        drop(storage);
+
+        let block_traces = client.trace_block((*self.0).into()).await?;
+        // Each transaction has a top-level call with 2 child calls.
+        assert_eq!(block_traces.len(), tx_results.len() * 3);
+        for (i, (tx_traces, tx_result)) in block_traces.chunks(3).zip(&tx_results).enumerate() {
+            for trace in tx_traces {
+                assert_eq!(trace.block_number, u64::from(self.0 .0));
+                assert_eq!(trace.block_hash, new_l2_block.hash);
+                assert_eq!(trace.transaction_hash, tx_result.hash);
+                assert_eq!(trace.transaction_position, i);
+                assert_eq!(trace.trace_type, TraceType::Call);
+            }
+
+            assert_eq!(tx_traces[0].caller(), Address::zero());
+            assert_eq!(tx_traces[0].callee(), BOOTLOADER_ADDRESS);
+            assert_eq!(tx_traces[0].subtraces, 2);
+            assert!(tx_traces[0].trace_address.is_empty());
+            for (j, (trace, call)) in tx_traces[1..]
+                .iter()
+                .zip(&tx_result.call_traces)
+                .enumerate()
+            {
+                assert_eq!(trace.caller(), call.from);
+                assert_eq!(trace.callee(), call.to);
+                assert_eq!(trace.subtraces, 0);
+                assert_eq!(trace.trace_address, [j]);
+            }
+        }
+
+        let latest_block_traces = client.trace_block(api::BlockNumber::Latest).await?;
+        assert_eq!(latest_block_traces, block_traces);
+
+        let tx_traces = client
+            .trace_transaction(tx_results[1].hash)
+            .await?
+            .expect("no traces for transaction");
+        assert_eq!(tx_traces, block_traces[3..6]);
+        let missing_tx_traces = client.trace_transaction(H256::zero()).await?;
+        assert_eq!(missing_tx_traces, None);
+
+        let filter = TraceFilter {
+            from_block: Some(api::BlockNumber::Earliest),
+            to_block: Some((*self.0 + 100).into()),
+            to_address: Some(vec![Address::repeat_byte(2)]),
+            ..TraceFilter::default()
+        };
+        let filtered_traces = client.trace_filter(filter).await?;
+        // Only the first call in the second transaction matches the filter.
+        assert_eq!(filtered_traces, block_traces[4..5]);
+
+        let filter = TraceFilter {
+            after: Some(1),
+            count: Some(2),
+            ..TraceFilter::default()
+        };
+        let filtered_traces = client.trace_filter(filter).await?;
+        assert_eq!(filtered_traces, block_traces[1..3]);
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_with_trace_namespace() {
+    test_http_server(TraceNamespaceTest(L2BlockNumber(1))).await;
+}
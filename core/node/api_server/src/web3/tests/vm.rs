@@ -4,7 +4,7 @@ use std::sync::atomic::{AtomicU32, Ordering};
 
 use itertools::Itertools;
 use multivm::{
-    interface::{ExecutionResult, VmRevertReason},
+    interface::{ExecutionResult, Halt, VmRevertReason},
     vm_latest::{VmExecutionLogs, VmExecutionResultAndLogs},
 };
 use zksync_types::{
@@ -160,6 +160,180 @@ async fn call_method_after_snapshot_recovery() {
     test_http_server(CallTestAfterSnapshotRecovery).await;
 }
 
+#[derive(Debug)]
+struct SimulateTest;
+
+impl SimulateTest {
+    fn simulate_block(calldata: &[&[u8]]) -> api::SimulateBlock {
+        api::SimulateBlock {
+            calls: calldata
+                .iter()
+                .map(|data| CallTest::call_request(data))
+                .collect(),
+            ..api::SimulateBlock::default()
+        }
+    }
+
+    fn assert_invalid_params(err: ClientError) {
+        if let ClientError::Call(err) = err {
+            assert_eq!(err.code(), ErrorCode::InvalidParams.code());
+        } else {
+            panic!("Unexpected error: {err:?}");
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for SimulateTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, block_args| {
+            assert_eq!(block_args.resolved_block_number(), L2BlockNumber(1));
+            match tx.execute.calldata() {
+                b"success" => ExecutionResult::Success {
+                    output: b"output".to_vec(),
+                },
+                b"revert" => ExecutionResult::Revert {
+                    output: VmRevertReason::General {
+                        msg: "oops".to_owned(),
+                        data: vec![],
+                    },
+                },
+                data => panic!("Unexpected calldata: {data:?}"),
+            }
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let mut second_block = Self::simulate_block(&[b"success"]);
+        second_block.block_overrides = Some(api::BlockOverrides {
+            time: Some(1_000.into()),
+            ..api::BlockOverrides::default()
+        });
+        let payload = api::SimulatePayload {
+            block_state_calls: vec![Self::simulate_block(&[b"success", b"revert"]), second_block],
+            ..api::SimulatePayload::default()
+        };
+        let blocks = client.simulate_v1(payload, None).await?;
+
+        assert_eq!(blocks.len(), 2);
+        let [successful_call, reverted_call] = blocks[0].calls.as_slice() else {
+            panic!("Unexpected calls: {:?}", blocks[0].calls);
+        };
+        assert_eq!(blocks[0].number, 1.into());
+        assert_eq!(successful_call.status, 1.into());
+        assert_eq!(successful_call.return_data.0, b"output");
+        assert!(successful_call.error.is_none());
+        assert_eq!(reverted_call.status, 0.into());
+        let error = reverted_call.error.as_ref().unwrap();
+        assert_eq!(error.code, 3);
+        assert!(error.message.contains("oops"), "{error:?}");
+
+        assert_eq!(blocks[1].number, 2.into());
+        assert_eq!(blocks[1].parent_hash, blocks[0].hash);
+        assert_eq!(blocks[1].timestamp, 1_000.into());
+        assert_eq!(blocks[1].calls.len(), 1);
+
+        let invalid_payloads = [
+            api::SimulatePayload::default(),
+            api::SimulatePayload {
+                block_state_calls: vec![Self::simulate_block(&[])],
+                ..api::SimulatePayload::default()
+            },
+            api::SimulatePayload {
+                block_state_calls: vec![Self::simulate_block(&[b"success"])],
+                validation: true,
+                ..api::SimulatePayload::default()
+            },
+        ];
+        for payload in invalid_payloads {
+            let err = client.simulate_v1(payload, None).await.unwrap_err();
+            Self::assert_invalid_params(err);
+        }
+
+        // The first block number cannot be overridden.
+        let mut block = Self::simulate_block(&[b"success"]);
+        block.block_overrides = Some(api::BlockOverrides {
+            number: Some(100.into()),
+            ..api::BlockOverrides::default()
+        });
+        let payload = api::SimulatePayload {
+            block_state_calls: vec![block],
+            ..api::SimulatePayload::default()
+        };
+        let err = client.simulate_v1(payload, None).await.unwrap_err();
+        Self::assert_invalid_params(err);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulating_calls() {
+    test_http_server(SimulateTest).await;
+}
+
+#[derive(Debug)]
+struct CreateAccessListTest;
+
+#[async_trait]
+impl HttpTest for CreateAccessListTest {
+    fn transaction_executor(&self) -> MockTransactionExecutor {
+        let mut tx_executor = MockTransactionExecutor::default();
+        tx_executor.set_call_responses(|tx, _| match tx.execute.calldata() {
+            b"success" => ExecutionResult::Success {
+                output: b"output".to_vec(),
+            },
+            b"revert" => ExecutionResult::Revert {
+                output: VmRevertReason::General {
+                    msg: "oops".to_owned(),
+                    data: vec![],
+                },
+            },
+            b"halt" => ExecutionResult::Halt {
+                reason: Halt::UnexpectedVMBehavior("oops".to_owned()),
+            },
+            data => panic!("Unexpected calldata: {data:?}"),
+        });
+        tx_executor
+    }
+
+    async fn test(
+        &self,
+        client: &DynClient<L2>,
+        _pool: &ConnectionPool<Core>,
+    ) -> anyhow::Result<()> {
+        let result = client
+            .create_access_list(CallTest::call_request(b"success"), None)
+            .await?;
+        // The mock executor doesn't run tracers, so the access list is always empty.
+        assert_eq!(result.access_list, []);
+        assert_eq!(result.error, None);
+
+        let result = client
+            .create_access_list(CallTest::call_request(b"revert"), None)
+            .await?;
+        let error = result.error.unwrap();
+        assert!(error.contains("oops"), "{error}");
+
+        let err = client
+            .create_access_list(CallTest::call_request(b"halt"), None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, ClientError::Call(_));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn creating_access_list() {
+    test_http_server(CreateAccessListTest).await;
+}
+
 #[derive(Debug)]
 struct SendRawTransactionTest {
     snapshot_recovery: bool,
@@ -328,6 +502,7 @@ impl HttpTest for SendTransactionWithDetailedOutputTest {
             events: self.vm_events(),
             user_l2_to_l1_logs: Default::default(),
             system_l2_to_l1_logs: Default::default(),
+            storage_reads: None,
             total_log_queries_count: 0,
         };
 
@@ -340,6 +515,8 @@ impl HttpTest for SendTransactionWithDetailedOutputTest {
                 logs: vm_execution_logs.clone(),
                 statistics: Default::default(),
                 refunds: Default::default(),
+                call_traces: None,
+                gas_by_contract: None,
             }
         });
         tx_executor
@@ -400,7 +577,10 @@ async fn send_raw_transaction_with_detailed_output() {
 struct TraceCallTest;
 
 impl TraceCallTest {
-    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugCall) {
+    fn assert_debug_call(call_request: &CallRequest, call_result: &api::DebugTrace) {
+        let api::DebugTrace::Call(call_result) = call_result else {
+            panic!("Unexpected trace: {call_result:?}");
+        };
         assert_eq!(call_result.from, Address::zero());
         assert_eq!(call_result.gas, call_request.gas.unwrap());
         assert_eq!(call_result.value, call_request.value.unwrap());
@@ -602,6 +782,23 @@ impl HttpTest for EstimateGasTest {
             );
         }
 
+        let estimate = client
+            .estimate_fee(l2_transaction.clone().into(), None)
+            .await?;
+        assert_eq!(estimate.breakdown, None);
+        let estimate = client
+            .estimate_fee(l2_transaction.clone().into(), Some(true))
+            .await?;
+        let breakdown = estimate.breakdown.context("no fee breakdown")?;
+        assert_eq!(
+            breakdown.computational_gas + breakdown.gas_for_pubdata + breakdown.overhead,
+            estimate.fee.gas_limit
+        );
+        assert_eq!(
+            breakdown.gas_per_pubdata,
+            estimate.fee.gas_per_pubdata_limit
+        );
+
         // Check transaction with value.
         if !self.snapshot_recovery {
             // Manually set sufficient balance for the transaction account.
@@ -8,7 +8,12 @@ use http::StatusCode;
 use tokio::sync::watch;
 use zksync_config::configs::chain::NetworkConfig;
 use zksync_dal::ConnectionPool;
-use zksync_types::{api, Address, L1BatchNumber, H160, H2048, H256, U64};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    api,
+    l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
+    Address, L1BatchNumber, H160, H2048, H256, U64,
+};
 use zksync_web3_decl::{
     client::{WsClient, L2},
     jsonrpsee::{
@@ -20,7 +25,7 @@ use zksync_web3_decl::{
         rpc_params,
     },
     namespaces::{EthNamespaceClient, ZksNamespaceClient},
-    types::{BlockHeader, Bytes, PubSubFilter},
+    types::{BlockHeader, Bytes, L2ToL1LogNotification, L2ToL1LogStatus, PubSubFilter},
 };
 
 use super::*;
@@ -114,8 +119,12 @@ async fn notifiers_start_after_snapshot_recovery() {
     let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
     let mut subscribe_logic = EthSubscribe::new();
     subscribe_logic.set_events_sender(events_sender);
-    let notifier_handles =
-        subscribe_logic.spawn_notifiers(pool.clone(), POLL_INTERVAL, stop_receiver);
+    let notifier_handles = subscribe_logic.spawn_notifiers(
+        pool.clone(),
+        Some(pool.clone()),
+        POLL_INTERVAL,
+        stop_receiver,
+    );
     assert!(!notifier_handles.is_empty());
 
     // Wait a little doing nothing and check that notifier tasks are still active (i.e., have not panicked).
@@ -136,6 +145,7 @@ async fn notifiers_start_after_snapshot_recovery() {
             SubscriptionType::Blocks,
             SubscriptionType::Txs,
             SubscriptionType::Logs,
+            SubscriptionType::L2ToL1Logs,
         ],
     )
     .await;
@@ -146,6 +156,64 @@ async fn notifiers_start_after_snapshot_recovery() {
     }
 }
 
+#[tokio::test]
+async fn notifiers_are_woken_up_by_new_l2_blocks() {
+    // Large enough for notifiers to never poll during the test.
+    const LONG_POLL_INTERVAL: Duration = Duration::from_secs(3_600);
+
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_recovery_snapshot(
+        &mut storage,
+        StorageInitialization::SNAPSHOT_RECOVERY_BATCH,
+        StorageInitialization::SNAPSHOT_RECOVERY_BLOCK,
+        &[],
+    )
+    .await;
+    // Notifiers wait for the first L2 block using polling, so we create it in advance.
+    store_l2_block(
+        &mut storage,
+        StorageInitialization::SNAPSHOT_RECOVERY_BLOCK + 1,
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let (events_sender, mut events_receiver) = mpsc::unbounded_channel();
+    let mut subscribe_logic = EthSubscribe::new();
+    subscribe_logic.set_events_sender(events_sender);
+    let notifier_handles = subscribe_logic.spawn_notifiers(
+        pool.clone(),
+        Some(pool.clone()),
+        LONG_POLL_INTERVAL,
+        stop_receiver,
+    );
+    wait_for_notifiers(
+        &mut events_receiver,
+        &[SubscriptionType::Blocks, SubscriptionType::Logs],
+    )
+    .await;
+
+    for offset in 2..=4 {
+        let l2_block_number = StorageInitialization::SNAPSHOT_RECOVERY_BLOCK + offset;
+        store_l2_block(&mut storage, l2_block_number, &[])
+            .await
+            .unwrap();
+        wait_for_notifier_l2_block(
+            &mut events_receiver,
+            SubscriptionType::Blocks,
+            l2_block_number,
+        )
+        .await;
+    }
+
+    stop_sender.send_replace(true);
+    for handle in notifier_handles {
+        handle.await.unwrap().expect("Notifier task failed");
+    }
+}
+
 #[async_trait]
 trait WsTest: Send + Sync {
     /// Prepares the storage before the server is started. The default implementation performs genesis.
@@ -662,6 +730,137 @@ async fn log_subscriptions_with_delay() {
     test_ws_server(LogSubscriptionsWithDelayTest).await;
 }
 
+#[derive(Debug)]
+struct L2ToL1LogSubscriptionsTest;
+
+impl L2ToL1LogSubscriptionsTest {
+    fn l2_to_l1_log(sender: Address, tx_number_in_block: u16) -> UserL2ToL1Log {
+        UserL2ToL1Log(L2ToL1Log {
+            shard_id: 0,
+            is_service: false,
+            tx_number_in_block,
+            sender,
+            key: H256::repeat_byte(1),
+            value: H256::repeat_byte(2),
+        })
+    }
+
+    async fn next_notification(
+        subscription: &mut Subscription<L2ToL1LogNotification>,
+    ) -> anyhow::Result<L2ToL1LogNotification> {
+        tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for L2-to-L1 log")?
+            .context("L2-to-L1 logs subscription terminated")?
+            .map_err(Into::into)
+    }
+}
+
+#[async_trait]
+impl WsTest for L2ToL1LogSubscriptionsTest {
+    async fn test(
+        &self,
+        client: &WsClient<L2>,
+        pool: &ConnectionPool<Core>,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifiers(&mut pub_sub_events, &[SubscriptionType::L2ToL1Logs]).await;
+
+        let params = rpc_params!["l2ToL1Logs"];
+        let mut all_logs_subscription = client
+            .subscribe::<L2ToL1LogNotification, _>("zks_subscribe", params, "zks_unsubscribe")
+            .await?;
+        let sender = Address::repeat_byte(23);
+        let sender_filter = PubSubFilter {
+            address: Some(sender.into()),
+            topics: None,
+        };
+        let params = rpc_params!["l2ToL1Logs", sender_filter];
+        let mut sender_subscription = client
+            .subscribe::<L2ToL1LogNotification, _>("zks_subscribe", params, "zks_unsubscribe")
+            .await?;
+        for _ in 0..2 {
+            wait_for_subscription(&mut pub_sub_events, SubscriptionType::L2ToL1Logs).await;
+        }
+
+        // Topics are not supported for L2-to-L1 logs.
+        let topic_filter = PubSubFilter {
+            address: None,
+            topics: Some(vec![Some(H256::zero().into())]),
+        };
+        let params = rpc_params!["l2ToL1Logs", topic_filter];
+        let err = client
+            .subscribe::<L2ToL1LogNotification, _>("zks_subscribe", params, "zks_unsubscribe")
+            .await
+            .unwrap_err();
+        assert_matches!(err, ClientError::Call(err) if err.code() == ErrorCode::InvalidParams.code());
+
+        let tx_result = execute_l2_transaction(create_l2_transaction(1, 2));
+        let tx_hash = tx_result.hash;
+        let logs = [
+            Self::l2_to_l1_log(Address::repeat_byte(1), 0),
+            Self::l2_to_l1_log(sender, 0),
+        ];
+        let location = IncludedTxLocation {
+            tx_hash,
+            tx_index_in_l2_block: 0,
+            tx_initiator_address: Address::default(),
+        };
+        let mut storage = pool.connection().await?;
+        // Store the block atomically, so that the notifier doesn't observe the block without logs.
+        let mut transaction = storage.start_transaction().await?;
+        store_l2_block(&mut transaction, L2BlockNumber(1), &[tx_result]).await?;
+        transaction
+            .events_dal()
+            .save_user_l2_to_l1_logs(L2BlockNumber(1), &[(location, logs.iter().collect())])
+            .await?;
+        transaction.commit().await?;
+
+        for (i, log) in logs.iter().enumerate() {
+            let notification = Self::next_notification(&mut all_logs_subscription).await?;
+            assert_eq!(notification.status, L2ToL1LogStatus::Sealed);
+            assert_eq!(notification.proof_id, None);
+            assert_eq!(notification.log.block_number, 1.into());
+            assert_eq!(notification.log.l1_batch_number, None);
+            assert_eq!(notification.log.transaction_hash, tx_hash);
+            assert_eq!(notification.log.log_index, i.into());
+            assert_eq!(notification.log.sender, log.0.sender);
+        }
+        let notification = Self::next_notification(&mut sender_subscription).await?;
+        assert_eq!(notification.status, L2ToL1LogStatus::Sealed);
+        assert_eq!(notification.log.sender, sender);
+
+        seal_l1_batch(&mut storage, L1BatchNumber(1)).await?;
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(1),
+                AggregatedActionType::Execute,
+                H256::repeat_byte(0xee),
+                chrono::Utc::now(),
+            )
+            .await?;
+        drop(storage);
+
+        for (i, log) in logs.iter().enumerate() {
+            let notification = Self::next_notification(&mut all_logs_subscription).await?;
+            assert_eq!(notification.status, L2ToL1LogStatus::Executed);
+            assert_eq!(notification.proof_id, Some(i as u32));
+            assert_eq!(notification.log.l1_batch_number, Some(1.into()));
+            assert_eq!(notification.log.sender, log.0.sender);
+        }
+        let notification = Self::next_notification(&mut sender_subscription).await?;
+        assert_eq!(notification.status, L2ToL1LogStatus::Executed);
+        assert_eq!(notification.proof_id, Some(1));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l2_to_l1_log_subscriptions() {
+    test_ws_server(L2ToL1LogSubscriptionsTest).await;
+}
+
 #[derive(Debug)]
 struct RateLimitingTest;
 
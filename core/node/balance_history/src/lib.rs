@@ -0,0 +1,147 @@
+//! Indexer of historical base token balances of accounts.
+
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_system_constants::L2_BASE_TOKEN_ADDRESS;
+use zksync_types::{api::BalanceCheckpoint, utils::storage_key_for_eth_balance, Address};
+use zksync_utils::{h256_to_account_address, h256_to_u256};
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of [`BalanceHistoryIndexer`].
+#[derive(Debug)]
+pub struct BalanceHistoryIndexerConfig {
+    /// Interval between polling for new L2 blocks if the indexer has caught up with the sealed L2 blocks.
+    pub poll_interval: Duration,
+    /// Maximum number of L2 blocks indexed at a time.
+    pub max_l2_blocks_per_iteration: u32,
+}
+
+impl Default for BalanceHistoryIndexerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            max_l2_blocks_per_iteration: 100,
+        }
+    }
+}
+
+/// Maintains balance checkpoints of accounts for sealed L2 blocks.
+///
+/// Accounts that could have their balance changed in an L2 block are determined from the indexed topics of events
+/// emitted by the base token contract (e.g., `Transfer`, `Mint` and `Withdrawal`). For each such account,
+/// the indexer records its balance after each L2 block in which the balance storage slot was written to.
+#[derive(Debug)]
+pub struct BalanceHistoryIndexer {
+    pool: ConnectionPool<Core>,
+    config: BalanceHistoryIndexerConfig,
+}
+
+impl BalanceHistoryIndexer {
+    pub fn new(pool: ConnectionPool<Core>, config: BalanceHistoryIndexerConfig) -> Self {
+        assert!(
+            config.max_l2_blocks_per_iteration > 0,
+            "max_l2_blocks_per_iteration must be positive"
+        );
+        Self { pool, config }
+    }
+
+    /// Indexes the next range of L2 blocks. Returns `Ok(true)` if there may be more L2 blocks to index,
+    /// and `Ok(false)` if the indexer should wait before the next iteration.
+    async fn index_next_range(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("balance_history").await?;
+        let Some(last_sealed_l2_block) = storage.blocks_dal().get_sealed_l2_block_number().await?
+        else {
+            return Ok(false);
+        };
+        let indexed_range = storage.balance_history_dal().get_indexed_range().await?;
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let next_l2_block = match &indexed_range {
+            Some(range) => *range.end() + 1,
+            None => pruning_info.first_retained_l2_block(),
+        };
+        pruning_info
+            .ensure_l2_block_retained(next_l2_block)
+            .context("next L2 block to index is pruned; the balance index cannot be continued")?;
+
+        if next_l2_block > last_sealed_l2_block {
+            return Ok(false);
+        }
+        let latency = METRICS.iteration_latency.start();
+        let last_l2_block =
+            last_sealed_l2_block.min(next_l2_block + (self.config.max_l2_blocks_per_iteration - 1));
+        let l2_blocks = next_l2_block..=last_l2_block;
+
+        let topics = storage
+            .balance_history_dal()
+            .get_event_topics(l2_blocks.clone(), L2_BASE_TOKEN_ADDRESS)
+            .await?;
+        let accounts_by_key: HashMap<_, _> = topics
+            .into_iter()
+            .filter(|topic| topic[..12] == [0; 12])
+            .map(|topic| {
+                let address = h256_to_account_address(&topic);
+                (storage_key_for_eth_balance(&address).hashed_key(), address)
+            })
+            .collect();
+        let hashed_keys: Vec<_> = accounts_by_key.keys().copied().collect();
+        let writes = storage
+            .balance_history_dal()
+            .get_final_writes(l2_blocks.clone(), &hashed_keys)
+            .await?;
+
+        let checkpoints: Vec<(Address, BalanceCheckpoint)> = writes
+            .into_iter()
+            .map(|(block_number, hashed_key, value)| {
+                let checkpoint = BalanceCheckpoint {
+                    block_number,
+                    balance: h256_to_u256(value),
+                };
+                (accounts_by_key[&hashed_key], checkpoint)
+            })
+            .collect();
+        storage
+            .balance_history_dal()
+            .insert_checkpoints(l2_blocks.clone(), &checkpoints)
+            .await?;
+        drop(storage);
+
+        let latency = latency.observe();
+        tracing::debug!(
+            "Indexed {} balance checkpoints for L2 blocks {l2_blocks:?} in {latency:?}",
+            checkpoints.len()
+        );
+        METRICS
+            .inserted_checkpoints
+            .inc_by(checkpoints.len() as u64);
+        METRICS.last_indexed_l2_block.set(last_l2_block.0.into());
+        Ok(last_l2_block < last_sealed_l2_block)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting balance history indexer with configuration {:?}",
+            self.config
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            let has_more_l2_blocks = self.index_next_range().await?;
+            if !has_more_l2_blocks
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, balance history indexer is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Gauge, Histogram, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "balance_history")]
+pub(super) struct BalanceHistoryMetrics {
+    /// Last L2 block covered by the balance index.
+    pub last_indexed_l2_block: Gauge<u64>,
+    /// Total number of balance checkpoints inserted into the index.
+    pub inserted_checkpoints: Counter,
+    /// Latency of indexing a range of L2 blocks.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub iteration_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<BalanceHistoryMetrics> = vise::Global::new();
@@ -0,0 +1,191 @@
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{create_l2_block, prepare_recovery_snapshot};
+use zksync_types::{
+    tx::IncludedTxLocation, L1BatchNumber, L2BlockNumber, StorageLog, VmEvent, H256, U256,
+};
+use zksync_utils::{address_to_h256, u256_to_h256};
+
+use super::*;
+
+fn test_config() -> BalanceHistoryIndexerConfig {
+    BalanceHistoryIndexerConfig {
+        poll_interval: Duration::from_millis(10),
+        max_l2_blocks_per_iteration: 1,
+    }
+}
+
+fn transfer_event(from: Address, to: Address) -> VmEvent {
+    VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: L2_BASE_TOKEN_ADDRESS,
+        indexed_topics: vec![
+            H256::repeat_byte(0xff), // Event signature; not used by the indexer
+            address_to_h256(&from),
+            address_to_h256(&to),
+        ],
+        value: vec![],
+    }
+}
+
+fn balance_write(address: Address, balance: u64) -> StorageLog {
+    StorageLog::new_write_log(
+        storage_key_for_eth_balance(&address),
+        u256_to_h256(balance.into()),
+    )
+}
+
+async fn seal_l2_block(
+    pool: &ConnectionPool<Core>,
+    number: u32,
+    events: &[VmEvent],
+    logs: Vec<StorageLog>,
+) {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(number))
+        .await
+        .unwrap();
+    let location = IncludedTxLocation {
+        tx_hash: H256::from_low_u64_be(number.into()),
+        tx_index_in_l2_block: 0,
+        tx_initiator_address: Address::zero(),
+    };
+    storage
+        .events_dal()
+        .save_events(
+            L2BlockNumber(number),
+            &[(location, events.iter().collect())],
+        )
+        .await
+        .unwrap();
+    storage
+        .storage_logs_dal()
+        .insert_storage_logs(L2BlockNumber(number), &[(location.tx_hash, logs)])
+        .await
+        .unwrap();
+}
+
+async fn get_history(pool: &ConnectionPool<Core>, address: Address) -> Vec<BalanceCheckpoint> {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .balance_history_dal()
+        .get_balance_history(address, L2BlockNumber(0)..=L2BlockNumber(u32::MAX), 100)
+        .await
+        .unwrap()
+}
+
+fn checkpoint(block_number: u32, balance: u64) -> BalanceCheckpoint {
+    BalanceCheckpoint {
+        block_number: L2BlockNumber(block_number),
+        balance: U256::from(balance),
+    }
+}
+
+#[tokio::test]
+async fn indexing_balance_changes() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(storage);
+
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    let carol = Address::repeat_byte(3);
+    seal_l2_block(
+        &pool,
+        1,
+        &[transfer_event(alice, bob)],
+        vec![balance_write(alice, 90), balance_write(bob, 10)],
+    )
+    .await;
+    seal_l2_block(
+        &pool,
+        2,
+        &[transfer_event(alice, carol), transfer_event(alice, carol)],
+        vec![
+            balance_write(alice, 85),
+            balance_write(carol, 5),
+            balance_write(alice, 80),
+            balance_write(carol, 10),
+            // Write unrelated to any event; should not be indexed.
+            balance_write(bob, 0),
+        ],
+    )
+    .await;
+
+    let indexer = BalanceHistoryIndexer::new(pool.clone(), test_config());
+    // Genesis block
+    assert!(indexer.index_next_range().await.unwrap());
+    assert!(indexer.index_next_range().await.unwrap());
+    assert!(!indexer.index_next_range().await.unwrap());
+    assert!(!indexer.index_next_range().await.unwrap());
+
+    let mut storage = pool.connection().await.unwrap();
+    let indexed_range = storage
+        .balance_history_dal()
+        .get_indexed_range()
+        .await
+        .unwrap();
+    assert_eq!(indexed_range, Some(L2BlockNumber(0)..=L2BlockNumber(2)));
+    drop(storage);
+
+    assert_eq!(
+        get_history(&pool, alice).await,
+        [checkpoint(1, 90), checkpoint(2, 80)]
+    );
+    assert_eq!(get_history(&pool, bob).await, [checkpoint(1, 10)]);
+    assert_eq!(get_history(&pool, carol).await, [checkpoint(2, 10)]);
+}
+
+#[tokio::test]
+async fn indexing_starts_from_first_retained_l2_block() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let alice = Address::repeat_byte(1);
+    let snapshot_recovery = prepare_recovery_snapshot(
+        &mut storage,
+        L1BatchNumber(23),
+        L2BlockNumber(42),
+        &[balance_write(alice, 100)],
+    )
+    .await;
+    drop(storage);
+
+    let indexer = BalanceHistoryIndexer::new(pool.clone(), test_config());
+    assert!(!indexer.index_next_range().await.unwrap());
+
+    let next_l2_block = snapshot_recovery.l2_block_number.0 + 1;
+    seal_l2_block(
+        &pool,
+        next_l2_block,
+        &[transfer_event(alice, Address::zero())],
+        vec![balance_write(alice, 50)],
+    )
+    .await;
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let indexer_task = tokio::spawn(indexer.run(stop_receiver));
+    loop {
+        let history = get_history(&pool, alice).await;
+        if !history.is_empty() {
+            assert_eq!(history, [checkpoint(next_l2_block, 50)]);
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    stop_sender.send_replace(true);
+    indexer_task.await.unwrap().unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    let indexed_range = storage
+        .balance_history_dal()
+        .get_indexed_range()
+        .await
+        .unwrap();
+    assert_eq!(
+        indexed_range,
+        Some(L2BlockNumber(next_l2_block)..=L2BlockNumber(next_l2_block))
+    );
+}
@@ -0,0 +1,115 @@
+//! Persister of the conversion ratio between ETH and the base token of the chain.
+
+use std::{fmt, num::NonZeroU64, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::sync::watch;
+use zksync_config::configs::BaseTokenAdjusterConfig;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::api::BaseTokenConversionRatio;
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Source of the ETH <-> base token conversion ratio.
+#[async_trait]
+pub trait BaseTokenRatioSource: fmt::Debug + Send + Sync {
+    /// Fetches the current conversion ratio.
+    async fn fetch_ratio(&self) -> anyhow::Result<BaseTokenConversionRatio>;
+}
+
+/// Ratio source returning the fixed ratio from the configuration.
+#[derive(Debug)]
+pub struct FixedRatioSource {
+    numerator: NonZeroU64,
+    denominator: NonZeroU64,
+}
+
+impl FixedRatioSource {
+    pub fn new(config: &BaseTokenAdjusterConfig) -> Self {
+        Self {
+            numerator: config.fixed_ratio_numerator,
+            denominator: config.fixed_ratio_denominator,
+        }
+    }
+}
+
+#[async_trait]
+impl BaseTokenRatioSource for FixedRatioSource {
+    async fn fetch_ratio(&self) -> anyhow::Result<BaseTokenConversionRatio> {
+        Ok(BaseTokenConversionRatio {
+            numerator: self.numerator,
+            denominator: self.denominator,
+            ratio_timestamp: Utc::now(),
+        })
+    }
+}
+
+/// Periodically fetches the conversion ratio from a [`BaseTokenRatioSource`] and persists it in Postgres
+/// if it has changed.
+#[derive(Debug)]
+pub struct BaseTokenRatioPersister {
+    pool: ConnectionPool<Core>,
+    poll_interval: Duration,
+    source: Box<dyn BaseTokenRatioSource>,
+}
+
+impl BaseTokenRatioPersister {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        config: &BaseTokenAdjusterConfig,
+        source: Box<dyn BaseTokenRatioSource>,
+    ) -> Self {
+        Self {
+            pool,
+            poll_interval: config.price_polling_interval(),
+            source,
+        }
+    }
+
+    /// Fetches the ratio and persists it. Returns `Ok(true)` if a new ratio was inserted.
+    async fn loop_iteration(&self) -> anyhow::Result<bool> {
+        let ratio = self.source.fetch_ratio().await?;
+        METRICS
+            .ratio
+            .set(ratio.numerator.get() as f64 / ratio.denominator.get() as f64);
+        let mut storage = self.pool.connection_tagged("base_token_adjuster").await?;
+        let latest_ratio = storage.base_token_dal().get_latest_ratio().await?;
+        let is_changed = latest_ratio.map_or(true, |latest| {
+            latest.numerator != ratio.numerator || latest.denominator != ratio.denominator
+        });
+        if is_changed {
+            let id = storage.base_token_dal().insert_token_ratio(&ratio).await?;
+            tracing::info!("Persisted base token conversion ratio {ratio:?} with ID {id}");
+            METRICS.persisted_ratios.inc();
+        }
+        Ok(is_changed)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting base token ratio persister with source {:?} and poll interval {:?}",
+            self.source,
+            self.poll_interval
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.loop_iteration().await {
+                tracing::warn!("Failed persisting base token conversion ratio: {err:#}");
+                METRICS.errors.inc();
+            }
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, base token ratio persister is shutting down");
+        Ok(())
+    }
+}
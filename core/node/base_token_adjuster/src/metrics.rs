@@ -0,0 +1,15 @@
+use vise::{Counter, Gauge, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "base_token_adjuster")]
+pub(super) struct BaseTokenAdjusterMetrics {
+    /// Last fetched conversion ratio, i.e. the number of ETH wei corresponding to a single base token unit.
+    pub ratio: Gauge<f64>,
+    /// Number of conversion ratios inserted into Postgres.
+    pub persisted_ratios: Counter,
+    /// Number of failed attempts to fetch or persist the conversion ratio.
+    pub errors: Counter,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<BaseTokenAdjusterMetrics> = vise::Global::new();
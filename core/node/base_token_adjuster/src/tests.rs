@@ -0,0 +1,77 @@
+use std::num::NonZeroU64;
+
+use super::*;
+
+fn test_config(numerator: u64, denominator: u64) -> BaseTokenAdjusterConfig {
+    BaseTokenAdjusterConfig {
+        price_polling_interval_ms: 10,
+        fixed_ratio_numerator: NonZeroU64::new(numerator).unwrap(),
+        fixed_ratio_denominator: NonZeroU64::new(denominator).unwrap(),
+    }
+}
+
+#[derive(Debug)]
+struct FailingSource;
+
+#[async_trait]
+impl BaseTokenRatioSource for FailingSource {
+    async fn fetch_ratio(&self) -> anyhow::Result<BaseTokenConversionRatio> {
+        anyhow::bail!("price source is unavailable")
+    }
+}
+
+#[tokio::test]
+async fn persisting_ratio_only_if_changed() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let config = test_config(3, 2);
+    let persister = BaseTokenRatioPersister::new(
+        pool.clone(),
+        &config,
+        Box::new(FixedRatioSource::new(&config)),
+    );
+
+    assert!(persister.loop_iteration().await.unwrap());
+    assert!(!persister.loop_iteration().await.unwrap());
+    let mut storage = pool.connection().await.unwrap();
+    let latest_ratio = storage
+        .base_token_dal()
+        .get_latest_ratio()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest_ratio.numerator.get(), 3);
+    assert_eq!(latest_ratio.denominator.get(), 2);
+
+    let config = test_config(5, 2);
+    let persister = BaseTokenRatioPersister::new(
+        pool.clone(),
+        &config,
+        Box::new(FixedRatioSource::new(&config)),
+    );
+    assert!(persister.loop_iteration().await.unwrap());
+    let latest_ratio = storage
+        .base_token_dal()
+        .get_latest_ratio()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest_ratio.numerator.get(), 5);
+}
+
+#[tokio::test]
+async fn persister_survives_source_errors() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let persister =
+        BaseTokenRatioPersister::new(pool.clone(), &test_config(1, 1), Box::new(FailingSource));
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let persister_task = tokio::spawn(persister.run(stop_receiver));
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!persister_task.is_finished());
+    stop_sender.send_replace(true);
+    persister_task.await.unwrap().unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    let latest_ratio = storage.base_token_dal().get_latest_ratio().await.unwrap();
+    assert_eq!(latest_ratio, None);
+}
@@ -0,0 +1,124 @@
+//! Dispatcher of block notifications from the transactional outbox.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+
+use self::metrics::METRICS;
+pub use self::sinks::{NotificationSink, WebhookSink};
+
+mod metrics;
+mod sinks;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of [`BlockNotificationsDispatcher`].
+#[derive(Debug)]
+pub struct BlockNotificationsDispatcherConfig {
+    /// Interval between polling the outbox if there are no pending notifications, or if publishing
+    /// the notifications has failed.
+    pub poll_interval: Duration,
+    /// Maximum number of notifications dispatched at a time.
+    pub max_batch_size: usize,
+}
+
+/// Dispatches notifications from the outbox to [sinks](NotificationSink), and then removes them from the outbox.
+///
+/// Notifications are dispatched in the order of their IDs. Sinks receive notifications at least once:
+/// if a sink fails, the notifications will be published to all sinks again after a delay.
+#[derive(Debug)]
+pub struct BlockNotificationsDispatcher {
+    pool: ConnectionPool<Core>,
+    config: BlockNotificationsDispatcherConfig,
+    sinks: Vec<Box<dyn NotificationSink>>,
+}
+
+impl BlockNotificationsDispatcher {
+    pub fn new(pool: ConnectionPool<Core>, config: BlockNotificationsDispatcherConfig) -> Self {
+        assert!(config.max_batch_size > 0, "max_batch_size must be positive");
+        Self {
+            pool,
+            config,
+            sinks: vec![],
+        }
+    }
+
+    /// Adds an external sink for notifications.
+    pub fn add_sink(&mut self, sink: Box<dyn NotificationSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Dispatches a single batch of notifications. Returns `Ok(true)` if there may be more pending notifications
+    /// in the outbox, and `Ok(false)` if the dispatcher should wait before the next iteration.
+    async fn dispatch_batch(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("block_notifications").await?;
+        let notifications = storage
+            .outbox_dal()
+            .get_pending_notifications(self.config.max_batch_size)
+            .await?;
+        // Do not hold the connection while notifications are published.
+        drop(storage);
+
+        let Some(last_notification) = notifications.last() else {
+            return Ok(false);
+        };
+        let last_id = last_notification.id;
+        tracing::debug!(
+            "Dispatching {} block notifications with IDs up to {last_id}",
+            notifications.len()
+        );
+
+        for sink in &self.sinks {
+            let latency = METRICS.sink_latency[&sink.name()].start();
+            if let Err(err) = sink.publish(&notifications).await {
+                tracing::warn!(
+                    "Failed publishing {} block notifications to sink `{}`, retrying in {:?}: {err:#}",
+                    notifications.len(),
+                    sink.name(),
+                    self.config.poll_interval
+                );
+                METRICS.sink_errors[&sink.name()].inc();
+                return Ok(false);
+            }
+            latency.observe();
+        }
+
+        let notifications_count = notifications.len();
+        let ids: Vec<_> = notifications
+            .iter()
+            .map(|notification| notification.id)
+            .collect();
+        let mut storage = self.pool.connection_tagged("block_notifications").await?;
+        storage.outbox_dal().remove_notifications(&ids).await?;
+        METRICS
+            .dispatched_notifications
+            .inc_by(notifications_count as u64);
+        METRICS.last_dispatched_id.set(last_id);
+        Ok(notifications_count == self.config.max_batch_size)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting block notifications dispatcher with configuration {:?} and sinks {:?}",
+            self.config,
+            self.sinks
+                .iter()
+                .map(|sink| sink.name())
+                .collect::<Vec<_>>()
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            let has_more_notifications = self.dispatch_batch().await?;
+            if !has_more_notifications
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, block notifications dispatcher is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "block_notifications")]
+pub(super) struct BlockNotificationsMetrics {
+    /// Total number of notifications dispatched from the outbox.
+    pub dispatched_notifications: Counter,
+    /// ID of the last dispatched notification.
+    pub last_dispatched_id: Gauge<u64>,
+    /// Latency of publishing a batch of notifications to a sink.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds, labels = ["sink"])]
+    pub sink_latency: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of errors publishing notifications to a sink.
+    #[metrics(labels = ["sink"])]
+    pub sink_errors: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<BlockNotificationsMetrics> = vise::Global::new();
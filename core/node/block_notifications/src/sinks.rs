@@ -0,0 +1,56 @@
+//! External sinks for block notifications.
+
+use std::{fmt, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use zksync_dal::outbox_dal::OutboxNotification;
+
+/// External destination for block notifications, such as a webhook or a message queue.
+#[async_trait]
+pub trait NotificationSink: fmt::Debug + Send + Sync + 'static {
+    /// Name of the sink used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Publishes a batch of notifications. Notifications are ordered by their ID; the same notifications
+    /// may be published again if publishing to this or another sink fails.
+    async fn publish(&self, notifications: &[OutboxNotification]) -> anyhow::Result<()>;
+}
+
+/// Sink `POST`ing notifications to the specified URL as a JSON array.
+#[derive(Debug)]
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn publish(&self, notifications: &[OutboxNotification]) -> anyhow::Result<()> {
+        self.client
+            .post(&self.url)
+            .json(notifications)
+            .timeout(Self::REQUEST_TIMEOUT)
+            .send()
+            .await
+            .context("failed sending webhook request")?
+            .error_for_status()
+            .context("webhook responded with error status")?;
+        Ok(())
+    }
+}
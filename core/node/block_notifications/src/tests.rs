@@ -0,0 +1,141 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+use async_trait::async_trait;
+use zksync_dal::outbox_dal::{BlockNotification, L1BatchStatus, OutboxNotification};
+use zksync_types::{L1BatchNumber, L2BlockNumber, H256};
+
+use super::*;
+
+#[derive(Debug, Default)]
+struct MockSink {
+    is_failing: AtomicBool,
+    published: Mutex<Vec<OutboxNotification>>,
+}
+
+#[async_trait]
+impl NotificationSink for Arc<MockSink> {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn publish(&self, notifications: &[OutboxNotification]) -> anyhow::Result<()> {
+        anyhow::ensure!(!self.is_failing.load(Ordering::SeqCst), "sink is failing");
+        self.published
+            .lock()
+            .unwrap()
+            .extend_from_slice(notifications);
+        Ok(())
+    }
+}
+
+fn test_config() -> BlockNotificationsDispatcherConfig {
+    BlockNotificationsDispatcherConfig {
+        poll_interval: Duration::from_millis(10),
+        max_batch_size: 2,
+    }
+}
+
+fn mock_notifications() -> Vec<BlockNotification> {
+    let mut notifications: Vec<_> = (1..=3)
+        .map(|number| BlockNotification::NewL2Block {
+            number: L2BlockNumber(number),
+            hash: H256::from_low_u64_be(number.into()),
+            timestamp: number.into(),
+        })
+        .collect();
+    notifications.push(BlockNotification::L1BatchStatus {
+        number: L1BatchNumber(1),
+        status: L1BatchStatus::Sealed,
+    });
+    notifications
+}
+
+async fn insert_notifications(pool: &ConnectionPool<Core>, notifications: &[BlockNotification]) {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .outbox_dal()
+        .insert_notifications(notifications)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn dispatching_notifications() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let notifications = mock_notifications();
+    insert_notifications(&pool, &notifications).await;
+
+    let sink = Arc::new(MockSink::default());
+    let mut dispatcher = BlockNotificationsDispatcher::new(pool.clone(), test_config());
+    dispatcher.add_sink(Box::new(sink.clone()));
+
+    assert!(dispatcher.dispatch_batch().await.unwrap());
+    assert!(dispatcher.dispatch_batch().await.unwrap());
+    assert!(!dispatcher.dispatch_batch().await.unwrap());
+
+    let published: Vec<_> = sink
+        .published
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|notification| notification.notification.clone())
+        .collect();
+    assert_eq!(published, notifications);
+
+    let mut storage = pool.connection().await.unwrap();
+    let pending = storage
+        .outbox_dal()
+        .get_pending_notifications(10)
+        .await
+        .unwrap();
+    assert!(pending.is_empty(), "{pending:?}");
+}
+
+#[tokio::test]
+async fn notifications_are_retained_if_sink_fails() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let notifications = mock_notifications();
+    insert_notifications(&pool, &notifications[..1]).await;
+
+    let sink = Arc::new(MockSink::default());
+    sink.is_failing.store(true, Ordering::SeqCst);
+    let mut dispatcher = BlockNotificationsDispatcher::new(pool.clone(), test_config());
+    dispatcher.add_sink(Box::new(sink.clone()));
+
+    assert!(!dispatcher.dispatch_batch().await.unwrap());
+    let mut storage = pool.connection().await.unwrap();
+    let pending = storage
+        .outbox_dal()
+        .get_pending_notifications(10)
+        .await
+        .unwrap();
+    assert_eq!(pending.len(), 1);
+    drop(storage);
+
+    sink.is_failing.store(false, Ordering::SeqCst);
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let dispatcher_task = tokio::spawn(dispatcher.run(stop_receiver));
+    loop {
+        let mut storage = pool.connection().await.unwrap();
+        let pending = storage
+            .outbox_dal()
+            .get_pending_notifications(10)
+            .await
+            .unwrap();
+        if pending.is_empty() {
+            break;
+        }
+        drop(storage);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    stop_sender.send_replace(true);
+    dispatcher_task.await.unwrap().unwrap();
+    let published = sink.published.lock().unwrap();
+    assert_eq!(published.len(), 1);
+    assert_eq!(published[0].notification, notifications[0]);
+    assert_eq!(published[0].id, pending[0].id);
+}
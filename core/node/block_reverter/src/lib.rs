@@ -341,6 +341,16 @@ impl BlockReverter {
             .storage_logs_dal()
             .roll_back_storage_logs(last_l2_block_to_keep)
             .await?;
+        tracing::info!("Rolling back balance checkpoints");
+        transaction
+            .balance_history_dal()
+            .roll_back_checkpoints(last_l2_block_to_keep)
+            .await?;
+        tracing::info!("Rolling back token transfers");
+        transaction
+            .token_transfers_dal()
+            .roll_back_transfers(last_l2_block_to_keep)
+            .await?;
         tracing::info!("Rolling back Ethereum transactions");
         transaction
             .eth_sender_dal()
@@ -0,0 +1,594 @@
+//! Decoding of messages produced by the `pgoutput` output plugin into [`ChangeEvent`]s.
+//!
+//! See [Postgres docs](https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html)
+//! for the description of the message formats.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber, H256};
+
+use crate::{ChangeEvent, TransactionStatus};
+
+/// Kind of a row change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Insert,
+    Update,
+}
+
+/// Value of a column in a decoded change.
+#[derive(Debug, PartialEq)]
+enum ColumnValue<'a> {
+    Null,
+    /// Value of a TOASTed column that wasn't changed by an `UPDATE`; the plugin doesn't output such values.
+    UnchangedToast,
+    /// Value in the Postgres text format.
+    Value(&'a str),
+}
+
+/// Reader for `pgoutput` messages. Integers are big-endian, and strings are null-terminated.
+#[derive(Debug)]
+struct MessageReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MessageReader<'a> {
+    fn bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        anyhow::ensure!(self.data.len() >= len, "unexpected end of message");
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> anyhow::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> anyhow::Result<u16> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> anyhow::Result<u32> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn str(&mut self) -> anyhow::Result<&'a str> {
+        let len = self
+            .data
+            .iter()
+            .position(|&byte| byte == 0)
+            .context("unterminated string")?;
+        let value = std::str::from_utf8(self.bytes(len)?).context("string is not UTF-8")?;
+        self.bytes(1)?; // null terminator
+        Ok(value)
+    }
+
+    fn tuple(&mut self) -> anyhow::Result<Vec<ColumnValue<'a>>> {
+        let column_count = self.u16()?;
+        (0..column_count)
+            .map(|_| {
+                Ok(match self.u8()? {
+                    b'n' => ColumnValue::Null,
+                    b'u' => ColumnValue::UnchangedToast,
+                    b't' => {
+                        let len = self.u32()? as usize;
+                        let value = std::str::from_utf8(self.bytes(len)?)
+                            .context("column value is not UTF-8")?;
+                        ColumnValue::Value(value)
+                    }
+                    kind => anyhow::bail!("unsupported column value kind `{}`", char::from(kind)),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Relation (i.e., table) description sent by the plugin before the first change in the relation.
+#[derive(Debug)]
+struct Relation {
+    namespace: String,
+    name: String,
+    /// Names of the published columns in the order they are output in row changes.
+    columns: Vec<String>,
+}
+
+/// Row change for a table in the `public` schema.
+#[derive(Debug)]
+struct RowChange<'a> {
+    table: &'a str,
+    action: Action,
+    columns: HashMap<&'a str, ColumnValue<'a>>,
+}
+
+impl<'a> RowChange<'a> {
+    fn optional_value(&self, column: &str) -> anyhow::Result<Option<&'a str>> {
+        match self.columns.get(column) {
+            Some(ColumnValue::Value(value)) => Ok(Some(*value)),
+            Some(ColumnValue::Null) => Ok(None),
+            Some(ColumnValue::UnchangedToast) => {
+                anyhow::bail!("column `{column}` is not output by the plugin")
+            }
+            None => anyhow::bail!("missing column `{column}`"),
+        }
+    }
+
+    fn value(&self, column: &str) -> anyhow::Result<&'a str> {
+        self.optional_value(column)?
+            .with_context(|| format!("column `{column}` is unexpectedly null"))
+    }
+
+    fn optional_u64(&self, column: &str) -> anyhow::Result<Option<u64>> {
+        let Some(value) = self.optional_value(column)? else {
+            return Ok(None);
+        };
+        let value = value
+            .parse()
+            .with_context(|| format!("invalid integer in column `{column}`"))?;
+        Ok(Some(value))
+    }
+
+    fn u64(&self, column: &str) -> anyhow::Result<u64> {
+        self.optional_u64(column)?
+            .with_context(|| format!("column `{column}` is unexpectedly null"))
+    }
+
+    fn u32(&self, column: &str) -> anyhow::Result<u32> {
+        let value = self.u64(column)?;
+        u32::try_from(value).with_context(|| format!("value in column `{column}` is too large"))
+    }
+
+    fn bytes(&self, column: &str) -> anyhow::Result<Vec<u8>> {
+        let value = self.value(column)?;
+        let hex_value = value
+            .strip_prefix("\\x")
+            .with_context(|| format!("column `{column}` is not a byte array"))?;
+        hex::decode(hex_value).with_context(|| format!("invalid hex in column `{column}`"))
+    }
+
+    fn h256(&self, column: &str) -> anyhow::Result<H256> {
+        let bytes = self.bytes(column)?;
+        anyhow::ensure!(bytes.len() == 32, "unexpected length of `{column}`");
+        Ok(H256::from_slice(&bytes))
+    }
+
+    fn address(&self, column: &str) -> anyhow::Result<Address> {
+        let bytes = self.bytes(column)?;
+        anyhow::ensure!(bytes.len() == 20, "unexpected length of `{column}`");
+        Ok(Address::from_slice(&bytes))
+    }
+
+    fn into_event(self) -> anyhow::Result<Option<ChangeEvent>> {
+        Ok(Some(match (self.table, self.action) {
+            ("transactions", Action::Insert) => ChangeEvent::NewTransaction {
+                hash: self.h256("hash")?,
+                initiator_address: self.address("initiator_address")?,
+                nonce: self.optional_u64("nonce")?,
+            },
+            ("transactions", Action::Update) => {
+                let status = if let Some(error) = self.optional_value("error")? {
+                    TransactionStatus::Rejected {
+                        error: error.to_owned(),
+                    }
+                } else if let Some(number) = self.optional_u64("miniblock_number")? {
+                    let l1_batch_number = self.optional_u64("l1_batch_number")?;
+                    TransactionStatus::Included {
+                        l2_block_number: L2BlockNumber(number.try_into()?),
+                        l1_batch_number: l1_batch_number
+                            .map(|number| number.try_into().map(L1BatchNumber))
+                            .transpose()?,
+                    }
+                } else {
+                    TransactionStatus::Pending
+                };
+                ChangeEvent::TransactionStatus {
+                    hash: self.h256("hash")?,
+                    status,
+                }
+            }
+            ("miniblocks", Action::Insert) => ChangeEvent::NewL2Block {
+                number: L2BlockNumber(self.u32("number")?),
+                hash: self.h256("hash")?,
+            },
+            ("l1_batches", Action::Insert) => ChangeEvent::NewL1Batch {
+                number: L1BatchNumber(self.u32("number")?),
+                timestamp: self.u64("timestamp")?,
+            },
+            _ => return Ok(None),
+        }))
+    }
+}
+
+/// Message decoded by [`ChangeDecoder`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum DecodedMessage {
+    /// Row change corresponding to a [`ChangeEvent`].
+    Event(ChangeEvent),
+    /// Commit of the transaction containing the preceding changes.
+    Commit,
+    /// Message not corresponding to a change event, e.g. a relation description or a transaction start.
+    Other,
+}
+
+/// Stateful decoder of `pgoutput` messages. The plugin describes each relation before the first change in it
+/// within a decoding session, so a new decoder should be used for each session.
+#[derive(Debug, Default)]
+pub(crate) struct ChangeDecoder {
+    relations: HashMap<u32, Relation>,
+}
+
+impl ChangeDecoder {
+    pub fn decode(&mut self, data: &[u8]) -> anyhow::Result<DecodedMessage> {
+        let mut reader = MessageReader { data };
+        match reader.u8()? {
+            b'R' => {
+                self.decode_relation(reader)
+                    .context("failed decoding relation")?;
+                Ok(DecodedMessage::Other)
+            }
+            b'C' => Ok(DecodedMessage::Commit),
+            b'I' => self.decode_row_change(Action::Insert, reader),
+            b'U' => self.decode_row_change(Action::Update, reader),
+            // Transaction starts, deletions (which are not published), type descriptions etc.
+            _ => Ok(DecodedMessage::Other),
+        }
+    }
+
+    fn decode_relation(&mut self, mut reader: MessageReader<'_>) -> anyhow::Result<()> {
+        let id = reader.u32()?;
+        let namespace = reader.str()?.to_owned();
+        let name = reader.str()?.to_owned();
+        let _replica_identity = reader.u8()?;
+        let column_count = reader.u16()?;
+        let columns = (0..column_count)
+            .map(|_| {
+                let _flags = reader.u8()?;
+                let name = reader.str()?.to_owned();
+                let _type_id = reader.u32()?;
+                let _type_modifier = reader.u32()?;
+                Ok(name)
+            })
+            .collect::<anyhow::Result<_>>()?;
+        self.relations.insert(
+            id,
+            Relation {
+                namespace,
+                name,
+                columns,
+            },
+        );
+        Ok(())
+    }
+
+    /// Parses an insert or update message. Returns `Ok(None)` for tables outside the `public` schema.
+    fn parse_row_change<'a>(
+        &'a self,
+        action: Action,
+        mut reader: MessageReader<'a>,
+    ) -> anyhow::Result<Option<RowChange<'a>>> {
+        let relation_id = reader.u32()?;
+        let relation = self
+            .relations
+            .get(&relation_id)
+            .with_context(|| format!("change for undescribed relation {relation_id}"))?;
+        if relation.namespace != "public" {
+            return Ok(None);
+        }
+
+        let mut tuple_kind = reader.u8()?;
+        // If an `UPDATE` changes the replica identity, the old key (or the entire old row for tables
+        // with the `FULL` replica identity) is output before the new row.
+        if action == Action::Update && matches!(tuple_kind, b'K' | b'O') {
+            reader.tuple()?;
+            tuple_kind = reader.u8()?;
+        }
+        anyhow::ensure!(
+            tuple_kind == b'N',
+            "unexpected tuple kind `{}`",
+            char::from(tuple_kind)
+        );
+        let values = reader.tuple()?;
+        anyhow::ensure!(
+            values.len() == relation.columns.len(),
+            "number of values ({}) differs from the number of columns ({})",
+            values.len(),
+            relation.columns.len()
+        );
+
+        let columns = relation.columns.iter().map(String::as_str).zip(values);
+        Ok(Some(RowChange {
+            table: &relation.name,
+            action,
+            columns: columns.collect(),
+        }))
+    }
+
+    fn decode_row_change(
+        &self,
+        action: Action,
+        reader: MessageReader<'_>,
+    ) -> anyhow::Result<DecodedMessage> {
+        let Some(change) = self.parse_row_change(action, reader)? else {
+            return Ok(DecodedMessage::Other);
+        };
+        let table = change.table;
+        let event = change
+            .into_event()
+            .with_context(|| format!("failed decoding change for table `{table}`"))?;
+        Ok(event.map_or(DecodedMessage::Other, DecodedMessage::Event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_str(message: &mut Vec<u8>, value: &str) {
+        message.extend_from_slice(value.as_bytes());
+        message.push(0);
+    }
+
+    fn push_tuple(message: &mut Vec<u8>, values: &[ColumnValue<'_>]) {
+        message.extend_from_slice(&(values.len() as u16).to_be_bytes());
+        for value in values {
+            match value {
+                ColumnValue::Null => message.push(b'n'),
+                ColumnValue::UnchangedToast => message.push(b'u'),
+                ColumnValue::Value(value) => {
+                    message.push(b't');
+                    message.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                    message.extend_from_slice(value.as_bytes());
+                }
+            }
+        }
+    }
+
+    fn relation_message(id: u32, namespace: &str, table: &str, columns: &[&str]) -> Vec<u8> {
+        let mut message = vec![b'R'];
+        message.extend_from_slice(&id.to_be_bytes());
+        push_str(&mut message, namespace);
+        push_str(&mut message, table);
+        message.push(b'd'); // default replica identity
+        message.extend_from_slice(&(columns.len() as u16).to_be_bytes());
+        for column in columns {
+            message.push(0); // flags
+            push_str(&mut message, column);
+            message.extend_from_slice(&25_u32.to_be_bytes()); // type ID (`text`)
+            message.extend_from_slice(&u32::MAX.to_be_bytes()); // type modifier (-1)
+        }
+        message
+    }
+
+    fn row_message(kind: u8, relation_id: u32, values: &[ColumnValue<'_>]) -> Vec<u8> {
+        let mut message = vec![kind];
+        message.extend_from_slice(&relation_id.to_be_bytes());
+        message.push(b'N');
+        push_tuple(&mut message, values);
+        message
+    }
+
+    fn value(value: &str) -> ColumnValue<'_> {
+        ColumnValue::Value(value)
+    }
+
+    fn optional_value(value: Option<&str>) -> ColumnValue<'_> {
+        value.map_or(ColumnValue::Null, ColumnValue::Value)
+    }
+
+    #[test]
+    fn parsing_row_change() {
+        let mut decoder = ChangeDecoder::default();
+        let relation = relation_message(
+            1,
+            "public",
+            "test",
+            &["id", "name", "data", "error", "payload"],
+        );
+        assert_eq!(decoder.decode(&relation).unwrap(), DecodedMessage::Other);
+
+        // Update with the old key preceding the new row.
+        let mut message = vec![b'U'];
+        message.extend_from_slice(&1_u32.to_be_bytes());
+        message.push(b'K');
+        push_tuple(
+            &mut message,
+            &[
+                value("0"),
+                ColumnValue::Null,
+                ColumnValue::Null,
+                ColumnValue::Null,
+                ColumnValue::Null,
+            ],
+        );
+        message.push(b'N');
+        push_tuple(
+            &mut message,
+            &[
+                value("1"),
+                value("it's a test"),
+                value("\\x0102"),
+                ColumnValue::Null,
+                ColumnValue::UnchangedToast,
+            ],
+        );
+
+        let reader = MessageReader {
+            data: &message[1..],
+        };
+        let change = decoder
+            .parse_row_change(Action::Update, reader)
+            .unwrap()
+            .unwrap();
+        assert_eq!(change.table, "test");
+        assert_eq!(change.action, Action::Update);
+        assert_eq!(change.columns.len(), 5);
+        assert_eq!(change.u64("id").unwrap(), 1);
+        assert_eq!(change.value("name").unwrap(), "it's a test");
+        assert_eq!(change.bytes("data").unwrap(), [1, 2]);
+        assert_eq!(change.optional_value("error").unwrap(), None);
+        change.value("payload").unwrap_err();
+        change.value("missing").unwrap_err();
+    }
+
+    #[test]
+    fn skipping_irrelevant_messages() {
+        let mut decoder = ChangeDecoder::default();
+        let mut begin = vec![b'B'];
+        begin.extend_from_slice(&[0; 20]);
+        assert_eq!(decoder.decode(&begin).unwrap(), DecodedMessage::Other);
+        let mut commit = vec![b'C'];
+        commit.extend_from_slice(&[0; 25]);
+        assert_eq!(decoder.decode(&commit).unwrap(), DecodedMessage::Commit);
+
+        // Changes for undescribed relations are errors.
+        let insert = row_message(b'I', 1, &[value("1")]);
+        decoder.decode(&insert).unwrap_err();
+
+        let relation = relation_message(1, "other", "l1_batches", &["number", "timestamp"]);
+        decoder.decode(&relation).unwrap();
+        let insert = row_message(b'I', 1, &[value("1"), value("2")]);
+        assert_eq!(decoder.decode(&insert).unwrap(), DecodedMessage::Other);
+
+        let relation = relation_message(2, "public", "events", &["miniblock_number"]);
+        decoder.decode(&relation).unwrap();
+        let insert = row_message(b'I', 2, &[value("1")]);
+        assert_eq!(decoder.decode(&insert).unwrap(), DecodedMessage::Other);
+
+        let relation = relation_message(3, "public", "transactions", &["hash"]);
+        decoder.decode(&relation).unwrap();
+        let delete = row_message(b'D', 3, &[value("\\x00")]);
+        assert_eq!(decoder.decode(&delete).unwrap(), DecodedMessage::Other);
+    }
+
+    #[test]
+    fn decoding_block_changes() {
+        let mut decoder = ChangeDecoder::default();
+        decoder
+            .decode(&relation_message(
+                1,
+                "public",
+                "miniblocks",
+                &["number", "hash"],
+            ))
+            .unwrap();
+        decoder
+            .decode(&relation_message(
+                2,
+                "public",
+                "l1_batches",
+                &["number", "timestamp"],
+            ))
+            .unwrap();
+
+        let hash = H256::repeat_byte(0x23);
+        let hash_value = format!("\\x{}", hex::encode(hash));
+        let change = row_message(b'I', 1, &[value("42"), value(&hash_value)]);
+        assert_eq!(
+            decoder.decode(&change).unwrap(),
+            DecodedMessage::Event(ChangeEvent::NewL2Block {
+                number: L2BlockNumber(42),
+                hash,
+            })
+        );
+
+        let change = row_message(b'I', 2, &[value("5"), value("1000")]);
+        assert_eq!(
+            decoder.decode(&change).unwrap(),
+            DecodedMessage::Event(ChangeEvent::NewL1Batch {
+                number: L1BatchNumber(5),
+                timestamp: 1_000,
+            })
+        );
+        // Updates of blocks are irrelevant.
+        let change = row_message(b'U', 2, &[value("5"), value("1000")]);
+        assert_eq!(decoder.decode(&change).unwrap(), DecodedMessage::Other);
+    }
+
+    #[test]
+    fn decoding_transaction_changes() {
+        let mut decoder = ChangeDecoder::default();
+        let columns = [
+            "hash",
+            "initiator_address",
+            "nonce",
+            "miniblock_number",
+            "l1_batch_number",
+            "error",
+        ];
+        decoder
+            .decode(&relation_message(1, "public", "transactions", &columns))
+            .unwrap();
+
+        let hash = H256::repeat_byte(1);
+        let hash_value = format!("\\x{}", hex::encode(hash));
+        let initiator_address = Address::repeat_byte(2);
+        let address_value = format!("\\x{}", hex::encode(initiator_address));
+        let change = row_message(
+            b'I',
+            1,
+            &[
+                value(&hash_value),
+                value(&address_value),
+                value("3"),
+                ColumnValue::Null,
+                ColumnValue::Null,
+                ColumnValue::Null,
+            ],
+        );
+        assert_eq!(
+            decoder.decode(&change).unwrap(),
+            DecodedMessage::Event(ChangeEvent::NewTransaction {
+                hash,
+                initiator_address,
+                nonce: Some(3),
+            })
+        );
+
+        let mut status =
+            |miniblock_number: Option<&str>, l1_batch_number: Option<&str>, error: Option<&str>| {
+                let change = row_message(
+                    b'U',
+                    1,
+                    &[
+                        value(&hash_value),
+                        value(&address_value),
+                        value("3"),
+                        optional_value(miniblock_number),
+                        optional_value(l1_batch_number),
+                        optional_value(error),
+                    ],
+                );
+                match decoder.decode(&change).unwrap() {
+                    DecodedMessage::Event(ChangeEvent::TransactionStatus {
+                        hash: event_hash,
+                        status,
+                    }) if event_hash == hash => status,
+                    message => panic!("unexpected message: {message:?}"),
+                }
+            };
+
+        assert_eq!(status(None, None, None), TransactionStatus::Pending);
+        assert_eq!(
+            status(Some("7"), None, None),
+            TransactionStatus::Included {
+                l2_block_number: L2BlockNumber(7),
+                l1_batch_number: None,
+            }
+        );
+        assert_eq!(
+            status(Some("7"), Some("2"), None),
+            TransactionStatus::Included {
+                l2_block_number: L2BlockNumber(7),
+                l1_batch_number: Some(L1BatchNumber(2)),
+            }
+        );
+        assert_eq!(
+            status(None, None, Some("nonce too low")),
+            TransactionStatus::Rejected {
+                error: "nonce too low".to_owned(),
+            }
+        );
+    }
+}
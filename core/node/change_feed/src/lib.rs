@@ -0,0 +1,266 @@
+//! Change-data feed tailing Postgres logical replication for the core tables.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, watch};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::{Address, L1BatchNumber, L2BlockNumber, H256};
+
+pub use self::sinks::{ChangeEventSink, WebhookSink};
+use self::{
+    decoding::{ChangeDecoder, DecodedMessage},
+    metrics::METRICS,
+};
+
+mod decoding;
+mod metrics;
+mod sinks;
+#[cfg(test)]
+mod tests;
+
+/// Status of a transaction reported in [`ChangeEvent::TransactionStatus`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// The transaction is in the mempool.
+    Pending,
+    /// The transaction was rejected by the state keeper.
+    Rejected { error: String },
+    /// The transaction is included into an L2 block and, if the batch is already sealed, into an L1 batch.
+    Included {
+        l2_block_number: L2BlockNumber,
+        l1_batch_number: Option<L1BatchNumber>,
+    },
+}
+
+/// Typed event decoded from a change in one of the core tables.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChangeEvent {
+    /// New transaction is persisted (e.g., received by the API server or executed from L1).
+    NewTransaction {
+        hash: H256,
+        initiator_address: Address,
+        nonce: Option<u64>,
+    },
+    /// Transaction row is updated. The status is reported on each update, so the same status
+    /// may be reported several times.
+    TransactionStatus {
+        hash: H256,
+        status: TransactionStatus,
+    },
+    /// New L2 block is inserted.
+    NewL2Block { number: L2BlockNumber, hash: H256 },
+    /// New L1 batch is inserted.
+    NewL1Batch {
+        number: L1BatchNumber,
+        timestamp: u64,
+    },
+}
+
+/// [`ChangeEvent`] together with its position in the Postgres write-ahead log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequencedChangeEvent {
+    /// Log sequence number (LSN) of the change. LSNs uniquely identify changes, so they can be used
+    /// to deduplicate events. Events are ordered by the commit order of their Postgres transactions,
+    /// so LSNs of events from different transactions are not necessarily increasing.
+    pub lsn: u64,
+    #[serde(flatten)]
+    pub event: ChangeEvent,
+}
+
+/// Configuration of [`ChangeFeed`].
+#[derive(Debug)]
+pub struct ChangeFeedConfig {
+    /// Name of the logical replication slot used by the feed. Also used as the name of the publication
+    /// for the tables tailed by the feed.
+    pub slot_name: String,
+    /// Interval between polling the replication slot if there are no new changes, or if publishing
+    /// the events has failed.
+    pub poll_interval: Duration,
+    /// Maximum number of changes read from the slot at a time. Postgres returns changes for whole transactions,
+    /// so this limit may be exceeded; changes are decoded as they are streamed. Also, the maximum number
+    /// of events published to a sink at a time.
+    pub max_batch_size: usize,
+}
+
+/// Tails changes from a Postgres logical replication slot, decodes them into [`ChangeEvent`]s and publishes
+/// the events to [sinks](ChangeEventSink) and [in-process subscribers](Self::subscribe()). Only changes
+/// in the tailed tables are decoded by Postgres, courtesy of a publication limited to these tables.
+///
+/// Changes are consumed from the slot only after the events for whole Postgres transactions are published
+/// to all sinks, so sinks receive events at least once. In-process subscribers falling behind by more than [`Self::SUBSCRIPTION_CAPACITY`]
+/// events will miss events.
+#[derive(Debug)]
+pub struct ChangeFeed {
+    pool: ConnectionPool<Core>,
+    config: ChangeFeedConfig,
+    sinks: Vec<Box<dyn ChangeEventSink>>,
+    events_sender: broadcast::Sender<SequencedChangeEvent>,
+}
+
+impl ChangeFeed {
+    /// Capacity of the channel for each in-process subscriber.
+    pub const SUBSCRIPTION_CAPACITY: usize = 1_024;
+
+    pub fn new(pool: ConnectionPool<Core>, config: ChangeFeedConfig) -> Self {
+        assert!(config.max_batch_size > 0, "max_batch_size must be positive");
+        Self {
+            pool,
+            config,
+            sinks: vec![],
+            events_sender: broadcast::channel(Self::SUBSCRIPTION_CAPACITY).0,
+        }
+    }
+
+    /// Adds an external sink for change events.
+    pub fn add_sink(&mut self, sink: Box<dyn ChangeEventSink>) {
+        self.sinks.push(sink);
+    }
+
+    /// Subscribes to published change events.
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedChangeEvent> {
+        self.events_sender.subscribe()
+    }
+
+    async fn ensure_replication_slot(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("change_feed").await?;
+        let wal_level = storage.change_feed_dal().get_wal_level().await?;
+        anyhow::ensure!(
+            wal_level == "logical",
+            "Change feed requires Postgres to run with `wal_level = logical`, but it is `{wal_level}`"
+        );
+
+        // The publication must be created first; otherwise, the initial changes couldn't be decoded.
+        let slot_name = &self.config.slot_name;
+        let created = storage
+            .change_feed_dal()
+            .create_publication_if_missing(slot_name)
+            .await?;
+        if created {
+            tracing::info!("Created publication `{slot_name}`");
+        }
+        let created = storage
+            .change_feed_dal()
+            .create_replication_slot_if_missing(slot_name)
+            .await?;
+        if created {
+            tracing::info!("Created logical replication slot `{slot_name}`");
+        }
+        Ok(())
+    }
+
+    /// Streams and decodes pending changes from the slot. Returns events for complete transactions, the LSN
+    /// to advance the slot to (`None` if there are no complete transactions), and the number of read changes.
+    async fn read_changes(
+        &self,
+    ) -> anyhow::Result<(Vec<SequencedChangeEvent>, Option<u64>, usize)> {
+        let slot_name = &self.config.slot_name;
+        let mut storage = self.pool.connection_tagged("change_feed").await?;
+        let mut dal = storage.change_feed_dal();
+        let mut changes = dal.stream_changes(slot_name, slot_name, self.config.max_batch_size);
+
+        // The plugin describes relations anew in each decoding session, so the decoder is not reused.
+        let mut decoder = ChangeDecoder::default();
+        let mut events = vec![];
+        let mut transaction_events = vec![];
+        let mut last_commit_lsn = None;
+        let mut changes_count = 0;
+        while let Some(change) = changes.try_next().await? {
+            changes_count += 1;
+            match decoder.decode(&change.data) {
+                Ok(DecodedMessage::Event(event)) => transaction_events.push(SequencedChangeEvent {
+                    lsn: change.lsn,
+                    event,
+                }),
+                Ok(DecodedMessage::Commit) => {
+                    events.append(&mut transaction_events);
+                    last_commit_lsn = Some(change.lsn);
+                }
+                Ok(DecodedMessage::Other) => { /* message is irrelevant */ }
+                Err(err) => {
+                    // Skip the change so that a single malformed change doesn't stall the feed.
+                    tracing::error!("Failed decoding change at LSN {}: {err:#}", change.lsn);
+                    METRICS.decoding_errors.inc();
+                }
+            }
+        }
+        Ok((events, last_commit_lsn, changes_count))
+    }
+
+    /// Publishes events for a single batch of changes. Returns `Ok(true)` if there may be more pending changes
+    /// in the slot, and `Ok(false)` if the feed should wait before the next iteration.
+    async fn publish_batch(&self) -> anyhow::Result<bool> {
+        let slot_name = &self.config.slot_name;
+        // The connection is released before events are published.
+        let (events, last_lsn, changes_count) = self.read_changes().await?;
+        let Some(last_lsn) = last_lsn else {
+            return Ok(false);
+        };
+        tracing::debug!(
+            "Decoded {} change events from {changes_count} changes with LSNs up to {last_lsn}",
+            events.len()
+        );
+
+        for chunk in events.chunks(self.config.max_batch_size) {
+            for sink in &self.sinks {
+                let latency = METRICS.sink_latency[&sink.name()].start();
+                if let Err(err) = sink.publish(chunk).await {
+                    tracing::warn!(
+                        "Failed publishing {} change events to sink `{}`, retrying in {:?}: {err:#}",
+                        chunk.len(),
+                        sink.name(),
+                        self.config.poll_interval
+                    );
+                    METRICS.sink_errors[&sink.name()].inc();
+                    return Ok(false);
+                }
+                latency.observe();
+            }
+        }
+
+        let events_count = events.len();
+        for event in events {
+            // Having no subscribers is fine.
+            self.events_sender.send(event).ok();
+        }
+
+        let mut storage = self.pool.connection_tagged("change_feed").await?;
+        storage
+            .change_feed_dal()
+            .advance_replication_slot(slot_name, last_lsn)
+            .await
+            .with_context(|| format!("failed advancing replication slot `{slot_name}`"))?;
+        METRICS.published_events.inc_by(events_count as u64);
+        METRICS.last_consumed_lsn.set(last_lsn);
+        Ok(changes_count >= self.config.max_batch_size)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting change feed with configuration {:?} and sinks {:?}",
+            self.config,
+            self.sinks
+                .iter()
+                .map(|sink| sink.name())
+                .collect::<Vec<_>>()
+        );
+        self.ensure_replication_slot().await?;
+
+        while !*stop_receiver.borrow_and_update() {
+            let has_more_changes = self.publish_batch().await?;
+            if !has_more_changes
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, change feed is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Gauge, Histogram, LabeledFamily, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "change_feed")]
+pub(super) struct ChangeFeedMetrics {
+    /// Total number of published change events.
+    pub published_events: Counter,
+    /// Number of changes that could not be decoded and were skipped.
+    pub decoding_errors: Counter,
+    /// Log sequence number (LSN) of the last change consumed from the replication slot.
+    pub last_consumed_lsn: Gauge<u64>,
+    /// Latency of publishing a batch of change events to a sink.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds, labels = ["sink"])]
+    pub sink_latency: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of errors publishing change events to a sink.
+    #[metrics(labels = ["sink"])]
+    pub sink_errors: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<ChangeFeedMetrics> = vise::Global::new();
@@ -0,0 +1,57 @@
+//! External sinks for change events.
+
+use std::{fmt, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+
+use crate::SequencedChangeEvent;
+
+/// External destination for change events, such as a webhook or a message queue.
+#[async_trait]
+pub trait ChangeEventSink: fmt::Debug + Send + Sync + 'static {
+    /// Name of the sink used in logs and metrics.
+    fn name(&self) -> &'static str;
+
+    /// Publishes a batch of events. Events are ordered by their LSN; the same events may be published again
+    /// if publishing to this or another sink fails, or if the node restarts.
+    async fn publish(&self, events: &[SequencedChangeEvent]) -> anyhow::Result<()>;
+}
+
+/// Sink `POST`ing change events to the specified URL as a JSON array.
+#[derive(Debug)]
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    pub fn new(url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl ChangeEventSink for WebhookSink {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn publish(&self, events: &[SequencedChangeEvent]) -> anyhow::Result<()> {
+        self.client
+            .post(&self.url)
+            .json(events)
+            .timeout(Self::REQUEST_TIMEOUT)
+            .send()
+            .await
+            .context("failed sending webhook request")?
+            .error_for_status()
+            .context("webhook responded with error status")?;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use serde_json::json;
+
+use super::*;
+
+#[test]
+fn serializing_change_events() {
+    let event = SequencedChangeEvent {
+        lsn: 100,
+        event: ChangeEvent::TransactionStatus {
+            hash: H256::repeat_byte(1),
+            status: TransactionStatus::Included {
+                l2_block_number: L2BlockNumber(3),
+                l1_batch_number: None,
+            },
+        },
+    };
+    let event_json = serde_json::to_value(&event).unwrap();
+    assert_eq!(
+        event_json,
+        json!({
+            "lsn": 100,
+            "type": "transaction_status",
+            "hash": format!("{:?}", H256::repeat_byte(1)),
+            "status": {
+                "status": "included",
+                "l2_block_number": 3,
+                "l1_batch_number": null,
+            },
+        })
+    );
+    let restored: SequencedChangeEvent = serde_json::from_value(event_json).unwrap();
+    assert_eq!(restored, event);
+
+    let event = SequencedChangeEvent {
+        lsn: 101,
+        event: ChangeEvent::NewL1Batch {
+            number: L1BatchNumber(2),
+            timestamp: 10,
+        },
+    };
+    let event_json = serde_json::to_value(&event).unwrap();
+    assert_eq!(
+        event_json,
+        json!({ "lsn": 101, "type": "new_l1_batch", "number": 2, "timestamp": 10 })
+    );
+}
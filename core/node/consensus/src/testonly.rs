@@ -366,6 +366,8 @@ impl StateKeeperRunner {
                     self.pool.0.clone(),
                     Default::default(),
                     Arc::default(),
+                    None,
+                    None,
                     stop_recv,
                 )
                 .await;
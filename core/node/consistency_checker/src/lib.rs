@@ -223,8 +223,11 @@ impl LocalL1BatchCommitData {
         let da = detect_da(protocol_version, reference)
             .context("cannot detect DA source from reference commitment token")?;
 
-        // For `PubdataDA::Calldata`, it's required that the pubdata fits into a single blob.
-        if matches!(da, PubdataDA::Calldata) {
+        // For `PubdataDA::Calldata`, it's required that the pubdata fits into a single blob. Validium pubdata is not
+        // sent to L1 (and may be persisted in a non-rollup encoding), so its size is irrelevant.
+        if matches!(da, PubdataDA::Calldata)
+            && self.commitment_mode == L1BatchCommitmentMode::Rollup
+        {
             let pubdata_len = self
                 .l1_batch
                 .header
@@ -238,8 +241,15 @@ impl LocalL1BatchCommitData {
             );
         }
 
-        let local_token =
-            CommitBatchInfo::new(self.commitment_mode, &self.l1_batch, da).into_token();
+        let local_token = if da == PubdataDA::Custom {
+            // DA inclusion data is not available locally (it's only fetched by the DA dispatcher), so it's taken
+            // from the reference; it's verified by L1 contracts anyway.
+            let mut l1_batch = self.l1_batch.clone();
+            l1_batch.metadata.da_inclusion_data = Some(reference_inclusion_data(reference));
+            CommitBatchInfo::new(self.commitment_mode, &l1_batch, da).into_token()
+        } else {
+            CommitBatchInfo::new(self.commitment_mode, &self.l1_batch, da).into_token()
+        };
         anyhow::ensure!(
             local_token == *reference,
             "Locally reproduced commitment differs from the reference obtained from L1; \
@@ -262,6 +272,7 @@ pub fn detect_da(
     /// These are used by the L1 Contracts to indicate what DA layer is used for pubdata
     const PUBDATA_SOURCE_CALLDATA: u8 = 0;
     const PUBDATA_SOURCE_BLOBS: u8 = 1;
+    const PUBDATA_SOURCE_CUSTOM: u8 = 2;
 
     fn parse_error(message: impl Into<Cow<'static, str>>) -> ethabi::Error {
         ethabi::Error::Other(message.into())
@@ -292,14 +303,27 @@ pub fn detect_da(
     match last_reference_token.first() {
         Some(&byte) if byte == PUBDATA_SOURCE_CALLDATA => Ok(PubdataDA::Calldata),
         Some(&byte) if byte == PUBDATA_SOURCE_BLOBS => Ok(PubdataDA::Blobs),
+        Some(&byte) if byte == PUBDATA_SOURCE_CUSTOM => Ok(PubdataDA::Custom),
         Some(&byte) => Err(parse_error(format!(
-            "unexpected first byte of the last reference token; expected one of [{PUBDATA_SOURCE_CALLDATA}, {PUBDATA_SOURCE_BLOBS}], \
-                got {byte}"
+            "unexpected first byte of the last reference token; expected one of [{PUBDATA_SOURCE_CALLDATA}, {PUBDATA_SOURCE_BLOBS}, \
+                {PUBDATA_SOURCE_CUSTOM}], got {byte}"
         ))),
         None => Err(parse_error("last reference token is empty")),
     }
 }
 
+/// Extracts DA inclusion data from the `reference` commitment created for the custom DA source.
+fn reference_inclusion_data(reference: &Token) -> Vec<u8> {
+    let Token::Tuple(reference) = reference else {
+        unreachable!("checked by `detect_da()`");
+    };
+    let Some(Token::Bytes(pubdata_commitments)) = reference.last() else {
+        unreachable!("checked by `detect_da()`");
+    };
+    // The first byte is the pubdata source.
+    pubdata_commitments[1..].to_vec()
+}
+
 #[derive(Debug)]
 pub struct ConsistencyChecker {
     /// ABI of the zkSync contract
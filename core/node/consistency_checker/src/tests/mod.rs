@@ -171,6 +171,38 @@ fn build_commit_tx_input_data_is_correct(commitment_mode: L1BatchCommitmentMode)
     }
 }
 
+#[test]
+fn verifying_commitment_with_custom_da() {
+    let mut l1_batch = create_l1_batch_with_metadata(1);
+    let local_data = LocalL1BatchCommitData {
+        l1_batch: l1_batch.clone(),
+        commit_tx_hash: H256::zero(),
+        commitment_mode: L1BatchCommitmentMode::Validium,
+    };
+
+    // Inclusion data is not available locally, but it is present in the reference commitment.
+    l1_batch.metadata.da_inclusion_data = Some(b"inclusion proof".to_vec());
+    let reference = CommitBatchInfo::new(
+        L1BatchCommitmentMode::Validium,
+        &l1_batch,
+        PubdataDA::Custom,
+    )
+    .into_token();
+    let version = l1_batch.header.protocol_version.unwrap();
+    assert_eq!(detect_da(version, &reference).unwrap(), PubdataDA::Custom);
+    local_data.verify_commitment(&reference).unwrap();
+    assert_eq!(reference_inclusion_data(&reference), b"inclusion proof");
+
+    l1_batch.metadata.commitment = H256::repeat_byte(0xff);
+    let reference = CommitBatchInfo::new(
+        L1BatchCommitmentMode::Validium,
+        &l1_batch,
+        PubdataDA::Custom,
+    )
+    .into_token();
+    local_data.verify_commitment(&reference).unwrap_err();
+}
+
 #[test]
 fn extracting_commit_data_for_boojum_batch() {
     let contract = zksync_contracts::hyperchain_contract();
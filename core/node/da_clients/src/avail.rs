@@ -0,0 +1,340 @@
+//! Client for the [Avail](https://www.availproject.org/) DA layer.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use zksync_config::configs::da_client::AvailConfig;
+use zksync_da_client::{
+    DAError, DataAvailabilityClient, DispatchResponse, InclusionData, InclusionStatus,
+};
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    web3::keccak256,
+    H256, U256,
+};
+
+use crate::utils::{http_error, non_retriable_error};
+
+/// Maximum size of a single data submission accepted by Avail.
+pub(crate) const BLOB_SIZE_LIMIT: usize = 512 * 1_024;
+
+#[derive(Debug, Serialize)]
+struct SubmitRequest {
+    data: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitResponse {
+    block_hash: H256,
+    index: u32,
+}
+
+/// Proof of the blob inclusion into a data root bridged to Ethereum, as returned by the bridge API.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BridgeProof {
+    data_root_proof: Vec<H256>,
+    leaf_proof: Vec<H256>,
+    range_hash: H256,
+    data_root_index: u64,
+    blob_root: H256,
+    bridge_root: H256,
+    leaf: H256,
+    leaf_index: u64,
+}
+
+impl BridgeProof {
+    fn param_type() -> ParamType {
+        let hashes = || ParamType::Array(Box::new(ParamType::FixedBytes(32)));
+        ParamType::Tuple(vec![
+            hashes(),
+            hashes(),
+            ParamType::FixedBytes(32),
+            ParamType::Uint(256),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::FixedBytes(32),
+            ParamType::Uint(256),
+        ])
+    }
+
+    /// ABI-encodes the proof as the `MerkleProofInput` struct expected by the Avail attestation verifier on L1.
+    fn abi_encode(&self) -> Vec<u8> {
+        let hashes = |hashes: &[H256]| {
+            Token::Array(
+                hashes
+                    .iter()
+                    .map(|hash| Token::FixedBytes(hash.as_bytes().to_vec()))
+                    .collect(),
+            )
+        };
+        let proof = Token::Tuple(vec![
+            hashes(&self.data_root_proof),
+            hashes(&self.leaf_proof),
+            Token::FixedBytes(self.range_hash.as_bytes().to_vec()),
+            Token::Uint(U256::from(self.data_root_index)),
+            Token::FixedBytes(self.blob_root.as_bytes().to_vec()),
+            Token::FixedBytes(self.bridge_root.as_bytes().to_vec()),
+            Token::FixedBytes(self.leaf.as_bytes().to_vec()),
+            Token::Uint(U256::from(self.leaf_index)),
+        ]);
+        ethabi::encode(&[proof])
+    }
+
+    /// Decodes a proof previously encoded with [`Self::abi_encode()`].
+    fn abi_decode(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut tokens = ethabi::decode(&[Self::param_type()], bytes)?;
+        let Some(Token::Tuple(fields)) = tokens.pop() else {
+            unreachable!("decoded tokens correspond to param types");
+        };
+        let hash = |token: &Token| H256::from_slice(&token.clone().into_fixed_bytes().unwrap());
+        let hashes = |token: &Token| -> Vec<H256> {
+            let tokens = token.clone().into_array().unwrap();
+            tokens.iter().map(hash).collect()
+        };
+        let index = |token: &Token| -> anyhow::Result<u64> {
+            let index = token.clone().into_uint().unwrap();
+            anyhow::ensure!(index <= U256::from(u64::MAX), "index {index} is too large");
+            Ok(index.as_u64())
+        };
+        Ok(Self {
+            data_root_proof: hashes(&fields[0]),
+            leaf_proof: hashes(&fields[1]),
+            range_hash: hash(&fields[2]),
+            data_root_index: index(&fields[3])?,
+            blob_root: hash(&fields[4]),
+            bridge_root: hash(&fields[5]),
+            leaf: hash(&fields[6]),
+            leaf_index: index(&fields[7])?,
+        })
+    }
+
+    /// Verifies the parts of the proof not anchored to L1. The data root proof is checked against the data root
+    /// commitment for `range_hash` stored on L1, which is done by the attestation verifier during batch commitment.
+    fn verify(&self, data: &[u8]) -> anyhow::Result<()> {
+        let blob_hash = H256(keccak256(data));
+        anyhow::ensure!(
+            self.leaf == blob_hash,
+            "proof leaf {:?} doesn't match the blob hash {blob_hash:?}",
+            self.leaf
+        );
+        anyhow::ensure!(!self.blob_root.is_zero(), "blob root is empty");
+        let hashed_leaf = H256(keccak256(self.leaf.as_bytes()));
+        anyhow::ensure!(
+            merkle_root(hashed_leaf, self.leaf_index, &self.leaf_proof) == self.blob_root,
+            "leaf proof doesn't lead to the blob root {:?}",
+            self.blob_root
+        );
+        Ok(())
+    }
+}
+
+/// Computes the Merkle root from a leaf, its index and the proof in the same way as the `Merkle` library
+/// used by the Avail bridge contracts.
+fn merkle_root(leaf: H256, mut index: u64, proof: &[H256]) -> H256 {
+    let mut value = leaf;
+    for sibling in proof {
+        let mut preimage = [0_u8; 64];
+        if index & 1 == 1 {
+            preimage[..32].copy_from_slice(sibling.as_bytes());
+            preimage[32..].copy_from_slice(value.as_bytes());
+        } else {
+            preimage[..32].copy_from_slice(value.as_bytes());
+            preimage[32..].copy_from_slice(sibling.as_bytes());
+        }
+        value = H256(keccak256(&preimage));
+        index >>= 1;
+    }
+    value
+}
+
+/// Parses a blob ID in the `{block_hash}:{extrinsic_index}` format produced by [`AvailClient`].
+fn parse_blob_id(blob_id: &str) -> anyhow::Result<(H256, u32)> {
+    let (block_hash, index) = blob_id
+        .split_once(':')
+        .context("blob ID must have `{block_hash}:{index}` format")?;
+    let block_hash = block_hash.parse().context("malformed block hash")?;
+    let index = index.parse().context("malformed extrinsic index")?;
+    Ok((block_hash, index))
+}
+
+/// Client for the Avail DA layer. Blobs are identified by the hash of the Avail block and the index
+/// of the data submission extrinsic in this block.
+#[derive(Debug, Clone)]
+pub struct AvailClient {
+    config: AvailConfig,
+    client: reqwest::Client,
+}
+
+impl AvailClient {
+    pub fn new(config: AvailConfig, request_timeout: Duration) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .context("failed building HTTP client")?;
+        Ok(Self { config, client })
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for AvailClient {
+    fn client_type(&self) -> &'static str {
+        "Avail"
+    }
+
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        let url = format!(
+            "{}/v2/submit",
+            self.config.api_node_url.trim_end_matches('/')
+        );
+        let request = SubmitRequest {
+            data: BASE64.encode(data),
+        };
+        let response = self
+            .client
+            .post(url)
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| http_error(err, "failed submitting data to Avail light client"))?;
+        let response: SubmitResponse = response
+            .json()
+            .await
+            .map_err(|err| http_error(err, "malformed submission response"))?;
+
+        tracing::debug!(
+            "Submitted pubdata for L1 batch #{batch_number} to Avail block {:?}, extrinsic #{}",
+            response.block_hash,
+            response.index
+        );
+        Ok(format!("{:?}:{}", response.block_hash, response.index).into())
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<InclusionStatus, DAError> {
+        let (block_hash, index) = parse_blob_id(blob_id).map_err(non_retriable_error)?;
+        let url = format!(
+            "{}/eth/proof/{block_hash:?}",
+            self.config.bridge_api_url.trim_end_matches('/')
+        );
+        let response = self
+            .client
+            .get(url)
+            .query(&[("index", index)])
+            .send()
+            .await
+            .map_err(|err| http_error(err, "failed requesting proof from Avail bridge API"))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            // The data root with the blob is not bridged to Ethereum yet.
+            return Ok(InclusionStatus::Pending);
+        }
+        let proof: BridgeProof = response
+            .error_for_status()
+            .map_err(|err| http_error(err, "failed requesting proof from Avail bridge API"))?
+            .json()
+            .await
+            .map_err(|err| http_error(err, "malformed proof response"))?;
+        Ok(InclusionStatus::Included(InclusionData {
+            data: proof.abi_encode(),
+        }))
+    }
+
+    async fn verify_inclusion_data(
+        &self,
+        _blob_id: &str,
+        data: &[u8],
+        inclusion_data: &InclusionData,
+    ) -> Result<(), DAError> {
+        BridgeProof::abi_decode(&inclusion_data.data)
+            .context("malformed bridge proof")
+            .and_then(|proof| proof.verify(data))
+            .map_err(non_retriable_error)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        Some(BLOB_SIZE_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_blob_id() {
+        let block_hash = H256::repeat_byte(0x42);
+        let (parsed_hash, index) = parse_blob_id(&format!("{block_hash:?}:3")).unwrap();
+        assert_eq!(parsed_hash, block_hash);
+        assert_eq!(index, 3);
+
+        parse_blob_id("0x42").unwrap_err();
+        parse_blob_id(&format!("{block_hash:?}:")).unwrap_err();
+    }
+
+    #[test]
+    fn encoding_bridge_proof() {
+        let proof = serde_json::json!({
+            "dataRootProof": [H256::repeat_byte(1)],
+            "leafProof": [H256::repeat_byte(2), H256::repeat_byte(3)],
+            "rangeHash": H256::repeat_byte(4),
+            "dataRootIndex": 5,
+            "blobRoot": H256::repeat_byte(6),
+            "bridgeRoot": H256::repeat_byte(7),
+            "leaf": H256::repeat_byte(8),
+            "leafIndex": 9,
+        });
+        let proof: BridgeProof = serde_json::from_value(proof).unwrap();
+        let encoded = proof.abi_encode();
+
+        let decoded = ethabi::decode(&[BridgeProof::param_type()], &encoded).unwrap();
+        let Token::Tuple(fields) = &decoded[0] else {
+            panic!("unexpected decoded proof: {decoded:?}");
+        };
+        assert_eq!(fields[3], Token::Uint(5.into()));
+        assert_eq!(fields[7], Token::Uint(9.into()));
+        assert_eq!(
+            fields[6],
+            Token::FixedBytes(H256::repeat_byte(8).as_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn verifying_bridge_proof() {
+        let data = b"pubdata";
+        let leaf = H256(keccak256(data));
+        let hashed_leaf = H256(keccak256(leaf.as_bytes()));
+        let siblings = [H256::repeat_byte(1), H256::repeat_byte(2)];
+        // The leaf has index 2, i.e. it's the left child on the first level and the right child on the second one.
+        let mut proof = BridgeProof {
+            data_root_proof: vec![],
+            leaf_proof: siblings.to_vec(),
+            range_hash: H256::repeat_byte(3),
+            data_root_index: 0,
+            blob_root: merkle_root(hashed_leaf, 2, &siblings),
+            bridge_root: H256::zero(),
+            leaf,
+            leaf_index: 2,
+        };
+        proof.verify(data).unwrap();
+        let decoded = BridgeProof::abi_decode(&proof.abi_encode()).unwrap();
+        assert_eq!(decoded.blob_root, proof.blob_root);
+        assert_eq!(decoded.leaf_proof, proof.leaf_proof);
+        decoded.verify(data).unwrap();
+
+        proof.verify(b"other pubdata").unwrap_err();
+        proof.leaf_index = 3;
+        proof.verify(data).unwrap_err();
+        BridgeProof::abi_decode(b"garbage").unwrap_err();
+    }
+}
@@ -0,0 +1,234 @@
+//! Computation of Celestia blob share commitments, as specified by [ADR-013] and implemented in `celestia-app`.
+//!
+//! [ADR-013]: https://github.com/celestiaorg/celestia-app/blob/main/docs/architecture/adr-013-non-interactive-default-rules-for-zero-padding.md
+
+use sha2::{Digest, Sha256};
+
+use super::NAMESPACE_SIZE;
+
+const SHARE_SIZE: usize = 512;
+/// Size of the info byte of a share, which contains the share version and the sequence start flag.
+const SHARE_INFO_SIZE: usize = 1;
+/// Size of the sequence length prefix in the first share of a blob.
+const SEQUENCE_LEN_SIZE: usize = 4;
+const FIRST_SHARE_CONTENT_SIZE: usize =
+    SHARE_SIZE - NAMESPACE_SIZE - SHARE_INFO_SIZE - SEQUENCE_LEN_SIZE;
+const CONTINUATION_SHARE_CONTENT_SIZE: usize = SHARE_SIZE - NAMESPACE_SIZE - SHARE_INFO_SIZE;
+/// Upper bound on the number of subtree roots a blob commitment is built from (`SubtreeRootThreshold`
+/// in `celestia-app`).
+const SUBTREE_ROOT_THRESHOLD: usize = 64;
+
+/// Size of a namespaced Merkle tree (NMT) node: the minimum and maximum namespace, and the SHA-256 digest.
+const NMT_NODE_SIZE: usize = 2 * NAMESPACE_SIZE + 32;
+
+type Share = [u8; SHARE_SIZE];
+type NmtNode = [u8; NMT_NODE_SIZE];
+
+/// Computes the share commitment of a blob with share version 0. The commitment is the Merkle root of
+/// the NMT subtree roots covering the blob shares, so it doesn't depend on the position of the blob in the block.
+pub(super) fn create_commitment(namespace: &[u8; NAMESPACE_SIZE], data: &[u8]) -> [u8; 32] {
+    let shares = split_into_shares(namespace, data);
+    let subtree_width = subtree_width(shares.len());
+    let mut remaining_shares = shares.as_slice();
+    let subtree_roots: Vec<_> = merkle_mountain_range_sizes(shares.len(), subtree_width)
+        .into_iter()
+        .map(|tree_size| {
+            let (leaves, rest) = remaining_shares.split_at(tree_size);
+            remaining_shares = rest;
+            nmt_root(namespace, leaves)
+        })
+        .collect();
+    merkle_root(&subtree_roots)
+}
+
+/// Splits blob data into sparse shares, zero-padding the last share.
+fn split_into_shares(namespace: &[u8; NAMESPACE_SIZE], data: &[u8]) -> Vec<Share> {
+    let sequence_len = u32::try_from(data.len()).expect("blob is too large");
+    let (first_chunk, rest) = data.split_at(data.len().min(FIRST_SHARE_CONTENT_SIZE));
+
+    let mut shares = Vec::with_capacity(1 + rest.len().div_ceil(CONTINUATION_SHARE_CONTENT_SIZE));
+    let mut first_share = new_share(namespace, true);
+    first_share[SHARE_SIZE - FIRST_SHARE_CONTENT_SIZE - SEQUENCE_LEN_SIZE..][..SEQUENCE_LEN_SIZE]
+        .copy_from_slice(&sequence_len.to_be_bytes());
+    first_share[SHARE_SIZE - FIRST_SHARE_CONTENT_SIZE..][..first_chunk.len()]
+        .copy_from_slice(first_chunk);
+    shares.push(first_share);
+
+    for chunk in rest.chunks(CONTINUATION_SHARE_CONTENT_SIZE) {
+        let mut share = new_share(namespace, false);
+        share[SHARE_SIZE - CONTINUATION_SHARE_CONTENT_SIZE..][..chunk.len()].copy_from_slice(chunk);
+        shares.push(share);
+    }
+    shares
+}
+
+fn new_share(namespace: &[u8; NAMESPACE_SIZE], is_sequence_start: bool) -> Share {
+    let mut share = [0_u8; SHARE_SIZE];
+    share[..NAMESPACE_SIZE].copy_from_slice(namespace);
+    // Share version 0 occupies the upper 7 bits of the info byte.
+    share[NAMESPACE_SIZE] = u8::from(is_sequence_start);
+    share
+}
+
+/// Returns the maximum width of a subtree used to compute a commitment for a blob with the specified
+/// number of shares.
+fn subtree_width(share_count: usize) -> usize {
+    let width = share_count
+        .div_ceil(SUBTREE_ROOT_THRESHOLD)
+        .next_power_of_two();
+    // Mirrors `celestia-app`, which uses floating-point arithmetic here as well.
+    let min_square_size = ((share_count as f64).sqrt().ceil() as usize).next_power_of_two();
+    width.min(min_square_size)
+}
+
+/// Splits `total_size` leaves into perfect binary trees of at most `max_tree_size` leaves, largest first.
+fn merkle_mountain_range_sizes(mut total_size: usize, max_tree_size: usize) -> Vec<usize> {
+    let mut tree_sizes = vec![];
+    while total_size > 0 {
+        let tree_size = if total_size >= max_tree_size {
+            max_tree_size
+        } else {
+            round_down_power_of_two(total_size)
+        };
+        tree_sizes.push(tree_size);
+        total_size -= tree_size;
+    }
+    tree_sizes
+}
+
+fn round_down_power_of_two(value: usize) -> usize {
+    1 << (usize::BITS - 1 - value.leading_zeros())
+}
+
+/// Returns the largest power of two strictly less than `len`, which is where RFC 6962 Merkle trees are split.
+fn split_point(len: usize) -> usize {
+    round_down_power_of_two(len - 1)
+}
+
+/// Computes the root of an NMT with the specified shares as leaves. All shares belong to the same namespace,
+/// so the namespace range of each node consists of this namespace.
+fn nmt_root(namespace: &[u8; NAMESPACE_SIZE], leaves: &[Share]) -> NmtNode {
+    let digest = if let [leaf] = leaves {
+        // The namespace is prepended to the share again, as in `celestia-app`.
+        Sha256::new()
+            .chain_update([0])
+            .chain_update(namespace)
+            .chain_update(leaf)
+            .finalize()
+    } else {
+        let (left, right) = leaves.split_at(split_point(leaves.len()));
+        Sha256::new()
+            .chain_update([1])
+            .chain_update(nmt_root(namespace, left))
+            .chain_update(nmt_root(namespace, right))
+            .finalize()
+    };
+
+    let mut node = [0_u8; NMT_NODE_SIZE];
+    node[..NAMESPACE_SIZE].copy_from_slice(namespace);
+    node[NAMESPACE_SIZE..2 * NAMESPACE_SIZE].copy_from_slice(namespace);
+    node[2 * NAMESPACE_SIZE..].copy_from_slice(&digest);
+    node
+}
+
+/// Computes the RFC 6962 Merkle root of the specified items, as done by `merkle.HashFromByteSlices` in CometBFT.
+fn merkle_root(items: &[NmtNode]) -> [u8; 32] {
+    let digest = match items {
+        [] => Sha256::digest(b""),
+        [item] => Sha256::new()
+            .chain_update([0])
+            .chain_update(item)
+            .finalize(),
+        _ => {
+            let (left, right) = items.split_at(split_point(items.len()));
+            Sha256::new()
+                .chain_update([1])
+                .chain_update(merkle_root(left))
+                .chain_update(merkle_root(right))
+                .finalize()
+        }
+    };
+    digest.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMESPACE: [u8; NAMESPACE_SIZE] = [1; NAMESPACE_SIZE];
+
+    #[test]
+    fn splitting_into_shares() {
+        let shares = split_into_shares(&NAMESPACE, &[7; FIRST_SHARE_CONTENT_SIZE]);
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0][..NAMESPACE_SIZE], NAMESPACE);
+        assert_eq!(shares[0][NAMESPACE_SIZE], 1);
+        assert_eq!(
+            shares[0][NAMESPACE_SIZE + 1..][..SEQUENCE_LEN_SIZE],
+            (FIRST_SHARE_CONTENT_SIZE as u32).to_be_bytes()
+        );
+        assert!(shares[0][SHARE_SIZE - FIRST_SHARE_CONTENT_SIZE..]
+            .iter()
+            .all(|&byte| byte == 7));
+
+        let shares = split_into_shares(&NAMESPACE, &[7; FIRST_SHARE_CONTENT_SIZE + 1]);
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[1][..NAMESPACE_SIZE], NAMESPACE);
+        assert_eq!(shares[1][NAMESPACE_SIZE], 0);
+        assert_eq!(shares[1][NAMESPACE_SIZE + 1], 7);
+        assert!(shares[1][NAMESPACE_SIZE + 2..]
+            .iter()
+            .all(|&byte| byte == 0));
+
+        let data_len = FIRST_SHARE_CONTENT_SIZE + 2 * CONTINUATION_SHARE_CONTENT_SIZE;
+        assert_eq!(split_into_shares(&NAMESPACE, &vec![7; data_len]).len(), 3);
+    }
+
+    #[test]
+    fn computing_subtree_sizes() {
+        assert_eq!(subtree_width(1), 1);
+        assert_eq!(subtree_width(64), 1);
+        assert_eq!(subtree_width(65), 2);
+        assert_eq!(subtree_width(128), 2);
+        assert_eq!(subtree_width(4_000), 64);
+
+        assert_eq!(merkle_mountain_range_sizes(11, 4), [4, 4, 2, 1]);
+        assert_eq!(merkle_mountain_range_sizes(3, 1), [1, 1, 1]);
+        assert_eq!(merkle_mountain_range_sizes(7, 8), [4, 2, 1]);
+    }
+
+    #[test]
+    fn computing_merkle_roots() {
+        assert_eq!(
+            hex::encode(merkle_root(&[])),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let share = split_into_shares(&NAMESPACE, b"test")[0];
+        let leaf = nmt_root(&NAMESPACE, &[share]);
+        assert_eq!(leaf[..2 * NAMESPACE_SIZE], [1; 2 * NAMESPACE_SIZE]);
+        let leaf_digest: [u8; 32] = Sha256::new()
+            .chain_update([0])
+            .chain_update(NAMESPACE)
+            .chain_update(share)
+            .finalize()
+            .into();
+        assert_eq!(leaf[2 * NAMESPACE_SIZE..], leaf_digest);
+
+        let expected_commitment: [u8; 32] = Sha256::new()
+            .chain_update([0])
+            .chain_update(leaf)
+            .finalize()
+            .into();
+        assert_eq!(create_commitment(&NAMESPACE, b"test"), expected_commitment);
+    }
+
+    #[test]
+    fn commitment_depends_on_data_and_namespace() {
+        let data = vec![42; 10 * SHARE_SIZE];
+        let commitment = create_commitment(&NAMESPACE, &data);
+        assert_eq!(commitment, create_commitment(&NAMESPACE, &data));
+        assert_ne!(commitment, create_commitment(&NAMESPACE, &data[1..]));
+        assert_ne!(commitment, create_commitment(&[2; NAMESPACE_SIZE], &data));
+    }
+}
@@ -0,0 +1,550 @@
+//! Client for the [Celestia](https://celestia.org/) DA layer.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use zksync_config::configs::da_client::CelestiaConfig;
+use zksync_da_client::{
+    DAError, DataAvailabilityClient, DispatchResponse, InclusionData, InclusionStatus,
+};
+use zksync_types::{
+    ethabi::{self, ParamType, Token},
+    U256,
+};
+
+use self::commitment::create_commitment;
+use crate::utils::{http_error, non_retriable_error};
+
+mod commitment;
+
+/// Maximum size of a blob fitting into a Celestia block with the default maximum square size.
+pub(crate) const BLOB_SIZE_LIMIT: usize = 1_973_786;
+/// Size of a Celestia namespace: 1 version byte and 28 ID bytes.
+const NAMESPACE_SIZE: usize = 29;
+/// Maximum size of a user-specified ID for a version 0 namespace; the remaining ID bytes must be zero.
+const NAMESPACE_V0_ID_SIZE: usize = 10;
+/// Maximum number of Celestia blocks searched for a blob whose submission wasn't confirmed by the node.
+const MAX_SEARCHED_BLOCKS: u64 = 100;
+
+/// Builds a version 0 namespace from the hex-encoded ID.
+fn parse_namespace(namespace_id: &str) -> anyhow::Result<[u8; NAMESPACE_SIZE]> {
+    let namespace_id = namespace_id.strip_prefix("0x").unwrap_or(namespace_id);
+    let id = hex::decode(namespace_id).context("namespace ID is not a valid hex string")?;
+    anyhow::ensure!(
+        !id.is_empty() && id.len() <= NAMESPACE_V0_ID_SIZE,
+        "namespace ID must have 1 to {NAMESPACE_V0_ID_SIZE} bytes, got {}",
+        id.len()
+    );
+    let mut namespace = [0_u8; NAMESPACE_SIZE];
+    namespace[NAMESPACE_SIZE - id.len()..].copy_from_slice(&id);
+    Ok(namespace)
+}
+
+/// Parses a blob ID in the `{height}:{hex_commitment}` format produced by [`CelestiaClient`].
+fn parse_blob_id(blob_id: &str) -> anyhow::Result<(u64, Vec<u8>)> {
+    let (height, commitment) = blob_id
+        .split_once(':')
+        .context("blob ID must have `{height}:{commitment}` format")?;
+    let height = height.parse().context("malformed block height")?;
+    let commitment = hex::decode(commitment).context("malformed commitment")?;
+    Ok((height, commitment))
+}
+
+/// Encodes inclusion data as the ABI-encoded `(uint256 height, bytes29 namespace, bytes commitment)` tuple.
+fn encode_inclusion_data(
+    height: u64,
+    namespace: &[u8; NAMESPACE_SIZE],
+    commitment: Vec<u8>,
+) -> Vec<u8> {
+    ethabi::encode(&[
+        Token::Uint(U256::from(height)),
+        Token::FixedBytes(namespace.to_vec()),
+        Token::Bytes(commitment),
+    ])
+}
+
+/// Decodes inclusion data produced by [`encode_inclusion_data()`] into the block height, namespace and blob commitment.
+fn decode_inclusion_data(data: &[u8]) -> anyhow::Result<(u64, Vec<u8>, Vec<u8>)> {
+    let param_types = [
+        ParamType::Uint(256),
+        ParamType::FixedBytes(NAMESPACE_SIZE),
+        ParamType::Bytes,
+    ];
+    let mut tokens = ethabi::decode(&param_types, data)?.into_iter();
+    let height = tokens.next().and_then(Token::into_uint).unwrap();
+    anyhow::ensure!(
+        height <= U256::from(u64::MAX),
+        "height {height} is too large"
+    );
+    let namespace = tokens.next().and_then(Token::into_fixed_bytes).unwrap();
+    let commitment = tokens.next().and_then(Token::into_bytes).unwrap();
+    Ok((height.as_u64(), namespace, commitment))
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse<T> {
+    result: Option<T>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+impl JsonRpcError {
+    /// Substrings of celestia-node error messages for errors that won't go away on retry.
+    const NON_RETRIABLE_MESSAGES: &'static [&'static str] = &[
+        "insufficient funds",
+        "insufficient fee",
+        "blob size",
+        "invalid namespace",
+        "missing permission",
+    ];
+
+    /// Returned by celestia-node if the requested blob doesn't exist.
+    fn is_blob_not_found(&self) -> bool {
+        self.message.contains("blob: not found")
+    }
+
+    /// Errors defined by the JSON-RPC spec (except for internal errors) are caused by malformed requests.
+    /// Other errors are considered retriable unless they are known to be permanent; most of them are caused
+    /// by transient conditions, such as a transaction sequence mismatch or the node syncing.
+    fn is_retriable(&self) -> bool {
+        !matches!(self.code, -32700 | -32600 | -32601 | -32602)
+            && !Self::NON_RETRIABLE_MESSAGES
+                .iter()
+                .any(|message| self.message.contains(message))
+    }
+
+    fn into_da_error(self, method: &str) -> DAError {
+        DAError {
+            is_retriable: self.is_retriable(),
+            error: anyhow::anyhow!("`{method}` returned error {}: {}", self.code, self.message),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtendedHeader {
+    header: RawHeader,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawHeader {
+    /// Encoded as a decimal string, as is customary in CometBFT.
+    height: String,
+}
+
+/// Blob submission that wasn't confirmed by the node, e.g. because the request timed out or the node
+/// was restarted. The blob may still have been included, so it's searched for before being resubmitted.
+#[derive(Debug, Clone)]
+struct PendingSubmission {
+    commitment: [u8; 32],
+    /// Height of the latest block known to the node before the submission.
+    start_height: u64,
+}
+
+/// Blob as represented in the celestia-node API. Binary fields are base64-encoded.
+#[derive(Debug, Serialize, Deserialize)]
+struct Blob {
+    namespace: String,
+    data: String,
+    share_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    commitment: Option<String>,
+}
+
+/// Client for the Celestia DA layer. Blobs are identified by the height of the Celestia block they are included in
+/// and the blob commitment.
+///
+/// Celestia blocks are final once produced, and celestia-node responds to a blob submission only after
+/// the blob is included into a block. Thus, inclusion data (the block height, the namespace and the blob commitment)
+/// is available right after the blob is dispatched, once the blob is confirmed to be retrievable at the recorded height.
+///
+/// Blob commitments are computed locally, so they don't depend on the node. If a submission fails without
+/// a response from the node, the client searches for the blob in the following blocks before resubmitting it,
+/// so that retries don't lead to paying for the same blob multiple times. Unconfirmed submissions are tracked
+/// in memory, so this doesn't work across node restarts.
+#[derive(Clone)]
+pub struct CelestiaClient {
+    api_node_url: String,
+    auth_token: String,
+    namespace: [u8; NAMESPACE_SIZE],
+    client: reqwest::Client,
+    pending_submissions: Arc<Mutex<HashMap<u32, PendingSubmission>>>,
+}
+
+impl fmt::Debug for CelestiaClient {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("CelestiaClient")
+            .field("api_node_url", &self.api_node_url)
+            .field("namespace", &hex::encode(self.namespace))
+            .finish_non_exhaustive()
+    }
+}
+
+impl CelestiaClient {
+    pub fn new(config: CelestiaConfig, request_timeout: Duration) -> anyhow::Result<Self> {
+        let namespace = parse_namespace(&config.namespace).context("namespace")?;
+        let client = reqwest::Client::builder()
+            .timeout(request_timeout)
+            .build()
+            .context("failed building HTTP client")?;
+        Ok(Self {
+            api_node_url: config.api_node_url,
+            auth_token: config.auth_token,
+            namespace,
+            client,
+            pending_submissions: Arc::default(),
+        })
+    }
+
+    /// Calls the specified celestia-node JSON-RPC method. See [`JsonRpcError::is_retriable()`] for how errors
+    /// returned by the node are classified.
+    async fn call<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<R, DAError> {
+        self.call_raw(method, params)
+            .await?
+            .map_err(|err| err.into_da_error(method))
+    }
+
+    /// Same as [`Self::call()`], but returns `None` if the requested blob doesn't exist.
+    async fn call_for_blob<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Option<R>, DAError> {
+        match self.call_raw(method, params).await? {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if err.is_blob_not_found() => Ok(None),
+            Err(err) => Err(err.into_da_error(method)),
+        }
+    }
+
+    /// Returns a transport-level error or the response of the node.
+    async fn call_raw<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<Result<R, JsonRpcError>, DAError> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        };
+        let response: JsonRpcResponse<R> = self
+            .client
+            .post(&self.api_node_url)
+            .bearer_auth(&self.auth_token)
+            .json(&request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|err| http_error(err, &format!("failed calling `{method}`")))?
+            .json()
+            .await
+            .map_err(|err| http_error(err, &format!("malformed `{method}` response")))?;
+
+        if let Some(err) = response.error {
+            return Ok(Err(err));
+        }
+        response
+            .result
+            .map(Ok)
+            .with_context(|| format!("`{method}` returned neither result nor error"))
+            .map_err(non_retriable_error)
+    }
+
+    /// Returns the height of the latest block known to the node.
+    async fn local_head_height(&self) -> Result<u64, DAError> {
+        let header: ExtendedHeader = self.call("header.LocalHead", serde_json::json!([])).await?;
+        header
+            .header
+            .height
+            .parse()
+            .context("malformed block height")
+            .map_err(non_retriable_error)
+    }
+
+    /// Searches for the blob with the specified commitment in our namespace in the blocks produced after
+    /// `start_height`. Returns the height of the block containing the blob.
+    async fn find_blob(
+        &self,
+        start_height: u64,
+        commitment: &[u8],
+    ) -> Result<Option<u64>, DAError> {
+        let head_height = self.local_head_height().await?;
+        let last_height = head_height.min(start_height + MAX_SEARCHED_BLOCKS);
+        for height in (start_height + 1)..=last_height {
+            let params = serde_json::json!([
+                height,
+                BASE64.encode(self.namespace),
+                BASE64.encode(commitment)
+            ]);
+            let blob: Option<Blob> = self.call_for_blob("blob.Get", params).await?;
+            if blob.is_some() {
+                return Ok(Some(height));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for CelestiaClient {
+    fn client_type(&self) -> &'static str {
+        "Celestia"
+    }
+
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        let commitment = create_commitment(&self.namespace, &data);
+        let pending_submission = self
+            .pending_submissions
+            .lock()
+            .unwrap()
+            .get(&batch_number)
+            .cloned()
+            .filter(|submission| submission.commitment == commitment);
+        if let Some(submission) = &pending_submission {
+            if let Some(height) = self.find_blob(submission.start_height, &commitment).await? {
+                tracing::info!(
+                    "Found previously submitted pubdata for L1 batch #{batch_number} in Celestia at height {height}"
+                );
+                self.pending_submissions
+                    .lock()
+                    .unwrap()
+                    .remove(&batch_number);
+                return Ok(format!("{height}:{}", hex::encode(commitment)).into());
+            }
+        }
+
+        // If the blob is resubmitted, keep searching for it from the height of the first submission.
+        let start_height = match &pending_submission {
+            Some(submission) => submission.start_height,
+            None => self.local_head_height().await?,
+        };
+        self.pending_submissions.lock().unwrap().insert(
+            batch_number,
+            PendingSubmission {
+                commitment,
+                start_height,
+            },
+        );
+
+        let blob = Blob {
+            namespace: BASE64.encode(self.namespace),
+            data: BASE64.encode(data),
+            share_version: 0,
+            commitment: None,
+        };
+        // Use the default gas settings of the node.
+        let height: u64 = self
+            .call("blob.Submit", serde_json::json!([[blob], {}]))
+            .await?;
+        self.pending_submissions
+            .lock()
+            .unwrap()
+            .remove(&batch_number);
+
+        tracing::debug!(
+            "Submitted pubdata for L1 batch #{batch_number} to Celestia at height {height}, commitment 0x{}",
+            hex::encode(&commitment)
+        );
+        Ok(format!("{height}:{}", hex::encode(commitment)).into())
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<InclusionStatus, DAError> {
+        let (height, commitment) = parse_blob_id(blob_id).map_err(non_retriable_error)?;
+        let params = serde_json::json!([
+            height,
+            BASE64.encode(self.namespace),
+            BASE64.encode(&commitment)
+        ]);
+        let Some(proof): Option<serde_json::Value> =
+            self.call_for_blob("blob.GetProof", params).await?
+        else {
+            return Ok(InclusionStatus::Pending);
+        };
+        let params = serde_json::json!([
+            height,
+            BASE64.encode(self.namespace),
+            proof,
+            BASE64.encode(&commitment)
+        ]);
+        let is_included: bool = self.call("blob.Included", params).await?;
+        if !is_included {
+            return Ok(InclusionStatus::Pending);
+        }
+
+        Ok(InclusionStatus::Included(InclusionData {
+            data: encode_inclusion_data(height, &self.namespace, commitment),
+        }))
+    }
+
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        data: &[u8],
+        inclusion_data: &InclusionData,
+    ) -> Result<(), DAError> {
+        let (height, commitment) = parse_blob_id(blob_id).map_err(non_retriable_error)?;
+        let (included_height, namespace, included_commitment) =
+            decode_inclusion_data(&inclusion_data.data)
+                .context("malformed inclusion data")
+                .map_err(non_retriable_error)?;
+        if included_height != height
+            || namespace != self.namespace
+            || included_commitment != commitment
+        {
+            return Err(non_retriable_error(anyhow::anyhow!(
+                "inclusion data doesn't correspond to blob {blob_id} in namespace 0x{}",
+                hex::encode(self.namespace)
+            )));
+        }
+
+        // Inclusion of the commitment is checked by the node, so check that the commitment actually commits
+        // to the expected data. This doesn't require trusting the node, since the commitment is a Merkle root
+        // over the blob shares.
+        let expected_commitment = create_commitment(&self.namespace, data);
+        if commitment != expected_commitment {
+            return Err(non_retriable_error(anyhow::anyhow!(
+                "commitment of blob {blob_id} differs from the commitment of the dispatched pubdata (0x{})",
+                hex::encode(expected_commitment)
+            )));
+        }
+        Ok(())
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        Some(BLOB_SIZE_LIMIT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_namespace() {
+        let namespace = parse_namespace("0x7a6b73796e63").unwrap();
+        assert_eq!(namespace[..23], [0; 23]);
+        assert_eq!(namespace[23..], *b"zksync");
+        let namespace = parse_namespace("00112233445566778899").unwrap();
+        assert_eq!(namespace[..19], [0; 19]);
+
+        parse_namespace("").unwrap_err();
+        parse_namespace("zz").unwrap_err();
+        parse_namespace("00112233445566778899aa").unwrap_err();
+    }
+
+    #[test]
+    fn parsing_blob_id() {
+        let (height, commitment) = parse_blob_id("123:0a0b").unwrap();
+        assert_eq!(height, 123);
+        assert_eq!(commitment, [10, 11]);
+
+        parse_blob_id("123").unwrap_err();
+        parse_blob_id("0x01:0a0b").unwrap_err();
+    }
+
+    #[test]
+    fn encoding_inclusion_data() {
+        let namespace = parse_namespace("0x7a6b73796e63").unwrap();
+        let encoded = encode_inclusion_data(123, &namespace, vec![10, 11]);
+        let (height, decoded_namespace, commitment) = decode_inclusion_data(&encoded).unwrap();
+        assert_eq!(height, 123);
+        assert_eq!(decoded_namespace, namespace);
+        assert_eq!(commitment, [10, 11]);
+
+        decode_inclusion_data(&encoded[..32]).unwrap_err();
+    }
+
+    #[test]
+    fn classifying_json_rpc_errors() {
+        let err: JsonRpcError = serde_json::from_value(serde_json::json!({
+            "code": 1,
+            "message": "account sequence mismatch, expected 5, got 4",
+        }))
+        .unwrap();
+        assert!(err.is_retriable());
+        assert!(!err.is_blob_not_found());
+
+        let err = JsonRpcError {
+            code: -32601,
+            message: "method 'blob.Submit' not found".to_owned(),
+        };
+        assert!(!err.is_retriable());
+
+        let err = JsonRpcError {
+            code: 1,
+            message: "broadcast tx error: insufficient funds: spendable balance 0utia".to_owned(),
+        };
+        assert!(!err.is_retriable());
+
+        let err = JsonRpcError {
+            code: 1,
+            message: "getting blob: blob: not found".to_owned(),
+        };
+        assert!(err.is_blob_not_found());
+    }
+
+    #[test]
+    fn deserializing_header() {
+        let header: ExtendedHeader = serde_json::from_value(serde_json::json!({
+            "header": { "chain_id": "mocha-4", "height": "123456" },
+            "commit": {},
+        }))
+        .unwrap();
+        assert_eq!(header.header.height, "123456");
+    }
+
+    #[test]
+    fn serializing_blob() {
+        let blob = Blob {
+            namespace: BASE64.encode([0; NAMESPACE_SIZE]),
+            data: BASE64.encode(b"test"),
+            share_version: 0,
+            commitment: None,
+        };
+        let json = serde_json::to_value(&blob).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "namespace": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+                "data": "dGVzdA==",
+                "share_version": 0,
+            })
+        );
+    }
+}
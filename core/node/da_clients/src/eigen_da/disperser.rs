@@ -0,0 +1,199 @@
+//! Messages of the EigenDA disperser gRPC API (`disperser.Disperser` service, API v1). Only the messages
+//! used by [`EigenDAClient`](super::EigenDAClient) are defined; field tags match the upstream protobuf definitions.
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct DisperseBlobRequest {
+    /// Blob data; each 32-byte chunk must be a valid BN254 field element.
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: Vec<u8>,
+    #[prost(uint32, repeated, tag = "2")]
+    pub custom_quorum_numbers: Vec<u32>,
+    /// Account ID for authenticated dispersal; empty for unauthenticated dispersal.
+    #[prost(string, tag = "3")]
+    pub account_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct DisperseBlobReply {
+    #[prost(enumeration = "BlobStatus", tag = "1")]
+    pub result: i32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub request_id: Vec<u8>,
+}
+
+/// Request sent over the bidirectional `DisperseBlobAuthenticated` stream.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct AuthenticatedRequest {
+    #[prost(oneof = "authenticated_request::Payload", tags = "1, 2")]
+    pub payload: Option<authenticated_request::Payload>,
+}
+
+pub(super) mod authenticated_request {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub(in crate::eigen_da) enum Payload {
+        /// First message of the stream; `account_id` must be set.
+        #[prost(message, tag = "1")]
+        DisperseRequest(super::DisperseBlobRequest),
+        /// Response to the challenge sent by the disperser.
+        #[prost(message, tag = "2")]
+        AuthenticationData(super::AuthenticationData),
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct AuthenticationData {
+    /// Signature of the challenge parameter by the account key.
+    #[prost(bytes = "vec", tag = "1")]
+    pub authentication_data: Vec<u8>,
+}
+
+/// Reply sent over the bidirectional `DisperseBlobAuthenticated` stream.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct AuthenticatedReply {
+    #[prost(oneof = "authenticated_reply::Payload", tags = "1, 2")]
+    pub payload: Option<authenticated_reply::Payload>,
+}
+
+pub(super) mod authenticated_reply {
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub(in crate::eigen_da) enum Payload {
+        /// Challenge that must be signed by the account key.
+        #[prost(message, tag = "1")]
+        BlobAuthHeader(super::BlobAuthHeader),
+        /// Final reply, sent once the challenge is verified.
+        #[prost(message, tag = "2")]
+        DisperseReply(super::DisperseBlobReply),
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobAuthHeader {
+    #[prost(uint32, tag = "1")]
+    pub challenge_parameter: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobStatusRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub request_id: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobStatusReply {
+    #[prost(enumeration = "BlobStatus", tag = "1")]
+    pub status: i32,
+    /// Set once the blob is confirmed.
+    #[prost(message, optional, tag = "2")]
+    pub info: Option<BlobInfo>,
+}
+
+/// Information necessary to verify the blob availability on Ethereum.
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobInfo {
+    #[prost(message, optional, tag = "1")]
+    pub blob_header: Option<BlobHeader>,
+    #[prost(message, optional, tag = "2")]
+    pub blob_verification_proof: Option<BlobVerificationProof>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobHeader {
+    #[prost(message, optional, tag = "1")]
+    pub commitment: Option<G1Commitment>,
+    #[prost(uint32, tag = "2")]
+    pub data_length: u32,
+    #[prost(message, repeated, tag = "3")]
+    pub blob_quorum_params: Vec<BlobQuorumParam>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct G1Commitment {
+    #[prost(bytes = "vec", tag = "1")]
+    pub x: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub y: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobQuorumParam {
+    #[prost(uint32, tag = "1")]
+    pub quorum_number: u32,
+    #[prost(uint32, tag = "2")]
+    pub adversary_threshold_percentage: u32,
+    #[prost(uint32, tag = "3")]
+    pub confirmation_threshold_percentage: u32,
+    #[prost(uint32, tag = "4")]
+    pub chunk_length: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BlobVerificationProof {
+    #[prost(uint32, tag = "1")]
+    pub batch_id: u32,
+    #[prost(uint32, tag = "2")]
+    pub blob_index: u32,
+    #[prost(message, optional, tag = "3")]
+    pub batch_metadata: Option<BatchMetadata>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub inclusion_proof: Vec<u8>,
+    #[prost(bytes = "vec", tag = "5")]
+    pub quorum_indexes: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BatchMetadata {
+    #[prost(message, optional, tag = "1")]
+    pub batch_header: Option<BatchHeader>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signatory_record_hash: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub fee: Vec<u8>,
+    #[prost(uint32, tag = "4")]
+    pub confirmation_block_number: u32,
+    #[prost(bytes = "vec", tag = "5")]
+    pub batch_header_hash: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub(super) struct BatchHeader {
+    #[prost(bytes = "vec", tag = "1")]
+    pub batch_root: Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub quorum_numbers: Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub quorum_signed_percentages: Vec<u8>,
+    #[prost(uint32, tag = "4")]
+    pub reference_block_number: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub(super) enum BlobStatus {
+    Unknown = 0,
+    /// The blob is queued for dispersal.
+    Processing = 1,
+    /// The blob batch is confirmed on Ethereum.
+    Confirmed = 2,
+    /// The blob has failed to be dispersed; it should be re-dispersed.
+    Failed = 3,
+    /// The blob batch confirmation is finalized on Ethereum.
+    Finalized = 4,
+    /// The blob didn't receive enough operator signatures; it should be re-dispersed.
+    InsufficientSignatures = 5,
+    /// The blob is being dispersed to operators.
+    Dispersing = 6,
+}
+
+impl BlobStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Processing => "processing",
+            Self::Confirmed => "confirmed",
+            Self::Failed => "failed",
+            Self::Finalized => "finalized",
+            Self::InsufficientSignatures => "insufficient_signatures",
+            Self::Dispersing => "dispersing",
+        }
+    }
+}
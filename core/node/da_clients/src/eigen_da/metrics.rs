@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+use vise::{Buckets, Counter, Histogram, LabeledFamily, Metrics, Unit};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_eigen_da_client")]
+pub(super) struct EigenDAMetrics {
+    /// Latency of requests to the disperser.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds, labels = ["method"])]
+    pub request_latency: LabeledFamily<&'static str, Histogram<Duration>>,
+    /// Number of failed requests to the disperser.
+    #[metrics(labels = ["method"])]
+    pub request_errors: LabeledFamily<&'static str, Counter>,
+    /// Number of blob statuses received from the disperser.
+    #[metrics(labels = ["status"])]
+    pub blob_statuses: LabeledFamily<&'static str, Counter>,
+    /// Size of dispersed blobs after encoding the data into field elements.
+    #[metrics(buckets = Buckets::exponential(1_024.0..=16_777_216.0, 2.0), unit = Unit::Bytes)]
+    pub dispersed_blob_size: Histogram<usize>,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<EigenDAMetrics> = vise::Global::new();
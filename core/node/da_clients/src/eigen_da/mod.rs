@@ -0,0 +1,568 @@
+//! Client for the [EigenDA](https://www.eigenda.xyz/) DA layer.
+
+use std::{path::Path, sync::Arc, time::Duration};
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use futures::channel::mpsc;
+use prost::Message as _;
+use tonic::{
+    client::Grpc,
+    codec::{ProstCodec, Streaming},
+    codegen::http::uri::PathAndQuery,
+    transport::{Channel, ClientTlsConfig, Endpoint},
+    Code, Status,
+};
+use zksync_config::configs::da_client::EigenDAConfig;
+use zksync_da_client::{
+    DAError, DataAvailabilityClient, DispatchResponse, InclusionData, InclusionStatus,
+};
+
+use self::{
+    disperser::{
+        authenticated_reply, authenticated_request, AuthenticatedReply, AuthenticatedRequest,
+        AuthenticationData, BlobInfo, BlobStatus, BlobStatusReply, BlobStatusRequest,
+        DisperseBlobReply, DisperseBlobRequest,
+    },
+    metrics::METRICS,
+    signer::AccountSigner,
+    verifier::{hash_blob_header, verify_inclusion_proof, G1Srs},
+};
+use crate::utils::non_retriable_error;
+
+mod disperser;
+mod metrics;
+mod signer;
+mod verifier;
+
+const DISPERSE_BLOB_PATH: &str = "/disperser.Disperser/DisperseBlob";
+const DISPERSE_BLOB_AUTHENTICATED_PATH: &str = "/disperser.Disperser/DisperseBlobAuthenticated";
+const GET_BLOB_STATUS_PATH: &str = "/disperser.Disperser/GetBlobStatus";
+
+/// Number of data bytes packed into a 32-byte field element. The first byte of each element is zero,
+/// which guarantees that the element is less than the BN254 modulus.
+const BYTES_PER_FIELD_ELEMENT: usize = 31;
+
+/// Encodes data into a sequence of BN254 field elements, as required by the disperser.
+fn encode_blob_data(data: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(data.len().div_ceil(BYTES_PER_FIELD_ELEMENT) * 32);
+    for chunk in data.chunks(BYTES_PER_FIELD_ELEMENT) {
+        encoded.push(0);
+        encoded.extend_from_slice(chunk);
+    }
+    encoded
+}
+
+/// Returns the maximum size of unencoded data that fits into a blob with the configured size limit.
+pub(crate) fn blob_size_limit(config: &EigenDAConfig) -> usize {
+    // The configured limit applies to the encoded data.
+    config.blob_size_limit as usize / 32 * BYTES_PER_FIELD_ELEMENT
+}
+
+/// Checks whether a blob with the specified status can be considered included into the DA layer. Returns `None`
+/// if the blob will never be included and must be dispersed again.
+fn is_included(status: BlobStatus, wait_for_finalization: bool) -> Option<bool> {
+    match status {
+        BlobStatus::Processing | BlobStatus::Dispersing => Some(false),
+        BlobStatus::Confirmed => Some(!wait_for_finalization),
+        BlobStatus::Finalized => Some(true),
+        BlobStatus::Failed | BlobStatus::InsufficientSignatures | BlobStatus::Unknown => None,
+    }
+}
+
+/// Checks that the blob info is consistent with the dispersed data: the blob commitment commits to the data, the blob
+/// header is included into the batch, and the blob is attested by the required quorums. That the batch is confirmed
+/// on Ethereum is checked by the EigenDA verifier contract on L1.
+fn verify_blob_info(
+    info: &BlobInfo,
+    data: &[u8],
+    custom_quorum_numbers: &[u32],
+    srs: &G1Srs,
+) -> anyhow::Result<()> {
+    let header = info.blob_header.as_ref().context("missing blob header")?;
+    let commitment = header
+        .commitment
+        .as_ref()
+        .context("missing blob commitment")?;
+    let encoded_data = encode_blob_data(data);
+    srs.verify_commitment(commitment, &encoded_data)?;
+    // Data length is measured in field elements and may include padding.
+    let min_data_length = encoded_data.len().div_ceil(32);
+    anyhow::ensure!(
+        header.data_length as usize >= min_data_length,
+        "blob data length {} is less than the length of dispersed data ({min_data_length} field elements)",
+        header.data_length
+    );
+    for &quorum_number in custom_quorum_numbers {
+        anyhow::ensure!(
+            header
+                .blob_quorum_params
+                .iter()
+                .any(|params| params.quorum_number == quorum_number),
+            "blob is not dispersed to custom quorum {quorum_number}"
+        );
+    }
+
+    let proof = info
+        .blob_verification_proof
+        .as_ref()
+        .context("missing blob verification proof")?;
+    let batch_metadata = proof
+        .batch_metadata
+        .as_ref()
+        .context("missing batch metadata")?;
+    anyhow::ensure!(
+        !batch_metadata.batch_header_hash.is_empty(),
+        "batch header hash is empty"
+    );
+    let batch_header = batch_metadata
+        .batch_header
+        .as_ref()
+        .context("missing batch header")?;
+    verify_inclusion_proof(
+        hash_blob_header(header, commitment),
+        proof.blob_index,
+        &proof.inclusion_proof,
+        &batch_header.batch_root,
+    )?;
+    anyhow::ensure!(
+        proof.quorum_indexes.len() == header.blob_quorum_params.len(),
+        "number of quorum indexes doesn't match the number of blob quorums"
+    );
+    for (params, &quorum_index) in header.blob_quorum_params.iter().zip(&proof.quorum_indexes) {
+        let quorum_index = usize::from(quorum_index);
+        let quorum_number = batch_header.quorum_numbers.get(quorum_index).copied();
+        anyhow::ensure!(
+            quorum_number.map(u32::from) == Some(params.quorum_number),
+            "quorum index {quorum_index} doesn't point to quorum {} in the batch header",
+            params.quorum_number
+        );
+        let signed_percentage = batch_header
+            .quorum_signed_percentages
+            .get(quorum_index)
+            .copied()
+            .unwrap_or(0);
+        anyhow::ensure!(
+            u32::from(signed_percentage) >= params.confirmation_threshold_percentage,
+            "quorum {} signed {signed_percentage}% of stake, less than the confirmation threshold {}%",
+            params.quorum_number,
+            params.confirmation_threshold_percentage
+        );
+    }
+    Ok(())
+}
+
+fn status_error(status: Status, method: &'static str) -> DAError {
+    METRICS.request_errors[&method].inc();
+    let is_retriable = matches!(
+        status.code(),
+        Code::Unavailable
+            | Code::DeadlineExceeded
+            | Code::ResourceExhausted
+            | Code::Aborted
+            | Code::Internal
+            | Code::Unknown
+    );
+    DAError {
+        error: anyhow::anyhow!("`{method}` request to EigenDA disperser failed: {status}"),
+        is_retriable,
+    }
+}
+
+/// Client for the EigenDA DA layer. Blobs are identified by the hex-encoded request ID returned by the disperser.
+///
+/// Dispersal is asynchronous: the disperser aggregates blobs into batches and confirms batches on Ethereum.
+/// Inclusion data (the protobuf-encoded `BlobInfo` with the blob header and the verification proof) becomes available
+/// once the blob batch is confirmed or, if configured, finalized. Blobs that fail to be confirmed are reported
+/// as [`InclusionStatus::Failed`], so that they are dispersed again.
+///
+/// If a signer is configured, blobs are dispersed on behalf of the signer account via the authenticated API.
+#[derive(Debug, Clone)]
+pub struct EigenDAClient {
+    config: EigenDAConfig,
+    signer: Option<AccountSigner>,
+    srs: Arc<G1Srs>,
+    channel: Channel,
+}
+
+impl EigenDAClient {
+    pub fn new(config: EigenDAConfig, request_timeout: Duration) -> anyhow::Result<Self> {
+        let signer = match &config.signer_private_key {
+            Some(private_key) => Some(
+                AccountSigner::new(private_key, config.account_id.as_deref())
+                    .context("invalid EigenDA signer")?,
+            ),
+            None => {
+                anyhow::ensure!(
+                    config.account_id.is_none(),
+                    "EigenDA account ID is specified without a signer private key"
+                );
+                None
+            }
+        };
+        // The SRS must cover the largest possible blob; the size limit applies to the encoded data.
+        let srs = G1Srs::load(
+            Path::new(&config.g1_srs_path),
+            config.blob_size_limit as usize / 32,
+        )
+        .context("failed loading EigenDA SRS")?;
+        let mut endpoint = Endpoint::from_shared(config.disperser_rpc.clone())
+            .context("invalid disperser URL")?
+            .timeout(request_timeout);
+        if config.disperser_rpc.starts_with("https://") {
+            endpoint = endpoint
+                .tls_config(ClientTlsConfig::new())
+                .context("failed configuring TLS")?;
+        }
+        // The connection is established on the first request, so that the node can start if the disperser is unavailable.
+        let channel = endpoint.connect_lazy();
+        Ok(Self {
+            config,
+            signer,
+            srs: Arc::new(srs),
+            channel,
+        })
+    }
+
+    async fn grpc_client(&self, method: &'static str) -> Result<Grpc<Channel>, DAError> {
+        let mut client = Grpc::new(self.channel.clone());
+        client.ready().await.map_err(|err| {
+            status_error(
+                Status::unavailable(format!("disperser is not ready: {err}")),
+                method,
+            )
+        })?;
+        Ok(client)
+    }
+
+    async fn unary<Req, Resp>(
+        &self,
+        method: &'static str,
+        path: &'static str,
+        request: Req,
+    ) -> Result<Resp, DAError>
+    where
+        Req: prost::Message + Send + 'static,
+        Resp: prost::Message + Default + Send + 'static,
+    {
+        let latency = METRICS.request_latency[&method].start();
+        let response = self
+            .grpc_client(method)
+            .await?
+            .unary(
+                tonic::Request::new(request),
+                PathAndQuery::from_static(path),
+                ProstCodec::default(),
+            )
+            .await
+            .map_err(|status| status_error(status, method))?;
+        latency.observe();
+        Ok(response.into_inner())
+    }
+
+    /// Disperses a blob via the authenticated API: sends the dispersal request, signs the challenge returned
+    /// by the disperser and waits for the dispersal reply.
+    async fn disperse_blob_authenticated(
+        &self,
+        signer: &AccountSigner,
+        request: DisperseBlobRequest,
+    ) -> Result<DisperseBlobReply, DAError> {
+        const METHOD: &str = "disperse_blob_authenticated";
+
+        let latency = METRICS.request_latency[&METHOD].start();
+        let (requests_sender, requests) = mpsc::unbounded();
+        let request = authenticated_request::Payload::DisperseRequest(request);
+        requests_sender
+            .unbounded_send(AuthenticatedRequest {
+                payload: Some(request),
+            })
+            .expect("requests receiver dropped");
+        let mut replies = self
+            .grpc_client(METHOD)
+            .await?
+            .streaming(
+                tonic::Request::new(requests),
+                PathAndQuery::from_static(DISPERSE_BLOB_AUTHENTICATED_PATH),
+                ProstCodec::<AuthenticatedRequest, AuthenticatedReply>::default(),
+            )
+            .await
+            .map_err(|status| status_error(status, METHOD))?
+            .into_inner();
+
+        let challenge = match next_reply(&mut replies, METHOD).await? {
+            authenticated_reply::Payload::BlobAuthHeader(header) => header.challenge_parameter,
+            authenticated_reply::Payload::DisperseReply(_) => {
+                return Err(non_retriable_error(anyhow::anyhow!(
+                    "disperser replied without authenticating the request"
+                )));
+            }
+        };
+        let authentication_data = AuthenticationData {
+            authentication_data: signer.sign_challenge(challenge),
+        };
+        requests_sender
+            .unbounded_send(AuthenticatedRequest {
+                payload: Some(authenticated_request::Payload::AuthenticationData(
+                    authentication_data,
+                )),
+            })
+            .map_err(|_| {
+                status_error(
+                    Status::unavailable("disperser closed the request stream"),
+                    METHOD,
+                )
+            })?;
+
+        let reply = match next_reply(&mut replies, METHOD).await? {
+            authenticated_reply::Payload::DisperseReply(reply) => reply,
+            authenticated_reply::Payload::BlobAuthHeader(_) => {
+                return Err(non_retriable_error(anyhow::anyhow!(
+                    "disperser sent a second authentication challenge"
+                )));
+            }
+        };
+        latency.observe();
+        Ok(reply)
+    }
+}
+
+async fn next_reply(
+    replies: &mut Streaming<AuthenticatedReply>,
+    method: &'static str,
+) -> Result<authenticated_reply::Payload, DAError> {
+    let reply = replies
+        .message()
+        .await
+        .map_err(|status| status_error(status, method))?;
+    reply.and_then(|reply| reply.payload).ok_or_else(|| {
+        status_error(
+            Status::unavailable("disperser closed the reply stream prematurely"),
+            method,
+        )
+    })
+}
+
+#[async_trait]
+impl DataAvailabilityClient for EigenDAClient {
+    fn client_type(&self) -> &'static str {
+        "EigenDA"
+    }
+
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        let data = encode_blob_data(&data);
+        METRICS.dispersed_blob_size.observe(data.len());
+        let mut request = DisperseBlobRequest {
+            data,
+            custom_quorum_numbers: self.config.custom_quorum_numbers.clone(),
+            account_id: String::new(),
+        };
+        let reply: DisperseBlobReply = if let Some(signer) = &self.signer {
+            request.account_id = signer.account_id().to_owned();
+            self.disperse_blob_authenticated(signer, request).await?
+        } else {
+            self.unary("disperse_blob", DISPERSE_BLOB_PATH, request)
+                .await?
+        };
+        let status = BlobStatus::try_from(reply.result).unwrap_or(BlobStatus::Unknown);
+        if matches!(
+            status,
+            BlobStatus::Failed | BlobStatus::InsufficientSignatures
+        ) {
+            return Err(DAError {
+                error: anyhow::anyhow!("disperser returned status `{}`", status.as_str()),
+                is_retriable: true,
+            });
+        }
+
+        let request_id = hex::encode(reply.request_id);
+        tracing::debug!(
+            "Dispersed pubdata for L1 batch #{batch_number} to EigenDA, request ID: {request_id}"
+        );
+        Ok(request_id.into())
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<InclusionStatus, DAError> {
+        let request_id = hex::decode(blob_id)
+            .context("malformed blob ID")
+            .map_err(non_retriable_error)?;
+        let reply: BlobStatusReply = self
+            .unary(
+                "get_blob_status",
+                GET_BLOB_STATUS_PATH,
+                BlobStatusRequest { request_id },
+            )
+            .await?;
+        let status = BlobStatus::try_from(reply.status).unwrap_or(BlobStatus::Unknown);
+        METRICS.blob_statuses[&status.as_str()].inc();
+        let Some(is_included) = is_included(status, self.config.wait_for_finalization) else {
+            return Ok(InclusionStatus::Failed {
+                reason: format!("disperser returned status `{}`", status.as_str()),
+            });
+        };
+        if !is_included {
+            return Ok(InclusionStatus::Pending);
+        }
+
+        let info = reply
+            .info
+            .with_context(|| format!("disperser returned no info for {} blob", status.as_str()))
+            .map_err(non_retriable_error)?;
+        Ok(InclusionStatus::Included(InclusionData {
+            data: info.encode_to_vec(),
+        }))
+    }
+
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        data: &[u8],
+        inclusion_data: &InclusionData,
+    ) -> Result<(), DAError> {
+        BlobInfo::decode(inclusion_data.data.as_slice())
+            .context("malformed blob info")
+            .and_then(|info| {
+                verify_blob_info(&info, data, &self.config.custom_quorum_numbers, &self.srs)
+            })
+            .with_context(|| format!("blob {blob_id}"))
+            .map_err(non_retriable_error)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        Some(blob_size_limit(&self.config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::web3::keccak256;
+
+    use super::{
+        disperser::{
+            BatchHeader, BatchMetadata, BlobHeader, BlobQuorumParam, BlobVerificationProof,
+        },
+        *,
+    };
+
+    #[test]
+    fn encoding_blob_data() {
+        let data: Vec<u8> = (1..=70).collect();
+        let encoded = encode_blob_data(&data);
+        assert_eq!(encoded.len(), 32 + 32 + 9);
+        for (i, element) in encoded.chunks(32).enumerate() {
+            assert_eq!(element[0], 0);
+            let start = i * BYTES_PER_FIELD_ELEMENT;
+            assert_eq!(element[1..], data[start..(start + element.len() - 1)]);
+        }
+        assert!(encode_blob_data(&[]).is_empty());
+    }
+
+    #[test]
+    fn checking_blob_inclusion() {
+        assert!(!is_included(BlobStatus::Processing, false).unwrap());
+        assert!(!is_included(BlobStatus::Dispersing, true).unwrap());
+        assert!(is_included(BlobStatus::Confirmed, false).unwrap());
+        assert!(!is_included(BlobStatus::Confirmed, true).unwrap());
+        assert!(is_included(BlobStatus::Finalized, true).unwrap());
+        assert_eq!(is_included(BlobStatus::Failed, false), None);
+        assert_eq!(is_included(BlobStatus::InsufficientSignatures, true), None);
+        assert_eq!(is_included(BlobStatus::Unknown, false), None);
+    }
+
+    #[test]
+    fn verifying_blob_info() {
+        let data = vec![1; 100];
+        let commitment = verifier::tests::commit(&encode_blob_data(&data));
+        let header = BlobHeader {
+            commitment: Some(commitment.clone()),
+            data_length: 4,
+            blob_quorum_params: vec![
+                BlobQuorumParam {
+                    quorum_number: 0,
+                    confirmation_threshold_percentage: 55,
+                    ..BlobQuorumParam::default()
+                },
+                BlobQuorumParam {
+                    quorum_number: 1,
+                    confirmation_threshold_percentage: 55,
+                    ..BlobQuorumParam::default()
+                },
+            ],
+        };
+        // The batch consists of 2 blobs; the blob is the first one.
+        let sibling = [5_u8; 32];
+        let leaf = keccak256(&hash_blob_header(&header, &commitment));
+        let batch_root = keccak256(&[leaf, sibling].concat());
+        let mut info = BlobInfo {
+            blob_header: Some(header),
+            blob_verification_proof: Some(BlobVerificationProof {
+                batch_metadata: Some(BatchMetadata {
+                    batch_header: Some(BatchHeader {
+                        batch_root: batch_root.to_vec(),
+                        quorum_numbers: vec![1, 0],
+                        quorum_signed_percentages: vec![60, 80],
+                        ..BatchHeader::default()
+                    }),
+                    batch_header_hash: vec![3; 32],
+                    ..BatchMetadata::default()
+                }),
+                blob_index: 0,
+                inclusion_proof: sibling.to_vec(),
+                quorum_indexes: vec![1, 0],
+                ..BlobVerificationProof::default()
+            }),
+        };
+        let srs_file = verifier::tests::create_srs_file(8);
+        let srs = G1Srs::load(srs_file.path(), 8).unwrap();
+        verify_blob_info(&info, &data, &[1], &srs).unwrap();
+        verify_blob_info(&info, &data, &[2], &srs).unwrap_err();
+        let err = verify_blob_info(&info, &[2; 100], &[], &srs).unwrap_err();
+        assert!(err.to_string().contains("commitment"), "{err}");
+
+        let proof = info.blob_verification_proof.as_mut().unwrap();
+        proof.blob_index = 1;
+        let err = verify_blob_info(&info, &data, &[], &srs).unwrap_err();
+        assert!(err.to_string().contains("batch root"), "{err}");
+        let proof = info.blob_verification_proof.as_mut().unwrap();
+        proof.blob_index = 0;
+        proof.quorum_indexes = vec![0, 1];
+        verify_blob_info(&info, &data, &[], &srs).unwrap_err();
+        let proof = info.blob_verification_proof.as_mut().unwrap();
+        proof.quorum_indexes = vec![1, 0];
+        let batch_header = proof
+            .batch_metadata
+            .as_mut()
+            .unwrap()
+            .batch_header
+            .as_mut()
+            .unwrap();
+        batch_header.quorum_signed_percentages = vec![50, 80];
+        verify_blob_info(&info, &data, &[], &srs).unwrap_err();
+    }
+
+    // The client must be created in a Tokio runtime, since the gRPC channel spawns a background task.
+    #[tokio::test]
+    async fn blob_size_limit_accounts_for_encoding() {
+        let srs_file = verifier::tests::create_srs_file(2);
+        let config = EigenDAConfig {
+            disperser_rpc: "http://localhost:51001".to_owned(),
+            custom_quorum_numbers: vec![],
+            wait_for_finalization: false,
+            blob_size_limit: 64,
+            account_id: None,
+            signer_private_key: None,
+            g1_srs_path: srs_file.path().to_str().unwrap().to_owned(),
+        };
+        let client = EigenDAClient::new(config, Duration::from_secs(1)).unwrap();
+        let limit = client.blob_size_limit().unwrap();
+        assert_eq!(limit, 62);
+        assert_eq!(encode_blob_data(&vec![1; limit]).len(), 64);
+    }
+}
@@ -0,0 +1,110 @@
+//! Signing of authenticated blob dispersal requests.
+
+use std::fmt;
+
+use anyhow::Context as _;
+use secp256k1::{Message, PublicKey, SecretKey, SECP256K1};
+use zksync_types::web3::keccak256;
+
+/// Account dispersing blobs via the authenticated disperser API. The disperser identifies the account by its
+/// public key and charges it for dispersal (e.g., against a reservation or on-demand payments).
+#[derive(Clone)]
+pub(super) struct AccountSigner {
+    private_key: SecretKey,
+    account_id: String,
+}
+
+impl fmt::Debug for AccountSigner {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("AccountSigner")
+            .field("account_id", &self.account_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AccountSigner {
+    /// Creates a signer from a hex-encoded secp256k1 private key. If `account_id` is specified, checks that
+    /// it corresponds to the key.
+    pub fn new(private_key: &str, account_id: Option<&str>) -> anyhow::Result<Self> {
+        let private_key = private_key.strip_prefix("0x").unwrap_or(private_key);
+        let private_key =
+            hex::decode(private_key).context("private key is not a valid hex string")?;
+        let private_key =
+            SecretKey::from_slice(&private_key).context("invalid secp256k1 private key")?;
+        // Account IDs are hex-encoded uncompressed public keys.
+        let public_key = PublicKey::from_secret_key(SECP256K1, &private_key);
+        let expected_account_id = format!("0x{}", hex::encode(public_key.serialize_uncompressed()));
+        if let Some(account_id) = account_id {
+            anyhow::ensure!(
+                account_id.eq_ignore_ascii_case(&expected_account_id),
+                "account ID {account_id} doesn't correspond to the signer private key \
+                 (expected {expected_account_id})"
+            );
+        }
+        Ok(Self {
+            private_key,
+            account_id: expected_account_id,
+        })
+    }
+
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    /// Signs the challenge sent by the disperser. The signature is a 65-byte recoverable ECDSA signature
+    /// (`r || s || v`, with `v` being 0 or 1) of the Keccak-256 digest of the big-endian challenge.
+    pub fn sign_challenge(&self, challenge: u32) -> Vec<u8> {
+        let digest = keccak256(&challenge.to_be_bytes());
+        let message = Message::from_slice(&digest).expect("digest has 32 bytes");
+        let signature = SECP256K1.sign_ecdsa_recoverable(&message, &self.private_key);
+        let (recovery_id, compact_signature) = signature.serialize_compact();
+        let mut signature = compact_signature.to_vec();
+        signature.push(recovery_id.to_i32() as u8);
+        signature
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+
+    use super::*;
+
+    const PRIVATE_KEY: &str = "0x27593fea79697e947890ecbecce7901b0008345e5d7259710d0dd5e500d040be";
+
+    #[test]
+    fn creating_signer() {
+        let signer = AccountSigner::new(PRIVATE_KEY, None).unwrap();
+        let account_id = signer.account_id().to_owned();
+        assert!(account_id.starts_with("0x04"), "{account_id}");
+        assert_eq!(account_id.len(), 2 + 65 * 2);
+
+        AccountSigner::new(
+            PRIVATE_KEY,
+            Some(&account_id.to_uppercase().replace("0X", "0x")),
+        )
+        .unwrap();
+        let err = AccountSigner::new(PRIVATE_KEY, Some("0x04")).unwrap_err();
+        assert!(err.to_string().contains("doesn't correspond"), "{err}");
+        AccountSigner::new("0x00", None).unwrap_err();
+    }
+
+    #[test]
+    fn signing_challenge() {
+        let signer = AccountSigner::new(PRIVATE_KEY, None).unwrap();
+        let signature = signer.sign_challenge(12345);
+        assert_eq!(signature.len(), 65);
+
+        let recovery_id = RecoveryId::from_i32(signature[64].into()).unwrap();
+        let signature = RecoverableSignature::from_compact(&signature[..64], recovery_id).unwrap();
+        let digest = keccak256(&12345_u32.to_be_bytes());
+        let public_key = SECP256K1
+            .recover_ecdsa(&Message::from_slice(&digest).unwrap(), &signature)
+            .unwrap();
+        assert_eq!(
+            format!("0x{}", hex::encode(public_key.serialize_uncompressed())),
+            signer.account_id()
+        );
+    }
+}
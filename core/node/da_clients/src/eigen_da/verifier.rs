@@ -0,0 +1,339 @@
+//! Verification of blob commitments and blob inclusion proofs returned by the EigenDA disperser.
+
+use std::{fmt, fs::File, io::Read, path::Path};
+
+use anyhow::Context as _;
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective};
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField};
+use zksync_types::{
+    ethabi::{self, Token},
+    web3::keccak256,
+    U256,
+};
+
+use super::disperser::{BlobHeader, G1Commitment};
+
+/// Size of a compressed G1 point in the SRS file.
+const COMPRESSED_G1_POINT_SIZE: usize = 32;
+/// Flags of compressed G1 points (as serialized by `gnark`) occupy the 2 most significant bits of the first byte.
+const COMPRESSION_FLAGS_MASK: u8 = 0b11 << 6;
+/// Flag of the compressed point with the lexicographically smallest of the 2 possible y coordinates.
+const COMPRESSED_SMALLEST: u8 = 0b10 << 6;
+/// Flag of the compressed point with the lexicographically largest of the 2 possible y coordinates.
+const COMPRESSED_LARGEST: u8 = 0b11 << 6;
+const COMPRESSED_INFINITY: u8 = 0b01 << 6;
+
+/// G1 points of the EigenDA structured reference string (SRS), i.e. `[τ^i] G1` for `i = 0, 1, ...`.
+/// Used to compute KZG commitments to blobs.
+pub(super) struct G1Srs {
+    points: Vec<G1Affine>,
+}
+
+impl fmt::Debug for G1Srs {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("G1Srs")
+            .field("point_count", &self.points.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl G1Srs {
+    /// Loads the first `point_count` points from the SRS file.
+    pub fn load(path: &Path, point_count: usize) -> anyhow::Result<Self> {
+        let file = File::open(path).with_context(|| format!("failed opening {path:?}"))?;
+        let mut bytes = Vec::with_capacity(point_count * COMPRESSED_G1_POINT_SIZE);
+        file.take((point_count * COMPRESSED_G1_POINT_SIZE) as u64)
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("failed reading {path:?}"))?;
+        anyhow::ensure!(
+            bytes.len() == point_count * COMPRESSED_G1_POINT_SIZE,
+            "SRS file {path:?} contains {} points, while at least {point_count} points are required",
+            bytes.len() / COMPRESSED_G1_POINT_SIZE
+        );
+
+        let points = bytes
+            .chunks(COMPRESSED_G1_POINT_SIZE)
+            .enumerate()
+            .map(|(i, point)| {
+                decompress_g1_point(point).with_context(|| format!("invalid SRS point #{i}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { points })
+    }
+
+    /// Computes the KZG commitment to the polynomial with the specified encoded data as coefficients. Each coefficient
+    /// is a big-endian 32-byte chunk of data; the last chunk is right-padded with zeros.
+    fn commit(&self, encoded_data: &[u8]) -> anyhow::Result<G1Affine> {
+        let coefficients: Vec<_> = encoded_data
+            .chunks(32)
+            .map(|chunk| {
+                let mut element = [0_u8; 32];
+                element[..chunk.len()].copy_from_slice(chunk);
+                Fr::from_be_bytes_mod_order(&element)
+            })
+            .collect();
+        anyhow::ensure!(
+            coefficients.len() <= self.points.len(),
+            "blob has {} field elements, while the SRS only has {} points",
+            coefficients.len(),
+            self.points.len()
+        );
+        let commitment = G1Projective::msm(&self.points[..coefficients.len()], &coefficients)
+            .expect("number of points and coefficients match");
+        Ok(commitment.into_affine())
+    }
+
+    /// Checks that `commitment` is the KZG commitment to the specified encoded data.
+    pub fn verify_commitment(
+        &self,
+        commitment: &G1Commitment,
+        encoded_data: &[u8],
+    ) -> anyhow::Result<()> {
+        let expected_commitment = self.commit(encoded_data)?;
+        let expected_x = expected_commitment.x.into_bigint().to_bytes_be();
+        let expected_y = expected_commitment.y.into_bigint().to_bytes_be();
+        anyhow::ensure!(
+            commitment.x == expected_x && commitment.y == expected_y,
+            "blob commitment (0x{}, 0x{}) differs from the commitment to dispersed data (0x{}, 0x{})",
+            hex::encode(&commitment.x),
+            hex::encode(&commitment.y),
+            hex::encode(expected_x),
+            hex::encode(expected_y)
+        );
+        Ok(())
+    }
+}
+
+fn decompress_g1_point(bytes: &[u8]) -> anyhow::Result<G1Affine> {
+    let flags = bytes[0] & COMPRESSION_FLAGS_MASK;
+    if flags == COMPRESSED_INFINITY {
+        return Ok(G1Affine::identity());
+    }
+
+    let mut x_bytes = bytes.to_vec();
+    x_bytes[0] &= !COMPRESSION_FLAGS_MASK;
+    let x = Fq::from_be_bytes_mod_order(&x_bytes);
+    anyhow::ensure!(
+        x.into_bigint().to_bytes_be() == x_bytes,
+        "x coordinate is not reduced"
+    );
+    let (smallest_y, largest_y) =
+        G1Affine::get_ys_from_x_unchecked(x).context("point is not on the curve")?;
+    let y = match flags {
+        COMPRESSED_SMALLEST => smallest_y,
+        COMPRESSED_LARGEST => largest_y,
+        _ => anyhow::bail!("invalid compression flags: {flags:#b}"),
+    };
+    // BN254 G1 has cofactor 1, so any point on the curve belongs to the prime-order subgroup.
+    Ok(G1Affine::new_unchecked(x, y))
+}
+
+/// Computes the hash of the blob header as done by the EigenDA verifier contract on L1
+/// (i.e., `keccak256(abi.encode(blobHeader))`).
+pub(super) fn hash_blob_header(header: &BlobHeader, commitment: &G1Commitment) -> [u8; 32] {
+    let quorum_params = header
+        .blob_quorum_params
+        .iter()
+        .map(|params| {
+            Token::Tuple(vec![
+                Token::Uint(params.quorum_number.into()),
+                Token::Uint(params.adversary_threshold_percentage.into()),
+                Token::Uint(params.confirmation_threshold_percentage.into()),
+                Token::Uint(params.chunk_length.into()),
+            ])
+        })
+        .collect();
+    let header = Token::Tuple(vec![
+        Token::Tuple(vec![
+            Token::Uint(U256::from_big_endian(&commitment.x)),
+            Token::Uint(U256::from_big_endian(&commitment.y)),
+        ]),
+        Token::Uint(header.data_length.into()),
+        Token::Array(quorum_params),
+    ]);
+    keccak256(&ethabi::encode(&[header]))
+}
+
+/// Checks the Merkle proof of inclusion of the blob header with the specified hash into the batch with the specified
+/// root, as done by `Merkle.verifyInclusionKeccak()` in the EigenDA contracts.
+pub(super) fn verify_inclusion_proof(
+    blob_header_hash: [u8; 32],
+    blob_index: u32,
+    proof: &[u8],
+    batch_root: &[u8],
+) -> anyhow::Result<()> {
+    anyhow::ensure!(
+        proof.len() % 32 == 0,
+        "inclusion proof length {} is not a multiple of 32",
+        proof.len()
+    );
+    let mut index = blob_index;
+    let mut node = keccak256(&blob_header_hash);
+    for sibling in proof.chunks(32) {
+        let mut pair = [0_u8; 64];
+        if index % 2 == 0 {
+            pair[..32].copy_from_slice(&node);
+            pair[32..].copy_from_slice(sibling);
+        } else {
+            pair[..32].copy_from_slice(sibling);
+            pair[32..].copy_from_slice(&node);
+        }
+        node = keccak256(&pair);
+        index /= 2;
+    }
+    anyhow::ensure!(
+        node.as_slice() == batch_root,
+        "inclusion proof for blob #{blob_index} doesn't lead to batch root 0x{}",
+        hex::encode(batch_root)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use std::io::Write as _;
+
+    use super::*;
+    use crate::eigen_da::disperser::BlobQuorumParam;
+
+    fn compress_g1_point(point: &G1Affine) -> [u8; COMPRESSED_G1_POINT_SIZE] {
+        let mut bytes = [0_u8; COMPRESSED_G1_POINT_SIZE];
+        if point.is_zero() {
+            bytes[0] = COMPRESSED_INFINITY;
+            return bytes;
+        }
+        bytes.copy_from_slice(&point.x.into_bigint().to_bytes_be());
+        bytes[0] |= if point.y > -point.y {
+            COMPRESSED_LARGEST
+        } else {
+            COMPRESSED_SMALLEST
+        };
+        bytes
+    }
+
+    fn tau() -> Fr {
+        Fr::from(123_456_789_u64)
+    }
+
+    /// Creates a file with a test SRS with the specified number of points.
+    pub(in crate::eigen_da) fn create_srs_file(point_count: usize) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let mut power = Fr::from(1_u64);
+        for _ in 0..point_count {
+            let point = (G1Affine::generator() * power).into_affine();
+            file.write_all(&compress_g1_point(&point)).unwrap();
+            power *= tau();
+        }
+        file.flush().unwrap();
+        file
+    }
+
+    /// Computes the commitment to the specified data using the test SRS.
+    pub(in crate::eigen_da) fn commit(encoded_data: &[u8]) -> G1Commitment {
+        let srs_file = create_srs_file(encoded_data.len().div_ceil(32));
+        let srs = G1Srs::load(srs_file.path(), encoded_data.len().div_ceil(32)).unwrap();
+        let commitment = srs.commit(encoded_data).unwrap();
+        G1Commitment {
+            x: commitment.x.into_bigint().to_bytes_be(),
+            y: commitment.y.into_bigint().to_bytes_be(),
+        }
+    }
+
+    #[test]
+    fn decompressing_points() {
+        let point = (G1Affine::generator() * Fr::from(42_u64)).into_affine();
+        let neg_point = -point;
+        assert_eq!(
+            decompress_g1_point(&compress_g1_point(&point)).unwrap(),
+            point
+        );
+        assert_eq!(
+            decompress_g1_point(&compress_g1_point(&neg_point)).unwrap(),
+            neg_point
+        );
+        let identity = G1Affine::identity();
+        assert_eq!(
+            decompress_g1_point(&compress_g1_point(&identity)).unwrap(),
+            identity
+        );
+
+        let mut invalid_point = compress_g1_point(&point);
+        invalid_point[0] &= !COMPRESSION_FLAGS_MASK;
+        decompress_g1_point(&invalid_point).unwrap_err();
+    }
+
+    #[test]
+    fn loading_srs() {
+        let srs_file = create_srs_file(4);
+        let srs = G1Srs::load(srs_file.path(), 3).unwrap();
+        assert_eq!(srs.points.len(), 3);
+        assert_eq!(srs.points[0], G1Affine::generator());
+        assert_eq!(
+            srs.points[2],
+            (G1Affine::generator() * tau() * tau()).into_affine()
+        );
+
+        let err = G1Srs::load(srs_file.path(), 5).unwrap_err();
+        assert!(format!("{err:#}").contains("contains 4 points"), "{err:#}");
+    }
+
+    #[test]
+    fn computing_commitment() {
+        let srs_file = create_srs_file(3);
+        let srs = G1Srs::load(srs_file.path(), 3).unwrap();
+        let mut data = [0_u8; 65];
+        data[31] = 1;
+        data[63] = 2;
+        data[64] = 3;
+
+        // The polynomial is `1 + 2x + (3 << 248) x^2`.
+        let last_coefficient = Fr::from_be_bytes_mod_order(&{
+            let mut element = [0_u8; 32];
+            element[0] = 3;
+            element
+        });
+        let value = Fr::from(1_u64) + Fr::from(2_u64) * tau() + last_coefficient * tau() * tau();
+        let expected = (G1Affine::generator() * value).into_affine();
+        assert_eq!(srs.commit(&data).unwrap(), expected);
+
+        let commitment = commit(&data);
+        srs.verify_commitment(&commitment, &data).unwrap();
+        data[0] = 1;
+        srs.verify_commitment(&commitment, &data).unwrap_err();
+        srs.commit(&[1; 97]).unwrap_err();
+    }
+
+    #[test]
+    fn verifying_inclusion_proof() {
+        let commitment = G1Commitment {
+            x: vec![1; 32],
+            y: vec![2; 32],
+        };
+        let header = BlobHeader {
+            commitment: Some(commitment.clone()),
+            data_length: 4,
+            blob_quorum_params: vec![BlobQuorumParam {
+                quorum_number: 0,
+                adversary_threshold_percentage: 33,
+                confirmation_threshold_percentage: 55,
+                chunk_length: 1,
+            }],
+        };
+        let header_hash = hash_blob_header(&header, &commitment);
+        let leaf = keccak256(&header_hash);
+
+        // Batch with the single blob.
+        verify_inclusion_proof(header_hash, 0, &[], &leaf).unwrap();
+        // Batch with 3 blobs, the blob being the last one.
+        let left_node = keccak256(&[[1_u8; 32], [2; 32]].concat());
+        let right_node = keccak256(&[leaf, [0; 32]].concat());
+        let root = keccak256(&[left_node, right_node].concat());
+        let proof = [[0_u8; 32], left_node].concat();
+        verify_inclusion_proof(header_hash, 2, &proof, &root).unwrap();
+        verify_inclusion_proof(header_hash, 1, &proof, &root).unwrap_err();
+        verify_inclusion_proof(header_hash, 2, &proof[..40], &root).unwrap_err();
+        verify_inclusion_proof([0; 32], 2, &proof, &root).unwrap_err();
+    }
+}
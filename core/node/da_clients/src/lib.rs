@@ -0,0 +1,19 @@
+//! Clients for specific data availability (DA) layers.
+
+use zksync_config::configs::da_client::DAClient;
+
+pub mod avail;
+pub mod celestia;
+pub mod eigen_da;
+mod utils;
+
+/// Returns the maximum pubdata size that fits into a single blob of the configured DA layer. Matches
+/// [`blob_size_limit()`](zksync_da_client::DataAvailabilityClient::blob_size_limit) of the corresponding client,
+/// but doesn't require creating one, so it can be used by the state keeper to seal L1 batches in time.
+pub fn blob_size_limit(config: &DAClient) -> Option<usize> {
+    match config {
+        DAClient::Avail(_) => Some(avail::BLOB_SIZE_LIMIT),
+        DAClient::Celestia(_) => Some(celestia::BLOB_SIZE_LIMIT),
+        DAClient::EigenDA(config) => Some(eigen_da::blob_size_limit(config)),
+    }
+}
@@ -0,0 +1,23 @@
+use zksync_da_client::DAError;
+
+/// Converts an error from an HTTP request to the DA layer into a [`DAError`]. Connection errors, timeouts
+/// and server-side errors are considered retriable; other errors (e.g., the request being rejected) are not.
+pub(crate) fn http_error(err: reqwest::Error, context: &str) -> DAError {
+    let is_retriable = err.is_timeout()
+        || err.is_connect()
+        || err
+            .status()
+            .map_or(false, |status| status.is_server_error());
+    DAError {
+        // Errors may contain the URL, which may contain sensitive info (e.g., an API key).
+        error: anyhow::Error::new(err.without_url()).context(context.to_owned()),
+        is_retriable,
+    }
+}
+
+pub(crate) fn non_retriable_error(error: anyhow::Error) -> DAError {
+    DAError {
+        error,
+        is_retriable: false,
+    }
+}
@@ -0,0 +1,254 @@
+//! Dispatcher of L1 batch pubdata to a data availability (DA) layer.
+
+use std::{future::Future, time::Duration};
+
+use anyhow::Context as _;
+use chrono::Utc;
+use tokio::sync::watch;
+use zksync_config::configs::da_dispatcher::DADispatcherConfig;
+use zksync_da_client::{DAError, DataAvailabilityClient, InclusionStatus};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L1BatchNumber;
+
+use self::metrics::{Operation, METRICS};
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Initial delay before retrying a request to the DA layer; doubled after each failed attempt.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Sends pubdata of sealed L1 batches to a DA layer and fetches inclusion data for the dispatched blobs.
+#[derive(Debug)]
+pub struct DataAvailabilityDispatcher {
+    client: Box<dyn DataAvailabilityClient>,
+    pool: ConnectionPool<Core>,
+    config: DADispatcherConfig,
+}
+
+impl DataAvailabilityDispatcher {
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        config: DADispatcherConfig,
+        client: Box<dyn DataAvailabilityClient>,
+    ) -> Self {
+        Self {
+            client,
+            pool,
+            config,
+        }
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting DA dispatcher for {} with configuration {:?}",
+            self.client.client_type(),
+            self.config
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            // Errors are logged and the operations are retried on the next iteration, so that a DA layer outage
+            // or a transient DB error doesn't bring down the node.
+            if let Err(err) = self.dispatch().await {
+                METRICS.errors[&Operation::Dispatch].inc();
+                tracing::error!("Failed dispatching blobs: {err:#}");
+            }
+            if let Err(err) = self.poll_for_inclusion().await {
+                METRICS.errors[&Operation::PollForInclusion].inc();
+                tracing::error!("Failed polling for inclusion data: {err:#}");
+            }
+
+            if tokio::time::timeout(self.config.polling_interval(), stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, DA dispatcher is shutting down");
+        Ok(())
+    }
+
+    /// Dispatches pubdata for L1 batches that weren't dispatched yet.
+    async fn dispatch(&self) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("da_dispatcher").await?;
+        let batches = storage
+            .data_availability_dal()
+            .get_ready_for_da_dispatch_l1_batches(self.config.max_rows_to_dispatch as usize)
+            .await?;
+        drop(storage);
+
+        for batch in batches {
+            let batch_number = batch.l1_batch_number;
+            let pubdata_size = batch.pubdata.len();
+            if let Some(size_limit) = self.client.blob_size_limit() {
+                if pubdata_size > size_limit {
+                    // Shouldn't happen if the state keeper seals batches by the blob size; the batch will be
+                    // retried on each iteration, so that it's reported until resolved manually. We don't stop
+                    // dispatching the following batches, so that they don't pile up in the meantime.
+                    METRICS.oversized_blobs.inc();
+                    tracing::error!(
+                        "Pubdata for L1 batch #{batch_number} ({pubdata_size} bytes) exceeds the blob size limit \
+                         of {} ({size_limit} bytes); skipping",
+                        self.client.client_type()
+                    );
+                    continue;
+                }
+            }
+
+            let latency = METRICS.blob_dispatch_latency.start();
+            let response = retry(self.config.max_retries, batch_number, || {
+                self.client
+                    .dispatch_blob(batch_number.0, batch.pubdata.clone())
+            })
+            .await
+            .with_context(|| format!("failed dispatching pubdata for L1 batch #{batch_number}"))?;
+            let latency = latency.observe();
+
+            let mut storage = self.pool.connection_tagged("da_dispatcher").await?;
+            storage
+                .data_availability_dal()
+                .insert_l1_batch_da(batch_number, self.client.client_type(), &response.blob_id)
+                .await?;
+            drop(storage);
+
+            METRICS.blob_size.observe(pubdata_size);
+            METRICS.last_dispatched_batch.set(batch_number.0.into());
+            tracing::info!(
+                "Dispatched pubdata for L1 batch #{batch_number} ({pubdata_size} bytes) in {latency:?}, blob ID: {}",
+                response.blob_id
+            );
+        }
+        Ok(())
+    }
+
+    /// Fetches and verifies inclusion data for dispatched blobs in the order of L1 batches, until a blob without
+    /// available inclusion data is encountered.
+    async fn poll_for_inclusion(&self) -> anyhow::Result<()> {
+        for _ in 0..self.config.max_rows_to_dispatch {
+            let mut storage = self.pool.connection_tagged("da_dispatcher").await?;
+            let Some(blob) = storage
+                .data_availability_dal()
+                .get_first_da_blob_awaiting_inclusion()
+                .await?
+            else {
+                return Ok(());
+            };
+            drop(storage);
+
+            let batch_number = blob.l1_batch_number;
+            let inclusion_status = retry(self.config.max_retries, batch_number, || {
+                self.client.get_inclusion_data(&blob.blob_id)
+            })
+            .await
+            .with_context(|| {
+                format!("failed getting inclusion data for L1 batch #{batch_number}")
+            })?;
+            let inclusion_data = match inclusion_status {
+                InclusionStatus::Pending => return Ok(()),
+                InclusionStatus::Included(inclusion_data) => inclusion_data,
+                InclusionStatus::Failed { reason } => {
+                    tracing::warn!(
+                        "Blob {} with pubdata for L1 batch #{batch_number} won't be included into the DA layer ({reason}); \
+                         the pubdata will be dispatched again",
+                        blob.blob_id
+                    );
+                    self.reset_blob(batch_number).await?;
+                    continue;
+                }
+            };
+
+            let mut storage = self.pool.connection_tagged("da_dispatcher").await?;
+            let pubdata = storage
+                .blocks_dal()
+                .get_l1_batch_header(batch_number)
+                .await?
+                .with_context(|| format!("L1 batch #{batch_number} is not in storage"))?
+                .pubdata_input
+                .with_context(|| format!("L1 batch #{batch_number} has no pubdata input"))?;
+            drop(storage);
+
+            // Inclusion data is used in the commit calldata, so it must not be trusted blindly.
+            let verification_result = retry(self.config.max_retries, batch_number, || {
+                self.client
+                    .verify_inclusion_data(&blob.blob_id, &pubdata, &inclusion_data)
+            })
+            .await;
+            match verification_result {
+                Ok(()) => {}
+                Err(err) if err.is_retriable() => {
+                    return Err(err).with_context(|| {
+                        format!("failed verifying inclusion data for L1 batch #{batch_number}")
+                    });
+                }
+                Err(err) => {
+                    METRICS.failed_verifications.inc();
+                    tracing::warn!(
+                        "Inclusion data for blob {} with pubdata for L1 batch #{batch_number} failed verification \
+                         ({err}); the pubdata will be dispatched again",
+                        blob.blob_id
+                    );
+                    self.reset_blob(batch_number).await?;
+                    continue;
+                }
+            }
+
+            let mut storage = self.pool.connection_tagged("da_dispatcher").await?;
+            storage
+                .data_availability_dal()
+                .save_l1_batch_inclusion_data(batch_number, &inclusion_data.data)
+                .await?;
+            drop(storage);
+
+            let inclusion_latency = (Utc::now() - blob.sent_at).to_std().unwrap_or_default();
+            METRICS.inclusion_latency.observe(inclusion_latency);
+            METRICS.last_included_batch.set(batch_number.0.into());
+            tracing::info!(
+                "Received inclusion data for L1 batch #{batch_number} {inclusion_latency:?} after dispatch"
+            );
+        }
+        Ok(())
+    }
+
+    /// Removes the dispatched blob for the specified L1 batch, so that its pubdata is dispatched again.
+    async fn reset_blob(&self, batch_number: L1BatchNumber) -> anyhow::Result<()> {
+        let mut storage = self.pool.connection_tagged("da_dispatcher").await?;
+        storage
+            .data_availability_dal()
+            .remove_l1_batch_da(batch_number)
+            .await?;
+        METRICS.redispatched_blobs.inc();
+        Ok(())
+    }
+}
+
+/// Retries a request to the DA layer with exponential backoff while it fails with a retriable error,
+/// making up to `max_retries` attempts in total.
+async fn retry<T, Fut>(
+    max_retries: u16,
+    batch_number: L1BatchNumber,
+    mut f: impl FnMut() -> Fut,
+) -> Result<T, DAError>
+where
+    Fut: Future<Output = Result<T, DAError>>,
+{
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) if err.is_retriable() && attempt < max_retries => {
+                tracing::warn!(
+                    "Request to DA layer for L1 batch #{batch_number} failed (attempt {attempt}/{max_retries}), \
+                     retrying in {backoff:?}: {err}"
+                );
+                METRICS.retries.inc();
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use vise::{
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics, Unit,
+};
+
+const BLOB_SIZE_BUCKETS: Buckets = Buckets::exponential(1_024.0..=16_777_216.0, 2.0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "operation", rename_all = "snake_case")]
+pub(super) enum Operation {
+    Dispatch,
+    PollForInclusion,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_da_dispatcher")]
+pub(super) struct DataAvailabilityDispatcherMetrics {
+    /// Latency of dispatching a blob to the DA layer, including retries.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub blob_dispatch_latency: Histogram<Duration>,
+    /// Time between dispatching a blob and receiving its inclusion data.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub inclusion_latency: Histogram<Duration>,
+    /// Size of dispatched blobs.
+    #[metrics(buckets = BLOB_SIZE_BUCKETS, unit = Unit::Bytes)]
+    pub blob_size: Histogram<usize>,
+    /// Number of the last L1 batch that was dispatched to the DA layer.
+    pub last_dispatched_batch: Gauge<u64>,
+    /// Number of the last L1 batch for which inclusion data was received.
+    pub last_included_batch: Gauge<u64>,
+    /// Number of inclusion data responses that failed verification.
+    pub failed_verifications: Counter,
+    /// Number of retried requests to the DA layer.
+    pub retries: Counter,
+    /// Number of failed dispatcher iterations, grouped by operation.
+    pub errors: Family<Operation, Counter>,
+    /// Number of blobs dropped by the DA layer and dispatched again.
+    pub redispatched_blobs: Counter,
+    /// Number of L1 batches skipped because their pubdata exceeds the blob size limit.
+    pub oversized_blobs: Counter,
+}
+
+#[vise::register]
+pub(super) static METRICS: vise::Global<DataAvailabilityDispatcherMetrics> = vise::Global::new();
@@ -0,0 +1,332 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use async_trait::async_trait;
+use zksync_contracts::BaseSystemContractsHashes;
+use zksync_da_client::{DispatchResponse, InclusionData, InclusionStatus};
+use zksync_types::{block::L1BatchHeader, ProtocolVersion, ProtocolVersionId};
+
+use super::*;
+
+#[derive(Debug, Default)]
+struct MockClientState {
+    dispatched: Vec<(u32, Vec<u8>)>,
+    included: Vec<String>,
+    /// Blobs for which the client returns inclusion data that doesn't pass verification.
+    invalid: Vec<String>,
+    /// Blobs dropped by the DA layer.
+    failed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockClient(Arc<Mutex<MockClientState>>);
+
+impl MockClient {
+    fn include(&self, blob_id: &str) {
+        self.0.lock().unwrap().included.push(blob_id.to_owned());
+    }
+
+    fn include_with_invalid_proof(&self, blob_id: &str) {
+        let mut state = self.0.lock().unwrap();
+        state.included.push(blob_id.to_owned());
+        state.invalid.push(blob_id.to_owned());
+    }
+
+    fn fail(&self, blob_id: &str) {
+        self.0.lock().unwrap().failed.push(blob_id.to_owned());
+    }
+}
+
+#[async_trait]
+impl DataAvailabilityClient for MockClient {
+    fn client_type(&self) -> &'static str {
+        "Mock"
+    }
+
+    async fn dispatch_blob(
+        &self,
+        batch_number: u32,
+        data: Vec<u8>,
+    ) -> Result<DispatchResponse, DAError> {
+        self.0.lock().unwrap().dispatched.push((batch_number, data));
+        Ok(format!("blob{batch_number}").into())
+    }
+
+    async fn get_inclusion_data(&self, blob_id: &str) -> Result<InclusionStatus, DAError> {
+        let mut state = self.0.lock().unwrap();
+        if let Some(pos) = state.failed.iter().position(|id| id == blob_id) {
+            state.failed.remove(pos);
+            return Ok(InclusionStatus::Failed {
+                reason: "dropped".to_owned(),
+            });
+        }
+        let is_included = state.included.iter().any(|id| id == blob_id);
+        let is_invalid = state.invalid.iter().any(|id| id == blob_id);
+        if !is_included {
+            return Ok(InclusionStatus::Pending);
+        }
+        Ok(InclusionStatus::Included(InclusionData {
+            data: if is_invalid {
+                b"garbage".to_vec()
+            } else {
+                format!("proof for {blob_id}").into_bytes()
+            },
+        }))
+    }
+
+    async fn verify_inclusion_data(
+        &self,
+        blob_id: &str,
+        data: &[u8],
+        inclusion_data: &InclusionData,
+    ) -> Result<(), DAError> {
+        let state = self.0.lock().unwrap();
+        let is_dispatched = state
+            .dispatched
+            .iter()
+            .any(|(batch_number, dispatched_data)| {
+                format!("blob{batch_number}") == blob_id && dispatched_data == data
+            });
+        if is_dispatched && inclusion_data.data == format!("proof for {blob_id}").into_bytes() {
+            Ok(())
+        } else {
+            Err(DAError {
+                error: anyhow::anyhow!("invalid inclusion data for {blob_id}"),
+                is_retriable: false,
+            })
+        }
+    }
+
+    fn clone_boxed(&self) -> Box<dyn DataAvailabilityClient> {
+        Box::new(self.clone())
+    }
+
+    fn blob_size_limit(&self) -> Option<usize> {
+        Some(4)
+    }
+}
+
+fn test_config() -> DADispatcherConfig {
+    DADispatcherConfig {
+        polling_interval_ms: 10,
+        max_rows_to_dispatch: 10,
+        max_retries: 3,
+    }
+}
+
+async fn insert_l1_batches(pool: &ConnectionPool<Core>, pubdata: &[&[u8]]) {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(&ProtocolVersion::default())
+        .await
+        .unwrap();
+    for (i, pubdata) in pubdata.iter().enumerate() {
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(i as u32 + 1),
+            100,
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.pubdata_input = Some(pubdata.to_vec());
+        storage
+            .blocks_dal()
+            .insert_mock_l1_batch(&header)
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn dispatching_blobs_and_polling_for_inclusion() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    insert_l1_batches(&pool, &[b"1", b"22"]).await;
+    let client = MockClient::default();
+    let dispatcher =
+        DataAvailabilityDispatcher::new(pool.clone(), test_config(), Box::new(client.clone()));
+
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        client.0.lock().unwrap().dispatched,
+        [(1, b"1".to_vec()), (2, b"22".to_vec())]
+    );
+    // Batches must not be dispatched repeatedly.
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(client.0.lock().unwrap().dispatched.len(), 2);
+
+    dispatcher.poll_for_inclusion().await.unwrap();
+    let mut storage = pool.connection().await.unwrap();
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap()
+        .expect("no blobs awaiting inclusion");
+    assert_eq!(blob.l1_batch_number, L1BatchNumber(1));
+    assert_eq!(blob.blob_id, "blob1");
+
+    client.include("blob1");
+    client.include("blob2");
+    dispatcher.poll_for_inclusion().await.unwrap();
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap();
+    assert_eq!(blob, None);
+    let details = storage
+        .data_availability_dal()
+        .get_l1_batch_da_details(L1BatchNumber(2))
+        .await
+        .unwrap()
+        .expect("no DA details");
+    assert_eq!(details.client_type, "Mock");
+    assert_eq!(details.blob_id, "blob2");
+    assert!(details.inclusion_data_available);
+}
+
+#[tokio::test]
+async fn failed_blobs_are_dispatched_again() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    insert_l1_batches(&pool, &[b"1", b"22"]).await;
+    let client = MockClient::default();
+    let dispatcher =
+        DataAvailabilityDispatcher::new(pool.clone(), test_config(), Box::new(client.clone()));
+    dispatcher.dispatch().await.unwrap();
+
+    client.fail("blob1");
+    client.include("blob2");
+    dispatcher.poll_for_inclusion().await.unwrap();
+    let mut storage = pool.connection().await.unwrap();
+    let details = storage
+        .data_availability_dal()
+        .get_l1_batch_da_details(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(details, None);
+    let inclusion_data = storage
+        .data_availability_dal()
+        .get_l1_batch_inclusion_data(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert!(inclusion_data.is_some());
+
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        client.0.lock().unwrap().dispatched,
+        [(1, b"1".to_vec()), (2, b"22".to_vec()), (1, b"1".to_vec())]
+    );
+    client.include("blob1");
+    dispatcher.poll_for_inclusion().await.unwrap();
+    let inclusion_data = storage
+        .data_availability_dal()
+        .get_l1_batch_inclusion_data(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        inclusion_data.as_deref(),
+        Some(b"proof for blob1".as_slice())
+    );
+}
+
+#[tokio::test]
+async fn inclusion_data_failing_verification_is_not_saved() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    insert_l1_batches(&pool, &[b"1", b"22"]).await;
+    let client = MockClient::default();
+    let dispatcher =
+        DataAvailabilityDispatcher::new(pool.clone(), test_config(), Box::new(client.clone()));
+    dispatcher.dispatch().await.unwrap();
+
+    client.include("blob1");
+    client.include_with_invalid_proof("blob2");
+    dispatcher.poll_for_inclusion().await.unwrap();
+
+    let mut storage = pool.connection().await.unwrap();
+    let inclusion_data = storage
+        .data_availability_dal()
+        .get_l1_batch_inclusion_data(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        inclusion_data.as_deref(),
+        Some(b"proof for blob1".as_slice())
+    );
+    let blob = storage
+        .data_availability_dal()
+        .get_first_da_blob_awaiting_inclusion()
+        .await
+        .unwrap();
+    assert!(blob.is_none(), "{blob:?}");
+    let inclusion_data = storage
+        .data_availability_dal()
+        .get_l1_batch_inclusion_data(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert_eq!(inclusion_data, None);
+
+    // The blob with invalid inclusion data must be dispatched again.
+    dispatcher.dispatch().await.unwrap();
+    assert_eq!(
+        client.0.lock().unwrap().dispatched,
+        [(1, b"1".to_vec()), (2, b"22".to_vec()), (2, b"22".to_vec())]
+    );
+}
+
+#[tokio::test]
+async fn pubdata_exceeding_blob_size_limit_is_skipped() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    insert_l1_batches(&pool, &[b"too large", b"ok"]).await;
+    let client = MockClient::default();
+    let dispatcher = DataAvailabilityDispatcher::new(pool, test_config(), Box::new(client.clone()));
+
+    dispatcher.dispatch().await.unwrap();
+    let dispatched = client.0.lock().unwrap().dispatched.clone();
+    assert_eq!(dispatched, [(2, b"ok".to_vec())]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn retriable_errors_are_retried() {
+    let attempts = AtomicUsize::new(0);
+    let output = retry(3, L1BatchNumber(1), || async {
+        if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err(DAError {
+                error: anyhow::anyhow!("unavailable"),
+                is_retriable: true,
+            })
+        } else {
+            Ok(42)
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(output, 42);
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    attempts.store(0, Ordering::SeqCst);
+    let err = retry(3, L1BatchNumber(1), || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>(DAError {
+            error: anyhow::anyhow!("unavailable"),
+            is_retriable: true,
+        })
+    })
+    .await
+    .unwrap_err();
+    assert!(err.is_retriable());
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+    attempts.store(0, Ordering::SeqCst);
+    retry(3, L1BatchNumber(1), || async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err::<(), _>(DAError {
+            error: anyhow::anyhow!("rejected"),
+            is_retriable: false,
+        })
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
@@ -5,7 +5,10 @@ use std::{sync::Arc, time::Duration};
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
-use zksync_dal::{pruning_dal::PruningInfo, Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    pruning_dal::{PruningInfo, StorageMode},
+    Connection, ConnectionPool, Core, CoreDal,
+};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use zksync_types::{L1BatchNumber, L2BlockNumber};
 
@@ -80,6 +83,20 @@ pub struct DbPruner {
 
 impl DbPruner {
     pub fn new(config: DbPrunerConfig, connection_pool: ConnectionPool<Core>) -> Self {
+        Self::with_default_conditions(config, connection_pool, true)
+    }
+
+    /// Creates a pruner for the main node. Unlike [`Self::new()`], L1 batches are not required to be processed
+    /// by the consistency checker, since it doesn't run on the main node.
+    pub fn for_main_node(config: DbPrunerConfig, connection_pool: ConnectionPool<Core>) -> Self {
+        Self::with_default_conditions(config, connection_pool, false)
+    }
+
+    fn with_default_conditions(
+        config: DbPrunerConfig,
+        connection_pool: ConnectionPool<Core>,
+        check_consistency: bool,
+    ) -> Self {
         let mut conditions: Vec<Arc<dyn PruneCondition>> = vec![
             Arc::new(L1BatchExistsCondition {
                 pool: connection_pool.clone(),
@@ -90,10 +107,12 @@ impl DbPruner {
             Arc::new(NextL1BatchWasExecutedCondition {
                 pool: connection_pool.clone(),
             }),
-            Arc::new(ConsistencyCheckerProcessedBatch {
-                pool: connection_pool.clone(),
-            }),
         ];
+        if check_consistency {
+            conditions.push(Arc::new(ConsistencyCheckerProcessedBatch {
+                pool: connection_pool.clone(),
+            }));
+        }
         if config.minimum_l1_batch_age > Duration::ZERO {
             // Do not add a condition if it's trivial in order to not clutter logs.
             conditions.push(Arc::new(L1BatchOlderThanPruneCondition {
@@ -299,6 +318,16 @@ impl DbPruner {
                 .collect::<Vec<_>>()
         );
 
+        let data_retention = (self.config.minimum_l1_batch_age > Duration::ZERO)
+            .then_some(self.config.minimum_l1_batch_age);
+        let mut storage = self.connection_pool.connection_tagged("db_pruner").await?;
+        storage
+            .pruning_dal()
+            .set_storage_mode(StorageMode::Pruned { data_retention })
+            .await
+            .context("failed setting storage mode")?;
+        drop(storage);
+
         while !*stop_receiver.borrow_and_update() {
             if let Err(err) = self.update_l1_batches_metric().await {
                 tracing::warn!("Error updating DB pruning metrics: {err:?}");
@@ -117,6 +117,7 @@ async fn insert_l2_blocks(
                 protocol_version: Some(Default::default()),
                 virtual_blocks: 0,
                 gas_limit: 0,
+                logs_bloom: Default::default(),
             };
 
             conn.blocks_dal()
@@ -362,6 +363,22 @@ async fn pruner_is_resistant_to_errors() {
 
     stop_sender.send_replace(true);
     pruner_task_handle.await.unwrap().unwrap();
+
+    // The pruner should mark the node as pruned on start.
+    let storage_mode = pool
+        .connection()
+        .await
+        .unwrap()
+        .pruning_dal()
+        .get_storage_mode()
+        .await
+        .unwrap();
+    assert_eq!(
+        storage_mode,
+        StorageMode::Pruned {
+            data_retention: None
+        }
+    );
 }
 
 /// Seals an L1 batch with a single L2 block.
@@ -1,9 +1,12 @@
 use std::sync::Arc;
 
-use zksync_config::configs::eth_sender::{ProofSendingMode, SenderConfig};
+use zksync_config::configs::eth_sender::{
+    L1BatchAggregationCriterion, ProofSendingMode, SenderConfig,
+};
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
+use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
 use zksync_object_store::{ObjectStore, ObjectStoreError};
 use zksync_prover_interface::outputs::L1BatchProofForL1;
 use zksync_types::{
@@ -11,23 +14,25 @@ use zksync_types::{
     commitment::{L1BatchCommitmentMode, L1BatchWithMetadata},
     helpers::unix_timestamp_ms,
     protocol_version::{L1VerifierConfig, ProtocolSemanticVersion},
-    pubdata_da::PubdataDA,
+    pubdata_da::{PubdataDA, PubdataEncoding},
     L1BatchNumber, ProtocolVersionId,
 };
 
 use super::{
     aggregated_operations::AggregatedOperation,
+    pause::EthSenderPauseHandle,
+    pubdata_da_selector::PubdataDASelector,
     publish_criterion::{
-        DataSizeCriterion, GasCriterion, L1BatchPublishCriterion, NumberCriterion,
-        TimestampDeadlineCriterion,
+        CriteriaAggregationPolicy, DataSizeCriterion, GasCriterion, L1BatchAggregationPolicy,
+        NumberCriterion, TimestampDeadlineCriterion,
     },
 };
 
 #[derive(Debug)]
 pub struct Aggregator {
-    commit_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
-    proof_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
-    execute_criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
+    commit_policy: Box<dyn L1BatchAggregationPolicy>,
+    proof_policy: Box<dyn L1BatchAggregationPolicy>,
+    execute_policy: Box<dyn L1BatchAggregationPolicy>,
     config: SenderConfig,
     blob_store: Arc<dyn ObjectStore>,
     /// If we are operating in 4844 mode we need to wait for commit transaction
@@ -37,7 +42,14 @@ pub struct Aggregator {
     /// transactions.
     operate_4844_mode: bool,
     pubdata_da: PubdataDA,
-    commitment_mode: L1BatchCommitmentMode,
+    /// Hysteresis for the dynamic pubdata DA choice; `None` if the choice is static.
+    dynamic_pubdata_da_hysteresis: Option<f64>,
+    /// Set if pubdata DA for commit operations is chosen dynamically.
+    pubdata_da_selector: Option<PubdataDASelector>,
+    pubdata_encoding: PubdataEncoding,
+    /// Whether the chain settles on a gateway, which doesn't support blobs.
+    settles_on_gateway: bool,
+    pause_handle: EthSenderPauseHandle,
 }
 
 impl Aggregator {
@@ -48,68 +60,199 @@ impl Aggregator {
         commitment_mode: L1BatchCommitmentMode,
     ) -> Self {
         let pubdata_da = config.pubdata_sending_mode.into();
+        let pubdata_encoding = PubdataEncoding::new(commitment_mode, config.pubdata_sending_mode)
+            .expect("invalid pubdata configuration");
+        let dynamic_pubdata_da_hysteresis = config.dynamic_pubdata_sending_hysteresis;
+        // Pubdata of non-rollup batches is not published on L1, so there's nothing to select DA for.
+        let dynamic_pubdata_da_hysteresis = if !pubdata_encoding.is_published_on_l1() {
+            if dynamic_pubdata_da_hysteresis.is_some() {
+                tracing::warn!(
+                    "Dynamic pubdata DA is configured, but pubdata is not published on L1 \
+                     ({pubdata_encoding:?} encoding); the configuration is ignored"
+                );
+            }
+            None
+        } else if operate_4844_mode {
+            dynamic_pubdata_da_hysteresis
+        } else {
+            if dynamic_pubdata_da_hysteresis.is_some() {
+                tracing::warn!(
+                    "Dynamic pubdata DA is configured, but there is no blob operator; pubdata will be sent using \
+                     the static {pubdata_da:?} mode"
+                );
+            }
+            None
+        };
+        // If pubdata DA is chosen dynamically, the commit data size is estimated for calldata, which is the larger
+        // of the two encodings.
+        let data_size_pubdata_da = if dynamic_pubdata_da_hysteresis.is_some() {
+            PubdataDA::Calldata
+        } else {
+            pubdata_da
+        };
 
         Self {
-            commit_criteria: vec![
-                Box::from(NumberCriterion {
-                    op: AggregatedActionType::Commit,
+            commit_policy: Box::new(Self::default_commit_policy(
+                &config,
+                data_size_pubdata_da,
+                commitment_mode,
+            )),
+            proof_policy: Box::new(Self::default_proof_policy(&config)),
+            execute_policy: Box::new(Self::default_execute_policy(&config)),
+            pause_handle: EthSenderPauseHandle::new(&config),
+            config,
+            blob_store,
+            operate_4844_mode,
+            pubdata_da,
+            dynamic_pubdata_da_hysteresis,
+            pubdata_da_selector: None,
+            pubdata_encoding,
+            settles_on_gateway: false,
+        }
+    }
+
+    /// Returns a handle allowing to pause and resume creating operations of specific types.
+    pub fn pause_handle(&self) -> EthSenderPauseHandle {
+        self.pause_handle.clone()
+    }
+
+    fn default_commit_policy(
+        config: &SenderConfig,
+        pubdata_da: PubdataDA,
+        commitment_mode: L1BatchCommitmentMode,
+    ) -> CriteriaAggregationPolicy {
+        let op = AggregatedActionType::Commit;
+        let mut policy = CriteriaAggregationPolicy::default();
+        for criterion in config.commit_aggregation_criteria() {
+            policy = match criterion {
+                L1BatchAggregationCriterion::Number => policy.with_criterion(NumberCriterion {
+                    op,
                     limit: config.max_aggregated_blocks_to_commit,
                 }),
-                Box::from(GasCriterion::new(
-                    AggregatedActionType::Commit,
-                    config.max_aggregated_tx_gas,
-                )),
-                Box::from(DataSizeCriterion {
-                    op: AggregatedActionType::Commit,
+                L1BatchAggregationCriterion::Gas => {
+                    policy.with_criterion(GasCriterion::new(op, config.max_aggregated_tx_gas))
+                }
+                L1BatchAggregationCriterion::DataSize => policy.with_criterion(DataSizeCriterion {
+                    op,
                     data_limit: config.max_eth_tx_data_size,
                     pubdata_da,
                     commitment_mode,
                 }),
-                Box::from(TimestampDeadlineCriterion {
-                    op: AggregatedActionType::Commit,
-                    deadline_seconds: config.aggregated_block_commit_deadline,
-                    max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
-                }),
-            ],
-            proof_criteria: vec![
-                Box::from(NumberCriterion {
-                    op: AggregatedActionType::PublishProofOnchain,
-                    limit: *config.aggregated_proof_sizes.iter().max().unwrap() as u32,
-                }),
-                Box::from(GasCriterion::new(
-                    AggregatedActionType::PublishProofOnchain,
-                    config.max_aggregated_tx_gas,
-                )),
-                Box::from(TimestampDeadlineCriterion {
-                    op: AggregatedActionType::PublishProofOnchain,
-                    deadline_seconds: config.aggregated_block_prove_deadline,
-                    // Currently, we can't use this functionality for proof criterion
-                    // since we don't send dummy and real proofs in the same range,
-                    // so even small ranges must be closed.
-                    max_allowed_lag: None,
-                }),
-            ],
-            execute_criteria: vec![
-                Box::from(NumberCriterion {
-                    op: AggregatedActionType::Execute,
-                    limit: config.max_aggregated_blocks_to_execute,
-                }),
-                Box::from(GasCriterion::new(
-                    AggregatedActionType::Execute,
-                    config.max_aggregated_tx_gas,
-                )),
-                Box::from(TimestampDeadlineCriterion {
-                    op: AggregatedActionType::Execute,
-                    deadline_seconds: config.aggregated_block_execute_deadline,
-                    max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
-                }),
-            ],
+                L1BatchAggregationCriterion::Deadline => {
+                    policy.with_criterion(TimestampDeadlineCriterion {
+                        op,
+                        deadline_seconds: config.aggregated_block_commit_deadline,
+                        max_allowed_lag: Some(config.timestamp_criteria_max_allowed_lag),
+                    })
+                }
+            };
+        }
+        policy
+    }
+
+    fn default_proof_policy(config: &SenderConfig) -> CriteriaAggregationPolicy {
+        Self::non_commit_policy(
+            AggregatedActionType::PublishProofOnchain,
+            config.proof_aggregation_criteria(),
+            *config.aggregated_proof_sizes.iter().max().unwrap() as u32,
             config,
-            blob_store,
-            operate_4844_mode,
-            pubdata_da,
-            commitment_mode,
+            config.aggregated_block_prove_deadline,
+            // Currently, we can't use this functionality for proof criterion
+            // since we don't send dummy and real proofs in the same range,
+            // so even small ranges must be closed.
+            None,
+        )
+    }
+
+    fn default_execute_policy(config: &SenderConfig) -> CriteriaAggregationPolicy {
+        Self::non_commit_policy(
+            AggregatedActionType::Execute,
+            config.execute_aggregation_criteria(),
+            config.max_aggregated_blocks_to_execute,
+            config,
+            config.aggregated_block_execute_deadline,
+            Some(config.timestamp_criteria_max_allowed_lag),
+        )
+    }
+
+    fn non_commit_policy(
+        op: AggregatedActionType,
+        criteria: &[L1BatchAggregationCriterion],
+        limit: u32,
+        config: &SenderConfig,
+        deadline_seconds: u64,
+        max_allowed_lag: Option<usize>,
+    ) -> CriteriaAggregationPolicy {
+        let mut policy = CriteriaAggregationPolicy::default();
+        for criterion in criteria {
+            policy = match criterion {
+                L1BatchAggregationCriterion::Number => {
+                    policy.with_criterion(NumberCriterion { op, limit })
+                }
+                L1BatchAggregationCriterion::Gas => {
+                    policy.with_criterion(GasCriterion::new(op, config.max_aggregated_tx_gas))
+                }
+                L1BatchAggregationCriterion::DataSize => {
+                    tracing::warn!(
+                        "`DataSize` aggregation criterion is only applicable to commit operations; ignoring it for {op}"
+                    );
+                    policy
+                }
+                L1BatchAggregationCriterion::Deadline => {
+                    policy.with_criterion(TimestampDeadlineCriterion {
+                        op,
+                        deadline_seconds,
+                        max_allowed_lag,
+                    })
+                }
+            };
+        }
+        policy
+    }
+
+    /// Overrides the policy deciding which L1 batches are published in a single L1 transaction of the specified type.
+    /// By default, L1 batches are aggregated based on the criteria specified in the config
+    /// (e.g., [`SenderConfig::commit_aggregation_criteria()`]).
+    ///
+    /// Note that the policy is still limited by the maximum number of L1 batches per transaction specified
+    /// in the config, since only this number of L1 batches is loaded from Postgres.
+    #[must_use]
+    pub fn with_aggregation_policy(
+        mut self,
+        op: AggregatedActionType,
+        policy: Box<dyn L1BatchAggregationPolicy>,
+    ) -> Self {
+        match op {
+            AggregatedActionType::Commit => self.commit_policy = policy,
+            AggregatedActionType::PublishProofOnchain => self.proof_policy = policy,
+            AggregatedActionType::Execute => self.execute_policy = policy,
+        }
+        self
+    }
+
+    /// Enables choosing pubdata DA for each commit operation based on the prices reported by `gas_adjuster`.
+    /// No-op unless dynamic pubdata DA is enabled in the config, and the aggregator operates in the 4844 mode.
+    #[must_use]
+    pub fn with_gas_adjuster(mut self, gas_adjuster: Arc<dyn L1TxParamsProvider>) -> Self {
+        if let Some(hysteresis) = self.dynamic_pubdata_da_hysteresis {
+            self.pubdata_da_selector = Some(PubdataDASelector::new(
+                gas_adjuster,
+                self.pubdata_da,
+                hysteresis,
+            ));
         }
+        self
+    }
+
+    /// Sets whether the chain settles on a gateway. Pubdata for commit operations settled on a gateway is always
+    /// sent using calldata.
+    pub(crate) fn set_settles_on_gateway(&mut self, settles_on_gateway: bool) {
+        self.settles_on_gateway = settles_on_gateway;
+    }
+
+    /// Checks whether pubdata DA is chosen dynamically, which requires [`Self::with_gas_adjuster()`] to be called.
+    pub fn has_dynamic_pubdata_da(&self) -> bool {
+        self.dynamic_pubdata_da_hysteresis.is_some()
     }
 
     pub async fn get_next_ready_operation(
@@ -128,35 +271,47 @@ impl Aggregator {
             return None; // No L1 batches in Postgres; no operations are ready yet
         };
 
-        if let Some(op) = self
-            .get_execute_operations(
-                storage,
-                self.config.max_aggregated_blocks_to_execute as usize,
-                last_sealed_l1_batch_number,
-            )
-            .await
-        {
-            Some(AggregatedOperation::Execute(op))
-        } else if let Some(op) = self
-            .get_proof_operation(
-                storage,
-                *self.config.aggregated_proof_sizes.iter().max().unwrap(),
-                last_sealed_l1_batch_number,
-                l1_verifier_config,
-            )
-            .await
+        // Operations may be paused or resumed by another process sharing the database (e.g., the API server).
+        self.pause_handle.refresh(storage).await.unwrap();
+        if !self.pause_handle.is_paused(AggregatedActionType::Execute) {
+            if let Some(op) = self
+                .get_execute_operations(
+                    storage,
+                    self.config.max_aggregated_blocks_to_execute as usize,
+                    last_sealed_l1_batch_number,
+                )
+                .await
+            {
+                return Some(AggregatedOperation::Execute(op));
+            }
+        }
+        if !self
+            .pause_handle
+            .is_paused(AggregatedActionType::PublishProofOnchain)
         {
-            Some(AggregatedOperation::PublishProofOnchain(op))
-        } else {
-            self.get_commit_operation(
-                storage,
-                self.config.max_aggregated_blocks_to_commit as usize,
-                last_sealed_l1_batch_number,
-                base_system_contracts_hashes,
-                protocol_version_id,
-            )
-            .await
+            if let Some(op) = self
+                .get_proof_operation(
+                    storage,
+                    *self.config.aggregated_proof_sizes.iter().max().unwrap(),
+                    last_sealed_l1_batch_number,
+                    l1_verifier_config,
+                )
+                .await
+            {
+                return Some(AggregatedOperation::PublishProofOnchain(op));
+            }
+        }
+        if self.pause_handle.is_paused(AggregatedActionType::Commit) {
+            return None;
         }
+        self.get_commit_operation(
+            storage,
+            self.config.max_aggregated_blocks_to_commit as usize,
+            last_sealed_l1_batch_number,
+            base_system_contracts_hashes,
+            protocol_version_id,
+        )
+        .await
     }
 
     async fn get_execute_operations(
@@ -174,9 +329,10 @@ impl Aggregator {
             .get_ready_for_execute_l1_batches(limit, max_l1_batch_timestamp_millis)
             .await
             .unwrap();
-        let l1_batches = extract_ready_subrange(
+        let l1_batches = Self::select_l1_batches(
+            self.execute_policy.as_mut(),
+            AggregatedActionType::Execute,
             storage,
-            &mut self.execute_criteria,
             ready_for_execute_batches,
             last_sealed_l1_batch,
         )
@@ -232,17 +388,103 @@ impl Aggregator {
                 }
             });
 
-        let batches = extract_ready_subrange(
+        let ready_for_commit_l1_batches = if self.pubdata_da == PubdataDA::Custom {
+            Self::load_da_inclusion_data(storage, ready_for_commit_l1_batches).await
+        } else {
+            ready_for_commit_l1_batches
+        };
+
+        let mut batches = Self::select_l1_batches(
+            self.commit_policy.as_mut(),
+            AggregatedActionType::Commit,
             storage,
-            &mut self.commit_criteria,
             ready_for_commit_l1_batches,
             last_sealed_batch,
         )
-        .await;
+        .await?;
+        let pubdata_da = self.select_pubdata_da(&batches);
+        if pubdata_da == PubdataDA::Blobs && self.pubdata_da_selector.is_some() {
+            // Blob sidecars are only supported for a single L1 batch per commit transaction.
+            batches.truncate(1);
+        }
+        Some(AggregatedOperation::Commit(
+            last_committed_l1_batch,
+            batches,
+            pubdata_da,
+        ))
+    }
 
-        batches.map(|batches| {
-            AggregatedOperation::Commit(last_committed_l1_batch, batches, self.pubdata_da)
-        })
+    /// Selects L1 batches to publish using `policy`, and checks that the selected batches form a non-empty prefix
+    /// of `l1_batches`; L1 batches must be published in order, so any other selection is a bug in the policy.
+    pub(crate) async fn select_l1_batches(
+        policy: &mut dyn L1BatchAggregationPolicy,
+        op: AggregatedActionType,
+        storage: &mut Connection<'_, Core>,
+        l1_batches: Vec<L1BatchWithMetadata>,
+        last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<Vec<L1BatchWithMetadata>> {
+        let l1_batch_numbers: Vec<_> = l1_batches.iter().map(|batch| batch.header.number).collect();
+        let selected = policy
+            .select_l1_batches(storage, l1_batches, last_sealed_l1_batch)
+            .await?;
+        let selected_numbers: Vec<_> = selected.iter().map(|batch| batch.header.number).collect();
+        if !l1_batch_numbers.starts_with(&selected_numbers) {
+            tracing::error!(
+                "Aggregation policy for {op} operations selected L1 batches {selected_numbers:?}, which is not a prefix \
+                 of unpublished L1 batches {l1_batch_numbers:?}; skipping the operation"
+            );
+            return None;
+        }
+        (!selected.is_empty()).then_some(selected)
+    }
+
+    /// Loads DA inclusion data for L1 batches ready to be committed. Batches are only returned up to the first batch
+    /// without inclusion data, so that commits wait until the DA dispatcher fetches and verifies the inclusion data.
+    async fn load_da_inclusion_data(
+        storage: &mut Connection<'_, Core>,
+        l1_batches: Vec<L1BatchWithMetadata>,
+    ) -> Vec<L1BatchWithMetadata> {
+        let mut ready_l1_batches = Vec::with_capacity(l1_batches.len());
+        for mut l1_batch in l1_batches {
+            let inclusion_data = storage
+                .data_availability_dal()
+                .get_l1_batch_inclusion_data(l1_batch.header.number)
+                .await
+                .unwrap();
+            let Some(inclusion_data) = inclusion_data else {
+                tracing::debug!(
+                    "L1 batch #{} cannot be committed until its DA inclusion data is available",
+                    l1_batch.header.number
+                );
+                break;
+            };
+            l1_batch.metadata.da_inclusion_data = Some(inclusion_data);
+            ready_l1_batches.push(l1_batch);
+        }
+        ready_l1_batches
+    }
+
+    fn select_pubdata_da(&mut self, l1_batches: &[L1BatchWithMetadata]) -> PubdataDA {
+        if self.pubdata_da == PubdataDA::Custom {
+            // Pubdata is always published to the DA layer, including for chains settling on a gateway.
+            return PubdataDA::Custom;
+        }
+        if self.settles_on_gateway {
+            return PubdataDA::Calldata;
+        }
+        let Some(selector) = &mut self.pubdata_da_selector else {
+            return self.pubdata_da;
+        };
+        // Blobs cannot be constructed without pubdata input (this is the case for old protocol versions).
+        // If some batches lack pubdata input, only the batches preceding them can be committed via blobs.
+        let pubdata_lens: Vec<_> = l1_batches
+            .iter()
+            .map_while(|batch| batch.header.pubdata_input.as_ref().map(Vec::len))
+            .collect();
+        if pubdata_lens.is_empty() {
+            return PubdataDA::Calldata;
+        }
+        selector.select(&pubdata_lens)
     }
 
     async fn load_dummy_proof_operations(
@@ -398,9 +640,10 @@ impl Aggregator {
         ready_for_proof_l1_batches: Vec<L1BatchWithMetadata>,
         last_sealed_l1_batch: L1BatchNumber,
     ) -> Option<ProveBatches> {
-        let batches = extract_ready_subrange(
+        let batches = Self::select_l1_batches(
+            self.proof_policy.as_mut(),
+            AggregatedActionType::PublishProofOnchain,
             storage,
-            &mut self.proof_criteria,
             ready_for_proof_l1_batches,
             last_sealed_l1_batch,
         )
@@ -478,38 +721,14 @@ impl Aggregator {
         }
     }
 
-    pub fn pubdata_da(&self) -> PubdataDA {
-        self.pubdata_da
-    }
-
     pub fn mode(&self) -> L1BatchCommitmentMode {
-        self.commitment_mode
+        self.pubdata_encoding.commitment_mode()
     }
-}
 
-async fn extract_ready_subrange(
-    storage: &mut Connection<'_, Core>,
-    publish_criteria: &mut [Box<dyn L1BatchPublishCriterion>],
-    unpublished_l1_batches: Vec<L1BatchWithMetadata>,
-    last_sealed_l1_batch: L1BatchNumber,
-) -> Option<Vec<L1BatchWithMetadata>> {
-    let mut last_l1_batch: Option<L1BatchNumber> = None;
-    for criterion in publish_criteria {
-        let l1_batch_by_criterion = criterion
-            .last_l1_batch_to_publish(storage, &unpublished_l1_batches, last_sealed_l1_batch)
-            .await;
-        if let Some(l1_batch) = l1_batch_by_criterion {
-            last_l1_batch = Some(last_l1_batch.map_or(l1_batch, |number| number.min(l1_batch)));
-        }
+    /// Returns the encoding of pubdata persisted for L1 batches by the state keeper.
+    pub fn pubdata_encoding(&self) -> PubdataEncoding {
+        self.pubdata_encoding
     }
-
-    let last_l1_batch = last_l1_batch?;
-    Some(
-        unpublished_l1_batches
-            .into_iter()
-            .take_while(|l1_batch| l1_batch.header.number <= last_l1_batch)
-            .collect(),
-    )
 }
 
 pub async fn load_wrapped_fri_proofs_for_range(
@@ -1,3 +1,5 @@
+use std::collections::{HashMap, HashSet};
+
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
 use zksync_contracts::BaseSystemContractsHashes;
@@ -20,6 +22,7 @@ use zksync_types::{
     l2_to_l1_log::UserL2ToL1Log,
     protocol_version::{L1VerifierConfig, VerifierParams, PACKED_SEMVER_MINOR_MASK},
     pubdata_da::PubdataDA,
+    settlement::StoredSettlementLayerSwitch,
     web3::{contract::Error as Web3ContractError, BlockNumber},
     Address, L2ChainId, ProtocolVersionId, H256, U256,
 };
@@ -29,7 +32,7 @@ use crate::{
     metrics::{PubdataKind, METRICS},
     utils::agg_l1_batch_base_cost,
     zksync_functions::ZkSyncFunctions,
-    Aggregator, EthSenderError,
+    Aggregator, EthSenderError, EthSenderPauseHandle,
 };
 
 /// Data queried from L1 using multicall contract.
@@ -41,6 +44,22 @@ pub struct MulticallData {
     pub protocol_version_id: ProtocolVersionId,
 }
 
+/// Settlement layer of the chain after an applied settlement layer switch.
+#[derive(Debug)]
+struct AppliedSettlementLayer {
+    switch: StoredSettlementLayerSwitch,
+    /// ID of the last transaction settled on the previous settlement layer.
+    applied_after_eth_tx_id: u32,
+    /// Pending nonce of the main operator on the settlement layer when the switch was loaded.
+    base_nonce: u64,
+}
+
+impl AppliedSettlementLayer {
+    fn is_gateway(&self) -> bool {
+        self.switch.switch.settlement_layer.is_gateway()
+    }
+}
+
 /// The component is responsible for aggregating l1 batches into eth_txs:
 /// Such as CommitBlocks, PublishProofBlocksOnchain and ExecuteBlock
 /// These eth_txs will be used as a queue for generating signed txs and send them later
@@ -61,6 +80,18 @@ pub struct EthTxAggregator {
     /// transactions. The `Some` then contains the address of this custom operator
     /// address.
     custom_commit_sender_addr: Option<Address>,
+    /// Successor of the main operator used for key rotation.
+    successor_operator_addr: Option<Address>,
+    /// Successor of the custom commit sender used for key rotation.
+    successor_commit_sender_addr: Option<Address>,
+    /// Base nonces for the successor operators.
+    successor_base_nonces: HashMap<Address, u64>,
+    /// Senders replaced by their successors that have all their transactions mined.
+    drained_senders: HashSet<Option<Address>>,
+    /// Client of the main operator on the gateway. Used after the chain settlement layer is switched to the gateway.
+    gateway_client: Option<Box<dyn BoundEthInterface>>,
+    /// Settlement layer after the latest applied switch; `None` if the chain has never switched its settlement layer.
+    settlement_layer: Option<AppliedSettlementLayer>,
     pool: ConnectionPool<Core>,
 }
 
@@ -109,12 +140,79 @@ impl EthTxAggregator {
             base_nonce_custom_commit_sender,
             rollup_chain_id,
             custom_commit_sender_addr,
+            successor_operator_addr: None,
+            successor_commit_sender_addr: None,
+            successor_base_nonces: HashMap::new(),
+            drained_senders: HashSet::new(),
+            gateway_client: None,
+            settlement_layer: None,
             pool,
         }
     }
 
+    /// Returns a handle allowing to pause and resume creating transactions of specific types.
+    pub fn pause_handle(&self) -> EthSenderPauseHandle {
+        self.aggregator.pause_handle()
+    }
+
+    /// Sets the client of the main operator on the gateway. This client is required to settle on the gateway
+    /// once the chain settlement layer is switched to it; without it, no transactions will be created after the switch.
+    pub fn with_gateway_client(mut self, gateway_client: Box<dyn BoundEthInterface>) -> Self {
+        self.gateway_client = Some(gateway_client.for_component("eth_tx_aggregator"));
+        self
+    }
+
+    /// Configures successor operators for key rotation. New transactions that would be sent by the main operator
+    /// (or the custom commit sender) will be sent by the corresponding successor instead, once all transactions
+    /// of the replaced operator are mined.
+    pub async fn with_successor_operators(
+        mut self,
+        successor_operator_addr: Option<Address>,
+        successor_commit_sender_addr: Option<Address>,
+    ) -> Self {
+        let successor_commit_sender_addr = if self.custom_commit_sender_addr.is_some() {
+            successor_commit_sender_addr
+        } else {
+            if successor_commit_sender_addr.is_some() {
+                tracing::warn!(
+                    "Successor blob operator is configured without the blob operator; it will be ignored"
+                );
+            }
+            None
+        };
+
+        for addr in [successor_operator_addr, successor_commit_sender_addr]
+            .into_iter()
+            .flatten()
+        {
+            let nonce = (*self.eth_client)
+                .as_ref()
+                .nonce_at_for_account(addr, BlockNumber::Pending)
+                .await
+                .unwrap()
+                .as_u64();
+            tracing::info!(
+                "Using successor operator {addr:?} with base nonce {nonce} for new transactions"
+            );
+            self.successor_base_nonces.insert(addr, nonce);
+        }
+        self.successor_operator_addr = successor_operator_addr;
+        self.successor_commit_sender_addr = successor_commit_sender_addr;
+        self
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let pool = self.pool.clone();
+        {
+            let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
+            self.attribute_txs_to_main_operator(&mut storage).await;
+            self.aggregator
+                .pause_handle()
+                .initialize(&mut storage)
+                .await
+                .unwrap();
+        }
+
         loop {
             let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
 
@@ -134,6 +232,201 @@ impl EthTxAggregator {
         Ok(())
     }
 
+    /// Handles a successor operator promoted to the main operator after key rotation. Transactions sent by
+    /// the successor are stored with its address, while transactions of the main operator have no address.
+    async fn attribute_txs_to_main_operator(&self, storage: &mut Connection<'_, Core>) {
+        let main_operator_addr = self.eth_client.sender_account();
+        if self.custom_commit_sender_addr == Some(main_operator_addr) {
+            return; // Shouldn't happen in practice, but we don't want to mix up senders in this case.
+        }
+        let affected_txs = storage
+            .eth_sender_dal()
+            .reset_sender_to_main_operator(main_operator_addr)
+            .await
+            .unwrap();
+        if affected_txs > 0 {
+            tracing::info!(
+                "Attributed {affected_txs} transaction(s) sent by {main_operator_addr:?} to the main operator"
+            );
+        }
+    }
+
+    /// Returns the regular sender and its successor (if any) for transactions of the specified type.
+    /// Senders are returned in the same format as `from_addr` in the `eth_txs` table, i.e. `None`
+    /// for the main operator.
+    fn senders(&self, op_type: AggregatedActionType) -> (Option<Address>, Option<Address>) {
+        if self.settles_on_gateway() {
+            // The gateway doesn't support blobs, and only the main operator is configured for it.
+            return (None, None);
+        }
+        // We may be using a custom sender for commit transactions, so use this
+        // var whatever it actually is: a `None` for single-addr operator or `Some`
+        // for multi-addr operator in 4844 mode.
+        match op_type {
+            AggregatedActionType::Commit if self.custom_commit_sender_addr.is_some() => (
+                self.custom_commit_sender_addr,
+                self.successor_commit_sender_addr,
+            ),
+            _ => (None, self.successor_operator_addr),
+        }
+    }
+
+    /// Returns the sender for a new transaction of the specified type.
+    fn sender_addr(&self, op_type: AggregatedActionType) -> Option<Address> {
+        match self.senders(op_type) {
+            (sender_addr, Some(successor_addr)) if self.drained_senders.contains(&sender_addr) => {
+                Some(successor_addr)
+            }
+            (sender_addr, _) => sender_addr,
+        }
+    }
+
+    /// Checks whether a new transaction of the specified type can be sent. This is not the case if the sender
+    /// is being rotated, and the replaced sender has unmined transactions.
+    async fn can_send(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        op_type: AggregatedActionType,
+    ) -> Result<bool, EthSenderError> {
+        match self.senders(op_type) {
+            (sender_addr, Some(_)) => self.is_sender_drained(storage, sender_addr).await,
+            (_, None) => Ok(true),
+        }
+    }
+
+    /// Checks whether all transactions of the specified sender are mined. This ensures that transactions
+    /// of the successor are not mined before the preceding transactions of the replaced sender.
+    async fn is_sender_drained(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        sender_addr: Option<Address>,
+    ) -> Result<bool, EthSenderError> {
+        if self.drained_senders.contains(&sender_addr) {
+            return Ok(true);
+        }
+
+        let next_db_nonce = storage
+            .eth_sender_dal()
+            .get_next_nonce(sender_addr)
+            .await
+            .unwrap();
+        let is_drained = if let Some(next_db_nonce) = next_db_nonce {
+            let address = sender_addr.unwrap_or_else(|| self.eth_client.sender_account());
+            let mined_nonce = (*self.eth_client)
+                .as_ref()
+                .nonce_at_for_account(address, BlockNumber::Latest)
+                .await?
+                .as_u64();
+            tracing::debug!(
+                "Checking whether replaced sender {address:?} is drained: next nonce in DB: {next_db_nonce}, \
+                 mined nonce: {mined_nonce}"
+            );
+            mined_nonce >= next_db_nonce
+        } else {
+            true
+        };
+
+        if is_drained {
+            tracing::info!(
+                "All transactions of sender {sender_addr:?} are mined; new transactions will be sent by its successor"
+            );
+            self.drained_senders.insert(sender_addr);
+        }
+        Ok(is_drained)
+    }
+
+    fn settles_on_gateway(&self) -> bool {
+        self.settlement_layer
+            .as_ref()
+            .is_some_and(AppliedSettlementLayer::is_gateway)
+    }
+
+    /// Applies the latest settlement layer switch announced on L1 (if any). A switch is applied only after
+    /// all transactions settled on the previous settlement layer are confirmed; until then, no new transactions
+    /// are created. After the switch, transactions are sent to the validator timelock on the new settlement layer,
+    /// and the main operator nonces are based on its pending nonce on this layer.
+    ///
+    /// Returns `false` if new transactions cannot be created.
+    pub(super) async fn update_settlement_layer(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<bool, EthSenderError> {
+        let Some(latest_switch) = storage
+            .settlement_layer_dal()
+            .get_latest_switch()
+            .await
+            .unwrap()
+        else {
+            return Ok(true);
+        };
+        let settlement_layer = latest_switch.switch.settlement_layer;
+        if self.settlement_layer.as_ref().map(|sl| sl.switch.id) == Some(latest_switch.id) {
+            return Ok(true);
+        }
+        if settlement_layer.is_gateway() && self.gateway_client.is_none() {
+            tracing::error!(
+                "Chain is switched to settlement layer {settlement_layer:?}, but the gateway client is not configured; \
+                 no transactions will be created"
+            );
+            return Ok(false);
+        }
+
+        let applied_after_eth_tx_id = if let Some(eth_tx_id) = latest_switch.applied_after_eth_tx_id
+        {
+            eth_tx_id
+        } else {
+            if storage
+                .eth_sender_dal()
+                .has_unconfirmed_txs()
+                .await
+                .unwrap()
+            {
+                tracing::info!(
+                    "Waiting for transactions on the current settlement layer to be confirmed before switching \
+                     to settlement layer {settlement_layer:?}"
+                );
+                return Ok(false);
+            }
+            let applied_after_eth_tx_id = storage
+                .settlement_layer_dal()
+                .mark_switch_applied(latest_switch.id)
+                .await
+                .unwrap();
+            let Some(applied_after_eth_tx_id) = applied_after_eth_tx_id else {
+                // The switch was applied concurrently (e.g., by another aggregator instance during a restart).
+                // Its state will be reloaded on the next iteration.
+                tracing::warn!(
+                    "Settlement layer switch #{} is already applied; reloading it",
+                    latest_switch.id
+                );
+                return Ok(false);
+            };
+            applied_after_eth_tx_id
+        };
+
+        let mut settlement_layer_state = AppliedSettlementLayer {
+            switch: latest_switch,
+            applied_after_eth_tx_id,
+            base_nonce: 0,
+        };
+        let client = if settlement_layer_state.is_gateway() {
+            self.gateway_client.as_deref().unwrap() // checked above
+        } else {
+            self.eth_client.as_ref()
+        };
+        settlement_layer_state.base_nonce = client.pending_nonce().await?.as_u64();
+        tracing::info!(
+            "Settling on {settlement_layer:?} starting after eth_tx #{applied_after_eth_tx_id}; \
+             validator timelock: {:?}, base nonce: {}",
+            settlement_layer_state.switch.switch.validator_timelock_addr,
+            settlement_layer_state.base_nonce
+        );
+        self.aggregator
+            .set_settles_on_gateway(settlement_layer_state.is_gateway());
+        self.settlement_layer = Some(settlement_layer_state);
+        Ok(true)
+    }
+
     pub(super) async fn get_multicall_data(&mut self) -> Result<MulticallData, EthSenderError> {
         let calldata = self.generate_calldata_for_multicall();
         let args = CallFunctionArgs::new(&self.functions.aggregate3.name, calldata).for_contract(
@@ -345,6 +638,10 @@ impl EthTxAggregator {
         &mut self,
         storage: &mut Connection<'_, Core>,
     ) -> Result<(), EthSenderError> {
+        if !self.update_settlement_layer(storage).await? {
+            return Ok(());
+        }
+
         let MulticallData {
             base_system_contracts_hashes,
             verifier_params,
@@ -377,6 +674,13 @@ impl EthTxAggregator {
             )
             .await
         {
+            if !self.can_send(storage, agg_op.get_action_type()).await? {
+                tracing::info!(
+                    "Waiting for transactions of the replaced operator to be mined before sending op {}",
+                    agg_op.get_action_caption()
+                );
+                return Ok(());
+            }
             let tx = self
                 .save_eth_tx(storage, &agg_op, contracts_are_pre_shared_bridge)
                 .await?;
@@ -455,7 +759,11 @@ impl EthTxAggregator {
                     )
                 };
 
-                let l1_batch_for_sidecar = if PubdataDA::Blobs == self.aggregator.pubdata_da() {
+                // Only the rollup encoding contains the full pubdata to be put into blobs; for validiums,
+                // the commit data only specifies the pubdata source.
+                let publishes_blobs = PubdataDA::Blobs == *pubdata_da
+                    && self.aggregator.pubdata_encoding().is_published_on_l1();
+                let l1_batch_for_sidecar = if publishes_blobs {
                     Some(l1_batches[0].clone())
                 } else {
                     None
@@ -546,13 +854,7 @@ impl EthTxAggregator {
     ) -> Result<EthTx, EthSenderError> {
         let mut transaction = storage.start_transaction().await.unwrap();
         let op_type = aggregated_op.get_action_type();
-        // We may be using a custom sender for commit transactions, so use this
-        // var whatever it actually is: a `None` for single-addr operator or `Some`
-        // for multi-addr operator in 4844 mode.
-        let sender_addr = match op_type {
-            AggregatedActionType::Commit => self.custom_commit_sender_addr,
-            _ => None,
-        };
+        let sender_addr = self.sender_addr(op_type);
         let nonce = self.get_next_nonce(&mut transaction, sender_addr).await?;
         let encoded_aggregated_op =
             self.encode_aggregated_op(aggregated_op, contracts_are_pre_shared_bridge);
@@ -565,13 +867,19 @@ impl EthTxAggregator {
             .unwrap();
         let eth_tx_predicted_gas = agg_l1_batch_base_cost(op_type) + predicted_gas_for_batches;
 
+        let contract_address = self
+            .settlement_layer
+            .as_ref()
+            .map_or(self.timelock_contract_address, |sl| {
+                sl.switch.switch.validator_timelock_addr
+            });
         let eth_tx = transaction
             .eth_sender_dal()
             .save_eth_tx(
                 nonce,
                 encoded_aggregated_op.calldata,
                 op_type,
-                self.timelock_contract_address,
+                contract_address,
                 eth_tx_predicted_gas,
                 sender_addr,
                 encoded_aggregated_op.sidecar,
@@ -593,21 +901,35 @@ impl EthTxAggregator {
         storage: &mut Connection<'_, Core>,
         from_addr: Option<Address>,
     ) -> Result<u64, EthSenderError> {
-        let db_nonce = storage
-            .eth_sender_dal()
-            .get_next_nonce(from_addr)
-            .await
-            .unwrap()
-            .unwrap_or(0);
+        // Only the main operator is used on all settlement layers, so nonces of the other senders
+        // are not affected by settlement layer switches.
+        let settlement_layer = self
+            .settlement_layer
+            .as_ref()
+            .filter(|_| from_addr.is_none());
+        let db_nonce = if let Some(settlement_layer) = settlement_layer {
+            storage
+                .eth_sender_dal()
+                .get_next_nonce_after_eth_tx(from_addr, settlement_layer.applied_after_eth_tx_id)
+                .await
+        } else {
+            storage.eth_sender_dal().get_next_nonce(from_addr).await
+        };
+        let db_nonce = db_nonce.unwrap().unwrap_or(0);
         // Between server starts we can execute some txs using operator account or remove some txs from the database
         // At the start we have to consider this fact and get the max nonce.
-        Ok(if from_addr.is_none() {
-            db_nonce.max(self.base_nonce)
-        } else {
-            db_nonce.max(
+        Ok(match from_addr {
+            None => db_nonce.max(settlement_layer.map_or(self.base_nonce, |sl| sl.base_nonce)),
+            Some(_) if from_addr == self.custom_commit_sender_addr => db_nonce.max(
                 self.base_nonce_custom_commit_sender
                     .expect("custom base nonce is expected to be initialized; qed"),
-            )
+            ),
+            Some(addr) => db_nonce.max(
+                *self
+                    .successor_base_nonces
+                    .get(&addr)
+                    .expect("successor base nonce is expected to be initialized; qed"),
+            ),
         })
     }
 }
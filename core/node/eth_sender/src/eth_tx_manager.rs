@@ -1,9 +1,12 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::configs::eth_sender::SenderConfig;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    outbox_dal::{BlockNotification, L1BatchStatus},
+    Connection, ConnectionPool, Core, CoreDal,
+};
 use zksync_eth_client::{
     clients::{DynClient, L1},
     encode_blob_tx_with_sidecar, BoundEthInterface, ClientError, EnrichedClientError, EthInterface,
@@ -13,19 +16,25 @@ use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
 use zksync_shared_metrics::BlockL1Stage;
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    eth_sender::{EthTx, EthTxBlobSidecar},
-    web3::{BlockId, BlockNumber},
+    eth_sender::{EthTx, EthTxBlobSidecar, EthTxDryRun, EthTxL1Cost, TxHistory},
+    web3::{BlockId, BlockNumber, CallRequest, TransactionReceipt},
     Address, L1BlockNumber, Nonce, EIP_1559_TX_TYPE, EIP_4844_TX_TYPE, H256, U256,
 };
 use zksync_utils::time::seconds_since_epoch;
 
-use super::{metrics::METRICS, EthSenderError};
+use super::{
+    fee_bump_policy::{ConfiguredFeeBumpPolicy, FeeBumpInput, FeeBumpPolicy},
+    metrics::{DryRunLabels, DryRunOutcome, NonceDivergenceKind, SubmissionChannel, METRICS},
+    EthSenderError,
+};
 
 #[derive(Debug)]
 struct EthFee {
     base_fee_per_gas: u64,
     priority_fee_per_gas: u64,
     blob_base_fee_per_gas: Option<u64>,
+    /// Parameters of the fee bump policy used to calculate fees; `None` if fees weren't bumped.
+    fee_bump_policy: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -55,9 +64,30 @@ pub struct EthTxManager {
     /// If the operator is in 4844 mode this is sent to `Some` and used to send
     /// commit transactions.
     ethereum_gateway_blobs: Option<Box<dyn BoundEthInterface>>,
+    /// Gateways for successor operators used during key rotation. Transactions sent by successors
+    /// have the successor address as `from_addr`.
+    successor_gateways: Vec<Box<dyn BoundEthInterface>>,
+    /// Client for a private relay RPC used to submit transactions without exposing them in the public mempool.
+    private_relay_client: Option<Box<DynClient<L1>>>,
+    /// Gateway of the main operator on the settlement layer other than the current one (L1 or the gateway chain).
+    /// Swapped with `ethereum_gateway` when a settlement layer switch is applied. `None` if the gateway chain
+    /// is not configured.
+    standby_gateway: Option<Box<dyn BoundEthInterface>>,
+    /// Gas adjuster for the settlement layer of `standby_gateway`. Swapped with `gas_adjuster` together with the gateway.
+    standby_gas_adjuster: Option<Arc<dyn L1TxParamsProvider>>,
+    /// Whether `ethereum_gateway` currently sends transactions to the gateway chain.
+    settles_on_gateway: bool,
     config: SenderConfig,
     gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    /// Policy for bumping the priority fee of resent transactions without blobs.
+    fee_bump_policy: Arc<dyn FeeBumpPolicy>,
+    /// Policy for bumping all fees of resent blob transactions.
+    blob_fee_bump_policy: Arc<dyn FeeBumpPolicy>,
     pool: ConnectionPool<Core>,
+    insert_block_notifications: bool,
+    /// L1 blocks at which nonce divergences were first detected, keyed by the operator address
+    /// (`None` for the main operator).
+    nonce_divergences: HashMap<Option<Address>, L1BlockNumber>,
 }
 
 impl EthTxManager {
@@ -72,12 +102,158 @@ impl EthTxManager {
             ethereum_gateway: ethereum_gateway.for_component("eth_tx_manager"),
             ethereum_gateway_blobs: ethereum_gateway_blobs
                 .map(|eth| eth.for_component("eth_tx_manager")),
+            successor_gateways: vec![],
+            private_relay_client: None,
+            standby_gateway: None,
+            standby_gas_adjuster: None,
+            settles_on_gateway: false,
+            fee_bump_policy: Arc::new(ConfiguredFeeBumpPolicy::for_txs_without_blobs(&config)),
+            blob_fee_bump_policy: Arc::new(ConfiguredFeeBumpPolicy::for_blob_txs(&config)),
             config,
             gas_adjuster,
             pool,
+            insert_block_notifications: false,
+            nonce_divergences: HashMap::new(),
+        }
+    }
+
+    /// Overrides the policy for bumping fees of resent transactions without blobs. By default,
+    /// the policy is configured using [`SenderConfig`].
+    pub fn with_fee_bump_policy(mut self, policy: Arc<dyn FeeBumpPolicy>) -> Self {
+        self.fee_bump_policy = policy;
+        self
+    }
+
+    /// Overrides the policy for bumping fees of resent blob transactions. By default,
+    /// the policy is configured using [`SenderConfig`].
+    pub fn with_blob_fee_bump_policy(mut self, policy: Arc<dyn FeeBumpPolicy>) -> Self {
+        self.blob_fee_bump_policy = policy;
+        self
+    }
+
+    /// Enables inserting notifications about L1 batch status changes to the block notifications outbox
+    /// when a transaction is confirmed. Notifications are inserted in the same transaction as the confirmation.
+    pub fn with_block_notifications(mut self) -> Self {
+        self.insert_block_notifications = true;
+        self
+    }
+
+    /// Adds gateways for successor operators used during key rotation (see `EthTxAggregator::with_successor_operators()`).
+    /// Transactions sent by successors will be signed by these gateways and tracked until finalization.
+    pub fn with_successor_gateways(
+        mut self,
+        gateways: impl IntoIterator<Item = Box<dyn BoundEthInterface>>,
+    ) -> Self {
+        self.successor_gateways.extend(
+            gateways
+                .into_iter()
+                .map(|eth| eth.for_component("eth_tx_manager")),
+        );
+        self
+    }
+
+    /// Makes transactions submitted via the private relay RPC using the provided `client`. If a transaction
+    /// isn't mined during `private_relay_fallback_blocks` L1 blocks after it was created, or if the relay
+    /// rejects it, it's submitted to the public mempool instead. Blob transactions are always submitted
+    /// to the public mempool since private relays generally don't accept them.
+    pub fn with_private_relay_client(mut self, client: Box<DynClient<L1>>) -> Self {
+        self.private_relay_client = Some(client.for_component("eth_tx_manager"));
+        self
+    }
+
+    /// Sets the gateway of the main operator on the gateway chain. It's used to send all transactions created
+    /// after the chain settlement layer is switched to the gateway chain (see `EthTxAggregator::with_gateway_client()`).
+    /// Fees for transactions on the gateway chain are estimated using `gas_adjuster` tracking gas prices
+    /// on the gateway chain.
+    pub fn with_gateway_client(
+        mut self,
+        gateway: Box<dyn BoundEthInterface>,
+        gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    ) -> Self {
+        self.standby_gateway = Some(gateway.for_component("eth_tx_manager"));
+        self.standby_gas_adjuster = Some(gas_adjuster);
+        self
+    }
+
+    /// Switches the main operator gateway if the applied settlement layer has changed. Since switches are applied
+    /// only after all transactions on the previous settlement layer are confirmed, this doesn't affect in-flight
+    /// transactions. Returns `true` if the gateway was switched.
+    ///
+    /// Returns an error if the chain is switched to the gateway chain, but its client is not configured.
+    /// In this case, no transactions are sent until the client is configured.
+    async fn update_settlement_layer(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<bool, EthSenderError> {
+        let applied_switch = storage
+            .settlement_layer_dal()
+            .get_latest_applied_switch()
+            .await
+            .unwrap();
+        let settles_on_gateway =
+            applied_switch.is_some_and(|switch| switch.switch.settlement_layer.is_gateway());
+        if settles_on_gateway == self.settles_on_gateway {
+            return Ok(false);
+        }
+
+        let (Some(new_gateway), Some(new_gas_adjuster)) = (
+            self.standby_gateway.take(),
+            self.standby_gas_adjuster.take(),
+        ) else {
+            tracing::error!(
+                "Chain settlement layer is switched to the gateway chain, but its client is not configured"
+            );
+            let err = ClientError::Custom("gateway client is not configured".to_owned());
+            return Err(EnrichedClientError::new(err, "update_settlement_layer").into());
+        };
+        let previous_gateway = std::mem::replace(&mut self.ethereum_gateway, new_gateway);
+        self.standby_gateway = Some(previous_gateway);
+        let previous_gas_adjuster = std::mem::replace(&mut self.gas_adjuster, new_gas_adjuster);
+        self.standby_gas_adjuster = Some(previous_gas_adjuster);
+        self.settles_on_gateway = settles_on_gateway;
+        tracing::info!(
+            "Switched main operator gateway to the {} settlement layer",
+            if settles_on_gateway { "gateway" } else { "L1" }
+        );
+        Ok(true)
+    }
+
+    /// Returns the client to submit `tx` that has spent `time_in_mempool` L1 blocks in the mempool.
+    fn submission_client(
+        &self,
+        tx: &EthTx,
+        time_in_mempool: u32,
+    ) -> (&DynClient<L1>, SubmissionChannel) {
+        // Approximate L1 block time used to measure the time since a transaction was created in L1 blocks.
+        // Time in the mempool is insufficient on its own since it isn't tracked if the relay doesn't accept
+        // the transaction.
+        const L1_BLOCK_TIME_SECONDS: u64 = 12;
+
+        let fallback_blocks = self.config.private_relay_fallback_blocks();
+        let blocks_since_creation =
+            seconds_since_epoch().saturating_sub(tx.created_at_timestamp) / L1_BLOCK_TIME_SECONDS;
+        match &self.private_relay_client {
+            // The private relay is only available on L1.
+            Some(client)
+                if !self.settles_on_gateway
+                    && tx.blob_sidecar.is_none()
+                    && time_in_mempool < fallback_blocks
+                    && blocks_since_creation < u64::from(fallback_blocks) =>
+            {
+                (client.as_ref(), SubmissionChannel::PrivateRelay)
+            }
+            _ => (self.query_client(), SubmissionChannel::PublicMempool),
         }
     }
 
+    fn successor_gateway(&self, address: Option<Address>) -> Option<&dyn BoundEthInterface> {
+        let address = address?;
+        self.successor_gateways
+            .iter()
+            .find(|gateway| gateway.sender_account() == address)
+            .map(|gateway| gateway.as_ref())
+    }
+
     pub(crate) fn query_client(&self) -> &DynClient<L1> {
         (*self.ethereum_gateway).as_ref()
     }
@@ -130,53 +306,83 @@ impl EthTxManager {
         let priority_fee_per_gas = self.gas_adjuster.get_priority_fee();
         let blob_base_fee_per_gas = Some(self.gas_adjuster.get_blob_base_fee());
 
+        let history = if time_in_mempool != 0 {
+            storage
+                .eth_sender_dal()
+                .get_tx_history(tx.id)
+                .await
+                .unwrap()
+        } else {
+            vec![]
+        };
+
         if tx.blob_sidecar.is_some() {
-            if time_in_mempool != 0 {
-                // for blob transactions on re-sending need to double all gas prices
-                let previous_sent_tx = storage
-                    .eth_sender_dal()
-                    .get_last_sent_eth_tx(tx.id)
-                    .await
-                    .unwrap()
-                    .unwrap();
+            if let (Some(first_sent_tx), Some(previous_sent_tx)) = (history.first(), history.last())
+            {
+                // For blob transactions, all gas prices need to be bumped on re-sending.
+                let policy = self.blob_fee_bump_policy.as_ref();
+                let bump_number = history.len() as u32;
+                let bump_fee = |initial_fee, previous_fee| {
+                    policy
+                        .bump_fee(FeeBumpInput {
+                            initial_fee,
+                            previous_fee,
+                            bump_number,
+                        })
+                        .ok_or_else(|| Self::fee_bump_cap_error(tx.id, bump_number))
+                };
+                let bumped_blob_base_fee = previous_sent_tx
+                    .blob_base_fee_per_gas
+                    .map(|previous_fee| {
+                        let initial_fee =
+                            first_sent_tx.blob_base_fee_per_gas.unwrap_or(previous_fee);
+                        bump_fee(initial_fee, previous_fee)
+                    })
+                    .transpose()?;
                 return Ok(EthFee {
-                    base_fee_per_gas: std::cmp::max(
-                        previous_sent_tx.base_fee_per_gas * 2,
-                        base_fee_per_gas,
-                    ),
-                    priority_fee_per_gas: std::cmp::max(
-                        previous_sent_tx.priority_fee_per_gas * 2,
-                        priority_fee_per_gas,
-                    ),
+                    base_fee_per_gas: bump_fee(
+                        first_sent_tx.base_fee_per_gas,
+                        previous_sent_tx.base_fee_per_gas,
+                    )?
+                    .max(base_fee_per_gas),
+                    priority_fee_per_gas: bump_fee(
+                        first_sent_tx.priority_fee_per_gas,
+                        previous_sent_tx.priority_fee_per_gas,
+                    )?
+                    .max(priority_fee_per_gas),
                     blob_base_fee_per_gas: std::cmp::max(
-                        previous_sent_tx.blob_base_fee_per_gas.map(|v| v * 2),
+                        bumped_blob_base_fee,
                         blob_base_fee_per_gas,
                     ),
+                    fee_bump_policy: Some(Self::fee_bump_parameters(policy, bump_number)),
                 });
             }
             return Ok(EthFee {
                 base_fee_per_gas,
                 priority_fee_per_gas,
                 blob_base_fee_per_gas,
+                fee_bump_policy: None,
             });
         }
 
         let base_fee_per_gas = self.gas_adjuster.get_base_fee(time_in_mempool);
 
-        let priority_fee_per_gas = if time_in_mempool != 0 {
+        let (priority_fee_per_gas, fee_bump_policy) = if history.is_empty() {
+            (self.gas_adjuster.get_priority_fee(), None)
+        } else {
             METRICS.transaction_resent.inc();
-            let priority_fee_per_gas = self
-                .increase_priority_fee(storage, tx.id, base_fee_per_gas)
-                .await?;
+            let priority_fee_per_gas =
+                self.increase_priority_fee(tx.id, &history, base_fee_per_gas)?;
             tracing::info!(
                 "Resending operation {} with base fee {:?} and priority fee {:?}",
                 tx.id,
                 base_fee_per_gas,
                 priority_fee_per_gas
             );
-            priority_fee_per_gas
-        } else {
-            self.gas_adjuster.get_priority_fee()
+            let bump_number = history.len() as u32;
+            let fee_bump_policy =
+                Self::fee_bump_parameters(self.fee_bump_policy.as_ref(), bump_number);
+            (priority_fee_per_gas, Some(fee_bump_policy))
         };
 
         // Extra check to prevent sending transaction will extremely high priority fee.
@@ -192,24 +398,44 @@ impl EthTxManager {
             base_fee_per_gas,
             blob_base_fee_per_gas: None,
             priority_fee_per_gas,
+            fee_bump_policy,
+        })
+    }
+
+    fn fee_bump_parameters(policy: &dyn FeeBumpPolicy, bump_number: u32) -> serde_json::Value {
+        serde_json::json!({
+            "bump_number": bump_number,
+            "policy": policy.parameters(),
         })
     }
 
-    async fn increase_priority_fee(
+    /// Returns an error signaling that fees of a transaction cannot be bumped any further, so it shouldn't be resent.
+    /// The transaction must be handled manually in this case (e.g., by raising the cap in the config).
+    fn fee_bump_cap_error(eth_tx_id: u32, bump_number: u32) -> EthSenderError {
+        tracing::error!(
+            "Not resending operation {eth_tx_id}: fees cannot be bumped for the attempt #{bump_number} \
+             without exceeding `max_total_fee_bump_percent`"
+        );
+        METRICS.fee_bump_cap_reached.inc();
+        let err = ClientError::Custom("fee bump cap is reached".into());
+        EnrichedClientError::new(err, "calculate_fee")
+            .with_arg("eth_tx_id", &eth_tx_id)
+            .with_arg("bump_number", &bump_number)
+            .into()
+    }
+
+    /// Increases the priority fee of a transaction based on its sending attempts `history`,
+    /// which must be non-empty.
+    fn increase_priority_fee(
         &self,
-        storage: &mut Connection<'_, Core>,
         eth_tx_id: u32,
+        history: &[TxHistory],
         base_fee_per_gas: u64,
     ) -> Result<u64, EthSenderError> {
-        let previous_sent_tx = storage
-            .eth_sender_dal()
-            .get_last_sent_eth_tx(eth_tx_id)
-            .await
-            .unwrap()
-            .unwrap();
+        let first_sent_tx = &history[0];
+        let previous_sent_tx = &history[history.len() - 1];
 
         let previous_base_fee = previous_sent_tx.base_fee_per_gas;
-        let previous_priority_fee = previous_sent_tx.priority_fee_per_gas;
         let next_block_minimal_base_fee = self.gas_adjuster.get_next_block_minimal_base_fee();
 
         if base_fee_per_gas <= next_block_minimal_base_fee.min(previous_base_fee) {
@@ -231,9 +457,18 @@ impl EthTxManager {
             return Err(err.into());
         }
 
-        // Increase `priority_fee_per_gas` by at least 20% to prevent "replacement transaction under-priced" error.
-        Ok((previous_priority_fee + (previous_priority_fee / 5) + 1)
-            .max(self.gas_adjuster.get_priority_fee()))
+        // The policy is expected to increase `priority_fee_per_gas` enough to prevent
+        // "replacement transaction under-priced" error.
+        let bump_number = history.len() as u32;
+        let priority_fee_per_gas = self
+            .fee_bump_policy
+            .bump_fee(FeeBumpInput {
+                initial_fee: first_sent_tx.priority_fee_per_gas,
+                previous_fee: previous_sent_tx.priority_fee_per_gas,
+                bump_number,
+            })
+            .ok_or_else(|| Self::fee_bump_cap_error(eth_tx_id, bump_number))?;
+        Ok(priority_fee_per_gas.max(self.gas_adjuster.get_priority_fee()))
     }
 
     pub(crate) async fn send_eth_tx(
@@ -247,6 +482,7 @@ impl EthTxManager {
             base_fee_per_gas,
             priority_fee_per_gas,
             blob_base_fee_per_gas,
+            fee_bump_policy,
         } = self.calculate_fee(storage, tx, time_in_mempool).await?;
 
         METRICS.used_base_fee_per_gas.observe(base_fee_per_gas);
@@ -284,12 +520,20 @@ impl EthTxManager {
                 blob_base_fee_per_gas,
                 signed_tx.hash,
                 signed_tx.raw_tx.as_ref(),
+                fee_bump_policy.as_ref(),
             )
             .await
             .unwrap()
         {
             if let Err(error) = self
-                .send_raw_transaction(storage, tx_history_id, signed_tx.raw_tx, current_block)
+                .send_raw_transaction(
+                    storage,
+                    tx,
+                    tx_history_id,
+                    signed_tx.raw_tx,
+                    current_block,
+                    time_in_mempool,
+                )
                 .await
             {
                 tracing::warn!(
@@ -307,11 +551,34 @@ impl EthTxManager {
     async fn send_raw_transaction(
         &self,
         storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
         tx_history_id: u32,
         raw_tx: RawTransactionBytes,
         current_block: L1BlockNumber,
+        time_in_mempool: u32,
     ) -> Result<(), EthSenderError> {
-        match self.query_client().send_raw_tx(raw_tx).await {
+        let (client, channel) = self.submission_client(tx, time_in_mempool);
+        if channel == SubmissionChannel::PublicMempool && self.private_relay_client.is_some() {
+            tracing::info!(
+                "Submitting tx history item {tx_history_id} for tx {} to the public mempool: it's either \
+                 a blob transaction or wasn't mined via the private relay in time",
+                tx.id
+            );
+        }
+        METRICS.l1_tx_submissions[&channel].inc();
+
+        let mut send_result = client.send_raw_tx(raw_tx.clone()).await;
+        if let (Err(error), SubmissionChannel::PrivateRelay) = (&send_result, channel) {
+            tracing::warn!(
+                "Private relay failed to accept tx history item {tx_history_id} for tx {}: {error}; \
+                 submitting it to the public mempool",
+                tx.id
+            );
+            METRICS.l1_tx_submissions[&SubmissionChannel::PublicMempool].inc();
+            send_result = self.query_client().send_raw_tx(raw_tx).await;
+        }
+
+        match send_result {
             Ok(_) => {
                 storage
                     .eth_sender_dal()
@@ -335,19 +602,17 @@ impl EthTxManager {
         }
     }
 
-    async fn get_operator_nonce(
-        &self,
+    async fn get_nonce(
+        gateway: &dyn BoundEthInterface,
         block_numbers: L1BlockNumbers,
     ) -> Result<OperatorNonce, EthSenderError> {
-        let finalized = self
-            .ethereum_gateway
+        let finalized = gateway
             .nonce_at(block_numbers.finalized.0.into())
             .await?
             .as_u32()
             .into();
 
-        let latest = self
-            .ethereum_gateway
+        let latest = gateway
             .nonce_at(block_numbers.latest.0.into())
             .await?
             .as_u32()
@@ -355,26 +620,22 @@ impl EthTxManager {
         Ok(OperatorNonce { finalized, latest })
     }
 
+    async fn get_operator_nonce(
+        &self,
+        block_numbers: L1BlockNumbers,
+    ) -> Result<OperatorNonce, EthSenderError> {
+        Self::get_nonce(self.ethereum_gateway.as_ref(), block_numbers).await
+    }
+
     async fn get_blobs_operator_nonce(
         &self,
         block_numbers: L1BlockNumbers,
     ) -> Result<Option<OperatorNonce>, EthSenderError> {
         match &self.ethereum_gateway_blobs {
             None => Ok(None),
-            Some(gateway) => {
-                let finalized = gateway
-                    .nonce_at(block_numbers.finalized.0.into())
-                    .await?
-                    .as_u32()
-                    .into();
-
-                let latest = gateway
-                    .nonce_at(block_numbers.latest.0.into())
-                    .await?
-                    .as_u32()
-                    .into();
-                Ok(Some(OperatorNonce { finalized, latest }))
-            }
+            Some(gateway) => Ok(Some(
+                Self::get_nonce(gateway.as_ref(), block_numbers).await?,
+            )),
         }
     }
 
@@ -443,16 +704,59 @@ impl EthTxManager {
             if blobs_operator_address.is_none() {
                 panic!("blobs_operator_address has to be set its nonce is known; qed");
             }
-            Ok(self
+            if let Some(res) = self
                 .monitor_inflight_transactions_inner(
                     storage,
                     l1_block_numbers,
                     blobs_operator_nonce,
                     blobs_operator_address,
                 )
-                .await?)
-        } else {
-            Ok(None)
+                .await?
+            {
+                return Ok(Some(res));
+            }
+        }
+
+        for i in 0..self.successor_gateways.len() {
+            let gateway = self.successor_gateways[i].as_ref();
+            let successor_address = gateway.sender_account();
+            let successor_nonce = Self::get_nonce(gateway, l1_block_numbers).await?;
+            if let Some(res) = self
+                .monitor_inflight_transactions_inner(
+                    storage,
+                    l1_block_numbers,
+                    successor_nonce,
+                    Some(successor_address),
+                )
+                .await?
+            {
+                return Ok(Some(res));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Checks that all in-flight transactions are sent by one of the known operators. Transactions sent by other
+    /// operators (e.g., an operator replaced after key rotation) would not be tracked.
+    async fn check_inflight_tx_senders(&self, storage: &mut Connection<'_, Core>) {
+        let blobs_operator_address = self
+            .ethereum_gateway_blobs
+            .as_ref()
+            .map(|gateway| gateway.sender_account());
+        let inflight_txs = storage.eth_sender_dal().get_inflight_txs().await.unwrap();
+        for tx in inflight_txs {
+            let is_known_sender = tx.from_addr.is_none()
+                || tx.from_addr == blobs_operator_address
+                || self.successor_gateway(tx.from_addr).is_some();
+            if !is_known_sender {
+                tracing::error!(
+                    "In-flight eth_tx {} is sent by {:?}, which is not a configured operator; it will not be tracked. \
+                     If operator keys were rotated, the replaced operator must be kept configured until \
+                     all its transactions are finalized",
+                    tx.id,
+                    tx.from_addr
+                );
+            }
         }
     }
 
@@ -476,6 +780,8 @@ impl EthTxManager {
             operator_nonce.finalized,
         );
 
+        // Transactions with nonces consumed on L1 by transactions not tracked by us.
+        let mut externally_consumed_txs = vec![];
         // Not confirmed transactions, ordered by nonce
         for tx in inflight_txs {
             tracing::trace!(
@@ -493,6 +799,37 @@ impl EthTxManager {
             // that `tx` is not mined and we should resend it.
             // We only resend the first un-mined transaction.
             if operator_nonce.latest <= tx.nonce {
+                let has_gap = tx.nonce > operator_nonce.latest;
+                if has_gap || !externally_consumed_txs.is_empty() {
+                    if has_gap {
+                        tracing::error!(
+                            "Nonce gap detected for operator {operator_address:?}: on-chain nonce is {}, \
+                             but the first pending tx {} has nonce {}; it will never be mined",
+                            operator_nonce.latest,
+                            tx.id,
+                            tx.nonce
+                        );
+                        METRICS.nonce_divergences[&NonceDivergenceKind::Gap].inc();
+                    }
+                    let nonces_changed = self
+                        .heal_nonces(
+                            storage,
+                            l1_block_numbers.latest,
+                            operator_nonce,
+                            operator_address,
+                            &externally_consumed_txs,
+                        )
+                        .await?;
+                    // If nonces have changed, `tx` and following transactions will be resent with the new nonces
+                    // on the next L1 block. If there's a gap, `tx` cannot be mined, and resending it would only
+                    // keep it in the mempool, which prevents healing.
+                    if nonces_changed || has_gap {
+                        return Ok(None);
+                    }
+                } else {
+                    self.nonce_divergences.remove(&operator_address);
+                }
+
                 // None means txs hasn't been sent yet
                 let first_sent_at_block = storage
                     .eth_sender_dal()
@@ -528,16 +865,167 @@ impl EthTxManager {
                     // This is an error because such a big re-org may cause transactions that were
                     // previously recorded as confirmed to become pending again and we have to
                     // make sure it's not the case - otherwise `eth_sender` may not work properly.
+                    //
+                    // Alternatively, the nonce could be consumed by a transaction sent from the operator account
+                    // bypassing `eth_sender` (e.g., during manual intervention).
                     tracing::error!(
-                        "Possible block reorgs: finalized nonce increase detected, but no tx receipt found for tx {:?}",
+                        "Possible block reorgs or external nonce use: finalized nonce increase detected, \
+                         but no tx receipt found for tx {:?}",
                         &tx
                     );
+                    METRICS.nonce_divergences[&NonceDivergenceKind::ConsumedExternally].inc();
+                    externally_consumed_txs.push(tx);
                 }
             }
         }
+
+        if externally_consumed_txs.is_empty() {
+            self.nonce_divergences.remove(&operator_address);
+        } else {
+            self.heal_nonces(
+                storage,
+                l1_block_numbers.latest,
+                operator_nonce,
+                operator_address,
+                &externally_consumed_txs,
+            )
+            .await?;
+        }
         Ok(None)
     }
 
+    /// Heals divergence between the on-chain nonce of the operator and nonces of its unconfirmed transactions
+    /// tracked in Postgres. Unconfirmed transactions that are not mined (i.e., `externally_consumed_txs` and
+    /// all transactions with nonces not lower than the latest on-chain nonce) are renumbered so that their nonces
+    /// are contiguous and start from the latest on-chain nonce. Transactions with externally consumed nonces go first
+    /// since they were supposed to be mined earlier.
+    ///
+    /// Nonces are only healed once the divergence persists for `heal_nonce_divergence_after_blocks` L1 blocks,
+    /// and none of the renumbered transactions has a sending attempt known to the L1 node. Otherwise, an attempt
+    /// made with an old nonce could be mined after renumbering, leading to the transaction being executed twice.
+    ///
+    /// Returns whether any nonces were changed.
+    async fn heal_nonces(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        current_block: L1BlockNumber,
+        operator_nonce: OperatorNonce,
+        operator_address: Option<Address>,
+        externally_consumed_txs: &[EthTx],
+    ) -> Result<bool, EthSenderError> {
+        if !self.config.heal_nonce_divergence() {
+            tracing::warn!(
+                "Healing nonce divergence is disabled; manual intervention is required for operator {operator_address:?}"
+            );
+            return Ok(false);
+        }
+
+        let detected_at_block = *self
+            .nonce_divergences
+            .entry(operator_address)
+            .or_insert(current_block);
+        let persisted_for_blocks = current_block.0.saturating_sub(detected_at_block.0);
+        let required_blocks = self.config.heal_nonce_divergence_after_blocks();
+        if persisted_for_blocks < required_blocks {
+            tracing::info!(
+                "Nonce divergence for operator {operator_address:?} persists for {persisted_for_blocks} L1 blocks; \
+                 it will be healed after {required_blocks} blocks"
+            );
+            return Ok(false);
+        }
+
+        let mut transaction = storage.start_transaction().await.unwrap();
+        let unconfirmed_txs = transaction
+            .eth_sender_dal()
+            .get_unconfirmed_txs(operator_address)
+            .await
+            .unwrap();
+        let is_externally_consumed = |tx: &EthTx| {
+            externally_consumed_txs
+                .iter()
+                .any(|consumed| consumed.id == tx.id)
+        };
+        let (consumed_txs, other_txs): (Vec<_>, Vec<_>) = unconfirmed_txs
+            .into_iter()
+            .partition(is_externally_consumed);
+        // Transactions with nonces in `finalized..latest` are mined, but not finalized yet; they are left intact.
+        let pending_txs = other_txs
+            .iter()
+            .filter(|tx| tx.nonce >= operator_nonce.latest);
+        let renumbered_txs: Vec<_> = consumed_txs
+            .iter()
+            .chain(pending_txs)
+            .zip(operator_nonce.latest.0..)
+            .filter(|(tx, new_nonce)| tx.nonce != Nonce(*new_nonce))
+            .collect();
+
+        // `check_all_sending_attempts()` ignores errors when checking attempts; since renumbering a transaction
+        // that is mined or may be mined would lead to it being executed twice, we recheck all attempts propagating
+        // errors.
+        for (tx, _) in &renumbered_txs {
+            if self.has_known_sending_attempt(&mut transaction, tx).await? {
+                tracing::warn!(
+                    "Tx {} has a sending attempt known to the L1 node (mined or in the mempool); \
+                     not healing nonces for operator {operator_address:?} yet",
+                    tx.id
+                );
+                return Ok(false);
+            }
+        }
+
+        let healed_tx_count = renumbered_txs.len() as u64;
+        for (tx, new_nonce) in renumbered_txs {
+            let new_nonce = Nonce(new_nonce);
+            tracing::info!(
+                "Changing nonce of tx {} sent by operator {operator_address:?} from {} to {new_nonce}",
+                tx.id,
+                tx.nonce
+            );
+            transaction
+                .eth_sender_dal()
+                .set_nonce(tx.id, new_nonce)
+                .await
+                .unwrap();
+        }
+        transaction.commit().await.unwrap();
+        self.nonce_divergences.remove(&operator_address);
+
+        METRICS.healed_nonce_txs.inc_by(healed_tx_count);
+        tracing::info!(
+            "Healed nonce divergence for operator {operator_address:?}: renumbered {healed_tx_count} txs \
+             starting from on-chain nonce {}",
+            operator_nonce.latest
+        );
+        Ok(healed_tx_count > 0)
+    }
+
+    /// Checks whether any sending attempt of `tx` is known to the L1 node, i.e., is either mined or in the mempool.
+    async fn has_known_sending_attempt(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        tx: &EthTx,
+    ) -> Result<bool, EthSenderError> {
+        let history = storage
+            .eth_sender_dal()
+            .get_tx_history_to_check(tx.id)
+            .await
+            .unwrap();
+        for history_item in history {
+            if self.get_tx_status(history_item.tx_hash).await?.is_some() {
+                return Ok(true);
+            }
+            if self
+                .query_client()
+                .get_tx(history_item.tx_hash)
+                .await?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     async fn sign_tx(
         &self,
         tx: &EthTx,
@@ -548,15 +1036,19 @@ impl EthTxManager {
         // Chose the signing gateway. Use a custom one in case
         // the operator is in 4844 mode and the operation at hand is Commit.
         // then the optional gateway is used to send this transaction from a
-        // custom sender account.
-        let signing_gateway = if let Some(blobs_gateway) = self.ethereum_gateway_blobs.as_ref() {
+        // custom sender account. Transactions sent by successor operators
+        // during key rotation are signed by the corresponding gateway.
+        let signing_gateway = if let Some(successor_gateway) = self.successor_gateway(tx.from_addr)
+        {
+            successor_gateway
+        } else if let Some(blobs_gateway) = self.ethereum_gateway_blobs.as_ref() {
             if tx.tx_type == AggregatedActionType::Commit {
-                blobs_gateway
+                blobs_gateway.as_ref()
             } else {
-                &self.ethereum_gateway
+                self.ethereum_gateway.as_ref()
             }
         } else {
-            &self.ethereum_gateway
+            self.ethereum_gateway.as_ref()
         };
 
         signing_gateway
@@ -575,19 +1067,23 @@ impl EthTxManager {
                     } else {
                         Some(EIP_1559_TX_TYPE.into())
                     };
-                    opt.blob_versioned_hashes = tx.blob_sidecar.as_ref().map(|s| match s {
-                        EthTxBlobSidecar::EthTxBlobSidecarV1(s) => s
-                            .blobs
-                            .iter()
-                            .map(|blob| H256::from_slice(&blob.versioned_hash))
-                            .collect(),
-                    });
+                    opt.blob_versioned_hashes = Self::blob_versioned_hashes(tx);
                 }),
             )
             .await
             .expect("Failed to sign transaction")
     }
 
+    fn blob_versioned_hashes(tx: &EthTx) -> Option<Vec<H256>> {
+        tx.blob_sidecar.as_ref().map(|sidecar| match sidecar {
+            EthTxBlobSidecar::EthTxBlobSidecarV1(sidecar) => sidecar
+                .blobs
+                .iter()
+                .map(|blob| H256::from_slice(&blob.versioned_hash))
+                .collect(),
+        })
+    }
+
     async fn send_unsent_txs(
         &mut self,
         storage: &mut Connection<'_, Core>,
@@ -598,6 +1094,12 @@ impl EthTxManager {
             // The common reason for this behavior is that we sent tx and stop the server
             // before updating the database
             let tx_status = self.get_tx_status(tx.tx_hash).await;
+            let eth_tx = storage
+                .eth_sender_dal()
+                .get_eth_tx(tx.eth_tx_id)
+                .await
+                .unwrap()
+                .expect("Eth tx should exist");
 
             if let Ok(Some(tx_status)) = tx_status {
                 tracing::info!("The tx {:?} has been already sent", tx.tx_hash);
@@ -607,21 +1109,17 @@ impl EthTxManager {
                     .await
                     .unwrap();
 
-                let eth_tx = storage
-                    .eth_sender_dal()
-                    .get_eth_tx(tx.eth_tx_id)
-                    .await
-                    .unwrap()
-                    .expect("Eth tx should exist");
-
                 self.apply_tx_status(storage, &eth_tx, tx_status, l1_block_numbers.finalized)
                     .await;
             } else if let Err(error) = self
                 .send_raw_transaction(
                     storage,
+                    &eth_tx,
                     tx.id,
                     RawTransactionBytes::new_unchecked(tx.signed_raw_tx.clone()),
                     l1_block_numbers.latest,
+                    // Time since the transaction creation is still accounted for when choosing the submission channel.
+                    0,
                 )
                 .await
             {
@@ -693,11 +1191,25 @@ impl EthTxManager {
             .gas_used
             .expect("light ETH clients are not supported");
 
-        storage
+        let l1_cost = Self::l1_cost(&tx_status.receipt, gas_used);
+
+        let mut transaction = storage.start_transaction().await.unwrap();
+        transaction
             .eth_sender_dal()
             .confirm_tx(tx_status.tx_hash, gas_used)
             .await
             .unwrap();
+        if let Some(l1_cost) = &l1_cost {
+            transaction
+                .eth_sender_dal()
+                .insert_l1_batch_l1_costs(tx.id, tx.tx_type, l1_cost)
+                .await
+                .unwrap();
+        }
+        if self.insert_block_notifications {
+            Self::insert_l1_batch_status_notifications(&mut transaction, tx).await;
+        }
+        transaction.commit().await.unwrap();
 
         METRICS
             .track_eth_tx_metrics(storage, BlockL1Stage::Mined, tx)
@@ -718,6 +1230,9 @@ impl EthTxManager {
         );
         let tx_type_label = tx.tx_type.into();
         METRICS.l1_gas_used[&tx_type_label].observe(gas_used.low_u128() as f64);
+        if let Some(l1_cost) = &l1_cost {
+            METRICS.observe_l1_cost(tx.tx_type, l1_cost);
+        }
         METRICS.l1_tx_mined_latency[&tx_type_label].observe(Duration::from_secs(
             seconds_since_epoch() - tx.created_at_timestamp,
         ));
@@ -732,14 +1247,66 @@ impl EthTxManager {
         METRICS.l1_blocks_waited_in_mempool[&tx_type_label].observe(waited_blocks.into());
     }
 
+    /// Extracts L1 costs from the receipt of a mined transaction. Returns `None` if the receipt doesn't contain
+    /// the effective gas price (this is only possible for L1 nodes predating EIP-1559).
+    fn l1_cost(receipt: &TransactionReceipt, gas_used: U256) -> Option<EthTxL1Cost> {
+        let Some(gas_price) = receipt.effective_gas_price else {
+            tracing::warn!(
+                "Receipt for L1 transaction {:?} doesn't contain effective gas price; its L1 costs won't be recorded",
+                receipt.transaction_hash
+            );
+            return None;
+        };
+        Some(EthTxL1Cost {
+            gas_used: gas_used.as_u64(),
+            gas_price,
+            blob_gas_used: receipt.blob_gas_used.map_or(0, |gas| gas.as_u64()),
+            blob_gas_price: receipt.blob_gas_price.unwrap_or_default(),
+        })
+    }
+
+    async fn insert_l1_batch_status_notifications(storage: &mut Connection<'_, Core>, tx: &EthTx) {
+        let status = match tx.tx_type {
+            AggregatedActionType::Commit => L1BatchStatus::Committed,
+            AggregatedActionType::PublishProofOnchain => L1BatchStatus::Proven,
+            AggregatedActionType::Execute => L1BatchStatus::Executed,
+        };
+        let mut l1_batch_numbers: Vec<_> = storage
+            .blocks_dal()
+            .get_l1_batches_for_eth_tx_id(tx.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|header| header.number)
+            .collect();
+        l1_batch_numbers.sort_unstable();
+        let notifications: Vec<_> = l1_batch_numbers
+            .into_iter()
+            .map(|number| BlockNotification::L1BatchStatus { number, status })
+            .collect();
+        storage
+            .outbox_dal()
+            .insert_notifications(&notifications)
+            .await
+            .unwrap();
+    }
+
     pub async fn run(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let pool = self.pool.clone();
-        {
+        if self.config.is_dry_run() {
+            tracing::warn!(
+                "eth_tx_manager is running in the dry-run mode; transactions will be simulated, but not sent to L1"
+            );
+        } else {
+            let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
+            self.update_settlement_layer(&mut storage)
+                .await
+                .context("update_settlement_layer()")?;
             let l1_block_numbers = self
                 .get_l1_block_numbers()
                 .await
                 .context("get_l1_block_numbers()")?;
-            let mut storage = pool.connection_tagged("eth_sender").await.unwrap();
+            self.check_inflight_tx_senders(&mut storage).await;
             self.send_unsent_txs(&mut storage, l1_block_numbers).await;
         }
 
@@ -768,11 +1335,12 @@ impl EthTxManager {
         Ok(())
     }
 
+    /// Returns `true` if sending was interrupted because of a settlement layer switch.
     async fn send_new_eth_txs(
         &mut self,
         storage: &mut Connection<'_, Core>,
         current_block: L1BlockNumber,
-    ) {
+    ) -> Result<bool, EthSenderError> {
         let number_inflight_txs = storage
             .eth_sender_dal()
             .get_inflight_txs()
@@ -791,11 +1359,104 @@ impl EthTxManager {
                 .get_new_eth_txs(number_of_available_slots_for_eth_txs)
                 .await
                 .unwrap();
+            // The settlement layer may have been switched before the new transactions were created;
+            // such transactions must not be sent to the previous settlement layer.
+            if !new_eth_tx.is_empty() && self.update_settlement_layer(storage).await? {
+                return Ok(true);
+            }
 
             for tx in new_eth_tx {
                 let _ = self.send_eth_tx(storage, &tx, 0, current_block).await;
             }
         }
+        Ok(false)
+    }
+
+    /// Simulates transactions that were not sent to L1 using `eth_call` and persists simulation results.
+    ///
+    /// Each transaction is simulated against the current L1 state. Since transactions are never sent in the dry-run
+    /// mode, the state doesn't include effects of previously simulated transactions. Thus, only simulations
+    /// of transactions that don't depend on other unsent transactions are meaningful (e.g., the first commit
+    /// transaction after the dry-run mode is enabled); other simulations are expected to revert.
+    pub(super) async fn dry_run_new_eth_txs(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        current_block: L1BlockNumber,
+    ) -> Result<(), EthSenderError> {
+        let new_eth_txs = storage
+            .eth_sender_dal()
+            .get_eth_txs_to_dry_run(self.config.max_txs_in_flight)
+            .await
+            .unwrap();
+        for tx in new_eth_txs {
+            let dry_run = self.dry_run_tx(&tx, current_block).await?;
+            let outcome = match &dry_run.revert_reason {
+                None => {
+                    tracing::info!(
+                        "Dry run of tx {} succeeded on L1 block {current_block}",
+                        tx.id
+                    );
+                    DryRunOutcome::Success
+                }
+                Some(reason) => {
+                    tracing::warn!(
+                        "Dry run of tx {} reverted on L1 block {current_block}: {reason}",
+                        tx.id
+                    );
+                    DryRunOutcome::Revert
+                }
+            };
+            METRICS.dry_runs[&DryRunLabels {
+                op: tx.tx_type.into(),
+                outcome,
+            }]
+                .inc();
+            storage
+                .eth_sender_dal()
+                .insert_dry_run(&dry_run)
+                .await
+                .unwrap();
+        }
+        Ok(())
+    }
+
+    async fn dry_run_tx(
+        &self,
+        tx: &EthTx,
+        current_block: L1BlockNumber,
+    ) -> Result<EthTxDryRun, EthSenderError> {
+        let sender = tx
+            .from_addr
+            .unwrap_or_else(|| self.ethereum_gateway.sender_account());
+        let request = CallRequest {
+            from: Some(sender),
+            to: Some(tx.contract_address),
+            data: Some(tx.raw_tx.clone().into()),
+            // Blobs themselves cannot be passed to `eth_call`, but contracts only access their versioned hashes.
+            blob_versioned_hashes: Self::blob_versioned_hashes(tx),
+            ..CallRequest::default()
+        };
+        let block = BlockId::Number(BlockNumber::Number(current_block.0.into()));
+
+        let revert_reason = match self
+            .query_client()
+            .call_contract_function(request, Some(block))
+            .await
+        {
+            Ok(_) => None,
+            Err(err) => {
+                if let ClientError::Call(call_err) = err.as_ref() {
+                    Some(call_err.message().to_owned())
+                } else {
+                    return Err(err.into());
+                }
+            }
+        };
+        Ok(EthTxDryRun {
+            eth_tx_id: tx.id,
+            l1_block_number: current_block,
+            revert_reason,
+        })
     }
 
     #[tracing::instrument(skip(self, storage))]
@@ -804,10 +1465,24 @@ impl EthTxManager {
         storage: &mut Connection<'_, Core>,
         previous_block: L1BlockNumber,
     ) -> Result<L1BlockNumber, EthSenderError> {
+        if self.update_settlement_layer(storage).await? {
+            // Block numbers on the new settlement layer are unrelated to the previous ones.
+            return Ok(L1BlockNumber(0));
+        }
         let l1_block_numbers = self.get_l1_block_numbers().await?;
 
-        self.send_new_eth_txs(storage, l1_block_numbers.latest)
-            .await;
+        if self.config.is_dry_run() {
+            self.dry_run_new_eth_txs(storage, l1_block_numbers.latest)
+                .await?;
+            return Ok(l1_block_numbers.latest);
+        }
+
+        if self
+            .send_new_eth_txs(storage, l1_block_numbers.latest)
+            .await?
+        {
+            return Ok(L1BlockNumber(0));
+        }
 
         if l1_block_numbers.latest <= previous_block {
             // Nothing to do - no new blocks were mined.
@@ -820,6 +1495,15 @@ impl EthTxManager {
         {
             // New gas price depends on the time this tx spent in mempool.
             let time_in_mempool = l1_block_numbers.latest.0 - sent_at_block;
+            let blocks_before_first_fee_bump = self.config.blocks_before_first_fee_bump();
+            if time_in_mempool != 0 && time_in_mempool < blocks_before_first_fee_bump {
+                tracing::debug!(
+                    "Not resending tx {} yet: it has spent {time_in_mempool} blocks in mempool, \
+                     while the first fee bump happens after {blocks_before_first_fee_bump} blocks",
+                    tx.id
+                );
+                return Ok(l1_block_numbers.latest);
+            }
 
             // We don't want to return early in case resend does not succeed -
             // the error is logged anyway, but early returns will prevent
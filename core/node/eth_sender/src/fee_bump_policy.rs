@@ -0,0 +1,183 @@
+//! Policies for bumping fees of L1 transactions that are resent because they are not mined.
+
+use std::fmt;
+
+use zksync_config::configs::eth_sender::{FeeBumpStrategy, SenderConfig};
+
+/// Fees used in the previous sending attempts of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeBumpInput {
+    /// Fee used in the first sending attempt.
+    pub initial_fee: u64,
+    /// Fee used in the latest sending attempt.
+    pub previous_fee: u64,
+    /// 1-based number of the bump, i.e., the number of preceding sending attempts.
+    pub bump_number: u32,
+}
+
+/// Policy for bumping fees of resent L1 transactions. The policy is applied to each fee of a transaction
+/// (e.g., the priority fee and the blob base fee) separately.
+pub trait FeeBumpPolicy: fmt::Debug + Send + Sync {
+    /// Returns the fee for the next sending attempt. The returned fee may be further increased
+    /// to match the fee currently suggested by the gas adjuster. Returns `None` if the fee cannot be bumped
+    /// any further (e.g., because of a cap on the total fee increase), in which case the transaction
+    /// is not resent.
+    fn bump_fee(&self, input: FeeBumpInput) -> Option<u64>;
+
+    /// Returns parameters of this policy. Parameters are persisted in Postgres together with each sending attempt
+    /// using fees calculated by the policy.
+    fn parameters(&self) -> serde_json::Value;
+}
+
+/// [`FeeBumpPolicy`] configured using [`SenderConfig`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfiguredFeeBumpPolicy {
+    strategy: FeeBumpStrategy,
+    bump_percent: u64,
+    max_total_bump_percent: Option<u64>,
+}
+
+impl ConfiguredFeeBumpPolicy {
+    pub fn new(
+        strategy: FeeBumpStrategy,
+        bump_percent: u64,
+        max_total_bump_percent: Option<u64>,
+    ) -> Self {
+        Self {
+            strategy,
+            bump_percent,
+            max_total_bump_percent,
+        }
+    }
+
+    /// Creates a policy for transactions without blobs.
+    pub fn for_txs_without_blobs(config: &SenderConfig) -> Self {
+        Self::new(
+            config.fee_bump_strategy.unwrap_or_default(),
+            config.fee_bump_percent(),
+            config.max_total_fee_bump_percent,
+        )
+    }
+
+    /// Creates a policy for blob transactions.
+    pub fn for_blob_txs(config: &SenderConfig) -> Self {
+        Self::new(
+            config.blob_fee_bump_strategy.unwrap_or_default(),
+            config.blob_fee_bump_percent(),
+            config.max_total_fee_bump_percent,
+        )
+    }
+
+    /// Returns `percent`% of `value` rounded up, so that bumps are never lower than configured.
+    fn percent_of(value: u64, percent: u64) -> u64 {
+        let product = u128::from(value) * u128::from(percent);
+        u64::try_from(product.div_ceil(100)).unwrap_or(u64::MAX)
+    }
+}
+
+impl FeeBumpPolicy for ConfiguredFeeBumpPolicy {
+    fn bump_fee(&self, input: FeeBumpInput) -> Option<u64> {
+        let FeeBumpInput {
+            initial_fee,
+            previous_fee,
+            bump_number,
+        } = input;
+
+        let bumped_fee = match self.strategy {
+            FeeBumpStrategy::Linear => {
+                let total_bump_percent = self.bump_percent.saturating_mul(bump_number.into());
+                initial_fee.saturating_add(Self::percent_of(initial_fee, total_bump_percent))
+            }
+            FeeBumpStrategy::Exponential => {
+                previous_fee.saturating_add(Self::percent_of(previous_fee, self.bump_percent))
+            }
+        };
+        // Ensure that the fee is increased even if it's very small (e.g., zero).
+        let bumped_fee = bumped_fee.max(previous_fee.saturating_add(1));
+
+        if let Some(max_total_bump_percent) = self.max_total_bump_percent {
+            let max_fee =
+                initial_fee.saturating_add(Self::percent_of(initial_fee, max_total_bump_percent));
+            // A bump lower than configured would be rejected by L1 nodes, so there's no point in capping the fee.
+            (bumped_fee <= max_fee).then_some(bumped_fee)
+        } else {
+            Some(bumped_fee)
+        }
+    }
+
+    fn parameters(&self) -> serde_json::Value {
+        let strategy = match self.strategy {
+            FeeBumpStrategy::Linear => "linear",
+            FeeBumpStrategy::Exponential => "exponential",
+        };
+        serde_json::json!({
+            "strategy": strategy,
+            "bump_percent": self.bump_percent,
+            "max_total_bump_percent": self.max_total_bump_percent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(initial_fee: u64, previous_fee: u64, bump_number: u32) -> FeeBumpInput {
+        FeeBumpInput {
+            initial_fee,
+            previous_fee,
+            bump_number,
+        }
+    }
+
+    #[test]
+    fn linear_fee_bumps() {
+        let policy = ConfiguredFeeBumpPolicy::new(FeeBumpStrategy::Linear, 20, None);
+        assert_eq!(policy.bump_fee(input(100, 100, 1)), Some(120));
+        assert_eq!(policy.bump_fee(input(100, 120, 2)), Some(140));
+        assert_eq!(policy.bump_fee(input(100, 140, 3)), Some(160));
+        // The fee must increase even if the computed bump is zero.
+        assert_eq!(policy.bump_fee(input(0, 0, 1)), Some(1));
+    }
+
+    #[test]
+    fn exponential_fee_bumps() {
+        let policy = ConfiguredFeeBumpPolicy::new(FeeBumpStrategy::Exponential, 100, None);
+        assert_eq!(policy.bump_fee(input(100, 100, 1)), Some(200));
+        assert_eq!(policy.bump_fee(input(100, 200, 2)), Some(400));
+
+        let policy = ConfiguredFeeBumpPolicy::new(FeeBumpStrategy::Exponential, 20, None);
+        // Bumps are rounded up.
+        assert_eq!(policy.bump_fee(input(7, 7, 1)), Some(9));
+        assert_eq!(policy.bump_fee(input(0, 0, 1)), Some(1));
+        assert_eq!(policy.bump_fee(input(1, u64::MAX - 1, 10)), Some(u64::MAX));
+    }
+
+    #[test]
+    fn capped_fee_bumps() {
+        let policy = ConfiguredFeeBumpPolicy::new(FeeBumpStrategy::Exponential, 100, Some(300));
+        assert_eq!(policy.bump_fee(input(100, 100, 1)), Some(200));
+        assert_eq!(policy.bump_fee(input(100, 200, 2)), Some(400));
+        // The fee cannot be bumped without exceeding the cap.
+        assert_eq!(policy.bump_fee(input(100, 400, 3)), None);
+        assert_eq!(policy.bump_fee(input(100, 500, 4)), None);
+
+        let policy = ConfiguredFeeBumpPolicy::new(FeeBumpStrategy::Linear, 20, Some(50));
+        assert_eq!(policy.bump_fee(input(100, 100, 1)), Some(120));
+        assert_eq!(policy.bump_fee(input(100, 120, 2)), Some(140));
+        assert_eq!(policy.bump_fee(input(100, 140, 3)), None);
+    }
+
+    #[test]
+    fn fee_bump_policy_parameters() {
+        let policy = ConfiguredFeeBumpPolicy::new(FeeBumpStrategy::Linear, 15, Some(500));
+        assert_eq!(
+            policy.parameters(),
+            serde_json::json!({
+                "strategy": "linear",
+                "bump_percent": 15,
+                "max_total_bump_percent": 500,
+            })
+        );
+    }
+}
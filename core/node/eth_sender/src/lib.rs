@@ -3,7 +3,10 @@ mod aggregator;
 mod error;
 mod eth_tx_aggregator;
 mod eth_tx_manager;
+mod fee_bump_policy;
 mod metrics;
+mod pause;
+mod pubdata_da_selector;
 mod publish_criterion;
 mod utils;
 mod zksync_functions;
@@ -12,6 +15,14 @@ mod zksync_functions;
 mod tests;
 
 pub use self::{
-    aggregator::Aggregator, error::EthSenderError, eth_tx_aggregator::EthTxAggregator,
+    aggregator::Aggregator,
+    error::EthSenderError,
+    eth_tx_aggregator::EthTxAggregator,
     eth_tx_manager::EthTxManager,
+    fee_bump_policy::{ConfiguredFeeBumpPolicy, FeeBumpInput, FeeBumpPolicy},
+    pause::EthSenderPauseHandle,
+    publish_criterion::{
+        CriteriaAggregationPolicy, DataSizeCriterion, GasCriterion, L1BatchAggregationPolicy,
+        L1BatchPublishCriterion, NumberCriterion, TimestampDeadlineCriterion,
+    },
 };
@@ -5,7 +5,11 @@ use std::{fmt, time::Duration};
 use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
 use zksync_dal::{Connection, Core, CoreDal};
 use zksync_shared_metrics::{BlockL1Stage, BlockStage, APP_METRICS};
-use zksync_types::{aggregated_operations::AggregatedActionType, eth_sender::EthTx};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    eth_sender::{EthTx, EthTxL1Cost},
+    pubdata_da::PubdataDA,
+};
 use zksync_utils::time::seconds_since_epoch;
 
 use crate::eth_tx_manager::L1BlockNumbers;
@@ -45,6 +49,51 @@ impl fmt::Display for ActionTypeLabel {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "pubdata_da")]
+pub(super) struct PubdataDALabel(pub PubdataDA);
+
+impl fmt::Display for PubdataDALabel {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self.0 {
+            PubdataDA::Calldata => "calldata",
+            PubdataDA::Blobs => "blobs",
+            PubdataDA::Custom => "custom",
+        })
+    }
+}
+
+/// Channel used to submit an L1 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "channel", rename_all = "snake_case")]
+pub(super) enum SubmissionChannel {
+    PublicMempool,
+    PrivateRelay,
+}
+
+/// Kind of divergence between the on-chain nonce of an operator and nonces tracked in Postgres.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet, EncodeLabelValue)]
+#[metrics(label = "kind", rename_all = "snake_case")]
+pub(super) enum NonceDivergenceKind {
+    /// On-chain nonce is lower than the nonce of the first pending transaction, so it can never be mined.
+    Gap,
+    /// Nonce of a transaction was consumed on L1 by a transaction not tracked by `eth_sender`.
+    ConsumedExternally,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+pub(super) enum DryRunOutcome {
+    Success,
+    Revert,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+pub(super) struct DryRunLabels {
+    pub op: ActionTypeLabel,
+    pub outcome: DryRunOutcome,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
 pub(super) struct AggregationReasonLabels {
     r#type: &'static str,
@@ -82,6 +131,8 @@ pub(super) struct EthSenderMetrics {
     pub block_range_size: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of transactions resent by the Ethereum sender.
     pub transaction_resent: Counter,
+    /// Number of times a transaction wasn't resent because its fees cannot be bumped without exceeding the cap.
+    pub fee_bump_cap_reached: Counter,
     #[metrics(buckets = FEE_BUCKETS)]
     pub used_base_fee_per_gas: Histogram<u64>,
     #[metrics(buckets = FEE_BUCKETS)]
@@ -98,6 +149,26 @@ pub(super) struct EthSenderMetrics {
     pub l1_blocks_waited_in_mempool: Family<ActionTypeLabel, Histogram<u64>>,
     /// Number of L1 batches aggregated for publishing with a specific reason.
     pub block_aggregation_reason: Family<AggregationReasonLabels, Counter>,
+    /// Number of times the pubdata DA for commit transactions was switched, labeled by the new DA.
+    pub pubdata_da_switches: Family<PubdataDALabel, Counter>,
+    /// Number of L1 transaction submissions, labeled by the submission channel.
+    pub l1_tx_submissions: Family<SubmissionChannel, Counter>,
+    /// Number of detected divergences between on-chain and DB nonces of operators.
+    pub nonce_divergences: Family<NonceDivergenceKind, Counter>,
+    /// Number of transactions renumbered to heal nonce divergences.
+    pub healed_nonce_txs: Counter,
+    /// Number of transactions simulated in the dry-run mode.
+    pub dry_runs: Family<DryRunLabels, Counter>,
+    /// Effective gas price of mined transactions.
+    #[metrics(buckets = FEE_BUCKETS)]
+    pub l1_effective_gas_price: Family<ActionTypeLabel, Histogram<u64>>,
+    /// Blob gas used by mined transactions with blobs.
+    #[metrics(buckets = Buckets::exponential(131_072.0..=786_432.0, 2.0))]
+    pub l1_blob_gas_used: Family<ActionTypeLabel, Histogram<u64>>,
+    /// Total L1 cost of mined transactions (in gwei). Per-batch costs are persisted in Postgres
+    /// and are available via the admin API.
+    #[metrics(buckets = Buckets::exponential(1_000.0..=1e10, 10.0))]
+    pub l1_tx_cost_gwei: Family<ActionTypeLabel, Histogram<f64>>,
 }
 
 impl EthSenderMetrics {
@@ -109,6 +180,16 @@ impl EthSenderMetrics {
         self.last_known_l1_block[&BlockNumberVariant::Safe].set(l1_block_numbers.safe.0 as usize);
     }
 
+    pub fn observe_l1_cost(&self, tx_type: AggregatedActionType, cost: &EthTxL1Cost) {
+        let tx_type_label = tx_type.into();
+        self.l1_effective_gas_price[&tx_type_label].observe(cost.gas_price.low_u64());
+        if cost.blob_gas_used > 0 {
+            self.l1_blob_gas_used[&tx_type_label].observe(cost.blob_gas_used);
+        }
+        let cost_gwei = cost.total_cost().low_u128() as f64 / 1e9;
+        self.l1_tx_cost_gwei[&tx_type_label].observe(cost_gwei);
+    }
+
     pub async fn track_eth_tx_metrics(
         &self,
         connection: &mut Connection<'_, Core>,
@@ -0,0 +1,208 @@
+//! Pausing creation of L1 transactions at runtime.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+};
+
+use async_trait::async_trait;
+use zksync_config::configs::eth_sender::SenderConfig;
+use zksync_dal::{Connection, Core, CoreDal, SqlxError};
+use zksync_health_check::{CheckHealth, Health, HealthStatus};
+use zksync_types::aggregated_operations::AggregatedActionType;
+
+const ALL_OPERATIONS: [AggregatedActionType; 3] = [
+    AggregatedActionType::Commit,
+    AggregatedActionType::PublishProofOnchain,
+    AggregatedActionType::Execute,
+];
+
+/// Handle allowing to pause and resume creation of L1 transactions of specific types (commit, prove or execute)
+/// without restarting the node, e.g., while handling an L1 incident or an upgrade. While an operation is paused,
+/// the aggregator doesn't create new transactions for it; other operations are unaffected, and transactions
+/// already created are still sent (they are sent in the nonce order, so skipping them would block all
+/// subsequent transactions).
+///
+/// The paused state is persisted in Postgres, so it survives node restarts, and is cached by the handle.
+/// The cache is shared among all clones of the handle and is refreshed by the aggregator on each iteration.
+/// If any operation is paused, the handle reports [`HealthStatus::Affected`] as a health check.
+#[derive(Debug, Clone)]
+pub struct EthSenderPauseHandle {
+    paused: Arc<RwLock<HashSet<AggregatedActionType>>>,
+    paused_by_config: Arc<[AggregatedActionType]>,
+}
+
+impl EthSenderPauseHandle {
+    /// Creates a handle with operations initially paused according to the config. Operations paused
+    /// by the config are persisted once the handle is initialized; resuming them doesn't require changing the config.
+    pub fn new(config: &SenderConfig) -> Self {
+        let paused = [
+            (AggregatedActionType::Commit, config.pause_commit),
+            (
+                AggregatedActionType::PublishProofOnchain,
+                config.pause_prove,
+            ),
+            (AggregatedActionType::Execute, config.pause_execute),
+        ];
+        let paused_by_config: Arc<[_]> = paused
+            .into_iter()
+            .filter_map(|(operation, is_paused)| is_paused.unwrap_or(false).then_some(operation))
+            .collect();
+        if !paused_by_config.is_empty() {
+            tracing::warn!("Operations {paused_by_config:?} are paused by the config");
+        }
+        Self {
+            paused: Arc::new(RwLock::new(paused_by_config.iter().copied().collect())),
+            paused_by_config,
+        }
+    }
+
+    /// Persists operations paused by the config and loads the persisted paused state.
+    pub(crate) async fn initialize(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), SqlxError> {
+        for &operation in self.paused_by_config.iter() {
+            storage.eth_sender_dal().pause_operation(operation).await?;
+        }
+        self.refresh(storage).await
+    }
+
+    /// Loads the persisted paused state, e.g. to take into account operations paused or resumed by another node.
+    pub async fn refresh(&self, storage: &mut Connection<'_, Core>) -> Result<(), SqlxError> {
+        let paused: HashSet<_> = storage
+            .eth_sender_dal()
+            .get_paused_operations()
+            .await?
+            .into_iter()
+            .collect();
+        *self.paused.write().expect("paused operations are poisoned") = paused;
+        Ok(())
+    }
+
+    pub fn is_paused(&self, operation: AggregatedActionType) -> bool {
+        self.paused
+            .read()
+            .expect("paused operations are poisoned")
+            .contains(&operation)
+    }
+
+    /// Returns currently paused operations in the order they are applied to L1 batches.
+    pub fn paused_operations(&self) -> Vec<AggregatedActionType> {
+        let paused = self.paused.read().expect("paused operations are poisoned");
+        ALL_OPERATIONS
+            .into_iter()
+            .filter(|operation| paused.contains(operation))
+            .collect()
+    }
+
+    /// Pauses the specified operation. Returns whether the operation was already paused.
+    pub async fn pause(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        operation: AggregatedActionType,
+    ) -> Result<bool, SqlxError> {
+        let was_paused = storage.eth_sender_dal().pause_operation(operation).await?;
+        self.paused
+            .write()
+            .expect("paused operations are poisoned")
+            .insert(operation);
+        if !was_paused {
+            tracing::warn!("Paused creating {operation} transactions");
+        }
+        Ok(was_paused)
+    }
+
+    /// Resumes the specified operation. Returns whether the operation was paused.
+    pub async fn resume(
+        &self,
+        storage: &mut Connection<'_, Core>,
+        operation: AggregatedActionType,
+    ) -> Result<bool, SqlxError> {
+        let was_paused = storage.eth_sender_dal().resume_operation(operation).await?;
+        self.paused
+            .write()
+            .expect("paused operations are poisoned")
+            .remove(&operation);
+        if was_paused {
+            tracing::info!("Resumed creating {operation} transactions");
+        }
+        Ok(was_paused)
+    }
+}
+
+#[async_trait]
+impl CheckHealth for EthSenderPauseHandle {
+    fn name(&self) -> &'static str {
+        "eth_sender_pause"
+    }
+
+    async fn check_health(&self) -> Health {
+        let paused_operations = self.paused_operations();
+        if paused_operations.is_empty() {
+            return HealthStatus::Ready.into();
+        }
+        let paused_operations: Vec<_> = paused_operations
+            .into_iter()
+            .map(AggregatedActionType::as_str)
+            .collect();
+        Health::from(HealthStatus::Affected).with_details(serde_json::json!({
+            "paused_operations": paused_operations,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_config::configs::eth_sender::EthConfig;
+    use zksync_dal::ConnectionPool;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn pausing_and_resuming_operations() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        let config = SenderConfig {
+            pause_execute: Some(true),
+            ..EthConfig::for_tests().sender.unwrap()
+        };
+        let handle = EthSenderPauseHandle::new(&config);
+        assert_eq!(handle.paused_operations(), [AggregatedActionType::Execute]);
+        handle.initialize(&mut storage).await.unwrap();
+        assert_eq!(handle.paused_operations(), [AggregatedActionType::Execute]);
+        assert!(!handle.is_paused(AggregatedActionType::Commit));
+
+        let handle_clone = handle.clone();
+        let commit = AggregatedActionType::Commit;
+        assert!(!handle_clone.pause(&mut storage, commit).await.unwrap());
+        assert!(handle_clone.pause(&mut storage, commit).await.unwrap());
+        assert_eq!(
+            handle.paused_operations(),
+            [AggregatedActionType::Commit, AggregatedActionType::Execute]
+        );
+        let health = handle.check_health().await;
+        assert_eq!(health.status(), HealthStatus::Affected);
+
+        // The paused state must be persisted.
+        let config = EthConfig::for_tests().sender.unwrap();
+        let new_handle = EthSenderPauseHandle::new(&config);
+        assert!(new_handle.paused_operations().is_empty());
+        new_handle.initialize(&mut storage).await.unwrap();
+        assert_eq!(
+            new_handle.paused_operations(),
+            [AggregatedActionType::Commit, AggregatedActionType::Execute]
+        );
+
+        let execute = AggregatedActionType::Execute;
+        assert!(handle.resume(&mut storage, commit).await.unwrap());
+        assert!(handle.resume(&mut storage, execute).await.unwrap());
+        assert!(!handle.resume(&mut storage, execute).await.unwrap());
+        assert!(handle.paused_operations().is_empty());
+        let health = handle.check_health().await;
+        assert_eq!(health.status(), HealthStatus::Ready);
+
+        new_handle.refresh(&mut storage).await.unwrap();
+        assert!(new_handle.paused_operations().is_empty());
+    }
+}
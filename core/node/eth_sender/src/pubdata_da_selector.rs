@@ -0,0 +1,206 @@
+//! Dynamic choice of the pubdata DA for commit transactions.
+
+use std::sync::Arc;
+
+use zksync_l1_contract_interface::i_executor::commit::kzg::ZK_SYNC_BYTES_PER_BLOB;
+use zksync_node_fee_model::l1_gas_price::L1TxParamsProvider;
+use zksync_types::{pubdata_da::PubdataDA, L1_GAS_PER_PUBDATA_BYTE};
+
+use crate::metrics::{PubdataDALabel, METRICS};
+
+/// Amount of blob gas consumed by a single blob (`GAS_PER_BLOB` from EIP-4844).
+const GAS_PER_BLOB: u128 = 1 << 17;
+
+/// Estimated L1 costs (in wei) of publishing pubdata of a certain size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PubdataCosts {
+    calldata: u128,
+    blobs: u128,
+}
+
+impl PubdataCosts {
+    fn new(pubdata_len: usize, base_fee: u64, blob_base_fee: u64) -> Self {
+        let pubdata_len = pubdata_len as u128;
+        // Blobs are paid for in full, regardless of how much data they actually contain.
+        let blob_count = pubdata_len.div_ceil(ZK_SYNC_BYTES_PER_BLOB as u128).max(1);
+        Self {
+            calldata: pubdata_len * u128::from(L1_GAS_PER_PUBDATA_BYTE) * u128::from(base_fee),
+            blobs: blob_count * GAS_PER_BLOB * u128::from(blob_base_fee),
+        }
+    }
+
+    /// Estimates costs of publishing pubdata of several L1 batches. Since each blob-based commit transaction
+    /// publishes pubdata of a single L1 batch, blob costs are estimated for each batch separately.
+    fn for_batches(pubdata_lens: &[usize], base_fee: u64, blob_base_fee: u64) -> Self {
+        pubdata_lens.iter().fold(
+            Self {
+                calldata: 0,
+                blobs: 0,
+            },
+            |acc, &pubdata_len| {
+                let costs = Self::new(pubdata_len, base_fee, blob_base_fee);
+                Self {
+                    calldata: acc.calldata + costs.calldata,
+                    blobs: acc.blobs + costs.blobs,
+                }
+            },
+        )
+    }
+
+    fn get(&self, pubdata_da: PubdataDA) -> u128 {
+        match pubdata_da {
+            PubdataDA::Calldata => self.calldata,
+            PubdataDA::Blobs => self.blobs,
+            PubdataDA::Custom => unreachable!("custom pubdata DA is never selected dynamically"),
+        }
+    }
+}
+
+/// Chooses between calldata and blobs for each commit transaction based on the current L1 prices.
+///
+/// To prevent flapping between DAs if prices are close, the choice is only switched if the other DA is cheaper
+/// than the current one by the configured relative margin.
+#[derive(Debug)]
+pub(crate) struct PubdataDASelector {
+    gas_adjuster: Arc<dyn L1TxParamsProvider>,
+    hysteresis: f64,
+    current: PubdataDA,
+}
+
+impl PubdataDASelector {
+    pub fn new(
+        gas_adjuster: Arc<dyn L1TxParamsProvider>,
+        initial: PubdataDA,
+        hysteresis: f64,
+    ) -> Self {
+        Self {
+            gas_adjuster,
+            hysteresis,
+            current: initial,
+        }
+    }
+
+    /// Selects DA for committing L1 batches with the specified pubdata lengths (in bytes). The choice is made
+    /// for all batches at once, since the batches committed via calldata in a single transaction would otherwise
+    /// need to be committed one by one via blobs.
+    pub fn select(&mut self, pubdata_lens: &[usize]) -> PubdataDA {
+        let base_fee = self.gas_adjuster.get_base_fee(0);
+        let blob_base_fee = self.gas_adjuster.get_blob_base_fee();
+        let costs = PubdataCosts::for_batches(pubdata_lens, base_fee, blob_base_fee);
+
+        let other = match self.current {
+            PubdataDA::Calldata => PubdataDA::Blobs,
+            PubdataDA::Blobs => PubdataDA::Calldata,
+            PubdataDA::Custom => return PubdataDA::Custom,
+        };
+        let (current_cost, other_cost) = (costs.get(self.current), costs.get(other));
+        #[allow(clippy::cast_precision_loss)] // acceptable for comparing costs
+        let should_switch = (other_cost as f64) * (1.0 + self.hysteresis) < current_cost as f64;
+        if should_switch {
+            let total_pubdata_len: usize = pubdata_lens.iter().sum();
+            tracing::info!(
+                "Switching pubdata DA for commit transactions from {:?} to {other:?}: estimated costs for \
+                 {total_pubdata_len} bytes of pubdata in {} L1 batch(es) are {current_cost} wei vs {other_cost} wei \
+                 (base fee: {base_fee}, blob base fee: {blob_base_fee})",
+                self.current,
+                pubdata_lens.len()
+            );
+            METRICS.pubdata_da_switches[&PubdataDALabel(other)].inc();
+            self.current = other;
+        }
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct MockL1TxParams {
+        base_fee: u64,
+        blob_base_fee: u64,
+    }
+
+    impl L1TxParamsProvider for MockL1TxParams {
+        fn get_base_fee(&self, _time_in_mempool: u32) -> u64 {
+            self.base_fee
+        }
+
+        fn get_blob_base_fee(&self) -> u64 {
+            self.blob_base_fee
+        }
+
+        fn get_priority_fee(&self) -> u64 {
+            unimplemented!()
+        }
+
+        fn get_next_block_minimal_base_fee(&self) -> u64 {
+            unimplemented!()
+        }
+    }
+
+    fn create_selector(base_fee: u64, blob_base_fee: u64, initial: PubdataDA) -> PubdataDASelector {
+        let gas_adjuster = Arc::new(MockL1TxParams {
+            base_fee,
+            blob_base_fee,
+        });
+        PubdataDASelector::new(gas_adjuster, initial, 0.1)
+    }
+
+    #[test]
+    fn estimating_pubdata_costs() {
+        let costs = PubdataCosts::new(1_000, 10, 1);
+        assert_eq!(costs.calldata, 1_000 * 17 * 10);
+        assert_eq!(costs.blobs, GAS_PER_BLOB);
+
+        let costs = PubdataCosts::new(ZK_SYNC_BYTES_PER_BLOB + 1, 10, 1);
+        assert_eq!(costs.blobs, 2 * GAS_PER_BLOB);
+    }
+
+    #[test]
+    fn switching_to_cheaper_da() {
+        let pubdata_len = ZK_SYNC_BYTES_PER_BLOB;
+        // Blobs are much cheaper.
+        let mut selector = create_selector(10, 1, PubdataDA::Calldata);
+        assert_eq!(selector.select(&[pubdata_len]), PubdataDA::Blobs);
+        // Calldata is much cheaper.
+        let mut selector = create_selector(1, 1_000, PubdataDA::Blobs);
+        assert_eq!(selector.select(&[pubdata_len]), PubdataDA::Calldata);
+        // Small pubdata is cheaper to publish via calldata, since blobs are paid in full.
+        let mut selector = create_selector(10, 10, PubdataDA::Blobs);
+        assert_eq!(selector.select(&[100]), PubdataDA::Calldata);
+    }
+
+    #[test]
+    fn hysteresis_prevents_flapping() {
+        let pubdata_len = ZK_SYNC_BYTES_PER_BLOB;
+        let calldata_gas = pubdata_len as u64 * u64::from(L1_GAS_PER_PUBDATA_BYTE);
+        let blob_gas = GAS_PER_BLOB as u64;
+        // Choose the blob base fee so that blobs are ~5% cheaper than calldata.
+        let base_fee = 1_000;
+        let blob_base_fee = calldata_gas * base_fee * 95 / 100 / blob_gas;
+
+        let mut selector = create_selector(base_fee, blob_base_fee, PubdataDA::Calldata);
+        assert_eq!(selector.select(&[pubdata_len]), PubdataDA::Calldata);
+        let mut selector = create_selector(base_fee, blob_base_fee, PubdataDA::Blobs);
+        assert_eq!(selector.select(&[pubdata_len]), PubdataDA::Blobs);
+    }
+
+    #[test]
+    fn selecting_da_for_multiple_batches() {
+        let calldata_gas = ZK_SYNC_BYTES_PER_BLOB as u64 * u64::from(L1_GAS_PER_PUBDATA_BYTE);
+        let blob_gas = GAS_PER_BLOB as u64;
+        // Choose the blob base fee so that blobs are ~40% cheaper than calldata for a full blob.
+        let base_fee = 10;
+        let blob_base_fee = calldata_gas * base_fee * 60 / 100 / blob_gas;
+
+        let mut selector = create_selector(base_fee, blob_base_fee, PubdataDA::Blobs);
+        assert_eq!(selector.select(&[ZK_SYNC_BYTES_PER_BLOB]), PubdataDA::Blobs);
+        // The second batch with small pubdata would require a separate blob, which makes calldata cheaper overall.
+        assert_eq!(
+            selector.select(&[ZK_SYNC_BYTES_PER_BLOB, 100]),
+            PubdataDA::Calldata
+        );
+    }
+}
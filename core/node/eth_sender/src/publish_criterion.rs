@@ -14,6 +14,69 @@ use zksync_types::{
 
 use super::{metrics::METRICS, utils::agg_l1_batch_base_cost};
 
+/// Policy deciding which L1 batches are published in a single L1 transaction of a certain type
+/// (commit, prove or execute).
+#[async_trait]
+pub trait L1BatchAggregationPolicy: fmt::Debug + Send + Sync {
+    /// Returns a prefix of `unpublished_l1_batches` that should be published in a single L1 transaction,
+    /// or `None` if L1 batches shouldn't be published yet.
+    async fn select_l1_batches(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        unpublished_l1_batches: Vec<L1BatchWithMetadata>,
+        last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<Vec<L1BatchWithMetadata>>;
+}
+
+/// [`L1BatchAggregationPolicy`] publishing L1 batches once any of its criteria is triggered.
+/// If several criteria are triggered, the shortest of their L1 batch ranges is published.
+#[derive(Debug, Default)]
+pub struct CriteriaAggregationPolicy {
+    criteria: Vec<Box<dyn L1BatchPublishCriterion>>,
+}
+
+impl CriteriaAggregationPolicy {
+    pub fn new(criteria: Vec<Box<dyn L1BatchPublishCriterion>>) -> Self {
+        Self { criteria }
+    }
+
+    /// Adds a criterion to this policy.
+    #[must_use]
+    pub fn with_criterion(mut self, criterion: impl L1BatchPublishCriterion + 'static) -> Self {
+        self.criteria.push(Box::new(criterion));
+        self
+    }
+}
+
+#[async_trait]
+impl L1BatchAggregationPolicy for CriteriaAggregationPolicy {
+    async fn select_l1_batches(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        unpublished_l1_batches: Vec<L1BatchWithMetadata>,
+        last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<Vec<L1BatchWithMetadata>> {
+        let mut last_l1_batch: Option<L1BatchNumber> = None;
+        for criterion in &mut self.criteria {
+            let l1_batch_by_criterion = criterion
+                .last_l1_batch_to_publish(storage, &unpublished_l1_batches, last_sealed_l1_batch)
+                .await;
+            if let Some(l1_batch) = l1_batch_by_criterion {
+                last_l1_batch = Some(last_l1_batch.map_or(l1_batch, |number| number.min(l1_batch)));
+            }
+        }
+
+        let last_l1_batch = last_l1_batch?;
+        Some(
+            unpublished_l1_batches
+                .into_iter()
+                .take_while(|l1_batch| l1_batch.header.number <= last_l1_batch)
+                .collect(),
+        )
+    }
+}
+
+/// Criterion used by [`CriteriaAggregationPolicy`].
 #[async_trait]
 pub trait L1BatchPublishCriterion: fmt::Debug + Send + Sync {
     #[allow(dead_code)]
@@ -4,17 +4,26 @@ use assert_matches::assert_matches;
 use once_cell::sync::Lazy;
 use test_casing::{test_casing, Product};
 use zksync_config::{
-    configs::eth_sender::{ProofSendingMode, PubdataSendingMode, SenderConfig},
+    configs::eth_sender::{
+        L1BatchAggregationCriterion, ProofSendingMode, PubdataSendingMode, SenderConfig,
+    },
     ContractsConfig, EthConfig, GasAdjusterConfig,
 };
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_eth_client::{clients::MockEthereum, EthInterface};
-use zksync_l1_contract_interface::i_executor::methods::{ExecuteBatches, ProveBatches};
+use zksync_l1_contract_interface::{
+    i_executor::{
+        methods::{ExecuteBatches, ProveBatches},
+        structures::CommitBatchInfo,
+    },
+    Tokenizable,
+};
 use zksync_node_fee_model::l1_gas_price::GasAdjuster;
 use zksync_node_test_utils::{create_l1_batch, l1_batch_metadata_to_commitment_artifacts};
 use zksync_object_store::MockObjectStore;
 use zksync_types::{
+    aggregated_operations::AggregatedActionType,
     block::L1BatchHeader,
     commitment::{
         L1BatchCommitmentMode, L1BatchMetaParameters, L1BatchMetadata, L1BatchWithMetadata,
@@ -22,14 +31,17 @@ use zksync_types::{
     ethabi::Token,
     helpers::unix_timestamp_ms,
     l2_to_l1_log::{L2ToL1Log, UserL2ToL1Log},
-    pubdata_da::PubdataDA,
+    pubdata_da::{PubdataDA, PubdataEncoding},
+    settlement::{SettlementLayer, SettlementLayerSwitch},
     web3::contract::Error,
-    Address, L1BatchNumber, ProtocolVersion, ProtocolVersionId, H256,
+    Address, L1BatchNumber, L1BlockNumber, L1ChainId, Nonce, ProtocolVersion, ProtocolVersionId,
+    H256,
 };
 
 use crate::{
     aggregated_operations::AggregatedOperation, eth_tx_manager::L1BlockNumbers, Aggregator,
-    EthSenderError, EthTxAggregator, EthTxManager,
+    CriteriaAggregationPolicy, EthSenderError, EthTxAggregator, EthTxManager,
+    L1BatchAggregationPolicy, NumberCriterion,
 };
 
 // Alias to conveniently call static methods of `ETHSender`.
@@ -253,6 +265,7 @@ fn default_l1_batch_metadata() -> L1BatchMetadata {
         events_queue_commitment: Some(H256::zero()),
         bootloader_initial_content_commitment: Some(H256::zero()),
         state_diffs_compressed: vec![],
+        da_inclusion_data: None,
     }
 }
 
@@ -492,6 +505,21 @@ async fn resend_each_block(commitment_mode: L1BatchCommitmentMode) -> anyhow::Re
         30.into() // `5 * 3 * 2^1`
     );
 
+    // check that the fee bump is recorded in the tx history
+    let history = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_tx_history(tx.id)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].fee_bump_policy, None);
+    let fee_bump_policy = history[1].fee_bump_policy.as_ref().unwrap();
+    assert_eq!(fee_bump_policy["bump_number"], 1);
+    assert_eq!(fee_bump_policy["policy"]["strategy"], "exponential");
+    assert!(history[1].priority_fee_per_gas > history[0].priority_fee_per_gas);
+
     Ok(())
 }
 
@@ -597,6 +625,183 @@ async fn dont_resend_already_mined(commitment_mode: L1BatchCommitmentMode) -> an
     Ok(())
 }
 
+#[tokio::test]
+async fn healing_nonce_gap() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    let config = SenderConfig {
+        heal_nonce_divergence: Some(true),
+        heal_nonce_divergence_after_blocks: Some(2),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    tester.manager = EthTxManager::new(
+        connection_pool.clone(),
+        config,
+        tester.gas_adjuster.clone(),
+        tester.gateway.clone(),
+        None,
+    );
+
+    // Emulate a transaction with a nonce ahead of the on-chain one (which is 0).
+    let tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .save_eth_tx(
+            3,
+            vec![],
+            AggregatedActionType::Commit,
+            Address::random(),
+            100,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let tx_hash = tester
+        .manager
+        .send_eth_tx(
+            &mut tester.conn.connection().await.unwrap(),
+            &tx,
+            0,
+            tester.get_block_numbers().await.latest,
+        )
+        .await
+        .unwrap();
+
+    // The divergence must persist for the configured number of blocks, and the tx must not be in the mempool.
+    for _ in 0..3 {
+        let to_resend = tester
+            .manager
+            .monitor_inflight_transactions(
+                &mut tester.conn.connection().await.unwrap(),
+                tester.get_block_numbers().await,
+            )
+            .await?;
+        // The tx cannot be mined because of the gap, so it's not resent.
+        assert!(to_resend.is_none());
+        let tx = tester
+            .storage()
+            .await
+            .eth_sender_dal()
+            .get_eth_tx(tx.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(tx.nonce, Nonce(3));
+        tester.gateway.advance_block_number(1);
+    }
+    assert_eq!(tester.gateway.sent_tx_count(), 1);
+
+    tester.gateway.drop_tx(tx_hash);
+    let to_resend = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.connection().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?;
+    assert!(to_resend.is_none());
+    let healed_tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .get_eth_tx(tx.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(healed_tx.nonce, Nonce(0));
+
+    // The healed transaction should be resent with the new nonce.
+    tester.gateway.advance_block_number(1);
+    let (to_resend, _) = tester
+        .manager
+        .monitor_inflight_transactions(
+            &mut tester.conn.connection().await.unwrap(),
+            tester.get_block_numbers().await,
+        )
+        .await?
+        .expect("healed tx should be resent");
+    assert_eq!(to_resend.id, tx.id);
+    assert_eq!(to_resend.nonce, Nonce(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn dry_run_mode() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+    let config = SenderConfig {
+        dry_run: Some(true),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    let manager = EthTxManager::new(
+        connection_pool.clone(),
+        config,
+        tester.gas_adjuster.clone(),
+        tester.gateway.clone(),
+        None,
+    );
+
+    // The mock L1 client only handles calls to Multicall3.
+    let contract_address = ContractsConfig::for_tests().l1_multicall3_addr;
+    let tx = tester
+        .storage()
+        .await
+        .eth_sender_dal()
+        .save_eth_tx(
+            0,
+            vec![],
+            AggregatedActionType::Commit,
+            contract_address,
+            100,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    let latest_block = tester.get_block_numbers().await.latest;
+    manager
+        .dry_run_new_eth_txs(&mut tester.storage().await, latest_block)
+        .await?;
+    assert_eq!(tester.gateway.sent_tx_count(), 0);
+
+    let mut storage = tester.storage().await;
+    let dry_run = storage
+        .eth_sender_dal()
+        .get_dry_run(tx.id)
+        .await
+        .unwrap()
+        .expect("no dry run result");
+    assert_eq!(dry_run.l1_block_number, latest_block);
+    assert_eq!(dry_run.revert_reason, None);
+    // Transactions are simulated only once.
+    let txs_to_dry_run = storage
+        .eth_sender_dal()
+        .get_eth_txs_to_dry_run(10)
+        .await
+        .unwrap();
+    assert!(txs_to_dry_run.is_empty());
+
+    Ok(())
+}
+
 #[test_casing(2, COMMITMENT_MODES)]
 #[tokio::test]
 async fn three_scenarios(commitment_mode: L1BatchCommitmentMode) -> anyhow::Result<()> {
@@ -1123,6 +1328,270 @@ async fn get_multicall_data(commitment_mode: L1BatchCommitmentMode) {
     assert!(multicall_data.is_ok());
 }
 
+#[tokio::test]
+async fn criteria_aggregation_policy() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let l1_batches: Vec<_> = (1..=5)
+        .map(|number| l1_batch_with_metadata(create_l1_batch(number)))
+        .collect();
+
+    let mut policy = CriteriaAggregationPolicy::default();
+    let selected = policy
+        .select_l1_batches(&mut storage, l1_batches.clone(), L1BatchNumber(5))
+        .await;
+    assert!(selected.is_none());
+
+    let mut policy = CriteriaAggregationPolicy::default()
+        .with_criterion(NumberCriterion {
+            op: AggregatedActionType::Commit,
+            limit: 3,
+        })
+        .with_criterion(NumberCriterion {
+            op: AggregatedActionType::Commit,
+            limit: 2,
+        });
+    let selected = policy
+        .select_l1_batches(&mut storage, l1_batches, L1BatchNumber(5))
+        .await
+        .unwrap();
+    let selected_numbers: Vec<_> = selected.iter().map(|batch| batch.header.number).collect();
+    assert_eq!(selected_numbers, [L1BatchNumber(1), L1BatchNumber(2)]);
+}
+
+/// Policy skipping the first unpublished L1 batch, which is invalid.
+#[derive(Debug)]
+struct SkippingAggregationPolicy;
+
+#[async_trait::async_trait]
+impl L1BatchAggregationPolicy for SkippingAggregationPolicy {
+    async fn select_l1_batches(
+        &mut self,
+        _storage: &mut Connection<'_, Core>,
+        mut unpublished_l1_batches: Vec<L1BatchWithMetadata>,
+        _last_sealed_l1_batch: L1BatchNumber,
+    ) -> Option<Vec<L1BatchWithMetadata>> {
+        unpublished_l1_batches.remove(0);
+        Some(unpublished_l1_batches)
+    }
+}
+
+#[tokio::test]
+async fn aggregation_policy_output_is_validated() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    let l1_batches: Vec<_> = (1..=3)
+        .map(|number| l1_batch_with_metadata(create_l1_batch(number)))
+        .collect();
+
+    let selected = Aggregator::select_l1_batches(
+        &mut SkippingAggregationPolicy,
+        AggregatedActionType::Commit,
+        &mut storage,
+        l1_batches.clone(),
+        L1BatchNumber(3),
+    )
+    .await;
+    assert!(selected.is_none());
+
+    let mut policy = CriteriaAggregationPolicy::default().with_criterion(NumberCriterion {
+        op: AggregatedActionType::Commit,
+        limit: 2,
+    });
+    let selected = Aggregator::select_l1_batches(
+        &mut policy,
+        AggregatedActionType::Commit,
+        &mut storage,
+        l1_batches,
+        L1BatchNumber(3),
+    )
+    .await
+    .unwrap();
+    assert_eq!(selected.len(), 2);
+}
+
+#[test]
+fn aggregation_policies_are_built_from_config() {
+    let config = SenderConfig {
+        commit_aggregation_criteria: Some(vec![L1BatchAggregationCriterion::Number]),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    config.validate().unwrap();
+    let aggregator = Aggregator::new(
+        config,
+        MockObjectStore::arc(),
+        false,
+        L1BatchCommitmentMode::Rollup,
+    );
+    let policy = format!("{aggregator:?}");
+    assert!(policy.contains("NumberCriterion"), "{policy}");
+    assert!(!policy.contains("DataSizeCriterion"), "{policy}");
+
+    let config = SenderConfig {
+        proof_aggregation_criteria: Some(vec![L1BatchAggregationCriterion::DataSize]),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    config.validate().unwrap_err();
+}
+
+#[test]
+fn dynamic_pubdata_da_is_disabled_for_validium() {
+    let config = SenderConfig {
+        pubdata_sending_mode: PubdataSendingMode::Blobs,
+        dynamic_pubdata_sending_hysteresis: Some(0.1),
+        ..EthConfig::for_tests().sender.unwrap()
+    };
+    let aggregator = Aggregator::new(
+        config.clone(),
+        MockObjectStore::arc(),
+        true,
+        L1BatchCommitmentMode::Rollup,
+    );
+    assert_eq!(aggregator.pubdata_encoding(), PubdataEncoding::Rollup);
+    assert!(aggregator.has_dynamic_pubdata_da());
+
+    let aggregator = Aggregator::new(
+        config,
+        MockObjectStore::arc(),
+        true,
+        L1BatchCommitmentMode::Validium,
+    );
+    assert_eq!(aggregator.pubdata_encoding(), PubdataEncoding::Validium);
+    assert_eq!(aggregator.mode(), L1BatchCommitmentMode::Validium);
+    assert!(!aggregator.has_dynamic_pubdata_da());
+}
+
+#[tokio::test]
+async fn switching_settlement_layer_on_l1() -> anyhow::Result<()> {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+    let hash = commit_l1_batch(&mut tester, genesis_l1_batch, first_l1_batch.clone(), false).await;
+
+    let new_timelock_addr = Address::repeat_byte(0x42);
+    let switch = SettlementLayerSwitch {
+        settlement_layer: SettlementLayer::L1(L1ChainId(9)),
+        diamond_proxy_addr: Address::repeat_byte(1),
+        validator_timelock_addr: new_timelock_addr,
+        l1_block_number: L1BlockNumber(1),
+        event_index: 0,
+    };
+    tester
+        .storage()
+        .await
+        .settlement_layer_dal()
+        .insert_switch(&switch)
+        .await
+        .unwrap();
+
+    // The switch must not be applied until the in-flight transaction is confirmed.
+    let can_create_txs = tester
+        .aggregator
+        .update_settlement_layer(&mut tester.conn.connection().await.unwrap())
+        .await?;
+    assert!(!can_create_txs);
+
+    confirm_tx(&mut tester, hash).await;
+    let can_create_txs = tester
+        .aggregator
+        .update_settlement_layer(&mut tester.conn.connection().await.unwrap())
+        .await?;
+    assert!(can_create_txs);
+    let applied_switch = tester
+        .storage()
+        .await
+        .settlement_layer_dal()
+        .get_latest_applied_switch()
+        .await
+        .unwrap()
+        .expect("switch should be applied");
+    assert_eq!(applied_switch.switch, switch);
+
+    // New transactions should be sent to the new validator timelock and continue the operator nonces.
+    let second_l1_batch = insert_l1_batch(&tester, L1BatchNumber(2)).await;
+    let operation = AggregatedOperation::Commit(
+        l1_batch_with_metadata(first_l1_batch),
+        vec![l1_batch_with_metadata(second_l1_batch)],
+        PubdataDA::Calldata,
+    );
+    let tx = tester
+        .aggregator
+        .save_eth_tx(
+            &mut tester.conn.connection().await.unwrap(),
+            &operation,
+            false,
+        )
+        .await?;
+    assert_eq!(tx.contract_address, new_timelock_addr);
+    assert_eq!(tx.nonce, Nonce(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn recording_l1_batch_l1_costs() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let mut tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Rollup,
+    )
+    .await;
+
+    insert_genesis_protocol_version(&tester).await;
+    let genesis_l1_batch = insert_l1_batch(&tester, L1BatchNumber(0)).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+    let second_l1_batch = insert_l1_batch(&tester, L1BatchNumber(2)).await;
+    // Commit both L1 batches in a single transaction, so that its costs are split between them.
+    let operation = AggregatedOperation::Commit(
+        l1_batch_with_metadata(genesis_l1_batch),
+        vec![
+            l1_batch_with_metadata(first_l1_batch),
+            l1_batch_with_metadata(second_l1_batch),
+        ],
+        PubdataDA::Calldata,
+    );
+    send_operation(&mut tester, operation, true).await;
+
+    let mut storage = tester.storage().await;
+    for number in [1, 2] {
+        let costs = storage
+            .eth_sender_dal()
+            .get_l1_batch_l1_costs(L1BatchNumber(number))
+            .await
+            .unwrap();
+        assert_eq!(costs.len(), 1, "{costs:?}");
+        let cost = &costs[0];
+        assert_eq!(cost.l1_batch_number, L1BatchNumber(number));
+        assert_eq!(cost.tx_type, AggregatedActionType::Commit);
+        assert_eq!(cost.l1_batches_in_tx, 2);
+        // The mock client reports 21,000 gas used for all transactions.
+        assert_eq!(cost.gas_used, 10_500);
+        assert!(!cost.gas_price.is_zero());
+        assert_eq!(cost.blob_gas_used, 0);
+        assert_eq!(cost.total_cost(), cost.gas_price * 10_500);
+    }
+
+    let costs = storage
+        .eth_sender_dal()
+        .get_l1_batch_l1_costs(L1BatchNumber(0))
+        .await
+        .unwrap();
+    assert!(costs.is_empty(), "{costs:?}");
+}
+
 async fn insert_genesis_protocol_version(tester: &EthSenderTester) {
     tester
         .storage()
@@ -1133,6 +1602,63 @@ async fn insert_genesis_protocol_version(tester: &EthSenderTester) {
         .unwrap();
 }
 
+#[tokio::test]
+async fn commits_with_custom_da_wait_for_inclusion_data() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let tester = EthSenderTester::new(
+        connection_pool.clone(),
+        vec![100; 100],
+        false,
+        false,
+        L1BatchCommitmentMode::Validium,
+    )
+    .await;
+
+    insert_genesis_protocol_version(&tester).await;
+    let first_l1_batch = insert_l1_batch(&tester, L1BatchNumber(1)).await;
+    let second_l1_batch = insert_l1_batch(&tester, L1BatchNumber(2)).await;
+    let third_l1_batch = insert_l1_batch(&tester, L1BatchNumber(3)).await;
+    let mut storage = tester.storage().await;
+    for number in [1, 2, 3] {
+        storage
+            .data_availability_dal()
+            .insert_l1_batch_da(L1BatchNumber(number), "Mock", &format!("blob{number}"))
+            .await
+            .unwrap();
+    }
+    // Inclusion data for the second batch is missing, so the third batch must not be committed either.
+    for number in [1, 3] {
+        storage
+            .data_availability_dal()
+            .save_l1_batch_inclusion_data(L1BatchNumber(number), &[number as u8])
+            .await
+            .unwrap();
+    }
+
+    let l1_batches = [first_l1_batch, second_l1_batch, third_l1_batch]
+        .into_iter()
+        .map(l1_batch_with_metadata)
+        .collect();
+    let ready_l1_batches = Aggregator::load_da_inclusion_data(&mut storage, l1_batches).await;
+    assert_eq!(ready_l1_batches.len(), 1);
+    assert_eq!(ready_l1_batches[0].header.number, L1BatchNumber(1));
+    assert_eq!(
+        ready_l1_batches[0].metadata.da_inclusion_data.as_deref(),
+        Some([1].as_slice())
+    );
+
+    let commit_data = CommitBatchInfo::new(
+        L1BatchCommitmentMode::Validium,
+        &ready_l1_batches[0],
+        PubdataDA::Custom,
+    )
+    .into_token();
+    let Token::Tuple(commit_data) = commit_data else {
+        panic!("unexpected commit data: {commit_data:?}");
+    };
+    assert_eq!(*commit_data.last().unwrap(), Token::Bytes(vec![2, 1]));
+}
+
 async fn insert_l1_batch(tester: &EthSenderTester, number: L1BatchNumber) -> L1BatchHeader {
     let header = create_l1_batch(number.0);
 
@@ -29,6 +29,9 @@ pub trait EthClient: 'static + fmt::Debug + Send + Sync {
         -> Result<H256, ContractCallError>;
     /// Sets list of topics to return events for.
     fn set_topics(&mut self, topics: Vec<H256>);
+    /// Sets the address of the chain diamond proxy to return events for. Used to re-point the client
+    /// after the chain migrates to a new settlement layer.
+    fn set_diamond_proxy_addr(&mut self, diamond_proxy_addr: Address);
 }
 
 pub const RETRY_LIMIT: usize = 5;
@@ -41,7 +44,8 @@ pub struct EthHttpQueryClient {
     client: Box<DynClient<L1>>,
     topics: Vec<H256>,
     diamond_proxy_addr: Address,
-    governance_address: Address,
+    // Not present for settlement layer clients.
+    governance_address: Option<Address>,
     // Only present for post-shared bridge chains.
     state_transition_manager_address: Option<Address>,
     verifier_contract_abi: Contract,
@@ -66,7 +70,24 @@ impl EthHttpQueryClient {
             topics: Vec::new(),
             diamond_proxy_addr,
             state_transition_manager_address,
-            governance_address,
+            governance_address: Some(governance_address),
+            verifier_contract_abi: verifier_contract(),
+            confirmations_for_eth_event,
+        }
+    }
+
+    /// Creates a client for a gateway settlement layer. The client only returns events emitted by the chain
+    /// diamond proxy on the gateway; its address must be set using [`EthClient::set_diamond_proxy_addr()`].
+    pub fn for_settlement_layer(
+        client: Box<DynClient<L1>>,
+        confirmations_for_eth_event: Option<u64>,
+    ) -> Self {
+        Self {
+            client: client.for_component("watch_settlement_layer"),
+            topics: Vec::new(),
+            diamond_proxy_addr: Address::zero(),
+            state_transition_manager_address: None,
+            governance_address: None,
             verifier_contract_abi: verifier_contract(),
             confirmations_for_eth_event,
         }
@@ -82,7 +103,7 @@ impl EthHttpQueryClient {
             .address(
                 [
                     Some(self.diamond_proxy_addr),
-                    Some(self.governance_address),
+                    self.governance_address,
                     self.state_transition_manager_address,
                 ]
                 .into_iter()
@@ -209,4 +230,9 @@ impl EthClient for EthHttpQueryClient {
     fn set_topics(&mut self, topics: Vec<H256>) {
         self.topics = topics;
     }
+
+    fn set_diamond_proxy_addr(&mut self, diamond_proxy_addr: Address) {
+        tracing::info!("Re-pointed eth client to zkSync addr {diamond_proxy_addr:x}");
+        self.diamond_proxy_addr = diamond_proxy_addr;
+    }
 }
@@ -6,11 +6,13 @@ use zksync_types::{web3::Log, H256};
 
 pub(crate) use self::{
     governance_upgrades::GovernanceUpgradesEventProcessor, priority_ops::PriorityOpsEventProcessor,
+    settlement_layer::SettlementLayerEventProcessor,
 };
 use crate::client::EthClient;
 
 mod governance_upgrades;
 mod priority_ops;
+pub(crate) mod settlement_layer;
 
 /// Errors issued by an [`EventProcessor`].
 #[derive(Debug, thiserror::Error)]
@@ -39,6 +41,15 @@ impl EventProcessorError {
     }
 }
 
+/// Chain on which events processed by an [`EventProcessor`] are emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum EventsSource {
+    /// Events are always emitted on L1.
+    L1,
+    /// Events are emitted on the current settlement layer of the chain, which can be L1 or a gateway.
+    SettlementLayer,
+}
+
 /// Processor for a single type of events emitted by the L1 contract. [`EthWatch`](crate::EthWatch)
 /// feeds events to all processors one-by-one.
 #[async_trait::async_trait]
@@ -53,4 +64,9 @@ pub(super) trait EventProcessor: 'static + fmt::Debug + Send + Sync {
 
     /// Relevant topic which defines what events to be processed
     fn relevant_topic(&self) -> H256;
+
+    /// Chain on which the processed events are emitted.
+    fn event_source(&self) -> EventsSource {
+        EventsSource::L1
+    }
 }
@@ -8,7 +8,7 @@ use zksync_types::{l1::L1Tx, web3::Log, PriorityOpId, H256};
 
 use crate::{
     client::EthClient,
-    event_processors::{EventProcessor, EventProcessorError},
+    event_processors::{EventProcessor, EventProcessorError, EventsSource},
     metrics::{PollStage, METRICS},
 };
 
@@ -99,4 +99,8 @@ impl EventProcessor for PriorityOpsEventProcessor {
     fn relevant_topic(&self) -> H256 {
         self.new_priority_request_signature
     }
+
+    fn event_source(&self) -> EventsSource {
+        EventsSource::SettlementLayer
+    }
 }
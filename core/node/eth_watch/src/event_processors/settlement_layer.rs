@@ -0,0 +1,102 @@
+use anyhow::Context as _;
+use zksync_dal::{Connection, Core, CoreDal, DalError};
+use zksync_types::{
+    ethabi,
+    settlement::{SettlementLayer, SettlementLayerSwitch},
+    web3::Log,
+    Address, L1BlockNumber, L1ChainId, L2ChainId, H256, U256,
+};
+
+use crate::{
+    client::EthClient,
+    event_processors::{EventProcessor, EventProcessorError},
+};
+
+/// Returns the signature of the `NewSettlementLayer(uint256 indexed, address indexed, address)` event emitted
+/// by the chain diamond proxy on L1 when the chain migrates to a new settlement layer. Indexed params are the chain ID
+/// of the new settlement layer and the address of the chain diamond proxy on it; the unindexed param is the address
+/// of the validator timelock on the new settlement layer.
+pub(crate) fn new_settlement_layer_signature() -> H256 {
+    ethabi::long_signature(
+        "NewSettlementLayer",
+        &[
+            ethabi::ParamType::Uint(256),
+            ethabi::ParamType::Address,
+            ethabi::ParamType::Address,
+        ],
+    )
+}
+
+/// Listens to settlement layer switches of the chain announced on L1 and saves them to the database.
+/// Switches are applied by eth_sender; eth_watch uses them to choose the chain to watch for priority operations.
+#[derive(Debug)]
+pub struct SettlementLayerEventProcessor {
+    l1_chain_id: L1ChainId,
+    new_settlement_layer_signature: H256,
+}
+
+impl SettlementLayerEventProcessor {
+    pub fn new(l1_chain_id: L1ChainId) -> Self {
+        Self {
+            l1_chain_id,
+            new_settlement_layer_signature: new_settlement_layer_signature(),
+        }
+    }
+
+    fn parse_switch(&self, event: &Log) -> anyhow::Result<SettlementLayerSwitch> {
+        anyhow::ensure!(event.topics.len() == 3, "unexpected number of topics");
+        anyhow::ensure!(event.data.0.len() == 32, "unexpected data length");
+
+        let chain_id = U256::from_big_endian(event.topics[1].as_bytes());
+        anyhow::ensure!(chain_id <= U256::from(u64::MAX), "chain ID overflow");
+        let chain_id = chain_id.as_u64();
+        let settlement_layer = if chain_id == self.l1_chain_id.0 {
+            SettlementLayer::L1(self.l1_chain_id)
+        } else {
+            SettlementLayer::Gateway(L2ChainId::try_from(chain_id).map_err(anyhow::Error::msg)?)
+        };
+
+        let l1_block_number = event.block_number.context("missing block number")?;
+        let event_index = event.log_index.context("missing log index")?;
+        Ok(SettlementLayerSwitch {
+            settlement_layer,
+            diamond_proxy_addr: Address::from_slice(&event.topics[2].as_bytes()[12..]),
+            validator_timelock_addr: Address::from_slice(&event.data.0[12..]),
+            l1_block_number: L1BlockNumber(l1_block_number.as_u32()),
+            event_index: event_index.as_u32(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventProcessor for SettlementLayerEventProcessor {
+    async fn process_events(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+        _client: &dyn EthClient,
+        events: Vec<Log>,
+    ) -> Result<(), EventProcessorError> {
+        for event in events {
+            assert_eq!(event.topics[0], self.new_settlement_layer_signature); // guaranteed by the watcher
+
+            let switch = self
+                .parse_switch(&event)
+                .map_err(|err| EventProcessorError::log_parse(err, "settlement layer switch"))?;
+            tracing::info!(
+                "Chain settlement layer is switched to {:?} in L1 block {}",
+                switch.settlement_layer,
+                switch.l1_block_number
+            );
+            storage
+                .settlement_layer_dal()
+                .insert_switch(&switch)
+                .await
+                .map_err(DalError::generalize)?;
+        }
+        Ok(())
+    }
+
+    fn relevant_topic(&self) -> H256 {
+        self.new_settlement_layer_signature
+    }
+}
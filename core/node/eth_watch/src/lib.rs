@@ -6,19 +6,23 @@ use std::time::Duration;
 
 use anyhow::Context as _;
 use tokio::sync::watch;
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
-    ethabi::Contract, protocol_version::ProtocolSemanticVersion,
-    web3::BlockNumber as Web3BlockNumber, Address, PriorityOpId,
+    ethabi::Contract,
+    protocol_version::ProtocolSemanticVersion,
+    settlement::{SettlementLayerSwitch, StoredSettlementLayerSwitch},
+    web3::{BlockNumber as Web3BlockNumber, Log},
+    Address, L1ChainId, PriorityOpId,
 };
 
-pub use self::client::EthHttpQueryClient;
+pub use self::client::{EthClient, EthHttpQueryClient};
 use self::{
-    client::{EthClient, RETRY_LIMIT},
+    client::RETRY_LIMIT,
     event_processors::{
-        EventProcessor, EventProcessorError, GovernanceUpgradesEventProcessor,
-        PriorityOpsEventProcessor,
+        settlement_layer::new_settlement_layer_signature, EventProcessor, EventProcessorError,
+        EventsSource, GovernanceUpgradesEventProcessor, PriorityOpsEventProcessor,
+        SettlementLayerEventProcessor,
     },
     metrics::{PollStage, METRICS},
 };
@@ -40,9 +44,18 @@ struct EthWatchState {
 #[derive(Debug)]
 pub struct EthWatch {
     client: Box<dyn EthClient>,
+    /// Client for the gateway settlement layer. Used to watch for events emitted on the settlement layer
+    /// (i.e., priority operations) while the chain settles on the gateway.
+    sl_client: Option<Box<dyn EthClient>>,
     poll_interval: Duration,
     event_processors: Vec<Box<dyn EventProcessor>>,
     last_processed_ethereum_block: u64,
+    /// Last processed block on the gateway. `Some(_)` iff the chain currently settles on the gateway.
+    last_processed_sl_block: Option<u64>,
+    /// Whether settlement layer switches are tracked.
+    tracks_settlement_layer: bool,
+    /// Latest settlement layer switch taken into account by the watcher.
+    settlement_layer_switch: Option<StoredSettlementLayerSwitch>,
     pool: ConnectionPool<Core>,
 }
 
@@ -79,13 +92,51 @@ impl EthWatch {
 
         Ok(Self {
             client,
+            sl_client: None,
             poll_interval,
             event_processors,
             last_processed_ethereum_block: state.last_processed_ethereum_block,
+            last_processed_sl_block: None,
+            tracks_settlement_layer: false,
+            settlement_layer_switch: None,
             pool,
         })
     }
 
+    /// Enables tracking settlement layer switches of the chain announced on L1. While the chain settles on
+    /// the gateway, priority operations are watched for using `gateway_client`; if the client is not provided,
+    /// the watcher will terminate with an error once the chain is switched to the gateway.
+    pub async fn with_settlement_layer_switches(
+        mut self,
+        l1_chain_id: L1ChainId,
+        gateway_client: Option<Box<dyn EthClient>>,
+    ) -> anyhow::Result<Self> {
+        let processor = SettlementLayerEventProcessor::new(l1_chain_id);
+        self.event_processors.push(Box::new(processor));
+        let topics = self
+            .event_processors
+            .iter()
+            .map(|processor| processor.relevant_topic())
+            .collect();
+        self.client.set_topics(topics);
+
+        self.sl_client = gateway_client.map(|mut client| {
+            let topics = self
+                .event_processors
+                .iter()
+                .filter(|processor| processor.event_source() == EventsSource::SettlementLayer)
+                .map(|processor| processor.relevant_topic())
+                .collect();
+            client.set_topics(topics);
+            client
+        });
+        self.tracks_settlement_layer = true;
+
+        let mut storage = self.pool.connection_tagged("eth_watch").await?;
+        self.restore_settlement_layer(&mut storage).await?;
+        Ok(self)
+    }
+
     async fn initialize_state(
         client: &dyn EthClient,
         storage: &mut Connection<'_, Core>,
@@ -125,6 +176,109 @@ impl EthWatch {
         })
     }
 
+    /// Restores the settlement layer state on initialization.
+    async fn restore_settlement_layer(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        let latest_switch = storage.settlement_layer_dal().get_latest_switch().await?;
+        let Some(latest_switch) = latest_switch else {
+            return Ok(());
+        };
+        tracing::info!(
+            "Chain settles on {:?} since L1 block {}",
+            latest_switch.switch.settlement_layer,
+            latest_switch.switch.l1_block_number
+        );
+        self.apply_switch(&latest_switch.switch).await?;
+        self.settlement_layer_switch = Some(latest_switch);
+        self.last_processed_ethereum_block = self.first_l1_block_to_rescan(None);
+        Ok(())
+    }
+
+    /// Returns the L1 block to rescan events from after (re)initialization.
+    fn first_l1_block_to_rescan(&self, last_processed_block: Option<u64>) -> u64 {
+        match &self.settlement_layer_switch {
+            // After a switch, the last processed priority operation may refer to a block on another chain,
+            // so it cannot be used to determine the starting L1 block. Instead, L1 is rescanned starting from the switch;
+            // events processed repeatedly are deduplicated by processors.
+            Some(switch) => u64::from(switch.switch.l1_block_number.0).saturating_sub(1),
+            None => last_processed_block.unwrap_or(self.last_processed_ethereum_block),
+        }
+    }
+
+    /// Resets the last processed L1 block after an error processing events. The settlement layer state is retained,
+    /// since it's only updated once a switch is successfully applied.
+    async fn reset_last_processed_block(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        let state = Self::initialize_state(&*self.client, storage).await?;
+        self.last_processed_ethereum_block =
+            self.first_l1_block_to_rescan(Some(state.last_processed_ethereum_block));
+        Ok(())
+    }
+
+    /// Starts watching for settlement layer events on the new settlement layer.
+    async fn apply_switch(
+        &mut self,
+        switch: &SettlementLayerSwitch,
+    ) -> Result<(), EventProcessorError> {
+        if switch.settlement_layer.is_gateway() {
+            let sl_client = self.sl_client.as_mut().with_context(|| {
+                format!(
+                    "chain is switched to settlement layer {:?}, but gateway client is not configured",
+                    switch.settlement_layer
+                )
+            })?;
+            sl_client.set_diamond_proxy_addr(switch.diamond_proxy_addr);
+            // Similar to the initial L1 scan, scan the last blocks on the gateway to be safe.
+            let finalized_block_number = sl_client.finalized_block_number().await?;
+            self.last_processed_sl_block =
+                Some(finalized_block_number.saturating_sub(PRIORITY_EXPIRATION));
+        } else {
+            // Priority operations on L1 are not processed while the chain settles on the gateway,
+            // so L1 must be rescanned starting from the switch.
+            self.last_processed_sl_block = None;
+            self.last_processed_ethereum_block = self
+                .last_processed_ethereum_block
+                .min(u64::from(switch.l1_block_number.0).saturating_sub(1));
+        }
+        Ok(())
+    }
+
+    /// Checks whether a new settlement layer switch was announced, and starts following it if so.
+    async fn update_settlement_layer(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EventProcessorError> {
+        let latest_switch = storage
+            .settlement_layer_dal()
+            .get_latest_switch()
+            .await
+            .map_err(DalError::generalize)?;
+        let Some(latest_switch) = latest_switch else {
+            return Ok(());
+        };
+        let current_switch_id = self
+            .settlement_layer_switch
+            .as_ref()
+            .map(|switch| switch.id);
+        if current_switch_id == Some(latest_switch.id) {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Switching watched settlement layer to {:?}",
+            latest_switch.switch.settlement_layer
+        );
+        // If the switch cannot be applied (e.g., because the gateway is unreachable), it will be retried
+        // on the next iteration.
+        self.apply_switch(&latest_switch.switch).await?;
+        self.settlement_layer_switch = Some(latest_switch);
+        Ok(())
+    }
+
     pub async fn run(mut self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut timer = tokio::time::interval(self.poll_interval);
         let pool = self.pool.clone();
@@ -147,10 +301,11 @@ impl EthWatch {
                     // This is an error because otherwise we could potentially miss a priority operation
                     // thus entering priority mode, which is not desired.
                     tracing::error!("Failed to process new blocks: {err}");
-                    self.last_processed_ethereum_block =
-                        Self::initialize_state(&*self.client, &mut storage)
-                            .await?
-                            .last_processed_ethereum_block;
+                    if let Err(err) = self.reset_last_processed_block(&mut storage).await {
+                        tracing::warn!(
+                            "Failed resetting last processed L1 block, will retry on next iteration: {err:#}"
+                        );
+                    }
                 }
             }
         }
@@ -163,6 +318,18 @@ impl EthWatch {
     async fn loop_iteration(
         &mut self,
         storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EventProcessorError> {
+        self.process_l1_events(storage).await?;
+        if self.tracks_settlement_layer {
+            self.update_settlement_layer(storage).await?;
+            self.process_sl_events(storage).await?;
+        }
+        Ok(())
+    }
+
+    async fn process_l1_events(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
     ) -> Result<(), EventProcessorError> {
         let stage_latency = METRICS.poll_eth_node[&PollStage::Request].start();
         let to_block = self.client.finalized_block_number().await?;
@@ -180,7 +347,27 @@ impl EthWatch {
             .await?;
         stage_latency.observe();
 
+        // Events following a new settlement layer switch may need to be processed from another chain,
+        // so they are only processed once the switch is applied. Events before the switch are processed repeatedly
+        // on the next iteration; they are deduplicated by processors.
+        let (events, to_block) = match self.first_new_switch_position(&events) {
+            Some(switch_position) => {
+                let events = events
+                    .into_iter()
+                    .filter(|event| {
+                        event_position(event).map_or(true, |position| position <= switch_position)
+                    })
+                    .collect();
+                (events, switch_position.0)
+            }
+            None => (events, to_block),
+        };
+
+        let settles_on_gateway = self.last_processed_sl_block.is_some();
         for processor in &mut self.event_processors {
+            if settles_on_gateway && processor.event_source() == EventsSource::SettlementLayer {
+                continue; // Events are processed from the gateway instead.
+            }
             let relevant_topic = processor.relevant_topic();
             let processor_events = events
                 .iter()
@@ -194,4 +381,73 @@ impl EthWatch {
         self.last_processed_ethereum_block = to_block;
         Ok(())
     }
+
+    /// Returns the position of the earliest settlement layer switch in `events` that wasn't applied by the watcher yet.
+    fn first_new_switch_position(&self, events: &[Log]) -> Option<(u64, u64)> {
+        if !self.tracks_settlement_layer {
+            return None;
+        }
+        let switch_topic = new_settlement_layer_signature();
+        let current_switch_position = self.settlement_layer_switch.as_ref().map(|switch| {
+            (
+                u64::from(switch.switch.l1_block_number.0),
+                u64::from(switch.switch.event_index),
+            )
+        });
+        events
+            .iter()
+            .filter(|event| event.topics.first() == Some(&switch_topic))
+            .filter_map(event_position)
+            .filter(|&position| current_switch_position.map_or(true, |current| position > current))
+            .min()
+    }
+
+    /// Processes events emitted on the gateway settlement layer (if the chain currently settles on it).
+    async fn process_sl_events(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> Result<(), EventProcessorError> {
+        let (Some(sl_client), Some(last_processed_sl_block)) =
+            (self.sl_client.as_deref(), self.last_processed_sl_block)
+        else {
+            return Ok(());
+        };
+
+        let stage_latency = METRICS.poll_eth_node[&PollStage::Request].start();
+        let to_block = sl_client.finalized_block_number().await?;
+        if to_block <= last_processed_sl_block {
+            return Ok(());
+        }
+
+        let events = sl_client
+            .get_events(
+                Web3BlockNumber::Number(last_processed_sl_block.into()),
+                Web3BlockNumber::Number(to_block.into()),
+                RETRY_LIMIT,
+            )
+            .await?;
+        stage_latency.observe();
+
+        for processor in &mut self.event_processors {
+            if processor.event_source() != EventsSource::SettlementLayer {
+                continue;
+            }
+            let relevant_topic = processor.relevant_topic();
+            let processor_events = events
+                .iter()
+                .filter(|event| event.topics.first() == Some(&relevant_topic))
+                .cloned()
+                .collect();
+            processor
+                .process_events(storage, sl_client, processor_events)
+                .await?;
+        }
+        self.last_processed_sl_block = Some(to_block);
+        Ok(())
+    }
+}
+
+/// Returns the (L1 block, log index) position of an event.
+fn event_position(event: &Log) -> Option<(u64, u64)> {
+    Some((event.block_number?.as_u64(), event.log_index?.as_u64()))
 }
@@ -10,18 +10,22 @@ use zksync_types::{
     l1::{L1Tx, OpProcessingType, PriorityQueueType},
     protocol_upgrade::{ProtocolUpgradeTx, ProtocolUpgradeTxCommonData},
     protocol_version::ProtocolSemanticVersion,
+    settlement::SettlementLayer,
     web3::{BlockNumber, Log},
-    Address, Execute, L1TxCommonData, PriorityOpId, ProtocolUpgrade, ProtocolVersion,
-    ProtocolVersionId, Transaction, H256, U256,
+    Address, Execute, L1ChainId, L1TxCommonData, L2ChainId, PriorityOpId, ProtocolUpgrade,
+    ProtocolVersion, ProtocolVersionId, Transaction, H256, U256,
 };
 
-use crate::{client::EthClient, EthWatch};
+use crate::{
+    client::EthClient, event_processors::settlement_layer::new_settlement_layer_signature, EthWatch,
+};
 
 #[derive(Debug)]
 struct FakeEthClientData {
     transactions: HashMap<u64, Vec<Log>>,
     diamond_upgrades: HashMap<u64, Vec<Log>>,
     governance_upgrades: HashMap<u64, Vec<Log>>,
+    settlement_layer_switches: HashMap<u64, Vec<Log>>,
     last_finalized_block_number: u64,
 }
 
@@ -31,6 +35,7 @@ impl FakeEthClientData {
             transactions: Default::default(),
             diamond_upgrades: Default::default(),
             governance_upgrades: Default::default(),
+            settlement_layer_switches: Default::default(),
             last_finalized_block_number: 0,
         }
     }
@@ -54,6 +59,13 @@ impl FakeEthClientData {
         }
     }
 
+    fn add_settlement_layer_switch(&mut self, chain_id: u64, eth_block: u64) {
+        self.settlement_layer_switches
+            .entry(eth_block)
+            .or_default()
+            .push(settlement_layer_switch_into_log(chain_id, eth_block));
+    }
+
     fn set_last_finalized_block_number(&mut self, number: u64) {
         self.last_finalized_block_number = number;
     }
@@ -79,6 +91,13 @@ impl MockEthClient {
         self.inner.write().await.add_governance_upgrades(upgrades);
     }
 
+    async fn add_settlement_layer_switch(&mut self, chain_id: u64, eth_block: u64) {
+        self.inner
+            .write()
+            .await
+            .add_settlement_layer_switch(chain_id, eth_block);
+    }
+
     async fn set_last_finalized_block_number(&mut self, number: u64) {
         self.inner
             .write()
@@ -119,12 +138,23 @@ impl EthClient for MockEthClient {
             if let Some(ops) = self.inner.read().await.governance_upgrades.get(&number) {
                 logs.extend_from_slice(ops);
             }
+            if let Some(ops) = self
+                .inner
+                .read()
+                .await
+                .settlement_layer_switches
+                .get(&number)
+            {
+                logs.extend_from_slice(ops);
+            }
         }
         Ok(logs)
     }
 
     fn set_topics(&mut self, _topics: Vec<Hash>) {}
 
+    fn set_diamond_proxy_addr(&mut self, _diamond_proxy_addr: Address) {}
+
     async fn scheduler_vk_hash(
         &self,
         _verifier_address: Address,
@@ -458,6 +488,105 @@ async fn test_overlapping_batches() {
     assert_eq!(tx.common_data.serial_id.0, 4);
 }
 
+#[tokio::test]
+async fn switching_settlement_layer_to_gateway() {
+    const L1_CHAIN_ID: L1ChainId = L1ChainId(9);
+    const GATEWAY_CHAIN_ID: u64 = 505;
+
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let mut gateway_client = MockEthClient::new();
+    let mut watcher = watcher
+        .with_settlement_layer_switches(L1_CHAIN_ID, Some(Box::new(gateway_client.clone())))
+        .await
+        .unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    client.add_transactions(&[build_l1_tx(0, 10)]).await;
+    client
+        .add_settlement_layer_switch(GATEWAY_CHAIN_ID, 12)
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    // Block numbers on the gateway are unrelated to L1 block numbers.
+    gateway_client.add_transactions(&[build_l1_tx(1, 5)]).await;
+    gateway_client.set_last_finalized_block_number(8).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let switch = storage
+        .settlement_layer_dal()
+        .get_latest_switch()
+        .await
+        .unwrap()
+        .expect("switch was not saved");
+    assert_eq!(
+        switch.switch.settlement_layer,
+        SettlementLayer::Gateway(L2ChainId::from(GATEWAY_CHAIN_ID as u32))
+    );
+    assert_eq!(switch.switch.l1_block_number.0, 12);
+    let db_txs = get_all_db_txs(&mut storage).await;
+    assert_eq!(db_txs.len(), 2);
+
+    // Priority ops are now taken from the gateway.
+    gateway_client.add_transactions(&[build_l1_tx(2, 10)]).await;
+    gateway_client.set_last_finalized_block_number(12).await;
+    client.set_last_finalized_block_number(20).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    let serial_ids: Vec<_> = db_txs.iter().map(|tx| tx.serial_id().0).collect();
+    assert_eq!(serial_ids, [0, 1, 2]);
+}
+
+#[tokio::test]
+async fn events_after_settlement_layer_switch_in_same_range() {
+    const L1_CHAIN_ID: L1ChainId = L1ChainId(9);
+    const GATEWAY_CHAIN_ID: u64 = 505;
+
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    setup_db(&connection_pool).await;
+    let (watcher, mut client) = create_test_watcher(connection_pool.clone()).await;
+    let mut gateway_client = MockEthClient::new();
+    let mut watcher = watcher
+        .with_settlement_layer_switches(L1_CHAIN_ID, Some(Box::new(gateway_client.clone())))
+        .await
+        .unwrap();
+
+    let mut storage = connection_pool.connection().await.unwrap();
+    // The priority op emitted on L1 after the switch must not be processed; it must be taken from the gateway instead.
+    client
+        .add_transactions(&[build_l1_tx(0, 10), build_l1_tx(1, 14)])
+        .await;
+    client
+        .add_settlement_layer_switch(GATEWAY_CHAIN_ID, 12)
+        .await;
+    client.set_last_finalized_block_number(15).await;
+    gateway_client.add_transactions(&[build_l1_tx(1, 5)]).await;
+    gateway_client.set_last_finalized_block_number(8).await;
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(watcher.last_processed_ethereum_block, 12);
+
+    watcher.loop_iteration(&mut storage).await.unwrap();
+    assert_eq!(watcher.last_processed_ethereum_block, 15);
+
+    let db_txs = get_all_db_txs(&mut storage).await;
+    let mut db_txs: Vec<L1Tx> = db_txs
+        .into_iter()
+        .map(|tx| tx.try_into().unwrap())
+        .collect();
+    db_txs.sort_by_key(|tx| tx.common_data.serial_id);
+    let txs: Vec<_> = db_txs
+        .iter()
+        .map(|tx| (tx.serial_id().0, tx.common_data.eth_block))
+        .collect();
+    assert_eq!(txs, [(0, 10), (1, 5)]);
+}
+
 async fn get_all_db_txs(storage: &mut Connection<'_, Core>) -> Vec<Transaction> {
     storage.transactions_dal().reset_mempool().await.unwrap();
     storage
@@ -508,6 +637,28 @@ fn tx_into_log(tx: L1Tx) -> Log {
     }
 }
 
+fn settlement_layer_switch_into_log(chain_id: u64, eth_block: u64) -> Log {
+    let mut diamond_proxy_topic = H256::zero();
+    diamond_proxy_topic.as_bytes_mut()[12..].copy_from_slice(Address::repeat_byte(0x22).as_bytes());
+    Log {
+        address: Address::repeat_byte(0x1),
+        topics: vec![
+            new_settlement_layer_signature(),
+            H256::from_low_u64_be(chain_id),
+            diamond_proxy_topic,
+        ],
+        data: ethabi::encode(&[Token::Address(Address::repeat_byte(0x23))]).into(),
+        block_hash: Some(H256::repeat_byte(0x11)),
+        block_number: Some(eth_block.into()),
+        transaction_hash: Some(H256::random()),
+        transaction_index: Some(0u64.into()),
+        log_index: Some(0u64.into()),
+        transaction_log_index: Some(0u64.into()),
+        log_type: None,
+        removed: None,
+    }
+}
+
 fn upgrade_into_governor_log(upgrade: ProtocolUpgrade, eth_block: u64) -> Log {
     let diamond_cut = upgrade_into_diamond_cut(upgrade);
     let execute_upgrade_selector = hyperchain_contract()
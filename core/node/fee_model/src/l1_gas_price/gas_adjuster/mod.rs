@@ -6,10 +6,14 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+use anyhow::Context as _;
 use tokio::sync::watch;
 use zksync_config::{configs::eth_sender::PubdataSendingMode, GasAdjusterConfig};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_eth_client::EthInterface;
-use zksync_types::{commitment::L1BatchCommitmentMode, L1_GAS_PER_PUBDATA_BYTE, U256, U64};
+use zksync_types::{
+    api::L1GasPriceSample, commitment::L1BatchCommitmentMode, L1_GAS_PER_PUBDATA_BYTE, U256, U64,
+};
 use zksync_web3_decl::client::{DynClient, L1};
 
 use self::metrics::METRICS;
@@ -33,6 +37,8 @@ pub struct GasAdjuster {
     pubdata_sending_mode: PubdataSendingMode,
     eth_client: Box<DynClient<L1>>,
     commitment_mode: L1BatchCommitmentMode,
+    /// Pool used to persist observed gas prices; if not set, the prices are not persisted.
+    history_pool: Option<ConnectionPool<Core>>,
 }
 
 impl GasAdjuster {
@@ -58,7 +64,7 @@ impl GasAdjuster {
 
         // Web3 API doesn't provide a method to fetch blob fees for multiple blocks using single request,
         // so we request blob base fee only for the latest block.
-        let (_, last_block_blob_base_fee) =
+        let (_, last_block_blob_base_fee, _) =
             Self::get_base_fees_history(eth_client.as_ref(), current_block..=current_block).await?;
 
         Ok(Self {
@@ -76,9 +82,16 @@ impl GasAdjuster {
             pubdata_sending_mode,
             eth_client,
             commitment_mode,
+            history_pool: None,
         })
     }
 
+    /// Enables persisting gas prices observed for new L1 blocks to the `l1_gas_price_history` table.
+    pub fn with_price_history(mut self, pool: ConnectionPool<Core>) -> Self {
+        self.history_pool = Some(pool);
+        self
+    }
+
     /// Performs an actualization routine for `GasAdjuster`.
     /// This method is intended to be invoked periodically.
     pub async fn keep_updated(&self) -> anyhow::Result<()> {
@@ -95,7 +108,7 @@ impl GasAdjuster {
         let last_processed_block = self.base_fee_statistics.last_processed_block();
 
         if current_block > last_processed_block {
-            let (base_fee_history, blob_base_fee_history) = Self::get_base_fees_history(
+            let (base_fee_history, blob_base_fee_history, samples) = Self::get_base_fees_history(
                 self.eth_client.as_ref(),
                 (last_processed_block + 1)..=current_block,
             )
@@ -123,6 +136,15 @@ impl GasAdjuster {
             }
             self.blob_base_fee_statistics
                 .add_samples(&blob_base_fee_history);
+
+            if let Some(pool) = &self.history_pool {
+                let mut storage = pool.connection_tagged("gas_adjuster").await?;
+                storage
+                    .l1_gas_price_history_dal()
+                    .insert_samples(&samples)
+                    .await
+                    .context("failed persisting L1 gas price samples")?;
+            }
         }
         Ok(())
     }
@@ -200,6 +222,8 @@ impl GasAdjuster {
             PubdataSendingMode::Calldata => {
                 self.estimate_effective_gas_price() * self.pubdata_byte_gas()
             }
+            // Pubdata is not published to L1; the DA layer costs are not covered by the pubdata price.
+            PubdataSendingMode::Custom => 0,
         }
     }
 
@@ -224,29 +248,40 @@ impl GasAdjuster {
         }
     }
 
-    /// Returns vector of base fees and blob base fees for given block range.
-    /// Note, that data for pre-dencun blocks won't be included in the vector returned.
+    /// Returns vector of base fees and blob base fees for given block range, together with
+    /// per-block samples to be persisted.
+    /// Note, that data for pre-dencun blocks won't be included in the blob base fee vector returned.
     async fn get_base_fees_history(
         eth_client: &DynClient<L1>,
         block_range: RangeInclusive<usize>,
-    ) -> anyhow::Result<(Vec<u64>, Vec<U256>)> {
+    ) -> anyhow::Result<(Vec<u64>, Vec<U256>, Vec<L1GasPriceSample>)> {
         let mut base_fee_history = Vec::new();
         let mut blob_base_fee_history = Vec::new();
+        let mut samples = Vec::new();
         for block_number in block_range {
             let header = eth_client.block(U64::from(block_number).into()).await?;
-            if let Some(base_fee_per_gas) =
-                header.as_ref().and_then(|header| header.base_fee_per_gas)
-            {
-                base_fee_history.push(base_fee_per_gas.as_u64())
+            let Some(header) = header else {
+                continue;
+            };
+            let blob_base_fee = header
+                .excess_blob_gas
+                .map(|excess_blob_gas| Self::blob_base_fee(excess_blob_gas.as_u64()));
+            if let Some(blob_base_fee) = blob_base_fee {
+                blob_base_fee_history.push(blob_base_fee);
             }
 
-            if let Some(excess_blob_gas) = header.as_ref().and_then(|header| header.excess_blob_gas)
-            {
-                blob_base_fee_history.push(Self::blob_base_fee(excess_blob_gas.as_u64()))
+            if let Some(base_fee_per_gas) = header.base_fee_per_gas {
+                base_fee_history.push(base_fee_per_gas.as_u64());
+                samples.push(L1GasPriceSample {
+                    l1_block_number: block_number as u64,
+                    l1_block_timestamp: header.timestamp.as_u64(),
+                    base_fee_per_gas,
+                    blob_base_fee,
+                });
             }
         }
 
-        Ok((base_fee_history, blob_base_fee_history))
+        Ok((base_fee_history, blob_base_fee_history, samples))
     }
 
     /// Calculates `blob_base_fee` given `excess_blob_gas`.
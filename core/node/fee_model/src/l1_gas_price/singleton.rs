@@ -3,6 +3,7 @@ use std::sync::Arc;
 use anyhow::Context as _;
 use tokio::{sync::watch, task::JoinHandle};
 use zksync_config::{configs::eth_sender::PubdataSendingMode, GasAdjusterConfig};
+use zksync_dal::{ConnectionPool, Core};
 use zksync_types::{commitment::L1BatchCommitmentMode, url::SensitiveUrl, L1ChainId};
 use zksync_web3_decl::client::Client;
 
@@ -18,6 +19,7 @@ pub struct GasAdjusterSingleton {
     pubdata_sending_mode: PubdataSendingMode,
     singleton: Option<Arc<GasAdjuster>>,
     commitment_mode: L1BatchCommitmentMode,
+    history_pool: Option<ConnectionPool<Core>>,
 }
 
 impl GasAdjusterSingleton {
@@ -35,9 +37,16 @@ impl GasAdjusterSingleton {
             pubdata_sending_mode,
             singleton: None,
             commitment_mode,
+            history_pool: None,
         }
     }
 
+    /// Makes the created `GasAdjuster` persist observed L1 gas prices using the provided pool.
+    pub fn with_price_history(mut self, pool: ConnectionPool<Core>) -> Self {
+        self.history_pool = Some(pool);
+        self
+    }
+
     pub async fn get_or_init(&mut self) -> anyhow::Result<Arc<GasAdjuster>> {
         if let Some(adjuster) = &self.singleton {
             Ok(adjuster.clone())
@@ -46,7 +55,7 @@ impl GasAdjusterSingleton {
                 .context("QueryClient::new()")?
                 .for_network(self.chain_id.into())
                 .build();
-            let adjuster = GasAdjuster::new(
+            let mut adjuster = GasAdjuster::new(
                 Box::new(query_client),
                 self.gas_adjuster_config,
                 self.pubdata_sending_mode,
@@ -54,6 +63,9 @@ impl GasAdjusterSingleton {
             )
             .await
             .context("GasAdjuster::new()")?;
+            if let Some(pool) = self.history_pool.clone() {
+                adjuster = adjuster.with_price_history(pool);
+            }
 
             self.singleton = Some(Arc::new(adjuster));
             Ok(self.singleton.as_ref().unwrap().clone())
@@ -10,7 +10,7 @@ use zksync_config::{configs::DatabaseSecrets, GenesisConfig};
 use zksync_contracts::{BaseSystemContracts, BaseSystemContractsHashes, SET_CHAIN_ID_EVENT};
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal, DalError};
 use zksync_eth_client::EthInterface;
-use zksync_merkle_tree::{domain::ZkSyncTree, TreeInstruction};
+use zksync_merkle_tree::{domain::ZkSyncTree, TreeHasher, TreeInstruction};
 use zksync_system_constants::PRIORITY_EXPIRATION;
 use zksync_types::{
     block::{BlockGasCount, DeployedContract, L1BatchHeader, L2BlockHasher, L2BlockHeader},
@@ -232,7 +232,8 @@ pub async fn insert_genesis_batch(
         })
         .collect();
 
-    let metadata = ZkSyncTree::process_genesis_batch(&storage_logs);
+    let tree_hasher = TreeHasher::for_protocol_version(genesis_params.minor_protocol_version());
+    let metadata = ZkSyncTree::process_genesis_batch(&storage_logs, tree_hasher);
     let genesis_root_hash = metadata.root_hash;
     let rollup_last_leaf_index = metadata.leaf_count + 1;
 
@@ -364,6 +365,7 @@ pub async fn create_genesis_l1_batch(
         protocol_version: Some(protocol_version.minor),
         virtual_blocks: 0,
         gas_limit: 0,
+        logs_bloom: Default::default(),
     };
 
     let mut transaction = storage.start_transaction().await?;
@@ -1,3 +1,4 @@
 pub mod blocks_state_reporter;
 pub mod periodic_job;
 pub mod prover;
+pub mod table_partition_manager;
@@ -0,0 +1,187 @@
+use anyhow::Context as _;
+use async_trait::async_trait;
+use vise::{Counter, LabeledFamily, Metrics};
+use zksync_dal::{
+    partitions_dal::{PartitionedTable, TablePartition},
+    Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_types::L2BlockNumber;
+
+use crate::periodic_job::PeriodicJob;
+
+/// Manages range partitions of the `events` table:
+///
+/// - Creates partitions ahead of the latest sealed L2 block, so that new data doesn't end up in the default partition.
+/// - Drops partitions once all their L2 blocks are soft-pruned (i.e., are no longer served by the API),
+///   so that hard pruning has nothing left to delete.
+///
+/// Errors are not fatal: partitions are created with a margin, so a failed iteration only postpones managing partitions
+/// until the next one.
+#[derive(Debug)]
+pub struct TablePartitionManager {
+    pool: ConnectionPool<Core>,
+    partitioning_interval_ms: u64,
+    partition_size: u32,
+}
+
+impl TablePartitionManager {
+    /// Number of partitions following the one with the latest sealed L2 block that are created in advance.
+    const PARTITIONS_AHEAD: u32 = 2;
+
+    pub fn new(
+        pool: ConnectionPool<Core>,
+        partitioning_interval_ms: u64,
+        partition_size: u32,
+    ) -> Self {
+        assert!(partition_size > 0, "Table partition size must be positive");
+        Self {
+            pool,
+            partitioning_interval_ms,
+            partition_size,
+        }
+    }
+
+    async fn create_partitions(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        table: PartitionedTable,
+        partitions: &[TablePartition],
+        latest_l2_block: L2BlockNumber,
+    ) -> anyhow::Result<()> {
+        let max_partition_end = partitions
+            .iter()
+            .filter_map(|partition| Some(partition.l2_blocks.as_ref()?.end))
+            .max()
+            .unwrap_or_default();
+        // New partitions must not overlap with data in the default partition.
+        let max_default_l2_block = conn
+            .partitions_dal()
+            .get_max_l2_block_in_default_partition(table)
+            .await?;
+        let mut next_start = max_default_l2_block.map_or(max_partition_end, |number| {
+            max_partition_end.max(number + 1)
+        });
+
+        let target_end = latest_l2_block
+            .0
+            .saturating_add(self.partition_size.saturating_mul(Self::PARTITIONS_AHEAD));
+        while next_start.0 < target_end {
+            let end = L2BlockNumber(next_start.0.saturating_add(self.partition_size));
+            let Some(name) = conn
+                .partitions_dal()
+                .create_partition(table, next_start..end)
+                .await?
+            else {
+                // L2 blocks were inserted into the default partition concurrently; partition bounds will be
+                // recomputed on the next iteration.
+                tracing::info!(
+                    "Default partition of `{}` contains L2 blocks from {next_start}..{end}; postponing creating partitions",
+                    table.name()
+                );
+                break;
+            };
+            tracing::info!("Created partition `{name}` for L2 blocks {next_start}..{end}");
+            PARTITION_METRICS.created_partitions[&table.name()].inc();
+            next_start = end;
+        }
+        Ok(())
+    }
+
+    async fn drop_partitions(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        table: PartitionedTable,
+        partitions: &[TablePartition],
+        last_pruned_l2_block: Option<L2BlockNumber>,
+    ) -> anyhow::Result<()> {
+        let Some(last_pruned_l2_block) = last_pruned_l2_block else {
+            return Ok(());
+        };
+
+        for partition in partitions {
+            let Some(l2_blocks) = &partition.l2_blocks else {
+                continue; // Never drop the default partition
+            };
+            if l2_blocks.end > last_pruned_l2_block + 1 {
+                continue;
+            }
+
+            conn.partitions_dal()
+                .drop_partition(table, &partition.name)
+                .await?;
+            PARTITION_METRICS.dropped_partitions[&table.name()].inc();
+            tracing::info!(
+                "Dropped partition `{}` for pruned L2 blocks {}..{}",
+                partition.name,
+                l2_blocks.start,
+                l2_blocks.end
+            );
+        }
+        Ok(())
+    }
+
+    async fn manage_partitions(&self, table: PartitionedTable) -> anyhow::Result<()> {
+        let mut conn = self
+            .pool
+            .connection_tagged("table_partition_manager")
+            .await?;
+        let latest_l2_block = conn
+            .blocks_dal()
+            .get_sealed_l2_block_number()
+            .await?
+            .unwrap_or_default();
+        let pruning_info = conn.pruning_dal().get_pruning_info().await?;
+
+        let partitions = conn.partitions_dal().get_partitions(table).await?;
+        self.create_partitions(&mut conn, table, &partitions, latest_l2_block)
+            .await
+            .context("failed creating partitions")?;
+
+        let last_pruned_l2_block = match table {
+            PartitionedTable::Events => pruning_info.last_soft_pruned_l2_block,
+        };
+        self.drop_partitions(&mut conn, table, &partitions, last_pruned_l2_block)
+            .await
+            .context("failed dropping partitions")
+    }
+}
+
+#[async_trait]
+impl PeriodicJob for TablePartitionManager {
+    const SERVICE_NAME: &'static str = "TablePartitionManager";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        for table in PartitionedTable::ALL {
+            if let Err(err) = self.manage_partitions(table).await {
+                tracing::warn!(
+                    "Failed managing partitions of `{}`, will retry in {}ms: {err:#}",
+                    table.name(),
+                    self.partitioning_interval_ms
+                );
+                PARTITION_METRICS.errors[&table.name()].inc();
+            }
+        }
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.partitioning_interval_ms
+    }
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "house_keeper_table_partitions")]
+struct TablePartitionMetrics {
+    /// Number of partitions created by the manager.
+    #[metrics(labels = ["table"])]
+    created_partitions: LabeledFamily<&'static str, Counter>,
+    /// Number of partitions dropped by the manager.
+    #[metrics(labels = ["table"])]
+    dropped_partitions: LabeledFamily<&'static str, Counter>,
+    /// Number of failed iterations of managing partitions.
+    #[metrics(labels = ["table"])]
+    errors: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+static PARTITION_METRICS: vise::Global<TablePartitionMetrics> = vise::Global::new();
@@ -61,10 +61,65 @@ impl TreeEntryWithProof {
     }
 }
 
+/// Maximum number of entries returned in a single tree diff chunk.
+const MAX_DIFF_ENTRIES: usize = 10_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TreeDiffRequest {
+    old_l1_batch_number: L1BatchNumber,
+    new_l1_batch_number: L1BatchNumber,
+    #[serde(default)]
+    start_key: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+/// Chunk of entries changed between two tree versions returned by the tree API.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeDiff {
+    /// Changed entries ordered by increasing hashed key.
+    pub entries: Vec<TreeEntryDiff>,
+    /// Hashed key to request the next chunk from, or `None` if this chunk is the last one.
+    pub next_key: Option<U256>,
+}
+
+impl TreeDiff {
+    fn new(src: zksync_merkle_tree::TreeDiff) -> Self {
+        Self {
+            entries: src.entries.into_iter().map(TreeEntryDiff::new).collect(),
+            next_key: src.next_key,
+        }
+    }
+}
+
+/// Change of a single entry between two tree versions. Zero index means that the entry is missing
+/// in the corresponding tree version.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeEntryDiff {
+    pub hashed_key: U256,
+    pub old_value: H256,
+    pub old_index: u64,
+    pub new_value: H256,
+    pub new_index: u64,
+}
+
+impl TreeEntryDiff {
+    fn new(src: zksync_merkle_tree::TreeEntryDiff) -> Self {
+        Self {
+            hashed_key: src.key(),
+            old_value: src.old.value,
+            old_index: src.old.leaf_index,
+            new_value: src.new.value,
+            new_index: src.new.leaf_index,
+        }
+    }
+}
+
 /// Server-side tree API error.
 #[derive(Debug)]
 enum TreeApiServerError {
     NoTreeVersion(NoVersionError),
+    CompactionInProgress,
 }
 
 // Contains the same fields as `NoVersionError` and is serializable.
@@ -117,6 +172,16 @@ impl IntoResponse for TreeApiServerError {
                 };
                 (StatusCode::NOT_FOUND, headers, Json(body)).into_response()
             }
+            Self::CompactionInProgress => {
+                let body = Problem {
+                    r#type: "/errors#compaction-in-progress",
+                    title: "Compaction is in progress",
+                    detail: "manual compaction of the tree database is already in progress"
+                        .to_owned(),
+                    data: (),
+                };
+                (StatusCode::CONFLICT, headers, Json(body)).into_response()
+            }
         }
     }
 }
@@ -188,12 +253,31 @@ impl TreeApiClient for LazyAsyncTreeReader {
     }
 }
 
+/// In-memory client implementation for an initialized tree (e.g., a read-only tree replica).
+#[async_trait]
+impl TreeApiClient for AsyncTreeReader {
+    async fn get_info(&self) -> Result<MerkleTreeInfo, TreeApiError> {
+        Ok(self.clone().info().await)
+    }
+
+    async fn get_proofs(
+        &self,
+        l1_batch_number: L1BatchNumber,
+        hashed_keys: Vec<U256>,
+    ) -> Result<Vec<TreeEntryWithProof>, TreeApiError> {
+        self.get_proofs_inner(l1_batch_number, hashed_keys)
+            .await
+            .map_err(TreeApiError::NoVersion)
+    }
+}
+
 /// [`TreeApiClient`] implementation requesting data from a Merkle tree API server.
 #[derive(Debug, Clone)]
 pub struct TreeApiHttpClient {
     inner: reqwest::Client,
     info_url: String,
     proofs_url: String,
+    diff_url: String,
 }
 
 impl TreeApiHttpClient {
@@ -208,8 +292,66 @@ impl TreeApiHttpClient {
             inner: client,
             info_url: url_base.to_owned(),
             proofs_url: format!("{url_base}/proofs"),
+            diff_url: format!("{url_base}/diff"),
         }
     }
+
+    /// Obtains a chunk of entries changed between the specified tree versions (= L1 batch numbers).
+    /// Entries start from `start_key`; at most `limit` entries are returned (the server may cap `limit`).
+    /// To fetch the entire diff, call this method repeatedly using [`TreeDiff::next_key`] as `start_key`.
+    pub async fn get_diff(
+        &self,
+        old_l1_batch_number: L1BatchNumber,
+        new_l1_batch_number: L1BatchNumber,
+        start_key: U256,
+        limit: usize,
+    ) -> Result<TreeDiff, TreeApiError> {
+        let response = self
+            .inner
+            .post(&self.diff_url)
+            .json(&TreeDiffRequest {
+                old_l1_batch_number,
+                new_l1_batch_number,
+                start_key,
+                limit: Some(limit),
+            })
+            .send()
+            .await
+            .map_err(|err| {
+                TreeApiError::for_request(
+                    err,
+                    format_args!(
+                        "diff between L1 batches #{old_l1_batch_number} and #{new_l1_batch_number}"
+                    ),
+                )
+            })?;
+
+        let response = Self::check_no_version_error(response).await?;
+        let response = response.error_for_status().with_context(|| {
+            format!("requesting diff between L1 batches #{old_l1_batch_number} and #{new_l1_batch_number} returned non-OK response")
+        })?;
+        Ok(response.json().await.with_context(|| {
+            format!("failed deserializing diff between L1 batches #{old_l1_batch_number} and #{new_l1_batch_number}")
+        })?)
+    }
+
+    async fn check_no_version_error(
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, TreeApiError> {
+        let is_problem = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
+        if response.status() == StatusCode::NOT_FOUND && is_problem {
+            // Try to parse `NoVersionError` from the response body.
+            let problem_data: NoVersionErrorData = response
+                .json()
+                .await
+                .context("failed parsing error response")?;
+            return Err(TreeApiError::NoVersion(problem_data.into()));
+        }
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -270,19 +412,7 @@ impl TreeApiClient for TreeApiHttpClient {
                 )
             })?;
 
-        let is_problem = response
-            .headers()
-            .get(header::CONTENT_TYPE)
-            .map_or(false, |header| *header == PROBLEM_CONTENT_TYPE);
-        if response.status() == StatusCode::NOT_FOUND && is_problem {
-            // Try to parse `NoVersionError` from the response body.
-            let problem_data: NoVersionErrorData = response
-                .json()
-                .await
-                .context("failed parsing error response")?;
-            return Err(TreeApiError::NoVersion(problem_data.into()));
-        }
-
+        let response = Self::check_no_version_error(response).await?;
         let response = response.error_for_status().with_context(|| {
             format!("requesting proofs for L1 batch #{l1_batch_number} returned non-OK response")
         })?;
@@ -327,6 +457,42 @@ impl AsyncTreeReader {
         Ok(Json(response))
     }
 
+    async fn get_diff_handler(
+        State(this): State<Self>,
+        Json(request): Json<TreeDiffRequest>,
+    ) -> Result<Json<TreeDiff>, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::GetDiff].start();
+        let limit = request
+            .limit
+            .unwrap_or(MAX_DIFF_ENTRIES)
+            .clamp(1, MAX_DIFF_ENTRIES);
+        let diff = this
+            .diff(
+                request.old_l1_batch_number,
+                request.new_l1_batch_number,
+                request.start_key,
+                limit,
+            )
+            .await
+            .map_err(TreeApiServerError::NoTreeVersion)?;
+        latency.observe();
+        Ok(Json(TreeDiff::new(diff)))
+    }
+
+    /// Triggers manual compaction of the tree database and waits for it to complete. Compaction
+    /// is not cancelled if the client disconnects.
+    async fn compact_handler(State(this): State<Self>) -> Result<StatusCode, TreeApiServerError> {
+        let latency = API_METRICS.latency[&MerkleTreeApiMethod::Compact].start();
+        tracing::info!("Requested manual compaction of Merkle tree RocksDB");
+        let compacted = this.compact().await;
+        latency.observe();
+        if compacted {
+            Ok(StatusCode::NO_CONTENT)
+        } else {
+            Err(TreeApiServerError::CompactionInProgress)
+        }
+    }
+
     fn create_api_server(
         self,
         bind_address: &SocketAddr,
@@ -334,10 +500,15 @@ impl AsyncTreeReader {
     ) -> anyhow::Result<MerkleTreeServer> {
         tracing::debug!("Starting Merkle tree API server on {bind_address}");
 
-        let app = Router::new()
+        let mut app = Router::new()
             .route("/", routing::get(Self::info_handler))
             .route("/proofs", routing::post(Self::get_proofs_handler))
-            .with_state(self);
+            .route("/diff", routing::post(Self::get_diff_handler));
+        // Read-only replicas cannot compact the tree database.
+        if !self.is_replica() {
+            app = app.route("/compact", routing::post(Self::compact_handler));
+        }
+        let app = app.with_state(self);
 
         let server = axum::Server::try_bind(bind_address)
             .with_context(|| format!("Failed binding Merkle tree API server to {bind_address}"))?
@@ -71,6 +71,37 @@ async fn merkle_tree_api() {
     assert_eq!(err.version_count, 6);
     assert_eq!(err.missing_version, 10);
 
+    let diff = api_client
+        .get_diff(L1BatchNumber(4), L1BatchNumber(5), U256::zero(), 3)
+        .await
+        .unwrap();
+    assert_eq!(diff.entries.len(), 3);
+    let next_key = diff.next_key.unwrap();
+    assert!(diff.entries.iter().all(|entry| entry.hashed_key < next_key));
+    assert!(diff
+        .entries
+        .windows(2)
+        .all(|window| window[0].hashed_key < window[1].hashed_key));
+    let diff = api_client
+        .get_diff(L1BatchNumber(4), L1BatchNumber(5), next_key, 10_000)
+        .await
+        .unwrap();
+    assert_eq!(diff.entries[0].hashed_key, next_key);
+    assert_eq!(diff.next_key, None);
+
+    let err = api_client
+        .get_diff(L1BatchNumber(4), L1BatchNumber(10), U256::zero(), 3)
+        .await
+        .unwrap_err();
+    assert_matches!(err, TreeApiError::NoVersion(err) if err.missing_version == 10);
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{local_addr}/compact"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
     // Stop the calculator and the tree API server.
     stop_sender.send_replace(true);
     api_server_task.await.unwrap().unwrap();
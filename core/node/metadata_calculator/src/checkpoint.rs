@@ -0,0 +1,739 @@
+//! Exporting Merkle tree checkpoints to an object store and importing them to bootstrap the tree.
+
+use std::{
+    collections::HashSet,
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::{fs, sync::watch};
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::{
+    domain::{ZkSyncTree, ZkSyncTreeReader},
+    RocksDBWrapper,
+};
+use zksync_object_store::{
+    serialize_using_bincode, Bucket, ObjectStore, ObjectStoreError, StoredObject,
+};
+use zksync_types::{L1BatchNumber, H256};
+
+use crate::{
+    helpers::{AsyncTreeReader, LazyAsyncTreeReader},
+    metrics::{CheckpointStage, CHECKPOINT_METRICS},
+};
+
+/// File in a Merkle tree checkpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct CheckpointFile {
+    name: String,
+    size: u64,
+}
+
+impl CheckpointFile {
+    /// Checks whether this file is never modified by RocksDB after it's created. Such files (SSTs) are shared
+    /// among checkpoints and are uploaded to the object store once.
+    fn is_immutable(&self) -> bool {
+        self.name.ends_with(".sst")
+    }
+}
+
+/// Manifest of a Merkle tree checkpoint stored in the object store. Checkpoint files are stored
+/// as separate objects in the same bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CheckpointManifest {
+    /// Latest L1 batch in the checkpointed tree.
+    l1_batch_number: L1BatchNumber,
+    /// Root hash of the tree for `l1_batch_number`.
+    root_hash: H256,
+    files: Vec<CheckpointFile>,
+}
+
+impl CheckpointManifest {
+    fn file_key(&self, file: &CheckpointFile) -> String {
+        if file.is_immutable() {
+            format!("shared_{}", file.name)
+        } else {
+            format!("l1_batch_{}_{}", self.l1_batch_number, file.name)
+        }
+    }
+
+    fn file_keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.files.iter().map(|file| self.file_key(file))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum CheckpointManifestKey {
+    /// Manifest of the latest exported checkpoint.
+    Latest,
+    /// Manifest of the checkpoint for the specified L1 batch.
+    L1Batch(L1BatchNumber),
+}
+
+impl StoredObject for CheckpointManifest {
+    const BUCKET: Bucket = Bucket::MerkleTreeCheckpoints;
+    type Key<'a> = CheckpointManifestKey;
+
+    fn encode_key(key: Self::Key<'_>) -> String {
+        match key {
+            CheckpointManifestKey::Latest => "latest_manifest.bin".to_owned(),
+            CheckpointManifestKey::L1Batch(number) => format!("l1_batch_{number}_manifest.bin"),
+        }
+    }
+
+    serialize_using_bincode!();
+}
+
+/// Checks whether a file in a RocksDB directory should be included into a checkpoint. Lock and info log files
+/// are not necessary to open the database.
+fn is_checkpoint_file(name: &str) -> bool {
+    name != "LOCK" && !name.starts_with("LOG")
+}
+
+/// Returns a sibling path for the tree RocksDB path. Sibling paths are used to create checkpoints; they should be located
+/// on the same filesystem as the tree RocksDB so that checkpoint files can be hard-linked or moved cheaply.
+fn sibling_path(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(db_path);
+    path.push(suffix);
+    path.into()
+}
+
+async fn latest_manifest(
+    object_store: &dyn ObjectStore,
+) -> anyhow::Result<Option<CheckpointManifest>> {
+    match object_store.get(CheckpointManifestKey::Latest).await {
+        Ok(manifest) => Ok(Some(manifest)),
+        Err(ObjectStoreError::KeyNotFound(_)) => Ok(None),
+        Err(err) => {
+            Err(anyhow::Error::from(err).context("failed loading latest checkpoint manifest"))
+        }
+    }
+}
+
+/// Removes a directory if it exists.
+async fn remove_dir_if_exists(path: &Path) -> anyhow::Result<()> {
+    if fs::try_exists(path).await? {
+        fs::remove_dir_all(path)
+            .await
+            .with_context(|| format!("failed removing directory `{}`", path.display()))?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct MerkleTreeCheckpointTaskHealth {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_exported_l1_batch_number: Option<L1BatchNumber>,
+}
+
+impl From<MerkleTreeCheckpointTaskHealth> for Health {
+    fn from(health: MerkleTreeCheckpointTaskHealth) -> Self {
+        Health::from(HealthStatus::Ready).with_details(health)
+    }
+}
+
+/// Task periodically exporting consistent checkpoints of the Merkle tree RocksDB to an object store.
+/// A checkpoint is created locally next to the tree RocksDB (which is cheap since SST files are hard-linked),
+/// and then its files are streamed to the store together with a manifest. Immutable SST files are shared among checkpoints,
+/// so only SSTs created since the previous export are uploaded. Files of the previously exported checkpoint
+/// not referenced by the new checkpoint are removed from the store afterwards.
+///
+/// Exported checkpoints can be imported on another node using [`MetadataCalculator::with_checkpoint_import()`]
+/// to bootstrap its tree without recomputing it from Postgres.
+///
+/// [`MetadataCalculator::with_checkpoint_import()`]: crate::MetadataCalculator::with_checkpoint_import()
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct MerkleTreeCheckpointTask {
+    tree_reader: LazyAsyncTreeReader,
+    object_store: Arc<dyn ObjectStore>,
+    checkpoint_path: PathBuf,
+    interval: Duration,
+    health_updater: HealthUpdater,
+}
+
+impl MerkleTreeCheckpointTask {
+    pub(super) fn new(
+        tree_reader: LazyAsyncTreeReader,
+        object_store: Arc<dyn ObjectStore>,
+        db_path: &Path,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            tree_reader,
+            object_store,
+            checkpoint_path: sibling_path(db_path, "_checkpoint"),
+            interval,
+            health_updater: ReactiveHealthCheck::new("tree_checkpoints").1,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Creates a local checkpoint and returns the latest L1 batch and the root hash of the checkpointed tree.
+    /// Returns `None` if the tree is empty.
+    async fn create_local_checkpoint(
+        &self,
+        tree_reader: &AsyncTreeReader,
+    ) -> anyhow::Result<Option<(L1BatchNumber, H256)>> {
+        // The checkpoint directory may remain after an unclean shutdown.
+        remove_dir_if_exists(&self.checkpoint_path).await?;
+        tree_reader
+            .clone()
+            .create_checkpoint(self.checkpoint_path.clone())
+            .await?;
+
+        // Since the checkpoint may contain a newer tree version than `tree_reader` has observed, we get
+        // checkpoint info from the checkpoint itself.
+        let checkpoint_path = self.checkpoint_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let db = RocksDBWrapper::new(&checkpoint_path)
+                .context("failed opening Merkle tree checkpoint")?;
+            let reader = ZkSyncTreeReader::new(db)?;
+            let Some(l1_batch_number) = reader.next_l1_batch_number().checked_sub(1) else {
+                return Ok(None);
+            };
+            let l1_batch_number = L1BatchNumber(l1_batch_number);
+            let (root_hash, _) = reader.root_info(l1_batch_number).with_context(|| {
+                format!("checkpoint has no root for L1 batch #{l1_batch_number}")
+            })?;
+            Ok(Some((l1_batch_number, root_hash)))
+        })
+        .await
+        .context("panicked opening Merkle tree checkpoint")?
+    }
+
+    /// Exports a tree checkpoint to the object store. Immutable files already uploaded as a part of the `previous`
+    /// checkpoint are not uploaded again. Returns `None` if the tree is empty.
+    async fn export_checkpoint(
+        &self,
+        tree_reader: &AsyncTreeReader,
+        previous: Option<&CheckpointManifest>,
+    ) -> anyhow::Result<Option<CheckpointManifest>> {
+        let latency = CHECKPOINT_METRICS.latency[&CheckpointStage::Create].start();
+        let checkpoint_info = self.create_local_checkpoint(tree_reader).await?;
+        latency.observe();
+        let Some((l1_batch_number, root_hash)) = checkpoint_info else {
+            remove_dir_if_exists(&self.checkpoint_path).await?;
+            return Ok(None);
+        };
+
+        let latency = CHECKPOINT_METRICS.latency[&CheckpointStage::Upload].start();
+        let mut manifest = CheckpointManifest {
+            l1_batch_number,
+            root_hash,
+            files: vec![],
+        };
+        let uploaded_files: HashSet<_> = previous
+            .into_iter()
+            .flat_map(|previous| &previous.files)
+            .filter(|file| file.is_immutable())
+            .collect();
+        let mut reused_file_count = 0;
+        let mut dir = fs::read_dir(&self.checkpoint_path).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let name = entry
+                .file_name()
+                .into_string()
+                .map_err(|name| anyhow::anyhow!("non-UTF-8 checkpoint file name: {name:?}"))?;
+            if !entry.file_type().await?.is_file() || !is_checkpoint_file(&name) {
+                continue;
+            }
+
+            let size = entry
+                .metadata()
+                .await
+                .with_context(|| format!("failed reading metadata of checkpoint file `{name}`"))?
+                .len();
+            let mut file = CheckpointFile { name, size };
+            if uploaded_files.contains(&file) {
+                reused_file_count += 1;
+                manifest.files.push(file);
+                continue;
+            }
+
+            file.size = self
+                .object_store
+                .put_file_raw(
+                    Bucket::MerkleTreeCheckpoints,
+                    &manifest.file_key(&file),
+                    &entry.path(),
+                )
+                .await
+                .with_context(|| format!("failed uploading checkpoint file `{}`", file.name))?;
+            CHECKPOINT_METRICS.exported_bytes.inc_by(file.size);
+            manifest.files.push(file);
+        }
+
+        // Manifests are uploaded after all files, so that a manifest always points to a complete checkpoint.
+        self.object_store
+            .put(CheckpointManifestKey::L1Batch(l1_batch_number), &manifest)
+            .await?;
+        self.object_store
+            .put(CheckpointManifestKey::Latest, &manifest)
+            .await?;
+        let latency = latency.observe();
+        tracing::info!(
+            "Uploaded Merkle tree checkpoint for L1 batch #{l1_batch_number} ({} files, {reused_file_count} of which \
+             are reused from the previous checkpoint) in {latency:?}",
+            manifest.files.len()
+        );
+
+        remove_dir_if_exists(&self.checkpoint_path).await?;
+        Ok(Some(manifest))
+    }
+
+    /// Removes objects of a previously exported checkpoint that are not referenced by the `latest` checkpoint.
+    async fn remove_stale_checkpoint(
+        &self,
+        stale: &CheckpointManifest,
+        latest: &CheckpointManifest,
+    ) -> anyhow::Result<()> {
+        let latest_keys: HashSet<_> = latest.file_keys().collect();
+        for key in stale.file_keys() {
+            if !latest_keys.contains(&key) {
+                self.object_store
+                    .remove_raw(Bucket::MerkleTreeCheckpoints, &key)
+                    .await
+                    .with_context(|| format!("failed removing stale checkpoint file `{key}`"))?;
+            }
+        }
+        if stale.l1_batch_number != latest.l1_batch_number {
+            let key = CheckpointManifestKey::L1Batch(stale.l1_batch_number);
+            self.object_store.remove::<CheckpointManifest>(key).await?;
+        }
+        Ok(())
+    }
+
+    /// Runs this task until a stop signal is received. The first checkpoint is exported immediately
+    /// if the object store doesn't contain checkpoints yet.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB, I/O and object store errors.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let tree_reader = tokio::select! {
+            res = self.tree_reader.clone().wait() => {
+                match res {
+                    Ok(reader) => reader,
+                    Err(_) => {
+                        tracing::info!("Merkle tree dropped; shutting down tree checkpoint exports");
+                        return Ok(());
+                    }
+                }
+            }
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop signal received before Merkle tree is initialized; shutting down tree checkpoint exports");
+                return Ok(());
+            }
+        };
+
+        let mut latest_manifest = latest_manifest(self.object_store.as_ref()).await?;
+        self.health_updater.update(
+            MerkleTreeCheckpointTaskHealth {
+                last_exported_l1_batch_number: latest_manifest
+                    .as_ref()
+                    .map(|manifest| manifest.l1_batch_number),
+            }
+            .into(),
+        );
+        tracing::info!(
+            "Starting Merkle tree checkpoint exports with interval {:?}; latest exported checkpoint: {:?}",
+            self.interval,
+            latest_manifest.as_ref().map(|manifest| manifest.l1_batch_number)
+        );
+
+        let mut delay = if latest_manifest.is_some() {
+            self.interval
+        } else {
+            Duration::ZERO
+        };
+        while tokio::time::timeout(delay, stop_receiver.changed())
+            .await
+            .is_err()
+        {
+            delay = self.interval;
+            let export_result = self
+                .export_checkpoint(&tree_reader, latest_manifest.as_ref())
+                .await?;
+            let Some(manifest) = export_result else {
+                tracing::info!("Merkle tree is empty; skipping checkpoint export");
+                continue;
+            };
+            CHECKPOINT_METRICS
+                .last_exported_l1_batch
+                .set(manifest.l1_batch_number.0.into());
+            self.health_updater.update(
+                MerkleTreeCheckpointTaskHealth {
+                    last_exported_l1_batch_number: Some(manifest.l1_batch_number),
+                }
+                .into(),
+            );
+
+            if let Some(stale_manifest) = latest_manifest.replace(manifest) {
+                let latest_manifest = latest_manifest.as_ref().unwrap();
+                self.remove_stale_checkpoint(&stale_manifest, latest_manifest)
+                    .await?;
+            }
+        }
+
+        tracing::info!("Stop signal received, Merkle tree checkpoint exports are shutting down");
+        self.health_updater
+            .update(HealthStatus::ShuttingDown.into());
+        Ok(())
+    }
+}
+
+/// Determines the L1 batch (and its root hash) the imported tree should be truncated to, so that it corresponds
+/// to Postgres data.
+async fn import_target(
+    pool: &ConnectionPool<Core>,
+) -> anyhow::Result<Option<(L1BatchNumber, H256)>> {
+    let mut storage = pool.connection_tagged("metadata_calculator").await?;
+    if let Some(l1_batch_number) = storage
+        .blocks_dal()
+        .get_last_l1_batch_number_with_tree_data()
+        .await?
+    {
+        let tree_data = storage
+            .blocks_dal()
+            .get_l1_batch_tree_data(l1_batch_number)
+            .await?
+            .with_context(|| format!("no tree data for L1 batch #{l1_batch_number} in Postgres"))?;
+        return Ok(Some((l1_batch_number, tree_data.hash)));
+    }
+
+    let snapshot_recovery = storage
+        .snapshot_recovery_dal()
+        .get_applied_snapshot_status()
+        .await?;
+    Ok(snapshot_recovery.map(|status| (status.l1_batch_number, status.l1_batch_root_hash)))
+}
+
+/// Imports the latest Merkle tree checkpoint from the object store to `db_path` unless the tree RocksDB
+/// already exists. The imported tree is truncated to the latest L1 batch with tree data in Postgres
+/// (or the snapshot L1 batch if the node was recovered from a snapshot), and its root hash is checked
+/// against Postgres.
+///
+/// Import is skipped (i.e., the tree will be built from scratch or recovered from a Postgres snapshot as usual)
+/// if the store has no checkpoints, or if the latest checkpoint doesn't contain the target L1 batch.
+pub(super) async fn import_checkpoint(
+    object_store: &dyn ObjectStore,
+    pool: &ConnectionPool<Core>,
+    db_path: &Path,
+) -> anyhow::Result<()> {
+    if fs::try_exists(db_path.join("CURRENT")).await? {
+        tracing::info!(
+            "Merkle tree RocksDB at `{}` already exists; skipping checkpoint import",
+            db_path.display()
+        );
+        return Ok(());
+    }
+    let Some((target_l1_batch, target_root_hash)) = import_target(pool).await? else {
+        tracing::info!("Postgres contains no tree data; skipping Merkle tree checkpoint import");
+        return Ok(());
+    };
+    let Some(manifest) = latest_manifest(object_store).await? else {
+        tracing::warn!(
+            "Object store contains no Merkle tree checkpoints; skipping checkpoint import"
+        );
+        return Ok(());
+    };
+    if manifest.l1_batch_number < target_l1_batch {
+        tracing::warn!(
+            "Latest Merkle tree checkpoint (L1 batch #{}) is older than the target L1 batch #{target_l1_batch}; \
+             skipping checkpoint import",
+            manifest.l1_batch_number
+        );
+        return Ok(());
+    }
+
+    tracing::info!(
+        "Importing Merkle tree checkpoint for L1 batch #{} ({} files) and truncating it to L1 batch #{target_l1_batch}",
+        manifest.l1_batch_number,
+        manifest.files.len()
+    );
+    let latency = CHECKPOINT_METRICS.latency[&CheckpointStage::Download].start();
+    let import_path = sibling_path(db_path, "_import");
+    remove_dir_if_exists(&import_path).await?;
+    fs::create_dir_all(&import_path).await?;
+    for file in &manifest.files {
+        let file_key = manifest.file_key(file);
+        let download_result = object_store
+            .get_file_raw(
+                Bucket::MerkleTreeCheckpoints,
+                &file_key,
+                &import_path.join(&file.name),
+            )
+            .await;
+        let size = match download_result {
+            Ok(size) => size,
+            Err(ObjectStoreError::KeyNotFound(err)) => {
+                // Files of the checkpoint can be removed by the exporting node if it has exported a newer checkpoint
+                // in the meantime.
+                tracing::warn!(
+                    "Merkle tree checkpoint file `{}` is missing from the object store (probably, a newer checkpoint \
+                     was exported during import): {err}; skipping checkpoint import",
+                    file.name
+                );
+                remove_dir_if_exists(&import_path).await?;
+                return Ok(());
+            }
+            Err(err) => {
+                return Err(anyhow::Error::from(err).context(format!(
+                    "failed downloading checkpoint file `{}`",
+                    file.name
+                )));
+            }
+        };
+        anyhow::ensure!(
+            size == file.size,
+            "unexpected size of checkpoint file `{}`: expected {}, got {size}",
+            file.name,
+            file.size
+        );
+    }
+    latency.observe();
+
+    let truncate_path = import_path.clone();
+    let is_imported = tokio::task::spawn_blocking(move || {
+        let db = RocksDBWrapper::new(&truncate_path)
+            .context("failed opening imported Merkle tree checkpoint")?;
+        let mut tree = ZkSyncTree::new_lightweight(db)?;
+        let Some((root_hash, _)) = tree.reader().root_info(target_l1_batch) else {
+            tracing::warn!(
+                "Imported Merkle tree checkpoint doesn't contain L1 batch #{target_l1_batch} (probably, it was pruned); \
+                 skipping checkpoint import"
+            );
+            return Ok(false);
+        };
+        anyhow::ensure!(
+            root_hash == target_root_hash,
+            "Root hash of the imported Merkle tree for L1 batch #{target_l1_batch} ({root_hash:?}) differs from \
+             the hash in Postgres ({target_root_hash:?})"
+        );
+        tree.roll_back_logs(target_l1_batch)?;
+        tree.save()?;
+        Ok(true)
+    })
+    .await
+    .context("panicked truncating imported Merkle tree")??;
+
+    if is_imported {
+        if fs::try_exists(db_path).await? {
+            // The directory must be empty since it doesn't contain a RocksDB; removing it allows to rename
+            // the imported tree in its place.
+            fs::remove_dir(db_path).await.with_context(|| {
+                format!(
+                    "failed removing Merkle tree directory `{}`",
+                    db_path.display()
+                )
+            })?;
+        }
+        fs::rename(&import_path, db_path).await?;
+        tracing::info!(
+            "Imported Merkle tree checkpoint to `{}`; the tree is at L1 batch #{target_l1_batch}",
+            db_path.display()
+        );
+    } else {
+        remove_dir_if_exists(&import_path).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_object_store::MockObjectStore;
+
+    use super::*;
+    use crate::{
+        helpers::GenericAsyncTree,
+        tests::{
+            extend_db_state, gen_storage_logs, reset_db_state, run_calculator, setup_calculator,
+        },
+    };
+
+    #[test]
+    fn filtering_checkpoint_files() {
+        assert!(is_checkpoint_file("000012.sst"));
+        assert!(is_checkpoint_file("MANIFEST-000005"));
+        assert!(is_checkpoint_file("CURRENT"));
+        assert!(!is_checkpoint_file("LOCK"));
+        assert!(!is_checkpoint_file("LOG"));
+        assert!(!is_checkpoint_file("LOG.old.1715000000000000"));
+    }
+
+    #[tokio::test]
+    async fn exporting_and_importing_checkpoint() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone()).await;
+        reset_db_state(&pool, 1).await;
+        let object_store = MockObjectStore::arc();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let task = calculator.checkpoint_task(object_store.clone(), Duration::from_millis(10));
+        let mut health_check = task.health_check();
+        let task_handle = tokio::spawn(task.run(stop_receiver.clone()));
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver));
+
+        health_check
+            .wait_for(|health| {
+                let details = health
+                    .details()
+                    .and_then(|details| details.get("last_exported_l1_batch_number")?.as_u64());
+                matches!(health.status(), HealthStatus::Ready) && details == Some(1)
+            })
+            .await;
+        stop_sender.send_replace(true);
+        task_handle.await.unwrap().unwrap();
+        calculator_handle.await.unwrap().unwrap();
+
+        let manifest = latest_manifest(object_store.as_ref())
+            .await
+            .unwrap()
+            .expect("no exported checkpoint");
+        assert_eq!(manifest.l1_batch_number, L1BatchNumber(1));
+        assert!(!manifest.files.is_empty());
+        assert!(
+            manifest.files.iter().any(CheckpointFile::is_immutable),
+            "{manifest:?}"
+        );
+
+        // Import the checkpoint and check that the tree can proceed from it.
+        let import_dir = TempDir::new().unwrap();
+        import_checkpoint(object_store.as_ref(), &pool, &import_dir.path().join("new"))
+            .await
+            .unwrap();
+        let (calculator, _) = setup_calculator(import_dir.path(), pool.clone()).await;
+        let tree = calculator.create_tree().await.unwrap();
+        let GenericAsyncTree::Ready(tree) = tree else {
+            panic!("Unexpected tree state: {tree:?}");
+        };
+        assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(2));
+        assert_eq!(tree.root_hash(), manifest.root_hash);
+        drop((tree, calculator));
+
+        let new_logs = gen_storage_logs(100..200, 5);
+        extend_db_state(&mut pool.connection().await.unwrap(), new_logs).await;
+        let (calculator, _) = setup_calculator(import_dir.path(), pool.clone()).await;
+        let imported_root_hash = run_calculator(calculator).await;
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool).await;
+        let root_hash = run_calculator(calculator).await;
+        assert_eq!(imported_root_hash, root_hash);
+    }
+
+    async fn export_checkpoint_for_tests(
+        pool: &ConnectionPool<Core>,
+        temp_dir: &TempDir,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> (
+        MerkleTreeCheckpointTask,
+        AsyncTreeReader,
+        CheckpointManifest,
+    ) {
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone()).await;
+        reset_db_state(pool, 1).await;
+        run_calculator(calculator).await;
+
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone()).await;
+        let task = calculator.checkpoint_task(object_store, Duration::from_secs(1_000));
+        let tree = calculator.create_tree().await.unwrap();
+        let GenericAsyncTree::Ready(tree) = tree else {
+            panic!("Unexpected tree state: {tree:?}");
+        };
+        let tree_reader = tree.reader();
+        let manifest = task
+            .export_checkpoint(&tree_reader, None)
+            .await
+            .unwrap()
+            .expect("tree is empty");
+        (task, tree_reader, manifest)
+    }
+
+    #[tokio::test]
+    async fn immutable_checkpoint_files_are_uploaded_once() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let object_store = MockObjectStore::arc();
+        let (task, tree_reader, manifest) =
+            export_checkpoint_for_tests(&pool, &temp_dir, object_store.clone()).await;
+
+        // Remove immutable files from the store; they must not be re-uploaded by the following export.
+        let immutable_keys: Vec<_> = manifest
+            .files
+            .iter()
+            .filter(|file| file.is_immutable())
+            .map(|file| manifest.file_key(file))
+            .collect();
+        assert!(!immutable_keys.is_empty(), "{manifest:?}");
+        for key in &immutable_keys {
+            object_store
+                .remove_raw(Bucket::MerkleTreeCheckpoints, key)
+                .await
+                .unwrap();
+        }
+
+        let new_manifest = task
+            .export_checkpoint(&tree_reader, Some(&manifest))
+            .await
+            .unwrap()
+            .expect("tree is empty");
+        let new_keys: HashSet<_> = new_manifest.file_keys().collect();
+        for key in &immutable_keys {
+            assert!(new_keys.contains(key), "{new_manifest:?}");
+            let err = object_store
+                .get_raw(Bucket::MerkleTreeCheckpoints, key)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+        }
+        // Mutable files are uploaded.
+        for file in new_manifest
+            .files
+            .iter()
+            .filter(|file| !file.is_immutable())
+        {
+            object_store
+                .get_raw(Bucket::MerkleTreeCheckpoints, &new_manifest.file_key(file))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn checkpoint_import_is_skipped_if_files_are_missing() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let object_store = MockObjectStore::arc();
+        let (_, _, manifest) =
+            export_checkpoint_for_tests(&pool, &temp_dir, object_store.clone()).await;
+
+        // Emulate removing the checkpoint concurrently with import.
+        let removed_key = manifest.file_key(&manifest.files[0]);
+        object_store
+            .remove_raw(Bucket::MerkleTreeCheckpoints, &removed_key)
+            .await
+            .unwrap();
+
+        let import_dir = TempDir::new().unwrap();
+        let db_path = import_dir.path().join("new");
+        import_checkpoint(object_store.as_ref(), &pool, &db_path)
+            .await
+            .unwrap();
+        assert!(!db_path.join("CURRENT").exists());
+        assert!(!sibling_path(&db_path, "_import").exists());
+
+        // The tree is initialized from Postgres as usual.
+        let (calculator, _) = setup_calculator(import_dir.path(), pool.clone()).await;
+        let root_hash = run_calculator(calculator).await;
+        assert_eq!(root_hash, manifest.root_hash);
+    }
+}
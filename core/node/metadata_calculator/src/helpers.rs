@@ -1,9 +1,10 @@
 //! Various helpers for the metadata calculator.
 
 use std::{
-    collections::{BTreeMap, HashSet},
+    any::Any,
+    collections::{BTreeMap, HashMap, HashSet},
     future::Future,
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
@@ -22,8 +23,10 @@ use zksync_health_check::{CheckHealth, Health, HealthStatus, ReactiveHealthCheck
 use zksync_merkle_tree::{
     domain::{TreeMetadata, ZkSyncTree, ZkSyncTreeReader},
     recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
-    Database, Key, MerkleTreeColumnFamily, NoVersionError, RocksDBWrapper, TreeEntry,
-    TreeEntryWithProof, TreeInstruction,
+    unstable::{DeserializeError, Manifest, Node, NodeKey, ProfiledTreeOperation, Root},
+    Database, HashTree, InMemoryDatabase, Key, MerkleTreeColumnFamily, NoVersionError, PatchSet,
+    PruneDatabase, PrunePatchSet, RocksDBWrapper, TreeDiff, TreeEntry, TreeEntryWithProof,
+    TreeHasher, TreeInstruction, TreeRangeProof,
 };
 use zksync_storage::{RocksDB, RocksDBOptions, StalledWritesRetries, WeakRocksDB};
 use zksync_types::{
@@ -126,14 +129,36 @@ impl CheckHealth for MerkleTreeHealthCheck {
     }
 }
 
-/// Creates a RocksDB wrapper with the specified params.
-pub(super) async fn create_db(config: MetadataCalculatorConfig) -> anyhow::Result<RocksDBWrapper> {
-    tokio::task::spawn_blocking(move || create_db_sync(&config))
+/// Creates a tree database with the specified params. If `in_memory_db` is provided, it is used instead of RocksDB.
+pub(super) async fn create_db(
+    config: MetadataCalculatorConfig,
+    in_memory_db: Option<InMemoryDatabase>,
+) -> anyhow::Result<TreeDatabase> {
+    if let Some(db) = in_memory_db {
+        tracing::info!("Using in-memory Merkle tree database");
+        return Ok(db.into());
+    }
+    let db = tokio::task::spawn_blocking(move || create_db_sync(&config, None))
         .await
-        .context("panicked creating Merkle tree RocksDB")?
+        .context("panicked creating Merkle tree RocksDB")??;
+    Ok(db.into())
 }
 
-fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWrapper> {
+/// Creates a read-only secondary RocksDB instance for the tree RocksDB at `config.db_path`.
+pub(super) async fn create_secondary_db(
+    config: MetadataCalculatorConfig,
+    secondary_path: PathBuf,
+) -> anyhow::Result<TreeDatabase> {
+    let db = tokio::task::spawn_blocking(move || create_db_sync(&config, Some(&secondary_path)))
+        .await
+        .context("panicked creating secondary Merkle tree RocksDB")??;
+    Ok(db.into())
+}
+
+fn create_db_sync(
+    config: &MetadataCalculatorConfig,
+    secondary_path: Option<&Path>,
+) -> anyhow::Result<RocksDBWrapper> {
     let path = Path::new(config.db_path.as_str());
     let &MetadataCalculatorConfig {
         max_open_files,
@@ -153,16 +178,18 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
         path = path.display()
     );
 
-    let mut db = RocksDB::with_options(
-        path,
-        RocksDBOptions {
-            block_cache_capacity: Some(block_cache_capacity),
-            include_indices_and_filters_in_block_cache,
-            large_memtable_capacity: Some(memtable_capacity),
-            stalled_writes_retries: StalledWritesRetries::new(stalled_writes_timeout),
-            max_open_files,
-        },
-    )?;
+    let options = RocksDBOptions {
+        block_cache_capacity: Some(block_cache_capacity),
+        include_indices_and_filters_in_block_cache,
+        large_memtable_capacity: Some(memtable_capacity),
+        stalled_writes_retries: StalledWritesRetries::new(stalled_writes_timeout),
+        max_open_files,
+    };
+    let mut db = if let Some(secondary_path) = secondary_path {
+        RocksDB::secondary(path, secondary_path, options)?
+    } else {
+        RocksDB::with_options(path, options)?
+    };
     if cfg!(test) {
         // We need sync writes for the unit tests to execute reliably. With the default config,
         // some writes to RocksDB may occur, but not be visible to the test code.
@@ -173,6 +200,159 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
     Ok(db)
 }
 
+/// Database backing the Merkle tree. Besides RocksDB used in production, the tree can be backed
+/// by an [`InMemoryDatabase`]; this allows testing components depending on the tree without creating RocksDB instances.
+#[derive(Debug, Clone)]
+pub(crate) enum TreeDatabase {
+    RocksDB(RocksDBWrapper),
+    InMemory(InMemoryDatabase),
+}
+
+impl From<RocksDBWrapper> for TreeDatabase {
+    fn from(db: RocksDBWrapper) -> Self {
+        Self::RocksDB(db)
+    }
+}
+
+impl From<InMemoryDatabase> for TreeDatabase {
+    fn from(db: InMemoryDatabase) -> Self {
+        Self::InMemory(db)
+    }
+}
+
+impl TreeDatabase {
+    fn is_secondary(&self) -> bool {
+        match self {
+            Self::RocksDB(db) => db.is_secondary(),
+            Self::InMemory(_) => false,
+        }
+    }
+
+    fn try_catch_up_with_primary(&self) -> anyhow::Result<()> {
+        match self {
+            Self::RocksDB(db) => db.try_catch_up_with_primary().map_err(Into::into),
+            Self::InMemory(_) => Ok(()),
+        }
+    }
+
+    fn compact(&self) -> bool {
+        match self {
+            Self::RocksDB(db) => db.compact(),
+            // Pruned data is removed from the in-memory database right away, so there's nothing to compact.
+            Self::InMemory(_) => true,
+        }
+    }
+
+    fn create_checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+        match self {
+            Self::RocksDB(db) => db.create_checkpoint(path).map_err(Into::into),
+            Self::InMemory(_) => anyhow::bail!("checkpoints are not supported for in-memory trees"),
+        }
+    }
+
+    fn downgrade(&self) -> WeakTreeDatabase {
+        match self {
+            Self::RocksDB(db) => WeakTreeDatabase::RocksDB(db.clone().into_inner().downgrade()),
+            Self::InMemory(db) => WeakTreeDatabase::InMemory(db.clone()),
+        }
+    }
+}
+
+impl Database for TreeDatabase {
+    fn try_manifest(&self) -> Result<Option<Manifest>, DeserializeError> {
+        match self {
+            Self::RocksDB(db) => db.try_manifest(),
+            Self::InMemory(db) => db.try_manifest(),
+        }
+    }
+
+    fn try_root(&self, version: u64) -> Result<Option<Root>, DeserializeError> {
+        match self {
+            Self::RocksDB(db) => db.try_root(version),
+            Self::InMemory(db) => db.try_root(version),
+        }
+    }
+
+    fn try_tree_node(
+        &self,
+        key: &NodeKey,
+        is_leaf: bool,
+    ) -> Result<Option<Node>, DeserializeError> {
+        match self {
+            Self::RocksDB(db) => db.try_tree_node(key, is_leaf),
+            Self::InMemory(db) => db.try_tree_node(key, is_leaf),
+        }
+    }
+
+    fn tree_nodes(&self, keys: &[(NodeKey, bool)]) -> Vec<Option<Node>> {
+        match self {
+            Self::RocksDB(db) => db.tree_nodes(keys),
+            Self::InMemory(db) => db.tree_nodes(keys),
+        }
+    }
+
+    fn start_profiling(&self, operation: ProfiledTreeOperation) -> Box<dyn Any> {
+        match self {
+            Self::RocksDB(db) => db.start_profiling(operation),
+            Self::InMemory(db) => db.start_profiling(operation),
+        }
+    }
+
+    fn apply_patch(&mut self, patch: PatchSet) -> anyhow::Result<()> {
+        match self {
+            Self::RocksDB(db) => db.apply_patch(patch),
+            Self::InMemory(db) => db.apply_patch(patch),
+        }
+    }
+}
+
+impl PruneDatabase for TreeDatabase {
+    fn min_stale_key_version(&self) -> Option<u64> {
+        match self {
+            Self::RocksDB(db) => db.min_stale_key_version(),
+            Self::InMemory(db) => db.min_stale_key_version(),
+        }
+    }
+
+    fn stale_keys(&self, version: u64) -> Vec<NodeKey> {
+        match self {
+            Self::RocksDB(db) => db.stale_keys(version),
+            Self::InMemory(db) => db.stale_keys(version),
+        }
+    }
+
+    fn prune(&mut self, patch: PrunePatchSet) -> anyhow::Result<()> {
+        match self {
+            Self::RocksDB(db) => db.prune(patch),
+            Self::InMemory(db) => db.prune(patch),
+        }
+    }
+
+    fn compact_pruned_data(&mut self, end_version: u64) {
+        match self {
+            Self::RocksDB(db) => db.compact_pruned_data(end_version),
+            Self::InMemory(db) => db.compact_pruned_data(end_version),
+        }
+    }
+}
+
+/// Weak version of [`TreeDatabase`]. In-memory databases are retained as is since they don't hold any resources
+/// that must be released on node shutdown.
+#[derive(Debug)]
+enum WeakTreeDatabase {
+    RocksDB(WeakRocksDB<MerkleTreeColumnFamily>),
+    InMemory(InMemoryDatabase),
+}
+
+impl WeakTreeDatabase {
+    fn upgrade(&self) -> Option<TreeDatabase> {
+        Some(match self {
+            Self::RocksDB(db) => TreeDatabase::RocksDB(db.upgrade()?.into()),
+            Self::InMemory(db) => TreeDatabase::InMemory(db.clone()),
+        })
+    }
+}
+
 /// Wrapper around the "main" tree implementation used by [`MetadataCalculator`].
 ///
 /// Async methods provided by this wrapper are not cancel-safe! This is probably not an issue;
@@ -183,7 +363,7 @@ fn create_db_sync(config: &MetadataCalculatorConfig) -> anyhow::Result<RocksDBWr
 /// cancellation is most probably the reason.
 #[derive(Debug)]
 pub(super) struct AsyncTree {
-    inner: Option<ZkSyncTree>,
+    inner: Option<ZkSyncTree<TreeDatabase>>,
     mode: MerkleTreeMode,
 }
 
@@ -191,10 +371,18 @@ impl AsyncTree {
     const INCONSISTENT_MSG: &'static str =
         "`AsyncTree` is in inconsistent state, which could occur after one of its async methods was cancelled or returned an error";
 
-    pub fn new(db: RocksDBWrapper, mode: MerkleTreeMode) -> anyhow::Result<Self> {
+    /// Creates a tree. `new_tree_hasher` is only used if the tree is new; otherwise, the hasher is determined
+    /// based on the tree tags.
+    pub fn new(
+        db: TreeDatabase,
+        mode: MerkleTreeMode,
+        new_tree_hasher: TreeHasher,
+    ) -> anyhow::Result<Self> {
         let tree = match mode {
-            MerkleTreeMode::Full => ZkSyncTree::new(db),
-            MerkleTreeMode::Lightweight => ZkSyncTree::new_lightweight(db),
+            MerkleTreeMode::Full => ZkSyncTree::new_with_hasher(db, new_tree_hasher),
+            MerkleTreeMode::Lightweight => {
+                ZkSyncTree::new_lightweight_with_hasher(db, new_tree_hasher)
+            }
         }?;
         Ok(Self {
             inner: Some(tree),
@@ -202,11 +390,11 @@ impl AsyncTree {
         })
     }
 
-    fn as_ref(&self) -> &ZkSyncTree {
+    fn as_ref(&self) -> &ZkSyncTree<TreeDatabase> {
         self.inner.as_ref().expect(Self::INCONSISTENT_MSG)
     }
 
-    fn as_mut(&mut self) -> &mut ZkSyncTree {
+    fn as_mut(&mut self) -> &mut ZkSyncTree<TreeDatabase> {
         self.inner.as_mut().expect(Self::INCONSISTENT_MSG)
     }
 
@@ -214,10 +402,18 @@ impl AsyncTree {
         self.mode
     }
 
+    pub fn hasher(&self) -> TreeHasher {
+        self.as_ref().hasher()
+    }
+
     pub fn pruner(&mut self) -> PruningHandles {
         self.as_mut().pruner()
     }
 
+    pub fn use_dedicated_thread_pool(&mut self, thread_count: usize) {
+        self.as_mut().use_dedicated_thread_pool(thread_count);
+    }
+
     pub fn reader(&self) -> AsyncTreeReader {
         AsyncTreeReader {
             inner: self.inner.as_ref().expect(Self::INCONSISTENT_MSG).reader(),
@@ -265,6 +461,33 @@ impl AsyncTree {
         Ok(metadata)
     }
 
+    /// Computes the root hash of the tree at the specified L1 batch using the provided `hasher`, taking into account
+    /// changes that are not saved yet. This is expensive if `hasher` differs from the tree hasher.
+    ///
+    /// Returned errors are unrecoverable; the tree must not be used after an error is returned.
+    pub async fn root_hash_with_hasher(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        hasher: TreeHasher,
+    ) -> anyhow::Result<H256> {
+        let tree = self.inner.take().context(Self::INCONSISTENT_MSG)?;
+        let (tree, root_hash) = tokio::task::spawn_blocking(move || {
+            let root_hash = tree.root_hash_with_hasher(l1_batch_number, hasher);
+            (tree, root_hash)
+        })
+        .await
+        .with_context(|| {
+            format!("Merkle tree panicked when rehashing L1 batch #{l1_batch_number}")
+        })?;
+
+        self.inner = Some(tree);
+        root_hash.with_context(|| {
+            format!(
+                "failed computing root hash for L1 batch #{l1_batch_number} with {hasher:?} hasher"
+            )
+        })
+    }
+
     /// Returned errors are unrecoverable; the tree must not be used after an error is returned.
     pub async fn save(&mut self) -> anyhow::Result<()> {
         let mut tree = self.inner.take().context(Self::INCONSISTENT_MSG)?;
@@ -287,14 +510,45 @@ impl AsyncTree {
 /// Async version of [`ZkSyncTreeReader`].
 #[derive(Debug, Clone)]
 pub struct AsyncTreeReader {
-    inner: ZkSyncTreeReader,
+    inner: ZkSyncTreeReader<TreeDatabase>,
     mode: MerkleTreeMode,
 }
 
 impl AsyncTreeReader {
+    pub(super) fn new(db: TreeDatabase, mode: MerkleTreeMode) -> anyhow::Result<Self> {
+        Ok(Self {
+            inner: ZkSyncTreeReader::new(db)?,
+            mode,
+        })
+    }
+
+    /// Creates a reader for a tree backed by the specified in-memory database. Useful for testing
+    /// components depending on the tree, e.g. the tree API.
+    pub fn in_memory(db: InMemoryDatabase, mode: MerkleTreeMode) -> anyhow::Result<Self> {
+        Self::new(db.into(), mode)
+    }
+
+    pub(crate) fn hasher(&self) -> TreeHasher {
+        self.inner.hasher()
+    }
+
+    /// Checks whether this reader is backed by a read-only tree replica.
+    pub(crate) fn is_replica(&self) -> bool {
+        self.inner.db().is_secondary()
+    }
+
+    /// Makes a tree replica catch up with the primary tree. This is a no-op if the reader is not backed by a replica.
+    pub(crate) async fn catch_up_with_primary(&self) -> anyhow::Result<()> {
+        let db = self.inner.db().clone();
+        tokio::task::spawn_blocking(move || db.try_catch_up_with_primary())
+            .await
+            .context("panicked catching up with primary Merkle tree RocksDB")?
+            .context("failed catching up with primary Merkle tree RocksDB")
+    }
+
     fn downgrade(&self) -> WeakAsyncTreeReader {
         WeakAsyncTreeReader {
-            db: self.inner.db().clone().into_inner().downgrade(),
+            db: self.inner.db().downgrade(),
             mode: self.mode,
         }
     }
@@ -308,7 +562,7 @@ impl AsyncTreeReader {
                     self.inner.root_info(L1BatchNumber(number))
                 } else {
                     // No L1 batches in the tree yet.
-                    Some((ZkSyncTree::empty_tree_hash(), 0))
+                    Some((self.inner.hasher().empty_tree_hash(), 0))
                 };
                 let Some((root_hash, leaf_count)) = root_info else {
                     // It is possible (although very unlikely) that the latest tree version was removed after requesting it,
@@ -351,19 +605,101 @@ impl AsyncTreeReader {
             .await
             .unwrap()
     }
+
+    pub(crate) async fn root_info(self, l1_batch_number: L1BatchNumber) -> Option<(H256, u64)> {
+        tokio::task::spawn_blocking(move || self.inner.root_info(l1_batch_number))
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn range_proof(
+        self,
+        l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        end_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeRangeProof, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner
+                .range_proof(l1_batch_number, start_key, end_key, max_entries)
+        })
+        .await
+        .unwrap()
+    }
+
+    pub(crate) async fn diff(
+        self,
+        old_l1_batch_number: L1BatchNumber,
+        new_l1_batch_number: L1BatchNumber,
+        start_key: Key,
+        max_entries: usize,
+    ) -> Result<TreeDiff, NoVersionError> {
+        tokio::task::spawn_blocking(move || {
+            self.inner.diff(
+                old_l1_batch_number,
+                new_l1_batch_number,
+                start_key,
+                max_entries,
+            )
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Performs manual compaction of the tree RocksDB. Returns `false` if another manual compaction
+    /// is already in progress.
+    ///
+    /// Compaction runs on a blocking thread and is not cancelled if the returned future is dropped.
+    pub async fn compact(self) -> bool {
+        tokio::task::spawn_blocking(move || self.inner.db().compact())
+            .await
+            .unwrap()
+    }
+
+    /// Creates a consistent checkpoint of the tree RocksDB in the specified directory, which must not exist.
+    pub(crate) async fn create_checkpoint(self, path: PathBuf) -> anyhow::Result<()> {
+        tokio::task::spawn_blocking(move || self.inner.db().create_checkpoint(&path))
+            .await
+            .context("panicked creating Merkle tree RocksDB checkpoint")?
+            .context("failed creating Merkle tree RocksDB checkpoint")
+    }
+
+    /// Periodically performs manual compaction of the tree RocksDB until a stop signal is received.
+    pub(crate) async fn run_periodic_compaction(
+        self,
+        interval: Duration,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting periodic compaction of Merkle tree RocksDB with interval {interval:?}"
+        );
+        while tokio::time::timeout(interval, stop_receiver.changed())
+            .await
+            .is_err()
+        {
+            tracing::info!("Starting scheduled compaction of Merkle tree RocksDB");
+            if !self.clone().compact().await {
+                tracing::info!(
+                    "Skipped scheduled tree compaction since another compaction is in progress"
+                );
+            }
+        }
+        tracing::info!("Stop signal received, stopping periodic compaction of Merkle tree RocksDB");
+        Ok(())
+    }
 }
 
 /// Version of async tree reader that holds a weak reference to RocksDB. Used in [`MerkleTreeHealthCheck`].
 #[derive(Debug)]
 struct WeakAsyncTreeReader {
-    db: WeakRocksDB<MerkleTreeColumnFamily>,
+    db: WeakTreeDatabase,
     mode: MerkleTreeMode,
 }
 
 impl WeakAsyncTreeReader {
     fn upgrade(&self) -> Option<AsyncTreeReader> {
         Some(AsyncTreeReader {
-            inner: ZkSyncTreeReader::new(self.db.upgrade()?.into()).ok()?,
+            inner: ZkSyncTreeReader::new(self.db.upgrade()?).ok()?,
             mode: self.mode,
         })
     }
@@ -393,34 +729,49 @@ impl LazyAsyncTreeReader {
     }
 }
 
+/// Returns the hasher that should be used to commit to the tree state after the specified L1 batch.
+/// This may differ from the hasher of the tree itself during a transition between hashers.
+pub(crate) fn committed_tree_hasher(header: &L1BatchHeader) -> TreeHasher {
+    header
+        .protocol_version
+        .map_or_else(TreeHasher::default, TreeHasher::for_protocol_version)
+}
+
 /// Async wrapper for [`MerkleTreeRecovery`].
 #[derive(Debug, Default)]
 pub(super) struct AsyncTreeRecovery {
-    inner: Option<MerkleTreeRecovery<RocksDBWrapper>>,
+    inner: Option<MerkleTreeRecovery<TreeDatabase, TreeHasher>>,
     mode: MerkleTreeMode,
 }
 
 impl AsyncTreeRecovery {
     const INCONSISTENT_MSG: &'static str =
         "`AsyncTreeRecovery` is in inconsistent state, which could occur after one of its async methods was cancelled";
+    /// Prefix of custom tree tags recording recovered chunks. The tag key is completed with the chunk ID,
+    /// and the value is the digest of the chunk entries.
+    const RECOVERED_CHUNK_TAG_PREFIX: &'static str = "recovery.chunk.";
+    const CHUNK_SIZE_KEY: &'static str = "recovery.desired_chunk_size";
 
+    /// Starts or resumes tree recovery. `hasher` must match the hasher of the tree being recovered (if any).
     pub fn new(
-        db: RocksDBWrapper,
+        db: TreeDatabase,
         recovered_version: u64,
         mode: MerkleTreeMode,
+        hasher: TreeHasher,
         config: &MetadataCalculatorRecoveryConfig,
     ) -> anyhow::Result<Self> {
-        Ok(Self::with_handle(db, recovered_version, mode, config)?.0)
+        Ok(Self::with_handle(db, recovered_version, mode, hasher, config)?.0)
     }
 
     // Public for testing purposes
     pub fn with_handle(
-        db: RocksDBWrapper,
+        db: TreeDatabase,
         recovered_version: u64,
         mode: MerkleTreeMode,
+        hasher: TreeHasher,
         config: &MetadataCalculatorRecoveryConfig,
     ) -> anyhow::Result<(Self, Option<PersistenceThreadHandle>)> {
-        let mut recovery = MerkleTreeRecovery::new(db, recovered_version)?;
+        let mut recovery = MerkleTreeRecovery::with_hasher(db, recovered_version, hasher)?;
         let handle = config
             .parallel_persistence_buffer
             .map(|buffer_capacity| recovery.parallelize_persistence(buffer_capacity.get()))
@@ -443,14 +794,12 @@ impl AsyncTreeRecovery {
         &mut self,
         desired_chunk_size: u64,
     ) -> anyhow::Result<()> {
-        const CHUNK_SIZE_KEY: &str = "recovery.desired_chunk_size";
-
         let mut tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
         let tree = tokio::task::spawn_blocking(move || {
             // **Important.** Tags should not be mutated on error (i.e., it would be an error to unconditionally call `tags.insert()`
             // and then check the previous value).
             tree.update_custom_tags(|tags| {
-                if let Some(chunk_size_in_tree) = tags.get(CHUNK_SIZE_KEY) {
+                if let Some(chunk_size_in_tree) = tags.get(Self::CHUNK_SIZE_KEY) {
                     let chunk_size_in_tree: u64 = chunk_size_in_tree
                         .parse()
                         .with_context(|| format!("error parsing desired_chunk_size `{chunk_size_in_tree}` in Merkle tree tags"))?;
@@ -460,7 +809,7 @@ impl AsyncTreeRecovery {
                          Either change the desired chunk size in configuration, or reset Merkle tree recovery by clearing its RocksDB directory"
                     );
                 } else {
-                    tags.insert(CHUNK_SIZE_KEY.to_owned(), desired_chunk_size.to_string());
+                    tags.insert(Self::CHUNK_SIZE_KEY.to_owned(), desired_chunk_size.to_string());
                 }
                 Ok(())
             })
@@ -473,6 +822,30 @@ impl AsyncTreeRecovery {
         Ok(())
     }
 
+    /// Returns digests of chunks recovered so far, keyed by the chunk ID.
+    pub fn recovered_chunks(&self) -> anyhow::Result<HashMap<u64, H256>> {
+        let tags = self
+            .inner
+            .as_ref()
+            .expect(Self::INCONSISTENT_MSG)
+            .custom_tags();
+        let chunks = tags.iter().filter_map(|(key, value)| {
+            let chunk_id = key.strip_prefix(Self::RECOVERED_CHUNK_TAG_PREFIX)?;
+            Some((chunk_id, value))
+        });
+        chunks
+            .map(|(chunk_id, digest)| {
+                let chunk_id = chunk_id.parse().with_context(|| {
+                    format!("error parsing recovered chunk ID `{chunk_id}` in Merkle tree tags")
+                })?;
+                let digest = digest.parse().with_context(|| {
+                    format!("error parsing digest `{digest}` for recovered chunk {chunk_id} in Merkle tree tags")
+                })?;
+                Ok((chunk_id, digest))
+            })
+            .collect()
+    }
+
     /// Returns an entry for the specified keys.
     pub async fn entries(&mut self, keys: Vec<Key>) -> Vec<TreeEntry> {
         let tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
@@ -493,11 +866,19 @@ impl AsyncTreeRecovery {
         root_hash
     }
 
-    /// Extends the tree with a chunk of recovery entries.
-    pub async fn extend(&mut self, entries: Vec<TreeEntry>) -> anyhow::Result<()> {
+    /// Extends the tree with a chunk of recovery entries. The chunk is marked as recovered atomically with persisting its entries.
+    pub async fn extend_chunk(
+        &mut self,
+        chunk_id: u64,
+        digest: H256,
+        entries: Vec<TreeEntry>,
+    ) -> anyhow::Result<()> {
         let mut tree = self.inner.take().expect(Self::INCONSISTENT_MSG);
         let tree = tokio::task::spawn_blocking(move || {
-            tree.extend_random(entries)?;
+            tree.extend_random_with_custom_tags(entries, |tags| {
+                let tag_key = format!("{}{chunk_id}", Self::RECOVERED_CHUNK_TAG_PREFIX);
+                tags.insert(tag_key, format!("{digest:?}"));
+            })?;
             anyhow::Ok(tree)
         })
         .await
@@ -516,11 +897,23 @@ impl AsyncTreeRecovery {
     }
 
     pub async fn finalize(self) -> anyhow::Result<AsyncTree> {
-        let tree = self.inner.expect(Self::INCONSISTENT_MSG);
-        let db = tokio::task::spawn_blocking(|| tree.finalize())
-            .await
-            .context("finalizing tree panicked")??;
-        AsyncTree::new(db, self.mode)
+        let mut tree = self.inner.expect(Self::INCONSISTENT_MSG);
+        let db = tokio::task::spawn_blocking(move || {
+            // Recovery-specific tags are meaningless once recovery is complete; remove them so that they don't linger
+            // in the tree manifest forever.
+            tree.update_custom_tags(|tags| {
+                tags.retain(|key, _| {
+                    key != Self::CHUNK_SIZE_KEY
+                        && !key.starts_with(Self::RECOVERED_CHUNK_TAG_PREFIX)
+                });
+            })
+            .context("failed removing recovery tags from Merkle tree")?;
+            tree.finalize()
+        })
+        .await
+        .context("finalizing tree panicked")??;
+        // The hasher is read from the tree tags.
+        AsyncTree::new(db, self.mode, TreeHasher::default())
     }
 }
 
@@ -529,7 +922,7 @@ impl AsyncTreeRecovery {
 pub(super) enum GenericAsyncTree {
     /// Uninitialized tree.
     Empty {
-        db: RocksDBWrapper,
+        db: TreeDatabase,
         mode: MerkleTreeMode,
     },
     /// The tree during recovery.
@@ -539,20 +932,20 @@ pub(super) enum GenericAsyncTree {
 }
 
 impl GenericAsyncTree {
-    pub async fn new(
-        db: RocksDBWrapper,
-        config: &MetadataCalculatorConfig,
-    ) -> anyhow::Result<Self> {
+    pub async fn new(db: TreeDatabase, config: &MetadataCalculatorConfig) -> anyhow::Result<Self> {
         let mode = config.mode;
         let recovery = config.recovery.clone();
         tokio::task::spawn_blocking(move || {
             let Some(manifest) = db.manifest() else {
                 return Ok(Self::Empty { db, mode });
             };
+            let hasher = TreeHasher::from_db(&db)?.unwrap_or_default();
             anyhow::Ok(if let Some(version) = manifest.recovered_version() {
-                Self::Recovering(AsyncTreeRecovery::new(db, version, mode, &recovery)?)
+                Self::Recovering(AsyncTreeRecovery::new(
+                    db, version, mode, hasher, &recovery,
+                )?)
             } else {
-                Self::Ready(AsyncTree::new(db, mode)?)
+                Self::Ready(AsyncTree::new(db, mode, hasher)?)
             })
         })
         .await
@@ -947,8 +1340,8 @@ mod tests {
     }
 
     async fn create_tree(temp_dir: &TempDir) -> AsyncTree {
-        let db = create_db(mock_config(temp_dir.path())).await.unwrap();
-        AsyncTree::new(db, MerkleTreeMode::Full).unwrap()
+        let db = create_db(mock_config(temp_dir.path()), None).await.unwrap();
+        AsyncTree::new(db, MerkleTreeMode::Full, TreeHasher::default()).unwrap()
     }
 
     async fn assert_log_equivalence(
@@ -3,6 +3,7 @@
 
 use std::{
     num::{NonZeroU32, NonZeroUsize},
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -15,27 +16,34 @@ use zksync_config::configs::{
 };
 use zksync_dal::{ConnectionPool, Core};
 use zksync_health_check::{CheckHealth, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::InMemoryDatabase;
 use zksync_object_store::ObjectStore;
 
+pub use self::{
+    checkpoint::MerkleTreeCheckpointTask,
+    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
+    pruning::MerkleTreePruningTask,
+    replica::MerkleTreeReplica,
+    verification::MerkleTreeVerificationTask,
+};
 use self::{
     helpers::{create_db, Delayer, GenericAsyncTree, MerkleTreeHealth, MerkleTreeHealthCheck},
     metrics::{ConfigLabels, METRICS},
     pruning::PruningHandles,
     updater::TreeUpdater,
 };
-pub use self::{
-    helpers::{AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeInfo},
-    pruning::MerkleTreePruningTask,
-};
 
 pub mod api_server;
+mod checkpoint;
 mod helpers;
 mod metrics;
 mod pruning;
 mod recovery;
+mod replica;
 #[cfg(test)]
 pub(crate) mod tests;
 mod updater;
+mod verification;
 
 #[derive(Debug, Clone)]
 pub struct MetadataCalculatorRecoveryConfig {
@@ -78,6 +86,9 @@ pub struct MetadataCalculatorConfig {
     /// Chunk size for multi-get operations. Can speed up loading data for the Merkle tree on some environments,
     /// but the effects vary wildly depending on the setup (e.g., the filesystem used).
     pub multi_get_chunk_size: usize,
+    /// Number of threads in a dedicated thread pool used to hash independent subtrees when processing L1 batches.
+    /// If not specified, the global `rayon` thread pool will be used. 0 means the default number of threads.
+    pub hashing_thread_count: Option<usize>,
     /// Capacity of RocksDB block cache in bytes. Reasonable values range from ~100 MiB to several GB.
     pub block_cache_capacity: usize,
     /// If specified, RocksDB indices and Bloom filters will be managed by the block cache, rather than
@@ -89,6 +100,18 @@ pub struct MetadataCalculatorConfig {
     pub memtable_capacity: usize,
     /// Timeout to wait for the Merkle tree database to run compaction on stalled writes.
     pub stalled_writes_timeout: Duration,
+    /// Number of latest L1 batches for which tree versions are retained by [`MerkleTreePruningTask`].
+    /// If not specified, the tree is only pruned following Postgres pruning.
+    pub pruning_retained_l1_batches: Option<NonZeroU32>,
+    /// Interval between manual compactions of the tree RocksDB. If not specified, the tree is only compacted
+    /// automatically by RocksDB, or manually via the tree API.
+    pub compaction_interval: Option<Duration>,
+    /// Interval between iterations of [`MerkleTreeVerificationTask`]. If not specified, the tree is not verified
+    /// in background.
+    pub verification_interval: Option<Duration>,
+    /// Interval between exports of tree checkpoints by [`MerkleTreeCheckpointTask`]. If not specified, checkpoints
+    /// are not exported.
+    pub checkpoint_interval: Option<Duration>,
     /// Configuration specific to the Merkle tree recovery.
     pub recovery: MetadataCalculatorRecoveryConfig,
 }
@@ -105,10 +128,15 @@ impl MetadataCalculatorConfig {
             delay_interval: operation_config.delay_interval(),
             max_l1_batches_per_iter: merkle_tree_config.max_l1_batches_per_iter,
             multi_get_chunk_size: merkle_tree_config.multi_get_chunk_size,
+            hashing_thread_count: merkle_tree_config.hashing_thread_count,
             block_cache_capacity: merkle_tree_config.block_cache_size(),
             include_indices_and_filters_in_block_cache: false,
             memtable_capacity: merkle_tree_config.memtable_capacity(),
             stalled_writes_timeout: merkle_tree_config.stalled_writes_timeout(),
+            pruning_retained_l1_batches: merkle_tree_config.pruning_retained_l1_batches,
+            compaction_interval: merkle_tree_config.compaction_interval(),
+            verification_interval: merkle_tree_config.verification_interval(),
+            checkpoint_interval: merkle_tree_config.checkpoint_interval(),
             // The main node isn't supposed to be recovered yet, so this value doesn't matter much
             recovery: MetadataCalculatorRecoveryConfig::default(),
         }
@@ -121,6 +149,8 @@ pub struct MetadataCalculator {
     tree_reader: watch::Sender<Option<AsyncTreeReader>>,
     pruning_handles_sender: oneshot::Sender<PruningHandles>,
     object_store: Option<Arc<dyn ObjectStore>>,
+    checkpoint_import_store: Option<Arc<dyn ObjectStore>>,
+    in_memory_db: Option<InMemoryDatabase>,
     pool: ConnectionPool<Core>,
     recovery_pool: ConnectionPool<Core>,
     delayer: Delayer,
@@ -162,6 +192,8 @@ impl MetadataCalculator {
             tree_reader: watch::channel(None).0,
             pruning_handles_sender: oneshot::channel().0,
             object_store,
+            checkpoint_import_store: None,
+            in_memory_db: None,
             recovery_pool: pool.clone(),
             pool,
             delayer: Delayer::new(config.delay_interval),
@@ -178,6 +210,22 @@ impl MetadataCalculator {
         self
     }
 
+    /// Enables importing the latest tree checkpoint exported by [`MerkleTreeCheckpointTask`] from the specified
+    /// object store if the tree RocksDB doesn't exist. This is significantly faster than building the tree
+    /// from Postgres data or recovering it from a Postgres snapshot. If the store has no suitable checkpoint,
+    /// the tree is initialized as usual.
+    pub fn with_checkpoint_import(mut self, object_store: Arc<dyn ObjectStore>) -> Self {
+        self.checkpoint_import_store = Some(object_store);
+        self
+    }
+
+    /// Backs the tree by the specified in-memory database instead of RocksDB at `config.db_path`. Useful for testing;
+    /// the database can be shared with other components (e.g., to inspect the tree state).
+    pub fn with_in_memory_db(mut self, db: InMemoryDatabase) -> Self {
+        self.in_memory_db = Some(db);
+        self
+    }
+
     /// Returns a health check for this calculator.
     pub fn tree_health_check(&self) -> impl CheckHealth {
         MerkleTreeHealthCheck::new(self.health_updater.subscribe(), self.tree_reader())
@@ -188,26 +236,69 @@ impl MetadataCalculator {
         LazyAsyncTreeReader(self.tree_reader.subscribe())
     }
 
-    /// Returns a task that can be used to prune the Merkle tree according to the pruning logs in Postgres.
-    /// This method should be called once; only the latest returned task will do any job, all previous ones
-    /// will terminate immediately.
+    /// Returns a task that can be used to prune the Merkle tree according to the pruning logs in Postgres
+    /// and the configured retention policy. This method should be called once; only the latest returned task
+    /// will do any job, all previous ones will terminate immediately.
     pub fn pruning_task(&mut self, poll_interval: Duration) -> MerkleTreePruningTask {
         let (pruning_handles_sender, pruning_handles) = oneshot::channel();
         self.pruning_handles_sender = pruning_handles_sender;
-        MerkleTreePruningTask::new(pruning_handles, self.pool.clone(), poll_interval)
+        MerkleTreePruningTask::new(
+            pruning_handles,
+            self.pool.clone(),
+            poll_interval,
+            self.config.pruning_retained_l1_batches,
+        )
+    }
+
+    /// Returns a task that periodically verifies the Merkle tree against Postgres. Unlike [`Self::pruning_task()`],
+    /// this method can be called multiple times; all returned tasks are functional.
+    pub fn verification_task(&self, poll_interval: Duration) -> MerkleTreeVerificationTask {
+        MerkleTreeVerificationTask::new(self.tree_reader(), self.pool.clone(), poll_interval)
+    }
+
+    /// Returns a task that periodically exports tree checkpoints to the specified object store. As with
+    /// [`Self::verification_task()`], returned tasks are independent, although there's usually no reason
+    /// to run more than one of them.
+    pub fn checkpoint_task(
+        &self,
+        object_store: Arc<dyn ObjectStore>,
+        interval: Duration,
+    ) -> MerkleTreeCheckpointTask {
+        MerkleTreeCheckpointTask::new(
+            self.tree_reader(),
+            object_store,
+            Path::new(&self.config.db_path),
+            interval,
+        )
     }
 
     async fn create_tree(&self) -> anyhow::Result<GenericAsyncTree> {
         self.health_updater
             .update(MerkleTreeHealth::Initialization.into());
 
-        let started_at = Instant::now();
-        let db = create_db(self.config.clone()).await.with_context(|| {
-            format!(
-                "failed opening Merkle tree RocksDB with configuration {:?}",
-                self.config
+        let checkpoint_import_store = self
+            .checkpoint_import_store
+            .as_ref()
+            .filter(|_| self.in_memory_db.is_none());
+        if let Some(object_store) = checkpoint_import_store {
+            checkpoint::import_checkpoint(
+                object_store.as_ref(),
+                &self.pool,
+                Path::new(&self.config.db_path),
             )
-        })?;
+            .await
+            .context("failed importing Merkle tree checkpoint")?;
+        }
+
+        let started_at = Instant::now();
+        let db = create_db(self.config.clone(), self.in_memory_db.clone())
+            .await
+            .with_context(|| {
+                format!(
+                    "failed opening Merkle tree RocksDB with configuration {:?}",
+                    self.config
+                )
+            })?;
         tracing::info!(
             "Opened Merkle tree RocksDB with configuration {:?} in {:?}",
             self.config,
@@ -231,20 +322,32 @@ impl MetadataCalculator {
         let Some(mut tree) = tree else {
             return Ok(()); // recovery was aborted because a stop signal was received
         };
+        if let Some(thread_count) = self.config.hashing_thread_count {
+            tree.use_dedicated_thread_pool(thread_count);
+        }
 
         let tree_reader = tree.reader();
         let tree_info = tree_reader.clone().info().await;
         if !self.pruning_handles_sender.is_closed() {
             self.pruning_handles_sender.send(tree.pruner()).ok();
         }
+        let compaction_task = self.config.compaction_interval.map(|interval| {
+            tree_reader
+                .clone()
+                .run_periodic_compaction(interval, stop_receiver.clone())
+        });
         self.tree_reader.send_replace(Some(tree_reader));
         tracing::info!("Merkle tree is initialized and ready to process L1 batches: {tree_info:?}");
         self.health_updater
             .update(MerkleTreeHealth::MainLoop(tree_info).into());
 
         let updater = TreeUpdater::new(tree, self.max_l1_batches_per_iter, self.object_store);
-        updater
-            .loop_updating_tree(self.delayer, &self.pool, stop_receiver)
-            .await
+        let update_task = updater.loop_updating_tree(self.delayer, &self.pool, stop_receiver);
+        if let Some(compaction_task) = compaction_task {
+            tokio::try_join!(update_task, compaction_task)?;
+            Ok(())
+        } else {
+            update_task.await
+        }
     }
 }
@@ -3,8 +3,8 @@
 use std::time::{Duration, Instant};
 
 use vise::{
-    Buckets, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Info,
-    LatencyObserver, Metrics, Unit,
+    Buckets, Counter, DurationAsSecs, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram,
+    Info, LatencyObserver, Metrics, Unit,
 };
 use zksync_config::configs::database::MerkleTreeMode;
 use zksync_shared_metrics::{BlockStage, APP_METRICS};
@@ -63,6 +63,8 @@ impl ConfigLabels {
 pub(super) enum TreeUpdateStage {
     LoadChanges,
     Compute,
+    /// Computing the root hash with a hasher different from the tree one (only during a transition between hashers).
+    Rehash,
     SavePostgres,
     SaveRocksdb,
     SaveGcs,
@@ -133,6 +135,10 @@ pub(super) struct MetadataCalculatorMetrics {
     /// The lag can only be positive if Postgres was restored from a backup truncating some
     /// of the batches already processed by the tree.
     pub backup_lag: Gauge<u64>,
+    /// Next L1 batch number to be processed by the tree, as observed by a read-only tree replica.
+    pub replica_next_l1_batch: Gauge<u64>,
+    /// Number of failed attempts of a read-only tree replica to catch up with the tree.
+    pub replica_catch_up_errors: Counter,
     /// Number of zero values that need to be checked for L1 batch of the initial write in the process
     /// of updating the Merkle tree.
     #[metrics(buckets = COUNTS_BUCKETS)]
@@ -223,6 +229,7 @@ impl MetadataCalculator {
 #[metrics(label = "stage", rename_all = "snake_case")]
 pub(super) enum RecoveryStage {
     LoadChunkStarts,
+    VerifyChunks,
     Finalize,
 }
 
@@ -253,3 +260,48 @@ pub(super) struct MetadataCalculatorRecoveryMetrics {
 #[vise::register]
 pub(super) static RECOVERY_METRICS: vise::Global<MetadataCalculatorRecoveryMetrics> =
     vise::Global::new();
+
+/// Metrics for background Merkle tree verification.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_metadata_calculator_verification")]
+pub(super) struct TreeVerificationMetrics {
+    /// Number of verified subtrees.
+    pub verified_subtrees: Counter,
+    /// Number of tree entries checked against Postgres.
+    pub verified_entries: Counter,
+    /// Number of detected divergences between the tree and Postgres.
+    pub divergences: Counter,
+    /// Latest L1 batch for which the tree was verified.
+    pub last_verified_l1_batch: Gauge<u64>,
+    /// Latency of verifying a single subtree.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static VERIFICATION_METRICS: vise::Global<TreeVerificationMetrics> = vise::Global::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(super) enum CheckpointStage {
+    Create,
+    Upload,
+    Download,
+}
+
+/// Metrics for exporting and importing Merkle tree checkpoints.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_metadata_calculator_checkpoint")]
+pub(super) struct TreeCheckpointMetrics {
+    /// Latency of a checkpoint export or import stage.
+    #[metrics(buckets = Buckets::LATENCIES, unit = Unit::Seconds)]
+    pub latency: Family<CheckpointStage, Histogram<Duration>>,
+    /// Total size of checkpoint files uploaded to the object store.
+    #[metrics(unit = Unit::Bytes)]
+    pub exported_bytes: Counter,
+    /// Latest L1 batch of an exported checkpoint.
+    pub last_exported_l1_batch: Gauge<u64>,
+}
+
+#[vise::register]
+pub(super) static CHECKPOINT_METRICS: vise::Global<TreeCheckpointMetrics> = vise::Global::new();
@@ -1,16 +1,18 @@
 //! Merkle tree pruning logic.
 
-use std::time::Duration;
+use std::{num::NonZeroU32, time::Duration};
 
 use anyhow::Context as _;
 use serde::Serialize;
 use tokio::sync::{oneshot, watch};
-use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
-use zksync_merkle_tree::{MerkleTreePruner, MerkleTreePrunerHandle, RocksDBWrapper};
+use zksync_merkle_tree::{MerkleTreePruner, MerkleTreePrunerHandle};
 use zksync_types::L1BatchNumber;
 
-pub(super) type PruningHandles = (MerkleTreePruner<RocksDBWrapper>, MerkleTreePrunerHandle);
+use crate::helpers::TreeDatabase;
+
+pub(super) type PruningHandles = (MerkleTreePruner<TreeDatabase>, MerkleTreePrunerHandle);
 
 #[derive(Debug, Serialize)]
 #[serde(tag = "stage", rename_all = "snake_case")]
@@ -36,7 +38,8 @@ impl From<MerkleTreePruningTaskHealth> for Health {
     }
 }
 
-/// Task performing Merkle tree pruning according to the pruning entries in Postgres.
+/// Task performing Merkle tree pruning according to the pruning entries in Postgres and, optionally,
+/// the retention policy limiting the number of retained tree versions.
 #[derive(Debug)]
 #[must_use = "Task should `run()` in a managed Tokio task"]
 pub struct MerkleTreePruningTask {
@@ -44,19 +47,25 @@ pub struct MerkleTreePruningTask {
     pool: ConnectionPool<Core>,
     health_updater: HealthUpdater,
     poll_interval: Duration,
+    retained_l1_batches: Option<NonZeroU32>,
 }
 
 impl MerkleTreePruningTask {
+    /// Poll interval recommended if the tree is pruned only according to the retention policy.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
     pub(super) fn new(
         handles: oneshot::Receiver<PruningHandles>,
         pool: ConnectionPool<Core>,
         poll_interval: Duration,
+        retained_l1_batches: Option<NonZeroU32>,
     ) -> Self {
         Self {
             handles,
             pool,
             health_updater: ReactiveHealthCheck::new("tree_pruner").1,
             poll_interval,
+            retained_l1_batches,
         }
     }
 
@@ -64,6 +73,48 @@ impl MerkleTreePruningTask {
         self.health_updater.subscribe()
     }
 
+    /// Returns the first L1 batch for which the tree version should be retained. Tree versions
+    /// for L1 batches pruned in Postgres are always pruned. If the retention policy is configured, the tree versions
+    /// not among the latest `retained_l1_batches` are pruned as well, provided that their L1 batches are proven;
+    /// the tree version for the last proven L1 batch is retained, so that the tree can be reverted to it.
+    async fn target_retained_l1_batch_number(
+        &self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<Option<L1BatchNumber>> {
+        let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+        let retained_by_db_pruning = pruning_info
+            .last_hard_pruned_l1_batch
+            .map(|l1_batch_number| l1_batch_number + 1);
+        let Some(retained_l1_batches) = self.retained_l1_batches else {
+            return Ok(retained_by_db_pruning);
+        };
+
+        let last_l1_batch_number = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_tree_data()
+            .await?;
+        let last_proven_l1_batch_number = storage
+            .blocks_dal()
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await?;
+        let (Some(last_l1_batch_number), Some(last_proven_l1_batch_number)) =
+            (last_l1_batch_number, last_proven_l1_batch_number)
+        else {
+            return Ok(retained_by_db_pruning);
+        };
+
+        let first_retained_l1_batch_number = L1BatchNumber(
+            last_l1_batch_number
+                .0
+                .saturating_sub(retained_l1_batches.get() - 1),
+        );
+        let retained_by_policy = first_retained_l1_batch_number.min(last_proven_l1_batch_number);
+        Ok(Some(
+            retained_by_db_pruning
+                .map_or(retained_by_policy, |number| number.max(retained_by_policy)),
+        ))
+    }
+
     pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         // The pruning task is "affected" (not functioning) until the Merkle tree is initialized.
         self.health_updater
@@ -98,11 +149,11 @@ impl MerkleTreePruningTask {
 
         while !*stop_receiver.borrow_and_update() {
             let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
-            let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+            let target_retained_l1_batch_number =
+                self.target_retained_l1_batch_number(&mut storage).await?;
             drop(storage);
 
-            if let Some(l1_batch_number) = pruning_info.last_hard_pruned_l1_batch {
-                let target_retained_l1_batch_number = l1_batch_number + 1;
+            if let Some(target_retained_l1_batch_number) = target_retained_l1_batch_number {
                 let target_retained_version = u64::from(target_retained_l1_batch_number.0);
                 let Ok(prev_target_version) =
                     pruner_handle.set_target_retained_version(target_retained_version)
@@ -148,12 +199,12 @@ mod tests {
     use test_casing::test_casing;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::prepare_recovery_snapshot;
-    use zksync_types::{L1BatchNumber, L2BlockNumber};
+    use zksync_types::{aggregated_operations::AggregatedActionType, L2BlockNumber, H256};
 
     use super::*;
     use crate::{
         tests::{extend_db_state_from_l1_batch, gen_storage_logs, mock_config, reset_db_state},
-        MetadataCalculator,
+        MetadataCalculator, MetadataCalculatorConfig,
     };
 
     const POLL_INTERVAL: Duration = Duration::from_millis(50);
@@ -209,6 +260,81 @@ mod tests {
             .await;
     }
 
+    #[tokio::test]
+    async fn tree_pruning_with_retention_policy() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let config = MetadataCalculatorConfig {
+            pruning_retained_l1_batches: NonZeroU32::new(2),
+            ..mock_config(temp_dir.path())
+        };
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        reset_db_state(&pool, 5).await;
+
+        let mut calculator = MetadataCalculator::new(config, None, pool.clone())
+            .await
+            .unwrap();
+        let reader = calculator.tree_reader();
+        let pruning_task = calculator.pruning_task(POLL_INTERVAL);
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let calculator_handle = tokio::spawn(calculator.run(stop_receiver.clone()));
+        let pruning_task_handle = tokio::spawn(pruning_task.run(stop_receiver));
+
+        let reader = reader.wait().await.unwrap();
+        while reader.clone().info().await.next_l1_batch_number < L1BatchNumber(6) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        // No L1 batches are proven yet, so the tree must not be pruned.
+        tokio::time::sleep(POLL_INTERVAL * 3).await;
+        assert_eq!(
+            reader.clone().info().await.min_l1_batch_number,
+            Some(L1BatchNumber(0))
+        );
+
+        // The tree version for the last proven L1 batch must be retained.
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(2),
+                AggregatedActionType::PublishProofOnchain,
+                H256::repeat_byte(1),
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+        while reader.clone().info().await.min_l1_batch_number.unwrap() < L1BatchNumber(2) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        // Once all L1 batches are proven, only the 2 latest tree versions must be retained.
+        storage
+            .eth_sender_dal()
+            .insert_bogus_confirmed_eth_tx(
+                L1BatchNumber(5),
+                AggregatedActionType::PublishProofOnchain,
+                H256::repeat_byte(2),
+                chrono::Utc::now(),
+            )
+            .await
+            .unwrap();
+        while reader.clone().info().await.min_l1_batch_number.unwrap() < L1BatchNumber(4) {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        tokio::time::sleep(POLL_INTERVAL * 3).await;
+        assert_eq!(
+            reader.clone().info().await.min_l1_batch_number,
+            Some(L1BatchNumber(4))
+        );
+        reader.verify_consistency(L1BatchNumber(5)).await.unwrap();
+
+        stop_sender.send_replace(true);
+        calculator_handle.await.unwrap().unwrap();
+        pruning_task_handle.await.unwrap().unwrap();
+    }
+
     #[derive(Debug)]
     enum PrematureExitScenario {
         CalculatorDrop,
@@ -14,9 +14,13 @@
 //! and feeding each chunk to the tree. Chunks are loaded concurrently since this is the most
 //! I/O-heavy operation; the concurrency is naturally limited by the number of connections to
 //! Postgres in the supplied connection pool, but we explicitly use a [`Semaphore`] to control it
-//! in order to not run into DB timeout errors. Before starting recovery in chunks, we filter out
-//! chunks that have already been recovered by checking if the first key in a chunk is present
-//! in the tree. (Note that for this to work, chunks **must** always be defined in the same way.)
+//! in order to not run into DB timeout errors. Each recovered chunk is recorded in the tree tags
+//! together with a digest of its entries; the record is persisted atomically with the chunk itself.
+//! Before starting recovery in chunks, we filter out chunks that have already been recovered using these records.
+//! (Note that for this to work, chunks **must** always be defined in the same way.) Digests of recovered chunks
+//! are verified against the Postgres snapshot, so that the tree doesn't end up mixing data from different snapshots.
+//! Trees that do not record recovered chunks (i.e., ones started recovery with an older node version) are handled
+//! by checking if the first key in a chunk is present in the tree.
 //!
 //! The recovery logic is fault-tolerant and supports graceful shutdown. If recovery is interrupted,
 //! recovery of the remaining chunks will continue when Metadata calculator is restarted.
@@ -26,25 +30,29 @@
 //! after recovery matches one in the Postgres snapshot etc.
 
 use std::{
+    collections::HashMap,
     fmt, ops,
     sync::atomic::{AtomicU64, Ordering},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::Context as _;
 use futures::future;
 use tokio::sync::{watch, Mutex, Semaphore};
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{helpers::wait_for_l1_batch, Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::HealthUpdater;
-use zksync_merkle_tree::TreeEntry;
+use zksync_merkle_tree::{TreeEntry, TreeHasher};
 use zksync_shared_metrics::{SnapshotRecoveryStage, APP_METRICS};
 use zksync_types::{
     snapshots::{uniform_hashed_keys_chunk, SnapshotRecoveryStatus},
+    web3::keccak256,
     L2BlockNumber, H256,
 };
 
 use super::{
-    helpers::{AsyncTree, AsyncTreeRecovery, GenericAsyncTree, MerkleTreeHealth},
+    helpers::{
+        committed_tree_hasher, AsyncTree, AsyncTreeRecovery, GenericAsyncTree, MerkleTreeHealth,
+    },
     metrics::{ChunkRecoveryStage, RecoveryStage, RECOVERY_METRICS},
     MetadataCalculatorRecoveryConfig,
 };
@@ -189,11 +197,17 @@ impl GenericAsyncTree {
                         "Starting Merkle tree recovery with status {snapshot_recovery:?}"
                     );
                     let l1_batch = snapshot_recovery.l1_batch_number;
-                    let tree = AsyncTreeRecovery::new(db, l1_batch.0.into(), mode, config)?;
+                    let hasher =
+                        TreeHasher::for_protocol_version(snapshot_recovery.protocol_version);
+                    let tree = AsyncTreeRecovery::new(db, l1_batch.0.into(), mode, hasher, config)?;
                     (tree, snapshot_recovery)
                 } else {
                     // Start the tree from scratch. The genesis block will be filled in `TreeUpdater::loop_updating_tree()`.
-                    return Ok(Some(AsyncTree::new(db, mode)?));
+                    let Some(hasher) = get_genesis_tree_hasher(main_pool, stop_receiver).await?
+                    else {
+                        return Ok(None); // Stop signal received
+                    };
+                    return Ok(Some(AsyncTree::new(db, mode, hasher)?));
                 }
             }
         };
@@ -234,16 +248,40 @@ impl AsyncTreeRecovery {
         let start_time = Instant::now();
         let chunk_count = options.chunk_count;
         let chunks: Vec<_> = (0..chunk_count)
-            .map(|chunk_id| uniform_hashed_keys_chunk(chunk_id, chunk_count))
+            .map(|chunk_id| KeyChunk {
+                id: chunk_id,
+                range: uniform_hashed_keys_chunk(chunk_id, chunk_count),
+            })
             .collect();
         tracing::info!(
             "Recovering Merkle tree from Postgres snapshot in {chunk_count} chunks with max concurrency {}",
             options.concurrency_limit
         );
 
+        let recovered_chunks = self.recovered_chunks()?;
+        if let Some(&max_chunk_id) = recovered_chunks.keys().max() {
+            anyhow::ensure!(
+                max_chunk_id < chunk_count,
+                "Merkle tree records recovered chunk {max_chunk_id}, but the snapshot only has {chunk_count} chunks; \
+                 reset Merkle tree recovery by clearing its RocksDB directory"
+            );
+        }
+        Self::verify_recovered_chunks(
+            snapshot.l2_block,
+            &chunks,
+            &recovered_chunks,
+            options.concurrency_limit,
+            pool,
+            stop_receiver,
+        )
+        .await?;
+        if *stop_receiver.borrow() {
+            return Ok(None);
+        }
+
         let mut storage = pool.connection_tagged("metadata_calculator").await?;
         let remaining_chunks = self
-            .filter_chunks(&mut storage, snapshot.l2_block, &chunks)
+            .filter_chunks(&mut storage, snapshot.l2_block, &chunks, &recovered_chunks)
             .await?;
         drop(storage);
         options
@@ -291,23 +329,90 @@ impl AsyncTreeRecovery {
         Ok(Some(tree))
     }
 
+    /// Checks that digests of recovered chunks match the Postgres snapshot.
+    async fn verify_recovered_chunks(
+        snapshot_l2_block: L2BlockNumber,
+        key_chunks: &[KeyChunk],
+        recovered_chunks: &HashMap<u64, H256>,
+        concurrency_limit: usize,
+        pool: &ConnectionPool<Core>,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        if recovered_chunks.is_empty() {
+            return Ok(());
+        }
+
+        let latency = RECOVERY_METRICS.latency[&RecoveryStage::VerifyChunks].start();
+        let semaphore = &Semaphore::new(concurrency_limit);
+        let verification_tasks = key_chunks.iter().filter_map(|chunk| {
+            let expected_digest = *recovered_chunks.get(&chunk.id)?;
+            Some(async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .context("semaphore is never closed")?;
+                if *stop_receiver.borrow() {
+                    return Ok(());
+                }
+
+                let mut storage = pool.connection_tagged("metadata_calculator").await?;
+                let entries =
+                    load_chunk_entries(&mut storage, snapshot_l2_block, chunk.range.clone())
+                        .await?;
+                drop(storage);
+                let actual_digest = chunk_digest(&entries);
+                anyhow::ensure!(
+                    actual_digest == expected_digest,
+                    "Digest of recovered chunk {} ({:?}) recorded in Merkle tree ({expected_digest:?}) differs from \
+                     the digest of the Postgres snapshot chunk for L2 block #{snapshot_l2_block} ({actual_digest:?}); \
+                     the recovery procedure may be corrupted. Reset Merkle tree recovery by clearing its RocksDB directory",
+                    chunk.id,
+                    chunk.range
+                );
+                anyhow::Ok(())
+            })
+        });
+        future::try_join_all(verification_tasks).await?;
+        let latency = latency.observe();
+        tracing::info!(
+            "Verified digests of {} recovered chunks in {latency:?}",
+            recovered_chunks.len()
+        );
+        Ok(())
+    }
+
     /// Filters out `key_chunks` for which recovery was successfully performed.
     async fn filter_chunks(
         &mut self,
         storage: &mut Connection<'_, Core>,
         snapshot_l2_block: L2BlockNumber,
-        key_chunks: &[ops::RangeInclusive<H256>],
-    ) -> anyhow::Result<Vec<ops::RangeInclusive<H256>>> {
+        key_chunks: &[KeyChunk],
+        recovered_chunks: &HashMap<u64, H256>,
+    ) -> anyhow::Result<Vec<KeyChunk>> {
+        let unrecorded_chunks: Vec<_> = key_chunks
+            .iter()
+            .filter(|chunk| !recovered_chunks.contains_key(&chunk.id))
+            .collect();
+        if unrecorded_chunks.is_empty() {
+            return Ok(vec![]);
+        }
+        let unrecorded_ranges: Vec<_> = unrecorded_chunks
+            .iter()
+            .map(|chunk| chunk.range.clone())
+            .collect();
+
+        // Chunks may be recovered without being recorded in tree tags if recovery was started by an older node version.
+        // We detect such chunks by checking whether their start entries are present in the tree.
         let chunk_starts_latency =
             RECOVERY_METRICS.latency[&RecoveryStage::LoadChunkStarts].start();
         let chunk_starts = storage
             .storage_logs_dal()
-            .get_chunk_starts_for_l2_block(snapshot_l2_block, key_chunks)
+            .get_chunk_starts_for_l2_block(snapshot_l2_block, &unrecorded_ranges)
             .await?;
         let chunk_starts_latency = chunk_starts_latency.observe();
         tracing::debug!(
             "Loaded start entries for {} chunks in {chunk_starts_latency:?}",
-            key_chunks.len()
+            unrecorded_ranges.len()
         );
 
         let existing_starts = chunk_starts
@@ -323,7 +428,7 @@ impl AsyncTreeRecovery {
         let mut output = vec![];
         for (tree_entry, (i, db_entry)) in tree_entries.into_iter().zip(existing_starts) {
             if tree_entry.is_empty() {
-                output.push(key_chunks[i].clone());
+                output.push(unrecorded_chunks[i].clone());
                 continue;
             }
             anyhow::ensure!(
@@ -339,7 +444,7 @@ impl AsyncTreeRecovery {
     async fn recover_key_chunk(
         tree: &Mutex<AsyncTreeRecovery>,
         snapshot_l2_block: L2BlockNumber,
-        key_chunk: ops::RangeInclusive<H256>,
+        key_chunk: KeyChunk,
         pool: &ConnectionPool<Core>,
         stop_receiver: &watch::Receiver<bool>,
     ) -> anyhow::Result<()> {
@@ -354,10 +459,8 @@ impl AsyncTreeRecovery {
 
         let entries_latency =
             RECOVERY_METRICS.chunk_latency[&ChunkRecoveryStage::LoadEntries].start();
-        let all_entries = storage
-            .storage_logs_dal()
-            .get_tree_entries_for_l2_block(snapshot_l2_block, key_chunk.clone())
-            .await?;
+        let all_entries =
+            load_chunk_entries(&mut storage, snapshot_l2_block, key_chunk.range.clone()).await?;
         drop(storage);
         let entries_latency = entries_latency.observe();
         tracing::debug!(
@@ -369,27 +472,7 @@ impl AsyncTreeRecovery {
             return Ok(());
         }
 
-        // Sanity check: all entry keys must be distinct. Otherwise, we may end up writing non-final values
-        // to the tree, since we don't enforce any ordering on entries besides by the hashed key.
-        for window in all_entries.windows(2) {
-            let [prev_entry, next_entry] = window else {
-                unreachable!();
-            };
-            anyhow::ensure!(
-                prev_entry.key != next_entry.key,
-                "node snapshot in Postgres is corrupted: entries {prev_entry:?} and {next_entry:?} \
-                 have same hashed_key"
-            );
-        }
-
-        let all_entries = all_entries
-            .into_iter()
-            .map(|entry| TreeEntry {
-                key: entry.tree_key(),
-                value: entry.value,
-                leaf_index: entry.leaf_index,
-            })
-            .collect();
+        let digest = chunk_digest(&all_entries);
         let lock_tree_latency =
             RECOVERY_METRICS.chunk_latency[&ChunkRecoveryStage::LockTree].start();
         let mut tree = tree.lock().await;
@@ -401,7 +484,7 @@ impl AsyncTreeRecovery {
 
         let extend_tree_latency =
             RECOVERY_METRICS.chunk_latency[&ChunkRecoveryStage::ExtendTree].start();
-        tree.extend(all_entries).await?;
+        tree.extend_chunk(key_chunk.id, digest, all_entries).await?;
         let extend_tree_latency = extend_tree_latency.observe();
         tracing::debug!(
             "Extended Merkle tree with entries for chunk {key_chunk:?} in {extend_tree_latency:?}"
@@ -410,6 +493,61 @@ impl AsyncTreeRecovery {
     }
 }
 
+/// Chunk of hashed keys in the snapshot.
+#[derive(Debug, Clone)]
+struct KeyChunk {
+    id: u64,
+    range: ops::RangeInclusive<H256>,
+}
+
+/// Loads tree entries for the specified chunk of a Postgres snapshot.
+async fn load_chunk_entries(
+    storage: &mut Connection<'_, Core>,
+    snapshot_l2_block: L2BlockNumber,
+    key_chunk: ops::RangeInclusive<H256>,
+) -> anyhow::Result<Vec<TreeEntry>> {
+    let all_entries = storage
+        .storage_logs_dal()
+        .get_tree_entries_for_l2_block(snapshot_l2_block, key_chunk)
+        .await?;
+
+    // Sanity check: all entry keys must be distinct. Otherwise, we may end up writing non-final values
+    // to the tree, since we don't enforce any ordering on entries besides by the hashed key.
+    for window in all_entries.windows(2) {
+        let [prev_entry, next_entry] = window else {
+            unreachable!();
+        };
+        anyhow::ensure!(
+            prev_entry.key != next_entry.key,
+            "node snapshot in Postgres is corrupted: entries {prev_entry:?} and {next_entry:?} \
+             have same hashed_key"
+        );
+    }
+
+    Ok(all_entries
+        .into_iter()
+        .map(|entry| TreeEntry {
+            key: entry.tree_key(),
+            value: entry.value,
+            leaf_index: entry.leaf_index,
+        })
+        .collect())
+}
+
+/// Computes a digest of chunk entries. Entries are expected to be ordered by key, which is the case for entries
+/// loaded from Postgres.
+fn chunk_digest(entries: &[TreeEntry]) -> H256 {
+    let mut buffer = Vec::with_capacity(entries.len() * 72);
+    for entry in entries {
+        let mut key_bytes = [0_u8; 32];
+        entry.key.to_big_endian(&mut key_bytes);
+        buffer.extend_from_slice(&key_bytes);
+        buffer.extend_from_slice(entry.value.as_bytes());
+        buffer.extend_from_slice(&entry.leaf_index.to_be_bytes());
+    }
+    H256(keccak256(&buffer))
+}
+
 async fn get_snapshot_recovery(
     pool: &ConnectionPool<Core>,
 ) -> anyhow::Result<Option<SnapshotRecoveryStatus>> {
@@ -419,3 +557,31 @@ async fn get_snapshot_recovery(
         .get_applied_snapshot_status()
         .await?)
 }
+
+/// Returns the hasher for a tree built from scratch based on the protocol version of the genesis L1 batch,
+/// waiting for the batch to appear in Postgres if necessary. Returns `None` if a stop signal is received while waiting.
+async fn get_genesis_tree_hasher(
+    pool: &ConnectionPool<Core>,
+    stop_receiver: &watch::Receiver<bool>,
+) -> anyhow::Result<Option<TreeHasher>> {
+    const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+    let mut stop_receiver = stop_receiver.clone();
+    let Some(earliest_l1_batch) =
+        wait_for_l1_batch(pool, POLL_INTERVAL, &mut stop_receiver).await?
+    else {
+        return Ok(None);
+    };
+    let mut storage = pool.connection_tagged("metadata_calculator").await?;
+    let header = storage
+        .blocks_dal()
+        .get_l1_batch_header(earliest_l1_batch)
+        .await?
+        .with_context(|| format!("L1 batch #{earliest_l1_batch} disappeared from Postgres"))?;
+    let hasher = committed_tree_hasher(&header);
+    tracing::info!(
+        "Using {hasher:?} hasher for the new Merkle tree based on L1 batch #{earliest_l1_batch} (protocol version: {:?})",
+        header.protocol_version
+    );
+    Ok(Some(hasher))
+}
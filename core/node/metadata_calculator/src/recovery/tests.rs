@@ -12,7 +12,11 @@ use zksync_config::configs::{
 };
 use zksync_dal::CoreDal;
 use zksync_health_check::{CheckHealth, HealthStatus, ReactiveHealthCheck};
-use zksync_merkle_tree::{domain::ZkSyncTree, recovery::PersistenceThreadHandle, TreeInstruction};
+use zksync_merkle_tree::{
+    domain::ZkSyncTree,
+    recovery::{MerkleTreeRecovery, PersistenceThreadHandle},
+    Database, TreeHasher, TreeInstruction,
+};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::prepare_recovery_snapshot;
 use zksync_types::{L1BatchNumber, ProtocolVersionId, StorageLog};
@@ -49,8 +53,15 @@ async fn create_tree_recovery(
     l1_batch: L1BatchNumber,
     config: &MetadataCalculatorRecoveryConfig,
 ) -> (AsyncTreeRecovery, Option<PersistenceThreadHandle>) {
-    let db = create_db(mock_config(path)).await.unwrap();
-    AsyncTreeRecovery::with_handle(db, l1_batch.0.into(), MerkleTreeMode::Full, config).unwrap()
+    let db = create_db(mock_config(path), None).await.unwrap();
+    AsyncTreeRecovery::with_handle(
+        db,
+        l1_batch.0.into(),
+        MerkleTreeMode::Full,
+        TreeHasher::default(),
+        config,
+    )
+    .unwrap()
 }
 
 #[tokio::test]
@@ -86,6 +97,15 @@ async fn basic_recovery_workflow() {
         assert_eq!(tree.root_hash(), snapshot_recovery.l1_batch_root_hash);
         let health = health_check.check_health().await;
         assert_matches!(health.status(), HealthStatus::Affected);
+        drop(tree);
+
+        let db = create_db(mock_config(&tree_path), None).await.unwrap();
+        let manifest = db.manifest().unwrap();
+        let custom_tags = manifest.custom_tags().cloned().unwrap_or_default();
+        assert!(
+            custom_tags.keys().all(|key| !key.starts_with("recovery.")),
+            "{custom_tags:?}"
+        );
     }
 }
 
@@ -273,6 +293,71 @@ async fn recovery_fault_tolerance(chunk_count: u64, case: FaultToleranceCase) {
     assert_eq!(tree.root_hash(), snapshot_recovery.l1_batch_root_hash);
 }
 
+#[tokio::test]
+async fn recovered_chunks_are_recorded_and_verified() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let snapshot_recovery = prepare_recovery_snapshot_with_genesis(pool.clone(), &temp_dir).await;
+
+    let tree_path = temp_dir.path().join("recovery");
+    let config = MetadataCalculatorRecoveryConfig {
+        parallel_persistence_buffer: None,
+        ..MetadataCalculatorRecoveryConfig::default()
+    };
+    let snapshot = SnapshotParameters::new(&pool, &snapshot_recovery, &config)
+        .await
+        .unwrap();
+    let (tree, _) = create_tree_recovery(&tree_path, L1BatchNumber(1), &config).await;
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let recovery_options = RecoveryOptions {
+        chunk_count: 5,
+        concurrency_limit: 1,
+        events: Box::new(TestEventListener::new(2, stop_sender)),
+    };
+    assert!(tree
+        .recover(snapshot, recovery_options, &pool, &stop_receiver)
+        .await
+        .unwrap()
+        .is_none());
+
+    let (tree, _) = create_tree_recovery(&tree_path, L1BatchNumber(1), &config).await;
+    let recovered_chunks = tree.recovered_chunks().unwrap();
+    assert_eq!(recovered_chunks.len(), 2, "{recovered_chunks:?}");
+    assert!(recovered_chunks.keys().all(|&chunk_id| chunk_id < 5));
+    drop(tree);
+
+    // Corrupt the digest of one of recovered chunks.
+    let corrupted_chunk_id = *recovered_chunks.keys().next().unwrap();
+    let db = create_db(mock_config(&tree_path), None).await.unwrap();
+    let mut recovery = MerkleTreeRecovery::new(db, 1).unwrap();
+    recovery
+        .update_custom_tags(|tags| {
+            tags.insert(
+                format!("recovery.chunk.{corrupted_chunk_id}"),
+                format!("{:?}", H256::repeat_byte(0xff)),
+            );
+        })
+        .unwrap();
+    drop(recovery);
+
+    let (tree, _) = create_tree_recovery(&tree_path, L1BatchNumber(1), &config).await;
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let recovery_options = RecoveryOptions {
+        chunk_count: 5,
+        concurrency_limit: 1,
+        events: Box::new(TestEventListener::new(u64::MAX, stop_sender)),
+    };
+    let err = tree
+        .recover(snapshot, recovery_options, &pool, &stop_receiver)
+        .await
+        .unwrap_err();
+    let err = format!("{err:#}");
+    assert!(
+        err.contains(&format!("Digest of recovered chunk {corrupted_chunk_id}")),
+        "{err}"
+    );
+}
+
 #[derive(Debug)]
 enum RecoveryWorkflowCase {
     Stop,
@@ -360,8 +445,9 @@ async fn entire_recovery_workflow(case: RecoveryWorkflowCase) {
                 .enumerate()
                 .map(|(i, log)| TreeInstruction::write(log.key, i as u64 + 1, log.value))
                 .collect();
+            let tree_hasher = TreeHasher::for_protocol_version(ProtocolVersionId::latest());
             let expected_new_root_hash =
-                ZkSyncTree::process_genesis_batch(&all_tree_instructions).root_hash;
+                ZkSyncTree::process_genesis_batch(&all_tree_instructions, tree_hasher).root_hash;
             assert_ne!(expected_new_root_hash, snapshot_recovery.l1_batch_root_hash);
             assert_eq!(new_root_hash, expected_new_root_hash);
         }
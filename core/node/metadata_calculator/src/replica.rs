@@ -0,0 +1,153 @@
+//! Read-only replica of the Merkle tree.
+
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+
+use crate::{
+    helpers::{create_secondary_db, AsyncTreeReader},
+    metrics::METRICS,
+    MetadataCalculatorConfig,
+};
+
+/// Read-only replica of the Merkle tree maintained by a [`MetadataCalculator`](crate::MetadataCalculator),
+/// which may run in another process. The replica opens the tree RocksDB as a secondary instance and periodically
+/// catches up with the changes made by the tree. This allows scaling the tree API (and thus proof-serving
+/// Web3 methods such as `zks_getProof`) independently of the metadata calculator.
+///
+/// The replica requires access to the filesystem of the tree RocksDB, i.e., it should run on the same machine
+/// as the metadata calculator or use a shared volume.
+#[derive(Debug)]
+#[must_use = "Replica should `run()` in a managed Tokio task"]
+pub struct MerkleTreeReplica {
+    tree_reader: AsyncTreeReader,
+    poll_interval: Duration,
+    health_updater: HealthUpdater,
+}
+
+impl MerkleTreeReplica {
+    /// Opens a replica for the tree RocksDB specified in `config`. `secondary_path` is a directory
+    /// to store replica-specific RocksDB metadata; it must differ from the tree RocksDB path.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the tree RocksDB cannot be opened (e.g., if it doesn't exist yet).
+    pub async fn new(
+        config: MetadataCalculatorConfig,
+        secondary_path: PathBuf,
+        poll_interval: Duration,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            PathBuf::from(&config.db_path) != secondary_path,
+            "Secondary path for a Merkle tree replica must differ from the tree RocksDB path"
+        );
+        let mode = config.mode;
+        let db = create_secondary_db(config, secondary_path)
+            .await
+            .context("failed opening secondary Merkle tree RocksDB")?;
+        Ok(Self {
+            tree_reader: AsyncTreeReader::new(db, mode)?,
+            poll_interval,
+            health_updater: ReactiveHealthCheck::new("tree_replica").1,
+        })
+    }
+
+    /// Returns a reader for the replica. The reader only observes changes to the tree after the replica
+    /// has caught up with them.
+    pub fn tree_reader(&self) -> AsyncTreeReader {
+        self.tree_reader.clone()
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    async fn catch_up(&self) -> anyhow::Result<()> {
+        self.tree_reader.catch_up_with_primary().await?;
+        let info = self.tree_reader.clone().info().await;
+        tracing::debug!(
+            "Merkle tree replica caught up with the tree; next L1 batch: {}",
+            info.next_l1_batch_number
+        );
+        METRICS
+            .replica_next_l1_batch
+            .set(info.next_l1_batch_number.0.into());
+        self.health_updater
+            .update(Health::from(HealthStatus::Ready).with_details(info));
+        Ok(())
+    }
+
+    /// Runs the replica until a stop signal is received. Errors catching up with the primary tree
+    /// (e.g., transient I/O errors) are logged and retried on the next iteration.
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting Merkle tree replica with poll interval {:?}",
+            self.poll_interval
+        );
+        while !*stop_receiver.borrow_and_update() {
+            if let Err(err) = self.catch_up().await {
+                tracing::warn!("Merkle tree replica failed catching up with the tree: {err:#}");
+                METRICS.replica_catch_up_errors.inc();
+                let details = serde_json::json!({ "error": format!("{err:#}") });
+                self.health_updater
+                    .update(Health::from(HealthStatus::Affected).with_details(details));
+            }
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, Merkle tree replica is shutting down");
+        self.health_updater
+            .update(HealthStatus::ShuttingDown.into());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use zksync_dal::{ConnectionPool, Core};
+    use zksync_types::L1BatchNumber;
+
+    use super::*;
+    use crate::tests::{reset_db_state, run_calculator, setup_calculator};
+
+    #[tokio::test]
+    async fn replica_follows_tree() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone()).await;
+        let config = calculator.config.clone();
+        reset_db_state(&pool, 1).await;
+        let root_hash = run_calculator(calculator).await;
+
+        let secondary_dir = TempDir::new().unwrap();
+        let replica = MerkleTreeReplica::new(
+            config,
+            secondary_dir.path().to_owned(),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+        let tree_reader = replica.tree_reader();
+        assert!(tree_reader.is_replica());
+        let mut health_check = replica.health_check();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let replica_task = tokio::spawn(replica.run(stop_receiver));
+
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::Ready))
+            .await;
+        let info = tree_reader.clone().info().await;
+        assert_eq!(info.next_l1_batch_number, L1BatchNumber(2));
+        assert_eq!(info.root_hash, root_hash);
+
+        stop_sender.send_replace(true);
+        replica_task.await.unwrap().unwrap();
+    }
+}
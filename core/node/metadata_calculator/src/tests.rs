@@ -12,15 +12,15 @@ use zksync_config::configs::{
 };
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_health_check::{CheckHealth, HealthStatus};
-use zksync_merkle_tree::domain::ZkSyncTree;
+use zksync_merkle_tree::{domain::ZkSyncTree, InMemoryDatabase, TreeHasher};
 use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
 use zksync_node_test_utils::{create_l1_batch, create_l2_block};
 use zksync_object_store::{MockObjectStore, ObjectStore};
 use zksync_prover_interface::inputs::PrepareBasicCircuitsJob;
 use zksync_storage::RocksDB;
 use zksync_types::{
-    block::L1BatchHeader, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, StorageKey,
-    StorageLog, H256,
+    block::L1BatchHeader, AccountTreeId, Address, L1BatchNumber, L2BlockNumber, ProtocolVersionId,
+    StorageKey, StorageLog, H256,
 };
 use zksync_utils::u32_to_h256;
 
@@ -50,10 +50,15 @@ pub(super) fn mock_config(db_path: &Path) -> MetadataCalculatorConfig {
         delay_interval: Duration::from_millis(100),
         max_l1_batches_per_iter: 10,
         multi_get_chunk_size: 500,
+        hashing_thread_count: Some(2),
         block_cache_capacity: 0,
         include_indices_and_filters_in_block_cache: false,
         memtable_capacity: 16 << 20,            // 16 MiB
         stalled_writes_timeout: Duration::ZERO, // writes should never be stalled in tests
+        pruning_retained_l1_batches: None,
+        compaction_interval: None,
+        verification_interval: None,
+        checkpoint_interval: None,
         recovery: MetadataCalculatorRecoveryConfig::default(),
     }
 }
@@ -103,6 +108,37 @@ async fn basic_workflow() {
     assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(2));
 }
 
+#[tokio::test]
+async fn basic_workflow_with_in_memory_tree() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+    let db = InMemoryDatabase::new();
+
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone()).await;
+    let calculator = calculator.with_in_memory_db(db.clone());
+    reset_db_state(&pool, 1).await;
+    let merkle_tree_hash = run_calculator(calculator).await;
+    assert_eq!(merkle_tree_hash, expected_tree_hash(&pool).await);
+    // RocksDB must not be created.
+    assert!(!temp_dir.path().join("new").exists());
+
+    // The tree state is retained in the database and is picked up by the next calculator.
+    let (calculator, _) = setup_calculator(temp_dir.path(), pool).await;
+    let tree = calculator
+        .with_in_memory_db(db)
+        .create_tree()
+        .await
+        .unwrap();
+    let GenericAsyncTree::Ready(tree) = tree else {
+        panic!("Unexpected tree state: {tree:?}");
+    };
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(2));
+    tree.reader()
+        .verify_consistency(L1BatchNumber(1))
+        .await
+        .unwrap();
+}
+
 async fn expected_tree_hash(pool: &ConnectionPool<Core>) -> H256 {
     let mut storage = pool.connection().await.unwrap();
     let sealed_l1_batch_number = storage
@@ -121,7 +157,8 @@ async fn expected_tree_hash(pool: &ConnectionPool<Core>) -> H256 {
 
         all_logs.extend(logs);
     }
-    ZkSyncTree::process_genesis_batch(&all_logs).root_hash
+    let tree_hasher = TreeHasher::for_protocol_version(ProtocolVersionId::latest());
+    ZkSyncTree::process_genesis_batch(&all_logs, tree_hasher).root_hash
 }
 
 #[tokio::test]
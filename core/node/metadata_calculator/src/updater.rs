@@ -14,7 +14,7 @@ use zksync_types::{
 };
 
 use super::{
-    helpers::{AsyncTree, Delayer, L1BatchWithLogs},
+    helpers::{committed_tree_hasher, AsyncTree, Delayer, L1BatchWithLogs},
     metrics::{TreeUpdateStage, METRICS},
     MetadataCalculator,
 };
@@ -125,9 +125,30 @@ impl TreeUpdater {
             let ((header, metadata, object_key), next_l1_batch_data) =
                 future::try_join(process_l1_batch_task, load_next_l1_batch_task).await?;
 
+            // During a transition between tree hashers, the tree may be hashed differently from what the protocol
+            // version of the batch requires. In this case, we commit to the root hash computed with the required hasher.
+            let committed_hasher = committed_tree_hasher(&header);
+            let root_hash = if committed_hasher == self.tree.hasher() {
+                metadata.root_hash
+            } else {
+                let rehash_latency = METRICS.start_stage(TreeUpdateStage::Rehash);
+                let root_hash = self
+                    .tree
+                    .root_hash_with_hasher(l1_batch_number, committed_hasher)
+                    .await?;
+                rehash_latency.observe();
+                tracing::info!(
+                    "Computed root hash {root_hash:?} for L1 batch #{l1_batch_number} with {committed_hasher:?} hasher \
+                     (tree is hashed with {:?}, root hash {:?})",
+                    self.tree.hasher(),
+                    metadata.root_hash
+                );
+                root_hash
+            };
+
             let save_postgres_latency = METRICS.start_stage(TreeUpdateStage::SavePostgres);
             let tree_data = L1BatchTreeData {
-                hash: metadata.root_hash,
+                hash: root_hash,
                 rollup_last_leaf_index: metadata.rollup_last_leaf_index,
             };
             storage
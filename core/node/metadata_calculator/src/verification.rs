@@ -0,0 +1,496 @@
+//! Background verification of the Merkle tree against Postgres.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use serde::Serialize;
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use zksync_merkle_tree::{Key, TreeEntry};
+use zksync_types::{L1BatchNumber, H256, U256};
+
+use crate::{
+    helpers::{committed_tree_hasher, AsyncTreeReader, LazyAsyncTreeReader},
+    metrics::VERIFICATION_METRICS,
+};
+
+/// Divergence between the Merkle tree and Postgres.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TreeDivergence {
+    /// Root hash or leaf count of the tree differs from the tree data persisted in Postgres.
+    Root {
+        tree_root_hash: H256,
+        tree_leaf_count: u64,
+        postgres_root_hash: H256,
+        postgres_rollup_last_leaf_index: u64,
+    },
+    /// Proof for a subtree of the tree is invalid.
+    SubtreeProof {
+        start_key: H256,
+        end_key: H256,
+        error: String,
+    },
+    /// Tree entry differs from the value and / or leaf index derived from Postgres.
+    Entry {
+        hashed_key: H256,
+        tree_value: H256,
+        tree_leaf_index: u64,
+        postgres_value: Option<H256>,
+        postgres_leaf_index: Option<u64>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+enum MerkleTreeVerificationTaskHealth {
+    Initialization,
+    Verifying {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_verified_l1_batch_number: Option<L1BatchNumber>,
+        verified_subtree_count: u64,
+    },
+    Diverged {
+        l1_batch_number: L1BatchNumber,
+        divergence: TreeDivergence,
+    },
+    ShuttingDown,
+}
+
+impl From<MerkleTreeVerificationTaskHealth> for Health {
+    fn from(health: MerkleTreeVerificationTaskHealth) -> Self {
+        let status = match &health {
+            MerkleTreeVerificationTaskHealth::Initialization
+            | MerkleTreeVerificationTaskHealth::Diverged { .. } => HealthStatus::Affected,
+            MerkleTreeVerificationTaskHealth::Verifying { .. } => HealthStatus::Ready,
+            MerkleTreeVerificationTaskHealth::ShuttingDown => HealthStatus::ShuttingDown,
+        };
+        Health::from(status).with_details(health)
+    }
+}
+
+#[derive(Debug)]
+enum VerificationOutcome {
+    Verified { entry_count: usize },
+    Diverged(TreeDivergence),
+}
+
+/// Task periodically verifying the latest root hash and a random subtree of the Merkle tree against
+/// Postgres-derived tree data and leaves. Each iteration only verifies a small subtree, so the task
+/// is cheap and can run alongside the tree updates.
+///
+/// Divergence is reported via the task health check, which stays affected once a divergence is detected.
+#[derive(Debug)]
+#[must_use = "Task should `run()` in a managed Tokio task"]
+pub struct MerkleTreeVerificationTask {
+    tree_reader: LazyAsyncTreeReader,
+    pool: ConnectionPool<Core>,
+    health_updater: HealthUpdater,
+    poll_interval: Duration,
+}
+
+impl MerkleTreeVerificationTask {
+    /// Recommended interval between verification iterations.
+    pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
+    /// Expected number of leaves in a verified subtree. Leaves are distributed uniformly in the tree,
+    /// so the subtree depth is chosen based on the total number of leaves.
+    const TARGET_SUBTREE_LEAF_COUNT: u64 = 256;
+    /// Maximum number of entries loaded from the tree for a single subtree. Caps the work per iteration
+    /// if the leaves are distributed non-uniformly; in this case, only a prefix of the subtree is verified.
+    const MAX_SUBTREE_ENTRIES: usize = 4 * Self::TARGET_SUBTREE_LEAF_COUNT as usize;
+
+    pub(super) fn new(
+        tree_reader: LazyAsyncTreeReader,
+        pool: ConnectionPool<Core>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            tree_reader,
+            pool,
+            health_updater: ReactiveHealthCheck::new("tree_verifier").1,
+            poll_interval,
+        }
+    }
+
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    /// Returns the depth of subtrees containing approximately [`Self::TARGET_SUBTREE_LEAF_COUNT`] leaves.
+    fn subtree_depth(leaf_count: u64) -> usize {
+        let ratio = leaf_count / Self::TARGET_SUBTREE_LEAF_COUNT;
+        (u64::BITS - ratio.leading_zeros()) as usize
+    }
+
+    /// Returns the inclusive key range of the subtree at the specified depth containing `key`.
+    fn subtree_key_range(key: Key, depth: usize) -> (Key, Key) {
+        let suffix_mask = if depth == 0 {
+            U256::MAX
+        } else {
+            U256::MAX >> depth
+        };
+        let start_key = key & !suffix_mask;
+        (start_key, start_key | suffix_mask)
+    }
+
+    fn hashed_key(key: Key) -> H256 {
+        let mut bytes = [0_u8; 32];
+        key.to_little_endian(&mut bytes);
+        H256(bytes)
+    }
+
+    /// Verifies the root hash and a subtree containing `sampled_key` for the latest L1 batch present both
+    /// in the tree and in Postgres. Returns `None` if there's nothing to verify yet (e.g., no L1 batches
+    /// are processed by the tree).
+    async fn verify_subtree(
+        &self,
+        tree_reader: &AsyncTreeReader,
+        sampled_key: Key,
+    ) -> anyhow::Result<Option<(L1BatchNumber, VerificationOutcome)>> {
+        let tree_info = tree_reader.clone().info().await;
+        let Some(last_tree_l1_batch_number) = tree_info.next_l1_batch_number.0.checked_sub(1)
+        else {
+            return Ok(None);
+        };
+
+        let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+        let Some(last_postgres_l1_batch_number) = storage
+            .blocks_dal()
+            .get_last_l1_batch_number_with_tree_data()
+            .await?
+        else {
+            return Ok(None);
+        };
+        let l1_batch_number =
+            last_postgres_l1_batch_number.min(L1BatchNumber(last_tree_l1_batch_number));
+        if tree_info
+            .min_l1_batch_number
+            .map_or(true, |min_l1_batch_number| {
+                l1_batch_number < min_l1_batch_number
+            })
+        {
+            return Ok(None); // The tree version is pruned
+        }
+
+        let tree_data = storage
+            .blocks_dal()
+            .get_l1_batch_tree_data(l1_batch_number)
+            .await?
+            .with_context(|| format!("no tree data for L1 batch #{l1_batch_number} in Postgres"))?;
+        let Some((_, last_l2_block_number)) = storage
+            .blocks_dal()
+            .get_l2_block_range_of_l1_batch(l1_batch_number)
+            .await?
+        else {
+            return Ok(None); // May happen for the snapshot L1 batch after snapshot recovery
+        };
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?
+            .with_context(|| format!("no header for L1 batch #{l1_batch_number} in Postgres"))?;
+        drop(storage);
+
+        let Some((root_hash, leaf_count)) = tree_reader.clone().root_info(l1_batch_number).await
+        else {
+            return Ok(None); // The tree version was pruned after requesting tree info
+        };
+        let tree_hasher = tree_reader.hasher();
+        // If the root hash committed to in Postgres is computed with another hasher (i.e., during a transition between hashers),
+        // it cannot be compared with the tree root hash cheaply, so we only compare leaf counts.
+        let is_root_hash_comparable = committed_tree_hasher(&header) == tree_hasher;
+        let is_root_diverged = is_root_hash_comparable && root_hash != tree_data.hash;
+        if is_root_diverged || leaf_count + 1 != tree_data.rollup_last_leaf_index {
+            let divergence = TreeDivergence::Root {
+                tree_root_hash: root_hash,
+                tree_leaf_count: leaf_count,
+                postgres_root_hash: tree_data.hash,
+                postgres_rollup_last_leaf_index: tree_data.rollup_last_leaf_index,
+            };
+            return Ok(Some((
+                l1_batch_number,
+                VerificationOutcome::Diverged(divergence),
+            )));
+        }
+
+        let depth = Self::subtree_depth(leaf_count);
+        let (start_key, end_key) = Self::subtree_key_range(sampled_key, depth);
+        let Ok(proof) = tree_reader
+            .clone()
+            .range_proof(
+                l1_batch_number,
+                start_key,
+                end_key,
+                Self::MAX_SUBTREE_ENTRIES,
+            )
+            .await
+        else {
+            return Ok(None); // The tree version was pruned
+        };
+        // The range may be truncated if the subtree contains too many entries.
+        let end_key = proof.end.base.key;
+        if let Err(err) = proof.verify(&tree_hasher, root_hash) {
+            let divergence = TreeDivergence::SubtreeProof {
+                start_key: Self::hashed_key(start_key),
+                end_key: Self::hashed_key(end_key),
+                error: format!("{err:#}"),
+            };
+            return Ok(Some((
+                l1_batch_number,
+                VerificationOutcome::Diverged(divergence),
+            )));
+        }
+
+        let mut entries: Vec<TreeEntry> = proof.entries;
+        entries.push(proof.start.base);
+        if end_key != start_key {
+            entries.push(proof.end.base);
+        }
+        entries.retain(|entry| !entry.is_empty());
+        let hashed_keys: Vec<_> = entries
+            .iter()
+            .map(|entry| Self::hashed_key(entry.key))
+            .collect();
+
+        let mut storage = self.pool.connection_tagged("metadata_calculator").await?;
+        let values = storage
+            .storage_logs_dal()
+            .get_storage_values(&hashed_keys, last_l2_block_number)
+            .await?;
+        let initial_writes = storage
+            .storage_logs_dal()
+            .get_l1_batches_and_indices_for_initial_writes(&hashed_keys)
+            .await?;
+        drop(storage);
+
+        for (entry, hashed_key) in entries.iter().zip(hashed_keys) {
+            let postgres_value = values.get(&hashed_key).copied().flatten();
+            let postgres_leaf_index = initial_writes
+                .get(&hashed_key)
+                .filter(|(write_l1_batch_number, _)| *write_l1_batch_number <= l1_batch_number)
+                .map(|&(_, leaf_index)| leaf_index);
+            if postgres_value != Some(entry.value) || postgres_leaf_index != Some(entry.leaf_index)
+            {
+                let divergence = TreeDivergence::Entry {
+                    hashed_key,
+                    tree_value: entry.value,
+                    tree_leaf_index: entry.leaf_index,
+                    postgres_value,
+                    postgres_leaf_index,
+                };
+                return Ok(Some((
+                    l1_batch_number,
+                    VerificationOutcome::Diverged(divergence),
+                )));
+            }
+        }
+
+        let outcome = VerificationOutcome::Verified {
+            entry_count: entries.len(),
+        };
+        Ok(Some((l1_batch_number, outcome)))
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.health_updater
+            .update(MerkleTreeVerificationTaskHealth::Initialization.into());
+
+        let tree_reader = tokio::select! {
+            res = self.tree_reader.clone().wait() => {
+                match res {
+                    Ok(reader) => reader,
+                    Err(_) => {
+                        tracing::info!("Merkle tree dropped; shutting down tree verification");
+                        return Ok(());
+                    }
+                }
+            }
+            _ = stop_receiver.changed() => {
+                tracing::info!("Stop signal received before Merkle tree is initialized; shutting down tree verification");
+                return Ok(());
+            }
+        };
+        self.health_updater.update(
+            MerkleTreeVerificationTaskHealth::Verifying {
+                last_verified_l1_batch_number: None,
+                verified_subtree_count: 0,
+            }
+            .into(),
+        );
+        tracing::info!("Merkle tree is initialized; starting tree verification");
+
+        let mut verified_subtree_count = 0;
+        let mut has_diverged = false;
+        while !*stop_receiver.borrow_and_update() {
+            let sampled_key = U256(rand::random());
+            let latency = VERIFICATION_METRICS.latency.start();
+            let verification_result = self.verify_subtree(&tree_reader, sampled_key).await?;
+            match verification_result {
+                Some((l1_batch_number, VerificationOutcome::Verified { entry_count })) => {
+                    let latency = latency.observe();
+                    tracing::debug!(
+                        "Verified subtree with {entry_count} entries for L1 batch #{l1_batch_number} in {latency:?}"
+                    );
+                    verified_subtree_count += 1;
+                    VERIFICATION_METRICS.verified_subtrees.inc();
+                    VERIFICATION_METRICS
+                        .verified_entries
+                        .inc_by(entry_count as u64);
+                    VERIFICATION_METRICS
+                        .last_verified_l1_batch
+                        .set(l1_batch_number.0.into());
+                    // Divergence remains reported even if subsequent checks succeed.
+                    if !has_diverged {
+                        let health = MerkleTreeVerificationTaskHealth::Verifying {
+                            last_verified_l1_batch_number: Some(l1_batch_number),
+                            verified_subtree_count,
+                        };
+                        self.health_updater.update(health.into());
+                    }
+                }
+                Some((l1_batch_number, VerificationOutcome::Diverged(divergence))) => {
+                    tracing::error!(
+                        "Merkle tree diverged from Postgres at L1 batch #{l1_batch_number}: {divergence:?}"
+                    );
+                    VERIFICATION_METRICS.divergences.inc();
+                    if !has_diverged {
+                        has_diverged = true;
+                        let health = MerkleTreeVerificationTaskHealth::Diverged {
+                            l1_batch_number,
+                            divergence,
+                        };
+                        self.health_updater.update(health.into());
+                    }
+                }
+                None => { /* nothing to verify yet */ }
+            }
+
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        self.health_updater
+            .update(MerkleTreeVerificationTaskHealth::ShuttingDown.into());
+        tracing::info!("Stop signal received, Merkle tree verification is shutting down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+    use tempfile::TempDir;
+    use zksync_types::{L2BlockNumber, StorageLog};
+
+    use super::*;
+    use crate::tests::{gen_storage_logs, reset_db_state, run_calculator, setup_calculator};
+
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    #[test]
+    fn subtree_key_ranges() {
+        assert_eq!(MerkleTreeVerificationTask::subtree_depth(0), 0);
+        assert_eq!(MerkleTreeVerificationTask::subtree_depth(255), 0);
+        assert_eq!(MerkleTreeVerificationTask::subtree_depth(256), 1);
+        assert_eq!(MerkleTreeVerificationTask::subtree_depth(1_000), 2);
+        assert_eq!(MerkleTreeVerificationTask::subtree_depth(1 << 30), 23);
+
+        let key = U256::from(0xdead_beef_u64) | (U256::from(0b101) << 253);
+        assert_eq!(
+            MerkleTreeVerificationTask::subtree_key_range(key, 0),
+            (U256::zero(), U256::MAX)
+        );
+        let (start_key, end_key) = MerkleTreeVerificationTask::subtree_key_range(key, 3);
+        assert_eq!(start_key, U256::from(0b101) << 253);
+        assert_eq!(end_key, start_key | (U256::MAX >> 3));
+        assert_eq!(
+            MerkleTreeVerificationTask::subtree_key_range(key, 256),
+            (key, key)
+        );
+    }
+
+    async fn setup_verification_task(
+        pool: &ConnectionPool<Core>,
+        temp_dir: &TempDir,
+    ) -> MerkleTreeVerificationTask {
+        let (calculator, _) = setup_calculator(temp_dir.path(), pool.clone()).await;
+        reset_db_state(pool, 5).await;
+        let verification_task = calculator.verification_task(POLL_INTERVAL);
+        run_calculator(calculator).await;
+        verification_task
+    }
+
+    fn is_diverged(health: &Health) -> bool {
+        health
+            .details()
+            .is_some_and(|details| details["stage"] == "diverged")
+    }
+
+    #[tokio::test]
+    async fn verifying_consistent_tree() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let verification_task = setup_verification_task(&pool, &temp_dir).await;
+        let mut health_check = verification_task.health_check();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let verification_task_handle = tokio::spawn(verification_task.run(stop_receiver));
+
+        let health = health_check
+            .wait_for(|health| {
+                health.details().is_some_and(|details| {
+                    details["stage"] == "verifying"
+                        && details["last_verified_l1_batch_number"] == 5
+                        && details["verified_subtree_count"].as_u64() >= Some(3)
+                })
+            })
+            .await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+
+        stop_sender.send_replace(true);
+        verification_task_handle.await.unwrap().unwrap();
+        health_check
+            .wait_for(|health| matches!(health.status(), HealthStatus::ShutDown))
+            .await;
+    }
+
+    #[tokio::test]
+    async fn detecting_divergence_in_leaves() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let temp_dir = TempDir::new().expect("failed get temporary directory for RocksDB");
+        let verification_task = setup_verification_task(&pool, &temp_dir).await;
+
+        // Overwrite a value from the first L1 batch in Postgres. The tree is small, so it's verified in full
+        // on each iteration.
+        let corrupted_log = &gen_storage_logs(0..100, 5)[0][0];
+        let corrupted_log = StorageLog::new_write_log(corrupted_log.key, H256::repeat_byte(0xff));
+        let mut storage = pool.connection().await.unwrap();
+        storage
+            .storage_logs_dal()
+            .insert_storage_logs(L2BlockNumber(5), &[(H256::zero(), vec![corrupted_log])])
+            .await
+            .unwrap();
+        drop(storage);
+
+        let mut health_check = verification_task.health_check();
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let verification_task_handle = tokio::spawn(verification_task.run(stop_receiver));
+
+        let health = health_check.wait_for(is_diverged).await;
+        assert_matches!(health.status(), HealthStatus::Affected);
+        let details = health.details().unwrap();
+        assert_eq!(details["l1_batch_number"], 5);
+        assert_eq!(details["divergence"]["kind"], "entry");
+        assert_eq!(
+            details["divergence"]["hashed_key"],
+            serde_json::to_value(corrupted_log.key.hashed_key()).unwrap()
+        );
+
+        stop_sender.send_replace(true);
+        verification_task_handle.await.unwrap().unwrap();
+    }
+}
@@ -12,8 +12,8 @@ use zksync_config::{
         fri_prover_group::FriProverGroupConfig,
         house_keeper::HouseKeeperConfig,
         wallets::Wallets,
-        DatabaseSecrets, FriProofCompressorConfig, FriProverConfig, FriWitnessGeneratorConfig,
-        L1Secrets, ObservabilityConfig, ProofDataHandlerConfig,
+        ApiSecrets, DatabaseSecrets, FriProofCompressorConfig, FriProverConfig,
+        FriWitnessGeneratorConfig, L1Secrets, ObservabilityConfig, ProofDataHandlerConfig,
     },
     ApiConfig, ContractVerifierConfig, ContractsConfig, DBConfig, EthConfig, EthWatchConfig,
     GasAdjusterConfig, GenesisConfig, ObjectStoreConfig, PostgresConfig,
@@ -100,8 +100,15 @@ impl MainNodeBuilder {
     fn add_query_eth_client_layer(mut self) -> anyhow::Result<Self> {
         let genesis = GenesisConfig::from_env()?;
         let eth_config = L1Secrets::from_env()?;
-        let query_eth_client_layer =
-            QueryEthClientLayer::new(genesis.l1_chain_id, eth_config.l1_rpc_url);
+        let mut query_eth_client_layer =
+            QueryEthClientLayer::new(genesis.l1_chain_id, eth_config.l1_rpc_url)
+                .with_fallback_urls(eth_config.fallback_l1_rpc_urls);
+        if let Some(url) = eth_config.private_relay_rpc_url {
+            query_eth_client_layer = query_eth_client_layer.with_private_relay_url(url);
+        }
+        if let Some(url) = eth_config.gateway_rpc_url {
+            query_eth_client_layer = query_eth_client_layer.with_gateway_url(url);
+        }
         self.node.add_layer(query_eth_client_layer);
         Ok(self)
     }
@@ -166,6 +173,7 @@ impl MainNodeBuilder {
         self.node.add_layer(EthWatchLayer::new(
             EthWatchConfig::from_env()?,
             ContractsConfig::from_env()?,
+            GenesisConfig::from_env()?.l1_chain_id,
         ));
         Ok(self)
     }
@@ -196,8 +204,13 @@ impl MainNodeBuilder {
         };
         let wallets = Wallets::from_env()?;
 
+        let mempool_config = MempoolConfig::from_env()?;
+
         // On main node we always use master pool sink.
-        self.node.add_layer(TxSinkLayer::MasterPoolSink);
+        self.node.add_layer(TxSinkLayer::MasterPoolSink {
+            replacement_fee_bump_percent: mempool_config.replacement_fee_bump_percent,
+            max_pending_txs_per_account: mempool_config.max_pending_txs_per_account,
+        });
         self.node.add_layer(TxSenderLayer::new(
             TxSenderConfig::new(
                 &state_keeper_config,
@@ -234,6 +247,7 @@ impl MainNodeBuilder {
 
     fn add_http_web3_api_layer(mut self) -> anyhow::Result<Self> {
         let rpc_config = ApiConfig::from_env()?.web3_json_rpc;
+        let api_secrets = ApiSecrets::from_env().ok();
         let contracts_config = ContractsConfig::from_env()?;
         let state_keeper_config = StateKeeperConfig::from_env()?;
         let with_debug_namespace = state_keeper_config.save_call_traces;
@@ -241,9 +255,9 @@ impl MainNodeBuilder {
 
         let mut namespaces = Namespace::DEFAULT.to_vec();
         if with_debug_namespace {
-            namespaces.push(Namespace::Debug)
+            namespaces.extend([Namespace::Debug, Namespace::Trace]);
         }
-        namespaces.push(Namespace::Snapshots);
+        namespaces.extend([Namespace::Snapshots, Namespace::Txpool]);
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -251,6 +265,9 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            client_rate_limits: Some(rpc_config.client_rate_limits(api_secrets.as_ref())),
+            method_filter: Some(rpc_config.http_method_filter()),
+            http_response_compression: rpc_config.http_response_compression,
             ..Default::default()
         };
         self.node.add_layer(Web3ServerLayer::http(
@@ -264,6 +281,7 @@ impl MainNodeBuilder {
 
     fn add_ws_web3_api_layer(mut self) -> anyhow::Result<Self> {
         let rpc_config = ApiConfig::from_env()?.web3_json_rpc;
+        let api_secrets = ApiSecrets::from_env().ok();
         let contracts_config = ContractsConfig::from_env()?;
         let genesis_config = GenesisConfig::from_env()?;
         let state_keeper_config = StateKeeperConfig::from_env()?;
@@ -272,9 +290,9 @@ impl MainNodeBuilder {
 
         let mut namespaces = Namespace::DEFAULT.to_vec();
         if with_debug_namespace {
-            namespaces.push(Namespace::Debug)
+            namespaces.extend([Namespace::Debug, Namespace::Trace]);
         }
-        namespaces.push(Namespace::Snapshots);
+        namespaces.extend([Namespace::Snapshots, Namespace::Txpool]);
 
         let optional_config = Web3ServerOptionalConfig {
             namespaces: Some(namespaces),
@@ -282,6 +300,8 @@ impl MainNodeBuilder {
             subscriptions_limit: Some(rpc_config.subscriptions_limit()),
             batch_request_size_limit: Some(rpc_config.max_batch_request_size()),
             response_body_size_limit: Some(rpc_config.max_response_body_size()),
+            client_rate_limits: Some(rpc_config.client_rate_limits(api_secrets.as_ref())),
+            method_filter: Some(rpc_config.ws_method_filter()),
             websocket_requests_per_minute_limit: Some(
                 rpc_config.websocket_requests_per_minute_limit(),
             ),
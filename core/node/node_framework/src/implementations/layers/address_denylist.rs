@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use zksync_config::configs::chain::MempoolConfig;
+use zksync_state_keeper::{AddressDenylist, AddressDenylistReloader};
+
+use crate::{
+    implementations::resources::state_keeper::AddressDenylistResource,
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Loads the address denylist from a file and provides it as a resource. The list is periodically reloaded
+/// by a separate task, so that it can be updated without restarting the node.
+#[derive(Debug)]
+pub struct AddressDenylistLayer {
+    path: String,
+    reload_interval: Duration,
+}
+
+impl AddressDenylistLayer {
+    /// Creates the layer if the denylist is enabled in the config.
+    pub fn new(mempool_config: &MempoolConfig) -> Option<Self> {
+        Some(Self {
+            path: mempool_config.address_denylist_path.clone()?,
+            reload_interval: mempool_config.address_denylist_reload_interval(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AddressDenylistLayer {
+    fn layer_name(&self) -> &'static str {
+        "address_denylist_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let denylist = AddressDenylist::from_file(&self.path)?;
+        tracing::info!(
+            "Loaded address denylist with {} addresses from `{}`",
+            denylist.len(),
+            self.path
+        );
+        context.insert_resource(AddressDenylistResource(denylist.clone()))?;
+
+        let reloader = AddressDenylistReloader::new(denylist, &self.path, self.reload_interval);
+        context.add_task(Box::new(AddressDenylistReloaderTask(reloader)));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct AddressDenylistReloaderTask(AddressDenylistReloader);
+
+#[async_trait::async_trait]
+impl Task for AddressDenylistReloaderTask {
+    fn id(&self) -> TaskId {
+        "address_denylist_reloader".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
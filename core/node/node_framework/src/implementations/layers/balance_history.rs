@@ -0,0 +1,49 @@
+use zksync_node_balance_history::{BalanceHistoryIndexer, BalanceHistoryIndexerConfig};
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the indexer of historical account balances used by the `zks_getBalanceHistory` API method.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Adds `balance_history_indexer` task to the node.
+#[derive(Debug)]
+pub struct BalanceHistoryLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for BalanceHistoryLayer {
+    fn layer_name(&self) -> &'static str {
+        "balance_history_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let main_pool = pool_resource.get().await?;
+
+        let indexer = BalanceHistoryIndexer::new(main_pool, BalanceHistoryIndexerConfig::default());
+        context.add_task(Box::new(BalanceHistoryIndexerTask { indexer }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct BalanceHistoryIndexerTask {
+    indexer: BalanceHistoryIndexer,
+}
+
+#[async_trait::async_trait]
+impl Task for BalanceHistoryIndexerTask {
+    fn id(&self) -> TaskId {
+        "balance_history_indexer".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.indexer.run(stop_receiver.0).await
+    }
+}
@@ -0,0 +1,60 @@
+use zksync_base_token_adjuster::{BaseTokenRatioPersister, FixedRatioSource};
+use zksync_config::configs::BaseTokenAdjusterConfig;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the persister of the ETH <-> base token conversion ratio used by
+/// the `zks_getBaseTokenConversionRatio` API method.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Adds `base_token_ratio_persister` task to the node.
+#[derive(Debug)]
+pub struct BaseTokenRatioPersisterLayer {
+    config: BaseTokenAdjusterConfig,
+}
+
+impl BaseTokenRatioPersisterLayer {
+    pub fn new(config: BaseTokenAdjusterConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BaseTokenRatioPersisterLayer {
+    fn layer_name(&self) -> &'static str {
+        "base_token_ratio_persister_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let main_pool = pool_resource.get().await?;
+
+        let source = FixedRatioSource::new(&self.config);
+        let persister = BaseTokenRatioPersister::new(main_pool, &self.config, Box::new(source));
+        context.add_task(Box::new(BaseTokenRatioPersisterTask { persister }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct BaseTokenRatioPersisterTask {
+    persister: BaseTokenRatioPersister,
+}
+
+#[async_trait::async_trait]
+impl Task for BaseTokenRatioPersisterTask {
+    fn id(&self) -> TaskId {
+        "base_token_ratio_persister".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.persister.run(stop_receiver.0).await
+    }
+}
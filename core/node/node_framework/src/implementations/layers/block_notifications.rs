@@ -0,0 +1,71 @@
+use zksync_config::configs::BlockNotificationsConfig;
+use zksync_node_block_notifications::{
+    BlockNotificationsDispatcher, BlockNotificationsDispatcherConfig, WebhookSink,
+};
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the block notifications dispatcher. Notifications are only written to the outbox
+/// if the state keeper and Ethereum sender layers are configured to do so.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Adds `block_notifications_dispatcher` task to the node.
+#[derive(Debug)]
+pub struct BlockNotificationsLayer {
+    config: BlockNotificationsConfig,
+}
+
+impl BlockNotificationsLayer {
+    pub fn new(config: BlockNotificationsConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for BlockNotificationsLayer {
+    fn layer_name(&self) -> &'static str {
+        "block_notifications_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let main_pool = pool_resource.get().await?;
+
+        let mut dispatcher = BlockNotificationsDispatcher::new(
+            main_pool,
+            BlockNotificationsDispatcherConfig {
+                poll_interval: self.config.poll_interval(),
+                max_batch_size: self.config.max_batch_size,
+            },
+        );
+        if let Some(webhook_url) = self.config.webhook_url {
+            dispatcher.add_sink(Box::new(WebhookSink::new(webhook_url)));
+        }
+
+        context.add_task(Box::new(BlockNotificationsDispatcherTask { dispatcher }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct BlockNotificationsDispatcherTask {
+    dispatcher: BlockNotificationsDispatcher,
+}
+
+#[async_trait::async_trait]
+impl Task for BlockNotificationsDispatcherTask {
+    fn id(&self) -> TaskId {
+        "block_notifications_dispatcher".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.dispatcher.run(stop_receiver.0).await
+    }
+}
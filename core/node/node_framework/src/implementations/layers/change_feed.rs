@@ -0,0 +1,69 @@
+use zksync_config::configs::ChangeFeedConfig;
+use zksync_node_change_feed::{ChangeFeed, WebhookSink};
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the change-data feed. Requires Postgres to run with `wal_level = logical`.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Adds `change_feed` task to the node.
+#[derive(Debug)]
+pub struct ChangeFeedLayer {
+    config: ChangeFeedConfig,
+}
+
+impl ChangeFeedLayer {
+    pub fn new(config: ChangeFeedConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ChangeFeedLayer {
+    fn layer_name(&self) -> &'static str {
+        "change_feed_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let main_pool = pool_resource.get().await?;
+
+        let mut feed = ChangeFeed::new(
+            main_pool,
+            zksync_node_change_feed::ChangeFeedConfig {
+                slot_name: self.config.slot_name.clone(),
+                poll_interval: self.config.poll_interval(),
+                max_batch_size: self.config.max_batch_size,
+            },
+        );
+        if let Some(webhook_url) = self.config.webhook_url {
+            feed.add_sink(Box::new(WebhookSink::new(webhook_url)));
+        }
+
+        context.add_task(Box::new(ChangeFeedTask { feed }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ChangeFeedTask {
+    feed: ChangeFeed,
+}
+
+#[async_trait::async_trait]
+impl Task for ChangeFeedTask {
+    fn id(&self) -> TaskId {
+        "change_feed".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.feed.run(stop_receiver.0).await
+    }
+}
@@ -1,13 +1,30 @@
-use zksync_circuit_breaker::CircuitBreakerChecker;
+use zksync_circuit_breaker::{
+    db_integrity::DbIntegrityChecker,
+    pool_saturation::{PoolSaturationChecker, PoolSaturationThresholds},
+    CircuitBreakerChecker,
+};
 use zksync_config::configs::chain::CircuitBreakerConfig;
 
 use crate::{
-    implementations::resources::circuit_breakers::CircuitBreakersResource,
+    implementations::resources::{
+        circuit_breakers::{CircuitBreakersResource, LoadSheddingResource},
+        pools::{PoolResource, ReplicaPool},
+    },
     service::{ServiceContext, StopReceiver},
     task::{TaskId, UnconstrainedTask},
     wiring_layer::{WiringError, WiringLayer},
 };
 
+/// Wiring layer for the circuit breaker checker.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<ReplicaPool>` (only if Postgres integrity checks are enabled).
+/// - Adds `db_integrity` to the `CircuitBreakersResource` (only if Postgres integrity checks are enabled).
+/// - Resolves `PoolResource<ReplicaPool>` (only if the pool saturation check is enabled).
+/// - Adds `pool_saturation` to the `CircuitBreakersResource` and adds `LoadSheddingResource`
+///   (only if the pool saturation check is enabled).
+/// - Adds `circuit_breaker_checker` unconstrained task to the node.
 #[derive(Debug)]
 pub struct CircuitBreakerCheckerLayer(pub CircuitBreakerConfig);
 
@@ -23,6 +40,40 @@ impl WiringLayer for CircuitBreakerCheckerLayer {
             .get_resource_or_default::<CircuitBreakersResource>()
             .await;
 
+        if let Some(chunk_size) = self.0.db_integrity_check_chunk_size {
+            let pool = node
+                .get_resource::<PoolResource<ReplicaPool>>()
+                .await?
+                .get_singleton()
+                .await?;
+            circuit_breaker_resource
+                .breakers
+                .insert(Box::new(DbIntegrityChecker::new(pool, chunk_size)))
+                .await;
+        }
+
+        if let Some(utilization_percent) = self.0.pool_saturation_shedding_percent {
+            // Use the shared pool so that the checker observes the same connections as API servers and the tx sender.
+            let pool = node
+                .get_resource::<PoolResource<ReplicaPool>>()
+                .await?
+                .get()
+                .await?;
+            let checker = PoolSaturationChecker::new(
+                pool,
+                PoolSaturationThresholds {
+                    utilization_percent,
+                    acquire_latency: self.0.pool_acquire_latency_shedding_threshold(),
+                    trip_after: self.0.pool_saturation_trip_after(),
+                },
+            );
+            node.insert_resource(LoadSheddingResource(checker.load_shedding_signal()))?;
+            circuit_breaker_resource
+                .breakers
+                .insert(Box::new(checker))
+                .await;
+        }
+
         let circuit_breaker_checker =
             CircuitBreakerChecker::new(circuit_breaker_resource.breakers, self.0.sync_interval());
 
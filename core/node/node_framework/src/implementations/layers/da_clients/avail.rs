@@ -0,0 +1,45 @@
+use zksync_config::configs::da_client::{DAClient, DAClientConfig};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_clients::avail::AvailClient;
+
+use crate::{
+    implementations::resources::da_client::DAClientResource,
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the Avail DA client.
+///
+/// ## Effects
+///
+/// - Adds `DAClientResource` to the node.
+#[derive(Debug)]
+pub struct AvailWiringLayer {
+    config: DAClientConfig,
+}
+
+impl AvailWiringLayer {
+    pub fn new(config: DAClientConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for AvailWiringLayer {
+    fn layer_name(&self) -> &'static str {
+        "avail_client_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let request_timeout = self.config.request_timeout();
+        let DAClient::Avail(avail_config) = self.config.client else {
+            return Err(WiringError::Configuration(
+                "DA client config is not for Avail".to_owned(),
+            ));
+        };
+        let client: Box<dyn DataAvailabilityClient> =
+            Box::new(AvailClient::new(avail_config, request_timeout)?);
+        context.insert_resource(DAClientResource(client))?;
+        Ok(())
+    }
+}
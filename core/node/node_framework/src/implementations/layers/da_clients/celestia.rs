@@ -0,0 +1,45 @@
+use zksync_config::configs::da_client::{DAClient, DAClientConfig};
+use zksync_da_client::DataAvailabilityClient;
+use zksync_da_clients::celestia::CelestiaClient;
+
+use crate::{
+    implementations::resources::da_client::DAClientResource,
+    service::ServiceContext,
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the Celestia DA client.
+///
+/// ## Effects
+///
+/// - Adds `DAClientResource` to the node.
+#[derive(Debug)]
+pub struct CelestiaWiringLayer {
+    config: DAClientConfig,
+}
+
+impl CelestiaWiringLayer {
+    pub fn new(config: DAClientConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for CelestiaWiringLayer {
+    fn layer_name(&self) -> &'static str {
+        "celestia_client_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let request_timeout = self.config.request_timeout();
+        let DAClient::Celestia(celestia_config) = self.config.client else {
+            return Err(WiringError::Configuration(
+                "DA client config is not for Celestia".to_owned(),
+            ));
+        };
+        let client: Box<dyn DataAvailabilityClient> =
+            Box::new(CelestiaClient::new(celestia_config, request_timeout)?);
+        context.insert_resource(DAClientResource(client))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod avail;
+pub mod celestia;
+pub mod eigen_da;
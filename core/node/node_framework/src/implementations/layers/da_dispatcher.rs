@@ -0,0 +1,65 @@
+use zksync_config::configs::da_dispatcher::DADispatcherConfig;
+use zksync_da_dispatcher::DataAvailabilityDispatcher;
+
+use crate::{
+    implementations::resources::{
+        da_client::DAClientResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the dispatcher sending L1 batch pubdata to a DA layer.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Resolves `DAClientResource`.
+/// - Adds `da_dispatcher` task to the node.
+#[derive(Debug)]
+pub struct DataAvailabilityDispatcherLayer {
+    da_config: DADispatcherConfig,
+}
+
+impl DataAvailabilityDispatcherLayer {
+    pub fn new(da_config: DADispatcherConfig) -> Self {
+        Self { da_config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for DataAvailabilityDispatcherLayer {
+    fn layer_name(&self) -> &'static str {
+        "da_dispatcher_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        // The dispatcher holds at most one connection at a time.
+        let master_pool = master_pool_resource.get_singleton().await?;
+        let da_client = context.get_resource::<DAClientResource>().await?.0;
+
+        context.add_task(Box::new(DataAvailabilityDispatcherTask {
+            dispatcher: DataAvailabilityDispatcher::new(master_pool, self.da_config, da_client),
+        }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct DataAvailabilityDispatcherTask {
+    dispatcher: DataAvailabilityDispatcher,
+}
+
+#[async_trait::async_trait]
+impl Task for DataAvailabilityDispatcherTask {
+    fn id(&self) -> TaskId {
+        "da_dispatcher".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.dispatcher.run(stop_receiver.0).await
+    }
+}
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 use zksync_circuit_breaker::l1_txs::FailedL1TransactionChecker;
 use zksync_config::configs::{eth_sender::EthConfig, ContractsConfig};
@@ -8,8 +10,14 @@ use zksync_types::{commitment::L1BatchCommitmentMode, L2ChainId};
 use crate::{
     implementations::resources::{
         circuit_breakers::CircuitBreakersResource,
-        eth_interface::{BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource},
-        l1_tx_params::L1TxParamsResource,
+        eth_interface::{
+            BoundEthInterfaceForBlobsResource, BoundEthInterfaceForGatewayResource,
+            BoundEthInterfaceForSuccessorBlobsResource, BoundEthInterfaceForSuccessorResource,
+            BoundEthInterfaceResource, PrivateRelayEthInterfaceResource,
+        },
+        eth_sender::EthSenderPauseHandleResource,
+        healthcheck::AppHealthCheckResource,
+        l1_tx_params::{GatewayL1TxParamsResource, L1TxParamsResource},
         object_store::ObjectStoreResource,
         pools::{MasterPool, PoolResource, ReplicaPool},
     },
@@ -21,11 +29,21 @@ use crate::{
 #[derive(Debug)]
 pub struct EthTxManagerLayer {
     eth_sender_config: EthConfig,
+    insert_block_notifications: bool,
 }
 
 impl EthTxManagerLayer {
     pub fn new(eth_sender_config: EthConfig) -> Self {
-        Self { eth_sender_config }
+        Self {
+            eth_sender_config,
+            insert_block_notifications: false,
+        }
+    }
+
+    /// Enables inserting notifications about L1 batch status changes to the block notifications outbox.
+    pub fn with_block_notifications(mut self) -> Self {
+        self.insert_block_notifications = true;
+        self
     }
 }
 
@@ -52,17 +70,39 @@ impl WiringLayer for EthTxManagerLayer {
             Err(err) => return Err(err),
         };
 
+        let (successor_client, successor_client_blobs) =
+            get_successor_clients(&mut context).await?;
+
         let config = self.eth_sender_config.sender.context("sender")?;
 
         let gas_adjuster = context.get_resource::<L1TxParamsResource>().await?.0;
 
-        let eth_tx_manager_actor = EthTxManager::new(
+        let mut eth_tx_manager_actor = EthTxManager::new(
             master_pool,
             config,
             gas_adjuster,
             eth_client,
             eth_client_blobs,
-        );
+        )
+        .with_successor_gateways(successor_client.into_iter().chain(successor_client_blobs));
+        if self.insert_block_notifications {
+            eth_tx_manager_actor = eth_tx_manager_actor.with_block_notifications();
+        }
+        match context
+            .get_resource::<PrivateRelayEthInterfaceResource>()
+            .await
+        {
+            Ok(PrivateRelayEthInterfaceResource(client)) => {
+                eth_tx_manager_actor = eth_tx_manager_actor.with_private_relay_client(client);
+            }
+            Err(WiringError::ResourceLacking { .. }) => {}
+            Err(err) => return Err(err),
+        }
+        if let Some(gateway_client) = get_gateway_client(&mut context).await? {
+            let gateway_gas_adjuster = context.get_resource::<GatewayL1TxParamsResource>().await?.0;
+            eth_tx_manager_actor =
+                eth_tx_manager_actor.with_gateway_client(gateway_client, gateway_gas_adjuster);
+        }
 
         context.add_task(Box::new(EthTxManagerTask {
             eth_tx_manager_actor,
@@ -130,16 +170,22 @@ impl WiringLayer for EthTxAggregatorLayer {
         let eth_client_blobs_addr = eth_client_blobs
             .as_deref()
             .map(BoundEthInterface::sender_account);
+        let (successor_client, successor_client_blobs) =
+            get_successor_clients(&mut context).await?;
 
         let config = self.eth_sender_config.sender.context("sender")?;
-        let aggregator = Aggregator::new(
+        let mut aggregator = Aggregator::new(
             config.clone(),
             object_store,
             eth_client_blobs_addr.is_some(),
             self.l1_batch_commit_data_generator_mode,
         );
+        if aggregator.has_dynamic_pubdata_da() {
+            let gas_adjuster = context.get_resource::<L1TxParamsResource>().await?.0;
+            aggregator = aggregator.with_gas_adjuster(gas_adjuster);
+        }
 
-        let eth_tx_aggregator_actor = EthTxAggregator::new(
+        let mut eth_tx_aggregator_actor = EthTxAggregator::new(
             master_pool.clone(),
             config.clone(),
             aggregator,
@@ -150,7 +196,27 @@ impl WiringLayer for EthTxAggregatorLayer {
             self.zksync_network_id,
             eth_client_blobs_addr,
         )
+        .await
+        .with_successor_operators(
+            successor_client
+                .as_deref()
+                .map(BoundEthInterface::sender_account),
+            successor_client_blobs
+                .as_deref()
+                .map(BoundEthInterface::sender_account),
+        )
         .await;
+        if let Some(gateway_client) = get_gateway_client(&mut context).await? {
+            eth_tx_aggregator_actor = eth_tx_aggregator_actor.with_gateway_client(gateway_client);
+        }
+
+        // Insert the pause handle and report the paused state as a part of the app health.
+        let pause_handle = eth_tx_aggregator_actor.pause_handle();
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health
+            .insert_custom_component(Arc::new(pause_handle.clone()))
+            .map_err(WiringError::internal)?;
+        context.insert_resource(EthSenderPauseHandleResource(pause_handle))?;
 
         context.add_task(Box::new(EthTxAggregatorTask {
             eth_tx_aggregator_actor,
@@ -166,6 +232,49 @@ impl WiringLayer for EthTxAggregatorLayer {
     }
 }
 
+/// Returns clients for successor operators used during key rotation, if they are configured.
+async fn get_successor_clients(
+    context: &mut ServiceContext<'_>,
+) -> Result<
+    (
+        Option<Box<dyn BoundEthInterface>>,
+        Option<Box<dyn BoundEthInterface>>,
+    ),
+    WiringError,
+> {
+    let successor_client = match context
+        .get_resource::<BoundEthInterfaceForSuccessorResource>()
+        .await
+    {
+        Ok(BoundEthInterfaceForSuccessorResource(client)) => Some(client),
+        Err(WiringError::ResourceLacking { .. }) => None,
+        Err(err) => return Err(err),
+    };
+    let successor_client_blobs = match context
+        .get_resource::<BoundEthInterfaceForSuccessorBlobsResource>()
+        .await
+    {
+        Ok(BoundEthInterfaceForSuccessorBlobsResource(client)) => Some(client),
+        Err(WiringError::ResourceLacking { .. }) => None,
+        Err(err) => return Err(err),
+    };
+    Ok((successor_client, successor_client_blobs))
+}
+
+/// Returns the signing client for the gateway settlement layer, if it is configured.
+async fn get_gateway_client(
+    context: &mut ServiceContext<'_>,
+) -> Result<Option<Box<dyn BoundEthInterface>>, WiringError> {
+    match context
+        .get_resource::<BoundEthInterfaceForGatewayResource>()
+        .await
+    {
+        Ok(BoundEthInterfaceForGatewayResource(client)) => Ok(Some(client)),
+        Err(WiringError::ResourceLacking { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
 #[derive(Debug)]
 struct EthTxAggregatorTask {
     eth_tx_aggregator_actor: EthTxAggregator,
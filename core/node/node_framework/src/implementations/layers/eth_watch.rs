@@ -3,12 +3,12 @@ use std::time::Duration;
 use zksync_config::{ContractsConfig, EthWatchConfig};
 use zksync_contracts::governance_contract;
 use zksync_dal::{ConnectionPool, Core};
-use zksync_eth_watch::{EthHttpQueryClient, EthWatch};
-use zksync_types::{ethabi::Contract, Address};
+use zksync_eth_watch::{EthClient, EthHttpQueryClient, EthWatch};
+use zksync_types::{ethabi::Contract, Address, L1ChainId};
 
 use crate::{
     implementations::resources::{
-        eth_interface::EthInterfaceResource,
+        eth_interface::{EthInterfaceResource, GatewayEthInterfaceResource},
         pools::{MasterPool, PoolResource},
     },
     service::{ServiceContext, StopReceiver},
@@ -20,13 +20,19 @@ use crate::{
 pub struct EthWatchLayer {
     eth_watch_config: EthWatchConfig,
     contracts_config: ContractsConfig,
+    l1_chain_id: L1ChainId,
 }
 
 impl EthWatchLayer {
-    pub fn new(eth_watch_config: EthWatchConfig, contracts_config: ContractsConfig) -> Self {
+    pub fn new(
+        eth_watch_config: EthWatchConfig,
+        contracts_config: ContractsConfig,
+        l1_chain_id: L1ChainId,
+    ) -> Self {
         Self {
             eth_watch_config,
             contracts_config,
+            l1_chain_id,
         }
     }
 }
@@ -52,9 +58,22 @@ impl WiringLayer for EthWatchLayer {
             self.contracts_config.governance_addr,
             self.eth_watch_config.confirmations_for_eth_event,
         );
+        let gateway_client = match context.get_resource::<GatewayEthInterfaceResource>().await {
+            Ok(GatewayEthInterfaceResource(client)) => {
+                Some(EthHttpQueryClient::for_settlement_layer(
+                    client,
+                    self.eth_watch_config.confirmations_for_eth_event,
+                ))
+            }
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
+
         context.add_task(Box::new(EthWatchTask {
             main_pool,
             client: eth_client,
+            gateway_client,
+            l1_chain_id: self.l1_chain_id,
             governance_contract: governance_contract(),
             diamond_proxy_address: self.contracts_config.diamond_proxy_addr,
             poll_interval: self.eth_watch_config.poll_interval(),
@@ -68,6 +87,8 @@ impl WiringLayer for EthWatchLayer {
 struct EthWatchTask {
     main_pool: ConnectionPool<Core>,
     client: EthHttpQueryClient,
+    gateway_client: Option<EthHttpQueryClient>,
+    l1_chain_id: L1ChainId,
     governance_contract: Contract,
     diamond_proxy_address: Address,
     poll_interval: Duration,
@@ -87,6 +108,12 @@ impl Task for EthWatchTask {
             self.main_pool,
             self.poll_interval,
         )
+        .await?
+        .with_settlement_layer_switches(
+            self.l1_chain_id,
+            self.gateway_client
+                .map(|client| Box::new(client) as Box<dyn EthClient>),
+        )
         .await?;
 
         eth_watch.run(stop_receiver.0).await
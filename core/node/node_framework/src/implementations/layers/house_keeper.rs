@@ -14,10 +14,11 @@ use zksync_house_keeper::{
         FriWitnessGeneratorJobRetryManager, FriWitnessGeneratorQueueReporter,
         WaitingToQueuedFriWitnessJobMover,
     },
+    table_partition_manager::TablePartitionManager,
 };
 
 use crate::{
-    implementations::resources::pools::{PoolResource, ProverPool, ReplicaPool},
+    implementations::resources::pools::{MasterPool, PoolResource, ProverPool, ReplicaPool},
     service::{ServiceContext, StopReceiver},
     task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
@@ -79,6 +80,22 @@ impl WiringLayer for HouseKeeperLayer {
             l1_batch_metrics_reporter,
         }));
 
+        if let Some((partitioning_interval, partition_size)) =
+            self.house_keeper_config.table_partitioning_params()
+        {
+            // Partitions are managed using DDL statements, which cannot be executed on a replica.
+            let master_pool = context
+                .get_resource::<PoolResource<MasterPool>>()
+                .await?
+                .get_singleton()
+                .await?;
+            let table_partition_manager =
+                TablePartitionManager::new(master_pool, partitioning_interval, partition_size);
+            context.add_task(Box::new(TablePartitionManagerTask {
+                table_partition_manager,
+            }));
+        }
+
         let fri_prover_job_retry_manager = FriProverJobRetryManager::new(
             self.fri_prover_config.max_attempts,
             self.fri_prover_config.proof_generation_timeout(),
@@ -212,6 +229,22 @@ impl Task for L1BatchMetricsReporterTask {
     }
 }
 
+#[derive(Debug)]
+struct TablePartitionManagerTask {
+    table_partition_manager: TablePartitionManager,
+}
+
+#[async_trait::async_trait]
+impl Task for TablePartitionManagerTask {
+    fn id(&self) -> TaskId {
+        "table_partition_manager".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.table_partition_manager.run(stop_receiver.0).await
+    }
+}
+
 #[derive(Debug)]
 struct FriProverJobRetryManagerTask {
     fri_prover_job_retry_manager: FriProverJobRetryManager,
@@ -10,8 +10,10 @@ use zksync_types::fee_model::FeeModelConfig;
 
 use crate::{
     implementations::resources::{
-        eth_interface::EthInterfaceResource, fee_input::FeeInputResource,
-        l1_tx_params::L1TxParamsResource,
+        eth_interface::{EthInterfaceResource, GatewayEthInterfaceResource},
+        fee_input::FeeInputResource,
+        l1_tx_params::{GatewayL1TxParamsResource, L1TxParamsResource},
+        pools::{MasterPool, PoolResource},
     },
     service::{ServiceContext, StopReceiver},
     task::{Task, TaskId},
@@ -50,6 +52,8 @@ impl WiringLayer for SequencerL1GasLayer {
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
         let client = context.get_resource::<EthInterfaceResource>().await?.0;
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let history_pool = pool_resource.get_singleton().await?;
         let adjuster = GasAdjuster::new(
             client,
             self.gas_adjuster_config,
@@ -57,7 +61,8 @@ impl WiringLayer for SequencerL1GasLayer {
             self.genesis_config.l1_batch_commit_data_generator_mode,
         )
         .await
-        .context("GasAdjuster::new()")?;
+        .context("GasAdjuster::new()")?
+        .with_price_history(history_pool);
         let gas_adjuster = Arc::new(adjuster);
 
         let batch_fee_input_provider = Arc::new(MainNodeFeeInputProvider::new(
@@ -68,20 +73,46 @@ impl WiringLayer for SequencerL1GasLayer {
 
         context.insert_resource(L1TxParamsResource(gas_adjuster.clone()))?;
 
-        context.add_task(Box::new(GasAdjusterTask { gas_adjuster }));
+        context.add_task(Box::new(GasAdjusterTask {
+            id: "gas_adjuster",
+            gas_adjuster,
+        }));
+
+        match context.get_resource::<GatewayEthInterfaceResource>().await {
+            Ok(GatewayEthInterfaceResource(gateway_client)) => {
+                // Pubdata is never sent to the gateway chain in blobs.
+                let gateway_gas_adjuster = GasAdjuster::new(
+                    gateway_client,
+                    self.gas_adjuster_config,
+                    PubdataSendingMode::Calldata,
+                    self.genesis_config.l1_batch_commit_data_generator_mode,
+                )
+                .await
+                .context("GasAdjuster::new() for gateway")?;
+                let gas_adjuster = Arc::new(gateway_gas_adjuster);
+                context.insert_resource(GatewayL1TxParamsResource(gas_adjuster.clone()))?;
+                context.add_task(Box::new(GasAdjusterTask {
+                    id: "gateway_gas_adjuster",
+                    gas_adjuster,
+                }));
+            }
+            Err(WiringError::ResourceLacking { .. }) => {}
+            Err(err) => return Err(err),
+        }
         Ok(())
     }
 }
 
 #[derive(Debug)]
 struct GasAdjusterTask {
+    id: &'static str,
     gas_adjuster: Arc<GasAdjuster>,
 }
 
 #[async_trait::async_trait]
 impl Task for GasAdjusterTask {
     fn id(&self) -> TaskId {
-        "gas_adjuster".into()
+        self.id.into()
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
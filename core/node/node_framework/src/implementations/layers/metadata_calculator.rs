@@ -1,12 +1,14 @@
 use std::{
     net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
     sync::Arc,
 };
 
 use anyhow::Context as _;
-use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode};
+use zksync_config::configs::{api::MerkleTreeApiConfig, database::MerkleTreeMode, PruningConfig};
 use zksync_metadata_calculator::{
-    LazyAsyncTreeReader, MetadataCalculator, MetadataCalculatorConfig,
+    AsyncTreeReader, LazyAsyncTreeReader, MerkleTreeCheckpointTask, MerkleTreePruningTask,
+    MerkleTreeReplica, MerkleTreeVerificationTask, MetadataCalculator, MetadataCalculatorConfig,
 };
 use zksync_storage::RocksDB;
 
@@ -28,13 +30,18 @@ use crate::{
 ///
 /// - Resolves `PoolResource<MasterPool>`.
 /// - Resolves `PoolResource<ReplicaPool>`.
-/// - Resolves `ObjectStoreResource` (optional).
+/// - Resolves `ObjectStoreResource` (optional; required in the full tree mode or if checkpoint exports are enabled).
 /// - Adds `tree_health_check` to the `ResourceCollection<HealthCheckResource>`.
 /// - Adds `metadata_calculator` to the node.
+/// - Adds `merkle_tree_pruning` to the node and its health check (if pruning is enabled, or the tree retention
+///   policy is configured).
+/// - Adds `merkle_tree_verification` to the node and its health check (if background verification is enabled).
+/// - Adds `merkle_tree_checkpoints` to the node and its health check (if checkpoint exports are enabled).
 #[derive(Debug)]
 pub struct MetadataCalculatorLayer {
     config: MetadataCalculatorConfig,
     tree_api_config: Option<MerkleTreeApiConfig>,
+    pruning_config: Option<PruningConfig>,
 }
 
 impl MetadataCalculatorLayer {
@@ -42,6 +49,7 @@ impl MetadataCalculatorLayer {
         Self {
             config,
             tree_api_config: None,
+            pruning_config: None,
         }
     }
 
@@ -49,6 +57,12 @@ impl MetadataCalculatorLayer {
         self.tree_api_config = Some(tree_api_config);
         self
     }
+
+    /// Enables pruning of the Merkle tree, which follows Postgres pruning.
+    pub fn with_pruning_config(mut self, pruning_config: PruningConfig) -> Self {
+        self.pruning_config = Some(pruning_config);
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -68,6 +82,16 @@ impl WiringLayer for MetadataCalculatorLayer {
             .get_custom(10)
             .await?;
 
+        let tree_verification_poll_interval = self.config.verification_interval;
+        let tree_checkpoint_interval = self.config.checkpoint_interval;
+        let tree_pruning_poll_interval = if let Some(pruning_config) = &self.pruning_config {
+            Some(pruning_config.removal_delay() / 2)
+        } else {
+            self.config
+                .pruning_retained_l1_batches
+                .map(|_| MerkleTreePruningTask::DEFAULT_POLL_INTERVAL)
+        };
+
         let object_store = match self.config.mode {
             MerkleTreeMode::Lightweight => None,
             MerkleTreeMode::Full => {
@@ -76,7 +100,13 @@ impl WiringLayer for MetadataCalculatorLayer {
             }
         };
 
-        let metadata_calculator = MetadataCalculator::new(
+        let checkpoint_object_store = if tree_checkpoint_interval.is_some() {
+            Some(context.get_resource::<ObjectStoreResource>().await?.0)
+        } else {
+            None
+        };
+
+        let mut metadata_calculator = MetadataCalculator::new(
             self.config,
             object_store.map(|store_resource| store_resource.0),
             main_pool,
@@ -98,6 +128,32 @@ impl WiringLayer for MetadataCalculatorLayer {
             }));
         }
 
+        if let Some(poll_interval) = tree_pruning_poll_interval {
+            let pruning_task = metadata_calculator.pruning_task(poll_interval);
+            app_health
+                .insert_component(pruning_task.health_check())
+                .map_err(WiringError::internal)?;
+            context.add_task(Box::new(TreePruningTask(pruning_task)));
+        }
+
+        if let Some(poll_interval) = tree_verification_poll_interval {
+            let verification_task = metadata_calculator.verification_task(poll_interval);
+            app_health
+                .insert_component(verification_task.health_check())
+                .map_err(WiringError::internal)?;
+            context.add_task(Box::new(TreeVerificationTask(verification_task)));
+        }
+
+        if let (Some(interval), Some(object_store)) =
+            (tree_checkpoint_interval, checkpoint_object_store)
+        {
+            let checkpoint_task = metadata_calculator.checkpoint_task(object_store, interval);
+            app_health
+                .insert_component(checkpoint_task.health_check())
+                .map_err(WiringError::internal)?;
+            context.add_task(Box::new(TreeCheckpointTask(checkpoint_task)));
+        }
+
         context.insert_resource(TreeApiClientResource(Arc::new(
             metadata_calculator.tree_reader(),
         )))?;
@@ -111,6 +167,69 @@ impl WiringLayer for MetadataCalculatorLayer {
     }
 }
 
+/// Builder for a read-only Merkle tree replica serving the tree API. The replica tails the RocksDB
+/// of the tree run by another process.
+///
+/// ## Effects
+///
+/// - Adds `tree_replica` health check to the `AppHealthCheckResource`.
+/// - Adds `TreeApiClientResource` (unless it is already provided).
+/// - Adds `tree_replica` and `tree_api` tasks to the node.
+#[derive(Debug)]
+pub struct TreeApiReplicaLayer {
+    config: MetadataCalculatorConfig,
+    tree_api_config: MerkleTreeApiConfig,
+}
+
+impl TreeApiReplicaLayer {
+    pub fn new(config: MetadataCalculatorConfig, tree_api_config: MerkleTreeApiConfig) -> Self {
+        Self {
+            config,
+            tree_api_config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for TreeApiReplicaLayer {
+    fn layer_name(&self) -> &'static str {
+        "tree_api_replica_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let replica_path = self.tree_api_config.replica_path.clone().ok_or_else(|| {
+            WiringError::Configuration(
+                "Merkle tree API run without a tree component requires `replica_path` to be specified"
+                    .to_owned(),
+            )
+        })?;
+        let replica = MerkleTreeReplica::new(
+            self.config,
+            PathBuf::from(replica_path),
+            self.tree_api_config.replica_poll_interval(),
+        )
+        .await?;
+
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health
+            .insert_component(replica.health_check())
+            .map_err(WiringError::internal)?;
+
+        let tree_reader = replica.tree_reader();
+        match context.insert_resource(TreeApiClientResource(Arc::new(tree_reader.clone()))) {
+            // Another client (e.g., an HTTP client) was already added; we don't want to replace it.
+            Ok(()) | Err(WiringError::ResourceAlreadyProvided { .. }) => {}
+            Err(err) => return Err(err),
+        }
+        context.add_task(Box::new(TreeApiReplicaServerTask {
+            bind_addr: (Ipv4Addr::UNSPECIFIED, self.tree_api_config.port).into(),
+            tree_reader,
+        }));
+        context.add_task(Box::new(TreeReplicaTask(replica)));
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct MetadataCalculatorTask {
     metadata_calculator: MetadataCalculator,
@@ -133,6 +252,48 @@ impl Task for MetadataCalculatorTask {
     }
 }
 
+#[derive(Debug)]
+pub struct TreePruningTask(MerkleTreePruningTask);
+
+#[async_trait::async_trait]
+impl Task for TreePruningTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_pruning".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
+
+#[derive(Debug)]
+pub struct TreeVerificationTask(MerkleTreeVerificationTask);
+
+#[async_trait::async_trait]
+impl Task for TreeVerificationTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_verification".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
+
+#[derive(Debug)]
+pub struct TreeCheckpointTask(MerkleTreeCheckpointTask);
+
+#[async_trait::async_trait]
+impl Task for TreeCheckpointTask {
+    fn id(&self) -> TaskId {
+        "merkle_tree_checkpoints".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
+
 #[derive(Debug)]
 pub struct TreeApiTask {
     bind_addr: SocketAddr,
@@ -154,3 +315,42 @@ impl Task for TreeApiTask {
             .await
     }
 }
+
+#[derive(Debug)]
+pub struct TreeReplicaTask(MerkleTreeReplica);
+
+#[async_trait::async_trait]
+impl Task for TreeReplicaTask {
+    fn id(&self) -> TaskId {
+        "tree_replica".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        let result = self.0.run(stop_receiver.0).await;
+
+        // Wait for all the instances of RocksDB to be destroyed.
+        tokio::task::spawn_blocking(RocksDB::await_rocksdb_termination)
+            .await
+            .context("failed terminating RocksDB instances")?;
+        result
+    }
+}
+
+#[derive(Debug)]
+pub struct TreeApiReplicaServerTask {
+    bind_addr: SocketAddr,
+    tree_reader: AsyncTreeReader,
+}
+
+#[async_trait::async_trait]
+impl Task for TreeApiReplicaServerTask {
+    fn id(&self) -> TaskId {
+        "tree_api".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.tree_reader
+            .run_api_server(self.bind_addr, stop_receiver.0)
+            .await
+    }
+}
@@ -1,8 +1,15 @@
+pub mod address_denylist;
+pub mod balance_history;
+pub mod base_token_adjuster;
+pub mod block_notifications;
+pub mod change_feed;
 pub mod circuit_breaker_checker;
 pub mod commitment_generator;
 pub mod consensus;
 pub mod consistency_checker;
 pub mod contract_verification_api;
+pub mod da_clients;
+pub mod da_dispatcher;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod healtcheck_server;
@@ -15,11 +22,13 @@ pub mod pk_signing_eth_client;
 pub mod pools_layer;
 pub mod prometheus_exporter;
 pub mod proof_data_handler;
+pub mod pruning;
 pub mod query_eth_client;
 pub mod reorg_detector_checker;
 pub mod reorg_detector_runner;
 pub mod sigint;
 pub mod state_keeper;
 pub mod tee_verifier_input_producer;
+pub mod token_transfers;
 pub mod vm_runner;
 pub mod web3_api;
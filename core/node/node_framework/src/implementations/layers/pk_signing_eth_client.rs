@@ -3,17 +3,21 @@ use zksync_config::{
     configs::{wallets, ContractsConfig},
     EthConfig,
 };
-use zksync_eth_client::clients::PKSigningClient;
-use zksync_types::L1ChainId;
+use zksync_eth_client::{clients::signing_client_for_wallet, EthInterface};
+use zksync_types::{Address, L1ChainId};
 
 use crate::{
     implementations::resources::eth_interface::{
-        BoundEthInterfaceForBlobsResource, BoundEthInterfaceResource, EthInterfaceResource,
+        BoundEthInterfaceForBlobsResource, BoundEthInterfaceForGatewayResource,
+        BoundEthInterfaceForSuccessorBlobsResource, BoundEthInterfaceForSuccessorResource,
+        BoundEthInterfaceResource, EthInterfaceResource, GatewayEthInterfaceResource,
     },
     service::ServiceContext,
     wiring_layer::{WiringError, WiringLayer},
 };
 
+/// Wiring layer providing signing Ethereum clients for eth_sender wallets. Wallets may be backed either
+/// by private keys or by remote signers (e.g., a cloud KMS).
 #[derive(Debug)]
 pub struct PKSigningEthClientLayer {
     eth_sender_config: EthConfig,
@@ -45,35 +49,86 @@ impl WiringLayer for PKSigningEthClientLayer {
     }
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
-        let private_key = self.wallets.operator.private_key();
         let gas_adjuster_config = self
             .eth_sender_config
             .gas_adjuster
             .as_ref()
             .context("gas_adjuster config is missing")?;
+        let default_priority_fee_per_gas = gas_adjuster_config.default_priority_fee_per_gas;
+        let diamond_proxy_addr = self.contracts_config.diamond_proxy_addr;
         let EthInterfaceResource(query_client) = context.get_resource().await?;
 
-        let signing_client = PKSigningClient::new_raw(
-            private_key.clone(),
-            self.contracts_config.diamond_proxy_addr,
-            gas_adjuster_config.default_priority_fee_per_gas,
+        let signing_client = signing_client_for_wallet(
+            &self.wallets.operator,
+            diamond_proxy_addr,
+            default_priority_fee_per_gas,
             self.l1_chain_id,
             query_client.clone(),
-        );
-        context.insert_resource(BoundEthInterfaceResource(Box::new(signing_client)))?;
+        )
+        .await
+        .context("failed creating signing client for operator")?;
+        context.insert_resource(BoundEthInterfaceResource(signing_client))?;
 
         if let Some(blob_operator) = &self.wallets.blob_operator {
-            let private_key = blob_operator.private_key();
-            let signing_client_for_blobs = PKSigningClient::new_raw(
-                private_key.clone(),
-                self.contracts_config.diamond_proxy_addr,
-                gas_adjuster_config.default_priority_fee_per_gas,
+            let signing_client_for_blobs = signing_client_for_wallet(
+                blob_operator,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
+                self.l1_chain_id,
+                query_client.clone(),
+            )
+            .await
+            .context("failed creating signing client for blob operator")?;
+            context.insert_resource(BoundEthInterfaceForBlobsResource(signing_client_for_blobs))?;
+        }
+
+        if let Some(successor_operator) = &self.wallets.successor_operator {
+            let signing_client = signing_client_for_wallet(
+                successor_operator,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
+                self.l1_chain_id,
+                query_client.clone(),
+            )
+            .await
+            .context("failed creating signing client for successor operator")?;
+            context.insert_resource(BoundEthInterfaceForSuccessorResource(signing_client))?;
+        }
+        if let Some(successor_blob_operator) = &self.wallets.successor_blob_operator {
+            let signing_client = signing_client_for_wallet(
+                successor_blob_operator,
+                diamond_proxy_addr,
+                default_priority_fee_per_gas,
                 self.l1_chain_id,
                 query_client,
-            );
-            context.insert_resource(BoundEthInterfaceForBlobsResource(Box::new(
-                signing_client_for_blobs,
-            )))?;
+            )
+            .await
+            .context("failed creating signing client for successor blob operator")?;
+            context.insert_resource(BoundEthInterfaceForSuccessorBlobsResource(signing_client))?;
+        }
+
+        let gateway_client = match context.get_resource::<GatewayEthInterfaceResource>().await {
+            Ok(GatewayEthInterfaceResource(client)) => Some(client),
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        if let Some(gateway_client) = gateway_client {
+            let gateway_chain_id = gateway_client
+                .fetch_chain_id()
+                .await
+                .context("failed fetching gateway chain ID")?;
+            // The diamond proxy address on the gateway is only known after the settlement layer switch,
+            // so the eth_sender specifies contract addresses for each transaction explicitly.
+            let signing_client = signing_client_for_wallet(
+                &self.wallets.operator,
+                Address::zero(),
+                default_priority_fee_per_gas,
+                gateway_chain_id,
+                gateway_client,
+            )
+            .await
+            .context("failed creating signing client for operator on gateway")?;
+            context.insert_resource(BoundEthInterfaceForGatewayResource(signing_client))?;
         }
 
         Ok(())
@@ -88,6 +88,12 @@ impl WiringLayer for PoolsLayer {
             if let Some(threshold) = self.config.long_connection_threshold() {
                 ConnectionPool::<Core>::global_config().set_long_connection_threshold(threshold)?;
             }
+            if let Some(report) = self.config.report_all_query_latencies {
+                ConnectionPool::<Core>::global_config().set_report_all_latencies(report);
+            }
+            if let Some(redact) = self.config.redact_query_args {
+                ConnectionPool::<Core>::global_config().set_redact_query_args(redact);
+            }
         }
 
         if self.with_master {
@@ -105,12 +111,17 @@ impl WiringLayer for PoolsLayer {
         if self.with_replica {
             // We're most interested in setting acquire / statement timeouts for the API server, which puts the most load
             // on Postgres.
-            context.insert_resource(PoolResource::<ReplicaPool>::new(
+            let pool = PoolResource::<ReplicaPool>::new(
                 self.secrets.replica_url()?,
                 self.config.max_connections()?,
                 self.config.statement_timeout(),
                 self.config.acquire_timeout(),
-            ))?;
+            )
+            .with_read_replicas(
+                self.secrets.server_read_replica_urls.clone(),
+                self.config.max_replica_lag(),
+            );
+            context.insert_resource(pool)?;
         }
 
         if self.with_prover {
@@ -0,0 +1,79 @@
+use zksync_config::configs::PruningConfig;
+use zksync_node_db_pruner::{DbPruner, DbPrunerConfig};
+
+use crate::{
+    implementations::resources::{
+        healthcheck::AppHealthCheckResource,
+        pools::{MasterPool, PoolResource},
+    },
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the Postgres pruner on the main node.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Adds `db_pruner` to the `AppHealthCheckResource`.
+/// - Adds `db_pruner` task to the node.
+#[derive(Debug)]
+pub struct PruningLayer {
+    config: PruningConfig,
+}
+
+impl PruningLayer {
+    pub fn new(config: PruningConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for PruningLayer {
+    fn layer_name(&self) -> &'static str {
+        "pruning_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let main_pool = pool_resource.get().await?;
+
+        let minimum_l1_batch_age = self.config.data_retention();
+        tracing::info!(
+            "Configured pruning of batches after they become {minimum_l1_batch_age:?} old"
+        );
+        let db_pruner = DbPruner::for_main_node(
+            DbPrunerConfig {
+                removal_delay: self.config.removal_delay(),
+                pruned_batch_chunk_size: self.config.chunk_size,
+                minimum_l1_batch_age,
+            },
+            main_pool,
+        );
+
+        let AppHealthCheckResource(app_health) = context.get_resource_or_default().await;
+        app_health
+            .insert_component(db_pruner.health_check())
+            .map_err(WiringError::internal)?;
+
+        context.add_task(Box::new(DbPrunerTask { db_pruner }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct DbPrunerTask {
+    db_pruner: DbPruner,
+}
+
+#[async_trait::async_trait]
+impl Task for DbPrunerTask {
+    fn id(&self) -> TaskId {
+        "db_pruner".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.db_pruner.run(stop_receiver.0).await
+    }
+}
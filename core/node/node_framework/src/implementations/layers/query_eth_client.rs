@@ -1,10 +1,13 @@
 use anyhow::Context;
 use zksync_types::{url::SensitiveUrl, L1ChainId};
-use zksync_web3_decl::client::Client;
+use zksync_web3_decl::client::{Client, DynClient, FailoverClient, FailoverProbe, L1};
 
 use crate::{
-    implementations::resources::eth_interface::EthInterfaceResource,
-    service::ServiceContext,
+    implementations::resources::eth_interface::{
+        EthInterfaceResource, GatewayEthInterfaceResource, PrivateRelayEthInterfaceResource,
+    },
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
     wiring_layer::{WiringError, WiringLayer},
 };
 
@@ -12,11 +15,39 @@ use crate::{
 pub struct QueryEthClientLayer {
     chain_id: L1ChainId,
     web3_url: SensitiveUrl,
+    private_relay_url: Option<SensitiveUrl>,
+    gateway_url: Option<SensitiveUrl>,
+    fallback_urls: Vec<SensitiveUrl>,
 }
 
 impl QueryEthClientLayer {
     pub fn new(chain_id: L1ChainId, web3_url: SensitiveUrl) -> Self {
-        Self { chain_id, web3_url }
+        Self {
+            chain_id,
+            web3_url,
+            private_relay_url: None,
+            gateway_url: None,
+            fallback_urls: Vec::new(),
+        }
+    }
+
+    /// Additionally provides a client for the private relay RPC at the specified URL.
+    pub fn with_private_relay_url(mut self, url: SensitiveUrl) -> Self {
+        self.private_relay_url = Some(url);
+        self
+    }
+
+    /// Additionally provides a client for the gateway chain RPC at the specified URL.
+    pub fn with_gateway_url(mut self, url: SensitiveUrl) -> Self {
+        self.gateway_url = Some(url);
+        self
+    }
+
+    /// Specifies fallback L1 RPC providers. If non-empty, the provided client fails over to these providers
+    /// if the main one is unavailable, and a task checking provider health is added.
+    pub fn with_fallback_urls(mut self, urls: Vec<SensitiveUrl>) -> Self {
+        self.fallback_urls = urls;
+        self
     }
 }
 
@@ -31,7 +62,56 @@ impl WiringLayer for QueryEthClientLayer {
             .context("Client::new()")?
             .for_network(self.chain_id.into())
             .build();
-        context.insert_resource(EthInterfaceResource(Box::new(query_client)))?;
+        if self.fallback_urls.is_empty() {
+            context.insert_resource(EthInterfaceResource(Box::new(query_client)))?;
+        } else {
+            let mut providers: Vec<Box<DynClient<L1>>> = vec![Box::new(query_client)];
+            for (i, url) in self.fallback_urls.into_iter().enumerate() {
+                let client = Client::http(url)
+                    .with_context(|| format!("Client::new() for fallback provider #{}", i + 1))?
+                    .for_network(self.chain_id.into())
+                    .build();
+                providers.push(Box::new(client));
+            }
+            let failover_client = FailoverClient::new(providers);
+            let probe = failover_client
+                .probe()
+                .with_expected_chain_id(self.chain_id.0);
+            context.insert_resource(EthInterfaceResource(Box::new(failover_client)))?;
+            context.add_task(Box::new(L1FailoverProbeTask(probe)));
+        }
+
+        if let Some(private_relay_url) = self.private_relay_url {
+            let private_relay_client = Client::http(private_relay_url)
+                .context("Client::new() for private relay")?
+                .for_network(self.chain_id.into())
+                .build();
+            context.insert_resource(PrivateRelayEthInterfaceResource(Box::new(
+                private_relay_client,
+            )))?;
+        }
+
+        if let Some(gateway_url) = self.gateway_url {
+            // The chain ID of the gateway isn't known in advance; it's fetched when creating signing clients.
+            let gateway_client = Client::http(gateway_url)
+                .context("Client::new() for gateway")?
+                .build();
+            context.insert_resource(GatewayEthInterfaceResource(Box::new(gateway_client)))?;
+        }
         Ok(())
     }
 }
+
+#[derive(Debug)]
+struct L1FailoverProbeTask(FailoverProbe<L1>);
+
+#[async_trait::async_trait]
+impl Task for L1FailoverProbeTask {
+    fn id(&self) -> TaskId {
+        "l1_failover_probe".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.0.run(stop_receiver.0).await
+    }
+}
@@ -1,8 +1,11 @@
 use zksync_config::configs::chain::StateKeeperConfig;
-use zksync_state_keeper::MainBatchExecutor;
+use zksync_state_keeper::{
+    ExecutionResultCache, MainBatchExecutor, MempoolPreExecutor, MempoolStoragePrefetcher,
+    ReadSetCache,
+};
 
 use crate::{
-    implementations::resources::state_keeper::BatchExecutorResource,
+    implementations::resources::state_keeper::{BatchExecutorResource, MempoolGuardResource},
     resource::Unique,
     service::ServiceContext,
     wiring_layer::{WiringError, WiringLayer},
@@ -28,7 +31,46 @@ impl WiringLayer for MainBatchExecutorLayer {
     }
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
-        let builder = MainBatchExecutor::new(self.state_keeper_config.save_call_traces, false);
+        if self.state_keeper_config.enable_execution_result_cache
+            && !self.state_keeper_config.enable_mempool_pre_execution
+        {
+            tracing::warn!(
+                "Execution result cache is enabled without mempool pre-execution and will not be used"
+            );
+        }
+        let mut builder = MainBatchExecutor::new(self.state_keeper_config.save_call_traces, false);
+        if self.state_keeper_config.enable_opcode_profiling {
+            builder = builder.with_opcode_profiling();
+        }
+        if self.state_keeper_config.enable_gas_attribution {
+            builder = builder.with_gas_attribution();
+        }
+        if let Some(timeout) = self.state_keeper_config.max_tx_execution_time() {
+            builder = builder.with_tx_execution_timeout(timeout);
+        }
+        if self.state_keeper_config.enable_mempool_pre_execution
+            || self.state_keeper_config.enable_storage_prefetching
+        {
+            // The mempool is provided by `MempoolIOLayer`, so these options are only supported on the main node.
+            let mempool = context.get_resource::<MempoolGuardResource>().await?.0;
+            // The pre-executor and the prefetcher must not share a cache.
+            if self.state_keeper_config.enable_mempool_pre_execution {
+                let cache = ReadSetCache::new(ReadSetCache::DEFAULT_CAPACITY);
+                let mut pre_executor = MempoolPreExecutor::new(mempool.clone(), cache);
+                if self.state_keeper_config.enable_execution_result_cache {
+                    let result_cache =
+                        ExecutionResultCache::new(ExecutionResultCache::DEFAULT_CAPACITY);
+                    pre_executor = pre_executor.with_result_cache(result_cache.clone());
+                    builder = builder.with_result_cache(result_cache);
+                }
+                builder = builder.with_pre_executor(pre_executor);
+            }
+            if self.state_keeper_config.enable_storage_prefetching {
+                let cache = ReadSetCache::new(ReadSetCache::DEFAULT_CAPACITY);
+                builder =
+                    builder.with_storage_prefetcher(MempoolStoragePrefetcher::new(mempool, cache));
+            }
+        }
 
         context.insert_resource(BatchExecutorResource(Unique::new(Box::new(builder))))?;
         Ok(())
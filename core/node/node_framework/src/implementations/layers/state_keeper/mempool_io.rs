@@ -4,21 +4,27 @@ use anyhow::Context as _;
 use zksync_config::{
     configs::{
         chain::{MempoolConfig, StateKeeperConfig},
+        da_client::DAClient,
         wallets,
     },
     ContractsConfig,
 };
 use zksync_state_keeper::{
-    io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess, MempoolFetcher, MempoolGuard,
-    MempoolIO, OutputHandler, SequencerSealer, StateKeeperPersistence, TreeWritesPersistence,
+    io::seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
+    seal_criteria::{DaBlobSizeCriterion, SealCriterion},
+    MempoolFetcher, MempoolGuard, MempoolIO, OutputHandler, SequencerSealer,
+    StateKeeperPersistence, TreeWritesPersistence,
 };
-use zksync_types::L2ChainId;
+use zksync_types::{pubdata_da::PubdataEncoding, L2ChainId};
 
 use crate::{
     implementations::resources::{
         fee_input::FeeInputResource,
         pools::{MasterPool, PoolResource},
-        state_keeper::{ConditionalSealerResource, OutputHandlerResource, StateKeeperIOResource},
+        state_keeper::{
+            AddressDenylistResource, ConditionalSealerResource, FeeAccountHandleResource,
+            MempoolGuardResource, OutputHandlerResource, StateKeeperIOResource,
+        },
     },
     resource::Unique,
     service::{ServiceContext, StopReceiver},
@@ -33,6 +39,9 @@ pub struct MempoolIOLayer {
     state_keeper_config: StateKeeperConfig,
     mempool_config: MempoolConfig,
     wallets: wallets::StateKeeper,
+    custom_seal_criteria: Vec<Box<dyn SealCriterion>>,
+    insert_block_notifications: bool,
+    pubdata_encoding: PubdataEncoding,
 }
 
 impl MempoolIOLayer {
@@ -49,9 +58,39 @@ impl MempoolIOLayer {
             state_keeper_config,
             mempool_config,
             wallets,
+            custom_seal_criteria: Vec::new(),
+            insert_block_notifications: false,
+            pubdata_encoding: PubdataEncoding::Rollup,
         }
     }
 
+    /// Registers a custom seal criterion that will be checked by the sequencer sealer in addition to the default ones.
+    pub fn with_seal_criterion(mut self, criterion: Box<dyn SealCriterion>) -> Self {
+        self.custom_seal_criteria.push(criterion);
+        self
+    }
+
+    /// Seals L1 batches so that their pubdata fits into a single blob of the configured DA layer.
+    pub fn with_da_client_config(mut self, config: &DAClient) -> Self {
+        if let Some(blob_size_limit) = zksync_da_clients::blob_size_limit(config) {
+            self.custom_seal_criteria
+                .push(Box::new(DaBlobSizeCriterion::new(blob_size_limit)));
+        }
+        self
+    }
+
+    /// Enables inserting notifications about sealed L2 blocks and L1 batches to the block notifications outbox.
+    pub fn with_block_notifications(mut self) -> Self {
+        self.insert_block_notifications = true;
+        self
+    }
+
+    /// Sets the encoding of pubdata persisted for sealed L1 batches.
+    pub fn with_pubdata_encoding(mut self, encoding: PubdataEncoding) -> Self {
+        self.pubdata_encoding = encoding;
+        self
+    }
+
     async fn build_mempool_guard(
         &self,
         master_pool: &PoolResource<MasterPool>,
@@ -64,7 +103,7 @@ impl MempoolIOLayer {
             .connection()
             .await
             .context("Access storage to build mempool")?;
-        let mempool = MempoolGuard::from_storage(&mut storage, self.mempool_config.capacity).await;
+        let mempool = MempoolGuard::from_storage(&mut storage, &self.mempool_config).await;
         mempool.register_metrics();
         Ok(mempool)
     }
@@ -80,6 +119,11 @@ impl WiringLayer for MempoolIOLayer {
         // Fetch required resources.
         let batch_fee_input_provider = context.get_resource::<FeeInputResource>().await?.0;
         let master_pool = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let address_denylist = match context.get_resource::<AddressDenylistResource>().await {
+            Ok(denylist) => Some(denylist.0),
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(other) => return Err(other),
+        };
 
         // Create L2 block sealer task and output handler.
         // L2 Block sealing process is parallelized, so we have to provide enough pooled connections.
@@ -87,11 +131,15 @@ impl WiringLayer for MempoolIOLayer {
             .get_custom(L2BlockSealProcess::subtasks_len())
             .await
             .context("Get master pool")?;
-        let (persistence, l2_block_sealer) = StateKeeperPersistence::new(
+        let (mut persistence, l2_block_sealer) = StateKeeperPersistence::new(
             persistence_pool.clone(),
             self.contracts_config.l2_shared_bridge_addr.unwrap(),
             self.state_keeper_config.l2_block_seal_queue_capacity,
         );
+        persistence = persistence.with_pubdata_encoding(self.pubdata_encoding);
+        if self.insert_block_notifications {
+            persistence = persistence.with_block_notifications();
+        }
         let tree_writes_persistence = TreeWritesPersistence::new(persistence_pool);
         let output_handler = OutputHandler::new(Box::new(persistence))
             .with_handler(Box::new(tree_writes_persistence));
@@ -111,13 +159,14 @@ impl WiringLayer for MempoolIOLayer {
             mempool_fetcher_pool,
         );
         context.add_task(Box::new(MempoolFetcherTask(mempool_fetcher)));
+        context.insert_resource(MempoolGuardResource(mempool_guard.clone()))?;
 
         // Create mempool IO resource.
         let mempool_db_pool = master_pool
             .get_singleton()
             .await
             .context("Get master pool")?;
-        let io = MempoolIO::new(
+        let mut io = MempoolIO::new(
             mempool_guard,
             batch_fee_input_provider,
             mempool_db_pool,
@@ -127,10 +176,17 @@ impl WiringLayer for MempoolIOLayer {
             self.zksync_network_id,
         )
         .await?;
+        if let Some(address_denylist) = address_denylist {
+            io = io.with_address_denylist(address_denylist);
+        }
+        context.insert_resource(FeeAccountHandleResource(io.fee_account_handle()))?;
         context.insert_resource(StateKeeperIOResource(Unique::new(Box::new(io))))?;
 
         // Create sealer.
-        let sealer = SequencerSealer::new(self.state_keeper_config);
+        let mut sealer = SequencerSealer::new(self.state_keeper_config);
+        for criterion in self.custom_seal_criteria {
+            sealer = sealer.with_criterion(criterion);
+        }
         context.insert_resource(ConditionalSealerResource(Arc::new(sealer)))?;
 
         Ok(())
@@ -4,8 +4,8 @@ use anyhow::Context;
 use zksync_config::DBConfig;
 use zksync_state::{AsyncCatchupTask, ReadStorageFactory, RocksdbStorageOptions};
 use zksync_state_keeper::{
-    seal_criteria::ConditionalSealer, AsyncRocksdbCache, BatchExecutor, OutputHandler,
-    StateKeeperIO, ZkSyncStateKeeper,
+    seal_criteria::ConditionalSealer, AsyncRocksdbCache, BatchExecutor, L1BatchAbortHandle,
+    L1BatchAbortReceiver, OutputHandler, StateKeeperIO, ZkSyncStateKeeper,
 };
 use zksync_storage::RocksDB;
 
@@ -16,8 +16,8 @@ use crate::{
     implementations::resources::{
         pools::{MasterPool, PoolResource},
         state_keeper::{
-            BatchExecutorResource, ConditionalSealerResource, OutputHandlerResource,
-            StateKeeperIOResource,
+            BatchExecutorResource, ConditionalSealerResource, L1BatchAbortHandleResource,
+            OutputHandlerResource, StateKeeperIOResource,
         },
     },
     service::{ServiceContext, StopReceiver},
@@ -30,14 +30,28 @@ use crate::{
 /// - `BatchExecutorResource`
 /// - `ConditionalSealerResource`
 ///
+/// Adds resources:
+/// - `L1BatchAbortHandleResource` (if L1 batch aborts are enabled)
+///
 #[derive(Debug)]
 pub struct StateKeeperLayer {
     db_config: DBConfig,
+    l1_batch_aborts_enabled: bool,
 }
 
 impl StateKeeperLayer {
     pub fn new(db_config: DBConfig) -> Self {
-        Self { db_config }
+        Self {
+            db_config,
+            l1_batch_aborts_enabled: false,
+        }
+    }
+
+    /// Allows aborting the pending L1 batch via `L1BatchAbortHandleResource`.
+    /// The state keeper I/O must support this operation (i.e., this should only be enabled on the main node).
+    pub fn with_l1_batch_aborts(mut self) -> Self {
+        self.l1_batch_aborts_enabled = true;
+        self
     }
 }
 
@@ -75,6 +89,10 @@ impl WiringLayer for StateKeeperLayer {
                 .experimental
                 .state_keeper_db_block_cache_capacity(),
             max_open_files: self.db_config.experimental.state_keeper_db_max_open_files,
+            compaction_interval: self
+                .db_config
+                .experimental
+                .state_keeper_db_compaction_interval(),
         };
         let (storage_factory, task) = AsyncRocksdbCache::new(
             master_pool.get_custom(2).await?,
@@ -83,12 +101,21 @@ impl WiringLayer for StateKeeperLayer {
         );
         context.add_task(Box::new(RocksdbCatchupTask(task)));
 
+        let abort_receiver = if self.l1_batch_aborts_enabled {
+            let (handle, receiver) = L1BatchAbortHandle::new();
+            context.insert_resource(L1BatchAbortHandleResource(handle))?;
+            Some(receiver)
+        } else {
+            None
+        };
+
         context.add_task(Box::new(StateKeeperTask {
             io,
             batch_executor_base,
             output_handler,
             sealer,
             storage_factory: Arc::new(storage_factory),
+            abort_receiver,
         }));
         Ok(())
     }
@@ -101,6 +128,7 @@ struct StateKeeperTask {
     output_handler: OutputHandler,
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
+    abort_receiver: Option<L1BatchAbortReceiver>,
 }
 
 #[async_trait::async_trait]
@@ -110,7 +138,7 @@ impl Task for StateKeeperTask {
     }
 
     async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
-        let state_keeper = ZkSyncStateKeeper::new(
+        let mut state_keeper = ZkSyncStateKeeper::new(
             stop_receiver.0,
             self.io,
             self.batch_executor_base,
@@ -118,6 +146,9 @@ impl Task for StateKeeperTask {
             self.sealer,
             self.storage_factory,
         );
+        if let Some(receiver) = self.abort_receiver {
+            state_keeper = state_keeper.with_l1_batch_abort_receiver(receiver);
+        }
         let result = state_keeper.run().await;
 
         // Wait for all the instances of RocksDB to be destroyed.
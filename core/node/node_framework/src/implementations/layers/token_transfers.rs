@@ -0,0 +1,50 @@
+use zksync_node_token_transfers::{TokenTransfersIndexer, TokenTransfersIndexerConfig};
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+/// Wiring layer for the indexer of ERC-20 token transfers and balances used by the `zks_getTokenBalances`
+/// and `zks_getTokenTransfers` API methods.
+///
+/// ## Effects
+///
+/// - Resolves `PoolResource<MasterPool>`.
+/// - Adds `token_transfers_indexer` task to the node.
+#[derive(Debug)]
+pub struct TokenTransfersLayer;
+
+#[async_trait::async_trait]
+impl WiringLayer for TokenTransfersLayer {
+    fn layer_name(&self) -> &'static str {
+        "token_transfers_layer"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let pool_resource = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let main_pool = pool_resource.get().await?;
+
+        let indexer = TokenTransfersIndexer::new(main_pool, TokenTransfersIndexerConfig::default());
+        context.add_task(Box::new(TokenTransfersIndexerTask { indexer }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct TokenTransfersIndexerTask {
+    indexer: TokenTransfersIndexer,
+}
+
+#[async_trait::async_trait]
+impl Task for TokenTransfersIndexerTask {
+    fn id(&self) -> TaskId {
+        "token_transfers_indexer".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.indexer.run(stop_receiver.0).await
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
 };
 
 pub mod protective_reads;
+pub mod shadow_execution;
 
 #[async_trait::async_trait]
 impl<Io: VmRunnerIo> Task for StorageSyncTask<Io> {
@@ -0,0 +1,81 @@
+use anyhow::Context as _;
+use zksync_config::configs::vm_runner::ShadowExecutionConfig;
+use zksync_types::{L2ChainId, ProtocolVersionId};
+use zksync_vm_runner::ShadowExecutor;
+
+use crate::{
+    implementations::resources::pools::{MasterPool, PoolResource},
+    service::{ServiceContext, StopReceiver},
+    task::{Task, TaskId},
+    wiring_layer::{WiringError, WiringLayer},
+};
+
+#[derive(Debug)]
+pub struct ShadowExecutorLayer {
+    shadow_execution_config: ShadowExecutionConfig,
+    zksync_network_id: L2ChainId,
+}
+
+impl ShadowExecutorLayer {
+    pub fn new(
+        shadow_execution_config: ShadowExecutionConfig,
+        zksync_network_id: L2ChainId,
+    ) -> Self {
+        Self {
+            shadow_execution_config,
+            zksync_network_id,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl WiringLayer for ShadowExecutorLayer {
+    fn layer_name(&self) -> &'static str {
+        "vm_runner_shadow_execution"
+    }
+
+    async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
+        let master_pool = context.get_resource::<PoolResource<MasterPool>>().await?;
+        let protocol_version = match self.shadow_execution_config.protocol_version {
+            Some(version) => ProtocolVersionId::try_from(version).with_context(|| {
+                format!("invalid candidate protocol version for shadow execution: {version}")
+            })?,
+            None => ProtocolVersionId::latest(),
+        };
+
+        let (shadow_executor, tasks) = ShadowExecutor::new(
+            // Same as for the protective reads writer: one connection for `StorageSyncTask`, one for
+            // `ConcurrentOutputHandlerFactoryTask`/`VmRunner`, and `window_size` connections for output handlers.
+            master_pool
+                .get_custom(self.shadow_execution_config.window_size + 2)
+                .await?,
+            self.shadow_execution_config.db_path,
+            self.zksync_network_id,
+            self.shadow_execution_config.first_processed_batch,
+            self.shadow_execution_config.window_size,
+            protocol_version,
+        )
+        .await?;
+
+        context.add_task(Box::new(tasks.loader_task));
+        context.add_task(Box::new(tasks.output_handler_factory_task));
+        context.add_task(Box::new(ShadowExecutorTask { shadow_executor }));
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct ShadowExecutorTask {
+    shadow_executor: ShadowExecutor,
+}
+
+#[async_trait::async_trait]
+impl Task for ShadowExecutorTask {
+    fn id(&self) -> TaskId {
+        "vm_runner/shadow_execution".into()
+    }
+
+    async fn run(self: Box<Self>, stop_receiver: StopReceiver) -> anyhow::Result<()> {
+        self.shadow_executor.run(&stop_receiver.0).await
+    }
+}
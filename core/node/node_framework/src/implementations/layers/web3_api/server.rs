@@ -1,15 +1,18 @@
 use std::{num::NonZeroU32, time::Duration};
 
+use secrecy::Secret;
 use tokio::{sync::oneshot, task::JoinHandle};
 use zksync_circuit_breaker::replication_lag::ReplicationLagChecker;
-use zksync_config::configs::api::MaxResponseSize;
+use zksync_config::configs::api::{ClientRateLimits, MaxResponseSize, MethodFilter};
 use zksync_node_api_server::web3::{state::InternalApiConfig, ApiBuilder, ApiServer, Namespace};
 
 use crate::{
     implementations::resources::{
-        circuit_breakers::CircuitBreakersResource,
+        circuit_breakers::{CircuitBreakersResource, LoadSheddingResource},
+        eth_sender::EthSenderPauseHandleResource,
         healthcheck::AppHealthCheckResource,
-        pools::{PoolResource, ReplicaPool},
+        pools::{MasterPool, PoolResource, ReplicaPool},
+        state_keeper::{FeeAccountHandleResource, L1BatchAbortHandleResource},
         sync_state::SyncStateResource,
         web3_api::{MempoolCacheResource, TreeApiClientResource, TxSenderResource},
     },
@@ -27,6 +30,9 @@ pub struct Web3ServerOptionalConfig {
     pub batch_request_size_limit: Option<usize>,
     pub response_body_size_limit: Option<MaxResponseSize>,
     pub websocket_requests_per_minute_limit: Option<NonZeroU32>,
+    pub client_rate_limits: Option<ClientRateLimits>,
+    pub method_filter: Option<MethodFilter>,
+    pub http_response_compression: bool,
     // used by circuit breaker.
     pub replication_lag_limit: Option<Duration>,
 }
@@ -53,6 +59,13 @@ impl Web3ServerOptionalConfig {
             api_builder = api_builder
                 .with_websocket_requests_per_minute_limit(websocket_requests_per_minute_limit);
         }
+        if let Some(client_rate_limits) = self.client_rate_limits {
+            api_builder = api_builder.with_client_rate_limits(client_rate_limits);
+        }
+        if let Some(method_filter) = self.method_filter {
+            api_builder = api_builder.with_method_filter(method_filter);
+        }
+        api_builder = api_builder.with_http_response_compression(self.http_response_compression);
         api_builder
     }
 }
@@ -62,6 +75,7 @@ impl Web3ServerOptionalConfig {
 enum Transport {
     Http,
     Ws,
+    AdminHttp,
 }
 
 #[derive(Debug)]
@@ -70,6 +84,7 @@ pub struct Web3ServerLayer {
     port: u16,
     internal_api_config: InternalApiConfig,
     optional_config: Web3ServerOptionalConfig,
+    admin_token: Option<Secret<String>>,
 }
 
 impl Web3ServerLayer {
@@ -83,6 +98,7 @@ impl Web3ServerLayer {
             port,
             internal_api_config,
             optional_config,
+            admin_token: None,
         }
     }
 
@@ -96,6 +112,23 @@ impl Web3ServerLayer {
             port,
             internal_api_config,
             optional_config,
+            admin_token: None,
+        }
+    }
+
+    /// Creates a layer for the private server exposing the `admin` namespace on localhost. The server uses handles
+    /// provided by the state keeper and eth_sender layers (if they are present) and requires `admin_token` as a bearer token.
+    pub fn admin_http(
+        port: u16,
+        internal_api_config: InternalApiConfig,
+        admin_token: Secret<String>,
+    ) -> Self {
+        Self {
+            transport: Transport::AdminHttp,
+            port,
+            internal_api_config,
+            optional_config: Web3ServerOptionalConfig::default(),
+            admin_token: Some(admin_token),
         }
     }
 }
@@ -106,6 +139,7 @@ impl WiringLayer for Web3ServerLayer {
         match self.transport {
             Transport::Http => "web3_http_server_layer",
             Transport::Ws => "web3_ws_server_layer",
+            Transport::AdminHttp => "web3_admin_http_server_layer",
         }
     }
 
@@ -125,7 +159,38 @@ impl WiringLayer for Web3ServerLayer {
             Err(WiringError::ResourceLacking { .. }) => None,
             Err(err) => return Err(err),
         };
+        // Admin handles are only ever provided to the private admin server.
+        let is_admin = matches!(self.transport, Transport::AdminHttp);
+        let l1_batch_abort_handle = match context.get_resource::<L1BatchAbortHandleResource>().await
+        {
+            Ok(handle) if is_admin => Some(handle.0),
+            Ok(_) | Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let fee_account_handle = match context.get_resource::<FeeAccountHandleResource>().await {
+            Ok(handle) if is_admin => Some(handle.0),
+            Ok(_) | Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
+        let eth_sender_pause_handle =
+            match context.get_resource::<EthSenderPauseHandleResource>().await {
+                Ok(handle) if is_admin => Some(handle.0),
+                Ok(_) | Err(WiringError::ResourceLacking { .. }) => None,
+                Err(err) => return Err(err),
+            };
+        let load_shedding = match context.get_resource::<LoadSheddingResource>().await {
+            Ok(signal) => Some(signal.0),
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(err) => return Err(err),
+        };
         let MempoolCacheResource(mempool_cache) = context.get_resource().await?;
+        // Persistent filters are written to, so they cannot be stored via the replica pool.
+        let filters_pool = if self.internal_api_config.persistent_filters {
+            let master_pool = context.get_resource::<PoolResource<MasterPool>>().await?;
+            Some(master_pool.get_custom(2).await?)
+        } else {
+            None
+        };
 
         // Build server.
         let mut api_builder =
@@ -141,12 +206,37 @@ impl WiringLayer for Web3ServerLayer {
                 api_builder = api_builder.http(self.port);
             }
             Transport::Ws => {
-                api_builder = api_builder.ws(self.port);
+                // Notifications about new L2 blocks are only sent by the main database.
+                let master_pool = context.get_resource::<PoolResource<MasterPool>>().await?;
+                api_builder = api_builder
+                    .ws(self.port)
+                    .with_l2_block_listener_pool(master_pool.get_singleton().await?);
+            }
+            Transport::AdminHttp => {
+                let admin_token = self.admin_token.ok_or_else(|| {
+                    WiringError::Configuration("admin API server requires a token".into())
+                })?;
+                api_builder = api_builder.admin_http(self.port, admin_token);
             }
         }
         if let Some(sync_state) = sync_state {
             api_builder = api_builder.with_sync_state(sync_state);
         }
+        if let Some(handle) = l1_batch_abort_handle {
+            api_builder = api_builder.with_l1_batch_abort_handle(handle);
+        }
+        if let Some(handle) = fee_account_handle {
+            api_builder = api_builder.with_fee_account_handle(handle);
+        }
+        if let Some(handle) = eth_sender_pause_handle {
+            api_builder = api_builder.with_eth_sender_pause_handle(handle);
+        }
+        if let Some(signal) = load_shedding {
+            api_builder = api_builder.with_load_shedding(signal);
+        }
+        if let Some(pool) = filters_pool {
+            api_builder = api_builder.with_filters_pool(pool);
+        }
         let replication_lag_limit = self.optional_config.replication_lag_limit;
         api_builder = self.optional_config.apply(api_builder);
         let server = api_builder.build()?;
@@ -210,6 +300,7 @@ impl Task for Web3ApiTask {
         match self.transport {
             Transport::Http => "web3_http_server".into(),
             Transport::Ws => "web3_ws_server".into(),
+            Transport::AdminHttp => "web3_admin_http_server".into(),
         }
     }
 
@@ -10,7 +10,7 @@ use crate::{
     implementations::resources::{
         fee_input::FeeInputResource,
         pools::{PoolResource, ReplicaPool},
-        state_keeper::ConditionalSealerResource,
+        state_keeper::{AddressDenylistResource, ConditionalSealerResource},
         web3_api::{TxSenderResource, TxSinkResource},
     },
     service::{ServiceContext, StopReceiver},
@@ -65,6 +65,11 @@ impl WiringLayer for TxSenderLayer {
             Err(WiringError::ResourceLacking { .. }) => None,
             Err(other) => return Err(other),
         };
+        let address_denylist = match context.get_resource::<AddressDenylistResource>().await {
+            Ok(denylist) => Some(denylist.0),
+            Err(WiringError::ResourceLacking { .. }) => None,
+            Err(other) => return Err(other),
+        };
         let fee_input = context.get_resource::<FeeInputResource>().await?.0;
 
         // Initialize Postgres caches.
@@ -96,6 +101,9 @@ impl WiringLayer for TxSenderLayer {
         if let Some(sealer) = sealer {
             tx_sender = tx_sender.with_sealer(sealer);
         }
+        if let Some(address_denylist) = address_denylist {
+            tx_sender = tx_sender.with_address_denylist(address_denylist);
+        }
         let tx_sender = tx_sender
             .build(
                 fee_input,
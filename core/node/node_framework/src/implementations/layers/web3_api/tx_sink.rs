@@ -15,7 +15,12 @@ use crate::{
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum TxSinkLayer {
-    MasterPoolSink,
+    MasterPoolSink {
+        /// Minimum fee bump (in percent) for replacing pending transactions.
+        replacement_fee_bump_percent: Option<u32>,
+        /// Maximum number of pending transactions per initiator account.
+        max_pending_txs_per_account: Option<u32>,
+    },
     ProxySink,
 }
 
@@ -27,13 +32,19 @@ impl WiringLayer for TxSinkLayer {
 
     async fn wire(self: Box<Self>, mut context: ServiceContext<'_>) -> Result<(), WiringError> {
         let tx_sink = match self.as_ref() {
-            TxSinkLayer::MasterPoolSink => {
+            TxSinkLayer::MasterPoolSink {
+                replacement_fee_bump_percent,
+                max_pending_txs_per_account,
+            } => {
                 let pool = context
                     .get_resource::<PoolResource<MasterPool>>()
                     .await?
                     .get()
                     .await?;
-                TxSinkResource(Arc::new(MasterPoolSink::new(pool)))
+                let sink = MasterPoolSink::new(pool)
+                    .with_replacement_fee_bump(*replacement_fee_bump_percent)
+                    .with_max_pending_txs_per_account(*max_pending_txs_per_account);
+                TxSinkResource(Arc::new(sink))
             }
             TxSinkLayer::ProxySink => {
                 let MainNodeClientResource(client) = context.get_resource().await?;
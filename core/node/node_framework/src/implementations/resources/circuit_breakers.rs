@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use tokio::sync::watch;
 use zksync_circuit_breaker::CircuitBreakers;
 
 use crate::resource::Resource;
@@ -14,3 +15,13 @@ impl Resource for CircuitBreakersResource {
         "common/circuit_breakers".into()
     }
 }
+
+/// A signal set while API read requests should be shed because the replica DB pool used by API servers is saturated.
+#[derive(Debug, Clone)]
+pub struct LoadSheddingResource(pub watch::Receiver<bool>);
+
+impl Resource for LoadSheddingResource {
+    fn name() -> String {
+        "common/load_shedding".into()
+    }
+}
@@ -12,6 +12,26 @@ impl Resource for EthInterfaceResource {
     }
 }
 
+/// Client for a private relay RPC used to submit L1 transactions bypassing the public mempool.
+#[derive(Debug, Clone)]
+pub struct PrivateRelayEthInterfaceResource(pub Box<DynClient<L1>>);
+
+impl Resource for PrivateRelayEthInterfaceResource {
+    fn name() -> String {
+        "common/private_relay_eth_interface".into()
+    }
+}
+
+/// Client for the gateway chain that the L1 batches may be settled on instead of L1.
+#[derive(Debug, Clone)]
+pub struct GatewayEthInterfaceResource(pub Box<DynClient<L1>>);
+
+impl Resource for GatewayEthInterfaceResource {
+    fn name() -> String {
+        "common/gateway_eth_interface".into()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BoundEthInterfaceResource(pub Box<dyn BoundEthInterface>);
 
@@ -29,3 +49,31 @@ impl Resource for BoundEthInterfaceForBlobsResource {
         "common/bound_eth_interface_for_blobs".into()
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForSuccessorResource(pub Box<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceForSuccessorResource {
+    fn name() -> String {
+        "common/bound_eth_interface_for_successor".into()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForSuccessorBlobsResource(pub Box<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceForSuccessorBlobsResource {
+    fn name() -> String {
+        "common/bound_eth_interface_for_successor_blobs".into()
+    }
+}
+
+/// Signing client for the gateway chain bound to the main operator account.
+#[derive(Debug, Clone)]
+pub struct BoundEthInterfaceForGatewayResource(pub Box<dyn BoundEthInterface>);
+
+impl Resource for BoundEthInterfaceForGatewayResource {
+    fn name() -> String {
+        "common/bound_eth_interface_for_gateway".into()
+    }
+}
@@ -0,0 +1,13 @@
+use zksync_eth_sender::EthSenderPauseHandle;
+
+use crate::resource::Resource;
+
+/// Handle allowing to pause and resume creating L1 transactions in the eth_sender.
+#[derive(Debug, Clone)]
+pub struct EthSenderPauseHandleResource(pub EthSenderPauseHandle);
+
+impl Resource for EthSenderPauseHandleResource {
+    fn name() -> String {
+        "eth_sender/pause_handle".into()
+    }
+}
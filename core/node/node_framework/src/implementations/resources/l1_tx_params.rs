@@ -13,3 +13,13 @@ impl Resource for L1TxParamsResource {
         "common/l1_tx_params".into()
     }
 }
+
+/// Wrapper for the tx params provider for the gateway chain that L1 batches may be settled on instead of L1.
+#[derive(Debug, Clone)]
+pub struct GatewayL1TxParamsResource(pub Arc<dyn L1TxParamsProvider>);
+
+impl Resource for GatewayL1TxParamsResource {
+    fn name() -> String {
+        "common/gateway_l1_tx_params".into()
+    }
+}
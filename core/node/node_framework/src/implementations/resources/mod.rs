@@ -1,6 +1,8 @@
 pub mod action_queue;
 pub mod circuit_breakers;
+pub mod da_client;
 pub mod eth_interface;
+pub mod eth_sender;
 pub mod fee_input;
 pub mod healthcheck;
 pub mod l1_tx_params;
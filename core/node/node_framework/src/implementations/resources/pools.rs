@@ -19,6 +19,8 @@ use crate::resource::Resource;
 pub struct PoolResource<P: PoolKind> {
     connections_count: Arc<AtomicU32>,
     url: SensitiveUrl,
+    replica_urls: Vec<SensitiveUrl>,
+    max_replica_lag: Option<Duration>,
     max_connections: u32,
     statement_timeout: Option<Duration>,
     acquire_timeout: Option<Duration>,
@@ -42,6 +44,8 @@ impl<P: PoolKind> PoolResource<P> {
         Self {
             connections_count: Arc::new(AtomicU32::new(0)),
             url,
+            replica_urls: vec![],
+            max_replica_lag: None,
             max_connections,
             statement_timeout,
             acquire_timeout,
@@ -50,8 +54,22 @@ impl<P: PoolKind> PoolResource<P> {
         }
     }
 
+    /// Specifies read replicas for read-only connections of pools created from this resource. The main URL is used
+    /// as a fallback if all replicas are unhealthy or lag by more than `max_lag`.
+    pub fn with_read_replicas(
+        mut self,
+        replica_urls: Vec<SensitiveUrl>,
+        max_lag: Option<Duration>,
+    ) -> Self {
+        self.replica_urls = replica_urls;
+        self.max_replica_lag = max_lag;
+        self
+    }
+
     fn builder(&self) -> ConnectionPoolBuilder<P::DbMarker> {
         let mut builder = ConnectionPool::builder(self.url.clone(), self.max_connections);
+        builder.set_replica_urls(self.replica_urls.clone());
+        builder.set_max_replica_lag(self.max_replica_lag);
         builder.set_statement_timeout(self.statement_timeout);
         builder.set_acquire_timeout(self.acquire_timeout);
         builder
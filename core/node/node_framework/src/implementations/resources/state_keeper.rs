@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
 use zksync_state_keeper::{
-    seal_criteria::ConditionalSealer, BatchExecutor, OutputHandler, StateKeeperIO,
+    seal_criteria::ConditionalSealer, AddressDenylist, BatchExecutor, FeeAccountHandle,
+    L1BatchAbortHandle, MempoolGuard, OutputHandler, StateKeeperIO,
 };
 
 use crate::resource::{Resource, Unique};
@@ -24,6 +25,16 @@ impl Resource for BatchExecutorResource {
     }
 }
 
+/// Mempool of the main node state keeper.
+#[derive(Debug, Clone)]
+pub struct MempoolGuardResource(pub MempoolGuard);
+
+impl Resource for MempoolGuardResource {
+    fn name() -> String {
+        "state_keeper/mempool_guard".into()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OutputHandlerResource(pub Unique<OutputHandler>);
 
@@ -41,3 +52,32 @@ impl Resource for ConditionalSealerResource {
         "state_keeper/conditional_sealer".into()
     }
 }
+
+/// Address denylist shared by the state keeper and the API server.
+#[derive(Debug, Clone)]
+pub struct AddressDenylistResource(pub AddressDenylist);
+
+impl Resource for AddressDenylistResource {
+    fn name() -> String {
+        "state_keeper/address_denylist".into()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct L1BatchAbortHandleResource(pub L1BatchAbortHandle);
+
+impl Resource for L1BatchAbortHandleResource {
+    fn name() -> String {
+        "state_keeper/l1_batch_abort_handle".into()
+    }
+}
+
+/// Handle allowing to switch the operator fee account used by the state keeper.
+#[derive(Debug, Clone)]
+pub struct FeeAccountHandleResource(pub FeeAccountHandle);
+
+impl Resource for FeeAccountHandleResource {
+    fn name() -> String {
+        "state_keeper/fee_account_handle".into()
+    }
+}
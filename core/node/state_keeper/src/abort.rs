@@ -0,0 +1,118 @@
+//! Aborting the L1 batch currently processed by the state keeper.
+
+use anyhow::Context as _;
+use tokio::sync::{mpsc, oneshot};
+use zksync_types::{api::AbortedL1Batch, L1BatchNumber};
+
+/// Errors that can occur when aborting an L1 batch.
+#[derive(Debug, thiserror::Error)]
+pub enum L1BatchAbortError {
+    /// The batch has sealed L2 blocks, which may have already been served to API clients
+    /// or synced by external nodes, so it cannot be aborted.
+    #[error("L1 batch #{0} has sealed L2 blocks and cannot be aborted")]
+    HasSealedL2Blocks(L1BatchNumber),
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+/// Request to abort the currently open L1 batch together with the return address for the outcome.
+#[derive(Debug)]
+pub(crate) struct AbortRequest(oneshot::Sender<Result<AbortedL1Batch, L1BatchAbortError>>);
+
+impl AbortRequest {
+    pub(crate) fn respond(self, outcome: Result<AbortedL1Batch, L1BatchAbortError>) {
+        // The requester may have gone away in the meantime; this is fine.
+        self.0.send(outcome).ok();
+    }
+}
+
+/// Handle allowing to abort the L1 batch currently processed by the state keeper.
+///
+/// Only the unsealed part of a batch can be aborted: the request is rejected if the batch has at least one sealed
+/// L2 block. Aborting a batch returns transactions executed in its open L2 block back to the mempool. After that,
+/// the state keeper is re-initialized and starts a new batch with fresh parameters (e.g., the fee input). If the state keeper
+/// is waiting for a new batch to be opened, the request is processed once the batch is opened.
+#[derive(Debug, Clone)]
+pub struct L1BatchAbortHandle(mpsc::Sender<AbortRequest>);
+
+impl L1BatchAbortHandle {
+    /// Creates a handle together with the receiver that should be plugged into the state keeper
+    /// using [`ZkSyncStateKeeper::with_l1_batch_abort_receiver()`](crate::ZkSyncStateKeeper::with_l1_batch_abort_receiver()).
+    pub fn new() -> (Self, L1BatchAbortReceiver) {
+        let (sender, receiver) = mpsc::channel(1);
+        (Self(sender), L1BatchAbortReceiver(receiver))
+    }
+
+    /// Aborts the currently open L1 batch and waits until the state keeper has processed the request.
+    pub async fn abort_l1_batch(&self) -> Result<AbortedL1Batch, L1BatchAbortError> {
+        let (response_sender, response_receiver) = oneshot::channel();
+        self.0
+            .send(AbortRequest(response_sender))
+            .await
+            .ok()
+            .context("state keeper is not running")?;
+        response_receiver
+            .await
+            .context("state keeper has stopped before aborting the L1 batch")?
+    }
+}
+
+/// Receiving part of [`L1BatchAbortHandle`].
+#[derive(Debug)]
+pub struct L1BatchAbortReceiver(mpsc::Receiver<AbortRequest>);
+
+impl L1BatchAbortReceiver {
+    pub(crate) fn try_recv(&mut self) -> Option<AbortRequest> {
+        self.0.try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    async fn next_request(receiver: &mut L1BatchAbortReceiver) -> AbortRequest {
+        loop {
+            if let Some(request) = receiver.try_recv() {
+                break request;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn processing_abort_request() {
+        let (handle, mut receiver) = L1BatchAbortHandle::new();
+        assert!(receiver.try_recv().is_none());
+
+        let abort_task = tokio::spawn(async move { handle.abort_l1_batch().await });
+        let request = next_request(&mut receiver).await;
+        let aborted_batch = AbortedL1Batch {
+            l1_batch_number: L1BatchNumber(3),
+            transaction_count: 5,
+        };
+        request.respond(Ok(aborted_batch));
+        assert_eq!(abort_task.await.unwrap().unwrap(), aborted_batch);
+    }
+
+    #[tokio::test]
+    async fn rejecting_abort_request() {
+        let (handle, mut receiver) = L1BatchAbortHandle::new();
+        let abort_task = tokio::spawn(async move { handle.abort_l1_batch().await });
+        let request = next_request(&mut receiver).await;
+        request.respond(Err(L1BatchAbortError::HasSealedL2Blocks(L1BatchNumber(3))));
+
+        let err = abort_task.await.unwrap().unwrap_err();
+        assert_matches!(err, L1BatchAbortError::HasSealedL2Blocks(L1BatchNumber(3)));
+    }
+
+    #[tokio::test]
+    async fn aborting_batch_without_state_keeper() {
+        let (handle, receiver) = L1BatchAbortHandle::new();
+        drop(receiver);
+        let err = handle.abort_l1_batch().await.unwrap_err();
+        assert!(err.to_string().contains("not running"), "{err}");
+    }
+}
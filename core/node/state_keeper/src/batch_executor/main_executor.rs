@@ -1,15 +1,15 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use async_trait::async_trait;
 use multivm::{
     interface::{
-        ExecutionResult, FinishedL1Batch, Halt, L1BatchEnv, L2BlockEnv, SystemEnv,
-        VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled,
+        ContractGasUsage, ExecutionResult, FinishedL1Batch, Halt, L1BatchEnv, L2BlockEnv,
+        SystemEnv, VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled,
     },
-    tracers::CallTracer,
+    tracers::{CallTracer, ExecutionDeadline},
     vm_latest::HistoryEnabled,
-    MultiVMTracer, VmInstance,
+    MultiVMTracer, MultiVmTracerPointer, VmInstance,
 };
 use once_cell::sync::OnceCell;
 use tokio::{
@@ -18,12 +18,19 @@ use tokio::{
 };
 use zksync_shared_metrics::{InteractionType, TxStage, APP_METRICS};
 use zksync_state::{ReadStorage, ReadStorageFactory, StorageView, WriteStorage};
-use zksync_types::{vm_trace::Call, Transaction};
+use zksync_types::{vm_trace::Call, L1BatchNumber, Transaction};
 use zksync_utils::bytecode::CompressedBytecodeInfo;
 
-use super::{BatchExecutor, BatchExecutorHandle, Command, TxExecutionResult};
+use super::{BatchExecutor, BatchExecutorHandle, Command, ExecutionResultCache, TxExecutionResult};
 use crate::{
-    metrics::{TxExecutionStage, BATCH_TIP_METRICS, EXECUTOR_METRICS, KEEPER_METRICS},
+    io::{
+        read_set_cache::PrefetchedStorage, MempoolPreExecutor, MempoolStoragePrefetcher,
+        ReadSetCache,
+    },
+    metrics::{
+        TxExecutionStage, BATCH_TIP_METRICS, EXECUTOR_METRICS, KEEPER_METRICS,
+        PRE_EXECUTION_METRICS, STORAGE_PREFETCH_METRICS,
+    },
     types::ExecutionMetricsForCriteria,
 };
 
@@ -33,6 +40,13 @@ use crate::{
 pub struct MainBatchExecutor {
     save_call_traces: bool,
     optional_bytecode_compression: bool,
+    opcode_profiling: bool,
+    gas_attribution: bool,
+    storage_reads_log: bool,
+    tx_execution_timeout: Option<Duration>,
+    pre_executor: Option<MempoolPreExecutor>,
+    storage_prefetcher: Option<MempoolStoragePrefetcher>,
+    result_cache: Option<ExecutionResultCache>,
 }
 
 impl MainBatchExecutor {
@@ -40,8 +54,65 @@ impl MainBatchExecutor {
         Self {
             save_call_traces,
             optional_bytecode_compression,
+            opcode_profiling: false,
+            gas_attribution: false,
+            storage_reads_log: false,
+            tx_execution_timeout: None,
+            pre_executor: None,
+            storage_prefetcher: None,
+            result_cache: None,
         }
     }
+
+    /// Enables collecting per-opcode execution statistics for executed transactions. Statistics are reported
+    /// as metrics. Only supported by the latest VM version; ignored for batches executed with older VMs.
+    pub fn with_opcode_profiling(mut self) -> Self {
+        self.opcode_profiling = true;
+        self
+    }
+
+    /// Enables attributing gas consumed by executed transactions to contracts. Contracts that consumed the most gas
+    /// in a batch are logged once the batch is finished. Like opcode profiling, only supported by the latest VM version.
+    pub fn with_gas_attribution(mut self) -> Self {
+        self.gas_attribution = true;
+        self
+    }
+
+    /// Enables collecting the ordered log of storage reads performed by executed transactions; the log is available
+    /// via `L2BlockUpdates::storage_reads`. Only supported by the latest VM version.
+    pub fn with_storage_reads_log(mut self) -> Self {
+        self.storage_reads_log = true;
+        self
+    }
+
+    /// Sets the maximum wall-clock time the VM may spend executing a single L2 transaction. Transactions exceeding
+    /// this time are aborted and rejected; L1 transactions are never aborted. Not enforced by VM versions
+    /// before `vm_refunds_enhancement`.
+    pub fn with_tx_execution_timeout(mut self, timeout: Duration) -> Self {
+        self.tx_execution_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables speculative pre-execution of mempool transactions. Storage read sets obtained by the pre-executor
+    /// are used to warm up the storage of the batch executor.
+    pub fn with_pre_executor(mut self, pre_executor: MempoolPreExecutor) -> Self {
+        self.pre_executor = Some(pre_executor);
+        self
+    }
+
+    /// Enables prefetching storage slots likely touched by mempool transactions. Prefetching is performed
+    /// in parallel with executing transactions, so that the batch executor doesn't need to wait for storage I/O.
+    pub fn with_storage_prefetcher(mut self, prefetcher: MempoolStoragePrefetcher) -> Self {
+        self.storage_prefetcher = Some(prefetcher);
+        self
+    }
+
+    /// Enables reusing cached execution results for transactions rejected by the VM when executed
+    /// as the first transaction in a batch. See [`ExecutionResultCache`] for details.
+    pub fn with_result_cache(mut self, cache: ExecutionResultCache) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
 }
 
 #[async_trait]
@@ -58,10 +129,100 @@ impl BatchExecutor for MainBatchExecutor {
         let (commands_sender, commands_receiver) = mpsc::channel(1);
         let executor = CommandReceiver {
             save_call_traces: self.save_call_traces,
+            vm_call_tracing: false,
             optional_bytecode_compression: self.optional_bytecode_compression,
+            tx_execution_timeout: self.tx_execution_timeout,
+            read_set_cache: self
+                .pre_executor
+                .as_ref()
+                .map(|pre_executor| pre_executor.cache().clone()),
+            prefetch_cache: self
+                .storage_prefetcher
+                .as_ref()
+                .map(|prefetcher| prefetcher.cache().clone()),
+            result_cache: self.result_cache.clone(),
             commands: commands_receiver,
         };
 
+        if let Some(pre_executor) = self.pre_executor.clone() {
+            let storage_factory = storage_factory.clone();
+            let l1_batch_params = l1_batch_params.clone();
+            let system_env = system_env.clone();
+            let stop_receiver = stop_receiver.clone();
+            // The pre-executor task is detached; it terminates on its own once the batch is finished.
+            tokio::task::spawn_blocking(move || {
+                let storage = Handle::current()
+                    .block_on(
+                        storage_factory.access_storage(&stop_receiver, l1_batch_params.number - 1),
+                    )
+                    .context("failed accessing pre-executor storage");
+                match storage {
+                    Ok(Some(storage)) => pre_executor.run_for_batch(
+                        storage,
+                        l1_batch_params,
+                        system_env,
+                        &stop_receiver,
+                    ),
+                    Ok(None) => {
+                        tracing::info!("Interrupted while trying to access pre-executor storage");
+                    }
+                    Err(err) => {
+                        tracing::warn!("Mempool transactions won't be pre-executed: {err:#}");
+                    }
+                }
+            });
+        }
+
+        if let Some(prefetcher) = self.storage_prefetcher.clone() {
+            let storage_factory = storage_factory.clone();
+            let l1_batch_params = l1_batch_params.clone();
+            let system_env = system_env.clone();
+            let stop_receiver = stop_receiver.clone();
+            // Like the pre-executor, the prefetcher task is detached and terminates once the batch is finished.
+            tokio::task::spawn_blocking(move || {
+                let storage = Handle::current()
+                    .block_on(
+                        storage_factory.access_storage(&stop_receiver, l1_batch_params.number - 1),
+                    )
+                    .context("failed accessing storage prefetcher storage");
+                match storage {
+                    Ok(Some(storage)) => prefetcher.run_for_batch(
+                        storage,
+                        &l1_batch_params,
+                        &system_env,
+                        &stop_receiver,
+                    ),
+                    Ok(None) => {
+                        tracing::info!(
+                            "Interrupted while trying to access storage prefetcher storage"
+                        );
+                    }
+                    Err(err) => {
+                        tracing::warn!("Storage won't be prefetched: {err:#}");
+                    }
+                }
+            });
+        }
+
+        // Instrumentation is only enabled for the main executor; the pre-executor and the prefetcher
+        // don't use execution outputs beyond storage reads.
+        let mut system_env = system_env;
+        system_env.enable_opcode_profiling = self.opcode_profiling;
+        // If supported by the VM, call traces are collected by the VM itself, which is more efficient
+        // than using an external call tracer.
+        system_env.enable_call_tracing =
+            self.save_call_traces && system_env.supports_instrumentation();
+        system_env.enable_gas_attribution = self.gas_attribution;
+        system_env.enable_storage_reads_log = self.storage_reads_log;
+        if (self.opcode_profiling || self.gas_attribution) && !system_env.supports_instrumentation()
+        {
+            tracing::warn!(
+                "Execution instrumentation is not supported for protocol version {:?} used in L1 batch #{}",
+                system_env.version,
+                l1_batch_params.number
+            );
+        }
+
         let stop_receiver = stop_receiver.clone();
         let handle = tokio::task::spawn_blocking(move || {
             if let Some(storage) = Handle::current()
@@ -80,6 +241,9 @@ impl BatchExecutor for MainBatchExecutor {
     }
 }
 
+/// Number of contracts logged as top gas consumers in a batch if gas attribution is enabled.
+const TOP_GAS_CONSUMERS_COUNT: usize = 10;
+
 /// Implementation of the "primary" (non-test) batch executor.
 /// Upon launch, it initializes the VM object with provided block context and properties, and keeps invoking the commands
 /// sent to it one by one until the batch is finished.
@@ -89,7 +253,14 @@ impl BatchExecutor for MainBatchExecutor {
 #[derive(Debug)]
 struct CommandReceiver {
     save_call_traces: bool,
+    /// Whether call traces are collected by the VM (as opposed to an external call tracer). Set based on the `SystemEnv`
+    /// the batch is executed with.
+    vm_call_tracing: bool,
     optional_bytecode_compression: bool,
+    tx_execution_timeout: Option<Duration>,
+    read_set_cache: Option<ReadSetCache>,
+    prefetch_cache: Option<ReadSetCache>,
+    result_cache: Option<ExecutionResultCache>,
     commands: mpsc::Receiver<Command>,
 }
 
@@ -102,32 +273,101 @@ impl CommandReceiver {
     ) {
         tracing::info!("Starting executing L1 batch #{}", &l1_batch_params.number);
 
+        // Read sets cached by the pre-executor are obtained against the state of the previous batch,
+        // i.e., the same state `secondary_storage` is based on.
+        let state_version = l1_batch_params.number - 1;
+        self.vm_call_tracing = system_env.enable_call_tracing;
+        let secondary_storage = PrefetchedStorage::new(secondary_storage);
+        let prefetched_values = secondary_storage.prefetched_values();
         let storage_view = StorageView::new(secondary_storage).to_rc_ptr();
 
+        let l1_batch_number = l1_batch_params.number;
+        let gas_attribution =
+            system_env.enable_gas_attribution && system_env.supports_instrumentation();
         let mut vm = VmInstance::new(l1_batch_params, system_env, storage_view.clone());
+        // Gas usage for each executed transaction; rolled back transactions are removed.
+        let mut gas_usage_by_tx = gas_attribution.then(Vec::new);
+        // Cached execution results correspond to executing transactions as the first transaction in the batch,
+        // so they can only be reused while the VM is in its initial state.
+        let mut pending_tx_count = 0_usize;
+        let mut started_l2_block = false;
 
         while let Some(cmd) = self.commands.blocking_recv() {
             match cmd {
                 Command::ExecuteTx(tx, resp) => {
-                    let result = self.execute_tx(&tx, &mut vm);
+                    let cached_rejection = if pending_tx_count == 0 && !started_l2_block {
+                        self.cached_rejection(&tx, state_version)
+                    } else {
+                        None
+                    };
+                    if let Some(cache) = &self.read_set_cache {
+                        if let Some(read_set) = cache.take(tx.hash(), state_version) {
+                            PRE_EXECUTION_METRICS.cache_hits.inc();
+                            prefetched_values.borrow_mut().extend(read_set);
+                        } else {
+                            PRE_EXECUTION_METRICS.cache_misses.inc();
+                        }
+                    }
+                    if let Some(cache) = &self.prefetch_cache {
+                        if let Some(read_set) = cache.take(tx.hash(), state_version) {
+                            STORAGE_PREFETCH_METRICS.cache_hits.inc();
+                            // Values are read from the same state as pre-executor values, so it's fine to overwrite them.
+                            prefetched_values.borrow_mut().extend(read_set);
+                        } else {
+                            STORAGE_PREFETCH_METRICS.cache_misses.inc();
+                        }
+                    }
+                    let result = match cached_rejection {
+                        Some(reason) => self.reject_tx(reason, &mut vm),
+                        None => self.execute_tx(&tx, &mut vm),
+                    };
+                    pending_tx_count += 1;
+                    if let Some(gas_usage_by_tx) = &mut gas_usage_by_tx {
+                        let usage = match &result {
+                            TxExecutionResult::Success { tx_result, .. } => {
+                                tx_result.gas_by_contract.clone().unwrap_or_default()
+                            }
+                            _ => ContractGasUsage::default(),
+                        };
+                        gas_usage_by_tx.push(usage);
+                    }
                     if resp.send(result).is_err() {
                         break;
                     }
                 }
                 Command::RollbackLastTx(resp) => {
                     self.rollback_last_tx(&mut vm);
+                    pending_tx_count = pending_tx_count.saturating_sub(1);
+                    if let Some(gas_usage_by_tx) = &mut gas_usage_by_tx {
+                        gas_usage_by_tx.pop();
+                    }
                     if resp.send(()).is_err() {
                         break;
                     }
                 }
                 Command::StartNextL2Block(l2_block_env, resp) => {
                     self.start_next_l2_block(l2_block_env, &mut vm);
+                    started_l2_block = true;
                     if resp.send(()).is_err() {
                         break;
                     }
                 }
                 Command::FinishBatch(resp) => {
                     let vm_block_result = self.finish_batch(&mut vm);
+                    if let Some(gas_usage_by_tx) = &gas_usage_by_tx {
+                        Self::report_gas_usage(
+                            l1_batch_number,
+                            gas_usage_by_tx,
+                            &vm_block_result.block_tip_execution_result,
+                        );
+                    }
+                    // Stops the pre-executor and the prefetcher for this batch and discards their read sets.
+                    for cache in [&self.read_set_cache, &self.prefetch_cache]
+                        .into_iter()
+                        .flatten()
+                    {
+                        cache.advance_state_version(state_version + 1);
+                    }
                     if resp.send(vm_block_result).is_err() {
                         break;
                     }
@@ -155,14 +395,21 @@ impl CommandReceiver {
         // Save pre-`execute_next_tx` VM snapshot.
         vm.make_snapshot();
 
-        // Execute the transaction.
+        // Execute the transaction. L1 transactions cannot be rejected, so they are executed without a deadline.
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::Execution].start();
-        let (tx_result, compressed_bytecodes, call_tracer_result) =
+        let deadline = self
+            .tx_execution_timeout
+            .filter(|_| !tx.is_l1())
+            .map(ExecutionDeadline::new);
+        let (mut tx_result, compressed_bytecodes, mut call_tracer_result) =
             if self.optional_bytecode_compression {
-                self.execute_tx_in_vm_with_optional_compression(tx, vm)
+                self.execute_tx_in_vm_with_optional_compression(tx, deadline.as_ref(), vm)
             } else {
-                self.execute_tx_in_vm(tx, vm)
+                self.execute_tx_in_vm(tx, deadline.as_ref(), vm)
             };
+        if let Some(call_traces) = tx_result.call_traces.take() {
+            call_tracer_result = call_traces;
+        }
         latency.observe();
         APP_METRICS.processed_txs[&TxStage::StateKeeper].inc();
         APP_METRICS.processed_l1_txs[&TxStage::StateKeeper].inc_by(tx.is_l1().into());
@@ -174,6 +421,9 @@ impl CommandReceiver {
             };
         }
 
+        if let Some(histogram) = &tx_result.statistics.opcode_histogram {
+            EXECUTOR_METRICS.observe_opcode_histogram(histogram);
+        }
         let tx_metrics = ExecutionMetricsForCriteria::new(Some(tx), &tx_result);
         let gas_remaining = vm.gas_remaining();
 
@@ -186,6 +436,67 @@ impl CommandReceiver {
         }
     }
 
+    fn report_gas_usage(
+        l1_batch_number: L1BatchNumber,
+        gas_usage_by_tx: &[ContractGasUsage],
+        block_tip_result: &VmExecutionResultAndLogs,
+    ) {
+        let mut batch_usage = ContractGasUsage::default();
+        for usage in gas_usage_by_tx
+            .iter()
+            .chain(block_tip_result.gas_by_contract.as_ref())
+        {
+            batch_usage.merge(usage);
+        }
+        let top_consumers = batch_usage.top(TOP_GAS_CONSUMERS_COUNT);
+        tracing::info!(
+            "Contracts that consumed the most gas in L1 batch #{l1_batch_number} (total gas attributed: {}): {top_consumers:?}",
+            batch_usage.total_gas_used()
+        );
+    }
+
+    fn uses_call_tracer(&self) -> bool {
+        self.save_call_traces && !self.vm_call_tracing
+    }
+
+    fn tracers<S: WriteStorage>(
+        &self,
+        call_tracer_result: &Arc<OnceCell<Vec<Call>>>,
+        deadline: Option<&ExecutionDeadline>,
+    ) -> Vec<MultiVmTracerPointer<S, HistoryEnabled>> {
+        let mut tracers = vec![];
+        if self.uses_call_tracer() {
+            tracers.push(CallTracer::new(call_tracer_result.clone()).into_tracer_pointer());
+        }
+        if let Some(deadline) = deadline {
+            tracers.push(deadline.clone().into_tracer_pointer());
+        }
+        tracers
+    }
+
+    fn cached_rejection(&self, tx: &Transaction, state_version: L1BatchNumber) -> Option<Halt> {
+        let cached_result = self.result_cache.as_ref()?.get(tx.hash(), state_version)?;
+        match cached_result.result {
+            ExecutionResult::Halt { reason } => Some(reason),
+            _ => None,
+        }
+    }
+
+    /// Rejects a transaction based on its cached execution result without executing it.
+    fn reject_tx<S: WriteStorage>(
+        &self,
+        reason: Halt,
+        vm: &mut VmInstance<S, HistoryEnabled>,
+    ) -> TxExecutionResult {
+        // The state keeper rolls back rejected transactions, so the snapshot must be created nevertheless.
+        vm.make_snapshot();
+        PRE_EXECUTION_METRICS.result_cache_hits.inc();
+        match reason {
+            Halt::BootloaderOutOfGas => TxExecutionResult::BootloaderOutOfGasForTx,
+            reason => TxExecutionResult::RejectedByVm { reason },
+        }
+    }
+
     fn rollback_last_tx<S: WriteStorage>(&self, vm: &mut VmInstance<S, HistoryEnabled>) {
         let latency = KEEPER_METRICS.tx_execution_time[&TxExecutionStage::TxRollback].start();
         vm.rollback_to_the_latest_snapshot();
@@ -221,6 +532,7 @@ impl CommandReceiver {
     fn execute_tx_in_vm_with_optional_compression<S: WriteStorage>(
         &self,
         tx: &Transaction,
+        deadline: Option<&ExecutionDeadline>,
         vm: &mut VmInstance<S, HistoryEnabled>,
     ) -> (
         VmExecutionResultAndLogs,
@@ -240,11 +552,7 @@ impl CommandReceiver {
         vm.make_snapshot();
 
         let call_tracer_result = Arc::new(OnceCell::default());
-        let tracer = if self.save_call_traces {
-            vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
-        } else {
-            vec![]
-        };
+        let tracer = self.tracers(&call_tracer_result, deadline);
 
         if let (Ok(()), result) =
             vm.inspect_transaction_with_bytecode_compression(tracer.into(), tx.clone(), true)
@@ -261,11 +569,7 @@ impl CommandReceiver {
         vm.rollback_to_the_latest_snapshot();
 
         let call_tracer_result = Arc::new(OnceCell::default());
-        let tracer = if self.save_call_traces {
-            vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
-        } else {
-            vec![]
-        };
+        let tracer = self.tracers(&call_tracer_result, deadline);
 
         let result =
             vm.inspect_transaction_with_bytecode_compression(tracer.into(), tx.clone(), false);
@@ -290,6 +594,7 @@ impl CommandReceiver {
     fn execute_tx_in_vm<S: WriteStorage>(
         &self,
         tx: &Transaction,
+        deadline: Option<&ExecutionDeadline>,
         vm: &mut VmInstance<S, HistoryEnabled>,
     ) -> (
         VmExecutionResultAndLogs,
@@ -297,11 +602,7 @@ impl CommandReceiver {
         Vec<Call>,
     ) {
         let call_tracer_result = Arc::new(OnceCell::default());
-        let tracer = if self.save_call_traces {
-            vec![CallTracer::new(call_tracer_result.clone()).into_tracer_pointer()]
-        } else {
-            vec![]
-        };
+        let tracer = self.tracers(&call_tracer_result, deadline);
 
         let (published_bytecodes, mut result) =
             vm.inspect_transaction_with_bytecode_compression(tracer.into(), tx.clone(), true);
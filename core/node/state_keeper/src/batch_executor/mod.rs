@@ -22,6 +22,9 @@ use crate::{
 mod tests;
 
 pub mod main_executor;
+mod result_cache;
+
+pub use self::result_cache::ExecutionResultCache;
 
 /// Representation of a transaction executed in the virtual machine.
 #[derive(Debug, Clone)]
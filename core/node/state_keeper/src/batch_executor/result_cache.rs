@@ -0,0 +1,147 @@
+//! LRU cache of transaction execution results.
+
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use lru::LruCache;
+use multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
+use zksync_types::{L1BatchNumber, H256};
+
+type CachedResults = LruCache<(H256, L1BatchNumber), VmExecutionResultAndLogs>;
+
+/// LRU cache memoizing execution results for transactions that were already executed against a certain state,
+/// e.g. by [`MempoolPreExecutor`](crate::MempoolPreExecutor). Results are keyed by the transaction hash
+/// and the state version, i.e. the number of the latest sealed L1 batch; the cached result must correspond
+/// to executing the transaction as the first one in the next L1 batch.
+///
+/// The batch executor only reuses results of halted transactions. Such transactions are always rolled back
+/// by the state keeper, so their execution can be skipped entirely; other results must be applied to the VM state
+/// and thus require executing the transaction anyway.
+///
+/// The cache is shared between the components filling it and the batch executor, so cloning it is cheap.
+#[derive(Debug, Clone)]
+pub struct ExecutionResultCache {
+    inner: Arc<Mutex<CachedResults>>,
+}
+
+impl ExecutionResultCache {
+    /// Default capacity of caches used by the state keeper.
+    pub const DEFAULT_CAPACITY: NonZeroUsize = match NonZeroUsize::new(1_024) {
+        Some(capacity) => capacity,
+        None => unreachable!(),
+    };
+
+    /// Creates a cache holding up to `capacity` execution results.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CachedResults> {
+        self.inner
+            .lock()
+            .expect("execution result cache is poisoned")
+    }
+
+    /// Inserts an execution result into the cache. Returns `false` if the result was not inserted because
+    /// it cannot be reused by the batch executor (i.e., the transaction was not halted).
+    pub fn insert(
+        &self,
+        tx_hash: H256,
+        state_version: L1BatchNumber,
+        result: VmExecutionResultAndLogs,
+    ) -> bool {
+        if !matches!(result.result, ExecutionResult::Halt { .. }) {
+            return false;
+        }
+        self.lock().put((tx_hash, state_version), result);
+        true
+    }
+
+    /// Returns a cached execution result for the specified transaction and state version.
+    pub fn get(
+        &self,
+        tx_hash: H256,
+        state_version: L1BatchNumber,
+    ) -> Option<VmExecutionResultAndLogs> {
+        self.lock().get(&(tx_hash, state_version)).cloned()
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use multivm::interface::{Halt, VmRevertReason};
+
+    use super::*;
+
+    fn execution_result(result: ExecutionResult) -> VmExecutionResultAndLogs {
+        VmExecutionResultAndLogs {
+            result,
+            logs: Default::default(),
+            statistics: Default::default(),
+            refunds: Default::default(),
+            call_traces: None,
+            gas_by_contract: None,
+        }
+    }
+
+    #[test]
+    fn only_halted_results_are_cached() {
+        let cache = ExecutionResultCache::new(NonZeroUsize::new(2).unwrap());
+        let tx_hash = H256::repeat_byte(1);
+
+        let success = execution_result(ExecutionResult::Success { output: vec![] });
+        assert!(!cache.insert(tx_hash, L1BatchNumber(1), success));
+        let revert = execution_result(ExecutionResult::Revert {
+            output: VmRevertReason::General {
+                msg: "oops".to_owned(),
+                data: vec![],
+            },
+        });
+        assert!(!cache.insert(tx_hash, L1BatchNumber(1), revert));
+        assert!(cache.is_empty());
+
+        let halt = execution_result(ExecutionResult::Halt {
+            reason: Halt::ValidationOutOfGas,
+        });
+        assert!(cache.insert(tx_hash, L1BatchNumber(1), halt));
+        let cached = cache.get(tx_hash, L1BatchNumber(1)).unwrap();
+        assert_eq!(
+            cached.result,
+            ExecutionResult::Halt {
+                reason: Halt::ValidationOutOfGas
+            }
+        );
+        assert!(cache.get(tx_hash, L1BatchNumber(2)).is_none());
+    }
+
+    #[test]
+    fn least_recently_used_results_are_evicted() {
+        let cache = ExecutionResultCache::new(NonZeroUsize::new(2).unwrap());
+        let halt = execution_result(ExecutionResult::Halt {
+            reason: Halt::ValidationOutOfGas,
+        });
+        for i in 0..2 {
+            cache.insert(H256::repeat_byte(i), L1BatchNumber(1), halt.clone());
+        }
+        // Touch the first entry so that the second one is evicted.
+        cache.get(H256::repeat_byte(0), L1BatchNumber(1)).unwrap();
+        cache.insert(H256::repeat_byte(2), L1BatchNumber(1), halt);
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(H256::repeat_byte(0), L1BatchNumber(1)).is_some());
+        assert!(cache.get(H256::repeat_byte(1), L1BatchNumber(1)).is_none());
+    }
+}
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use assert_matches::assert_matches;
+use multivm::tracers::ExecutionDeadline;
 use test_casing::{test_casing, Product};
 use zksync_dal::{ConnectionPool, Core};
 use zksync_test_account::Account;
@@ -57,6 +60,124 @@ async fn execute_l2_tx(storage_type: StorageType) {
     executor.finish_batch().await.unwrap();
 }
 
+#[tokio::test]
+async fn execute_l2_tx_with_opcode_profiling() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut alice = Account::random();
+    let mut tester = Tester::with_config(
+        connection_pool,
+        TestConfig {
+            opcode_profiling: true,
+            ..TestConfig::new()
+        },
+    );
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let mut executor = tester
+        .create_batch_executor(StorageType::AsyncRocksdbCache)
+        .await;
+
+    let res = executor.execute_tx(alice.execute()).await.unwrap();
+    let TxExecutionResult::Success { tx_result, .. } = res else {
+        panic!("Unexpected execution result: {res:?}");
+    };
+    let histogram = tx_result.statistics.opcode_histogram.unwrap();
+    assert!(histogram.total_count() > 0);
+    executor.finish_batch().await.unwrap();
+}
+
+#[tokio::test]
+async fn execute_l2_tx_with_gas_attribution() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut alice = Account::random();
+    let mut tester = Tester::with_config(
+        connection_pool,
+        TestConfig {
+            gas_attribution: true,
+            ..TestConfig::new()
+        },
+    );
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let mut executor = tester
+        .create_batch_executor(StorageType::AsyncRocksdbCache)
+        .await;
+
+    let res = executor.execute_tx(alice.execute()).await.unwrap();
+    let TxExecutionResult::Success { tx_result, .. } = res else {
+        panic!("Unexpected execution result: {res:?}");
+    };
+    let gas_usage = tx_result.gas_by_contract.unwrap();
+    assert!(gas_usage.total_gas_used() > 0);
+    executor.finish_batch().await.unwrap();
+}
+
+/// Checks that L2 transactions exceeding the execution timeout are aborted, while L1 ones are executed regardless.
+#[tokio::test]
+async fn execute_txs_with_execution_timeout() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut alice = Account::random();
+    let mut tester = Tester::with_config(
+        connection_pool,
+        TestConfig {
+            tx_execution_timeout: Some(Duration::ZERO),
+            ..TestConfig::new()
+        },
+    );
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let mut executor = tester
+        .create_batch_executor(StorageType::AsyncRocksdbCache)
+        .await;
+
+    let res = executor.execute_tx(alice.execute()).await.unwrap();
+    let TxExecutionResult::RejectedByVm { reason } = &res else {
+        panic!("Unexpected execution result: {res:?}");
+    };
+    assert!(ExecutionDeadline::is_exceeded(reason), "{reason:?}");
+    executor.rollback_last_tx().await.unwrap();
+
+    let res = executor
+        .execute_tx(alice.l1_execute(PriorityOpId(1)))
+        .await
+        .unwrap();
+    assert_executed(&res);
+    executor.finish_batch().await.unwrap();
+}
+
+/// Checks that call traces are returned for executed transactions if they are collected by the VM.
+#[tokio::test]
+async fn execute_l2_tx_with_call_traces() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let mut alice = Account::random();
+    let mut tester = Tester::with_config(
+        connection_pool,
+        TestConfig {
+            save_call_traces: true,
+            ..TestConfig::new()
+        },
+    );
+    tester.genesis().await;
+    tester.fund(&[alice.address()]).await;
+    let mut executor = tester
+        .create_batch_executor(StorageType::AsyncRocksdbCache)
+        .await;
+
+    let res = executor.execute_tx(alice.execute()).await.unwrap();
+    let TxExecutionResult::Success {
+        tx_result,
+        call_tracer_result,
+        ..
+    } = res
+    else {
+        panic!("Unexpected execution result: {res:?}");
+    };
+    assert!(!call_tracer_result.is_empty());
+    // Traces must be moved out of the VM result so that they aren't stored twice.
+    assert!(tx_result.call_traces.is_none());
+    executor.finish_batch().await.unwrap();
+}
+
 #[derive(Debug, Clone, Copy)]
 enum SnapshotRecoveryMutation {
     RemoveNonce,
@@ -404,6 +525,9 @@ async fn bootloader_out_of_gas_for_any_tx() {
         connection_pool,
         TestConfig {
             save_call_traces: false,
+            opcode_profiling: false,
+            gas_attribution: false,
+            tx_execution_timeout: None,
             vm_gas_limit: Some(10),
             validation_computational_gas_limit: u32::MAX,
         },
@@ -443,6 +567,8 @@ async fn bootloader_tip_out_of_gas() {
     // but not enough to execute the block tip.
     tester.set_config(TestConfig {
         save_call_traces: false,
+        opcode_profiling: false,
+        gas_attribution: false,
         vm_gas_limit: Some(
             finished_batch
                 .block_tip_execution_result
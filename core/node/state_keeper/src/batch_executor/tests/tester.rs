@@ -1,7 +1,7 @@
 //! Testing harness for the batch executor.
 //! Contains helper functionality to initialize test context and perform tests without too much boilerplate.
 
-use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
 use multivm::{
     interface::{L1BatchEnv, L2BlockEnv, SystemEnv},
@@ -44,6 +44,9 @@ const DEFAULT_GAS_PER_PUBDATA: u32 = 10000;
 #[derive(Debug)]
 pub(super) struct TestConfig {
     pub(super) save_call_traces: bool,
+    pub(super) opcode_profiling: bool,
+    pub(super) gas_attribution: bool,
+    pub(super) tx_execution_timeout: Option<Duration>,
     pub(super) vm_gas_limit: Option<u32>,
     pub(super) validation_computational_gas_limit: u32,
 }
@@ -55,6 +58,9 @@ impl TestConfig {
         Self {
             vm_gas_limit: None,
             save_call_traces: false,
+            opcode_profiling: false,
+            gas_attribution: false,
+            tx_execution_timeout: None,
             validation_computational_gas_limit: config.validation_computational_gas_limit,
         }
     }
@@ -146,6 +152,15 @@ impl Tester {
         system_env: SystemEnv,
     ) -> BatchExecutorHandle {
         let mut batch_executor = MainBatchExecutor::new(self.config.save_call_traces, false);
+        if self.config.opcode_profiling {
+            batch_executor = batch_executor.with_opcode_profiling();
+        }
+        if self.config.gas_attribution {
+            batch_executor = batch_executor.with_gas_attribution();
+        }
+        if let Some(timeout) = self.config.tx_execution_timeout {
+            batch_executor = batch_executor.with_tx_execution_timeout(timeout);
+        }
         let (_stop_sender, stop_receiver) = watch::channel(false);
         batch_executor
             .init_batch(storage_factory, l1_batch_env, system_env, &stop_receiver)
@@ -0,0 +1,215 @@
+//! Denylist of addresses that cannot send or receive L2 transactions.
+
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use zksync_types::{Address, ExecuteTransactionCommon, Transaction};
+
+use crate::metrics::KEEPER_METRICS;
+
+/// Denylist of addresses checked for L2 transactions both on submission (by the API server) and on inclusion
+/// into a block (by the state keeper). A transaction is denied if either its initiator or its target address
+/// is in the list. L1 transactions are never denied since they must be processed in order to keep the priority queue
+/// consistent.
+///
+/// The list is shared among all its clones, so it can be updated in place by [`AddressDenylistReloader`].
+#[derive(Debug, Clone, Default)]
+pub struct AddressDenylist(Arc<RwLock<HashSet<Address>>>);
+
+impl AddressDenylist {
+    pub fn new(addresses: HashSet<Address>) -> Self {
+        Self(Arc::new(RwLock::new(addresses)))
+    }
+
+    /// Loads the denylist from a file. The file must contain one hex-encoded address per line;
+    /// empty lines and lines starting with `#` are ignored.
+    pub fn from_file(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let contents = std::fs::read_to_string(&path).with_context(|| {
+            format!("failed reading address denylist from `{}`", path.display())
+        })?;
+        Ok(Self::new(parse_denylist(&contents)?))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.read().expect("address denylist is poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0
+            .read()
+            .expect("address denylist is poisoned")
+            .contains(address)
+    }
+
+    /// Returns the first denylisted address among the provided ones, if any.
+    pub fn first_denied(&self, addresses: impl IntoIterator<Item = Address>) -> Option<Address> {
+        let denylist = self.0.read().expect("address denylist is poisoned");
+        addresses
+            .into_iter()
+            .find(|address| denylist.contains(address))
+    }
+
+    /// Returns a denylisted address that the transaction is sent from or to, if any.
+    pub fn denied_address(&self, transaction: &Transaction) -> Option<Address> {
+        if !matches!(transaction.common_data, ExecuteTransactionCommon::L2(_)) {
+            return None;
+        }
+        self.first_denied([
+            transaction.initiator_account(),
+            transaction.execute.contract_address,
+        ])
+    }
+
+    fn replace(&self, addresses: HashSet<Address>) {
+        *self.0.write().expect("address denylist is poisoned") = addresses;
+    }
+}
+
+fn parse_denylist(contents: &str) -> anyhow::Result<HashSet<Address>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| {
+            line.parse::<Address>()
+                .with_context(|| format!("invalid address on line {}: {line}", i + 1))
+        })
+        .collect()
+}
+
+/// Task periodically reloading an [`AddressDenylist`] from a file. If the file cannot be read or parsed,
+/// the error is logged and the previously loaded list is retained.
+#[derive(Debug)]
+pub struct AddressDenylistReloader {
+    denylist: AddressDenylist,
+    path: PathBuf,
+    reload_interval: Duration,
+}
+
+impl AddressDenylistReloader {
+    pub fn new(
+        denylist: AddressDenylist,
+        path: impl Into<PathBuf>,
+        reload_interval: Duration,
+    ) -> Self {
+        Self {
+            denylist,
+            path: path.into(),
+            reload_interval,
+        }
+    }
+
+    async fn reload(&self) -> anyhow::Result<()> {
+        let path = self.path.clone();
+        let contents = tokio::task::spawn_blocking(move || std::fs::read_to_string(path))
+            .await
+            .context("panicked reading address denylist")?
+            .with_context(|| {
+                format!(
+                    "failed reading address denylist from `{}`",
+                    self.path.display()
+                )
+            })?;
+        let addresses = parse_denylist(&contents)?;
+        if addresses.len() != self.denylist.len() {
+            tracing::info!(
+                "Reloaded address denylist from `{}`: {} addresses",
+                self.path.display(),
+                addresses.len()
+            );
+        }
+        KEEPER_METRICS.denylisted_addresses.set(addresses.len());
+        self.denylist.replace(addresses);
+        Ok(())
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, address denylist reloader is shutting down");
+                break;
+            }
+            if let Err(err) = self.reload().await {
+                tracing::error!("Failed reloading address denylist: {err:#}");
+            }
+            tokio::time::timeout(self.reload_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l2_transaction;
+
+    use super::*;
+
+    #[test]
+    fn parsing_denylist() {
+        let contents = "# Denied addresses\n\
+            0x0000000000000000000000000000000000000001\n\
+            \n   0x0000000000000000000000000000000000000002  \n";
+        let addresses = parse_denylist(contents).unwrap();
+        assert_eq!(
+            addresses,
+            HashSet::from([Address::from_low_u64_be(1), Address::from_low_u64_be(2)])
+        );
+
+        let err = parse_denylist("0x0000000000000000000000000000000000000001\nnot an address")
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("line 2"), "{err:#}");
+    }
+
+    #[test]
+    fn checking_transactions() {
+        let tx: Transaction = create_l2_transaction(10, 100).into();
+        let denylist = AddressDenylist::default();
+        assert_eq!(denylist.denied_address(&tx), None);
+
+        denylist.replace(HashSet::from([tx.initiator_account()]));
+        assert_eq!(denylist.denied_address(&tx), Some(tx.initiator_account()));
+
+        let target = tx.execute.contract_address;
+        denylist.replace(HashSet::from([target]));
+        assert_eq!(denylist.denied_address(&tx), Some(target));
+
+        denylist.replace(HashSet::from([Address::repeat_byte(0xff)]));
+        assert_eq!(denylist.denied_address(&tx), None);
+    }
+
+    #[tokio::test]
+    async fn reloading_denylist() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("denylist.txt");
+        std::fs::write(&path, format!("{:?}\n", Address::repeat_byte(1))).unwrap();
+
+        let denylist = AddressDenylist::from_file(&path).unwrap();
+        assert!(denylist.contains(&Address::repeat_byte(1)));
+        let reloader =
+            AddressDenylistReloader::new(denylist.clone(), &path, Duration::from_millis(10));
+
+        std::fs::write(&path, format!("{:?}\n", Address::repeat_byte(2))).unwrap();
+        reloader.reload().await.unwrap();
+        assert!(!denylist.contains(&Address::repeat_byte(1)));
+        assert!(denylist.contains(&Address::repeat_byte(2)));
+
+        // Invalid contents must not affect the loaded list.
+        std::fs::write(&path, "garbage").unwrap();
+        reloader.reload().await.unwrap_err();
+        assert_eq!(denylist.len(), 1);
+    }
+}
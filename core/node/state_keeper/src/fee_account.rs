@@ -0,0 +1,51 @@
+//! Operator fee account that can be switched at runtime.
+
+use std::sync::{Arc, RwLock};
+
+use zksync_types::Address;
+
+/// Handle to the operator fee account used by the state keeper as the fee / commit reward recipient
+/// for new L1 batches. The address can be switched without restarting the node; the switch takes effect
+/// starting from the next opened L1 batch (all L2 blocks in a batch always share the same fee account).
+/// The fee account used for each L2 block is persisted in Postgres.
+///
+/// The address is shared among all clones of the handle.
+#[derive(Debug, Clone)]
+pub struct FeeAccountHandle(Arc<RwLock<Address>>);
+
+impl FeeAccountHandle {
+    pub fn new(address: Address) -> Self {
+        Self(Arc::new(RwLock::new(address)))
+    }
+
+    /// Returns the currently active fee account.
+    pub fn address(&self) -> Address {
+        *self.0.read().expect("fee account is poisoned")
+    }
+
+    /// Sets the fee account for new L1 batches. Returns the previously active address.
+    pub fn set(&self, address: Address) -> Address {
+        let mut guard = self.0.write().expect("fee account is poisoned");
+        let prev_address = std::mem::replace(&mut *guard, address);
+        if prev_address != address {
+            tracing::info!("Switched operator fee account from {prev_address:?} to {address:?}");
+        }
+        prev_address
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switching_fee_account() {
+        let handle = FeeAccountHandle::new(Address::repeat_byte(1));
+        let handle_clone = handle.clone();
+        assert_eq!(
+            handle_clone.set(Address::repeat_byte(2)),
+            Address::repeat_byte(1)
+        );
+        assert_eq!(handle.address(), Address::repeat_byte(2));
+    }
+}
@@ -15,15 +15,18 @@ use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_mempool::L2TxFilter;
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 use zksync_types::{
-    protocol_upgrade::ProtocolUpgradeTx, utils::display_timestamp, Address, L1BatchNumber,
-    L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
+    l2::TransactionType, protocol_upgrade::ProtocolUpgradeTx, utils::display_timestamp, Address,
+    L1BatchNumber, L2BlockNumber, L2ChainId, ProtocolVersionId, Transaction, H256, U256,
 };
 // TODO (SMA-1206): use seconds instead of milliseconds.
 use zksync_utils::time::millis_since_epoch;
 
 use crate::{
+    denylist::AddressDenylist,
+    fee_account::FeeAccountHandle,
     io::{
         common::{load_pending_batch, poll_iters, IoCursor},
+        priority_op_throttler::PriorityOpThrottler,
         seal_logic::l2_block_seal_subtasks::L2BlockSealProcess,
         L1BatchParams, L2BlockParams, PendingBatchData, StateKeeperIO,
     },
@@ -48,13 +51,15 @@ pub struct MempoolIO {
     l2_block_max_payload_size_sealer: L2BlockMaxPayloadSizeSealer,
     filter: L2TxFilter,
     l1_batch_params_provider: L1BatchParamsProvider,
-    fee_account: Address,
+    fee_account: FeeAccountHandle,
     validation_computational_gas_limit: u32,
     max_allowed_tx_gas_limit: U256,
     delay_interval: Duration,
     // Used to keep track of gas prices to set accepted price per pubdata byte in blocks.
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     chain_id: L2ChainId,
+    address_denylist: Option<AddressDenylist>,
+    priority_op_throttler: PriorityOpThrottler,
 }
 
 impl IoSealCriteria for MempoolIO {
@@ -122,6 +127,13 @@ impl StateKeeperIO for MempoolIO {
             system_env,
             pending_l2_blocks,
         } = pending_batch_data;
+        // Account for priority operations already included into the pending batch.
+        self.priority_op_throttler.start_l1_batch();
+        let pending_txs = pending_l2_blocks.iter().flat_map(|l2_block| &l2_block.txs);
+        for tx in pending_txs.filter(|tx| tx.is_l1()) {
+            self.priority_op_throttler.record(tx.gas_limit().as_u64());
+        }
+        self.priority_op_throttler.start_l2_block();
         // Initialize the filter for the transactions that come after the pending batch.
         // We use values from the pending block to match the filter with one used before the restart.
         let (base_fee, gas_per_pubdata) =
@@ -190,10 +202,11 @@ impl StateKeeperIO for MempoolIO {
                 continue;
             }
 
+            self.priority_op_throttler.start_l1_batch();
             return Ok(Some(L1BatchParams {
                 protocol_version,
                 validation_computational_gas_limit: self.validation_computational_gas_limit,
-                operator_address: self.fee_account,
+                operator_address: self.fee_account.address(),
                 fee_input: self.filter.fee_input,
                 first_l2_block: L2BlockParams {
                     timestamp,
@@ -221,6 +234,7 @@ impl StateKeeperIO for MempoolIO {
             return Ok(None);
         };
 
+        self.priority_op_throttler.start_l2_block();
         Ok(Some(L2BlockParams {
             timestamp,
             // This value is effectively ignored by the protocol.
@@ -235,7 +249,12 @@ impl StateKeeperIO for MempoolIO {
         let started_at = Instant::now();
         while started_at.elapsed() <= max_wait {
             let get_latency = KEEPER_METRICS.get_tx_from_mempool.start();
-            let maybe_tx = self.mempool.next_transaction(&self.filter);
+            let throttler = &self.priority_op_throttler;
+            let maybe_tx = self
+                .mempool
+                .next_transaction_with(&self.filter, |priority_op| {
+                    throttler.allows(priority_op.common_data.gas_limit.as_u64())
+                });
             get_latency.observe();
 
             if let Some(tx) = maybe_tx {
@@ -251,6 +270,19 @@ impl StateKeeperIO for MempoolIO {
                         .await?;
                     continue;
                 }
+                // The denylist may have been updated after the transaction was accepted by the API server.
+                let denied_address = self
+                    .address_denylist
+                    .as_ref()
+                    .and_then(|denylist| denylist.denied_address(&tx));
+                if let Some(address) = denied_address {
+                    self.reject(&tx, UnexecutableReason::DeniedAddress(address))
+                        .await?;
+                    continue;
+                }
+                if tx.is_l1() {
+                    self.priority_op_throttler.record(tx.gas_limit().as_u64());
+                }
                 return Ok(Some(tx));
             } else {
                 tokio::time::sleep(self.delay_interval).await;
@@ -261,6 +293,9 @@ impl StateKeeperIO for MempoolIO {
     }
 
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()> {
+        if tx.is_l1() {
+            self.priority_op_throttler.revert(tx.gas_limit().as_u64());
+        }
         // Reset nonces in the mempool.
         self.mempool.rollback(&tx);
         // Insert the transaction back.
@@ -297,6 +332,32 @@ impl StateKeeperIO for MempoolIO {
         Ok(())
     }
 
+    async fn abort_pending_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        transactions: Vec<Transaction>,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "Returning {} transactions from aborted L1 batch #{l1_batch_number}",
+            transactions.len()
+        );
+        // L2 transactions are reloaded from the storage, which contains them as not executed. Since
+        // the mempool may have pruned some of the initiators, we don't insert transactions directly.
+        let mut initiators = vec![];
+        for tx in transactions {
+            if tx.is_l1() {
+                self.rollback(tx).await?;
+            } else if tx.tx_format() != TransactionType::ProtocolUpgradeTransaction {
+                // Upgrade transactions are reloaded when initializing the batch.
+                initiators.push(tx.initiator_account());
+            }
+        }
+        initiators.sort_unstable();
+        initiators.dedup();
+        self.mempool.stash_accounts(&initiators);
+        Ok(())
+    }
+
     async fn load_base_system_contracts(
         &self,
         protocol_version: ProtocolVersionId,
@@ -430,14 +491,27 @@ impl MempoolIO {
             filter: L2TxFilter::default(),
             // ^ Will be initialized properly on the first newly opened batch
             l1_batch_params_provider,
-            fee_account,
+            fee_account: FeeAccountHandle::new(fee_account),
             validation_computational_gas_limit: config.validation_computational_gas_limit,
             max_allowed_tx_gas_limit: config.max_allowed_l2_tx_gas_limit.into(),
             delay_interval,
             batch_fee_input_provider,
             chain_id,
+            address_denylist: None,
+            priority_op_throttler: PriorityOpThrottler::new(config),
         })
     }
+
+    /// Rejects L2 transactions sent from or to addresses in the provided denylist.
+    pub fn with_address_denylist(mut self, denylist: AddressDenylist) -> Self {
+        self.address_denylist = Some(denylist);
+        self
+    }
+
+    /// Returns a handle allowing to switch the fee account for new L1 batches at runtime.
+    pub fn fee_account_handle(&self) -> FeeAccountHandle {
+        self.fee_account.clone()
+    }
 }
 
 /// Getters required for testing the MempoolIO.
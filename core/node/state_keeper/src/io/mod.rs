@@ -13,6 +13,9 @@ pub use self::{
     common::IoCursor,
     output_handler::{OutputHandler, StateKeeperOutputHandler},
     persistence::{L2BlockSealerTask, StateKeeperPersistence, TreeWritesPersistence},
+    pre_executor::MempoolPreExecutor,
+    read_set_cache::{ReadSet, ReadSetCache},
+    storage_prefetcher::MempoolStoragePrefetcher,
 };
 use super::seal_criteria::{IoSealCriteria, UnexecutableReason};
 
@@ -20,7 +23,11 @@ pub mod common;
 pub(crate) mod mempool;
 mod output_handler;
 mod persistence;
+mod pre_executor;
+mod priority_op_throttler;
+pub(crate) mod read_set_cache;
 pub mod seal_logic;
+mod storage_prefetcher;
 #[cfg(test)]
 mod tests;
 
@@ -137,6 +144,17 @@ pub trait StateKeeperIO: 'static + Send + Sync + fmt::Debug + IoSealCriteria {
     async fn rollback(&mut self, tx: Transaction) -> anyhow::Result<()>;
     /// Marks the transaction as "rejected", e.g. one that is not correct and can't be executed.
     async fn reject(&mut self, tx: &Transaction, reason: UnexecutableReason) -> anyhow::Result<()>;
+    /// Aborts the pending L1 batch with the specified number, which doesn't have sealed L2 blocks: returns `transactions`
+    /// executed in the batch, so that they can be retrieved from the I/O again.
+    ///
+    /// The default implementation returns an error; aborting batches is only supported by the main node I/O.
+    async fn abort_pending_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+        _transactions: Vec<Transaction>,
+    ) -> anyhow::Result<()> {
+        anyhow::bail!("aborting L1 batch #{l1_batch_number} is not supported by {self:?}")
+    }
 
     /// Loads base system contracts with the specified version.
     async fn load_base_system_contracts(
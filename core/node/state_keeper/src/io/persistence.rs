@@ -8,7 +8,9 @@ use multivm::zk_evm_latest::ethereum_types::H256;
 use tokio::sync::{mpsc, oneshot};
 use zksync_dal::{ConnectionPool, Core, CoreDal};
 use zksync_shared_metrics::{BlockStage, APP_METRICS};
-use zksync_types::{writes::TreeWrite, AccountTreeId, Address, StorageKey};
+use zksync_types::{
+    pubdata_da::PubdataEncoding, writes::TreeWrite, AccountTreeId, Address, StorageKey,
+};
 use zksync_utils::u256_to_h256;
 
 use crate::{
@@ -33,6 +35,8 @@ pub struct StateKeeperPersistence {
     l2_shared_bridge_addr: Address,
     pre_insert_txs: bool,
     insert_protective_reads: bool,
+    insert_block_notifications: bool,
+    pubdata_encoding: PubdataEncoding,
     commands_sender: mpsc::Sender<Completable<L2BlockSealCommand>>,
     latest_completion_receiver: Option<oneshot::Receiver<()>>,
     // If true, `submit_l2_block()` will wait for the operation to complete.
@@ -64,6 +68,8 @@ impl StateKeeperPersistence {
             l2_shared_bridge_addr,
             pre_insert_txs: false,
             insert_protective_reads: true,
+            insert_block_notifications: false,
+            pubdata_encoding: PubdataEncoding::Rollup,
             commands_sender,
             latest_completion_receiver: None,
             is_sync,
@@ -83,6 +89,21 @@ impl StateKeeperPersistence {
         self
     }
 
+    /// Enables inserting notifications about sealed L2 blocks and L1 batches to the block notifications outbox.
+    /// Notifications are inserted in the same transaction as the block data, so they are never dispatched
+    /// for blocks that weren't persisted.
+    pub fn with_block_notifications(mut self) -> Self {
+        self.insert_block_notifications = true;
+        self
+    }
+
+    /// Sets the encoding of pubdata persisted for L1 batches. By default, the rollup encoding is used
+    /// (i.e., the full pubdata is persisted).
+    pub fn with_pubdata_encoding(mut self, encoding: PubdataEncoding) -> Self {
+        self.pubdata_encoding = encoding;
+        self
+    }
+
     /// Submits a new sealing `command` to the sealer that this handle is attached to.
     ///
     /// If there are currently too many unprocessed commands, this method will wait until
@@ -158,8 +179,11 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
     }
 
     async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
-        let command =
-            updates_manager.seal_l2_block_command(self.l2_shared_bridge_addr, self.pre_insert_txs);
+        let command = updates_manager.seal_l2_block_command(
+            self.l2_shared_bridge_addr,
+            self.pre_insert_txs,
+            self.insert_block_notifications,
+        );
         self.submit_l2_block(command).await;
         Ok(())
     }
@@ -177,6 +201,8 @@ impl StateKeeperOutputHandler for StateKeeperPersistence {
                 self.pool.clone(),
                 self.l2_shared_bridge_addr,
                 self.insert_protective_reads,
+                self.insert_block_notifications,
+                self.pubdata_encoding,
             )
             .await
             .with_context(|| format!("cannot persist L1 batch #{batch_number}"))?;
@@ -354,12 +380,16 @@ impl StateKeeperOutputHandler for TreeWritesPersistence {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
 
     use assert_matches::assert_matches;
     use futures::FutureExt;
     use multivm::zk_evm_latest::ethereum_types::{H256, U256};
-    use zksync_dal::CoreDal;
+    use zksync_dal::{
+        outbox_dal::{BlockNotification, L1BatchStatus},
+        CoreDal,
+    };
+    use zksync_merkle_tree::{domain::ZkSyncTree, InMemoryDatabase, TreeInstruction};
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_types::{
         api::TransactionStatus, block::BlockGasCount, tx::ExecutionMetrics,
@@ -453,6 +483,39 @@ mod tests {
         let actual_index = tree_writes[0].leaf_index;
         let expected_index = initial_writes_in_genesis_batch + 1;
         assert_eq!(actual_index, expected_index);
+
+        // Check that persisted tree writes can be applied to the Merkle tree.
+        let leaf_indices: HashMap<_, _> = initial_writes
+            .iter()
+            .map(|write| (write.hashed_key, write.index))
+            .collect();
+        let genesis_logs = storage
+            .storage_logs_dal()
+            .get_touched_slots_for_l1_batch(L1BatchNumber(0))
+            .await
+            .unwrap();
+        let mut genesis_instructions: Vec<_> = genesis_logs
+            .into_iter()
+            .map(|(key, value)| TreeInstruction::write(key, leaf_indices[&key.hashed_key()], value))
+            .collect();
+        genesis_instructions.sort_unstable_by_key(|instruction| match instruction {
+            TreeInstruction::Write(entry) => entry.leaf_index,
+            TreeInstruction::Read(_) => 0,
+        });
+        let instructions: Vec<_> = tree_writes
+            .into_iter()
+            .map(|write| {
+                let key = StorageKey::new(AccountTreeId::new(write.address), write.key);
+                TreeInstruction::write(key, write.leaf_index, write.value)
+            })
+            .collect();
+
+        let mut tree = ZkSyncTree::in_memory(InMemoryDatabase::new()).unwrap();
+        tree.process_l1_batch(&genesis_instructions).unwrap();
+        let metadata = tree.process_l1_batch(&instructions).unwrap();
+        assert_eq!(metadata.rollup_last_leaf_index, expected_index + 1);
+        tree.save().unwrap();
+        tree.verify_consistency(L1BatchNumber(1)).unwrap();
     }
 
     async fn execute_mock_batch(output_handler: &mut OutputHandler) -> H256 {
@@ -579,6 +642,97 @@ mod tests {
         assert_eq!(protective_reads, HashSet::new());
     }
 
+    #[tokio::test]
+    async fn inserting_block_notifications() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        // Save metadata for the genesis L1 batch so that we don't hang in `seal_l1_batch`.
+        storage
+            .blocks_dal()
+            .set_l1_batch_hash(L1BatchNumber(0), H256::zero())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let (persistence, l2_block_sealer) =
+            StateKeeperPersistence::new(pool.clone(), Address::default(), 1);
+        let persistence = persistence.with_block_notifications();
+        let mut output_handler = OutputHandler::new(Box::new(persistence));
+        tokio::spawn(l2_block_sealer.run());
+        execute_mock_batch(&mut output_handler).await;
+
+        let mut storage = pool.connection().await.unwrap();
+        let notifications = storage
+            .outbox_dal()
+            .get_pending_notifications(10)
+            .await
+            .unwrap();
+        let notifications: Vec<_> = notifications
+            .into_iter()
+            .map(|notification| notification.notification)
+            .collect();
+        assert_eq!(notifications.len(), 3, "{notifications:?}");
+        for (i, number) in [L2BlockNumber(1), L2BlockNumber(2)].into_iter().enumerate() {
+            let header = storage
+                .blocks_dal()
+                .get_l2_block_header(number)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                notifications[i],
+                BlockNotification::NewL2Block {
+                    number,
+                    hash: header.hash,
+                    timestamp: header.timestamp,
+                }
+            );
+        }
+        assert_eq!(
+            notifications[2],
+            BlockNotification::L1BatchStatus {
+                number: L1BatchNumber(1),
+                status: L1BatchStatus::Sealed,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn persisting_pubdata_in_validium_encoding() {
+        let pool = ConnectionPool::constrained_test_pool(1).await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+        // Save metadata for the genesis L1 batch so that we don't hang in `seal_l1_batch`.
+        storage
+            .blocks_dal()
+            .set_l1_batch_hash(L1BatchNumber(0), H256::zero())
+            .await
+            .unwrap();
+        drop(storage);
+
+        let (persistence, l2_block_sealer) =
+            StateKeeperPersistence::new(pool.clone(), Address::default(), 1);
+        let persistence = persistence.with_pubdata_encoding(PubdataEncoding::Validium);
+        let mut output_handler = OutputHandler::new(Box::new(persistence));
+        tokio::spawn(l2_block_sealer.run());
+        execute_mock_batch(&mut output_handler).await;
+
+        let mut storage = pool.connection().await.unwrap();
+        let l1_batch_header = storage
+            .blocks_dal()
+            .get_l1_batch_header(L1BatchNumber(1))
+            .await
+            .unwrap()
+            .expect("No L1 batch #1");
+        let expected_pubdata = PubdataEncoding::Validium.encode(vec![]);
+        assert_eq!(l1_batch_header.pubdata_input, Some(expected_pubdata));
+    }
+
     #[tokio::test]
     async fn l2_block_sealer_handle_blocking() {
         let pool = ConnectionPool::constrained_test_pool(1).await;
@@ -587,7 +741,7 @@ mod tests {
 
         // The first command should be successfully submitted immediately.
         let mut updates_manager = create_updates_manager();
-        let seal_command = updates_manager.seal_l2_block_command(Address::default(), false);
+        let seal_command = updates_manager.seal_l2_block_command(Address::default(), false, false);
         persistence.submit_l2_block(seal_command).await;
 
         // The second command should lead to blocking
@@ -595,7 +749,7 @@ mod tests {
             timestamp: 2,
             virtual_blocks: 1,
         });
-        let seal_command = updates_manager.seal_l2_block_command(Address::default(), false);
+        let seal_command = updates_manager.seal_l2_block_command(Address::default(), false, false);
         {
             let submit_future = persistence.submit_l2_block(seal_command);
             futures::pin_mut!(submit_future);
@@ -623,7 +777,7 @@ mod tests {
             timestamp: 3,
             virtual_blocks: 1,
         });
-        let seal_command = updates_manager.seal_l2_block_command(Address::default(), false);
+        let seal_command = updates_manager.seal_l2_block_command(Address::default(), false, false);
         persistence.submit_l2_block(seal_command).await;
         let command = sealer.commands_receiver.recv().await.unwrap();
         command.completion_sender.send(()).unwrap();
@@ -639,7 +793,8 @@ mod tests {
         // 5 L2 block sealing commands can be submitted without blocking.
         let mut updates_manager = create_updates_manager();
         for i in 1..=5 {
-            let seal_command = updates_manager.seal_l2_block_command(Address::default(), false);
+            let seal_command =
+                updates_manager.seal_l2_block_command(Address::default(), false, false);
             updates_manager.push_l2_block(L2BlockParams {
                 timestamp: i,
                 virtual_blocks: 1,
@@ -0,0 +1,270 @@
+//! Speculative pre-execution of mempool transactions.
+
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use multivm::{
+    interface::{L1BatchEnv, SystemEnv, VmExecutionResultAndLogs, VmInterface},
+    utils::{derive_base_fee_and_gas_per_pubdata, supports_speculative_execution},
+    vm_latest::{HistoryDisabled, HistoryEnabled},
+    VmInstance,
+};
+use tokio::sync::watch;
+use zksync_mempool::L2TxFilter;
+use zksync_state::{ReadStorage, StoragePtr, StorageView, WriteStorage};
+use zksync_types::{L1BatchNumber, StorageKey, StorageValue, Transaction, H256};
+
+use super::read_set_cache::{ReadSet, ReadSetCache};
+use crate::{
+    batch_executor::ExecutionResultCache, metrics::PRE_EXECUTION_METRICS, types::MempoolGuard,
+};
+
+/// Pre-executes transactions that are likely to be included into the currently executed L1 batch
+/// against the state as of the previous L1 batch, and caches their storage read sets in a [`ReadSetCache`].
+/// The batch executor then uses these read sets to warm up its storage before executing the transactions.
+///
+/// Pre-execution doesn't affect execution results: the cached values are only used for storage slots
+/// not modified in the current batch, and all of them are read from the state the batch executor is based on.
+///
+/// If the VM version [supports speculative execution](supports_speculative_execution()), a single VM
+/// is used for the entire batch, and its state is rolled back after each pre-executed transaction.
+/// Otherwise, a new VM is created for each transaction.
+#[derive(Debug, Clone)]
+pub struct MempoolPreExecutor {
+    mempool: MempoolGuard,
+    cache: ReadSetCache,
+    result_cache: Option<ExecutionResultCache>,
+    max_transactions_per_iteration: usize,
+    poll_interval: Duration,
+}
+
+impl MempoolPreExecutor {
+    const DEFAULT_MAX_TRANSACTIONS_PER_ITERATION: usize = 32;
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    pub fn new(mempool: MempoolGuard, cache: ReadSetCache) -> Self {
+        Self {
+            mempool,
+            cache,
+            result_cache: None,
+            max_transactions_per_iteration: Self::DEFAULT_MAX_TRANSACTIONS_PER_ITERATION,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Sets the maximum number of mempool transactions considered for pre-execution at once.
+    pub fn with_max_transactions_per_iteration(mut self, max_transactions: usize) -> Self {
+        self.max_transactions_per_iteration = max_transactions;
+        self
+    }
+
+    /// Sets the interval to wait for new mempool transactions if there's nothing to pre-execute.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Sets the cache to store execution results of pre-executed transactions in. Only results of transactions
+    /// rejected by the VM are stored; see [`ExecutionResultCache`] for details.
+    pub fn with_result_cache(mut self, cache: ExecutionResultCache) -> Self {
+        self.result_cache = Some(cache);
+        self
+    }
+
+    pub fn cache(&self) -> &ReadSetCache {
+        &self.cache
+    }
+
+    /// Pre-executes mempool transactions for the specified L1 batch. Returns once the stop signal is received,
+    /// or the cache state version is advanced past the state this batch is based on (i.e., once the batch is sealed).
+    ///
+    /// This method is blocking, so it should be run on a dedicated thread.
+    pub(crate) fn run_for_batch<S: ReadStorage>(
+        &self,
+        storage: S,
+        l1_batch_env: L1BatchEnv,
+        system_env: SystemEnv,
+        stop_receiver: &watch::Receiver<bool>,
+    ) {
+        let state_version = l1_batch_env.number - 1;
+        self.cache.advance_state_version(state_version);
+        let filter = l2_tx_filter_for_batch(&l1_batch_env, &system_env);
+        let storage = SharedStorage(Rc::new(RefCell::new(storage)));
+        let mut vm = PreExecutionVm::new(storage, &l1_batch_env, &system_env);
+        tracing::debug!(
+            "Starting pre-executing transactions for L1 batch #{} (reusing VM: {})",
+            l1_batch_env.number,
+            matches!(vm, PreExecutionVm::Speculative { .. })
+        );
+
+        while !self.should_stop(state_version, stop_receiver) {
+            let transactions: Vec<_> = self
+                .mempool
+                .peek_l2_transactions(&filter, self.max_transactions_per_iteration)
+                .into_iter()
+                .filter(|tx| !self.cache.contains(tx.hash(), state_version))
+                .collect();
+            let mut is_idle = transactions.is_empty();
+
+            for tx in transactions {
+                if self.should_stop(state_version, stop_receiver) {
+                    break;
+                }
+                let tx_hash = tx.hash();
+                let latency = PRE_EXECUTION_METRICS.tx_latency.start();
+                let (read_set, result) = vm.pre_execute(&l1_batch_env, &system_env, tx);
+                latency.observe();
+                PRE_EXECUTION_METRICS.executed_transactions.inc();
+
+                if let (Some(result_cache), Some(result)) = (&self.result_cache, result) {
+                    result_cache.insert(tx_hash, state_version, result);
+                }
+
+                if !self.cache.insert(tx_hash, state_version, read_set) {
+                    // The cache is full (or outdated); wait until the batch executor consumes some read sets.
+                    is_idle = true;
+                    break;
+                }
+            }
+
+            if is_idle {
+                std::thread::sleep(self.poll_interval);
+            }
+        }
+        tracing::debug!(
+            "Stopped pre-executing transactions for L1 batch #{}",
+            l1_batch_env.number
+        );
+    }
+
+    fn should_stop(
+        &self,
+        state_version: L1BatchNumber,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> bool {
+        *stop_receiver.borrow() || self.cache.state_version() > state_version
+    }
+}
+
+/// VM used to pre-execute transactions for a single L1 batch.
+#[derive(Debug)]
+enum PreExecutionVm<S: ReadStorage> {
+    /// VM reused for all pre-executed transactions. Its storage view is replaced after each transaction
+    /// so that read sets of different transactions don't affect each other; this is sound since the VM state
+    /// (including storage writes) is rolled back after each transaction.
+    Speculative {
+        vm: Box<VmInstance<StorageView<SharedStorage<S>>, HistoryEnabled>>,
+        storage_view: StoragePtr<StorageView<SharedStorage<S>>>,
+        storage: SharedStorage<S>,
+    },
+    /// A new VM is created for each transaction.
+    Disposable(SharedStorage<S>),
+}
+
+impl<S: ReadStorage> PreExecutionVm<S> {
+    fn new(storage: SharedStorage<S>, l1_batch_env: &L1BatchEnv, system_env: &SystemEnv) -> Self {
+        if !supports_speculative_execution(system_env.version.into()) {
+            return Self::Disposable(storage);
+        }
+        let storage_view = StorageView::new(storage.clone()).to_rc_ptr();
+        let vm = VmInstance::new(
+            l1_batch_env.clone(),
+            system_env.clone(),
+            storage_view.clone(),
+        );
+        Self::Speculative {
+            vm: Box::new(vm),
+            storage_view,
+            storage,
+        }
+    }
+
+    /// Executes the transaction as the first one in the batch and returns all storage slots read from the storage,
+    /// together with the execution result. The result is not returned if bytecode compression has failed, since
+    /// the batch executor may handle such transactions differently.
+    fn pre_execute(
+        &mut self,
+        l1_batch_env: &L1BatchEnv,
+        system_env: &SystemEnv,
+        tx: Transaction,
+    ) -> (ReadSet, Option<VmExecutionResultAndLogs>) {
+        match self {
+            Self::Speculative {
+                vm,
+                storage_view,
+                storage,
+            } => {
+                let (compression_result, result) = vm.execute_speculatively(|vm| {
+                    vm.execute_transaction_with_bytecode_compression(tx, true)
+                });
+                let compressed = compression_result.is_ok();
+                let read_set = Self::read_set(storage_view);
+                *storage_view.borrow_mut() = StorageView::new(storage.clone());
+                (read_set, compressed.then_some(result))
+            }
+            Self::Disposable(storage) => {
+                let storage_view = StorageView::new(storage.clone()).to_rc_ptr();
+                let mut vm = VmInstance::<_, HistoryDisabled>::new(
+                    l1_batch_env.clone(),
+                    system_env.clone(),
+                    storage_view.clone(),
+                );
+                let (compression_result, result) =
+                    vm.execute_transaction_with_bytecode_compression(tx, true);
+                let compressed = compression_result.is_ok();
+                drop(vm);
+                (Self::read_set(&storage_view), compressed.then_some(result))
+            }
+        }
+    }
+
+    fn read_set(storage_view: &StoragePtr<StorageView<SharedStorage<S>>>) -> ReadSet {
+        let storage_view = storage_view.borrow();
+        storage_view
+            .read_storage_keys()
+            .iter()
+            .map(|(key, value)| (*key, *value))
+            .collect()
+    }
+}
+
+/// Returns the filter for mempool transactions that can be included into the specified L1 batch.
+pub(super) fn l2_tx_filter_for_batch(
+    l1_batch_env: &L1BatchEnv,
+    system_env: &SystemEnv,
+) -> L2TxFilter {
+    let (base_fee, gas_per_pubdata) =
+        derive_base_fee_and_gas_per_pubdata(l1_batch_env.fee_input, system_env.version.into());
+    L2TxFilter {
+        fee_input: l1_batch_env.fee_input,
+        fee_per_gas: base_fee,
+        gas_per_pubdata: gas_per_pubdata as u32,
+    }
+}
+
+/// Allows to use the same storage for multiple short-lived [`StorageView`]s.
+#[derive(Debug)]
+struct SharedStorage<S>(Rc<RefCell<S>>);
+
+impl<S> Clone for SharedStorage<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for SharedStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        self.0.borrow_mut().read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.0.borrow_mut().is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.0.borrow_mut().load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.0.borrow_mut().get_enumeration_index(key)
+    }
+}
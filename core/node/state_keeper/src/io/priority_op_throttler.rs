@@ -0,0 +1,143 @@
+//! Throttling of L1 priority operations included into L1 batches and L2 blocks.
+
+use zksync_config::configs::chain::StateKeeperConfig;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PriorityOpLimits {
+    max_count: Option<u32>,
+    max_gas: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct PriorityOpUsage {
+    count: u32,
+    gas: u64,
+}
+
+impl PriorityOpUsage {
+    fn allows(&self, limits: PriorityOpLimits, gas_limit: u64) -> bool {
+        if limits
+            .max_count
+            .is_some_and(|max_count| self.count >= max_count)
+        {
+            return false;
+        }
+        // Always allow the first operation so that operations exceeding the gas limit on their own aren't stuck forever.
+        self.count == 0
+            || limits.max_gas.map_or(true, |max_gas| {
+                self.gas.saturating_add(gas_limit) <= max_gas
+            })
+    }
+
+    fn add(&mut self, gas_limit: u64) {
+        self.count += 1;
+        self.gas = self.gas.saturating_add(gas_limit);
+    }
+
+    fn remove(&mut self, gas_limit: u64) {
+        self.count = self.count.saturating_sub(1);
+        self.gas = self.gas.saturating_sub(gas_limit);
+    }
+}
+
+/// Caps the number and total gas limit of L1 priority operations included into a single L1 batch and L2 block.
+///
+/// Priority operations still take precedence over L2 transactions while within limits. Once a limit is reached,
+/// the state keeper switches to L2 transactions until the corresponding L2 block or L1 batch is sealed, so that
+/// a burst of priority operations is spread across multiple blocks instead of starving L2 transactions.
+#[derive(Debug, Default)]
+pub(crate) struct PriorityOpThrottler {
+    batch_limits: PriorityOpLimits,
+    l2_block_limits: PriorityOpLimits,
+    batch_usage: PriorityOpUsage,
+    l2_block_usage: PriorityOpUsage,
+}
+
+impl PriorityOpThrottler {
+    pub fn new(config: &StateKeeperConfig) -> Self {
+        Self {
+            batch_limits: PriorityOpLimits {
+                max_count: config.max_priority_ops_per_batch,
+                max_gas: config.max_priority_ops_gas_per_batch,
+            },
+            l2_block_limits: PriorityOpLimits {
+                max_count: config.max_priority_ops_per_l2_block,
+                max_gas: config.max_priority_ops_gas_per_l2_block,
+            },
+            ..Self::default()
+        }
+    }
+
+    /// Checks whether a priority operation with the specified gas limit can be included into the current L2 block.
+    pub fn allows(&self, gas_limit: u64) -> bool {
+        self.batch_usage.allows(self.batch_limits, gas_limit)
+            && self.l2_block_usage.allows(self.l2_block_limits, gas_limit)
+    }
+
+    pub fn record(&mut self, gas_limit: u64) {
+        self.batch_usage.add(gas_limit);
+        self.l2_block_usage.add(gas_limit);
+    }
+
+    /// Reverts [`Self::record()`] for a rolled back priority operation.
+    pub fn revert(&mut self, gas_limit: u64) {
+        self.batch_usage.remove(gas_limit);
+        self.l2_block_usage.remove(gas_limit);
+    }
+
+    pub fn start_l1_batch(&mut self) {
+        self.batch_usage = PriorityOpUsage::default();
+        self.l2_block_usage = PriorityOpUsage::default();
+    }
+
+    pub fn start_l2_block(&mut self) {
+        self.l2_block_usage = PriorityOpUsage::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_throttler() {
+        let mut throttler = PriorityOpThrottler::default();
+        for _ in 0..1_000 {
+            assert!(throttler.allows(u64::MAX));
+            throttler.record(u64::MAX);
+        }
+    }
+
+    #[test]
+    fn throttling_priority_ops() {
+        let mut config = StateKeeperConfig::for_tests();
+        config.max_priority_ops_per_batch = Some(3);
+        config.max_priority_ops_per_l2_block = Some(2);
+        config.max_priority_ops_gas_per_l2_block = Some(1_000);
+        let mut throttler = PriorityOpThrottler::new(&config);
+
+        // The first operation is allowed even if it exceeds the gas limit.
+        assert!(throttler.allows(5_000));
+        throttler.record(5_000);
+        assert!(!throttler.allows(1));
+        throttler.revert(5_000);
+        assert!(throttler.allows(600));
+        throttler.record(600);
+        assert!(!throttler.allows(600));
+        assert!(throttler.allows(400));
+        throttler.record(400);
+        // The L2 block count limit is reached.
+        assert!(!throttler.allows(0));
+
+        throttler.start_l2_block();
+        assert!(throttler.allows(100));
+        throttler.record(100);
+        // The L1 batch count limit is reached.
+        assert!(!throttler.allows(0));
+        throttler.start_l2_block();
+        assert!(!throttler.allows(0));
+
+        throttler.start_l1_batch();
+        assert!(throttler.allows(100));
+    }
+}
@@ -0,0 +1,222 @@
+//! Cache of storage read sets obtained by pre-executing mempool transactions.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use zksync_state::ReadStorage;
+use zksync_types::{L1BatchNumber, StorageKey, StorageValue, H256};
+
+/// Storage slots read by a transaction together with their values.
+pub type ReadSet = Vec<(StorageKey, StorageValue)>;
+
+#[derive(Debug)]
+struct ReadSetCacheInner {
+    /// Number of the latest sealed L1 batch; all cached read sets were obtained against the state after this batch.
+    state_version: L1BatchNumber,
+    read_sets: HashMap<H256, ReadSet>,
+}
+
+/// Cache of storage read sets keyed by the transaction hash and the state version (i.e., the number
+/// of the latest sealed L1 batch) they were obtained against. Read sets for outdated state versions are discarded
+/// once the state version is advanced.
+///
+/// The cache is shared between the pre-executor filling it and the batch executor consuming it, so cloning it is cheap.
+#[derive(Debug, Clone)]
+pub struct ReadSetCache {
+    inner: Arc<Mutex<ReadSetCacheInner>>,
+    capacity: usize,
+}
+
+impl ReadSetCache {
+    /// Default capacity of caches used by the state keeper.
+    pub const DEFAULT_CAPACITY: usize = 1_024;
+
+    /// Creates a cache holding up to `capacity` read sets.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ReadSetCacheInner {
+                state_version: L1BatchNumber(0),
+                read_sets: HashMap::new(),
+            })),
+            capacity,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ReadSetCacheInner> {
+        self.inner.lock().expect("read set cache is poisoned")
+    }
+
+    /// Returns the current state version of the cache.
+    pub fn state_version(&self) -> L1BatchNumber {
+        self.lock().state_version
+    }
+
+    /// Advances the state version, discarding all read sets obtained against the previous versions.
+    /// Versions older than the current one are ignored.
+    pub fn advance_state_version(&self, state_version: L1BatchNumber) {
+        let mut inner = self.lock();
+        if state_version > inner.state_version {
+            inner.state_version = state_version;
+            inner.read_sets.clear();
+        }
+    }
+
+    /// Checks whether the cache contains a read set for the specified transaction and state version.
+    pub fn contains(&self, tx_hash: H256, state_version: L1BatchNumber) -> bool {
+        let inner = self.lock();
+        inner.state_version == state_version && inner.read_sets.contains_key(&tx_hash)
+    }
+
+    /// Inserts a read set into the cache. Returns `false` if the read set was not inserted, either because
+    /// it was obtained against an outdated state version, or because the cache is full.
+    pub fn insert(&self, tx_hash: H256, state_version: L1BatchNumber, read_set: ReadSet) -> bool {
+        let mut inner = self.lock();
+        if state_version < inner.state_version {
+            return false;
+        }
+        if state_version > inner.state_version {
+            inner.state_version = state_version;
+            inner.read_sets.clear();
+        }
+        if inner.read_sets.len() >= self.capacity && !inner.read_sets.contains_key(&tx_hash) {
+            return false;
+        }
+        inner.read_sets.insert(tx_hash, read_set);
+        true
+    }
+
+    /// Removes and returns a read set for the specified transaction if it was obtained against the specified
+    /// state version.
+    pub fn take(&self, tx_hash: H256, state_version: L1BatchNumber) -> Option<ReadSet> {
+        let mut inner = self.lock();
+        if inner.state_version != state_version {
+            return None;
+        }
+        inner.read_sets.remove(&tx_hash)
+    }
+
+    /// Returns the number of cached read sets.
+    pub fn len(&self) -> usize {
+        self.lock().read_sets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Storage answering reads from the read sets taken from [`ReadSetCache`] before falling back to the wrapped storage.
+///
+/// Must only be used with the read sets obtained against the same state as the wrapped storage.
+#[derive(Debug)]
+pub(crate) struct PrefetchedStorage<S> {
+    inner: S,
+    prefetched: Rc<RefCell<HashMap<StorageKey, StorageValue>>>,
+}
+
+impl<S: ReadStorage> PrefetchedStorage<S> {
+    pub(crate) fn new(inner: S) -> Self {
+        Self {
+            inner,
+            prefetched: Rc::default(),
+        }
+    }
+
+    /// Returns a handle allowing to add prefetched values while the storage is owned by the VM.
+    pub(crate) fn prefetched_values(&self) -> Rc<RefCell<HashMap<StorageKey, StorageValue>>> {
+        self.prefetched.clone()
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for PrefetchedStorage<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(value) = self.prefetched.borrow_mut().remove(key) {
+            // `StorageView` caches read values, so the prefetched value is no longer needed.
+            return value;
+        }
+        self.inner.read_value(key)
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        self.inner.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        self.inner.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        self.inner.get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_state::InMemoryStorage;
+    use zksync_types::{AccountTreeId, Address};
+
+    use super::*;
+
+    fn storage_key(index: u64) -> StorageKey {
+        StorageKey::new(
+            AccountTreeId::new(Address::repeat_byte(1)),
+            H256::from_low_u64_be(index),
+        )
+    }
+
+    #[test]
+    fn read_sets_are_keyed_by_state_version() {
+        let cache = ReadSetCache::new(2);
+        let tx_hash = H256::repeat_byte(1);
+        let read_set = vec![(storage_key(1), H256::repeat_byte(0xff))];
+
+        assert!(cache.insert(tx_hash, L1BatchNumber(1), read_set.clone()));
+        assert!(cache.contains(tx_hash, L1BatchNumber(1)));
+        assert!(!cache.contains(tx_hash, L1BatchNumber(2)));
+        assert_eq!(cache.take(tx_hash, L1BatchNumber(2)), None);
+        assert_eq!(
+            cache.take(tx_hash, L1BatchNumber(1)),
+            Some(read_set.clone())
+        );
+        assert!(cache.is_empty());
+
+        assert!(cache.insert(tx_hash, L1BatchNumber(1), read_set.clone()));
+        cache.advance_state_version(L1BatchNumber(2));
+        assert!(cache.is_empty());
+        // Read sets for outdated versions are not inserted.
+        assert!(!cache.insert(tx_hash, L1BatchNumber(1), read_set));
+    }
+
+    #[test]
+    fn cache_capacity_is_respected() {
+        let cache = ReadSetCache::new(2);
+        for i in 0..2 {
+            assert!(cache.insert(H256::repeat_byte(i), L1BatchNumber(1), vec![]));
+        }
+        assert!(!cache.insert(H256::repeat_byte(2), L1BatchNumber(1), vec![]));
+        // Replacing an existing entry is fine.
+        assert!(cache.insert(H256::repeat_byte(0), L1BatchNumber(1), vec![]));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn prefetched_storage_prefers_prefetched_values() {
+        let mut inner = InMemoryStorage::default();
+        inner.set_value(storage_key(1), H256::repeat_byte(1));
+        inner.set_value(storage_key(2), H256::repeat_byte(2));
+        let mut storage = PrefetchedStorage::new(inner);
+
+        storage
+            .prefetched_values()
+            .borrow_mut()
+            .insert(storage_key(1), H256::repeat_byte(0xff));
+        assert_eq!(storage.read_value(&storage_key(1)), H256::repeat_byte(0xff));
+        assert_eq!(storage.read_value(&storage_key(2)), H256::repeat_byte(2));
+        // Prefetched values are consumed on the first read.
+        assert_eq!(storage.read_value(&storage_key(1)), H256::repeat_byte(1));
+    }
+}
@@ -167,7 +167,7 @@ impl L2BlockSealSubtask for InsertStorageLogsSubtask {
         let write_log_count: usize = write_logs.iter().map(|(_, logs)| logs.len()).sum();
         connection
             .storage_logs_dal()
-            .insert_storage_logs(command.l2_block.number, &write_logs)
+            .copy_storage_logs(command.l2_block.number, &write_logs)
             .await?;
 
         progress.observe(write_log_count);
@@ -209,7 +209,7 @@ impl L2BlockSealSubtask for InsertFactoryDepsSubtask {
         if !command.l2_block.new_factory_deps.is_empty() {
             connection
                 .factory_deps_dal()
-                .insert_factory_deps(command.l2_block.number, &command.l2_block.new_factory_deps)
+                .copy_factory_deps(command.l2_block.number, &command.l2_block.new_factory_deps)
                 .await?;
         }
         progress.observe(command.l2_block.new_factory_deps.len());
@@ -296,7 +296,7 @@ impl L2BlockSealSubtask for InsertEventsSubtask {
         let progress = L2_BLOCK_METRICS.start(L2BlockSealStage::InsertEvents, is_fictive);
         connection
             .events_dal()
-            .save_events(command.l2_block.number, &l2_block_events)
+            .copy_events(command.l2_block.number, &l2_block_events)
             .await?;
         progress.observe(l2_block_event_count);
         Ok(())
@@ -454,6 +454,7 @@ mod tests {
                 executed_transactions,
                 events,
                 storage_logs,
+                storage_reads: None,
                 user_l2_to_l1_logs,
                 system_l2_to_l1_logs: Default::default(),
                 new_factory_deps,
@@ -475,6 +476,7 @@ mod tests {
             protocol_version: Some(ProtocolVersionId::latest()),
             l2_shared_bridge_addr: Default::default(),
             pre_insert_txs: false,
+            insert_block_notifications: false,
         };
 
         // Run.
@@ -536,6 +538,7 @@ mod tests {
             gas_per_pubdata_limit: get_max_gas_per_pubdata_byte(VmVersion::latest()),
             virtual_blocks: l2_block_seal_command.l2_block.virtual_blocks,
             gas_limit: get_max_batch_gas_limit(VmVersion::latest()),
+            logs_bloom: Default::default(),
         };
         connection
             .protocol_versions_dal()
@@ -9,13 +9,17 @@ use std::{
 use anyhow::Context as _;
 use itertools::Itertools;
 use multivm::utils::{get_max_batch_gas_limit, get_max_gas_per_pubdata_byte};
-use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
+use zksync_dal::{
+    outbox_dal::{BlockNotification, L1BatchStatus},
+    Connection, ConnectionPool, Core, CoreDal,
+};
 use zksync_shared_metrics::{BlockStage, L2BlockStage, APP_METRICS};
 use zksync_types::{
     block::{L1BatchHeader, L2BlockHeader},
-    event::extract_long_l2_to_l1_messages,
+    event::{extract_logs_bloom, extract_long_l2_to_l1_messages},
     helpers::unix_timestamp_ms,
     l2_to_l1_log::UserL2ToL1Log,
+    pubdata_da::PubdataEncoding,
     storage_writes_deduplicator::{ModifiedSlot, StorageWritesDeduplicator},
     tx::{
         tx_execution_info::DeduplicatedWritesMetrics, IncludedTxLocation,
@@ -48,6 +52,8 @@ impl UpdatesManager {
         pool: ConnectionPool<Core>,
         l2_shared_bridge_addr: Address,
         insert_protective_reads: bool,
+        insert_block_notifications: bool,
+        pubdata_encoding: PubdataEncoding,
     ) -> anyhow::Result<()> {
         let started_at = Instant::now();
         let finished_batch = self
@@ -61,6 +67,7 @@ impl UpdatesManager {
         let l2_block_command = self.seal_l2_block_command(
             l2_shared_bridge_addr,
             false, // fictive L2 blocks don't have txs, so it's fine to pass `false` here.
+            insert_block_notifications,
         );
 
         let mut connection = pool.connection_tagged("state_keeper").await?;
@@ -98,8 +105,9 @@ impl UpdatesManager {
             "Sealing L1 batch {current_l1_batch_number} with timestamp {ts}, {total_tx_count} \
              ({l2_tx_count} L2 + {l1_tx_count} L1) txs, {l2_to_l1_log_count} l2_l1_logs, \
              {event_count} events, {dedup_reads_count} deduped reads, \
-             {dedup_writes_count} deduped writes",
+             {dedup_writes_count} deduped writes; seal criterion: {seal_criterion}",
             ts = display_timestamp(self.batch_timestamp()),
+            seal_criterion = self.l1_batch.seal_criterion.unwrap_or("io"),
             total_tx_count = l1_tx_count + l2_tx_count,
             l2_to_l1_log_count = finished_batch
                 .final_execution_state
@@ -131,7 +139,7 @@ impl UpdatesManager {
             base_system_contracts_hashes: self.base_system_contract_hashes(),
             protocol_version: Some(self.protocol_version()),
             system_logs: finished_batch.final_execution_state.system_logs.clone(),
-            pubdata_input: finished_batch.pubdata_input.clone(),
+            pubdata_input: finished_batch.encoded_pubdata(pubdata_encoding),
         };
 
         let final_bootloader_memory = finished_batch
@@ -150,6 +158,12 @@ impl UpdatesManager {
                 self.pending_execution_metrics().circuit_statistic,
             )
             .await?;
+        if let Some(criterion) = self.l1_batch.seal_criterion {
+            transaction
+                .blocks_dal()
+                .set_l1_batch_seal_criterion(self.l1_batch.number, criterion)
+                .await?;
+        }
         progress.observe(None);
 
         let progress = L1_BATCH_METRICS.start(L1BatchSealStage::SetL1BatchNumberForL2Blocks);
@@ -243,6 +257,17 @@ impl UpdatesManager {
             .await?;
         progress.observe(initial_writes.len());
 
+        if insert_block_notifications {
+            let notification = BlockNotification::L1BatchStatus {
+                number: self.l1_batch.number,
+                status: L1BatchStatus::Sealed,
+            };
+            transaction
+                .outbox_dal()
+                .insert_notifications(&[notification])
+                .await?;
+        }
+
         let progress = L1_BATCH_METRICS.start(L1BatchSealStage::CommitL1Batch);
         transaction.commit().await?;
         progress.observe(None);
@@ -273,6 +298,7 @@ impl UpdatesManager {
         L1_BATCH_METRICS
             .transactions_in_l1_batch
             .observe(self.l1_batch.executed_transactions.len());
+        L1_BATCH_METRICS.sealed_by_criterion[&self.l1_batch.seal_criterion.unwrap_or("io")].inc();
 
         let batch_timestamp = self.batch_timestamp();
         let l1_batch_latency =
@@ -388,13 +414,33 @@ impl L2BlockSealCommand {
             gas_per_pubdata_limit: get_max_gas_per_pubdata_byte(definite_vm_version),
             virtual_blocks: self.l2_block.virtual_blocks,
             gas_limit: get_max_batch_gas_limit(definite_vm_version),
+            logs_bloom: extract_logs_bloom(&self.l2_block.events),
         };
 
         let mut connection = strategy.connection().await?;
-        connection
-            .blocks_dal()
-            .insert_l2_block(&l2_block_header)
-            .await?;
+        if self.insert_block_notifications {
+            // The notification must be committed atomically with the header, which marks the L2 block as sealed.
+            let notification = BlockNotification::NewL2Block {
+                number: l2_block_header.number,
+                hash: l2_block_header.hash,
+                timestamp: l2_block_header.timestamp,
+            };
+            let mut transaction = connection.start_transaction().await?;
+            transaction
+                .blocks_dal()
+                .insert_l2_block(&l2_block_header)
+                .await?;
+            transaction
+                .outbox_dal()
+                .insert_notifications(&[notification])
+                .await?;
+            transaction.commit().await?;
+        } else {
+            connection
+                .blocks_dal()
+                .insert_l2_block(&l2_block_header)
+                .await?;
+        }
         progress.observe(None);
 
         // Report metrics.
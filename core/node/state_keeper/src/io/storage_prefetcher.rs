@@ -0,0 +1,222 @@
+//! Heuristic prefetching of storage slots touched by mempool transactions.
+
+use std::time::Duration;
+
+use multivm::interface::{L1BatchEnv, SystemEnv};
+use tokio::sync::watch;
+use zksync_state::ReadStorage;
+use zksync_types::{
+    get_code_key, get_nonce_key,
+    utils::{storage_key_for_eth_balance, storage_key_for_standard_token_balance},
+    AccountTreeId, Address, ExecuteTransactionCommon, L1BatchNumber, StorageKey, Transaction,
+};
+
+use super::{pre_executor::l2_tx_filter_for_batch, read_set_cache::ReadSetCache};
+use crate::{metrics::STORAGE_PREFETCH_METRICS, types::MempoolGuard};
+
+/// Selector of the ERC-20 `transfer(address,uint256)` function.
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// Prefetches storage slots that mempool transactions are likely to touch (nonces and base token balances
+/// of their initiators, balances of transferred ERC-20 tokens etc.) and caches their values in a [`ReadSetCache`],
+/// so that storage I/O is overlapped with VM execution in the batch executor.
+///
+/// Unlike [`MempoolPreExecutor`](crate::MempoolPreExecutor), the prefetcher doesn't execute transactions, so it's much
+/// cheaper, but only covers slots that can be predicted from the transaction itself. Like with pre-execution,
+/// prefetched values don't affect execution results since they are read from the same state the batch executor is based on.
+#[derive(Debug, Clone)]
+pub struct MempoolStoragePrefetcher {
+    mempool: MempoolGuard,
+    cache: ReadSetCache,
+    max_transactions_per_iteration: usize,
+    poll_interval: Duration,
+}
+
+impl MempoolStoragePrefetcher {
+    const DEFAULT_MAX_TRANSACTIONS_PER_ITERATION: usize = 128;
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Creates a prefetcher. The cache must not be shared with a [`MempoolPreExecutor`](crate::MempoolPreExecutor).
+    pub fn new(mempool: MempoolGuard, cache: ReadSetCache) -> Self {
+        Self {
+            mempool,
+            cache,
+            max_transactions_per_iteration: Self::DEFAULT_MAX_TRANSACTIONS_PER_ITERATION,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Sets the maximum number of mempool transactions considered for prefetching at once.
+    pub fn with_max_transactions_per_iteration(mut self, max_transactions: usize) -> Self {
+        self.max_transactions_per_iteration = max_transactions;
+        self
+    }
+
+    /// Sets the interval to wait for new mempool transactions if there's nothing to prefetch.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    pub fn cache(&self) -> &ReadSetCache {
+        &self.cache
+    }
+
+    /// Prefetches storage slots for mempool transactions that can be included into the specified L1 batch.
+    /// Returns once the stop signal is received, or the cache state version is advanced past the state this batch
+    /// is based on (i.e., once the batch is sealed).
+    ///
+    /// This method is blocking, so it should be run on a dedicated thread.
+    pub(crate) fn run_for_batch<S: ReadStorage>(
+        &self,
+        mut storage: S,
+        l1_batch_env: &L1BatchEnv,
+        system_env: &SystemEnv,
+        stop_receiver: &watch::Receiver<bool>,
+    ) {
+        let state_version = l1_batch_env.number - 1;
+        self.cache.advance_state_version(state_version);
+        let filter = l2_tx_filter_for_batch(l1_batch_env, system_env);
+        tracing::debug!(
+            "Starting prefetching storage for L1 batch #{}",
+            l1_batch_env.number
+        );
+
+        while !self.should_stop(state_version, stop_receiver) {
+            let transactions: Vec<_> = self
+                .mempool
+                .peek_l2_transactions(&filter, self.max_transactions_per_iteration)
+                .into_iter()
+                .filter(|tx| !self.cache.contains(tx.hash(), state_version))
+                .collect();
+            let mut is_idle = transactions.is_empty();
+
+            for tx in transactions {
+                if self.should_stop(state_version, stop_receiver) {
+                    break;
+                }
+                let latency = STORAGE_PREFETCH_METRICS.tx_latency.start();
+                let read_set: Vec<_> = predicted_storage_keys(&tx)
+                    .into_iter()
+                    .map(|key| (key, storage.read_value(&key)))
+                    .collect();
+                latency.observe();
+                STORAGE_PREFETCH_METRICS
+                    .prefetched_slots
+                    .inc_by(read_set.len() as u64);
+
+                if !self.cache.insert(tx.hash(), state_version, read_set) {
+                    // The cache is full (or outdated); wait until the batch executor consumes some read sets.
+                    is_idle = true;
+                    break;
+                }
+                STORAGE_PREFETCH_METRICS.prefetched_transactions.inc();
+            }
+
+            if is_idle {
+                std::thread::sleep(self.poll_interval);
+            }
+        }
+        tracing::debug!(
+            "Stopped prefetching storage for L1 batch #{}",
+            l1_batch_env.number
+        );
+    }
+
+    fn should_stop(
+        &self,
+        state_version: L1BatchNumber,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> bool {
+        *stop_receiver.borrow() || self.cache.state_version() > state_version
+    }
+}
+
+/// Returns storage keys the transaction is likely to read. The returned keys may contain duplicates.
+fn predicted_storage_keys(tx: &Transaction) -> Vec<StorageKey> {
+    let ExecuteTransactionCommon::L2(common_data) = &tx.common_data else {
+        return vec![];
+    };
+    let initiator = common_data.initiator_address;
+    let target = tx.execute.contract_address;
+    let mut keys = vec![
+        get_nonce_key(&initiator),
+        get_code_key(&initiator),
+        storage_key_for_eth_balance(&initiator),
+        get_code_key(&target),
+    ];
+
+    let paymaster = common_data.paymaster_params.paymaster;
+    if paymaster != Address::zero() {
+        keys.push(storage_key_for_eth_balance(&paymaster));
+    }
+    if !tx.execute.value.is_zero() {
+        keys.push(storage_key_for_eth_balance(&target));
+    }
+    if let Some(recipient) = erc20_transfer_recipient(&tx.execute.calldata) {
+        let token = AccountTreeId::new(target);
+        keys.push(storage_key_for_standard_token_balance(token, &initiator));
+        keys.push(storage_key_for_standard_token_balance(token, &recipient));
+    }
+    keys
+}
+
+fn erc20_transfer_recipient(calldata: &[u8]) -> Option<Address> {
+    let args = calldata.strip_prefix(&ERC20_TRANSFER_SELECTOR)?;
+    // The recipient is the first ABI-encoded argument, i.e. a left-padded 32-byte word.
+    let recipient_word = args.get(..32)?;
+    if args.len() < 64 || recipient_word[..12].iter().any(|&byte| byte != 0) {
+        return None;
+    }
+    Some(Address::from_slice(&recipient_word[12..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_node_test_utils::create_l2_transaction;
+    use zksync_types::{l2::L2Tx, H256, U256};
+
+    use super::*;
+
+    fn transfer_calldata(recipient: Address) -> Vec<u8> {
+        let mut calldata = ERC20_TRANSFER_SELECTOR.to_vec();
+        calldata.extend_from_slice(H256::from(recipient).as_bytes());
+        calldata.extend_from_slice(H256::from_low_u64_be(100).as_bytes());
+        calldata
+    }
+
+    #[test]
+    fn parsing_erc20_transfer_recipient() {
+        let recipient = Address::repeat_byte(0x23);
+        let calldata = transfer_calldata(recipient);
+        assert_eq!(erc20_transfer_recipient(&calldata), Some(recipient));
+        assert_eq!(erc20_transfer_recipient(&calldata[..40]), None);
+
+        let mut invalid_calldata = calldata.clone();
+        invalid_calldata[4] = 1; // Not a valid address encoding
+        assert_eq!(erc20_transfer_recipient(&invalid_calldata), None);
+        let mut other_calldata = calldata;
+        other_calldata[0] = 0;
+        assert_eq!(erc20_transfer_recipient(&other_calldata), None);
+    }
+
+    #[test]
+    fn predicting_storage_keys() {
+        let mut tx: L2Tx = create_l2_transaction(10, 100);
+        let initiator = tx.initiator_account();
+        let target = tx.execute.contract_address;
+        let keys = predicted_storage_keys(&tx.clone().into());
+        assert!(keys.contains(&get_nonce_key(&initiator)));
+        assert!(keys.contains(&storage_key_for_eth_balance(&initiator)));
+        assert!(!keys.contains(&storage_key_for_eth_balance(&target)));
+
+        tx.execute.value = U256::from(1);
+        let recipient = Address::repeat_byte(0x23);
+        tx.execute.calldata = transfer_calldata(recipient);
+        let keys = predicted_storage_keys(&tx.into());
+        assert!(keys.contains(&storage_key_for_eth_balance(&target)));
+        let token = AccountTreeId::new(target);
+        assert!(keys.contains(&storage_key_for_standard_token_balance(token, &initiator)));
+        assert!(keys.contains(&storage_key_for_standard_token_balance(token, &recipient)));
+    }
+}
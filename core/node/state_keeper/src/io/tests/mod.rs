@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::{collections::HashSet, time::Duration};
 
 use multivm::utils::derive_base_fee_and_gas_per_pubdata;
 use test_casing::test_casing;
@@ -24,7 +24,7 @@ use crate::{
     testonly::BASE_SYSTEM_CONTRACTS,
     tests::{create_execution_result, create_transaction, Query},
     updates::{L2BlockSealCommand, L2BlockUpdates, UpdatesManager},
-    StateKeeperOutputHandler, StateKeeperPersistence,
+    AddressDenylist, StateKeeperOutputHandler, StateKeeperPersistence,
 };
 
 mod tester;
@@ -285,6 +285,7 @@ async fn processing_storage_logs_when_sealing_l2_block() {
         protocol_version: Some(ProtocolVersionId::latest()),
         l2_shared_bridge_addr: Address::default(),
         pre_insert_txs: false,
+        insert_block_notifications: false,
     };
     connection_pool
         .connection()
@@ -374,6 +375,7 @@ async fn processing_events_when_sealing_l2_block() {
         protocol_version: Some(ProtocolVersionId::latest()),
         l2_shared_bridge_addr: Address::default(),
         pre_insert_txs: false,
+        insert_block_notifications: false,
     };
     pool.connection()
         .await
@@ -551,3 +553,97 @@ async fn different_timestamp_for_l2_blocks_in_same_batch(commitment_mode: L1Batc
         .expect("no new L2 block params");
     assert!(l2_block_params.timestamp > current_timestamp);
 }
+
+#[tokio::test]
+async fn rejecting_transactions_with_denylisted_addresses() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    let (mempool, mut guard) = tester.create_test_mempool_io(connection_pool).await;
+    let denied_tx = tester.insert_tx(&mut guard, 100, 800);
+    let denylist = AddressDenylist::new(HashSet::from([denied_tx.initiator_account()]));
+    let mut mempool = mempool.with_address_denylist(denylist);
+    let tx = mempool
+        .wait_for_next_tx(Duration::from_millis(100))
+        .await
+        .unwrap();
+    assert!(tx.is_none(), "{tx:?}");
+
+    let allowed_tx = tester.insert_tx(&mut guard, 100, 800);
+    let tx = mempool
+        .wait_for_next_tx(Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("no transaction");
+    assert_eq!(tx.hash(), allowed_tx.hash());
+}
+
+#[tokio::test]
+async fn aborting_pending_batch() {
+    let connection_pool = ConnectionPool::<Core>::test_pool().await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+    let mut storage = connection_pool.connection().await.unwrap();
+
+    let (mut mempool, mut guard) = tester.create_test_mempool_io(connection_pool.clone()).await;
+    let (cursor, _) = mempool.initialize().await.unwrap();
+    let tx = tester.insert_tx(&mut guard, 100, 800);
+    storage
+        .transactions_dal()
+        .insert_transaction_l2(&tx, TransactionExecutionMetrics::default())
+        .await
+        .unwrap();
+
+    mempool
+        .wait_for_new_batch_params(&cursor, Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("no batch params generated");
+    let executed_tx = mempool
+        .wait_for_next_tx(Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("no transaction");
+    assert_eq!(executed_tx.hash(), tx.hash());
+
+    mempool
+        .abort_pending_batch(cursor.l1_batch, vec![executed_tx])
+        .await
+        .unwrap();
+
+    let (new_cursor, pending_batch) = mempool.initialize().await.unwrap();
+    assert_eq!(new_cursor.next_l2_block, cursor.next_l2_block);
+    assert!(pending_batch.is_none());
+
+    // The transaction initiator must be stashed, so that the transaction is reloaded from the storage.
+    let mempool_info = guard.get_mempool_info();
+    assert_eq!(mempool_info.stashed_accounts, [tx.initiator_account()]);
+    let reloaded_txs = storage
+        .transactions_dal()
+        .sync_mempool(&mempool_info.stashed_accounts, &[], 0, 0, 100)
+        .await
+        .unwrap();
+    assert_eq!(reloaded_txs.len(), 1);
+    assert_eq!(reloaded_txs[0].hash(), tx.hash());
+}
+
+#[tokio::test]
+async fn switching_fee_account_for_new_batches() {
+    let connection_pool = ConnectionPool::<Core>::constrained_test_pool(1).await;
+    let tester = Tester::new(L1BatchCommitmentMode::Rollup);
+    tester.genesis(&connection_pool).await;
+
+    let (mut mempool, mut guard) = tester.create_test_mempool_io(connection_pool).await;
+    let (cursor, _) = mempool.initialize().await.unwrap();
+    tester.insert_tx(&mut guard, 100, 800);
+
+    let new_fee_account = Address::repeat_byte(0x42);
+    let prev_fee_account = mempool.fee_account_handle().set(new_fee_account);
+    assert_ne!(prev_fee_account, new_fee_account);
+    let l1_batch_params = mempool
+        .wait_for_new_batch_params(&cursor, Duration::from_secs(10))
+        .await
+        .unwrap()
+        .expect("no batch params generated");
+    assert_eq!(l1_batch_params.operator_address, new_fee_account);
+}
@@ -5,16 +5,21 @@ use std::{
 };
 
 use anyhow::Context as _;
-use multivm::interface::{Halt, L1BatchEnv, SystemEnv};
+use multivm::{
+    interface::{Halt, L1BatchEnv, SystemEnv},
+    tracers::ExecutionDeadline,
+};
 use tokio::sync::watch;
 use zksync_state::ReadStorageFactory;
 use zksync_types::{
-    block::L2BlockExecutionData, l2::TransactionType, protocol_upgrade::ProtocolUpgradeTx,
-    protocol_version::ProtocolVersionId, storage_writes_deduplicator::StorageWritesDeduplicator,
-    utils::display_timestamp, L1BatchNumber, Transaction,
+    api::AbortedL1Batch, block::L2BlockExecutionData, l2::TransactionType,
+    protocol_upgrade::ProtocolUpgradeTx, protocol_version::ProtocolVersionId,
+    storage_writes_deduplicator::StorageWritesDeduplicator, utils::display_timestamp,
+    L1BatchNumber, Transaction,
 };
 
 use super::{
+    abort::{L1BatchAbortError, L1BatchAbortReceiver},
     batch_executor::{BatchExecutor, BatchExecutorHandle, TxExecutionResult},
     io::{IoCursor, L1BatchParams, L2BlockParams, OutputHandler, PendingBatchData, StateKeeperIO},
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
@@ -34,6 +39,9 @@ pub(super) const POLL_WAIT_DURATION: Duration = Duration::from_secs(1);
 pub(super) enum Error {
     #[error("canceled")]
     Canceled,
+    /// The pending L1 batch was aborted, and the state keeper needs to be re-initialized.
+    #[error("L1 batch aborted")]
+    Restart,
     #[error(transparent)]
     Fatal(#[from] anyhow::Error),
 }
@@ -42,6 +50,7 @@ impl Error {
     fn context(self, msg: &'static str) -> Self {
         match self {
             Self::Canceled => Self::Canceled,
+            Self::Restart => Self::Restart,
             Self::Fatal(err) => Self::Fatal(err.context(msg)),
         }
     }
@@ -64,6 +73,7 @@ pub struct ZkSyncStateKeeper {
     batch_executor_base: Box<dyn BatchExecutor>,
     sealer: Arc<dyn ConditionalSealer>,
     storage_factory: Arc<dyn ReadStorageFactory>,
+    abort_receiver: Option<L1BatchAbortReceiver>,
 }
 
 impl ZkSyncStateKeeper {
@@ -82,16 +92,29 @@ impl ZkSyncStateKeeper {
             output_handler,
             sealer,
             storage_factory,
+            abort_receiver: None,
         }
     }
 
+    /// Allows aborting L1 batches using the [`L1BatchAbortHandle`](crate::L1BatchAbortHandle) paired
+    /// with the provided receiver.
+    pub fn with_l1_batch_abort_receiver(mut self, receiver: L1BatchAbortReceiver) -> Self {
+        self.abort_receiver = Some(receiver);
+        self
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
-        match self.run_inner().await {
-            Ok(_) => unreachable!(),
-            Err(Error::Fatal(err)) => Err(err).context("state_keeper failed"),
-            Err(Error::Canceled) => {
-                tracing::info!("Stop signal received, state keeper is shutting down");
-                Ok(())
+        loop {
+            match self.run_inner().await {
+                Ok(_) => unreachable!(),
+                Err(Error::Fatal(err)) => return Err(err).context("state_keeper failed"),
+                Err(Error::Canceled) => {
+                    tracing::info!("Stop signal received, state keeper is shutting down");
+                    return Ok(());
+                }
+                Err(Error::Restart) => {
+                    tracing::info!("Re-initializing state keeper after aborting L1 batch");
+                }
             }
         }
     }
@@ -491,6 +514,24 @@ impl ZkSyncStateKeeper {
         }
 
         while !self.is_canceled() {
+            let abort_request = self
+                .abort_receiver
+                .as_mut()
+                .and_then(L1BatchAbortReceiver::try_recv);
+            if let Some(request) = abort_request {
+                let l1_batch_number = updates_manager.l1_batch.number;
+                if updates_manager.has_sealed_l2_blocks() {
+                    tracing::info!(
+                        "Rejecting request to abort L1 batch #{l1_batch_number} since it has sealed L2 blocks"
+                    );
+                    request.respond(Err(L1BatchAbortError::HasSealedL2Blocks(l1_batch_number)));
+                } else {
+                    let aborted_batch = self.abort_l1_batch(updates_manager).await?;
+                    request.respond(Ok(aborted_batch));
+                    return Err(Error::Restart);
+                }
+            }
+
             if self
                 .io
                 .should_seal_l1_batch_unconditionally(updates_manager)
@@ -600,6 +641,37 @@ impl ZkSyncStateKeeper {
         Err(Error::Canceled)
     }
 
+    /// Aborts the currently open L1 batch, which must not have sealed L2 blocks. Transactions executed
+    /// in the open L2 block are returned to the I/O. Since nothing has been persisted for the batch yet,
+    /// no data needs to be removed from the storage.
+    async fn abort_l1_batch(
+        &mut self,
+        updates_manager: &UpdatesManager,
+    ) -> anyhow::Result<AbortedL1Batch> {
+        let l1_batch_number = updates_manager.l1_batch.number;
+        tracing::info!("Aborting L1 batch #{l1_batch_number}");
+        let transactions: Vec<_> = updates_manager
+            .l2_block
+            .executed_transactions
+            .iter()
+            .map(|tx| tx.transaction.clone())
+            .collect();
+        let transaction_count = transactions.len();
+        self.io
+            .abort_pending_batch(l1_batch_number, transactions)
+            .await
+            .with_context(|| format!("failed aborting L1 batch #{l1_batch_number}"))?;
+
+        KEEPER_METRICS.aborted_l1_batches.inc();
+        tracing::info!(
+            "Aborted L1 batch #{l1_batch_number}; {transaction_count} executed transactions are returned to I/O"
+        );
+        Ok(AbortedL1Batch {
+            l1_batch_number,
+            transaction_count,
+        })
+    }
+
     async fn process_upgrade_tx(
         &mut self,
         batch_executor: &mut BatchExecutorHandle,
@@ -710,8 +782,16 @@ impl ZkSyncStateKeeper {
                     SealResolution::ExcludeAndSeal
                 };
                 AGGREGATION_METRICS.inc(criterion, &resolution);
+                if resolution.should_seal() {
+                    updates_manager.l1_batch.seal_criterion = Some(criterion);
+                }
                 resolution
             }
+            TxExecutionResult::RejectedByVm { reason }
+                if ExecutionDeadline::is_exceeded(reason) =>
+            {
+                UnexecutableReason::ExecutionTimeout.into()
+            }
             TxExecutionResult::RejectedByVm { reason } => {
                 UnexecutableReason::Halt(reason.clone()).into()
             }
@@ -763,12 +843,14 @@ impl ZkSyncStateKeeper {
                     gas_count_from_writes(&tx_writes_metrics, updates_manager.protocol_version());
                 let tx_gas_excluding_writes = tx_l1_gas_this_tx;
 
+                let fair_pubdata_price = updates_manager.batch_fee_input().fair_pubdata_price();
                 let tx_data = SealData {
                     execution_metrics: tx_execution_metrics,
                     gas_count: tx_gas_excluding_writes + tx_writes_l1_gas,
                     cumulative_size: encoding_len,
                     writes_metrics: tx_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    fair_pubdata_price,
                 };
                 let block_data = SealData {
                     execution_metrics: tx_data.execution_metrics
@@ -780,16 +862,21 @@ impl ZkSyncStateKeeper {
                         + updates_manager.pending_txs_encoding_size(),
                     writes_metrics: block_writes_metrics,
                     gas_remaining: *gas_remaining,
+                    fair_pubdata_price,
                 };
 
-                self.sealer.should_seal_l1_batch(
+                let decision = self.sealer.should_seal_l1_batch(
                     updates_manager.l1_batch.number.0,
                     updates_manager.batch_timestamp() as u128 * 1_000,
                     updates_manager.pending_executed_transactions_len() + 1,
                     &block_data,
                     &tx_data,
                     updates_manager.protocol_version(),
-                )
+                );
+                if decision.resolution.should_seal() {
+                    updates_manager.l1_batch.seal_criterion = decision.criterion;
+                }
+                decision.resolution
             }
         };
         Ok((resolution, exec_result))
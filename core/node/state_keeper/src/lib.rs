@@ -9,12 +9,18 @@ use zksync_dal::{ConnectionPool, Core};
 use zksync_node_fee_model::BatchFeeModelInputProvider;
 use zksync_types::L2ChainId;
 
+use self::seal_criteria::SealCriterion;
 pub use self::{
+    abort::{L1BatchAbortError, L1BatchAbortHandle, L1BatchAbortReceiver},
     batch_executor::{
-        main_executor::MainBatchExecutor, BatchExecutor, BatchExecutorHandle, TxExecutionResult,
+        main_executor::MainBatchExecutor, BatchExecutor, BatchExecutorHandle, ExecutionResultCache,
+        TxExecutionResult,
     },
+    denylist::{AddressDenylist, AddressDenylistReloader},
+    fee_account::FeeAccountHandle,
     io::{
-        mempool::MempoolIO, L2BlockParams, L2BlockSealerTask, OutputHandler, StateKeeperIO,
+        mempool::MempoolIO, L2BlockParams, L2BlockSealerTask, MempoolPreExecutor,
+        MempoolStoragePrefetcher, OutputHandler, ReadSetCache, StateKeeperIO,
         StateKeeperOutputHandler, StateKeeperPersistence, TreeWritesPersistence,
     },
     keeper::ZkSyncStateKeeper,
@@ -25,7 +31,10 @@ pub use self::{
     updates::UpdatesManager,
 };
 
+mod abort;
 mod batch_executor;
+mod denylist;
+mod fee_account;
 pub mod io;
 mod keeper;
 mod mempool_actor;
@@ -50,11 +59,43 @@ pub async fn create_state_keeper(
     mempool: MempoolGuard,
     batch_fee_input_provider: Arc<dyn BatchFeeModelInputProvider>,
     output_handler: OutputHandler,
+    address_denylist: Option<AddressDenylist>,
+    custom_seal_criteria: Vec<Box<dyn SealCriterion>>,
     stop_receiver: watch::Receiver<bool>,
-) -> ZkSyncStateKeeper {
-    let batch_executor_base = MainBatchExecutor::new(state_keeper_config.save_call_traces, false);
+) -> (ZkSyncStateKeeper, FeeAccountHandle) {
+    let mut batch_executor_base =
+        MainBatchExecutor::new(state_keeper_config.save_call_traces, false);
+    if state_keeper_config.enable_opcode_profiling {
+        batch_executor_base = batch_executor_base.with_opcode_profiling();
+    }
+    if state_keeper_config.enable_gas_attribution {
+        batch_executor_base = batch_executor_base.with_gas_attribution();
+    }
+    if let Some(timeout) = state_keeper_config.max_tx_execution_time() {
+        batch_executor_base = batch_executor_base.with_tx_execution_timeout(timeout);
+    }
+    // The pre-executor and the prefetcher must not share a cache.
+    if state_keeper_config.enable_mempool_pre_execution {
+        let cache = ReadSetCache::new(ReadSetCache::DEFAULT_CAPACITY);
+        let mut pre_executor = MempoolPreExecutor::new(mempool.clone(), cache);
+        if state_keeper_config.enable_execution_result_cache {
+            let result_cache = ExecutionResultCache::new(ExecutionResultCache::DEFAULT_CAPACITY);
+            pre_executor = pre_executor.with_result_cache(result_cache.clone());
+            batch_executor_base = batch_executor_base.with_result_cache(result_cache);
+        }
+        batch_executor_base = batch_executor_base.with_pre_executor(pre_executor);
+    } else if state_keeper_config.enable_execution_result_cache {
+        tracing::warn!(
+            "Execution result cache is enabled without mempool pre-execution and will not be used"
+        );
+    }
+    if state_keeper_config.enable_storage_prefetching {
+        let cache = ReadSetCache::new(ReadSetCache::DEFAULT_CAPACITY);
+        batch_executor_base = batch_executor_base
+            .with_storage_prefetcher(MempoolStoragePrefetcher::new(mempool.clone(), cache));
+    }
 
-    let io = MempoolIO::new(
+    let mut io = MempoolIO::new(
         mempool,
         batch_fee_input_provider,
         pool,
@@ -65,15 +106,23 @@ pub async fn create_state_keeper(
     )
     .await
     .expect("Failed initializing main node I/O for state keeper");
+    if let Some(address_denylist) = address_denylist {
+        io = io.with_address_denylist(address_denylist);
+    }
 
-    let sealer = SequencerSealer::new(state_keeper_config);
+    let fee_account_handle = io.fee_account_handle();
+    let mut sealer = SequencerSealer::new(state_keeper_config);
+    for criterion in custom_seal_criteria {
+        sealer = sealer.with_criterion(criterion);
+    }
 
-    ZkSyncStateKeeper::new(
+    let state_keeper = ZkSyncStateKeeper::new(
         stop_receiver,
         Box::new(io),
         Box::new(batch_executor_base),
         output_handler,
         Arc::new(sealer),
         Arc::new(async_cache),
-    )
+    );
+    (state_keeper, fee_account_handle)
 }
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::Context as _;
 use multivm::utils::derive_base_fee_and_gas_per_pubdata;
@@ -39,6 +43,9 @@ pub struct MempoolFetcher {
     sync_interval: Duration,
     sync_batch_size: usize,
     stuck_tx_timeout: Option<Duration>,
+    tx_ttl: Option<Duration>,
+    max_nonce_gap: Option<u32>,
+    eviction_interval: Duration,
     #[cfg(test)]
     transaction_hashes_sender: mpsc::UnboundedSender<Vec<H256>>,
 }
@@ -57,6 +64,9 @@ impl MempoolFetcher {
             sync_interval: config.sync_interval(),
             sync_batch_size: config.sync_batch_size,
             stuck_tx_timeout: config.remove_stuck_txs.then(|| config.stuck_tx_timeout()),
+            tx_ttl: config.tx_ttl(),
+            max_nonce_gap: config.max_nonce_gap,
+            eviction_interval: config.eviction_interval(),
             #[cfg(test)]
             transaction_hashes_sender: mpsc::unbounded_channel().0,
         }
@@ -75,6 +85,7 @@ impl MempoolFetcher {
         storage.transactions_dal().reset_mempool().await?;
         drop(storage);
 
+        let mut last_eviction: Option<Instant> = None;
         loop {
             if *stop_receiver.borrow() {
                 tracing::info!("Stop signal received, mempool is shutting down");
@@ -82,7 +93,18 @@ impl MempoolFetcher {
             }
             let latency = KEEPER_METRICS.mempool_sync.start();
             let mut storage = self.pool.connection_tagged("state_keeper").await?;
+            if last_eviction.map_or(true, |ts| ts.elapsed() >= self.eviction_interval) {
+                self.evict_expired_transactions(&mut storage).await?;
+                last_eviction = Some(Instant::now());
+            }
             let mempool_info = self.mempool.get_mempool_info();
+            if !mempool_info.rejected_transactions.is_empty() {
+                storage
+                    .transactions_dal()
+                    .reset_mempool_transactions(&mempool_info.rejected_transactions)
+                    .await
+                    .context("failed returning rejected transactions from mempool")?;
+            }
             let protocol_version = storage
                 .blocks_dal()
                 .pending_protocol_version()
@@ -125,6 +147,63 @@ impl MempoolFetcher {
         }
         Ok(())
     }
+
+    /// Marks pending transactions exceeding the TTL or the nonce gap as expired, and evicts their initiators
+    /// from the mempool. The remaining transactions of these initiators will be reloaded on the next sync.
+    async fn evict_expired_transactions(
+        &mut self,
+        storage: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<()> {
+        let mut expired_tx_initiators = vec![];
+        if let Some(tx_ttl) = self.tx_ttl {
+            let initiators = storage
+                .transactions_dal()
+                .mark_stale_txs_as_expired(tx_ttl)
+                .await
+                .context("failed marking stale transactions as expired")?;
+            expired_tx_initiators.extend(initiators);
+        }
+
+        if let Some(max_nonce_gap) = self.max_nonce_gap {
+            let max_pending_nonces = storage
+                .transactions_dal()
+                .get_max_pending_l2_nonces()
+                .await
+                .context("failed getting max pending nonces")?;
+            let account_nonces =
+                get_account_nonces(storage, max_pending_nonces.keys().copied()).await?;
+            let max_allowed_nonces: HashMap<_, _> = max_pending_nonces
+                .into_iter()
+                .filter_map(|(address, max_pending_nonce)| {
+                    let account_nonce = account_nonces.get(&address).copied().unwrap_or(Nonce(0));
+                    let max_allowed_nonce = Nonce(account_nonce.0.saturating_add(max_nonce_gap));
+                    (max_pending_nonce > max_allowed_nonce).then_some((address, max_allowed_nonce))
+                })
+                .collect();
+            if !max_allowed_nonces.is_empty() {
+                let initiators = storage
+                    .transactions_dal()
+                    .mark_txs_as_expired_after_nonces(&max_allowed_nonces)
+                    .await
+                    .context("failed marking transactions with nonce gap as expired")?;
+                expired_tx_initiators.extend(initiators);
+            }
+        }
+
+        if !expired_tx_initiators.is_empty() {
+            tracing::info!(
+                "Marked {} pending transactions as expired",
+                expired_tx_initiators.len()
+            );
+            KEEPER_METRICS
+                .mempool_expired_txs
+                .inc_by(expired_tx_initiators.len() as u64);
+            let accounts: HashSet<_> = expired_tx_initiators.into_iter().collect();
+            let accounts: Vec<_> = accounts.into_iter().collect();
+            self.mempool.stash_accounts(&accounts);
+        }
+        Ok(())
+    }
 }
 
 /// Loads nonces for all distinct `transactions` initiators from the storage.
@@ -132,10 +211,20 @@ async fn get_transaction_nonces(
     storage: &mut Connection<'_, Core>,
     transactions: &[Transaction],
 ) -> anyhow::Result<HashMap<Address, Nonce>> {
-    let (nonce_keys, address_by_nonce_key): (Vec<_>, HashMap<_, _>) = transactions
-        .iter()
-        .map(|tx| {
-            let address = tx.initiator_account();
+    get_account_nonces(
+        storage,
+        transactions.iter().map(Transaction::initiator_account),
+    )
+    .await
+}
+
+/// Loads nonces for the specified accounts from the storage.
+async fn get_account_nonces(
+    storage: &mut Connection<'_, Core>,
+    accounts: impl Iterator<Item = Address>,
+) -> anyhow::Result<HashMap<Address, Nonce>> {
+    let (nonce_keys, address_by_nonce_key): (Vec<_>, HashMap<_, _>) = accounts
+        .map(|address| {
             let nonce_key = get_nonce_key(&address).hashed_key();
             (nonce_key, (nonce_key, address))
         })
@@ -158,12 +247,13 @@ async fn get_transaction_nonces(
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
     use zksync_node_fee_model::MockBatchFeeParamsProvider;
     use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
     use zksync_node_test_utils::create_l2_transaction;
     use zksync_types::{
-        fee::TransactionExecutionMetrics, L2BlockNumber, PriorityOpId, ProtocolVersionId,
-        StorageLog, H256,
+        api::TransactionStatus, fee::TransactionExecutionMetrics, L2BlockNumber, PriorityOpId,
+        ProtocolVersionId, StorageLog, H256,
     };
     use zksync_utils::u256_to_h256;
 
@@ -176,6 +266,14 @@ mod tests {
         stuck_tx_timeout: 0,
         remove_stuck_txs: false,
         delay_interval: 10,
+        fifo_ordering: false,
+        replacement_fee_bump_percent: None,
+        tx_ttl_sec: None,
+        max_nonce_gap: None,
+        eviction_interval_ms: None,
+        max_pending_txs_per_account: None,
+        address_denylist_path: None,
+        address_denylist_reload_interval_ms: None,
     };
 
     #[tokio::test]
@@ -371,4 +469,56 @@ mod tests {
         stop_sender.send_replace(true);
         fetcher_task.await.unwrap().expect("fetcher errored");
     }
+
+    #[tokio::test]
+    async fn evicting_transactions_with_nonce_gap() {
+        let pool = ConnectionPool::<Core>::test_pool().await;
+        let mut storage = pool.connection().await.unwrap();
+        insert_genesis_batch(&mut storage, &GenesisParams::mock())
+            .await
+            .unwrap();
+
+        let transaction = create_l2_transaction(10, 100);
+        let mut transaction_with_gap = create_l2_transaction(10, 100);
+        transaction_with_gap.common_data.initiator_address = transaction.initiator_account();
+        transaction_with_gap.common_data.nonce = Nonce(5);
+        for tx in [&transaction, &transaction_with_gap] {
+            storage
+                .transactions_dal()
+                .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+                .await
+                .unwrap();
+        }
+
+        let config = MempoolConfig {
+            max_nonce_gap: Some(2),
+            ..TEST_MEMPOOL_CONFIG
+        };
+        let mempool = MempoolGuard::new(PriorityOpId(0), 100);
+        let mut fetcher = MempoolFetcher::new(
+            mempool,
+            Arc::new(MockBatchFeeParamsProvider::default()),
+            &config,
+            pool.clone(),
+        );
+        fetcher
+            .evict_expired_transactions(&mut storage)
+            .await
+            .unwrap();
+
+        let details = storage
+            .transactions_web3_dal()
+            .get_transaction_details(transaction.hash())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(details.status, TransactionStatus::Pending);
+        let details = storage
+            .transactions_web3_dal()
+            .get_transaction_details(transaction_with_gap.hash())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(details.status, TransactionStatus::Expired);
+    }
 }
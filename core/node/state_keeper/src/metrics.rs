@@ -5,10 +5,10 @@ use std::{
     time::Duration,
 };
 
-use multivm::interface::{VmExecutionResultAndLogs, VmRevertReason};
+use multivm::interface::{OpcodeHistogram, VmExecutionResultAndLogs, VmRevertReason};
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LatencyObserver,
-    Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    LatencyObserver, Metrics,
 };
 use zksync_mempool::MempoolStore;
 use zksync_shared_metrics::InteractionType;
@@ -65,6 +65,10 @@ pub struct StateKeeperMetrics {
     /// Latency to synchronize the mempool with Postgres.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub mempool_sync: Histogram<Duration>,
+    /// Number of pending transactions marked as expired because of their age or nonce gap.
+    pub mempool_expired_txs: Counter,
+    /// Number of addresses in the address denylist.
+    pub denylisted_addresses: Gauge<usize>,
     /// Latency of the state keeper waiting for a transaction.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub waiting_for_tx: Histogram<Duration>,
@@ -89,6 +93,8 @@ pub struct StateKeeperMetrics {
     pub gas_price_too_high: Counter,
     /// Number of times blob base fee was reported as too high.
     pub blob_base_fee_too_high: Counter,
+    /// Number of L1 batches aborted by an operator request.
+    pub aborted_l1_batches: Counter,
 }
 
 fn vm_revert_reason_as_metric_label(reason: &VmRevertReason) -> &'static str {
@@ -265,6 +271,10 @@ pub(crate) struct L1BatchMetrics {
     /// Number of transactions in a single L1 batch.
     #[metrics(buckets = COUNT_BUCKETS)]
     pub transactions_in_l1_batch: Histogram<usize>,
+    /// Number of sealed L1 batches split by the seal criterion that has triggered sealing. Batches sealed
+    /// by I/O criteria (e.g., because of a timeout) are reported with the `io` criterion.
+    #[metrics(labels = ["criterion"])]
+    pub sealed_by_criterion: LabeledFamily<&'static str, Counter>,
     /// Total latency of sealing an L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub sealed_time: Histogram<Duration>,
@@ -438,11 +448,68 @@ pub(super) struct ExecutorMetrics {
     pub computational_gas_per_nanosecond: Histogram<f64>,
     #[metrics(buckets = GAS_PER_NANOSECOND_BUCKETS)]
     pub failed_tx_gas_limit_per_nanosecond: Histogram<f64>,
+    /// Number of executed opcodes split by the opcode kind. Only reported if opcode profiling is enabled.
+    #[metrics(labels = ["opcode"])]
+    executed_opcodes: LabeledFamily<String, Counter>,
+    /// Computational gas charged for executed opcodes split by the opcode kind. Only reported
+    /// if opcode profiling is enabled.
+    #[metrics(labels = ["opcode"])]
+    opcode_gas: LabeledFamily<String, Counter>,
+}
+
+impl ExecutorMetrics {
+    pub fn observe_opcode_histogram(&self, histogram: &OpcodeHistogram) {
+        for (kind, stat) in histogram.iter() {
+            let label = format!("{kind:?}");
+            self.executed_opcodes[&label].inc_by(stat.count);
+            self.opcode_gas[&label].inc_by(stat.gas_used);
+        }
+    }
 }
 
 #[vise::register]
 pub(super) static EXECUTOR_METRICS: vise::Global<ExecutorMetrics> = vise::Global::new();
 
+/// Metrics related to speculative pre-execution of mempool transactions.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "state_keeper_pre_execution")]
+pub(crate) struct PreExecutionMetrics {
+    /// Latency of pre-executing a single transaction.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub tx_latency: Histogram<Duration>,
+    /// Number of pre-executed transactions.
+    pub executed_transactions: Counter,
+    /// Number of transactions executed by the batch executor with a cached read set.
+    pub cache_hits: Counter,
+    /// Number of transactions executed by the batch executor without a cached read set.
+    pub cache_misses: Counter,
+    /// Number of transactions rejected by the batch executor based on a cached execution result.
+    pub result_cache_hits: Counter,
+}
+
+#[vise::register]
+pub(crate) static PRE_EXECUTION_METRICS: vise::Global<PreExecutionMetrics> = vise::Global::new();
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "state_keeper_storage_prefetch")]
+pub(crate) struct StoragePrefetchMetrics {
+    /// Latency of prefetching storage slots for a single transaction.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub tx_latency: Histogram<Duration>,
+    /// Number of transactions with prefetched storage slots.
+    pub prefetched_transactions: Counter,
+    /// Total number of prefetched storage slots.
+    pub prefetched_slots: Counter,
+    /// Number of transactions executed by the batch executor with prefetched storage slots.
+    pub cache_hits: Counter,
+    /// Number of transactions executed by the batch executor without prefetched storage slots.
+    pub cache_misses: Counter,
+}
+
+#[vise::register]
+pub(crate) static STORAGE_PREFETCH_METRICS: vise::Global<StoragePrefetchMetrics> =
+    vise::Global::new();
+
 #[derive(Debug, Metrics)]
 #[metrics(prefix = "batch_tip")]
 pub(crate) struct BatchTipMetrics {
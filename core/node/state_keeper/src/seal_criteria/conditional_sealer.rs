@@ -31,11 +31,27 @@ pub trait ConditionalSealer: 'static + fmt::Debug + Send + Sync {
         block_data: &SealData,
         tx_data: &SealData,
         protocol_version: ProtocolVersionId,
-    ) -> SealResolution;
+    ) -> SealDecision;
+}
+
+/// Decision returned by [`ConditionalSealer::should_seal_l1_batch()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SealDecision {
+    pub resolution: SealResolution,
+    /// Name of the criterion that has triggered the `resolution`. Always `None` for [`SealResolution::NoSeal`].
+    pub criterion: Option<&'static str>,
+}
+
+impl SealDecision {
+    const NO_SEAL: Self = Self {
+        resolution: SealResolution::NoSeal,
+        criterion: None,
+    };
 }
 
 /// Implementation of [`ConditionalSealer`] used by the main node.
 /// Internally uses a set of [`SealCriterion`]s to determine whether the batch should be sealed.
+/// Besides the default criteria, custom criteria can be registered with [`Self::with_criterion()`].
 ///
 /// The checks are deterministic, i.e., should depend solely on execution metrics and [`StateKeeperConfig`].
 /// Non-deterministic seal criteria are expressed using [`IoSealCriteria`](super::IoSealCriteria).
@@ -78,14 +94,14 @@ impl ConditionalSealer for SequencerSealer {
         block_data: &SealData,
         tx_data: &SealData,
         protocol_version: ProtocolVersionId,
-    ) -> SealResolution {
+    ) -> SealDecision {
         tracing::trace!(
             "Determining seal resolution for L1 batch #{l1_batch_number} with {tx_count} transactions \
              and metrics {:?}",
             block_data.execution_metrics
         );
 
-        let mut final_seal_resolution = SealResolution::NoSeal;
+        let mut decision = SealDecision::NO_SEAL;
         for sealer in &self.sealers {
             let seal_resolution = sealer.should_seal(
                 &self.config,
@@ -108,9 +124,15 @@ impl ConditionalSealer for SequencerSealer {
                 SealResolution::NoSeal => { /* Don't do anything */ }
             }
 
-            final_seal_resolution = final_seal_resolution.stricter(seal_resolution);
+            let stricter_resolution = decision.resolution.clone().stricter(seal_resolution);
+            if stricter_resolution != decision.resolution {
+                decision = SealDecision {
+                    resolution: stricter_resolution,
+                    criterion: Some(sealer.prom_criterion_name()),
+                };
+            }
         }
-        final_seal_resolution
+        decision
     }
 }
 
@@ -120,6 +142,13 @@ impl SequencerSealer {
         Self { config, sealers }
     }
 
+    /// Registers an additional seal criterion. Criteria are checked in the order of registration,
+    /// after the default ones.
+    pub fn with_criterion(mut self, criterion: Box<dyn SealCriterion>) -> Self {
+        self.sealers.push(criterion);
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn with_sealers(
         config: StateKeeperConfig,
@@ -129,7 +158,7 @@ impl SequencerSealer {
     }
 
     fn default_sealers(config: &StateKeeperConfig) -> Vec<Box<dyn SealCriterion>> {
-        vec![
+        let mut sealers: Vec<Box<dyn SealCriterion>> = vec![
             Box::new(criteria::SlotsCriterion),
             Box::new(criteria::GasCriterion),
             Box::new(criteria::PubDataBytesCriterion {
@@ -138,7 +167,13 @@ impl SequencerSealer {
             Box::new(criteria::CircuitsCriterion),
             Box::new(criteria::TxEncodingSizeCriterion),
             Box::new(criteria::GasForBatchTipCriterion),
-        ]
+        ];
+        if let Some(max_pubdata_cost_per_batch) = config.max_pubdata_cost_per_batch {
+            sealers.push(Box::new(criteria::PubdataCostCriterion {
+                max_pubdata_cost_per_batch,
+            }));
+        }
+        sealers
     }
 }
 
@@ -165,7 +200,7 @@ impl ConditionalSealer for NoopSealer {
         _block_data: &SealData,
         _tx_data: &SealData,
         _protocol_version: ProtocolVersionId,
-    ) -> SealResolution {
-        SealResolution::NoSeal
+    ) -> SealDecision {
+        SealDecision::NO_SEAL
     }
 }
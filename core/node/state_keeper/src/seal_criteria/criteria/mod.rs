@@ -2,11 +2,14 @@ mod gas;
 mod gas_for_batch_tip;
 mod geometry_seal_criteria;
 mod pubdata_bytes;
+mod pubdata_cost;
 mod slots;
 mod tx_encoding_size;
 
+pub use self::pubdata_bytes::DaBlobSizeCriterion;
 pub(crate) use self::{
     gas::GasCriterion, gas_for_batch_tip::GasForBatchTipCriterion,
     geometry_seal_criteria::CircuitsCriterion, pubdata_bytes::PubDataBytesCriterion,
-    slots::SlotsCriterion, tx_encoding_size::TxEncodingSizeCriterion,
+    pubdata_cost::PubdataCostCriterion, slots::SlotsCriterion,
+    tx_encoding_size::TxEncodingSizeCriterion,
 };
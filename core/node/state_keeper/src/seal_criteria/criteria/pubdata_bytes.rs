@@ -24,39 +24,13 @@ impl SealCriterion for PubDataBytesCriterion {
         tx_data: &SealData,
         protocol_version: ProtocolVersionId,
     ) -> SealResolution {
-        let max_pubdata_per_l1_batch = self.max_pubdata_per_batch as usize;
-        let reject_bound =
-            (max_pubdata_per_l1_batch as f64 * config.reject_tx_at_eth_params_percentage).round();
-        let include_and_seal_bound =
-            (max_pubdata_per_l1_batch as f64 * config.close_block_at_eth_params_percentage).round();
-
-        let block_size =
-            block_data.execution_metrics.size() + block_data.writes_metrics.size(protocol_version);
-        // For backward compatibility, we need to keep calculating the size of the pubdata based
-        // `StorageDeduplication` metrics. All vm versions
-        // after vm with virtual blocks will provide the size of the pubdata in the execution metrics.
-        let tx_size = if tx_data.execution_metrics.pubdata_published == 0 {
-            tx_data.execution_metrics.size() + tx_data.writes_metrics.size(protocol_version)
-        } else {
-            tx_data.execution_metrics.pubdata_published as usize
-        };
-        if tx_size + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into())
-            > reject_bound as usize
-        {
-            UnexecutableReason::PubdataLimit.into()
-        } else if block_size
-            + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into())
-            > max_pubdata_per_l1_batch
-        {
-            SealResolution::ExcludeAndSeal
-        } else if block_size
-            + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into())
-            > include_and_seal_bound as usize
-        {
-            SealResolution::IncludeAndSeal
-        } else {
-            SealResolution::NoSeal
-        }
+        seal_by_pubdata_size(
+            self.max_pubdata_per_batch as usize,
+            config,
+            block_data,
+            tx_data,
+            protocol_version,
+        )
     }
 
     fn prom_criterion_name(&self) -> &'static str {
@@ -64,6 +38,82 @@ impl SealCriterion for PubDataBytesCriterion {
     }
 }
 
+/// Seals L1 batches so that their pubdata fits into a single blob of the DA layer the pubdata is dispatched to.
+/// Unlike [`PubDataBytesCriterion`], the limit is set by the DA layer rather than by the L1 commitment mode.
+#[derive(Debug)]
+pub struct DaBlobSizeCriterion {
+    blob_size_limit: usize,
+}
+
+impl DaBlobSizeCriterion {
+    pub fn new(blob_size_limit: usize) -> Self {
+        Self { blob_size_limit }
+    }
+}
+
+impl SealCriterion for DaBlobSizeCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        _tx_count: usize,
+        block_data: &SealData,
+        tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        seal_by_pubdata_size(
+            self.blob_size_limit,
+            config,
+            block_data,
+            tx_data,
+            protocol_version,
+        )
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "da_blob_size"
+    }
+}
+
+fn seal_by_pubdata_size(
+    max_pubdata_per_l1_batch: usize,
+    config: &StateKeeperConfig,
+    block_data: &SealData,
+    tx_data: &SealData,
+    protocol_version: ProtocolVersionId,
+) -> SealResolution {
+    let reject_bound =
+        (max_pubdata_per_l1_batch as f64 * config.reject_tx_at_eth_params_percentage).round();
+    let include_and_seal_bound =
+        (max_pubdata_per_l1_batch as f64 * config.close_block_at_eth_params_percentage).round();
+
+    let block_size =
+        block_data.execution_metrics.size() + block_data.writes_metrics.size(protocol_version);
+    // For backward compatibility, we need to keep calculating the size of the pubdata based
+    // `StorageDeduplication` metrics. All vm versions
+    // after vm with virtual blocks will provide the size of the pubdata in the execution metrics.
+    let tx_size = if tx_data.execution_metrics.pubdata_published == 0 {
+        tx_data.execution_metrics.size() + tx_data.writes_metrics.size(protocol_version)
+    } else {
+        tx_data.execution_metrics.pubdata_published as usize
+    };
+    if tx_size + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into())
+        > reject_bound as usize
+    {
+        UnexecutableReason::PubdataLimit.into()
+    } else if block_size + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into())
+        > max_pubdata_per_l1_batch
+    {
+        SealResolution::ExcludeAndSeal
+    } else if block_size + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into())
+        > include_and_seal_bound as usize
+    {
+        SealResolution::IncludeAndSeal
+    } else {
+        SealResolution::NoSeal
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use zksync_types::tx::ExecutionMetrics;
@@ -146,4 +196,53 @@ mod tests {
         );
         assert_eq!(full_block_resolution, SealResolution::ExcludeAndSeal);
     }
+
+    #[test]
+    fn da_blob_size_criterion() {
+        let config = StateKeeperConfig {
+            reject_tx_at_eth_params_percentage: 0.95,
+            close_block_at_eth_params_percentage: 0.95,
+            max_pubdata_per_batch: 100000,
+            ..Default::default()
+        };
+        let criterion = DaBlobSizeCriterion::new(10000);
+
+        // The batch fits into the L1 pubdata limit, but not into a DA blob.
+        let block_execution_metrics = ExecutionMetrics {
+            l2_l1_long_messages: 10001,
+            ..ExecutionMetrics::default()
+        };
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            0,
+            &SealData {
+                execution_metrics: block_execution_metrics,
+                ..SealData::default()
+            },
+            &SealData::default(),
+            ProtocolVersionId::latest(),
+        );
+        assert_eq!(resolution, SealResolution::ExcludeAndSeal);
+
+        let tx_execution_metrics = ExecutionMetrics {
+            pubdata_published: 10000,
+            ..ExecutionMetrics::default()
+        };
+        let resolution = criterion.should_seal(
+            &config,
+            0,
+            0,
+            &SealData::default(),
+            &SealData {
+                execution_metrics: tx_execution_metrics,
+                ..SealData::default()
+            },
+            ProtocolVersionId::latest(),
+        );
+        assert!(
+            matches!(resolution, SealResolution::Unexecutable(_)),
+            "{resolution:?}"
+        );
+    }
 }
@@ -0,0 +1,108 @@
+use multivm::utils::execution_metrics_bootloader_batch_tip_overhead;
+use zksync_types::ProtocolVersionId;
+
+use crate::seal_criteria::{SealCriterion, SealData, SealResolution, StateKeeperConfig};
+
+/// Seals the batch if the L1 cost of publishing its pubdata exceeds the configured budget.
+///
+/// Unlike [`PubDataBytesCriterion`](super::PubDataBytesCriterion), the limit is expressed in wei, and the cost
+/// is estimated using the fair pubdata price of the batch. Thus, the criterion reacts to the cost of the used
+/// DA solution; e.g., batches are sealed earlier if calldata is used and L1 gas is expensive.
+#[derive(Debug)]
+pub struct PubdataCostCriterion {
+    pub max_pubdata_cost_per_batch: u64,
+}
+
+impl SealCriterion for PubdataCostCriterion {
+    fn should_seal(
+        &self,
+        config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        tx_count: usize,
+        block_data: &SealData,
+        _tx_data: &SealData,
+        protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        let fair_pubdata_price = block_data.fair_pubdata_price;
+        if fair_pubdata_price == 0 {
+            // Either the price is unknown, or publishing pubdata is free.
+            return SealResolution::NoSeal;
+        }
+
+        let max_cost = u128::from(self.max_pubdata_cost_per_batch);
+        let include_and_seal_bound =
+            (max_cost as f64 * config.close_block_at_eth_params_percentage).round() as u128;
+        let block_size = block_data.execution_metrics.size()
+            + block_data.writes_metrics.size(protocol_version)
+            + execution_metrics_bootloader_batch_tip_overhead(protocol_version.into());
+        let block_cost = block_size as u128 * u128::from(fair_pubdata_price);
+
+        if block_cost > max_cost {
+            // A transaction cannot be rejected based on the cost since the pubdata price changes over time.
+            // Instead, a batch consisting of a single expensive transaction is sealed right away.
+            if tx_count > 1 {
+                SealResolution::ExcludeAndSeal
+            } else {
+                SealResolution::IncludeAndSeal
+            }
+        } else if block_cost > include_and_seal_bound {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "pubdata_cost"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::tx::ExecutionMetrics;
+
+    use super::*;
+
+    fn block_data(pubdata_size: usize, fair_pubdata_price: u64) -> SealData {
+        let overhead =
+            execution_metrics_bootloader_batch_tip_overhead(ProtocolVersionId::latest().into());
+        SealData {
+            execution_metrics: ExecutionMetrics {
+                l2_l1_long_messages: pubdata_size - overhead,
+                ..ExecutionMetrics::default()
+            },
+            fair_pubdata_price,
+            ..SealData::default()
+        }
+    }
+
+    #[test]
+    fn seal_criterion() {
+        let config = StateKeeperConfig {
+            close_block_at_eth_params_percentage: 0.9,
+            ..Default::default()
+        };
+        let criterion = PubdataCostCriterion {
+            max_pubdata_cost_per_batch: 1_000_000,
+        };
+        let should_seal = |pubdata_size, fair_pubdata_price, tx_count| {
+            criterion.should_seal(
+                &config,
+                0,
+                tx_count,
+                &block_data(pubdata_size, fair_pubdata_price),
+                &SealData::default(),
+                ProtocolVersionId::latest(),
+            )
+        };
+
+        assert_eq!(should_seal(50_000, 10, 10), SealResolution::NoSeal);
+        assert_eq!(should_seal(95_000, 10, 10), SealResolution::IncludeAndSeal);
+        assert_eq!(should_seal(100_001, 10, 10), SealResolution::ExcludeAndSeal);
+        assert_eq!(should_seal(100_001, 10, 1), SealResolution::IncludeAndSeal);
+        // The same amount of pubdata is fine if it's cheaper to publish.
+        assert_eq!(should_seal(100_001, 1, 10), SealResolution::NoSeal);
+        // Free pubdata never leads to sealing.
+        assert_eq!(should_seal(10_000_000, 0, 10), SealResolution::NoSeal);
+    }
+}
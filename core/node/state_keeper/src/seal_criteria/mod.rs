@@ -19,14 +19,17 @@ use zksync_types::{
     fee::TransactionExecutionMetrics,
     tx::tx_execution_info::{DeduplicatedWritesMetrics, ExecutionMetrics},
     utils::display_timestamp,
-    ProtocolVersionId, Transaction,
+    Address, ProtocolVersionId, Transaction,
 };
 use zksync_utils::time::millis_since;
 
 mod conditional_sealer;
 pub(super) mod criteria;
 
-pub use self::conditional_sealer::{ConditionalSealer, NoopSealer, SequencerSealer};
+pub use self::{
+    conditional_sealer::{ConditionalSealer, NoopSealer, SealDecision, SequencerSealer},
+    criteria::DaBlobSizeCriterion,
+};
 use super::{
     metrics::AGGREGATION_METRICS,
     updates::UpdatesManager,
@@ -69,6 +72,8 @@ pub enum UnexecutableReason {
     OutOfGasForBatchTip,
     BootloaderOutOfGas,
     NotEnoughGasProvided,
+    DeniedAddress(Address),
+    ExecutionTimeout,
 }
 
 impl UnexecutableReason {
@@ -83,6 +88,8 @@ impl UnexecutableReason {
             UnexecutableReason::OutOfGasForBatchTip => "OutOfGasForBatchTip",
             UnexecutableReason::BootloaderOutOfGas => "BootloaderOutOfGas",
             UnexecutableReason::NotEnoughGasProvided => "NotEnoughGasProvided",
+            UnexecutableReason::DeniedAddress(_) => "DeniedAddress",
+            UnexecutableReason::ExecutionTimeout => "ExecutionTimeout",
         }
     }
 }
@@ -107,6 +114,10 @@ impl fmt::Display for UnexecutableReason {
             UnexecutableReason::OutOfGasForBatchTip => write!(f, "Out of gas for batch tip"),
             UnexecutableReason::BootloaderOutOfGas => write!(f, "Bootloader out of gas"),
             UnexecutableReason::NotEnoughGasProvided => write!(f, "Not enough gas provided"),
+            UnexecutableReason::DeniedAddress(address) => {
+                write!(f, "Address {address:?} is denylisted")
+            }
+            UnexecutableReason::ExecutionTimeout => write!(f, "Transaction execution timed out"),
         }
     }
 }
@@ -164,6 +175,7 @@ pub struct SealData {
     pub(super) cumulative_size: usize,
     pub(super) writes_metrics: DeduplicatedWritesMetrics,
     pub(super) gas_remaining: u32,
+    pub(super) fair_pubdata_price: u64,
 }
 
 impl SealData {
@@ -184,11 +196,46 @@ impl SealData {
             cumulative_size: transaction.bootloader_encoding_size(),
             writes_metrics,
             gas_remaining: tx_metrics.gas_remaining,
+            fair_pubdata_price: 0,
         }
     }
+
+    /// Returns execution metrics of the transaction or the L1 batch.
+    pub fn execution_metrics(&self) -> &ExecutionMetrics {
+        &self.execution_metrics
+    }
+
+    /// Returns the L1 gas required to commit, prove and execute the transaction or the L1 batch.
+    pub fn gas_count(&self) -> &BlockGasCount {
+        &self.gas_count
+    }
+
+    /// Returns the bootloader encoding size of the transaction or of all transactions in the L1 batch.
+    pub fn cumulative_size(&self) -> usize {
+        self.cumulative_size
+    }
+
+    /// Returns metrics of deduplicated storage writes.
+    pub fn writes_metrics(&self) -> &DeduplicatedWritesMetrics {
+        &self.writes_metrics
+    }
+
+    /// Returns the amount of gas remaining in the bootloader after executing the transaction.
+    pub fn gas_remaining(&self) -> u32 {
+        self.gas_remaining
+    }
+
+    /// Returns the fair pubdata price (in wei per byte) of the L1 batch, or 0 if it is unknown
+    /// (e.g., if the data is created for a transaction outside of an L1 batch).
+    pub fn fair_pubdata_price(&self) -> u64 {
+        self.fair_pubdata_price
+    }
 }
 
-pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
+/// Deterministic criterion used by [`SequencerSealer`] to decide whether an L1 batch should be sealed
+/// after executing a transaction. Besides the built-in criteria, [`SequencerSealer`] can be extended
+/// with custom ones using [`SequencerSealer::with_criterion()`].
+pub trait SealCriterion: fmt::Debug + Send + Sync + 'static {
     fn should_seal(
         &self,
         config: &StateKeeperConfig,
@@ -199,6 +246,7 @@ pub(super) trait SealCriterion: fmt::Debug + Send + Sync + 'static {
         protocol_version: ProtocolVersionId,
     ) -> SealResolution;
 
+    /// Name of the criterion used in metrics and persisted for L1 batches sealed because of it.
     // We need self here only for rust restrictions for creating an object from trait
     // https://doc.rust-lang.org/reference/items/traits.html#object-safety
     fn prom_criterion_name(&self) -> &'static str;
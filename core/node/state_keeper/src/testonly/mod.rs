@@ -33,6 +33,8 @@ pub(super) fn default_vm_batch_result() -> FinishedL1Batch {
             logs: VmExecutionLogs::default(),
             statistics: VmExecutionStatistics::default(),
             refunds: Refunds::default(),
+            call_traces: None,
+            gas_by_contract: None,
         },
         final_execution_state: CurrentExecutionState {
             events: vec![],
@@ -60,6 +62,8 @@ pub(crate) fn successful_exec() -> TxExecutionResult {
             logs: Default::default(),
             statistics: Default::default(),
             refunds: Default::default(),
+            call_traces: None,
+            gas_by_contract: None,
         }),
         tx_metrics: Box::new(ExecutionMetricsForCriteria {
             l1_gas: Default::default(),
@@ -260,6 +260,8 @@ pub(crate) fn successful_exec_with_metrics(
             logs: Default::default(),
             statistics: Default::default(),
             refunds: Default::default(),
+            call_traces: None,
+            gas_by_contract: None,
         }),
         tx_metrics: Box::new(tx_metrics),
         compressed_bytecodes: vec![],
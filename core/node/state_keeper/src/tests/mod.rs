@@ -32,7 +32,7 @@ use crate::{
     keeper::POLL_WAIT_DURATION,
     seal_criteria::{
         criteria::{GasCriterion, SlotsCriterion},
-        SequencerSealer, UnexecutableReason,
+        SealCriterion, SealData, SealResolution, SequencerSealer, UnexecutableReason,
     },
     testonly::{
         successful_exec,
@@ -60,6 +60,10 @@ pub(crate) fn pending_batch_data(pending_l2_blocks: Vec<L2BlockExecutionData>) -
             execution_mode: TxExecutionMode::VerifyExecute,
             default_validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
             chain_id: L2ChainId::from(270),
+            enable_opcode_profiling: false,
+            enable_call_tracing: false,
+            enable_gas_attribution: false,
+            enable_storage_reads_log: false,
         },
         pending_l2_blocks,
     }
@@ -74,6 +78,10 @@ pub(super) fn default_system_env() -> SystemEnv {
         execution_mode: TxExecutionMode::VerifyExecute,
         default_validation_computational_gas_limit: BATCH_COMPUTATIONAL_GAS_LIMIT,
         chain_id: L2ChainId::from(270),
+        enable_opcode_profiling: false,
+        enable_call_tracing: false,
+        enable_gas_attribution: false,
+        enable_storage_reads_log: false,
     }
 }
 
@@ -128,6 +136,7 @@ pub(super) fn create_execution_result(
             system_l2_to_l1_logs: vec![],
             user_l2_to_l1_logs: vec![],
             storage_logs,
+            storage_reads: None,
             total_log_queries_count: total_log_queries,
         },
         statistics: VmExecutionStatistics {
@@ -139,8 +148,12 @@ pub(super) fn create_execution_result(
             total_log_queries,
             pubdata_published: 0,
             circuit_statistic: Default::default(),
+            circuit_cycles: Default::default(),
+            opcode_histogram: None,
         },
         refunds: Refunds::default(),
+        call_traces: None,
+        gas_by_contract: None,
     }
 }
 
@@ -198,7 +211,54 @@ async fn sealed_by_number_of_txs() {
         .l2_block_sealed("L2 block 1")
         .next_tx("Second tx", random_tx(2), successful_exec())
         .l2_block_sealed("L2 block 2")
-        .batch_sealed("Batch 1")
+        .batch_sealed_with("Batch 1", |updates| {
+            assert_eq!(updates.l1_batch.seal_criterion, Some("slots"));
+        })
+        .run(sealer)
+        .await;
+}
+
+/// Custom criterion sealing the batch after the specified number of transactions.
+#[derive(Debug)]
+struct TxCountCriterion(usize);
+
+impl SealCriterion for TxCountCriterion {
+    fn should_seal(
+        &self,
+        _config: &StateKeeperConfig,
+        _block_open_timestamp_ms: u128,
+        tx_count: usize,
+        _block_data: &SealData,
+        _tx_data: &SealData,
+        _protocol_version: ProtocolVersionId,
+    ) -> SealResolution {
+        if tx_count >= self.0 {
+            SealResolution::IncludeAndSeal
+        } else {
+            SealResolution::NoSeal
+        }
+    }
+
+    fn prom_criterion_name(&self) -> &'static str {
+        "tx_count"
+    }
+}
+
+#[tokio::test]
+async fn sealed_by_custom_criterion() {
+    let sealer = SequencerSealer::default().with_criterion(Box::new(TxCountCriterion(3)));
+
+    TestScenario::new()
+        .seal_l2_block_when(|updates| updates.l2_block.executed_transactions.len() == 4)
+        .next_tx("First tx", random_tx(1), successful_exec())
+        .next_tx("Second tx", random_tx(2), successful_exec())
+        .next_tx("Third tx", random_tx(3), successful_exec())
+        .l2_block_sealed_with("L2 block with three txs", |updates| {
+            assert_eq!(updates.l2_block.executed_transactions.len(), 3);
+        })
+        .batch_sealed_with("Batch 1", |updates| {
+            assert_eq!(updates.l1_batch.seal_criterion, Some("tx_count"));
+        })
         .run(sealer)
         .await;
 }
@@ -4,10 +4,11 @@ use std::{
 };
 
 use multivm::interface::VmExecutionResultAndLogs;
+use zksync_config::configs::chain::MempoolConfig;
 use zksync_dal::{Connection, Core, CoreDal};
-use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use zksync_mempool::{L2TxFilter, MempoolInfo, MempoolOrdering, MempoolStore};
 use zksync_types::{
-    block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
+    block::BlockGasCount, l1::L1Tx, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
 };
 
 use super::{
@@ -19,12 +20,28 @@ use super::{
 pub struct MempoolGuard(Arc<Mutex<MempoolStore>>);
 
 impl MempoolGuard {
-    pub async fn from_storage(storage_processor: &mut Connection<'_, Core>, capacity: u64) -> Self {
+    pub async fn from_storage(
+        storage_processor: &mut Connection<'_, Core>,
+        config: &MempoolConfig,
+    ) -> Self {
         let next_priority_id = storage_processor
             .transactions_dal()
             .next_priority_id()
             .await;
-        Self::new(next_priority_id, capacity)
+        let ordering = if config.fifo_ordering {
+            MempoolOrdering::Fifo
+        } else {
+            MempoolOrdering::PriorityFee
+        };
+        let store = MempoolStore::new(next_priority_id, config.capacity)
+            .with_ordering(ordering)
+            .with_replacement_fee_bump(config.replacement_fee_bump_percent)
+            .with_max_transactions_per_account(
+                config
+                    .max_pending_txs_per_account
+                    .map(|limit| limit as usize),
+            );
+        Self(Arc::new(Mutex::new(store)))
     }
 
     pub(super) fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
@@ -53,6 +70,31 @@ impl MempoolGuard {
             .next_transaction(filter)
     }
 
+    /// Same as [`Self::next_transaction()`], but only returns the next L1 priority operation if it satisfies
+    /// the provided predicate. Otherwise, the next L2 transaction is returned (if any).
+    pub fn next_transaction_with(
+        &mut self,
+        filter: &L2TxFilter,
+        include_priority_op: impl FnOnce(&L1Tx) -> bool,
+    ) -> Option<Transaction> {
+        let mut mempool = self.0.lock().expect("failed to acquire mempool lock");
+        match mempool.next_priority_op() {
+            Some(priority_op) if !include_priority_op(priority_op) => {
+                mempool.next_l2_transaction(filter)
+            }
+            _ => mempool.next_transaction(filter),
+        }
+    }
+
+    /// Returns up to `limit` transactions that are likely to be returned by [`Self::next_transaction()`]
+    /// without removing them from the mempool.
+    pub fn peek_l2_transactions(&self, filter: &L2TxFilter, limit: usize) -> Vec<Transaction> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .peek_l2_transactions(filter, limit)
+    }
+
     pub fn rollback(&mut self, rejected: &Transaction) {
         self.0
             .lock()
@@ -60,6 +102,13 @@ impl MempoolGuard {
             .rollback(rejected);
     }
 
+    pub fn stash_accounts(&mut self, accounts: &[Address]) {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .stash_accounts(accounts);
+    }
+
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
         self.0
             .lock()
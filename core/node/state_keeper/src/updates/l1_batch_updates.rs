@@ -17,6 +17,9 @@ pub struct L1BatchUpdates {
     // how much L1 gas will it take to submit this block?
     pub l1_gas_count: BlockGasCount,
     pub txs_encoding_size: usize,
+    /// Name of the seal criterion that has triggered sealing the batch. `None` if the batch is not sealed yet,
+    /// or if it was sealed by I/O criteria (e.g., because of a timeout).
+    pub seal_criterion: Option<&'static str>,
     pub finished: Option<FinishedL1Batch>,
 }
 
@@ -29,6 +32,7 @@ impl L1BatchUpdates {
             block_execution_metrics: Default::default(),
             l1_gas_count: new_block_gas_count(),
             txs_encoding_size: 0,
+            seal_criterion: None,
             finished: None,
         }
     }
@@ -21,6 +21,9 @@ pub struct L2BlockUpdates {
     pub executed_transactions: Vec<TransactionExecutionResult>,
     pub events: Vec<VmEvent>,
     pub storage_logs: Vec<StorageLogQuery>,
+    /// Ordered storage reads performed in this L2 block. Only present if the batch executor collects
+    /// the storage reads log, and the block contains at least one executed transaction (or the batch tip).
+    pub storage_reads: Option<Vec<StorageLogQuery>>,
     pub user_l2_to_l1_logs: Vec<UserL2ToL1Log>,
     pub system_l2_to_l1_logs: Vec<SystemL2ToL1Log>,
     pub new_factory_deps: HashMap<H256, Vec<u8>>,
@@ -48,6 +51,7 @@ impl L2BlockUpdates {
             executed_transactions: vec![],
             events: vec![],
             storage_logs: vec![],
+            storage_reads: None,
             user_l2_to_l1_logs: vec![],
             system_l2_to_l1_logs: vec![],
             new_factory_deps: HashMap::new(),
@@ -71,6 +75,7 @@ impl L2BlockUpdates {
     ) {
         self.events.extend(result.logs.events);
         self.storage_logs.extend(result.logs.storage_logs);
+        self.extend_storage_reads(result.logs.storage_reads);
         self.user_l2_to_l1_logs
             .extend(result.logs.user_l2_to_l1_logs);
         self.system_l2_to_l1_logs
@@ -80,6 +85,14 @@ impl L2BlockUpdates {
         self.block_execution_metrics += execution_metrics;
     }
 
+    fn extend_storage_reads(&mut self, reads: Option<Vec<StorageLogQuery>>) {
+        if let Some(reads) = reads {
+            self.storage_reads
+                .get_or_insert_with(Vec::new)
+                .extend(reads);
+        }
+    }
+
     pub(crate) fn extend_from_executed_transaction(
         &mut self,
         tx: Transaction,
@@ -146,6 +159,7 @@ impl L2BlockUpdates {
             zksync_protobuf::repr::encode::<zksync_dal::consensus::proto::Transaction>(&tx).len();
         self.storage_logs
             .extend(tx_execution_result.logs.storage_logs);
+        self.extend_storage_reads(tx_execution_result.logs.storage_reads);
 
         self.executed_transactions.push(TransactionExecutionResult {
             hash: tx.hash(),
@@ -35,6 +35,7 @@ pub struct UpdatesManager {
     base_fee_per_gas: u64,
     base_system_contract_hashes: BaseSystemContractsHashes,
     protocol_version: ProtocolVersionId,
+    first_l2_block_number: L2BlockNumber,
     pub l1_batch: L1BatchUpdates,
     pub l2_block: L2BlockUpdates,
     pub storage_writes_deduplicator: StorageWritesDeduplicator,
@@ -50,6 +51,7 @@ impl UpdatesManager {
             base_fee_per_gas: get_batch_base_fee(l1_batch_env, protocol_version.into()),
             protocol_version,
             base_system_contract_hashes: system_env.base_system_smart_contracts.hashes(),
+            first_l2_block_number: L2BlockNumber(l1_batch_env.first_l2_block.number),
             l1_batch: L1BatchUpdates::new(l1_batch_env.number),
             l2_block: L2BlockUpdates::new(
                 l1_batch_env.first_l2_block.timestamp,
@@ -66,6 +68,10 @@ impl UpdatesManager {
         self.batch_timestamp
     }
 
+    pub(crate) fn batch_fee_input(&self) -> BatchFeeInput {
+        self.batch_fee_input
+    }
+
     pub(crate) fn base_system_contract_hashes(&self) -> BaseSystemContractsHashes {
         self.base_system_contract_hashes
     }
@@ -83,6 +89,7 @@ impl UpdatesManager {
         &self,
         l2_shared_bridge_addr: Address,
         pre_insert_txs: bool,
+        insert_block_notifications: bool,
     ) -> L2BlockSealCommand {
         L2BlockSealCommand {
             l1_batch_number: self.l1_batch.number,
@@ -95,6 +102,7 @@ impl UpdatesManager {
             protocol_version: Some(self.protocol_version),
             l2_shared_bridge_addr,
             pre_insert_txs,
+            insert_block_notifications,
         }
     }
 
@@ -161,6 +169,11 @@ impl UpdatesManager {
             .extend_from_sealed_l2_block(old_l2_block_updates);
     }
 
+    /// Checks whether at least one L2 block in the batch is sealed (i.e., was pushed to the output handler).
+    pub(crate) fn has_sealed_l2_blocks(&self) -> bool {
+        self.l2_block.number > self.first_l2_block_number
+    }
+
     pub(crate) fn pending_executed_transactions_len(&self) -> usize {
         self.l1_batch.executed_transactions.len() + self.l2_block.executed_transactions.len()
     }
@@ -194,6 +207,9 @@ pub struct L2BlockSealCommand {
     /// Should be set to `true` for EN's IO as EN doesn't store transactions in DB
     /// before they are included into L2 blocks.
     pub pre_insert_txs: bool,
+    /// Whether a notification about the sealed L2 block should be inserted to the outbox
+    /// in the same transaction as the L2 block header.
+    pub insert_block_notifications: bool,
 }
 
 #[cfg(test)]
@@ -5,7 +5,7 @@ use std::collections::HashMap;
 use multivm::utils::get_max_gas_per_pubdata_byte;
 use zksync_contracts::BaseSystemContractsHashes;
 use zksync_dal::{Connection, Core, CoreDal};
-use zksync_merkle_tree::{domain::ZkSyncTree, TreeInstruction};
+use zksync_merkle_tree::{domain::ZkSyncTree, TreeHasher, TreeInstruction};
 use zksync_node_genesis::GenesisParams;
 use zksync_system_constants::{get_intrinsic_constants, ZKPORTER_IS_AVAILABLE};
 use zksync_types::{
@@ -40,6 +40,7 @@ pub fn create_l2_block(number: u32) -> L2BlockHeader {
         protocol_version: Some(ProtocolVersionId::latest()),
         virtual_blocks: 1,
         gas_limit: 0,
+        logs_bloom: Default::default(),
     }
 }
 
@@ -74,6 +75,7 @@ pub fn create_l1_batch_metadata(number: u32) -> L1BatchMetadata {
         events_queue_commitment: Some(H256::zero()),
         bootloader_initial_content_commitment: Some(H256::zero()),
         state_diffs_compressed: vec![],
+        da_inclusion_data: None,
     }
 }
 
@@ -188,6 +190,7 @@ impl Snapshot {
             protocol_version: Some(genesis_params.minor_protocol_version()),
             virtual_blocks: 1,
             gas_limit: 0,
+            logs_bloom: Default::default(),
         };
         Snapshot {
             l1_batch,
@@ -272,7 +275,9 @@ pub async fn recover(
         .enumerate()
         .map(|(i, log)| TreeInstruction::write(log.key, i as u64 + 1, log.value))
         .collect();
-    let l1_batch_root_hash = ZkSyncTree::process_genesis_batch(&tree_instructions).root_hash;
+    let tree_hasher = TreeHasher::for_protocol_version(snapshot.l1_batch.protocol_version.unwrap());
+    let l1_batch_root_hash =
+        ZkSyncTree::process_genesis_batch(&tree_instructions, tree_hasher).root_hash;
 
     let protocol_version = storage
         .protocol_versions_dal()
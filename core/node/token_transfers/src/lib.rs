@@ -0,0 +1,124 @@
+//! Indexer of ERC-20 token transfers and token balances.
+
+use std::time::Duration;
+
+use tokio::sync::watch;
+use zksync_dal::{ConnectionPool, Core, CoreDal};
+use zksync_types::L2BlockNumber;
+
+use self::metrics::METRICS;
+
+mod metrics;
+#[cfg(test)]
+mod tests;
+
+/// Configuration of [`TokenTransfersIndexer`].
+#[derive(Debug)]
+pub struct TokenTransfersIndexerConfig {
+    /// Interval between polling for new L2 blocks if the indexer has caught up with the sealed L2 blocks.
+    pub poll_interval: Duration,
+    /// Maximum number of L2 blocks indexed at a time.
+    pub max_l2_blocks_per_iteration: u32,
+}
+
+impl Default for TokenTransfersIndexerConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            max_l2_blocks_per_iteration: 100,
+        }
+    }
+}
+
+/// Decodes `Transfer` events from sealed L2 blocks into the transfer index and maintains token balances
+/// computed from the indexed transfers.
+#[derive(Debug)]
+pub struct TokenTransfersIndexer {
+    pool: ConnectionPool<Core>,
+    config: TokenTransfersIndexerConfig,
+}
+
+impl TokenTransfersIndexer {
+    pub fn new(pool: ConnectionPool<Core>, config: TokenTransfersIndexerConfig) -> Self {
+        assert!(
+            config.max_l2_blocks_per_iteration > 0,
+            "max_l2_blocks_per_iteration must be positive"
+        );
+        Self { pool, config }
+    }
+
+    /// Indexes the next range of L2 blocks. Returns `Ok(true)` if there may be more L2 blocks to index,
+    /// and `Ok(false)` if the indexer should wait before the next iteration.
+    async fn index_next_range(&self) -> anyhow::Result<bool> {
+        let mut storage = self.pool.connection_tagged("token_transfers").await?;
+        let next_l2_block = match storage
+            .token_transfers_dal()
+            .get_last_indexed_l2_block()
+            .await?
+        {
+            Some(number) => number + 1,
+            None => {
+                // Balances are computed by summing transfers, so they'd be incorrect
+                // if the index didn't start from genesis.
+                let pruning_info = storage.pruning_dal().get_pruning_info().await?;
+                anyhow::ensure!(
+                    !pruning_info.is_pruned(),
+                    "Token transfers index requires full L2 block history, but the node storage is pruned \
+                     (the first retained L2 block is {})",
+                    pruning_info.first_retained_l2_block()
+                );
+                L2BlockNumber(0)
+            }
+        };
+        let Some(last_sealed_l2_block) = storage.blocks_dal().get_sealed_l2_block_number().await?
+        else {
+            return Ok(false);
+        };
+        if next_l2_block > last_sealed_l2_block {
+            return Ok(false);
+        }
+
+        let latency = METRICS.iteration_latency.start();
+        let last_l2_block =
+            last_sealed_l2_block.min(next_l2_block + (self.config.max_l2_blocks_per_iteration - 1));
+        let l2_blocks = next_l2_block..=last_l2_block;
+        let transfers = storage
+            .token_transfers_dal()
+            .get_transfers_from_events(l2_blocks.clone())
+            .await?;
+        storage
+            .token_transfers_dal()
+            .insert_transfers(l2_blocks.clone(), &transfers)
+            .await?;
+        drop(storage);
+
+        let latency = latency.observe();
+        tracing::debug!(
+            "Indexed {} token transfers for L2 blocks {l2_blocks:?} in {latency:?}",
+            transfers.len()
+        );
+        METRICS.inserted_transfers.inc_by(transfers.len() as u64);
+        METRICS.last_indexed_l2_block.set(last_l2_block.0.into());
+        Ok(last_l2_block < last_sealed_l2_block)
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        tracing::info!(
+            "Starting token transfers indexer with configuration {:?}",
+            self.config
+        );
+
+        while !*stop_receiver.borrow_and_update() {
+            let has_more_l2_blocks = self.index_next_range().await?;
+            if !has_more_l2_blocks
+                && tokio::time::timeout(self.config.poll_interval, stop_receiver.changed())
+                    .await
+                    .is_ok()
+            {
+                break;
+            }
+        }
+        tracing::info!("Stop signal received, token transfers indexer is shutting down");
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use zksync_node_genesis::{insert_genesis_batch, GenesisParams};
+use zksync_node_test_utils::{create_l2_block, prepare_recovery_snapshot};
+use zksync_types::{
+    event::TRANSFER_EVENT_SIGNATURE, tx::IncludedTxLocation, Address, L1BatchNumber, VmEvent, H256,
+    U256,
+};
+use zksync_utils::{address_to_h256, u256_to_h256};
+
+use super::*;
+
+fn test_config() -> TokenTransfersIndexerConfig {
+    TokenTransfersIndexerConfig {
+        poll_interval: Duration::from_millis(10),
+        max_l2_blocks_per_iteration: 1,
+    }
+}
+
+fn transfer_event(token: Address, from: Address, to: Address, amount: u64) -> VmEvent {
+    VmEvent {
+        location: (L1BatchNumber(1), 0),
+        address: token,
+        indexed_topics: vec![
+            *TRANSFER_EVENT_SIGNATURE,
+            address_to_h256(&from),
+            address_to_h256(&to),
+        ],
+        value: u256_to_h256(amount.into()).as_bytes().to_vec(),
+    }
+}
+
+async fn seal_l2_block(pool: &ConnectionPool<Core>, number: u32, events: &[VmEvent]) {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .blocks_dal()
+        .insert_l2_block(&create_l2_block(number))
+        .await
+        .unwrap();
+    let location = IncludedTxLocation {
+        tx_hash: H256::from_low_u64_be(number.into()),
+        tx_index_in_l2_block: 0,
+        tx_initiator_address: Address::zero(),
+    };
+    storage
+        .events_dal()
+        .save_events(
+            L2BlockNumber(number),
+            &[(location, events.iter().collect())],
+        )
+        .await
+        .unwrap();
+}
+
+async fn get_balances(pool: &ConnectionPool<Core>, address: Address) -> HashMap<Address, U256> {
+    let mut storage = pool.connection().await.unwrap();
+    storage
+        .token_transfers_dal()
+        .get_token_balances(address, None, 100)
+        .await
+        .unwrap()
+        .into_iter()
+        .collect()
+}
+
+#[tokio::test]
+async fn indexing_transfers() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    insert_genesis_batch(&mut storage, &GenesisParams::mock())
+        .await
+        .unwrap();
+    drop(storage);
+
+    let token = Address::repeat_byte(0xff);
+    let other_token = Address::repeat_byte(0xfe);
+    let alice = Address::repeat_byte(1);
+    let bob = Address::repeat_byte(2);
+    seal_l2_block(
+        &pool,
+        1,
+        &[
+            transfer_event(token, Address::zero(), alice, 100),
+            transfer_event(other_token, Address::zero(), bob, 5),
+        ],
+    )
+    .await;
+    seal_l2_block(&pool, 2, &[transfer_event(token, alice, bob, 40)]).await;
+
+    let indexer = TokenTransfersIndexer::new(pool.clone(), test_config());
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let indexer_task = tokio::spawn(indexer.run(stop_receiver));
+    loop {
+        let mut storage = pool.connection().await.unwrap();
+        let last_indexed_l2_block = storage
+            .token_transfers_dal()
+            .get_last_indexed_l2_block()
+            .await
+            .unwrap();
+        if last_indexed_l2_block == Some(L2BlockNumber(2)) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    stop_sender.send_replace(true);
+    indexer_task.await.unwrap().unwrap();
+
+    assert_eq!(
+        get_balances(&pool, alice).await,
+        HashMap::from([(token, 60.into())])
+    );
+    assert_eq!(
+        get_balances(&pool, bob).await,
+        HashMap::from([(token, 40.into()), (other_token, 5.into())])
+    );
+}
+
+#[tokio::test]
+async fn indexer_requires_full_history() {
+    let pool = ConnectionPool::<Core>::test_pool().await;
+    let mut storage = pool.connection().await.unwrap();
+    prepare_recovery_snapshot(&mut storage, L1BatchNumber(23), L2BlockNumber(42), &[]).await;
+    drop(storage);
+
+    let indexer = TokenTransfersIndexer::new(pool, test_config());
+    let err = indexer.index_next_range().await.unwrap_err();
+    assert!(
+        err.to_string().contains("requires full L2 block history"),
+        "{err:#}"
+    );
+}
@@ -1,3 +1,5 @@
 mod protective_reads;
+mod shadow_execution;
 
 pub use protective_reads::{ProtectiveReadsWriter, ProtectiveReadsWriterTasks};
+pub use shadow_execution::{ShadowExecutor, ShadowExecutorTasks};
@@ -1,11 +1,13 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use anyhow::Context;
 use async_trait::async_trait;
 use tokio::sync::watch;
 use zksync_dal::{Connection, ConnectionPool, Core, CoreDal};
 use zksync_state_keeper::{MainBatchExecutor, StateKeeperOutputHandler, UpdatesManager};
-use zksync_types::{zk_evm_types::LogQuery, AccountTreeId, L1BatchNumber, L2ChainId, StorageKey};
+use zksync_types::{
+    zk_evm_types::LogQuery, AccountTreeId, L1BatchNumber, L2ChainId, StorageKey, StorageLogQuery,
+};
 use zksync_utils::u256_to_h256;
 
 use crate::{
@@ -38,7 +40,8 @@ impl ProtectiveReadsWriter {
         let output_handler_factory = ProtectiveReadsOutputHandlerFactory { pool: pool.clone() };
         let (output_handler_factory, output_handler_factory_task) =
             ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
-        let batch_processor = MainBatchExecutor::new(false, false);
+        // Protective reads are taken from the storage reads log if it's supported by the VM.
+        let batch_processor = MainBatchExecutor::new(false, false).with_storage_reads_log();
         let vm_runner = VmRunner::new(
             pool,
             Box::new(io),
@@ -123,11 +126,57 @@ impl VmRunnerIo for ProtectiveReadsIo {
 #[derive(Debug)]
 struct ProtectiveReadsOutputHandler {
     pool: ConnectionPool<Core>,
+    /// Keys read in the processed L2 blocks according to the storage reads log.
+    read_keys: HashSet<StorageKey>,
+}
+
+impl ProtectiveReadsOutputHandler {
+    fn extend_read_keys(&mut self, reads: &[StorageLogQuery]) {
+        let keys = reads.iter().map(|read| {
+            let address = AccountTreeId::new(read.log_query.address);
+            StorageKey::new(address, u256_to_h256(read.log_query.key))
+        });
+        self.read_keys.extend(keys);
+    }
+
+    /// Returns protective reads for the batch, i.e. keys that were read, but not written to in the batch.
+    /// If the storage reads log is available, the reads are taken from it directly; otherwise (i.e., for older VMs),
+    /// they are taken from the deduplicated log queries.
+    fn protective_reads(
+        &mut self,
+        updates_manager: &UpdatesManager,
+        deduplicated_log_queries: &[LogQuery],
+    ) -> Vec<StorageKey> {
+        let (written, deduplicated_reads): (Vec<&LogQuery>, Vec<&LogQuery>) =
+            deduplicated_log_queries
+                .iter()
+                .partition(|log_query| log_query.rw_flag);
+        let to_key = |log_query: &&LogQuery| {
+            StorageKey::new(
+                AccountTreeId::new(log_query.address),
+                u256_to_h256(log_query.key),
+            )
+        };
+
+        // The fictive L2 block always contains the batch tip, so the presence of the log in it indicates
+        // that the log was collected for the entire batch.
+        let Some(tip_reads) = &updates_manager.l2_block.storage_reads else {
+            return deduplicated_reads.iter().map(to_key).collect();
+        };
+        self.extend_read_keys(tip_reads);
+        let written_keys: HashSet<_> = written.iter().map(to_key).collect();
+        let mut read_keys = std::mem::take(&mut self.read_keys);
+        read_keys.retain(|key| !written_keys.contains(key));
+        read_keys.into_iter().collect()
+    }
 }
 
 #[async_trait]
 impl StateKeeperOutputHandler for ProtectiveReadsOutputHandler {
-    async fn handle_l2_block(&mut self, _updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+    async fn handle_l2_block(&mut self, updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+        if let Some(reads) = &updates_manager.l2_block.storage_reads {
+            self.extend_read_keys(reads);
+        }
         Ok(())
     }
 
@@ -140,11 +189,12 @@ impl StateKeeperOutputHandler for ProtectiveReadsOutputHandler {
             .finished
             .as_ref()
             .context("L1 batch is not actually finished")?;
-        let (_, protective_reads): (Vec<LogQuery>, Vec<LogQuery>) = finished_batch
-            .final_execution_state
-            .deduplicated_storage_log_queries
-            .iter()
-            .partition(|log_query| log_query.rw_flag);
+        let protective_reads = self.protective_reads(
+            &updates_manager,
+            &finished_batch
+                .final_execution_state
+                .deduplicated_storage_log_queries,
+        );
 
         let mut connection = self
             .pool
@@ -156,13 +206,11 @@ impl StateKeeperOutputHandler for ProtectiveReadsOutputHandler {
             .await?;
 
         for protective_read in protective_reads {
-            let address = AccountTreeId::new(protective_read.address);
-            let key = u256_to_h256(protective_read.key);
-            if !expected_protective_reads.remove(&StorageKey::new(address, key)) {
+            if !expected_protective_reads.remove(&protective_read) {
                 tracing::error!(
                     l1_batch_number = %updates_manager.l1_batch.number,
-                    address = %protective_read.address,
-                    key = %key,
+                    address = %protective_read.address(),
+                    key = %protective_read.key(),
                     "VM runner produced a protective read that did not happen in state keeper"
                 );
             }
@@ -193,6 +241,7 @@ impl OutputHandlerFactory for ProtectiveReadsOutputHandlerFactory {
     ) -> anyhow::Result<Box<dyn StateKeeperOutputHandler>> {
         Ok(Box::new(ProtectiveReadsOutputHandler {
             pool: self.pool.clone(),
+            read_keys: HashSet::new(),
         }))
     }
 }
@@ -0,0 +1,399 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use multivm::interface::{L1BatchEnv, SystemEnv};
+use tokio::sync::watch;
+use zksync_dal::{
+    vm_runner_dal::{TxExecutionOutcome, VmDivergence},
+    Connection, ConnectionPool, Core, CoreDal,
+};
+use zksync_state::ReadStorageFactory;
+use zksync_state_keeper::{
+    BatchExecutor, BatchExecutorHandle, MainBatchExecutor, StateKeeperOutputHandler, UpdatesManager,
+};
+use zksync_types::{
+    tx::TransactionExecutionResult, AccountTreeId, L1BatchNumber, L2ChainId, ProtocolVersionId,
+    StorageKey, H256,
+};
+use zksync_utils::u256_to_h256;
+
+use crate::{
+    storage::StorageSyncTask, ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask,
+    OutputHandlerFactory, VmRunner, VmRunnerIo, VmRunnerStorage,
+};
+
+/// Maximum number of divergences recorded per L1 batch. Prevents flooding the divergences table
+/// if the candidate VM is completely broken.
+const MAX_DIVERGENCES_PER_BATCH: usize = 100;
+
+/// A standalone component that re-executes sealed L1 batches using a candidate protocol version
+/// and records divergences from the canonical execution. Doesn't affect the canonical chain state;
+/// the only output is the `vm_runner_shadow_divergences` table.
+#[derive(Debug)]
+pub struct ShadowExecutor {
+    vm_runner: VmRunner,
+}
+
+impl ShadowExecutor {
+    /// Creates a new shadow executor from the provided DB parameters and window size which
+    /// regulates how many batches this component can handle at the same time.
+    pub async fn new(
+        pool: ConnectionPool<Core>,
+        rocksdb_path: String,
+        chain_id: L2ChainId,
+        first_processed_batch: L1BatchNumber,
+        window_size: u32,
+        protocol_version: ProtocolVersionId,
+    ) -> anyhow::Result<(Self, ShadowExecutorTasks)> {
+        let io = ShadowExecutionIo {
+            first_processed_batch,
+            window_size,
+        };
+        let (loader, loader_task) =
+            VmRunnerStorage::new(pool.clone(), rocksdb_path, io.clone(), chain_id).await?;
+        let output_handler_factory = ShadowExecutionOutputHandlerFactory {
+            pool: pool.clone(),
+            protocol_version,
+        };
+        let (output_handler_factory, output_handler_factory_task) =
+            ConcurrentOutputHandlerFactory::new(pool.clone(), io.clone(), output_handler_factory);
+        let batch_processor = CandidateBatchExecutor {
+            inner: MainBatchExecutor::new(false, false),
+            protocol_version,
+        };
+        let vm_runner = VmRunner::new(
+            pool,
+            Box::new(io),
+            Arc::new(loader),
+            Box::new(output_handler_factory),
+            Box::new(batch_processor),
+        )
+        .skipping_rejected_transactions();
+        Ok((
+            Self { vm_runner },
+            ShadowExecutorTasks {
+                loader_task,
+                output_handler_factory_task,
+            },
+        ))
+    }
+
+    /// Continuously loads new available batches, re-executes them and records the found divergences.
+    ///
+    /// # Errors
+    ///
+    /// Propagates RocksDB and Postgres errors.
+    pub async fn run(self, stop_receiver: &watch::Receiver<bool>) -> anyhow::Result<()> {
+        self.vm_runner.run(stop_receiver).await
+    }
+}
+
+/// A collections of tasks that need to be run in order for shadow executor to work as intended.
+#[derive(Debug)]
+pub struct ShadowExecutorTasks {
+    /// Task that synchronizes storage with new available batches.
+    pub loader_task: StorageSyncTask<ShadowExecutionIo>,
+    /// Task that handles output from processed batches.
+    pub output_handler_factory_task: ConcurrentOutputHandlerFactoryTask<ShadowExecutionIo>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShadowExecutionIo {
+    first_processed_batch: L1BatchNumber,
+    window_size: u32,
+}
+
+#[async_trait]
+impl VmRunnerIo for ShadowExecutionIo {
+    fn name(&self) -> &'static str {
+        "shadow_execution"
+    }
+
+    async fn latest_processed_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_shadow_execution_latest_processed_batch(self.first_processed_batch)
+            .await?)
+    }
+
+    async fn last_ready_to_be_loaded_batch(
+        &self,
+        conn: &mut Connection<'_, Core>,
+    ) -> anyhow::Result<L1BatchNumber> {
+        Ok(conn
+            .vm_runner_dal()
+            .get_shadow_execution_last_ready_batch(self.window_size)
+            .await?)
+    }
+
+    async fn mark_l1_batch_as_completed(
+        &self,
+        conn: &mut Connection<'_, Core>,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<()> {
+        Ok(conn
+            .vm_runner_dal()
+            .mark_shadow_execution_batch_as_completed(l1_batch_number)
+            .await?)
+    }
+}
+
+/// Batch executor running batches with the candidate protocol version. Base system contracts
+/// of the batch are retained, so the candidate version must be compatible with them.
+#[derive(Debug)]
+struct CandidateBatchExecutor {
+    inner: MainBatchExecutor,
+    protocol_version: ProtocolVersionId,
+}
+
+#[async_trait]
+impl BatchExecutor for CandidateBatchExecutor {
+    async fn init_batch(
+        &mut self,
+        storage_factory: Arc<dyn ReadStorageFactory>,
+        l1_batch_params: L1BatchEnv,
+        mut system_env: SystemEnv,
+        stop_receiver: &watch::Receiver<bool>,
+    ) -> Option<BatchExecutorHandle> {
+        system_env.version = self.protocol_version;
+        self.inner
+            .init_batch(storage_factory, l1_batch_params, system_env, stop_receiver)
+            .await
+    }
+}
+
+#[derive(Debug)]
+struct ShadowExecutionOutputHandler {
+    pool: ConnectionPool<Core>,
+    protocol_version: ProtocolVersionId,
+}
+
+#[async_trait]
+impl StateKeeperOutputHandler for ShadowExecutionOutputHandler {
+    async fn handle_l2_block(&mut self, _updates_manager: &UpdatesManager) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn handle_l1_batch(
+        &mut self,
+        updates_manager: Arc<UpdatesManager>,
+    ) -> anyhow::Result<()> {
+        let l1_batch_number = updates_manager.l1_batch.number;
+        let finished_batch = updates_manager
+            .l1_batch
+            .finished
+            .as_ref()
+            .context("L1 batch is not actually finished")?;
+        // Transactions in the last L2 block are not moved to the L1 batch updates.
+        let executed_transactions = updates_manager
+            .l1_batch
+            .executed_transactions
+            .iter()
+            .chain(&updates_manager.l2_block.executed_transactions);
+        let storage_writes = finished_batch
+            .final_execution_state
+            .deduplicated_storage_log_queries
+            .iter()
+            .filter(|log_query| log_query.rw_flag)
+            .map(|log_query| {
+                let key = StorageKey::new(
+                    AccountTreeId::new(log_query.address),
+                    u256_to_h256(log_query.key),
+                );
+                (key, u256_to_h256(log_query.written_value))
+            })
+            .collect();
+
+        let mut connection = self.pool.connection_tagged("shadow_execution").await?;
+        let expected_outcomes = connection
+            .vm_runner_dal()
+            .get_tx_execution_outcomes(l1_batch_number)
+            .await?;
+        let expected_storage_writes = connection
+            .storage_logs_dal()
+            .get_touched_slots_for_l1_batch(l1_batch_number)
+            .await?;
+
+        let mut divergences = diff_tx_outcomes(&expected_outcomes, executed_transactions);
+        divergences.extend(diff_storage_writes(expected_storage_writes, storage_writes));
+        if divergences.is_empty() {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            %l1_batch_number,
+            protocol_version = ?self.protocol_version,
+            "Shadow execution of L1 batch diverged in {} places",
+            divergences.len()
+        );
+        divergences.truncate(MAX_DIVERGENCES_PER_BATCH);
+        connection
+            .vm_runner_dal()
+            .insert_shadow_divergences(l1_batch_number, self.protocol_version, &divergences)
+            .await?;
+        Ok(())
+    }
+}
+
+fn diff_tx_outcomes<'a>(
+    expected_outcomes: &[TxExecutionOutcome],
+    executed_transactions: impl Iterator<Item = &'a TransactionExecutionResult>,
+) -> Vec<VmDivergence> {
+    let executed_transactions: HashMap<_, _> = executed_transactions
+        .map(|tx_result| (tx_result.hash, tx_result))
+        .collect();
+    let mut divergences = vec![];
+    for expected in expected_outcomes {
+        let Some(&actual) = executed_transactions.get(&expected.hash) else {
+            divergences.push(VmDivergence {
+                tx_hash: Some(expected.hash),
+                kind: "rejected".into(),
+                details: "transaction was rejected by the candidate VM".into(),
+            });
+            continue;
+        };
+        if actual.execution_status != expected.status {
+            divergences.push(VmDivergence {
+                tx_hash: Some(expected.hash),
+                kind: "status".into(),
+                details: format!(
+                    "expected {:?}, got {:?} (revert reason: {:?})",
+                    expected.status, actual.execution_status, actual.revert_reason
+                ),
+            });
+        }
+        if actual.refunded_gas != expected.refunded_gas {
+            divergences.push(VmDivergence {
+                tx_hash: Some(expected.hash),
+                kind: "refunded_gas".into(),
+                details: format!(
+                    "expected {}, got {}",
+                    expected.refunded_gas, actual.refunded_gas
+                ),
+            });
+        }
+    }
+    divergences
+}
+
+fn diff_storage_writes(
+    mut expected_writes: HashMap<StorageKey, H256>,
+    actual_writes: HashMap<StorageKey, H256>,
+) -> Vec<VmDivergence> {
+    let mut divergences = vec![];
+    for (key, actual_value) in actual_writes {
+        let details = match expected_writes.remove(&key) {
+            Some(expected_value) if expected_value == actual_value => continue,
+            Some(expected_value) => format!(
+                "slot {:?}/{:?}: expected {expected_value:?}, got {actual_value:?}",
+                key.address(),
+                key.key()
+            ),
+            None => format!(
+                "slot {:?}/{:?}: unexpected write of {actual_value:?}",
+                key.address(),
+                key.key()
+            ),
+        };
+        divergences.push(VmDivergence {
+            tx_hash: None,
+            kind: "storage_write".into(),
+            details,
+        });
+    }
+    for (key, expected_value) in expected_writes {
+        divergences.push(VmDivergence {
+            tx_hash: None,
+            kind: "storage_write".into(),
+            details: format!(
+                "slot {:?}/{:?}: missing write of {expected_value:?}",
+                key.address(),
+                key.key()
+            ),
+        });
+    }
+    // Make the order of divergences deterministic.
+    divergences.sort_unstable_by(|a, b| a.details.cmp(&b.details));
+    divergences
+}
+
+#[derive(Debug)]
+struct ShadowExecutionOutputHandlerFactory {
+    pool: ConnectionPool<Core>,
+    protocol_version: ProtocolVersionId,
+}
+
+#[async_trait]
+impl OutputHandlerFactory for ShadowExecutionOutputHandlerFactory {
+    async fn create_handler(
+        &mut self,
+        _l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<Box<dyn StateKeeperOutputHandler>> {
+        Ok(Box::new(ShadowExecutionOutputHandler {
+            pool: self.pool.clone(),
+            protocol_version: self.protocol_version,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_types::{tx::tx_execution_info::TxExecutionStatus, Address};
+
+    use super::*;
+
+    #[test]
+    fn diffing_storage_writes() {
+        let key = |byte| {
+            StorageKey::new(
+                AccountTreeId::new(Address::repeat_byte(1)),
+                H256::repeat_byte(byte),
+            )
+        };
+        let expected = HashMap::from([
+            (key(1), H256::repeat_byte(1)),
+            (key(2), H256::repeat_byte(2)),
+            (key(3), H256::repeat_byte(3)),
+        ]);
+        let divergences = diff_storage_writes(expected.clone(), expected.clone());
+        assert!(divergences.is_empty());
+
+        let actual = HashMap::from([
+            (key(1), H256::repeat_byte(1)),
+            (key(2), H256::repeat_byte(0xff)),
+            (key(4), H256::repeat_byte(4)),
+        ]);
+        let divergences = diff_storage_writes(expected, actual);
+        assert_eq!(divergences.len(), 3, "{divergences:?}");
+        assert!(divergences
+            .iter()
+            .all(|divergence| divergence.tx_hash.is_none() && divergence.kind == "storage_write"));
+        let details: Vec<_> = divergences.iter().map(|d| d.details.as_str()).collect();
+        assert!(details.iter().any(|d| d.contains("missing write")));
+        assert!(details.iter().any(|d| d.contains("unexpected write")));
+        assert!(details.iter().any(|d| d.contains(", got ")));
+    }
+
+    #[test]
+    fn diffing_tx_outcomes() {
+        let expected_outcomes = [
+            TxExecutionOutcome {
+                hash: H256::repeat_byte(1),
+                status: TxExecutionStatus::Success,
+                refunded_gas: 100,
+            },
+            TxExecutionOutcome {
+                hash: H256::repeat_byte(2),
+                status: TxExecutionStatus::Success,
+                refunded_gas: 100,
+            },
+        ];
+        let divergences = diff_tx_outcomes(&expected_outcomes, std::iter::empty());
+        assert_eq!(divergences.len(), 2);
+        assert!(divergences.iter().all(|d| d.kind == "rejected"));
+    }
+}
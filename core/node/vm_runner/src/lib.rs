@@ -12,7 +12,9 @@ mod storage;
 #[cfg(test)]
 mod tests;
 
-pub use impls::{ProtectiveReadsWriter, ProtectiveReadsWriterTasks};
+pub use impls::{
+    ProtectiveReadsWriter, ProtectiveReadsWriterTasks, ShadowExecutor, ShadowExecutorTasks,
+};
 pub use io::VmRunnerIo;
 pub use output_handler::{
     ConcurrentOutputHandlerFactory, ConcurrentOutputHandlerFactoryTask, OutputHandlerFactory,
@@ -30,6 +30,7 @@ pub struct VmRunner {
     loader: Arc<dyn StorageLoader>,
     output_handler_factory: Box<dyn OutputHandlerFactory>,
     batch_processor: Box<dyn BatchExecutor>,
+    skip_rejected_transactions: bool,
 }
 
 impl VmRunner {
@@ -52,14 +53,25 @@ impl VmRunner {
             loader,
             output_handler_factory,
             batch_processor,
+            skip_rejected_transactions: false,
         }
     }
 
+    /// Makes VM runner roll back and skip transactions rejected by the batch executor instead of failing
+    /// the batch. This only makes sense for VM runners executing batches in an environment that differs from
+    /// the one used by state keeper (e.g., with a different protocol version); by default, a rejected transaction
+    /// is treated as an error.
+    pub fn skipping_rejected_transactions(mut self) -> Self {
+        self.skip_rejected_transactions = true;
+        self
+    }
+
     async fn process_batch(
         mut batch_executor: BatchExecutorHandle,
         l2_blocks: Vec<L2BlockExecutionData>,
         mut updates_manager: UpdatesManager,
         mut output_handler: Box<dyn StateKeeperOutputHandler>,
+        skip_rejected_transactions: bool,
     ) -> anyhow::Result<()> {
         for (i, l2_block) in l2_blocks.into_iter().enumerate() {
             if i > 0 {
@@ -81,16 +93,34 @@ impl VmRunner {
                     .execute_tx(tx.clone())
                     .await
                     .with_context(|| format!("failed executing transaction {:?}", tx.hash()))?;
-                let TxExecutionResult::Success {
-                    tx_result,
-                    tx_metrics,
-                    call_tracer_result,
-                    compressed_bytecodes,
-                    ..
-                } = exec_result
-                else {
-                    anyhow::bail!("Unexpected non-successful transaction");
-                };
+                let (tx_result, tx_metrics, call_tracer_result, compressed_bytecodes) =
+                    match exec_result {
+                        TxExecutionResult::Success {
+                            tx_result,
+                            tx_metrics,
+                            call_tracer_result,
+                            compressed_bytecodes,
+                            ..
+                        } => (
+                            tx_result,
+                            tx_metrics,
+                            call_tracer_result,
+                            compressed_bytecodes,
+                        ),
+                        TxExecutionResult::RejectedByVm { reason }
+                            if skip_rejected_transactions =>
+                        {
+                            tracing::debug!(
+                                "Transaction {:?} was rejected by VM ({reason}); skipping it",
+                                tx.hash()
+                            );
+                            batch_executor.rollback_last_tx().await.with_context(|| {
+                                format!("failed rolling back transaction {:?}", tx.hash())
+                            })?;
+                            continue;
+                        }
+                        _ => anyhow::bail!("Unexpected non-successful transaction"),
+                    };
                 let ExecutionMetricsForCriteria {
                     l1_gas: tx_l1_gas_this_tx,
                     execution_metrics: tx_execution_metrics,
@@ -188,6 +218,7 @@ impl VmRunner {
                 batch_data.l2_blocks,
                 updates_manager,
                 output_handler,
+                self.skip_rejected_transactions,
             ));
             task_handles.push((next_batch, handle));
 
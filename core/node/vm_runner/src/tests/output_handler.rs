@@ -73,6 +73,10 @@ impl OutputHandlerTester {
                 execution_mode: TxExecutionMode::VerifyExecute,
                 default_validation_computational_gas_limit: 0,
                 chain_id: Default::default(),
+                enable_opcode_profiling: false,
+                enable_call_tracing: false,
+                enable_gas_attribution: false,
+                enable_storage_reads_log: false,
             };
             let updates_manager = UpdatesManager::new(&l1_batch_env, &system_env);
             output_handler
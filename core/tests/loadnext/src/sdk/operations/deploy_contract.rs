@@ -155,8 +155,9 @@ where
         );
         self.wallet
             .provider
-            .estimate_fee(l2_tx.into())
+            .estimate_fee(l2_tx.into(), None)
             .await
+            .map(|estimate| estimate.fee)
             .map_err(Into::into)
     }
 }
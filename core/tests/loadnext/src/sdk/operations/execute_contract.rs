@@ -155,8 +155,9 @@ where
         );
         self.wallet
             .provider
-            .estimate_fee(execute.into())
+            .estimate_fee(execute.into(), None)
             .await
+            .map(|estimate| estimate.fee)
             .map_err(Into::into)
     }
 }